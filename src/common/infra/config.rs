@@ -19,13 +19,15 @@ use config::{
     RwAHashMap, RwHashMap,
     meta::{
         alerts::alert::Alert,
+        dataset::Dataset,
         destinations::{Destination, Template},
         folder::Folder,
-        function::Transform,
+        function::{Transform, VRLResultResolver},
+        log_metrics::LogMetricRule,
         pipeline::Pipeline,
         promql::ClusterLeader,
         ratelimit::CachedUserRoles,
-        stream::StreamParams,
+        stream::{LevelMapping, StreamParams},
         system_settings::SystemSetting,
         user::User,
     },
@@ -40,7 +42,8 @@ use vector_enrichment::TableRegistry;
 use crate::{
     common::meta::{
         maxmind::MaxmindClient,
-        organization::{Organization, OrganizationSetting},
+        organization::{OrgLifecycle, Organization, OrganizationSetting},
+        stream::StreamAlias,
     },
     service::{
         db::scheduler as db_scheduler, enrichment::StreamTable, enrichment_table::geoip::Geoip,
@@ -100,6 +103,21 @@ pub static SCHEDULED_PIPELINES: Lazy<RwAHashMap<String, Pipeline>> = Lazy::new(D
 pub static USER_SESSIONS: Lazy<RwHashMap<String, String>> = Lazy::new(Default::default);
 pub static USER_SESSIONS_EXPIRY: Lazy<RwHashMap<String, i64>> = Lazy::new(Default::default);
 pub static SHORT_URLS: Lazy<RwHashMap<String, ShortUrlRecord>> = Lazy::new(DashMap::default);
+/// Stream aliases, key format: "{org_id}/{stream_type}/{alias}"
+pub static STREAM_ALIASES: Lazy<RwHashMap<String, StreamAlias>> = Lazy::new(DashMap::default);
+/// Datasets, key format: "{org_id}/{stream_type}/{name}"
+pub static DATASETS: Lazy<RwHashMap<String, Dataset>> = Lazy::new(DashMap::default);
+/// Lifecycle of the organizations which aren't active, key format: "{org_id}"
+pub static ORG_LIFECYCLES: Lazy<RwHashMap<String, OrgLifecycle>> = Lazy::new(DashMap::default);
+/// Level mappings the organizations edited, key format: "{org_id}"
+pub static LEVEL_MAPPINGS: Lazy<RwHashMap<String, Arc<LevelMapping>>> = Lazy::new(DashMap::default);
+/// Log metric rules by source stream, key format: "{org_id}/{stream_name}"
+pub static LOG_METRIC_RULES: Lazy<RwHashMap<String, Vec<Arc<LogMetricRule>>>> =
+    Lazy::new(DashMap::default);
+/// Compiled transforms of the webhook sources with the program they were
+/// compiled from, key format: "{org_id}/{name}"
+pub static WEBHOOK_TRANSFORMS: Lazy<RwHashMap<String, (String, Arc<VRLResultResolver>)>> =
+    Lazy::new(DashMap::default);
 pub static USER_ROLES_CACHE: Lazy<RwAHashMap<String, CachedUserRoles>> =
     Lazy::new(Default::default);
 
@@ -33,6 +33,19 @@ pub enum SystemJobType {
     ServiceGraph,
     SelfReporting,
     InternalGrpc,
+    FlowCollector,
+    K8sEventsWatcher,
+    DockerGelf,
+    AccessLogImport,
+    Webhook,
+    KafkaConsumer,
+    Gelf,
+    RecordingRules,
+    KinesisConsumer,
+    MqttConsumer,
+    RabbitmqConsumer,
+    LogMetrics,
+    RedisStreamsConsumer,
 }
 
 impl SystemJobType {
@@ -44,6 +57,19 @@ impl SystemJobType {
             SystemJobType::ServiceGraph => "service_graph",
             SystemJobType::SelfReporting => "self_reporting",
             SystemJobType::InternalGrpc => "internal_grpc",
+            SystemJobType::FlowCollector => "flow_collector",
+            SystemJobType::K8sEventsWatcher => "k8s_events_watcher",
+            SystemJobType::DockerGelf => "docker_gelf",
+            SystemJobType::AccessLogImport => "access_log_import",
+            SystemJobType::Webhook => "webhook",
+            SystemJobType::KafkaConsumer => "kafka_consumer",
+            SystemJobType::Gelf => "gelf",
+            SystemJobType::RecordingRules => "recording_rules",
+            SystemJobType::KinesisConsumer => "kinesis_consumer",
+            SystemJobType::MqttConsumer => "mqtt_consumer",
+            SystemJobType::RabbitmqConsumer => "rabbitmq_consumer",
+            SystemJobType::LogMetrics => "log_metrics",
+            SystemJobType::RedisStreamsConsumer => "redis_streams_consumer",
         }
     }
 }
@@ -146,7 +172,7 @@ pub struct StreamSchemaChk {
     pub has_metrics_metadata: bool,
 }
 
-pub const INGESTION_EP: [&str; 16] = [
+pub const INGESTION_EP: [&str; 18] = [
     "_bulk",
     "_json",
     "_multi",
@@ -163,6 +189,8 @@ pub const INGESTION_EP: [&str; 16] = [
     "_json_arrow",
     "_hec",
     "push",
+    "_docker",
+    "_logplex",
 ];
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
@@ -394,10 +422,23 @@ pub enum IngestionRequest {
     Usage(Bytes),
 }
 
+#[derive(Clone, Copy)]
 pub enum IngestionValueType {
     Bulk,
     Hec,
     Loki,
+    Flow,
+    K8sEvents,
+    Docker,
+    Logplex,
+    AccessLogs,
+    Webhook,
+    Kafka,
+    Gelf,
+    Kinesis,
+    Mqtt,
+    Rabbitmq,
+    RedisStreams,
 }
 
 pub enum IngestionData {
@@ -455,7 +496,11 @@ impl From<HecStatus> for HecResponse {
             HecStatus::InvalidIndex => ("Incorrect index".to_string(), 400),
             HecStatus::Custom(s, c) => (s, c),
         };
-        Self { text, code }
+        Self {
+            text,
+            code,
+            ack_id: None,
+        }
     }
 }
 
@@ -463,6 +508,48 @@ impl From<HecStatus> for HecResponse {
 pub struct HecResponse {
     pub text: String,
     pub code: u16,
+    /// Set when the request was sent on a channel, for indexer
+    /// acknowledgement
+    #[serde(rename = "ackId", skip_serializing_if = "Option::is_none")]
+    pub ack_id: Option<u64>,
+}
+
+/// Response of the Splunk compatible event endpoint used by the docker splunk
+/// log driver, codes follow Splunk HEC rather than HTTP
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SplunkEventResponse {
+    pub text: String,
+    pub code: u16,
+    #[serde(rename = "ackId", skip_serializing_if = "Option::is_none")]
+    pub ack_id: Option<u64>,
+}
+
+impl SplunkEventResponse {
+    pub fn success(ack_id: Option<u64>) -> Self {
+        Self {
+            text: "Success".to_string(),
+            code: 0,
+            ack_id,
+        }
+    }
+
+    pub fn invalid_format() -> Self {
+        Self {
+            text: "Invalid data format".to_string(),
+            code: 6,
+            ack_id: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SplunkAckRequest {
+    pub acks: Vec<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SplunkAckResponse {
+    pub acks: HashMap<String, bool>,
 }
 
 #[cfg(test)]
@@ -761,5 +848,9 @@ mod tests {
         let custom: HecResponse = HecStatus::Custom("Test error".to_string(), 418).into();
         assert_eq!(custom.text, "Test error");
         assert_eq!(custom.code, 418);
+        assert_eq!(
+            serde_json::to_string(&custom).unwrap(),
+            r#"{"text":"Test error","code":418}"#
+        );
     }
 }
@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use axum::response::IntoResponse;
 use serde::{Deserialize, Serialize};
@@ -57,6 +57,52 @@ pub struct LokiPushResponse {
     pub success: bool,
 }
 
+/// Loki query_range request parameters
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct LokiQueryRangeRequest {
+    /// LogQL log query, a stream selector followed by line filters
+    pub query: String,
+    /// Start timestamp, nanosecond unix epoch or RFC3339, defaults to an hour
+    /// before `end`
+    pub start: Option<String>,
+    /// End timestamp, nanosecond unix epoch or RFC3339, defaults to now
+    pub end: Option<String>,
+    /// Max number of entries to return, defaults to 100
+    pub limit: Option<i64>,
+    /// `backward` (default) or `forward`
+    pub direction: Option<String>,
+}
+
+/// Loki labels and label values request parameters
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct LokiLabelsRequest {
+    /// Start timestamp, nanosecond unix epoch or RFC3339
+    pub start: Option<String>,
+    /// End timestamp, nanosecond unix epoch or RFC3339
+    pub end: Option<String>,
+    /// Optional stream selector narrowing the streams looked at
+    pub query: Option<String>,
+}
+
+/// Loki query_range response data for log queries
+#[derive(Clone, Debug, Default, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LokiStreamsData {
+    /// Always `streams`, metric queries are not supported
+    pub result_type: String,
+    pub result: Vec<LokiStreamResult>,
+    #[schema(value_type = Object)]
+    pub stats: serde_json::Value,
+}
+
+/// Log entries sharing a label set
+#[derive(Clone, Debug, Default, Serialize, ToSchema)]
+pub struct LokiStreamResult {
+    pub stream: BTreeMap<String, String>,
+    /// `[nanosecond timestamp, line]` pairs
+    pub values: Vec<[String; 2]>,
+}
+
 /// Errors specific to Loki processing
 #[derive(Debug, thiserror::Error)]
 pub enum LokiError {
@@ -21,6 +21,7 @@ pub mod maxmind;
 pub mod middleware_data;
 pub mod organization;
 pub mod proxy;
+pub mod rabbitmq;
 pub mod saved_view;
 pub mod search;
 pub mod service;
@@ -29,3 +30,4 @@ pub mod stream;
 pub mod telemetry;
 pub mod traces;
 pub mod user;
+pub mod webhook;
@@ -13,7 +13,16 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use config::{meta::user::UserRole, stats::MemorySize};
+use std::collections::HashMap;
+
+use config::{
+    meta::{
+        search::{QueryLimits, SearchLimits, SearchQuota},
+        stream::{IngestQuota, StreamSettingsTemplate, StreamType},
+        user::UserRole,
+    },
+    stats::MemorySize,
+};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -76,6 +85,149 @@ pub struct OrgRenameBody {
     pub new_name: String,
 }
 
+/// The resources a new organization is provisioned with, see
+/// [`crate::service::organization::provision_org`]
+#[derive(Clone, Debug, Default)]
+pub struct OrgTemplate {
+    pub streams: Vec<OrgTemplateStream>,
+    pub roles: Vec<OrgTemplateRole>,
+    pub functions: Vec<config::meta::function::Transform>,
+    pub pipelines: Vec<config::meta::pipeline::Pipeline>,
+    pub dashboards: Vec<config::meta::dashboards::Dashboard>,
+    pub alerts: Vec<config::meta::alerts::alert::Alert>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct OrgTemplateStream {
+    pub name: String,
+    #[serde(default)]
+    pub stream_type: StreamType,
+    #[serde(default)]
+    pub fields: Vec<config::meta::stream::StreamField>,
+    #[serde(default)]
+    pub settings: config::meta::stream::StreamSettings,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct OrgTemplateRole {
+    pub name: String,
+    /// Permissions of the role, like the ones added by a role update, e.g.
+    /// `{"object": "stream:_all_default", "permission": "AllowAll"}`
+    #[serde(default)]
+    #[schema(value_type = Vec<Object>)]
+    pub permissions: Vec<serde_json::Value>,
+}
+
+/// The count of the resources of the template created in the organization,
+/// and the errors of the ones which couldn't be
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug, Default)]
+pub struct OrgProvisionSummary {
+    pub streams: usize,
+    pub roles: usize,
+    pub functions: usize,
+    pub pipelines: usize,
+    pub dashboards: usize,
+    pub alerts: usize,
+    /// Data files registered by an import
+    pub files: usize,
+    pub errors: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct OrgProvisionResponse {
+    #[serde(flatten)]
+    pub organization: OrganizationCreationResponse,
+    pub provisioned: OrgProvisionSummary,
+}
+
+/// Version of the [`OrgArchive`] format, the clusters import the archives of
+/// the versions up to theirs
+pub const ORG_ARCHIVE_VERSION: u32 = 1;
+
+/// A portable copy of an organization, to move it to another cluster, see
+/// [`crate::service::organization::export_org`]
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct OrgArchive {
+    pub version: u32,
+    /// Identifier of the exported organization, the keys of its data files
+    /// start with it
+    pub org_id: String,
+    pub org_name: String,
+    /// In microseconds
+    pub exported_at: i64,
+    #[serde(default)]
+    pub streams: Vec<OrgTemplateStream>,
+    #[serde(default)]
+    pub functions: Vec<config::meta::function::Transform>,
+    #[serde(default)]
+    pub pipelines: Vec<config::meta::pipeline::Pipeline>,
+    #[serde(default)]
+    pub dashboards: Vec<config::meta::dashboards::Dashboard>,
+    #[serde(default)]
+    pub alerts: Vec<config::meta::alerts::alert::Alert>,
+    /// Manifest of the parquet files of the streams, only when the export
+    /// includes the data. The files aren't in the archive, they are copied to
+    /// the object storage of the other cluster before the import
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<OrgArchiveFile>>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug, PartialEq)]
+pub struct OrgArchiveFile {
+    /// e.g. `files/{org_id}/logs/app/2026/01/01/00/{id}.parquet`
+    pub key: String,
+    #[serde(flatten)]
+    #[schema(value_type = Object)]
+    pub meta: config::meta::stream::FileMeta,
+}
+
+/// Lifecycle state of an organization. A suspended organization rejects
+/// ingestion but can still be searched, an archived one is read-only until its
+/// data is purged.
+#[derive(Serialize, Deserialize, ToSchema, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OrgLifecycleState {
+    #[default]
+    Active,
+    Suspended,
+    Archived,
+}
+
+impl std::fmt::Display for OrgLifecycleState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OrgLifecycleState::Active => write!(f, "active"),
+            OrgLifecycleState::Suspended => write!(f, "suspended"),
+            OrgLifecycleState::Archived => write!(f, "archived"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug, Default, PartialEq)]
+pub struct OrgLifecycle {
+    pub state: OrgLifecycleState,
+    #[serde(default)]
+    pub reason: String,
+    /// When the data of an archived organization is purged, in microseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purge_at: Option<i64>,
+    #[serde(default)]
+    pub updated_by: String,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct OrgLifecycleRequest {
+    pub state: OrgLifecycleState,
+    #[serde(default)]
+    pub reason: String,
+    /// Days the data of an archived organization is kept before it is purged,
+    /// `ZO_ORG_ARCHIVE_GRACE_DAYS` when not set
+    #[serde(default)]
+    pub grace_days: Option<i64>,
+}
+
 #[cfg(feature = "cloud")]
 #[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
 pub struct OrganizationInvites {
@@ -316,6 +468,34 @@ fn default_claim_parser_function() -> String {
     "".to_string()
 }
 
+/// SMTP server the email alert destinations of the org send through, instead
+/// of the one of the instance
+#[derive(Serialize, ToSchema, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct OrgSmtp {
+    pub host: String,
+    /// 25 when unset
+    pub port: u16,
+    pub username: String,
+    /// It is never returned, leave it empty on update to keep the current one
+    /// while the host and the port stay the same
+    pub password: String,
+    pub from_email: String,
+    pub reply_to: String,
+    /// `starttls`, `ssltls` or empty for none
+    pub encryption: String,
+}
+
+impl OrgSmtp {
+    pub fn is_empty(&self) -> bool {
+        self.host.is_empty()
+    }
+
+    pub fn port(&self) -> u16 {
+        if self.port == 0 { 25 } else { self.port }
+    }
+}
+
 #[derive(Serialize, ToSchema, Deserialize, Debug, Clone)]
 pub struct OrganizationSettingPayload {
     /// Ideally this should be the same as prometheus-scrape-interval (in
@@ -340,6 +520,21 @@ pub struct OrganizationSettingPayload {
     pub dark_mode_theme_color: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_series_per_query: Option<usize>,
+    /// Replaces the settings templates of new streams, keyed by stream type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_settings_templates: Option<HashMap<StreamType, StreamSettingsTemplate>>,
+    /// Ingestion quota of the org, only the root user can change it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingest_quota: Option<IngestQuota>,
+    /// Search quota of the org, only the root user can change it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_quota: Option<SearchQuota>,
+    /// Limits of the searches of the users, by role
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_limits: Option<SearchLimits>,
+    /// SMTP server of the email alert destinations, an empty host removes it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smtp: Option<OrgSmtp>,
     #[cfg(feature = "enterprise")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub claim_parser_function: Option<String>,
@@ -373,6 +568,26 @@ pub struct OrganizationSetting {
     pub dark_mode_theme_color: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_series_per_query: Option<usize>,
+    /// Settings applied to streams auto-created by ingestion, keyed by
+    /// stream type
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub stream_settings_templates: HashMap<StreamType, StreamSettingsTemplate>,
+    /// Records and bytes per second the org can ingest, unset limits use the
+    /// configured defaults
+    #[serde(default, skip_serializing_if = "IngestQuota::is_empty")]
+    pub ingest_quota: IngestQuota,
+    /// Searches the org can run at once and MB it can scan per minute, unset
+    /// limits use the configured defaults
+    #[serde(default, skip_serializing_if = "SearchQuota::is_empty")]
+    pub search_quota: SearchQuota,
+    /// MB a search can scan, rows it can return and searches a user can run
+    /// at once, by role
+    #[serde(default, skip_serializing_if = "SearchLimits::is_empty")]
+    pub search_limits: SearchLimits,
+    /// SMTP server the email alert destinations send through, the one of the
+    /// instance when unset
+    #[serde(default, skip_serializing_if = "OrgSmtp::is_empty")]
+    pub smtp: OrgSmtp,
     #[cfg(feature = "enterprise")]
     #[serde(default = "default_claim_parser_function")]
     pub claim_parser_function: String,
@@ -404,6 +619,11 @@ impl Default for OrganizationSetting {
             light_mode_theme_color,
             dark_mode_theme_color,
             max_series_per_query: None,
+            stream_settings_templates: HashMap::new(),
+            ingest_quota: IngestQuota::default(),
+            search_quota: SearchQuota::default(),
+            search_limits: SearchLimits::default(),
+            smtp: OrgSmtp::default(),
             #[cfg(feature = "enterprise")]
             claim_parser_function: default_claim_parser_function(),
         }
@@ -417,6 +637,11 @@ impl MemorySize for OrganizationSetting {
             + self.span_id_field_name.mem_size()
             + self.light_mode_theme_color.mem_size()
             + self.dark_mode_theme_color.mem_size()
+            + self.stream_settings_templates.len()
+                * (std::mem::size_of::<StreamType>()
+                    + std::mem::size_of::<StreamSettingsTemplate>())
+            + self.search_limits.roles.len()
+                * (std::mem::size_of::<String>() + std::mem::size_of::<QueryLimits>())
     }
 }
 
@@ -765,6 +990,24 @@ mod tests {
         assert_eq!(setting.span_id_field_name, "span_id");
     }
 
+    #[test]
+    fn test_organization_setting_stream_templates() {
+        let setting: OrganizationSetting = serde_json::from_str(
+            r#"{"stream_settings_templates": {"logs": {"data_retention": 7, "index_fields": ["service"]}}}"#,
+        )
+        .unwrap();
+        let template = setting
+            .stream_settings_templates
+            .get(&StreamType::Logs)
+            .unwrap();
+        assert_eq!(template.data_retention, 7);
+        assert_eq!(template.index_fields, vec!["service"]);
+
+        // settings without templates don't serialize the field
+        let data = serde_json::to_string(&OrganizationSetting::default()).unwrap();
+        assert!(!data.contains("stream_settings_templates"));
+    }
+
     #[test]
     fn test_organization_setting_response() {
         let setting = OrganizationSetting::default();
@@ -0,0 +1,78 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A RabbitMQ queue consumed by the ingesters, the messages are ingested into
+/// `stream_name` and acknowledged once written
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RabbitmqSource {
+    pub name: String,
+    /// `amqp://host:5672/vhost`, or `amqps://` for TLS, without credentials
+    pub uri: String,
+    #[serde(default)]
+    pub username: String,
+    /// It is never returned by the API, leave it empty on update to keep the
+    /// current one
+    #[serde(default)]
+    pub password: String,
+    /// Queue to consume, it must exist unless `exchange` is set
+    pub queue: String,
+    /// Exchange to bind the queue to, the queue is declared durable when it
+    /// doesn't exist
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exchange: Option<String>,
+    /// Routing key of the binding, e.g. `logs.#` for a topic exchange
+    #[serde(default)]
+    pub routing_key: String,
+    pub stream_name: String,
+    /// Maximum number of unacknowledged messages delivered to each ingester,
+    /// also the maximum number of messages ingested at once
+    #[serde(default = "default_prefetch")]
+    pub prefetch: u16,
+    /// Maximum time a message waits for its batch to fill, in milliseconds
+    #[serde(default = "default_flush_interval_ms")]
+    pub flush_interval_ms: u64,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_prefetch() -> u16 {
+    500
+}
+
+fn default_flush_interval_ms() -> u64 {
+    1000
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl RabbitmqSource {
+    /// Copy that is safe to return from the API
+    pub fn redacted(&self) -> Self {
+        Self {
+            password: String::new(),
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct RabbitmqSourceList {
+    pub list: Vec<RabbitmqSource>,
+}
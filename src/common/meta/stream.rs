@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use arrow_schema::Field;
 use config::{
@@ -85,6 +85,35 @@ pub struct ListStream {
     pub total: usize,
 }
 
+/// Another name of a stream, searches by the alias search the stream
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StreamAlias {
+    pub alias: String,
+    pub stream_name: String,
+    pub stream_type: StreamType,
+    /// The stream was renamed to the alias, it is searched and ingested into
+    /// by the alias while its data stays under its name
+    #[serde(default)]
+    pub renamed: bool,
+    /// Time the alias was created, in microseconds
+    pub created_at: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct StreamAliasCreate {
+    pub alias: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct StreamRename {
+    pub new_name: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListStreamAlias {
+    pub list: Vec<StreamAlias>,
+}
+
 pub struct SchemaEvolution {
     pub is_schema_changed: bool,
     pub types_delta: Option<Vec<Field>>,
@@ -119,6 +148,269 @@ pub struct StreamUpdateFields {
     pub fields: Vec<FieldUpdate>,
 }
 
+/// How often a field was referenced by searches, split by the kind of search
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FieldUsage {
+    /// Searches from the logs page, the API and everything else
+    #[serde(default)]
+    pub queries: u64,
+    #[serde(default)]
+    pub dashboards: u64,
+    /// Alerts and derived streams
+    #[serde(default)]
+    pub alerts: u64,
+    #[serde(default)]
+    pub reports: u64,
+    /// First time the field was referenced, in microseconds
+    #[serde(default)]
+    pub first_used: i64,
+    /// Last time the field was referenced, in microseconds
+    #[serde(default)]
+    pub last_used: i64,
+}
+
+impl FieldUsage {
+    pub fn total(&self) -> u64 {
+        self.queries + self.dashboards + self.alerts + self.reports
+    }
+
+    pub fn merge(&mut self, other: &FieldUsage) {
+        self.queries += other.queries;
+        self.dashboards += other.dashboards;
+        self.alerts += other.alerts;
+        self.reports += other.reports;
+        if self.first_used == 0 || (other.first_used > 0 && other.first_used < self.first_used) {
+            self.first_used = other.first_used;
+        }
+        self.last_used = self.last_used.max(other.last_used);
+    }
+}
+
+/// Field usage of a stream as persisted in the kv store
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct StreamFieldUsage {
+    /// Start of the tracking, in microseconds
+    pub tracking_since: i64,
+    #[serde(default)]
+    pub fields: HashMap<String, FieldUsage>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct FieldUsageEntry {
+    pub name: String,
+    pub data_type: String,
+    /// `None` for fields never referenced since the tracking started
+    pub usage: Option<FieldUsage>,
+    /// Estimated compressed bytes stored for the field
+    pub storage_size: i64,
+    pub full_text_search: bool,
+    pub index: bool,
+    pub user_defined_schema: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct FieldUsageResponse {
+    /// Start of the tracking, in microseconds, 0 when nothing was tracked yet
+    pub tracking_since: i64,
+    /// Number of recent files the storage sizes are estimated from
+    pub sampled_files: usize,
+    /// Estimated compressed bytes of the fields never referenced
+    pub unused_storage_size: i64,
+    pub fields: Vec<FieldUsageEntry>,
+}
+
+/// Counts of a value of a distinct value field, per time bucket
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct DistinctValueCounts {
+    #[schema(value_type = Object)]
+    pub value: json::Value,
+    /// Count over the whole time range
+    pub count: i64,
+    /// Count per bucket, in the order of the buckets of the response
+    pub counts: Vec<i64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct DistinctValueBucketsResponse {
+    pub field: String,
+    /// Size of the buckets, in microseconds
+    pub interval: i64,
+    /// Start of the buckets, in microseconds
+    pub buckets: Vec<i64>,
+    /// The most frequent values, the most frequent first
+    pub values: Vec<DistinctValueCounts>,
+}
+
+/// Clock skew of a source of a stream
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SourceClockSkew {
+    /// Moving average of the offset of the timestamps of the records from
+    /// their arrival time, in microseconds, negative when the clock is behind
+    pub skew: i64,
+    /// Records the skew was measured on
+    pub records: u64,
+    /// Records whose timestamp was corrected
+    #[serde(default)]
+    pub corrected: u64,
+    /// Last time a record of the source arrived, in microseconds
+    pub last_seen: i64,
+}
+
+/// Clock skew of the sources of a stream as persisted in the kv store
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct StreamClockSkew {
+    #[serde(default)]
+    pub sources: HashMap<String, SourceClockSkew>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ClockSkewEntry {
+    pub stream_type: StreamType,
+    pub stream_name: String,
+    pub source: String,
+    #[serde(flatten)]
+    pub skew: SourceClockSkew,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ClockSkewResponse {
+    /// The sources skewed the most first
+    pub list: Vec<ClockSkewEntry>,
+}
+
+/// Retention policy of a stream, enforced by the compactor
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct StreamRetention {
+    /// Days the data is kept, 0 falls back to the retention of the cluster
+    #[serde(default)]
+    pub days: i64,
+    /// Copy the expired files to the archive storage before deleting them
+    #[serde(default)]
+    pub archive: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct StreamRetentionResponse {
+    #[serde(flatten)]
+    pub retention: StreamRetention,
+    /// Days the data is actually kept
+    pub effective_days: i64,
+}
+
+/// Time range of a stream to bring back from the cold storage, in
+/// microseconds
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ColdStorageRestoreRequest {
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ColdStorageRestoreStatus {
+    #[default]
+    Running,
+    Completed,
+    Failed,
+}
+
+/// Restore of the files of a time range moved to the cold storage, they are
+/// moved back to it once the restore expires
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct ColdStorageRestore {
+    pub id: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub status: ColdStorageRestoreStatus,
+    /// Files copied back from the cold storage
+    #[serde(default)]
+    pub files: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ColdStorageRestore {
+    /// The restore keeps the files of the time range out of the cold storage
+    /// until it expires, the failed ones too, they can be partially done
+    pub fn is_active(&self, now: i64) -> bool {
+        self.expires_at > now
+    }
+
+    pub fn overlaps(&self, min_ts: i64, max_ts: i64) -> bool {
+        min_ts <= self.end_time && max_ts >= self.start_time
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ColdStorageRestoreList {
+    /// The latest restores first
+    pub list: Vec<ColdStorageRestore>,
+}
+
+/// Statistics of a field over a time range, from the metadata of the files
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct FieldStats {
+    pub name: String,
+    pub data_type: String,
+    /// Smallest value, `None` when the field is always null or the files have
+    /// no statistics for it
+    #[schema(value_type = Object)]
+    pub min: Option<json::Value>,
+    /// Largest value, `None` when the field is always null or the files have
+    /// no statistics for it
+    #[schema(value_type = Object)]
+    pub max: Option<json::Value>,
+    /// Estimated number of distinct values, `None` when unknown
+    pub ndv: Option<u64>,
+    /// Records without the field
+    pub null_count: u64,
+    /// Share of the records without the field, from 0 to 1
+    pub null_fraction: f64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct FieldStatsResponse {
+    /// Records of the files the statistics are computed from, the files
+    /// overlapping the edges of the time range count all their records
+    pub records: u64,
+    /// Number of files in the time range
+    pub files: usize,
+    /// Number of the newest files the statistics are computed from
+    pub sampled_files: usize,
+    pub fields: Vec<FieldStats>,
+}
+
+/// Suggested user defined schema and index fields of a stream
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct SchemaSuggestion {
+    /// Time the suggestion was made, in microseconds
+    pub generated_at: i64,
+    pub total_fields: usize,
+    /// Number of records the cardinality of fields is measured on
+    pub sampled_records: usize,
+    /// The suggested user defined schema, as a whole
+    pub defined_schema_fields: Vec<String>,
+    /// Fields suggested for the secondary index, in addition to the current ones
+    pub index_fields: Vec<String>,
+    /// Why the fields of the suggestion were picked
+    pub fields: Vec<FieldSuggestion>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FieldSuggestion {
+    pub name: String,
+    /// Number of times searches referenced the field
+    pub usage: u64,
+    /// Share of the sampled records having the field
+    pub presence: f64,
+    /// Distinct values of the field in the sampled records
+    pub distinct_values: usize,
+    pub index: bool,
+    pub reason: String,
+}
+
 #[cfg(test)]
 mod tests {
     use config::meta::stream::{StreamSettings, StreamType};
@@ -279,4 +571,43 @@ mod tests {
         let delete_fields = StreamDeleteFields::default();
         assert!(delete_fields.fields.is_empty());
     }
+
+    #[test]
+    fn test_field_usage_merge() {
+        let mut usage = FieldUsage::default();
+        usage.merge(&FieldUsage {
+            queries: 2,
+            dashboards: 1,
+            first_used: 200,
+            last_used: 300,
+            ..Default::default()
+        });
+        usage.merge(&FieldUsage {
+            alerts: 3,
+            first_used: 100,
+            last_used: 150,
+            ..Default::default()
+        });
+        assert_eq!(usage.total(), 6);
+        assert_eq!(usage.first_used, 100);
+        assert_eq!(usage.last_used, 300);
+    }
+
+    #[test]
+    fn test_cold_storage_restore_active() {
+        let mut restore = ColdStorageRestore {
+            start_time: 100,
+            end_time: 200,
+            expires_at: 1000,
+            ..Default::default()
+        };
+        assert!(restore.is_active(999));
+        assert!(!restore.is_active(1000));
+        assert!(restore.overlaps(150, 300));
+        assert!(restore.overlaps(50, 100));
+        assert!(!restore.overlaps(201, 300));
+
+        restore.status = ColdStorageRestoreStatus::Failed;
+        assert!(restore.is_active(999));
+    }
 }
@@ -0,0 +1,154 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Sender of a webhook source, decides how payloads are signed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookKind {
+    /// `X-Hub-Signature-256` HMAC-SHA256 of the body
+    Github,
+    /// `Stripe-Signature` HMAC-SHA256 of the timestamp and the body
+    Stripe,
+    /// `X-PagerDuty-Signature` HMAC-SHA256 of the body
+    Pagerduty,
+    /// HMAC-SHA256 of the body in `signature_header`, or the secret itself in
+    /// the `token` query parameter for senders that can't sign
+    #[default]
+    Custom,
+}
+
+impl WebhookKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookKind::Github => "github",
+            WebhookKind::Stripe => "stripe",
+            WebhookKind::Pagerduty => "pagerduty",
+            WebhookKind::Custom => "custom",
+        }
+    }
+
+    /// Mapping of the fields of the payloads the sender posts most often,
+    /// empty for custom sources
+    pub fn mapping_template(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            WebhookKind::Github => &[
+                ("action", "$.action"),
+                ("repository", "$.repository.full_name"),
+                ("sender", "$.sender.login"),
+                ("ref", "$.ref"),
+                ("commit_messages", "$.commits[*].message"),
+                ("pull_request", "$.pull_request.number"),
+                ("issue", "$.issue.number"),
+                ("url", "$.pull_request.html_url"),
+            ],
+            WebhookKind::Stripe => &[
+                ("event_id", "$.id"),
+                ("event_type", "$.type"),
+                ("livemode", "$.livemode"),
+                ("object_id", "$.data.object.id"),
+                ("object_type", "$.data.object.object"),
+                ("status", "$.data.object.status"),
+                ("amount", "$.data.object.amount"),
+                ("currency", "$.data.object.currency"),
+                ("customer", "$.data.object.customer"),
+            ],
+            WebhookKind::Pagerduty => &[
+                ("event_id", "$.event.id"),
+                ("event_type", "$.event.event_type"),
+                ("occurred_at", "$.event.occurred_at"),
+                ("incident_id", "$.event.data.id"),
+                ("title", "$.event.data.title"),
+                ("status", "$.event.data.status"),
+                ("urgency", "$.event.data.urgency"),
+                ("service", "$.event.data.service.summary"),
+                ("url", "$.event.data.html_url"),
+            ],
+            WebhookKind::Custom => &[],
+        }
+    }
+}
+
+/// A catch-all webhook endpoint, events posted to
+/// `/webhooks/{org_id}/{name}` are validated and ingested into `stream_name`
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct WebhookSource {
+    pub name: String,
+    #[serde(default)]
+    pub kind: WebhookKind,
+    pub stream_name: String,
+    /// Shared secret payloads are signed with. It is never returned by the API,
+    /// leave it empty on update to keep the current one.
+    #[serde(default)]
+    pub secret: String,
+    /// Header carrying the hex encoded signature of custom sources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_header: Option<String>,
+    /// Fields of the events picked out of the payload with JSONPath, e.g.
+    /// `"repository": "$.repository.full_name"`, applied before the transform.
+    /// Only the mapped fields are ingested unless `keep_payload` is set.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub mapping: BTreeMap<String, String>,
+    /// Ingest the fields of the payload along with the mapped ones
+    #[serde(default)]
+    pub keep_payload: bool,
+    /// VRL program applied to each event before it is ingested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform: Option<String>,
+}
+
+impl WebhookSource {
+    /// Copy that is safe to return from the API
+    pub fn redacted(&self) -> Self {
+        Self {
+            secret: String::new(),
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct WebhookSourceList {
+    pub list: Vec<WebhookSource>,
+}
+
+/// Mapping to start the sources of a sender from
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct WebhookTemplate {
+    pub kind: WebhookKind,
+    pub mapping: BTreeMap<String, String>,
+}
+
+impl From<WebhookKind> for WebhookTemplate {
+    fn from(kind: WebhookKind) -> Self {
+        Self {
+            kind,
+            mapping: kind
+                .mapping_template()
+                .iter()
+                .map(|(field, path)| (field.to_string(), path.to_string()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct WebhookTemplateList {
+    pub list: Vec<WebhookTemplate>,
+}
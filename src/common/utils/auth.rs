@@ -113,6 +113,14 @@ pub(crate) fn is_root_user(user_id: &str) -> bool {
     }
 }
 
+/// Whether the user is the root user or an admin of the organization
+pub(crate) fn is_org_admin(org_id: &str, user_id: &str) -> bool {
+    is_root_user(user_id)
+        || ORG_USERS
+            .get(&format!("{org_id}/{user_id}"))
+            .is_some_and(|user| user.role.eq(&UserRole::Admin))
+}
+
 #[cfg(feature = "enterprise")]
 pub async fn save_org_tuples(org_id: &str) {
     use o2_openfga::config::get_config as get_openfga_config;
@@ -516,12 +524,13 @@ where
                 }
             } else if path_columns[2].starts_with("_values")
                 || path_columns[2].starts_with("_around")
+                || path_columns[2].starts_with("_tail")
             {
                 if method.eq("POST") {
                     // For _around search, the rbac check will be "GET"
                     method = "GET".to_string();
                 }
-                // special case of _values/_around , where we need permission on that stream,
+                // special case of _values/_around/_tail, where we need permission on that stream,
                 // as it is part of search, but still 3-part route
                 format!(
                     "{}:{}",
@@ -1016,13 +1025,18 @@ where
             || path.contains("/prometheus/api/v1/query")
             || path.contains("/resources")
             || path.contains("/format_query")
+            || path.contains("/translate_query")
             || path.contains("/prometheus/api/v1/series")
+            || path.contains("/prometheus/api/v1/read")
             || path.contains("/traces/latest")
+            || path.contains("/traces/by_attribute")
             || path.contains("clusters")
             || path.contains("query_manager")
             || path.contains("/short")
             || path.contains("/ws")
             || path.contains("/_values_stream")
+            // async search jobs are checked against the submitting user
+            || path.contains("/_search_async")
             // bulk enable of pipelines and alerts
             || path.contains("/bulk/enable")
             || path_is_bulk_operation
@@ -120,6 +120,11 @@ pub(crate) fn get_is_multi_stream_search_from_request(query: &HashMap<String, St
     get_key_as_bool(query, "is_multi_stream_search")
 }
 
+#[inline(always)]
+pub(crate) fn get_bypass_max_query_range_from_request(query: &HashMap<String, String>) -> bool {
+    get_key_as_bool(query, "bypass_max_query_range")
+}
+
 #[inline(always)]
 pub(crate) fn get_clear_cache_from_request(query: &HashMap<String, String>) -> bool {
     get_key_as_bool(query, "clear_cache")
@@ -18,7 +18,9 @@ pub mod functions;
 pub mod http;
 pub mod js;
 pub mod jwt;
+pub mod otlp;
 pub mod redirect_response;
 pub mod ssrf_guard;
 pub mod stream;
+pub mod vrl_functions;
 pub mod zo_logger;
@@ -0,0 +1,278 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Incremental reading of OTLP/HTTP bodies.
+//!
+//! The export requests of the three signals only have one field, the repeated
+//! resource entries (field 1). Protobuf bodies are decoded an entry at a time
+//! while the decompressed body is read, so only the entry being received is
+//! buffered instead of the whole body.
+
+use axum::{
+    Json,
+    body::{Body, Bytes},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use bytes::{Buf, BytesMut};
+use futures::StreamExt;
+use prost::Message;
+
+use crate::common::meta::http::HttpResponse as MetaHttpResponse;
+
+/// Field number of the resource entries in the export requests
+const RESOURCE_FIELD: u64 = 1;
+
+#[derive(Debug, thiserror::Error)]
+pub enum BodyError {
+    #[error("request body exceeds the limit of {0} bytes")]
+    TooLarge(usize),
+    #[error("failed to read request body: {0}")]
+    Read(String),
+    #[error("Invalid proto: {0}")]
+    Invalid(String),
+}
+
+impl IntoResponse for BodyError {
+    fn into_response(self) -> Response {
+        match self {
+            BodyError::TooLarge(_) => (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                Json(MetaHttpResponse::error(StatusCode::PAYLOAD_TOO_LARGE, self)),
+            )
+                .into_response(),
+            _ => MetaHttpResponse::bad_request(self),
+        }
+    }
+}
+
+/// Reads the whole body, for the formats which can't be decoded incrementally
+pub async fn read_body(body: Body, limit: usize) -> Result<Bytes, BodyError> {
+    let mut stream = body.into_data_stream();
+    let mut buf = BytesMut::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| BodyError::Read(e.to_string()))?;
+        if buf.len() + chunk.len() > limit {
+            return Err(BodyError::TooLarge(limit));
+        }
+        buf.extend_from_slice(&chunk);
+    }
+    Ok(buf.freeze())
+}
+
+/// Decodes the resource entries of a protobuf export request as the body is
+/// read, failing once more than `limit` bytes are received
+pub async fn decode_resources<M: Message + Default>(
+    body: Body,
+    limit: usize,
+) -> Result<Vec<M>, BodyError> {
+    let mut stream = body.into_data_stream();
+    let mut buf = BytesMut::new();
+    let mut received = 0;
+    let mut resources = Vec::new();
+    loop {
+        while let Some(field) = next_field(&buf)? {
+            buf.advance(field.header_len);
+            let value = buf.split_to(field.value_len).freeze();
+            if field.number == RESOURCE_FIELD {
+                resources.push(M::decode(value).map_err(|e| BodyError::Invalid(e.to_string()))?);
+            }
+        }
+        match stream.next().await {
+            Some(chunk) => {
+                let chunk = chunk.map_err(|e| BodyError::Read(e.to_string()))?;
+                received += chunk.len();
+                if received > limit {
+                    return Err(BodyError::TooLarge(limit));
+                }
+                buf.extend_from_slice(&chunk);
+            }
+            None if buf.is_empty() => return Ok(resources),
+            None => return Err(BodyError::Invalid("unexpected end of message".to_string())),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Field {
+    number: u64,
+    header_len: usize,
+    value_len: usize,
+}
+
+/// Returns the next top level field of `buf`, `None` when it is not fully
+/// received yet. Fields other than the resource entries are skipped like
+/// unknown fields.
+fn next_field(buf: &[u8]) -> Result<Option<Field>, BodyError> {
+    let Some((key, key_len)) = varint(buf)? else {
+        return Ok(None);
+    };
+    let number = key >> 3;
+    if number == 0 {
+        return Err(BodyError::Invalid("invalid field number 0".to_string()));
+    }
+    let (header_len, value_len) = match key & 0x07 {
+        // varint
+        0 => match varint(&buf[key_len..])? {
+            Some((_, len)) => (key_len, len),
+            None => return Ok(None),
+        },
+        // 64-bit
+        1 => (key_len, 8),
+        // length delimited
+        2 => match varint(&buf[key_len..])? {
+            Some((len, len_len)) => (
+                key_len + len_len,
+                usize::try_from(len)
+                    .map_err(|_| BodyError::Invalid("invalid length".to_string()))?,
+            ),
+            None => return Ok(None),
+        },
+        // 32-bit
+        5 => (key_len, 4),
+        wire_type => {
+            return Err(BodyError::Invalid(format!(
+                "invalid wire type {wire_type} for field {number}"
+            )));
+        }
+    };
+    if number == RESOURCE_FIELD && key & 0x07 != 2 {
+        return Err(BodyError::Invalid(format!(
+            "invalid wire type {} for field {number}",
+            key & 0x07
+        )));
+    }
+    if buf.len() - header_len < value_len {
+        return Ok(None);
+    }
+    Ok(Some(Field {
+        number,
+        header_len,
+        value_len,
+    }))
+}
+
+/// Reads the varint at the start of `buf` with its length, `None` when it is
+/// not fully received yet
+fn varint(buf: &[u8]) -> Result<Option<(u64, usize)>, BodyError> {
+    let mut value = 0u64;
+    for (i, byte) in buf.iter().take(10).enumerate() {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok(Some((value, i + 1)));
+        }
+    }
+    if buf.len() >= 10 {
+        return Err(BodyError::Invalid("invalid varint".to_string()));
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry_proto::tonic::{
+        collector::logs::v1::ExportLogsServiceRequest,
+        logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+    };
+
+    use super::*;
+
+    fn request(count: usize) -> ExportLogsServiceRequest {
+        ExportLogsServiceRequest {
+            resource_logs: (0..count)
+                .map(|i| ResourceLogs {
+                    scope_logs: vec![ScopeLogs {
+                        log_records: vec![LogRecord {
+                            time_unix_nano: i as u64,
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    fn chunked(data: Vec<u8>, size: usize) -> Body {
+        let chunks: Vec<Result<Bytes, std::io::Error>> = data
+            .chunks(size)
+            .map(|c| Ok(Bytes::copy_from_slice(c)))
+            .collect();
+        Body::from_stream(futures::stream::iter(chunks))
+    }
+
+    #[tokio::test]
+    async fn test_decode_resources() {
+        let req = request(5);
+        let data = req.encode_to_vec();
+        for size in [1, 3, 64, data.len()] {
+            let resources: Vec<ResourceLogs> = decode_resources(chunked(data.clone(), size), 1024)
+                .await
+                .unwrap();
+            assert_eq!(resources, req.resource_logs);
+        }
+        let resources: Vec<ResourceLogs> = decode_resources(Body::empty(), 1024).await.unwrap();
+        assert!(resources.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_decode_resources_invalid() {
+        let data = request(5).encode_to_vec();
+        let truncated = data[..data.len() - 1].to_vec();
+        assert!(matches!(
+            decode_resources::<ResourceLogs>(chunked(truncated, 7), 1024).await,
+            Err(BodyError::Invalid(_))
+        ));
+        assert!(matches!(
+            decode_resources::<ResourceLogs>(chunked(data, 7), 16).await,
+            Err(BodyError::TooLarge(16))
+        ));
+        // field 1 as a varint
+        assert!(matches!(
+            decode_resources::<ResourceLogs>(chunked(vec![0x08, 0x01], 1), 1024).await,
+            Err(BodyError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_next_field() {
+        // unknown varint field 2 is skipped like prost does
+        assert_eq!(
+            next_field(&[0x10, 0x96, 0x01]).unwrap(),
+            Some(Field {
+                number: 2,
+                header_len: 1,
+                value_len: 2
+            })
+        );
+        assert_eq!(next_field(&[0x0a, 0x03, 0x01]).unwrap(), None);
+        assert_eq!(next_field(&[0x0a]).unwrap(), None);
+        assert!(next_field(&[0x00]).is_err());
+        assert!(next_field(&[0xff; 11]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_body() {
+        let body = read_body(chunked(b"{\"resourceLogs\":[]}".to_vec(), 4), 1024)
+            .await
+            .unwrap();
+        assert_eq!(body.as_ref(), b"{\"resourceLogs\":[]}");
+        assert!(matches!(
+            read_body(chunked(vec![0; 32], 4), 16).await,
+            Err(BodyError::TooLarge(16))
+        ));
+    }
+}
@@ -25,7 +25,7 @@ use config::{
     },
 };
 
-use crate::service::users;
+use crate::{common::utils::auth::is_root_user, service::users};
 
 #[inline(always)]
 pub fn stream_type_query_param_error() -> Result<Response, Error> {
@@ -171,6 +171,72 @@ pub fn get_max_query_range_by_user_role(stream_max_query_range: i64, user: &User
     effective_max_query_range
 }
 
+/// Whether the role may search past the max query range of streams
+fn can_role_bypass_max_query_range(role: &UserRole) -> bool {
+    matches!(role, UserRole::Root | UserRole::Admin)
+}
+
+/// Whether the user may search past the max query range of streams, only the
+/// root user and the admins of the organization can once it is enabled
+pub async fn can_bypass_max_query_range(org_id: &str, user_id: &str) -> bool {
+    if !get_config().limit.max_query_range_bypass_enabled {
+        return false;
+    }
+    if is_root_user(user_id) {
+        return true;
+    }
+    users::get_user(Some(org_id), user_id)
+        .await
+        .is_some_and(|user| can_role_bypass_max_query_range(&user.role))
+}
+
+/// Records a search let past the max query range of a stream, in the audit
+/// log when auditing is enabled
+pub async fn audit_max_query_range_bypass(
+    trace_id: &str,
+    org_id: &str,
+    user_id: &str,
+    stream_name: &str,
+    max_query_range: i64,
+    start_time: i64,
+    end_time: i64,
+) {
+    let message = format!(
+        "max query range of {max_query_range} hours of stream {stream_name} bypassed for a query of {} hours",
+        (end_time - start_time) / 3600 / 1_000_000
+    );
+    log::warn!("[trace_id {trace_id}] {org_id}/{user_id}: {message}");
+
+    #[cfg(feature = "enterprise")]
+    {
+        use o2_enterprise::enterprise::common::{
+            auditor::{AuditMessage, Protocol, ResponseMeta},
+            config::get_config as get_o2_config,
+        };
+
+        if get_o2_config().common.audit_enabled {
+            crate::service::self_reporting::audit(AuditMessage {
+                user_email: user_id.to_string(),
+                org_id: org_id.to_string(),
+                _timestamp: chrono::Utc::now().timestamp(),
+                protocol: Protocol::Http,
+                response_meta: ResponseMeta {
+                    http_method: "POST".to_string(),
+                    http_path: format!("/api/{org_id}/_search"),
+                    http_query_params: format!(
+                        "bypass_max_query_range=true&start_time={start_time}&end_time={end_time}"
+                    ),
+                    http_body: "".to_string(),
+                    http_response_code: 200,
+                    error_msg: Some(message),
+                    trace_id: Some(trace_id.to_string()),
+                },
+            })
+            .await;
+        }
+    }
+}
+
 /// Get the maximum query range for a list of streams in hours
 pub async fn get_max_query_range(
     stream_names: &[String],
@@ -340,6 +406,19 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[tokio::test]
+    async fn test_can_bypass_max_query_range() {
+        assert!(can_role_bypass_max_query_range(&UserRole::Root));
+        assert!(can_role_bypass_max_query_range(&UserRole::Admin));
+        assert!(!can_role_bypass_max_query_range(&UserRole::User));
+        assert!(!can_role_bypass_max_query_range(&UserRole::ServiceAccount));
+
+        // disabled by default
+        if !get_config().limit.max_query_range_bypass_enabled {
+            assert!(!can_bypass_max_query_range("test_org", "root@example.com").await);
+        }
+    }
+
     #[tokio::test]
     async fn test_get_settings_max_query_range() {
         // Test with no user_id (should return effective max query range)
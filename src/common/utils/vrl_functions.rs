@@ -0,0 +1,345 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! VRL functions added to the standard library, for the functions of
+//! pipelines, ingestion and search alike.
+//!
+//! - `sha256(value)`: hex digest
+//! - `hmac_sha256(value, key)`: hex digest, the standard `hmac` returns bytes
+//! - `encode_base32(value)`, `decode_base32!(value)`: RFC 4648 with padding
+//! - `url_decode!(value)`: decodes `%XX` and `+` of query strings and forms
+//! - `parse_jwt!(value)`: the claims of a JWT, its signature isn't verified
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use vrl::{prelude::*, value::kind::Collection};
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+pub fn all() -> Vec<Box<dyn Function>> {
+    vec![
+        Box::new(BytesFunction {
+            identifier: "sha256",
+            op: BytesOp::Sha256,
+        }),
+        Box::new(HmacSha256),
+        Box::new(BytesFunction {
+            identifier: "encode_base32",
+            op: BytesOp::EncodeBase32,
+        }),
+        Box::new(BytesFunction {
+            identifier: "decode_base32",
+            op: BytesOp::DecodeBase32,
+        }),
+        Box::new(BytesFunction {
+            identifier: "url_decode",
+            op: BytesOp::UrlDecode,
+        }),
+        Box::new(BytesFunction {
+            identifier: "parse_jwt",
+            op: BytesOp::ParseJwt,
+        }),
+    ]
+}
+
+#[derive(Clone, Copy, Debug)]
+enum BytesOp {
+    Sha256,
+    EncodeBase32,
+    DecodeBase32,
+    UrlDecode,
+    ParseJwt,
+}
+
+impl BytesOp {
+    fn apply(self, value: &[u8]) -> Result<Value, String> {
+        match self {
+            BytesOp::Sha256 => Ok(hex::encode(Sha256::digest(value)).into()),
+            BytesOp::EncodeBase32 => Ok(encode_base32(value).into()),
+            BytesOp::DecodeBase32 => decode_base32(value).map(|v| Value::Bytes(v.into())),
+            BytesOp::UrlDecode => url_decode(value).map(Value::from),
+            BytesOp::ParseJwt => parse_jwt(value).map(|v| Value::from(&v)),
+        }
+    }
+
+    fn type_def(self) -> TypeDef {
+        match self {
+            BytesOp::Sha256 | BytesOp::EncodeBase32 => TypeDef::bytes().infallible(),
+            BytesOp::DecodeBase32 | BytesOp::UrlDecode => TypeDef::bytes().fallible(),
+            BytesOp::ParseJwt => TypeDef::object(Collection::any()).fallible(),
+        }
+    }
+}
+
+/// A function of a single bytes argument
+#[derive(Clone, Copy, Debug)]
+struct BytesFunction {
+    identifier: &'static str,
+    op: BytesOp,
+}
+
+impl Function for BytesFunction {
+    fn identifier(&self) -> &'static str {
+        self.identifier
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        Ok(BytesFn { op: self.op, value }.as_expr())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct BytesFn {
+    op: BytesOp,
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for BytesFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_bytes()?;
+        self.op.apply(&value).map_err(Into::into)
+    }
+
+    fn type_def(&self, _state: &TypeState) -> TypeDef {
+        self.op.type_def()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct HmacSha256;
+
+impl Function for HmacSha256 {
+    fn identifier(&self) -> &'static str {
+        "hmac_sha256"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let key = arguments.required("key");
+        Ok(HmacSha256Fn { value, key }.as_expr())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct HmacSha256Fn {
+    value: Box<dyn Expression>,
+    key: Box<dyn Expression>,
+}
+
+impl FunctionExpression for HmacSha256Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_bytes()?;
+        let key = self.key.resolve(ctx)?.try_bytes()?;
+        Ok(hmac_sha256(&value, &key).into())
+    }
+
+    fn type_def(&self, _state: &TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+fn hmac_sha256(value: &[u8], key: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(value);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+fn encode_base32(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(5) * 8);
+    for chunk in data.chunks(5) {
+        let mut buf = [0u8; 8];
+        buf[3..3 + chunk.len()].copy_from_slice(chunk);
+        let bits = u64::from_be_bytes(buf);
+        let chars = (chunk.len() * 8).div_ceil(5);
+        for i in 0..8 {
+            if i < chars {
+                out.push(BASE32_ALPHABET[((bits >> (35 - i * 5)) & 0x1f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn decode_base32(data: &[u8]) -> Result<Vec<u8>, String> {
+    let data = data.trim_ascii();
+    let end = data.iter().rposition(|c| *c != b'=').map_or(0, |i| i + 1);
+    let mut out = Vec::with_capacity(end * 5 / 8);
+    let (mut buffer, mut bits) = (0u64, 0);
+    for c in &data[..end] {
+        let v = match c.to_ascii_uppercase() {
+            c @ b'A'..=b'Z' => c - b'A',
+            c @ b'2'..=b'7' => c - b'2' + 26,
+            _ => return Err(format!("invalid base32 character {:?}", *c as char)),
+        };
+        buffer = (buffer << 5) | v as u64;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Ok(out)
+}
+
+fn url_decode(data: &[u8]) -> Result<String, String> {
+    let mut out = Vec::with_capacity(data.len());
+    let mut i = 0;
+    while i < data.len() {
+        match data[i] {
+            b'+' => out.push(b' '),
+            b'%' => {
+                let byte = data
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| std::str::from_utf8(hex).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| format!("invalid percent encoding at {i}"))?;
+                out.push(byte);
+                i += 2;
+            }
+            c => out.push(c),
+        }
+        i += 1;
+    }
+    String::from_utf8(out).map_err(|e| format!("decoded value isn't UTF-8: {e}"))
+}
+
+/// The claims of the token, its signature isn't verified
+fn parse_jwt(token: &[u8]) -> Result<serde_json::Value, String> {
+    let token = std::str::from_utf8(token).map_err(|_| "invalid JWT".to_string())?;
+    let mut parts = token.trim().split('.');
+    let (Some(_header), Some(payload)) = (parts.next(), parts.next()) else {
+        return Err("invalid JWT, it has no payload".to_string());
+    };
+    let payload = URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .map_err(|e| format!("invalid JWT payload: {e}"))?;
+    match serde_json::from_slice(&payload) {
+        Ok(claims @ serde_json::Value::Object(_)) => Ok(claims),
+        Ok(_) => Err("invalid JWT payload, it isn't an object".to_string()),
+        Err(e) => Err(format!("invalid JWT payload: {e}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::ingestion::{apply_vrl_fn, compile_vrl_function};
+
+    #[test]
+    fn test_encodings() {
+        assert_eq!(
+            hex::encode(Sha256::digest(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hmac_sha256(b"what do ya want for nothing?", b"Jefe"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(encode_base32(b"foobar"), "MZXW6YTBOI======");
+        assert_eq!(encode_base32(b"f"), "MY======");
+        assert_eq!(decode_base32(b"MZXW6YTBOI======").unwrap(), b"foobar");
+        assert_eq!(decode_base32(b"mzxw6").unwrap(), b"foo");
+        assert!(decode_base32(b"MZ1W").is_err());
+        assert_eq!(url_decode(b"a%20b+c%2Fd").unwrap(), "a b c/d");
+        assert!(url_decode(b"100%").is_err());
+    }
+
+    #[test]
+    fn test_parse_jwt() {
+        let token = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.eyJzdWIiOiIxMjM0NTY3ODkwIiwibmFtZSI6IkpvaG4gRG9lIiwiaWF0IjoxNTE2MjM5MDIyfQ.signature";
+        let claims = parse_jwt(token.as_bytes()).unwrap();
+        assert_eq!(claims["sub"], "1234567890");
+        assert_eq!(claims["iat"], 1516239022);
+        assert!(parse_jwt(b"no_payload").is_err());
+        assert!(parse_jwt(b"a.b.c").is_err());
+    }
+
+    #[test]
+    fn test_vrl_functions_registered() {
+        let program = compile_vrl_function(
+            r#".digest = sha256(.value)
+.signature = hmac_sha256(.value, "key")
+.encoded = encode_base32(.value)
+.decoded = decode_base32!(.encoded)
+.path = url_decode!("a%2Fb")
+.
+"#,
+            "default",
+        )
+        .unwrap();
+        let mut runtime = crate::common::utils::functions::init_vrl_runtime();
+        let (ret, err) = apply_vrl_fn(
+            &mut runtime,
+            &config::meta::function::VRLResultResolver {
+                program: program.program,
+                fields: vec![],
+            },
+            serde_json::json!({"value": "foobar"}),
+            "default",
+            &["test".to_string()],
+        );
+        assert!(err.is_none());
+        assert_eq!(ret["encoded"], "MZXW6YTBOI======");
+        assert_eq!(ret["decoded"], "foobar");
+        assert_eq!(ret["path"], "a/b");
+        assert_eq!(ret["digest"].as_str().unwrap().len(), 64);
+    }
+}
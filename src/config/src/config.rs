@@ -40,7 +40,10 @@ use serde::Serialize;
 use sha256::digest;
 
 use crate::{
-    meta::{cluster, stream::QueryPartitionStrategy},
+    meta::{
+        cluster,
+        stream::{QueryPartitionStrategy, WalSyncPolicy},
+    },
     utils::sysinfo,
 };
 
@@ -497,6 +500,16 @@ pub struct Config {
     pub health_check: HealthCheck,
     pub encryption: Encryption,
     pub enrichment_table: EnrichmentTable,
+    pub flow_collector: FlowCollector,
+    pub k8s_events: K8sEvents,
+    pub docker_logs: DockerLogs,
+    pub gelf: Gelf,
+    pub access_log_import: AccessLogImport,
+    pub kafka_ingestion: KafkaIngestion,
+    pub kinesis_ingestion: KinesisIngestion,
+    pub mqtt_ingestion: MqttIngestion,
+    pub redis_streams_ingestion: RedisStreamsIngestion,
+    pub pgwire: Pgwire,
 }
 
 #[derive(Serialize, EnvConfig, Default)]
@@ -513,6 +526,18 @@ pub struct HttpStreaming {
         help = "Enable streaming"
     )]
     pub streaming_enabled: bool,
+    #[env_config(
+        name = "ZO_TAIL_MAX_PER_NODE",
+        default = 100,
+        help = "Max live tails of the streams served by a node at once"
+    )]
+    pub tail_max_per_node: usize,
+    #[env_config(
+        name = "ZO_TAIL_BUFFER_SIZE",
+        default = 1024,
+        help = "Write batches buffered for a live tail, the ones of a tail too slow to follow are skipped"
+    )]
+    pub tail_buffer_size: usize,
 }
 
 #[derive(Serialize, EnvConfig, Default)]
@@ -687,6 +712,12 @@ pub struct Grpc {
         help = "Max grpc message size in MB, default is 16 MB"
     )]
     pub max_message_size: usize,
+    #[env_config(
+        name = "ZO_GRPC_OTLP_MAX_MESSAGE_SIZE",
+        default = 128,
+        help = "Max decompressed message size in MB accepted by the OTLP gRPC services"
+    )]
+    pub otlp_max_message_size: usize,
     #[env_config(name = "ZO_GRPC_CONNECT_TIMEOUT", default = 5)] // in seconds
     pub connect_timeout: u64,
     #[env_config(name = "ZO_GRPC_CHANNEL_CACHE_DISABLED", default = false)]
@@ -699,6 +730,50 @@ pub struct Grpc {
     pub tls_cert_path: String,
     #[env_config(name = "ZO_GRPC_TLS_KEY_PATH", default = "")]
     pub tls_key_path: String,
+    #[env_config(
+        name = "ZO_GRPC_TLS_CA_CERT_PATH",
+        default = "",
+        help = "CA certificates the gRPC certificates of the nodes are signed with. Clients verify the servers with it, defaults to ZO_GRPC_TLS_CERT_PATH"
+    )]
+    pub tls_ca_cert_path: String,
+    #[env_config(
+        name = "ZO_GRPC_TLS_CLIENT_AUTH",
+        default = false,
+        help = "Mutual TLS: the gRPC servers only accept clients, other nodes and OTLP senders alike, presenting a certificate signed by ZO_GRPC_TLS_CA_CERT_PATH. Nodes present ZO_GRPC_TLS_CERT_PATH"
+    )]
+    pub tls_client_auth: bool,
+    #[env_config(
+        name = "ZO_GRPC_TLS_RELOAD_INTERVAL",
+        default = 60,
+        help = "Seconds between checks of the gRPC certificate files, changed certificates are used by the new connections without a restart. 0 disables the reload"
+    )]
+    pub tls_reload_interval: u64,
+    #[env_config(
+        name = "ZO_GRPC_FLIGHT_ENABLED",
+        default = false,
+        help = "Serve search results to external clients over Arrow Flight, on queriers"
+    )]
+    pub flight_enabled: bool,
+    #[env_config(name = "ZO_GRPC_FLIGHT_PORT", default = 5082)]
+    pub flight_port: u16,
+    #[env_config(
+        name = "ZO_GRPC_FLIGHT_COMPRESSION",
+        default = "zstd",
+        help = "Compression of the record batches streamed between nodes over Arrow Flight: zstd, lz4 or none. With none the gRPC compression is skipped too, the batches are decoded without copying the received buffers"
+    )]
+    pub flight_compression: String,
+    #[env_config(
+        name = "ZO_GRPC_FLIGHT_MAX_DATA_SIZE",
+        default = 32,
+        help = "Max size in MB of a record batch message of the Arrow Flight streams, larger batches are split"
+    )]
+    pub flight_max_data_size: usize,
+    #[env_config(
+        name = "ZO_GRPC_STREAM_WINDOW_SIZE",
+        default = 0,
+        help = "HTTP/2 stream window size in KB of the gRPC client connections, it bounds the data a node streams ahead of the reader, like the record batches of a search. 0 keeps the default of the client"
+    )]
+    pub stream_window_size: u32,
 }
 
 #[derive(Serialize, PartialEq, Default)]
@@ -762,7 +837,7 @@ pub struct Common {
     #[env_config(
         name = "ZO_NODE_ROLE_GROUP",
         default = "",
-        help = "Role group can be empty (default), interactive, or background"
+        help = "Role group can be empty (default), interactive, background, or shadow for the queriers running the shadow searches"
     )]
     pub node_role_group: String,
     #[env_config(name = "ZO_CLUSTER_NAME", default = "zo1")]
@@ -799,6 +874,18 @@ pub struct Common {
         help = "Disable timestamp field compression"
     )]
     pub timestamp_compression_disabled: bool,
+    #[env_config(
+        name = "ZO_PARQUET_PAGE_INDEX_ENABLED",
+        default = true,
+        help = "Write page level statistics to parquet files and use them to skip the pages out of the query time range or filters"
+    )]
+    pub parquet_page_index_enabled: bool,
+    #[env_config(
+        name = "ZO_PARQUET_PAGE_ROW_LIMIT",
+        default = 0,
+        help = "Maximum number of rows in a parquet data page, default to the write batch size"
+    )]
+    pub parquet_page_row_limit: usize,
     #[env_config(name = "ZO_FEATURE_INGESTER_NONE_COMPRESSION", default = false)]
     pub feature_ingester_none_compression: bool,
     #[env_config(name = "ZO_FEATURE_FULLTEXT_EXTRA_FIELDS", default = "")]
@@ -879,6 +966,12 @@ pub struct Common {
         help = "Skip WAL for query"
     )]
     pub feature_query_skip_wal: bool,
+    #[env_config(
+        name = "ZO_FEATURE_QUERY_PROVENANCE_ENABLED",
+        default = false,
+        help = "Allow the queries to select the _o2_source_file, _o2_source_node and _o2_source_tier columns, the file, node and storage tier every row is read from (used for debug)"
+    )]
+    pub feature_query_provenance_enabled: bool,
     #[env_config(
         name = "ZO_FEATURE_SHARED_MEMTABLE_ENABLED",
         default = false,
@@ -915,14 +1008,42 @@ pub struct Common {
         help = "Bloom filter ndv ratio, set to 100 means NDV = row_count / 100, if set to 1 means will use NDV = row_count"
     )]
     pub bloom_filter_ndv_ratio: u64,
+    #[env_config(
+        name = "ZO_INDEX_BLOOM_FILTER_ENABLED",
+        default = false,
+        help = "Store bloom filters of the bloom filter fields in the index files, searches for `field = 'value'` skip the files without the value before downloading them"
+    )]
+    pub index_bloom_filter_enabled: bool,
     #[env_config(
         name = "ZO_SEARCH_AROUND_DEFAULT_FIELDS",
         default = "",
         help = "Comma separated list of fields to use for search around"
     )]
     pub search_around_default_fields: String,
-    #[env_config(name = "ZO_WAL_FSYNC_DISABLED", default = true)]
+    #[env_config(
+        name = "ZO_WAL_FSYNC_DISABLED",
+        default = true,
+        help = "Deprecated, use ZO_WAL_SYNC_POLICY. Used when ZO_WAL_SYNC_POLICY is not set: true means async, false means batch"
+    )]
     pub wal_fsync_disabled: bool,
+    #[env_config(
+        name = "ZO_WAL_SYNC_POLICY",
+        default = "",
+        help = "When the WAL is synced to disk for the streams without a wal_sync_policy setting: batch, interval or async"
+    )]
+    pub wal_sync_policy: String,
+    #[env_config(
+        name = "ZO_WAL_SYNC_POLICY_HIGH_PRIORITY",
+        default = "",
+        help = "WAL sync policy of the high priority streams without a wal_sync_policy setting, defaults to ZO_WAL_SYNC_POLICY"
+    )]
+    pub wal_sync_policy_high_priority: String,
+    #[env_config(
+        name = "ZO_WAL_SYNC_INTERVAL",
+        default = 1000,
+        help = "Max milliseconds between two fsync of the WAL for the interval sync policy"
+    )]
+    pub wal_sync_interval: u64,
     #[env_config(
         name = "ZO_WAL_WRITE_QUEUE_ENABLED",
         default = false,
@@ -1002,6 +1123,30 @@ pub struct Common {
     pub usage_reporting_creds: String,
     #[env_config(name = "ZO_USAGE_REPORTING_ERRORS_ENABLED", default = true)]
     pub usage_reporting_errors_enabled: bool,
+    #[env_config(
+        name = "ZO_SEARCH_AUDIT_ENABLED",
+        default = false,
+        help = "Write every search request, with its cost and timings, to the _audit_search stream of the meta org"
+    )]
+    pub search_audit_enabled: bool,
+    #[env_config(
+        name = "ZO_SEARCH_SHADOW_ENABLED",
+        default = false,
+        help = "Run a sample of the searches again on the queriers of the shadow role group and write the mismatches to the _query_shadow stream of the meta org"
+    )]
+    pub search_shadow_enabled: bool,
+    #[env_config(
+        name = "ZO_SEARCH_SHADOW_RATIO",
+        default = 1,
+        help = "Percentage of the interactive searches also run on the shadow queriers"
+    )]
+    pub search_shadow_ratio: usize,
+    #[env_config(
+        name = "ZO_SEARCH_SHADOW_LATENCY_RATIO",
+        default = 200,
+        help = "A shadow search taking more than this percentage of the time of the production one is recorded as slower"
+    )]
+    pub search_shadow_latency_ratio: usize,
     #[env_config(name = "ZO_USAGE_BATCH_SIZE", default = 2000)]
     pub usage_batch_size: usize,
     #[env_config(
@@ -1011,6 +1156,68 @@ pub struct Common {
     )]
     // in seconds
     pub usage_publish_interval: i64,
+    // field usage analytics
+    #[env_config(
+        name = "ZO_FIELD_USAGE_ENABLED",
+        default = true,
+        help = "Track which stream fields are referenced by searches"
+    )]
+    pub field_usage_enabled: bool,
+    #[env_config(
+        name = "ZO_FIELD_USAGE_FLUSH_INTERVAL",
+        default = 300,
+        help = "duration in seconds between persisting the field usage counters"
+    )]
+    pub field_usage_flush_interval: u64,
+    #[env_config(
+        name = "ZO_CLOCK_SKEW_FLUSH_INTERVAL",
+        default = 60,
+        help = "duration in seconds between persisting the clock skew of the sources of the streams, 0 disables it"
+    )]
+    pub clock_skew_flush_interval: u64,
+    #[env_config(
+        name = "ZO_SERVICE_MAP_FLUSH_INTERVAL",
+        default = 60,
+        help = "duration in seconds between persisting the calls between services seen in the ingested spans, 0 disables the service map"
+    )]
+    pub service_map_flush_interval: u64,
+    #[env_config(
+        name = "ZO_LOG_METRICS_FLUSH_INTERVAL",
+        default = 30,
+        help = "duration in seconds between writing the metrics extracted from the ingested logs, 0 disables the log metrics rules"
+    )]
+    pub log_metrics_flush_interval: u64,
+    #[env_config(
+        name = "ZO_TAIL_SAMPLING_MAX_BUFFER_MB",
+        default = 512,
+        help = "memory the spans held by tail sampling can take per node, the oldest traces are decided early beyond it"
+    )]
+    pub tail_sampling_max_buffer_mb: usize,
+    // user defined schema suggestions
+    #[env_config(
+        name = "ZO_SCHEMA_SUGGESTION_ENABLED",
+        default = true,
+        help = "Periodically suggest user defined schema and index fields for wide streams"
+    )]
+    pub schema_suggestion_enabled: bool,
+    #[env_config(
+        name = "ZO_SCHEMA_SUGGESTION_INTERVAL",
+        default = 86400,
+        help = "duration in seconds between schema suggestion runs"
+    )]
+    pub schema_suggestion_interval: u64,
+    #[env_config(
+        name = "ZO_PATTERN_STATS_INTERVAL",
+        default = 3600,
+        help = "duration in seconds between the background pattern mining runs of the logs streams with log patterns enabled, 0 disables it"
+    )]
+    pub pattern_stats_interval: u64,
+    #[env_config(
+        name = "ZO_SCHEMA_SUGGESTION_MIN_FIELDS",
+        default = 100,
+        help = "Streams with fewer fields get no schema suggestion in the background"
+    )]
+    pub schema_suggestion_min_fields: usize,
     #[env_config(
         name = "ZO_ERROR_PUBLISH_TIMEOUT_SECS",
         default = 2,
@@ -1059,6 +1266,42 @@ pub struct Common {
         help = "Disk space threshold. Values < 100 are treated as percentage of total disk space used (e.g., 90 = trigger at 90% usage), values >= 100 are treated as absolute MB of required free space"
     )]
     pub disk_circuit_breaker_threshold: usize,
+    #[env_config(
+        name = "ZO_INGEST_BACKPRESSURE_ENABLED",
+        default = false,
+        help = "Reject ingestion with 503 and Retry-After (gRPC RESOURCE_EXHAUSTED) when the WAL, memtable or parquet conversion backlog crosses its threshold"
+    )]
+    pub ingest_backpressure_enabled: bool,
+    #[env_config(
+        name = "ZO_INGEST_BACKPRESSURE_WAL_MAX_SIZE",
+        default = 0,
+        help = "Maximum WAL disk usage in MB before ingestion is rejected, 0 disables the check"
+    )]
+    pub ingest_backpressure_wal_max_size: usize,
+    #[env_config(
+        name = "ZO_INGEST_BACKPRESSURE_MEMTABLE_RATIO",
+        default = 90,
+        help = "Percentage of ZO_MEM_TABLE_MAX_SIZE at which ingestion is rejected, 0 disables the check"
+    )]
+    pub ingest_backpressure_memtable_ratio: usize,
+    #[env_config(
+        name = "ZO_INGEST_BACKPRESSURE_PENDING_MEMTABLES",
+        default = 0,
+        help = "Maximum number of memtables waiting to be converted to parquet before ingestion is rejected, 0 disables the check"
+    )]
+    pub ingest_backpressure_pending_memtables: usize,
+    #[env_config(
+        name = "ZO_INGEST_BACKPRESSURE_LOW_PRIORITY_RATIO",
+        default = 80,
+        help = "Percentage of the backpressure thresholds at which low priority streams start being throttled, they are fully rejected at 100%"
+    )]
+    pub ingest_backpressure_low_priority_ratio: usize,
+    #[env_config(
+        name = "ZO_INGEST_BACKPRESSURE_RETRY_AFTER",
+        default = 5,
+        help = "Seconds sent in Retry-After when ingestion is rejected"
+    )]
+    pub ingest_backpressure_retry_after: u64,
     #[env_config(
         name = "ZO_RESTRICTED_ROUTES_ON_EMPTY_DATA",
         default = false,
@@ -1071,6 +1314,12 @@ pub struct Common {
         help = "Toggle inverted index generation."
     )]
     pub inverted_index_enabled: bool,
+    #[env_config(
+        name = "ZO_INVERTED_INDEX_OFFLOAD_ENABLED",
+        default = false,
+        help = "Build the inverted index of new files on the indexer nodes instead of the ingesters. The ingesters build it themselves while no indexer node is online."
+    )]
+    pub inverted_index_offload_enabled: bool,
     #[env_config(
         name = "ZO_INVERTED_INDEX_RESULT_CACHE_ENABLED",
         default = false,
@@ -1155,6 +1404,12 @@ pub struct Common {
         help = "Enable to use mulple result caches for query results"
     )]
     pub use_multi_result_cache: bool,
+    #[env_config(
+        name = "ZO_RESULT_CACHE_APPEND_ENABLED",
+        default = true,
+        help = "Replace the cached results a refreshed query extends with the new results, instead of keeping both"
+    )]
+    pub result_cache_append_enabled: bool,
     #[env_config(
         name = "ZO_RESULT_CACHE_SELECTION_STRATEGY",
         default = "overlap",
@@ -1251,6 +1506,12 @@ pub struct Common {
         help = "enable ingestion error logs reporting"
     )]
     pub ingestion_log_enabled: bool,
+    #[env_config(
+        name = "ZO_ALERT_EGRESS_ALLOWLIST",
+        default = "",
+        help = "Comma separated CIDRs and hostnames the alert destinations may send to, e.g. 10.0.0.0/8,hooks.example.com,*.example.org. Empty allows all"
+    )]
+    pub alert_egress_allowlist: String,
 }
 
 #[derive(Serialize, EnvConfig, Default)]
@@ -1263,6 +1524,12 @@ pub struct Limit {
     pub disk_free: usize,
     #[env_config(name = "ZO_PAYLOAD_LIMIT", default = 209715200)]
     pub req_payload_limit: usize,
+    #[env_config(
+        name = "ZO_OTLP_PAYLOAD_LIMIT",
+        default = 1073741824,
+        help = "Max decompressed body size in bytes accepted by the OTLP/HTTP endpoints"
+    )]
+    pub otlp_payload_limit: usize,
     #[env_config(name = "ZO_MAX_FILE_RETENTION_TIME", default = 600)] // seconds
     pub max_file_retention_time: u64,
     // MB, per log file size limit on disk
@@ -1302,6 +1569,24 @@ pub struct Limit {
         help = "MemTable bucket num, default is 1"
     )] // default is 1
     pub mem_table_bucket_num: usize,
+    #[env_config(
+        name = "ZO_MEM_TABLE_SPILL_ENABLED",
+        default = false,
+        help = "Bounded memory mode, memtables spill to compressed arrow files on disk when they go over ZO_MEM_TABLE_SPILL_RATIO of ZO_MEM_TABLE_MAX_SIZE instead of growing"
+    )]
+    pub mem_table_spill_enabled: bool,
+    #[env_config(
+        name = "ZO_MEM_TABLE_SPILL_RATIO",
+        default = 70,
+        help = "Percentage of ZO_MEM_TABLE_MAX_SIZE at which the memtables start spilling to disk"
+    )]
+    pub mem_table_spill_ratio: usize,
+    #[env_config(
+        name = "ZO_MEM_TABLE_SPILL_INTERVAL",
+        default = 1,
+        help = "Seconds between two checks of the memtable size for spilling"
+    )]
+    pub mem_table_spill_interval: u64,
     #[env_config(name = "ZO_MEM_PERSIST_INTERVAL", default = 2)] // seconds
     pub mem_persist_interval: u64,
     #[env_config(name = "ZO_WAL_WRITE_BUFFER_SIZE", default = 16384)] // 16 KB
@@ -1321,6 +1606,12 @@ pub struct Limit {
     pub file_merge_thread_num: usize,
     #[env_config(name = "ZO_MEM_DUMP_THREAD_NUM", default = 0)]
     pub mem_dump_thread_num: usize,
+    #[env_config(
+        name = "ZO_INDEXER_THREAD_NUM",
+        default = 0,
+        help = "Number of inverted indexes built at the same time on an indexer node, the number of CPU cores by default"
+    )]
+    pub indexer_thread_num: usize,
     #[env_config(name = "ZO_USAGE_REPORTING_THREAD_NUM", default = 0)]
     pub usage_reporting_thread_num: usize,
     #[env_config(name = "ZO_QUERY_THREAD_NUM", default = 0)]
@@ -1366,6 +1657,36 @@ pub struct Limit {
     pub query_recommendation_analysis_interval: i64,
     #[env_config(name = "ZO_QUERY_RECOMMENDATION_TOP_K", default = 128)]
     pub query_recommendation_top_k: usize,
+    #[env_config(
+        name = "ZO_PATTERN_MINING_MAX_LOGS",
+        default = 10000,
+        help = "Maximum number of log messages sampled by the pattern mining API"
+    )]
+    pub pattern_mining_max_logs: usize,
+    #[env_config(
+        name = "ZO_PATTERN_MINING_PARTITIONS",
+        default = 4,
+        help = "Number of time partitions the pattern mining API searches concurrently"
+    )]
+    pub pattern_mining_partitions: usize,
+    #[env_config(
+        name = "ZO_SCHEDULED_EXPORT_MAX_ROWS",
+        default = 100000,
+        help = "Maximum number of rows a scheduled export writes per run"
+    )]
+    pub scheduled_export_max_rows: usize,
+    #[env_config(
+        name = "ZO_ALERT_BACKTEST_MAX_WINDOWS",
+        default = 10000,
+        help = "Maximum number of alert evaluations an alert backtest replays"
+    )]
+    pub alert_backtest_max_windows: usize,
+    #[env_config(
+        name = "ZO_DASHBOARD_VARIABLES_CACHE_TTL",
+        default = 60,
+        help = "Seconds the values of the dashboard variables are cached, 0 disables the cache"
+    )]
+    pub dashboard_variables_cache_ttl: i64,
     #[env_config(name = "ZO_INGEST_ALLOWED_UPTO", default = 5)] // in hours - in past
     pub ingest_allowed_upto: i64,
     pub ingest_allowed_upto_micro: i64,
@@ -1374,6 +1695,72 @@ pub struct Limit {
     pub ingest_allowed_in_future_micro: i64,
     #[env_config(name = "ZO_INGEST_FLATTEN_LEVEL", default = 3)] // default flatten level
     pub ingest_flatten_level: u32,
+    #[env_config(
+        name = "ZO_INGEST_QUOTA_ENABLED",
+        default = false,
+        help = "Reject ingestion with 429 and Retry-After when an org or stream goes over its records or bytes per second quota"
+    )]
+    pub ingest_quota_enabled: bool,
+    #[env_config(
+        name = "ZO_INGEST_QUOTA_ORG_RECORDS",
+        default = 0,
+        help = "Records per second an org can ingest across the cluster, 0 is unlimited. Can be set per org in the org settings"
+    )]
+    pub ingest_quota_org_records: u64,
+    #[env_config(
+        name = "ZO_INGEST_QUOTA_ORG_BYTES",
+        default = 0,
+        help = "Bytes per second an org can ingest across the cluster, 0 is unlimited. Can be set per org in the org settings"
+    )]
+    pub ingest_quota_org_bytes: u64,
+    #[env_config(
+        name = "ZO_INGEST_QUOTA_STREAM_RECORDS",
+        default = 0,
+        help = "Records per second a stream can ingest across the cluster, 0 is unlimited. Can be set per stream in the stream settings"
+    )]
+    pub ingest_quota_stream_records: u64,
+    #[env_config(
+        name = "ZO_INGEST_QUOTA_STREAM_BYTES",
+        default = 0,
+        help = "Bytes per second a stream can ingest across the cluster, 0 is unlimited. Can be set per stream in the stream settings"
+    )]
+    pub ingest_quota_stream_bytes: u64,
+    #[env_config(
+        name = "ZO_INGEST_QUOTA_SYNC_INTERVAL",
+        default = 2,
+        help = "Seconds between two exchanges of the ingestion rates of the ingesters through the cluster coordinator"
+    )]
+    pub ingest_quota_sync_interval: u64,
+    #[env_config(
+        name = "ZO_SEARCH_QUOTA_ENABLED",
+        default = false,
+        help = "Reject searches with 429 when an org runs too many searches at once or scans too much per minute"
+    )]
+    pub search_quota_enabled: bool,
+    #[env_config(
+        name = "ZO_SEARCH_QUOTA_ORG_CONCURRENT",
+        default = 0,
+        help = "Searches an org can run at once across the cluster, 0 is unlimited. Can be set per org in the org settings"
+    )]
+    pub search_quota_org_concurrent: u64,
+    #[env_config(
+        name = "ZO_SEARCH_QUOTA_ORG_SCAN_MB",
+        default = 0,
+        help = "MB an org can scan per minute across the cluster, 0 is unlimited. Can be set per org in the org settings"
+    )]
+    pub search_quota_org_scan_mb: u64,
+    #[env_config(
+        name = "ZO_SEARCH_QUOTA_SYNC_INTERVAL",
+        default = 2,
+        help = "Seconds between two exchanges of the search consumption of the queriers through the cluster coordinator"
+    )]
+    pub search_quota_sync_interval: u64,
+    #[env_config(
+        name = "ZO_INGEST_DEDUP_MAX_ENTRIES",
+        default = 1000000,
+        help = "Record hashes kept by an ingester for the deduplication of the streams with an ingest_dedup window, the least recently seen are evicted first"
+    )]
+    pub ingest_dedup_max_entries: usize,
     #[env_config(name = "ZO_LOGS_FILE_RETENTION", default = "hourly")]
     pub logs_file_retention: String,
     #[env_config(name = "ZO_TRACES_FILE_RETENTION", default = "hourly")]
@@ -1394,6 +1781,12 @@ pub struct Limit {
     pub metrics_cache_max_entries: usize,
     #[env_config(name = "ZO_METRICS_INLIST_FILTER_ENABLED", default = false)]
     pub metrics_inlist_filter_enabled: bool,
+    #[env_config(
+        name = "ZO_METRICS_QUERY_SHARD_HOURS",
+        default = 24,
+        help = "Max time range in hours of a shard of a promql range query, the shards are executed in parallel across the queriers. 0 splits the range only by querier"
+    )]
+    pub metrics_query_shard_hours: i64,
     #[env_config(name = "ZO_COLS_PER_RECORD_LIMIT", default = 1000)]
     pub req_cols_per_record_limit: usize,
     #[env_config(name = "ZO_NODE_HEARTBEAT_TTL", default = 30)] // seconds
@@ -1468,6 +1861,16 @@ pub struct Limit {
         help = "Retention for search job"
     )]
     pub search_job_retention: i64,
+    #[env_config(name = "ZO_ASYNC_SEARCH_WORKERS", default = 1)]
+    pub async_search_workers: i64,
+    #[env_config(name = "ZO_ASYNC_SEARCH_INTERVAL", default = 5)] // seconds
+    pub async_search_interval: i64,
+    #[env_config(
+        name = "ZO_ASYNC_SEARCH_RESULT_TTL",
+        default = 24, // hours
+        help = "Hours the results of an async search are kept once it has ended"
+    )]
+    pub async_search_result_ttl: i64,
     #[env_config(name = "ZO_STARTING_EXPECT_QUERIER_NUM", default = 0)]
     pub starting_expect_querier_num: usize,
     #[env_config(name = "ZO_QUERY_OPTIMIZATION_NUM_FIELDS", default = 1000)]
@@ -1590,6 +1993,12 @@ pub struct Limit {
         help = "unit: Hour. Optional env variable to add restriction for SA, if not set SA will use max_query_range stream setting. When set which ever is smaller value will apply to api calls"
     )]
     pub max_query_range_for_sa: i64,
+    #[env_config(
+        name = "ZO_MAX_QUERY_RANGE_BYPASS_ENABLED",
+        default = false,
+        help = "Allow the root user and organization admins to search past the max query range of streams with the bypass_max_query_range parameter, every bypass is audited"
+    )]
+    pub max_query_range_bypass_enabled: bool,
     #[env_config(
         name = "ZO_MAX_DASHBOARD_SERIES",
         default = 100,
@@ -1639,6 +2048,12 @@ pub struct Compact {
     pub old_data_streams: String,
     #[env_config(name = "ZO_COMPACT_DATA_RETENTION_DAYS", default = 3650)] // days
     pub data_retention_days: i64,
+    #[env_config(
+        name = "ZO_ORG_ARCHIVE_GRACE_DAYS",
+        default = 30,
+        help = "Days the data of an archived organization is kept read-only before it is purged"
+    )] // days
+    pub org_archive_grace_days: i64,
     #[env_config(name = "ZO_COMPACT_OLD_DATA_MAX_DAYS", default = 7)] // days
     pub old_data_max_days: i64,
     #[env_config(name = "ZO_COMPACT_OLD_DATA_MIN_HOURS", default = 2)] // hours
@@ -1695,6 +2110,58 @@ pub struct Compact {
         help = "Comma-separated list of hours (0-23) when retention can run. Empty means run at all hours. Example: 5,6,8"
     )]
     pub retention_allowed_hours: String,
+    #[env_config(
+        name = "ZO_COMPACT_RETENTION_ARCHIVE_ACCOUNT",
+        default = "",
+        help = "Storage account the expired files of the streams with archiving enabled are copied to before being deleted, empty means the account of the file"
+    )]
+    pub retention_archive_account: String,
+    #[env_config(
+        name = "ZO_COMPACT_RETENTION_ARCHIVE_PREFIX",
+        default = "archive",
+        help = "Prefix of the archived copies of the expired files, it can only be empty when ZO_COMPACT_RETENTION_ARCHIVE_ACCOUNT is set"
+    )]
+    pub retention_archive_prefix: String,
+    #[env_config(
+        name = "ZO_COMPACT_METRICS_ROLLUP_RULES",
+        default = "",
+        help = "Comma separated rollup levels of the metrics streams as step:function:retention, e.g. 5m:avg:30d,1h:avg:365d. Function is one of avg, sum, count, min, max, it applies to the gauges"
+    )]
+    pub metrics_rollup_rules: String,
+    #[env_config(name = "ZO_COMPACT_METRICS_ROLLUP_INTERVAL", default = 300)] // seconds
+    pub metrics_rollup_interval: u64,
+    #[env_config(
+        name = "ZO_COMPACT_METRICS_ROLLUP_DELAY",
+        default = 600,
+        help = "Seconds to wait before a step is rolled up, for the late samples"
+    )]
+    pub metrics_rollup_delay: u64,
+    #[env_config(
+        name = "ZO_COMPACT_COLD_STORAGE_ACCOUNT",
+        default = "",
+        help = "Storage account of ZO_S3_ACCOUNTS, not the first one, the old files are moved to, empty disables the cold storage tier"
+    )]
+    pub cold_storage_account: String,
+    #[env_config(
+        name = "ZO_COMPACT_COLD_STORAGE_AFTER_DAYS",
+        default = 0,
+        help = "Days after which the files are moved to the cold storage account, 0 disables the cold storage tier"
+    )]
+    pub cold_storage_after_days: i64,
+    #[env_config(name = "ZO_COMPACT_COLD_STORAGE_INTERVAL", default = 3600)] // seconds
+    pub cold_storage_interval: u64,
+    #[env_config(
+        name = "ZO_COMPACT_COLD_STORAGE_QUERY_MODE",
+        default = "fetch",
+        help = "How the searches read the files in the cold storage: fetch reads them like the other files, restore fails the search until the time range is restored"
+    )]
+    pub cold_storage_query_mode: String,
+    #[env_config(
+        name = "ZO_COMPACT_COLD_STORAGE_RESTORE_DAYS",
+        default = 7,
+        help = "Days the restored files stay in the storage of the stream before being moved back to the cold storage"
+    )]
+    pub cold_storage_restore_days: i64,
 }
 
 #[derive(Serialize, EnvConfig, Default)]
@@ -2098,6 +2565,12 @@ pub struct Pipeline {
         help = "pipeline max file size on disk in MB"
     )]
     pub pipeline_max_file_size_on_disk_mb: usize,
+    #[env_config(
+        name = "ZO_PIPELINE_ERROR_STREAM",
+        default = "",
+        help = "Logs stream the records failing in a pipeline node are written to, with the error, in the organization of the pipeline. Disabled when empty"
+    )]
+    pub error_stream: String,
     #[env_config(
         name = "ZO_PIPELINE_MAX_FILE_RETENTION_TIME_SECONDS",
         default = 600,
@@ -2224,26 +2697,556 @@ pub struct EnrichmentTable {
         help = "Number of stale jobs each ingester attempts to claim per recovery check. Higher values allow faster recovery but may cause uneven distribution."
     )]
     pub url_recovery_jobs_per_check: usize,
+    #[env_config(
+        name = "ZO_ENRICHMENT_TABLE_QUERY_CACHE_ENABLED",
+        default = true,
+        help = "Keep the enrichment tables joined by queries in memory on the queriers, they are reloaded when a table is updated"
+    )]
+    pub query_cache_enabled: bool,
+    #[env_config(
+        name = "ZO_ENRICHMENT_TABLE_QUERY_CACHE_MAX_SIZE",
+        default = 256,
+        help = "Maximum size of an enrichment table kept in the query cache, larger tables are read from the database by each query (in MB)"
+    )]
+    pub query_cache_max_size_mb: usize,
+    #[env_config(
+        name = "ZO_ENRICHMENT_TABLE_BROADCAST_MAX_SIZE",
+        default = 10,
+        help = "Enrichment tables up to this size are read once and shared by all the partitions of a join instead of being repartitioned (in MB)"
+    )]
+    pub broadcast_max_size_mb: usize,
 }
 
-pub fn init() -> Config {
-    if let Err(e) = load_config() {
-        log::error!("Failed to load config {e}");
-        // do nothing
-    }
-    let mut cfg = Config::init().expect("config init error");
-
-    // set local mode
-    if cfg.common.local_mode {
-        cfg.common.node_role = "all".to_string();
-        cfg.common.node_role_group = "".to_string();
-    }
-    cfg.common.is_local_storage = cfg.common.local_mode
-        && (cfg.common.local_mode_storage == "disk" || cfg.common.local_mode_storage == "local");
+#[derive(Serialize, EnvConfig, Default)]
+pub struct FlowCollector {
+    #[env_config(
+        name = "ZO_FLOW_COLLECTOR_ENABLED",
+        default = false,
+        help = "Enable the NetFlow/IPFIX and sFlow UDP collector on ingester nodes"
+    )]
+    pub enabled: bool,
+    #[env_config(name = "ZO_FLOW_COLLECTOR_ADDR", default = "0.0.0.0")]
+    pub addr: String,
+    #[env_config(
+        name = "ZO_FLOW_COLLECTOR_NETFLOW_PORT",
+        default = 2055,
+        help = "UDP port for NetFlow v5/v9 and IPFIX, set to 0 to disable"
+    )]
+    pub netflow_port: u16,
+    #[env_config(
+        name = "ZO_FLOW_COLLECTOR_SFLOW_PORT",
+        default = 6343,
+        help = "UDP port for sFlow v5, set to 0 to disable"
+    )]
+    pub sflow_port: u16,
+    #[env_config(name = "ZO_FLOW_COLLECTOR_ORG", default = "default")]
+    pub org_id: String,
+    #[env_config(name = "ZO_FLOW_COLLECTOR_STREAM", default = "network_flows")]
+    pub stream_name: String,
+    #[env_config(
+        name = "ZO_FLOW_COLLECTOR_BATCH_SIZE",
+        default = 1000,
+        help = "Maximum number of flow records buffered before they are ingested"
+    )]
+    pub batch_size: usize,
+    #[env_config(
+        name = "ZO_FLOW_COLLECTOR_FLUSH_INTERVAL_MS",
+        default = 1000,
+        help = "Maximum time flow records are buffered before they are ingested (in milliseconds)"
+    )]
+    pub flush_interval_ms: u64,
+    #[env_config(
+        name = "ZO_FLOW_COLLECTOR_TEMPLATE_TTL",
+        default = 1800,
+        help = "NetFlow v9/IPFIX templates not refreshed by the exporter within this time are evicted (in seconds)"
+    )]
+    pub template_ttl: u64,
+}
 
-    // check limit config
-    if let Err(e) = check_limit_config(&mut cfg) {
-        panic!("limit config error: {e}");
+#[derive(Serialize, EnvConfig, Default)]
+pub struct K8sEvents {
+    #[env_config(
+        name = "ZO_K8S_EVENTS_ENABLED",
+        default = false,
+        help = "Watch Kubernetes events from inside the cluster and ingest them, one ingester holds the watch at a time"
+    )]
+    pub enabled: bool,
+    #[env_config(
+        name = "ZO_K8S_EVENTS_API_SERVER",
+        default = "",
+        help = "Kubernetes API server url, defaults to the in-cluster service address"
+    )]
+    pub api_server: String,
+    #[env_config(
+        name = "ZO_K8S_EVENTS_NAMESPACES",
+        default = "",
+        help = "Comma separated namespaces to watch, empty means all namespaces"
+    )]
+    pub namespaces: String,
+    #[env_config(name = "ZO_K8S_EVENTS_ORG", default = "default")]
+    pub org_id: String,
+    #[env_config(name = "ZO_K8S_EVENTS_STREAM", default = "k8s_events")]
+    pub stream_name: String,
+    #[env_config(
+        name = "ZO_K8S_EVENTS_POD_LOGS_ENABLED",
+        default = false,
+        help = "Also follow container logs of running pods through the Kubernetes API"
+    )]
+    pub pod_logs_enabled: bool,
+    #[env_config(name = "ZO_K8S_EVENTS_POD_LOGS_STREAM", default = "k8s_pod_logs")]
+    pub pod_logs_stream_name: String,
+    #[env_config(
+        name = "ZO_K8S_EVENTS_BATCH_SIZE",
+        default = 500,
+        help = "Maximum number of records buffered before they are ingested"
+    )]
+    pub batch_size: usize,
+    #[env_config(
+        name = "ZO_K8S_EVENTS_FLUSH_INTERVAL_MS",
+        default = 2000,
+        help = "Maximum time records are buffered before they are ingested (in milliseconds)"
+    )]
+    pub flush_interval_ms: u64,
+}
+
+#[derive(Serialize, EnvConfig, Default)]
+pub struct DockerLogs {
+    #[env_config(
+        name = "ZO_DOCKER_LOGS_STREAM",
+        default = "docker",
+        help = "Default stream for the docker splunk and gelf log driver endpoints"
+    )]
+    pub stream_name: String,
+    #[env_config(
+        name = "ZO_DOCKER_GELF_ENABLED",
+        default = false,
+        help = "Enable the GELF listener for the docker gelf log driver on ingester nodes"
+    )]
+    pub gelf_enabled: bool,
+    #[env_config(name = "ZO_DOCKER_GELF_ADDR", default = "0.0.0.0")]
+    pub gelf_addr: String,
+    #[env_config(
+        name = "ZO_DOCKER_GELF_UDP_PORT",
+        default = 12201,
+        help = "UDP port for GELF messages, set to 0 to disable"
+    )]
+    pub gelf_udp_port: u16,
+    #[env_config(
+        name = "ZO_DOCKER_GELF_TCP_PORT",
+        default = 12201,
+        help = "TCP port for GELF messages, set to 0 to disable"
+    )]
+    pub gelf_tcp_port: u16,
+    #[env_config(
+        name = "ZO_DOCKER_GELF_ORG",
+        default = "default",
+        help = "Organization GELF messages are ingested into, GELF has no authentication"
+    )]
+    pub gelf_org_id: String,
+}
+
+#[derive(Serialize, EnvConfig, Default)]
+pub struct Gelf {
+    #[env_config(
+        name = "ZO_GELF_ENABLED",
+        default = false,
+        help = "Enable the GELF listener for Graylog shippers on ingester nodes"
+    )]
+    pub enabled: bool,
+    #[env_config(name = "ZO_GELF_ADDR", default = "0.0.0.0")]
+    pub addr: String,
+    #[env_config(
+        name = "ZO_GELF_UDP_PORT",
+        default = 12202,
+        help = "UDP port for GELF messages, set to 0 to disable"
+    )]
+    pub udp_port: u16,
+    #[env_config(
+        name = "ZO_GELF_TCP_PORT",
+        default = 12202,
+        help = "TCP port for GELF messages, set to 0 to disable"
+    )]
+    pub tcp_port: u16,
+    #[env_config(
+        name = "ZO_GELF_ORG",
+        default = "default",
+        help = "Organization GELF messages are ingested into, GELF has no authentication"
+    )]
+    pub org_id: String,
+    #[env_config(
+        name = "ZO_GELF_STREAM",
+        default = "gelf",
+        help = "Stream GELF messages received by the listener are ingested into"
+    )]
+    pub stream_name: String,
+}
+
+#[derive(Serialize, EnvConfig, Default)]
+pub struct Pgwire {
+    #[env_config(
+        name = "ZO_PGWIRE_ENABLED",
+        default = false,
+        help = "Serve read only SQL over the PostgreSQL wire protocol on querier nodes, the database is the organization and the tables are its streams"
+    )]
+    pub enabled: bool,
+    #[env_config(name = "ZO_PGWIRE_ADDR", default = "0.0.0.0")]
+    pub addr: String,
+    #[env_config(name = "ZO_PGWIRE_PORT", default = 5432)]
+    pub port: u16,
+    #[env_config(
+        name = "ZO_PGWIRE_QUERY_RANGE_HOURS",
+        default = 24,
+        help = "Time range searched by the queries up to now, the PostgreSQL clients can't send one"
+    )]
+    pub query_range_hours: i64,
+    #[env_config(
+        name = "ZO_PGWIRE_ALLOW_CLEARTEXT",
+        default = false,
+        help = "Accept the passwords over connections without TLS. The listener uses the certificate of ZO_HTTP_TLS_CERT_PATH, without HTTP TLS it only starts with this option"
+    )]
+    pub allow_cleartext: bool,
+    #[env_config(
+        name = "ZO_PGWIRE_MAX_CONNECTIONS",
+        default = 100,
+        help = "Most connections open at once, the next ones are refused"
+    )]
+    pub max_connections: usize,
+    #[env_config(
+        name = "ZO_PGWIRE_IDLE_TIMEOUT",
+        default = 600,
+        help = "Seconds a connection can wait for the next message of the client before being closed"
+    )]
+    pub idle_timeout: u64,
+}
+
+#[derive(Serialize, EnvConfig, Default)]
+pub struct AccessLogImport {
+    #[env_config(
+        name = "ZO_ACCESS_LOG_IMPORT_ENABLED",
+        default = false,
+        help = "Import CDN and load balancer access logs from S3 on one ingester node"
+    )]
+    pub enabled: bool,
+    #[env_config(
+        name = "ZO_ACCESS_LOG_IMPORT_SOURCES",
+        default = "",
+        help = "Comma separated list of format:s3://bucket/prefix, format is one of cloudfront, w3c, alb, elb, clf, json"
+    )]
+    pub sources: String,
+    #[env_config(
+        name = "ZO_ACCESS_LOG_IMPORT_REGION",
+        default = "",
+        help = "Region of the source buckets and the SQS queue"
+    )]
+    pub region: String,
+    #[env_config(
+        name = "ZO_ACCESS_LOG_IMPORT_SQS_QUEUE_URL",
+        default = "",
+        help = "SQS queue receiving S3 object created notifications, the prefixes are polled when empty"
+    )]
+    pub sqs_queue_url: String,
+    #[env_config(name = "ZO_ACCESS_LOG_IMPORT_ORG", default = "default")]
+    pub org_id: String,
+    #[env_config(name = "ZO_ACCESS_LOG_IMPORT_STREAM", default = "access_logs")]
+    pub stream_name: String,
+    #[env_config(
+        name = "ZO_ACCESS_LOG_IMPORT_INTERVAL",
+        default = 60,
+        help = "Seconds between listings of the source prefixes"
+    )]
+    pub interval_secs: u64,
+    #[env_config(
+        name = "ZO_ACCESS_LOG_IMPORT_BATCH_SIZE",
+        default = 5000,
+        help = "Maximum number of records sent in one ingestion request"
+    )]
+    pub batch_size: usize,
+}
+
+#[derive(Serialize, EnvConfig, Default)]
+pub struct KafkaIngestion {
+    #[env_config(
+        name = "ZO_KAFKA_INGESTION_ENABLED",
+        default = false,
+        help = "Consume Kafka topics into log streams on one ingester node"
+    )]
+    pub enabled: bool,
+    #[env_config(
+        name = "ZO_KAFKA_BROKERS",
+        default = "",
+        help = "Comma separated list of host:port bootstrap brokers"
+    )]
+    pub brokers: String,
+    #[env_config(
+        name = "ZO_KAFKA_TOPICS",
+        default = "",
+        help = "Comma separated list of topic:org/stream, records of the topic are ingested into the logs stream of the org"
+    )]
+    pub topics: String,
+    #[env_config(
+        name = "ZO_KAFKA_START_OFFSET",
+        default = "earliest",
+        help = "Where partitions without a checkpoint start consuming, earliest or latest"
+    )]
+    pub start_offset: String,
+    #[env_config(
+        name = "ZO_KAFKA_SASL_USERNAME",
+        default = "",
+        help = "SASL/PLAIN user name, SASL is disabled when empty"
+    )]
+    pub sasl_username: String,
+    #[env_config(name = "ZO_KAFKA_SASL_PASSWORD", default = "")]
+    pub sasl_password: String,
+    #[env_config(
+        name = "ZO_KAFKA_TLS_ENABLED",
+        default = false,
+        help = "Connect to the brokers over TLS, trusting the system root certificates"
+    )]
+    pub tls_enabled: bool,
+    #[env_config(
+        name = "ZO_KAFKA_FETCH_MAX_BYTES",
+        default = 1048576,
+        help = "Maximum bytes fetched from a partition at once"
+    )]
+    pub fetch_max_bytes: i32,
+    #[env_config(
+        name = "ZO_KAFKA_FETCH_MAX_WAIT_MS",
+        default = 500,
+        help = "Maximum time a fetch waits for new records (in milliseconds)"
+    )]
+    pub fetch_max_wait_ms: i32,
+    #[env_config(
+        name = "ZO_KAFKA_BATCH_SIZE",
+        default = 5000,
+        help = "Maximum number of records sent in one ingestion request"
+    )]
+    pub batch_size: usize,
+    #[env_config(
+        name = "ZO_KAFKA_METADATA_REFRESH_INTERVAL",
+        default = 60,
+        help = "Seconds between checks for new partitions of the topics"
+    )]
+    pub metadata_refresh_interval: u64,
+    #[env_config(
+        name = "ZO_KAFKA_MAX_RETRIES",
+        default = 10,
+        help = "Attempts to ingest fetched records before they are moved to the dead letter stream, or skipped"
+    )]
+    pub max_retries: u32,
+    #[env_config(
+        name = "ZO_KAFKA_DEAD_LETTER_STREAM",
+        default = "",
+        help = "Logs stream of the org of the subscription receiving the records which failed to ingest, they are skipped when empty"
+    )]
+    pub dead_letter_stream: String,
+}
+
+#[derive(Serialize, EnvConfig, Default)]
+pub struct KinesisIngestion {
+    #[env_config(
+        name = "ZO_KINESIS_INGESTION_ENABLED",
+        default = false,
+        help = "Consume Kinesis data streams into log streams, the shards are shared by the ingester nodes"
+    )]
+    pub enabled: bool,
+    #[env_config(
+        name = "ZO_KINESIS_STREAMS",
+        default = "",
+        help = "Comma separated list of kinesis_stream:org/stream, records of the Kinesis stream are ingested into the logs stream of the org"
+    )]
+    pub streams: String,
+    #[env_config(
+        name = "ZO_KINESIS_REGION",
+        default = "",
+        help = "AWS region of the Kinesis streams, the region of the environment when empty"
+    )]
+    pub region: String,
+    #[env_config(
+        name = "ZO_KINESIS_ENDPOINT",
+        default = "",
+        help = "Kinesis endpoint url, the endpoint of the region when empty"
+    )]
+    pub endpoint: String,
+    #[env_config(
+        name = "ZO_KINESIS_START_POSITION",
+        default = "trim_horizon",
+        help = "Where shards without a checkpoint start consuming, trim_horizon or latest"
+    )]
+    pub start_position: String,
+    #[env_config(
+        name = "ZO_KINESIS_MAX_RECORDS",
+        default = 1000,
+        help = "Maximum number of records read from a shard at once, at most 10000"
+    )]
+    pub max_records: i32,
+    #[env_config(
+        name = "ZO_KINESIS_POLL_INTERVAL_MS",
+        default = 1000,
+        help = "Time a shard waits before reading again once it is caught up (in milliseconds)"
+    )]
+    pub poll_interval_ms: u64,
+    #[env_config(
+        name = "ZO_KINESIS_LEASE_DURATION",
+        default = 30,
+        help = "Seconds a node holds the lease of a shard without renewing it before another node can take it over"
+    )]
+    pub lease_duration: i64,
+    #[env_config(
+        name = "ZO_KINESIS_SHARD_SYNC_INTERVAL",
+        default = 60,
+        help = "Seconds between checks for new shards of the streams, e.g. after resharding"
+    )]
+    pub shard_sync_interval: u64,
+}
+
+#[derive(Serialize, EnvConfig, Default)]
+pub struct MqttIngestion {
+    #[env_config(
+        name = "ZO_MQTT_INGESTION_ENABLED",
+        default = false,
+        help = "Subscribe to MQTT topics and ingest their messages into log streams on one ingester node"
+    )]
+    pub enabled: bool,
+    #[env_config(
+        name = "ZO_MQTT_BROKER",
+        default = "",
+        help = "host:port of the MQTT broker, the port defaults to 1883, or 8883 over TLS"
+    )]
+    pub broker: String,
+    #[env_config(
+        name = "ZO_MQTT_TOPICS",
+        default = "",
+        help = "Comma separated list of topic_filter:org/stream, e.g. devices/{device_id}/telemetry:iot/telemetry. A {name} level matches any level like +, and its value is ingested as the name field"
+    )]
+    pub topics: String,
+    #[env_config(
+        name = "ZO_MQTT_CLIENT_ID",
+        default = "openobserve",
+        help = "Client id of the persistent session, the node taking over the subscriptions resumes it"
+    )]
+    pub client_id: String,
+    #[env_config(name = "ZO_MQTT_USERNAME", default = "")]
+    pub username: String,
+    #[env_config(name = "ZO_MQTT_PASSWORD", default = "")]
+    pub password: String,
+    #[env_config(
+        name = "ZO_MQTT_TLS_ENABLED",
+        default = false,
+        help = "Connect to the broker over TLS, trusting the system root certificates"
+    )]
+    pub tls_enabled: bool,
+    #[env_config(
+        name = "ZO_MQTT_QOS",
+        default = 1,
+        help = "QoS of the subscriptions, 0 (at most once) or 1 (at least once, messages are acknowledged once ingested)"
+    )]
+    pub qos: u8,
+    #[env_config(
+        name = "ZO_MQTT_PAYLOAD_FORMAT",
+        default = "auto",
+        help = "Format of the payloads, json, cbor or auto. Payloads which aren't objects are ingested as the message field"
+    )]
+    pub payload_format: String,
+    #[env_config(
+        name = "ZO_MQTT_BATCH_SIZE",
+        default = 1000,
+        help = "Maximum number of messages sent in one ingestion request. QoS 1 messages are acknowledged once ingested, the limit of unacknowledged messages of the broker should allow a full batch"
+    )]
+    pub batch_size: usize,
+    #[env_config(
+        name = "ZO_MQTT_FLUSH_INTERVAL_MS",
+        default = 1000,
+        help = "Maximum time a message waits for its batch to fill (in milliseconds)"
+    )]
+    pub flush_interval_ms: u64,
+    #[env_config(
+        name = "ZO_MQTT_KEEP_ALIVE",
+        default = 30,
+        help = "Seconds between keep alive pings to the broker"
+    )]
+    pub keep_alive: u64,
+}
+
+#[derive(Serialize, EnvConfig, Default)]
+pub struct RedisStreamsIngestion {
+    #[env_config(
+        name = "ZO_REDIS_STREAMS_INGESTION_ENABLED",
+        default = false,
+        help = "Consume Redis Streams into log streams, every ingester node is a consumer of the consumer group"
+    )]
+    pub enabled: bool,
+    #[env_config(
+        name = "ZO_REDIS_STREAMS_URL",
+        default = "",
+        help = "Redis url, e.g. redis://redis:6379/0, or rediss:// over TLS"
+    )]
+    pub url: String,
+    #[env_config(name = "ZO_REDIS_STREAMS_USERNAME", default = "")]
+    pub username: String,
+    #[env_config(name = "ZO_REDIS_STREAMS_PASSWORD", default = "")]
+    pub password: String,
+    #[env_config(
+        name = "ZO_REDIS_STREAMS_STREAMS",
+        default = "",
+        help = "Comma separated list of redis_stream:org/stream, entries of the Redis stream are ingested into the logs stream of the org"
+    )]
+    pub streams: String,
+    #[env_config(
+        name = "ZO_REDIS_STREAMS_GROUP",
+        default = "openobserve",
+        help = "Consumer group of the ingester nodes, created on the Redis streams when missing"
+    )]
+    pub group: String,
+    #[env_config(
+        name = "ZO_REDIS_STREAMS_START_POSITION",
+        default = "earliest",
+        help = "Where the consumer group starts when it is created, earliest or latest"
+    )]
+    pub start_position: String,
+    #[env_config(
+        name = "ZO_REDIS_STREAMS_BATCH_SIZE",
+        default = 1000,
+        help = "Maximum number of entries read and sent in one ingestion request"
+    )]
+    pub batch_size: usize,
+    #[env_config(
+        name = "ZO_REDIS_STREAMS_BLOCK_MS",
+        default = 1000,
+        help = "Maximum time a read waits for new entries (in milliseconds)"
+    )]
+    pub block_ms: usize,
+    #[env_config(
+        name = "ZO_REDIS_STREAMS_CLAIM_IDLE_MS",
+        default = 60000,
+        help = "Time an entry stays pending for another consumer before it is claimed, e.g. when the ingester which read it stopped (in milliseconds)"
+    )]
+    pub claim_idle_ms: usize,
+    #[env_config(
+        name = "ZO_REDIS_STREAMS_CLAIM_INTERVAL",
+        default = 30,
+        help = "Seconds between the claims of the pending entries of the other consumers"
+    )]
+    pub claim_interval: u64,
+}
+
+pub fn init() -> Config {
+    if let Err(e) = load_config() {
+        log::error!("Failed to load config {e}");
+        // do nothing
+    }
+    let mut cfg = Config::init().expect("config init error");
+
+    // set local mode
+    if cfg.common.local_mode {
+        cfg.common.node_role = "all".to_string();
+        cfg.common.node_role_group = "".to_string();
+    }
+    cfg.common.is_local_storage = cfg.common.local_mode
+        && (cfg.common.local_mode_storage == "disk" || cfg.common.local_mode_storage == "local");
+
+    // check limit config
+    if let Err(e) = check_limit_config(&mut cfg) {
+        panic!("limit config error: {e}");
     }
 
     // check route config
@@ -2319,6 +3322,47 @@ pub fn init() -> Config {
         panic!("inverted index config error: {e}");
     }
 
+    // check docker logs config
+    if let Err(e) = check_docker_logs_config(&mut cfg) {
+        panic!("docker logs config error: {e}");
+    }
+
+    if let Err(e) = check_gelf_config(&mut cfg) {
+        panic!("gelf config error: {e}");
+    }
+
+    if let Err(e) = check_pgwire_config(&mut cfg) {
+        panic!("pgwire config error: {e}");
+    }
+
+    if let Err(e) = check_k8s_events_config(&mut cfg) {
+        panic!("k8s events config error: {e}");
+    }
+
+    if let Err(e) = check_flow_collector_config(&mut cfg) {
+        panic!("flow collector config error: {e}");
+    }
+
+    if let Err(e) = check_access_log_import_config(&mut cfg) {
+        panic!("access log import config error: {e}");
+    }
+
+    if let Err(e) = check_kafka_ingestion_config(&mut cfg) {
+        panic!("kafka ingestion config error: {e}");
+    }
+
+    if let Err(e) = check_kinesis_ingestion_config(&mut cfg) {
+        panic!("kinesis ingestion config error: {e}");
+    }
+
+    if let Err(e) = check_mqtt_ingestion_config(&mut cfg) {
+        panic!("mqtt ingestion config error: {e}");
+    }
+
+    if let Err(e) = check_redis_streams_ingestion_config(&mut cfg) {
+        panic!("redis streams ingestion config error: {e}");
+    }
+
     cfg
 }
 
@@ -2386,6 +3430,10 @@ fn check_limit_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
             cfg.limit.file_merge_thread_num = cpu_num;
         }
     }
+    // HACK for indexer_thread_num equal to CPU core
+    if cfg.limit.indexer_thread_num == 0 {
+        cfg.limit.indexer_thread_num = cpu_num;
+    }
     // HACK for mem_dump_thread_num equal to CPU core
     if cfg.limit.mem_dump_thread_num == 0 {
         cfg.limit.mem_dump_thread_num = cpu_num;
@@ -2473,6 +3521,30 @@ fn check_common_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
     if cfg.limit.req_cols_per_record_limit == 0 {
         cfg.limit.req_cols_per_record_limit = 1000;
     }
+    if cfg.common.wal_sync_policy.is_empty() {
+        cfg.common.wal_sync_policy = if cfg.common.wal_fsync_disabled {
+            WalSyncPolicy::Async
+        } else {
+            WalSyncPolicy::Batch
+        }
+        .as_str()
+        .to_string();
+    }
+    for policy in [
+        &mut cfg.common.wal_sync_policy,
+        &mut cfg.common.wal_sync_policy_high_priority,
+    ] {
+        if !policy.is_empty() {
+            *policy = policy.parse::<WalSyncPolicy>()?.as_str().to_string();
+        }
+    }
+    if cfg.common.wal_sync_interval == 0 {
+        cfg.common.wal_sync_interval = 1000;
+    }
+    // pages are cut between write batches, a smaller limit has no effect
+    if cfg.common.parquet_page_row_limit < PARQUET_BATCH_SIZE {
+        cfg.common.parquet_page_row_limit = PARQUET_BATCH_SIZE;
+    }
 
     // check max_file_size_on_disk to MB
     if cfg.limit.max_file_size_on_disk == 0 {
@@ -2486,18 +3558,55 @@ fn check_common_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
     } else {
         cfg.limit.max_file_size_in_memory *= 1024 * 1024;
     }
-
-    // check for metrics limit
-    if cfg.limit.metrics_max_points_per_series == 0 {
-        cfg.limit.metrics_max_points_per_series = 30_000;
+
+    // check for metrics limit
+    if cfg.limit.metrics_max_points_per_series == 0 {
+        cfg.limit.metrics_max_points_per_series = 30_000;
+    }
+    if cfg.limit.metrics_cache_max_entries == 0 {
+        cfg.limit.metrics_cache_max_entries = 10_000;
+    }
+
+    // check search job retention
+    if cfg.limit.search_job_retention == 0 {
+        return Err(anyhow::anyhow!("search job retention is set to zero"));
+    }
+    if cfg.limit.async_search_interval <= 0 {
+        cfg.limit.async_search_interval = 5;
+    }
+    if cfg.limit.async_search_result_ttl <= 0 {
+        cfg.limit.async_search_result_ttl = 24;
+    }
+
+    // check search shadowing
+    if cfg.common.search_shadow_ratio > 100 {
+        return Err(anyhow::anyhow!(
+            "ZO_SEARCH_SHADOW_RATIO must be between 0 and 100"
+        ));
+    }
+    if cfg.common.search_shadow_latency_ratio == 0 {
+        cfg.common.search_shadow_latency_ratio = 200;
+    }
+
+    // check ingestion backpressure
+    if cfg.common.ingest_backpressure_memtable_ratio > 100 {
+        return Err(anyhow::anyhow!(
+            "ZO_INGEST_BACKPRESSURE_MEMTABLE_RATIO must be between 0 and 100"
+        ));
+    }
+    if cfg.common.ingest_backpressure_low_priority_ratio > 100 {
+        return Err(anyhow::anyhow!(
+            "ZO_INGEST_BACKPRESSURE_LOW_PRIORITY_RATIO must be between 0 and 100"
+        ));
+    }
+    if cfg.common.ingest_backpressure_retry_after == 0 {
+        cfg.common.ingest_backpressure_retry_after = 5;
     }
-    if cfg.limit.metrics_cache_max_entries == 0 {
-        cfg.limit.metrics_cache_max_entries = 10_000;
+    if cfg.limit.ingest_quota_sync_interval == 0 {
+        cfg.limit.ingest_quota_sync_interval = 2;
     }
-
-    // check search job retention
-    if cfg.limit.search_job_retention == 0 {
-        return Err(anyhow::anyhow!("search job retention is set to zero"));
+    if cfg.limit.search_quota_sync_interval == 0 {
+        cfg.limit.search_quota_sync_interval = 2;
     }
 
     if cfg.common.tracing_search_enabled
@@ -2624,6 +3733,13 @@ fn check_common_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
         cfg.common.usage_publish_interval = 60;
     }
 
+    if cfg.common.field_usage_flush_interval == 0 {
+        cfg.common.field_usage_flush_interval = 300;
+    }
+    if cfg.common.schema_suggestion_interval == 0 {
+        cfg.common.schema_suggestion_interval = 86400;
+    }
+
     cfg.common.log_page_default_field_list = cfg.common.log_page_default_field_list.to_lowercase();
     if !matches!(
         cfg.common.log_page_default_field_list.as_str(),
@@ -2645,6 +3761,20 @@ fn check_grpc_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
             "ZO_GRPC_TLS_CERT_DOMAIN, ZO_GRPC_TLS_CERT_PATH and ZO_GRPC_TLS_KEY_PATH must be set when ZO_GRPC_TLS_ENABLED is true"
         ));
     }
+    if cfg.grpc.tls_client_auth && (!cfg.grpc.tls_enabled || cfg.grpc.tls_ca_cert_path.is_empty()) {
+        return Err(anyhow::anyhow!(
+            "ZO_GRPC_TLS_ENABLED and ZO_GRPC_TLS_CA_CERT_PATH must be set when ZO_GRPC_TLS_CLIENT_AUTH is true"
+        ));
+    }
+    cfg.grpc.flight_compression = cfg.grpc.flight_compression.trim().to_lowercase();
+    if !["zstd", "lz4", "none"].contains(&cfg.grpc.flight_compression.as_str()) {
+        return Err(anyhow::anyhow!(
+            "ZO_GRPC_FLIGHT_COMPRESSION must be one of zstd, lz4 or none"
+        ));
+    }
+    if cfg.grpc.flight_max_data_size == 0 {
+        cfg.grpc.flight_max_data_size = 32;
+    }
     Ok(())
 }
 
@@ -2778,6 +3908,14 @@ fn check_memory_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
     } else {
         cfg.limit.mem_table_max_size *= 1024 * 1024;
     }
+    if cfg.limit.mem_table_spill_ratio == 0 || cfg.limit.mem_table_spill_ratio > 100 {
+        return Err(anyhow::anyhow!(
+            "ZO_MEM_TABLE_SPILL_RATIO must be between 1 and 100"
+        ));
+    }
+    if cfg.limit.mem_table_spill_interval == 0 {
+        cfg.limit.mem_table_spill_interval = 1;
+    }
     if cfg.limit.mem_table_bucket_num == 0 {
         cfg.limit.mem_table_bucket_num = 1;
     }
@@ -2802,6 +3940,24 @@ fn check_memory_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
     if cfg.limit.query_default_limit == 0 {
         cfg.limit.query_default_limit = 1000;
     }
+    if cfg.limit.otlp_payload_limit == 0 {
+        cfg.limit.otlp_payload_limit = cfg.limit.req_payload_limit;
+    }
+    if cfg.grpc.otlp_max_message_size == 0 {
+        cfg.grpc.otlp_max_message_size = cfg.grpc.max_message_size;
+    }
+    if cfg.limit.pattern_mining_max_logs == 0 {
+        cfg.limit.pattern_mining_max_logs = 10000;
+    }
+    if cfg.limit.pattern_mining_partitions == 0 {
+        cfg.limit.pattern_mining_partitions = 4;
+    }
+    if cfg.limit.scheduled_export_max_rows == 0 {
+        cfg.limit.scheduled_export_max_rows = 100000;
+    }
+    if cfg.limit.alert_backtest_max_windows == 0 {
+        cfg.limit.alert_backtest_max_windows = 10000;
+    }
     Ok(())
 }
 
@@ -2937,6 +4093,9 @@ fn check_compact_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
     if cfg.compact.interval < 1 {
         cfg.compact.interval = 10;
     }
+    if cfg.compact.org_archive_grace_days < 1 {
+        cfg.compact.org_archive_grace_days = 30;
+    }
 
     // check compact_max_file_size to MB
     if cfg.compact.max_file_size < 1 {
@@ -2974,6 +4133,69 @@ fn check_compact_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
         cfg.compact.pending_jobs_metric_interval = 300;
     }
 
+    cfg.compact.retention_archive_account =
+        cfg.compact.retention_archive_account.trim().to_string();
+    cfg.compact.retention_archive_prefix = cfg
+        .compact
+        .retention_archive_prefix
+        .trim_matches('/')
+        .to_string();
+    // the archived copies would have the keys of the files they are made of
+    if cfg.compact.retention_archive_account.is_empty()
+        && cfg.compact.retention_archive_prefix.is_empty()
+    {
+        return Err(anyhow::anyhow!(
+            "ZO_COMPACT_RETENTION_ARCHIVE_PREFIX can't be empty when ZO_COMPACT_RETENTION_ARCHIVE_ACCOUNT is empty"
+        ));
+    }
+
+    crate::meta::promql::parse_rollup_rules(&cfg.compact.metrics_rollup_rules)?;
+    if cfg.compact.metrics_rollup_interval == 0 {
+        cfg.compact.metrics_rollup_interval = 300;
+    }
+
+    cfg.compact.cold_storage_account = cfg.compact.cold_storage_account.trim().to_string();
+    if !cfg.compact.cold_storage_account.is_empty() {
+        let accounts = cfg
+            .s3
+            .accounts
+            .split(',')
+            .map(|s| s.trim())
+            .collect::<Vec<_>>();
+        if accounts.first() == Some(&cfg.compact.cold_storage_account.as_str())
+            || !accounts.contains(&cfg.compact.cold_storage_account.as_str())
+        {
+            return Err(anyhow::anyhow!(
+                "ZO_COMPACT_COLD_STORAGE_ACCOUNT must be one of ZO_S3_ACCOUNTS other than the first one"
+            ));
+        }
+    }
+    if cfg.compact.cold_storage_after_days < 0 {
+        cfg.compact.cold_storage_after_days = 0;
+    }
+    if cfg.compact.cold_storage_after_days > 0
+        && cfg.compact.cold_storage_after_days <= cfg.compact.old_data_max_days
+    {
+        return Err(anyhow::anyhow!(
+            "ZO_COMPACT_COLD_STORAGE_AFTER_DAYS must be greater than ZO_COMPACT_OLD_DATA_MAX_DAYS, the merged old files would stay in the hot storage"
+        ));
+    }
+    if cfg.compact.cold_storage_interval == 0 {
+        cfg.compact.cold_storage_interval = 3600;
+    }
+    cfg.compact.cold_storage_query_mode = cfg.compact.cold_storage_query_mode.to_lowercase();
+    if cfg.compact.cold_storage_query_mode.is_empty() {
+        cfg.compact.cold_storage_query_mode = "fetch".to_string();
+    }
+    if !["fetch", "restore"].contains(&cfg.compact.cold_storage_query_mode.as_str()) {
+        return Err(anyhow::anyhow!(
+            "ZO_COMPACT_COLD_STORAGE_QUERY_MODE must be fetch or restore"
+        ));
+    }
+    if cfg.compact.cold_storage_restore_days < 1 {
+        cfg.compact.cold_storage_restore_days = 7;
+    }
+
     Ok(())
 }
 
@@ -3168,6 +4390,299 @@ fn check_inverted_index_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+fn check_flow_collector_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
+    if !cfg.flow_collector.enabled {
+        return Ok(());
+    }
+    if cfg.flow_collector.netflow_port == 0 && cfg.flow_collector.sflow_port == 0 {
+        return Err(anyhow::anyhow!(
+            "ZO_FLOW_COLLECTOR_NETFLOW_PORT and ZO_FLOW_COLLECTOR_SFLOW_PORT can't both be 0"
+        ));
+    }
+    if cfg.flow_collector.stream_name.is_empty() {
+        cfg.flow_collector.stream_name = "network_flows".to_string();
+    }
+    if cfg.flow_collector.batch_size == 0 {
+        cfg.flow_collector.batch_size = 1000;
+    }
+    if cfg.flow_collector.flush_interval_ms == 0 {
+        cfg.flow_collector.flush_interval_ms = 1000;
+    }
+    if cfg.flow_collector.template_ttl == 0 {
+        cfg.flow_collector.template_ttl = 1800;
+    }
+    Ok(())
+}
+
+fn check_docker_logs_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
+    if cfg.docker_logs.stream_name.is_empty() {
+        cfg.docker_logs.stream_name = "docker".to_string();
+    }
+    if cfg.docker_logs.gelf_enabled
+        && cfg.docker_logs.gelf_udp_port == 0
+        && cfg.docker_logs.gelf_tcp_port == 0
+    {
+        return Err(anyhow::anyhow!(
+            "ZO_DOCKER_GELF_UDP_PORT and ZO_DOCKER_GELF_TCP_PORT can't both be 0"
+        ));
+    }
+    Ok(())
+}
+
+fn check_gelf_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
+    if cfg.gelf.stream_name.is_empty() {
+        cfg.gelf.stream_name = "gelf".to_string();
+    }
+    if !cfg.gelf.enabled {
+        return Ok(());
+    }
+    if cfg.gelf.udp_port == 0 && cfg.gelf.tcp_port == 0 {
+        return Err(anyhow::anyhow!(
+            "ZO_GELF_UDP_PORT and ZO_GELF_TCP_PORT can't both be 0"
+        ));
+    }
+    // the docker gelf listener runs next to this one on the same nodes
+    let docker_ports = [cfg.docker_logs.gelf_udp_port, cfg.docker_logs.gelf_tcp_port];
+    if cfg.docker_logs.gelf_enabled
+        && [cfg.gelf.udp_port, cfg.gelf.tcp_port]
+            .iter()
+            .any(|port| *port > 0 && docker_ports.contains(port))
+    {
+        return Err(anyhow::anyhow!(
+            "ZO_GELF_UDP_PORT and ZO_GELF_TCP_PORT can't be the ports of the docker GELF listener"
+        ));
+    }
+    Ok(())
+}
+
+fn check_pgwire_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
+    if cfg.pgwire.addr.is_empty() {
+        cfg.pgwire.addr = "0.0.0.0".to_string();
+    }
+    if cfg.pgwire.query_range_hours <= 0 {
+        cfg.pgwire.query_range_hours = 24;
+    }
+    if cfg.pgwire.max_connections == 0 {
+        cfg.pgwire.max_connections = 100;
+    }
+    if cfg.pgwire.idle_timeout == 0 {
+        cfg.pgwire.idle_timeout = 600;
+    }
+    if cfg.pgwire.enabled && cfg.pgwire.port == 0 {
+        return Err(anyhow::anyhow!("ZO_PGWIRE_PORT can't be 0"));
+    }
+    if cfg.pgwire.enabled && !cfg.http.tls_enabled && !cfg.pgwire.allow_cleartext {
+        return Err(anyhow::anyhow!(
+            "ZO_PGWIRE_ENABLED needs ZO_HTTP_TLS_ENABLED, or ZO_PGWIRE_ALLOW_CLEARTEXT to receive the passwords in clear text"
+        ));
+    }
+    Ok(())
+}
+
+fn check_access_log_import_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
+    if cfg.access_log_import.stream_name.is_empty() {
+        cfg.access_log_import.stream_name = "access_logs".to_string();
+    }
+    if cfg.access_log_import.interval_secs == 0 {
+        cfg.access_log_import.interval_secs = 60;
+    }
+    if cfg.access_log_import.batch_size == 0 {
+        cfg.access_log_import.batch_size = 5000;
+    }
+    if cfg.access_log_import.enabled && cfg.access_log_import.sources.trim().is_empty() {
+        return Err(anyhow::anyhow!(
+            "ZO_ACCESS_LOG_IMPORT_SOURCES must be set when the importer is enabled"
+        ));
+    }
+    Ok(())
+}
+
+fn check_kafka_ingestion_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
+    cfg.kafka_ingestion.start_offset = cfg.kafka_ingestion.start_offset.to_lowercase();
+    if cfg.kafka_ingestion.start_offset.is_empty() {
+        cfg.kafka_ingestion.start_offset = "earliest".to_string();
+    }
+    if !matches!(
+        cfg.kafka_ingestion.start_offset.as_str(),
+        "earliest" | "latest"
+    ) {
+        return Err(anyhow::anyhow!(
+            "ZO_KAFKA_START_OFFSET must be earliest or latest"
+        ));
+    }
+    if cfg.kafka_ingestion.fetch_max_bytes <= 0 {
+        cfg.kafka_ingestion.fetch_max_bytes = 1024 * 1024;
+    }
+    if cfg.kafka_ingestion.fetch_max_wait_ms <= 0 {
+        cfg.kafka_ingestion.fetch_max_wait_ms = 500;
+    }
+    if cfg.kafka_ingestion.batch_size == 0 {
+        cfg.kafka_ingestion.batch_size = 5000;
+    }
+    if cfg.kafka_ingestion.metadata_refresh_interval == 0 {
+        cfg.kafka_ingestion.metadata_refresh_interval = 60;
+    }
+    cfg.kafka_ingestion.dead_letter_stream =
+        cfg.kafka_ingestion.dead_letter_stream.trim().to_string();
+    if cfg.kafka_ingestion.enabled
+        && (cfg.kafka_ingestion.brokers.trim().is_empty()
+            || cfg.kafka_ingestion.topics.trim().is_empty())
+    {
+        return Err(anyhow::anyhow!(
+            "ZO_KAFKA_BROKERS and ZO_KAFKA_TOPICS must be set when Kafka ingestion is enabled"
+        ));
+    }
+    Ok(())
+}
+
+fn check_kinesis_ingestion_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
+    cfg.kinesis_ingestion.start_position = cfg.kinesis_ingestion.start_position.to_lowercase();
+    if cfg.kinesis_ingestion.start_position.is_empty() {
+        cfg.kinesis_ingestion.start_position = "trim_horizon".to_string();
+    }
+    if !matches!(
+        cfg.kinesis_ingestion.start_position.as_str(),
+        "trim_horizon" | "latest"
+    ) {
+        return Err(anyhow::anyhow!(
+            "ZO_KINESIS_START_POSITION must be trim_horizon or latest"
+        ));
+    }
+    if cfg.kinesis_ingestion.max_records <= 0 || cfg.kinesis_ingestion.max_records > 10_000 {
+        cfg.kinesis_ingestion.max_records = 1000;
+    }
+    if cfg.kinesis_ingestion.poll_interval_ms == 0 {
+        cfg.kinesis_ingestion.poll_interval_ms = 1000;
+    }
+    if cfg.kinesis_ingestion.lease_duration <= 0 {
+        cfg.kinesis_ingestion.lease_duration = 30;
+    }
+    if cfg.kinesis_ingestion.shard_sync_interval == 0 {
+        cfg.kinesis_ingestion.shard_sync_interval = 60;
+    }
+    if cfg.kinesis_ingestion.enabled && cfg.kinesis_ingestion.streams.trim().is_empty() {
+        return Err(anyhow::anyhow!(
+            "ZO_KINESIS_STREAMS must be set when Kinesis ingestion is enabled"
+        ));
+    }
+    Ok(())
+}
+
+fn check_mqtt_ingestion_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
+    cfg.mqtt_ingestion.payload_format = cfg.mqtt_ingestion.payload_format.to_lowercase();
+    if cfg.mqtt_ingestion.payload_format.is_empty() {
+        cfg.mqtt_ingestion.payload_format = "auto".to_string();
+    }
+    if !matches!(
+        cfg.mqtt_ingestion.payload_format.as_str(),
+        "json" | "cbor" | "auto"
+    ) {
+        return Err(anyhow::anyhow!(
+            "ZO_MQTT_PAYLOAD_FORMAT must be json, cbor or auto"
+        ));
+    }
+    if cfg.mqtt_ingestion.qos > 1 {
+        return Err(anyhow::anyhow!("ZO_MQTT_QOS must be 0 or 1"));
+    }
+    if cfg.mqtt_ingestion.client_id.trim().is_empty() {
+        cfg.mqtt_ingestion.client_id = "openobserve".to_string();
+    }
+    if cfg.mqtt_ingestion.batch_size == 0 {
+        cfg.mqtt_ingestion.batch_size = 1000;
+    }
+    if cfg.mqtt_ingestion.flush_interval_ms == 0 {
+        cfg.mqtt_ingestion.flush_interval_ms = 1000;
+    }
+    if cfg.mqtt_ingestion.keep_alive < 5 {
+        cfg.mqtt_ingestion.keep_alive = 30;
+    }
+    if cfg.mqtt_ingestion.enabled
+        && (cfg.mqtt_ingestion.broker.trim().is_empty()
+            || cfg.mqtt_ingestion.topics.trim().is_empty())
+    {
+        return Err(anyhow::anyhow!(
+            "ZO_MQTT_BROKER and ZO_MQTT_TOPICS must be set when MQTT ingestion is enabled"
+        ));
+    }
+    Ok(())
+}
+
+fn check_redis_streams_ingestion_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
+    cfg.redis_streams_ingestion.start_position =
+        cfg.redis_streams_ingestion.start_position.to_lowercase();
+    if cfg.redis_streams_ingestion.start_position.is_empty() {
+        cfg.redis_streams_ingestion.start_position = "earliest".to_string();
+    }
+    if !matches!(
+        cfg.redis_streams_ingestion.start_position.as_str(),
+        "earliest" | "latest"
+    ) {
+        return Err(anyhow::anyhow!(
+            "ZO_REDIS_STREAMS_START_POSITION must be earliest or latest"
+        ));
+    }
+    if cfg.redis_streams_ingestion.group.trim().is_empty() {
+        cfg.redis_streams_ingestion.group = "openobserve".to_string();
+    }
+    if cfg.redis_streams_ingestion.batch_size == 0 {
+        cfg.redis_streams_ingestion.batch_size = 1000;
+    }
+    if cfg.redis_streams_ingestion.block_ms == 0 {
+        cfg.redis_streams_ingestion.block_ms = 1000;
+    }
+    if cfg.redis_streams_ingestion.claim_idle_ms == 0 {
+        cfg.redis_streams_ingestion.claim_idle_ms = 60000;
+    }
+    if cfg.redis_streams_ingestion.claim_interval == 0 {
+        cfg.redis_streams_ingestion.claim_interval = 30;
+    }
+    if cfg.redis_streams_ingestion.enabled
+        && (cfg.redis_streams_ingestion.url.trim().is_empty()
+            || cfg.redis_streams_ingestion.streams.trim().is_empty())
+    {
+        return Err(anyhow::anyhow!(
+            "ZO_REDIS_STREAMS_URL and ZO_REDIS_STREAMS_STREAMS must be set when Redis Streams ingestion is enabled"
+        ));
+    }
+    Ok(())
+}
+
+fn check_k8s_events_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
+    if !cfg.k8s_events.enabled {
+        return Ok(());
+    }
+    if cfg.k8s_events.api_server.is_empty() {
+        let host = std::env::var("KUBERNETES_SERVICE_HOST").unwrap_or_default();
+        if host.is_empty() {
+            return Err(anyhow::anyhow!(
+                "ZO_K8S_EVENTS_API_SERVER must be set when not running inside Kubernetes"
+            ));
+        }
+        let port = std::env::var("KUBERNETES_SERVICE_PORT").unwrap_or_else(|_| "443".to_string());
+        // IPv6 service addresses need brackets in urls
+        let host = if host.contains(':') {
+            format!("[{host}]")
+        } else {
+            host
+        };
+        cfg.k8s_events.api_server = format!("https://{host}:{port}");
+    }
+    cfg.k8s_events.api_server = cfg.k8s_events.api_server.trim_end_matches('/').to_string();
+    if cfg.k8s_events.stream_name.is_empty() {
+        cfg.k8s_events.stream_name = "k8s_events".to_string();
+    }
+    if cfg.k8s_events.pod_logs_stream_name.is_empty() {
+        cfg.k8s_events.pod_logs_stream_name = "k8s_pod_logs".to_string();
+    }
+    if cfg.k8s_events.batch_size == 0 {
+        cfg.k8s_events.batch_size = 500;
+    }
+    if cfg.k8s_events.flush_interval_ms == 0 {
+        cfg.k8s_events.flush_interval_ms = 2000;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3237,6 +4752,27 @@ mod tests {
         assert_eq!(cfg.compact.data_retention_days, 10);
         assert_eq!(cfg.limit.req_cols_per_record_limit, 1000);
 
+        cfg.common.ingest_backpressure_retry_after = 0;
+        assert!(check_common_config(&mut cfg).is_ok());
+        assert_eq!(cfg.common.ingest_backpressure_retry_after, 5);
+        cfg.common.ingest_backpressure_memtable_ratio = 120;
+        assert!(check_common_config(&mut cfg).is_err());
+        cfg.common.ingest_backpressure_memtable_ratio = 90;
+        cfg.common.ingest_backpressure_low_priority_ratio = 101;
+        assert!(check_common_config(&mut cfg).is_err());
+        cfg.common.ingest_backpressure_low_priority_ratio = 80;
+
+        cfg.common.wal_sync_policy = "".to_string();
+        cfg.common.wal_fsync_disabled = false;
+        assert!(check_common_config(&mut cfg).is_ok());
+        assert_eq!(cfg.common.wal_sync_policy, "batch");
+        cfg.common.wal_sync_policy_high_priority = "Interval".to_string();
+        assert!(check_common_config(&mut cfg).is_ok());
+        assert_eq!(cfg.common.wal_sync_policy_high_priority, "interval");
+        cfg.common.wal_sync_policy = "fast".to_string();
+        assert!(check_common_config(&mut cfg).is_err());
+        cfg.common.wal_sync_policy = "async".to_string();
+
         cfg.compact.data_retention_days = 2;
         let ret = check_compact_config(&mut cfg);
         assert!(ret.is_err());
@@ -3268,6 +4804,272 @@ mod tests {
         assert!(check_route_config(&cfg).is_err());
     }
 
+    #[test]
+    fn test_check_cold_storage_config() {
+        let mut cfg = Config::init().unwrap();
+        cfg.s3.accounts = "hot,cold".to_string();
+        cfg.compact.cold_storage_account = "hot".to_string();
+        assert!(check_compact_config(&mut cfg).is_err());
+        cfg.compact.cold_storage_account = "glacier".to_string();
+        assert!(check_compact_config(&mut cfg).is_err());
+
+        cfg.compact.cold_storage_account = " cold ".to_string();
+        cfg.compact.cold_storage_after_days = cfg.compact.old_data_max_days;
+        assert!(check_compact_config(&mut cfg).is_err());
+        cfg.compact.cold_storage_after_days = 90;
+        cfg.compact.cold_storage_query_mode = "Restore".to_string();
+        cfg.compact.cold_storage_interval = 0;
+        check_compact_config(&mut cfg).unwrap();
+        assert_eq!(cfg.compact.cold_storage_account, "cold");
+        assert_eq!(cfg.compact.cold_storage_query_mode, "restore");
+        assert_eq!(cfg.compact.cold_storage_interval, 3600);
+
+        cfg.compact.cold_storage_query_mode = "thaw".to_string();
+        assert!(check_compact_config(&mut cfg).is_err());
+    }
+
+    #[test]
+    fn test_check_retention_archive_config() {
+        let mut cfg = Config::init().unwrap();
+        cfg.compact.retention_archive_account = "".to_string();
+        cfg.compact.retention_archive_prefix = "/".to_string();
+        assert!(check_compact_config(&mut cfg).is_err());
+
+        cfg.compact.retention_archive_account = "archive".to_string();
+        check_compact_config(&mut cfg).unwrap();
+        assert_eq!(cfg.compact.retention_archive_prefix, "");
+    }
+
+    #[test]
+    fn test_check_flow_collector_config() {
+        let mut cfg = Config::init().unwrap();
+        cfg.flow_collector.enabled = false;
+        cfg.flow_collector.netflow_port = 0;
+        cfg.flow_collector.sflow_port = 0;
+        assert!(check_flow_collector_config(&mut cfg).is_ok());
+
+        cfg.flow_collector.enabled = true;
+        assert!(check_flow_collector_config(&mut cfg).is_err());
+
+        cfg.flow_collector.netflow_port = 2055;
+        cfg.flow_collector.stream_name = "".to_string();
+        cfg.flow_collector.batch_size = 0;
+        cfg.flow_collector.template_ttl = 0;
+        check_flow_collector_config(&mut cfg).unwrap();
+        assert_eq!(cfg.flow_collector.stream_name, "network_flows");
+        assert_eq!(cfg.flow_collector.batch_size, 1000);
+        assert_eq!(cfg.flow_collector.template_ttl, 1800);
+    }
+
+    #[test]
+    fn test_check_docker_logs_config() {
+        let mut cfg = Config::init().unwrap();
+        cfg.docker_logs.stream_name = "".to_string();
+        cfg.docker_logs.gelf_enabled = false;
+        cfg.docker_logs.gelf_udp_port = 0;
+        cfg.docker_logs.gelf_tcp_port = 0;
+        check_docker_logs_config(&mut cfg).unwrap();
+        assert_eq!(cfg.docker_logs.stream_name, "docker");
+
+        cfg.docker_logs.gelf_enabled = true;
+        assert!(check_docker_logs_config(&mut cfg).is_err());
+        cfg.docker_logs.gelf_udp_port = 12201;
+        assert!(check_docker_logs_config(&mut cfg).is_ok());
+    }
+
+    #[test]
+    fn test_check_gelf_config() {
+        let mut cfg = Config::init().unwrap();
+        cfg.gelf.stream_name = "".to_string();
+        cfg.gelf.enabled = false;
+        check_gelf_config(&mut cfg).unwrap();
+        assert_eq!(cfg.gelf.stream_name, "gelf");
+
+        cfg.gelf.enabled = true;
+        cfg.gelf.udp_port = 0;
+        cfg.gelf.tcp_port = 0;
+        assert!(check_gelf_config(&mut cfg).is_err());
+
+        cfg.gelf.udp_port = 12201;
+        cfg.docker_logs.gelf_enabled = true;
+        cfg.docker_logs.gelf_udp_port = 12201;
+        cfg.docker_logs.gelf_tcp_port = 12201;
+        assert!(check_gelf_config(&mut cfg).is_err());
+        cfg.gelf.udp_port = 12202;
+        assert!(check_gelf_config(&mut cfg).is_ok());
+    }
+
+    #[test]
+    fn test_check_pgwire_config() {
+        let mut cfg = Config::init().unwrap();
+        cfg.pgwire.enabled = false;
+        cfg.pgwire.addr = "".to_string();
+        cfg.pgwire.query_range_hours = 0;
+        check_pgwire_config(&mut cfg).unwrap();
+        assert_eq!(cfg.pgwire.addr, "0.0.0.0");
+        assert_eq!(cfg.pgwire.query_range_hours, 24);
+
+        cfg.pgwire.enabled = true;
+        cfg.pgwire.port = 0;
+        assert!(check_pgwire_config(&mut cfg).is_err());
+        cfg.pgwire.port = 5432;
+        cfg.http.tls_enabled = false;
+        cfg.pgwire.allow_cleartext = false;
+        assert!(check_pgwire_config(&mut cfg).is_err());
+        cfg.pgwire.allow_cleartext = true;
+        assert!(check_pgwire_config(&mut cfg).is_ok());
+    }
+
+    #[test]
+    fn test_check_access_log_import_config() {
+        let mut cfg = Config::init().unwrap();
+        cfg.access_log_import.enabled = false;
+        cfg.access_log_import.sources = "".to_string();
+        cfg.access_log_import.stream_name = "".to_string();
+        cfg.access_log_import.interval_secs = 0;
+        cfg.access_log_import.batch_size = 0;
+        check_access_log_import_config(&mut cfg).unwrap();
+        assert_eq!(cfg.access_log_import.stream_name, "access_logs");
+        assert_eq!(cfg.access_log_import.interval_secs, 60);
+        assert_eq!(cfg.access_log_import.batch_size, 5000);
+
+        cfg.access_log_import.enabled = true;
+        assert!(check_access_log_import_config(&mut cfg).is_err());
+        cfg.access_log_import.sources = "alb:s3://logs/AWSLogs/".to_string();
+        assert!(check_access_log_import_config(&mut cfg).is_ok());
+    }
+
+    #[test]
+    fn test_check_grpc_config_client_auth() {
+        let mut cfg = Config::init().unwrap();
+        cfg.grpc.tls_enabled = false;
+        cfg.grpc.tls_client_auth = true;
+        assert!(check_grpc_config(&mut cfg).is_err());
+
+        cfg.grpc.tls_enabled = true;
+        cfg.grpc.tls_cert_domain = "openobserve.local".to_string();
+        cfg.grpc.tls_cert_path = "/certs/node.crt".to_string();
+        cfg.grpc.tls_key_path = "/certs/node.key".to_string();
+        cfg.grpc.tls_ca_cert_path = "".to_string();
+        assert!(check_grpc_config(&mut cfg).is_err());
+        cfg.grpc.tls_ca_cert_path = "/certs/ca.crt".to_string();
+        assert!(check_grpc_config(&mut cfg).is_ok());
+    }
+
+    #[test]
+    fn test_check_kafka_ingestion_config() {
+        let mut cfg = Config::init().unwrap();
+        cfg.kafka_ingestion.enabled = false;
+        cfg.kafka_ingestion.start_offset = "Latest".to_string();
+        cfg.kafka_ingestion.fetch_max_bytes = 0;
+        cfg.kafka_ingestion.batch_size = 0;
+        check_kafka_ingestion_config(&mut cfg).unwrap();
+        assert_eq!(cfg.kafka_ingestion.start_offset, "latest");
+        assert_eq!(cfg.kafka_ingestion.fetch_max_bytes, 1024 * 1024);
+        assert_eq!(cfg.kafka_ingestion.batch_size, 5000);
+
+        cfg.kafka_ingestion.start_offset = "middle".to_string();
+        assert!(check_kafka_ingestion_config(&mut cfg).is_err());
+        cfg.kafka_ingestion.start_offset = "earliest".to_string();
+
+        cfg.kafka_ingestion.enabled = true;
+        cfg.kafka_ingestion.brokers = "".to_string();
+        cfg.kafka_ingestion.topics = "app-logs:default/app".to_string();
+        assert!(check_kafka_ingestion_config(&mut cfg).is_err());
+        cfg.kafka_ingestion.brokers = "kafka:9092".to_string();
+        assert!(check_kafka_ingestion_config(&mut cfg).is_ok());
+    }
+
+    #[test]
+    fn test_check_kinesis_ingestion_config() {
+        let mut cfg = Config::init().unwrap();
+        cfg.kinesis_ingestion.enabled = false;
+        cfg.kinesis_ingestion.start_position = "LATEST".to_string();
+        cfg.kinesis_ingestion.max_records = 20_000;
+        cfg.kinesis_ingestion.lease_duration = 0;
+        check_kinesis_ingestion_config(&mut cfg).unwrap();
+        assert_eq!(cfg.kinesis_ingestion.start_position, "latest");
+        assert_eq!(cfg.kinesis_ingestion.max_records, 1000);
+        assert_eq!(cfg.kinesis_ingestion.lease_duration, 30);
+
+        cfg.kinesis_ingestion.start_position = "at_timestamp".to_string();
+        assert!(check_kinesis_ingestion_config(&mut cfg).is_err());
+        cfg.kinesis_ingestion.start_position = "trim_horizon".to_string();
+
+        cfg.kinesis_ingestion.enabled = true;
+        cfg.kinesis_ingestion.streams = "".to_string();
+        assert!(check_kinesis_ingestion_config(&mut cfg).is_err());
+        cfg.kinesis_ingestion.streams = "app-events:default/app".to_string();
+        assert!(check_kinesis_ingestion_config(&mut cfg).is_ok());
+    }
+
+    #[test]
+    fn test_check_mqtt_ingestion_config() {
+        let mut cfg = Config::init().unwrap();
+        cfg.mqtt_ingestion.enabled = false;
+        cfg.mqtt_ingestion.payload_format = "CBOR".to_string();
+        cfg.mqtt_ingestion.batch_size = 0;
+        cfg.mqtt_ingestion.keep_alive = 0;
+        check_mqtt_ingestion_config(&mut cfg).unwrap();
+        assert_eq!(cfg.mqtt_ingestion.payload_format, "cbor");
+        assert_eq!(cfg.mqtt_ingestion.batch_size, 1000);
+        assert_eq!(cfg.mqtt_ingestion.keep_alive, 30);
+
+        cfg.mqtt_ingestion.payload_format = "protobuf".to_string();
+        assert!(check_mqtt_ingestion_config(&mut cfg).is_err());
+        cfg.mqtt_ingestion.payload_format = "auto".to_string();
+        cfg.mqtt_ingestion.qos = 2;
+        assert!(check_mqtt_ingestion_config(&mut cfg).is_err());
+        cfg.mqtt_ingestion.qos = 1;
+
+        cfg.mqtt_ingestion.enabled = true;
+        cfg.mqtt_ingestion.broker = "".to_string();
+        assert!(check_mqtt_ingestion_config(&mut cfg).is_err());
+        cfg.mqtt_ingestion.broker = "mqtt.example.com".to_string();
+        cfg.mqtt_ingestion.topics = "devices/{device_id}/telemetry:iot/telemetry".to_string();
+        assert!(check_mqtt_ingestion_config(&mut cfg).is_ok());
+    }
+
+    #[test]
+    fn test_check_redis_streams_ingestion_config() {
+        let mut cfg = Config::init().unwrap();
+        cfg.redis_streams_ingestion.enabled = false;
+        cfg.redis_streams_ingestion.start_position = "Latest".to_string();
+        cfg.redis_streams_ingestion.group = "".to_string();
+        cfg.redis_streams_ingestion.batch_size = 0;
+        check_redis_streams_ingestion_config(&mut cfg).unwrap();
+        assert_eq!(cfg.redis_streams_ingestion.start_position, "latest");
+        assert_eq!(cfg.redis_streams_ingestion.group, "openobserve");
+        assert_eq!(cfg.redis_streams_ingestion.batch_size, 1000);
+
+        cfg.redis_streams_ingestion.start_position = "middle".to_string();
+        assert!(check_redis_streams_ingestion_config(&mut cfg).is_err());
+        cfg.redis_streams_ingestion.start_position = "earliest".to_string();
+
+        cfg.redis_streams_ingestion.enabled = true;
+        cfg.redis_streams_ingestion.url = "".to_string();
+        cfg.redis_streams_ingestion.streams = "app-logs:default/app".to_string();
+        assert!(check_redis_streams_ingestion_config(&mut cfg).is_err());
+        cfg.redis_streams_ingestion.url = "redis://redis:6379".to_string();
+        assert!(check_redis_streams_ingestion_config(&mut cfg).is_ok());
+    }
+
+    #[test]
+    fn test_check_k8s_events_config() {
+        let mut cfg = Config::init().unwrap();
+        cfg.k8s_events.enabled = false;
+        assert!(check_k8s_events_config(&mut cfg).is_ok());
+
+        cfg.k8s_events.enabled = true;
+        cfg.k8s_events.api_server = "https://k8s.example.com:6443/".to_string();
+        cfg.k8s_events.stream_name = "".to_string();
+        cfg.k8s_events.batch_size = 0;
+        check_k8s_events_config(&mut cfg).unwrap();
+        assert_eq!(cfg.k8s_events.api_server, "https://k8s.example.com:6443");
+        assert_eq!(cfg.k8s_events.stream_name, "k8s_events");
+        assert_eq!(cfg.k8s_events.batch_size, 500);
+    }
+
     #[test]
     fn test_usage_report_to_own_org_field_exists() {
         // Test that usage_report_to_own_org field exists and is accessible
@@ -33,6 +33,8 @@ pub struct Request {
     pub use_cache: bool,
     pub overwrite_cache: bool,
     pub histogram_interval: i64,
+    pub shadow: bool,     // run on the shadow queriers
+    pub max_scan_mb: u64, // 0 is unlimited
 }
 
 impl Default for Request {
@@ -52,6 +54,8 @@ impl Default for Request {
             use_cache: default_use_cache(),
             overwrite_cache: false,
             histogram_interval: 0,
+            shadow: false,
+            max_scan_mb: 0,
         }
     }
 }
@@ -84,6 +88,8 @@ impl Request {
             use_cache: default_use_cache(),
             overwrite_cache,
             histogram_interval,
+            shadow: false,
+            max_scan_mb: 0,
         }
     }
 
@@ -103,6 +109,14 @@ impl Request {
     pub fn set_use_cache(&mut self, use_cache: bool) {
         self.use_cache = use_cache;
     }
+
+    pub fn set_shadow(&mut self, shadow: bool) {
+        self.shadow = shadow;
+    }
+
+    pub fn set_max_scan_mb(&mut self, max_scan_mb: u64) {
+        self.max_scan_mb = max_scan_mb;
+    }
 }
 
 impl From<FlightSearchRequest> for Request {
@@ -122,6 +136,8 @@ impl From<FlightSearchRequest> for Request {
             use_cache: req.search_info.use_cache,
             overwrite_cache: req.search_info.clear_cache,
             histogram_interval: req.search_info.histogram_interval,
+            shadow: false,
+            max_scan_mb: 0,
         }
     }
 }
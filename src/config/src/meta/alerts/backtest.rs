@@ -0,0 +1,119 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::meta::alerts::alert::Alert;
+
+/// Stream of the org the evaluated windows of its backtests are written to
+pub const ALERT_BACKTEST_STREAM: &str = "_alert_backtest";
+
+/// Number of fired windows kept in the status of a backtest, all of them are
+/// in the stream
+pub const MAX_FIRED_WINDOWS: usize = 100;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BacktestStatus {
+    #[default]
+    Running,
+    Completed,
+    Failed,
+}
+
+/// Replays an alert over a past time range, either a saved alert or a
+/// definition which isn't saved yet
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct AlertBacktestRequest {
+    #[serde(default)]
+    pub alert_id: Option<String>,
+    #[serde(default)]
+    pub alert: Option<Alert>,
+    /// In microseconds
+    pub start_time: i64,
+    /// In microseconds
+    pub end_time: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AlertBacktest {
+    pub id: String,
+    pub alert: Alert,
+    pub start_time: i64,
+    pub end_time: i64,
+    #[serde(default)]
+    pub status: BacktestStatus,
+    /// End of the last evaluated window
+    pub position: i64,
+    /// Whether the last evaluated window fired, the alert is silenced after it
+    #[serde(default)]
+    pub last_fired: bool,
+    #[serde(default)]
+    pub windows_evaluated: usize,
+    #[serde(default)]
+    pub windows_fired: usize,
+    /// End of the windows which fired, the first `MAX_FIRED_WINDOWS` ones
+    #[serde(default)]
+    pub fired_windows: Vec<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub created_by: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl AlertBacktestRequest {
+    pub fn validate(&self, now: i64) -> Result<(), String> {
+        if self.alert_id.is_none() == self.alert.is_none() {
+            return Err("one of alert_id or alert is required".to_string());
+        }
+        if self.start_time >= self.end_time {
+            return Err("start_time must be before end_time".to_string());
+        }
+        if self.end_time > now {
+            return Err("end_time must not be in the future".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::json;
+
+    #[test]
+    fn test_backtest_request_validate() {
+        let now = 1_000;
+        let req: AlertBacktestRequest =
+            json::from_str(r#"{"alert_id": "abc", "start_time": 100, "end_time": 200}"#).unwrap();
+        assert!(req.validate(now).is_ok());
+        assert!(req.validate(150).is_err());
+
+        let req = AlertBacktestRequest {
+            alert: Some(Default::default()),
+            ..req
+        };
+        assert!(req.validate(now).is_err());
+
+        let req = AlertBacktestRequest {
+            alert_id: None,
+            start_time: 200,
+            ..req
+        };
+        assert!(req.validate(now).is_err());
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{meta::search::Query, utils::json};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DetectionMethod {
+    /// Spikes: points whose robust z-score, based on the median absolute
+    /// deviation, exceeds the threshold
+    #[default]
+    Mad,
+    /// Changepoints: points where the two-sided cumulative sum of the
+    /// standardized values exceeds the threshold
+    Cusum,
+}
+
+impl DetectionMethod {
+    pub fn default_threshold(&self) -> f64 {
+        match self {
+            DetectionMethod::Mad => 3.5,
+            DetectionMethod::Cusum => 5.0,
+        }
+    }
+}
+
+/// Request to detect the anomalies of the series returned by a query
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct AnomalyRequest {
+    /// Query returning one row per series and time bucket, usually a histogram
+    pub query: Query,
+    /// Column holding the time bucket, `x_axis_1` or the timestamp column when
+    /// empty
+    #[serde(default)]
+    pub x_axis: Option<String>,
+    /// Numeric columns to analyze, every numeric column when empty
+    #[serde(default)]
+    pub y_axis: Vec<String>,
+    #[serde(default)]
+    pub method: DetectionMethod,
+    /// Detection threshold, 3.5 for mad and 5 for cusum when empty
+    #[serde(default)]
+    pub threshold: Option<f64>,
+    /// Series with fewer points are skipped, 8 when empty
+    #[serde(default)]
+    pub min_points: Option<usize>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct AnomalyResponse {
+    pub took: usize,
+    pub method: DetectionMethod,
+    pub threshold: f64,
+    pub series_analyzed: usize,
+    pub total_anomalies: usize,
+    /// Series having at least one anomaly
+    pub series: Vec<SeriesAnomalies>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub function_error: Vec<String>,
+    #[serde(default)]
+    pub is_partial: bool,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct SeriesAnomalies {
+    /// Values of the non numeric columns identifying the series
+    #[schema(value_type = Object)]
+    pub labels: json::Map<String, json::Value>,
+    /// Numeric column analyzed
+    pub field: String,
+    pub points: usize,
+    pub anomalies: Vec<Anomaly>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct Anomaly {
+    /// Time bucket of the anomaly
+    #[schema(value_type = Object)]
+    pub x: json::Value,
+    pub value: f64,
+    /// Robust z-score for mad, cumulative sum for cusum
+    pub score: f64,
+    pub direction: Direction,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    #[default]
+    Up,
+    Down,
+}
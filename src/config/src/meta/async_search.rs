@@ -0,0 +1,155 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::meta::{
+    search::{Request, Response},
+    stream::StreamType,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AsyncSearchStatus {
+    #[default]
+    Pending,
+    Running,
+    Finished,
+    Failed,
+    Cancelled,
+}
+
+impl AsyncSearchStatus {
+    pub fn is_done(&self) -> bool {
+        matches!(
+            self,
+            AsyncSearchStatus::Finished | AsyncSearchStatus::Failed | AsyncSearchStatus::Cancelled
+        )
+    }
+}
+
+/// A search run in the background. The job is stored with its progress, so
+/// any alert manager can resume it from the last finished partition.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct AsyncSearchJob {
+    pub id: String,
+    pub org_id: String,
+    pub user_id: String,
+    pub stream_type: StreamType,
+    pub request: Request,
+    pub status: AsyncSearchStatus,
+    /// Node running the job, empty while pending
+    #[serde(default)]
+    pub node: String,
+    /// Time ranges searched one after the other, empty until the job starts
+    #[serde(default)]
+    pub partitions: Vec<[i64; 2]>,
+    /// Partitions whose results are stored
+    #[serde(default)]
+    pub partitions_done: usize,
+    /// Rows stored so far
+    #[serde(default)]
+    pub hits: usize,
+    /// Rows stored by every finished partition, a page of the results only
+    /// reads the partitions holding it
+    #[serde(default)]
+    pub partition_hits: Vec<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub created_at: i64,
+    /// Updated after every partition, a running job not updated for long is
+    /// taken over by another node
+    pub updated_at: i64,
+    /// When the job and its results are deleted, 0 while the job is not done
+    #[serde(default)]
+    pub expires_at: i64,
+}
+
+impl AsyncSearchJob {
+    /// Rows still wanted, the job stops once the request's size is reached
+    pub fn remaining(&self, default_size: i64) -> i64 {
+        let size = if self.request.query.size > 0 {
+            self.request.query.size
+        } else {
+            default_size
+        };
+        size + self.request.query.from - self.hits as i64
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct AsyncSearchSubmitResponse {
+    pub id: String,
+    pub status: AsyncSearchStatus,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct AsyncSearchStatusResponse {
+    pub id: String,
+    pub status: AsyncSearchStatus,
+    pub partitions: usize,
+    pub partitions_done: usize,
+    pub hits: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub expires_at: i64,
+}
+
+impl From<&AsyncSearchJob> for AsyncSearchStatusResponse {
+    fn from(job: &AsyncSearchJob) -> Self {
+        Self {
+            id: job.id.clone(),
+            status: job.status,
+            partitions: job.partitions.len(),
+            partitions_done: job.partitions_done,
+            hits: job.hits,
+            error: job.error.clone(),
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+            expires_at: job.expires_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct AsyncSearchResultResponse {
+    pub status: AsyncSearchStatus,
+    /// The job has not finished, more rows may come
+    pub is_partial: bool,
+    pub partitions: usize,
+    pub partitions_done: usize,
+    pub response: Response,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_async_search_remaining() {
+        let mut job = AsyncSearchJob::default();
+        assert_eq!(job.remaining(100), 100);
+        job.request.query.size = 10;
+        job.request.query.from = 5;
+        job.hits = 12;
+        assert_eq!(job.remaining(100), 3);
+        assert!(!job.status.is_done());
+        job.status = AsyncSearchStatus::Cancelled;
+        assert!(job.status.is_done());
+    }
+}
@@ -123,6 +123,11 @@ impl Node {
     pub fn is_flatten_compactor(&self) -> bool {
         self.role.contains(&Role::FlattenCompactor)
     }
+    /// Indexer nodes only run when configured explicitly, the inverted index
+    /// is built by the ingesters otherwise
+    pub fn is_indexer(&self) -> bool {
+        self.role.contains(&Role::Indexer)
+    }
     pub fn is_alert_manager(&self) -> bool {
         self.role.contains(&Role::AlertManager) || self.role.contains(&Role::All)
     }
@@ -207,6 +212,7 @@ pub enum Role {
     AlertManager,
     FlattenCompactor,
     ActionServer,
+    Indexer,
 }
 
 impl FromStr for Role {
@@ -224,6 +230,7 @@ impl FromStr for Role {
             "action_server" | "actionserver" | "script_server" | "scriptserver" => {
                 Ok(Role::ActionServer)
             }
+            "indexer" => Ok(Role::Indexer),
             _ => Err(format!("Invalid cluster role: {s}")),
         }
     }
@@ -240,6 +247,7 @@ impl std::fmt::Display for Role {
             Role::AlertManager => write!(f, "alert_manager"),
             Role::FlattenCompactor => write!(f, "flatten_compactor"),
             Role::ActionServer => write!(f, "action_server"),
+            Role::Indexer => write!(f, "indexer"),
         }
     }
 }
@@ -248,12 +256,14 @@ impl std::fmt::Display for Role {
 /// None        -> All tasks
 /// Background  -> Low-priority tasks
 /// Interactive -> High-priority tasks
+/// Shadow      -> Only the shadow copies of sampled searches, e.g. on a new version
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Default, ToSchema)]
 pub enum RoleGroup {
     #[default]
     None,
     Interactive,
     Background,
+    Shadow,
 }
 
 impl From<&str> for RoleGroup {
@@ -261,6 +271,7 @@ impl From<&str> for RoleGroup {
         match s.to_lowercase().as_str() {
             "background" => RoleGroup::Background,
             "interactive" => RoleGroup::Interactive,
+            "shadow" => RoleGroup::Shadow,
             _ => RoleGroup::None,
         }
     }
@@ -284,6 +295,7 @@ impl std::fmt::Display for RoleGroup {
             RoleGroup::None => write!(f, ""),
             RoleGroup::Interactive => write!(f, "interactive"),
             RoleGroup::Background => write!(f, "background"),
+            RoleGroup::Shadow => write!(f, "shadow"),
         }
     }
 }
@@ -378,5 +390,28 @@ mod tests {
         // Test flatten compactor
         node.role = vec![Role::FlattenCompactor];
         assert!(node.is_flatten_compactor());
+
+        // Test indexer
+        node.role = vec![Role::Indexer];
+        assert!(node.is_indexer());
+        assert!(!node.is_ingester());
+        node.role = vec![Role::All];
+        assert!(!node.is_indexer());
+    }
+
+    #[test]
+    fn test_shadow_querier() {
+        let mut node = Node {
+            role: vec![Role::Querier],
+            role_group: RoleGroup::from("shadow"),
+            ..Default::default()
+        };
+        assert_eq!(node.role_group, RoleGroup::Shadow);
+        assert_eq!(node.role_group.to_string(), "shadow");
+        // the shadow queriers never serve the production searches
+        assert!(!node.is_interactive_querier());
+        assert!(!node.is_background_querier());
+        node.role_group = RoleGroup::None;
+        assert!(node.is_interactive_querier());
     }
 }
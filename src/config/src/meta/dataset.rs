@@ -0,0 +1,94 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::meta::stream::StreamType;
+
+/// A named group of streams of a type, queried like a stream: the query of a
+/// dataset runs on the union of its member streams.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Dataset {
+    pub name: String,
+    #[serde(default)]
+    pub stream_type: StreamType,
+    /// Names of the member streams, `*` matches any characters, e.g. `k8s_*`.
+    /// The patterns are matched against the streams when the dataset is
+    /// queried, so new streams join it without updating it.
+    pub streams: Vec<String>,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub created_at: i64,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+impl Dataset {
+    /// Whether the stream is a member of the dataset
+    pub fn matches(&self, stream_name: &str) -> bool {
+        self.streams
+            .iter()
+            .any(|pattern| glob_match(pattern, stream_name))
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct DatasetList {
+    pub list: Vec<Dataset>,
+}
+
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let Some((prefix, rest)) = pattern.split_once('*') else {
+        return pattern == name;
+    };
+    let Some(mut name) = name.strip_prefix(prefix) else {
+        return false;
+    };
+    let mut parts = rest.split('*').collect::<Vec<_>>();
+    let suffix = parts.pop().unwrap_or_default();
+    for part in parts {
+        match name.find(part) {
+            Some(i) => name = &name[i + part.len()..],
+            None => return false,
+        }
+    }
+    name.ends_with(suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dataset_matches() {
+        let dataset = Dataset {
+            name: "k8s_logs".to_string(),
+            streams: vec!["k8s_*".to_string(), "kube_events".to_string()],
+            ..Default::default()
+        };
+        assert!(dataset.matches("k8s_default"));
+        assert!(dataset.matches("kube_events"));
+        assert!(!dataset.matches("kube_events_old"));
+        assert!(!dataset.matches("app_k8s_default"));
+
+        assert!(glob_match("*_prod_*", "web_prod_eu"));
+        assert!(glob_match("a*b*c", "abc"));
+        assert!(!glob_match("a*b*c", "acb"));
+        assert!(!glob_match("ab*ba", "aba"));
+        assert!(glob_match("*", "anything"));
+    }
+}
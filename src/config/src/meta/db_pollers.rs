@@ -0,0 +1,246 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DbPollerDriver {
+    #[default]
+    Postgres,
+    Mysql,
+}
+
+/// The last value of the watermark column ingested, the next poll reads the
+/// rows after it
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum Watermark {
+    Integer(i64),
+    Float(f64),
+    Text(String),
+    /// Microseconds since the epoch, the timestamps without time zone are
+    /// read as UTC
+    Timestamp(i64),
+}
+
+/// A SQL query run on a schedule against a Postgres or MySQL database, the
+/// rows after the watermark are ingested into a logs stream, e.g. the audit
+/// table of an application which only logs to its database
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DbPoller {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub driver: DbPollerDriver,
+    /// `postgres://host:5432/db` or `mysql://host:3306/db`, without
+    /// credentials
+    pub url: String,
+    #[serde(default)]
+    pub username: String,
+    /// It is never returned by the API, leave it empty on update to keep the
+    /// current one while the url, the driver and the username are unchanged
+    #[serde(default)]
+    pub password: String,
+    /// Rows to ingest, e.g. `SELECT * FROM audit_log`. It is wrapped to only
+    /// read the rows after the watermark, in the order of the watermark
+    /// column
+    pub query: String,
+    /// Increasing column of the rows, e.g. an id or a creation time. Rows
+    /// sharing a value are only read together when they fit in a batch
+    pub watermark_column: String,
+    /// Column holding the time of the rows, the rows are ingested at the
+    /// time they are polled without it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_column: Option<String>,
+    pub stream_name: String,
+    /// Seconds between two polls
+    #[serde(default = "default_interval")]
+    pub interval: i64,
+    /// Maximum number of rows read at once, a poll reads up to
+    /// `MAX_BATCHES_PER_POLL` batches
+    #[serde(default = "default_batch_size")]
+    pub batch_size: i64,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Where the next poll starts, none to read the table from its first row
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watermark: Option<Watermark>,
+    #[serde(default)]
+    pub last_run: Option<DbPollerRun>,
+}
+
+/// Batches a poll reads before it waits for the next one
+pub const MAX_BATCHES_PER_POLL: usize = 10;
+
+fn default_interval() -> i64 {
+    60
+}
+
+fn default_batch_size() -> i64 {
+    10_000
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Column names usable in the wrapped query without quoting
+fn is_valid_column_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+impl DbPoller {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("name is required".to_string());
+        }
+        let scheme = match self.driver {
+            DbPollerDriver::Postgres => "postgres",
+            DbPollerDriver::Mysql => "mysql",
+        };
+        let Some((url_scheme, rest)) = self.url.split_once("://") else {
+            return Err(format!("url must be a {scheme}:// url"));
+        };
+        if url_scheme != scheme && !(scheme == "postgres" && url_scheme == "postgresql") {
+            return Err(format!("url must be a {scheme}:// url"));
+        }
+        if rest
+            .split('/')
+            .next()
+            .is_some_and(|host| host.contains('@'))
+        {
+            return Err(
+                "url must not carry credentials, set username and password instead".to_string(),
+            );
+        }
+        if self.query.trim().is_empty() {
+            return Err("query is required".to_string());
+        }
+        if self.query.contains(';') {
+            return Err("query must be a single statement".to_string());
+        }
+        for column in std::iter::once(&self.watermark_column).chain(&self.timestamp_column) {
+            if !is_valid_column_name(column) {
+                return Err(format!("invalid column name: {column}"));
+            }
+        }
+        if self.stream_name.trim().is_empty() {
+            return Err("stream_name is required".to_string());
+        }
+        if self.interval < 1 {
+            return Err("interval must be at least 1 second".to_string());
+        }
+        if !(1..=100_000).contains(&self.batch_size) {
+            return Err("batch_size must be between 1 and 100000".to_string());
+        }
+        Ok(())
+    }
+
+    /// Copy that is safe to return from the API
+    pub fn redacted(&self) -> Self {
+        Self {
+            password: String::new(),
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DbPollerRun {
+    /// Start of the poll, in microseconds
+    pub timestamp: i64,
+    /// Rows ingested
+    pub rows: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct DbPollerList {
+    pub list: Vec<DbPoller>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::json;
+
+    #[test]
+    fn test_db_poller_validate() {
+        let poller: DbPoller = json::from_str(
+            r#"{"name": "audit", "url": "postgres://db:5432/app", "query": "SELECT * FROM audit_log", "watermark_column": "id", "stream_name": "audit"}"#,
+        )
+        .unwrap();
+        assert!(poller.validate().is_ok());
+        assert!(poller.enabled);
+        assert_eq!(poller.driver, DbPollerDriver::Postgres);
+        assert_eq!(poller.interval, 60);
+        assert_eq!(poller.batch_size, 10_000);
+
+        for url in [
+            "mysql://db:3306/app",
+            "postgres://admin:secret@db:5432/app",
+            "db:5432/app",
+        ] {
+            let p = DbPoller {
+                url: url.to_string(),
+                ..poller.clone()
+            };
+            assert!(p.validate().is_err(), "{url}");
+        }
+        let mysql = DbPoller {
+            driver: DbPollerDriver::Mysql,
+            url: "mysql://db:3306/app".to_string(),
+            ..poller.clone()
+        };
+        assert!(mysql.validate().is_ok());
+
+        for column in ["", "id; DROP TABLE x", "created at"] {
+            let p = DbPoller {
+                watermark_column: column.to_string(),
+                ..poller.clone()
+            };
+            assert!(p.validate().is_err(), "{column}");
+        }
+        let p = DbPoller {
+            query: "SELECT 1; DELETE FROM audit_log".to_string(),
+            ..poller.clone()
+        };
+        assert!(p.validate().is_err());
+        let p = DbPoller {
+            batch_size: 0,
+            ..poller
+        };
+        assert!(p.validate().is_err());
+    }
+
+    #[test]
+    fn test_watermark_serde() {
+        let w = Watermark::Timestamp(1_700_000_000_000_000);
+        let v = json::to_value(&w).unwrap();
+        assert_eq!(
+            v,
+            json::json!({"type": "timestamp", "value": 1_700_000_000_000_000i64})
+        );
+        assert_eq!(json::from_value::<Watermark>(v).unwrap(), w);
+    }
+}
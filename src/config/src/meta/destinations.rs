@@ -88,6 +88,10 @@ pub enum DestinationType {
     Http(Endpoint),
     Email(Email),
     Sns(AwsSns),
+    Kafka(KafkaTopic),
+    Jira(Jira),
+    #[serde(rename = "servicenow")]
+    ServiceNow(ServiceNow),
 }
 
 impl Default for DestinationType {
@@ -100,6 +104,21 @@ impl Default for DestinationType {
 #[serde(default)]
 pub struct Email {
     pub recipients: Vec<String>,
+    /// Whether the rows which triggered the alert are attached as CSV
+    pub attach_csv: bool,
+}
+
+/// An email sent by an email alert destination
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct EmailDelivery {
+    /// When it was sent, in microseconds
+    pub timestamp: i64,
+    pub alert_name: String,
+    pub recipients: Vec<String>,
+    /// Whether the SMTP server accepted it
+    pub delivered: bool,
+    /// Response of the SMTP server, or the error
+    pub response: String,
 }
 
 #[derive(Serialize, Debug, PartialEq, Eq, Deserialize, Clone, Default)]
@@ -121,6 +140,14 @@ pub struct Endpoint {
     pub destination_type: Option<String>,
     #[serde(default)]
     pub metadata: HashMap<String, String>,
+    /// VRL program rendering the body and headers of the alert notifications
+    /// instead of the template
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vrl_template: Option<String>,
+    /// Proxy the requests of the destination go through, e.g.
+    /// `http://proxy.example.com:3128`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
@@ -130,6 +157,97 @@ pub struct AwsSns {
     pub aws_region: String,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct KafkaTopic {
+    /// Comma separated list of host:port bootstrap brokers
+    pub brokers: String,
+    pub topic: String,
+    /// SASL/PLAIN user name, SASL is disabled when empty
+    pub sasl_username: String,
+    pub sasl_password: String,
+    pub tls_enabled: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Jira {
+    /// Base url of the Jira site, e.g. `https://example.atlassian.net`
+    pub url: String,
+    /// User the issues are opened by, with one of its API tokens
+    pub username: String,
+    pub api_token: String,
+    pub project_key: String,
+    pub issue_type: String,
+    /// Fields of the issue set from the labels of the alert, e.g.
+    /// `{"priority": "{severity}"}`, a `{label}` is replaced by the context
+    /// attribute of the alert, or the field of the first row, of that name
+    pub fields: HashMap<String, String>,
+    /// Proxy the requests to the Jira site go through
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ServiceNow {
+    /// Url of the instance, e.g. `https://example.service-now.com`
+    pub url: String,
+    pub username: String,
+    pub password: String,
+    /// Table the incidents are opened in, `incident` when empty. With
+    /// `import_set` it is the staging table of an import set, whose
+    /// transform map opens and closes the incidents
+    pub table: String,
+    pub import_set: bool,
+    /// Label of the alert its severity is read from, `severity` when empty
+    pub severity_label: String,
+    /// Urgency and impact of the incidents by severity, e.g.
+    /// `{"critical": "1"}`, the severities not mapped leave them unset
+    pub urgency_mapping: HashMap<String, String>,
+    pub impact_mapping: HashMap<String, String>,
+    /// Fields of the incident set from the labels of the alert, e.g.
+    /// `{"assignment_group": "{team}"}`, a `{label}` is replaced by the
+    /// context attribute of the alert, or the field of the first row, of that
+    /// name
+    pub fields: HashMap<String, String>,
+    /// Whether the incident is resolved once the alert is not satisfied
+    /// anymore
+    pub close_on_resolve: bool,
+    /// Proxy the requests to the instance go through
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+}
+
+/// Version of [`KafkaAlertMessage`], only bumped by changes consumers can
+/// not ignore, fields may be added without a bump
+pub const KAFKA_ALERT_MESSAGE_VERSION: u32 = 1;
+
+/// The notification published to a Kafka destination, one record per
+/// notification, keyed by the alert so the notifications of an alert keep
+/// their order
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct KafkaAlertMessage {
+    pub version: u32,
+    pub org_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alert_id: Option<String>,
+    pub alert_name: String,
+    pub stream_type: String,
+    pub stream_name: String,
+    pub is_real_time: bool,
+    /// When the alert was evaluated, in microseconds
+    pub evaluation_timestamp: i64,
+    /// Time range of the rows, in microseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<i64>,
+    pub end_time: i64,
+    /// The rows which triggered the alert
+    pub rows: Vec<Value>,
+    /// The template of the destination rendered for the rows
+    pub message: String,
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum HTTPType {
@@ -355,6 +473,8 @@ mod tests {
             output_format: Some(HTTPOutputFormat::JSON),
             destination_type: Some("custom".to_string()),
             metadata: HashMap::new(),
+            vrl_template: None,
+            proxy: None,
         };
 
         assert_eq!(endpoint.url, "https://api.example.com");
@@ -411,6 +531,7 @@ mod tests {
                 template: Some("alert_template".to_string()),
                 destination_type: DestinationType::Email(Email {
                     recipients: vec!["user@example.com".to_string()],
+                    attach_csv: false,
                 }),
             },
         };
@@ -434,6 +555,8 @@ mod tests {
             output_format: None,
             destination_type: Some("openobserve".to_string()),
             metadata: HashMap::new(),
+            vrl_template: None,
+            proxy: None,
         };
 
         let dest_type = DestinationType::Http(endpoint.clone());
@@ -447,12 +570,66 @@ mod tests {
                 "admin@example.com".to_string(),
                 "user@example.com".to_string(),
             ],
+            attach_csv: false,
         };
 
         let dest_type = DestinationType::Email(email.clone());
         assert!(matches!(dest_type, DestinationType::Email(_)));
     }
 
+    #[test]
+    fn test_destination_type_kafka() {
+        let dest_type: DestinationType = serde_json::from_value(serde_json::json!({
+            "type": "kafka",
+            "brokers": "kafka-1:9092,kafka-2:9092",
+            "topic": "alerts"
+        }))
+        .unwrap();
+        let DestinationType::Kafka(kafka) = dest_type else {
+            panic!("expected a kafka destination");
+        };
+        assert_eq!(kafka.topic, "alerts");
+        assert!(kafka.sasl_username.is_empty());
+        assert!(!kafka.tls_enabled);
+    }
+
+    #[test]
+    fn test_destination_type_jira() {
+        let dest_type: DestinationType = serde_json::from_value(serde_json::json!({
+            "type": "jira",
+            "url": "https://example.atlassian.net",
+            "project_key": "OPS",
+            "issue_type": "Incident",
+            "fields": {"priority": "{severity}"}
+        }))
+        .unwrap();
+        let DestinationType::Jira(jira) = dest_type else {
+            panic!("expected a jira destination");
+        };
+        assert_eq!(jira.project_key, "OPS");
+        assert_eq!(jira.fields.get("priority").unwrap(), "{severity}");
+        assert!(jira.api_token.is_empty());
+    }
+
+    #[test]
+    fn test_destination_type_servicenow() {
+        let dest_type: DestinationType = serde_json::from_value(serde_json::json!({
+            "type": "servicenow",
+            "url": "https://example.service-now.com",
+            "username": "o2",
+            "urgency_mapping": {"critical": "1"},
+            "close_on_resolve": true
+        }))
+        .unwrap();
+        let DestinationType::ServiceNow(servicenow) = dest_type else {
+            panic!("expected a servicenow destination");
+        };
+        assert_eq!(servicenow.urgency_mapping.get("critical").unwrap(), "1");
+        assert!(servicenow.table.is_empty());
+        assert!(!servicenow.import_set);
+        assert!(servicenow.close_on_resolve);
+    }
+
     #[test]
     fn test_destination_type_sns() {
         let sns = AwsSns {
@@ -494,6 +671,8 @@ mod tests {
             output_format: None,
             destination_type: Some("splunk".to_string()),
             metadata: HashMap::new(),
+            vrl_template: None,
+            proxy: None,
         };
 
         let module = Module::Pipeline {
@@ -0,0 +1,239 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::meta::stream::StreamType;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Parquet,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Parquet => "parquet",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportProvider {
+    #[default]
+    S3,
+    Gcs,
+}
+
+/// Object store the scheduled exports of an org are written to. The
+/// credentials are required, the ones of the node running the export are
+/// never used.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ExportDestination {
+    #[serde(default)]
+    pub provider: ExportProvider,
+    pub bucket: String,
+    /// Key prefix of the exported objects
+    #[serde(default)]
+    pub prefix: String,
+    #[serde(default)]
+    pub region: String,
+    /// Custom endpoint of S3 compatible stores
+    #[serde(default)]
+    pub endpoint: String,
+    #[serde(default)]
+    pub access_key: String,
+    #[serde(default)]
+    pub secret_key: String,
+    /// Service account key, in JSON, for gcs
+    #[serde(default)]
+    pub service_account_key: String,
+}
+
+impl ExportDestination {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.bucket.trim().is_empty() {
+            return Err("bucket is required".to_string());
+        }
+        match self.provider {
+            ExportProvider::S3 if self.access_key.is_empty() || self.secret_key.is_empty() => {
+                Err("access_key and secret_key are required".to_string())
+            }
+            ExportProvider::Gcs if self.service_account_key.is_empty() => {
+                Err("service_account_key is required".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Hides the credentials, for responses
+    pub fn masked(mut self) -> Self {
+        for secret in [
+            &mut self.access_key,
+            &mut self.secret_key,
+            &mut self.service_account_key,
+        ] {
+            if !secret.is_empty() {
+                *secret = "*".repeat(8);
+            }
+        }
+        self
+    }
+}
+
+/// A search run on a schedule, its results written to the org destination
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ScheduledExport {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub stream_type: StreamType,
+    pub sql: String,
+    /// Minutes between two runs
+    pub frequency: i64,
+    /// Minutes of data each run exports, ending at the run time. Same as the
+    /// frequency when empty, so the runs cover consecutive windows
+    #[serde(default)]
+    pub period: i64,
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// Directory under the destination prefix, the export id when empty
+    #[serde(default)]
+    pub path: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// User the search runs as, the last one who saved the export
+    #[serde(default)]
+    pub owner: String,
+    #[serde(default)]
+    pub last_run: Option<ExportRun>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl ScheduledExport {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("name is required".to_string());
+        }
+        if self.sql.trim().is_empty() {
+            return Err("sql is required".to_string());
+        }
+        if self.frequency < 1 {
+            return Err("frequency must be at least 1 minute".to_string());
+        }
+        if self.period < 0 {
+            return Err("period must not be negative".to_string());
+        }
+        if self.path.split('/').any(|v| v == "..") {
+            return Err("path must not contain '..'".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn period(&self) -> i64 {
+        if self.period > 0 {
+            self.period
+        } else {
+            self.frequency
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ExportRun {
+    /// End of the exported window, in microseconds
+    pub end_time: i64,
+    pub rows: usize,
+    /// Object written, empty when the run failed
+    #[serde(default)]
+    pub object: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct ScheduledExportList {
+    pub list: Vec<ScheduledExport>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::json;
+
+    #[test]
+    fn test_scheduled_export_defaults() {
+        let export: ScheduledExport = json::from_str(
+            r#"{"name": "daily", "sql": "SELECT * FROM \"app\"", "frequency": 1440}"#,
+        )
+        .unwrap();
+        assert!(export.validate().is_ok());
+        assert!(export.enabled);
+        assert_eq!(export.format, ExportFormat::Csv);
+        assert_eq!(export.stream_type, StreamType::Logs);
+        assert_eq!(export.period(), 1440);
+
+        let export = ScheduledExport {
+            path: "../other".to_string(),
+            ..export
+        };
+        assert!(export.validate().is_err());
+    }
+
+    #[test]
+    fn test_export_destination_masked() {
+        let dest = ExportDestination {
+            bucket: "exports".to_string(),
+            access_key: "key".to_string(),
+            secret_key: "secret".to_string(),
+            ..Default::default()
+        };
+        assert!(dest.validate().is_ok());
+        let masked = dest.masked();
+        assert_eq!(masked.secret_key, "********");
+        assert!(masked.service_account_key.is_empty());
+
+        let dest = ExportDestination {
+            bucket: "exports".to_string(),
+            access_key: "key".to_string(),
+            ..Default::default()
+        };
+        assert!(dest.validate().is_err());
+
+        // the credentials of the node are never used
+        let dest = ExportDestination {
+            bucket: "exports".to_string(),
+            ..Default::default()
+        };
+        assert!(dest.validate().is_err());
+        let dest = ExportDestination {
+            provider: ExportProvider::Gcs,
+            bucket: "exports".to_string(),
+            service_account_key: "{}".to_string(),
+            ..Default::default()
+        };
+        assert!(dest.validate().is_ok());
+    }
+}
@@ -82,17 +82,130 @@ impl Transform {
     }
 }
 
+/// Number of records read from the stream when testing a function on a
+/// stream, by default and at most
+pub const TEST_STREAM_SAMPLE_SIZE: usize = 10;
+pub const TEST_STREAM_MAX_SAMPLE_SIZE: usize = 100;
+
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct TestVRLRequest {
-    pub function: String,         // Transform function as a string (VRL or JS)
+    pub function: String, // Transform function as a string (VRL or JS)
+    #[serde(default)]
     pub events: Vec<json::Value>, // List of events (JSON objects)
     #[serde(default)]
     pub trans_type: Option<u8>, // Optional: 0=vrl, 1=js. Auto-detected if not provided
+    /// Stream whose latest records are the events, when no events are given
+    #[serde(default)]
+    pub stream_name: Option<String>,
+    #[serde(default)]
+    pub stream_type: Option<StreamType>,
+    /// Number of records read from the stream
+    #[serde(default)]
+    pub size: Option<usize>,
+}
+
+impl TestVRLRequest {
+    pub fn sample_size(&self) -> usize {
+        self.size
+            .unwrap_or(TEST_STREAM_SAMPLE_SIZE)
+            .clamp(1, TEST_STREAM_MAX_SAMPLE_SIZE)
+    }
+}
+
+/// Number of versions kept for each function
+pub const FUNCTION_MAX_VERSIONS: usize = 50;
+
+/// A saved version of a function, recorded each time the function changes
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FunctionVersion {
+    pub version: u32,
+    pub function: String,
+    #[serde(default)]
+    pub params: String,
+    #[serde(default = "default_trans_type")]
+    pub trans_type: Option<u8>,
+    /// Time the version was saved, in microseconds
+    #[serde(default)]
+    pub created_at: i64,
+}
+
+impl FunctionVersion {
+    pub fn new(version: u32, func: &Transform) -> Self {
+        Self {
+            version,
+            function: func.function.clone(),
+            params: func.params.clone(),
+            trans_type: func.trans_type,
+            created_at: crate::utils::time::now_micros(),
+        }
+    }
+
+    /// The function with the content of this version
+    pub fn apply_to(&self, func: &Transform) -> Transform {
+        Transform {
+            function: self.function.clone(),
+            params: self.params.clone(),
+            trans_type: self.trans_type,
+            ..func.clone()
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct FunctionVersionList {
+    pub list: Vec<FunctionVersion>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct RollbackFunctionRequest {
+    pub version: u32,
+}
+
+/// Dry run of a saved function, or one of its versions, on sample events
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct TestFunctionRequest {
+    /// Version to run, the current function when not given
+    #[serde(default)]
+    pub version: Option<u32>,
+    #[serde(default)]
+    pub events: Vec<json::Value>,
+    /// Stream whose latest records are the events, when no events are given
+    #[serde(default)]
+    pub stream_name: Option<String>,
+    #[serde(default)]
+    pub stream_type: Option<StreamType>,
+    /// Number of records read from the stream
+    #[serde(default)]
+    pub size: Option<usize>,
+}
+
+impl TestFunctionRequest {
+    pub fn sample_size(&self) -> usize {
+        self.size
+            .unwrap_or(TEST_STREAM_SAMPLE_SIZE)
+            .clamp(1, TEST_STREAM_MAX_SAMPLE_SIZE)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct TestVRLResponse {
     pub results: Vec<VRLResult>, // Transformed events
+    /// Number of events the function failed on
+    #[serde(default)]
+    pub errors: usize,
+    /// Time the function took over all the events, in microseconds
+    #[serde(default)]
+    pub took_in_us: u64,
+}
+
+impl TestVRLResponse {
+    pub fn new(results: Vec<VRLResult>, took: std::time::Duration) -> Self {
+        Self {
+            errors: results.iter().filter(|r| !r.message.is_empty()).count(),
+            results,
+            took_in_us: took.as_micros() as u64,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
@@ -314,6 +427,29 @@ mod tests {
         assert_eq!(result.event, event);
     }
 
+    #[test]
+    fn test_test_vrl_request_sample_size() {
+        let req: TestVRLRequest =
+            json::from_str(r#"{"function": ".", "stream_name": "app"}"#).unwrap();
+        assert!(req.events.is_empty());
+        assert_eq!(req.sample_size(), TEST_STREAM_SAMPLE_SIZE);
+        let req = TestVRLRequest {
+            size: Some(1000),
+            ..req
+        };
+        assert_eq!(req.sample_size(), TEST_STREAM_MAX_SAMPLE_SIZE);
+
+        let resp = TestVRLResponse::new(
+            vec![
+                VRLResult::new("", json::json!({})),
+                VRLResult::new("error", json::json!({})),
+            ],
+            std::time::Duration::from_millis(2),
+        );
+        assert_eq!(resp.errors, 1);
+        assert_eq!(resp.took_in_us, 2000);
+    }
+
     #[test]
     fn test_default_trans_type() {
         let trans = Transform {
@@ -326,4 +462,28 @@ mod tests {
         };
         assert_eq!(trans.trans_type, Some(0));
     }
+
+    #[test]
+    fn test_function_version_apply_to() {
+        let current = Transform {
+            function: ".b = 2 \n .".to_string(),
+            name: "test".to_string(),
+            params: "row".to_string(),
+            num_args: 1,
+            trans_type: Some(0),
+            streams: None,
+        };
+        let old = Transform {
+            function: ".a = 1 \n .".to_string(),
+            ..current.clone()
+        };
+        let version = FunctionVersion::new(1, &old);
+        assert_eq!(version.version, 1);
+        assert!(version.created_at > 0);
+
+        let restored = version.apply_to(&current);
+        assert_eq!(restored.name, "test");
+        assert_eq!(restored, old);
+        assert_ne!(restored, current);
+    }
 }
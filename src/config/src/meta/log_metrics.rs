@@ -0,0 +1,183 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::meta::recording_rules::{is_valid_label_name, is_valid_metric_name};
+
+/// Label of the node which counted the series, every ingester writes its own
+/// cumulative series
+pub const INSTANCE_LABEL: &str = "instance";
+
+/// Maximum number of labels of a log metric
+pub const MAX_LABELS: usize = 10;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LogMetricKind {
+    /// Counts the matching records, or adds up `value_field` when it is set
+    #[default]
+    Counter,
+    /// Observes `value_field` into buckets, written as the `_bucket`, `_sum`
+    /// and `_count` series of a Prometheus histogram
+    Histogram,
+}
+
+/// A metric extracted from the records of a logs stream at ingestion, e.g.
+/// the requests by `status_code`, so dashboards read the metric instead of
+/// aggregating the logs on every refresh
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct LogMetricRule {
+    #[serde(default)]
+    pub id: String,
+    /// Name of the metric, e.g. `http_requests_total`
+    pub name: String,
+    /// Logs stream whose records are observed
+    pub stream_name: String,
+    #[serde(default)]
+    pub kind: LogMetricKind,
+    /// Only the records whose fields have these values are observed
+    #[serde(default)]
+    pub filters: BTreeMap<String, String>,
+    /// Fields whose values label the series, a record without the field gets
+    /// an empty label
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Numeric field observed, e.g. `duration`. Required by histograms, the
+    /// records without it are skipped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_field: Option<String>,
+    /// Upper bounds of the buckets of a histogram
+    #[serde(default = "default_buckets")]
+    pub buckets: Vec<f64>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+/// The default buckets of the Prometheus clients
+fn default_buckets() -> Vec<f64> {
+    vec![
+        0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+    ]
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl LogMetricRule {
+    pub fn validate(&self) -> Result<(), String> {
+        if !is_valid_metric_name(&self.name) {
+            return Err(format!("invalid metric name: {}", self.name));
+        }
+        if self.stream_name.trim().is_empty() {
+            return Err("stream_name is required".to_string());
+        }
+        if self.labels.len() > MAX_LABELS {
+            return Err(format!("a log metric can have at most {MAX_LABELS} labels"));
+        }
+        for (i, name) in self.labels.iter().enumerate() {
+            if name.starts_with("__")
+                || name == INSTANCE_LABEL
+                || (self.kind == LogMetricKind::Histogram && name == "le")
+                || !is_valid_label_name(name)
+            {
+                return Err(format!("invalid label name: {name}"));
+            }
+            if self.labels[..i].contains(name) {
+                return Err(format!("duplicated label: {name}"));
+            }
+        }
+        if self
+            .value_field
+            .as_ref()
+            .is_some_and(|v| v.trim().is_empty())
+        {
+            return Err("value_field can't be empty".to_string());
+        }
+        if self.kind == LogMetricKind::Histogram {
+            if self.value_field.is_none() {
+                return Err("value_field is required by histograms".to_string());
+            }
+            if self.buckets.is_empty()
+                || self.buckets.iter().any(|v| !v.is_finite())
+                || self.buckets.windows(2).any(|w| w[0] >= w[1])
+            {
+                return Err("buckets must be finite and in increasing order".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct LogMetricRuleList {
+    pub list: Vec<LogMetricRule>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::json;
+
+    #[test]
+    fn test_log_metric_rule_validate() {
+        let rule: LogMetricRule = json::from_str(
+            r#"{"name": "http_requests_total", "stream_name": "nginx", "labels": ["status_code"]}"#,
+        )
+        .unwrap();
+        assert!(rule.validate().is_ok());
+        assert!(rule.enabled);
+        assert_eq!(rule.kind, LogMetricKind::Counter);
+
+        for label in ["__name__", "instance", "status-code"] {
+            let rule = LogMetricRule {
+                labels: vec![label.to_string()],
+                ..rule.clone()
+            };
+            assert!(rule.validate().is_err(), "{label}");
+        }
+        let dup = LogMetricRule {
+            labels: vec!["method".to_string(), "method".to_string()],
+            ..rule.clone()
+        };
+        assert!(dup.validate().is_err());
+
+        let histogram = LogMetricRule {
+            name: "http_request_duration_seconds".to_string(),
+            kind: LogMetricKind::Histogram,
+            ..rule.clone()
+        };
+        assert!(histogram.validate().is_err());
+        let histogram = LogMetricRule {
+            value_field: Some("duration".to_string()),
+            ..histogram
+        };
+        assert!(histogram.validate().is_ok());
+        let le = LogMetricRule {
+            labels: vec!["le".to_string()],
+            ..histogram.clone()
+        };
+        assert!(le.validate().is_err());
+        let unsorted = LogMetricRule {
+            buckets: vec![1.0, 0.5],
+            ..histogram
+        };
+        assert!(unsorted.validate().is_err());
+    }
+}
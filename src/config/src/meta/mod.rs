@@ -16,24 +16,36 @@
 pub mod actions;
 pub mod ai;
 pub mod alerts;
+pub mod anomalies;
+pub mod async_search;
 pub mod bitvec;
 pub mod cluster;
 pub mod correlation;
 pub mod dashboards;
+pub mod dataset;
+pub mod db_pollers;
 pub mod destinations;
+pub mod exports;
 pub mod enrichment_table;
 pub mod folder;
 pub mod function;
 pub mod inverted_index;
+pub mod log_metrics;
 pub mod logger;
 pub mod meta_store;
 pub mod organization;
 pub mod otlp;
 pub mod pipeline;
+pub mod patterns;
+pub mod pivot;
 pub mod plan;
 pub mod projections;
 pub mod promql;
+pub mod query_diff;
 pub mod ratelimit;
+pub mod record_deletion;
+pub mod recording_rules;
+pub mod replay;
 pub mod search;
 pub mod self_reporting;
 pub mod service_graph;
@@ -0,0 +1,151 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+pub const DEFAULT_SIMILARITY: f64 = 0.5;
+pub const DEFAULT_TOP_K: usize = 100;
+
+/// Request to mine the message templates of a logs stream
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct PatternRequest {
+    /// Start of the time range (microseconds since epoch)
+    pub start_time: i64,
+    /// End of the time range (microseconds since epoch)
+    pub end_time: i64,
+    /// Field holding the message, the first full text search field of the
+    /// stream when empty
+    #[serde(default)]
+    pub field: Option<String>,
+    /// SQL condition applied before mining, e.g. `level = 'error'`. A single
+    /// condition over the fields of the stream, subqueries are rejected
+    #[serde(default)]
+    pub filter: Option<String>,
+    /// Maximum number of logs to sample, capped by ZO_PATTERN_MINING_MAX_LOGS
+    #[serde(default)]
+    pub size: Option<usize>,
+    /// Minimum share of equal tokens for a message to join a template, 0.5
+    /// when empty
+    #[serde(default)]
+    pub similarity: Option<f64>,
+    /// Maximum number of patterns returned, 100 when empty
+    #[serde(default)]
+    pub top_k: Option<usize>,
+    /// Start of the baseline time range, patterns not seen in the baseline
+    /// are flagged as new
+    #[serde(default)]
+    pub baseline_start_time: Option<i64>,
+    /// End of the baseline time range
+    #[serde(default)]
+    pub baseline_end_time: Option<i64>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct PatternResponse {
+    pub field: String,
+    /// Number of logs sampled, at most `size`
+    pub total_logs: u64,
+    pub total_patterns: usize,
+    /// Number of patterns not seen in the baseline time range
+    pub new_patterns: usize,
+    pub patterns: Vec<LogPattern>,
+    pub took: usize,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct LogPattern {
+    /// Message template, variable tokens are replaced by `<*>`
+    pub template: String,
+    /// Number of the sampled logs matching the template, not of all the logs
+    /// of the time range
+    pub count: u64,
+    /// Share of the sampled logs matching the template (0-100)
+    pub percentage: f64,
+    pub examples: Vec<String>,
+    pub first_seen: i64,
+    pub last_seen: i64,
+    #[serde(default)]
+    pub is_new: bool,
+}
+
+/// Logs sampled by a run of the background pattern mining of a stream
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PatternWindow {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub total_logs: u64,
+}
+
+/// Sampled logs of a pattern in the window starting at `start_time`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PatternWindowCount {
+    pub start_time: i64,
+    pub count: u64,
+}
+
+/// A pattern followed by the background pattern mining of a stream
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TrackedPattern {
+    pub template: String,
+    pub examples: Vec<String>,
+    pub first_seen: i64,
+    pub last_seen: i64,
+    /// Counts of the windows the pattern was seen in, oldest first
+    pub counts: Vec<PatternWindowCount>,
+}
+
+/// Patterns of a stream mined in the background, kept for a week
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StreamPatternStats {
+    pub field: String,
+    /// Oldest first
+    pub windows: Vec<PatternWindow>,
+    pub patterns: Vec<TrackedPattern>,
+}
+
+/// A pattern of a stream over the recent windows compared to the earlier ones
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct PatternTrend {
+    pub template: String,
+    /// Sampled logs of the pattern in the recent windows
+    pub count: u64,
+    /// Share of the sampled logs of the recent windows (0-100)
+    pub percentage: f64,
+    /// Share of the sampled logs of the earlier windows (0-100)
+    pub baseline_percentage: f64,
+    pub examples: Vec<String>,
+    pub first_seen: i64,
+    pub last_seen: i64,
+    /// First seen in the recent windows
+    pub is_new: bool,
+    /// Its share of the recent windows is several times the one of the
+    /// earlier windows
+    pub is_anomalous: bool,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct PatternTrendResponse {
+    pub field: String,
+    /// Start of the recent windows
+    pub start_time: i64,
+    /// End of the last mined window, 0 when the stream wasn't mined yet
+    pub end_time: i64,
+    pub total_logs: u64,
+    pub new_patterns: usize,
+    pub anomalous_patterns: usize,
+    /// New patterns first, then the anomalous ones, then by count
+    pub patterns: Vec<PatternTrend>,
+}
@@ -0,0 +1,85 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{meta::stream::StreamType, utils::json};
+
+/// Keys the related events share with the record when the request sets none
+pub const DEFAULT_PIVOT_KEYS: [&str; 3] = ["host", "container_id", "trace_id"];
+
+/// Request for the events around a record in the streams related to it, which
+/// are the ones sharing one of the keys of the record
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct PivotRequest {
+    /// Stream of the record
+    pub stream_name: String,
+    #[serde(default)]
+    pub stream_type: StreamType,
+    /// Timestamp of the record, in microseconds
+    pub timestamp: i64,
+    /// The record, read from the stream at the timestamp when not given
+    #[serde(default)]
+    #[schema(value_type = Option<Object>)]
+    pub record: Option<json::Map<String, json::Value>>,
+    /// Keys shared with the related events, host, container_id and trace_id
+    /// when empty. A key also matches the fields of its semantic field group.
+    #[serde(default)]
+    pub keys: Vec<String>,
+    /// Streams to search, every logs and traces stream having one of the keys
+    /// when empty
+    #[serde(default)]
+    pub streams: Vec<PivotStream>,
+    /// Seconds searched before and after the record, 300 when empty
+    #[serde(default)]
+    pub window: Option<i64>,
+    /// Events returned for each stream, half before and half after the record,
+    /// 20 when empty
+    #[serde(default)]
+    pub size: Option<i64>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub struct PivotStream {
+    pub stream_name: String,
+    #[serde(default)]
+    pub stream_type: StreamType,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct PivotResponse {
+    pub took: usize,
+    pub timestamp: i64,
+    /// Values of the keys in the record
+    #[schema(value_type = Object)]
+    pub keys: json::Map<String, json::Value>,
+    pub streams: Vec<PivotStreamHits>,
+}
+
+/// Events of a related stream, newest first
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct PivotStreamHits {
+    pub stream_name: String,
+    pub stream_type: StreamType,
+    /// Fields of the stream matched against the keys of the record
+    pub fields: Vec<String>,
+    #[schema(value_type = Vec<Object>)]
+    pub hits: Vec<json::Value>,
+    /// Set when the stream could not be searched, the other streams are still
+    /// returned
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
@@ -300,6 +300,30 @@ pub struct RequestFormatQuery {
     pub query: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct RequestTranslateQuery {
+    /// PromQL expression to translate to SQL
+    #[serde(default)]
+    pub query: Option<String>,
+    /// SQL query to translate to PromQL
+    #[serde(default)]
+    pub sql: Option<String>,
+    /// Time bucket of the translated aggregations, duration or float number of
+    /// seconds
+    #[serde(default)]
+    pub step: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TranslateQueryResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sql: Option<String>,
+    /// Where the results of the translation can differ from the original
+    pub warnings: Vec<String>,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Function {
     Avg,
@@ -362,6 +386,90 @@ impl DownsamplingRule {
     }
 }
 
+/// Marks the streams written by the rollups, `{stream}__rollup_{level}`
+pub const ROLLUP_STREAM_INFIX: &str = "__rollup_";
+
+/// A lower resolution copy of the metrics streams, e.g. `5m:avg:30d` keeps
+/// the 5 minutes averages of the gauges for 30 days. The counters, histograms
+/// and summaries are always rolled up with `max`, the last value of a
+/// monotonic series in the step. PromQL only reads a rollup for the
+/// `*_over_time` function of a range selector matching the rule's function.
+#[derive(Debug, Clone)]
+pub struct RollupRule {
+    /// The step as configured, e.g. `5m`, used in the stream name
+    pub level: String,
+    pub step: i64, // seconds
+    pub function: Function,
+    pub retention_days: i64,
+}
+
+impl RollupRule {
+    pub fn step_micros(&self) -> i64 {
+        self.step * 1_000_000
+    }
+
+    pub fn stream_name(&self, stream_name: &str) -> String {
+        format!("{stream_name}{ROLLUP_STREAM_INFIX}{}", self.level)
+    }
+}
+
+pub fn is_rollup_stream(stream_name: &str) -> bool {
+    stream_name.contains(ROLLUP_STREAM_INFIX)
+}
+
+/// Parses `ZO_COMPACT_METRICS_ROLLUP_RULES`, comma separated
+/// `{step}:{function}:{retention}` levels, sorted by step
+pub fn parse_rollup_rules(s: &str) -> Result<Vec<RollupRule>, anyhow::Error> {
+    let mut rules = Vec::new();
+    for item in s.split(',').map(str::trim).filter(|v| !v.is_empty()) {
+        let parts = item.split(':').map(str::trim).collect::<Vec<_>>();
+        let [level, function, retention] = parts[..] else {
+            return Err(anyhow::anyhow!(
+                "invalid metrics rollup rule: {item}, must be step:function:retention"
+            ));
+        };
+        let step = (crate::utils::time::parse_milliseconds(level)? / 1000) as i64;
+        if step < 60 {
+            return Err(anyhow::anyhow!(
+                "invalid metrics rollup rule: {item}, step must be at least 1m"
+            ));
+        }
+        let function = match function.to_lowercase().as_str() {
+            "avg" | "sum" | "count" | "min" | "max" => Function::from(function),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "invalid metrics rollup rule: {item}, function must be one of avg, sum, count, min, max"
+                ));
+            }
+        };
+        let retention_days =
+            (crate::utils::time::parse_milliseconds(retention)? / 86_400_000) as i64;
+        if retention_days < 1 {
+            return Err(anyhow::anyhow!(
+                "invalid metrics rollup rule: {item}, retention must be at least 1d"
+            ));
+        }
+        if rules.iter().any(|r: &RollupRule| r.step == step) {
+            return Err(anyhow::anyhow!(
+                "invalid metrics rollup rule: {item}, duplicated step"
+            ));
+        }
+        rules.push(RollupRule {
+            level: level.to_string(),
+            step,
+            function,
+            retention_days,
+        });
+    }
+    rules.sort_by_key(|r| r.step);
+    Ok(rules)
+}
+
+/// The rollup levels, checked when the config is loaded
+pub fn get_rollup_rules() -> Vec<RollupRule> {
+    parse_rollup_rules(&crate::get_config().compact.metrics_rollup_rules).unwrap_or_default()
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryResult {
@@ -440,6 +548,28 @@ mod tests {
         assert_eq!(MetricType::Unknown.to_string(), "unknown");
     }
 
+    #[test]
+    fn test_parse_rollup_rules() {
+        let rules = parse_rollup_rules("1h:max:365d, 5m:avg:30d").unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].level, "5m");
+        assert_eq!(rules[0].step, 300);
+        assert_eq!(rules[0].function, Function::Avg);
+        assert_eq!(rules[0].retention_days, 30);
+        assert_eq!(rules[1].step, 3600);
+        assert_eq!(rules[1].function, Function::Max);
+        assert_eq!(rules[0].stream_name("cpu_usage"), "cpu_usage__rollup_5m");
+        assert!(is_rollup_stream("cpu_usage__rollup_5m"));
+        assert!(!is_rollup_stream("cpu_usage"));
+
+        assert!(parse_rollup_rules("").unwrap().is_empty());
+        assert!(parse_rollup_rules("5m:avg").is_err());
+        assert!(parse_rollup_rules("30s:avg:30d").is_err());
+        assert!(parse_rollup_rules("5m:last:30d").is_err());
+        assert!(parse_rollup_rules("5m:avg:1h").is_err());
+        assert!(parse_rollup_rules("5m:avg:30d,300:sum:30d").is_err());
+    }
+
     #[test]
     fn test_deserialize_string_or_vec() {
         // Test with comma-separated string
@@ -0,0 +1,117 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{meta::search::Query, utils::json};
+
+pub const DEFAULT_DIFF_THRESHOLD: f64 = 10.0;
+
+/// Request to run a query over a baseline and a comparison, and diff the rows
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct QueryDiffRequest {
+    /// Baseline query, usually grouped
+    pub query: Query,
+    /// What the comparison run changes from the baseline
+    pub compare: CompareTarget,
+    /// Columns identifying a group, every non numeric column except the
+    /// timestamp when empty
+    #[serde(default)]
+    pub group_by: Vec<String>,
+    /// Numeric columns compared, every numeric column when empty
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// Relative change, in percent, above which a value is reported, 10 when
+    /// empty. Every change is reported when 0
+    #[serde(default)]
+    pub threshold: Option<f64>,
+}
+
+/// The comparison run uses the baseline query with these changes
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct CompareTarget {
+    #[serde(default)]
+    pub start_time: Option<i64>,
+    #[serde(default)]
+    pub end_time: Option<i64>,
+    /// Stream queried instead of the stream of the baseline query
+    #[serde(default)]
+    pub stream: Option<String>,
+}
+
+impl CompareTarget {
+    pub fn is_empty(&self) -> bool {
+        self.start_time.is_none()
+            && self.end_time.is_none()
+            && self.stream.as_ref().is_none_or(|s| s.is_empty())
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct QueryDiffResponse {
+    pub took: usize,
+    pub threshold: f64,
+    pub baseline_rows: usize,
+    pub compare_rows: usize,
+    /// Groups only found in the comparison
+    pub new_groups: Vec<DiffGroup>,
+    /// Groups only found in the baseline
+    pub missing_groups: Vec<DiffGroup>,
+    /// Values of the groups found on both sides which changed beyond the
+    /// threshold, largest relative change first
+    pub changed: Vec<ValueDelta>,
+    /// Groups found on both sides with no value beyond the threshold
+    pub unchanged_groups: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub function_error: Vec<String>,
+    #[serde(default)]
+    pub is_partial: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DiffGroup {
+    #[schema(value_type = Object)]
+    pub labels: json::Map<String, json::Value>,
+    #[schema(value_type = Object)]
+    pub values: json::Map<String, json::Value>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ValueDelta {
+    #[schema(value_type = Object)]
+    pub labels: json::Map<String, json::Value>,
+    pub field: String,
+    pub baseline: f64,
+    pub compare: f64,
+    /// compare - baseline
+    pub delta: f64,
+    /// Change relative to the baseline, empty when the baseline is 0
+    pub delta_percent: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_target_is_empty() {
+        assert!(CompareTarget::default().is_empty());
+        let target: CompareTarget = json::from_str(r#"{"stream": ""}"#).unwrap();
+        assert!(target.is_empty());
+        let target: CompareTarget = json::from_str(r#"{"start_time": 1}"#).unwrap();
+        assert!(!target.is_empty());
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::meta::stream::StreamType;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordDeletionStatus {
+    #[default]
+    Pending,
+    Running,
+    Completed,
+    /// The files of the range were rewritten but the ones only known from the
+    /// dumped file list, see `files_skipped`
+    Partial,
+    Failed,
+}
+
+/// Deletes the records of a stream matching a filter, e.g. the records of a
+/// user asking to be forgotten
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct RecordDeletionRequest {
+    #[serde(default)]
+    pub stream_type: StreamType,
+    pub stream_name: String,
+    /// SQL condition of the records to delete, like the WHERE clause of a
+    /// search, e.g. `user_id = 'u-42'`
+    pub filter: String,
+    /// In microseconds, the beginning of the stream by default
+    #[serde(default)]
+    pub start_time: i64,
+    /// In microseconds, the creation of the job by default
+    #[serde(default)]
+    pub end_time: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct RecordDeletion {
+    pub id: String,
+    #[serde(flatten)]
+    pub request: RecordDeletionRequest,
+    #[serde(default)]
+    pub status: RecordDeletionStatus,
+    #[serde(default)]
+    pub files_scanned: usize,
+    #[serde(default)]
+    pub files_rewritten: usize,
+    #[serde(default)]
+    pub records_deleted: i64,
+    /// Files of the range only known from the dumped file list, they can't be
+    /// rewritten and may still hold matching records
+    #[serde(default)]
+    pub files_skipped: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub created_by: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct RecordDeletionList {
+    pub list: Vec<RecordDeletion>,
+}
+
+impl RecordDeletionRequest {
+    /// Checks the request and defaults its time range, the records ingested
+    /// after the creation of the job are kept
+    pub fn validate(&mut self, now: i64) -> Result<(), String> {
+        if self.stream_name.is_empty() {
+            return Err("stream_name is required".to_string());
+        }
+        if self.filter.trim().is_empty() {
+            return Err(
+                "filter is required, delete the stream to delete all its records".to_string(),
+            );
+        }
+        if self.end_time == 0 || self.end_time > now {
+            self.end_time = now;
+        }
+        if self.start_time < 0 || self.start_time >= self.end_time {
+            return Err("start_time must be before end_time".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::json;
+
+    #[test]
+    fn test_record_deletion_request_validate() {
+        let now = 1_000;
+        let mut req: RecordDeletionRequest =
+            json::from_str(r#"{"stream_name": "app", "filter": "user_id = 'u-42'"}"#).unwrap();
+        assert_eq!(req.stream_type, StreamType::Logs);
+        assert!(req.validate(now).is_ok());
+        assert_eq!((req.start_time, req.end_time), (0, now));
+
+        let mut future = RecordDeletionRequest {
+            start_time: 100,
+            end_time: 5_000,
+            ..req.clone()
+        };
+        assert!(future.validate(now).is_ok());
+        assert_eq!(future.end_time, now);
+
+        let mut empty = RecordDeletionRequest {
+            filter: " ".to_string(),
+            ..req.clone()
+        };
+        assert!(empty.validate(now).is_err());
+
+        let mut reversed = RecordDeletionRequest {
+            start_time: 500,
+            end_time: 200,
+            ..req
+        };
+        assert!(reversed.validate(now).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::meta::promql::NAME_LABEL;
+
+/// A PromQL expression evaluated on a schedule, its result written as a new
+/// metric, like a Prometheus recording rule
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RecordingRule {
+    #[serde(default)]
+    pub id: String,
+    /// Name of the recorded metric, e.g. `job:http_requests:rate5m`
+    pub name: String,
+    /// PromQL expression returning an instant vector or a scalar
+    pub expr: String,
+    /// Seconds between two evaluations
+    #[serde(default = "default_interval")]
+    pub interval: i64,
+    /// Labels added to the recorded series, replacing the ones of the result
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub last_run: Option<RecordingRuleRun>,
+}
+
+fn default_interval() -> i64 {
+    60
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Prometheus metric names, `[a-zA-Z_:][a-zA-Z0-9_:]*`
+pub(crate) fn is_valid_metric_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == ':')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+}
+
+/// Prometheus label names, `[a-zA-Z_][a-zA-Z0-9_]*`
+pub(crate) fn is_valid_label_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+impl RecordingRule {
+    pub fn validate(&self) -> Result<(), String> {
+        if !is_valid_metric_name(&self.name) {
+            return Err(format!("invalid metric name: {}", self.name));
+        }
+        if self.expr.trim().is_empty() {
+            return Err("expr is required".to_string());
+        }
+        if self.interval < 1 {
+            return Err("interval must be at least 1 second".to_string());
+        }
+        for name in self.labels.keys() {
+            if name == NAME_LABEL || name.starts_with("__") || !is_valid_label_name(name) {
+                return Err(format!("invalid label name: {name}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RecordingRuleRun {
+    /// Evaluation time, in microseconds
+    pub timestamp: i64,
+    /// Series written
+    pub series: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct RecordingRuleList {
+    pub list: Vec<RecordingRule>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::json;
+
+    #[test]
+    fn test_recording_rule_validate() {
+        let rule: RecordingRule = json::from_str(
+            r#"{"name": "job:http_requests:rate5m", "expr": "sum by (job) (rate(http_requests_total[5m]))"}"#,
+        )
+        .unwrap();
+        assert!(rule.validate().is_ok());
+        assert!(rule.enabled);
+        assert_eq!(rule.interval, 60);
+
+        for name in ["", "5xx_rate", "http-requests"] {
+            let rule = RecordingRule {
+                name: name.to_string(),
+                ..rule.clone()
+            };
+            assert!(rule.validate().is_err(), "{name}");
+        }
+        for label in ["__name__", "__tenant", "team:name"] {
+            let rule = RecordingRule {
+                labels: BTreeMap::from([(label.to_string(), "x".to_string())]),
+                ..rule.clone()
+            };
+            assert!(rule.validate().is_err(), "{label}");
+        }
+        let rule = RecordingRule {
+            interval: 0,
+            ..rule
+        };
+        assert!(rule.validate().is_err());
+    }
+}
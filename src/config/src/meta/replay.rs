@@ -0,0 +1,131 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::meta::stream::StreamType;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayStatus {
+    #[default]
+    Running,
+    Completed,
+    Failed,
+}
+
+/// Replays the stored records of a stream over a past time range into another
+/// stream, optionally through a pipeline
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct ReplayRequest {
+    #[serde(default)]
+    pub stream_type: StreamType,
+    pub stream_name: String,
+    /// In microseconds
+    pub start_time: i64,
+    /// In microseconds
+    pub end_time: i64,
+    /// Stream of the same type the replayed records are written to
+    pub destination_stream: String,
+    /// Pipeline the records go through before being written, its outputs are
+    /// all written to the destination stream
+    #[serde(default)]
+    pub pipeline_id: Option<String>,
+    /// Replays the `_original` column, the records as they were received,
+    /// instead of the stored fields. Records without it are replayed as stored
+    #[serde(default)]
+    pub use_original: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct Replay {
+    pub id: String,
+    #[serde(flatten)]
+    pub request: ReplayRequest,
+    #[serde(default)]
+    pub status: ReplayStatus,
+    /// Start of the window being read
+    pub position: i64,
+    /// Records of the window already read
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default)]
+    pub records_read: usize,
+    #[serde(default)]
+    pub records_written: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub created_by: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct ReplayList {
+    pub list: Vec<Replay>,
+}
+
+impl ReplayRequest {
+    pub fn validate(&self, now: i64) -> Result<(), String> {
+        if self.stream_name.is_empty() {
+            return Err("stream_name is required".to_string());
+        }
+        if self.destination_stream.is_empty() {
+            return Err("destination_stream is required".to_string());
+        }
+        if self.destination_stream == self.stream_name {
+            return Err("destination_stream must be another stream".to_string());
+        }
+        if self.start_time >= self.end_time {
+            return Err("start_time must be before end_time".to_string());
+        }
+        if self.end_time > now {
+            return Err("end_time must not be in the future".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::json;
+
+    #[test]
+    fn test_replay_request_validate() {
+        let now = 1_000;
+        let req: ReplayRequest = json::from_str(
+            r#"{"stream_name": "app", "destination_stream": "app_fixed", "start_time": 100, "end_time": 200}"#,
+        )
+        .unwrap();
+        assert_eq!(req.stream_type, StreamType::Logs);
+        assert!(!req.use_original);
+        assert!(req.validate(now).is_ok());
+        assert!(req.validate(150).is_err());
+
+        let same = ReplayRequest {
+            destination_stream: "app".to_string(),
+            ..req.clone()
+        };
+        assert!(same.validate(now).is_err());
+
+        let empty = ReplayRequest {
+            start_time: 200,
+            ..req
+        };
+        assert!(empty.validate(now).is_err());
+    }
+}
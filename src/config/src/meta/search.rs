@@ -1900,6 +1900,113 @@ impl StreamResponses {
     }
 }
 
+/// Search request fanned out to several orgs, by the users of the meta org
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct CrossOrgRequest {
+    pub org_ids: Vec<String>,
+    #[serde(flatten)]
+    pub request: Request,
+}
+
+/// Searches an org can run at once and MB it can scan per minute across the
+/// cluster, 0 falls back to the configured default
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct SearchQuota {
+    #[serde(default)]
+    pub max_concurrent: u64,
+    #[serde(default)]
+    pub scan_mb_per_min: u64,
+}
+
+impl SearchQuota {
+    pub fn is_empty(&self) -> bool {
+        self.max_concurrent == 0 && self.scan_mb_per_min == 0
+    }
+
+    /// Fills the unset limits with the ones of `default`
+    pub fn or(self, default: SearchQuota) -> SearchQuota {
+        SearchQuota {
+            max_concurrent: if self.max_concurrent > 0 {
+                self.max_concurrent
+            } else {
+                default.max_concurrent
+            },
+            scan_mb_per_min: if self.scan_mb_per_min > 0 {
+                self.scan_mb_per_min
+            } else {
+                default.scan_mb_per_min
+            },
+        }
+    }
+}
+
+/// Current consumption of an org against its search quota
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct SearchQuotaUsage {
+    pub quota: SearchQuota,
+    pub running: u64,
+    pub scan_mb_last_min: u64,
+}
+
+/// Limits of a single search, 0 is unlimited
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct QueryLimits {
+    /// MB of data, before compression, a search can scan
+    #[serde(default)]
+    pub max_scan_mb: u64,
+    /// Rows a search can return
+    #[serde(default)]
+    pub max_result_rows: u64,
+    /// Searches a user can run at once
+    #[serde(default)]
+    pub max_concurrent: u64,
+}
+
+impl QueryLimits {
+    pub fn is_empty(&self) -> bool {
+        self.max_scan_mb == 0 && self.max_result_rows == 0 && self.max_concurrent == 0
+    }
+
+    /// The higher of each limit, unlimited wins
+    pub fn max(self, other: QueryLimits) -> QueryLimits {
+        let max = |a: u64, b: u64| if a == 0 || b == 0 { 0 } else { a.max(b) };
+        QueryLimits {
+            max_scan_mb: max(self.max_scan_mb, other.max_scan_mb),
+            max_result_rows: max(self.max_result_rows, other.max_result_rows),
+            max_concurrent: max(self.max_concurrent, other.max_concurrent),
+        }
+    }
+}
+
+/// Limits of the searches of the users of an org, by role. The users apply
+/// the limits of their roles, the most permissive when they have several,
+/// and the default ones when none of their roles has limits
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct SearchLimits {
+    #[serde(default)]
+    pub default: QueryLimits,
+    /// Limits keyed by role, `admin`, `editor`, `viewer` or a custom role
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub roles: HashMap<String, QueryLimits>,
+}
+
+impl SearchLimits {
+    pub fn is_empty(&self) -> bool {
+        self.default.is_empty() && self.roles.is_empty()
+    }
+
+    /// The limits of a user with these roles
+    pub fn for_roles<'a>(&self, roles: impl IntoIterator<Item = &'a str>) -> QueryLimits {
+        roles
+            .into_iter()
+            .filter_map(|role| self.roles.get(role))
+            .copied()
+            .reduce(QueryLimits::max)
+            .unwrap_or(self.default)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3252,4 +3359,65 @@ mod tests {
             "Negative time range should return FiveMinutes"
         );
     }
+
+    #[test]
+    fn test_search_quota_or() {
+        let default = SearchQuota {
+            max_concurrent: 10,
+            scan_mb_per_min: 1024,
+        };
+        assert_eq!(SearchQuota::default().or(default), default);
+        let quota = SearchQuota {
+            max_concurrent: 2,
+            scan_mb_per_min: 0,
+        };
+        assert_eq!(
+            quota.or(default),
+            SearchQuota {
+                max_concurrent: 2,
+                scan_mb_per_min: 1024,
+            }
+        );
+        assert!(SearchQuota::default().is_empty());
+    }
+
+    #[test]
+    fn test_search_limits_for_roles() {
+        let limits = SearchLimits {
+            default: QueryLimits {
+                max_scan_mb: 1024,
+                max_result_rows: 1000,
+                max_concurrent: 2,
+            },
+            roles: HashMap::from([
+                (
+                    "viewer".to_string(),
+                    QueryLimits {
+                        max_scan_mb: 100,
+                        max_result_rows: 100,
+                        max_concurrent: 1,
+                    },
+                ),
+                (
+                    "analyst".to_string(),
+                    QueryLimits {
+                        max_scan_mb: 0,
+                        max_result_rows: 10,
+                        max_concurrent: 4,
+                    },
+                ),
+            ]),
+        };
+        assert_eq!(limits.for_roles(["editor"]), limits.default);
+        assert_eq!(limits.for_roles(["viewer"]), limits.roles["viewer"]);
+        assert_eq!(
+            limits.for_roles(["viewer", "analyst"]),
+            QueryLimits {
+                max_scan_mb: 0,
+                max_result_rows: 100,
+                max_concurrent: 4,
+            }
+        );
+        assert!(SearchLimits::default().is_empty());
+    }
 }
@@ -20,7 +20,7 @@ use tokio::{
     sync::{mpsc, oneshot},
     time,
 };
-use usage::{TriggerData, UsageData};
+use usage::{SearchAuditData, TriggerData, UsageData};
 
 pub mod error;
 pub mod usage;
@@ -42,6 +42,7 @@ pub enum ReportingData {
     Usage(Box<UsageData>),
     Trigger(Box<TriggerData>),
     Error(Box<ErrorData>),
+    SearchAudit(Box<SearchAuditData>),
 }
 
 /// Error type for enqueue operations with timeout
@@ -89,6 +90,7 @@ impl ReportingQueue {
             ReportingData::Usage(_) => "Usage",
             ReportingData::Trigger(_) => "Trigger",
             ReportingData::Error(_) => "Error",
+            ReportingData::SearchAudit(_) => "SearchAudit",
         };
 
         log::trace!(
@@ -130,6 +132,7 @@ impl ReportingQueue {
             ReportingData::Usage(_) => "Usage",
             ReportingData::Trigger(_) => "Trigger",
             ReportingData::Error(_) => "Error",
+            ReportingData::SearchAudit(_) => "SearchAudit",
         };
 
         match self
@@ -173,6 +176,7 @@ impl ReportingQueue {
             ReportingData::Usage(_) => "Usage",
             ReportingData::Trigger(_) => "Trigger",
             ReportingData::Error(_) => "Error",
+            ReportingData::SearchAudit(_) => "SearchAudit",
         };
 
         log::trace!(
@@ -939,6 +943,7 @@ mod tests {
                 ReportingData::Usage(_) => usage_count += 1,
                 ReportingData::Trigger(_) => trigger_count += 1,
                 ReportingData::Error(_) => error_count += 1,
+                ReportingData::SearchAudit(_) => {}
             }
         }
 
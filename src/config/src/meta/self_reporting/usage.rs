@@ -19,7 +19,7 @@ use crate::{
     SIZE_IN_MB, get_config,
     meta::{
         dashboards::usage_report::DashboardInfo,
-        search::{SearchEventContext, SearchEventType},
+        search::{ResponseTook, SearchEventContext, SearchEventType},
         stream::{FileMeta, StreamType},
     },
 };
@@ -29,6 +29,8 @@ pub const STATS_STREAM: &str = "stats";
 pub const TRIGGERS_STREAM: &str = "triggers";
 pub const ERROR_STREAM: &str = "errors";
 pub const DATA_RETENTION_USAGE_STREAM: &str = "data_retention_usage";
+pub const SEARCH_AUDIT_STREAM: &str = "_audit_search";
+pub const QUERY_SHADOW_STREAM: &str = "_query_shadow";
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum TriggerDataStatus {
@@ -54,6 +56,16 @@ pub enum TriggerDataType {
     DerivedStream,
     #[serde(rename = "backfill")]
     Backfill,
+    #[serde(rename = "scheduled_export")]
+    ScheduledExport,
+    #[serde(rename = "alert_backtest")]
+    AlertBacktest,
+    #[serde(rename = "recording_rule")]
+    RecordingRule,
+    #[serde(rename = "replay")]
+    Replay,
+    #[serde(rename = "db_poller")]
+    DbPoller,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -259,6 +271,110 @@ pub struct AggregatedData {
     pub count: u64,
 }
 
+/// One search request, with its cost and where its time went
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchAuditData {
+    pub _timestamp: i64,
+    pub org_id: String,
+    pub stream_type: StreamType,
+    pub stream_name: String,
+    pub user_email: String,
+    pub trace_id: String,
+    pub sql: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    pub start_time: i64,
+    pub end_time: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_type: Option<SearchEventType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub work_group: Option<String>,
+    pub records: i64,
+    pub scan_files: i64,
+    /// in MB
+    pub scan_size: f64,
+    pub cached_ratio: usize,
+    pub result_cache_ratio: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_memory_usage: Option<f64>,
+    pub is_partial: bool,
+    /// in seconds
+    pub response_time: f64,
+    /// per phase timings, in milliseconds
+    pub took_cache: usize,
+    pub took_file_list: usize,
+    pub took_wait_in_queue: usize,
+    pub took_idx: usize,
+    pub took_search: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_name: Option<String>,
+}
+
+impl SearchAuditData {
+    pub fn new(
+        stats: &RequestStats,
+        org_id: &str,
+        stream_name: &str,
+        stream_type: StreamType,
+        timestamp: i64,
+    ) -> Self {
+        let took = stats.took_detail.clone().unwrap_or_default();
+        Self {
+            _timestamp: timestamp,
+            org_id: org_id.to_string(),
+            stream_type,
+            stream_name: stream_name.to_string(),
+            user_email: stats.user_email.clone().unwrap_or_default(),
+            trace_id: stats.trace_id.clone().unwrap_or_default(),
+            sql: stats.request_body.clone().unwrap_or_default(),
+            function: stats.function.clone(),
+            start_time: stats.min_ts.unwrap_or_default(),
+            end_time: stats.max_ts.unwrap_or_default(),
+            search_type: stats.search_type,
+            work_group: stats.work_group.clone(),
+            records: stats.records,
+            scan_files: stats.scan_files.unwrap_or_default(),
+            scan_size: stats.size,
+            cached_ratio: stats.cached_ratio.unwrap_or_default(),
+            result_cache_ratio: stats.result_cache_ratio.unwrap_or_default(),
+            peak_memory_usage: stats.peak_memory_usage,
+            is_partial: stats.is_partial,
+            response_time: stats.response_time,
+            took_cache: took.cache_took,
+            took_file_list: took.file_list_took,
+            took_wait_in_queue: stats.took_wait_in_queue.unwrap_or(took.wait_in_queue),
+            took_idx: took.idx_took,
+            took_search: took.search_took,
+            node_name: stats.node_name.clone(),
+        }
+    }
+}
+
+/// A search run again on the shadow queriers whose result or latency differs
+/// from the production one
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct QueryShadowData {
+    pub _timestamp: i64,
+    pub org_id: String,
+    pub stream_type: StreamType,
+    pub trace_id: String,
+    pub sql: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    /// result, error or latency
+    pub mismatch: String,
+    pub detail: String,
+    pub total: usize,
+    pub shadow_total: usize,
+    pub hits: usize,
+    pub shadow_hits: usize,
+    /// in milliseconds
+    pub took: usize,
+    pub shadow_took: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_name: Option<String>,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct DataRetentionUsageData {
     pub _timestamp: i64,
@@ -364,6 +480,28 @@ pub enum UsageType {
     Syslog,
     #[serde(rename = "enrichment_table")]
     EnrichmentTable,
+    #[serde(rename = "flow")]
+    Flow,
+    #[serde(rename = "k8s_events")]
+    K8sEvents,
+    #[serde(rename = "docker")]
+    Docker,
+    #[serde(rename = "access_logs")]
+    AccessLogs,
+    #[serde(rename = "webhook")]
+    Webhook,
+    #[serde(rename = "kafka")]
+    Kafka,
+    #[serde(rename = "gelf")]
+    Gelf,
+    #[serde(rename = "kinesis")]
+    Kinesis,
+    #[serde(rename = "mqtt")]
+    Mqtt,
+    #[serde(rename = "rabbitmq")]
+    Rabbitmq,
+    #[serde(rename = "redis_streams")]
+    RedisStreams,
 }
 
 impl UsageType {
@@ -396,6 +534,17 @@ impl UsageType {
                 | UsageType::RUM
                 | UsageType::EnrichmentTable
                 | UsageType::Syslog
+                | UsageType::Flow
+                | UsageType::K8sEvents
+                | UsageType::Docker
+                | UsageType::AccessLogs
+                | UsageType::Webhook
+                | UsageType::Kafka
+                | UsageType::Gelf
+                | UsageType::Kinesis
+                | UsageType::Mqtt
+                | UsageType::Rabbitmq
+                | UsageType::RedisStreams
         )
     }
 
@@ -439,6 +588,17 @@ impl std::fmt::Display for UsageType {
             UsageType::Retention => write!(f, "data_retention"),
             UsageType::Syslog => write!(f, "syslog"),
             UsageType::EnrichmentTable => write!(f, "enrichment_table"),
+            UsageType::Flow => write!(f, "flow"),
+            UsageType::K8sEvents => write!(f, "k8s_events"),
+            UsageType::Docker => write!(f, "docker"),
+            UsageType::AccessLogs => write!(f, "access_logs"),
+            UsageType::Webhook => write!(f, "webhook"),
+            UsageType::Kafka => write!(f, "kafka"),
+            UsageType::Gelf => write!(f, "gelf"),
+            UsageType::Kinesis => write!(f, "kinesis"),
+            UsageType::Mqtt => write!(f, "mqtt"),
+            UsageType::Rabbitmq => write!(f, "rabbitmq"),
+            UsageType::RedisStreams => write!(f, "redis_streams"),
         }
     }
 }
@@ -487,6 +647,9 @@ pub struct RequestStats {
     pub dashboard_info: Option<DashboardInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub peak_memory_usage: Option<f64>,
+    /// per phase timings of a search, only for the search audit
+    #[serde(skip)]
+    pub took_detail: Option<ResponseTook>,
 }
 impl Default for RequestStats {
     fn default() -> Self {
@@ -513,6 +676,7 @@ impl Default for RequestStats {
             node_name: Some(get_config().common.instance_name.clone()),
             dashboard_info: None,
             peak_memory_usage: None,
+            took_detail: None,
         }
     }
 }
@@ -542,6 +706,7 @@ impl From<FileMeta> for RequestStats {
             node_name: None,
             dashboard_info: None,
             peak_memory_usage: None,
+            took_detail: None,
         }
     }
 }
@@ -619,6 +784,17 @@ mod tests {
             format!("{}", UsageType::EnrichmentTable),
             "enrichment_table"
         );
+        assert_eq!(format!("{}", UsageType::Flow), "flow");
+        assert_eq!(format!("{}", UsageType::K8sEvents), "k8s_events");
+        assert_eq!(format!("{}", UsageType::Docker), "docker");
+        assert_eq!(format!("{}", UsageType::AccessLogs), "access_logs");
+        assert_eq!(format!("{}", UsageType::Webhook), "webhook");
+        assert_eq!(format!("{}", UsageType::Kafka), "kafka");
+        assert_eq!(format!("{}", UsageType::Gelf), "gelf");
+        assert_eq!(format!("{}", UsageType::Kinesis), "kinesis");
+        assert_eq!(format!("{}", UsageType::Mqtt), "mqtt");
+        assert_eq!(format!("{}", UsageType::Rabbitmq), "rabbitmq");
+        assert_eq!(format!("{}", UsageType::RedisStreams), "redis_streams");
     }
 
     #[test]
@@ -652,6 +828,17 @@ mod tests {
         assert!(UsageType::RUM.is_ingestion());
         assert!(UsageType::EnrichmentTable.is_ingestion());
         assert!(UsageType::Syslog.is_ingestion());
+        assert!(UsageType::Flow.is_ingestion());
+        assert!(UsageType::K8sEvents.is_ingestion());
+        assert!(UsageType::Docker.is_ingestion());
+        assert!(UsageType::AccessLogs.is_ingestion());
+        assert!(UsageType::Webhook.is_ingestion());
+        assert!(UsageType::Kafka.is_ingestion());
+        assert!(UsageType::Gelf.is_ingestion());
+        assert!(UsageType::Kinesis.is_ingestion());
+        assert!(UsageType::Mqtt.is_ingestion());
+        assert!(UsageType::Rabbitmq.is_ingestion());
+        assert!(UsageType::RedisStreams.is_ingestion());
 
         assert!(!UsageType::Search.is_ingestion());
         assert!(!UsageType::MetricSearch.is_ingestion());
@@ -982,6 +1169,7 @@ mod tests {
                 tab_name: "test_tab_name".to_string(),
             }),
             peak_memory_usage: Some(1024000.0),
+            took_detail: None,
         };
 
         let json = serde_json::to_string(&stats).unwrap();
@@ -1179,6 +1367,17 @@ mod tests {
             UsageType::Retention,
             UsageType::Syslog,
             UsageType::EnrichmentTable,
+            UsageType::Flow,
+            UsageType::K8sEvents,
+            UsageType::Docker,
+            UsageType::AccessLogs,
+            UsageType::Webhook,
+            UsageType::Kafka,
+            UsageType::Gelf,
+            UsageType::Kinesis,
+            UsageType::Mqtt,
+            UsageType::Rabbitmq,
+            UsageType::RedisStreams,
         ];
 
         for variant in variants {
@@ -1203,6 +1402,46 @@ mod tests {
             assert_eq!(variant, deserialized);
         }
     }
+
+    #[test]
+    fn test_search_audit_data_new() {
+        let stats = RequestStats {
+            size: 12.5,
+            records: 42,
+            response_time: 1.5,
+            request_body: Some("SELECT * FROM \"default\"".to_string()),
+            scan_files: Some(3),
+            min_ts: Some(100),
+            max_ts: Some(200),
+            user_email: Some("root@example.com".to_string()),
+            trace_id: Some("trace123".to_string()),
+            cached_ratio: Some(50),
+            took_detail: Some(ResponseTook {
+                total: 1500,
+                cache_took: 10,
+                file_list_took: 20,
+                wait_in_queue: 30,
+                idx_took: 40,
+                search_took: 1400,
+            }),
+            ..Default::default()
+        };
+        let audit = SearchAuditData::new(&stats, "org1", "default", StreamType::Logs, 1000);
+        assert_eq!(audit._timestamp, 1000);
+        assert_eq!(audit.org_id, "org1");
+        assert_eq!(audit.sql, "SELECT * FROM \"default\"");
+        assert_eq!(audit.user_email, "root@example.com");
+        assert_eq!((audit.start_time, audit.end_time), (100, 200));
+        assert_eq!(audit.scan_files, 3);
+        assert_eq!(audit.cached_ratio, 50);
+        assert_eq!(audit.took_file_list, 20);
+        assert_eq!(audit.took_wait_in_queue, 30);
+        assert_eq!(audit.took_search, 1400);
+
+        // the timings are not part of the usage report
+        let json = serde_json::to_string(&stats).unwrap();
+        assert!(!json.contains("took_detail"));
+    }
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
@@ -23,12 +23,13 @@ use utoipa::ToSchema;
 
 use super::bitvec::BitVec;
 use crate::{
-    get_config,
+    TIMESTAMP_COL_NAME, get_config,
     meta::self_reporting::usage::Stats,
     stats::MemorySize,
     utils::{
         hash::{Sum64, gxhash},
         json::{self, Value},
+        time::day_micros,
     },
 };
 
@@ -107,6 +108,12 @@ impl PartialEq for DataField {
     }
 }
 
+impl MemorySize for DataField {
+    fn mem_size(&self) -> usize {
+        std::mem::size_of::<DataField>() + self.name.mem_size()
+    }
+}
+
 pub const ALL_STREAM_TYPES: [StreamType; 8] = [
     StreamType::Logs,
     StreamType::Metrics,
@@ -744,6 +751,35 @@ pub struct UpdateStreamSettings {
     pub enable_distinct_fields: Option<bool>,
     #[serde(default)]
     pub enable_log_patterns_extraction: Option<bool>,
+    #[serde(default)]
+    pub ingest_priority: Option<IngestPriority>,
+    #[serde(default)]
+    pub ingest_quota: Option<IngestQuota>,
+    #[serde(default)]
+    pub redaction_rules: UpdateSettingsWrapper<RedactionRule>,
+    #[serde(default)]
+    pub computed_fields: UpdateSettingsWrapper<ComputedField>,
+    #[serde(default)]
+    pub low_cardinality_fields: UpdateSettingsWrapper<String>,
+    #[serde(default)]
+    pub ingest_dedup: Option<IngestDedup>,
+    #[serde(default)]
+    pub wal_sync_policy: Option<WalSyncPolicy>,
+    /// Removed by name, adding a field migrates the latest schema to its type
+    #[serde(default)]
+    pub pinned_field_types: UpdateSettingsWrapper<DataField>,
+    #[serde(default)]
+    pub level_normalization: Option<LevelNormalization>,
+    #[serde(default)]
+    pub clock_skew: Option<ClockSkew>,
+    #[serde(default)]
+    pub tail_sampling: Option<TailSampling>,
+    #[serde(default)]
+    pub field_access_rules: UpdateSettingsWrapper<FieldAccessRule>,
+    #[serde(default)]
+    pub archive_expired_data: Option<bool>,
+    #[serde(default)]
+    pub compact_sort: Option<CompactSort>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
@@ -851,6 +887,581 @@ impl TimeRange {
     }
 }
 
+/// Ingestion priority of a stream. When the node sheds load, low priority
+/// streams are throttled first and high priority streams, such as audit or
+/// security logs, keep being accepted until a hard limit is hit.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum IngestPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl IngestPriority {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IngestPriority::Low => "low",
+            IngestPriority::Normal => "normal",
+            IngestPriority::High => "high",
+        }
+    }
+}
+
+/// Records and bytes per second that can be ingested across the cluster, 0
+/// falls back to the configured default
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct IngestQuota {
+    #[serde(default)]
+    pub records_per_sec: u64,
+    #[serde(default)]
+    pub bytes_per_sec: u64,
+}
+
+impl IngestQuota {
+    pub fn is_empty(&self) -> bool {
+        self.records_per_sec == 0 && self.bytes_per_sec == 0
+    }
+
+    /// Fills the unset limits with the ones of `default`
+    pub fn or(self, default: IngestQuota) -> IngestQuota {
+        IngestQuota {
+            records_per_sec: if self.records_per_sec > 0 {
+                self.records_per_sec
+            } else {
+                default.records_per_sec
+            },
+            bytes_per_sec: if self.bytes_per_sec > 0 {
+                self.bytes_per_sec
+            } else {
+                default.bytes_per_sec
+            },
+        }
+    }
+}
+
+/// Drops the records already ingested within the window, compared by the
+/// hash of the given fields, or of the whole record when there is none. The
+/// columns set by the server, `_timestamp` included, aren't part of the whole
+/// record, list the fields to tell apart records differing by their time
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct IngestDedup {
+    #[serde(default)]
+    pub window_secs: u64,
+    #[serde(default)]
+    pub fields: Vec<String>,
+}
+
+impl IngestDedup {
+    pub fn is_empty(&self) -> bool {
+        self.window_secs == 0
+    }
+}
+
+/// Field the normalized level of the records is written to
+pub const LEVEL_FIELD: &str = "level";
+
+/// The values the levels are normalized to
+pub const CANONICAL_LEVELS: [&str; 6] = ["trace", "debug", "info", "warn", "error", "fatal"];
+
+/// Fields the level is read from when a stream doesn't set any
+pub const DEFAULT_LEVEL_SOURCE_FIELDS: [&str; 6] = [
+    "level",
+    "severity",
+    "severity_text",
+    "log_level",
+    "loglevel",
+    "sev",
+];
+
+/// Maps the level of the records to one of the canonical levels in `level`,
+/// through the level mapping of the organization
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct LevelNormalization {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Fields the level is read from, the first one a record has is used
+    #[serde(default)]
+    pub source_fields: Vec<String>,
+}
+
+impl LevelNormalization {
+    pub fn is_empty(&self) -> bool {
+        !self.enabled && self.source_fields.is_empty()
+    }
+}
+
+/// Fields the source of a record is read from when a stream doesn't set any
+pub const DEFAULT_CLOCK_SKEW_SOURCE_FIELDS: [&str; 5] =
+    ["host", "hostname", "host_name", "agent", "source"];
+
+/// Skew from which the sources are corrected when a stream doesn't set it, in
+/// seconds
+pub const DEFAULT_CLOCK_SKEW_THRESHOLD_SECS: u64 = 300;
+
+/// Tracks the offset of the timestamps of the records of every source from
+/// their arrival time, and optionally shifts the timestamps of the sources
+/// whose clock is consistently off
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ClockSkew {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Fields the source is read from, the first one a record has is used
+    #[serde(default)]
+    pub source_fields: Vec<String>,
+    /// Shifts the timestamps of the sources skewed by more than the threshold
+    /// by their skew. Only for streams of live sources, a source sending old
+    /// records looks skewed as well.
+    #[serde(default)]
+    pub correct: bool,
+    /// Skew from which a source is corrected, in seconds
+    #[serde(default)]
+    pub threshold_secs: u64,
+}
+
+impl ClockSkew {
+    pub fn is_empty(&self) -> bool {
+        !self.enabled && !self.correct && self.source_fields.is_empty() && self.threshold_secs == 0
+    }
+
+    pub fn threshold_micros(&self) -> i64 {
+        let secs = if self.threshold_secs > 0 {
+            self.threshold_secs
+        } else {
+            DEFAULT_CLOCK_SKEW_THRESHOLD_SECS
+        };
+        secs as i64 * 1_000_000
+    }
+}
+
+/// Time the spans of a trace are held when a stream doesn't set it, in seconds
+pub const DEFAULT_TAIL_SAMPLING_DECISION_WAIT_SECS: u64 = 10;
+
+/// Longest time the spans of a trace can be held, in seconds
+pub const MAX_TAIL_SAMPLING_DECISION_WAIT_SECS: u64 = 300;
+
+/// Holds the spans of the traces of a traces stream for a while after their
+/// first span arrived, then persists or drops every trace as a whole. The
+/// traces with an error span are always kept, the ones lasting at least the
+/// latency threshold as well, and the rest at the sample rate.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TailSampling {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Time the spans of a trace are held after its first span, in seconds.
+    /// The spans arriving later follow the decision taken for their trace.
+    #[serde(default)]
+    pub decision_wait_secs: u64,
+    /// Traces lasting at least this long are kept, in milliseconds, 0 turns
+    /// the policy off
+    #[serde(default)]
+    pub latency_threshold_ms: u64,
+    /// Fraction of the other traces kept, from 0 to 1. The decision depends
+    /// on the trace id only, so every ingester takes the same.
+    #[serde(default)]
+    pub sample_rate: f64,
+}
+
+impl TailSampling {
+    pub fn is_empty(&self) -> bool {
+        !self.enabled
+            && self.decision_wait_secs == 0
+            && self.latency_threshold_ms == 0
+            && self.sample_rate == 0.0
+    }
+
+    pub fn decision_wait_micros(&self) -> i64 {
+        let secs = if self.decision_wait_secs > 0 {
+            self.decision_wait_secs
+        } else {
+            DEFAULT_TAIL_SAMPLING_DECISION_WAIT_SECS
+        };
+        secs as i64 * 1_000_000
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.sample_rate) {
+            return Err(format!(
+                "tail sampling rate must be between 0 and 1, got {}",
+                self.sample_rate
+            ));
+        }
+        if self.decision_wait_secs > MAX_TAIL_SAMPLING_DECISION_WAIT_SECS {
+            return Err(format!(
+                "tail sampling decision wait can't be longer than {MAX_TAIL_SAMPLING_DECISION_WAIT_SECS} seconds"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Most fields the merged files of a stream can be sorted by
+pub const MAX_COMPACT_SORT_FIELDS: usize = 4;
+
+/// How the compactor orders the records of the files it merges
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CompactSortMode {
+    /// By the first field, then the second one, and so on
+    #[default]
+    Hierarchical,
+    /// By the interleaved bits of the fields, every field gets a similar
+    /// clustering instead of the first one only
+    #[serde(rename = "zorder")]
+    ZOrder,
+}
+
+/// Sorts the records of the files merged by the compactor by the given fields
+/// before the timestamp, so the files and row groups cover narrow ranges of
+/// the fields filtered on most and are pruned by their statistics. The files
+/// of these streams are no longer read as sorted by time by the searches.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct CompactSort {
+    #[serde(default)]
+    pub fields: Vec<String>,
+    #[serde(default)]
+    pub mode: CompactSortMode,
+}
+
+impl CompactSort {
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.fields.len() > MAX_COMPACT_SORT_FIELDS {
+            return Err(format!(
+                "compact sort supports up to {MAX_COMPACT_SORT_FIELDS} fields"
+            ));
+        }
+        for (i, field) in self.fields.iter().enumerate() {
+            if field.is_empty() || field == TIMESTAMP_COL_NAME {
+                return Err(format!("compact sort field [{field}] is not allowed"));
+            }
+            if self.fields[..i].contains(field) {
+                return Err(format!("compact sort field [{field}] is duplicated"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Mapping of the level values to the canonical levels, one per organization.
+/// The values are matched case insensitively, `sev=4` is also matched by `4`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct LevelMapping {
+    pub mappings: std::collections::BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_by: Option<String>,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+impl Default for LevelMapping {
+    /// The common names, the syslog severities and the bunyan levels
+    fn default() -> Self {
+        let levels = [
+            ("trace", "trace trc finest verbose 10"),
+            ("debug", "debug dbg fine finer 7 20"),
+            ("info", "info inf information informational notice 5 6 30"),
+            ("warn", "warn wrn warning 4 40"),
+            ("error", "error err eror severe 3 50"),
+            ("fatal", "fatal crit critical alert emerg panic 0 1 2 60"),
+        ];
+        let mappings = levels
+            .into_iter()
+            .flat_map(|(level, values)| {
+                values
+                    .split_whitespace()
+                    .map(move |v| (v.to_string(), level.to_string()))
+            })
+            .collect();
+        Self {
+            mappings,
+            updated_by: None,
+            updated_at: 0,
+        }
+    }
+}
+
+impl LevelMapping {
+    /// Lowercases the values and checks they map to canonical levels
+    pub fn validate(&mut self) -> Result<(), String> {
+        let mut mappings = std::collections::BTreeMap::new();
+        for (value, level) in std::mem::take(&mut self.mappings) {
+            let value = value.trim().to_lowercase();
+            if value.is_empty() {
+                return Err("level mapping values can't be empty".to_string());
+            }
+            let level = level.trim().to_lowercase();
+            if !CANONICAL_LEVELS.contains(&level.as_str()) {
+                return Err(format!(
+                    "{value} maps to {level}, which isn't one of {}",
+                    CANONICAL_LEVELS.join(", ")
+                ));
+            }
+            mappings.insert(value, level);
+        }
+        self.mappings = mappings;
+        Ok(())
+    }
+
+    /// The canonical level of the value, if it is mapped
+    pub fn get(&self, value: &str) -> Option<&str> {
+        let value = value.trim().to_lowercase();
+        if let Some(level) = self.mappings.get(&value) {
+            return Some(level);
+        }
+        let (_, value) = value.rsplit_once('=')?;
+        self.mappings.get(value.trim()).map(|v| v.as_str())
+    }
+}
+
+/// When the WAL writes of a stream are synced to disk
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum WalSyncPolicy {
+    /// fsync after every batch, the request returns once its data is durable
+    Batch,
+    /// fsync at most every `ZO_WAL_SYNC_INTERVAL` milliseconds
+    Interval,
+    /// never fsync, the OS flushes the page cache
+    Async,
+}
+
+impl WalSyncPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WalSyncPolicy::Batch => "batch",
+            WalSyncPolicy::Interval => "interval",
+            WalSyncPolicy::Async => "async",
+        }
+    }
+
+    /// The policy of the streams without one, `ZO_WAL_SYNC_POLICY_HIGH_PRIORITY`
+    /// for the high priority streams and `ZO_WAL_SYNC_POLICY` for the others
+    pub fn default_for(priority: IngestPriority) -> Self {
+        let cfg = crate::get_config();
+        let policy = match priority {
+            IngestPriority::High if !cfg.common.wal_sync_policy_high_priority.is_empty() => {
+                &cfg.common.wal_sync_policy_high_priority
+            }
+            _ => &cfg.common.wal_sync_policy,
+        };
+        policy.parse().unwrap_or(WalSyncPolicy::Async)
+    }
+}
+
+impl FromStr for WalSyncPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "batch" => Ok(WalSyncPolicy::Batch),
+            "interval" => Ok(WalSyncPolicy::Interval),
+            "async" => Ok(WalSyncPolicy::Async),
+            _ => Err(anyhow::anyhow!(
+                "invalid wal sync policy: {s}, must be one of batch, interval, async"
+            )),
+        }
+    }
+}
+
+pub const DEFAULT_REDACTION_REPLACEMENT: &str = "[REDACTED]";
+
+fn default_redaction_replacement() -> String {
+    DEFAULT_REDACTION_REPLACEMENT.to_string()
+}
+
+/// Masks sensitive values of the records before they are written to the WAL.
+/// A rule with fields only replaces the whole value of those fields, a rule
+/// with a pattern only replaces the matches in every string field.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RedactionRule {
+    /// Unique name of the rule, used to remove it
+    pub name: String,
+    /// Fields the rule applies to, every string field when empty
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// Regex matched against the values, the whole value is replaced when
+    /// empty
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// Replacement of the matched values, may refer to capture groups
+    #[serde(default = "default_redaction_replacement")]
+    pub replacement: String,
+}
+
+impl RedactionRule {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("redaction rule name is required".to_string());
+        }
+        match self.pattern.as_deref() {
+            Some(pattern) if !pattern.is_empty() => regex::Regex::new(pattern)
+                .map(|_| ())
+                .map_err(|e| format!("invalid pattern of redaction rule {}: {e}", self.name)),
+            _ if self.fields.is_empty() => Err(format!(
+                "redaction rule {} needs fields or a pattern",
+                self.name
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl MemorySize for RedactionRule {
+    fn mem_size(&self) -> usize {
+        std::mem::size_of::<RedactionRule>()
+            + self.name.mem_size()
+            + self.fields.mem_size()
+            + self.pattern.mem_size()
+            + self.replacement.mem_size()
+    }
+}
+
+/// How a field denied to a user is hidden from the results of `SELECT *`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldAccessMode {
+    /// The field is left out of the results
+    #[default]
+    Strip,
+    /// The field is returned with its values replaced by
+    /// [`DEFAULT_REDACTION_REPLACEMENT`]
+    Mask,
+}
+
+/// Denies roles access to a field of the stream at query time, e.g. to hide a
+/// PII field from the analysts. The queries of a denied user which reference
+/// the field fail and their `SELECT *` hides it, the raw records of
+/// `_original` are hidden too. The data is stored unchanged.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FieldAccessRule {
+    pub field: String,
+    /// Standard roles, e.g. `viewer`, or custom roles denied access to the
+    /// field
+    pub denied_roles: Vec<String>,
+    #[serde(default)]
+    pub mode: FieldAccessMode,
+}
+
+impl FieldAccessRule {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.field.trim().is_empty() {
+            return Err("field of field access rule is required".to_string());
+        }
+        if self.field == TIMESTAMP_COL_NAME {
+            return Err(format!("access to field {} can't be denied", self.field));
+        }
+        if self.denied_roles.is_empty() {
+            return Err(format!(
+                "field access rule of {} needs denied roles",
+                self.field
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns true if one of the roles is denied access to the field
+    pub fn denies<'a>(&self, mut roles: impl Iterator<Item = &'a str>) -> bool {
+        roles.any(|role| self.denied_roles.iter().any(|r| r == role))
+    }
+}
+
+impl MemorySize for FieldAccessRule {
+    fn mem_size(&self) -> usize {
+        std::mem::size_of::<FieldAccessRule>()
+            + self.field.mem_size()
+            + self.denied_roles.mem_size()
+    }
+}
+
+/// A field computed by the searches from the fields of the stream, to rename
+/// an awkward field or to derive a new one without reindexing. The stored
+/// fields keep their name, the computed ones are added next to them.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ComputedField {
+    /// Name of the field in the searches, also used to remove it
+    pub name: String,
+    /// SQL expression over the fields of the stream, e.g.
+    /// `k8s_pod_labels_app` or `CAST(took AS DOUBLE) / 1000`
+    pub expr: String,
+}
+
+impl ComputedField {
+    fn parse_expr(&self) -> Result<sqlparser::ast::Expr, String> {
+        let dialect = sqlparser::dialect::PostgreSqlDialect {};
+        let mut parser = sqlparser::parser::Parser::new(&dialect)
+            .try_with_sql(&self.expr)
+            .map_err(|e| format!("invalid expression of computed field {}: {e}", self.name))?;
+        let expr = parser
+            .parse_expr()
+            .map_err(|e| format!("invalid expression of computed field {}: {e}", self.name))?;
+        if parser.peek_token().token != sqlparser::tokenizer::Token::EOF {
+            return Err(format!(
+                "invalid expression of computed field {}: unexpected {}",
+                self.name,
+                parser.peek_token().token
+            ));
+        }
+        Ok(expr)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("computed field name is required".to_string());
+        }
+        if self.expr.trim().is_empty() {
+            return Err(format!("computed field {} needs an expression", self.name));
+        }
+        self.parse_expr().map(|_| ())
+    }
+
+    /// The fields of the stream the field is computed from
+    pub fn source_fields(&self) -> Vec<String> {
+        let Ok(expr) = self.parse_expr() else {
+            return vec![];
+        };
+        let mut fields = Vec::new();
+        let _ = sqlparser::ast::visit_expressions(&expr, |e| {
+            match e {
+                sqlparser::ast::Expr::Identifier(ident) => fields.push(ident.value.clone()),
+                sqlparser::ast::Expr::CompoundIdentifier(idents) => {
+                    if let Some(ident) = idents.last() {
+                        fields.push(ident.value.clone());
+                    }
+                }
+                _ => {}
+            }
+            std::ops::ControlFlow::<()>::Continue(())
+        });
+        fields.sort();
+        fields.dedup();
+        fields
+    }
+}
+
+impl MemorySize for ComputedField {
+    fn mem_size(&self) -> usize {
+        std::mem::size_of::<ComputedField>() + self.name.mem_size() + self.expr.mem_size()
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, ToSchema, PartialEq)]
 pub struct StreamSettings {
     #[serde(default)]
@@ -889,6 +1500,48 @@ pub struct StreamSettings {
     pub enable_distinct_fields: bool,
     #[serde(default)]
     pub enable_log_patterns_extraction: bool,
+    #[serde(default)]
+    pub ingest_priority: IngestPriority,
+    #[serde(default)]
+    pub ingest_quota: IngestQuota,
+    #[serde(default)]
+    pub redaction_rules: Vec<RedactionRule>,
+    #[serde(default)]
+    pub computed_fields: Vec<ComputedField>,
+    /// String fields with few distinct values, e.g. `level`. Only a hint for
+    /// now: the parquet writer already dictionary encodes the string columns
+    /// and the query layer still reads them as plain strings
+    #[serde(default)]
+    pub low_cardinality_fields: Vec<String>,
+    #[serde(default)]
+    pub ingest_dedup: IngestDedup,
+    /// None falls back to the policy of the ingest priority class, see
+    /// [`WalSyncPolicy::default_for`]
+    #[serde(default)]
+    pub wal_sync_policy: Option<WalSyncPolicy>,
+    /// Fields which keep their type whatever the producers send, the values
+    /// of another type are cast to it instead of widening the field
+    #[serde(default)]
+    pub pinned_field_types: Vec<DataField>,
+    #[serde(default)]
+    pub level_normalization: LevelNormalization,
+    #[serde(default)]
+    pub clock_skew: ClockSkew,
+    #[serde(default)]
+    pub tail_sampling: TailSampling,
+    #[serde(default)]
+    pub field_access_rules: Vec<FieldAccessRule>,
+    /// The files expired by the data retention are copied to the archive
+    /// storage before being deleted, the deletions by time range are never
+    /// archived
+    #[serde(default)]
+    pub archive_expired_data: bool,
+    #[serde(default)]
+    pub compact_sort: CompactSort,
+    /// When the compact sort was last removed, the files of the days before
+    /// may still be sorted by its fields
+    #[serde(default)]
+    pub compact_sort_removed_at: i64,
 }
 
 impl Default for StreamSettings {
@@ -912,6 +1565,193 @@ impl Default for StreamSettings {
             index_all_values: false,
             enable_distinct_fields: true,
             enable_log_patterns_extraction: false,
+            ingest_priority: IngestPriority::Normal,
+            ingest_quota: IngestQuota::default(),
+            redaction_rules: Vec::new(),
+            computed_fields: Vec::new(),
+            low_cardinality_fields: Vec::new(),
+            ingest_dedup: IngestDedup::default(),
+            wal_sync_policy: None,
+            pinned_field_types: Vec::new(),
+            level_normalization: LevelNormalization::default(),
+            clock_skew: ClockSkew::default(),
+            tail_sampling: TailSampling::default(),
+            field_access_rules: Vec::new(),
+            archive_expired_data: false,
+            compact_sort: CompactSort::default(),
+            compact_sort_removed_at: 0,
+        }
+    }
+}
+
+impl StreamSettings {
+    /// Whether the files holding records from `start_time` on are sorted by
+    /// time, the ones merged until the day after the compact sort was removed
+    /// may still be sorted by its fields
+    pub fn files_sorted_by_time(&self, start_time: i64) -> bool {
+        let removed_at = self.compact_sort_removed_at;
+        self.compact_sort.is_empty()
+            && (removed_at == 0
+                || start_time >= removed_at - removed_at % day_micros(1) + day_micros(1))
+    }
+}
+
+/// Settings given to a stream when ingestion creates it, configured per
+/// stream type in the organization settings
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct StreamSettingsTemplate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partition_time_level: Option<PartitionTimeLevel>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub partition_keys: Vec<StreamPartition>,
+    /// Only the ones the first records carry as text are applied, like a
+    /// settings update they must exist in the schema
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub full_text_search_keys: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub index_fields: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub bloom_filter_fields: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub defined_schema_fields: Vec<String>,
+    /// Retention in days, 0 keeps the global retention
+    #[serde(skip_serializing_if = "is_zero")]
+    pub data_retention: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flatten_level: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store_original_data: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_original_data: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_all_values: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingest_priority: Option<IngestPriority>,
+}
+
+fn is_zero(v: &i64) -> bool {
+    *v == 0
+}
+
+impl StreamSettingsTemplate {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Checks the template against the rules of a stream settings update
+    pub fn validate(&self, stream_type: StreamType) -> Result<(), String> {
+        let cfg = get_config();
+        if !matches!(
+            stream_type,
+            StreamType::Logs | StreamType::Metrics | StreamType::Traces
+        ) {
+            return Err(format!(
+                "stream type [{stream_type}] doesn't support settings templates"
+            ));
+        }
+        if self.data_retention < 0 {
+            return Err("data_retention can't be negative".to_string());
+        }
+        if !self.defined_schema_fields.is_empty() {
+            if !stream_type.support_uds() {
+                return Err(format!(
+                    "stream type [{stream_type}] don't support user defined schema"
+                ));
+            }
+            if !cfg.common.allow_user_defined_schemas {
+                return Err("user defined schema is not allowed, you need to set \
+                            ZO_ALLOW_USER_DEFINED_SCHEMAS=true"
+                    .to_string());
+            }
+            if self.defined_schema_fields.len() > cfg.limit.user_defined_schema_max_fields {
+                return Err(format!(
+                    "user defined schema fields count exceeds the limit: {}",
+                    cfg.limit.user_defined_schema_max_fields
+                ));
+            }
+        }
+        if let Some(key) = self
+            .full_text_search_keys
+            .iter()
+            .find(|key| **key == cfg.common.column_all)
+        {
+            return Err(format!("field [{key}] can't be used for full text search"));
+        }
+        if let Some(key) = self
+            .index_fields
+            .iter()
+            .find(|key| **key == cfg.common.column_all)
+        {
+            return Err(format!("field [{key}] can't be used for secondary index"));
+        }
+        if let Some(key) = self.partition_keys.iter().find(|key| {
+            crate::SQL_FULL_TEXT_SEARCH_FIELDS.contains(&key.field)
+                || key.field == cfg.common.column_all
+        }) {
+            return Err(format!(
+                "field [{}] can't be used for partition key",
+                key.field
+            ));
+        }
+        if self.index_original_data == Some(true) && self.index_all_values == Some(true) {
+            return Err(
+                "index_original_data & index_all_values cannot be true at the same time"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Fills the settings of a new stream from the template
+    pub fn apply(&self, settings: &mut StreamSettings) {
+        if self.partition_time_level.is_some() {
+            settings.partition_time_level = self.partition_time_level;
+        }
+        for key in self.partition_keys.iter() {
+            if !settings.partition_keys.iter().any(|k| k.field == key.field) {
+                settings.partition_keys.push(key.clone());
+            }
+        }
+        extend_unique(
+            &mut settings.full_text_search_keys,
+            &self.full_text_search_keys,
+        );
+        extend_unique(&mut settings.index_fields, &self.index_fields);
+        extend_unique(&mut settings.bloom_filter_fields, &self.bloom_filter_fields);
+        extend_unique(
+            &mut settings.defined_schema_fields,
+            &self.defined_schema_fields,
+        );
+        if self.data_retention > 0 {
+            settings.data_retention = self.data_retention;
+        }
+        if self.flatten_level.is_some() {
+            settings.flatten_level = self.flatten_level;
+        }
+        if let Some(v) = self.store_original_data {
+            settings.store_original_data = v;
+        }
+        if let Some(v) = self.index_original_data {
+            settings.index_original_data = v;
+        }
+        if let Some(v) = self.index_all_values {
+            settings.index_all_values = v;
+        }
+        // if index_original_data is true, store_original_data must be true
+        if settings.index_original_data {
+            settings.store_original_data = true;
+        }
+        if let Some(v) = self.ingest_priority {
+            settings.ingest_priority = v;
+        }
+    }
+}
+
+fn extend_unique(dst: &mut Vec<String>, src: &[String]) {
+    for v in src {
+        if !dst.contains(v) {
+            dst.push(v.clone());
         }
     }
 }
@@ -948,6 +1788,77 @@ impl Serialize for StreamSettings {
             "enable_log_patterns_extraction",
             &self.enable_log_patterns_extraction,
         )?;
+        state.serialize_field("ingest_priority", &self.ingest_priority)?;
+        if !self.ingest_quota.is_empty() {
+            state.serialize_field("ingest_quota", &self.ingest_quota)?;
+        } else {
+            state.skip_field("ingest_quota")?;
+        }
+        if !self.redaction_rules.is_empty() {
+            state.serialize_field("redaction_rules", &self.redaction_rules)?;
+        } else {
+            state.skip_field("redaction_rules")?;
+        }
+        if !self.computed_fields.is_empty() {
+            state.serialize_field("computed_fields", &self.computed_fields)?;
+        } else {
+            state.skip_field("computed_fields")?;
+        }
+        if !self.low_cardinality_fields.is_empty() {
+            state.serialize_field("low_cardinality_fields", &self.low_cardinality_fields)?;
+        } else {
+            state.skip_field("low_cardinality_fields")?;
+        }
+        if !self.ingest_dedup.is_empty() {
+            state.serialize_field("ingest_dedup", &self.ingest_dedup)?;
+        } else {
+            state.skip_field("ingest_dedup")?;
+        }
+        if let Some(policy) = self.wal_sync_policy {
+            state.serialize_field("wal_sync_policy", &policy)?;
+        } else {
+            state.skip_field("wal_sync_policy")?;
+        }
+        if !self.pinned_field_types.is_empty() {
+            state.serialize_field("pinned_field_types", &self.pinned_field_types)?;
+        } else {
+            state.skip_field("pinned_field_types")?;
+        }
+        if !self.level_normalization.is_empty() {
+            state.serialize_field("level_normalization", &self.level_normalization)?;
+        } else {
+            state.skip_field("level_normalization")?;
+        }
+        if !self.clock_skew.is_empty() {
+            state.serialize_field("clock_skew", &self.clock_skew)?;
+        } else {
+            state.skip_field("clock_skew")?;
+        }
+        if !self.tail_sampling.is_empty() {
+            state.serialize_field("tail_sampling", &self.tail_sampling)?;
+        } else {
+            state.skip_field("tail_sampling")?;
+        }
+        if !self.field_access_rules.is_empty() {
+            state.serialize_field("field_access_rules", &self.field_access_rules)?;
+        } else {
+            state.skip_field("field_access_rules")?;
+        }
+        if self.archive_expired_data {
+            state.serialize_field("archive_expired_data", &self.archive_expired_data)?;
+        } else {
+            state.skip_field("archive_expired_data")?;
+        }
+        if !self.compact_sort.is_empty() {
+            state.serialize_field("compact_sort", &self.compact_sort)?;
+        } else {
+            state.skip_field("compact_sort")?;
+        }
+        if self.compact_sort_removed_at > 0 {
+            state.serialize_field("compact_sort_removed_at", &self.compact_sort_removed_at)?;
+        } else {
+            state.skip_field("compact_sort_removed_at")?;
+        }
 
         if !self.defined_schema_fields.is_empty() {
             let mut fields = self.defined_schema_fields.clone();
@@ -1110,6 +2021,65 @@ impl From<&str> for StreamSettings {
             .get("enable_log_patterns_extraction")
             .and_then(Value::as_bool)
             .unwrap_or_default();
+        let ingest_priority = settings
+            .get("ingest_priority")
+            .and_then(|v| json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let ingest_quota = settings
+            .get("ingest_quota")
+            .and_then(|v| json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let redaction_rules = settings
+            .get("redaction_rules")
+            .and_then(|v| json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let computed_fields = settings
+            .get("computed_fields")
+            .and_then(|v| json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let low_cardinality_fields = settings
+            .get("low_cardinality_fields")
+            .and_then(|v| json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let ingest_dedup = settings
+            .get("ingest_dedup")
+            .and_then(|v| json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let wal_sync_policy = settings
+            .get("wal_sync_policy")
+            .and_then(|v| json::from_value(v.clone()).ok());
+        let pinned_field_types = settings
+            .get("pinned_field_types")
+            .and_then(|v| json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let level_normalization = settings
+            .get("level_normalization")
+            .and_then(|v| json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let clock_skew = settings
+            .get("clock_skew")
+            .and_then(|v| json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let tail_sampling = settings
+            .get("tail_sampling")
+            .and_then(|v| json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let field_access_rules = settings
+            .get("field_access_rules")
+            .and_then(|v| json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let archive_expired_data = settings
+            .get("archive_expired_data")
+            .and_then(Value::as_bool)
+            .unwrap_or_default();
+        let compact_sort = settings
+            .get("compact_sort")
+            .and_then(|v| json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let compact_sort_removed_at = settings
+            .get("compact_sort_removed_at")
+            .and_then(Value::as_i64)
+            .unwrap_or_default();
         Self {
             partition_time_level,
             partition_keys,
@@ -1129,6 +2099,21 @@ impl From<&str> for StreamSettings {
             index_all_values,
             enable_distinct_fields,
             enable_log_patterns_extraction,
+            ingest_priority,
+            ingest_quota,
+            redaction_rules,
+            computed_fields,
+            low_cardinality_fields,
+            ingest_dedup,
+            wal_sync_policy,
+            pinned_field_types,
+            level_normalization,
+            clock_skew,
+            tail_sampling,
+            field_access_rules,
+            archive_expired_data,
+            compact_sort,
+            compact_sort_removed_at,
         }
     }
 }
@@ -1143,6 +2128,15 @@ impl MemorySize for StreamSettings {
             + self.defined_schema_fields.mem_size()
             + self.distinct_value_fields.mem_size()
             + self.extended_retention_days.mem_size()
+            + self.redaction_rules.mem_size()
+            + self.computed_fields.mem_size()
+            + self.low_cardinality_fields.mem_size()
+            + self.ingest_dedup.fields.mem_size()
+            + self.pinned_field_types.mem_size()
+            + self.level_normalization.source_fields.mem_size()
+            + self.clock_skew.source_fields.mem_size()
+            + self.field_access_rules.mem_size()
+            + self.compact_sort.fields.mem_size()
     }
 }
 
@@ -1514,4 +2508,395 @@ mod tests {
         let expected_res = vec![TimeRange::new(0, 199), TimeRange::new(200, 300)];
         assert_eq!(TimeRange::flatten_overlapping_ranges(ranges), expected_res);
     }
+
+    #[test]
+    fn test_stream_settings_ingest_priority() {
+        let settings = StreamSettings {
+            ingest_priority: IngestPriority::High,
+            ..Default::default()
+        };
+        let data = json::to_string(&settings).unwrap();
+        assert!(data.contains(r#""ingest_priority":"high""#));
+        assert_eq!(
+            StreamSettings::from(data.as_str()).ingest_priority,
+            IngestPriority::High
+        );
+
+        // settings saved before the field existed
+        let settings = StreamSettings::from(r#"{"data_retention": 10}"#);
+        assert_eq!(settings.ingest_priority, IngestPriority::Normal);
+        assert!(IngestPriority::Low < IngestPriority::Normal);
+    }
+
+    #[test]
+    fn test_stream_settings_ingest_quota() {
+        let quota = IngestQuota {
+            records_per_sec: 1000,
+            bytes_per_sec: 0,
+        };
+        let settings = StreamSettings {
+            ingest_quota: quota,
+            ..Default::default()
+        };
+        let data = json::to_string(&settings).unwrap();
+        assert_eq!(StreamSettings::from(data.as_str()).ingest_quota, quota);
+        let data = json::to_string(&StreamSettings::default()).unwrap();
+        assert!(!data.contains("ingest_quota"));
+
+        let default = IngestQuota {
+            records_per_sec: 10,
+            bytes_per_sec: 20,
+        };
+        assert_eq!(
+            quota.or(default),
+            IngestQuota {
+                records_per_sec: 1000,
+                bytes_per_sec: 20,
+            }
+        );
+    }
+
+    #[test]
+    fn test_stream_settings_low_cardinality_fields() {
+        let settings = StreamSettings::from(r#"{"low_cardinality_fields": ["level", "service"]}"#);
+        assert_eq!(settings.low_cardinality_fields, vec!["level", "service"]);
+        let data = json::to_string(&settings).unwrap();
+        assert_eq!(StreamSettings::from(data.as_str()), settings);
+        let data = json::to_string(&StreamSettings::default()).unwrap();
+        assert!(!data.contains("low_cardinality_fields"));
+    }
+
+    #[test]
+    fn test_stream_settings_computed_fields() {
+        let settings = StreamSettings::from(
+            r#"{"computed_fields": [{"name": "app", "expr": "k8s_pod_labels_app"}]}"#,
+        );
+        assert_eq!(settings.computed_fields[0].name, "app");
+        let data = json::to_string(&settings).unwrap();
+        assert_eq!(StreamSettings::from(data.as_str()), settings);
+        let data = json::to_string(&StreamSettings::default()).unwrap();
+        assert!(!data.contains("computed_fields"));
+
+        let field = ComputedField {
+            name: "took_secs".to_string(),
+            expr: "CAST(took AS DOUBLE) / 1000 + \"delay\"".to_string(),
+        };
+        assert!(field.validate().is_ok());
+        assert_eq!(field.source_fields(), vec!["delay", "took"]);
+        let field = ComputedField {
+            name: "bad".to_string(),
+            expr: "took took".to_string(),
+        };
+        assert!(field.validate().is_err());
+        assert!(field.source_fields().is_empty());
+    }
+
+    #[test]
+    fn test_stream_settings_ingest_dedup() {
+        let settings =
+            StreamSettings::from(r#"{"ingest_dedup": {"window_secs": 300, "fields": ["id"]}}"#);
+        assert_eq!(
+            settings.ingest_dedup,
+            IngestDedup {
+                window_secs: 300,
+                fields: vec!["id".to_string()],
+            }
+        );
+        let data = json::to_string(&settings).unwrap();
+        assert_eq!(StreamSettings::from(data.as_str()), settings);
+        let data = json::to_string(&StreamSettings::default()).unwrap();
+        assert!(!data.contains("ingest_dedup"));
+    }
+
+    #[test]
+    fn test_stream_settings_clock_skew() {
+        let settings =
+            StreamSettings::from(r#"{"clock_skew": {"enabled": true, "correct": true}}"#);
+        assert!(settings.clock_skew.enabled && settings.clock_skew.correct);
+        assert_eq!(
+            settings.clock_skew.threshold_micros(),
+            DEFAULT_CLOCK_SKEW_THRESHOLD_SECS as i64 * 1_000_000
+        );
+        let data = json::to_string(&settings).unwrap();
+        assert_eq!(StreamSettings::from(data.as_str()), settings);
+        let data = json::to_string(&StreamSettings::default()).unwrap();
+        assert!(!data.contains("clock_skew"));
+    }
+
+    #[test]
+    fn test_stream_settings_compact_sort() {
+        let settings = StreamSettings::from(
+            r#"{"compact_sort": {"fields": ["k8s_namespace", "service"], "mode": "zorder"}}"#,
+        );
+        assert_eq!(
+            settings.compact_sort.fields,
+            vec!["k8s_namespace", "service"]
+        );
+        assert_eq!(settings.compact_sort.mode, CompactSortMode::ZOrder);
+        let data = json::to_string(&settings).unwrap();
+        assert_eq!(StreamSettings::from(data.as_str()), settings);
+        let data = json::to_string(&StreamSettings::default()).unwrap();
+        assert!(!data.contains("compact_sort"));
+        let settings = StreamSettings::from(r#"{"compact_sort": {"fields": ["service"]}}"#);
+        assert_eq!(settings.compact_sort.mode, CompactSortMode::Hierarchical);
+        let settings = StreamSettings::from(r#"{"compact_sort_removed_at": 1700000000000000}"#);
+        assert_eq!(settings.compact_sort_removed_at, 1700000000000000);
+        let data = json::to_string(&settings).unwrap();
+        assert_eq!(StreamSettings::from(data.as_str()), settings);
+    }
+
+    #[test]
+    fn test_stream_settings_files_sorted_by_time() {
+        let mut settings = StreamSettings::default();
+        assert!(settings.files_sorted_by_time(0));
+        // removed on 2023-11-14 22:13:20, the files of that day may be sorted
+        settings.compact_sort_removed_at = 1700000000000000;
+        assert!(!settings.files_sorted_by_time(1699920000000000));
+        assert!(!settings.files_sorted_by_time(1700000000000000));
+        assert!(settings.files_sorted_by_time(1700006400000000));
+        settings.compact_sort_removed_at = 0;
+        settings.compact_sort.fields = vec!["service".to_string()];
+        assert!(!settings.files_sorted_by_time(1700006400000000));
+    }
+
+    #[test]
+    fn test_compact_sort_validate() {
+        let sort = |fields: &[&str]| CompactSort {
+            fields: fields.iter().map(|f| f.to_string()).collect(),
+            mode: CompactSortMode::ZOrder,
+        };
+        assert!(sort(&["k8s_namespace", "service"]).validate().is_ok());
+        assert!(sort(&["service", "service"]).validate().is_err());
+        assert!(sort(&[TIMESTAMP_COL_NAME]).validate().is_err());
+        assert!(sort(&["a", "b", "c", "d", "e"]).validate().is_err());
+    }
+
+    #[test]
+    fn test_stream_settings_tail_sampling() {
+        let settings = StreamSettings::from(
+            r#"{"tail_sampling": {"enabled": true, "latency_threshold_ms": 500, "sample_rate": 0.1}}"#,
+        );
+        assert!(settings.tail_sampling.enabled);
+        assert_eq!(settings.tail_sampling.latency_threshold_ms, 500);
+        assert_eq!(
+            settings.tail_sampling.decision_wait_micros(),
+            DEFAULT_TAIL_SAMPLING_DECISION_WAIT_SECS as i64 * 1_000_000
+        );
+        assert!(settings.tail_sampling.validate().is_ok());
+        let data = json::to_string(&settings).unwrap();
+        assert_eq!(StreamSettings::from(data.as_str()), settings);
+        let data = json::to_string(&StreamSettings::default()).unwrap();
+        assert!(!data.contains("tail_sampling"));
+
+        let invalid = TailSampling {
+            sample_rate: 1.5,
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+        let invalid = TailSampling {
+            decision_wait_secs: MAX_TAIL_SAMPLING_DECISION_WAIT_SECS + 1,
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_stream_settings_field_access_rules() {
+        let settings = StreamSettings::from(
+            r#"{"field_access_rules": [{"field": "ssn", "denied_roles": ["viewer", "analyst"]}]}"#,
+        );
+        assert_eq!(settings.field_access_rules.len(), 1);
+        let rule = &settings.field_access_rules[0];
+        assert_eq!(rule.mode, FieldAccessMode::Strip);
+        assert!(rule.validate().is_ok());
+        assert!(rule.denies(["editor", "analyst"].into_iter()));
+        assert!(!rule.denies(["admin"].into_iter()));
+        let data = json::to_string(&settings).unwrap();
+        assert_eq!(StreamSettings::from(data.as_str()), settings);
+        let data = json::to_string(&StreamSettings::default()).unwrap();
+        assert!(!data.contains("field_access_rules"));
+
+        let rule = FieldAccessRule {
+            field: "ssn".to_string(),
+            ..Default::default()
+        };
+        assert!(rule.validate().is_err());
+        let rule = FieldAccessRule {
+            field: TIMESTAMP_COL_NAME.to_string(),
+            denied_roles: vec!["viewer".to_string()],
+            mode: FieldAccessMode::Mask,
+        };
+        assert!(rule.validate().is_err());
+    }
+
+    #[test]
+    fn test_stream_settings_archive_expired_data() {
+        let settings =
+            StreamSettings::from(r#"{"data_retention": 30, "archive_expired_data": true}"#);
+        assert_eq!(settings.data_retention, 30);
+        assert!(settings.archive_expired_data);
+        let data = json::to_string(&settings).unwrap();
+        assert_eq!(StreamSettings::from(data.as_str()), settings);
+        let data = json::to_string(&StreamSettings::default()).unwrap();
+        assert!(!data.contains("archive_expired_data"));
+    }
+
+    #[test]
+    fn test_stream_settings_wal_sync_policy() {
+        let settings = StreamSettings::from(r#"{"wal_sync_policy": "batch"}"#);
+        assert_eq!(settings.wal_sync_policy, Some(WalSyncPolicy::Batch));
+        let data = json::to_string(&settings).unwrap();
+        assert!(data.contains(r#""wal_sync_policy":"batch""#));
+        assert_eq!(StreamSettings::from(data.as_str()), settings);
+        let data = json::to_string(&StreamSettings::default()).unwrap();
+        assert!(!data.contains("wal_sync_policy"));
+        assert_eq!(
+            StreamSettings::from(r#"{"wal_sync_policy": "fast"}"#).wal_sync_policy,
+            None
+        );
+
+        assert_eq!(
+            "Interval".parse::<WalSyncPolicy>().unwrap(),
+            WalSyncPolicy::Interval
+        );
+        assert!("".parse::<WalSyncPolicy>().is_err());
+    }
+
+    #[test]
+    fn test_stream_settings_redaction_rules() {
+        let settings = StreamSettings::from(
+            r#"{"redaction_rules": [{"name": "email", "pattern": "[a-z]+@[a-z.]+"}]}"#,
+        );
+        assert_eq!(settings.redaction_rules.len(), 1);
+        assert_eq!(
+            settings.redaction_rules[0].replacement,
+            DEFAULT_REDACTION_REPLACEMENT
+        );
+        assert!(settings.redaction_rules[0].validate().is_ok());
+        let data = json::to_string(&settings).unwrap();
+        assert_eq!(StreamSettings::from(data.as_str()), settings);
+        let data = json::to_string(&StreamSettings::default()).unwrap();
+        assert!(!data.contains("redaction_rules"));
+
+        let rule = RedactionRule {
+            name: "bad".to_string(),
+            pattern: Some("(unclosed".to_string()),
+            ..Default::default()
+        };
+        assert!(rule.validate().is_err());
+        let rule = RedactionRule {
+            name: "empty".to_string(),
+            ..Default::default()
+        };
+        assert!(rule.validate().is_err());
+    }
+
+    #[test]
+    fn test_stream_settings_pinned_field_types() {
+        let settings = StreamSettings::from(
+            r#"{"pinned_field_types": [{"name": "status_code", "type": "Int64"}]}"#,
+        );
+        assert_eq!(
+            settings.pinned_field_types,
+            vec![DataField::new("status_code", DataType::Int64)]
+        );
+        let data = json::to_string(&settings).unwrap();
+        assert_eq!(StreamSettings::from(data.as_str()), settings);
+        let data = json::to_string(&StreamSettings::default()).unwrap();
+        assert!(!data.contains("pinned_field_types"));
+    }
+
+    #[test]
+    fn test_level_mapping() {
+        let mapping = LevelMapping::default();
+        assert_eq!(mapping.get("WARNING"), Some("warn"));
+        assert_eq!(mapping.get(" Err "), Some("error"));
+        assert_eq!(mapping.get("30"), Some("info"));
+        assert_eq!(mapping.get("sev=4"), Some("warn"));
+        assert_eq!(mapping.get("chatty"), None);
+        assert!(
+            mapping
+                .mappings
+                .values()
+                .all(|v| CANONICAL_LEVELS.contains(&v.as_str()))
+        );
+
+        let mut mapping: LevelMapping =
+            json::from_str(r#"{"mappings": {"Chatty": "DEBUG"}}"#).unwrap();
+        assert!(mapping.validate().is_ok());
+        assert_eq!(mapping.get("chatty"), Some("debug"));
+        let mut mapping: LevelMapping =
+            json::from_str(r#"{"mappings": {"loud": "critical"}}"#).unwrap();
+        assert!(mapping.validate().is_err());
+    }
+
+    #[test]
+    fn test_stream_settings_template_apply() {
+        let template: StreamSettingsTemplate = json::from_str(
+            r#"{
+                "partition_keys": [{"field": "service"}],
+                "full_text_search_keys": ["log", "body"],
+                "index_fields": ["trace_id"],
+                "data_retention": 30,
+                "index_original_data": true,
+                "ingest_priority": "low"
+            }"#,
+        )
+        .unwrap();
+        assert!(!template.is_empty());
+        assert!(StreamSettingsTemplate::default().is_empty());
+
+        let mut settings = StreamSettings {
+            full_text_search_keys: vec!["log".to_string()],
+            ..Default::default()
+        };
+        template.apply(&mut settings);
+        assert_eq!(
+            settings.partition_keys,
+            vec![StreamPartition::new("service")]
+        );
+        assert_eq!(settings.full_text_search_keys, vec!["log", "body"]);
+        assert_eq!(settings.index_fields, vec!["trace_id"]);
+        assert_eq!(settings.data_retention, 30);
+        assert!(settings.index_original_data);
+        assert!(settings.store_original_data);
+        assert_eq!(settings.ingest_priority, IngestPriority::Low);
+        // unset template fields keep the defaults
+        assert!(settings.partition_time_level.is_none());
+        assert!(settings.enable_distinct_fields);
+    }
+
+    #[test]
+    fn test_stream_settings_template_validate() {
+        let template = StreamSettingsTemplate {
+            index_fields: vec!["trace_id".to_string()],
+            ..Default::default()
+        };
+        assert!(template.validate(StreamType::Logs).is_ok());
+
+        let template = StreamSettingsTemplate {
+            data_retention: -1,
+            ..Default::default()
+        };
+        assert!(template.validate(StreamType::Logs).is_err());
+
+        let template = StreamSettingsTemplate {
+            index_original_data: Some(true),
+            index_all_values: Some(true),
+            ..Default::default()
+        };
+        assert!(template.validate(StreamType::Logs).is_err());
+
+        let template = StreamSettingsTemplate {
+            full_text_search_keys: vec![get_config().common.column_all.clone()],
+            ..Default::default()
+        };
+        assert!(template.validate(StreamType::Logs).is_err());
+
+        let template = StreamSettingsTemplate {
+            defined_schema_fields: vec!["service".to_string()],
+            ..Default::default()
+        };
+        assert!(template.validate(StreamType::EnrichmentTables).is_err());
+    }
 }
@@ -37,6 +37,11 @@ pub enum TriggerModule {
     DerivedStream,
     QueryRecommendations,
     Backfill,
+    ScheduledExport,
+    AlertBacktest,
+    RecordingRule,
+    Replay,
+    DbPoller,
 }
 
 impl std::fmt::Display for TriggerModule {
@@ -47,6 +52,11 @@ impl std::fmt::Display for TriggerModule {
             Self::DerivedStream => write!(f, "derived_stream"),
             Self::QueryRecommendations => write!(f, "query_recommendations"),
             Self::Backfill => write!(f, "backfill"),
+            Self::ScheduledExport => write!(f, "scheduled_export"),
+            Self::AlertBacktest => write!(f, "alert_backtest"),
+            Self::RecordingRule => write!(f, "recording_rule"),
+            Self::Replay => write!(f, "replay"),
+            Self::DbPoller => write!(f, "db_poller"),
         }
     }
 }
@@ -95,6 +105,10 @@ pub struct ScheduledTriggerData {
     pub tolerance: i64,
     #[serde(default)]
     pub last_satisfied_at: Option<i64>,
+    /// Whether the alert was satisfied by its last evaluation, its incidents
+    /// are resolved once it is not anymore
+    #[serde(default)]
+    pub firing: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub backfill_job: Option<BackfillJob>,
 }
@@ -139,6 +139,73 @@ pub static INGEST_ERRORS: Lazy<IntCounterVec> = Lazy::new(|| {
     )
     .expect("Metric created")
 });
+pub static INGEST_RECEIVER_RECORDS: Lazy<IntCounterVec> = Lazy::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "ingest_receiver_records",
+            "Records seen by each ingestion receiver, by outcome: accepted, refused or dropped."
+                .to_owned()
+                + HELP_SUFFIX,
+        )
+        .namespace(NAMESPACE)
+        .const_labels(create_const_labels()),
+        &["receiver", "outcome"],
+    )
+    .expect("Metric created")
+});
+pub static INGEST_CLOCK_SKEW_SECONDS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    IntGaugeVec::new(
+        Opts::new(
+            "ingest_clock_skew_seconds",
+            "Largest clock skew of the sources of the stream seen by this node, negative when \
+             their clock is behind."
+                .to_owned()
+                + HELP_SUFFIX,
+        )
+        .namespace(NAMESPACE)
+        .const_labels(create_const_labels()),
+        &["organization", "stream_type", "stream"],
+    )
+    .expect("Metric created")
+});
+pub static INGEST_CLOCK_SKEW_CORRECTED_RECORDS: Lazy<IntCounterVec> = Lazy::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "ingest_clock_skew_corrected_records",
+            "Records whose timestamp was corrected for the clock skew of their source.".to_owned()
+                + HELP_SUFFIX,
+        )
+        .namespace(NAMESPACE)
+        .const_labels(create_const_labels()),
+        &["organization", "stream_type", "stream"],
+    )
+    .expect("Metric created")
+});
+pub static INGEST_TAIL_SAMPLING_TRACES: Lazy<IntCounterVec> = Lazy::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "ingest_tail_sampling_traces",
+            "Traces decided by tail sampling, by the policy keeping them or dropped.".to_owned()
+                + HELP_SUFFIX,
+        )
+        .namespace(NAMESPACE)
+        .const_labels(create_const_labels()),
+        &["organization", "stream", "decision"],
+    )
+    .expect("Metric created")
+});
+pub static INGEST_TAIL_SAMPLING_BUFFERED_BYTES: Lazy<IntGauge> = Lazy::new(|| {
+    IntGauge::with_opts(
+        Opts::new(
+            "ingest_tail_sampling_buffered_bytes",
+            "Estimated size of the spans held until their trace is decided.".to_owned()
+                + HELP_SUFFIX,
+        )
+        .namespace(NAMESPACE)
+        .const_labels(create_const_labels()),
+    )
+    .expect("Metric created")
+});
 pub static INGEST_WAL_USED_BYTES: Lazy<IntGaugeVec> = Lazy::new(|| {
     IntGaugeVec::new(
         Opts::new(
@@ -212,6 +279,42 @@ pub static INGEST_MEMTABLE_ARROW_BYTES: Lazy<IntGaugeVec> = Lazy::new(|| {
     )
     .expect("Metric created")
 });
+pub static INGEST_MEMTABLE_SPILLED_BYTES: Lazy<IntGaugeVec> = Lazy::new(|| {
+    IntGaugeVec::new(
+        Opts::new(
+            "ingest_memtable_spilled_bytes",
+            "Ingestor memtable bytes spilled to disk.".to_owned(),
+        )
+        .namespace(NAMESPACE)
+        .const_labels(create_const_labels()),
+        &[],
+    )
+    .expect("Metric created")
+});
+pub static INGEST_MEMTABLE_SPILL_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "ingest_memtable_spill_total",
+            "Ingestor memtable arrow bytes spilled to disk.".to_owned(),
+        )
+        .namespace(NAMESPACE)
+        .const_labels(create_const_labels()),
+        &[],
+    )
+    .expect("Metric created")
+});
+pub static INGEST_MEMTABLE_SPILL_READ_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "ingest_memtable_spill_read_total",
+            "Ingestor spilled memtable bytes read back from disk.".to_owned(),
+        )
+        .namespace(NAMESPACE)
+        .const_labels(create_const_labels()),
+        &[],
+    )
+    .expect("Metric created")
+});
 pub static INGEST_MEMTABLE_FILES: Lazy<IntGaugeVec> = Lazy::new(|| {
     IntGaugeVec::new(
         Opts::new(
@@ -1251,6 +1354,19 @@ pub static PIPELINE_EXPORTED_BYTES: Lazy<IntCounterVec> = Lazy::new(|| {
     .expect("Metric created")
 });
 
+pub static PIPELINE_NODE_ERRORS: Lazy<IntCounterVec> = Lazy::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "pipeline_node_errors",
+            "Records failing in each pipeline node",
+        )
+        .namespace(NAMESPACE)
+        .const_labels(create_const_labels()),
+        &["organization", "pipeline_id", "node_id", "node_type"],
+    )
+    .expect("Metric created")
+});
+
 pub static QUERY_AGGREGATION_CACHE_ITEMS: Lazy<IntGaugeVec> = Lazy::new(|| {
     IntGaugeVec::new(
         Opts::new(
@@ -1620,6 +1736,21 @@ fn register_metrics(registry: &Registry) {
     registry
         .register(Box::new(INGEST_ERRORS.clone()))
         .expect("Metric registered");
+    registry
+        .register(Box::new(INGEST_RECEIVER_RECORDS.clone()))
+        .expect("Metric registered");
+    registry
+        .register(Box::new(INGEST_CLOCK_SKEW_SECONDS.clone()))
+        .expect("Metric registered");
+    registry
+        .register(Box::new(INGEST_CLOCK_SKEW_CORRECTED_RECORDS.clone()))
+        .expect("Metric registered");
+    registry
+        .register(Box::new(INGEST_TAIL_SAMPLING_TRACES.clone()))
+        .expect("Metric registered");
+    registry
+        .register(Box::new(INGEST_TAIL_SAMPLING_BUFFERED_BYTES.clone()))
+        .expect("Metric registered");
     registry
         .register(Box::new(INGEST_WAL_USED_BYTES.clone()))
         .expect("Metric registered");
@@ -1638,6 +1769,15 @@ fn register_metrics(registry: &Registry) {
     registry
         .register(Box::new(INGEST_MEMTABLE_ARROW_BYTES.clone()))
         .expect("Metric registered");
+    registry
+        .register(Box::new(INGEST_MEMTABLE_SPILLED_BYTES.clone()))
+        .expect("Metric registered");
+    registry
+        .register(Box::new(INGEST_MEMTABLE_SPILL_TOTAL.clone()))
+        .expect("Metric registered");
+    registry
+        .register(Box::new(INGEST_MEMTABLE_SPILL_READ_TOTAL.clone()))
+        .expect("Metric registered");
     registry
         .register(Box::new(INGEST_MEMTABLE_FILES.clone()))
         .expect("Metric registered");
@@ -1918,6 +2058,9 @@ fn register_metrics(registry: &Registry) {
     registry
         .register(Box::new(PIPELINE_EXPORTED_BYTES.clone()))
         .expect("Metric registered");
+    registry
+        .register(Box::new(PIPELINE_NODE_ERRORS.clone()))
+        .expect("Metric registered");
     registry
         .register(Box::new(QUERY_AGGREGATION_CACHE_ITEMS.clone()))
         .expect("Metric registered");
@@ -426,6 +426,8 @@ fn convert_to_destination(config: PrebuiltDestinationConfig) -> Result<Destinati
                     output_format: Some(output_format),
                     destination_type: Some(endpoint_config.destination_type),
                     metadata: endpoint_metadata,
+                    vrl_template: None,
+                    proxy: None,
                 }),
             }
         }
@@ -500,6 +502,8 @@ fn load_builtin_destinations() -> Vec<Destination> {
                             "Slack webhook for team notifications".to_string(),
                         ),
                     ]),
+                    vrl_template: None,
+                    proxy: None,
                 }),
             },
         },
@@ -528,6 +532,8 @@ fn load_builtin_destinations() -> Vec<Destination> {
                             "Microsoft Teams webhook for team notifications".to_string(),
                         ),
                     ]),
+                    vrl_template: None,
+                    proxy: None,
                 }),
             },
         },
@@ -558,6 +564,8 @@ fn load_builtin_destinations() -> Vec<Destination> {
                             "PagerDuty incident management".to_string(),
                         ),
                     ]),
+                    vrl_template: None,
+                    proxy: None,
                 }),
             },
         },
@@ -585,6 +593,8 @@ fn load_builtin_destinations() -> Vec<Destination> {
                             "Discord webhook for community notifications".to_string(),
                         ),
                     ]),
+                    vrl_template: None,
+                    proxy: None,
                 }),
             },
         },
@@ -612,6 +622,8 @@ fn load_builtin_destinations() -> Vec<Destination> {
                             "Generic webhook destination".to_string(),
                         ),
                     ]),
+                    vrl_template: None,
+                    proxy: None,
                 }),
             },
         },
@@ -642,6 +654,8 @@ fn load_builtin_destinations() -> Vec<Destination> {
                             "Opsgenie incident management and alerting".to_string(),
                         ),
                     ]),
+                    vrl_template: None,
+                    proxy: None,
                 }),
             },
         },
@@ -672,6 +686,8 @@ fn load_builtin_destinations() -> Vec<Destination> {
                             "ServiceNow incident management".to_string(),
                         ),
                     ]),
+                    vrl_template: None,
+                    proxy: None,
                 }),
             },
         },
@@ -683,6 +699,7 @@ fn load_builtin_destinations() -> Vec<Destination> {
                 template: Some("prebuilt_email".to_string()),
                 destination_type: DestinationType::Email(Email {
                     recipients: vec!["admin@your-domain.com".to_string()],
+                    attach_csv: false,
                 }),
             },
         },
@@ -14,7 +14,7 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 /// usize indicates the number of parts to skip based on their actual paths.
-const QUERIER_ROUTES: [(&str, usize); 25] = [
+const QUERIER_ROUTES: [(&str, usize); 30] = [
     ("config", 0),         // /config
     ("summary", 2),        // /api/{org_id}/summary
     ("organizations", 1),  // /api/organizations
@@ -26,6 +26,8 @@ const QUERIER_ROUTES: [(&str, usize); 25] = [
     ("query_manager", 2),  // /api/{org_id}/query_manager/...
     ("_search", 2),        // /api/{org_id}/_search
     ("_search_stream", 2), // /api/{org_id}/_search_stream
+    ("_msearch", 2),       // /api/{org_id}/_msearch
+    ("_msearch", 3),       // /api/{org_id}/{stream_name}/_msearch
     ("_values_stream", 2), // /api/{org_id}/_values_stream
     ("_around", 3),        // /api/{org_id}/{stream_name}/_around
     ("_values", 3),        // /api/{org_id}/{stream_name}/_values
@@ -39,6 +41,9 @@ const QUERIER_ROUTES: [(&str, usize); 25] = [
     ("prometheus/api/v1/metadata", 2),        // /api/{org_id}/prometheus/api/v1/metadata
     ("prometheus/api/v1/labels", 2),          // /api/{org_id}/prometheus/api/v1/labels
     ("prometheus/api/v1/label/", 2),          // /api/{org_id}/prometheus/api/v1/label/
+    ("prometheus/api/v1/read", 2),            // /api/{org_id}/prometheus/api/v1/read
+    ("loki/api/v1/query_range", 2),           // /api/{org_id}/loki/api/v1/query_range
+    ("loki/api/v1/label", 2),                 // /api/{org_id}/loki/api/v1/labels, label/
     ("chat_stream", 3),                       /* /api/{org_id}/ai/chat_stream
                                                * {label_name}/
                                                * values */
@@ -148,6 +153,19 @@ mod tests {
         // Test prometheus routes
         assert!(is_querier_route("/api/org1/prometheus/api/v1/query"));
         assert!(is_querier_route("/api/org1/prometheus/api/v1/query_range"));
+        assert!(is_querier_route("/api/org1/prometheus/api/v1/read"));
+        assert!(!is_querier_route("/api/org1/prometheus/api/v1/write"));
+
+        // Test loki routes, push stays on the ingesters
+        assert!(is_querier_route("/api/org1/loki/api/v1/query_range"));
+        assert!(is_querier_route("/api/org1/loki/api/v1/labels"));
+        assert!(is_querier_route("/api/org1/loki/api/v1/label/app/values"));
+        assert!(!is_querier_route("/api/org1/loki/api/v1/push"));
+
+        // Test elasticsearch multi search routes
+        assert!(is_querier_route("/api/org1/_msearch"));
+        assert!(is_querier_route("/api/org1/_search_anomalies"));
+        assert!(is_querier_route("/api/org1/logs/_msearch"));
 
         // Test service_streams routes
         assert!(is_querier_route("/api/org1/service_streams/_analytics"));
@@ -0,0 +1,309 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Log template miner based on Drain: An Online Log Parsing Approach with
+//! Fixed Depth Tree (He et al., ICWS 2017).
+//!
+//! Messages are routed to a leaf by their token count and first token, then
+//! joined to the most similar template of the leaf, replacing the tokens that
+//! differ with a wildcard. Miners built over different slices of the data can
+//! be merged, so the work can be spread over several searches.
+
+use hashbrown::HashMap;
+
+pub const WILDCARD: &str = "<*>";
+
+/// Number of leading tokens used to route a message to a leaf, names and
+/// other variables often come second so only the first one is used.
+const DEFAULT_DEPTH: usize = 1;
+/// Messages are cut to this many tokens, the rest becomes one wildcard.
+const MAX_TOKENS: usize = 128;
+const DEFAULT_MAX_EXAMPLES: usize = 3;
+const DEFAULT_MAX_CLUSTERS: usize = 1000;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cluster {
+    pub tokens: Vec<String>,
+    pub count: u64,
+    pub examples: Vec<String>,
+    pub first_seen: i64,
+    pub last_seen: i64,
+}
+
+impl Cluster {
+    pub fn template(&self) -> String {
+        self.tokens.join(" ")
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Drain {
+    similarity: f64,
+    depth: usize,
+    max_examples: usize,
+    max_clusters: usize,
+    clusters: Vec<Cluster>,
+    leaves: HashMap<(usize, Vec<String>), Vec<usize>>,
+    total: u64,
+    dropped: u64,
+}
+
+impl Drain {
+    /// `similarity` is the minimum share of equal tokens for a message to join
+    /// a template.
+    pub fn new(similarity: f64) -> Self {
+        Self {
+            similarity,
+            depth: DEFAULT_DEPTH,
+            max_examples: DEFAULT_MAX_EXAMPLES,
+            max_clusters: DEFAULT_MAX_CLUSTERS,
+            clusters: Vec::new(),
+            leaves: HashMap::new(),
+            total: 0,
+            dropped: 0,
+        }
+    }
+
+    pub fn with_max_clusters(mut self, max_clusters: usize) -> Self {
+        self.max_clusters = max_clusters;
+        self
+    }
+
+    /// Number of messages added, including the ones merged from other miners.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Number of messages that matched no template after the cluster limit was
+    /// reached.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    pub fn add(&mut self, message: &str, timestamp: i64) {
+        let tokens = tokenize(message);
+        self.add_tokens(tokens, 1, &[message.to_string()], timestamp, timestamp);
+    }
+
+    pub fn merge(&mut self, other: Drain) {
+        self.dropped += other.dropped;
+        for cluster in other.clusters {
+            self.add_tokens(
+                cluster.tokens,
+                cluster.count,
+                &cluster.examples,
+                cluster.first_seen,
+                cluster.last_seen,
+            );
+        }
+    }
+
+    /// Returns true if the template tokens would join one of the templates of
+    /// this miner.
+    pub fn has_match(&self, tokens: &[String]) -> bool {
+        self.leaves
+            .get(&leaf_key(tokens, self.depth))
+            .is_some_and(|ids| {
+                ids.iter()
+                    .any(|id| similarity(&self.clusters[*id].tokens, tokens) >= self.similarity)
+            })
+    }
+
+    /// Returns the clusters ordered by count, most frequent first.
+    pub fn into_clusters(self) -> Vec<Cluster> {
+        let mut clusters = self.clusters;
+        clusters.sort_by(|a, b| b.count.cmp(&a.count).then(a.tokens.cmp(&b.tokens)));
+        clusters
+    }
+
+    fn add_tokens(
+        &mut self,
+        tokens: Vec<String>,
+        count: u64,
+        examples: &[String],
+        first_seen: i64,
+        last_seen: i64,
+    ) {
+        self.total += count;
+        let leaf = self
+            .leaves
+            .entry(leaf_key(&tokens, self.depth))
+            .or_default();
+        let best = leaf
+            .iter()
+            .map(|id| (*id, similarity(&self.clusters[*id].tokens, &tokens)))
+            .filter(|(_, sim)| *sim >= self.similarity)
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        match best {
+            Some((id, _)) => {
+                let cluster = &mut self.clusters[id];
+                for (template, token) in cluster.tokens.iter_mut().zip(tokens.iter()) {
+                    if template != token {
+                        *template = WILDCARD.to_string();
+                    }
+                }
+                cluster.count += count;
+                let room = self.max_examples.saturating_sub(cluster.examples.len());
+                cluster.examples.extend(examples.iter().take(room).cloned());
+                cluster.first_seen = cluster.first_seen.min(first_seen);
+                cluster.last_seen = cluster.last_seen.max(last_seen);
+            }
+            None if self.clusters.len() < self.max_clusters => {
+                leaf.push(self.clusters.len());
+                self.clusters.push(Cluster {
+                    tokens,
+                    count,
+                    examples: examples.iter().take(self.max_examples).cloned().collect(),
+                    first_seen,
+                    last_seen,
+                });
+            }
+            None => self.dropped += count,
+        }
+    }
+}
+
+/// Splits a message on whitespace and masks the tokens holding digits, which
+/// are almost always ids, numbers, addresses or timestamps.
+pub fn tokenize(message: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for token in message.split_whitespace() {
+        if tokens.len() == MAX_TOKENS {
+            tokens.push(WILDCARD.to_string());
+            break;
+        }
+        if token.bytes().any(|b| b.is_ascii_digit()) {
+            tokens.push(WILDCARD.to_string());
+        } else {
+            tokens.push(token.to_string());
+        }
+    }
+    tokens
+}
+
+fn leaf_key(tokens: &[String], depth: usize) -> (usize, Vec<String>) {
+    (tokens.len(), tokens.iter().take(depth).cloned().collect())
+}
+
+/// Share of the tokens equal to a constant token of the template.
+pub fn similarity(template: &[String], tokens: &[String]) -> f64 {
+    if template.is_empty() {
+        return 1.0;
+    }
+    let equal = template
+        .iter()
+        .zip(tokens.iter())
+        .filter(|(t, n)| t.as_str() != WILDCARD && t == n)
+        .count();
+    equal as f64 / template.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn templates(drain: Drain) -> Vec<(String, u64)> {
+        drain
+            .into_clusters()
+            .into_iter()
+            .map(|c| (c.template(), c.count))
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize_masks_digits() {
+        assert_eq!(
+            tokenize("connected to 10.0.0.1 port 8080 as   admin"),
+            vec!["connected", "to", "<*>", "port", "<*>", "as", "admin"]
+        );
+        assert!(tokenize("").is_empty());
+        let long = vec!["a"; MAX_TOKENS + 10].join(" ");
+        let tokens = tokenize(&long);
+        assert_eq!(tokens.len(), MAX_TOKENS + 1);
+        assert_eq!(tokens.last().unwrap(), WILDCARD);
+    }
+
+    #[test]
+    fn test_drain_groups_messages() {
+        let mut drain = Drain::new(0.5);
+        drain.add("user alice logged in from web", 3);
+        drain.add("user bob logged in from web", 1);
+        drain.add("user carol logged in from mobile", 2);
+        drain.add("disk full on /dev/sda1", 5);
+        drain.add("disk full on /dev/sdb1", 4);
+        assert_eq!(drain.total(), 5);
+
+        let clusters = drain.into_clusters();
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].template(), "user <*> logged in from <*>");
+        assert_eq!(clusters[0].count, 3);
+        assert_eq!(clusters[0].first_seen, 1);
+        assert_eq!(clusters[0].last_seen, 3);
+        assert_eq!(clusters[0].examples.len(), 3);
+        assert_eq!(clusters[1].template(), "disk full on <*>");
+        assert_eq!(clusters[1].count, 2);
+    }
+
+    #[test]
+    fn test_drain_keeps_dissimilar_messages_apart() {
+        let mut drain = Drain::new(0.5);
+        drain.add("request failed with timeout", 1);
+        drain.add("request served from cache", 1);
+        drain.add("connection reset by peer", 1);
+        assert_eq!(drain.into_clusters().len(), 3);
+    }
+
+    #[test]
+    fn test_drain_limits_examples_and_clusters() {
+        let mut drain = Drain::new(0.5).with_max_clusters(1);
+        for _ in 0..10 {
+            drain.add("cache miss for key", 1);
+        }
+        drain.add("something else entirely happened", 1);
+        assert_eq!(drain.dropped(), 1);
+        let clusters = drain.into_clusters();
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].count, 10);
+        assert_eq!(clusters[0].examples.len(), DEFAULT_MAX_EXAMPLES);
+    }
+
+    #[test]
+    fn test_drain_merge() {
+        let mut a = Drain::new(0.5);
+        a.add("user alice logged in", 1);
+        a.add("job build finished", 2);
+        let mut b = Drain::new(0.5);
+        b.add("user bob logged in", 3);
+        b.add("job build started", 4);
+        a.merge(b);
+        assert_eq!(a.total(), 4);
+        assert_eq!(
+            templates(a),
+            vec![
+                ("job build <*>".to_string(), 2),
+                ("user <*> logged in".to_string(), 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_drain_has_match() {
+        let mut baseline = Drain::new(0.5);
+        baseline.add("user alice logged in", 1);
+        assert!(baseline.has_match(&tokenize("user bob logged in")));
+        assert!(!baseline.has_match(&tokenize("user bob logged out now")));
+        assert!(!baseline.has_match(&tokenize("payment declined for order")));
+    }
+}
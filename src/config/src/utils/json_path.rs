@@ -0,0 +1,171 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The subset of JSONPath needed to pick values out of payloads: the root
+//! `$`, members `.name` and `['name']`, array indexes `[0]`, negative ones
+//! counting from the end, and the wildcards `.*` and `[*]`.
+
+use std::str::FromStr;
+
+use serde_json::Value;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Segment {
+    Member(String),
+    Index(i64),
+    Wildcard,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonPath {
+    segments: Vec<Segment>,
+}
+
+impl FromStr for JsonPath {
+    type Err = String;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let mut rest = path
+            .trim()
+            .strip_prefix('$')
+            .ok_or_else(|| format!("JSONPath {path} must start with '$'"))?;
+        let mut segments = Vec::new();
+        while !rest.is_empty() {
+            if let Some(r) = rest.strip_prefix('.') {
+                if r.starts_with('.') {
+                    return Err(format!("recursive descent isn't supported in {path}"));
+                }
+                let end = r.find(['.', '[']).unwrap_or(r.len());
+                let name = &r[..end];
+                if name.is_empty() {
+                    return Err(format!("empty member name in JSONPath {path}"));
+                }
+                segments.push(if name == "*" {
+                    Segment::Wildcard
+                } else {
+                    Segment::Member(name.to_string())
+                });
+                rest = &r[end..];
+            } else if let Some(r) = rest.strip_prefix('[') {
+                let (segment, r) = match r.chars().next() {
+                    Some(quote @ ('\'' | '"')) => {
+                        let inner = &r[1..];
+                        let end = inner
+                            .find(quote)
+                            .ok_or_else(|| format!("unterminated member name in {path}"))?;
+                        (Segment::Member(inner[..end].to_string()), &inner[end + 1..])
+                    }
+                    _ => {
+                        let end = r
+                            .find(']')
+                            .ok_or_else(|| format!("unterminated index in {path}"))?;
+                        let index = r[..end].trim();
+                        let segment = if index == "*" {
+                            Segment::Wildcard
+                        } else {
+                            Segment::Index(
+                                index
+                                    .parse()
+                                    .map_err(|_| format!("invalid index {index} in {path}"))?,
+                            )
+                        };
+                        (segment, &r[end..])
+                    }
+                };
+                rest = r
+                    .strip_prefix(']')
+                    .ok_or_else(|| format!("expected ']' in JSONPath {path}"))?;
+                segments.push(segment);
+            } else {
+                return Err(format!("unexpected {rest} in JSONPath {path}"));
+            }
+        }
+        Ok(Self { segments })
+    }
+}
+
+impl JsonPath {
+    /// The values at the path, in the order of the document
+    pub fn select<'a>(&self, value: &'a Value) -> Vec<&'a Value> {
+        let mut current = vec![value];
+        for segment in self.segments.iter() {
+            current = current
+                .into_iter()
+                .flat_map(|v| -> Vec<&'a Value> {
+                    match (segment, v) {
+                        (Segment::Member(name), Value::Object(map)) => {
+                            map.get(name).into_iter().collect()
+                        }
+                        (Segment::Index(i), Value::Array(arr)) => {
+                            let i = if *i < 0 { arr.len() as i64 + i } else { *i };
+                            usize::try_from(i)
+                                .ok()
+                                .and_then(|i| arr.get(i))
+                                .into_iter()
+                                .collect()
+                        }
+                        (Segment::Wildcard, Value::Object(map)) => map.values().collect(),
+                        (Segment::Wildcard, Value::Array(arr)) => arr.iter().collect(),
+                        _ => vec![],
+                    }
+                })
+                .collect();
+        }
+        current
+    }
+
+    /// The value at the path, or the array of the values matched by the
+    /// wildcards, `None` when nothing matches
+    pub fn query(&self, value: &Value) -> Option<Value> {
+        let values = self.select(value);
+        if !self.segments.contains(&Segment::Wildcard) {
+            return values.first().map(|v| (*v).clone());
+        }
+        (!values.is_empty()).then(|| Value::Array(values.into_iter().cloned().collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_json_path() {
+        let doc = json!({
+            "repository": {"full_name": "openobserve/openobserve"},
+            "commits": [{"id": "a1"}, {"id": "b2"}],
+            "labels.name": "bug"
+        });
+        let query = |path: &str| path.parse::<JsonPath>().unwrap().query(&doc);
+        assert_eq!(
+            query("$.repository.full_name").unwrap(),
+            "openobserve/openobserve"
+        );
+        assert_eq!(query("$.commits[0].id").unwrap(), "a1");
+        assert_eq!(query("$.commits[-1].id").unwrap(), "b2");
+        assert_eq!(query("$.commits[*].id").unwrap(), json!(["a1", "b2"]));
+        assert_eq!(query("$['labels.name']").unwrap(), "bug");
+        assert_eq!(query("$").unwrap(), doc);
+        assert!(query("$.missing.field").is_none());
+        assert!(query("$.commits[5]").is_none());
+        assert!(query("$.missing[*]").is_none());
+
+        for invalid in ["repository", "$..id", "$.commits[x]", "$['open", "$.a[0"] {
+            assert!(invalid.parse::<JsonPath>().is_err(), "{invalid}");
+        }
+    }
+}
@@ -18,12 +18,14 @@ pub mod async_file;
 pub mod async_walkdir;
 pub mod base64;
 pub mod download_utils;
+pub mod drain;
 pub mod enrichment_local_cache;
 pub mod file;
 pub mod flatten;
 pub mod hash;
 pub mod inverted_index;
 pub mod json;
+pub mod json_path;
 pub mod md5;
 pub mod parquet;
 pub mod pausable_job;
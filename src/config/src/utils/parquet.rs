@@ -30,7 +30,10 @@ use parquet::{
         async_reader::{AsyncFileReader, ParquetRecordBatchStream},
     },
     basic::{Compression, Encoding},
-    file::{metadata::KeyValue, properties::WriterProperties},
+    file::{
+        metadata::KeyValue,
+        properties::{EnabledStatistics, WriterProperties},
+    },
 };
 
 use crate::{config::*, ider, meta::stream::FileMeta};
@@ -61,6 +64,16 @@ pub fn new_parquet_writer<'a>(
         writer_props = writer_props
             .set_column_compression(TIMESTAMP_COL_NAME.into(), Compression::UNCOMPRESSED);
     }
+    // Page level min/max go to the column index, with the offset index the
+    // reader can skip the pages out of the filters inside a row group, e.g. the
+    // pages out of the time range since the rows are sorted by _timestamp
+    writer_props = if cfg.common.parquet_page_index_enabled {
+        writer_props
+            .set_statistics_enabled(EnabledStatistics::Page)
+            .set_data_page_row_count_limit(cfg.common.parquet_page_row_limit)
+    } else {
+        writer_props.set_statistics_enabled(EnabledStatistics::Chunk)
+    };
     if write_metadata {
         writer_props = writer_props.set_key_value_metadata(Some(vec![
             KeyValue::new("min_ts".to_string(), metadata.min_ts.to_string()),
@@ -249,10 +262,11 @@ mod tests {
     use std::sync::Arc;
 
     use arrow::{
-        array::{Int32Array, StringArray},
+        array::{Int32Array, Int64Array, StringArray},
         datatypes::{DataType, Field, Schema},
         record_batch::RecordBatch,
     };
+    use parquet::arrow::arrow_reader::ArrowReaderOptions;
 
     use super::*;
 
@@ -421,6 +435,43 @@ mod tests {
         assert_eq!(read_metadata.original_size, metadata.original_size);
     }
 
+    #[tokio::test]
+    async fn test_write_page_index() {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            TIMESTAMP_COL_NAME,
+            DataType::Int64,
+            false,
+        )]));
+        let rows = 3 * PARQUET_BATCH_SIZE as i64;
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int64Array::from_iter_values((0..rows).rev()))],
+        )
+        .unwrap();
+        let metadata = FileMeta {
+            min_ts: 0,
+            max_ts: rows - 1,
+            records: rows,
+            ..Default::default()
+        };
+        let data = write_recordbatch_to_parquet(schema, &[batch], &[], &metadata)
+            .await
+            .unwrap();
+
+        let reader = ParquetRecordBatchStreamBuilder::new_with_options(
+            Cursor::new(data),
+            ArrowReaderOptions::new().with_page_index(true),
+        )
+        .await
+        .unwrap();
+        let metadata = reader.metadata();
+        assert_eq!(metadata.num_row_groups(), 1);
+        assert!(metadata.column_index().is_some());
+        let offset_index = metadata.offset_index().unwrap();
+        // one page per write batch
+        assert_eq!(offset_index[0][0].page_locations().len(), 3);
+    }
+
     #[test]
     fn test_parse_file_key_columns() {
         let key = "files/default/logs/olympics/2022/10/03/10/6982652937134804993_1.parquet";
@@ -25,6 +25,19 @@ pub fn get_hostname() -> String {
     sysinfo::System::host_name().unwrap_or("unknown".to_string())
 }
 
+/// Start time of the current process, in seconds since the epoch
+pub fn get_process_start_time() -> u64 {
+    let Ok(pid) = sysinfo::get_current_pid() else {
+        return 0;
+    };
+    let mut system = sysinfo::System::new();
+    system.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[pid]), false);
+    system
+        .process(pid)
+        .map(|p| p.start_time())
+        .unwrap_or_default()
+}
+
 #[cfg(target_os = "linux")]
 pub fn get_open_fds() -> usize {
     match std::fs::read_dir("/proc/self/fd") {
@@ -66,6 +79,13 @@ mod tests {
         assert_ne!(hostname, "unknown");
     }
 
+    #[test]
+    fn test_get_process_start_time() {
+        let start = get_process_start_time();
+        assert!(start > 0);
+        assert!(start <= chrono::Utc::now().timestamp() as u64);
+    }
+
     #[test]
     #[cfg(target_os = "linux")]
     fn test_get_open_fds() {
@@ -18,7 +18,7 @@ use std::sync::Arc;
 use arrow::{
     array::{Array, ArrayRef, AsArray, RecordBatch, RecordBatchOptions, StringViewBuilder},
     ipc::{
-        MessageHeader,
+        CompressionType, MessageHeader,
         writer::{CompressionContext, DictionaryTracker, IpcDataGenerator, IpcWriteOptions},
     },
 };
@@ -89,6 +89,29 @@ impl FlightDataEncoder {
     }
 }
 
+/// IPC write options of the record batches streamed between nodes, the
+/// compression follows `ZO_GRPC_FLIGHT_COMPRESSION`
+pub fn ipc_write_options() -> Result<IpcWriteOptions> {
+    let compression = match config::get_config().grpc.flight_compression.as_str() {
+        "none" => None,
+        "lz4" => Some(CompressionType::LZ4_FRAME),
+        _ => Some(CompressionType::ZSTD),
+    };
+    Ok(IpcWriteOptions::default().try_with_compression(compression)?)
+}
+
+/// Whether the Arrow Flight streams are gzip compressed by gRPC. It is skipped
+/// when the batches are not compressed, then the decoded arrays point to the
+/// received buffers instead of a decompressed copy
+pub fn grpc_compression_enabled() -> bool {
+    config::get_config().grpc.flight_compression != "none"
+}
+
+/// Max size of a record batch message of the Arrow Flight streams
+pub fn max_flight_data_size() -> usize {
+    config::get_config().grpc.flight_max_data_size * 1024 * 1024
+}
+
 pub fn header_none() -> Bytes {
     let mut builder: FlatBufferBuilder<'_> = FlatBufferBuilder::new();
 
@@ -323,6 +346,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ipc_write_options() {
+        let options = ipc_write_options().unwrap();
+        let mut encoder = FlightDataEncoder::new(options, 8192);
+        let flight_data = encoder.encode_batch(create_test_record_batch()).unwrap();
+        assert!(!flight_data.is_empty());
+        assert!(grpc_compression_enabled());
+        assert_eq!(max_flight_data_size(), 32 * 1024 * 1024);
+    }
+
     #[test]
     fn test_header_none() {
         let header = header_none();
@@ -74,36 +74,40 @@ pub fn check_auth(req: Request<()>) -> Result<Request<()>, Status> {
         };
 
         let user_id = credentials.user_id;
-        let user = if is_root_user(&user_id) {
-            ROOT_USER.get("root").unwrap().to_owned()
-        } else if let Some(user) = get_cached_user_org(org_id.unwrap().to_str().unwrap(), &user_id)
-        {
-            user
-        } else {
-            return Err(Status::unauthenticated("No valid auth token[4]"));
-        };
-
-        if user.token.eq(&credentials.password) {
-            let mut req = req;
-            let user_id_metadata = MetadataValue::try_from(&user_id).unwrap();
-            req.metadata_mut().append("user_id", user_id_metadata);
-            return Ok(req);
-        }
-        let in_pass = get_hash(&credentials.password, &user.salt);
-        if user_id.eq(&user.email)
-            && (credentials.password.eq(&user.password) || in_pass.eq(&user.password))
-        {
-            let mut req = req;
-            let user_id_metadata = MetadataValue::try_from(&user_id).unwrap();
-            req.metadata_mut().append("user_id", user_id_metadata);
-
-            Ok(req)
-        } else {
-            Err(Status::unauthenticated("No valid auth token[5]"))
+        match check_credentials(
+            org_id.unwrap().to_str().unwrap(),
+            &user_id,
+            &credentials.password,
+        ) {
+            Some(true) => {
+                let mut req = req;
+                let user_id_metadata = MetadataValue::try_from(&user_id)
+                    .map_err(|_| Status::unauthenticated("No valid auth token[6]"))?;
+                // replaces any user_id sent by the client, the services trust it
+                req.metadata_mut().insert("user_id", user_id_metadata);
+                Ok(req)
+            }
+            Some(false) => Err(Status::unauthenticated("No valid auth token[5]")),
+            None => Err(Status::unauthenticated("No valid auth token[4]")),
         }
     }
 }
 
+/// Checks the password, or the token, of a user of the organization. None
+/// when the user isn't a member of the organization.
+pub fn check_credentials(org_id: &str, user_id: &str, password: &str) -> Option<bool> {
+    let user = if is_root_user(user_id) {
+        ROOT_USER.get("root").unwrap().to_owned()
+    } else {
+        get_cached_user_org(org_id, user_id)?
+    };
+    if user.token.eq(password) {
+        return Some(true);
+    }
+    let in_pass = get_hash(password, &user.salt);
+    Some(user_id.eq(&user.email) && (password.eq(&user.password) || in_pass.eq(&user.password)))
+}
+
 #[cfg(test)]
 mod tests {
     use config::{
@@ -191,8 +195,18 @@ mod tests {
         let meta: &mut tonic::metadata::MetadataMap = request.metadata_mut();
         meta.insert("authorization", token.clone());
         meta.insert("organization", "default".parse().unwrap());
-
-        assert!(check_auth(request).is_ok());
+        // a user_id sent by the client is replaced by the authenticated one
+        meta.append("user_id", "admin@example.com".parse().unwrap());
+        meta.append("user_id", "other@example.com".parse().unwrap());
+
+        let req = check_auth(request).unwrap();
+        let user_ids = req
+            .metadata()
+            .get_all("user_id")
+            .iter()
+            .map(|v| v.to_str().unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(user_ids, vec!["root@example.com".to_string()]);
     }
 
     #[tokio::test]
@@ -0,0 +1,255 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Arrow Flight endpoint for external clients, like pyarrow or BI tools.
+//!
+//! Clients authenticate with the same basic auth and organization header as
+//! the other gRPC services. The ticket, or the command of a flight
+//! descriptor, is a JSON search query:
+//!
+//! ```json
+//! {"sql": "SELECT * FROM \"default\"", "start_time": 1, "end_time": 2, "size": 100000}
+//! ```
+//!
+//! `stream_type` defaults to logs. The result is sent as Arrow record batches.
+
+use std::sync::Arc;
+
+use arrow_flight::{
+    Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightEndpoint, FlightInfo,
+    HandshakeRequest, HandshakeResponse, PollInfo, PutResult, SchemaResult, Ticket,
+    encode::FlightDataEncoderBuilder, flight_service_server::FlightService,
+};
+use arrow_schema::Schema;
+use config::{
+    get_config, ider,
+    meta::{search, sql::resolve_stream_names, stream::StreamType},
+    utils::json,
+};
+use futures::{StreamExt, TryStreamExt, stream::BoxStream};
+use serde::Deserialize;
+use tonic::{Request, Response, Status, Streaming};
+
+use crate::service::search::{external_flight, information_schema};
+
+/// Search query carried by a ticket
+#[derive(Debug, Deserialize)]
+struct FlightQuery {
+    #[serde(flatten)]
+    query: search::Query,
+    #[serde(default)]
+    stream_type: StreamType,
+}
+
+impl FlightQuery {
+    fn decode(buf: &[u8]) -> Result<Self, Status> {
+        json::from_slice(buf)
+            .map_err(|e| Status::invalid_argument(format!("invalid search query: {e}")))
+    }
+}
+
+#[derive(Default)]
+pub struct ExternalFlightServiceImpl;
+
+#[tonic::async_trait]
+impl FlightService for ExternalFlightServiceImpl {
+    type HandshakeStream = BoxStream<'static, Result<HandshakeResponse, Status>>;
+    type ListFlightsStream = BoxStream<'static, Result<FlightInfo, Status>>;
+    type DoGetStream = BoxStream<'static, Result<FlightData, Status>>;
+    type DoPutStream = BoxStream<'static, Result<PutResult, Status>>;
+    type DoActionStream = BoxStream<'static, Result<arrow_flight::Result, Status>>;
+    type ListActionsStream = BoxStream<'static, Result<ActionType, Status>>;
+    type DoExchangeStream = BoxStream<'static, Result<FlightData, Status>>;
+
+    /// The credentials were checked by the auth interceptor, they are sent
+    /// back as the token of the session, as expected by the basic token
+    /// authentication of the Flight clients
+    async fn handshake(
+        &self,
+        request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, Status> {
+        let token = request.metadata().get("authorization").cloned();
+        let output = futures::stream::iter(vec![Ok(HandshakeResponse::default())]);
+        let mut resp = Response::new(Box::pin(output) as Self::HandshakeStream);
+        if let Some(token) = token {
+            resp.metadata_mut().insert("authorization", token);
+        }
+        Ok(resp)
+    }
+
+    async fn get_flight_info(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let descriptor = request.into_inner();
+        FlightQuery::decode(&descriptor.cmd)?;
+        let endpoint = FlightEndpoint::new().with_ticket(Ticket::new(descriptor.cmd.clone()));
+        let info = FlightInfo::new()
+            .with_descriptor(descriptor)
+            .with_endpoint(endpoint);
+        Ok(Response::new(info))
+    }
+
+    async fn do_get(
+        &self,
+        request: Request<Ticket>,
+    ) -> Result<Response<Self::DoGetStream>, Status> {
+        let cfg = get_config();
+        let org_id = request
+            .metadata()
+            .get(&cfg.grpc.org_header_key)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string())
+            .ok_or_else(|| {
+                Status::invalid_argument(format!(
+                    "Please specify organization id with header key '{}' ",
+                    &cfg.grpc.org_header_key
+                ))
+            })?;
+        // set by the auth interceptor, absent for the internal token
+        let user_id = request
+            .metadata()
+            .get("user_id")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string());
+        let FlightQuery { query, stream_type } = FlightQuery::decode(&request.into_inner().ticket)?;
+
+        // the information_schema views only list the streams the user can read
+        let stream_names = if information_schema::is_query(&query.sql) {
+            vec![]
+        } else {
+            resolve_stream_names(&query.sql).map_err(|e| Status::invalid_argument(e.to_string()))?
+        };
+        #[cfg(feature = "enterprise")]
+        for stream_name in stream_names.iter() {
+            if let Err(e) = crate::service::search::check_search_allowed(&org_id, Some(stream_name))
+            {
+                return Err(Status::resource_exhausted(e.to_string()));
+            }
+            if let Some(user_id) = user_id.as_deref()
+                && crate::handler::http::request::search::utils::check_stream_permissions(
+                    stream_name,
+                    &org_id,
+                    user_id,
+                    &stream_type,
+                )
+                .await
+                .is_some()
+            {
+                return Err(Status::permission_denied("Unauthorized Access"));
+            }
+        }
+        #[cfg(not(feature = "enterprise"))]
+        drop(stream_names);
+
+        let trace_id = ider::generate_trace_id();
+        log::info!("[trace_id {trace_id}] external flight->search: org: {org_id}");
+        let req = search::Request {
+            query,
+            search_type: Some(search::SearchEventType::Other),
+            ..Default::default()
+        };
+        let (batches, scan_stats) =
+            external_flight::search(&trace_id, &org_id, stream_type, user_id, &req)
+                .await
+                .map_err(|e| {
+                    log::error!("[trace_id {trace_id}] external flight->search: error: {e}");
+                    Status::internal(e.to_string())
+                })?;
+        log::info!(
+            "[trace_id {trace_id}] external flight->search: batches: {}, scan_size: {} mb",
+            batches.len(),
+            scan_stats.original_size / 1024 / 1024
+        );
+
+        // clients expect a schema even when there are no rows
+        let schema = batches
+            .first()
+            .map(|b| b.schema())
+            .unwrap_or_else(|| Arc::new(Schema::empty()));
+        let stream = FlightDataEncoderBuilder::new()
+            .with_schema(schema)
+            .with_max_flight_data_size(cfg.grpc.max_message_size * 1024 * 1024 / 2)
+            .build(futures::stream::iter(batches.into_iter().map(Ok)))
+            .map_err(Status::from);
+        Ok(Response::new(stream.boxed()))
+    }
+
+    async fn list_flights(
+        &self,
+        _request: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, Status> {
+        Err(Status::unimplemented("Implement list_flights"))
+    }
+
+    async fn poll_flight_info(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<PollInfo>, Status> {
+        Err(Status::unimplemented("Implement poll_flight_info"))
+    }
+
+    async fn get_schema(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, Status> {
+        Err(Status::unimplemented("Implement get_schema"))
+    }
+
+    async fn do_put(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, Status> {
+        Err(Status::unimplemented("Implement do_put"))
+    }
+
+    async fn do_action(
+        &self,
+        _request: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, Status> {
+        Err(Status::unimplemented("Implement do_action"))
+    }
+
+    async fn list_actions(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, Status> {
+        Err(Status::unimplemented("Implement list_actions"))
+    }
+
+    async fn do_exchange(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, Status> {
+        Err(Status::unimplemented("Implement do_exchange"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flight_query_decode() {
+        let q = FlightQuery::decode(
+            br#"{"sql": "SELECT * FROM t", "start_time": 1, "end_time": 2, "stream_type": "traces"}"#,
+        )
+        .unwrap();
+        assert_eq!(q.query.sql, "SELECT * FROM t");
+        assert_eq!(q.query.end_time, 2);
+        assert_eq!(q.stream_type, StreamType::Traces);
+        assert!(FlightQuery::decode(b"SELECT * FROM t").is_err());
+    }
+}
@@ -15,7 +15,6 @@
 
 use std::{io::Cursor, sync::Arc};
 
-use arrow::ipc::{CompressionType, writer::IpcWriteOptions};
 use arrow_flight::{
     Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightInfo,
     HandshakeRequest, HandshakeResponse, PollInfo, PutResult, SchemaResult, Ticket,
@@ -29,7 +28,10 @@ use datafusion::{
     common::{DataFusionError, Result},
     physical_plan::{ExecutionPlan, coalesce_batches::CoalesceBatchesExec, execute_stream},
 };
-use flight::common::{MetricsInfo, PreCustomMessage};
+use flight::{
+    common::{MetricsInfo, PreCustomMessage},
+    encoder::{ipc_write_options, max_flight_data_size},
+};
 use futures::{StreamExt, stream::BoxStream};
 use futures_util::pin_mut;
 use prost::Message;
@@ -59,6 +61,7 @@ use crate::{
     },
 };
 
+pub mod external;
 mod stream;
 pub mod visitor;
 
@@ -182,16 +185,14 @@ impl FlightService for FlightServiceImpl {
         }
 
         let start = std::time::Instant::now();
-        let write_options: IpcWriteOptions = IpcWriteOptions::default()
-            .try_with_compression(Some(CompressionType::ZSTD))
-            .map_err(|e| {
-                // clear session data
-                clear_session_data(&trace_id);
-                log::error!(
-                    "[trace_id {trace_id}] flight->search: do_get create IPC write options error: {e:?}",
-                );
-                Status::internal(e.to_string())
-            })?;
+        let write_options = ipc_write_options().map_err(|e| {
+            // clear session data
+            clear_session_data(&trace_id);
+            log::error!(
+                "[trace_id {trace_id}] flight->search: do_get create IPC write options error: {e:?}",
+            );
+            Status::internal(e.to_string())
+        })?;
 
         // used for EXPLAIN ANALYZE to collect metrics after stream is done
         let metrics = req.search_info.is_analyze.then_some(MetricsInfo {
@@ -218,7 +219,7 @@ impl FlightService for FlightServiceImpl {
             Status::internal(e.to_string())
         })?;
 
-        let mut stream = FlightEncoderStreamBuilder::new(write_options, 33554432)
+        let mut stream = FlightEncoderStreamBuilder::new(write_options, max_flight_data_size())
             .with_trace_id(trace_id.to_string())
             .with_is_super(is_super_cluster)
             .with_defer_lock(lock)
@@ -13,7 +13,10 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use config::{meta::otlp::OtlpRequestType, metrics};
+use config::{
+    meta::{otlp::OtlpRequestType, stream::StreamType},
+    metrics,
+};
 use opentelemetry_proto::tonic::collector::logs::v1::{
     ExportLogsServiceRequest, ExportLogsServiceResponse, logs_service_server::LogsService,
 };
@@ -45,12 +48,21 @@ impl LogsService for LogsServer {
         if org_id.is_none() {
             return Err(Status::invalid_argument(msg));
         }
+
         let stream_name = metadata.get(&cfg.grpc.stream_header_key);
         let mut in_stream_name: Option<&str> = None;
         if let Some(stream_name) = stream_name {
             in_stream_name = Some(stream_name.to_str().unwrap());
         };
 
+        // reject early while the node is under pressure
+        super::check_ingestion_resources(
+            org_id.unwrap().to_str().unwrap_or_default(),
+            StreamType::Logs,
+            in_stream_name,
+        )
+        .await?;
+
         let user_id = metadata.get("user_id");
         let mut user_email: &str = "";
         if let Some(user_id) = user_id {
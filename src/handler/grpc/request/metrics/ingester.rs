@@ -13,7 +13,10 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use config::{meta::otlp::OtlpRequestType, metrics};
+use config::{
+    meta::{otlp::OtlpRequestType, stream::StreamType},
+    metrics,
+};
 use opentelemetry_proto::tonic::collector::metrics::v1::{
     ExportMetricsServiceRequest, ExportMetricsServiceResponse,
     metrics_service_server::MetricsService,
@@ -49,6 +52,14 @@ impl MetricsService for MetricsIngester {
             return Err(Status::invalid_argument(msg));
         }
 
+        // reject early while the node is under pressure
+        super::super::check_ingestion_resources(
+            org_id.unwrap().to_str().unwrap_or_default(),
+            StreamType::Metrics,
+            None,
+        )
+        .await?;
+
         let user_email = metadata
             .get("user_id")
             .and_then(|id| id.to_str().ok())
@@ -21,3 +21,40 @@ pub mod query_cache;
 pub mod search;
 pub mod stream;
 pub mod traces;
+
+use std::time::Duration;
+
+use config::meta::stream::StreamType;
+use tonic::{Code, Status};
+use tonic_types::{ErrorDetails, StatusExt};
+
+use crate::service::ingestion;
+
+/// Rejects OTLP exports with RESOURCE_EXHAUSTED while the node is under
+/// pressure or the org is over its quota. OTLP exporters only retry that code
+/// when a RetryInfo detail is present, which also carries how long to wait.
+pub(crate) async fn check_ingestion_resources(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: Option<&str>,
+) -> Result<(), Status> {
+    ingestion::quota::check(org_id, stream_type, stream_name)
+        .await
+        .map_err(|e| {
+            let retry_after = config::get_config().limit.ingest_quota_sync_interval;
+            Status::with_error_details(
+                Code::ResourceExhausted,
+                e.to_string(),
+                ErrorDetails::with_retry_info(Some(Duration::from_secs(retry_after))),
+            )
+        })?;
+    let priority = ingestion::get_ingest_priority(org_id, stream_type, stream_name).await;
+    ingestion::check_ingestion_resources(priority).map_err(|e| {
+        let retry_after = config::get_config().common.ingest_backpressure_retry_after;
+        Status::with_error_details(
+            Code::ResourceExhausted,
+            e.to_string(),
+            ErrorDetails::with_retry_info(Some(Duration::from_secs(retry_after))),
+        )
+    })
+}
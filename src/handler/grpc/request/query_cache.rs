@@ -30,7 +30,13 @@ impl QueryCache for QueryCacheServerImpl {
         request: Request<DeleteResultCacheRequest>,
     ) -> Result<Response<DeleteResultCacheResponse>, Status> {
         let req: DeleteResultCacheRequest = request.into_inner();
-        let deleted = cacher::delete_cache(&req.path, req.ts, None, None)
+        // an empty time range is sent by the nodes without it
+        let (start_time, end_time) = if req.end_time > 0 {
+            (Some(req.start_time), Some(req.end_time))
+        } else {
+            (None, None)
+        };
+        let deleted = cacher::delete_cache(&req.path, req.ts, start_time, end_time)
             .await
             .is_ok();
 
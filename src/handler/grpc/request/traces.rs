@@ -13,7 +13,10 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use config::{meta::otlp::OtlpRequestType, metrics};
+use config::{
+    meta::{otlp::OtlpRequestType, stream::StreamType},
+    metrics,
+};
 use opentelemetry_proto::tonic::collector::trace::v1::{
     ExportTraceServiceRequest, ExportTraceServiceResponse, trace_service_server::TraceService,
 };
@@ -54,6 +57,14 @@ impl TraceService for TraceServer {
             in_stream_name = Some(stream_name.to_str().unwrap());
         };
 
+        // reject early while the node is under pressure
+        super::check_ingestion_resources(
+            org_id.unwrap().to_str().unwrap_or_default(),
+            StreamType::Traces,
+            in_stream_name,
+        )
+        .await?;
+
         let user_email = metadata
             .get("user_id")
             .and_then(|id| id.to_str().ok())
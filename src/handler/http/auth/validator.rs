@@ -865,6 +865,26 @@ async fn oo_validator_internal(
     req_data: &RequestData,
     auth_info: &AuthExtractor,
     path_prefix: &str,
+) -> Result<AuthValidationResult, AuthError> {
+    let res = authenticate(req_data, auth_info, path_prefix).await?;
+    // archived orgs are read-only whatever the credentials, root users can
+    // still restore them
+    if !is_root_user(&res.user_email) {
+        let path = extract_relative_path(req_data.uri.path(), path_prefix);
+        crate::service::org_lifecycle::check_request(
+            &auth_info.org_id,
+            &req_data.method,
+            path.trim_start_matches('/'),
+        )
+        .map_err(AuthError::Forbidden)?;
+    }
+    Ok(res)
+}
+
+async fn authenticate(
+    req_data: &RequestData,
+    auth_info: &AuthExtractor,
+    path_prefix: &str,
 ) -> Result<AuthValidationResult, AuthError> {
     // Check if this is a session-based auth (marked with Session:: prefix)
     let (is_from_session, auth_str) = if let Some(rest) = auth_info.auth.strip_prefix("Session::") {
@@ -878,7 +898,18 @@ async fn oo_validator_internal(
         (false, auth_info.auth.clone())
     };
 
-    if let Some(info) = auth_str.strip_prefix("Basic ").map(str::trim) {
+    // splunk clients such as the docker log driver can only send
+    // `Authorization: Splunk <token>`, the token being the basic credentials,
+    // the scheme is only accepted on the splunk compatible endpoints
+    if let Some(info) = auth_str
+        .strip_prefix("Basic ")
+        .or_else(|| {
+            is_splunk_path(req_data.uri.path())
+                .then(|| auth_str.strip_prefix("Splunk "))
+                .flatten()
+        })
+        .map(str::trim)
+    {
         let decoded = match base64::decode(info) {
             Ok(val) => val,
             Err(_) => return Err(AuthError::Unauthorized("Unauthorized Access".to_string())),
@@ -1184,6 +1215,12 @@ fn extract_relative_path(full_path: &str, path_prefix: &str) -> String {
         .to_string()
 }
 
+/// Whether the path is a splunk HEC or docker log driver endpoint
+fn is_splunk_path(path: &str) -> bool {
+    path.split('/')
+        .any(|segment| segment == "_hec" || segment == "_docker")
+}
+
 /// Helper function to check if the path corresponds to a short URL
 fn _is_short_url_path(path_columns: &[&str]) -> bool {
     path_columns
@@ -1433,6 +1470,17 @@ mod tests {
         assert_eq!(result, "/other/path");
     }
 
+    #[test]
+    fn test_is_splunk_path() {
+        assert!(is_splunk_path("/api/default/_hec"));
+        assert!(is_splunk_path("/api/default/_hec/services/collector/ack"));
+        assert!(is_splunk_path(
+            "/api/default/logs/_docker/services/collector/event/1.0"
+        ));
+        assert!(!is_splunk_path("/api/default/logs/_json"));
+        assert!(!is_splunk_path("/api/default/_hec_logs/_search"));
+    }
+
     #[test]
     fn test_is_short_url_path() {
         // Test short URL path
@@ -37,6 +37,7 @@ impl From<meta_dest::Destination> for Destination {
                 meta_dest::DestinationType::Email(email) => Self {
                     name: value.name,
                     emails: email.recipients,
+                    email_attach_csv: email.attach_csv,
                     template,
                     destination_type: DestinationType::Email,
                     ..Default::default()
@@ -61,6 +62,8 @@ impl From<meta_dest::Destination> for Destination {
                     output_format: endpoint.output_format,
                     destination_type_name: endpoint.destination_type,
                     metadata: endpoint.metadata,
+                    vrl_template: endpoint.vrl_template,
+                    proxy: endpoint.proxy,
                     ..Default::default()
                 },
                 meta_dest::DestinationType::Sns(aws_sns) => Self {
@@ -71,6 +74,48 @@ impl From<meta_dest::Destination> for Destination {
                     destination_type: DestinationType::Sns,
                     ..Default::default()
                 },
+                // the password is never returned
+                meta_dest::DestinationType::Kafka(kafka) => Self {
+                    name: value.name,
+                    template,
+                    kafka_brokers: Some(kafka.brokers),
+                    kafka_topic: Some(kafka.topic),
+                    kafka_sasl_username: Some(kafka.sasl_username).filter(|v| !v.is_empty()),
+                    kafka_tls_enabled: kafka.tls_enabled,
+                    destination_type: DestinationType::Kafka,
+                    ..Default::default()
+                },
+                // the API token is never returned
+                meta_dest::DestinationType::Jira(jira) => Self {
+                    name: value.name,
+                    template,
+                    url: jira.url,
+                    jira_username: Some(jira.username),
+                    jira_project_key: Some(jira.project_key),
+                    jira_issue_type: Some(jira.issue_type),
+                    jira_fields: jira.fields,
+                    proxy: jira.proxy,
+                    destination_type: DestinationType::Jira,
+                    ..Default::default()
+                },
+                // the password is never returned
+                meta_dest::DestinationType::ServiceNow(servicenow) => Self {
+                    name: value.name,
+                    template,
+                    url: servicenow.url,
+                    servicenow_username: Some(servicenow.username),
+                    servicenow_table: Some(servicenow.table).filter(|v| !v.is_empty()),
+                    servicenow_import_set: servicenow.import_set,
+                    servicenow_severity_label: Some(servicenow.severity_label)
+                        .filter(|v| !v.is_empty()),
+                    servicenow_urgency_mapping: servicenow.urgency_mapping,
+                    servicenow_impact_mapping: servicenow.impact_mapping,
+                    servicenow_fields: servicenow.fields,
+                    servicenow_close_on_resolve: servicenow.close_on_resolve,
+                    proxy: servicenow.proxy,
+                    destination_type: DestinationType::ServiceNow,
+                    ..Default::default()
+                },
             },
             meta_dest::Module::Pipeline { endpoint } => Self {
                 name: value.name,
@@ -104,6 +149,7 @@ impl Destination {
             let destination_type = match self.destination_type {
                 DestinationType::Email => meta_dest::DestinationType::Email(meta_dest::Email {
                     recipients: self.emails,
+                    attach_csv: self.email_attach_csv,
                 }),
                 DestinationType::Http => meta_dest::DestinationType::Http(meta_dest::Endpoint {
                     url: self.url,
@@ -114,11 +160,48 @@ impl Destination {
                     output_format: self.output_format,
                     destination_type: self.destination_type_name,
                     metadata: self.metadata,
+                    vrl_template: self.vrl_template.filter(|v| !v.trim().is_empty()),
+                    proxy: self.proxy.filter(|v| !v.trim().is_empty()),
                 }),
                 DestinationType::Sns => meta_dest::DestinationType::Sns(meta_dest::AwsSns {
                     sns_topic_arn: self.sns_topic_arn.ok_or(DestinationError::InvalidSns)?,
                     aws_region: self.aws_region.ok_or(DestinationError::InvalidSns)?,
                 }),
+                DestinationType::Kafka => {
+                    meta_dest::DestinationType::Kafka(meta_dest::KafkaTopic {
+                        brokers: self.kafka_brokers.ok_or(DestinationError::InvalidKafka)?,
+                        topic: self.kafka_topic.ok_or(DestinationError::InvalidKafka)?,
+                        sasl_username: self.kafka_sasl_username.unwrap_or_default(),
+                        sasl_password: self.kafka_sasl_password.unwrap_or_default(),
+                        tls_enabled: self.kafka_tls_enabled,
+                    })
+                }
+                DestinationType::Jira => meta_dest::DestinationType::Jira(meta_dest::Jira {
+                    url: self.url,
+                    username: self.jira_username.ok_or(DestinationError::InvalidJira)?,
+                    api_token: self.jira_api_token.unwrap_or_default(),
+                    project_key: self.jira_project_key.ok_or(DestinationError::InvalidJira)?,
+                    issue_type: self.jira_issue_type.ok_or(DestinationError::InvalidJira)?,
+                    fields: self.jira_fields,
+                    proxy: self.proxy.filter(|v| !v.trim().is_empty()),
+                }),
+                DestinationType::ServiceNow => {
+                    meta_dest::DestinationType::ServiceNow(meta_dest::ServiceNow {
+                        url: self.url,
+                        username: self
+                            .servicenow_username
+                            .ok_or(DestinationError::InvalidServiceNow)?,
+                        password: self.servicenow_password.unwrap_or_default(),
+                        table: self.servicenow_table.unwrap_or_default(),
+                        import_set: self.servicenow_import_set,
+                        severity_label: self.servicenow_severity_label.unwrap_or_default(),
+                        urgency_mapping: self.servicenow_urgency_mapping,
+                        impact_mapping: self.servicenow_impact_mapping,
+                        fields: self.servicenow_fields,
+                        close_on_resolve: self.servicenow_close_on_resolve,
+                        proxy: self.proxy.filter(|v| !v.trim().is_empty()),
+                    })
+                }
                 #[cfg(feature = "enterprise")]
                 DestinationType::Action => {
                     if let Some(action_id) = self.action_id {
@@ -137,6 +220,8 @@ impl Destination {
                             output_format: self.output_format,
                             destination_type: self.destination_type_name,
                             metadata: self.metadata,
+                            vrl_template: None,
+                            proxy: None,
                         })
                     } else {
                         return Err(DestinationError::InvalidActionId(anyhow::anyhow!(
@@ -165,6 +250,8 @@ impl Destination {
                 output_format: self.output_format,
                 destination_type: self.destination_type_name,
                 metadata: self.metadata,
+                vrl_template: None,
+                proxy: None,
             };
             Ok(meta_dest::Destination {
                 id: None,
@@ -199,7 +286,10 @@ impl Template {
         let template_type = match self.template_type {
             DestinationType::Email => meta_dest::TemplateType::Email { title: self.title },
             DestinationType::Sns => meta_dest::TemplateType::Sns,
-            DestinationType::Http => meta_dest::TemplateType::Http,
+            DestinationType::Http
+            | DestinationType::Kafka
+            | DestinationType::Jira
+            | DestinationType::ServiceNow => meta_dest::TemplateType::Http,
             #[cfg(feature = "enterprise")]
             DestinationType::Action => meta_dest::TemplateType::Http,
         };
@@ -261,6 +351,18 @@ pub struct Destination {
     /// Optional HTTP headers to include with webhook requests.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub headers: Option<HashMap<String, String>>,
+    /// Proxy the requests of HTTP, Jira and ServiceNow alert destinations go through.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "http://proxy.example.com:3128")]
+    pub proxy: Option<String>,
+    /// VRL program rendering the requests of HTTP alert destinations, instead of the template.
+    /// It runs on the context of the notification, `.alert`, `.rows`, `.headers`, ... and
+    /// returns the body, the headers it leaves in `.headers` are sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(
+        example = ".headers.\"X-Severity\" = .alert.labels.severity\n{\"text\": .alert.name, \"count\": length(array!(.rows))}"
+    )]
+    pub vrl_template: Option<String>,
     /// REQUIRED for alert destinations. Name of the template to use for formatting alert messages.
     /// Use "Default" for the built-in default template. Without a template, the destination
     /// becomes a pipeline destination and cannot be used with alerts.
@@ -270,13 +372,91 @@ pub struct Destination {
     /// Email recipients for Email destinations. Required when `type` is `email`.
     #[serde(default)]
     pub emails: Vec<String>,
+    /// Whether Email destinations attach the rows which triggered the alert as CSV.
+    #[serde(default)]
+    pub email_attach_csv: bool,
     /// SNS topic ARN for SNS destinations. Required when `type` is `sns`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sns_topic_arn: Option<String>,
     /// AWS region for SNS destinations. Required when `type` is `sns`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub aws_region: Option<String>,
-    /// Destination type: `http` (webhook), `email`, or `sns`. Default is `http`.
+    /// Comma separated host:port bootstrap brokers for Kafka destinations. Required when
+    /// `type` is `kafka`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "kafka-1:9092,kafka-2:9092")]
+    pub kafka_brokers: Option<String>,
+    /// Topic the notifications are published to for Kafka destinations. Required when `type`
+    /// is `kafka`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kafka_topic: Option<String>,
+    /// SASL/PLAIN user name for Kafka destinations, SASL is disabled when empty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kafka_sasl_username: Option<String>,
+    /// SASL/PLAIN password for Kafka destinations. It is never returned, leave it empty on
+    /// update to keep the current one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kafka_sasl_password: Option<String>,
+    /// Whether to connect to the brokers of Kafka destinations over TLS.
+    #[serde(default)]
+    pub kafka_tls_enabled: bool,
+    /// User the issues of Jira destinations are opened by. Required when `type` is `jira`, the
+    /// `url` is the one of the Jira site.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jira_username: Option<String>,
+    /// API token of the user for Jira destinations. It is never returned, leave it empty on
+    /// update to keep the current one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jira_api_token: Option<String>,
+    /// Key of the project the issues are opened in for Jira destinations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "OPS")]
+    pub jira_project_key: Option<String>,
+    /// Issue type of the issues opened by Jira destinations, e.g. `Bug` or `Incident`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jira_issue_type: Option<String>,
+    /// Fields of the issues opened by Jira destinations, `{label}` is replaced by the context
+    /// attribute of the alert, or the field of the first row, of that name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub jira_fields: HashMap<String, String>,
+    /// User the incidents of ServiceNow destinations are opened by. Required when `type` is
+    /// `servicenow`, the `url` is the one of the instance.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub servicenow_username: Option<String>,
+    /// Password of the user for ServiceNow destinations. It is never returned, leave it empty on
+    /// update to keep the current one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub servicenow_password: Option<String>,
+    /// Table the incidents of ServiceNow destinations are opened in, `incident` by default. The
+    /// staging table when `servicenow_import_set` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "incident")]
+    pub servicenow_table: Option<String>,
+    /// Whether ServiceNow destinations post to an import set, whose transform map opens and
+    /// closes the incidents, instead of the table of the incidents.
+    #[serde(default)]
+    pub servicenow_import_set: bool,
+    /// Label of the alert its severity is read from for ServiceNow destinations, `severity` by
+    /// default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub servicenow_severity_label: Option<String>,
+    /// Urgency of the incidents opened by ServiceNow destinations by severity, e.g.
+    /// `{"critical": "1"}`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub servicenow_urgency_mapping: HashMap<String, String>,
+    /// Impact of the incidents opened by ServiceNow destinations by severity.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub servicenow_impact_mapping: HashMap<String, String>,
+    /// Fields of the incidents opened by ServiceNow destinations, `{label}` is replaced by the
+    /// context attribute of the alert, or the field of the first row, of that name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub servicenow_fields: HashMap<String, String>,
+    /// Whether ServiceNow destinations resolve the incident of a scheduled alert once it is not
+    /// satisfied anymore.
+    #[serde(default)]
+    pub servicenow_close_on_resolve: bool,
+    /// Destination type: `http` (webhook), `email`, `sns`, `kafka`, `jira` or `servicenow`.
+    /// Default is `http`.
     #[serde(rename = "type")]
     #[serde(default)]
     #[schema(example = "http")]
@@ -303,6 +483,10 @@ pub enum DestinationType {
     Http,
     Email,
     Sns,
+    Kafka,
+    Jira,
+    #[serde(rename = "servicenow")]
+    ServiceNow,
     #[cfg(feature = "enterprise")]
     Action,
 }
@@ -312,6 +496,9 @@ impl From<&str> for DestinationType {
         match value.to_lowercase().as_str() {
             "email" => DestinationType::Email,
             "sns" => DestinationType::Sns,
+            "kafka" => DestinationType::Kafka,
+            "jira" => DestinationType::Jira,
+            "servicenow" => DestinationType::ServiceNow,
             #[cfg(feature = "enterprise")]
             "action" => DestinationType::Action,
             _ => DestinationType::Http,
@@ -325,6 +512,9 @@ impl fmt::Display for DestinationType {
             DestinationType::Email => write!(f, "email"),
             DestinationType::Http => write!(f, "http"),
             DestinationType::Sns => write!(f, "sns"),
+            DestinationType::Kafka => write!(f, "kafka"),
+            DestinationType::Jira => write!(f, "jira"),
+            DestinationType::ServiceNow => write!(f, "servicenow"),
             #[cfg(feature = "enterprise")]
             DestinationType::Action => write!(f, "action"),
         }
@@ -25,5 +25,6 @@ pub mod billings;
 pub mod dashboards;
 pub mod destinations;
 pub mod folders;
+pub mod organizations;
 pub mod pipelines;
 pub mod reports;
@@ -0,0 +1,74 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! These models define the schemas of HTTP request and response JSON bodies in
+//! organization API endpoints.
+
+use config::meta::{function::Transform, pipeline::Pipeline};
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::{
+    common::meta::organization::{
+        OrgArchive, OrgTemplate, OrgTemplateRole, OrgTemplateStream, Organization,
+    },
+    handler::http::models::{
+        alerts::requests::CreateAlertRequestBody, dashboards::DashboardRequestBody,
+    },
+};
+
+/// HTTP request body for the `ProvisionOrganization` endpoint.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ProvisionOrgRequestBody {
+    #[serde(flatten)]
+    pub organization: Organization,
+    #[serde(default)]
+    pub template: OrgTemplateRequestBody,
+}
+
+/// HTTP request body for the `ImportOrganization` endpoint.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ImportOrgRequestBody {
+    #[serde(flatten)]
+    pub organization: Organization,
+    /// The archive returned by the `ExportOrganization` endpoint
+    pub archive: OrgArchive,
+}
+
+/// A template bundle, the resources are given like to their create
+/// endpoints. The dashboards and alerts are created in the default folder.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct OrgTemplateRequestBody {
+    pub streams: Vec<OrgTemplateStream>,
+    pub roles: Vec<OrgTemplateRole>,
+    pub functions: Vec<Transform>,
+    pub pipelines: Vec<Pipeline>,
+    pub dashboards: Vec<DashboardRequestBody>,
+    pub alerts: Vec<CreateAlertRequestBody>,
+}
+
+impl From<OrgTemplateRequestBody> for OrgTemplate {
+    fn from(value: OrgTemplateRequestBody) -> Self {
+        Self {
+            streams: value.streams,
+            roles: value.roles,
+            functions: value.functions,
+            pipelines: value.pipelines,
+            dashboards: value.dashboards.into_iter().map(Into::into).collect(),
+            alerts: value.alerts.into_iter().map(Into::into).collect(),
+        }
+    }
+}
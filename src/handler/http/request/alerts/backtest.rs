@@ -0,0 +1,127 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use axum::{
+    Json,
+    extract::Path,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use config::meta::alerts::backtest::{AlertBacktest, AlertBacktestRequest};
+
+#[cfg(feature = "enterprise")]
+use crate::handler::http::request::search::utils::check_stream_permissions;
+use crate::{
+    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    handler::http::extractors::Headers,
+    service::alerts::backtest::{self, BacktestError},
+};
+
+impl From<BacktestError> for Response {
+    fn from(value: BacktestError) -> Self {
+        match &value {
+            BacktestError::InvalidBacktest(_) => MetaHttpResponse::bad_request(value),
+            BacktestError::AlertNotFound => MetaHttpResponse::not_found(value),
+            BacktestError::BacktestNotFound => MetaHttpResponse::not_found(value),
+            BacktestError::InfraError(e) => MetaHttpResponse::internal_error(e),
+        }
+    }
+}
+
+/// CreateAlertBacktest
+#[utoipa::path(
+    post,
+    path = "/v2/{org_id}/alerts/backtest",
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "CreateAlertBacktest",
+    summary = "Backtest an alert",
+    description = "Replays a scheduled alert, saved or not, over a past time range. The alert is evaluated on every window \
+                   it would have run on and each window is written to the `_alert_backtest` stream, with whether it \
+                   fired. The backtest runs in the background, its progress and the windows which fired are returned \
+                   by the get endpoint. No notification is sent",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = AlertBacktestRequest, description = "Alert and time range", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = AlertBacktest),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Alerts", "operation": "create"})),
+        ("x-o2-mcp" = json!({"description": "Backtest an alert over a past time range", "category": "alerts"}))
+    )
+)]
+pub async fn create_backtest(
+    Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
+    Json(req): Json<AlertBacktestRequest>,
+) -> Response {
+    #[cfg(feature = "enterprise")]
+    if let Some(alert) = &req.alert
+        && let Some(response) = check_stream_permissions(
+            &alert.stream_name,
+            &org_id,
+            &user_email.user_id,
+            &alert.stream_type,
+        )
+        .await
+    {
+        return response;
+    }
+
+    match backtest::create(&org_id, req, &user_email.user_id).await {
+        Ok(v) => (StatusCode::OK, Json(v)).into_response(),
+        Err(e) => e.into(),
+    }
+}
+
+/// GetAlertBacktest
+#[utoipa::path(
+    get,
+    path = "/v2/{org_id}/alerts/backtest/{backtest_id}",
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "GetAlertBacktest",
+    summary = "Get alert backtest",
+    description = "Gets the status of the backtest, the number of windows evaluated and fired so far and the end of the \
+                   first windows which fired",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("backtest_id" = String, Path, description = "Backtest ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = AlertBacktest),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Alerts", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Get the results of an alert backtest", "category": "alerts"}))
+    )
+)]
+pub async fn get_backtest(Path((org_id, backtest_id)): Path<(String, String)>) -> Response {
+    match backtest::get(&org_id, &backtest_id).await {
+        Ok(v) => (StatusCode::OK, Json(v)).into_response(),
+        Err(e) => e.into(),
+    }
+}
@@ -21,6 +21,10 @@ use axum::{
     http::StatusCode,
     response::Response,
 };
+use config::{
+    meta::destinations::{DestinationType, EmailDelivery, Module},
+    utils::json::{Map, Value},
+};
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "enterprise")]
@@ -35,7 +39,10 @@ use crate::{
         models::destinations::Destination,
         request::{BulkDeleteRequest, BulkDeleteResponse},
     },
-    service::{alerts::destinations, db::alerts::destinations::DestinationError},
+    service::{
+        alerts::{alert, destinations, egress, email, templates},
+        db::alerts::destinations::DestinationError,
+    },
 };
 
 #[derive(Debug, Clone, Deserialize, Serialize, utoipa::ToSchema)]
@@ -46,6 +53,7 @@ pub struct TestDestinationRequest {
     pub headers: Option<HashMap<String, String>>,
     pub body: Option<String>,
     pub skip_tls_verify: Option<bool>,
+    pub proxy: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, utoipa::ToSchema)]
@@ -57,6 +65,27 @@ pub struct TestDestinationResponse {
     pub error: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewDestinationRequest {
+    /// The destination, an HTTP one with a VRL template
+    pub destination: Destination,
+    /// Context attributes of the sample alert
+    #[serde(default)]
+    pub context_attributes: Option<HashMap<String, String>>,
+    /// Rows which triggered the sample alert, a sample row when empty
+    #[serde(default)]
+    #[schema(value_type = Vec<Object>)]
+    pub rows: Vec<Map<String, Value>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewDestinationResponse {
+    pub body: String,
+    pub headers: HashMap<String, String>,
+}
+
 impl From<DestinationError> for Response {
     fn from(value: DestinationError) -> Self {
         match &value {
@@ -119,8 +148,19 @@ pub async fn test_destination(
         });
     }
 
-    // Build HTTP client
-    let mut client_builder = reqwest::Client::builder().timeout(std::time::Duration::from_secs(30));
+    // Build HTTP client, limited to the hosts the alert destinations may reach
+    let client_builder = match egress::client_builder(url, test_req.proxy.as_deref()).await {
+        Ok(client_builder) => client_builder,
+        Err(e) => {
+            return MetaHttpResponse::json(TestDestinationResponse {
+                success: false,
+                status_code: None,
+                response_body: None,
+                error: Some(e.to_string()),
+            });
+        }
+    };
+    let mut client_builder = client_builder.timeout(std::time::Duration::from_secs(30));
 
     if skip_tls_verify {
         client_builder = client_builder.danger_accept_invalid_certs(true);
@@ -195,6 +235,84 @@ pub async fn test_destination(
     }
 }
 
+/// PreviewDestination
+#[utoipa::path(
+    post,
+    path = "/{org_id}/alerts/destinations/preview",
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "PreviewDestination",
+    summary = "Preview alert destination request",
+    description = "Renders the request an HTTP alert destination with a VRL template sends for a sample alert, \
+                   without sending it. The VRL template runs on the sample alert with the given context attributes \
+                   and rows, the template of the destination, if any, is rendered as its message. The rendered \
+                   body and headers can be sent with the test endpoint.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+      ),
+    request_body(content = inline(PreviewDestinationRequest), description = "Destination and sample alert", content_type = "application/json", example = json!({
+        "destination": {
+            "name": "webhook",
+            "type": "http",
+            "url": "https://example.com/webhook",
+            "vrl_template": ".headers.\"X-Severity\" = .alert.labels.severity\n{\"text\": .alert.name, \"count\": length(array!(.rows))}"
+        },
+        "contextAttributes": {"severity": "critical"}
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(PreviewDestinationResponse)),
+        (status = 400, description = "Error",   content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Destinations", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Preview the request of an alert destination", "category": "alerts"}))
+    )
+)]
+pub async fn preview_destination(
+    Path(org_id): Path<String>,
+    Json(req): Json<PreviewDestinationRequest>,
+) -> Response {
+    let dest = match req.destination.into(org_id.clone(), true) {
+        Ok(dest) => dest,
+        Err(e) => return e.into(),
+    };
+    let Module::Alert {
+        template,
+        destination_type: DestinationType::Http(endpoint),
+    } = dest.module
+    else {
+        return MetaHttpResponse::bad_request("Only HTTP destinations have a VRL template");
+    };
+    let template = match template {
+        Some(name) => match templates::get(&org_id, &name).await {
+            Ok(template) => Some(template),
+            Err(e) => return MetaHttpResponse::bad_request(e),
+        },
+        None => None,
+    };
+    let context_attributes = req
+        .context_attributes
+        .map(|attrs| attrs.into_iter().collect());
+    match alert::preview_notification(
+        &org_id,
+        &endpoint,
+        template.as_ref(),
+        context_attributes,
+        req.rows,
+    )
+    .await
+    {
+        Ok((body, headers)) => MetaHttpResponse::json(PreviewDestinationResponse {
+            body,
+            headers: headers.into_iter().collect(),
+        }),
+        Err(e) => MetaHttpResponse::bad_request(e),
+    }
+}
+
 /// CreateDestination
 #[utoipa::path(
     post,
@@ -340,6 +458,39 @@ pub async fn get_destination(Path((org_id, name)): Path<(String, String)>) -> Re
     }
 }
 
+/// ListEmailDeliveries
+#[utoipa::path(
+    get,
+    path = "/{org_id}/alerts/destinations/{destination_name}/deliveries",
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "ListEmailDeliveries",
+    summary = "List the emails sent by an alert destination",
+    description = "Lists the last emails sent by an email alert destination, the newest first, with whether the SMTP \
+                   server accepted them and its response or the error.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("destination_name" = String, Path, description = "Destination name"),
+      ),
+    responses(
+        (status = 200, description = "Success",  content_type = "application/json", body = inline(Vec<EmailDelivery>)),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Destinations", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "List the emails sent by a destination", "category": "alerts"}))
+    )
+)]
+pub async fn list_email_deliveries(Path((org_id, name)): Path<(String, String)>) -> Response {
+    if let Err(e) = destinations::get(&org_id, &name).await {
+        return MetaHttpResponse::not_found(e);
+    }
+    MetaHttpResponse::json(email::list_deliveries(&org_id, &name).await)
+}
+
 /// ListDestinations
 #[utoipa::path(
     get,
@@ -64,6 +64,7 @@ use crate::{
     },
 };
 
+pub mod backtest;
 pub mod dedup_stats;
 pub mod deduplication;
 pub mod destinations;
@@ -91,6 +92,7 @@ impl From<AlertError> for Response {
             AlertError::DecodeVrl(err) => MetaHttpResponse::bad_request(err),
             AlertError::ParseCron(err) => MetaHttpResponse::bad_request(err),
             AlertError::RealtimeMissingCustomQuery => MetaHttpResponse::bad_request(value),
+            AlertError::RealtimeDataset { .. } => MetaHttpResponse::bad_request(value),
             AlertError::SqlMissingQuery => MetaHttpResponse::bad_request(value),
             AlertError::SqlContainsSelectStar => MetaHttpResponse::bad_request(value),
             AlertError::PromqlMissingQuery => MetaHttpResponse::bad_request(value),
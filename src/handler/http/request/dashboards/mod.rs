@@ -42,6 +42,7 @@ use crate::{
 
 pub mod reports;
 pub mod timed_annotations;
+pub mod variables;
 
 impl From<DashboardError> for Response {
     fn from(value: DashboardError) -> Self {
@@ -0,0 +1,131 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use axum::{
+    extract::{Path, Query},
+    response::Response,
+};
+use config::{
+    meta::dashboards::variables::VariableValuesResponse, utils::schema::format_stream_name,
+};
+use hashbrown::HashMap;
+
+#[cfg(feature = "enterprise")]
+use crate::handler::http::request::search::utils::check_stream_permissions;
+use crate::{
+    common::{
+        meta::http::HttpResponse as MetaHttpResponse,
+        utils::{
+            auth::UserEmail,
+            http::{get_stream_type_from_request, get_ts_from_request_with_key},
+        },
+    },
+    handler::http::extractors::Headers,
+    service::dashboards::variables::{self, ValuesQuery, VariableError},
+};
+
+impl From<VariableError> for Response {
+    fn from(value: VariableError) -> Self {
+        match value {
+            VariableError::InvalidRequest(_) => MetaHttpResponse::bad_request(value),
+            VariableError::AccessDenied(_) => MetaHttpResponse::forbidden(value),
+            VariableError::SearchError(e) => MetaHttpResponse::internal_error(e),
+        }
+    }
+}
+
+/// GetVariableValues
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/dashboards/variables/{stream_name}",
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "GetDashboardVariableValues",
+    summary = "Get the values of a dashboard variable",
+    description = "Lists the most frequent values of a field for the dropdown of a dashboard variable, from the \
+                   distinct values the stream keeps instead of a search over its records. The field must be one of \
+                   the distinct value fields of the stream, the fields of the variables of a dashboard are added to \
+                   them when the dashboard is saved. The values are cached for a short time",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("type" = Option<String>, Query, description = "Stream type, logs by default"),
+        ("field" = String, Query, description = "Field of the variable"),
+        ("prefix" = Option<String>, Query, description = "Only the values starting with it, ignoring the case"),
+        ("start_time" = i64, Query, description = "Start time, in microseconds"),
+        ("end_time" = i64, Query, description = "End time, in microseconds"),
+        ("size" = Option<usize>, Query, description = "Number of values, 100 by default"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(VariableValuesResponse)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Dashboards", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "List the values of a dashboard variable", "category": "dashboards"}))
+    )
+)]
+pub async fn get_variable_values(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+    Headers(user_email): Headers<UserEmail>,
+) -> Response {
+    let mut stream_name = stream_name;
+    if !config::get_config().common.skip_formatting_stream_name {
+        stream_name = format_stream_name(stream_name);
+    }
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    let Some(field) = query.get("field").filter(|v| !v.is_empty()) else {
+        return MetaHttpResponse::bad_request("field is required");
+    };
+    let time_range = match (
+        get_ts_from_request_with_key(&query, "start_time"),
+        get_ts_from_request_with_key(&query, "end_time"),
+    ) {
+        (Ok(start), Ok(end)) => (start, end),
+        (Err(e), _) | (_, Err(e)) => return MetaHttpResponse::bad_request(e),
+    };
+    let size = query
+        .get("size")
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(100);
+    let user_id = user_email.user_id;
+
+    #[cfg(feature = "enterprise")]
+    if let Some(res) = check_stream_permissions(&stream_name, &org_id, &user_id, &stream_type).await
+    {
+        return res;
+    }
+
+    let trace_id = config::ider::generate_trace_id();
+    let values_query = ValuesQuery {
+        stream_type,
+        stream_name: &stream_name,
+        field,
+        prefix: query.get("prefix").map(|v| v.as_str()),
+        time_range,
+        size,
+    };
+    match variables::get_values(&trace_id, &org_id, &user_id, &values_query).await {
+        Ok(resp) => MetaHttpResponse::json(resp),
+        Err(e) => e.into(),
+    }
+}
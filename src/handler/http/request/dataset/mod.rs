@@ -0,0 +1,250 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use axum::{
+    Json,
+    extract::{Path, Query},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use config::meta::dataset::{Dataset, DatasetList};
+use hashbrown::HashMap;
+
+#[cfg(feature = "enterprise")]
+use crate::handler::http::request::search::utils::check_stream_permissions;
+use crate::{
+    common::{
+        meta::http::HttpResponse as MetaHttpResponse,
+        utils::{auth::UserEmail, http::get_stream_type_from_request},
+    },
+    handler::http::extractors::Headers,
+    service::dataset,
+};
+
+/// The user must be allowed to read every stream the dataset groups, a grant
+/// of the dataset covers its members
+#[cfg_attr(not(feature = "enterprise"), allow(unused_variables))]
+async fn check_members(org_id: &str, user_id: &str, ds: &Dataset) -> Option<Response> {
+    #[cfg(feature = "enterprise")]
+    for stream_name in dataset::member_streams(org_id, ds).await {
+        if let Some(res) =
+            check_stream_permissions(&stream_name, org_id, user_id, &ds.stream_type).await
+        {
+            return Some(res);
+        }
+    }
+    None
+}
+
+/// ListDatasets
+#[utoipa::path(
+    get,
+    path = "/{org_id}/datasets",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "DatasetList",
+    summary = "List datasets",
+    description = "Lists the datasets of the organization, of the given stream type or of every type",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("type" = Option<String>, Query, description = "Stream type"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = DatasetList),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "list"})),
+        ("x-o2-mcp" = json!({"description": "List datasets", "category": "streams"}))
+    )
+)]
+pub async fn list(
+    Path(org_id): Path<String>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    let list = dataset::list(&org_id, get_stream_type_from_request(&query));
+    (StatusCode::OK, Json(DatasetList { list })).into_response()
+}
+
+/// GetDataset
+#[utoipa::path(
+    get,
+    path = "/{org_id}/datasets/{name}",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "DatasetGet",
+    summary = "Get dataset",
+    description = "Gets the dataset with the streams it currently groups",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "Dataset name"),
+        ("type" = String, Query, description = "Stream type"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Get a dataset", "category": "streams"}))
+    )
+)]
+pub async fn get(
+    Path((org_id, name)): Path<(String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    let Some(ds) = dataset::get(&org_id, stream_type, &name) else {
+        return MetaHttpResponse::not_found("dataset not found");
+    };
+    let members = dataset::member_streams(&org_id, &ds).await;
+    let mut body = serde_json::to_value(&ds).unwrap_or_default();
+    body["members"] = serde_json::json!(members);
+    (StatusCode::OK, Json(body)).into_response()
+}
+
+/// CreateDataset
+#[utoipa::path(
+    post,
+    path = "/{org_id}/datasets",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "DatasetCreate",
+    summary = "Create dataset",
+    description = "Groups streams of a type under a name queried like a stream. Searches, alerts and dashboards \
+                   querying the dataset query the union of the streams matching its patterns. The name can't be \
+                   the name or the alias of an existing stream",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = Dataset, description = "Dataset", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Dataset),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "create"})),
+        ("x-o2-mcp" = json!({"description": "Create a dataset", "category": "streams"}))
+    )
+)]
+pub async fn create(
+    Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
+    Json(mut req): Json<Dataset>,
+) -> Response {
+    if let Err(e) = dataset::normalize(&mut req) {
+        return MetaHttpResponse::bad_request(e);
+    }
+    if let Some(res) = check_members(&org_id, &user_email.user_id, &req).await {
+        return res;
+    }
+    match dataset::save(&org_id, req, true).await {
+        Ok(v) => (StatusCode::OK, Json(v)).into_response(),
+        Err(e) => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// UpdateDataset
+#[utoipa::path(
+    put,
+    path = "/{org_id}/datasets/{name}",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "DatasetUpdate",
+    summary = "Update dataset",
+    description = "Replaces the streams and the description of the dataset",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "Dataset name"),
+    ),
+    request_body(content = Dataset, description = "Dataset", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Dataset),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "update"})),
+        ("x-o2-mcp" = json!({"description": "Update a dataset", "category": "streams"}))
+    )
+)]
+pub async fn update(
+    Path((org_id, name)): Path<(String, String)>,
+    Headers(user_email): Headers<UserEmail>,
+    Json(mut req): Json<Dataset>,
+) -> Response {
+    req.name = name;
+    if let Err(e) = dataset::normalize(&mut req) {
+        return MetaHttpResponse::bad_request(e);
+    }
+    if let Some(res) = check_members(&org_id, &user_email.user_id, &req).await {
+        return res;
+    }
+    match dataset::save(&org_id, req, false).await {
+        Ok(v) => (StatusCode::OK, Json(v)).into_response(),
+        Err(e) => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// DeleteDataset
+#[utoipa::path(
+    delete,
+    path = "/{org_id}/datasets/{name}",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "DatasetDelete",
+    summary = "Delete dataset",
+    description = "Deletes the dataset, its streams are kept and the queries using the dataset stop working",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "Dataset name"),
+        ("type" = String, Query, description = "Stream type"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "delete"})),
+        ("x-o2-mcp" = json!({"description": "Delete a dataset", "category": "streams"}))
+    )
+)]
+pub async fn delete(
+    Path((org_id, name)): Path<(String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    match dataset::delete(&org_id, stream_type, &name).await {
+        Ok(true) => MetaHttpResponse::ok("dataset deleted"),
+        Ok(false) => MetaHttpResponse::not_found("dataset not found"),
+        Err(e) => MetaHttpResponse::internal_error(e),
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use axum::{Json, extract::Path, response::Response};
+use config::meta::db_pollers::{DbPoller, DbPollerList};
+
+use crate::{
+    common::meta::http::HttpResponse as MetaHttpResponse,
+    service::db_pollers::{self, DbPollerError},
+};
+
+impl From<DbPollerError> for Response {
+    fn from(value: DbPollerError) -> Self {
+        match &value {
+            DbPollerError::InvalidPoller(_) => MetaHttpResponse::bad_request(value),
+            DbPollerError::PollerNotFound => MetaHttpResponse::not_found(value),
+            DbPollerError::InfraError(e) => MetaHttpResponse::internal_error(e),
+            DbPollerError::PollError(_) => MetaHttpResponse::internal_error(value),
+        }
+    }
+}
+
+/// CreateDbPoller
+
+#[utoipa::path(
+    post,
+    path = "/{org_id}/db_pollers",
+    context_path = "/api",
+    tag = "DB Pollers",
+    operation_id = "CreateDbPoller",
+    summary = "Create database poller",
+    description = "Creates a database poller. Every `interval` seconds the query runs against the Postgres or MySQL database and the rows with a `watermark_column` greater than the last one ingested are written to the logs stream, in batches of `batch_size` rows. The first poll ingests every row of the query, unless a `watermark` to start after is given. The password is never returned.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = inline(DbPoller), description = "Database poller details", example = json!({
+        "name": "audit_log",
+        "driver": "postgres",
+        "url": "postgres://db.internal:5432/app",
+        "username": "reader",
+        "password": "secret",
+        "query": "SELECT id, user_id, action, created_at FROM audit_log",
+        "watermark_column": "id",
+        "timestamp_column": "created_at",
+        "stream_name": "audit_log",
+        "interval": 60
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(DbPoller)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "DB Pollers", "operation": "create"})),
+        ("x-o2-mcp" = json!({"description": "Create a database poller", "category": "ingestion"}))
+    )
+)]
+pub async fn create(Path(org_id): Path<String>, Json(poller): Json<DbPoller>) -> Response {
+    match db_pollers::create(&org_id, poller).await {
+        Ok(poller) => MetaHttpResponse::json(poller),
+        Err(e) => e.into(),
+    }
+}
+
+/// UpdateDbPoller
+
+#[utoipa::path(
+    put,
+    path = "/{org_id}/db_pollers/{id}",
+    context_path = "/api",
+    tag = "DB Pollers",
+    operation_id = "UpdateDbPoller",
+    summary = "Update database poller",
+    description = "Updates a database poller. An empty password keeps the current one. The watermark is kept unless one is given, which restarts the polls after it.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Database poller id"),
+    ),
+    request_body(content = inline(DbPoller), description = "Database poller details"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(DbPoller)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "DB Pollers", "operation": "update"})),
+        ("x-o2-mcp" = json!({"description": "Update a database poller", "category": "ingestion"}))
+    )
+)]
+pub async fn update(
+    Path((org_id, id)): Path<(String, String)>,
+    Json(poller): Json<DbPoller>,
+) -> Response {
+    match db_pollers::update(&org_id, &id, poller).await {
+        Ok(poller) => MetaHttpResponse::json(poller),
+        Err(e) => e.into(),
+    }
+}
+
+/// GetDbPoller
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/db_pollers/{id}",
+    context_path = "/api",
+    tag = "DB Pollers",
+    operation_id = "GetDbPoller",
+    summary = "Get database poller",
+    description = "Retrieves a database poller, its watermark and the outcome of its last poll.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Database poller id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(DbPoller)),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "DB Pollers", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Get database poller details", "category": "ingestion"}))
+    )
+)]
+pub async fn get(Path((org_id, id)): Path<(String, String)>) -> Response {
+    match db_pollers::get(&org_id, &id).await {
+        Ok(poller) => MetaHttpResponse::json(poller),
+        Err(e) => e.into(),
+    }
+}
+
+/// ListDbPollers
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/db_pollers",
+    context_path = "/api",
+    tag = "DB Pollers",
+    operation_id = "ListDbPollers",
+    summary = "List database pollers",
+    description = "Lists the database pollers of the organization.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(DbPollerList)),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "DB Pollers", "operation": "list"})),
+        ("x-o2-mcp" = json!({"description": "List database pollers", "category": "ingestion"}))
+    )
+)]
+pub async fn list(Path(org_id): Path<String>) -> Response {
+    match db_pollers::list(&org_id).await {
+        Ok(list) => MetaHttpResponse::json(DbPollerList { list }),
+        Err(e) => e.into(),
+    }
+}
+
+/// DeleteDbPoller
+
+#[utoipa::path(
+    delete,
+    path = "/{org_id}/db_pollers/{id}",
+    context_path = "/api",
+    tag = "DB Pollers",
+    operation_id = "DeleteDbPoller",
+    summary = "Delete database poller",
+    description = "Deletes a database poller and stops its polls. The rows already ingested are kept.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Database poller id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = ()),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "DB Pollers", "operation": "delete"})),
+        ("x-o2-mcp" = json!({"description": "Delete a database poller", "category": "ingestion"}))
+    )
+)]
+pub async fn delete(Path((org_id, id)): Path<(String, String)>) -> Response {
+    match db_pollers::delete(&org_id, &id).await {
+        Ok(_) => MetaHttpResponse::ok("Database poller deleted"),
+        Err(e) => e.into(),
+    }
+}
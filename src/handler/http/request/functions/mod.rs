@@ -19,10 +19,21 @@ use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
 };
-use config::meta::function::{FunctionList, TestVRLRequest, Transform};
+use config::{
+    meta::{
+        function::{
+            FunctionList, FunctionVersionList, RollbackFunctionRequest, TestFunctionRequest,
+            TestVRLRequest, Transform,
+        },
+        stream::StreamType,
+    },
+    utils::json,
+};
 
 #[cfg(feature = "enterprise")]
 use crate::common::utils::auth::check_permissions;
+#[cfg(feature = "enterprise")]
+use crate::handler::http::request::search::utils::check_stream_permissions;
 use crate::{
     common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
     handler::http::{
@@ -352,7 +363,9 @@ pub async fn list_pipeline_dependencies(
     description = "Tests a VRL transformation function against sample events to validate the function logic and preview \
                    the expected output before deployment to production pipelines. Allows developers to verify data \
                    transformations, debug VRL code issues, and ensure correct field mappings without affecting live \
-                   data processing workflows.",
+                   data processing workflows. When no events are given, the function runs on the latest records of \
+                   the given stream. The response has the output or the error for each event, the number of errors \
+                   and the time the function took.",
     security(
         ("Authorization"= [])
     ),
@@ -369,14 +382,33 @@ pub async fn list_pipeline_dependencies(
 )]
 pub async fn test_function(
     Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
     Json(req_body): Json<TestVRLRequest>,
 ) -> Response {
+    let size = req_body.sample_size();
     let TestVRLRequest {
         function,
         events,
         trans_type,
+        stream_name,
+        stream_type,
+        ..
     } = req_body;
 
+    let events = match resolve_events(
+        &org_id,
+        &user_email.user_id,
+        events,
+        stream_name,
+        stream_type,
+        size,
+    )
+    .await
+    {
+        Ok(events) => events,
+        Err(response) => return response,
+    };
+
     // test_run_function will auto-detect VRL vs JS if trans_type is None
     match crate::service::functions::test_run_function(&org_id, function, events, trans_type).await
     {
@@ -384,3 +416,180 @@ pub async fn test_function(
         Err(err) => (StatusCode::BAD_REQUEST, err.to_string()).into_response(),
     }
 }
+
+/// The events to test a function on: the given ones, or the latest records of
+/// the stream when none are given.
+async fn resolve_events(
+    org_id: &str,
+    _user_id: &str,
+    events: Vec<json::Value>,
+    stream_name: Option<String>,
+    stream_type: Option<StreamType>,
+    size: usize,
+) -> Result<Vec<json::Value>, Response> {
+    if !events.is_empty() {
+        return Ok(events);
+    }
+    let Some(stream_name) = stream_name.filter(|s| !s.is_empty()) else {
+        return Ok(events);
+    };
+    let stream_type = stream_type.unwrap_or_default();
+    #[cfg(feature = "enterprise")]
+    if let Some(response) =
+        check_stream_permissions(&stream_name, org_id, _user_id, &stream_type).await
+    {
+        return Err(response);
+    }
+    let trace_id = config::ider::generate_trace_id();
+    let events = crate::service::functions::sample_events(
+        &trace_id,
+        org_id,
+        stream_type,
+        &stream_name,
+        size,
+    )
+    .await
+    .map_err(MetaHttpResponse::bad_request)?;
+    if events.is_empty() {
+        return Err(MetaHttpResponse::bad_request(format!(
+            "no recent records in stream [{stream_name}]"
+        )));
+    }
+    Ok(events)
+}
+
+/// ListFunctionVersions
+#[utoipa::path(
+    get,
+    path = "/{org_id}/functions/{name}/versions",
+    context_path = "/api",
+    tag = "Functions",
+    operation_id = "listFunctionVersions",
+    summary = "List function versions",
+    description = "Lists the saved versions of a transformation function, newest first. A new version is recorded each \
+                   time the function is created, changed or rolled back, and the oldest versions are dropped once the \
+                   limit is reached.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "Function name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(FunctionVersionList)),
+        (status = 404, description = "Function not found", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Functions", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "List the versions of a function", "category": "functions"}))
+    )
+)]
+pub async fn list_function_versions(Path((org_id, name)): Path<(String, String)>) -> Response {
+    match crate::service::functions::list_function_versions(&org_id, &name).await {
+        Ok(resp) => resp,
+        Err(e) => MetaHttpResponse::internal_error(e.to_string()),
+    }
+}
+
+/// RollbackFunction
+#[utoipa::path(
+    post,
+    path = "/{org_id}/functions/{name}/rollback",
+    context_path = "/api",
+    tag = "Functions",
+    operation_id = "rollbackFunction",
+    summary = "Roll back function",
+    description = "Restores the code and parameters of a previous version of a transformation function. The restored \
+                   content is saved as a new version and takes effect immediately in the pipelines using the function.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "Function name"),
+    ),
+    request_body(content = inline(RollbackFunctionRequest), description = "Version to restore", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 404, description = "Function or version not found", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Functions", "operation": "update"})),
+        ("x-o2-mcp" = json!({"description": "Roll back a function to a previous version", "category": "functions"}))
+    )
+)]
+pub async fn rollback_function(
+    Path((org_id, name)): Path<(String, String)>,
+    Json(req): Json<RollbackFunctionRequest>,
+) -> Response {
+    match crate::service::functions::rollback_function(&org_id, &name, req.version).await {
+        Ok(resp) => resp,
+        Err(e) => MetaHttpResponse::internal_error(e.to_string()),
+    }
+}
+
+/// Test a saved Function
+#[utoipa::path(
+    post,
+    path = "/{org_id}/functions/{name}/test",
+    context_path = "/api",
+    tag = "Functions",
+    operation_id = "testSavedFunction",
+    summary = "Dry run saved function",
+    description = "Runs a saved transformation function, or one of its previous versions, against sample events without \
+                   changing anything. When no events are given, the function runs on the latest records of the given \
+                   stream. The response has the output or the error for each event, the number of errors and the time \
+                   the function took.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "Function name"),
+    ),
+    request_body(content = inline(TestFunctionRequest), description = "Events to run the function on", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 404, description = "Function or version not found", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Functions", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Dry run a saved function on sample events", "category": "functions"}))
+    )
+)]
+pub async fn test_saved_function(
+    Path((org_id, name)): Path<(String, String)>,
+    Headers(user_email): Headers<UserEmail>,
+    Json(req_body): Json<TestFunctionRequest>,
+) -> Response {
+    let size = req_body.sample_size();
+    let TestFunctionRequest {
+        version,
+        events,
+        stream_name,
+        stream_type,
+        ..
+    } = req_body;
+
+    let events = match resolve_events(
+        &org_id,
+        &user_email.user_id,
+        events,
+        stream_name,
+        stream_type,
+        size,
+    )
+    .await
+    {
+        Ok(events) => events,
+        Err(response) => return response,
+    };
+
+    match crate::service::functions::test_saved_function(&org_id, &name, version, events).await {
+        Ok(result) => result,
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()).into_response(),
+    }
+}
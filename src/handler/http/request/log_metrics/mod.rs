@@ -0,0 +1,207 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use axum::{Json, extract::Path, response::Response};
+use config::meta::log_metrics::{LogMetricRule, LogMetricRuleList};
+
+use crate::{
+    common::meta::http::HttpResponse as MetaHttpResponse,
+    service::log_metrics::{self, LogMetricError},
+};
+
+impl From<LogMetricError> for Response {
+    fn from(value: LogMetricError) -> Self {
+        match &value {
+            LogMetricError::InvalidRule(_) => MetaHttpResponse::bad_request(value),
+            LogMetricError::RuleNotFound => MetaHttpResponse::not_found(value),
+            LogMetricError::InfraError(e) => MetaHttpResponse::internal_error(e),
+        }
+    }
+}
+
+/// CreateLogMetricRule
+
+#[utoipa::path(
+    post,
+    path = "/{org_id}/log_metrics",
+    context_path = "/api",
+    tag = "Log Metrics",
+    operation_id = "CreateLogMetricRule",
+    summary = "Create log metric rule",
+    description = "Creates a log metric rule. The records ingested into the logs stream matching the filters are counted, or their `value_field` observed into a histogram, by the values of the label fields. Every ingester writes its cumulative series to the metric `name` periodically, labeled with its `instance`.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = inline(LogMetricRule), description = "Log metric rule details", example = json!({
+        "name": "nginx_requests_total",
+        "stream_name": "nginx",
+        "kind": "counter",
+        "filters": {"method": "GET"},
+        "labels": ["status"]
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(LogMetricRule)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Log Metrics", "operation": "create"})),
+        ("x-o2-mcp" = json!({"description": "Create a rule extracting a metric from ingested logs", "category": "metrics"}))
+    )
+)]
+pub async fn create_rule(Path(org_id): Path<String>, Json(rule): Json<LogMetricRule>) -> Response {
+    match log_metrics::create(&org_id, rule).await {
+        Ok(rule) => MetaHttpResponse::json(rule),
+        Err(e) => e.into(),
+    }
+}
+
+/// UpdateLogMetricRule
+
+#[utoipa::path(
+    put,
+    path = "/{org_id}/log_metrics/{id}",
+    context_path = "/api",
+    tag = "Log Metrics",
+    operation_id = "UpdateLogMetricRule",
+    summary = "Update log metric rule",
+    description = "Updates a log metric rule. Changing the name, the kind, the labels or the buckets starts its series over.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Log metric rule id"),
+    ),
+    request_body(content = inline(LogMetricRule), description = "Log metric rule details"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(LogMetricRule)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Log Metrics", "operation": "update"})),
+        ("x-o2-mcp" = json!({"description": "Update a log metric rule", "category": "metrics"}))
+    )
+)]
+pub async fn update_rule(
+    Path((org_id, id)): Path<(String, String)>,
+    Json(rule): Json<LogMetricRule>,
+) -> Response {
+    match log_metrics::update(&org_id, &id, rule).await {
+        Ok(rule) => MetaHttpResponse::json(rule),
+        Err(e) => e.into(),
+    }
+}
+
+/// GetLogMetricRule
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/log_metrics/{id}",
+    context_path = "/api",
+    tag = "Log Metrics",
+    operation_id = "GetLogMetricRule",
+    summary = "Get log metric rule",
+    description = "Retrieves a log metric rule.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Log metric rule id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(LogMetricRule)),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Log Metrics", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Get log metric rule details", "category": "metrics"}))
+    )
+)]
+pub async fn get_rule(Path((org_id, id)): Path<(String, String)>) -> Response {
+    match log_metrics::get(&org_id, &id).await {
+        Ok(rule) => MetaHttpResponse::json(rule),
+        Err(e) => e.into(),
+    }
+}
+
+/// ListLogMetricRules
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/log_metrics",
+    context_path = "/api",
+    tag = "Log Metrics",
+    operation_id = "ListLogMetricRules",
+    summary = "List log metric rules",
+    description = "Lists the log metric rules of the organization.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(LogMetricRuleList)),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Log Metrics", "operation": "list"})),
+        ("x-o2-mcp" = json!({"description": "List log metric rules", "category": "metrics"}))
+    )
+)]
+pub async fn list_rules(Path(org_id): Path<String>) -> Response {
+    match log_metrics::list(&org_id).await {
+        Ok(list) => MetaHttpResponse::json(LogMetricRuleList { list }),
+        Err(e) => e.into(),
+    }
+}
+
+/// DeleteLogMetricRule
+
+#[utoipa::path(
+    delete,
+    path = "/{org_id}/log_metrics/{id}",
+    context_path = "/api",
+    tag = "Log Metrics",
+    operation_id = "DeleteLogMetricRule",
+    summary = "Delete log metric rule",
+    description = "Deletes a log metric rule. The series already written are kept.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Log metric rule id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = ()),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Log Metrics", "operation": "delete"})),
+        ("x-o2-mcp" = json!({"description": "Delete a log metric rule", "category": "metrics"}))
+    )
+)]
+pub async fn delete_rule(Path((org_id, id)): Path<(String, String)>) -> Response {
+    match log_metrics::delete(&org_id, &id).await {
+        Ok(_) => MetaHttpResponse::ok("Log metric rule deleted"),
+        Err(e) => e.into(),
+    }
+}
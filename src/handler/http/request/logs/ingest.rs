@@ -13,10 +13,12 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::HashMap;
+
 use axum::{
     Json,
-    body::Bytes,
-    extract::Path,
+    body::{Body, Bytes},
+    extract::{Path, Query},
     http::{HeaderMap, StatusCode},
     response::{IntoResponse, Response},
 };
@@ -27,7 +29,6 @@ use config::{
     meta::otlp::OtlpRequestType,
 };
 use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
-use prost::Message;
 
 #[cfg(feature = "cloud")]
 use crate::service::ingestion::check_ingestion_allowed;
@@ -37,10 +38,11 @@ use crate::{
             http::HttpResponse as MetaHttpResponse,
             ingestion::{
                 GCPIngestionRequest, HecResponse, HecStatus, IngestUser, IngestionRequest,
-                KinesisFHIngestionResponse, KinesisFHRequest,
+                KinesisFHIngestionResponse, KinesisFHRequest, SplunkAckRequest, SplunkAckResponse,
+                SplunkEventResponse,
             },
         },
-        utils::auth::UserEmail,
+        utils::{auth::UserEmail, otlp},
     },
     handler::http::{
         extractors::Headers,
@@ -108,8 +110,11 @@ pub async fn bulk(
     {
         Ok(v) => MetaHttpResponse::json(v),
         Err(e) => {
-            // we do not want to log trial period expired errors
-            if !matches!(e, infra::errors::Error::TrialPeriodExpired) {
+            // we do not want to log trial period expired and quota errors
+            if !matches!(
+                e,
+                infra::errors::Error::TrialPeriodExpired | infra::errors::Error::QuotaExceeded(_)
+            ) {
                 log::error!("Error processing request {org_id}/_bulk: {e}");
             }
             if matches!(e, infra::errors::Error::ResourceError(_)) {
@@ -118,6 +123,8 @@ pub async fn bulk(
                     Json(MetaHttpResponse::error(StatusCode::SERVICE_UNAVAILABLE, e)),
                 )
                     .into_response()
+            } else if matches!(e, infra::errors::Error::QuotaExceeded(_)) {
+                MetaHttpResponse::too_many_requests(e)
             } else {
                 (
                     StatusCode::BAD_REQUEST,
@@ -196,8 +203,11 @@ pub async fn multi(
             _ => MetaHttpResponse::json(v),
         },
         Err(e) => {
-            // we do not want to log trial period expired errors
-            if !matches!(e, infra::errors::Error::TrialPeriodExpired) {
+            // we do not want to log trial period expired and quota errors
+            if !matches!(
+                e,
+                infra::errors::Error::TrialPeriodExpired | infra::errors::Error::QuotaExceeded(_)
+            ) {
                 log::error!("Error processing request {org_id}/{stream_name}/_multi: {e}");
             }
             if matches!(e, infra::errors::Error::ResourceError(_)) {
@@ -206,6 +216,8 @@ pub async fn multi(
                     Json(MetaHttpResponse::error(StatusCode::SERVICE_UNAVAILABLE, e)),
                 )
                     .into_response()
+            } else if matches!(e, infra::errors::Error::QuotaExceeded(_)) {
+                MetaHttpResponse::too_many_requests(e)
             } else {
                 (
                     StatusCode::BAD_REQUEST,
@@ -284,8 +296,11 @@ pub async fn json(
             _ => MetaHttpResponse::json(v),
         },
         Err(e) => {
-            // we do not want to log trial period expired errors
-            if !matches!(e, infra::errors::Error::TrialPeriodExpired) {
+            // we do not want to log trial period expired and quota errors
+            if !matches!(
+                e,
+                infra::errors::Error::TrialPeriodExpired | infra::errors::Error::QuotaExceeded(_)
+            ) {
                 log::error!("Error processing request {org_id}/{stream_name}/_json: {e}");
             }
             if matches!(e, infra::errors::Error::ResourceError(_)) {
@@ -294,6 +309,8 @@ pub async fn json(
                     Json(MetaHttpResponse::error(StatusCode::SERVICE_UNAVAILABLE, e)),
                 )
                     .into_response()
+            } else if matches!(e, infra::errors::Error::QuotaExceeded(_)) {
+                MetaHttpResponse::too_many_requests(e)
             } else {
                 MetaHttpResponse::bad_request(e)
             }
@@ -371,31 +388,27 @@ pub async fn handle_kinesis_request(
             error_message: None,
         }),
         Err(e) => {
-            // we do not want to log trial period expired errors
-            if !matches!(e, infra::errors::Error::TrialPeriodExpired) {
+            // we do not want to log trial period expired and quota errors
+            if !matches!(
+                e,
+                infra::errors::Error::TrialPeriodExpired | infra::errors::Error::QuotaExceeded(_)
+            ) {
                 log::error!("Error processing kinesis request:  org_id: {org_id} {e}");
             }
-            if matches!(e, infra::errors::Error::ResourceError(_)) {
-                (
-                    StatusCode::SERVICE_UNAVAILABLE,
-                    Json(KinesisFHIngestionResponse {
-                        request_id,
-                        timestamp: request_time,
-                        error_message: e.to_string().into(),
-                    }),
-                )
-                    .into_response()
-            } else {
-                (
-                    StatusCode::BAD_REQUEST,
-                    Json(KinesisFHIngestionResponse {
-                        request_id,
-                        timestamp: request_time,
-                        error_message: e.to_string().into(),
-                    }),
-                )
-                    .into_response()
-            }
+            let status = match e {
+                infra::errors::Error::ResourceError(_) => StatusCode::SERVICE_UNAVAILABLE,
+                infra::errors::Error::QuotaExceeded(_) => StatusCode::TOO_MANY_REQUESTS,
+                _ => StatusCode::BAD_REQUEST,
+            };
+            (
+                status,
+                Json(KinesisFHIngestionResponse {
+                    request_id,
+                    timestamp: request_time,
+                    error_message: e.to_string().into(),
+                }),
+            )
+                .into_response()
         }
     }
 }
@@ -430,8 +443,11 @@ pub async fn handle_gcp_request(
     {
         Ok(v) => MetaHttpResponse::json(v),
         Err(e) => {
-            // we do not want to log trial period expired errors
-            if !matches!(e, infra::errors::Error::TrialPeriodExpired) {
+            // we do not want to log trial period expired and quota errors
+            if !matches!(
+                e,
+                infra::errors::Error::TrialPeriodExpired | infra::errors::Error::QuotaExceeded(_)
+            ) {
                 log::error!("Error processing request {org_id}/{stream_name}/_gcp: {e:?}");
             }
             if matches!(e, infra::errors::Error::ResourceError(_)) {
@@ -440,6 +456,8 @@ pub async fn handle_gcp_request(
                     Json(MetaHttpResponse::error(StatusCode::SERVICE_UNAVAILABLE, e)),
                 )
                     .into_response()
+            } else if matches!(e, infra::errors::Error::QuotaExceeded(_)) {
+                MetaHttpResponse::too_many_requests(e)
             } else {
                 (
                     StatusCode::BAD_REQUEST,
@@ -475,15 +493,16 @@ pub async fn otlp_logs_write(
     Path(org_id): Path<String>,
     Headers(user_email): Headers<UserEmail>,
     headers: HeaderMap,
-    body: Bytes,
+    body: Body,
 ) -> Response {
     let content_type = headers
         .get("Content-Type")
         .and_then(|v| v.to_str().ok())
         .unwrap_or(CONTENT_TYPE_PROTO);
     let user_email = &user_email.user_id;
+    let cfg = config::get_config();
     let in_stream_name = headers
-        .get(&config::get_config().grpc.stream_header_key)
+        .get(&cfg.grpc.stream_header_key)
         .and_then(|v| v.to_str().ok());
     let thread_id = get_thread_id();
 
@@ -497,21 +516,23 @@ pub async fn otlp_logs_write(
     }
 
     let (request, request_type) = match content_type {
-        CONTENT_TYPE_PROTO => match ExportLogsServiceRequest::decode(body) {
-            Ok(req) => (req, OtlpRequestType::HttpProtobuf),
-            Err(e) => {
-                log::error!("[LOGS:OTLP] Invalid proto: org_id: {org_id} {e}");
-                return (
-                    StatusCode::BAD_REQUEST,
-                    Json(MetaHttpResponse::error(
-                        StatusCode::BAD_REQUEST,
-                        format!("Invalid proto: {e}"),
-                    )),
-                )
-                    .into_response();
+        CONTENT_TYPE_PROTO => {
+            match otlp::decode_resources(body, cfg.limit.otlp_payload_limit).await {
+                Ok(resource_logs) => (
+                    ExportLogsServiceRequest { resource_logs },
+                    OtlpRequestType::HttpProtobuf,
+                ),
+                Err(e) => {
+                    log::error!("[LOGS:OTLP] Invalid proto: org_id: {org_id} {e}");
+                    return e.into_response();
+                }
             }
-        },
+        }
         CONTENT_TYPE_JSON => {
+            let body = match otlp::read_body(body, cfg.limit.otlp_payload_limit).await {
+                Ok(v) => v,
+                Err(e) => return e.into_response(),
+            };
             match serde_json::from_slice::<ExportLogsServiceRequest>(body.as_ref()) {
                 Ok(req) => (req, OtlpRequestType::HttpJson),
                 Err(e) => {
@@ -544,8 +565,11 @@ pub async fn otlp_logs_write(
     {
         Ok(v) => v,
         Err(e) => {
-            // we do not want to log trial period expired errors
-            if !matches!(e, infra::errors::Error::TrialPeriodExpired) {
+            // we do not want to log trial period expired and quota errors
+            if !matches!(
+                e,
+                infra::errors::Error::TrialPeriodExpired | infra::errors::Error::QuotaExceeded(_)
+            ) {
                 log::error!(
                     "Error processing otlp {content_type} logs write request {org_id}/{in_stream_name:?}: {e:?}"
                 );
@@ -556,6 +580,8 @@ pub async fn otlp_logs_write(
                     Json(MetaHttpResponse::error(StatusCode::SERVICE_UNAVAILABLE, e)),
                 )
                     .into_response()
+            } else if matches!(e, infra::errors::Error::QuotaExceeded(_)) {
+                MetaHttpResponse::too_many_requests(e)
             } else {
                 (
                     StatusCode::BAD_REQUEST,
@@ -577,16 +603,19 @@ pub async fn otlp_logs_write(
     summary = "Ingest logs via Splunk HEC format",
     description = "Ingests log data using Splunk HTTP Event Collector (HEC) format, providing compatibility with Splunk \
                    forwarders and applications. This endpoint accepts the standard HEC JSON format, making it easy to \
-                   migrate from or integrate with existing Splunk deployments.",
+                   migrate from or integrate with existing Splunk deployments. Requests sent on a channel get an \
+                   `ackId` for indexer acknowledgement.",
     security(
         ("Authorization"= [])
     ),
     params(
         ("org_id" = String, Path, description = "Organization name"),
+        ("X-Splunk-Request-Channel" = Option<String>, Header, description = "Channel for indexer acknowledgement"),
+        ("channel" = Option<String>, Query, description = "Channel for indexer acknowledgement, when not in the header"),
     ),
     request_body(content = String, description = "Ingest data (hec)"),
     responses(
-        (status = 200, description = "Success", content_type = "application/json", body = inline(HecResponse), example = json!({"text":"Success","code": 200})),
+        (status = 200, description = "Success", content_type = "application/json", body = inline(HecResponse), example = json!({"text":"Success","code": 200,"ackId": 0})),
         (status = 200, description = "Failure", content_type = "application/json", body = inline(HecResponse), example = json!({"text":"Invalid data format","code": 400})),
     ),
     extensions(
@@ -596,6 +625,8 @@ pub async fn otlp_logs_write(
 pub async fn hec(
     Path(org_id): Path<String>,
     Headers(user_email): Headers<UserEmail>,
+    Query(query): Query<HashMap<String, String>>,
+    headers: HeaderMap,
     body: Bytes,
 ) -> Response {
     let user_email = &user_email.user_id;
@@ -613,7 +644,8 @@ pub async fn hec(
     // log start processing time
     let process_time = get_process_time();
 
-    let mut resp = match logs::hec::ingest(thread_id, &org_id, body, user_email).await {
+    let channel = hec_channel(&headers, &query);
+    let mut resp = match logs::hec::ingest(thread_id, &org_id, body, user_email, channel).await {
         Ok(v) => {
             if v.code > 299 {
                 (StatusCode::BAD_REQUEST, Json(v)).into_response()
@@ -622,13 +654,18 @@ pub async fn hec(
             }
         }
         Err(e) => {
-            // we do not want to log trial period expired errors
-            if !matches!(e, infra::errors::Error::TrialPeriodExpired) {
+            // we do not want to log trial period expired and quota errors
+            if !matches!(
+                e,
+                infra::errors::Error::TrialPeriodExpired | infra::errors::Error::QuotaExceeded(_)
+            ) {
                 log::error!("Error processing request {org_id}/_hec: {e}");
             }
             let res = HecResponse::from(HecStatus::Custom(e.to_string(), 400));
             if matches!(e, infra::errors::Error::ResourceError(_)) {
                 (StatusCode::SERVICE_UNAVAILABLE, Json(res)).into_response()
+            } else if matches!(e, infra::errors::Error::QuotaExceeded(_)) {
+                (StatusCode::TOO_MANY_REQUESTS, Json(res)).into_response()
             } else {
                 (StatusCode::BAD_REQUEST, Json(res)).into_response()
             }
@@ -639,3 +676,439 @@ pub async fn hec(
 
     resp
 }
+
+/// Indexer acknowledgement status for the HEC endpoint
+#[utoipa::path(
+    post,
+    path = "/{org_id}/_hec/services/collector/ack",
+    context_path = "/api",
+    tag = "Logs",
+    operation_id = "LogsIngestionHecAck",
+    summary = "Query HEC indexer acknowledgement status",
+    description = "Reports which ack ids returned by the HEC endpoint on the channel have their events written to the \
+                   WAL. An acknowledged id is reported once, unknown ids are not acknowledged. The ids are only known \
+                   by the node that took the events, the load balancer has to keep a channel on the same node.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("X-Splunk-Request-Channel" = Option<String>, Header, description = "Channel the events were sent on"),
+        ("channel" = Option<String>, Query, description = "Channel the events were sent on, when not in the header"),
+    ),
+    request_body(content = inline(SplunkAckRequest), description = "Ack ids to check", content_type = "application/json", example = json!({"acks": [0, 1]})),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(SplunkAckResponse), example = json!({"acks": {"0": true, "1": false}})),
+        (status = 400, description = "Failure", content_type = "application/json", body = inline(HecResponse), example = json!({"text":"Data channel is missing","code": 400})),
+    ),
+    extensions(
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn hec_ack(
+    Path(_org_id): Path<String>,
+    Query(query): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+    Json(req): Json<SplunkAckRequest>,
+) -> Response {
+    let Some(channel) = hec_channel(&headers, &query) else {
+        let res = HecResponse::from(HecStatus::Custom(
+            "Data channel is missing".to_string(),
+            400,
+        ));
+        return (StatusCode::BAD_REQUEST, Json(res)).into_response();
+    };
+    let acks = logs::hec::query_acks(channel, &req.acks);
+    MetaHttpResponse::json(SplunkAckResponse { acks })
+}
+
+/// The channel of a HEC request, from the header or the query
+fn hec_channel<'a>(headers: &'a HeaderMap, query: &'a HashMap<String, String>) -> Option<&'a str> {
+    headers
+        .get("X-Splunk-Request-Channel")
+        .and_then(|v| v.to_str().ok())
+        .or_else(|| query.get("channel").map(|v| v.as_str()))
+        .filter(|v| !v.is_empty())
+}
+
+/// Docker splunk log driver compatible ingestion API
+#[utoipa::path(
+    post,
+    path = "/{org_id}/_docker/services/collector/event/1.0",
+    context_path = "/api",
+    tag = "Logs",
+    operation_id = "LogsIngestionDockerSplunk",
+    summary = "Ingest logs from the docker splunk log driver",
+    description = "Accepts logs sent by the docker `splunk` log driver. Point `splunk-url` at `/api/{org_id}/_docker` \
+                   (or `/api/{org_id}/{stream_name}/_docker`) and set `splunk-token` to the base64 encoded \
+                   `email:token` credentials. All `splunk-format` values are supported, `splunk-index` selects the \
+                   stream. Set `splunk-verify-connection=false` as the connection check is not authenticated.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("X-Splunk-Request-Channel" = Option<String>, Header, description = "Channel for indexer acknowledgement"),
+        ("channel" = Option<String>, Query, description = "Channel for indexer acknowledgement, when not in the header"),
+    ),
+    request_body(content = String, description = "Concatenated splunk driver events", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(SplunkEventResponse), example = json!({"text":"Success","code": 0,"ackId": 3})),
+        (status = 400, description = "Failure", content_type = "application/json", body = inline(SplunkEventResponse), example = json!({"text":"Invalid data format","code": 6})),
+    ),
+    extensions(
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn docker_splunk(
+    Path(org_id): Path<String>,
+    Query(query): Query<HashMap<String, String>>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let channel = hec_channel(&headers, &query);
+    docker_splunk_inner(org_id, None, &user_email.user_id, channel, body).await
+}
+
+/// Docker splunk log driver compatible ingestion API with a default stream
+#[utoipa::path(
+    post,
+    path = "/{org_id}/{stream_name}/_docker/services/collector/event/1.0",
+    context_path = "/api",
+    tag = "Logs",
+    operation_id = "LogsIngestionDockerSplunkStream",
+    summary = "Ingest logs from the docker splunk log driver into a stream",
+    description = "Same as the org level docker endpoint, events without a `splunk-index` go to `stream_name`.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+    ),
+    request_body(content = String, description = "Concatenated splunk driver events", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(SplunkEventResponse), example = json!({"text":"Success","code": 0})),
+        (status = 400, description = "Failure", content_type = "application/json", body = inline(SplunkEventResponse), example = json!({"text":"Invalid data format","code": 6})),
+    ),
+    extensions(
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn docker_splunk_stream(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let channel = hec_channel(&headers, &query);
+    docker_splunk_inner(
+        org_id,
+        Some(stream_name),
+        &user_email.user_id,
+        channel,
+        body,
+    )
+    .await
+}
+
+async fn docker_splunk_inner(
+    org_id: String,
+    stream_name: Option<String>,
+    user_email: &str,
+    channel: Option<&str>,
+    body: Bytes,
+) -> Response {
+    let thread_id = get_thread_id();
+
+    #[cfg(feature = "cloud")]
+    if let Err(e) = check_ingestion_allowed(&org_id, StreamType::Logs, None).await {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(MetaHttpResponse::error(StatusCode::TOO_MANY_REQUESTS, e)),
+        )
+            .into_response();
+    }
+
+    // log start processing time
+    let process_time = get_process_time();
+
+    let mut resp = match logs::docker::ingest_splunk(
+        thread_id,
+        &org_id,
+        stream_name.as_deref(),
+        body,
+        user_email,
+        channel,
+    )
+    .await
+    {
+        Ok(v) if v.code == 0 => MetaHttpResponse::json(v),
+        Ok(v) => (StatusCode::BAD_REQUEST, Json(v)).into_response(),
+        Err(e) => {
+            // we do not want to log trial period expired and quota errors
+            if !matches!(
+                e,
+                infra::errors::Error::TrialPeriodExpired | infra::errors::Error::QuotaExceeded(_)
+            ) {
+                log::error!("Error processing request {org_id}/_docker: {e}");
+            }
+            let res = SplunkEventResponse {
+                text: e.to_string(),
+                code: 8, // Splunk: internal server error
+                ack_id: None,
+            };
+            if matches!(e, infra::errors::Error::ResourceError(_)) {
+                (StatusCode::SERVICE_UNAVAILABLE, Json(res)).into_response()
+            } else if matches!(e, infra::errors::Error::QuotaExceeded(_)) {
+                (StatusCode::TOO_MANY_REQUESTS, Json(res)).into_response()
+            } else {
+                (StatusCode::BAD_REQUEST, Json(res)).into_response()
+            }
+        }
+    };
+
+    insert_process_time_header(process_time, resp.headers_mut());
+
+    resp
+}
+
+/// Indexer acknowledgement status for the docker splunk log driver endpoint
+#[utoipa::path(
+    post,
+    path = "/{org_id}/_docker/services/collector/ack",
+    context_path = "/api",
+    tag = "Logs",
+    operation_id = "LogsIngestionDockerSplunkAck",
+    summary = "Query indexer acknowledgement status",
+    description = "Reports which ack ids returned by the docker splunk endpoint on the channel have their events \
+                   written to the WAL. An acknowledged id is reported once, unknown ids are not acknowledged.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("X-Splunk-Request-Channel" = Option<String>, Header, description = "Channel the events were sent on"),
+        ("channel" = Option<String>, Query, description = "Channel the events were sent on, when not in the header"),
+    ),
+    request_body(content = inline(SplunkAckRequest), description = "Ack ids to check", content_type = "application/json", example = json!({"acks": [0, 1]})),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(SplunkAckResponse), example = json!({"acks": {"0": true, "1": false}})),
+        (status = 400, description = "Failure", content_type = "application/json", body = inline(SplunkEventResponse), example = json!({"text":"Data channel is missing","code": 10})),
+    ),
+    extensions(
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn docker_splunk_ack(
+    Path(_org_id): Path<String>,
+    Query(query): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+    Json(req): Json<SplunkAckRequest>,
+) -> Response {
+    let Some(channel) = hec_channel(&headers, &query) else {
+        let res = SplunkEventResponse {
+            text: "Data channel is missing".to_string(),
+            code: 10, // Splunk: data channel is missing
+            ack_id: None,
+        };
+        return (StatusCode::BAD_REQUEST, Json(res)).into_response();
+    };
+    let acks = logs::hec::query_acks(channel, &req.acks);
+    MetaHttpResponse::json(SplunkAckResponse { acks })
+}
+
+/// Heroku / Cloud Foundry Logplex drain ingestion API
+#[utoipa::path(
+    post,
+    path = "/{org_id}/{stream_name}/_logplex",
+    context_path = "/api",
+    tag = "Logs",
+    operation_id = "LogsIngestionLogplex",
+    summary = "Ingest logs from a Logplex HTTPS drain",
+    description = "Accepts octet counted syslog frames posted by Heroku and Cloud Foundry HTTPS log drains. \
+                   Add it with `heroku drains:add https://{email}:{token}@{host}/api/{org_id}/{stream_name}/_logplex`. \
+                   Requests whose frame count doesn't match `Logplex-Msg-Count` are rejected, and frames retried \
+                   with an already ingested `Logplex-Frame-Id` are dropped.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("Logplex-Msg-Count" = Option<usize>, Header, description = "Number of syslog messages in the body"),
+        ("Logplex-Frame-Id" = Option<String>, Header, description = "Unique id of the frame, reused on retries"),
+        ("Logplex-Drain-Token" = Option<String>, Header, description = "Token of the drain that sent the frame"),
+    ),
+    request_body(content = String, description = "Octet counted syslog frames", content_type = "application/logplex-1"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({"code": 200,"status": [{"name": "olympics","successful": 3,"failed": 0}]})),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn logplex(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let user_email = &user_email.user_id;
+    let thread_id = get_thread_id();
+
+    #[cfg(feature = "cloud")]
+    if let Err(e) = check_ingestion_allowed(&org_id, StreamType::Logs, None).await {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(MetaHttpResponse::error(StatusCode::TOO_MANY_REQUESTS, e)),
+        )
+            .into_response();
+    }
+
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.trim().to_string())
+    };
+    let logplex_headers = logs::logplex::LogplexHeaders {
+        msg_count: header("Logplex-Msg-Count").and_then(|v| v.parse().ok()),
+        frame_id: header("Logplex-Frame-Id"),
+        drain_token: header("Logplex-Drain-Token"),
+    };
+
+    // log start processing time
+    let process_time = get_process_time();
+
+    let mut resp = match logs::logplex::ingest(
+        thread_id,
+        &org_id,
+        &stream_name,
+        body,
+        logplex_headers,
+        user_email,
+    )
+    .await
+    {
+        Ok(v) => {
+            if v.code > 299 {
+                (StatusCode::BAD_REQUEST, Json(v)).into_response()
+            } else {
+                MetaHttpResponse::json(v)
+            }
+        }
+        Err(e) => {
+            // we do not want to log trial period expired and quota errors
+            if !matches!(
+                e,
+                infra::errors::Error::TrialPeriodExpired | infra::errors::Error::QuotaExceeded(_)
+            ) {
+                log::error!("Error processing request {org_id}/{stream_name}/_logplex: {e}");
+            }
+            if matches!(e, infra::errors::Error::ResourceError(_)) {
+                (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    Json(MetaHttpResponse::error(StatusCode::SERVICE_UNAVAILABLE, e)),
+                )
+                    .into_response()
+            } else if matches!(e, infra::errors::Error::QuotaExceeded(_)) {
+                MetaHttpResponse::too_many_requests(e)
+            } else {
+                MetaHttpResponse::bad_request(e)
+            }
+        }
+    };
+
+    insert_process_time_header(process_time, resp.headers_mut());
+
+    resp
+}
+
+/// GELF ingestion API
+#[utoipa::path(
+    post,
+    path = "/{org_id}/{stream_name}/_gelf",
+    context_path = "/api",
+    tag = "Logs",
+    operation_id = "LogsIngestionGelf",
+    summary = "Ingest GELF messages",
+    description = "Accepts GELF messages posted by Graylog shippers, one message or several concatenated or \
+                   newline delimited ones, optionally gzip or zlib compressed. `short_message` is stored as \
+                   `message`, the syslog `level` is named in `severity` and the additional `_` fields are \
+                   stored without their prefix.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+    ),
+    request_body(content = String, description = "GELF messages", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({"code": 200,"status": [{"name": "olympics","successful": 3,"failed": 0}]})),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn gelf(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Headers(user_email): Headers<UserEmail>,
+    body: Bytes,
+) -> Response {
+    let user_email = &user_email.user_id;
+    let thread_id = get_thread_id();
+
+    #[cfg(feature = "cloud")]
+    if let Err(e) = check_ingestion_allowed(&org_id, StreamType::Logs, None).await {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(MetaHttpResponse::error(StatusCode::TOO_MANY_REQUESTS, e)),
+        )
+            .into_response();
+    }
+
+    // log start processing time
+    let process_time = get_process_time();
+
+    let mut resp = match logs::gelf::ingest(thread_id, &org_id, &stream_name, body, user_email)
+        .await
+    {
+        Ok(v) => {
+            if v.code > 299 {
+                (StatusCode::BAD_REQUEST, Json(v)).into_response()
+            } else {
+                MetaHttpResponse::json(v)
+            }
+        }
+        Err(e) => {
+            // we do not want to log trial period expired and quota errors
+            if !matches!(
+                e,
+                infra::errors::Error::TrialPeriodExpired | infra::errors::Error::QuotaExceeded(_)
+            ) {
+                log::error!("Error processing request {org_id}/{stream_name}/_gelf: {e}");
+            }
+            if matches!(e, infra::errors::Error::ResourceError(_)) {
+                (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    Json(MetaHttpResponse::error(StatusCode::SERVICE_UNAVAILABLE, e)),
+                )
+                    .into_response()
+            } else if matches!(e, infra::errors::Error::QuotaExceeded(_)) {
+                MetaHttpResponse::too_many_requests(e)
+            } else {
+                MetaHttpResponse::bad_request(e)
+            }
+        }
+    };
+
+    insert_process_time_header(process_time, resp.headers_mut());
+
+    resp
+}
@@ -16,22 +16,43 @@
 use std::io::Read;
 
 use axum::{
+    Json,
     body::Bytes,
-    extract::Path,
+    extract::{Path, Query},
     http::{HeaderMap, StatusCode},
     response::{IntoResponse, Response},
 };
-use config::axum::middlewares::{get_process_time, insert_process_time_header};
+use config::{
+    axum::middlewares::{get_process_time, insert_process_time_header},
+    meta::promql::ApiFuncResponse,
+    utils::time::{now_micros, parse_str_to_timestamp_micros},
+};
 use flate2::read::GzDecoder;
 use prost::Message;
 use proto::loki_rpc;
+#[cfg(feature = "enterprise")]
+use {
+    crate::handler::http::request::search::utils::check_stream_permissions,
+    config::meta::stream::StreamType,
+};
 
 use crate::{
-    common::meta::loki::{LokiError, LokiPushRequest},
-    handler::http::request::{CONTENT_TYPE_JSON, CONTENT_TYPE_PROTO},
+    common::{
+        meta::loki::{LokiError, LokiLabelsRequest, LokiPushRequest, LokiQueryRangeRequest},
+        utils::auth::UserEmail,
+    },
+    handler::http::{
+        extractors::Headers,
+        request::{CONTENT_TYPE_JSON, CONTENT_TYPE_PROTO},
+    },
     service::{ingestion::get_thread_id, logs},
 };
 
+/// Entries returned by query_range when no limit is given, same as Loki
+const DEFAULT_QUERY_LIMIT: i64 = 100;
+/// Time range looked at when no start is given, same as Loki
+const DEFAULT_QUERY_RANGE_MICROS: i64 = 3600 * 1_000_000;
+
 #[utoipa::path(
     post,
     path = "/{org_id}/loki/api/v1/push",
@@ -127,6 +148,274 @@ pub async fn loki_push(Path(org_id): Path<String>, headers: HeaderMap, body: Byt
     resp
 }
 
+/// Loki query_range
+// refer: https://grafana.com/docs/loki/latest/reference/loki-http-api/#query-logs-within-a-range-of-time
+#[utoipa::path(
+    get,
+    path = "/{org_id}/loki/api/v1/query_range",
+    context_path = "/api",
+    tag = "Logs",
+    operation_id = "LogsQueryLoki",
+    summary = "Query logs via Loki API",
+    description = "Runs a LogQL log query so a Grafana Loki datasource can read log streams. The stream selector \
+                   picks the stream like the push API does, from the 'o2_stream_name' label or the default \
+                   stream, the other label matchers and the line filters (|=, !=, |~, !~) filter its records. \
+                   Metric queries and parser stages are not supported.",
+    security(("Authorization"= [])),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("query" = String, Query, description = "LogQL log query, e.g. {o2_stream_name=\"k8s\", app=\"api\"} |= \"error\""),
+        ("start" = Option<String>, Query, description = "<nanosecond unix epoch | rfc3339>: Start timestamp, defaults to an hour before end"),
+        ("end" = Option<String>, Query, description = "<nanosecond unix epoch | rfc3339>: End timestamp, defaults to now"),
+        ("limit" = Option<i64>, Query, description = "Max number of entries to return, defaults to 100"),
+        ("direction" = Option<String>, Query, description = "forward or backward (default)"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({
+            "status": "success",
+            "data": {
+                "resultType": "streams",
+                "result": [{
+                    "stream": {"app": "api", "o2_stream_name": "k8s"},
+                    "values": [["1609459200000000000", "request failed with error"]]
+                }],
+                "stats": {}
+            }
+        })),
+        (status = 400, description = "Bad Request", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Logs", "operation": "get"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn loki_query_range(
+    Path(org_id): Path<String>,
+    Query(req): Query<LokiQueryRangeRequest>,
+    Headers(user_email): Headers<UserEmail>,
+) -> Response {
+    let query = match logs::logql::parse(&req.query) {
+        Ok(v) => v,
+        Err(e) => return bad_data(e),
+    };
+    #[cfg(feature = "enterprise")]
+    if let Some(res) = check_stream_permissions(
+        &query.stream_name(),
+        &org_id,
+        &user_email.user_id,
+        &StreamType::Logs,
+    )
+    .await
+    {
+        return res;
+    }
+    let (start, end) = match parse_time_range(req.start.as_deref(), req.end.as_deref()) {
+        Ok(v) => v,
+        Err(e) => return bad_data(e),
+    };
+    let limit = req.limit.filter(|v| *v > 0).unwrap_or(DEFAULT_QUERY_LIMIT);
+    let forward = match req.direction.as_deref() {
+        None | Some("backward") => false,
+        Some("forward") => true,
+        Some(v) => return bad_data(format!("invalid direction: {v}")),
+    };
+
+    match logs::logql::query_range(
+        &org_id,
+        &user_email.user_id,
+        &query,
+        start,
+        end,
+        limit,
+        forward,
+    )
+    .await
+    {
+        Ok(data) => (StatusCode::OK, Json(ApiFuncResponse::ok(data, None))).into_response(),
+        Err(e) => {
+            log::error!("[Loki] query_range failed for org '{org_id}': {e}");
+            internal_error(e)
+        }
+    }
+}
+
+/// Loki labels
+// refer: https://grafana.com/docs/loki/latest/reference/loki-http-api/#query-labels
+#[utoipa::path(
+    get,
+    path = "/{org_id}/loki/api/v1/labels",
+    context_path = "/api",
+    tag = "Logs",
+    operation_id = "LogsLabelsLoki",
+    summary = "Get label names via Loki API",
+    description = "Returns the label names of the log streams with data in the time range, or of the stream picked \
+                   by the optional stream selector.",
+    security(("Authorization"= [])),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("start" = Option<String>, Query, description = "<nanosecond unix epoch | rfc3339>: Start timestamp"),
+        ("end" = Option<String>, Query, description = "<nanosecond unix epoch | rfc3339>: End timestamp"),
+        ("query" = Option<String>, Query, description = "Stream selector"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({
+            "status": "success",
+            "data": ["app", "level", "o2_stream_name"]
+        })),
+        (status = 400, description = "Bad Request", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Logs", "operation": "get"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn loki_labels(
+    Path(org_id): Path<String>,
+    Query(req): Query<LokiLabelsRequest>,
+    Headers(user_email): Headers<UserEmail>,
+) -> Response {
+    let (query, start, end) = match parse_labels_request(&req) {
+        Ok(v) => v,
+        Err(e) => return bad_data(e),
+    };
+    #[cfg(feature = "enterprise")]
+    if let Some(query) = query.as_ref()
+        && let Some(res) = check_stream_permissions(
+            &query.stream_name(),
+            &org_id,
+            &user_email.user_id,
+            &StreamType::Logs,
+        )
+        .await
+    {
+        return res;
+    }
+    match logs::logql::get_labels(&org_id, &user_email.user_id, query.as_ref(), start, end).await {
+        Ok(labels) => (StatusCode::OK, Json(ApiFuncResponse::ok(labels, None))).into_response(),
+        Err(e) => {
+            log::error!("[Loki] labels failed for org '{org_id}': {e}");
+            internal_error(e)
+        }
+    }
+}
+
+/// Loki label values
+// refer: https://grafana.com/docs/loki/latest/reference/loki-http-api/#query-label-values
+#[utoipa::path(
+    get,
+    path = "/{org_id}/loki/api/v1/label/{label_name}/values",
+    context_path = "/api",
+    tag = "Logs",
+    operation_id = "LogsLabelValuesLoki",
+    summary = "Get label values via Loki API",
+    description = "Returns the values of a label in the stream picked by the optional stream selector, or the \
+                   default stream. The 'o2_stream_name' label lists the log streams with data in the time range.",
+    security(("Authorization"= [])),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("label_name" = String, Path, description = "Label name"),
+        ("start" = Option<String>, Query, description = "<nanosecond unix epoch | rfc3339>: Start timestamp"),
+        ("end" = Option<String>, Query, description = "<nanosecond unix epoch | rfc3339>: End timestamp"),
+        ("query" = Option<String>, Query, description = "Stream selector"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({
+            "status": "success",
+            "data": ["api", "web"]
+        })),
+        (status = 400, description = "Bad Request", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Logs", "operation": "get"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn loki_label_values(
+    Path((org_id, label_name)): Path<(String, String)>,
+    Query(req): Query<LokiLabelsRequest>,
+    Headers(user_email): Headers<UserEmail>,
+) -> Response {
+    let (query, start, end) = match parse_labels_request(&req) {
+        Ok(v) => v,
+        Err(e) => return bad_data(e),
+    };
+    // the values of a label come from the selected stream, or the default one
+    #[cfg(feature = "enterprise")]
+    if label_name != config::STREAM_NAME_LABEL
+        && let Some(res) = check_stream_permissions(
+            &query.clone().unwrap_or_default().stream_name(),
+            &org_id,
+            &user_email.user_id,
+            &StreamType::Logs,
+        )
+        .await
+    {
+        return res;
+    }
+    match logs::logql::get_label_values(
+        &org_id,
+        &user_email.user_id,
+        &label_name,
+        query.as_ref(),
+        start,
+        end,
+    )
+    .await
+    {
+        Ok(values) => (StatusCode::OK, Json(ApiFuncResponse::ok(values, None))).into_response(),
+        Err(e) => {
+            log::error!("[Loki] label values failed for org '{org_id}': {e}");
+            internal_error(e)
+        }
+    }
+}
+
+fn parse_labels_request(
+    req: &LokiLabelsRequest,
+) -> Result<(Option<logs::logql::LogQuery>, i64, i64), String> {
+    let query = match req.query.as_deref().map(str::trim) {
+        Some(v) if !v.is_empty() => Some(logs::logql::parse(v)?),
+        _ => None,
+    };
+    let (start, end) = parse_time_range(req.start.as_deref(), req.end.as_deref())?;
+    Ok((query, start, end))
+}
+
+/// Returns the time range in microseconds, defaulting to the last hour
+fn parse_time_range(start: Option<&str>, end: Option<&str>) -> Result<(i64, i64), String> {
+    let end = match end {
+        Some(v) => parse_str_to_timestamp_micros(v).map_err(|e| format!("invalid end: {e}"))?,
+        None => now_micros(),
+    };
+    let start = match start {
+        Some(v) => parse_str_to_timestamp_micros(v).map_err(|e| format!("invalid start: {e}"))?,
+        None => end - DEFAULT_QUERY_RANGE_MICROS,
+    };
+    if start >= end {
+        return Err("end timestamp must be after the start timestamp".to_string());
+    }
+    Ok((start, end))
+}
+
+fn bad_data(e: impl ToString) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ApiFuncResponse::<()>::err_bad_data(e, None)),
+    )
+        .into_response()
+}
+
+fn internal_error(e: impl ToString) -> Response {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiFuncResponse::<()>::err_internal(e, None)),
+    )
+        .into_response()
+}
+
 fn parse_json_request(
     content_encoding: Option<&str>,
     body: Bytes,
@@ -220,4 +509,37 @@ mod tests {
         let resp = app.oneshot(req).await.unwrap();
         assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
     }
+
+    #[test]
+    fn test_parse_time_range() {
+        let (start, end) =
+            parse_time_range(Some("1609459200000000000"), Some("2021-01-01T01:00:00Z")).unwrap();
+        assert_eq!(start, 1609459200000000);
+        assert_eq!(end, 1609462800000000);
+
+        let (start, end) = parse_time_range(None, Some("1609459200000000000")).unwrap();
+        assert_eq!(end - start, DEFAULT_QUERY_RANGE_MICROS);
+
+        assert!(
+            parse_time_range(Some("1609462800000000000"), Some("1609459200000000000")).is_err()
+        );
+        assert!(parse_time_range(Some("yesterday"), None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_loki_query_range_bad_requests() {
+        let app = Router::new().route(
+            "/{org_id}/loki/api/v1/query_range",
+            axum::routing::get(loki_query_range),
+        );
+
+        for uri in [
+            "/test_org/loki/api/v1/query_range?query=rate(%7Bapp%3D%22api%22%7D%5B5m%5D)",
+            "/test_org/loki/api/v1/query_range?query=%7Bapp%3D%22api%22%7D&direction=up",
+        ] {
+            let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            let resp = app.clone().oneshot(req).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        }
+    }
 }
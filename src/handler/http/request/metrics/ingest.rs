@@ -14,7 +14,7 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use axum::{
-    body::Bytes,
+    body::{Body, Bytes},
     extract::Path,
     http::{HeaderMap, StatusCode},
     response::{IntoResponse, Response},
@@ -127,7 +127,7 @@ pub async fn otlp_metrics_write(
     Path(org_id): Path<String>,
     Headers(user_email): Headers<UserEmail>,
     headers: HeaderMap,
-    body: Bytes,
+    body: Body,
 ) -> Response {
     // log start processing time
     let process_time = get_process_time();
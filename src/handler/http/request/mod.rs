@@ -27,6 +27,8 @@ pub mod authz;
 pub mod cloud;
 pub mod clusters;
 pub mod dashboards;
+pub mod dataset;
+pub mod db_pollers;
 #[cfg(feature = "enterprise")]
 pub mod domain_management;
 pub mod enrichment_table;
@@ -37,6 +39,7 @@ pub mod keys;
 pub mod kv;
 #[cfg(feature = "enterprise")]
 pub mod license;
+pub mod log_metrics;
 pub mod logs;
 pub mod mcp;
 pub mod metrics;
@@ -47,10 +50,15 @@ pub mod pipelines;
 #[cfg(feature = "profiling")]
 pub mod profiling;
 pub mod promql;
+pub mod rabbitmq;
 pub mod ratelimit;
 #[cfg(feature = "enterprise")]
 pub mod re_pattern;
+pub mod record_deletion;
+pub mod recording_rules;
+pub mod replay;
 pub mod rum;
+pub mod scheduled_exports;
 pub mod search;
 pub mod service_accounts;
 pub mod service_streams;
@@ -59,6 +67,8 @@ pub mod status;
 pub mod stream;
 pub mod traces;
 pub mod users;
+pub mod webhooks;
+pub mod zpages;
 
 pub const CONTENT_TYPE_JSON: &str = "application/json";
 pub const CONTENT_TYPE_PROTO: &str = "application/x-protobuf";
@@ -0,0 +1,114 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use axum::{Json, extract::Path, response::Response};
+use config::meta::stream::LevelMapping;
+
+use crate::{
+    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    handler::http::extractors::Headers,
+    service::ingestion::level,
+};
+
+/// GetLevelMapping
+#[utoipa::path(
+    get,
+    path = "/{org_id}/level_mapping",
+    context_path = "/api",
+    tag = "Organizations",
+    operation_id = "GetLevelMapping",
+    summary = "Get level mapping",
+    description = "Gets the mapping of the level values to the canonical levels (trace, debug, info, warn, error, fatal) used by the streams which normalize their levels at ingestion. The built-in mapping is returned until the organization edits it.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(LevelMapping)),
+    ),
+    extensions(
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn get_level_mapping(Path(org_id): Path<String>) -> Response {
+    MetaHttpResponse::json(level::get_mapping(&org_id).as_ref().clone())
+}
+
+/// UpdateLevelMapping
+#[utoipa::path(
+    put,
+    path = "/{org_id}/level_mapping",
+    context_path = "/api",
+    tag = "Organizations",
+    operation_id = "UpdateLevelMapping",
+    summary = "Update level mapping",
+    description = "Replaces the level mapping of the organization, the values are matched case insensitively and must map to canonical levels. Records ingested after the update are normalized with it.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization id"),
+    ),
+    request_body(content = inline(LevelMapping), description = "Level mapping", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(LevelMapping)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn update_level_mapping(
+    Headers(user_email): Headers<UserEmail>,
+    Path(org_id): Path<String>,
+    Json(mapping): Json<LevelMapping>,
+) -> Response {
+    match level::set_mapping(&org_id, mapping, &user_email.user_id).await {
+        Ok(v) => MetaHttpResponse::json(v),
+        Err(e) => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// ResetLevelMapping
+#[utoipa::path(
+    delete,
+    path = "/{org_id}/level_mapping",
+    context_path = "/api",
+    tag = "Organizations",
+    operation_id = "ResetLevelMapping",
+    summary = "Reset level mapping",
+    description = "Removes the level mapping of the organization, the built-in one is used again",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn reset_level_mapping(Path(org_id): Path<String>) -> Response {
+    match level::reset_mapping(&org_id).await {
+        Ok(()) => MetaHttpResponse::ok("level mapping reset"),
+        Err(e) => MetaHttpResponse::internal_error(e),
+    }
+}
@@ -40,15 +40,22 @@ use crate::{
         meta::{
             http::HttpResponse as MetaHttpResponse,
             organization::{
-                ClusterInfo, ClusterInfoResponse, NodeListResponse, OrgDetails, OrgRenameBody,
-                OrgUser, Organization, OrganizationCreationResponse, OrganizationResponse,
-                PasscodeResponse, RumIngestionResponse, THRESHOLD,
+                ClusterInfo, ClusterInfoResponse, NodeListResponse, OrgArchive, OrgDetails,
+                OrgLifecycle, OrgLifecycleRequest, OrgProvisionResponse, OrgRenameBody, OrgUser,
+                Organization, OrganizationCreationResponse, OrganizationResponse, PasscodeResponse,
+                RumIngestionResponse, THRESHOLD,
             },
         },
-        utils::auth::{UserEmail, is_root_user},
+        utils::auth::{UserEmail, is_org_admin, is_root_user},
+    },
+    handler::http::{
+        extractors::Headers,
+        models::organizations::{ImportOrgRequestBody, ProvisionOrgRequestBody},
+    },
+    service::{
+        org_lifecycle,
+        organization::{self, get_passcode, get_rum_token, update_passcode, update_rum_token},
     },
-    handler::http::extractors::Headers,
-    service::organization::{self, get_passcode, get_rum_token, update_passcode, update_rum_token},
 };
 
 /// GetOrganizations
@@ -502,6 +509,123 @@ pub async fn create_org(
     }
 }
 
+/// ProvisionOrganization
+
+#[utoipa::path(
+    post,
+    path = "/organizations/provision",
+    context_path = "/api",
+    tag = "Organizations",
+    operation_id = "ProvisionOrganization",
+    summary = "Create organization from template",
+    description = "Creates a new organization like the create organization endpoint, then provisions it with the resources of the template bundle: streams with their settings, custom roles with their permissions, pipelines, dashboards and alerts, created in that order. The dashboards and alerts are created in the default folder. A resource which can't be created doesn't stop the others, the response counts the created resources and lists the errors. Custom roles require enterprise features.",
+    security(
+        ("Authorization"= [])
+    ),
+    request_body(content = inline(ProvisionOrgRequestBody), description = "Organization data and template bundle", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(OrgProvisionResponse)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Organizations", "operation": "create"})),
+        ("x-o2-mcp" = json!({"description": "Create an organization from a template", "category": "organizations"}))
+    )
+)]
+pub async fn provision_org(
+    Headers(user_email): Headers<UserEmail>,
+    Json(req): Json<ProvisionOrgRequestBody>,
+) -> Response {
+    let mut org = req.organization;
+    match organization::provision_org(&mut org, &user_email.user_id, req.template.into()).await {
+        Ok(resp) => MetaHttpResponse::json(resp),
+        Err(err) => MetaHttpResponse::bad_request(err),
+    }
+}
+
+/// ExportOrganization
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/export",
+    context_path = "/api",
+    tag = "Organizations",
+    operation_id = "ExportOrganization",
+    summary = "Export organization",
+    description = "Exports the organization into a portable archive, to move it to another cluster with the import organization endpoint: the streams with their schemas and settings, functions, pipelines, dashboards and alerts. With `include_data=true` the archive also lists the parquet files of the streams with their metadata, the files themselves are copied to the object storage of the other cluster, with the same keys, before the import. Users and roles aren't exported. Only the admins of the organization can export it.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("include_data" = Option<bool>, Query, description = "Include the manifest of the data files, defaults to false"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = OrgArchive),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Organizations", "operation": "get"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn export_org(
+    Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    if !is_org_admin(&org_id, &user_email.user_id) {
+        return MetaHttpResponse::forbidden("Only the admins of the organization can export it");
+    }
+    let include_data = query
+        .get("include_data")
+        .and_then(|v| v.parse::<bool>().ok())
+        .unwrap_or_default();
+    match organization::export_org(&org_id, include_data).await {
+        Ok(archive) => MetaHttpResponse::json(archive),
+        Err(err) => MetaHttpResponse::bad_request(err),
+    }
+}
+
+/// ImportOrganization
+
+#[utoipa::path(
+    post,
+    path = "/organizations/import",
+    context_path = "/api",
+    tag = "Organizations",
+    operation_id = "ImportOrganization",
+    summary = "Import organization",
+    description = "Creates a new organization like the create organization endpoint, then provisions it with the resources of an archive returned by the export organization endpoint, like a template. The dashboards and alerts are created in the default folder. When the archive lists data files, they are expected in the object storage of this cluster with their exported keys, they are copied under the new organization and registered in its file list. A resource or file which can't be imported doesn't stop the others, the response counts the imported ones and lists the errors. Only root users can import an organization.",
+    security(
+        ("Authorization"= [])
+    ),
+    request_body(content = inline(ImportOrgRequestBody), description = "Organization data and archive", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(OrgProvisionResponse)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Organizations", "operation": "create"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn import_org(
+    Headers(user_email): Headers<UserEmail>,
+    Json(req): Json<ImportOrgRequestBody>,
+) -> Response {
+    if !is_root_user(&user_email.user_id) {
+        return MetaHttpResponse::forbidden("Only root users can import an organization");
+    }
+    let mut org = req.organization;
+    match organization::import_org(&mut org, &user_email.user_id, req.archive).await {
+        Ok(resp) => MetaHttpResponse::json(resp),
+        Err(err) => MetaHttpResponse::bad_request(err),
+    }
+}
+
 #[cfg(feature = "cloud")]
 #[utoipa::path(
     put,
@@ -598,6 +722,71 @@ pub async fn rename_org(
     }
 }
 
+/// GetOrganizationLifecycle
+#[utoipa::path(
+    get,
+    path = "/{org_id}/lifecycle",
+    context_path = "/api",
+    tag = "Organizations",
+    operation_id = "GetOrganizationLifecycle",
+    summary = "Get organization lifecycle state",
+    description = "Gets whether the organization is active, suspended or archived, and when the data of an archived organization is purged",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(OrgLifecycle)),
+    ),
+    extensions(
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn get_lifecycle(Path(org_id): Path<String>) -> Response {
+    MetaHttpResponse::json(org_lifecycle::get(&org_id))
+}
+
+/// UpdateOrganizationLifecycle
+#[utoipa::path(
+    put,
+    path = "/{org_id}/lifecycle",
+    context_path = "/api",
+    tag = "Organizations",
+    operation_id = "UpdateOrganizationLifecycle",
+    summary = "Suspend, archive or reactivate an organization",
+    description = "Moves the organization to another lifecycle state, only root users can. A suspended organization rejects ingestion but can still be searched. An archived organization is read-only and its data is purged after a grace period, `ZO_ORG_ARCHIVE_GRACE_DAYS` unless `grace_days` is given. Moving it back to active before the purge keeps all its data.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization id"),
+    ),
+    request_body(content = inline(OrgLifecycleRequest), description = "Lifecycle state", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(OrgLifecycle)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn update_lifecycle(
+    Headers(user_email): Headers<UserEmail>,
+    Path(org_id): Path<String>,
+    Json(req): Json<OrgLifecycleRequest>,
+) -> Response {
+    if !is_root_user(&user_email.user_id) {
+        return MetaHttpResponse::forbidden("only root users can change the lifecycle state");
+    }
+    match org_lifecycle::set(&org_id, req, &user_email.user_id).await {
+        Ok(v) => MetaHttpResponse::json(v),
+        Err(e) => MetaHttpResponse::bad_request(e),
+    }
+}
+
 /// InviteOrganizationMembers
 #[cfg(feature = "cloud")]
 #[utoipa::path(
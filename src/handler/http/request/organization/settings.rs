@@ -27,13 +27,21 @@ use {
 };
 
 use crate::{
-    common::meta::{
-        http::HttpResponse as MetaHttpResponse,
-        organization::{
-            OrganizationSetting, OrganizationSettingPayload, OrganizationSettingResponse,
+    common::{
+        meta::{
+            http::HttpResponse as MetaHttpResponse,
+            organization::{
+                OrgSmtp, OrganizationSetting, OrganizationSettingPayload,
+                OrganizationSettingResponse,
+            },
         },
+        utils::auth::{UserEmail, is_root_user},
+    },
+    handler::http::extractors::Headers,
+    service::{
+        alerts::{egress, email},
+        db::organization::{get_org_setting, set_org_setting},
     },
-    service::db::organization::{get_org_setting, set_org_setting},
 };
 
 /// Organization specific settings
@@ -47,7 +55,13 @@ use crate::{
     summary = "Update organization settings",
     description = "Creates or updates organization-specific settings such as scrape interval, trace field names, ingestion \
                    toggles, and streaming configurations. Allows administrators to customize organizational behavior and \
-                   operational parameters to match specific requirements and use cases.",
+                   operational parameters to match specific requirements and use cases. `stream_settings_templates` \
+                   sets, per stream type, the settings given to streams that ingestion creates. `ingest_quota` \
+                   sets the records and bytes per second the org can ingest and `search_quota` the searches it can run at \
+                   once and the MB it can scan per minute, only the root user can change them. `search_limits` sets, by \
+                   role, the MB a search can scan, the rows it can return and the searches a user can run at once. \
+                   `smtp` sets the SMTP server the email alert destinations of the org send through, its password is \
+                   never returned and kept when left empty, an empty host removes it.",
     security(
         ("Authorization"= [])
     ),
@@ -66,6 +80,7 @@ use crate::{
 )]
 pub async fn create(
     Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
     Json(settings): Json<OrganizationSettingPayload>,
 ) -> Response {
     let mut data = match get_org_setting(&org_id).await {
@@ -134,6 +149,68 @@ pub async fn create(
         data.max_series_per_query = Some(max_series_per_query);
     }
 
+    if let Some(mut templates) = settings.stream_settings_templates {
+        templates.retain(|_, template| !template.is_empty());
+        for (stream_type, template) in templates.iter() {
+            if let Err(e) = template.validate(*stream_type) {
+                return MetaHttpResponse::bad_request(format!(
+                    "invalid {stream_type} stream settings template: {e}"
+                ));
+            }
+        }
+        field_found = true;
+        data.stream_settings_templates = templates;
+    }
+
+    if let Some(ingest_quota) = settings.ingest_quota {
+        // the quota protects the other tenants, the org can't raise it itself
+        if !is_root_user(&user_email.user_id) {
+            return MetaHttpResponse::forbidden("Only the root user can change ingest_quota");
+        }
+        field_found = true;
+        data.ingest_quota = ingest_quota;
+    }
+
+    if let Some(search_quota) = settings.search_quota {
+        // the quota protects the other tenants, the org can't raise it itself
+        if !is_root_user(&user_email.user_id) {
+            return MetaHttpResponse::forbidden("Only the root user can change search_quota");
+        }
+        field_found = true;
+        data.search_quota = search_quota;
+    }
+
+    if let Some(search_limits) = settings.search_limits {
+        field_found = true;
+        data.search_limits = search_limits;
+    }
+
+    if let Some(mut smtp) = settings.smtp {
+        // the password is never returned, an empty one keeps the current one
+        // unless it would be sent to another server
+        if smtp.password.is_empty()
+            && !smtp.is_empty()
+            && smtp.host == data.smtp.host
+            && smtp.port() == data.smtp.port()
+        {
+            smtp.password = std::mem::take(&mut data.smtp.password);
+        }
+        if let Err(e) = email::validate_smtp(&smtp) {
+            return MetaHttpResponse::bad_request(e);
+        }
+        if !smtp.is_empty()
+            && let Err(e) = egress::check_server(&smtp.host, smtp.port()).await
+        {
+            return MetaHttpResponse::bad_request(e);
+        }
+        field_found = true;
+        data.smtp = if smtp.is_empty() {
+            OrgSmtp::default()
+        } else {
+            smtp
+        };
+    }
+
     #[cfg(feature = "enterprise")]
     if let Some(claim_parser_function) = settings.claim_parser_function {
         field_found = true;
@@ -185,7 +262,10 @@ pub async fn create(
 )]
 pub async fn get(Path(org_id): Path<String>) -> Response {
     match get_org_setting(&org_id).await {
-        Ok(data) => (StatusCode::OK, Json(OrganizationSettingResponse { data })).into_response(),
+        Ok(mut data) => {
+            data.smtp.password.clear();
+            (StatusCode::OK, Json(OrganizationSettingResponse { data })).into_response()
+        }
         Err(err) => {
             if let Error::DbError(DbError::KeyNotExists(_e)) = &err {
                 let setting = OrganizationSetting::default();
@@ -13,18 +13,29 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::HashMap;
+
 use axum::{
-    extract::{FromRequestParts, Path},
+    Json,
+    extract::{FromRequestParts, Path, Query},
+    http::HeaderMap,
     response::{IntoResponse, Response},
 };
+use config::meta::patterns::PatternRequest;
 #[cfg(feature = "enterprise")]
 use o2_enterprise::enterprise::common::config::get_config as get_o2_config;
 
 #[cfg(feature = "enterprise")]
 use crate::handler::http::request::search::utils::check_stream_permissions;
 use crate::{
-    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
-    handler::http::extractors::Headers,
+    common::{
+        meta::http::HttpResponse as MetaHttpResponse,
+        utils::{auth::UserEmail, http::get_or_create_trace_id},
+    },
+    handler::http::{
+        extractors::Headers, request::search::error_utils::map_error_to_http_response,
+    },
+    service::{pattern_stats, search::patterns},
 };
 
 /// Extract patterns from search results
@@ -232,6 +243,7 @@ pub async fn extract_patterns(
                 audit_ctx, // audit_ctx - now populated for auditing
                 false,     // is_multi_stream_search
                 true,      // extract_patterns = TRUE
+                false,     // bypass_max_query_range
             )
             .await;
         });
@@ -313,6 +325,148 @@ pub async fn extract_patterns(
     }
 }
 
+/// Mine log patterns
+///
+/// Clusters the messages of a logs stream in a time range into templates
+/// with counts and examples. Patterns missing from the optional baseline
+/// time range are flagged as new.
+///
+/// POST /api/{org_id}/streams/{stream_name}/patterns/mine
+#[utoipa::path(
+    post,
+    path = "/{org_id}/streams/{stream_name}/patterns/mine",
+    context_path = "/api",
+    tag = "Patterns",
+    operation_id = "MinePatterns",
+    summary = "Mine log patterns",
+    description = "Clusters the messages of a logs stream in a time range into templates with Drain, returning the count, share, examples and first and last timestamps of every template. At most `size` logs are sampled, the counts and shares are those of the sampled logs. The time range is searched in concurrent partitions whose results are merged. When a baseline time range is given, the templates not seen in it are flagged as new.",
+    security(
+        ("Authorization" = [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+    ),
+    request_body(
+        content = inline(PatternRequest),
+        description = "Time range and mining options",
+        content_type = "application/json"
+    ),
+    responses(
+        (status = 200, description = "Success", body = inline(config::meta::patterns::PatternResponse)),
+        (status = 400, description = "Bad Request"),
+        (status = 403, description = "Unauthorized Access"),
+        (status = 500, description = "Internal Server Error"),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Mine log patterns of a stream", "category": "patterns"}))
+    )
+)]
+pub async fn mine_patterns(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    Json(req): Json<PatternRequest>,
+) -> Response {
+    let cfg = config::get_config();
+    let http_span = if cfg.common.tracing_search_enabled || cfg.common.tracing_enabled {
+        tracing::info_span!(
+            "/api/{org_id}/streams/{stream_name}/patterns/mine",
+            org_id = org_id.clone(),
+            stream_name = stream_name.clone()
+        )
+    } else {
+        tracing::Span::none()
+    };
+    let trace_id = get_or_create_trace_id(&headers, &http_span);
+    let user_id = user_email.user_id;
+
+    #[cfg(feature = "enterprise")]
+    if let Some(res) = check_stream_permissions(
+        &stream_name,
+        &org_id,
+        &user_id,
+        &config::meta::stream::StreamType::Logs,
+    )
+    .await
+    {
+        return res;
+    }
+
+    match patterns::mine(&trace_id, &org_id, &stream_name, Some(user_id), &req).await {
+        Ok(res) => MetaHttpResponse::json(res),
+        Err(err) => {
+            log::error!(
+                "[trace_id {trace_id}] pattern mining failed for {org_id}/{stream_name}: {err}"
+            );
+            map_error_to_http_response(&err, Some(trace_id))
+        }
+    }
+}
+
+/// Get log pattern trends
+///
+/// Patterns of a logs stream mined in the background since a time, compared
+/// to the earlier ones.
+///
+/// GET /api/{org_id}/streams/{stream_name}/patterns
+#[utoipa::path(
+    get,
+    path = "/{org_id}/streams/{stream_name}/patterns",
+    context_path = "/api",
+    tag = "Patterns",
+    operation_id = "GetPatternTrends",
+    summary = "Get log pattern trends",
+    description = "Returns the patterns of a logs stream mined in the background since the start time, one hour ago by default. Streams are mined every interval once log patterns are enabled in their settings, the count of every pattern is kept per window for a week. Patterns not seen before the start time are flagged as new, patterns whose share of the logs grew several times over the earlier windows as anomalous. The end time is 0 when the stream wasn't mined yet.",
+    security(
+        ("Authorization" = [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("start_time" = Option<i64>, Query, description = "Start of the recent windows in microseconds, defaults to one hour ago"),
+    ),
+    responses(
+        (status = 200, description = "Success", body = inline(config::meta::patterns::PatternTrendResponse)),
+        (status = 400, description = "Bad Request"),
+        (status = 403, description = "Unauthorized Access"),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Get new and anomalous log patterns of a stream", "category": "patterns"}))
+    )
+)]
+pub async fn get_pattern_trends(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+    #[allow(unused_variables)] Headers(user_email): Headers<UserEmail>,
+) -> Response {
+    let start_time = match query.get("start_time").map(|v| v.parse::<i64>()) {
+        Some(Ok(v)) => v,
+        Some(Err(_)) => {
+            return MetaHttpResponse::bad_request("start_time must be in microseconds");
+        }
+        None => config::utils::time::now_micros() - 3600 * 1_000_000,
+    };
+
+    #[cfg(feature = "enterprise")]
+    if let Some(res) = check_stream_permissions(
+        &stream_name,
+        &org_id,
+        &user_email.user_id,
+        &config::meta::stream::StreamType::Logs,
+    )
+    .await
+    {
+        return res;
+    }
+    let stats = pattern_stats::get(&org_id, &stream_name)
+        .await
+        .unwrap_or_default();
+    MetaHttpResponse::json(pattern_stats::trends(&stats, start_time))
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -85,6 +85,138 @@ pub async fn remote_write(
     }
 }
 
+/// prometheus remote-read endpoint for metrics
+#[utoipa::path(
+    post,
+    path = "/{org_id}/prometheus/api/v1/read",
+    context_path = "/api",
+    tag = "Metrics",
+    operation_id = "PrometheusRemoteRead",
+    summary = "Read Prometheus metrics",
+    description = "Serves stored metrics via the Prometheus remote read protocol. Accepts a snappy compressed protobuf ReadRequest and returns the raw samples of the series selected by every query in a snappy compressed ReadResponse. Only the SAMPLES response type is supported. Compatible with standard Prometheus remote read configuration.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = String, description = "prometheus ReadRequest", content_type = "application/x-protobuf"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/x-protobuf", body = String),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Metrics", "operation": "get"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn remote_read(
+    Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let user_email = &user_email.user_id;
+    let cfg = config::get_config();
+    let http_span = if cfg.common.tracing_search_enabled || cfg.common.tracing_enabled {
+        tracing::info_span!(
+            "/api/{org_id}/prometheus/api/v1/read",
+            org_id = org_id.to_string()
+        )
+    } else {
+        tracing::Span::none()
+    };
+    let trace_id = get_or_create_trace_id(&headers, &http_span);
+
+    let request = match metrics::prom::decode_read_request(&body) {
+        Ok(v) => v,
+        Err(e) => return MetaHttpResponse::bad_request(e),
+    };
+
+    #[cfg(feature = "enterprise")]
+    {
+        if let Err(e) = crate::service::search::check_search_allowed(&org_id, None) {
+            return MetaHttpResponse::too_many_requests(e);
+        }
+        use crate::{
+            common::utils::auth::{AuthExtractor, is_root_user},
+            service::db::org_users::get_cached_user_org,
+        };
+
+        if !is_root_user(user_email) {
+            let stream_type_str = StreamType::Metrics.as_str();
+            for name in request
+                .queries
+                .iter()
+                .flat_map(metrics::prom::read_query_metric_names)
+            {
+                let user: config::meta::user::User =
+                    get_cached_user_org(&org_id, user_email).unwrap();
+                if !crate::handler::http::auth::validator::check_permissions(
+                    user_email,
+                    AuthExtractor {
+                        auth: "".to_string(),
+                        method: "GET".to_string(),
+                        o2_type: format!(
+                            "{}:{}",
+                            OFGA_MODELS
+                                .get(stream_type_str)
+                                .map_or(stream_type_str, |model| model.key),
+                            name
+                        ),
+                        org_id: org_id.to_string(),
+                        bypass_check: false,
+                        parent_id: "".to_string(),
+                    },
+                    user.role,
+                    user.is_external,
+                )
+                .await
+                {
+                    return MetaHttpResponse::forbidden("Unauthorized Access");
+                }
+            }
+        }
+    }
+
+    // check super cluster
+    #[cfg(not(feature = "enterprise"))]
+    let is_super_cluster = false;
+    #[cfg(feature = "enterprise")]
+    let is_super_cluster = o2_enterprise::enterprise::common::config::get_config()
+        .super_cluster
+        .enabled;
+
+    let response = match metrics::prom::remote_read(
+        &trace_id,
+        &org_id,
+        &request,
+        user_email,
+        0,
+        is_super_cluster,
+    )
+    .await
+    {
+        Ok(v) => v,
+        Err(err) => {
+            let err = match err {
+                errors::Error::ErrorCode(code) => code.get_error_detail(),
+                _ => err.to_string(),
+            };
+            return MetaHttpResponse::bad_request(err);
+        }
+    };
+    match metrics::prom::encode_read_response(&response) {
+        Ok(body) => Response::builder()
+            .status(StatusCode::OK)
+            .header("Content-Type", "application/x-protobuf")
+            .header("Content-Encoding", "snappy")
+            .body(Body::from(body))
+            .unwrap(),
+        Err(e) => MetaHttpResponse::internal_error(e),
+    }
+}
+
 /// prometheus instant queries
 
 // refer: https://prometheus.io/docs/prometheus/latest/querying/api/#instant-queries
@@ -1147,6 +1279,109 @@ fn format_query(query: &str) -> Response {
         .into_response()
 }
 
+/// translate between PromQL and SQL
+#[utoipa::path(
+    get,
+    path = "/{org_id}/prometheus/api/v1/translate_query",
+    context_path = "/api",
+    tag = "Metrics",
+    operation_id = "PrometheusTranslateQuery",
+    summary = "Translate between PromQL and SQL",
+    description = "Translates a PromQL expression to the equivalent SQL, or a SQL query to the equivalent PromQL. Vector selectors, the sum, avg, min, max and count aggregations by labels and the *_over_time functions are supported. The warnings tell where the results of the translated query can differ from the original one.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("query" = Option<String>, Query, description = "PromQL expression to translate to SQL"),
+        ("sql" = Option<String>, Query, description = "SQL query to translate to PromQL"),
+        ("step" = Option<String>, Query, description = "Time bucket of the translated aggregations in duration format or float number of seconds, defaults to 1m"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({
+            "status" : "success",
+            "data" : {
+                "sql": "SELECT histogram(_timestamp, '60 second') AS x_axis_1, \"job\", sum(value) AS value FROM \"up\" GROUP BY x_axis_1, \"job\" ORDER BY x_axis_1",
+                "warnings": ["PromQL takes the sum of the last sample of every series at each step, SQL takes the sum of every sample of the bucket"]
+            }
+        })),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Metrics", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Translate between PromQL and SQL", "category": "metrics"}))
+    )
+)]
+pub async fn translate_query_get(
+    Path(_org_id): Path<String>,
+    Query(req): Query<config::meta::promql::RequestTranslateQuery>,
+) -> Response {
+    translate_query(req)
+}
+
+pub async fn translate_query_post(
+    Path(_org_id): Path<String>,
+    Query(req): Query<config::meta::promql::RequestTranslateQuery>,
+    axum::Form(form): axum::Form<config::meta::promql::RequestTranslateQuery>,
+) -> Response {
+    let req = if form.query.is_some() || form.sql.is_some() {
+        form
+    } else {
+        req
+    };
+    translate_query(req)
+}
+
+fn translate_query(req: config::meta::promql::RequestTranslateQuery) -> Response {
+    let step = match req.step.as_deref() {
+        None | Some("") => std::time::Duration::from_secs(60),
+        Some(v) => match parse_milliseconds(v) {
+            Ok(v) => std::time::Duration::from_millis(v),
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    axum::Json(config::meta::promql::ApiFuncResponse::<()>::err_bad_data(
+                        e.to_string(),
+                        None,
+                    )),
+                )
+                    .into_response();
+            }
+        },
+    };
+    let ret = match (req.query, req.sql) {
+        (Some(query), None) => promql::translate::promql_to_sql(&query, step).map(|t| {
+            config::meta::promql::TranslateQueryResult {
+                query: None,
+                sql: Some(t.query),
+                warnings: t.warnings,
+            }
+        }),
+        (None, Some(sql)) => promql::translate::sql_to_promql(&sql).map(|t| {
+            config::meta::promql::TranslateQueryResult {
+                query: Some(t.query),
+                sql: None,
+                warnings: t.warnings,
+            }
+        }),
+        _ => Err("exactly one of query and sql is required".to_string()),
+    };
+    match ret {
+        Ok(result) => (
+            StatusCode::OK,
+            axum::Json(config::meta::promql::ApiFuncResponse::ok(result, None)),
+        )
+            .into_response(),
+        Err(err) => (
+            StatusCode::BAD_REQUEST,
+            axum::Json(config::meta::promql::ApiFuncResponse::<()>::err_bad_data(
+                err, None,
+            )),
+        )
+            .into_response(),
+    }
+}
+
 fn search_timeout(timeout: Option<String>) -> i64 {
     match timeout {
         None => 0,
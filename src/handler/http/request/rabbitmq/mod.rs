@@ -0,0 +1,209 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use axum::{Json, extract::Path, response::Response};
+
+use crate::{
+    common::meta::{
+        http::HttpResponse as MetaHttpResponse,
+        rabbitmq::{RabbitmqSource, RabbitmqSourceList},
+    },
+    service::rabbitmq,
+};
+
+/// ListRabbitmqSources
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/rabbitmq_sources",
+    context_path = "/api",
+    tag = "RabbitMQ",
+    operation_id = "ListRabbitmqSources",
+    summary = "List RabbitMQ sources",
+    description = "Lists the RabbitMQ queues the ingesters consume for the organization. Passwords are never returned.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(RabbitmqSourceList)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "RabbitMQ", "operation": "list"})),
+        ("x-o2-mcp" = json!({"description": "List RabbitMQ sources", "category": "ingestion"}))
+    )
+)]
+pub async fn list(Path(org_id): Path<String>) -> Response {
+    match rabbitmq::list(&org_id).await {
+        Ok(list) => MetaHttpResponse::json(RabbitmqSourceList {
+            list: list.iter().map(|v| v.redacted()).collect(),
+        }),
+        Err(e) => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// GetRabbitmqSource
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/rabbitmq_sources/{name}",
+    context_path = "/api",
+    tag = "RabbitMQ",
+    operation_id = "GetRabbitmqSource",
+    summary = "Get RabbitMQ source",
+    description = "Retrieves a RabbitMQ source without its password.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "RabbitMQ source name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(RabbitmqSource)),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "RabbitMQ", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Get RabbitMQ source details", "category": "ingestion"}))
+    )
+)]
+pub async fn get(Path((org_id, name)): Path<(String, String)>) -> Response {
+    match rabbitmq::get(&org_id, &name).await {
+        Ok(source) => MetaHttpResponse::json(source.redacted()),
+        Err(_) => MetaHttpResponse::not_found("rabbitmq source not found"),
+    }
+}
+
+/// CreateRabbitmqSource
+
+#[utoipa::path(
+    post,
+    path = "/{org_id}/rabbitmq_sources",
+    context_path = "/api",
+    tag = "RabbitMQ",
+    operation_id = "CreateRabbitmqSource",
+    summary = "Create RabbitMQ source",
+    description = "Creates a RabbitMQ source. Every ingester consumes `queue`, optionally bound to `exchange` with \
+                   `routing_key`, and ingests its messages into `stream_name` in batches of up to `prefetch` \
+                   messages. Messages are acknowledged once written, the ones of a failed batch are requeued. \
+                   Ingesters pick up new and changed sources within 30 seconds.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = inline(RabbitmqSource), description = "RabbitMQ source", content_type = "application/json", example = json!({
+        "name": "orders",
+        "uri": "amqps://rabbitmq.example.com:5671/prod",
+        "username": "openobserve",
+        "password": "secret",
+        "queue": "openobserve-orders",
+        "exchange": "logs",
+        "routing_key": "orders.#",
+        "stream_name": "orders",
+        "prefetch": 500
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({"code": 200, "message": "RabbitMQ source saved"})),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "RabbitMQ", "operation": "create"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn create(Path(org_id): Path<String>, Json(source): Json<RabbitmqSource>) -> Response {
+    match rabbitmq::save(&org_id, source, true).await {
+        Ok(_) => MetaHttpResponse::ok("RabbitMQ source saved"),
+        Err(e) => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// UpdateRabbitmqSource
+
+#[utoipa::path(
+    put,
+    path = "/{org_id}/rabbitmq_sources/{name}",
+    context_path = "/api",
+    tag = "RabbitMQ",
+    operation_id = "UpdateRabbitmqSource",
+    summary = "Update RabbitMQ source",
+    description = "Replaces a RabbitMQ source, its consumers are restarted. Leave `password` empty to keep the current \
+                   password.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "RabbitMQ source name"),
+    ),
+    request_body(content = inline(RabbitmqSource), description = "RabbitMQ source", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({"code": 200, "message": "RabbitMQ source saved"})),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "RabbitMQ", "operation": "update"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn update(
+    Path((org_id, name)): Path<(String, String)>,
+    Json(mut source): Json<RabbitmqSource>,
+) -> Response {
+    source.name = name;
+    match rabbitmq::save(&org_id, source, false).await {
+        Ok(_) => MetaHttpResponse::ok("RabbitMQ source saved"),
+        Err(e) => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// DeleteRabbitmqSource
+
+#[utoipa::path(
+    delete,
+    path = "/{org_id}/rabbitmq_sources/{name}",
+    context_path = "/api",
+    tag = "RabbitMQ",
+    operation_id = "DeleteRabbitmqSource",
+    summary = "Delete RabbitMQ source",
+    description = "Deletes a RabbitMQ source, the ingesters stop consuming its queue.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "RabbitMQ source name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({"code": 200, "message": "RabbitMQ source deleted"})),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "RabbitMQ", "operation": "delete"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn delete(Path((org_id, name)): Path<(String, String)>) -> Response {
+    match rabbitmq::delete(&org_id, &name).await {
+        Ok(_) => MetaHttpResponse::ok("RabbitMQ source deleted"),
+        Err(e) => MetaHttpResponse::not_found(e),
+    }
+}
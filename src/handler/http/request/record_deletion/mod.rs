@@ -0,0 +1,201 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use axum::{Json, extract::Path, response::Response};
+use config::meta::record_deletion::{RecordDeletion, RecordDeletionList, RecordDeletionRequest};
+
+use crate::{
+    common::{
+        meta::http::HttpResponse as MetaHttpResponse,
+        utils::auth::{UserEmail, is_org_admin},
+    },
+    handler::http::extractors::Headers,
+    service::record_deletion::{self, RecordDeletionError},
+};
+
+impl From<RecordDeletionError> for Response {
+    fn from(value: RecordDeletionError) -> Self {
+        match &value {
+            RecordDeletionError::InvalidRequest(_) => MetaHttpResponse::bad_request(value),
+            RecordDeletionError::StreamNotFound => MetaHttpResponse::not_found(value),
+            RecordDeletionError::NotFound => MetaHttpResponse::not_found(value),
+            RecordDeletionError::Running => MetaHttpResponse::conflict(value),
+            RecordDeletionError::InfraError(e) => MetaHttpResponse::internal_error(e),
+        }
+    }
+}
+
+/// CreateRecordDeletion
+
+#[utoipa::path(
+    post,
+    path = "/{org_id}/record_deletions",
+    context_path = "/api",
+    tag = "Record Deletions",
+    operation_id = "CreateRecordDeletion",
+    summary = "Delete records by filter",
+    description = "Deletes the records of a stream matching a SQL condition, e.g. the records of a user asking to be \
+                   forgotten. A compactor rewrites the files holding matching records without them, in the \
+                   background, its progress is returned by the get endpoint. Only the records stored when the job \
+                   is created are deleted. Only the admins of the organization can delete records",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = RecordDeletionRequest, description = "Stream, filter and time range", content_type = "application/json", example = json!({
+        "stream_type": "logs",
+        "stream_name": "app",
+        "filter": "user_id = 'u-42'",
+        "start_time": 1767225600000000i64
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = RecordDeletion),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = ()),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Record Deletions", "operation": "create"})),
+        ("x-o2-mcp" = json!({"description": "Delete the records of a stream matching a filter", "category": "streams"}))
+    )
+)]
+pub async fn create_record_deletion(
+    Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
+    Json(req): Json<RecordDeletionRequest>,
+) -> Response {
+    if !is_org_admin(&org_id, &user_email.user_id) {
+        return MetaHttpResponse::forbidden(
+            "Only the admins of the organization can delete records",
+        );
+    }
+    match record_deletion::create(&org_id, req, &user_email.user_id).await {
+        Ok(v) => MetaHttpResponse::json(v),
+        Err(e) => e.into(),
+    }
+}
+
+/// GetRecordDeletion
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/record_deletions/{id}",
+    context_path = "/api",
+    tag = "Record Deletions",
+    operation_id = "GetRecordDeletion",
+    summary = "Get record deletion",
+    description = "Gets the status of the record deletion, the number of files scanned and rewritten and of records \
+                   deleted so far. The files only known from the dumped file list can't be rewritten, they are \
+                   counted as skipped",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Record deletion id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = RecordDeletion),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Record Deletions", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Get the progress of a record deletion", "category": "streams"}))
+    )
+)]
+pub async fn get_record_deletion(Path((org_id, id)): Path<(String, String)>) -> Response {
+    match record_deletion::get(&org_id, &id).await {
+        Ok(v) => MetaHttpResponse::json(v),
+        Err(e) => e.into(),
+    }
+}
+
+/// ListRecordDeletions
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/record_deletions",
+    context_path = "/api",
+    tag = "Record Deletions",
+    operation_id = "ListRecordDeletions",
+    summary = "List record deletions",
+    description = "Lists the record deletions of the organization, the latest first",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = RecordDeletionList),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Record Deletions", "operation": "list"})),
+        ("x-o2-mcp" = json!({"description": "List record deletions", "category": "streams"}))
+    )
+)]
+pub async fn list_record_deletions(Path(org_id): Path<String>) -> Response {
+    match record_deletion::list(&org_id).await {
+        Ok(list) => MetaHttpResponse::json(RecordDeletionList { list }),
+        Err(e) => e.into(),
+    }
+}
+
+/// DeleteRecordDeletion
+
+#[utoipa::path(
+    delete,
+    path = "/{org_id}/record_deletions/{id}",
+    context_path = "/api",
+    tag = "Record Deletions",
+    operation_id = "DeleteRecordDeletion",
+    summary = "Delete record deletion",
+    description = "Deletes a record deletion job, a pending one is cancelled. A running job can't be deleted, the \
+                   records a job deleted are not restored",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Record deletion id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = ()),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = ()),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+        (status = 409, description = "Running", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Record Deletions", "operation": "delete"})),
+        ("x-o2-mcp" = json!({"description": "Delete a record deletion job", "category": "streams"}))
+    )
+)]
+pub async fn delete_record_deletion(
+    Path((org_id, id)): Path<(String, String)>,
+    Headers(user_email): Headers<UserEmail>,
+) -> Response {
+    if !is_org_admin(&org_id, &user_email.user_id) {
+        return MetaHttpResponse::forbidden(
+            "Only the admins of the organization can delete a record deletion",
+        );
+    }
+    match record_deletion::delete(&org_id, &id).await {
+        Ok(_) => MetaHttpResponse::ok("Record deletion deleted"),
+        Err(e) => e.into(),
+    }
+}
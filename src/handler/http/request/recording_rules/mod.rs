@@ -0,0 +1,207 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use axum::{Json, extract::Path, response::Response};
+use config::meta::recording_rules::{RecordingRule, RecordingRuleList};
+
+use crate::{
+    common::meta::http::HttpResponse as MetaHttpResponse,
+    service::recording_rules::{self, RecordingRuleError},
+};
+
+impl From<RecordingRuleError> for Response {
+    fn from(value: RecordingRuleError) -> Self {
+        match &value {
+            RecordingRuleError::InvalidRule(_) => MetaHttpResponse::bad_request(value),
+            RecordingRuleError::RuleNotFound => MetaHttpResponse::not_found(value),
+            RecordingRuleError::InfraError(e) => MetaHttpResponse::internal_error(e),
+            RecordingRuleError::WriteError(_) => MetaHttpResponse::internal_error(value),
+        }
+    }
+}
+
+/// CreateRecordingRule
+
+#[utoipa::path(
+    post,
+    path = "/{org_id}/recording_rules",
+    context_path = "/api",
+    tag = "Recording Rules",
+    operation_id = "CreateRecordingRule",
+    summary = "Create recording rule",
+    description = "Creates a PromQL recording rule. The expression is evaluated every `interval` seconds and its result is written as the metric `name`, with the labels of the rule added to every series, so dashboards can query the precomputed metric instead of the expression.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = inline(RecordingRule), description = "Recording rule details", example = json!({
+        "name": "job:http_requests:rate5m",
+        "expr": "sum by (job) (rate(http_requests_total[5m]))",
+        "interval": 60,
+        "labels": {"team": "platform"}
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(RecordingRule)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Recording Rules", "operation": "create"})),
+        ("x-o2-mcp" = json!({"description": "Create a PromQL recording rule", "category": "metrics"}))
+    )
+)]
+pub async fn create_rule(Path(org_id): Path<String>, Json(rule): Json<RecordingRule>) -> Response {
+    match recording_rules::create(&org_id, rule).await {
+        Ok(rule) => MetaHttpResponse::json(rule),
+        Err(e) => e.into(),
+    }
+}
+
+/// UpdateRecordingRule
+
+#[utoipa::path(
+    put,
+    path = "/{org_id}/recording_rules/{id}",
+    context_path = "/api",
+    tag = "Recording Rules",
+    operation_id = "UpdateRecordingRule",
+    summary = "Update recording rule",
+    description = "Updates a recording rule. Changing the interval reschedules the next evaluation.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Recording rule id"),
+    ),
+    request_body(content = inline(RecordingRule), description = "Recording rule details"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(RecordingRule)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Recording Rules", "operation": "update"})),
+        ("x-o2-mcp" = json!({"description": "Update a PromQL recording rule", "category": "metrics"}))
+    )
+)]
+pub async fn update_rule(
+    Path((org_id, id)): Path<(String, String)>,
+    Json(rule): Json<RecordingRule>,
+) -> Response {
+    match recording_rules::update(&org_id, &id, rule).await {
+        Ok(rule) => MetaHttpResponse::json(rule),
+        Err(e) => e.into(),
+    }
+}
+
+/// GetRecordingRule
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/recording_rules/{id}",
+    context_path = "/api",
+    tag = "Recording Rules",
+    operation_id = "GetRecordingRule",
+    summary = "Get recording rule",
+    description = "Retrieves a recording rule and the outcome of its last evaluation.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Recording rule id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(RecordingRule)),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Recording Rules", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Get recording rule details", "category": "metrics"}))
+    )
+)]
+pub async fn get_rule(Path((org_id, id)): Path<(String, String)>) -> Response {
+    match recording_rules::get(&org_id, &id).await {
+        Ok(rule) => MetaHttpResponse::json(rule),
+        Err(e) => e.into(),
+    }
+}
+
+/// ListRecordingRules
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/recording_rules",
+    context_path = "/api",
+    tag = "Recording Rules",
+    operation_id = "ListRecordingRules",
+    summary = "List recording rules",
+    description = "Lists the recording rules of the organization.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(RecordingRuleList)),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Recording Rules", "operation": "list"})),
+        ("x-o2-mcp" = json!({"description": "List PromQL recording rules", "category": "metrics"}))
+    )
+)]
+pub async fn list_rules(Path(org_id): Path<String>) -> Response {
+    match recording_rules::list(&org_id).await {
+        Ok(list) => MetaHttpResponse::json(RecordingRuleList { list }),
+        Err(e) => e.into(),
+    }
+}
+
+/// DeleteRecordingRule
+
+#[utoipa::path(
+    delete,
+    path = "/{org_id}/recording_rules/{id}",
+    context_path = "/api",
+    tag = "Recording Rules",
+    operation_id = "DeleteRecordingRule",
+    summary = "Delete recording rule",
+    description = "Deletes a recording rule and stops its evaluations. The series already recorded are kept.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Recording rule id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = ()),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Recording Rules", "operation": "delete"})),
+        ("x-o2-mcp" = json!({"description": "Delete a PromQL recording rule", "category": "metrics"}))
+    )
+)]
+pub async fn delete_rule(Path((org_id, id)): Path<(String, String)>) -> Response {
+    match recording_rules::delete(&org_id, &id).await {
+        Ok(_) => MetaHttpResponse::ok("Recording rule deleted"),
+        Err(e) => e.into(),
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use axum::{Json, extract::Path, response::Response};
+use config::meta::replay::{Replay, ReplayList, ReplayRequest};
+
+use crate::{
+    common::{
+        meta::http::HttpResponse as MetaHttpResponse,
+        utils::auth::{UserEmail, is_org_admin},
+    },
+    handler::http::extractors::Headers,
+    service::replay::{self, ReplayError},
+};
+
+impl From<ReplayError> for Response {
+    fn from(value: ReplayError) -> Self {
+        match &value {
+            ReplayError::InvalidReplay(_) => MetaHttpResponse::bad_request(value),
+            ReplayError::StreamNotFound => MetaHttpResponse::not_found(value),
+            ReplayError::PipelineNotFound => MetaHttpResponse::not_found(value),
+            ReplayError::ReplayNotFound => MetaHttpResponse::not_found(value),
+            ReplayError::InfraError(e) => MetaHttpResponse::internal_error(e),
+        }
+    }
+}
+
+/// CreateReplay
+
+#[utoipa::path(
+    post,
+    path = "/{org_id}/replays",
+    context_path = "/api",
+    tag = "Replays",
+    operation_id = "CreateReplay",
+    summary = "Replay a stream",
+    description = "Reads back the records a stream stored over a past time range and writes them to another stream, \
+                   through a pipeline when one is given, e.g. to reprocess the last week with a fixed parser. With \
+                   `use_original` the records are replayed as they were received, from the `_original` column. The \
+                   replay runs in the background, its progress is returned by the get endpoint. Only the admins of \
+                   the organization can replay a stream",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = ReplayRequest, description = "Stream, time range and destination", content_type = "application/json", example = json!({
+        "stream_type": "logs",
+        "stream_name": "nginx",
+        "start_time": 1767225600000000i64,
+        "end_time": 1767830400000000i64,
+        "destination_stream": "nginx_reparsed",
+        "pipeline_id": "2qyz8eiNVbBaSvnWqBbG7Fwuh0q",
+        "use_original": true
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Replay),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = ()),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Replays", "operation": "create"})),
+        ("x-o2-mcp" = json!({"description": "Replay the stored records of a stream into another stream", "category": "streams"}))
+    )
+)]
+pub async fn create_replay(
+    Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
+    Json(req): Json<ReplayRequest>,
+) -> Response {
+    if !is_org_admin(&org_id, &user_email.user_id) {
+        return MetaHttpResponse::forbidden(
+            "Only the admins of the organization can replay a stream",
+        );
+    }
+    match replay::create(&org_id, req, &user_email.user_id).await {
+        Ok(v) => MetaHttpResponse::json(v),
+        Err(e) => e.into(),
+    }
+}
+
+/// GetReplay
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/replays/{id}",
+    context_path = "/api",
+    tag = "Replays",
+    operation_id = "GetReplay",
+    summary = "Get replay",
+    description = "Gets the status of the replay and the number of records read and written so far",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Replay id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Replay),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Replays", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Get the progress of a replay", "category": "streams"}))
+    )
+)]
+pub async fn get_replay(Path((org_id, id)): Path<(String, String)>) -> Response {
+    match replay::get(&org_id, &id).await {
+        Ok(v) => MetaHttpResponse::json(v),
+        Err(e) => e.into(),
+    }
+}
+
+/// ListReplays
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/replays",
+    context_path = "/api",
+    tag = "Replays",
+    operation_id = "ListReplays",
+    summary = "List replays",
+    description = "Lists the replays of the organization, the latest first",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = ReplayList),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Replays", "operation": "list"})),
+        ("x-o2-mcp" = json!({"description": "List replays", "category": "streams"}))
+    )
+)]
+pub async fn list_replays(Path(org_id): Path<String>) -> Response {
+    match replay::list(&org_id).await {
+        Ok(list) => MetaHttpResponse::json(ReplayList { list }),
+        Err(e) => e.into(),
+    }
+}
+
+/// DeleteReplay
+
+#[utoipa::path(
+    delete,
+    path = "/{org_id}/replays/{id}",
+    context_path = "/api",
+    tag = "Replays",
+    operation_id = "DeleteReplay",
+    summary = "Delete replay",
+    description = "Deletes a replay and stops it if it is running. The records already written are kept",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Replay id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = ()),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = ()),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Replays", "operation": "delete"})),
+        ("x-o2-mcp" = json!({"description": "Delete a replay", "category": "streams"}))
+    )
+)]
+pub async fn delete_replay(
+    Path((org_id, id)): Path<(String, String)>,
+    Headers(user_email): Headers<UserEmail>,
+) -> Response {
+    if !is_org_admin(&org_id, &user_email.user_id) {
+        return MetaHttpResponse::forbidden(
+            "Only the admins of the organization can delete a replay",
+        );
+    }
+    match replay::delete(&org_id, &id).await {
+        Ok(_) => MetaHttpResponse::ok("Replay deleted"),
+        Err(e) => e.into(),
+    }
+}
@@ -0,0 +1,326 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use axum::{Json, extract::Path, response::Response};
+use config::meta::exports::{ExportDestination, ScheduledExport, ScheduledExportList};
+
+use crate::{
+    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    handler::http::extractors::Headers,
+    service::scheduled_exports::{self, ExportError},
+};
+
+impl From<ExportError> for Response {
+    fn from(value: ExportError) -> Self {
+        match &value {
+            ExportError::InvalidExport(_) => MetaHttpResponse::bad_request(value),
+            ExportError::ExportNotFound => MetaHttpResponse::not_found(value),
+            ExportError::DestinationNotFound => MetaHttpResponse::not_found(value),
+            ExportError::PermissionDenied(_) => MetaHttpResponse::forbidden(value),
+            ExportError::ObjectStoreError(e) => MetaHttpResponse::bad_request(e),
+            ExportError::InfraError(e) => MetaHttpResponse::internal_error(e),
+            ExportError::EncodeError(_) => MetaHttpResponse::internal_error(value),
+        }
+    }
+}
+
+/// CreateScheduledExport
+
+#[utoipa::path(
+    post,
+    path = "/{org_id}/scheduled_exports",
+    context_path = "/api",
+    tag = "Scheduled Exports",
+    operation_id = "CreateScheduledExport",
+    summary = "Create scheduled export",
+    description = "Creates a search that runs on a schedule and writes its results as CSV or Parquet to the object store configured for the organization. Each run exports the `period` minutes ending at the run time, every `frequency` minutes. The export runs as the user who saved it, who must be allowed to read every stream of the query.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = inline(ScheduledExport), description = "Scheduled export details", example = json!({
+        "name": "hourly_errors",
+        "stream_type": "logs",
+        "sql": "SELECT _timestamp, service, message FROM \"default\" WHERE level = 'error'",
+        "frequency": 60,
+        "format": "parquet",
+        "path": "errors/hourly"
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(ScheduledExport)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = ()),
+        (status = 404, description = "Export destination not configured", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Scheduled Exports", "operation": "create"})),
+        ("x-o2-mcp" = json!({"description": "Create a scheduled export", "category": "exports"}))
+    )
+)]
+pub async fn create_export(
+    Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
+    Json(export): Json<ScheduledExport>,
+) -> Response {
+    match scheduled_exports::create(&org_id, &user_email.user_id, export).await {
+        Ok(export) => MetaHttpResponse::json(export),
+        Err(e) => e.into(),
+    }
+}
+
+/// UpdateScheduledExport
+
+#[utoipa::path(
+    put,
+    path = "/{org_id}/scheduled_exports/{id}",
+    context_path = "/api",
+    tag = "Scheduled Exports",
+    operation_id = "UpdateScheduledExport",
+    summary = "Update scheduled export",
+    description = "Updates a scheduled export. Changing the frequency reschedules the next run. The export then runs as the user who updated it.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Scheduled export id"),
+    ),
+    request_body(content = inline(ScheduledExport), description = "Scheduled export details"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(ScheduledExport)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = ()),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Scheduled Exports", "operation": "update"})),
+        ("x-o2-mcp" = json!({"description": "Update a scheduled export", "category": "exports"}))
+    )
+)]
+pub async fn update_export(
+    Path((org_id, id)): Path<(String, String)>,
+    Headers(user_email): Headers<UserEmail>,
+    Json(export): Json<ScheduledExport>,
+) -> Response {
+    match scheduled_exports::update(&org_id, &user_email.user_id, &id, export).await {
+        Ok(export) => MetaHttpResponse::json(export),
+        Err(e) => e.into(),
+    }
+}
+
+/// GetScheduledExport
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/scheduled_exports/{id}",
+    context_path = "/api",
+    tag = "Scheduled Exports",
+    operation_id = "GetScheduledExport",
+    summary = "Get scheduled export",
+    description = "Retrieves a scheduled export and the outcome of its last run.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Scheduled export id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(ScheduledExport)),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Scheduled Exports", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Get scheduled export details", "category": "exports"}))
+    )
+)]
+pub async fn get_export(Path((org_id, id)): Path<(String, String)>) -> Response {
+    match scheduled_exports::get(&org_id, &id).await {
+        Ok(export) => MetaHttpResponse::json(export),
+        Err(e) => e.into(),
+    }
+}
+
+/// ListScheduledExports
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/scheduled_exports",
+    context_path = "/api",
+    tag = "Scheduled Exports",
+    operation_id = "ListScheduledExports",
+    summary = "List scheduled exports",
+    description = "Lists the scheduled exports of the organization.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(ScheduledExportList)),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Scheduled Exports", "operation": "list"})),
+        ("x-o2-mcp" = json!({"description": "List scheduled exports", "category": "exports"}))
+    )
+)]
+pub async fn list_exports(Path(org_id): Path<String>) -> Response {
+    match scheduled_exports::list(&org_id).await {
+        Ok(list) => MetaHttpResponse::json(ScheduledExportList { list }),
+        Err(e) => e.into(),
+    }
+}
+
+/// DeleteScheduledExport
+
+#[utoipa::path(
+    delete,
+    path = "/{org_id}/scheduled_exports/{id}",
+    context_path = "/api",
+    tag = "Scheduled Exports",
+    operation_id = "DeleteScheduledExport",
+    summary = "Delete scheduled export",
+    description = "Deletes a scheduled export and stops its runs. Objects already exported are kept.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Scheduled export id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = ()),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Scheduled Exports", "operation": "delete"})),
+        ("x-o2-mcp" = json!({"description": "Delete a scheduled export", "category": "exports"}))
+    )
+)]
+pub async fn delete_export(Path((org_id, id)): Path<(String, String)>) -> Response {
+    match scheduled_exports::delete(&org_id, &id).await {
+        Ok(_) => MetaHttpResponse::ok("Scheduled export deleted"),
+        Err(e) => e.into(),
+    }
+}
+
+/// GetExportDestination
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/export_destination",
+    context_path = "/api",
+    tag = "Scheduled Exports",
+    operation_id = "GetExportDestination",
+    summary = "Get export destination",
+    description = "Retrieves the object store the scheduled exports of the organization are written to. Credentials are masked.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(ExportDestination)),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Scheduled Exports", "operation": "get"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn get_destination(Path(org_id): Path<String>) -> Response {
+    match scheduled_exports::get_destination(&org_id).await {
+        Ok(destination) => MetaHttpResponse::json(destination),
+        Err(e) => e.into(),
+    }
+}
+
+/// SetExportDestination
+
+#[utoipa::path(
+    put,
+    path = "/{org_id}/export_destination",
+    context_path = "/api",
+    tag = "Scheduled Exports",
+    operation_id = "SetExportDestination",
+    summary = "Set export destination",
+    description = "Sets the S3 or GCS bucket the scheduled exports of the organization are written to. The credentials are required: an access and secret key for S3, a service account key for GCS. Masked credentials sent back unchanged keep their stored value.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = inline(ExportDestination), description = "Export destination", example = json!({
+        "provider": "s3",
+        "bucket": "analytics",
+        "prefix": "openobserve",
+        "region": "us-east-1"
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = ()),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Scheduled Exports", "operation": "update"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn set_destination(
+    Path(org_id): Path<String>,
+    Json(destination): Json<ExportDestination>,
+) -> Response {
+    match scheduled_exports::set_destination(&org_id, destination).await {
+        Ok(_) => MetaHttpResponse::ok("Export destination saved"),
+        Err(e) => e.into(),
+    }
+}
+
+/// DeleteExportDestination
+
+#[utoipa::path(
+    delete,
+    path = "/{org_id}/export_destination",
+    context_path = "/api",
+    tag = "Scheduled Exports",
+    operation_id = "DeleteExportDestination",
+    summary = "Delete export destination",
+    description = "Removes the export destination of the organization. Scheduled exports fail until a destination is set again.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Scheduled Exports", "operation": "delete"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn delete_destination(Path(org_id): Path<String>) -> Response {
+    match scheduled_exports::delete_destination(&org_id).await {
+        Ok(_) => MetaHttpResponse::ok("Export destination deleted"),
+        Err(e) => e.into(),
+    }
+}
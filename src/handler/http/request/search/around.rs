@@ -279,6 +279,11 @@ pub(crate) async fn around(
                 .unwrap_or(0.0)
                 .max(resp_backward.peak_memory_usage.unwrap_or(0.0)),
         ),
+        took_detail: Some({
+            let mut took = resp_forward.took_detail.clone();
+            took.add(&resp_backward.took_detail);
+            took
+        }),
         ..Default::default()
     };
     let num_fn = req.query.query_fn.is_some() as u16;
@@ -0,0 +1,254 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use axum::{
+    Json,
+    extract::{Path, Query},
+    response::Response,
+};
+use config::meta::{
+    async_search::{
+        AsyncSearchJob, AsyncSearchResultResponse, AsyncSearchStatusResponse,
+        AsyncSearchSubmitResponse,
+    },
+    search::Request,
+    sql::resolve_stream_names,
+};
+use hashbrown::HashMap;
+
+#[cfg(feature = "enterprise")]
+use crate::handler::http::request::search::utils::check_stream_permissions;
+use crate::{
+    common::{
+        meta::http::HttpResponse as MetaHttpResponse,
+        utils::{auth::UserEmail, http::get_stream_type_from_request},
+    },
+    handler::http::extractors::Headers,
+    service::async_search::{self, AsyncSearchError},
+};
+
+impl From<AsyncSearchError> for Response {
+    fn from(value: AsyncSearchError) -> Self {
+        match &value {
+            AsyncSearchError::InvalidRequest(_) => MetaHttpResponse::bad_request(value),
+            AsyncSearchError::JobNotFound => MetaHttpResponse::not_found(value),
+            AsyncSearchError::InfraError(e) => MetaHttpResponse::internal_error(e),
+        }
+    }
+}
+
+/// Jobs are only visible to the user who submitted them
+async fn get_own_job(org_id: &str, id: &str, user_id: &str) -> Result<AsyncSearchJob, Response> {
+    match async_search::get(org_id, id).await {
+        Ok(job) if job.user_id == user_id => Ok(job),
+        Ok(_) => Err(AsyncSearchError::JobNotFound.into()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// SubmitAsyncSearch
+
+#[utoipa::path(
+    post,
+    path = "/{org_id}/_search_async",
+    context_path = "/api",
+    tag = "Search",
+    operation_id = "SubmitAsyncSearch",
+    summary = "Submit async search",
+    description = "Submits a search to run in the background and returns its job id. The search runs one time range partition at a time, and the results of every partition are kept, so they can be read while the search runs and the search resumes on another node if its node stops. The job and its results are deleted once ZO_ASYNC_SEARCH_RESULT_TTL hours have passed after it ended.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("type" = Option<String>, Query, description = "Stream type, logs by default"),
+    ),
+    request_body(content = inline(Request), description = "Search query", content_type = "application/json", example = json!({
+        "query": {
+            "sql": "SELECT * FROM \"default\" WHERE level = 'error'",
+            "start_time": 1675182660872049i64,
+            "end_time": 1675785660872049i64,
+            "size": 10000
+        }
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(AsyncSearchSubmitResponse)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Submit a long search to run in the background", "category": "search"}))
+    )
+)]
+pub async fn submit_async_search(
+    Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
+    Query(url_query): Query<HashMap<String, String>>,
+    Json(mut req): Json<Request>,
+) -> Response {
+    let user_id = &user_email.user_id;
+    let stream_type = get_stream_type_from_request(&url_query).unwrap_or_default();
+
+    if let Ok(sql) = config::utils::query_select_utils::replace_o2_custom_patterns(&req.query.sql) {
+        req.query.sql = sql;
+    };
+    let stream_names = match resolve_stream_names(&req.query.sql) {
+        Ok(v) => v,
+        Err(e) => return MetaHttpResponse::bad_request(e),
+    };
+
+    #[cfg(feature = "enterprise")]
+    for stream_name in stream_names.iter() {
+        if let Err(e) = crate::service::search::check_search_allowed(&org_id, Some(stream_name)) {
+            return MetaHttpResponse::too_many_requests(e);
+        }
+        if let Some(res) =
+            check_stream_permissions(stream_name, &org_id, user_id, &stream_type).await
+        {
+            return res;
+        }
+    }
+    #[cfg(not(feature = "enterprise"))]
+    drop(stream_names);
+
+    match async_search::submit(&org_id, user_id, stream_type, req).await {
+        Ok(job) => MetaHttpResponse::json(AsyncSearchSubmitResponse {
+            id: job.id,
+            status: job.status,
+        }),
+        Err(e) => e.into(),
+    }
+}
+
+/// GetAsyncSearch
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/_search_async/{id}",
+    context_path = "/api",
+    tag = "Search",
+    operation_id = "GetAsyncSearch",
+    summary = "Get async search status",
+    description = "Retrieves the status and progress of an async search.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Async search id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(AsyncSearchStatusResponse)),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Get the status of an async search", "category": "search"}))
+    )
+)]
+pub async fn get_async_search(
+    Path((org_id, id)): Path<(String, String)>,
+    Headers(user_email): Headers<UserEmail>,
+) -> Response {
+    match get_own_job(&org_id, &id, &user_email.user_id).await {
+        Ok(job) => MetaHttpResponse::json(AsyncSearchStatusResponse::from(&job)),
+        Err(res) => res,
+    }
+}
+
+/// GetAsyncSearchResult
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/_search_async/{id}/result",
+    context_path = "/api",
+    tag = "Search",
+    operation_id = "GetAsyncSearchResult",
+    summary = "Get async search results",
+    description = "Retrieves the rows an async search has found so far. The response is partial until the search has finished. from and size default to the ones of the submitted query.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Async search id"),
+        ("from" = Option<i64>, Query, description = "Offset of the first row"),
+        ("size" = Option<i64>, Query, description = "Number of rows"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(AsyncSearchResultResponse)),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Get the results of an async search", "category": "search"}))
+    )
+)]
+pub async fn get_async_search_result(
+    Path((org_id, id)): Path<(String, String)>,
+    Headers(user_email): Headers<UserEmail>,
+    Query(url_query): Query<HashMap<String, String>>,
+) -> Response {
+    if let Err(res) = get_own_job(&org_id, &id, &user_email.user_id).await {
+        return res;
+    }
+    let from = url_query.get("from").and_then(|v| v.parse::<i64>().ok());
+    let size = url_query.get("size").and_then(|v| v.parse::<i64>().ok());
+    match async_search::get_result(&org_id, &id, from, size).await {
+        Ok(res) => MetaHttpResponse::json(res),
+        Err(e) => e.into(),
+    }
+}
+
+/// DeleteAsyncSearch
+
+#[utoipa::path(
+    delete,
+    path = "/{org_id}/_search_async/{id}",
+    context_path = "/api",
+    tag = "Search",
+    operation_id = "DeleteAsyncSearch",
+    summary = "Delete async search",
+    description = "Stops an async search, if it is still running, and deletes it with its results.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Async search id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = ()),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "delete"})),
+        ("x-o2-mcp" = json!({"description": "Cancel and delete an async search", "category": "search"}))
+    )
+)]
+pub async fn delete_async_search(
+    Path((org_id, id)): Path<(String, String)>,
+    Headers(user_email): Headers<UserEmail>,
+) -> Response {
+    if let Err(res) = get_own_job(&org_id, &id, &user_email.user_id).await {
+        return res;
+    }
+    match async_search::delete(&org_id, &id).await {
+        Ok(_) => MetaHttpResponse::ok("Async search deleted"),
+        Err(e) => e.into(),
+    }
+}
@@ -0,0 +1,175 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use axum::{
+    Json,
+    extract::{Path, Query},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+};
+use config::{
+    META_ORG_ID, get_config,
+    meta::{search::CrossOrgRequest, sql::resolve_stream_names},
+};
+use hashbrown::{HashMap, HashSet};
+use tracing::{Instrument, Span};
+
+#[cfg(feature = "enterprise")]
+use crate::handler::http::request::search::utils::check_stream_permissions;
+use crate::{
+    common::{
+        meta::http::HttpResponse as MetaHttpResponse,
+        utils::{
+            auth::{UserEmail, is_root_user},
+            http::{get_or_create_trace_id, get_stream_type_from_request},
+        },
+    },
+    handler::http::{
+        extractors::Headers, request::search::error_utils::map_error_to_http_response,
+    },
+};
+
+/// SearchCrossOrg
+#[utoipa::path(
+    post,
+    path = "/{org_id}/_search_cross_org",
+    context_path = "/api",
+    tag = "Search",
+    operation_id = "SearchCrossOrg",
+    summary = "Search several organizations",
+    description = "Runs a search query in each of the given organizations and merges the results, the org of each hit \
+                   is in `_org_id`. Only available in the meta organization, for users who can read the queried \
+                   streams in every one of the organizations. The response is partial when the search failed in \
+                   some of them.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Meta organization name"),
+        ("type" = Option<String>, Query, description = "Stream type, default is logs"),
+    ),
+    request_body(content = inline(CrossOrgRequest), description = "Organizations and search query", content_type = "application/json", example = json!({
+        "org_ids": ["tenant1", "tenant2"],
+        "query": {
+            "sql": "select * from k8s where log like '%error%'",
+            "start_time": 1675182660872049i64,
+            "end_time": 1675185660872049i64,
+            "from": 0,
+            "size": 10
+        }
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({
+            "took": 155,
+            "hits": [
+                {
+                    "_org_id": "tenant1",
+                    "_timestamp": 1674213225158000i64,
+                    "log": "[2023-01-20T11:13:45Z ERROR] connection refused",
+                }
+            ],
+            "total": 27,
+            "from": 0,
+            "size": 10,
+            "scan_size": 28943
+        })),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"}))
+    )
+)]
+pub async fn search_cross_org(
+    Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    Query(url_query): Query<HashMap<String, String>>,
+    Json(mut req): Json<CrossOrgRequest>,
+) -> Response {
+    if org_id != META_ORG_ID {
+        return MetaHttpResponse::forbidden(format!(
+            "Cross org search is only available in the meta organization {META_ORG_ID}"
+        ));
+    }
+    let cfg = get_config();
+    let http_span = if cfg.common.tracing_search_enabled || cfg.common.tracing_enabled {
+        tracing::info_span!("/api/{org_id}/_search_cross_org", org_id = org_id.clone())
+    } else {
+        Span::none()
+    };
+    let trace_id = get_or_create_trace_id(&headers, &http_span);
+    let user_id = user_email.user_id;
+    let stream_type = get_stream_type_from_request(&url_query).unwrap_or_default();
+
+    let mut seen = HashSet::new();
+    req.org_ids
+        .retain(|id| !id.is_empty() && seen.insert(id.clone()));
+    if req.org_ids.is_empty() {
+        return MetaHttpResponse::bad_request("org_ids can't be empty");
+    }
+    if let Err(e) = req.request.decode() {
+        return MetaHttpResponse::bad_request(e);
+    }
+    if let Ok(sql) =
+        config::utils::query_select_utils::replace_o2_custom_patterns(&req.request.query.sql)
+    {
+        req.request.query.sql = sql;
+    };
+    #[allow(unused_variables)]
+    let stream_names = match resolve_stream_names(&req.request.query.sql) {
+        Ok(v) => v,
+        Err(e) => {
+            return map_error_to_http_response(&(e.into()), Some(trace_id));
+        }
+    };
+
+    // the user must be allowed to search in every org
+    if !is_root_user(&user_id) {
+        for id in req.org_ids.iter() {
+            if crate::service::users::get_user(Some(id.as_str()), &user_id)
+                .await
+                .is_none()
+            {
+                return MetaHttpResponse::forbidden(format!(
+                    "Unauthorized Access to organization {id}"
+                ));
+            }
+            #[cfg(feature = "enterprise")]
+            for stream_name in stream_names.iter() {
+                if let Some(res) =
+                    check_stream_permissions(stream_name, id, &user_id, &stream_type).await
+                {
+                    return res;
+                }
+            }
+        }
+    }
+
+    let res = crate::service::search::cross_org::search(
+        &trace_id,
+        &req.org_ids,
+        stream_type,
+        Some(user_id),
+        &req.request,
+    )
+    .instrument(http_span)
+    .await;
+    match res {
+        Ok(res) => Json(res).into_response(),
+        Err(e) => map_error_to_http_response(&e, Some(trace_id)),
+    }
+}
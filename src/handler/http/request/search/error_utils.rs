@@ -27,14 +27,27 @@ use crate::{
 pub fn map_error_to_http_response(err: &errors::Error, trace_id: Option<String>) -> Response {
     match err {
         errors::Error::ErrorCode(code) => match code {
-            errors::ErrorCodes::SearchCancelQuery(_) | errors::ErrorCodes::RatelimitExceeded(_) => {
-                (
-                    StatusCode::TOO_MANY_REQUESTS,
-                    [(ERROR_HEADER, code.to_json())],
-                    Json(MetaHttpResponse::error_code_with_trace_id(code, trace_id)),
-                )
-                    .into_response()
-            }
+            errors::ErrorCodes::SearchCancelQuery(_)
+            | errors::ErrorCodes::RatelimitExceeded(_)
+            | errors::ErrorCodes::SearchQuotaExceeded(_) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(ERROR_HEADER, code.to_json())],
+                Json(MetaHttpResponse::error_code_with_trace_id(code, trace_id)),
+            )
+                .into_response(),
+            errors::ErrorCodes::SearchLimitExceeded(_)
+            | errors::ErrorCodes::SearchFieldAccessDenied(_) => (
+                StatusCode::FORBIDDEN,
+                [(ERROR_HEADER, code.to_json())],
+                Json(MetaHttpResponse::error_code_with_trace_id(code, trace_id)),
+            )
+                .into_response(),
+            errors::ErrorCodes::SearchColdStorageRestoreRequired(_) => (
+                StatusCode::CONFLICT,
+                [(ERROR_HEADER, code.to_json())],
+                Json(MetaHttpResponse::error_code_with_trace_id(code, trace_id)),
+            )
+                .into_response(),
             errors::ErrorCodes::SearchTimeout(_) => (
                 StatusCode::REQUEST_TIMEOUT,
                 [(ERROR_HEADER, code.to_json())],
@@ -113,6 +126,42 @@ mod tests {
         assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
     }
 
+    #[test]
+    fn test_map_error_to_http_response_search_quota_exceeded() {
+        let err = errors::Error::ErrorCode(errors::ErrorCodes::SearchQuotaExceeded(
+            "Search quota exceeded".to_string(),
+        ));
+        let response = map_error_to_http_response(&err, None);
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[test]
+    fn test_map_error_to_http_response_search_limit_exceeded() {
+        let err = errors::Error::ErrorCode(errors::ErrorCodes::SearchLimitExceeded(
+            "Search limit exceeded".to_string(),
+        ));
+        let response = map_error_to_http_response(&err, None);
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_map_error_to_http_response_search_field_access_denied() {
+        let err = errors::Error::ErrorCode(errors::ErrorCodes::SearchFieldAccessDenied(
+            "access to field ssn is denied".to_string(),
+        ));
+        let response = map_error_to_http_response(&err, None);
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_map_error_to_http_response_search_cold_storage_restore_required() {
+        let err = errors::Error::ErrorCode(errors::ErrorCodes::SearchColdStorageRestoreRequired(
+            "12 files are in the cold storage".to_string(),
+        ));
+        let response = map_error_to_http_response(&err, None);
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
     #[test]
     fn test_map_error_to_http_response_search_timeout() {
         let err = errors::Error::ErrorCode(errors::ErrorCodes::SearchTimeout(
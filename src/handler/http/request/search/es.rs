@@ -0,0 +1,219 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use axum::{
+    body::{Body, Bytes},
+    extract::Path,
+    http::{HeaderMap, StatusCode, header},
+    response::Response,
+};
+use config::{
+    get_config,
+    meta::stream::StreamType,
+    utils::{json, time::now_micros},
+};
+use tracing::Span;
+
+#[cfg(feature = "enterprise")]
+use super::utils::check_stream_permissions;
+use crate::{
+    common::{
+        meta::http::HttpResponse as MetaHttpResponse,
+        utils::{auth::UserEmail, http::get_or_create_trace_id},
+    },
+    handler::http::extractors::Headers,
+    service::search::{self as SearchService, es},
+};
+
+/// ElasticsearchMultiSearch
+
+#[utoipa::path(
+    post,
+    path = "/{org_id}/_msearch",
+    context_path = "/api",
+    tag = "Search",
+    operation_id = "SearchElasticsearchMulti",
+    summary = "Elasticsearch compatible multi search",
+    description = "Runs the searches of an Elasticsearch `_msearch` NDJSON body, a header line naming the index (stream) \
+                   followed by a query line. The basics of the query DSL are supported: match_all, term, terms, match, \
+                   match_phrase, multi_match, query_string, range, exists, prefix, wildcard and bool. A range on \
+                   `@timestamp` sets the time range of the search. Aggregations are not supported. Every search gets an \
+                   Elasticsearch shaped response, failed searches get an error item.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = String, description = "NDJSON multi search body", content_type = "application/x-ndjson", example = json!("{\"index\":\"k8s\"}\n{\"query\":{\"bool\":{\"filter\":[{\"range\":{\"@timestamp\":{\"gte\":\"now-15m\"}}},{\"match\":{\"log\":\"error\"}}]}},\"size\":10}\n")),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({
+            "took": 12,
+            "responses": [{
+                "took": 12,
+                "timed_out": false,
+                "_shards": {"total": 1, "successful": 1, "skipped": 0, "failed": 0},
+                "hits": {
+                    "total": {"value": 1, "relation": "eq"},
+                    "max_score": null,
+                    "hits": [{
+                        "_index": "k8s",
+                        "_id": "k8s-0",
+                        "_score": null,
+                        "_source": {"_timestamp": 1674213225158000i64, "@timestamp": "2023-01-20T11:13:45.158Z", "log": "error"}
+                    }]
+                },
+                "status": 200
+            }]
+        })),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"}))
+    )
+)]
+pub async fn msearch(
+    Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    multi_search(org_id, None, &user_email.user_id, &headers, &body).await
+}
+
+/// ElasticsearchMultiSearchIndex
+
+#[utoipa::path(
+    post,
+    path = "/{org_id}/{stream_name}/_msearch",
+    context_path = "/api",
+    tag = "Search",
+    operation_id = "SearchElasticsearchMultiIndex",
+    summary = "Elasticsearch compatible multi search on an index",
+    description = "Same as `/{org_id}/_msearch`, the index of the path is used by the searches whose header names no \
+                   index.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Default index (stream) name"),
+    ),
+    request_body(content = String, description = "NDJSON multi search body", content_type = "application/x-ndjson"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"}))
+    )
+)]
+pub async fn msearch_index(
+    Path((org_id, index)): Path<(String, String)>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    multi_search(org_id, Some(index), &user_email.user_id, &headers, &body).await
+}
+
+async fn multi_search(
+    org_id: String,
+    default_index: Option<String>,
+    user_id: &str,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Response {
+    let start = std::time::Instant::now();
+    let cfg = get_config();
+    let items = match es::parse_msearch(body) {
+        Ok(items) => items,
+        Err(e) => return MetaHttpResponse::bad_request(e),
+    };
+
+    let http_span = if cfg.common.tracing_search_enabled || cfg.common.tracing_enabled {
+        tracing::info_span!("/api/{org_id}/_msearch", org_id = org_id.clone())
+    } else {
+        Span::none()
+    };
+    let trace_id = get_or_create_trace_id(headers, &http_span);
+
+    let mut responses = Vec::with_capacity(items.len());
+    for (i, item) in items.iter().enumerate() {
+        let Some(index) = item.index.as_ref().or(default_index.as_ref()) else {
+            responses.push(es::to_es_error(
+                400,
+                "action_request_validation_exception",
+                "index is missing",
+            ));
+            continue;
+        };
+        let stream_name = match es::resolve_index(&org_id, index).await {
+            Ok(v) => v,
+            Err(e) => {
+                responses.push(es::to_es_error(404, "index_not_found_exception", e));
+                continue;
+            }
+        };
+
+        #[cfg(feature = "enterprise")]
+        if check_stream_permissions(&stream_name, &org_id, user_id, &StreamType::Logs)
+            .await
+            .is_some()
+        {
+            responses.push(es::to_es_error(
+                403,
+                "security_exception",
+                format!("unauthorized to search index [{index}]"),
+            ));
+            continue;
+        }
+
+        let req = match es::build_request(&stream_name, &item.body, now_micros()) {
+            Ok(v) => v,
+            Err(e) => {
+                responses.push(es::to_es_error(400, "parsing_exception", e));
+                continue;
+            }
+        };
+        let track_total_hits = req.query.track_total_hits;
+        match SearchService::search(
+            &format!("{trace_id}-{i}"),
+            &org_id,
+            StreamType::Logs,
+            Some(user_id.to_string()),
+            &req,
+        )
+        .await
+        {
+            Ok(resp) => responses.push(es::to_es_response(&stream_name, resp, track_total_hits)),
+            Err(e) => {
+                log::error!("[trace_id {trace_id}] _msearch error: {e}");
+                responses.push(es::to_es_error(500, "search_phase_execution_exception", e));
+            }
+        }
+    }
+
+    let body = json::json!({
+        "took": start.elapsed().as_millis() as usize,
+        "responses": responses,
+    });
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("X-Elastic-Product", "Elasticsearch")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
@@ -26,9 +26,12 @@ use chrono::Utc;
 use config::{
     DISTINCT_FIELDS, META_ORG_ID, TIMESTAMP_COL_NAME, get_config,
     meta::{
+        anomalies::{AnomalyRequest, AnomalyResponse},
+        pivot::{PivotRequest, PivotResponse},
+        query_diff::{QueryDiffRequest, QueryDiffResponse},
         search::{
             Request, ResultSchemaResponse, SearchEventType, SearchHistoryHitResponse,
-            SearchHistoryRequest, SearchPartitionRequest, default_use_cache,
+            SearchHistoryRequest, SearchPartitionRequest, SearchQuotaUsage, default_use_cache,
         },
         self_reporting::usage::{RequestStats, USAGE_STREAM, UsageType},
         sql::resolve_stream_names,
@@ -37,6 +40,7 @@ use config::{
     utils::{base64, json, time::now_micros, util::DISTINCT_STREAM_PREFIX},
 };
 use error_utils::map_error_to_http_response;
+use futures::StreamExt;
 use hashbrown::HashMap;
 use http::HeaderMap;
 use tracing::{Instrument, Span};
@@ -54,34 +58,49 @@ use crate::{
             auth::UserEmail,
             functions,
             http::{
-                get_clear_cache_from_request, get_dashboard_info_from_request,
-                get_enable_align_histogram_from_request, get_is_multi_stream_search_from_request,
-                get_is_ui_histogram_from_request, get_or_create_trace_id,
-                get_search_event_context_from_request, get_search_type_from_request,
-                get_stream_type_from_request, get_use_cache_from_request, get_work_group,
+                get_bypass_max_query_range_from_request, get_clear_cache_from_request,
+                get_dashboard_info_from_request, get_enable_align_histogram_from_request,
+                get_is_multi_stream_search_from_request, get_is_ui_histogram_from_request,
+                get_or_create_trace_id, get_search_event_context_from_request,
+                get_search_type_from_request, get_stream_type_from_request,
+                get_use_cache_from_request, get_work_group,
+            },
+            stream::{
+                audit_max_query_range_bypass, can_bypass_max_query_range,
+                get_settings_max_query_range,
             },
-            stream::get_settings_max_query_range,
         },
     },
     handler::http::extractors::Headers,
     service::{
         db::enrichment_table,
         search::{
-            self as SearchService, datafusion::plan::projections::get_result_schema,
-            sql::visitor::pickup_where::pickup_where, utils::is_permissable_function_error,
+            self as SearchService,
+            batch_query::{BatchQuery, BatchQueryRequest, BatchQueryTrailer},
+            datafusion::plan::projections::get_result_schema,
+            field_access,
+            ordered_export::{
+                self, ExportTarget, OrderedExport, OrderedExportRequest, OrderedExportResponse,
+            },
+            sql::visitor::pickup_where::pickup_where,
+            utils::is_permissable_function_error,
         },
         self_reporting::{http_report_metrics, report_request_usage_stats},
     },
 };
 
 pub(crate) mod around;
+pub mod async_search;
+pub mod cross_org;
 pub(crate) mod error_utils;
+pub mod es;
 pub mod multi_streams;
 pub mod query_manager;
 pub mod saved_view;
 pub mod search_inspector;
 pub mod search_job;
 pub mod search_stream;
+pub mod tail;
 pub(crate) mod utils;
 
 async fn can_use_distinct_stream(
@@ -182,6 +201,7 @@ async fn can_use_distinct_stream(
         ("is_ui_histogram" = bool, Query, description = "Whether to return histogram data for UI"),
         ("is_multi_stream_search" = bool, Query, description = "Indicate is search is for multi stream"),
         ("validate" = bool, Query, description = "Validate query fields against stream schema and User-Defined Schema (UDS). When enabled, returns error if queried fields are not in schema or not allowed by UDS"),
+        ("bypass_max_query_range" = Option<bool>, Query, description = "Search past the max query range of the streams, allowed to the root user and admins when ZO_MAX_QUERY_RANGE_BYPASS_ENABLED is set, every bypass is audited"),
     ),
     request_body(content = inline(Request), description = "Search query", content_type = "application/json", example = json!({
         "query": {
@@ -358,6 +378,13 @@ pub async fn search(
             .and_then(|event_type| get_search_event_context_from_request(event_type, &url_query));
     }
 
+    let bypass_max_query_range = get_bypass_max_query_range_from_request(&url_query);
+    if bypass_max_query_range && !can_bypass_max_query_range(&org_id, user_id).await {
+        return MetaHttpResponse::forbidden(
+            "Only the root user and admins can bypass the max query range",
+        );
+    }
+
     // get stream settings
     for stream_name in stream_names {
         if let Some(settings) =
@@ -369,10 +396,23 @@ pub async fn search(
             if max_query_range > 0
                 && (req.query.end_time - req.query.start_time) > max_query_range * 3600 * 1_000_000
             {
-                req.query.start_time = req.query.end_time - max_query_range * 3600 * 1_000_000;
-                range_error = format!(
-                    "Query duration is modified due to query range restriction of {max_query_range} hours"
-                );
+                if bypass_max_query_range {
+                    audit_max_query_range_bypass(
+                        &trace_id,
+                        &org_id,
+                        user_id,
+                        &stream_name,
+                        max_query_range,
+                        req.query.start_time,
+                        req.query.end_time,
+                    )
+                    .await;
+                } else {
+                    req.query.start_time = req.query.end_time - max_query_range * 3600 * 1_000_000;
+                    range_error = format!(
+                        "Query duration is modified due to query range restriction of {max_query_range} hours"
+                    );
+                }
             }
         }
 
@@ -502,6 +542,606 @@ pub async fn search(
     }
 }
 
+/// SearchAnomalies
+
+#[utoipa::path(
+    post,
+    path = "/{org_id}/_search_anomalies",
+    context_path = "/api",
+    tag = "Search",
+    operation_id = "SearchAnomalies",
+    summary = "Detect spikes and changepoints",
+    description = "Runs a query returning one row per series and time bucket, usually a histogram, and returns only the detected anomalies of every series. The mad method flags spikes whose robust z-score exceeds the threshold, the cusum method flags changepoints where the cumulative sum of the standardized values exceeds the threshold. The non numeric columns identify the series and every numeric column is analyzed unless y_axis is set.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("type" = Option<String>, Query, description = "Stream type, logs by default"),
+    ),
+    request_body(content = inline(AnomalyRequest), description = "Query and detection options", content_type = "application/json", example = json!({
+        "query": {
+            "sql": "SELECT histogram(_timestamp, '1 minute') AS x_axis_1, k8s_namespace_name, count(*) AS errors FROM default WHERE level = 'error' GROUP BY x_axis_1, k8s_namespace_name",
+            "start_time": 1675182660872049i64,
+            "end_time": 1675185660872049i64
+        },
+        "method": "mad",
+        "threshold": 3.5
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(AnomalyResponse), example = json!({
+            "took": 120,
+            "method": "mad",
+            "threshold": 3.5,
+            "series_analyzed": 4,
+            "total_anomalies": 1,
+            "series": [{
+                "labels": {"k8s_namespace_name": "ingress"},
+                "field": "errors",
+                "points": 50,
+                "anomalies": [{"x": "2023-01-31T16:45:00", "value": 812.0, "score": 41.3, "direction": "up"}]
+            }],
+            "is_partial": false
+        })),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Detect spikes and changepoints in the series returned by a SQL query", "category": "search"}))
+    )
+)]
+pub async fn search_anomalies(
+    Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    Query(url_query): Query<HashMap<String, String>>,
+    Json(mut req): Json<AnomalyRequest>,
+) -> Response {
+    let cfg = get_config();
+    let http_span = if cfg.common.tracing_search_enabled || cfg.common.tracing_enabled {
+        tracing::info_span!("/api/{org_id}/_search_anomalies", org_id = org_id.clone())
+    } else {
+        Span::none()
+    };
+    let trace_id = get_or_create_trace_id(&headers, &http_span);
+    let user_id = &user_email.user_id;
+    let stream_type = get_stream_type_from_request(&url_query).unwrap_or_default();
+
+    if let Ok(sql) = config::utils::query_select_utils::replace_o2_custom_patterns(&req.query.sql) {
+        req.query.sql = sql;
+    };
+    let stream_names = match resolve_stream_names(&req.query.sql) {
+        Ok(v) => v,
+        Err(e) => {
+            return map_error_to_http_response(&(e.into()), Some(trace_id));
+        }
+    };
+
+    #[cfg(feature = "enterprise")]
+    for stream_name in stream_names.iter() {
+        if let Err(e) = crate::service::search::check_search_allowed(&org_id, Some(stream_name)) {
+            return MetaHttpResponse::too_many_requests(e);
+        }
+        if let Some(res) =
+            check_stream_permissions(stream_name, &org_id, user_id, &stream_type).await
+        {
+            return res;
+        }
+    }
+    #[cfg(not(feature = "enterprise"))]
+    drop(stream_names);
+
+    match SearchService::anomalies::detect(
+        &trace_id,
+        &org_id,
+        stream_type,
+        Some(user_id.to_string()),
+        &req,
+    )
+    .instrument(http_span)
+    .await
+    {
+        Ok(res) => Json(res).into_response(),
+        Err(err) => {
+            log::error!("[trace_id {trace_id}] search anomalies error: {err}");
+            map_error_to_http_response(&err, Some(trace_id))
+        }
+    }
+}
+
+/// SearchPivot
+
+#[utoipa::path(
+    post,
+    path = "/{org_id}/_search_pivot",
+    context_path = "/api",
+    tag = "Search",
+    operation_id = "SearchPivot",
+    summary = "Search around a record in related streams",
+    description = "Returns the events around a record in the streams related to it, the ones sharing a key with the record, to investigate an event. The keys are host, container_id and trace_id unless set, and a key also matches the fields of its semantic field group. The record is read from its stream at the timestamp when it is not given. Every logs and traces stream having one of the keys is searched unless the streams are set, and for each stream the events matching one of the key values are returned, half before and half after the record, newest first.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = inline(PivotRequest), description = "Record and pivot options", content_type = "application/json", example = json!({
+        "stream_name": "default",
+        "stream_type": "logs",
+        "timestamp": 1674213225158000i64,
+        "keys": ["host", "trace_id"],
+        "window": 300,
+        "size": 20
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(PivotResponse), example = json!({
+            "took": 85,
+            "timestamp": 1674213225158000i64,
+            "keys": {"host": "ip.us-east-2.compute.internal", "trace_id": "4bf92f3577b34da6a3ce929d0e0e4736"},
+            "streams": [{
+                "stream_name": "default",
+                "stream_type": "logs",
+                "fields": ["host", "trace_id"],
+                "hits": [{"_timestamp": 1674213225158000i64, "host": "ip.us-east-2.compute.internal", "log": "request failed"}]
+            }]
+        })),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Find the events around a record in the streams sharing its host, container or trace", "category": "search"}))
+    )
+)]
+pub async fn search_pivot(
+    Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    Json(req): Json<PivotRequest>,
+) -> Response {
+    let cfg = get_config();
+    let http_span = if cfg.common.tracing_search_enabled || cfg.common.tracing_enabled {
+        tracing::info_span!("/api/{org_id}/_search_pivot", org_id = org_id.clone())
+    } else {
+        Span::none()
+    };
+    let trace_id = get_or_create_trace_id(&headers, &http_span);
+    let user_id = &user_email.user_id;
+
+    #[cfg(feature = "enterprise")]
+    {
+        if let Err(e) =
+            crate::service::search::check_search_allowed(&org_id, Some(&req.stream_name))
+        {
+            return MetaHttpResponse::too_many_requests(e);
+        }
+        if let Some(res) =
+            check_stream_permissions(&req.stream_name, &org_id, user_id, &req.stream_type).await
+        {
+            return res;
+        }
+    }
+
+    #[allow(unused_mut)]
+    let mut plan =
+        match SearchService::pivot::plan(&trace_id, &org_id, Some(user_id.to_string()), &req)
+            .instrument(http_span.clone())
+            .await
+        {
+            Ok(plan) => plan,
+            Err(err) => {
+                log::error!("[trace_id {trace_id}] search pivot error: {err}");
+                return map_error_to_http_response(&err, Some(trace_id));
+            }
+        };
+
+    // the streams given must all be allowed, the related ones found from the
+    // keys are skipped when they are not
+    #[cfg(feature = "enterprise")]
+    {
+        let mut allowed = Vec::with_capacity(plan.streams.len());
+        for target in plan.streams {
+            let stream = &target.stream;
+            if let Some(res) =
+                check_stream_permissions(&stream.stream_name, &org_id, user_id, &stream.stream_type)
+                    .await
+            {
+                if !req.streams.is_empty() {
+                    return res;
+                }
+                continue;
+            }
+            allowed.push(target);
+        }
+        plan.streams = allowed;
+    }
+
+    match SearchService::pivot::search(&trace_id, &org_id, Some(user_id.to_string()), &req, plan)
+        .instrument(http_span)
+        .await
+    {
+        Ok(res) => Json(res).into_response(),
+        Err(err) => {
+            log::error!("[trace_id {trace_id}] search pivot error: {err}");
+            map_error_to_http_response(&err, Some(trace_id))
+        }
+    }
+}
+
+/// SearchDiff
+
+#[utoipa::path(
+    post,
+    path = "/{org_id}/_search_diff",
+    context_path = "/api",
+    tag = "Search",
+    operation_id = "SearchDiff",
+    summary = "Diff query results",
+    description = "Runs the same query over a baseline and a comparison, another time range or another stream, and returns the groups found on one side only and the values which changed by more than the threshold percent. Groups are identified by the group_by columns, every non numeric column when empty, which makes it suited to comparing a release with the previous one.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("type" = Option<String>, Query, description = "Stream type, logs by default"),
+    ),
+    request_body(content = inline(QueryDiffRequest), description = "Query and comparison", content_type = "application/json", example = json!({
+        "query": {
+            "sql": "SELECT service, count(*) AS errors FROM default WHERE level = 'error' GROUP BY service",
+            "start_time": 1675182660872049i64,
+            "end_time": 1675185660872049i64
+        },
+        "compare": {
+            "start_time": 1675185660872049i64,
+            "end_time": 1675188660872049i64
+        },
+        "threshold": 20
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(QueryDiffResponse), example = json!({
+            "took": 210,
+            "threshold": 20.0,
+            "baseline_rows": 3,
+            "compare_rows": 3,
+            "new_groups": [{"labels": {"service": "checkout"}, "values": {"errors": 12.0}}],
+            "missing_groups": [{"labels": {"service": "legacy"}, "values": {"errors": 3.0}}],
+            "changed": [{
+                "labels": {"service": "api"},
+                "field": "errors",
+                "baseline": 40.0,
+                "compare": 95.0,
+                "delta": 55.0,
+                "delta_percent": 137.5
+            }],
+            "unchanged_groups": 1,
+            "is_partial": false
+        })),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Compare the results of a SQL query over two time ranges or two streams", "category": "search"}))
+    )
+)]
+pub async fn search_diff(
+    Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    Query(url_query): Query<HashMap<String, String>>,
+    Json(mut req): Json<QueryDiffRequest>,
+) -> Response {
+    let cfg = get_config();
+    let http_span = if cfg.common.tracing_search_enabled || cfg.common.tracing_enabled {
+        tracing::info_span!("/api/{org_id}/_search_diff", org_id = org_id.clone())
+    } else {
+        Span::none()
+    };
+    let trace_id = get_or_create_trace_id(&headers, &http_span);
+    let user_id = &user_email.user_id;
+    let stream_type = get_stream_type_from_request(&url_query).unwrap_or_default();
+
+    if let Ok(sql) = config::utils::query_select_utils::replace_o2_custom_patterns(&req.query.sql) {
+        req.query.sql = sql;
+    };
+    let mut stream_names = match resolve_stream_names(&req.query.sql) {
+        Ok(v) => v,
+        Err(e) => {
+            return map_error_to_http_response(&(e.into()), Some(trace_id));
+        }
+    };
+    if let Some(stream) = req.compare.stream.as_ref().filter(|s| !s.is_empty()) {
+        stream_names.push(stream.to_string());
+    }
+
+    #[cfg(feature = "enterprise")]
+    for stream_name in stream_names.iter() {
+        if let Err(e) = crate::service::search::check_search_allowed(&org_id, Some(stream_name)) {
+            return MetaHttpResponse::too_many_requests(e);
+        }
+        if let Some(res) =
+            check_stream_permissions(stream_name, &org_id, user_id, &stream_type).await
+        {
+            return res;
+        }
+    }
+    #[cfg(not(feature = "enterprise"))]
+    drop(stream_names);
+
+    match SearchService::query_diff::diff(
+        &trace_id,
+        &org_id,
+        stream_type,
+        Some(user_id.to_string()),
+        &req,
+    )
+    .instrument(http_span)
+    .await
+    {
+        Ok(res) => Json(res).into_response(),
+        Err(err) => {
+            log::error!("[trace_id {trace_id}] search diff error: {err}");
+            map_error_to_http_response(&err, Some(trace_id))
+        }
+    }
+}
+
+/// SearchExport
+
+#[utoipa::path(
+    post,
+    path = "/{org_id}/_search_export",
+    context_path = "/api",
+    tag = "Search",
+    operation_id = "SearchExport",
+    summary = "Export query results in timestamp order",
+    description = "Exports the full result of a filtered query, without limit, in strict _timestamp order, ascending or descending as set by the ORDER BY of the query. The time range is searched one partition at a time and the rows of each partition are merged in order across the cluster. With the `client` target the rows are streamed in the response as newline delimited JSON, with the `destination` target they are written to the export destination of the organization. Aggregations, LIMIT and ordering by other fields are not supported.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("type" = Option<String>, Query, description = "Stream type, logs by default"),
+    ),
+    request_body(content = inline(OrderedExportRequest), description = "Query to export", content_type = "application/json", example = json!({
+        "sql": "SELECT * FROM \"default\" WHERE service = 'checkout' ORDER BY _timestamp ASC",
+        "start_time": 1675182660872049i64,
+        "end_time": 1675185660872049i64,
+        "target": "destination",
+        "path": "replay/checkout"
+    })),
+    responses(
+        (status = 200, description = "Rows as newline delimited JSON for the client target, the written object for the destination target", content_type = "application/json", body = inline(OrderedExportResponse), example = json!({
+            "took": 52310,
+            "rows": 1843200,
+            "partitions": 12,
+            "object": "openobserve/replay/checkout/2026/01/02/3f1c6a2d9b.json"
+        })),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 404, description = "Export destination not configured", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"}))
+    )
+)]
+pub async fn search_export(
+    Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    Query(url_query): Query<HashMap<String, String>>,
+    Json(mut req): Json<OrderedExportRequest>,
+) -> Response {
+    let cfg = get_config();
+    let http_span = if cfg.common.tracing_search_enabled || cfg.common.tracing_enabled {
+        tracing::info_span!("/api/{org_id}/_search_export", org_id = org_id.clone())
+    } else {
+        Span::none()
+    };
+    let trace_id = get_or_create_trace_id(&headers, &http_span);
+    let user_id = &user_email.user_id;
+    let stream_type = get_stream_type_from_request(&url_query).unwrap_or_default();
+
+    if let Ok(sql) = config::utils::query_select_utils::replace_o2_custom_patterns(&req.sql) {
+        req.sql = sql;
+    };
+    let stream_names = match resolve_stream_names(&req.sql) {
+        Ok(v) => v,
+        Err(e) => {
+            return map_error_to_http_response(&(e.into()), Some(trace_id));
+        }
+    };
+
+    #[cfg(feature = "enterprise")]
+    for stream_name in stream_names.iter() {
+        if let Err(e) = crate::service::search::check_search_allowed(&org_id, Some(stream_name)) {
+            return MetaHttpResponse::too_many_requests(e);
+        }
+        if let Some(res) =
+            check_stream_permissions(stream_name, &org_id, user_id, &stream_type).await
+        {
+            return res;
+        }
+    }
+    #[cfg(not(feature = "enterprise"))]
+    drop(stream_names);
+
+    let export = match OrderedExport::new(
+        &trace_id,
+        &org_id,
+        stream_type,
+        Some(user_id.to_string()),
+        &req,
+    )
+    .instrument(http_span.clone())
+    .await
+    {
+        Ok(v) => v,
+        Err(err) => {
+            log::error!("[trace_id {trace_id}] search export error: {err}");
+            return map_error_to_http_response(&err, Some(trace_id));
+        }
+    };
+    log::info!(
+        "[trace_id {trace_id}] search export: org: {org_id}, partitions: {}, target: {:?}",
+        export.partitions(),
+        req.target
+    );
+
+    match req.target {
+        ExportTarget::Client => {
+            // an error ends the response early, the rows sent so far are still
+            // in order
+            let stream = export.into_stream().map(move |batch| {
+                batch
+                    .and_then(|batch| ordered_export::encode_ndjson(&batch))
+                    .map_err(|e| {
+                        log::error!("[trace_id {trace_id}] search export error: {e}");
+                        Error::other(e.to_string())
+                    })
+            });
+            axum::response::Response::builder()
+                .header("content-type", "application/x-ndjson")
+                .body(axum::body::Body::from_stream(stream))
+                .unwrap()
+        }
+        ExportTarget::Destination => {
+            let Ok(destination) =
+                crate::service::db::scheduled_exports::get_destination(&org_id).await
+            else {
+                return MetaHttpResponse::not_found(
+                    "Export destination is not configured for the organization",
+                );
+            };
+            match ordered_export::write_to_destination(export, &destination, &req.path)
+                .instrument(http_span)
+                .await
+            {
+                Ok(res) => Json(res).into_response(),
+                Err(err) => {
+                    log::error!("[trace_id {trace_id}] search export error: {err}");
+                    map_error_to_http_response(&err, Some(trace_id))
+                }
+            }
+        }
+    }
+}
+
+/// SearchNdjson
+
+#[utoipa::path(
+    post,
+    path = "/{org_id}/_search_ndjson",
+    context_path = "/api",
+    tag = "Search",
+    operation_id = "SearchNdjson",
+    summary = "Run a query for scripts, streaming newline delimited JSON",
+    description = "Runs a SQL query and streams the rows as newline delimited JSON, one object per line, flushed after each record batch, for the scripts and command line tools piping the results. The last line is `{\"_trailer\": {...}}` with the rows sent and the scan stats, and the error when the query failed after the first rows. A query on one stream ordered by _timestamp only, without aggregation nor LIMIT, is searched one partition at a time in its order, the others in one go. A query with `LIMIT 0` is only validated: the response is the trailer alone, the invalid queries fail before any line is sent.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("type" = Option<String>, Query, description = "Stream type, logs by default"),
+    ),
+    request_body(content = inline(BatchQueryRequest), description = "Query to run", content_type = "application/json", example = json!({
+        "sql": "SELECT _timestamp, level, message FROM \"default\" WHERE level = 'error'",
+        "start_time": 1675182660872049i64,
+        "end_time": 1675185660872049i64
+    })),
+    responses(
+        (status = 200, description = "Rows as newline delimited JSON, then the trailer", content_type = "application/x-ndjson", body = inline(BatchQueryTrailer), example = json!({
+            "_trailer": {
+                "took": 1520,
+                "rows": 12034,
+                "partitions": 4,
+                "scan_files": 38,
+                "scan_records": 2019342,
+                "scan_size": 734003200,
+                "compressed_size": 52428800,
+                "idx_scan_size": 0,
+                "validate_only": false
+            }
+        })),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn search_ndjson(
+    Path(org_id): Path<String>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+    Query(url_query): Query<HashMap<String, String>>,
+    Json(mut req): Json<BatchQueryRequest>,
+) -> Response {
+    let cfg = get_config();
+    let http_span = if cfg.common.tracing_search_enabled || cfg.common.tracing_enabled {
+        tracing::info_span!("/api/{org_id}/_search_ndjson", org_id = org_id.clone())
+    } else {
+        Span::none()
+    };
+    let trace_id = get_or_create_trace_id(&headers, &http_span);
+    let user_id = &user_email.user_id;
+    let stream_type = get_stream_type_from_request(&url_query).unwrap_or_default();
+
+    if let Ok(sql) = config::utils::query_select_utils::replace_o2_custom_patterns(&req.sql) {
+        req.sql = sql;
+    };
+    let stream_names = match resolve_stream_names(&req.sql) {
+        Ok(v) => v,
+        Err(e) => {
+            return map_error_to_http_response(&(e.into()), Some(trace_id));
+        }
+    };
+
+    #[cfg(feature = "enterprise")]
+    for stream_name in stream_names.iter() {
+        if let Err(e) = crate::service::search::check_search_allowed(&org_id, Some(stream_name)) {
+            return MetaHttpResponse::too_many_requests(e);
+        }
+        if let Some(res) =
+            check_stream_permissions(stream_name, &org_id, user_id, &stream_type).await
+        {
+            return res;
+        }
+    }
+    #[cfg(not(feature = "enterprise"))]
+    drop(stream_names);
+
+    let query = match BatchQuery::new(
+        &trace_id,
+        &org_id,
+        stream_type,
+        Some(user_id.to_string()),
+        &req,
+    )
+    .instrument(http_span)
+    .await
+    {
+        Ok(v) => v,
+        Err(err) => {
+            log::error!("[trace_id {trace_id}] search ndjson error: {err}");
+            return map_error_to_http_response(&err, Some(trace_id));
+        }
+    };
+    log::info!(
+        "[trace_id {trace_id}] search ndjson: org: {org_id}, partitions: {}",
+        query.partitions()
+    );
+
+    let stream = query.into_stream().map(Ok::<_, Error>);
+    axum::response::Response::builder()
+        .header("content-type", "application/x-ndjson")
+        .body(axum::body::Body::from_stream(stream))
+        .unwrap()
+}
+
 /// SearchAround
 
 #[utoipa::path(
@@ -1079,6 +1719,12 @@ async fn values_v1(
         Some(v) => v.split(',').map(|s| s.to_string()).collect::<Vec<_>>(),
         None => return MetaHttpResponse::bad_request("fields is empty"),
     };
+    // the values of the distinct value streams aren't checked by the search
+    if let Err(e) =
+        field_access::check_fields(org_id, user_id, stream_type, stream_name, &fields).await
+    {
+        return map_error_to_http_response(&e, Some(trace_id));
+    }
     let query_fn = query
         .get("query_fn")
         .and_then(|v| base64::decode_url(v.as_ref()).ok())
@@ -1387,6 +2033,7 @@ async fn values_v1(
         took_wait_in_queue: Some(resp.took_detail.wait_in_queue),
         work_group: get_work_group(work_group_set),
         peak_memory_usage: resp.peak_memory_usage,
+        took_detail: Some(resp.took_detail.clone()),
         ..Default::default()
     };
     let num_fn = req.query.query_fn.is_some() as u16;
@@ -1747,6 +2394,7 @@ pub async fn search_history(
         took_wait_in_queue,
         work_group: search_res.work_group.clone(),
         peak_memory_usage: search_res.peak_memory_usage,
+        took_detail: Some(search_res.took_detail.clone()),
         ..Default::default()
     };
     let num_fn = search_query_req.query.query_fn.is_some() as u16;
@@ -1980,3 +2628,35 @@ pub async fn result_schema(
     })
     .into_response()
 }
+
+/// GetSearchQuota
+#[utoipa::path(
+    get,
+    path = "/{org_id}/search/quota",
+    context_path = "/api",
+    tag = "Search",
+    operation_id = "GetSearchQuota",
+    summary = "Get search quota consumption",
+    description = "Returns the search quota of the organization, the searches it runs now across the cluster and the \
+                   MB it scanned over the last minute. Searches are rejected with 429 when the org is at one of its \
+                   limits.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(SearchQuotaUsage), example = json!({
+            "quota": {"max_concurrent": 10, "scan_mb_per_min": 102400},
+            "running": 3,
+            "scan_mb_last_min": 2048,
+        })),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"}))
+    )
+)]
+pub async fn get_search_quota(Path(org_id): Path<String>) -> Response {
+    Json(crate::service::search::quota::usage(&org_id).await).into_response()
+}
@@ -55,13 +55,16 @@ use crate::{
             auth::UserEmail,
             functions,
             http::{
-                get_clear_cache_from_request, get_dashboard_info_from_request,
-                get_enable_align_histogram_from_request, get_fallback_order_by_col_from_request,
-                get_or_create_trace_id, get_search_event_context_from_request,
-                get_search_type_from_request, get_stream_type_from_request,
-                get_use_cache_from_request,
+                get_bypass_max_query_range_from_request, get_clear_cache_from_request,
+                get_dashboard_info_from_request, get_enable_align_histogram_from_request,
+                get_fallback_order_by_col_from_request, get_or_create_trace_id,
+                get_search_event_context_from_request, get_search_type_from_request,
+                get_stream_type_from_request, get_use_cache_from_request,
+            },
+            stream::{
+                audit_max_query_range_bypass, can_bypass_max_query_range,
+                get_settings_max_query_range,
             },
-            stream::get_settings_max_query_range,
         },
     },
     handler::http::request::search::{Headers, error_utils::map_error_to_http_response},
@@ -84,6 +87,7 @@ use crate::{
     params(
         ("org_id" = String, Path, description = "Organization name"),
         ("validate" = bool, Query, description = "Validate query fields against stream schema and User-Defined Schema (UDS). When enabled, returns error if queried fields are not in schema or not allowed by UDS"),
+        ("bypass_max_query_range" = Option<bool>, Query, description = "Search past the max query range of the streams, allowed to the root user and admins when ZO_MAX_QUERY_RANGE_BYPASS_ENABLED is set, every bypass is audited"),
     ),
     request_body(
         content = inline(search::MultiStreamRequest),
@@ -219,6 +223,12 @@ pub async fn search_multi(
     let mut range_error = String::new();
 
     let user_id = &user_email.user_id;
+    let bypass_max_query_range = get_bypass_max_query_range_from_request(&query);
+    if bypass_max_query_range && !can_bypass_max_query_range(&org_id, user_id).await {
+        return MetaHttpResponse::forbidden(
+            "Only the root user and admins can bypass the max query range",
+        );
+    }
     let mut queries = multi_req.to_query_req();
     let mut multi_res = search::Response::new(multi_req.from, multi_req.size);
 
@@ -254,15 +264,28 @@ pub async fn search_multi(
             if max_query_range > 0
                 && (req.query.end_time - req.query.start_time) > max_query_range * 3600 * 1_000_000
             {
-                req.query.start_time = req.query.end_time - max_query_range * 3600 * 1_000_000;
-                range_error = format!(
-                    "{} Query duration for stream {} is modified due to query range restriction of {} hours",
-                    range_error, &stream_name, max_query_range
-                );
+                if bypass_max_query_range {
+                    audit_max_query_range_bypass(
+                        &trace_id,
+                        &org_id,
+                        user_id,
+                        &stream_name,
+                        max_query_range,
+                        req.query.start_time,
+                        req.query.end_time,
+                    )
+                    .await;
+                } else {
+                    req.query.start_time = req.query.end_time - max_query_range * 3600 * 1_000_000;
+                    range_error = format!(
+                        "{} Query duration for stream {} is modified due to query range restriction of {} hours",
+                        range_error, &stream_name, max_query_range
+                    );
 
-                if multi_res.new_start_time.is_none() {
-                    multi_res.new_start_time = Some(req.query.start_time);
-                    multi_res.new_end_time = Some(req.query.end_time);
+                    if multi_res.new_start_time.is_none() {
+                        multi_res.new_start_time = Some(req.query.start_time);
+                        multi_res.new_end_time = Some(req.query.end_time);
+                    }
                 }
             }
         }
@@ -439,6 +462,7 @@ pub async fn search_multi(
                     search_event_context: search_event_context.clone(),
                     trace_id: Some(res.trace_id.clone()),
                     took_wait_in_queue: Some(res.took_detail.wait_in_queue),
+                    took_detail: Some(res.took_detail.clone()),
                     work_group: res.work_group,
                     peak_memory_usage: res.peak_memory_usage,
                     ..Default::default()
@@ -55,12 +55,13 @@ use crate::{
         utils::{
             auth::UserEmail,
             http::{
-                get_clear_cache_from_request, get_fallback_order_by_col_from_request,
-                get_is_multi_stream_search_from_request, get_is_ui_histogram_from_request,
-                get_or_create_trace_id, get_search_event_context_from_request,
-                get_search_type_from_request, get_stream_type_from_request,
-                get_use_cache_from_request,
+                get_bypass_max_query_range_from_request, get_clear_cache_from_request,
+                get_fallback_order_by_col_from_request, get_is_multi_stream_search_from_request,
+                get_is_ui_histogram_from_request, get_or_create_trace_id,
+                get_search_event_context_from_request, get_search_type_from_request,
+                get_stream_type_from_request, get_use_cache_from_request,
             },
+            stream::can_bypass_max_query_range,
         },
     },
     handler::http::{
@@ -92,6 +93,7 @@ use crate::{
         ("org_id" = String, Path, description = "Organization name"),
         ("is_ui_histogram" = bool, Query, description = "Whether to return histogram data for UI"),
         ("is_multi_stream_search" = bool, Query, description = "Indicate is search is for multi stream"),
+        ("bypass_max_query_range" = Option<bool>, Query, description = "Search past the max query range of the streams, allowed to the root user and admins when ZO_MAX_QUERY_RANGE_BYPASS_ENABLED is set, every bypass is audited"),
     ),
     request_body(content = String, description = "Search query", content_type = "application/json", example = json!({
         "sql": "select * from logs LIMIT 10",
@@ -402,6 +404,32 @@ pub async fn search_http2_stream(
         }
     }
 
+    // Check if user may bypass the max query range when it is requested
+    let bypass_max_query_range = get_bypass_max_query_range_from_request(&query);
+    if bypass_max_query_range && !can_bypass_max_query_range(&org_id, &user_id).await {
+        // Add audit before closing
+        #[cfg(feature = "enterprise")]
+        report_to_audit(
+            user_id,
+            org_id.clone(),
+            trace_id,
+            403,
+            Some("Unauthorized to bypass the max query range - requires admin role".to_string()),
+            "POST".to_string(),
+            format!("/api/{}/_search_stream", org_id),
+            query
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect::<Vec<_>>()
+                .join("&"),
+            body_bytes,
+        )
+        .await;
+        return MetaHttpResponse::forbidden(
+            "Only the root user and admins can bypass the max query range",
+        );
+    }
+
     // Set use_cache from query params
     req.clear_cache = get_clear_cache_from_request(&query);
     req.use_cache = get_use_cache_from_request(&query) && !req.clear_cache;
@@ -536,6 +564,7 @@ pub async fn search_http2_stream(
         audit_ctx,
         is_multi_stream_search,
         extract_patterns,
+        bypass_max_query_range,
     ));
 
     // Return streaming response
@@ -919,6 +948,7 @@ pub async fn values_http2_stream(
         audit_ctx,
         false,
         extract_patterns,
+        false,
     ));
 
     // Return streaming response
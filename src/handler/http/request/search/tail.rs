@@ -0,0 +1,143 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+use axum::{
+    body::Body,
+    extract::{Path, Query},
+    http::{HeaderMap, header},
+    response::Response,
+};
+use config::get_config;
+use futures::StreamExt;
+use tokio::sync::mpsc;
+
+use crate::{
+    common::{
+        meta::http::HttpResponse as MetaHttpResponse,
+        utils::{auth::UserEmail, http::get_stream_type_from_request},
+    },
+    handler::http::extractors::Headers,
+    service::tail::{self, TailError, TailRequest},
+};
+
+impl From<TailError> for Response {
+    fn from(value: TailError) -> Self {
+        match &value {
+            TailError::StreamNotFound => MetaHttpResponse::not_found(value),
+            TailError::InvalidFilter(_) => MetaHttpResponse::bad_request(value),
+            TailError::AccessDenied(_) => MetaHttpResponse::forbidden(value),
+            TailError::TooManyTails => MetaHttpResponse::too_many_requests(value),
+        }
+    }
+}
+
+/// TailStream
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/{stream_name}/_tail",
+    context_path = "/api",
+    tag = "Search",
+    operation_id = "TailStream",
+    summary = "Live tail a stream",
+    description = "Streams the records written to a stream from now on as server sent events, like `kubectl logs -f`, \
+                   without polling the search. The records matching the filter, a SQL condition like the WHERE clause \
+                   of a search, are sent as `records` events holding a JSON array. A `lagged` event tells how many \
+                   write batches were skipped when the client could not follow. The field access rules of the stream \
+                   apply like to the searches.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("type" = Option<String>, Query, description = "Stream type, logs by default"),
+        ("filter" = Option<String>, Query, description = "SQL condition on the records, e.g. `level = 'error'`"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "text/event-stream"),
+        (status = 400, description = "Invalid filter", content_type = "application/json", body = ()),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = ()),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+        (status = 429, description = "Too many tails", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Search", "operation": "get"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn tail_stream(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+    Headers(user_email): Headers<UserEmail>,
+    headers: HeaderMap,
+) -> Response {
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    #[cfg(feature = "enterprise")]
+    if let Some(res) = super::utils::check_stream_permissions(
+        &stream_name,
+        &org_id,
+        &user_email.user_id,
+        &stream_type,
+    )
+    .await
+    {
+        return res;
+    }
+    let req = TailRequest {
+        org_id: org_id.clone(),
+        stream_type,
+        stream_name: stream_name.clone(),
+        filter: query.get("filter").cloned(),
+        user_id: user_email.user_id,
+    };
+    let tail = match tail::open(&req).await {
+        Ok(tail) => tail,
+        Err(e) => return e.into(),
+    };
+
+    let cfg = get_config();
+    let (tx, rx) = mpsc::channel::<String>(cfg.http_streaming.tail_buffer_size.max(1));
+    // a request of another ingester only tails this node
+    let is_local = query.get("local").is_some_and(|v| v == "true");
+    if !is_local {
+        let mut remote_query = url::form_urlencoded::Serializer::new(String::new());
+        remote_query.extend_pairs(query.iter().filter(|(k, _)| k.as_str() != "local"));
+        remote_query.append_pair("local", "true");
+        let path = format!(
+            "{}/api/{org_id}/{stream_name}/_tail?{}",
+            cfg.common.base_uri,
+            remote_query.finish()
+        );
+        let mut auth_headers = HeaderMap::new();
+        for name in [header::AUTHORIZATION, header::COOKIE] {
+            if let Some(value) = headers.get(&name) {
+                auth_headers.insert(name, value.clone());
+            }
+        }
+        tokio::spawn(tail::follow_remote(path, auth_headers, tx.clone()));
+    }
+    tokio::spawn(tail::run(tail, tx));
+
+    let stream =
+        tokio_stream::wrappers::ReceiverStream::new(rx).map(Ok::<_, std::convert::Infallible>);
+    Response::builder()
+        .header(header::CONTENT_TYPE, "text/event-stream")
+        .header(header::CACHE_CONTROL, "no-cache")
+        .body(Body::from_stream(stream))
+        .unwrap()
+}
@@ -47,8 +47,15 @@ pub async fn check_stream_permissions(
     stream_type: &StreamType,
 ) -> Option<Response> {
     if !is_root_user(user_id) {
-        let user: User = get_user(Some(org_id), user_id).await.unwrap();
+        let Some(user): Option<User> = get_user(Some(org_id), user_id).await else {
+            return Some(MetaHttpResponse::forbidden("Unauthorized Access"));
+        };
         let stream_type_str = stream_type.as_str();
+        // the permissions of an alias are the ones of its stream. A dataset is
+        // granted like a stream of its name, its member streams aren't checked
+        // when they are read through it
+        let stream_name = crate::service::stream_alias::resolve(org_id, *stream_type, stream_name)
+            .unwrap_or_else(|| stream_name.to_string());
 
         if !crate::handler::http::auth::validator::check_permissions(
             user_id,
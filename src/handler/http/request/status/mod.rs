@@ -73,10 +73,14 @@ use {
 };
 
 use crate::{
-    common::meta::{
-        http::HttpResponse as MetaHttpResponse,
-        user::{AuthTokens, AuthTokensExt},
+    common::{
+        meta::{
+            http::HttpResponse as MetaHttpResponse,
+            user::{AuthTokens, AuthTokensExt},
+        },
+        utils::auth::{UserEmail, is_root_user},
     },
+    handler::http::extractors::Headers,
     service::{
         db,
         search::{
@@ -1422,6 +1426,75 @@ pub async fn flush_node() -> Response {
     }
 }
 
+#[derive(serde::Deserialize)]
+pub struct OrphanWalQuery {
+    file: String,
+}
+
+/// The orphaned wal files can hold the data of any org, only the root user
+/// can see them
+fn check_orphan_wal_access(user_id: &str) -> Option<Response> {
+    if !LOCAL_NODE.is_ingester() {
+        return Some(MetaHttpResponse::not_found("local node is not an ingester"));
+    }
+    if !is_root_user(user_id) {
+        return Some(MetaHttpResponse::forbidden("Unauthorized Access"));
+    }
+    None
+}
+
+fn orphan_wal_error(e: ingester::errors::Error) -> Response {
+    match e {
+        ingester::errors::Error::WalFileNotOrphan { .. } => MetaHttpResponse::not_found(e),
+        _ => MetaHttpResponse::internal_error(e),
+    }
+}
+
+pub async fn list_orphan_wal(Headers(user_email): Headers<UserEmail>) -> Response {
+    if let Some(res) = check_orphan_wal_access(&user_email.user_id) {
+        return res;
+    }
+    match ingester::list_orphan_wal_files().await {
+        Ok(files) => MetaHttpResponse::json(files),
+        Err(e) => orphan_wal_error(e),
+    }
+}
+
+pub async fn replay_orphan_wal(
+    Headers(user_email): Headers<UserEmail>,
+    Query(query): Query<OrphanWalQuery>,
+) -> Response {
+    if let Some(res) = check_orphan_wal_access(&user_email.user_id) {
+        return res;
+    }
+    log::warn!(
+        "[NODE] user {} replays orphan wal file {}",
+        user_email.user_id,
+        query.file
+    );
+    match ingester::replay_orphan_wal_file(&query.file).await {
+        Ok(_) => MetaHttpResponse::json(true),
+        Err(e) => orphan_wal_error(e),
+    }
+}
+
+pub async fn export_orphan_wal(
+    Headers(user_email): Headers<UserEmail>,
+    Query(query): Query<OrphanWalQuery>,
+) -> Response {
+    if let Some(res) = check_orphan_wal_access(&user_email.user_id) {
+        return res;
+    }
+    match ingester::export_orphan_wal_file(&query.file).await {
+        Ok(data) => Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "application/x-ndjson")
+            .body(Body::from(data))
+            .unwrap(),
+        Err(e) => orphan_wal_error(e),
+    }
+}
+
 #[cfg(feature = "enterprise")]
 pub async fn drain_status() -> Response {
     let is_ingester = LOCAL_NODE.is_ingester();
@@ -1434,9 +1507,23 @@ pub async fn list_node() -> Response {
     MetaHttpResponse::json(nodes)
 }
 
+#[derive(Serialize)]
+struct NodeMetricsResponse {
+    #[serde(flatten)]
+    metrics: config::utils::sysinfo::NodeMetrics,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ingest_backpressure: Option<ingester::BackpressureStatus>,
+}
+
 pub async fn node_metrics() -> Response {
     let metrics = config::utils::sysinfo::get_node_metrics();
-    MetaHttpResponse::json(metrics)
+    let ingest_backpressure = LOCAL_NODE
+        .is_ingester()
+        .then(ingester::get_backpressure_status);
+    MetaHttpResponse::json(NodeMetricsResponse {
+        metrics,
+        ingest_backpressure,
+    })
 }
 
 pub async fn consistent_hash(axum::Json(body): axum::Json<HashFileRequest>) -> Response {
@@ -1501,6 +1588,11 @@ const CACHE_MODULES: &[&str] = &[
     "realtime_triggers",
     "org_users",
     "compact_retention",
+    "stream_alias",
+    "dataset",
+    "org_lifecycle",
+    "level_mapping",
+    "log_metrics",
 ];
 
 // Helper function to reload cache for a specific module
@@ -1519,6 +1611,11 @@ async fn reload_module_cache(module: &str) -> Result<(), anyhow::Error> {
         "realtime_triggers" => db::alerts::realtime_triggers::cache().await,
         "org_users" => db::org_users::cache().await,
         "compact_retention" => db::compact::retention::cache().await,
+        "stream_alias" => db::stream_alias::cache().await,
+        "dataset" => db::dataset::cache().await,
+        "org_lifecycle" => db::org_lifecycle::cache().await,
+        "level_mapping" => db::level_mapping::cache().await,
+        "log_metrics" => db::log_metrics::cache().await,
         _ => Err(anyhow::anyhow!("unsupported module")),
     }
 }
@@ -36,15 +36,31 @@ use crate::{
         meta::{
             self,
             http::HttpResponse as MetaHttpResponse,
-            stream::{ListStream, StreamCreate, StreamDeleteFields, StreamUpdateFields},
+            stream::{
+                ClockSkewResponse, ColdStorageRestore, ColdStorageRestoreList,
+                ColdStorageRestoreRequest, DistinctValueBucketsResponse, FieldStatsResponse,
+                FieldUsageResponse, ListStream, ListStreamAlias, SchemaSuggestion, StreamAlias,
+                StreamAliasCreate, StreamCreate, StreamDeleteFields, StreamRename, StreamRetention,
+                StreamRetentionResponse, StreamUpdateFields,
+            },
         },
         utils::{
             auth::UserEmail,
             http::{get_stream_type_from_request, get_ts_from_request_with_key},
         },
     },
-    handler::http::extractors::Headers,
-    service::stream,
+    handler::http::{
+        extractors::Headers, request::search::error_utils::map_error_to_http_response,
+    },
+    service::{
+        compact::tiering,
+        field_stats, field_usage,
+        ingestion::clock_skew,
+        metadata::distinct_values::{self, DistinctValuesError},
+        schema_suggestion,
+        search::field_access,
+        stream, stream_alias,
+    },
 };
 
 /// GetSchema
@@ -140,6 +156,380 @@ pub async fn schema(
     (StatusCode::OK, Json(schema)).into_response()
 }
 
+/// GetFieldUsage
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/streams/{stream_name}/field_usage",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "StreamFieldUsage",
+    summary = "Get stream field usage",
+    description = "Lists the fields of a stream with how often searches, dashboards, alerts and reports referenced them \
+                   since the tracking started, and their estimated compressed storage size. Fields that were never \
+                   referenced are candidates for removal from the user defined schema or from the index settings",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("type" = String, Query, description = "Stream type"),
+        ("unused" = bool, Query, description = "Only list the fields never referenced"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(FieldUsageResponse)),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "List never queried fields and their storage cost", "category": "streams"}))
+    )
+)]
+pub async fn field_usage(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    let mut stream_name = stream_name;
+    if !config::get_config().common.skip_formatting_stream_name {
+        stream_name = format_stream_name(stream_name);
+    }
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    let unused_only = query
+        .get("unused")
+        .is_some_and(|v| v.eq_ignore_ascii_case("true"));
+    let trace_id = config::ider::generate_trace_id();
+    match field_usage::list(&trace_id, &org_id, stream_type, &stream_name, unused_only).await {
+        Ok(Some(resp)) => (StatusCode::OK, Json(resp)).into_response(),
+        Ok(None) => MetaHttpResponse::not_found("stream not found"),
+        Err(e) => MetaHttpResponse::internal_error(e),
+    }
+}
+
+/// GetDistinctValueBuckets
+#[utoipa::path(
+    get,
+    path = "/{org_id}/streams/{stream_name}/distinct_values",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "StreamDistinctValueBuckets",
+    summary = "Get distinct value counts per time bucket",
+    description = "Counts the most frequent values of a distinct value field per time bucket, from the distinct values \
+                   the stream keeps, to draw the sparklines of the field value facets. The field must be one of the \
+                   distinct value fields of the stream",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("type" = String, Query, description = "Stream type"),
+        ("field" = String, Query, description = "Distinct value field"),
+        ("start_time" = i64, Query, description = "Start time, in microseconds"),
+        ("end_time" = i64, Query, description = "End time, in microseconds"),
+        ("interval" = Option<String>, Query, description = "Size of the buckets, e.g. `1 hour`, derived from the time range by default"),
+        ("size" = Option<usize>, Query, description = "Number of values, 10 by default"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(DistinctValueBucketsResponse)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Count the values of a field per time bucket", "category": "streams"}))
+    )
+)]
+pub async fn distinct_value_buckets(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Headers(user_email): Headers<UserEmail>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    let mut stream_name = stream_name;
+    if !config::get_config().common.skip_formatting_stream_name {
+        stream_name = format_stream_name(stream_name);
+    }
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    let Some(field) = query.get("field").filter(|v| !v.is_empty()) else {
+        return MetaHttpResponse::bad_request("field is required");
+    };
+    if let Err(e) = field_access::check_fields(
+        &org_id,
+        &user_email.user_id,
+        stream_type,
+        &stream_name,
+        std::slice::from_ref(field),
+    )
+    .await
+    {
+        return map_error_to_http_response(&e, None);
+    }
+    let time_range = match (
+        get_ts_from_request_with_key(&query, "start_time"),
+        get_ts_from_request_with_key(&query, "end_time"),
+    ) {
+        (Ok(start), Ok(end)) => (start, end),
+        (Err(e), _) | (_, Err(e)) => return MetaHttpResponse::bad_request(e),
+    };
+    let size = query
+        .get("size")
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(10);
+    let trace_id = config::ider::generate_trace_id();
+    match distinct_values::get_value_buckets(
+        &trace_id,
+        &org_id,
+        stream_type,
+        &stream_name,
+        field,
+        time_range,
+        query.get("interval").map(|v| v.as_str()),
+        size,
+    )
+    .await
+    {
+        Ok(resp) => (StatusCode::OK, Json(resp)).into_response(),
+        Err(e @ DistinctValuesError::InvalidRequest(_)) => MetaHttpResponse::bad_request(e),
+        Err(e) => MetaHttpResponse::internal_error(e),
+    }
+}
+
+/// GetFieldStats
+#[utoipa::path(
+    get,
+    path = "/{org_id}/streams/{stream_name}/field_stats",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "StreamFieldStats",
+    summary = "Get stream field statistics",
+    description = "Returns the min, max, estimated number of distinct values and null fraction of the fields of a \
+                   stream over a time range. The statistics are merged from the metadata of the newest files in the \
+                   time range, no data is scanned, so the files overlapping the edges of the time range count all \
+                   their records. The fields denied to the user by the field access rules are left out",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("type" = String, Query, description = "Stream type"),
+        ("start_time" = i64, Query, description = "Start time, in microseconds"),
+        ("end_time" = i64, Query, description = "End time, in microseconds"),
+        ("fields" = Option<String>, Query, description = "Comma separated fields, all the fields by default"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(FieldStatsResponse)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Get min, max, distinct count and null fraction of fields", "category": "streams"}))
+    )
+)]
+pub async fn field_stats(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Headers(user_email): Headers<UserEmail>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    let mut stream_name = stream_name;
+    if !config::get_config().common.skip_formatting_stream_name {
+        stream_name = format_stream_name(stream_name);
+    }
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    let time_range = match (
+        get_ts_from_request_with_key(&query, "start_time"),
+        get_ts_from_request_with_key(&query, "end_time"),
+    ) {
+        (Ok(start), Ok(end)) => (start, end),
+        (Err(e), _) | (_, Err(e)) => return MetaHttpResponse::bad_request(e),
+    };
+    let fields = query
+        .get("fields")
+        .map(|v| {
+            v.split(',')
+                .map(|f| f.trim().to_string())
+                .filter(|f| !f.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    // the statistics of the fields denied to the user are left out
+    let denied =
+        field_access::denied_fields(&org_id, &user_email.user_id, stream_type, &stream_name).await;
+    let trace_id = config::ider::generate_trace_id();
+    match field_stats::list(
+        &trace_id,
+        &org_id,
+        stream_type,
+        &stream_name,
+        time_range,
+        &fields,
+    )
+    .await
+    {
+        Ok(Some(mut resp)) => {
+            resp.fields.retain(|f| !denied.contains(&f.name));
+            (StatusCode::OK, Json(resp)).into_response()
+        }
+        Ok(None) => MetaHttpResponse::not_found("stream not found"),
+        Err(e) => MetaHttpResponse::internal_error(e),
+    }
+}
+
+/// ListClockSkew
+#[utoipa::path(
+    get,
+    path = "/{org_id}/clock_skew",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "ListClockSkew",
+    summary = "List skewed sources",
+    description = "Lists the sources of the streams tracking their clock skew, the most skewed first. The skew of a \
+                   source is the moving average of the offset of the timestamps of its records from their arrival \
+                   time, negative when its clock is behind, which hides its recent records from the searches of the \
+                   last minutes",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("type" = Option<String>, Query, description = "Stream type, all the stream types by default"),
+        ("limit" = Option<usize>, Query, description = "Number of sources, 20 by default"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(ClockSkewResponse)),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "list"})),
+        ("x-o2-mcp" = json!({"description": "List the sources with the most clock skew", "category": "streams"}))
+    )
+)]
+pub async fn list_clock_skew(
+    Path(org_id): Path<String>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    let stream_type = get_stream_type_from_request(&query);
+    let limit = query
+        .get("limit")
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(20);
+    match clock_skew::list(&org_id, stream_type, limit).await {
+        Ok(resp) => (StatusCode::OK, Json(resp)).into_response(),
+        Err(e) => MetaHttpResponse::internal_error(e),
+    }
+}
+
+/// GetSchemaSuggestion
+#[utoipa::path(
+    get,
+    path = "/{org_id}/streams/{stream_name}/schema_suggestion",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "StreamSchemaSuggestion",
+    summary = "Get user defined schema suggestion",
+    description = "Suggests a user defined schema and secondary index fields for a stream from the fields searches \
+                   reference and the presence and cardinality of fields in its newest records. Wide streams are \
+                   analyzed periodically in the background, `refresh=true` analyzes the stream right away",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("type" = String, Query, description = "Stream type"),
+        ("refresh" = bool, Query, description = "Analyze the stream instead of returning the last suggestion"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(SchemaSuggestion)),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Suggest user defined schema and index fields", "category": "streams"}))
+    )
+)]
+pub async fn schema_suggestion(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    let mut stream_name = stream_name;
+    if !config::get_config().common.skip_formatting_stream_name {
+        stream_name = format_stream_name(stream_name);
+    }
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    let refresh = query
+        .get("refresh")
+        .is_some_and(|v| v.eq_ignore_ascii_case("true"));
+    if !refresh
+        && let Some(suggestion) = schema_suggestion::get(&org_id, stream_type, &stream_name).await
+    {
+        return (StatusCode::OK, Json(suggestion)).into_response();
+    }
+    let trace_id = config::ider::generate_trace_id();
+    match schema_suggestion::refresh(&trace_id, &org_id, stream_type, &stream_name).await {
+        Ok(Some(suggestion)) => (StatusCode::OK, Json(suggestion)).into_response(),
+        Ok(None) => MetaHttpResponse::not_found("stream not found"),
+        Err(e) => MetaHttpResponse::internal_error(e),
+    }
+}
+
+/// ApplySchemaSuggestion
+#[utoipa::path(
+    post,
+    path = "/{org_id}/streams/{stream_name}/schema_suggestion/apply",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "StreamApplySchemaSuggestion",
+    summary = "Apply user defined schema suggestion",
+    description = "Updates the stream settings to the last suggestion: the user defined schema becomes the suggested \
+                   one and the suggested secondary index fields are added. Records ingested before keep their fields",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("type" = String, Query, description = "Stream type"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "update"})),
+        ("x-o2-mcp" = json!({"description": "Apply the user defined schema suggestion", "category": "streams"}))
+    )
+)]
+pub async fn apply_schema_suggestion(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    let mut stream_name = stream_name;
+    if !config::get_config().common.skip_formatting_stream_name {
+        stream_name = format_stream_name(stream_name);
+    }
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    let Some(suggestion) = schema_suggestion::get(&org_id, stream_type, &stream_name).await else {
+        return MetaHttpResponse::not_found("no schema suggestion for the stream");
+    };
+    let Some(settings) = infra::schema::get_settings(&org_id, &stream_name, stream_type).await
+    else {
+        return MetaHttpResponse::not_found("stream not found");
+    };
+    let update = schema_suggestion::to_settings_update(&suggestion, &settings);
+    match stream::update_stream_settings(&org_id, &stream_name, stream_type, update).await {
+        Ok(resp) => resp,
+        Err(e) => MetaHttpResponse::internal_error(e),
+    }
+}
+
 /// CreateStream
 #[utoipa::path(
     post,
@@ -258,6 +648,192 @@ pub async fn update_settings(
     }
 }
 
+/// GetStreamRetention
+#[utoipa::path(
+    get,
+    path = "/{org_id}/streams/{stream_name}/retention",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "GetStreamRetention",
+    summary = "Get stream retention policy",
+    description = "Returns the retention policy of the stream: the days its data is kept, 0 when it falls back to \
+                   the retention of the cluster, whether the expired files are archived, and the days the data is \
+                   actually kept",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("type" = String, Query, description = "Stream type"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(StreamRetentionResponse)),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Get the retention policy of a stream", "category": "streams"}))
+    )
+)]
+pub async fn get_retention(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    let mut stream_name = stream_name;
+    if !config::get_config().common.skip_formatting_stream_name {
+        stream_name = format_stream_name(stream_name);
+    }
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    match stream::get_retention(&org_id, &stream_name, stream_type).await {
+        Some(retention) => (StatusCode::OK, Json(retention)).into_response(),
+        None => MetaHttpResponse::not_found("stream not found"),
+    }
+}
+
+/// SetStreamRetention
+#[utoipa::path(
+    put,
+    path = "/{org_id}/streams/{stream_name}/retention",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "SetStreamRetention",
+    summary = "Set stream retention policy",
+    description = "Sets the days the data of the stream is kept, 0 falls back to the retention of the cluster. With \
+                   archive enabled the compactor copies the expired files to the archive storage before deleting \
+                   them, the deletions by time range are never archived",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("type" = String, Query, description = "Stream type"),
+    ),
+    request_body(content = inline(StreamRetention), description = "Retention policy", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "update"})),
+        ("x-o2-mcp" = json!({"description": "Set the retention policy of a stream", "category": "streams"}))
+    )
+)]
+pub async fn set_retention(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+    Json(retention): Json<StreamRetention>,
+) -> Response {
+    let mut stream_name = stream_name;
+    if !config::get_config().common.skip_formatting_stream_name {
+        stream_name = format_stream_name(stream_name);
+    }
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    if stream_type == StreamType::EnrichmentTables || stream_type == StreamType::Index {
+        return MetaHttpResponse::bad_request(format!("Stream type '{stream_type}' not allowed"));
+    }
+    match stream::set_retention(&org_id, &stream_name, stream_type, retention).await {
+        Ok(resp) => resp,
+        Err(e) => MetaHttpResponse::internal_error(e),
+    }
+}
+
+/// RestoreStreamColdStorage
+#[utoipa::path(
+    post,
+    path = "/{org_id}/streams/{stream_name}/cold_storage/restore",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "RestoreStreamColdStorage",
+    summary = "Restore stream data from the cold storage",
+    description = "Copies the files of the time range moved to the cold storage back to the storage of the stream, \
+                   in the background, so the searches can read them. They are moved back to the cold storage once \
+                   the restore expires",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("type" = String, Query, description = "Stream type"),
+    ),
+    request_body(content = inline(ColdStorageRestoreRequest), description = "Time range to restore, in microseconds", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(ColdStorageRestore)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "update"})),
+        ("x-o2-mcp" = json!({"description": "Restore the data of a stream from the cold storage", "category": "streams"}))
+    )
+)]
+pub async fn restore_cold_storage(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+    Json(req): Json<ColdStorageRestoreRequest>,
+) -> Response {
+    let mut stream_name = stream_name;
+    if !config::get_config().common.skip_formatting_stream_name {
+        stream_name = format_stream_name(stream_name);
+    }
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    if infra::schema::get_settings(&org_id, &stream_name, stream_type)
+        .await
+        .is_none()
+    {
+        return MetaHttpResponse::not_found("stream not found");
+    }
+    match tiering::restore(&org_id, stream_type, &stream_name, req).await {
+        Ok(restore) => (StatusCode::OK, Json(restore)).into_response(),
+        Err(e) => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// ListStreamColdStorageRestores
+#[utoipa::path(
+    get,
+    path = "/{org_id}/streams/{stream_name}/cold_storage/restore",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "ListStreamColdStorageRestores",
+    summary = "List stream cold storage restores",
+    description = "Returns the restores of the stream from the cold storage with their status, they are removed once expired",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("type" = String, Query, description = "Stream type"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(ColdStorageRestoreList)),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "List the cold storage restores of a stream", "category": "streams"}))
+    )
+)]
+pub async fn list_cold_storage_restores(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    let mut stream_name = stream_name;
+    if !config::get_config().common.skip_formatting_stream_name {
+        stream_name = format_stream_name(stream_name);
+    }
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    match tiering::list_restores(&org_id, stream_type, &stream_name).await {
+        Ok(list) => (StatusCode::OK, Json(ColdStorageRestoreList { list })).into_response(),
+        Err(e) => MetaHttpResponse::internal_error(e),
+    }
+}
+
 /// UpdateStreamFields
 ///
 /// #{"ratelimit_module":"Streams", "ratelimit_module_operation":"update"}#
@@ -425,6 +1001,172 @@ pub async fn delete(
     }
 }
 
+/// ListStreamAliases
+#[utoipa::path(
+    get,
+    path = "/{org_id}/streams/{stream_name}/aliases",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "StreamAliasList",
+    summary = "List stream aliases",
+    description = "Lists the other names the stream is searched by, including the names it was renamed to",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("type" = String, Query, description = "Stream type"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(ListStreamAlias)),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "List stream aliases", "category": "streams"}))
+    )
+)]
+pub async fn list_aliases(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    let stream_name =
+        stream_alias::resolve(&org_id, stream_type, &stream_name).unwrap_or(stream_name);
+    let list = stream_alias::list(&org_id, stream_type, Some(&stream_name));
+    (StatusCode::OK, Json(ListStreamAlias { list })).into_response()
+}
+
+/// CreateStreamAlias
+#[utoipa::path(
+    post,
+    path = "/{org_id}/streams/{stream_name}/aliases",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "StreamAliasCreate",
+    summary = "Create stream alias",
+    description = "Adds another name to the stream. Searches, alerts and dashboards querying the alias query the \
+                   stream, and the records ingested into the alias go to the stream. The alias can't be the name of \
+                   an existing stream",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("type" = String, Query, description = "Stream type"),
+    ),
+    request_body(content = inline(StreamAliasCreate), description = "Stream alias", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(StreamAlias)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "update"})),
+        ("x-o2-mcp" = json!({"description": "Create a stream alias", "category": "streams"}))
+    )
+)]
+pub async fn create_alias(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+    Json(req): Json<StreamAliasCreate>,
+) -> Response {
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    match stream_alias::create(&org_id, stream_type, &stream_name, &req.alias, false).await {
+        Ok(alias) => (StatusCode::OK, Json(alias)).into_response(),
+        Err(e) => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// DeleteStreamAlias
+#[utoipa::path(
+    delete,
+    path = "/{org_id}/streams/{stream_name}/aliases/{alias}",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "StreamAliasDelete",
+    summary = "Delete stream alias",
+    description = "Removes an alias of the stream, the queries using it stop working",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("alias" = String, Path, description = "Alias"),
+        ("type" = String, Query, description = "Stream type"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "update"})),
+        ("x-o2-mcp" = json!({"description": "Delete a stream alias", "category": "streams"}))
+    )
+)]
+pub async fn delete_alias(
+    Path((org_id, stream_name, alias)): Path<(String, String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    let stream_name =
+        stream_alias::resolve(&org_id, stream_type, &stream_name).unwrap_or(stream_name);
+    if stream_alias::resolve(&org_id, stream_type, &alias).as_deref() != Some(stream_name.as_str())
+    {
+        return MetaHttpResponse::not_found("stream alias not found");
+    }
+    match stream_alias::delete(&org_id, stream_type, &alias).await {
+        Ok(_) => MetaHttpResponse::ok("stream alias deleted"),
+        Err(e) => MetaHttpResponse::internal_error(e),
+    }
+}
+
+/// RenameStream
+#[utoipa::path(
+    post,
+    path = "/{org_id}/streams/{stream_name}/rename",
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "StreamRename",
+    summary = "Rename stream",
+    description = "Renames the stream without moving its data: the new name becomes an alias by which the stream is \
+                   searched and ingested into, and the old name keeps working so the saved queries, alerts and \
+                   dashboards using it don't break",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("type" = String, Query, description = "Stream type"),
+    ),
+    request_body(content = inline(StreamRename), description = "New stream name", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(StreamAlias)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Streams", "operation": "update"})),
+        ("x-o2-mcp" = json!({"description": "Rename a stream", "category": "streams"}))
+    )
+)]
+pub async fn rename(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+    Json(req): Json<StreamRename>,
+) -> Response {
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    if stream_type == StreamType::EnrichmentTables || stream_type == StreamType::Index {
+        return MetaHttpResponse::bad_request(format!("Stream type '{stream_type}' not allowed"));
+    }
+    match stream_alias::rename(&org_id, stream_type, &stream_name, &req.new_name).await {
+        Ok(alias) => (StatusCode::OK, Json(alias)).into_response(),
+        Err(e) => MetaHttpResponse::bad_request(e),
+    }
+}
+
 /// ListStreams
 
 #[utoipa::path(
@@ -614,7 +1356,7 @@ fn stream_comparator(
     tag = "Streams",
     operation_id = "StreamDeleteCache",
     summary = "Delete stream result cache",
-    description = "Clears cached search results for a stream. Optionally specify a timestamp to retain cache from that point forward and delete older cache",
+    description = "Clears cached search results for a stream. Optionally specify a timestamp to retain cache from that point forward and delete older cache, or a time range to only delete the cache overlapping it, e.g. after backfilling or deleting data",
     security(
         ("Authorization"= [])
     ),
@@ -623,6 +1365,8 @@ fn stream_comparator(
         ("stream_name" = String, Path, description = "Stream name"),
         ("type" = String, Query, description = "Stream type"),
         ("ts" = i64, Query, description = "Timestamp in microseconds. If provided, must be > 0. Cache from this timestamp onwards will be retained, older cache will be deleted."),
+        ("start" = Option<i64>, Query, description = "Start time in microseconds, with end only the cache overlapping the time range is deleted"),
+        ("end" = Option<i64>, Query, description = "End time in microseconds"),
     ),
     responses(
         (status = 200, description = "Success", content_type = "application/json", body = Object),
@@ -653,6 +1397,31 @@ pub async fn delete_stream_cache(
     }
     let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
     let delete_ts = get_ts_from_request_with_key(&query, "ts").unwrap_or(0);
+    let time_range = match (query.contains_key("start"), query.contains_key("end")) {
+        (false, false) => None,
+        _ => match get_ts_from_request_with_key(&query, "start")
+            .and_then(|start| Ok((start, get_ts_from_request_with_key(&query, "end")?)))
+        {
+            Ok((start, end)) if start < end => Some((start, end)),
+            Ok(_) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(MetaHttpResponse::error(
+                        StatusCode::BAD_REQUEST,
+                        "start must be less than end",
+                    )),
+                )
+                    .into_response();
+            }
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(MetaHttpResponse::error(StatusCode::BAD_REQUEST, e)),
+                )
+                    .into_response();
+            }
+        },
+    };
 
     let path = if stream_name.eq("_all") {
         org_id
@@ -660,7 +1429,11 @@ pub async fn delete_stream_cache(
         format!("{org_id}/{stream_type}/{stream_name}")
     };
 
-    match crate::service::search::cluster::cacher::delete_cached_results(path, delete_ts).await {
+    match crate::service::search::cluster::cacher::delete_cached_results(
+        path, delete_ts, time_range,
+    )
+    .await
+    {
         true => (
             StatusCode::OK,
             Json(MetaHttpResponse::message(
@@ -689,7 +1462,7 @@ pub async fn delete_stream_cache(
     tag = "Streams",
     operation_id = "StreamDeleteDataByTimeRange",
     summary = "Delete stream data by time range",
-    description = "Creates a deletion job to permanently remove stream data within the specified time range. Returns a job ID to track the deletion progress",
+    description = "Creates a deletion job to permanently remove stream data within the specified time range. Returns a job ID to track the deletion progress. A range not aligned to the hours, or days, of the partitions of the stream only removes the records inside it, the files of its bounds are rewritten",
     security(
         ("Authorization"= [])
     ),
@@ -13,12 +13,12 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use axum::{body::Bytes, extract::Path, http::HeaderMap, response::Response};
+use axum::{body::Body, extract::Path, http::HeaderMap, response::Response};
 use config::{
     TIMESTAMP_COL_NAME,
     axum::middlewares::{get_process_time, insert_process_time_header},
     get_config,
-    meta::{search::default_use_cache, stream::StreamType},
+    meta::{search::default_use_cache, service_graph::ServiceGraphData, stream::StreamType},
     metrics,
     utils::json,
 };
@@ -72,7 +72,7 @@ pub async fn traces_write(
     Path(org_id): Path<String>,
     Headers(user_email): Headers<UserEmail>,
     headers: HeaderMap,
-    body: Bytes,
+    body: Body,
 ) -> Response {
     // log start processing time
     let process_time = get_process_time();
@@ -572,6 +572,226 @@ pub async fn get_latest_traces(
     MetaHttpResponse::json(resp)
 }
 
+/// GetTracesByAttribute
+#[utoipa::path(
+    get,
+    path = "/{org_id}/{stream_name}/traces/by_attribute",
+    context_path = "/api",
+    tag = "Traces",
+    operation_id = "GetTracesByAttribute",
+    summary = "Find traces by span attribute",
+    description = "Finds the traces having a span whose attribute equals a value within a time range, and returns their spans. The trace ids are looked up from the secondary index files only, so both the attribute and trace_id must be secondary index fields of the stream.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("attribute" = String, Query, description = "Span attribute, a secondary index field"),
+        ("value" = String, Query, description = "Value of the attribute"),
+        ("size" = Option<i64>, Query, description = "Maximum number of traces, default 10"),
+        ("start_time" = i64, Query, description = "start time"),
+        ("end_time" = i64, Query, description = "end time"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(traces::by_attribute::TracesByAttributeResponse), example = json!({
+            "took": 35,
+            "total": 1,
+            "traces": [
+                {
+                    "trace_id": "12345678",
+                    "spans": [{"span_id": "1", "user_id": "alice"}]
+                }
+            ],
+            "is_partial": false
+        })),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    )
+)]
+pub async fn get_traces_by_attribute(
+    Path((org_id, stream_name)): Path<(String, String)>,
+    axum::extract::Query(query): axum::extract::Query<HashMap<String, String>>,
+    headers: HeaderMap,
+    Headers(user_email): Headers<UserEmail>,
+) -> Response {
+    let user_id = &user_email.user_id;
+
+    #[cfg(feature = "enterprise")]
+    {
+        if let Err(e) = crate::service::search::check_search_allowed(&org_id, Some(&stream_name)) {
+            return MetaHttpResponse::too_many_requests(e.to_string());
+        }
+        if let Some(res) = crate::handler::http::request::search::utils::check_stream_permissions(
+            &stream_name,
+            &org_id,
+            user_id,
+            &StreamType::Traces,
+        )
+        .await
+        {
+            return res;
+        }
+    }
+
+    let Some(attribute) = query.get("attribute").filter(|v| !v.is_empty()) else {
+        return MetaHttpResponse::bad_request("attribute is empty");
+    };
+    let Some(value) = query.get("value") else {
+        return MetaHttpResponse::bad_request("value is empty");
+    };
+    let size = query
+        .get("size")
+        .map_or(10, |v| v.parse::<i64>().unwrap_or(10));
+    let mut start_time = query
+        .get("start_time")
+        .map_or(0, |v| v.parse::<i64>().unwrap_or(0));
+    if start_time == 0 {
+        return MetaHttpResponse::bad_request("start_time is empty");
+    }
+    let end_time = query
+        .get("end_time")
+        .map_or(0, |v| v.parse::<i64>().unwrap_or(0));
+    if end_time == 0 {
+        return MetaHttpResponse::bad_request("end_time is empty");
+    }
+
+    let max_query_range = crate::common::utils::stream::get_max_query_range(
+        std::slice::from_ref(&stream_name),
+        org_id.as_str(),
+        user_id,
+        StreamType::Traces,
+    )
+    .await;
+    if max_query_range > 0 && (end_time - start_time) > max_query_range * 3600 * 1_000_000 {
+        start_time = end_time - max_query_range * 3600 * 1_000_000;
+    }
+
+    let trace_id = get_or_create_trace_id(&headers, &Span::none());
+    let attribute_query = traces::by_attribute::AttributeQuery {
+        attribute: attribute.to_string(),
+        value: value.to_string(),
+        start_time,
+        end_time,
+        size,
+    };
+    match traces::by_attribute::search(
+        &trace_id,
+        &org_id,
+        &stream_name,
+        Some(user_id.to_string()),
+        &attribute_query,
+    )
+    .await
+    {
+        Ok(resp) => MetaHttpResponse::json(resp),
+        Err(err) => {
+            log::error!("[trace_id {trace_id}] get traces by attribute error: {err:?}");
+            map_error_to_http_response(&err, Some(trace_id))
+        }
+    }
+}
+
+/// GetServiceMap
+#[utoipa::path(
+    get,
+    path = "/{org_id}/traces/service_map",
+    context_path = "/api",
+    tag = "Traces",
+    operation_id = "GetServiceMap",
+    summary = "Get the service map",
+    description = "Returns the services of the trace streams and the calls between them over a time range, rounded to \
+                   whole hours. A span whose parent belongs to another service is a call from that service, the edges \
+                   carry the number of calls, their error rate in percent and the p50, p95 and p99 of their latency in \
+                   nanoseconds. The map is built at ingestion from the last 7 days of spans.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = Option<String>, Query, description = "Trace stream, all the trace streams by default"),
+        ("start_time" = Option<i64>, Query, description = "Start time, in microseconds, an hour ago by default"),
+        ("end_time" = Option<i64>, Query, description = "End time, in microseconds, now by default"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(ServiceGraphData), example = json!({
+            "nodes": [
+                {"id": "checkout", "label": "checkout", "requests": 120, "errors": 3, "error_rate": 2.5},
+                {"id": "frontend", "label": "frontend", "requests": 0, "errors": 0, "error_rate": 0.0}
+            ],
+            "edges": [
+                {
+                    "from": "frontend",
+                    "to": "checkout",
+                    "total_requests": 120,
+                    "failed_requests": 3,
+                    "error_rate": 2.5,
+                    "p50_latency_ns": 19027314,
+                    "p95_latency_ns": 53817370,
+                    "p99_latency_ns": 90509668,
+                    "connection_type": "standard"
+                }
+            ]
+        })),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+        (status = 500, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Traces", "operation": "list"})),
+        ("x-o2-mcp" = json!({"description": "Get the calls between services with latency and error rate", "category": "traces"}))
+    )
+)]
+pub async fn get_service_map(
+    Path(org_id): Path<String>,
+    axum::extract::Query(query): axum::extract::Query<HashMap<String, String>>,
+    #[allow(unused_variables)] Headers(user_email): Headers<UserEmail>,
+) -> Response {
+    let stream_name = query.get("stream_name").filter(|v| !v.is_empty());
+
+    #[cfg(feature = "enterprise")]
+    if let Some(stream_name) = stream_name
+        && let Some(res) = crate::handler::http::request::search::utils::check_stream_permissions(
+            stream_name,
+            &org_id,
+            &user_email.user_id,
+            &StreamType::Traces,
+        )
+        .await
+    {
+        return res;
+    }
+
+    let end_time = match query.get("end_time") {
+        Some(v) => match v.parse::<i64>() {
+            Ok(v) => v,
+            Err(_) => return MetaHttpResponse::bad_request("end_time is not a valid timestamp"),
+        },
+        None => config::utils::time::now_micros(),
+    };
+    let start_time = match query.get("start_time") {
+        Some(v) => match v.parse::<i64>() {
+            Ok(v) => v,
+            Err(_) => return MetaHttpResponse::bad_request("start_time is not a valid timestamp"),
+        },
+        None => end_time - 3600 * 1_000_000,
+    };
+    if start_time >= end_time {
+        return MetaHttpResponse::bad_request("start_time must be before end_time");
+    }
+
+    match traces::service_map::get(
+        &org_id,
+        stream_name.map(|s| s.as_str()),
+        start_time,
+        end_time,
+    )
+    .await
+    {
+        Ok(resp) => MetaHttpResponse::json(resp),
+        Err(e) => MetaHttpResponse::internal_error(e),
+    }
+}
+
 #[derive(Debug, Serialize)]
 struct TraceResponseItem {
     trace_id: String,
@@ -0,0 +1,334 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use axum::{
+    Json,
+    body::Bytes,
+    extract::{Path, Query},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use config::axum::middlewares::{get_process_time, insert_process_time_header};
+#[cfg(feature = "cloud")]
+use config::meta::stream::StreamType;
+use hashbrown::HashMap;
+
+#[cfg(feature = "cloud")]
+use crate::service::ingestion::check_ingestion_allowed;
+use crate::{
+    common::meta::{
+        http::HttpResponse as MetaHttpResponse,
+        webhook::{
+            WebhookKind, WebhookSource, WebhookSourceList, WebhookTemplate, WebhookTemplateList,
+        },
+    },
+    service::{ingestion::get_thread_id, webhook},
+};
+
+/// ListWebhookSources
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/webhooks",
+    context_path = "/api",
+    tag = "Webhooks",
+    operation_id = "ListWebhookSources",
+    summary = "List webhook sources",
+    description = "Lists the catch-all webhook sources of the organization. Secrets are never returned.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(WebhookSourceList)),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Webhooks", "operation": "list"})),
+        ("x-o2-mcp" = json!({"description": "List webhook sources", "category": "ingestion"}))
+    )
+)]
+pub async fn list(Path(org_id): Path<String>) -> Response {
+    match webhook::list(&org_id).await {
+        Ok(list) => MetaHttpResponse::json(WebhookSourceList {
+            list: list.iter().map(|v| v.redacted()).collect(),
+        }),
+        Err(e) => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// ListWebhookTemplates
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/webhook_templates",
+    context_path = "/api",
+    tag = "Webhooks",
+    operation_id = "ListWebhookTemplates",
+    summary = "List webhook mapping templates",
+    description = "Lists the JSONPath mappings of the payloads GitHub, Stripe and PagerDuty post, to start the mapping \
+                   of a new source from.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(WebhookTemplateList)),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Webhooks", "operation": "list"})),
+        ("x-o2-mcp" = json!({"description": "List webhook mapping templates", "category": "ingestion"}))
+    )
+)]
+pub async fn templates(Path(_org_id): Path<String>) -> Response {
+    let list = [
+        WebhookKind::Github,
+        WebhookKind::Stripe,
+        WebhookKind::Pagerduty,
+    ]
+    .into_iter()
+    .map(WebhookTemplate::from)
+    .collect();
+    MetaHttpResponse::json(WebhookTemplateList { list })
+}
+
+/// GetWebhookSource
+
+#[utoipa::path(
+    get,
+    path = "/{org_id}/webhooks/{name}",
+    context_path = "/api",
+    tag = "Webhooks",
+    operation_id = "GetWebhookSource",
+    summary = "Get webhook source",
+    description = "Retrieves a webhook source without its secret.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "Webhook source name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = inline(WebhookSource)),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Webhooks", "operation": "get"})),
+        ("x-o2-mcp" = json!({"description": "Get webhook source details", "category": "ingestion"}))
+    )
+)]
+pub async fn get(Path((org_id, name)): Path<(String, String)>) -> Response {
+    match webhook::get(&org_id, &name).await {
+        Ok(source) => MetaHttpResponse::json(source.redacted()),
+        Err(_) => MetaHttpResponse::not_found("webhook source not found"),
+    }
+}
+
+/// CreateWebhookSource
+
+#[utoipa::path(
+    post,
+    path = "/{org_id}/webhooks",
+    context_path = "/api",
+    tag = "Webhooks",
+    operation_id = "CreateWebhookSource",
+    summary = "Create webhook source",
+    description = "Creates a catch-all webhook source. Events posted to `/webhooks/{org_id}/{name}` are checked against \
+                   the source's secret the way the sender signs them (GitHub, Stripe, PagerDuty, or HMAC-SHA256 in a \
+                   custom header), optionally mapped with JSONPath and transformed with VRL, and ingested into \
+                   `stream_name`.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = inline(WebhookSource), description = "Webhook source", content_type = "application/json", example = json!({
+        "name": "github",
+        "kind": "github",
+        "stream_name": "github_audit",
+        "secret": "webhook-secret",
+        "mapping": {
+            "action": "$.action",
+            "repository": "$.repository.full_name",
+            "sender": "$.sender.login"
+        },
+        "keep_payload": false
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({"code": 200, "message": "Webhook source saved"})),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Webhooks", "operation": "create"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn create(Path(org_id): Path<String>, Json(source): Json<WebhookSource>) -> Response {
+    match webhook::save(&org_id, source, true).await {
+        Ok(_) => MetaHttpResponse::ok("Webhook source saved"),
+        Err(e) => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// UpdateWebhookSource
+
+#[utoipa::path(
+    put,
+    path = "/{org_id}/webhooks/{name}",
+    context_path = "/api",
+    tag = "Webhooks",
+    operation_id = "UpdateWebhookSource",
+    summary = "Update webhook source",
+    description = "Replaces a webhook source. Leave `secret` empty to keep the current secret.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "Webhook source name"),
+    ),
+    request_body(content = inline(WebhookSource), description = "Webhook source", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({"code": 200, "message": "Webhook source saved"})),
+        (status = 400, description = "Failure", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Webhooks", "operation": "update"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn update(
+    Path((org_id, name)): Path<(String, String)>,
+    Json(mut source): Json<WebhookSource>,
+) -> Response {
+    source.name = name;
+    match webhook::save(&org_id, source, false).await {
+        Ok(_) => MetaHttpResponse::ok("Webhook source saved"),
+        Err(e) => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// DeleteWebhookSource
+
+#[utoipa::path(
+    delete,
+    path = "/{org_id}/webhooks/{name}",
+    context_path = "/api",
+    tag = "Webhooks",
+    operation_id = "DeleteWebhookSource",
+    summary = "Delete webhook source",
+    description = "Deletes a webhook source, its URL stops accepting events.",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "Webhook source name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Object, example = json!({"code": 200, "message": "Webhook source deleted"})),
+        (status = 404, description = "NotFound", content_type = "application/json", body = ()),
+    ),
+    extensions(
+        ("x-o2-ratelimit" = json!({"module": "Webhooks", "operation": "delete"})),
+        ("x-o2-mcp" = json!({"enabled": false}))
+    )
+)]
+pub async fn delete(Path((org_id, name)): Path<(String, String)>) -> Response {
+    match webhook::delete(&org_id, &name).await {
+        Ok(_) => MetaHttpResponse::ok("Webhook source deleted"),
+        Err(e) => MetaHttpResponse::not_found(e),
+    }
+}
+
+/// Receives events for a webhook source. The route is not behind the
+/// authentication middleware, the signature made with the source's secret is
+/// the authentication.
+pub async fn receive(
+    Path((org_id, name)): Path<(String, String)>,
+    Query(params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    // unknown sources and bad signatures get the same answer
+    let source = match webhook::get(&org_id, &name).await {
+        Ok(v) => v,
+        Err(_) => return MetaHttpResponse::unauthorized("invalid webhook signature"),
+    };
+    let now = chrono::Utc::now().timestamp();
+    if let Err(e) = webhook::verify(
+        &source,
+        &headers,
+        params.get("token").map(|v| v.as_str()),
+        &body,
+        now,
+    ) {
+        log::warn!("[WEBHOOK] rejected request for {org_id}/{name}: {e}");
+        return MetaHttpResponse::unauthorized("invalid webhook signature");
+    }
+
+    #[cfg(feature = "cloud")]
+    if let Err(e) = check_ingestion_allowed(&org_id, StreamType::Logs, None).await {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(MetaHttpResponse::error(StatusCode::TOO_MANY_REQUESTS, e)),
+        )
+            .into_response();
+    }
+
+    // log start processing time
+    let process_time = get_process_time();
+
+    let thread_id = get_thread_id();
+    let mut resp = match webhook::ingest(thread_id, &org_id, &source, &headers, &body).await {
+        Ok(v) => {
+            if v.code > 299 {
+                (StatusCode::BAD_REQUEST, Json(v)).into_response()
+            } else {
+                MetaHttpResponse::json(v)
+            }
+        }
+        Err(e) => {
+            // we do not want to log trial period expired and quota errors
+            if !matches!(
+                e,
+                infra::errors::Error::TrialPeriodExpired | infra::errors::Error::QuotaExceeded(_)
+            ) {
+                log::error!("Error processing request {org_id}/webhooks/{name}: {e}");
+            }
+            if matches!(e, infra::errors::Error::ResourceError(_)) {
+                (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    Json(MetaHttpResponse::error(StatusCode::SERVICE_UNAVAILABLE, e)),
+                )
+                    .into_response()
+            } else if matches!(e, infra::errors::Error::QuotaExceeded(_)) {
+                MetaHttpResponse::too_many_requests(e)
+            } else {
+                MetaHttpResponse::bad_request(e)
+            }
+        }
+    };
+
+    insert_process_time_header(process_time, resp.headers_mut());
+
+    resp
+}
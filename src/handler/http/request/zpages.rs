@@ -0,0 +1,73 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! HTTP handlers for the OpenTelemetry Collector compatible zPages
+
+use axum::{
+    Json,
+    extract::Query,
+    response::{Html, IntoResponse, Response},
+};
+use serde::Deserialize;
+
+use crate::service::zpages;
+
+#[derive(Deserialize)]
+pub struct ZpagesQuery {
+    /// `html` (default, like the collector) or `json`
+    format: Option<String>,
+}
+
+impl ZpagesQuery {
+    fn is_json(&self) -> bool {
+        self.format
+            .as_deref()
+            .is_some_and(|v| v.eq_ignore_ascii_case("json"))
+    }
+}
+
+/// GET /debug/health
+///
+/// Same response as the collector's `health_check` extension. Unauthenticated
+/// like `/healthz`, for the probes.
+pub async fn health() -> impl IntoResponse {
+    Json(zpages::health())
+}
+
+/// GET /debug/servicez
+///
+/// Build, node and process information. Requires authentication.
+pub async fn servicez(Query(query): Query<ZpagesQuery>) -> Response {
+    let page = zpages::servicez();
+    if query.is_json() {
+        Json(page).into_response()
+    } else {
+        Html(page.to_html()).into_response()
+    }
+}
+
+/// GET /debug/pipelinez
+///
+/// Accepted, refused and dropped records per ingestion receiver since the
+/// node started, and the current size of the queues behind them. Requires
+/// authentication.
+pub async fn pipelinez(Query(query): Query<ZpagesQuery>) -> Response {
+    let page = zpages::pipelinez();
+    if query.is_json() {
+        Json(page).into_response()
+    } else {
+        Html(page.to_html()).into_response()
+    }
+}
@@ -16,7 +16,7 @@
 //! Preprocessing middleware for Content-Encoding header to support snappy pass-through.
 //!
 //! This middleware removes `Content-Encoding: snappy` before the request reaches
-//! tower_http's RequestDecompressionLayer (which only supports gzip/deflate/brotli).
+//! tower_http's RequestDecompressionLayer (which only supports gzip/deflate/brotli/zstd).
 //! This allows handlers like Prometheus remote write to manually decompress snappy data.
 
 use axum::{extract::Request, http::header, middleware::Next, response::Response};
@@ -31,7 +31,7 @@ pub const X_ORIGINAL_ENCODING: &str = "x-original-content-encoding";
 /// - Removes the Content-Encoding header (so tower_http doesn't return 415)
 /// - Adds X-Original-Content-Encoding: snappy (so handler knows to decompress)
 ///
-/// All other encodings (gzip, deflate, brotli, identity) pass through unchanged
+/// All other encodings (gzip, deflate, brotli, zstd, identity) pass through unchanged
 /// and are handled by tower_http's RequestDecompressionLayer.
 pub async fn preprocess_encoding_middleware(mut request: Request, next: Next) -> Response {
     // Check if Content-Encoding is snappy
@@ -165,4 +165,28 @@ mod tests {
 
         assert_eq!(body_str, "content-encoding:none,original:none");
     }
+
+    #[tokio::test]
+    async fn test_zstd_decompression() {
+        let app = Router::new()
+            .route("/test", post(|body: axum::body::Bytes| async move { body }))
+            .layer(tower_http::decompression::RequestDecompressionLayer::new())
+            .layer(middleware::from_fn(preprocess_encoding_middleware));
+
+        let payload = "otlp payload ".repeat(1000);
+        let request = Request::builder()
+            .uri("/test")
+            .method("POST")
+            .header("Content-Encoding", "zstd")
+            .body(Body::from(zstd::encode_all(payload.as_bytes(), 3).unwrap()))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, payload.as_bytes());
+    }
 }
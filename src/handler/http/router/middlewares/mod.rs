@@ -14,5 +14,7 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 mod org_blocking;
+mod retry_after;
 
 pub use org_blocking::blocked_orgs_middleware;
+pub use retry_after::retry_after_middleware;
@@ -0,0 +1,99 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use axum::{
+    extract::Request,
+    http::{HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::Response,
+};
+use config::get_config;
+
+/// Adds `Retry-After` to 503 responses, which is what ingestion returns when
+/// the node is under pressure, and to 429 responses, returned when an org or
+/// stream is over its quota, so clients back off instead of retrying at once
+pub async fn retry_after_middleware(request: Request, next: Next) -> Response {
+    let mut response = next.run(request).await;
+    if response.headers().contains_key(header::RETRY_AFTER) {
+        return response;
+    }
+    let secs = match response.status() {
+        StatusCode::SERVICE_UNAVAILABLE => get_config().common.ingest_backpressure_retry_after,
+        StatusCode::TOO_MANY_REQUESTS => get_config().limit.ingest_quota_sync_interval,
+        _ => return response,
+    };
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, body::Body, middleware, routing::get};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn app() -> Router {
+        Router::new()
+            .route("/busy", get(|| async { StatusCode::SERVICE_UNAVAILABLE }))
+            .route(
+                "/busy_with_header",
+                get(|| async {
+                    (
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        [(header::RETRY_AFTER, "30")],
+                    )
+                }),
+            )
+            .route("/quota", get(|| async { StatusCode::TOO_MANY_REQUESTS }))
+            .route("/ok", get(|| async { StatusCode::OK }))
+            .layer(middleware::from_fn(retry_after_middleware))
+    }
+
+    async fn call(uri: &str) -> Response {
+        app()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_added_on_503() {
+        let resp = call("/busy").await;
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert!(resp.headers().contains_key(header::RETRY_AFTER));
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_added_on_429() {
+        let resp = call("/quota").await;
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(resp.headers().contains_key(header::RETRY_AFTER));
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_kept() {
+        let resp = call("/busy_with_header").await;
+        assert_eq!(resp.headers()[header::RETRY_AFTER], "30");
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_not_added_on_success() {
+        let resp = call("/ok").await;
+        assert!(!resp.headers().contains_key(header::RETRY_AFTER));
+    }
+}
@@ -53,7 +53,7 @@ use crate::{
             RequestData, oo_validator, validator_aws, validator_gcp, validator_proxy_url,
             validator_rum,
         },
-        router::middlewares::blocked_orgs_middleware,
+        router::middlewares::{blocked_orgs_middleware, retry_after_middleware},
     },
 };
 
@@ -389,6 +389,7 @@ pub fn basic_routes() -> Router {
     let mut router = Router::new()
         .route("/healthz", get(status::healthz).head(status::healthz_head))
         .route("/schedulez", get(status::schedulez))
+        .route("/debug/health", get(zpages::health))
         .route("/metrics", get(get_metrics));
 
     #[cfg(feature = "cloud")]
@@ -428,6 +429,9 @@ pub fn basic_routes() -> Router {
         .route("/status", get(status::cache_status))
         .route("/enable", put(status::enable_node))
         .route("/flush", put(status::flush_node))
+        .route("/wal/orphans", get(status::list_orphan_wal))
+        .route("/wal/orphans/replay", put(status::replay_orphan_wal))
+        .route("/wal/orphans/export", get(status::export_orphan_wal))
         .route("/reload", get(status::cache_reload))
         .route("/list", get(status::list_node))
         .route("/metrics", get(status::node_metrics));
@@ -445,18 +449,24 @@ pub fn basic_routes() -> Router {
 
     router = router.nest("/node", node_routes);
 
-    // Debug/profiling routes with auth
+    // Debug/zpages/profiling routes with auth, the zpages expose the node and
+    // process details so only the health probe is mounted without auth above
+    let mut debug_routes = Router::new()
+        .route("/servicez", get(zpages::servicez))
+        .route("/pipelinez", get(zpages::pipelinez));
+
     #[cfg(feature = "profiling")]
     {
-        let debug_routes = Router::new()
+        debug_routes = debug_routes
             .route("/profile/memory", get(profiling::memory_profile))
             .route("/profile/stats", get(profiling::jemalloc_stats))
-            .route("/profile/cpu", get(profiling::cpu_profile))
-            .layer(middleware::from_fn(auth_middleware));
-
-        router = router.nest("/debug", debug_routes);
+            .route("/profile/cpu", get(profiling::cpu_profile));
     }
 
+    debug_routes = debug_routes.layer(middleware::from_fn(auth_middleware));
+
+    router = router.nest("/debug", debug_routes);
+
     // Swagger UI
     if get_config().common.swagger_enabled {
         router = router.merge(
@@ -516,6 +526,9 @@ pub fn service_routes() -> Router {
 
         // Organizations
         .route("/organizations", get(organization::org::organizations).post(organization::org::create_org))
+        .route("/organizations/provision", post(organization::org::provision_org))
+        .route("/organizations/import", post(organization::org::import_org))
+        .route("/{org_id}/export", get(organization::org::export_org))
         .route("/{org_id}/organizations/assume_service_account", post(organization::assume_service_account::assume_service_account))
         .route("/{org_id}/settings", get(organization::settings::get).post(organization::settings::create))
         .route("/{org_id}/settings/logo", post(organization::settings::upload_logo).delete(organization::settings::delete_logo))
@@ -534,6 +547,8 @@ pub fn service_routes() -> Router {
         .route("/{org_id}/node/list", get(organization::org::node_list))
         .route("/{org_id}/cluster/info", get(organization::org::cluster_info))
         .route("/{org_id}/rename", put(organization::org::rename_org))
+        .route("/{org_id}/lifecycle", get(organization::org::get_lifecycle).put(organization::org::update_lifecycle))
+        .route("/{org_id}/level_mapping", get(organization::level_mapping::get_level_mapping).put(organization::level_mapping::update_level_mapping).delete(organization::level_mapping::reset_level_mapping))
 
         // ES compatibility
         .route("/{org_id}/", get(organization::es::org_index).head(organization::es::org_index))
@@ -548,7 +563,20 @@ pub fn service_routes() -> Router {
         .route("/{org_id}/streams", get(stream::list))
         .route("/{org_id}/streams/{stream_name}", post(stream::create).delete(stream::delete))
         .route("/{org_id}/streams/{stream_name}/schema", get(stream::schema))
+        .route("/{org_id}/streams/{stream_name}/field_usage", get(stream::field_usage))
+        .route("/{org_id}/streams/{stream_name}/distinct_values", get(stream::distinct_value_buckets))
+        .route("/{org_id}/streams/{stream_name}/field_stats", get(stream::field_stats))
+        .route("/{org_id}/clock_skew", get(stream::list_clock_skew))
+        .route("/{org_id}/streams/{stream_name}/schema_suggestion", get(stream::schema_suggestion))
+        .route("/{org_id}/streams/{stream_name}/schema_suggestion/apply", post(stream::apply_schema_suggestion))
         .route("/{org_id}/streams/{stream_name}/settings", put(stream::update_settings))
+        .route("/{org_id}/streams/{stream_name}/retention", get(stream::get_retention).put(stream::set_retention))
+        .route("/{org_id}/streams/{stream_name}/cold_storage/restore", get(stream::list_cold_storage_restores).post(stream::restore_cold_storage))
+        .route("/{org_id}/streams/{stream_name}/aliases", get(stream::list_aliases).post(stream::create_alias))
+        .route("/{org_id}/streams/{stream_name}/aliases/{alias}", delete(stream::delete_alias))
+        .route("/{org_id}/streams/{stream_name}/rename", post(stream::rename))
+        .route("/{org_id}/datasets", get(dataset::list).post(dataset::create))
+        .route("/{org_id}/datasets/{name}", get(dataset::get).put(dataset::update).delete(dataset::delete))
         .route("/{org_id}/streams/{stream_name}/update_fields", put(stream::update_fields))
         .route("/{org_id}/streams/{stream_name}/delete_fields", put(stream::delete_fields))
         .route("/{org_id}/streams/{stream_name}/cache/results", delete(stream::delete_stream_cache))
@@ -560,7 +588,17 @@ pub fn service_routes() -> Router {
         .route("/{org_id}/{stream_name}/_multi", post(logs::ingest::multi))
         .route("/{org_id}/{stream_name}/_json", post(logs::ingest::json))
         .route("/{org_id}/_hec", post(logs::ingest::hec))
+        .route("/{org_id}/_hec/services/collector/ack", post(logs::ingest::hec_ack))
+        .route("/{org_id}/_docker/services/collector/event/1.0", post(logs::ingest::docker_splunk))
+        .route("/{org_id}/_docker/services/collector/ack", post(logs::ingest::docker_splunk_ack))
+        .route("/{org_id}/{stream_name}/_docker/services/collector/event/1.0", post(logs::ingest::docker_splunk_stream))
+        .route("/{org_id}/{stream_name}/_logplex", post(logs::ingest::logplex))
+        .route("/{org_id}/{stream_name}/_gelf", post(logs::ingest::gelf))
         .route("/{org_id}/loki/api/v1/push", post(logs::loki::loki_push))
+        .route("/{org_id}/loki/api/v1/query_range", get(logs::loki::loki_query_range))
+        .route("/{org_id}/loki/api/v1/labels", get(logs::loki::loki_labels))
+        .route("/{org_id}/loki/api/v1/label/{label_name}/values", get(logs::loki::loki_label_values))
+        // OTLP bodies are decoded as they are read, against ZO_OTLP_PAYLOAD_LIMIT
         .route("/{org_id}/v1/logs", post(logs::ingest::otlp_logs_write))
         .route("/{org_id}/v1/metrics", post(metrics::ingest::otlp_metrics_write))
         .route("/{org_id}/v1/traces", post(traces::traces_write))
@@ -569,12 +607,15 @@ pub fn service_routes() -> Router {
 
         // Traces
         .route("/{org_id}/{stream_name}/traces/latest", get(traces::get_latest_traces))
+        .route("/{org_id}/{stream_name}/traces/by_attribute", get(traces::get_traces_by_attribute))
+        .route("/{org_id}/traces/service_map", get(traces::get_service_map))
 
         // Metrics
         .route("/{org_id}/ingest/metrics/_json", post(metrics::ingest::json))
 
         // PromQL
         .route("/{org_id}/prometheus/api/v1/write", post(promql::remote_write))
+        .route("/{org_id}/prometheus/api/v1/read", post(promql::remote_read))
         .route("/{org_id}/prometheus/api/v1/query", get(promql::query_get).post(promql::query_post))
         .route("/{org_id}/prometheus/api/v1/query_range", get(promql::query_range_get).post(promql::query_range_post))
         .route("/{org_id}/prometheus/api/v1/query_exemplars", get(promql::query_exemplars_get).post(promql::query_exemplars_post))
@@ -583,15 +624,29 @@ pub fn service_routes() -> Router {
         .route("/{org_id}/prometheus/api/v1/labels", get(promql::labels_get).post(promql::labels_post))
         .route("/{org_id}/prometheus/api/v1/label/{label_name}/values", get(promql::label_values))
         .route("/{org_id}/prometheus/api/v1/format_query", get(promql::format_query_get).post(promql::format_query_post))
+        .route("/{org_id}/prometheus/api/v1/translate_query", get(promql::translate_query_get).post(promql::translate_query_post))
 
         // Search
         .route("/{org_id}/_search", post(search::search))
+        .route("/{org_id}/_search_cross_org", post(search::cross_org::search_cross_org))
         .route("/{org_id}/_search_partition", post(search::search_partition))
+        .route("/{org_id}/_search_anomalies", post(search::search_anomalies))
+        .route("/{org_id}/_search_pivot", post(search::search_pivot))
+        .route("/{org_id}/_search_diff", post(search::search_diff))
+        .route("/{org_id}/_search_export", post(search::search_export))
+        .route("/{org_id}/_search_ndjson", post(search::search_ndjson))
+        .route("/{org_id}/_search_async", post(search::async_search::submit_async_search))
+        .route("/{org_id}/_search_async/{id}", get(search::async_search::get_async_search).delete(search::async_search::delete_async_search))
+        .route("/{org_id}/_search_async/{id}/result", get(search::async_search::get_async_search_result))
         .route("/{org_id}/{stream_name}/_around", get(search::around_v1).post(search::around_v2))
+        .route("/{org_id}/{stream_name}/_tail", get(search::tail::tail_stream))
         .route("/{org_id}/{stream_name}/_values", get(search::values))
+        .route("/{org_id}/_msearch", post(search::es::msearch))
+        .route("/{org_id}/{stream_name}/_msearch", post(search::es::msearch_index))
         .route("/{org_id}/_search_history", post(search::search_history))
         .route("/{org_id}/result_schema", post(search::result_schema))
         .route("/{org_id}/search/profile", get(search::search_inspector::get_search_profile))
+        .route("/{org_id}/search/quota", get(search::get_search_quota))
 
         // Multi-stream search
         .route("/{org_id}/_search_multi", post(search::multi_streams::search_multi))
@@ -607,11 +662,30 @@ pub fn service_routes() -> Router {
         .route("/{org_id}/savedviews", get(search::saved_view::get_views).post(search::saved_view::create_view))
         .route("/{org_id}/savedviews/{view_id}", get(search::saved_view::get_view).put(search::saved_view::update_view).delete(search::saved_view::delete_view))
 
+        // Scheduled exports
+        .route("/{org_id}/scheduled_exports", get(scheduled_exports::list_exports).post(scheduled_exports::create_export))
+        .route("/{org_id}/scheduled_exports/{id}", get(scheduled_exports::get_export).put(scheduled_exports::update_export).delete(scheduled_exports::delete_export))
+        .route("/{org_id}/export_destination", get(scheduled_exports::get_destination).put(scheduled_exports::set_destination).delete(scheduled_exports::delete_destination))
+
+        // Recording rules
+        .route("/{org_id}/recording_rules", get(recording_rules::list_rules).post(recording_rules::create_rule))
+        .route("/{org_id}/recording_rules/{id}", get(recording_rules::get_rule).put(recording_rules::update_rule).delete(recording_rules::delete_rule))
+        // Log metrics
+        .route("/{org_id}/log_metrics", get(log_metrics::list_rules).post(log_metrics::create_rule))
+        .route("/{org_id}/log_metrics/{id}", get(log_metrics::get_rule).put(log_metrics::update_rule).delete(log_metrics::delete_rule))
+        .route("/{org_id}/replays", get(replay::list_replays).post(replay::create_replay))
+        .route("/{org_id}/replays/{id}", get(replay::get_replay).delete(replay::delete_replay))
+        .route("/{org_id}/record_deletions", get(record_deletion::list_record_deletions).post(record_deletion::create_record_deletion))
+        .route("/{org_id}/record_deletions/{id}", get(record_deletion::get_record_deletion).delete(record_deletion::delete_record_deletion))
+
         // Functions
         .route("/{org_id}/functions", get(functions::list_functions).post(functions::save_function))
         .route("/{org_id}/functions/test", post(functions::test_function))
         .route("/{org_id}/functions/bulk", delete(functions::delete_function_bulk))
         .route("/{org_id}/functions/{name}", get(functions::list_pipeline_dependencies).put(functions::update_function).delete(functions::delete_function))
+        .route("/{org_id}/functions/{name}/versions", get(functions::list_function_versions))
+        .route("/{org_id}/functions/{name}/rollback", post(functions::rollback_function))
+        .route("/{org_id}/functions/{name}/test", post(functions::test_saved_function))
 
         // Dashboards
         .route("/{org_id}/dashboards", get(dashboards::list_dashboards).post(dashboards::create_dashboard))
@@ -620,6 +694,7 @@ pub fn service_routes() -> Router {
         .route("/{org_id}/dashboards/bulk", delete(dashboards::delete_dashboard_bulk))
         .route("/{org_id}/folders/dashboards/{dashboard_id}", put(dashboards::move_dashboard))
         .route("/{org_id}/dashboards/move", patch(dashboards::move_dashboards))
+        .route("/{org_id}/dashboards/variables/{stream_name}", get(dashboards::variables::get_variable_values))
 
         // Reports
         .route("/{org_id}/reports", get(dashboards::reports::list_reports).post(dashboards::reports::create_report))
@@ -649,6 +724,8 @@ pub fn service_routes() -> Router {
         .route("/v2/{org_id}/alerts/generate_sql", post(alerts::generate_sql))
         .route("/v2/{org_id}/alerts/move", patch(alerts::move_alerts))
         .route("/v2/{org_id}/alerts/history", get(alerts::history::get_alert_history))
+        .route("/v2/{org_id}/alerts/backtest", post(alerts::backtest::create_backtest))
+        .route("/v2/{org_id}/alerts/backtest/{backtest_id}", get(alerts::backtest::get_backtest))
         .route("/v2/{org_id}/alerts/dedup/summary", get(alerts::dedup_stats::get_dedup_summary))
 
         // Alerts - incidents must be before alerts to avoid route conflicts
@@ -669,7 +746,9 @@ pub fn service_routes() -> Router {
         .route("/{org_id}/alerts/destinations/prebuilt", get(alerts::destinations::list_prebuilt_destinations))
         .route("/{org_id}/alerts/destinations/{destination_name}", get(alerts::destinations::get_destination).put(alerts::destinations::update_destination).delete(alerts::destinations::delete_destination))
         .route("/{org_id}/alerts/destinations/test", post(alerts::destinations::test_destination))
+        .route("/{org_id}/alerts/destinations/preview", post(alerts::destinations::preview_destination))
         .route("/{org_id}/alerts/destinations/bulk", delete(alerts::destinations::delete_destination_bulk))
+        .route("/{org_id}/alerts/destinations/{destination_name}/deliveries", get(alerts::destinations::list_email_deliveries))
 
         // Deduplication
         .route("/{org_id}/alerts/deduplication/config", get(alerts::deduplication::get_config).post(alerts::deduplication::set_config).delete(alerts::deduplication::delete_config))
@@ -679,6 +758,13 @@ pub fn service_routes() -> Router {
         // KV store
         .route("/{org_id}/kv/{key}", get(kv::get).post(kv::set).delete(kv::delete))
         .route("/{org_id}/kv", get(kv::list))
+        .route("/{org_id}/webhooks", get(webhooks::list).post(webhooks::create))
+        .route("/{org_id}/webhooks/{name}", get(webhooks::get).put(webhooks::update).delete(webhooks::delete))
+        .route("/{org_id}/rabbitmq_sources", get(rabbitmq::list).post(rabbitmq::create))
+        .route("/{org_id}/rabbitmq_sources/{name}", get(rabbitmq::get).put(rabbitmq::update).delete(rabbitmq::delete))
+        .route("/{org_id}/db_pollers", get(db_pollers::list).post(db_pollers::create))
+        .route("/{org_id}/db_pollers/{id}", get(db_pollers::get).put(db_pollers::update).delete(db_pollers::delete))
+        .route("/{org_id}/webhook_templates", get(webhooks::templates))
 
         // Enrichment tables
         .route("/{org_id}/enrichment_tables/{table_name}", post(enrichment_table::save_enrichment_table))
@@ -787,6 +873,8 @@ pub fn service_routes() -> Router {
 
             // Patterns
             .route("/{org_id}/streams/{stream_name}/patterns/extract", post(patterns::extract_patterns))
+            .route("/{org_id}/streams/{stream_name}/patterns/mine", post(patterns::mine_patterns))
+            .route("/{org_id}/streams/{stream_name}/patterns", get(patterns::get_pattern_trends))
 
             // Service streams
             .route("/{org_id}/service_streams/_analytics", get(service_streams::get_dimension_analytics))
@@ -862,11 +950,12 @@ pub fn service_routes() -> Router {
     }
 
     // Apply middlewares in order: preprocessing -> decompression -> cors -> server header -> auth
-    // -> audit -> blocked orgs NOTE: Preprocessing middleware removes Content-Encoding: snappy
-    // header before tower_http sees it. This prevents 415 errors while allowing handlers to
-    // manually decompress snappy data. tower_http's RequestDecompressionLayer handles gzip,
-    // deflate, and brotli.
+    // -> audit -> blocked orgs -> retry after NOTE: Preprocessing middleware removes
+    // Content-Encoding: snappy header before tower_http sees it. This prevents 415 errors while
+    // allowing handlers to manually decompress snappy data. tower_http's
+    // RequestDecompressionLayer handles gzip, deflate, brotli and zstd.
     router
+        .layer(middleware::from_fn(retry_after_middleware))
         .layer(middleware::from_fn(blocked_orgs_middleware))
         .layer(middleware::from_fn(audit_middleware))
         .layer(middleware::from_fn(auth_middleware))
@@ -888,9 +977,9 @@ pub fn service_routes() -> Router {
         }))
 }
 
-/// Create other service routes (AWS, GCP, RUM)
+/// Create other service routes (AWS, GCP, RUM, webhooks)
 pub fn other_service_routes() -> Router {
-    // AWS routes - with standard decompression (gzip/deflate/brotli) + snappy preprocessing
+    // AWS routes - with standard decompression (gzip/deflate/brotli/zstd) + snappy preprocessing
     let aws_routes = Router::new()
         .route(
             "/{org_id}/{stream_name}/_kinesis_firehose",
@@ -902,7 +991,7 @@ pub fn other_service_routes() -> Router {
             decompression::preprocess_encoding_middleware,
         ));
 
-    // GCP routes - with standard decompression (gzip/deflate/brotli) + snappy preprocessing
+    // GCP routes - with standard decompression (gzip/deflate/brotli/zstd) + snappy preprocessing
     let gcp_routes = Router::new()
         .route(
             "/{org_id}/{stream_name}/_sub",
@@ -914,7 +1003,7 @@ pub fn other_service_routes() -> Router {
             decompression::preprocess_encoding_middleware,
         ));
 
-    // RUM routes - with standard decompression (gzip/deflate/brotli) + snappy preprocessing
+    // RUM routes - with standard decompression (gzip/deflate/brotli/zstd) + snappy preprocessing
     let rum_routes = Router::new()
         .route("/v1/{org_id}/logs", post(rum::ingest::log))
         .route("/v1/{org_id}/replay", post(rum::ingest::sessionreplay))
@@ -926,10 +1015,20 @@ pub fn other_service_routes() -> Router {
             decompression::preprocess_encoding_middleware,
         ));
 
+    // Webhook routes - no auth middleware, requests are checked against the source's secret
+    let webhook_routes = Router::new()
+        .route("/{org_id}/{name}", post(webhooks::receive))
+        .layer(RequestDecompressionLayer::new())
+        .layer(middleware::from_fn(
+            decompression::preprocess_encoding_middleware,
+        ));
+
     Router::new()
         .nest("/aws", aws_routes)
         .nest("/gcp", gcp_routes)
         .nest("/rum", rum_routes)
+        .nest("/webhooks", webhook_routes)
+        .layer(middleware::from_fn(retry_after_middleware))
 }
 
 /// Create the full application router
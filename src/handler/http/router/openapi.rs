@@ -31,7 +31,15 @@ use crate::{common::meta, handler::http::request};
         request::users::add_user_to_org,
         request::organization::org::organizations,
         request::organization::org::create_org,
+        request::organization::org::provision_org,
+        request::organization::org::export_org,
+        request::organization::org::import_org,
         request::organization::org::rename_org,
+        request::organization::org::get_lifecycle,
+        request::organization::org::update_lifecycle,
+        request::organization::level_mapping::get_level_mapping,
+        request::organization::level_mapping::update_level_mapping,
+        request::organization::level_mapping::reset_level_mapping,
         request::organization::assume_service_account::assume_service_account,
         request::organization::org::org_summary,
         request::organization::org::get_user_passcode,
@@ -49,18 +57,43 @@ use crate::{common::meta, handler::http::request};
         request::organization::system_settings::delete_user_setting,
         request::stream::list,
         request::stream::schema,
+        request::stream::field_usage,
+        request::stream::distinct_value_buckets,
+        request::stream::field_stats,
+        request::stream::list_clock_skew,
+        request::stream::schema_suggestion,
+        request::stream::apply_schema_suggestion,
         request::stream::create,
         request::stream::update_settings,
+        request::stream::get_retention,
+        request::stream::set_retention,
+        request::stream::restore_cold_storage,
+        request::stream::list_cold_storage_restores,
         request::stream::delete_fields,
         request::stream::delete,
+        request::stream::list_aliases,
+        request::stream::create_alias,
+        request::stream::delete_alias,
+        request::stream::rename,
+        request::dataset::list,
+        request::dataset::get,
+        request::dataset::create,
+        request::dataset::update,
+        request::dataset::delete,
         request::logs::ingest::bulk,
         request::logs::ingest::multi,
         request::logs::ingest::json,
         request::logs::loki::loki_push,
+        request::logs::loki::loki_query_range,
+        request::logs::loki::loki_labels,
+        request::logs::loki::loki_label_values,
         request::traces::traces_write,
         request::traces::get_latest_traces,
+        request::traces::get_traces_by_attribute,
+        request::traces::get_service_map,
         request::metrics::ingest::json,
         request::promql::remote_write,
+        request::promql::remote_read,
         request::promql::query_get,
         request::promql::query_range_get,
         request::promql::metadata,
@@ -68,6 +101,7 @@ use crate::{common::meta, handler::http::request};
         request::promql::labels_get,
         request::promql::label_values,
         request::promql::format_query_get,
+        request::promql::translate_query_get,
         request::enrichment_table::save_enrichment_table,
         request::enrichment_table::save_enrichment_table_from_url,
         request::rum::ingest::log,
@@ -75,15 +109,54 @@ use crate::{common::meta, handler::http::request};
         request::rum::ingest::sessionreplay,
         request::search::search,
         request::search::search_partition,
+        request::search::search_anomalies,
+        request::search::search_pivot,
+        request::search::search_diff,
+        request::search::search_export,
+        request::search::search_ndjson,
+        request::search::async_search::submit_async_search,
+        request::search::async_search::get_async_search,
+        request::search::async_search::get_async_search_result,
+        request::search::async_search::delete_async_search,
         request::search::around_v1,
         request::search::around_v2,
+        request::search::es::msearch,
+        request::search::es::msearch_index,
         request::search::values,
         request::search::search_history,
+        request::search::get_search_quota,
+        request::search::cross_org::search_cross_org,
         request::search::saved_view::create_view,
         request::search::saved_view::delete_view,
         request::search::saved_view::get_view,
         request::search::saved_view::get_views,
         request::search::saved_view::update_view,
+        request::scheduled_exports::create_export,
+        request::scheduled_exports::update_export,
+        request::scheduled_exports::get_export,
+        request::scheduled_exports::list_exports,
+        request::scheduled_exports::delete_export,
+        request::scheduled_exports::get_destination,
+        request::scheduled_exports::set_destination,
+        request::scheduled_exports::delete_destination,
+        request::recording_rules::create_rule,
+        request::recording_rules::update_rule,
+        request::recording_rules::get_rule,
+        request::recording_rules::list_rules,
+        request::recording_rules::delete_rule,
+        request::log_metrics::create_rule,
+        request::log_metrics::update_rule,
+        request::log_metrics::get_rule,
+        request::log_metrics::list_rules,
+        request::log_metrics::delete_rule,
+        request::replay::create_replay,
+        request::replay::get_replay,
+        request::replay::list_replays,
+        request::replay::delete_replay,
+        request::record_deletion::create_record_deletion,
+        request::record_deletion::get_record_deletion,
+        request::record_deletion::list_record_deletions,
+        request::record_deletion::delete_record_deletion,
         request::folders::delete_folder,
         request::folders::create_folder,
         request::folders::list_folders,
@@ -102,6 +175,9 @@ use crate::{common::meta, handler::http::request};
         request::functions::delete_function,
         request::functions::list_pipeline_dependencies,
         request::functions::test_function,
+        request::functions::list_function_versions,
+        request::functions::rollback_function,
+        request::functions::test_saved_function,
         request::dashboards::create_dashboard,
         request::dashboards::update_dashboard,
         request::dashboards::list_dashboards,
@@ -115,6 +191,7 @@ use crate::{common::meta, handler::http::request};
         request::dashboards::timed_annotations::delete_annotations,
         request::dashboards::timed_annotations::update_annotations,
         request::dashboards::timed_annotations::delete_annotation_panels,
+        request::dashboards::variables::get_variable_values,
         request::alerts::create_alert,
         request::alerts::get_alert,
         request::alerts::export_alert,
@@ -127,6 +204,8 @@ use crate::{common::meta, handler::http::request};
         request::alerts::generate_sql,
         request::alerts::move_alerts,
         request::alerts::history::get_alert_history,
+        request::alerts::backtest::create_backtest,
+        request::alerts::backtest::get_backtest,
         request::alerts::incidents::list_incidents,
         request::alerts::incidents::get_incident,
         request::alerts::incidents::update_incident,
@@ -143,10 +222,28 @@ use crate::{common::meta, handler::http::request};
         request::alerts::destinations::save_destination,
         request::alerts::destinations::update_destination,
         request::alerts::destinations::delete_destination,
+        request::alerts::destinations::preview_destination,
+        request::alerts::destinations::list_email_deliveries,
         request::kv::get,
         request::kv::set,
         request::kv::delete,
         request::kv::list,
+        request::webhooks::list,
+        request::webhooks::templates,
+        request::webhooks::get,
+        request::webhooks::create,
+        request::webhooks::update,
+        request::webhooks::delete,
+        request::rabbitmq::list,
+        request::rabbitmq::get,
+        request::rabbitmq::create,
+        request::rabbitmq::update,
+        request::rabbitmq::delete,
+        request::db_pollers::list,
+        request::db_pollers::get,
+        request::db_pollers::create,
+        request::db_pollers::update,
+        request::db_pollers::delete,
         request::clusters::list_clusters,
         request::short_url::shorten,
         request::short_url::retrieve,
@@ -213,7 +310,10 @@ use crate::{common::meta, handler::http::request};
         request::search::search_job::retry_job,
         request::search::search_stream::search_http2_stream,
         request::search::search_stream::values_http2_stream,
+        request::search::tail::tail_stream,
         request::patterns::extract_patterns,
+        request::patterns::mine_patterns,
+        request::patterns::get_pattern_trends,
         crate::service::traces::service_graph::api::get_current_topology,
         request::service_streams::get_dimension_analytics,
         request::service_streams::correlate_streams,
@@ -231,8 +331,32 @@ use crate::{common::meta, handler::http::request};
             StreamType,
             meta::stream::Stream,
             meta::stream::StreamDeleteFields,
+            meta::stream::FieldUsage,
+            meta::stream::FieldUsageEntry,
+            meta::stream::FieldUsageResponse,
+            meta::stream::DistinctValueCounts,
+            meta::stream::DistinctValueBucketsResponse,
+            meta::stream::FieldStats,
+            meta::stream::FieldStatsResponse,
+            meta::stream::SourceClockSkew,
+            meta::stream::ClockSkewEntry,
+            meta::stream::ClockSkewResponse,
+            meta::stream::StreamRetention,
+            meta::stream::StreamRetentionResponse,
+            meta::stream::ColdStorageRestoreRequest,
+            meta::stream::ColdStorageRestoreStatus,
+            meta::stream::ColdStorageRestore,
+            meta::stream::ColdStorageRestoreList,
+            meta::stream::SchemaSuggestion,
+            meta::stream::FieldSuggestion,
             meta::stream::StreamCreate,
             meta::stream::ListStream,
+            meta::stream::StreamAlias,
+            meta::stream::StreamAliasCreate,
+            meta::stream::StreamRename,
+            meta::stream::ListStreamAlias,
+            config::meta::dataset::Dataset,
+            config::meta::dataset::DatasetList,
             config::meta::stream::StreamField,
             config::meta::stream::StreamSettings,
             config::meta::stream::StreamPartition,
@@ -240,6 +364,19 @@ use crate::{common::meta, handler::http::request};
             config::meta::stream::StreamStats,
             config::meta::stream::PartitionTimeLevel,
             config::meta::stream::UpdateStreamSettings,
+            config::meta::stream::IngestPriority,
+            config::meta::stream::StreamSettingsTemplate,
+            config::meta::stream::LevelNormalization,
+            config::meta::stream::ClockSkew,
+            config::meta::stream::TailSampling,
+            config::meta::stream::CompactSort,
+            config::meta::stream::CompactSortMode,
+            config::meta::stream::FieldAccessRule,
+            config::meta::stream::FieldAccessMode,
+            config::meta::stream::LevelMapping,
+            config::meta::service_graph::ServiceGraphData,
+            config::meta::service_graph::ServiceNode,
+            config::meta::service_graph::ServiceEdge,
             config::meta::dashboards::Dashboard,
             config::meta::dashboards::v1::AxisItem,
             config::meta::dashboards::v1::Dashboard,
@@ -254,6 +391,9 @@ use crate::{common::meta, handler::http::request};
             config::meta::dashboards::v1::CustomFieldsOption,
             config::meta::dashboards::v1::VariableList,
             config::meta::alerts::alert::Alert,
+            config::meta::alerts::backtest::AlertBacktestRequest,
+            config::meta::alerts::backtest::AlertBacktest,
+            config::meta::alerts::backtest::BacktestStatus,
             config::meta::alerts::Aggregation,
             config::meta::alerts::AggFunction,
             config::meta::alerts::Condition,
@@ -326,11 +466,20 @@ use crate::{common::meta, handler::http::request};
             config::meta::function::FunctionList,
             config::meta::function::StreamOrder,
             config::meta::function::TestVRLRequest,
+            config::meta::function::FunctionVersion,
+            config::meta::function::FunctionVersionList,
+            config::meta::function::RollbackFunctionRequest,
+            config::meta::function::TestFunctionRequest,
             config::meta::sql::OrderBy,
             config::meta::search::Query,
             config::meta::search::Request,
             config::meta::search::RequestEncoding,
             config::meta::search::Response,
+            config::meta::search::SearchQuota,
+            config::meta::search::SearchQuotaUsage,
+            config::meta::search::QueryLimits,
+            config::meta::search::SearchLimits,
+            config::meta::search::CrossOrgRequest,
             config::meta::search::ResponseTook,
             config::meta::search::SearchEventType,
             config::meta::search::SearchEventContext,
@@ -342,6 +491,35 @@ use crate::{common::meta, handler::http::request};
             config::meta::search::QueryStatus,
             config::meta::search::QueryInfo,
             config::meta::search::ScanStats,
+            config::meta::anomalies::AnomalyRequest,
+            config::meta::anomalies::AnomalyResponse,
+            config::meta::anomalies::SeriesAnomalies,
+            config::meta::anomalies::Anomaly,
+            config::meta::anomalies::DetectionMethod,
+            config::meta::anomalies::Direction,
+            config::meta::pivot::PivotRequest,
+            config::meta::pivot::PivotStream,
+            config::meta::pivot::PivotResponse,
+            config::meta::pivot::PivotStreamHits,
+            config::meta::query_diff::QueryDiffRequest,
+            config::meta::query_diff::CompareTarget,
+            config::meta::query_diff::QueryDiffResponse,
+            config::meta::query_diff::DiffGroup,
+            config::meta::query_diff::ValueDelta,
+            crate::service::search::ordered_export::OrderedExportRequest,
+            crate::service::search::ordered_export::ExportTarget,
+            crate::service::search::ordered_export::OrderedExportResponse,
+            crate::service::search::batch_query::BatchQueryRequest,
+            crate::service::search::batch_query::BatchQueryTrailer,
+            config::meta::async_search::AsyncSearchStatus,
+            config::meta::async_search::AsyncSearchSubmitResponse,
+            config::meta::async_search::AsyncSearchStatusResponse,
+            config::meta::async_search::AsyncSearchResultResponse,
+            config::meta::patterns::PatternRequest,
+            config::meta::patterns::PatternResponse,
+            config::meta::patterns::LogPattern,
+            config::meta::patterns::PatternTrend,
+            config::meta::patterns::PatternTrendResponse,
             config::meta::short_url::ShortenUrlRequest,
             config::meta::short_url::ShortenUrlResponse,
             config::meta::user::UserRole,
@@ -352,6 +530,8 @@ use crate::{common::meta, handler::http::request};
             meta::loki::LokiPushRequest,
             meta::loki::LokiStream,
             meta::loki::LokiEntry,
+            meta::loki::LokiStreamsData,
+            meta::loki::LokiStreamResult,
             meta::saved_view::View,
             meta::saved_view::ViewWithoutData,
             meta::saved_view::ViewsWithoutData,
@@ -359,6 +539,38 @@ use crate::{common::meta, handler::http::request};
             meta::saved_view::DeleteViewResponse,
             meta::saved_view::CreateViewResponse,
             meta::saved_view::UpdateViewRequest,
+            config::meta::exports::ExportFormat,
+            config::meta::exports::ExportProvider,
+            config::meta::exports::ExportDestination,
+            config::meta::exports::ScheduledExport,
+            config::meta::exports::ExportRun,
+            config::meta::exports::ScheduledExportList,
+            config::meta::recording_rules::RecordingRule,
+            config::meta::recording_rules::RecordingRuleRun,
+            config::meta::recording_rules::RecordingRuleList,
+            config::meta::log_metrics::LogMetricRule,
+            config::meta::log_metrics::LogMetricKind,
+            config::meta::log_metrics::LogMetricRuleList,
+            config::meta::replay::ReplayRequest,
+            config::meta::replay::Replay,
+            config::meta::replay::ReplayStatus,
+            config::meta::replay::ReplayList,
+            config::meta::record_deletion::RecordDeletionRequest,
+            config::meta::record_deletion::RecordDeletion,
+            config::meta::record_deletion::RecordDeletionStatus,
+            config::meta::record_deletion::RecordDeletionList,
+            meta::webhook::WebhookKind,
+            meta::webhook::WebhookSource,
+            meta::webhook::WebhookSourceList,
+            meta::rabbitmq::RabbitmqSource,
+            meta::rabbitmq::RabbitmqSourceList,
+            config::meta::db_pollers::DbPoller,
+            config::meta::db_pollers::DbPollerDriver,
+            config::meta::db_pollers::Watermark,
+            config::meta::db_pollers::DbPollerRun,
+            config::meta::db_pollers::DbPollerList,
+            meta::webhook::WebhookTemplate,
+            meta::webhook::WebhookTemplateList,
             meta::user::UpdateUser,
             meta::user::UserRoleRequest,
             meta::user::PostUserRequest,
@@ -377,6 +589,19 @@ use crate::{common::meta, handler::http::request};
             meta::organization::PasscodeResponse,
             meta::organization::Organization,
             meta::organization::OrgRenameBody,
+            meta::organization::OrgLifecycleState,
+            meta::organization::OrgLifecycle,
+            meta::organization::OrgLifecycleRequest,
+            meta::organization::OrgTemplateStream,
+            meta::organization::OrgTemplateRole,
+            meta::organization::OrgProvisionSummary,
+            meta::organization::OrgProvisionResponse,
+            meta::organization::OrgArchive,
+            meta::organization::OrgArchiveFile,
+            meta::organization::OrganizationCreationResponse,
+            crate::handler::http::models::organizations::ProvisionOrgRequestBody,
+            crate::handler::http::models::organizations::OrgTemplateRequestBody,
+            crate::handler::http::models::organizations::ImportOrgRequestBody,
             meta::organization::OrganizationSetting,
             meta::organization::OrganizationSettingResponse,
             meta::organization::RumIngestionResponse,
@@ -419,6 +644,11 @@ use crate::{common::meta, handler::http::request};
         (name = "Dashboards", description = "Dashboard operations"),
         (name = "Search", description = "Search/Query operations"),
         (name = "Saved Views", description = "Collection of saved search views for easy retrieval"),
+        (name = "Scheduled Exports", description = "Saved searches exported on a schedule to an object store"),
+        (name = "Recording Rules", description = "PromQL expressions evaluated on a schedule into new metrics"),
+        (name = "Log Metrics", description = "Counters and histograms extracted from the logs at ingestion"),
+        (name = "Replays", description = "Stored records of a stream reprocessed into another stream"),
+        (name = "Record Deletions", description = "Records of a stream matching a filter deleted by the compactor"),
         (name = "Alerts", description = "Alerts retrieval & management operations"),
         (name = "Incidents", description = "Alert incident correlation & management operations"),
         (name = "Agents", description = "AI agent chat and analysis operations (enterprise)"),
@@ -427,12 +657,15 @@ use crate::{common::meta, handler::http::request};
         (name = "Streams", description = "Stream retrieval & management operations"),
         (name = "Users", description = "Users retrieval & management operations"),
         (name = "KV", description = "Key Value retrieval & management operations"),
+        (name = "Webhooks", description = "Catch-all webhook sources management"),
+        (name = "RabbitMQ", description = "RabbitMQ ingestion sources management"),
+        (name = "DB Pollers", description = "Postgres and MySQL tables polled into logs streams"),
         (name = "Metrics", description = "Metrics data ingestion operations"),
         (name = "Traces", description = "Traces data ingestion operations"),
         (name = "Clusters", description = "Super cluster operations"),
         (name = "Short Url", description = "Short Url Service"),
         (name = "Ratelimit", description = "Ratelimit operations"),
-        (name = "Patterns", description = "Log pattern extraction operations (enterprise)"),
+        (name = "Patterns", description = "Log pattern mining and extraction operations"),
         (name = "Service Streams", description = "Multi-signal correlation across logs, traces, and metrics (enterprise)"),
     ),
     info(
@@ -0,0 +1,688 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Messages of the PostgreSQL frontend/backend protocol version 3, and the
+//! encoding of the Arrow values in the text and binary formats.
+
+use std::io::{Error, ErrorKind, Result};
+
+use arrow::{
+    array::{Array, ArrayRef, AsArray},
+    compute::cast,
+    datatypes::{DataType, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, TimeUnit},
+    util::display::{ArrayFormatter, FormatOptions},
+};
+use hashbrown::HashMap;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Messages larger than this are rejected, the queries are small
+const MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+const PROTOCOL_VERSION_3: i32 = 196608;
+const SSL_REQUEST_CODE: i32 = 80877103;
+const GSSENC_REQUEST_CODE: i32 = 80877104;
+const CANCEL_REQUEST_CODE: i32 = 80877102;
+
+pub(super) const BOOL_OID: u32 = 16;
+pub(super) const INT8_OID: u32 = 20;
+pub(super) const INT2_OID: u32 = 21;
+pub(super) const INT4_OID: u32 = 23;
+pub(super) const TEXT_OID: u32 = 25;
+pub(super) const FLOAT4_OID: u32 = 700;
+pub(super) const FLOAT8_OID: u32 = 701;
+pub(super) const TIMESTAMP_OID: u32 = 1114;
+pub(super) const TIMESTAMPTZ_OID: u32 = 1184;
+
+/// Microseconds from the unix epoch to the PostgreSQL epoch, 2000-01-01
+const PG_EPOCH_MICROS: i64 = 946_684_800_000_000;
+
+const FORMAT_TEXT: i16 = 0;
+const FORMAT_BINARY: i16 = 1;
+
+/// First message of a connection, the only one without a type byte
+#[derive(Debug, PartialEq)]
+pub(super) enum Startup {
+    /// The client asks for TLS, accepted when the listener has a certificate
+    SslRequest,
+    /// The client asks for GSS encryption, which is refused
+    GssEncRequest,
+    Cancel,
+    Params(HashMap<String, String>),
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) enum FrontendMessage {
+    Query(String),
+    Parse {
+        name: String,
+        query: String,
+        param_types: Vec<u32>,
+    },
+    Bind {
+        portal: String,
+        statement: String,
+        param_formats: Vec<i16>,
+        params: Vec<Option<Vec<u8>>>,
+        result_formats: Vec<i16>,
+    },
+    Describe {
+        kind: u8,
+        name: String,
+    },
+    Execute {
+        portal: String,
+        max_rows: i32,
+    },
+    Close {
+        kind: u8,
+        name: String,
+    },
+    Password(String),
+    Sync,
+    Flush,
+    Terminate,
+    Unsupported(u8),
+}
+
+pub(super) async fn read_startup<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Startup> {
+    let len = reader.read_i32().await?;
+    let body = read_body(reader, len).await?;
+    let mut body = Body::new(&body);
+    match body.i32()? {
+        SSL_REQUEST_CODE => Ok(Startup::SslRequest),
+        GSSENC_REQUEST_CODE => Ok(Startup::GssEncRequest),
+        CANCEL_REQUEST_CODE => Ok(Startup::Cancel),
+        PROTOCOL_VERSION_3 => {
+            let mut params = HashMap::new();
+            loop {
+                let key = body.cstr()?;
+                if key.is_empty() {
+                    break;
+                }
+                let value = body.cstr()?;
+                params.insert(key, value);
+            }
+            Ok(Startup::Params(params))
+        }
+        version => Err(invalid_data(format!(
+            "unsupported protocol version {}.{}",
+            version >> 16,
+            version & 0xffff
+        ))),
+    }
+}
+
+/// Reads the next message, None when the client closed the connection
+pub(super) async fn read_message<R: AsyncRead + Unpin>(
+    reader: &mut R,
+) -> Result<Option<FrontendMessage>> {
+    let tag = match reader.read_u8().await {
+        Ok(tag) => tag,
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let len = reader.read_i32().await?;
+    let body = read_body(reader, len).await?;
+    decode_message(tag, &body).map(Some)
+}
+
+async fn read_body<R: AsyncRead + Unpin>(reader: &mut R, len: i32) -> Result<Vec<u8>> {
+    // the length includes itself
+    let len = len
+        .checked_sub(4)
+        .and_then(|len| usize::try_from(len).ok())
+        .ok_or_else(|| invalid_data(format!("invalid message length {len}")))?;
+    if len > MAX_MESSAGE_SIZE {
+        return Err(invalid_data(format!("message of {len} bytes is too large")));
+    }
+    let mut body = vec![0; len];
+    reader.read_exact(&mut body).await?;
+    Ok(body)
+}
+
+fn decode_message(tag: u8, body: &[u8]) -> Result<FrontendMessage> {
+    let mut body = Body::new(body);
+    let msg = match tag {
+        b'Q' => FrontendMessage::Query(body.cstr()?),
+        b'P' => {
+            let name = body.cstr()?;
+            let query = body.cstr()?;
+            let num = body.i16()?;
+            let param_types = (0..num)
+                .map(|_| body.i32().map(|v| v as u32))
+                .collect::<Result<_>>()?;
+            FrontendMessage::Parse {
+                name,
+                query,
+                param_types,
+            }
+        }
+        b'B' => {
+            let portal = body.cstr()?;
+            let statement = body.cstr()?;
+            let num = body.i16()?;
+            let param_formats = (0..num).map(|_| body.i16()).collect::<Result<_>>()?;
+            let num = body.i16()?;
+            let params = (0..num)
+                .map(|_| {
+                    let len = body.i32()?;
+                    if len < 0 {
+                        Ok(None)
+                    } else {
+                        body.bytes(len as usize).map(|v| Some(v.to_vec()))
+                    }
+                })
+                .collect::<Result<_>>()?;
+            let num = body.i16()?;
+            let result_formats = (0..num).map(|_| body.i16()).collect::<Result<_>>()?;
+            FrontendMessage::Bind {
+                portal,
+                statement,
+                param_formats,
+                params,
+                result_formats,
+            }
+        }
+        b'D' => FrontendMessage::Describe {
+            kind: body.u8()?,
+            name: body.cstr()?,
+        },
+        b'E' => FrontendMessage::Execute {
+            portal: body.cstr()?,
+            max_rows: body.i32()?,
+        },
+        b'C' => FrontendMessage::Close {
+            kind: body.u8()?,
+            name: body.cstr()?,
+        },
+        b'p' => FrontendMessage::Password(body.cstr()?),
+        b'S' => FrontendMessage::Sync,
+        b'H' => FrontendMessage::Flush,
+        b'X' => FrontendMessage::Terminate,
+        tag => FrontendMessage::Unsupported(tag),
+    };
+    Ok(msg)
+}
+
+struct Body<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Body<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.buf.len() < len {
+            return Err(invalid_data("message is truncated"));
+        }
+        let (v, rest) = self.buf.split_at(len);
+        self.buf = rest;
+        Ok(v)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        self.bytes(1).map(|v| v[0])
+    }
+
+    fn i16(&mut self) -> Result<i16> {
+        self.bytes(2).map(|v| i16::from_be_bytes([v[0], v[1]]))
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        self.bytes(4)
+            .map(|v| i32::from_be_bytes([v[0], v[1], v[2], v[3]]))
+    }
+
+    fn cstr(&mut self) -> Result<String> {
+        let end = self
+            .buf
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or_else(|| invalid_data("string is not terminated"))?;
+        let v = String::from_utf8(self.bytes(end)?.to_vec())
+            .map_err(|_| invalid_data("string is not valid utf-8"))?;
+        self.bytes(1)?;
+        Ok(v)
+    }
+}
+
+fn invalid_data(msg: impl Into<String>) -> Error {
+    Error::new(ErrorKind::InvalidData, msg.into())
+}
+
+/// A column of a row description
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct FieldDescription {
+    pub name: String,
+    pub type_oid: u32,
+    pub format: i16,
+}
+
+/// Appends the backend messages to a buffer, sent when the client waits for
+/// an answer
+#[derive(Default)]
+pub(super) struct Backend {
+    pub buf: Vec<u8>,
+}
+
+impl Backend {
+    fn message(&mut self, tag: u8, body: &[u8]) {
+        self.buf.push(tag);
+        self.buf
+            .extend_from_slice(&(body.len() as i32 + 4).to_be_bytes());
+        self.buf.extend_from_slice(body);
+    }
+
+    pub fn encryption_accepted(&mut self) {
+        self.buf.push(b'S');
+    }
+
+    pub fn encryption_refused(&mut self) {
+        self.buf.push(b'N');
+    }
+
+    pub fn auth_cleartext_password(&mut self) {
+        self.message(b'R', &3i32.to_be_bytes());
+    }
+
+    pub fn auth_ok(&mut self) {
+        self.message(b'R', &0i32.to_be_bytes());
+    }
+
+    pub fn parameter_status(&mut self, key: &str, value: &str) {
+        let mut body = Vec::new();
+        put_cstr(&mut body, key);
+        put_cstr(&mut body, value);
+        self.message(b'S', &body);
+    }
+
+    pub fn backend_key_data(&mut self, pid: i32, secret: i32) {
+        let mut body = pid.to_be_bytes().to_vec();
+        body.extend_from_slice(&secret.to_be_bytes());
+        self.message(b'K', &body);
+    }
+
+    /// Always idle, the sessions have no transactions
+    pub fn ready_for_query(&mut self) {
+        self.message(b'Z', b"I");
+    }
+
+    pub fn row_description(&mut self, fields: &[FieldDescription]) {
+        let mut body = (fields.len() as i16).to_be_bytes().to_vec();
+        for field in fields {
+            put_cstr(&mut body, &field.name);
+            // no table and column of the table
+            body.extend_from_slice(&0i32.to_be_bytes());
+            body.extend_from_slice(&0i16.to_be_bytes());
+            body.extend_from_slice(&field.type_oid.to_be_bytes());
+            body.extend_from_slice(&type_len(field.type_oid).to_be_bytes());
+            // no type modifier
+            body.extend_from_slice(&(-1i32).to_be_bytes());
+            body.extend_from_slice(&field.format.to_be_bytes());
+        }
+        self.message(b'T', &body);
+    }
+
+    pub fn data_row(&mut self, values: &[Option<Vec<u8>>]) {
+        let mut body = (values.len() as i16).to_be_bytes().to_vec();
+        for value in values {
+            match value {
+                Some(v) => {
+                    body.extend_from_slice(&(v.len() as i32).to_be_bytes());
+                    body.extend_from_slice(v);
+                }
+                None => body.extend_from_slice(&(-1i32).to_be_bytes()),
+            }
+        }
+        self.message(b'D', &body);
+    }
+
+    pub fn command_complete(&mut self, tag: &str) {
+        let mut body = Vec::new();
+        put_cstr(&mut body, tag);
+        self.message(b'C', &body);
+    }
+
+    pub fn empty_query(&mut self) {
+        self.message(b'I', &[]);
+    }
+
+    pub fn error(&mut self, severity: &str, code: &str, message: &str) {
+        let mut body = Vec::new();
+        for (field, value) in [
+            (b'S', severity),
+            (b'V', severity),
+            (b'C', code),
+            (b'M', message),
+        ] {
+            body.push(field);
+            put_cstr(&mut body, value);
+        }
+        body.push(0);
+        self.message(b'E', &body);
+    }
+
+    pub fn parse_complete(&mut self) {
+        self.message(b'1', &[]);
+    }
+
+    pub fn bind_complete(&mut self) {
+        self.message(b'2', &[]);
+    }
+
+    pub fn close_complete(&mut self) {
+        self.message(b'3', &[]);
+    }
+
+    pub fn no_data(&mut self) {
+        self.message(b'n', &[]);
+    }
+
+    pub fn portal_suspended(&mut self) {
+        self.message(b's', &[]);
+    }
+
+    pub fn parameter_description(&mut self, types: &[u32]) {
+        let mut body = (types.len() as i16).to_be_bytes().to_vec();
+        for oid in types {
+            body.extend_from_slice(&oid.to_be_bytes());
+        }
+        self.message(b't', &body);
+    }
+}
+
+fn put_cstr(buf: &mut Vec<u8>, v: &str) {
+    buf.extend_from_slice(v.as_bytes());
+    buf.push(0);
+}
+
+/// The type the values of a column are sent as
+pub(super) fn type_oid(data_type: &DataType) -> u32 {
+    match data_type {
+        DataType::Boolean => BOOL_OID,
+        DataType::Int8 | DataType::Int16 | DataType::UInt8 => INT2_OID,
+        DataType::Int32 | DataType::UInt16 => INT4_OID,
+        DataType::Int64 | DataType::UInt32 | DataType::UInt64 => INT8_OID,
+        DataType::Float16 | DataType::Float32 => FLOAT4_OID,
+        DataType::Float64 => FLOAT8_OID,
+        DataType::Timestamp(_, None) => TIMESTAMP_OID,
+        DataType::Timestamp(_, Some(_)) => TIMESTAMPTZ_OID,
+        _ => TEXT_OID,
+    }
+}
+
+fn type_len(oid: u32) -> i16 {
+    match oid {
+        BOOL_OID => 1,
+        INT2_OID => 2,
+        INT4_OID | FLOAT4_OID => 4,
+        INT8_OID | FLOAT8_OID | TIMESTAMP_OID | TIMESTAMPTZ_OID => 8,
+        _ => -1,
+    }
+}
+
+/// The format of the column `i`, a single format applies to all the columns
+pub(super) fn column_format(formats: &[i16], i: usize) -> i16 {
+    match formats {
+        [] => FORMAT_TEXT,
+        [format] => *format,
+        formats => formats.get(i).copied().unwrap_or(FORMAT_TEXT),
+    }
+}
+
+/// Encodes the values of a column in the text or the binary format of its
+/// type, see [`type_oid`]
+pub(super) fn encode_column(array: &ArrayRef, format: i16) -> Result<Vec<Option<Vec<u8>>>> {
+    let oid = type_oid(array.data_type());
+    if format == FORMAT_BINARY && oid != TEXT_OID {
+        return encode_binary(array, oid).map_err(|e| Error::other(e.to_string()));
+    }
+    if oid == BOOL_OID {
+        let values = array.as_boolean();
+        return Ok((0..values.len())
+            .map(|i| {
+                values
+                    .is_valid(i)
+                    .then(|| if values.value(i) { b"t" } else { b"f" }.to_vec())
+            })
+            .collect());
+    }
+    let options = FormatOptions::default()
+        .with_timestamp_format(Some("%Y-%m-%d %H:%M:%S%.6f"))
+        .with_timestamp_tz_format(Some("%Y-%m-%d %H:%M:%S%.6f%:z"));
+    let formatter = ArrayFormatter::try_new(array.as_ref(), &options)
+        .map_err(|e| Error::other(e.to_string()))?;
+    Ok((0..array.len())
+        .map(|i| {
+            array
+                .is_valid(i)
+                .then(|| formatter.value(i).to_string().into_bytes())
+        })
+        .collect())
+}
+
+fn encode_binary(
+    array: &ArrayRef,
+    oid: u32,
+) -> std::result::Result<Vec<Option<Vec<u8>>>, arrow::error::ArrowError> {
+    let values = match oid {
+        BOOL_OID => {
+            let array = array.as_boolean();
+            (0..array.len())
+                .map(|i| array.is_valid(i).then(|| vec![array.value(i) as u8]))
+                .collect()
+        }
+        INT2_OID => cast(array, &DataType::Int16)?
+            .as_primitive::<Int16Type>()
+            .iter()
+            .map(|v| v.map(|v| v.to_be_bytes().to_vec()))
+            .collect(),
+        INT4_OID => cast(array, &DataType::Int32)?
+            .as_primitive::<Int32Type>()
+            .iter()
+            .map(|v| v.map(|v| v.to_be_bytes().to_vec()))
+            .collect(),
+        INT8_OID => cast(array, &DataType::Int64)?
+            .as_primitive::<Int64Type>()
+            .iter()
+            .map(|v| v.map(|v| v.to_be_bytes().to_vec()))
+            .collect(),
+        FLOAT4_OID => cast(array, &DataType::Float32)?
+            .as_primitive::<Float32Type>()
+            .iter()
+            .map(|v| v.map(|v| v.to_be_bytes().to_vec()))
+            .collect(),
+        FLOAT8_OID => cast(array, &DataType::Float64)?
+            .as_primitive::<Float64Type>()
+            .iter()
+            .map(|v| v.map(|v| v.to_be_bytes().to_vec()))
+            .collect(),
+        _ => {
+            // the timestamps, in microseconds since the PostgreSQL epoch
+            let tz = match array.data_type() {
+                DataType::Timestamp(_, tz) => tz.clone(),
+                _ => None,
+            };
+            let array = cast(array, &DataType::Timestamp(TimeUnit::Microsecond, tz))?;
+            cast(&array, &DataType::Int64)?
+                .as_primitive::<Int64Type>()
+                .iter()
+                .map(|v| v.map(|v| (v - PG_EPOCH_MICROS).to_be_bytes().to_vec()))
+                .collect()
+        }
+    };
+    Ok(values)
+}
+
+/// The SQL literal of a bound parameter, the text values are quoted and
+/// compared to the other types through the coercion of the query
+pub(super) fn param_literal(value: Option<&[u8]>, format: i16, oid: u32) -> Result<String> {
+    let Some(value) = value else {
+        return Ok("NULL".to_string());
+    };
+    if format != FORMAT_BINARY {
+        let value =
+            std::str::from_utf8(value).map_err(|_| invalid_data("parameter is not utf-8"))?;
+        return Ok(format!("'{}'", value.replace('\'', "''")));
+    }
+    let literal = match (oid, value.len()) {
+        (BOOL_OID, 1) => (value[0] != 0).to_string(),
+        (INT2_OID, 2) => i16::from_be_bytes(value.try_into().unwrap()).to_string(),
+        (INT4_OID, 4) => i32::from_be_bytes(value.try_into().unwrap()).to_string(),
+        (INT8_OID, 8) => i64::from_be_bytes(value.try_into().unwrap()).to_string(),
+        (FLOAT4_OID, 4) => f32::from_be_bytes(value.try_into().unwrap()).to_string(),
+        (FLOAT8_OID, 8) => f64::from_be_bytes(value.try_into().unwrap()).to_string(),
+        (TEXT_OID, _) | (0, _) => {
+            return param_literal(Some(value), FORMAT_TEXT, oid);
+        }
+        _ => {
+            return Err(invalid_data(format!(
+                "binary parameters of type {oid} are not supported"
+            )));
+        }
+    };
+    Ok(literal)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{BooleanArray, Int64Array, StringArray, TimestampMicrosecondArray};
+
+    use super::*;
+
+    fn frame(tag: u8, body: &[u8]) -> Vec<u8> {
+        let mut backend = Backend::default();
+        backend.message(tag, body);
+        backend.buf
+    }
+
+    #[tokio::test]
+    async fn test_read_startup() {
+        let mut body = PROTOCOL_VERSION_3.to_be_bytes().to_vec();
+        body.extend_from_slice(b"user\0root@example.com\0database\0default\0\0");
+        let mut buf = ((body.len() + 4) as i32).to_be_bytes().to_vec();
+        buf.extend_from_slice(&body);
+        let Startup::Params(params) = read_startup(&mut buf.as_slice()).await.unwrap() else {
+            panic!("expected the startup parameters");
+        };
+        assert_eq!(params.get("user").unwrap(), "root@example.com");
+        assert_eq!(params.get("database").unwrap(), "default");
+
+        let mut buf = 8i32.to_be_bytes().to_vec();
+        buf.extend_from_slice(&SSL_REQUEST_CODE.to_be_bytes());
+        assert_eq!(
+            read_startup(&mut buf.as_slice()).await.unwrap(),
+            Startup::SslRequest
+        );
+
+        // the length includes itself, it can't be less than 4
+        for len in [0, i32::MIN] {
+            let buf = len.to_be_bytes().to_vec();
+            assert!(read_startup(&mut buf.as_slice()).await.is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_message() {
+        let buf = frame(b'Q', b"SELECT 1\0");
+        assert_eq!(
+            read_message(&mut buf.as_slice()).await.unwrap(),
+            Some(FrontendMessage::Query("SELECT 1".to_string()))
+        );
+
+        let mut body = b"\0stmt\0".to_vec();
+        body.extend_from_slice(&1i16.to_be_bytes());
+        body.extend_from_slice(&0i16.to_be_bytes());
+        body.extend_from_slice(&2i16.to_be_bytes());
+        body.extend_from_slice(&3i32.to_be_bytes());
+        body.extend_from_slice(b"abc");
+        body.extend_from_slice(&(-1i32).to_be_bytes());
+        body.extend_from_slice(&0i16.to_be_bytes());
+        let buf = frame(b'B', &body);
+        assert_eq!(
+            read_message(&mut buf.as_slice()).await.unwrap(),
+            Some(FrontendMessage::Bind {
+                portal: "".to_string(),
+                statement: "stmt".to_string(),
+                param_formats: vec![0],
+                params: vec![Some(b"abc".to_vec()), None],
+                result_formats: vec![],
+            })
+        );
+
+        assert_eq!(read_message(&mut &b""[..]).await.unwrap(), None);
+        let buf = frame(b'Q', b"SELECT 1");
+        assert!(read_message(&mut buf.as_slice()).await.is_err());
+    }
+
+    #[test]
+    fn test_encode_column() {
+        let array = Arc::new(BooleanArray::from(vec![Some(true), None])) as ArrayRef;
+        assert_eq!(
+            encode_column(&array, FORMAT_TEXT).unwrap(),
+            vec![Some(b"t".to_vec()), None]
+        );
+        let array = Arc::new(Int64Array::from(vec![258])) as ArrayRef;
+        assert_eq!(
+            encode_column(&array, FORMAT_TEXT).unwrap(),
+            vec![Some(b"258".to_vec())]
+        );
+        assert_eq!(
+            encode_column(&array, FORMAT_BINARY).unwrap(),
+            vec![Some(258i64.to_be_bytes().to_vec())]
+        );
+        let array = Arc::new(StringArray::from(vec!["a"])) as ArrayRef;
+        assert_eq!(
+            encode_column(&array, FORMAT_BINARY).unwrap(),
+            vec![Some(b"a".to_vec())]
+        );
+        let array =
+            Arc::new(TimestampMicrosecondArray::from(vec![PG_EPOCH_MICROS + 1])) as ArrayRef;
+        assert_eq!(
+            encode_column(&array, FORMAT_TEXT).unwrap(),
+            vec![Some(b"2000-01-01 00:00:00.000001".to_vec())]
+        );
+        assert_eq!(
+            encode_column(&array, FORMAT_BINARY).unwrap(),
+            vec![Some(1i64.to_be_bytes().to_vec())]
+        );
+    }
+
+    #[test]
+    fn test_param_literal() {
+        assert_eq!(param_literal(None, FORMAT_TEXT, 0).unwrap(), "NULL");
+        assert_eq!(
+            param_literal(Some(b"it's"), FORMAT_TEXT, TEXT_OID).unwrap(),
+            "'it''s'"
+        );
+        assert_eq!(
+            param_literal(Some(&7i32.to_be_bytes()), FORMAT_BINARY, INT4_OID).unwrap(),
+            "7"
+        );
+        assert!(param_literal(Some(&[0; 3]), FORMAT_BINARY, INT4_OID).is_err());
+    }
+
+    #[test]
+    fn test_column_format() {
+        assert_eq!(column_format(&[], 3), FORMAT_TEXT);
+        assert_eq!(column_format(&[FORMAT_BINARY], 3), FORMAT_BINARY);
+        assert_eq!(
+            column_format(&[FORMAT_TEXT, FORMAT_BINARY], 1),
+            FORMAT_BINARY
+        );
+    }
+}
@@ -0,0 +1,864 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Read only SQL over the PostgreSQL wire protocol, for the BI tools and the
+//! generic SQL clients using a PostgreSQL driver.
+//!
+//! The database is the organization, the users log in with their password or
+//! token. The connections use TLS with the certificate of the HTTP server, the
+//! passwords are only accepted without it when `ZO_PGWIRE_ALLOW_CLEARTEXT` is
+//! set. The connections are capped, and closed once idle for
+//! `ZO_PGWIRE_IDLE_TIMEOUT` seconds.
+//!
+//! The tables are the logs streams, the streams of the other types are
+//! prefixed by their type, like `traces."default"`. As the clients can't send
+//! a time range the queries search the last `ZO_PGWIRE_QUERY_RANGE_HOURS`.
+//!
+//! The simple and the extended query protocols are supported, the results are
+//! sent in the text or the binary format. The sessions have no transactions,
+//! `SET`, `BEGIN` and the like are accepted and ignored.
+
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use arrow::{
+    array::{RecordBatch, StringArray},
+    compute::concat_batches,
+    datatypes::{DataType, Field, Schema},
+};
+#[cfg(feature = "enterprise")]
+use config::meta::sql::TableReferenceExt;
+use config::{
+    get_config, ider,
+    meta::{search, sql::resolve_stream_names_with_type, stream::StreamType},
+    utils::time::now_micros,
+};
+use datafusion::prelude::SessionContext;
+use hashbrown::HashMap;
+use infra::errors::{Error, ErrorCodes};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::Semaphore,
+};
+use tokio_rustls::TlsAcceptor;
+
+use self::codec::{
+    Backend, FieldDescription, FrontendMessage, Startup, TEXT_OID, column_format, encode_column,
+    param_literal, type_oid,
+};
+use crate::{
+    common::meta::organization::DEFAULT_ORG,
+    handler::grpc::auth::check_credentials,
+    service::{
+        search::{external_flight, information_schema},
+        tls::http_tls_config,
+    },
+};
+
+mod codec;
+
+/// Sent to the clients after the authentication, and returned by `SHOW`
+const SERVER_PARAMETERS: [(&str, &str); 8] = [
+    ("server_version", "14.0"),
+    ("server_encoding", "UTF8"),
+    ("client_encoding", "UTF8"),
+    ("DateStyle", "ISO, MDY"),
+    ("IntervalStyle", "postgres"),
+    ("TimeZone", "UTC"),
+    ("integer_datetimes", "on"),
+    ("standard_conforming_strings", "on"),
+];
+
+const SYNTAX_ERROR: &str = "42601";
+const UNDEFINED_TABLE: &str = "42P01";
+const UNDEFINED_OBJECT: &str = "42704";
+#[cfg(feature = "enterprise")]
+const INSUFFICIENT_PRIVILEGE: &str = "42501";
+const INVALID_PASSWORD: &str = "28P01";
+const INVALID_AUTHORIZATION: &str = "28000";
+const TOO_MANY_CONNECTIONS: &str = "53300";
+const READ_ONLY_TRANSACTION: &str = "25006";
+const INVALID_STATEMENT_NAME: &str = "26000";
+const INVALID_CURSOR_NAME: &str = "34000";
+const PROTOCOL_VIOLATION: &str = "08P01";
+const INTERNAL_ERROR: &str = "XX000";
+
+pub async fn run_server() -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    if !cfg.pgwire.enabled {
+        return Ok(());
+    }
+    let tls = if cfg.http.tls_enabled {
+        Some(TlsAcceptor::from(Arc::new(http_tls_config()?)))
+    } else {
+        None
+    };
+    let connections = Arc::new(Semaphore::new(cfg.pgwire.max_connections));
+    let addr: SocketAddr = format!("{}:{}", cfg.pgwire.addr, cfg.pgwire.port).parse()?;
+    let listener = TcpListener::bind(addr).await?;
+    log::info!("[PGWIRE] listening on tcp://{addr}");
+    loop {
+        let (mut stream, peer) = match listener.accept().await {
+            Ok(v) => v,
+            Err(e) => {
+                log::error!("[PGWIRE] accept error: {e}");
+                continue;
+            }
+        };
+        let Ok(permit) = connections.clone().try_acquire_owned() else {
+            log::warn!("[PGWIRE] connection from {peer} refused, too many connections");
+            tokio::task::spawn(async move {
+                let mut backend = Backend::default();
+                backend.error("FATAL", TOO_MANY_CONNECTIONS, "too many connections");
+                flush(&mut stream, &mut backend).await.ok();
+            });
+            continue;
+        };
+        let tls = tls.clone();
+        tokio::task::spawn(async move {
+            if let Err(e) = serve_connection(stream, tls).await {
+                log::debug!("[PGWIRE] connection from {peer} closed: {e}");
+            }
+            drop(permit);
+        });
+    }
+}
+
+/// Waits for the next message of the client up to the idle timeout
+async fn idle<T>(read: impl Future<Output = std::io::Result<T>>) -> std::io::Result<T> {
+    let timeout = Duration::from_secs(get_config().pgwire.idle_timeout);
+    tokio::time::timeout(timeout, read).await.map_err(|_| {
+        std::io::Error::new(std::io::ErrorKind::TimedOut, "idle connection timed out")
+    })?
+}
+
+async fn serve_connection(mut stream: TcpStream, tls: Option<TlsAcceptor>) -> std::io::Result<()> {
+    stream.set_nodelay(true).ok();
+    // the clients ask for TLS before sending the startup message
+    let startup = idle(codec::read_startup(&mut stream)).await?;
+    match tls {
+        Some(tls) if startup == Startup::SslRequest => {
+            let mut backend = Backend::default();
+            backend.encryption_accepted();
+            flush(&mut stream, &mut backend).await?;
+            let stream = tls.accept(stream).await?;
+            serve_session(stream, None, true).await
+        }
+        _ => serve_session(stream, Some(startup), false).await,
+    }
+}
+
+async fn serve_session<S: AsyncRead + AsyncWrite>(
+    stream: S,
+    mut startup: Option<Startup>,
+    encrypted: bool,
+) -> std::io::Result<()> {
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut reader = BufReader::new(reader);
+    let mut backend = Backend::default();
+
+    let params = loop {
+        let next = match startup.take() {
+            Some(startup) => startup,
+            None => idle(codec::read_startup(&mut reader)).await?,
+        };
+        match next {
+            Startup::SslRequest | Startup::GssEncRequest => {
+                backend.encryption_refused();
+                flush(&mut writer, &mut backend).await?;
+            }
+            // the queries can't be cancelled
+            Startup::Cancel => return Ok(()),
+            Startup::Params(params) => break params,
+        }
+    };
+    if !encrypted && !get_config().pgwire.allow_cleartext {
+        backend.error(
+            "FATAL",
+            INVALID_AUTHORIZATION,
+            "the connection must use SSL to send the password",
+        );
+        return flush(&mut writer, &mut backend).await;
+    }
+    let user_id = params.get("user").cloned().unwrap_or_default();
+    let org_id = params
+        .get("database")
+        .filter(|v| !v.is_empty())
+        .cloned()
+        .unwrap_or_else(|| DEFAULT_ORG.to_string());
+
+    backend.auth_cleartext_password();
+    flush(&mut writer, &mut backend).await?;
+    let Some(FrontendMessage::Password(password)) = idle(codec::read_message(&mut reader)).await?
+    else {
+        return Ok(());
+    };
+    if check_credentials(&org_id, &user_id, &password) != Some(true) {
+        backend.error(
+            "FATAL",
+            INVALID_PASSWORD,
+            &format!("password authentication failed for user \"{user_id}\""),
+        );
+        return flush(&mut writer, &mut backend).await;
+    }
+    backend.auth_ok();
+    for (key, value) in SERVER_PARAMETERS {
+        backend.parameter_status(key, value);
+    }
+    backend.backend_key_data(rand::random(), rand::random());
+    backend.ready_for_query();
+    flush(&mut writer, &mut backend).await?;
+    log::info!("[PGWIRE] user {user_id} connected to organization {org_id}");
+
+    let mut session = Session::new(org_id, user_id);
+    while let Some(msg) = idle(codec::read_message(&mut reader)).await? {
+        match msg {
+            FrontendMessage::Terminate => break,
+            FrontendMessage::Sync => {
+                session.failed = false;
+                backend.ready_for_query();
+                flush(&mut writer, &mut backend).await?;
+            }
+            FrontendMessage::Flush => flush(&mut writer, &mut backend).await?,
+            FrontendMessage::Query(sql) => {
+                session.simple_query(&sql, &mut backend).await;
+                backend.ready_for_query();
+                flush(&mut writer, &mut backend).await?;
+            }
+            // after an error the messages are skipped up to the next sync
+            _ if session.failed => {}
+            msg => {
+                if let Err(e) = session.extended_query(msg, &mut backend).await {
+                    backend.error("ERROR", e.code, &e.message);
+                    session.failed = true;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn flush<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    backend: &mut Backend,
+) -> std::io::Result<()> {
+    writer.write_all(&backend.buf).await?;
+    backend.buf.clear();
+    writer.flush().await
+}
+
+#[derive(Debug)]
+struct PgError {
+    code: &'static str,
+    message: String,
+}
+
+impl PgError {
+    fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<Error> for PgError {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::ErrorCode(e) => {
+                let code = match e {
+                    ErrorCodes::SearchSQLNotValid(_) => SYNTAX_ERROR,
+                    ErrorCodes::SearchStreamNotFound(_) => UNDEFINED_TABLE,
+                    _ => INTERNAL_ERROR,
+                };
+                Self::new(code, e.get_message())
+            }
+            e => Self::new(INTERNAL_ERROR, e.to_string()),
+        }
+    }
+}
+
+enum QueryResult {
+    Empty,
+    Command(String),
+    Rows(RecordBatch),
+}
+
+/// A statement prepared by a parse message
+struct Statement {
+    sql: String,
+    param_types: Vec<u32>,
+}
+
+/// A statement bound to its parameters, run by the first describe or execute
+/// message, the rows are sent from where the previous execute stopped
+struct Portal {
+    sql: String,
+    result_formats: Vec<i16>,
+    result: Option<QueryResult>,
+    sent_rows: usize,
+}
+
+struct Session {
+    org_id: String,
+    user_id: String,
+    statements: HashMap<String, Statement>,
+    portals: HashMap<String, Portal>,
+    failed: bool,
+}
+
+impl Session {
+    fn new(org_id: String, user_id: String) -> Self {
+        Self {
+            org_id,
+            user_id,
+            statements: HashMap::new(),
+            portals: HashMap::new(),
+            failed: false,
+        }
+    }
+
+    async fn simple_query(&mut self, sql: &str, backend: &mut Backend) {
+        let statements = split_statements(sql);
+        if statements.is_empty() {
+            backend.empty_query();
+            return;
+        }
+        for sql in statements {
+            match self.run(sql).await {
+                Ok(result) => {
+                    if matches!(result, QueryResult::Rows(_)) {
+                        describe(&result, &[], backend);
+                    }
+                    send_result(&result, &[], 0, 0, backend);
+                }
+                Err(e) => {
+                    backend.error("ERROR", e.code, &e.message);
+                    return;
+                }
+            }
+        }
+    }
+
+    async fn extended_query(
+        &mut self,
+        msg: FrontendMessage,
+        backend: &mut Backend,
+    ) -> Result<(), PgError> {
+        match msg {
+            FrontendMessage::Parse {
+                name,
+                query,
+                param_types,
+            } => {
+                self.statements.insert(
+                    name,
+                    Statement {
+                        sql: query,
+                        param_types,
+                    },
+                );
+                backend.parse_complete();
+            }
+            FrontendMessage::Bind {
+                portal,
+                statement,
+                param_formats,
+                params,
+                result_formats,
+            } => {
+                let stmt = self.statement(&statement)?;
+                let literals = params
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| {
+                        let oid = stmt.param_types.get(i).copied().unwrap_or_default();
+                        param_literal(v.as_deref(), column_format(&param_formats, i), oid)
+                            .map_err(|e| PgError::new(PROTOCOL_VIOLATION, e.to_string()))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let sql = bind_params(&stmt.sql, &literals)?;
+                self.portals.insert(
+                    portal,
+                    Portal {
+                        sql,
+                        result_formats,
+                        result: None,
+                        sent_rows: 0,
+                    },
+                );
+                backend.bind_complete();
+            }
+            FrontendMessage::Describe { kind: b'S', name } => {
+                let stmt = self.statement(&name)?;
+                let num_params = count_params(&stmt.sql).max(stmt.param_types.len());
+                let types = (0..num_params)
+                    .map(|i| match stmt.param_types.get(i) {
+                        Some(oid) if *oid != 0 => *oid,
+                        _ => TEXT_OID,
+                    })
+                    .collect::<Vec<_>>();
+                // the columns are known once the statement runs, without the
+                // parameters it runs with nulls
+                let sql = bind_params(&stmt.sql, &vec!["NULL".to_string(); num_params])?;
+                backend.parameter_description(&types);
+                let result = self.run(&sql).await?;
+                describe(&result, &[], backend);
+            }
+            FrontendMessage::Describe { name, .. } => {
+                let portal = self.portal(&name).await?;
+                describe(
+                    portal.result.as_ref().unwrap(),
+                    &portal.result_formats,
+                    backend,
+                );
+            }
+            FrontendMessage::Execute { portal, max_rows } => {
+                let portal = self.portal(&portal).await?;
+                let sent_rows = send_result(
+                    portal.result.as_ref().unwrap(),
+                    &portal.result_formats,
+                    portal.sent_rows,
+                    max_rows.max(0) as usize,
+                    backend,
+                );
+                portal.sent_rows += sent_rows;
+            }
+            FrontendMessage::Close { kind, name } => {
+                if kind == b'S' {
+                    self.statements.remove(&name);
+                } else {
+                    self.portals.remove(&name);
+                }
+                backend.close_complete();
+            }
+            _ => {
+                return Err(PgError::new(
+                    PROTOCOL_VIOLATION,
+                    "unsupported message, only the simple and extended query protocols are supported",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn statement(&self, name: &str) -> Result<&Statement, PgError> {
+        self.statements.get(name).ok_or_else(|| {
+            PgError::new(
+                INVALID_STATEMENT_NAME,
+                format!("prepared statement \"{name}\" does not exist"),
+            )
+        })
+    }
+
+    /// The portal, with the result of its statement
+    async fn portal(&mut self, name: &str) -> Result<&mut Portal, PgError> {
+        let Some(portal) = self.portals.get(name) else {
+            return Err(PgError::new(
+                INVALID_CURSOR_NAME,
+                format!("portal \"{name}\" does not exist"),
+            ));
+        };
+        if portal.result.is_none() {
+            let sql = portal.sql.clone();
+            let result = self.run(&sql).await?;
+            self.portals.get_mut(name).unwrap().result = Some(result);
+        }
+        Ok(self.portals.get_mut(name).unwrap())
+    }
+
+    async fn run(&self, sql: &str) -> Result<QueryResult, PgError> {
+        match classify(sql) {
+            StatementKind::Empty => Ok(QueryResult::Empty),
+            StatementKind::Command(tag) => Ok(QueryResult::Command(tag)),
+            StatementKind::Show(name) => show(&name),
+            StatementKind::Query => self.search(sql).await,
+            StatementKind::Write => Err(PgError::new(
+                READ_ONLY_TRANSACTION,
+                "only queries are supported, the streams are read only",
+            )),
+        }
+    }
+
+    async fn search(&self, sql: &str) -> Result<QueryResult, PgError> {
+        // the information_schema views only list the streams the user can read
+        if information_schema::is_query(sql) {
+            let batches = information_schema::search(&self.org_id, Some(&self.user_id), sql)
+                .await
+                .map_err(PgError::from)?;
+            return rows(batches);
+        }
+        let streams = resolve_stream_names_with_type(sql)
+            .map_err(|e| PgError::new(SYNTAX_ERROR, e.to_string()))?;
+        // the queries without a stream, like `SELECT version()`
+        if streams.is_empty() {
+            let ctx = SessionContext::new();
+            let batches = async { ctx.sql(sql).await?.collect().await }
+                .await
+                .map_err(|e| PgError::new(SYNTAX_ERROR, e.to_string()))?;
+            return rows(batches);
+        }
+        #[cfg(feature = "enterprise")]
+        for stream in streams.iter() {
+            let stream_name = stream.stream_name();
+            if let Err(e) =
+                crate::service::search::check_search_allowed(&self.org_id, Some(&stream_name))
+            {
+                return Err(PgError::new(INTERNAL_ERROR, e.to_string()));
+            }
+            if crate::handler::http::request::search::utils::check_stream_permissions(
+                &stream_name,
+                &self.org_id,
+                &self.user_id,
+                &stream.get_stream_type(StreamType::Logs),
+            )
+            .await
+            .is_some()
+            {
+                return Err(PgError::new(
+                    INSUFFICIENT_PRIVILEGE,
+                    format!("permission denied for stream {stream_name}"),
+                ));
+            }
+        }
+        #[cfg(not(feature = "enterprise"))]
+        drop(streams);
+
+        let cfg = get_config();
+        let end_time = now_micros();
+        let start_time = end_time - cfg.pgwire.query_range_hours * 3600 * 1_000_000;
+        let req = search::Request {
+            query: search::Query {
+                sql: sql.to_string(),
+                start_time,
+                end_time,
+                // the limit of the query, or the default one
+                size: -1,
+                ..Default::default()
+            },
+            search_type: Some(search::SearchEventType::Other),
+            ..Default::default()
+        };
+        let trace_id = ider::generate_trace_id();
+        log::info!(
+            "[trace_id {trace_id}] pgwire->search: org: {}, user: {}",
+            self.org_id,
+            self.user_id
+        );
+        let (batches, _) = external_flight::search(
+            &trace_id,
+            &self.org_id,
+            StreamType::Logs,
+            Some(self.user_id.clone()),
+            &req,
+        )
+        .await
+        .map_err(|e| {
+            log::error!("[trace_id {trace_id}] pgwire->search: error: {e}");
+            PgError::from(e)
+        })?;
+        rows(batches)
+    }
+}
+
+fn rows(batches: Vec<RecordBatch>) -> Result<QueryResult, PgError> {
+    let Some(schema) = batches.first().map(|b| b.schema()) else {
+        return Ok(QueryResult::Rows(RecordBatch::new_empty(Arc::new(
+            Schema::empty(),
+        ))));
+    };
+    concat_batches(&schema, &batches)
+        .map(QueryResult::Rows)
+        .map_err(|e| PgError::new(INTERNAL_ERROR, e.to_string()))
+}
+
+fn describe(result: &QueryResult, formats: &[i16], backend: &mut Backend) {
+    let QueryResult::Rows(batch) = result else {
+        backend.no_data();
+        return;
+    };
+    let fields = batch
+        .schema()
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(i, f)| FieldDescription {
+            name: f.name().to_string(),
+            type_oid: type_oid(f.data_type()),
+            format: column_format(formats, i),
+        })
+        .collect::<Vec<_>>();
+    backend.row_description(&fields);
+}
+
+/// Sends the rows from `offset`, up to `max_rows` when not 0, and returns the
+/// number of rows sent
+fn send_result(
+    result: &QueryResult,
+    formats: &[i16],
+    offset: usize,
+    max_rows: usize,
+    backend: &mut Backend,
+) -> usize {
+    let batch = match result {
+        QueryResult::Empty => {
+            backend.empty_query();
+            return 0;
+        }
+        QueryResult::Command(tag) => {
+            backend.command_complete(tag);
+            return 0;
+        }
+        QueryResult::Rows(batch) => batch,
+    };
+    let remaining = batch.num_rows().saturating_sub(offset);
+    let num_rows = if max_rows > 0 {
+        remaining.min(max_rows)
+    } else {
+        remaining
+    };
+    let slice = batch.slice(offset, num_rows);
+    let mut columns = Vec::with_capacity(slice.num_columns());
+    for (i, column) in slice.columns().iter().enumerate() {
+        match encode_column(column, column_format(formats, i)) {
+            Ok(values) => columns.push(values),
+            Err(e) => {
+                backend.error("ERROR", INTERNAL_ERROR, &e.to_string());
+                return 0;
+            }
+        }
+    }
+    let mut values = Vec::with_capacity(columns.len());
+    for row in 0..num_rows {
+        values.clear();
+        values.extend(columns.iter_mut().map(|c| c[row].take()));
+        backend.data_row(&values);
+    }
+    if num_rows < remaining {
+        backend.portal_suspended();
+    } else {
+        backend.command_complete(&format!("SELECT {}", offset + num_rows));
+    }
+    num_rows
+}
+
+fn show(name: &str) -> Result<QueryResult, PgError> {
+    let name = name.trim().trim_matches('"');
+    let value = match name.to_lowercase().as_str() {
+        "transaction isolation level" | "transaction_isolation" => "read committed",
+        "search_path" => "public",
+        "max_identifier_length" => "63",
+        lower => SERVER_PARAMETERS
+            .iter()
+            .find(|(key, _)| key.to_lowercase() == lower)
+            .map(|(_, value)| *value)
+            .ok_or_else(|| {
+                PgError::new(
+                    UNDEFINED_OBJECT,
+                    format!("unrecognized configuration parameter \"{name}\""),
+                )
+            })?,
+    };
+    let schema = Arc::new(Schema::new(vec![Field::new(name, DataType::Utf8, false)]));
+    RecordBatch::try_new(schema, vec![Arc::new(StringArray::from(vec![value]))])
+        .map(QueryResult::Rows)
+        .map_err(|e| PgError::new(INTERNAL_ERROR, e.to_string()))
+}
+
+#[derive(Debug, PartialEq)]
+enum StatementKind {
+    Empty,
+    Query,
+    Show(String),
+    /// Accepted and ignored, with the tag of its completion
+    Command(String),
+    Write,
+}
+
+fn classify(sql: &str) -> StatementKind {
+    let sql = sql.trim().trim_start_matches('(').trim_start();
+    let (word, rest) = sql.split_once(char::is_whitespace).unwrap_or((sql, ""));
+    match word.trim_end_matches(';').to_uppercase().as_str() {
+        "" => StatementKind::Empty,
+        "SELECT" | "WITH" | "VALUES" => StatementKind::Query,
+        "SHOW" => StatementKind::Show(rest.trim_end_matches(';').to_string()),
+        "START" => StatementKind::Command("START TRANSACTION".to_string()),
+        "END" => StatementKind::Command("COMMIT".to_string()),
+        tag @ ("SET" | "RESET" | "BEGIN" | "COMMIT" | "ROLLBACK" | "DISCARD" | "DEALLOCATE"
+        | "CLOSE" | "LISTEN" | "UNLISTEN" | "SAVEPOINT" | "RELEASE") => {
+            StatementKind::Command(tag.to_string())
+        }
+        _ => StatementKind::Write,
+    }
+}
+
+/// Calls `f` on the characters out of the quoted strings and identifiers,
+/// with their position, the others are skipped
+fn scan_unquoted(sql: &str, mut f: impl FnMut(usize, char)) {
+    let mut quote = None;
+    for (i, c) in sql.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '\'' || c == '"' => quote = Some(c),
+            None => f(i, c),
+        }
+    }
+}
+
+fn split_statements(sql: &str) -> Vec<&str> {
+    let mut ends = Vec::new();
+    scan_unquoted(sql, |i, c| {
+        if c == ';' {
+            ends.push(i);
+        }
+    });
+    let mut start = 0;
+    let mut statements = Vec::new();
+    for end in ends.into_iter().chain(std::iter::once(sql.len())) {
+        let statement = sql[start..end].trim();
+        if !statement.is_empty() {
+            statements.push(statement);
+        }
+        start = (end + 1).min(sql.len());
+    }
+    statements
+}
+
+/// The `$n` placeholders out of the quoted strings and identifiers, as their
+/// position, length and number
+fn find_params(sql: &str) -> Vec<(usize, usize, usize)> {
+    let mut dollars = Vec::new();
+    scan_unquoted(sql, |i, c| {
+        if c == '$' {
+            dollars.push(i);
+        }
+    });
+    dollars
+        .into_iter()
+        .filter_map(|i| {
+            let digits = sql[i + 1..]
+                .bytes()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+            let num = sql[i + 1..i + 1 + digits].parse().ok()?;
+            Some((i, digits + 1, num))
+        })
+        .collect()
+}
+
+fn count_params(sql: &str) -> usize {
+    find_params(sql)
+        .into_iter()
+        .map(|(_, _, num)| num)
+        .max()
+        .unwrap_or_default()
+}
+
+fn bind_params(sql: &str, literals: &[String]) -> Result<String, PgError> {
+    let mut ret = String::with_capacity(sql.len());
+    let mut start = 0;
+    for (pos, len, num) in find_params(sql) {
+        let Some(literal) = num.checked_sub(1).and_then(|i| literals.get(i)) else {
+            return Err(PgError::new(
+                PROTOCOL_VIOLATION,
+                format!(
+                    "the statement needs parameter ${num}, but {} were bound",
+                    literals.len()
+                ),
+            ));
+        };
+        ret.push_str(&sql[start..pos]);
+        ret.push_str(literal);
+        start = pos + len;
+    }
+    ret.push_str(&sql[start..]);
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify("  "), StatementKind::Empty);
+        assert_eq!(classify("select * from t"), StatementKind::Query);
+        assert_eq!(classify("(SELECT 1)"), StatementKind::Query);
+        assert_eq!(
+            classify("SET extra_float_digits = 3"),
+            StatementKind::Command("SET".to_string())
+        );
+        assert_eq!(
+            classify("start transaction"),
+            StatementKind::Command("START TRANSACTION".to_string())
+        );
+        assert_eq!(
+            classify("SHOW TimeZone;"),
+            StatementKind::Show("TimeZone".to_string())
+        );
+        assert_eq!(classify("DELETE FROM t"), StatementKind::Write);
+    }
+
+    #[test]
+    fn test_split_statements() {
+        assert_eq!(
+            split_statements("SET a = 1; SELECT ';' FROM t;"),
+            vec!["SET a = 1", "SELECT ';' FROM t"]
+        );
+        assert!(split_statements(" ; ").is_empty());
+    }
+
+    #[test]
+    fn test_bind_params() {
+        let sql = "SELECT '$1' FROM \"t$2\" WHERE a = $1 AND b = $2 LIMIT $10";
+        assert_eq!(count_params(sql), 10);
+        let literals = (1..=10).map(|i| i.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            bind_params(sql, &literals).unwrap(),
+            "SELECT '$1' FROM \"t$2\" WHERE a = 1 AND b = 2 LIMIT 10"
+        );
+        assert!(bind_params("SELECT $2", &["1".to_string()]).is_err());
+        assert_eq!(bind_params("SELECT 1", &[]).unwrap(), "SELECT 1");
+    }
+
+    #[test]
+    fn test_show() {
+        let QueryResult::Rows(batch) = show("server_version").unwrap() else {
+            panic!("expected rows");
+        };
+        assert_eq!(batch.schema().field(0).name(), "server_version");
+        assert_eq!(batch.num_rows(), 1);
+        assert!(show("no_such_parameter").is_err());
+    }
+
+    #[test]
+    fn test_send_result() {
+        let schema = Arc::new(Schema::new(vec![Field::new("v", DataType::Utf8, false)]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(StringArray::from(vec!["a", "b", "c"]))],
+        )
+        .unwrap();
+        let result = QueryResult::Rows(batch);
+        let mut backend = Backend::default();
+        assert_eq!(send_result(&result, &[], 0, 2, &mut backend), 2);
+        // two data rows then portal suspended
+        assert_eq!(backend.buf.iter().filter(|b| **b == b'D').count(), 2);
+        assert_eq!(backend.buf.last(), Some(&4));
+        let mut backend = Backend::default();
+        assert_eq!(send_result(&result, &[], 2, 2, &mut backend), 1);
+        assert!(backend.buf.ends_with(b"SELECT 3\0"));
+    }
+}
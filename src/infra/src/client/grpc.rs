@@ -26,7 +26,7 @@ use tonic::{
     codec::CompressionEncoding,
     metadata::{MetadataKey, MetadataValue},
     service::interceptor::InterceptedService,
-    transport::{Certificate, Channel, ClientTlsConfig},
+    transport::{Certificate, Channel, ClientTlsConfig, Identity},
 };
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
@@ -70,6 +70,12 @@ pub async fn get_cached_channel(grpc_addr: &str) -> Result<Channel, tonic::Statu
     Ok(channel.clone())
 }
 
+/// Drops the cached channels, the next requests connect again. Called when the
+/// gRPC certificates changed, so the nodes present and trust the new ones.
+pub async fn clear_cached_channels() {
+    CHANNELS.write().await.clear();
+}
+
 pub async fn create_channel(grpc_addr: &str) -> Result<Channel, tonic::Status> {
     let cfg = config::get_config();
     let mut channel = Channel::from_shared(grpc_addr.to_string()).map_err(|err| {
@@ -77,16 +83,29 @@ pub async fn create_channel(grpc_addr: &str) -> Result<Channel, tonic::Status> {
         Status::internal("parse gRPC node error".to_string())
     })?;
     if cfg.grpc.tls_enabled {
-        let pem = std::fs::read_to_string(&cfg.grpc.tls_cert_path)?;
+        let ca_path = if cfg.grpc.tls_ca_cert_path.is_empty() {
+            &cfg.grpc.tls_cert_path
+        } else {
+            &cfg.grpc.tls_ca_cert_path
+        };
+        let pem = std::fs::read_to_string(ca_path)?;
         let cert = Certificate::from_pem(pem);
-        let tls = ClientTlsConfig::new()
+        let mut tls = ClientTlsConfig::new()
             .ca_certificate(cert)
             .domain_name(&cfg.grpc.tls_cert_domain);
+        if cfg.grpc.tls_client_auth {
+            let cert = std::fs::read_to_string(&cfg.grpc.tls_cert_path)?;
+            let key = std::fs::read_to_string(&cfg.grpc.tls_key_path)?;
+            tls = tls.identity(Identity::from_pem(cert, key));
+        }
         channel = channel.tls_config(tls).map_err(|err| {
             log::error!("gRPC node: {}, tls err: {:?}", &grpc_addr, err);
             Status::internal("tls gRPC node error".to_string())
         })?;
     }
+    if cfg.grpc.stream_window_size > 0 {
+        channel = channel.initial_stream_window_size(cfg.grpc.stream_window_size * 1024);
+    }
     let channel = channel
         .connect_timeout(std::time::Duration::from_secs(
             config::get_config().grpc.connect_timeout,
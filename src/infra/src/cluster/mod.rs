@@ -501,6 +501,11 @@ pub async fn get_cached_online_ingester_nodes() -> Option<Vec<Node>> {
     get_cached_nodes(|node| node.status == NodeStatus::Online && node.is_ingester()).await
 }
 
+#[inline]
+pub async fn get_cached_online_indexer_nodes() -> Option<Vec<Node>> {
+    get_cached_nodes(|node| node.status == NodeStatus::Online && node.is_indexer()).await
+}
+
 #[inline]
 pub async fn get_cached_schedulable_ingester_nodes() -> Option<Vec<Node>> {
     get_cached_nodes(|node| {
@@ -527,6 +532,9 @@ fn filter_nodes_with_group(
                 || n.role_group == RoleGroup::None
                 || n.role_group == RoleGroup::Background
         }),
+        Some(RoleGroup::Shadow) => {
+            nodes.retain(|n| !n.is_querier() || n.role_group == RoleGroup::Shadow)
+        }
         _ => {}
     };
     Some(nodes)
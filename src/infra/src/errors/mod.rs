@@ -88,6 +88,8 @@ pub enum Error {
     #[error("Error# {0}")]
     IngestionError(String),
     #[error("Error# {0}")]
+    QuotaExceeded(String),
+    #[error("Error# {0}")]
     WalFileError(String),
     #[error("Error# {0}")]
     OtherError(#[from] anyhow::Error),
@@ -199,6 +201,10 @@ pub enum ErrorCodes {
     InvalidParams(String),
     RatelimitExceeded(String),
     SearchHistogramNotAvailable(String),
+    SearchQuotaExceeded(String),
+    SearchLimitExceeded(String),
+    SearchFieldAccessDenied(String),
+    SearchColdStorageRestoreRequired(String),
 }
 
 impl From<sea_orm::DbErr> for Error {
@@ -254,6 +260,10 @@ impl ErrorCodes {
             ErrorCodes::InvalidParams(_) => 20011,
             ErrorCodes::RatelimitExceeded(_) => 20012,
             ErrorCodes::SearchHistogramNotAvailable(_) => 20013,
+            ErrorCodes::SearchQuotaExceeded(_) => 20014,
+            ErrorCodes::SearchLimitExceeded(_) => 20015,
+            ErrorCodes::SearchFieldAccessDenied(_) => 20016,
+            ErrorCodes::SearchColdStorageRestoreRequired(_) => 20017,
         }
     }
 
@@ -283,6 +293,12 @@ impl ErrorCodes {
             ErrorCodes::SearchHistogramNotAvailable(_) => {
                 "Search histogram not available".to_string()
             }
+            ErrorCodes::SearchQuotaExceeded(_) => "Search quota exceeded".to_string(),
+            ErrorCodes::SearchLimitExceeded(_) => "Search limit exceeded".to_string(),
+            ErrorCodes::SearchFieldAccessDenied(_) => "Search field access denied".to_string(),
+            ErrorCodes::SearchColdStorageRestoreRequired(_) => {
+                "Search data in the cold storage, restore required".to_string()
+            }
         }
     }
 
@@ -302,6 +318,10 @@ impl ErrorCodes {
             ErrorCodes::InvalidParams(msg) => msg.to_owned(),
             ErrorCodes::RatelimitExceeded(msg) => msg.to_owned(),
             ErrorCodes::SearchHistogramNotAvailable(msg) => msg.to_owned(),
+            ErrorCodes::SearchQuotaExceeded(msg) => msg.to_owned(),
+            ErrorCodes::SearchLimitExceeded(msg) => msg.to_owned(),
+            ErrorCodes::SearchFieldAccessDenied(msg) => msg.to_owned(),
+            ErrorCodes::SearchColdStorageRestoreRequired(msg) => msg.to_owned(),
         }
     }
 
@@ -321,6 +341,10 @@ impl ErrorCodes {
             ErrorCodes::InvalidParams(msg) => msg.to_owned(),
             ErrorCodes::RatelimitExceeded(msg) => msg.to_owned(),
             ErrorCodes::SearchHistogramNotAvailable(msg) => msg.to_owned(),
+            ErrorCodes::SearchQuotaExceeded(msg) => msg.to_owned(),
+            ErrorCodes::SearchLimitExceeded(msg) => msg.to_owned(),
+            ErrorCodes::SearchFieldAccessDenied(msg) => msg.to_owned(),
+            ErrorCodes::SearchColdStorageRestoreRequired(msg) => msg.to_owned(),
         }
     }
 
@@ -370,6 +394,10 @@ impl ErrorCodes {
             20008 => Ok(ErrorCodes::SearchSQLExecuteError(message)),
             20009 => Ok(ErrorCodes::SearchCancelQuery(message)),
             20010 => Ok(ErrorCodes::SearchTimeout(message)),
+            20014 => Ok(ErrorCodes::SearchQuotaExceeded(message)),
+            20015 => Ok(ErrorCodes::SearchLimitExceeded(message)),
+            20016 => Ok(ErrorCodes::SearchFieldAccessDenied(message)),
+            20017 => Ok(ErrorCodes::SearchColdStorageRestoreRequired(message)),
             _ => Ok(ErrorCodes::ServerInternalError(json.to_string())),
         }
     }
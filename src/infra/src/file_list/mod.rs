@@ -71,6 +71,8 @@ pub trait FileList: Sync + Send + 'static {
     async fn contains(&self, file: &str) -> Result<bool>;
     async fn update_flattened(&self, file: &str, flattened: bool) -> Result<()>;
     async fn update_compressed_size(&self, file: &str, size: i64) -> Result<()>;
+    async fn update_index_size(&self, file: &str, size: i64) -> Result<()>;
+    async fn update_account(&self, file: &str, account: &str) -> Result<()>;
     async fn list(&self) -> Result<Vec<FileKey>>;
     async fn query(
         &self,
@@ -281,6 +283,16 @@ pub async fn update_compressed_size(file: &str, size: i64) -> Result<()> {
     CLIENT.update_compressed_size(file, size).await
 }
 
+#[inline]
+pub async fn update_index_size(file: &str, size: i64) -> Result<()> {
+    CLIENT.update_index_size(file, size).await
+}
+
+#[inline]
+pub async fn update_account(file: &str, account: &str) -> Result<()> {
+    CLIENT.update_account(file, account).await
+}
+
 #[inline]
 pub async fn list() -> Result<Vec<FileKey>> {
     CLIENT.list().await
@@ -22,7 +22,9 @@ use config::{
     RwHashMap, RwHashSet, SQL_FULL_TEXT_SEARCH_FIELDS, SQL_SECONDARY_INDEX_SEARCH_FIELDS,
     get_config,
     ider::SnowflakeIdGenerator,
-    meta::stream::{PartitionTimeLevel, StreamSettings, StreamType},
+    meta::stream::{
+        CompactSort, IngestPriority, PartitionTimeLevel, StreamSettings, StreamType, WalSyncPolicy,
+    },
     stats::MemorySize,
     utils::{json, schema_ext::SchemaExt, time::now_micros},
 };
@@ -391,6 +393,45 @@ pub fn get_stream_setting_bloom_filter_fields(settings: &Option<StreamSettings>)
     }
 }
 
+/// The bloom filter fields stored in the index files, none unless enabled
+pub fn get_stream_setting_index_bloom_filter_fields(
+    settings: &Option<StreamSettings>,
+) -> Vec<String> {
+    if !get_config().common.index_bloom_filter_enabled {
+        return vec![];
+    }
+    get_stream_setting_bloom_filter_fields(settings)
+}
+
+/// The sort of the merged files, none when the stream keeps them sorted by time
+pub fn get_stream_setting_compact_sort(settings: &Option<StreamSettings>) -> Option<CompactSort> {
+    settings
+        .as_ref()
+        .filter(|s| !s.compact_sort.is_empty())
+        .map(|s| s.compact_sort.clone())
+}
+
+/// The fields pinned to a type, as fields of the schema
+pub fn get_stream_setting_pinned_fields(settings: &Option<StreamSettings>) -> Vec<FieldRef> {
+    match settings {
+        Some(settings) => settings
+            .pinned_field_types
+            .iter()
+            .map(|f| Arc::new(Field::new(&f.name, f.r#type.into(), true)))
+            .collect(),
+        None => vec![],
+    }
+}
+
+pub fn get_stream_setting_wal_sync_policy(settings: &Option<StreamSettings>) -> WalSyncPolicy {
+    match settings {
+        Some(settings) => settings
+            .wal_sync_policy
+            .unwrap_or_else(|| WalSyncPolicy::default_for(settings.ingest_priority)),
+        None => WalSyncPolicy::default_for(IngestPriority::Normal),
+    }
+}
+
 pub fn get_stream_setting_log_patterns_enabled(settings: &Option<StreamSettings>) -> bool {
     settings
         .as_ref()
@@ -501,10 +542,15 @@ pub async fn merge(
                     let final_schema = Schema::new(merged_fields).with_metadata(metadata);
 
                     // Casting of data to existing schema isnt new version, we remove records
-                    // with zo_cast metadata
+                    // with zo_cast metadata, unless the field is migrated to its pinned type
                     let schema_version_changes = field_datatype_delta
                         .iter()
-                        .filter(|f| f.metadata().get("zo_cast").is_none())
+                        .filter(|f| {
+                            f.metadata().get("zo_cast").is_none()
+                                || latest_schema
+                                    .field_with_name(f.name())
+                                    .is_ok_and(|e| e.data_type() != f.data_type())
+                        })
                         .collect::<Vec<_>>();
                     let need_new_version = !schema_version_changes.is_empty();
 
@@ -697,6 +743,7 @@ pub fn get_merge_schema_changes(
     let mut is_schema_changed = false;
     let mut field_datatype_delta: Vec<_> = vec![];
 
+    let pinned_fields = get_stream_setting_pinned_fields(&unwrap_stream_settings(schema));
     let mut merged_fields = schema.fields().iter().collect::<Vec<_>>();
     let mut merged_fields_chk = hashbrown::HashMap::with_capacity(merged_fields.len());
     for (i, f) in merged_fields.iter().enumerate() {
@@ -707,6 +754,34 @@ pub fn get_merge_schema_changes(
         let item_name = item.name();
         let item_data_type = item.data_type();
 
+        // a pinned field never widens, the values are cast to its type and
+        // the field is migrated to it when the schema has another type
+        if let Some(pinned) = pinned_fields.iter().find(|f| f.name() == item_name) {
+            let mut meta = pinned.metadata().clone();
+            meta.insert("zo_cast".to_owned(), true.to_string());
+            let cast_field = pinned.as_ref().clone().with_metadata(meta);
+            match merged_fields_chk.get(item_name) {
+                None => {
+                    is_schema_changed = true;
+                    merged_fields.push(pinned);
+                    merged_fields_chk.insert(item_name, merged_fields.len() - 1);
+                    if item_data_type != pinned.data_type() {
+                        field_datatype_delta.push(cast_field);
+                    }
+                }
+                Some(idx) => {
+                    if merged_fields[*idx].data_type() != pinned.data_type() {
+                        is_schema_changed = true;
+                        merged_fields[*idx] = pinned;
+                        field_datatype_delta.push(cast_field);
+                    } else if item_data_type != pinned.data_type() {
+                        field_datatype_delta.push(cast_field);
+                    }
+                }
+            }
+            continue;
+        }
+
         match merged_fields_chk.get(item_name) {
             None => {
                 is_schema_changed = true;
@@ -1081,6 +1156,21 @@ mod tests {
         assert_eq!(unique_count, fields.len());
     }
 
+    #[test]
+    fn test_get_stream_setting_compact_sort() {
+        assert!(get_stream_setting_compact_sort(&None).is_none());
+        assert!(get_stream_setting_compact_sort(&Some(StreamSettings::default())).is_none());
+        let sort = CompactSort {
+            fields: vec!["service".to_string()],
+            ..Default::default()
+        };
+        let settings = StreamSettings {
+            compact_sort: sort.clone(),
+            ..Default::default()
+        };
+        assert_eq!(get_stream_setting_compact_sort(&Some(settings)), Some(sort));
+    }
+
     #[test]
     fn test_get_stream_setting_log_patterns_enabled() {
         // Test with None
@@ -1177,6 +1267,44 @@ mod tests {
         assert!(merged.is_empty());
     }
 
+    #[test]
+    fn test_get_merge_schema_changes_pinned_field() {
+        let settings = StreamSettings {
+            pinned_field_types: vec![config::meta::stream::DataField::new(
+                "status_code",
+                config::meta::stream::DataType::Int64,
+            )],
+            ..Default::default()
+        };
+        let metadata =
+            HashMap::from([("settings".to_string(), json::to_string(&settings).unwrap())]);
+        let inferred_schema = Schema::new(vec![Field::new("status_code", DataType::Utf8, true)]);
+
+        // the values are cast instead of widening the field
+        let schema = Schema::new(vec![Field::new("status_code", DataType::Int64, true)])
+            .with_metadata(metadata.clone());
+        let (is_changed, delta, merged) = get_merge_schema_changes(&schema, &inferred_schema);
+        assert!(!is_changed);
+        assert_eq!(delta.len(), 1);
+        assert_eq!(delta[0].data_type(), &DataType::Int64);
+        assert!(delta[0].metadata().contains_key("zo_cast"));
+        assert!(merged.is_empty());
+
+        // a field widened before it was pinned is migrated to its type
+        let schema = Schema::new(vec![Field::new("status_code", DataType::Utf8, true)])
+            .with_metadata(metadata.clone());
+        let (is_changed, _, merged) = get_merge_schema_changes(&schema, &inferred_schema);
+        assert!(is_changed);
+        assert_eq!(merged[0].data_type(), &DataType::Int64);
+
+        // a new field is created with its pinned type
+        let schema = Schema::empty().with_metadata(metadata);
+        let (is_changed, delta, merged) = get_merge_schema_changes(&schema, &inferred_schema);
+        assert!(is_changed);
+        assert_eq!(delta.len(), 1);
+        assert_eq!(merged[0].data_type(), &DataType::Int64);
+    }
+
     #[test]
     fn test_is_widening_conversion_comprehensive() {
         // Test Boolean conversions
@@ -33,6 +33,7 @@ pub struct StorageClientFactory {
     accounts: HashMap<String, Box<dyn ObjectStore>>,
     stream_strategy: StreamStrategy,
     only_default: bool,
+    cold_account: Option<String>,
 }
 
 impl Default for StorageClientFactory {
@@ -61,6 +62,7 @@ impl StorageClientFactory {
             accounts: HashMap::with_capacity(accounts.len()),
             only_default: accounts.len() == 1,
             stream_strategy,
+            cold_account: None,
         };
 
         if local_mode {
@@ -78,6 +80,10 @@ impl StorageClientFactory {
                     .accounts
                     .insert(name, Box::new(super::remote::Remote::new(config)));
             }
+            let cold_account = &get_config().compact.cold_storage_account;
+            if storage.accounts.contains_key(cold_account) {
+                storage.cold_account = Some(cold_account.to_string());
+            }
         }
         storage
     }
@@ -118,6 +124,18 @@ impl StorageClientFactory {
             .get(DEFAULT_ACCOUNT)
             .expect("default object store account not found")
     }
+
+    /// The client of the cold storage account to retry a read on when the
+    /// file is not found, the file list of a querier can still point to the
+    /// deleted hot copy of a file moved to the cold storage.
+    fn cold_fallback(&self, account: &str, err: &object_store::Error) -> Option<&dyn ObjectStore> {
+        match (&self.cold_account, err) {
+            (Some(cold), object_store::Error::NotFound { .. }) if cold != account => {
+                self.accounts.get(cold).map(|client| client.as_ref())
+            }
+            _ => None,
+        }
+    }
 }
 
 pub fn parse_storage_config(
@@ -184,7 +202,12 @@ pub fn parse_storage_config(
         );
     }
 
-    // parse stream strategy
+    // parse stream strategy, the new files never go to the cold storage account
+    let cold_account = &get_config().compact.cold_storage_account;
+    let account_names = account_names
+        .into_iter()
+        .filter(|name| name != cold_account)
+        .collect();
     let stream_strategy = StreamStrategy::new(&config.stream_strategy, account_names);
 
     (stream_strategy, accounts)
@@ -293,7 +316,13 @@ impl ObjectStoreExt for StorageClientFactory {
     }
 
     async fn get(&self, account: &str, location: &Path) -> Result<GetResult> {
-        self.get_client_by_name(account).get(location).await
+        match self.get_client_by_name(account).get(location).await {
+            Ok(ret) => Ok(ret),
+            Err(e) => match self.cold_fallback(account, &e) {
+                Some(client) => client.get(location).await,
+                None => Err(e),
+            },
+        }
     }
 
     async fn get_opts(
@@ -302,15 +331,31 @@ impl ObjectStoreExt for StorageClientFactory {
         location: &Path,
         options: GetOptions,
     ) -> Result<GetResult> {
-        self.get_client_by_name(account)
-            .get_opts(location, options)
+        match self
+            .get_client_by_name(account)
+            .get_opts(location, options.clone())
             .await
+        {
+            Ok(ret) => Ok(ret),
+            Err(e) => match self.cold_fallback(account, &e) {
+                Some(client) => client.get_opts(location, options).await,
+                None => Err(e),
+            },
+        }
     }
 
     async fn get_range(&self, account: &str, location: &Path, range: Range<u64>) -> Result<Bytes> {
-        self.get_client_by_name(account)
-            .get_range(location, range)
+        match self
+            .get_client_by_name(account)
+            .get_range(location, range.clone())
             .await
+        {
+            Ok(ret) => Ok(ret),
+            Err(e) => match self.cold_fallback(account, &e) {
+                Some(client) => client.get_range(location, range).await,
+                None => Err(e),
+            },
+        }
     }
 
     async fn get_ranges(
@@ -319,13 +364,27 @@ impl ObjectStoreExt for StorageClientFactory {
         location: &Path,
         ranges: &[Range<u64>],
     ) -> Result<Vec<Bytes>> {
-        self.get_client_by_name(account)
+        match self
+            .get_client_by_name(account)
             .get_ranges(location, ranges)
             .await
+        {
+            Ok(ret) => Ok(ret),
+            Err(e) => match self.cold_fallback(account, &e) {
+                Some(client) => client.get_ranges(location, ranges).await,
+                None => Err(e),
+            },
+        }
     }
 
     async fn head(&self, account: &str, location: &Path) -> Result<ObjectMeta> {
-        self.get_client_by_name(account).head(location).await
+        match self.get_client_by_name(account).head(location).await {
+            Ok(ret) => Ok(ret),
+            Err(e) => match self.cold_fallback(account, &e) {
+                Some(client) => client.head(location).await,
+                None => Err(e),
+            },
+        }
     }
 
     async fn delete(&self, account: &str, location: &Path) -> Result<()> {
@@ -250,7 +250,27 @@ pub async fn get_file_meta(account: &str, file: &str) -> Result<FileMeta, anyhow
     Ok(file_meta)
 }
 
-async fn get_parquet_metadata(
+/// Returns the compressed size of every top level column of a parquet file
+pub async fn get_column_sizes(
+    account: &str,
+    file: &str,
+) -> Result<std::collections::HashMap<String, i64>, anyhow::Error> {
+    let (_, parquet_meta) = get_parquet_metadata(account, file).await?;
+    let mut sizes = std::collections::HashMap::new();
+    for row_group in parquet_meta.row_groups() {
+        for column in row_group.columns() {
+            let Some(name) = column.column_path().parts().first() else {
+                continue;
+            };
+            *sizes.entry(name.to_string()).or_insert(0) += column.compressed_size();
+        }
+    }
+    Ok(sizes)
+}
+
+/// Returns the size and the metadata in the footer of a parquet file, only the
+/// footer is read
+pub async fn get_parquet_metadata(
     account: &str,
     file: &str,
 ) -> Result<(usize, Arc<ParquetMetaData>), anyhow::Error> {
@@ -0,0 +1,270 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+
+use config::{get_config, meta::stream::IngestPriority, metrics};
+use prometheus::core::Collector;
+use serde::Serialize;
+
+use crate::errors::*;
+
+// the WAL gauge is labelled by org, so its sum is cached for a second
+static WAL_USED_BYTES: AtomicI64 = AtomicI64::new(0);
+static WAL_SAMPLED_AT: AtomicI64 = AtomicI64::new(0);
+
+// spreads the admitted share of low priority requests evenly
+static LOW_PRIORITY_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Ingestion pressure of the node against the configured thresholds, a
+/// threshold of 0 means the check is disabled
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BackpressureStatus {
+    pub enabled: bool,
+    pub wal_used_bytes: i64,
+    pub wal_max_bytes: i64,
+    pub memtable_bytes: i64,
+    pub memtable_max_bytes: i64,
+    /// Memtable bytes spilled to disk in the bounded memory mode
+    pub memtable_spilled_bytes: i64,
+    pub pending_memtables: i64,
+    pub pending_memtables_max: i64,
+    /// Highest usage against the enabled thresholds, 1.0 means a threshold
+    /// is reached
+    pub pressure: f64,
+    /// Percentage of the thresholds at which low priority streams start
+    /// being throttled
+    pub low_priority_ratio: usize,
+    pub retry_after_secs: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl BackpressureStatus {
+    fn evaluate(mut self) -> Self {
+        self.pressure = [
+            (self.wal_used_bytes, self.wal_max_bytes),
+            (self.memtable_bytes, self.memtable_max_bytes),
+            (self.pending_memtables, self.pending_memtables_max),
+        ]
+        .into_iter()
+        .filter(|(_, max)| *max > 0)
+        .map(|(used, max)| used as f64 / max as f64)
+        .fold(0.0, f64::max);
+        self.reason = if !self.enabled {
+            None
+        } else if self.wal_max_bytes > 0 && self.wal_used_bytes >= self.wal_max_bytes {
+            Some(format!(
+                "WAL disk usage {} bytes reached the limit of {} bytes",
+                self.wal_used_bytes, self.wal_max_bytes
+            ))
+        } else if self.memtable_max_bytes > 0 && self.memtable_bytes >= self.memtable_max_bytes {
+            Some(format!(
+                "memtable size {} bytes reached the limit of {} bytes",
+                self.memtable_bytes, self.memtable_max_bytes
+            ))
+        } else if self.pending_memtables_max > 0
+            && self.pending_memtables >= self.pending_memtables_max
+        {
+            Some(format!(
+                "{} memtables waiting for parquet conversion, the limit is {}",
+                self.pending_memtables, self.pending_memtables_max
+            ))
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Returns why a request of the given priority is rejected. High priority
+    /// streams always pass, normal ones are rejected once a threshold is
+    /// reached, low priority ones are throttled from `low_priority_ratio` on,
+    /// admitting a share of requests that shrinks to none at the threshold.
+    fn admit(&self, priority: IngestPriority, seq: u64) -> Option<String> {
+        if !self.enabled || priority == IngestPriority::High {
+            return None;
+        }
+        if self.reason.is_some() {
+            return self.reason.clone();
+        }
+        let start = self.low_priority_ratio as f64 / 100.0;
+        if priority == IngestPriority::Low && start < 1.0 && self.pressure >= start {
+            let keep = ((1.0 - self.pressure) / (1.0 - start) * 100.0) as u64;
+            if seq % 100 >= keep {
+                return Some(format!(
+                    "low priority stream throttled at {:.0}% of the backpressure thresholds",
+                    self.pressure * 100.0
+                ));
+            }
+        }
+        None
+    }
+}
+
+/// Current ingestion pressure, thresholds are read from the config on every
+/// call so they follow `/config/reload`
+pub fn get_backpressure_status() -> BackpressureStatus {
+    let cfg = get_config();
+    BackpressureStatus {
+        enabled: cfg.common.ingest_backpressure_enabled,
+        wal_used_bytes: wal_used_bytes(),
+        wal_max_bytes: (cfg.common.ingest_backpressure_wal_max_size * 1024 * 1024) as i64,
+        memtable_bytes: metrics::INGEST_MEMTABLE_ARROW_BYTES
+            .with_label_values::<&str>(&[])
+            .get(),
+        memtable_max_bytes: (cfg.limit.mem_table_max_size / 100
+            * cfg.common.ingest_backpressure_memtable_ratio) as i64,
+        memtable_spilled_bytes: metrics::INGEST_MEMTABLE_SPILLED_BYTES
+            .with_label_values::<&str>(&[])
+            .get(),
+        pending_memtables: metrics::INGEST_MEMTABLE_FILES
+            .with_label_values::<&str>(&[])
+            .get(),
+        pending_memtables_max: cfg.common.ingest_backpressure_pending_memtables as i64,
+        pressure: 0.0,
+        low_priority_ratio: cfg.common.ingest_backpressure_low_priority_ratio,
+        retry_after_secs: cfg.common.ingest_backpressure_retry_after,
+        reason: None,
+    }
+    .evaluate()
+}
+
+// check ingestion backpressure for a stream priority, rejects before the
+// circuit breakers trip
+pub fn check_backpressure(priority: IngestPriority) -> Result<()> {
+    if !get_config().common.ingest_backpressure_enabled || priority == IngestPriority::High {
+        return Ok(());
+    }
+    let seq = if priority == IngestPriority::Low {
+        LOW_PRIORITY_SEQ.fetch_add(1, Ordering::Relaxed)
+    } else {
+        0
+    };
+    match get_backpressure_status().admit(priority, seq) {
+        Some(reason) => Err(Error::IngestBackpressureError { reason }),
+        None => Ok(()),
+    }
+}
+
+fn wal_used_bytes() -> i64 {
+    let now = chrono::Utc::now().timestamp();
+    if WAL_SAMPLED_AT.swap(now, Ordering::Relaxed) != now {
+        let total = metrics::INGEST_WAL_USED_BYTES
+            .collect()
+            .iter()
+            .flat_map(|mf| mf.get_metric())
+            .map(|m| m.get_gauge().value() as i64)
+            .sum();
+        WAL_USED_BYTES.store(total, Ordering::Relaxed);
+    }
+    WAL_USED_BYTES.load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status() -> BackpressureStatus {
+        BackpressureStatus {
+            enabled: true,
+            wal_used_bytes: 100,
+            wal_max_bytes: 1000,
+            memtable_bytes: 100,
+            memtable_max_bytes: 1000,
+            memtable_spilled_bytes: 0,
+            pending_memtables: 1,
+            pending_memtables_max: 10,
+            pressure: 0.0,
+            low_priority_ratio: 80,
+            retry_after_secs: 5,
+            reason: None,
+        }
+    }
+
+    #[test]
+    fn test_backpressure_under_thresholds() {
+        assert!(status().evaluate().reason.is_none());
+    }
+
+    #[test]
+    fn test_backpressure_triggers() {
+        let mut s = status();
+        s.wal_used_bytes = 1000;
+        assert!(s.evaluate().reason.unwrap().contains("WAL"));
+
+        let mut s = status();
+        s.memtable_bytes = 2000;
+        assert!(s.evaluate().reason.unwrap().contains("memtable size"));
+
+        let mut s = status();
+        s.pending_memtables = 10;
+        assert!(s.evaluate().reason.unwrap().contains("parquet conversion"));
+    }
+
+    #[test]
+    fn test_backpressure_disabled_checks() {
+        let mut s = status();
+        s.wal_used_bytes = 5000;
+        s.wal_max_bytes = 0;
+        s.pending_memtables = 50;
+        s.pending_memtables_max = 0;
+        assert!(s.evaluate().reason.is_none());
+
+        let mut s = status();
+        s.enabled = false;
+        s.memtable_bytes = 5000;
+        assert!(s.evaluate().reason.is_none());
+    }
+
+    #[test]
+    fn test_backpressure_pressure() {
+        let s = status().evaluate();
+        assert_eq!(s.pressure, 0.1);
+
+        let mut s = status();
+        s.memtable_bytes = 900;
+        s.pending_memtables_max = 0;
+        assert_eq!(s.evaluate().pressure, 0.9);
+    }
+
+    #[test]
+    fn test_backpressure_priorities() {
+        let mut s = status();
+        s.wal_used_bytes = 2000;
+        let s = s.evaluate();
+        assert!(s.admit(IngestPriority::High, 0).is_none());
+        assert!(s.admit(IngestPriority::Normal, 0).is_some());
+        assert!(s.admit(IngestPriority::Low, 0).is_some());
+    }
+
+    #[test]
+    fn test_backpressure_low_priority_throttling() {
+        // below the low priority ratio everything passes
+        let mut s = status();
+        s.wal_used_bytes = 700;
+        let s = s.evaluate();
+        assert!((0..100).all(|seq| s.admit(IngestPriority::Low, seq).is_none()));
+
+        // at 90%, half way between 80% and the threshold, half is admitted
+        let mut s = status();
+        s.wal_used_bytes = 900;
+        let s = s.evaluate();
+        let admitted = (0..100)
+            .filter(|seq| s.admit(IngestPriority::Low, *seq).is_none())
+            .count();
+        assert!((49..=50).contains(&admitted));
+        assert!(s.admit(IngestPriority::Normal, 99).is_none());
+    }
+}
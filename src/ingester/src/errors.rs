@@ -100,6 +100,11 @@ pub enum Error {
     MergeRecordBatchError {
         source: datafusion::error::DataFusionError,
     },
+    #[snafu(display("Failed to spill memtable to {}: {}", path.display(), source))]
+    SpillError {
+        source: arrow::error::ArrowError,
+        path: PathBuf,
+    },
     TokioJoinError {
         source: tokio::task::JoinError,
     },
@@ -107,8 +112,11 @@ pub enum Error {
         source: tokio::sync::mpsc::error::SendError<PathBuf>,
     },
     TokioMpscSendEntriesError {
-        source:
-            tokio::sync::mpsc::error::SendError<(crate::WriterSignal, crate::ProcessedBatch, bool)>,
+        source: tokio::sync::mpsc::error::SendError<(
+            crate::WriterSignal,
+            crate::ProcessedBatch,
+            config::meta::stream::WalSyncPolicy,
+        )>,
     },
     #[snafu(display("MemoryTableOverflowError"))]
     MemoryTableOverflowError {},
@@ -116,6 +124,14 @@ pub enum Error {
     MemoryCircuitBreakerError {},
     #[snafu(display("DiskCircuitBreakerError"))]
     DiskCircuitBreakerError {},
+    #[snafu(display("Wal file {file} is not an orphan"))]
+    WalFileNotOrphan {
+        file: String,
+    },
+    #[snafu(display("IngestBackpressureError# {reason}"))]
+    IngestBackpressureError {
+        reason: String,
+    },
     ExternalError {
         source: Box<dyn std::error::Error + Send + Sync>,
     },
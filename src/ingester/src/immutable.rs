@@ -141,12 +141,14 @@ pub(crate) async fn persist(tx: mpsc::Sender<PathBuf>) -> Result<()> {
     }
     drop(r);
     for path in paths {
-        // check if the file is processing
-        if PROCESSING_TABLES.read().await.contains(&path) {
+        // check if the file is processing, or being spilled
+        if !PROCESSING_TABLES.write().await.insert(path.clone()) {
             continue;
         }
-        tx.send(path.clone()).await.context(TokioMpscSendSnafu)?;
-        PROCESSING_TABLES.write().await.insert(path);
+        if let Err(e) = tx.send(path.clone()).await {
+            PROCESSING_TABLES.write().await.remove(&path);
+            return Err(e).context(TokioMpscSendSnafu);
+        }
     }
 
     IMMUTABLES.write().await.shrink_to_fit();
@@ -214,6 +216,37 @@ pub(crate) async fn persist_table(idx: usize, path: PathBuf) -> Result<()> {
     Ok(())
 }
 
+/// Spills the immutables, oldest first, until `max` arrow bytes are freed. The
+/// immutables being persisted are skipped, their memory is soon released.
+pub(crate) async fn spill(max: usize) -> usize {
+    let r = IMMUTABLES.read().await;
+    let tables = r
+        .iter()
+        .map(|(path, i)| (path.clone(), i.clone()))
+        .collect::<Vec<_>>();
+    drop(r);
+
+    let mut freed = 0;
+    for (path, immutable) in tables {
+        if freed >= max {
+            break;
+        }
+        // the persist doesn't take the immutable while it is spilled
+        if !PROCESSING_TABLES.write().await.insert(path.clone()) {
+            continue;
+        }
+        match immutable.memtable.spill(max - freed) {
+            Ok(v) => freed += v,
+            Err(e) => log::error!(
+                "[INGESTER:SPILL] spill immutable {} error: {e}",
+                path.display()
+            ),
+        }
+        PROCESSING_TABLES.write().await.remove(&path);
+    }
+    freed
+}
+
 // check if the persist is done for the given seq_id
 // if there is no id less than the given seq_id, return true
 pub async fn check_persist_done(seq_id: u64) -> bool {
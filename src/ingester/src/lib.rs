@@ -13,12 +13,15 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+mod backpressure;
 mod entry;
 pub mod errors;
 mod immutable;
 mod memtable;
 mod partition;
+mod recovery;
 mod rwmap;
+mod spill;
 mod stream;
 mod wal;
 mod writer;
@@ -26,6 +29,7 @@ mod writer;
 use std::{fs::create_dir_all, path::PathBuf, sync::Arc};
 
 use arrow_schema::Schema;
+pub use backpressure::{BackpressureStatus, check_backpressure, get_backpressure_status};
 use config::RwAHashMap;
 pub use entry::Entry;
 pub use immutable::{
@@ -33,12 +37,15 @@ pub use immutable::{
     read_from_immutable,
 };
 use once_cell::sync::Lazy;
+pub use recovery::{
+    OrphanWalFile, export_orphan_wal_file, list_orphan_wal_files, replay_orphan_wal_file,
+};
 use snafu::ResultExt;
 use tokio::sync::{Mutex, mpsc};
 pub use wal::collect_wal_parquet_metrics;
 pub use writer::{
-    Writer, check_disk_circuit_breaker, check_memory_circuit_breaker, check_memtable_size,
-    flush_all, get_max_writer_seq_id, get_writer, read_from_memtable,
+    WalCommit, Writer, check_disk_circuit_breaker, check_memory_circuit_breaker,
+    check_memtable_size, flush_all, get_max_writer_seq_id, get_writer, read_from_memtable,
 };
 
 use crate::errors::OpenDirSnafu;
@@ -91,6 +98,10 @@ pub async fn init() -> errors::Result<()> {
     // check uncompleted parquet files, need delete those files
     wal::check_uncompleted_parquet_files().await?;
 
+    // the memtables are rebuilt from the wal, the spilled batches of the
+    // previous run are not needed anymore
+    spill::clean()?;
+
     // replay wal files to create immutable
     let wal_dir = PathBuf::from(&config::get_config().common.data_wal_dir).join("logs");
     create_dir_all(&wal_dir).context(OpenDirSnafu {
@@ -119,6 +130,12 @@ pub async fn init() -> errors::Result<()> {
         }
     });
 
+    // start a job to spill the memtables to disk under memory pressure
+    tokio::task::spawn(spill::run());
+
+    // start a job to fsync the wal of the interval sync policy
+    tokio::task::spawn(writer::sync_interval());
+
     // start a job to flush memtable to immutable
     tokio::task::spawn(async move {
         if let Err(e) = run().await {
@@ -120,6 +120,19 @@ impl MemTable {
         Ok((schema_size, paths))
     }
 
+    /// Moves batches to disk until `max` arrow bytes are freed, returns the
+    /// bytes freed
+    pub(crate) fn spill(&self, max: usize) -> Result<usize> {
+        let mut freed = 0;
+        for stream in self.streams.values() {
+            if freed >= max {
+                break;
+            }
+            freed += stream.spill(max - freed)?;
+        }
+        Ok(freed)
+    }
+
     // Return the number of bytes written (json format size, arrow format size)
     pub(crate) fn size(&self) -> (usize, usize) {
         (
@@ -28,6 +28,7 @@ use config::{
     },
 };
 use hashbrown::HashSet;
+use parking_lot::RwLock;
 use snafu::ResultExt;
 use tokio::{fs::OpenOptions, io::AsyncWriteExt};
 
@@ -35,6 +36,7 @@ use crate::{
     ReadRecordBatchEntry,
     entry::{Entry, PersistStat, RecordBatchEntry},
     errors::*,
+    spill::SpillFile,
 };
 
 pub(crate) struct Partition {
@@ -151,14 +153,15 @@ impl Partition {
         path.push(idx.to_string());
         let mut paths = Vec::with_capacity(self.files.len());
         for (hour, data) in self.files.iter() {
-            if data.data.is_empty() {
+            let data = data.load(None)?;
+            if data.is_empty() {
                 continue;
             }
             let mut chunks = Vec::new();
             let mut cur_batches = Vec::new();
             let mut cur_num_rows = 0;
-            for data in data.data.iter() {
-                let num_rows = data.data.num_rows();
+            for data in data.iter() {
+                let num_rows = data.0.data.num_rows();
                 if cur_num_rows > 0 && cur_num_rows + num_rows > config::PARQUET_FILE_CHUNK_SIZE {
                     chunks.push(cur_batches);
                     cur_num_rows = 0;
@@ -172,7 +175,7 @@ impl Partition {
             }
             for data in chunks {
                 let mut file_meta = FileMeta::default();
-                data.iter().for_each(|r| {
+                data.iter().for_each(|(r, _)| {
                     file_meta.original_size += r.data_json_size as i64;
                     file_meta.records += r.data.num_rows() as i64;
                     if file_meta.min_ts == 0 || file_meta.min_ts > r.min_ts {
@@ -198,10 +201,13 @@ impl Partition {
                         vec![]
                     };
 
+                // the spilled batches are not counted in memory anymore
                 let batches = data
                     .iter()
-                    .map(|r| {
-                        persist_stat.arrow_size += r.data_arrow_size;
+                    .map(|(r, spilled)| {
+                        if !spilled {
+                            persist_stat.arrow_size += r.data_arrow_size;
+                        }
                         r.data.clone()
                     })
                     .collect::<Vec<_>>();
@@ -282,6 +288,18 @@ impl Partition {
         }
         Ok((self.schema.size(), paths))
     }
+
+    /// Spills the files until `max` arrow bytes are freed
+    pub(crate) fn spill(&self, max: usize) -> Result<usize> {
+        let mut freed = 0;
+        for file in self.files.values() {
+            if freed >= max {
+                break;
+            }
+            freed += file.spill()?;
+        }
+        Ok(freed)
+    }
 }
 
 impl MemorySize for Partition {
@@ -292,19 +310,46 @@ impl MemorySize for Partition {
     }
 }
 
+#[derive(Clone)]
+enum Chunk {
+    Memory(Arc<RecordBatchEntry>),
+    Spilled(Arc<SpillFile>),
+}
+
+impl Chunk {
+    fn time_range(&self) -> (i64, i64) {
+        match self {
+            Chunk::Memory(r) => (r.min_ts, r.max_ts),
+            Chunk::Spilled(s) => (s.min_ts, s.max_ts),
+        }
+    }
+}
+
+impl MemorySize for Chunk {
+    fn mem_size(&self) -> usize {
+        match self {
+            Chunk::Memory(r) => r.mem_size(),
+            Chunk::Spilled(_) => std::mem::size_of::<Chunk>() + std::mem::size_of::<SpillFile>(),
+        }
+    }
+}
+
 struct PartitionFile {
-    data: Vec<Arc<RecordBatchEntry>>,
+    // behind a lock, the batches are spilled from a shared memtable
+    data: RwLock<Vec<Chunk>>,
 }
 
 impl PartitionFile {
     fn new() -> Self {
-        Self { data: Vec::new() }
+        Self {
+            data: RwLock::new(Vec::new()),
+        }
     }
 
     fn write(&mut self, batch: Arc<RecordBatchEntry>) -> Result<usize> {
         let json_size = batch.data_json_size;
         let arrow_size = batch.data_arrow_size;
-        self.data.push(batch);
+        self.data.get_mut().push(Chunk::Memory(batch));
         metrics::INGEST_MEMTABLE_ARROW_BYTES
             .with_label_values::<&str>(&[])
             .add(arrow_size as i64);
@@ -315,20 +360,127 @@ impl PartitionFile {
     }
 
     fn read(&self, time_range: Option<(i64, i64)>) -> Result<Vec<Arc<RecordBatchEntry>>> {
-        match time_range {
-            None | Some((0, 0)) => Ok(self.data.clone()),
-            Some((min_ts, max_ts)) => Ok(self
-                .data
-                .iter()
-                .filter(|r| r.min_ts <= max_ts && r.max_ts >= min_ts)
-                .cloned()
-                .collect()),
+        Ok(self.load(time_range)?.into_iter().map(|(r, _)| r).collect())
+    }
+
+    /// Returns the batches in the time range, the spilled ones are read from
+    /// disk and flagged
+    fn load(&self, time_range: Option<(i64, i64)>) -> Result<Vec<(Arc<RecordBatchEntry>, bool)>> {
+        let chunks = self.data.read().clone();
+        chunks
+            .into_iter()
+            .filter(|c| match time_range {
+                None | Some((0, 0)) => true,
+                Some((min_ts, max_ts)) => {
+                    let (start, end) = c.time_range();
+                    start <= max_ts && end >= min_ts
+                }
+            })
+            .map(|c| match c {
+                Chunk::Memory(r) => Ok((r, false)),
+                Chunk::Spilled(s) => Ok((s.read()?, true)),
+            })
+            .collect()
+    }
+
+    /// Moves the batches in memory to disk, returns the arrow bytes freed
+    fn spill(&self) -> Result<usize> {
+        let batches = self
+            .data
+            .read()
+            .iter()
+            .filter_map(|c| match c {
+                Chunk::Memory(r) => Some(r.clone()),
+                Chunk::Spilled(_) => None,
+            })
+            .collect::<Vec<_>>();
+        // a spill file holds the consecutive batches with the same schema
+        let mut spilled = Vec::new();
+        for group in batches.chunk_by(|a, b| a.data.schema() == b.data.schema()) {
+            spilled.push(Chunk::Spilled(Arc::new(SpillFile::write(group)?)));
         }
+        if spilled.is_empty() {
+            return Ok(0);
+        }
+
+        // the memtable is only read while it is spilled, so the batches are
+        // still the same
+        let mut data = self.data.write();
+        data.retain(|c| matches!(c, Chunk::Spilled(_)));
+        data.extend(spilled);
+        drop(data);
+        let freed = batches.iter().map(|r| r.data_arrow_size).sum::<usize>();
+        metrics::INGEST_MEMTABLE_ARROW_BYTES
+            .with_label_values::<&str>(&[])
+            .sub(freed as i64);
+        Ok(freed)
     }
 }
 
 impl MemorySize for PartitionFile {
     fn mem_size(&self) -> usize {
-        std::mem::size_of::<PartitionFile>() + self.data.mem_size()
+        std::mem::size_of::<PartitionFile>() + self.data.read().mem_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::{
+        array::{Int64Array, StringArray},
+        record_batch::RecordBatch,
+    };
+    use arrow_schema::{DataType, Field};
+
+    use super::*;
+
+    fn entry(ts: i64, fields: &[&str]) -> Arc<RecordBatchEntry> {
+        let mut schema_fields = vec![Field::new("_timestamp", DataType::Int64, false)];
+        let mut columns: Vec<arrow::array::ArrayRef> = vec![Arc::new(Int64Array::from(vec![ts]))];
+        for field in fields {
+            schema_fields.push(Field::new(*field, DataType::Utf8, true));
+            columns.push(Arc::new(StringArray::from(vec!["v"])));
+        }
+        let data = RecordBatch::try_new(Arc::new(Schema::new(schema_fields)), columns).unwrap();
+        Arc::new(RecordBatchEntry {
+            data,
+            data_json_size: 10,
+            data_arrow_size: 100,
+            min_ts: ts,
+            max_ts: ts,
+        })
+    }
+
+    #[test]
+    fn test_partition_file_spill() {
+        let mut file = PartitionFile::new();
+        file.write(entry(1, &["a"])).unwrap();
+        file.write(entry(2, &["a"])).unwrap();
+        file.write(entry(3, &["a", "b"])).unwrap();
+
+        // one spill file per schema
+        assert_eq!(file.spill().unwrap(), 300);
+        assert_eq!(file.data.read().len(), 2);
+        assert!(
+            file.data
+                .read()
+                .iter()
+                .all(|c| matches!(c, Chunk::Spilled(_)))
+        );
+        // nothing left in memory
+        assert_eq!(file.spill().unwrap(), 0);
+
+        let loaded = file.load(None).unwrap();
+        assert!(loaded.iter().all(|(_, spilled)| *spilled));
+        assert_eq!(
+            loaded.iter().map(|(r, _)| r.data.num_rows()).sum::<usize>(),
+            3
+        );
+        let batches = file.read(Some((3, 10))).unwrap();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].data.num_columns(), 3);
+
+        file.write(entry(4, &["a"])).unwrap();
+        let loaded = file.load(None).unwrap();
+        assert_eq!(loaded.iter().filter(|(_, spilled)| !spilled).count(), 1);
     }
 }
@@ -0,0 +1,168 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Recovery of the orphaned wal files.
+//!
+//! A wal file is an orphan when no writer writes to it, no immutable is
+//! waiting to persist it and the replay of the startup doesn't process it,
+//! typically a file the replay failed on after a crash, for example on an
+//! entry the upgraded version couldn't read. The orphans can be replayed
+//! again into parquet files, or exported as JSON to be ingested elsewhere.
+
+use std::{io::Write, path::PathBuf, time::UNIX_EPOCH};
+
+use serde::Serialize;
+use snafu::ResultExt;
+
+use crate::{
+    entry::Entry,
+    errors::*,
+    wal::{
+        is_replaying, parse_wal_file_name, replay_wal_file, set_replaying, unset_replaying,
+        wal_file_name, wal_scan_files,
+    },
+    writer::get_active_wal_files,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OrphanWalFile {
+    /// Path of the file relative to the wal dir
+    pub file: String,
+    pub org_id: String,
+    pub stream_type: String,
+    pub size: u64,
+    /// Last modification time in microseconds
+    pub updated_at: i64,
+}
+
+fn wal_dir() -> PathBuf {
+    PathBuf::from(&config::get_config().common.data_wal_dir).join(crate::WAL_DIR_DEFAULT_PREFIX)
+}
+
+pub async fn list_orphan_wal_files() -> Result<Vec<OrphanWalFile>> {
+    let wal_dir = wal_dir();
+    // a writer may rotate its file while the dir is scanned, so the files in
+    // use before and after the scan are both excluded
+    let mut active = get_active_wal_files().await;
+    let files = wal_scan_files(&wal_dir, "wal").await?;
+    active.extend(get_active_wal_files().await);
+
+    let mut orphans = Vec::new();
+    for path in files {
+        if active.contains(&path) || is_replaying(&path).await {
+            continue;
+        }
+        let Ok(meta) = std::fs::metadata(&path) else {
+            // persisted in the meantime
+            continue;
+        };
+        let file = wal_file_name(&wal_dir, &path);
+        let (_, org_id, stream_type) = parse_wal_file_name(&file);
+        orphans.push(OrphanWalFile {
+            org_id: org_id.to_string(),
+            stream_type: stream_type.to_string(),
+            size: meta.len(),
+            updated_at: meta
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_micros() as i64)
+                .unwrap_or_default(),
+            file,
+        });
+    }
+    orphans.sort_by(|a, b| a.file.cmp(&b.file));
+    Ok(orphans)
+}
+
+/// The path of the orphan, only the listed files are accepted so the name
+/// can't point outside of the wal dir
+async fn get_orphan_path(file: &str) -> Result<PathBuf> {
+    if !list_orphan_wal_files()
+        .await?
+        .iter()
+        .any(|orphan| orphan.file == file)
+    {
+        return Err(Error::WalFileNotOrphan {
+            file: file.to_string(),
+        });
+    }
+    Ok(wal_dir().join(file))
+}
+
+/// Replays the orphan into parquet files like the replay of the startup, the
+/// wal file is deleted once they are written
+pub async fn replay_orphan_wal_file(file: &str) -> Result<()> {
+    let path = get_orphan_path(file).await?;
+    // another replay of the file may be running
+    if !set_replaying(&path).await {
+        return Err(Error::WalFileNotOrphan {
+            file: file.to_string(),
+        });
+    }
+    log::warn!("replay orphan wal file: {path:?} starting...");
+    let ret = replay_wal_file(&wal_dir(), &path).await;
+    unset_replaying(&path).await;
+    ret
+}
+
+/// Exports the entries of the orphan as JSON lines of the org, stream and
+/// records, the entries which can't be read are skipped
+pub async fn export_orphan_wal_file(file: &str) -> Result<Vec<u8>> {
+    let path = get_orphan_path(file).await?;
+    let (_, file_org_id, _) = parse_wal_file_name(file);
+    let mut reader = wal::Reader::from_path(&path).context(WalSnafu)?;
+    let mut buf = Vec::new();
+    let mut skipped = 0;
+    loop {
+        let entry = match reader.read_entry() {
+            Ok(Some(entry)) => entry,
+            Ok(None) => break,
+            Err(
+                wal::Error::UnableToReadData { .. }
+                | wal::Error::LengthMismatch { .. }
+                | wal::Error::ChecksumMismatch { .. },
+            ) => {
+                skipped += 1;
+                continue;
+            }
+            Err(e) => return Err(Error::WalError { source: e }),
+        };
+        let entry = match Entry::from_bytes(&entry) {
+            Ok(v) => v,
+            Err(Error::ReadDataError { .. }) => {
+                skipped += 1;
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        let org_id = if entry.org_id.is_empty() {
+            file_org_id
+        } else {
+            entry.org_id.as_ref()
+        };
+        let line = serde_json::json!({
+            "org_id": org_id,
+            "stream": entry.stream.as_ref(),
+            "records": entry.data,
+        });
+        serde_json::to_writer(&mut buf, &line).context(JSONSerializationSnafu)?;
+        buf.write_all(b"\n").context(WriteDataSnafu)?;
+    }
+    if skipped > 0 {
+        log::warn!("export orphan wal file: {path:?}, skipped {skipped} unreadable entries");
+    }
+    Ok(buf)
+}
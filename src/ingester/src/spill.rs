@@ -0,0 +1,255 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Bounded memory mode of the ingester.
+//!
+//! With `ZO_MEM_TABLE_SPILL_ENABLED`, once the memtables go over
+//! `ZO_MEM_TABLE_SPILL_RATIO` of `ZO_MEM_TABLE_MAX_SIZE`, their batches are
+//! moved to zstd compressed arrow IPC files under `{wal_dir}/spill`, the
+//! immutable memtables first as they are not written anymore. The spilled
+//! batches are read back from disk by the searches and the persist to
+//! parquet, and their file is removed with the memtable. The spill files are
+//! not needed on restart, the memtables are rebuilt from the WAL.
+
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter},
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use arrow::{
+    ipc::{
+        CompressionType,
+        reader::FileReader,
+        writer::{FileWriter, IpcWriteOptions},
+    },
+    record_batch::RecordBatch,
+};
+use config::{get_config, metrics};
+use snafu::ResultExt;
+
+use crate::{
+    entry::RecordBatchEntry,
+    errors::{CreateFileSnafu, OpenDirSnafu, OpenFileSnafu, Result, SpillSnafu},
+};
+
+static SPILL_FILE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Batches of a memtable spilled to disk, the file is removed on drop
+pub(crate) struct SpillFile {
+    path: PathBuf,
+    size: usize,
+    pub(crate) data_json_size: usize,
+    pub(crate) data_arrow_size: usize,
+    pub(crate) min_ts: i64,
+    pub(crate) max_ts: i64,
+}
+
+impl SpillFile {
+    /// Writes the batches, which share the same schema, into one batch on
+    /// disk
+    pub(crate) fn write(entries: &[Arc<RecordBatchEntry>]) -> Result<Self> {
+        let dir = spill_dir();
+        fs::create_dir_all(&dir).context(OpenDirSnafu { path: dir.clone() })?;
+        let path = dir.join(format!(
+            "{}.arrow",
+            SPILL_FILE_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let schema = entries[0].data.schema();
+        let batch = arrow::compute::concat_batches(&schema, entries.iter().map(|e| &e.data))
+            .context(SpillSnafu { path: path.clone() })?;
+        let file = File::create(&path).context(CreateFileSnafu { path: path.clone() })?;
+        let options = IpcWriteOptions::default()
+            .try_with_compression(Some(CompressionType::ZSTD))
+            .context(SpillSnafu { path: path.clone() })?;
+        let mut writer = FileWriter::try_new_with_options(BufWriter::new(file), &schema, options)
+            .context(SpillSnafu { path: path.clone() })?;
+        writer
+            .write(&batch)
+            .and_then(|_| writer.finish())
+            .context(SpillSnafu { path: path.clone() })?;
+        drop(writer);
+        let size = fs::metadata(&path)
+            .context(OpenFileSnafu { path: path.clone() })?
+            .len() as usize;
+
+        let spilled = Self {
+            path,
+            size,
+            data_json_size: entries.iter().map(|e| e.data_json_size).sum(),
+            data_arrow_size: entries.iter().map(|e| e.data_arrow_size).sum(),
+            min_ts: entries.iter().map(|e| e.min_ts).min().unwrap_or_default(),
+            max_ts: entries.iter().map(|e| e.max_ts).max().unwrap_or_default(),
+        };
+        metrics::INGEST_MEMTABLE_SPILLED_BYTES
+            .with_label_values::<&str>(&[])
+            .add(size as i64);
+        metrics::INGEST_MEMTABLE_SPILL_TOTAL
+            .with_label_values::<&str>(&[])
+            .inc_by(spilled.data_arrow_size as u64);
+        Ok(spilled)
+    }
+
+    pub(crate) fn read(&self) -> Result<Arc<RecordBatchEntry>> {
+        let file = File::open(&self.path).context(OpenFileSnafu {
+            path: self.path.clone(),
+        })?;
+        let reader = FileReader::try_new(BufReader::new(file), None).context(SpillSnafu {
+            path: self.path.clone(),
+        })?;
+        let schema = reader.schema();
+        let batches = reader
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .context(SpillSnafu {
+                path: self.path.clone(),
+            })?;
+        let data = match batches.len() {
+            1 => batches.into_iter().next().unwrap(),
+            _ => arrow::compute::concat_batches(&schema, &batches).context(SpillSnafu {
+                path: self.path.clone(),
+            })?,
+        };
+        metrics::INGEST_MEMTABLE_SPILL_READ_TOTAL
+            .with_label_values::<&str>(&[])
+            .inc_by(self.size as u64);
+        Ok(Arc::new(RecordBatchEntry {
+            data,
+            data_json_size: self.data_json_size,
+            data_arrow_size: self.data_arrow_size,
+            min_ts: self.min_ts,
+            max_ts: self.max_ts,
+        }))
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_file(&self.path) {
+            log::error!(
+                "[INGESTER:SPILL] remove spill file {} error: {e}",
+                self.path.display()
+            );
+        }
+        metrics::INGEST_MEMTABLE_SPILLED_BYTES
+            .with_label_values::<&str>(&[])
+            .sub(self.size as i64);
+    }
+}
+
+fn spill_dir() -> PathBuf {
+    PathBuf::from(&get_config().common.data_wal_dir).join("spill")
+}
+
+/// Removes the spill files left by a previous run
+pub(crate) fn clean() -> Result<()> {
+    let dir = spill_dir();
+    match fs::remove_dir_all(&dir) {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e).context(OpenDirSnafu { path: dir }),
+    }
+}
+
+/// Arrow bytes to move to disk to get the memtables back under the spill
+/// threshold
+pub(crate) fn bytes_to_spill() -> usize {
+    let cfg = get_config();
+    if !cfg.limit.mem_table_spill_enabled {
+        return 0;
+    }
+    let threshold = cfg.limit.mem_table_max_size / 100 * cfg.limit.mem_table_spill_ratio;
+    let used = metrics::INGEST_MEMTABLE_ARROW_BYTES
+        .with_label_values::<&str>(&[])
+        .get()
+        .max(0) as usize;
+    used.saturating_sub(threshold)
+}
+
+/// Spills the memtables while they are over the threshold
+pub(crate) async fn run() {
+    loop {
+        tokio::time::sleep(tokio::time::Duration::from_secs(
+            get_config().limit.mem_table_spill_interval,
+        ))
+        .await;
+        let to_spill = bytes_to_spill();
+        if to_spill == 0 {
+            continue;
+        }
+        let start = std::time::Instant::now();
+        let mut spilled = crate::immutable::spill(to_spill).await;
+        if spilled < to_spill {
+            spilled += crate::writer::spill(to_spill - spilled).await;
+        }
+        log::warn!(
+            "[INGESTER:SPILL] memtables over the spill threshold by {to_spill} bytes, spilled {spilled} bytes to disk, took: {} ms",
+            start.elapsed().as_millis()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{Int64Array, StringArray};
+    use arrow_schema::{DataType, Field, Schema};
+
+    use super::*;
+
+    fn entry(ts: i64, message: &str) -> Arc<RecordBatchEntry> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("_timestamp", DataType::Int64, false),
+            Field::new("message", DataType::Utf8, true),
+        ]));
+        let data = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from(vec![ts])),
+                Arc::new(StringArray::from(vec![message])),
+            ],
+        )
+        .unwrap();
+        Arc::new(RecordBatchEntry {
+            data,
+            data_json_size: 10,
+            data_arrow_size: 100,
+            min_ts: ts,
+            max_ts: ts,
+        })
+    }
+
+    #[test]
+    fn test_spill_file_roundtrip() {
+        let entries = vec![entry(1, "a"), entry(3, "b"), entry(2, "c")];
+        let spilled = SpillFile::write(&entries).unwrap();
+        assert_eq!(spilled.data_json_size, 30);
+        assert_eq!(spilled.data_arrow_size, 300);
+        assert_eq!((spilled.min_ts, spilled.max_ts), (1, 3));
+
+        let loaded = spilled.read().unwrap();
+        assert_eq!(loaded.data.num_rows(), 3);
+        assert_eq!(loaded.data.schema(), entries[0].data.schema());
+        assert_eq!((loaded.min_ts, loaded.max_ts), (1, 3));
+
+        let path = spilled.path.clone();
+        assert!(path.exists());
+        drop(spilled);
+        assert!(!path.exists());
+    }
+}
@@ -87,6 +87,17 @@ impl Stream {
         }
         Ok((schema_size, paths))
     }
+
+    pub(crate) fn spill(&self, max: usize) -> Result<usize> {
+        let mut freed = 0;
+        for partition in self.partitions.values() {
+            if freed >= max {
+                break;
+            }
+            freed += partition.spill(max - freed)?;
+        }
+        Ok(freed)
+    }
 }
 
 impl MemorySize for Stream {
@@ -16,16 +16,17 @@
 use std::{
     fs::{File, create_dir_all},
     io::{BufRead, BufReader},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 
 use config::{
-    get_config, metrics,
+    RwAHashSet, get_config, metrics,
     utils::{async_walkdir::WalkDir, schema::infer_json_schema_from_values, schema_ext::SchemaExt},
 };
 use futures::StreamExt;
 use hashbrown::HashMap;
+use once_cell::sync::Lazy;
 use snafu::ResultExt;
 
 use crate::{errors::*, immutable, memtable, writer::WriterKey};
@@ -104,137 +105,164 @@ pub(crate) async fn check_uncompleted_parquet_files() -> Result<()> {
     Ok(())
 }
 
-// replay wal files to create immutable
+/// The wal files queued for the replay of the startup, they are orphans only
+/// once their replay failed
+static REPLAYING: Lazy<RwAHashSet<PathBuf>> = Lazy::new(Default::default);
+
+// replay wal files to create immutable, the files which can't be replayed are
+// left on disk as orphans
 pub(crate) async fn replay_wal_files(wal_dir: PathBuf, wal_files: Vec<PathBuf>) -> Result<()> {
     if wal_files.is_empty() {
         return Ok(());
     }
+    REPLAYING.write().await.extend(wal_files.iter().cloned());
     for wal_file in wal_files.iter() {
         log::warn!("replay wal file: {wal_file:?} starting...");
-        let file_str = wal_file
-            .strip_prefix(&wal_dir)
-            .unwrap()
-            .to_str()
-            .unwrap()
-            .replace('\\', "/")
-            .to_string();
-        let file_columns = file_str.split('/').collect::<Vec<_>>();
-        let stream_type = file_columns[file_columns.len() - 2];
-        let org_id = file_columns[file_columns.len() - 3];
-        let idx: usize = file_columns[file_columns.len() - 4]
-            .parse()
-            .unwrap_or_default();
-        let key = WriterKey::new_replay(org_id, stream_type);
-        let mut memtable = memtable::MemTable::new();
-        let mut reader = match wal::Reader::from_path(wal_file) {
-            Ok(v) => v,
-            Err(e) => {
-                log::error!("Unable to open the wal file err: {e}, skip");
-                continue;
-            }
-        };
-        let mut total = 0;
-        let mut i = 0;
-        loop {
-            if i > 0 && i % 1000 == 0 {
-                log::warn!("replay wal file: {wal_file:?}, entries: {i}, records: {total}");
-            }
-            let entry = match reader.read_entry() {
-                Ok(entry) => entry,
-                Err(wal::Error::UnableToReadData { source }) => {
-                    log::error!("Unable to read entry from: {source}, skip the entry");
-                    continue;
-                }
-                Err(wal::Error::LengthMismatch { expected, actual }) => {
-                    log::error!(
-                        "Unable to read entry: Length mismatch: expected {expected}, actual {actual}, skip the entry"
-                    );
-                    continue;
-                }
-                Err(wal::Error::ChecksumMismatch { expected, actual }) => {
-                    log::error!(
-                        "Unable to read entry: Checksum mismatch: expected {expected}, actual {actual}, skip the entry"
-                    );
-                    continue;
-                }
-                Err(e) => {
-                    return Err(Error::WalError { source: e });
-                }
-            };
-            let Some(entry_bytes) = entry else {
-                break;
-            };
-            let mut entry = match super::Entry::from_bytes(&entry_bytes) {
-                Ok(v) => v,
-                Err(Error::ReadDataError { source }) => {
-                    log::error!("Unable to read entry from: {source}, skip the entry");
-                    continue;
-                }
-                Err(e) => {
-                    return Err(e);
-                }
-            };
-            i += 1;
-            total += entry.data.len();
+        if let Err(e) = replay_wal_file(&wal_dir, wal_file).await {
+            log::error!("replay wal file: {wal_file:?} error: {e}, left as orphan");
+        }
+        REPLAYING.write().await.remove(wal_file);
+    }
+    REPLAYING.write().await.shrink_to_fit();
 
-            // Use Entry org_id if available, otherwise fall back to file path
-            let org_id = if !entry.org_id.is_empty() {
-                entry.org_id.as_ref()
-            } else {
-                org_id
-            };
+    Ok(())
+}
 
-            let infer_schema =
-                infer_json_schema_from_values(entry.data.iter().cloned(), stream_type)
-                    .context(InferJsonSchemaSnafu)?;
-            let latest_schema = infra::schema::get_cache(org_id, &entry.stream, stream_type.into())
-                .await
-                .map_err(|e| Error::ExternalError {
-                    source: Box::new(e),
-                })?;
-            entry.schema_key = latest_schema.hash_key().into();
-            let infer_schema = Arc::new(infer_schema.cloned_from(latest_schema.schema()));
-            let batch = entry.into_batch(key.stream_type.clone(), infer_schema.clone())?;
-            memtable.write(infer_schema, entry, batch)?;
-        }
+pub(crate) async fn is_replaying(wal_file: &PathBuf) -> bool {
+    REPLAYING.read().await.contains(wal_file)
+}
 
-        // directly dump the memtable to disk
-        let start = std::time::Instant::now();
-        let wal_path = wal_file.to_owned();
-        let immutable = immutable::Immutable::new(idx, key, memtable);
-        let stat = match immutable.persist(&wal_path).await {
-            Ok(v) => v,
+/// Marks the file as being replayed, returns false when it already was
+pub(crate) async fn set_replaying(wal_file: &Path) -> bool {
+    REPLAYING.write().await.insert(wal_file.to_path_buf())
+}
+
+pub(crate) async fn unset_replaying(wal_file: &Path) {
+    REPLAYING.write().await.remove(wal_file);
+}
+
+pub(crate) async fn replay_wal_file(wal_dir: &Path, wal_file: &PathBuf) -> Result<()> {
+    let file_str = wal_file_name(wal_dir, wal_file);
+    let (idx, org_id, stream_type) = parse_wal_file_name(&file_str);
+    let key = WriterKey::new_replay(org_id, stream_type);
+    let mut memtable = memtable::MemTable::new();
+    let mut reader = wal::Reader::from_path(wal_file).context(WalSnafu)?;
+    let mut total = 0;
+    let mut i = 0;
+    loop {
+        if i > 0 && i % 1000 == 0 {
+            log::warn!("replay wal file: {wal_file:?}, entries: {i}, records: {total}");
+        }
+        let entry = match reader.read_entry() {
+            Ok(entry) => entry,
+            Err(wal::Error::UnableToReadData { source }) => {
+                log::error!("Unable to read entry from: {source}, skip the entry");
+                continue;
+            }
+            Err(wal::Error::LengthMismatch { expected, actual }) => {
+                log::error!(
+                    "Unable to read entry: Length mismatch: expected {expected}, actual {actual}, skip the entry"
+                );
+                continue;
+            }
+            Err(wal::Error::ChecksumMismatch { expected, actual }) => {
+                log::error!(
+                    "Unable to read entry: Checksum mismatch: expected {expected}, actual {actual}, skip the entry"
+                );
+                continue;
+            }
             Err(e) => {
-                log::error!("persist wal file: {wal_file:?} to disk error: {e}");
+                return Err(Error::WalError { source: e });
+            }
+        };
+        let Some(entry_bytes) = entry else {
+            break;
+        };
+        let mut entry = match super::Entry::from_bytes(&entry_bytes) {
+            Ok(v) => v,
+            Err(Error::ReadDataError { source }) => {
+                log::error!("Unable to read entry from: {source}, skip the entry");
                 continue;
             }
+            Err(e) => {
+                return Err(e);
+            }
         };
+        i += 1;
+        total += entry.data.len();
 
-        // update metrics
-        metrics::INGEST_MEMTABLE_BYTES
-            .with_label_values::<&str>(&[])
-            .sub(stat.json_size);
-        metrics::INGEST_MEMTABLE_ARROW_BYTES
-            .with_label_values::<&str>(&[])
-            .sub(stat.arrow_size as i64);
-        metrics::INGEST_MEMTABLE_FILES
-            .with_label_values::<&str>(&[])
-            .dec();
+        // Use Entry org_id if available, otherwise fall back to file path
+        let org_id = if !entry.org_id.is_empty() {
+            entry.org_id.as_ref()
+        } else {
+            org_id
+        };
 
-        log::warn!(
-            "replay wal file: {:?} done, json_size: {}, arrow_size: {}, file_num: {} batch_num: {}, took: {} ms",
-            wal_path.to_string_lossy(),
-            stat.json_size,
-            stat.arrow_size,
-            stat.file_num,
-            stat.batch_num,
-            start.elapsed().as_millis(),
-        );
+        let infer_schema = infer_json_schema_from_values(entry.data.iter().cloned(), stream_type)
+            .context(InferJsonSchemaSnafu)?;
+        let latest_schema = infra::schema::get_cache(org_id, &entry.stream, stream_type.into())
+            .await
+            .map_err(|e| Error::ExternalError {
+                source: Box::new(e),
+            })?;
+        entry.schema_key = latest_schema.hash_key().into();
+        let infer_schema = Arc::new(infer_schema.cloned_from(latest_schema.schema()));
+        let batch = entry.into_batch(key.stream_type.clone(), infer_schema.clone())?;
+        memtable.write(infer_schema, entry, batch)?;
     }
 
+    // directly dump the memtable to disk
+    let start = std::time::Instant::now();
+    let wal_path = wal_file.to_owned();
+    let immutable = immutable::Immutable::new(idx, key, memtable);
+    let stat = immutable.persist(&wal_path).await?;
+
+    // update metrics
+    metrics::INGEST_MEMTABLE_BYTES
+        .with_label_values::<&str>(&[])
+        .sub(stat.json_size);
+    metrics::INGEST_MEMTABLE_ARROW_BYTES
+        .with_label_values::<&str>(&[])
+        .sub(stat.arrow_size as i64);
+    metrics::INGEST_MEMTABLE_FILES
+        .with_label_values::<&str>(&[])
+        .dec();
+
+    log::warn!(
+        "replay wal file: {:?} done, json_size: {}, arrow_size: {}, file_num: {} batch_num: {}, took: {} ms",
+        wal_path.to_string_lossy(),
+        stat.json_size,
+        stat.arrow_size,
+        stat.file_num,
+        stat.batch_num,
+        start.elapsed().as_millis(),
+    );
     Ok(())
 }
 
+/// The path of the wal file relative to the wal dir
+pub(crate) fn wal_file_name(wal_dir: &Path, wal_file: &Path) -> String {
+    wal_file
+        .strip_prefix(wal_dir)
+        .unwrap_or(wal_file)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+// wal file format: {idx}/{org}/{stype}/{id}.wal
+pub(crate) fn parse_wal_file_name(file: &str) -> (usize, &str, &str) {
+    let columns = file.split('/').collect::<Vec<_>>();
+    let n = columns.len();
+    if n < 4 {
+        return (0, "", "");
+    }
+    (
+        columns[n - 4].parse().unwrap_or_default(),
+        columns[n - 3],
+        columns[n - 2],
+    )
+}
+
 pub(crate) async fn wal_scan_files(
     root_dir: impl Into<PathBuf>,
     ext: &str,
@@ -301,3 +329,21 @@ pub async fn collect_wal_parquet_metrics() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_wal_file_name() {
+        assert_eq!(
+            parse_wal_file_name("3/default/logs/7291561434352549888.wal"),
+            (3, "default", "logs")
+        );
+        assert_eq!(
+            parse_wal_file_name("/data/wal/logs/0/org1/traces/1.wal"),
+            (0, "org1", "traces")
+        );
+        assert_eq!(parse_wal_file_name("logs/1.wal"), (0, "", ""));
+    }
+}
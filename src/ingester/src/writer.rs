@@ -17,8 +17,8 @@ use std::{
     collections::HashSet,
     path::PathBuf,
     sync::{
-        Arc,
-        atomic::{AtomicI64, AtomicU64, Ordering},
+        Arc, Weak,
+        atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering},
     },
     time::Instant,
 };
@@ -26,7 +26,9 @@ use std::{
 use arrow_schema::Schema;
 use chrono::{Duration, Utc};
 use config::{
-    MEM_TABLE_INDIVIDUAL_STREAMS, get_config, metrics,
+    MEM_TABLE_INDIVIDUAL_STREAMS, get_config,
+    meta::stream::WalSyncPolicy,
+    metrics,
     stats::MemorySize,
     utils::hash::{Sum64, gxhash},
 };
@@ -54,6 +56,9 @@ static WRITERS: Lazy<Vec<RwMap<WriterKey, Arc<Writer>>>> = Lazy::new(|| {
     writers
 });
 
+/// Batches of the write queues that failed to be written into the WAL
+static WRITE_QUEUE_ERRORS: AtomicU64 = AtomicU64::new(0);
+
 static WAL_RUNTIME: Lazy<Option<Arc<tokio::runtime::Runtime>>> = Lazy::new(|| {
     let cfg = get_config();
     if !cfg.common.wal_dedicated_runtime_enabled {
@@ -81,7 +86,58 @@ pub struct Writer {
     memtable: Arc<RwLock<MemTable>>,
     next_seq: AtomicU64,
     created_at: AtomicI64,
-    write_queue: Arc<mpsc::Sender<(WriterSignal, crate::ProcessedBatch, bool)>>,
+    write_queue: Arc<mpsc::Sender<(WriterSignal, crate::ProcessedBatch, WalSyncPolicy)>>,
+    /// Batches sent to the write queue
+    queued: AtomicU64,
+    /// Batches taken off the write queue
+    committed: AtomicU64,
+    /// Batches of the interval sync policy written but not fsynced yet
+    unsynced: AtomicBool,
+    synced_at: AtomicI64,
+}
+
+/// Tells when the batches written between [`WalCommit::begin`] and
+/// [`WalCommit::end`] are in the WAL.
+///
+/// Without the write queue a write returns once it is in the WAL. With it,
+/// the positions of the queues are taken at the end, as a queue is consumed
+/// in order the batches are in the WAL once the queues reach them. A batch
+/// that fails on any queue in the meantime makes it uncommitted for good, the
+/// client is expected to send it again.
+#[derive(Default)]
+pub struct WalCommit {
+    errors: u64,
+    queues: Vec<(Weak<Writer>, u64)>,
+}
+
+impl WalCommit {
+    pub fn begin() -> Self {
+        Self {
+            errors: WRITE_QUEUE_ERRORS.load(Ordering::SeqCst),
+            queues: Vec::new(),
+        }
+    }
+
+    pub async fn end(&mut self) {
+        for w in WRITERS.iter() {
+            let w = w.read().await;
+            for r in w.values() {
+                let queued = r.queued.load(Ordering::SeqCst);
+                if queued > r.committed.load(Ordering::SeqCst) {
+                    self.queues.push((Arc::downgrade(r), queued));
+                }
+            }
+        }
+    }
+
+    pub fn is_committed(&self) -> bool {
+        // a closed writer consumed its whole queue before it was dropped
+        WRITE_QUEUE_ERRORS.load(Ordering::SeqCst) == self.errors
+            && self.queues.iter().all(|(w, queued)| {
+                w.upgrade()
+                    .is_none_or(|w| w.committed.load(Ordering::SeqCst) >= *queued)
+            })
+    }
 }
 
 // check total memtable size
@@ -269,7 +325,11 @@ pub async fn check_ttl() -> Result<()> {
         for r in w.values() {
             if let Err(e) = r
                 .write_queue
-                .send((WriterSignal::Rotate, crate::ProcessedBatch::empty(), false))
+                .send((
+                    WriterSignal::Rotate,
+                    crate::ProcessedBatch::empty(),
+                    WalSyncPolicy::Async,
+                ))
                 .await
             {
                 log::error!("[INGESTER:MEM:{}] writer queue rotate error: {e}", r.idx);
@@ -279,6 +339,52 @@ pub async fn check_ttl() -> Result<()> {
     Ok(())
 }
 
+/// Spills the memtables of the writers until `max` arrow bytes are freed, the
+/// writes of a writer wait while its memtable is spilled
+pub(crate) async fn spill(max: usize) -> usize {
+    let mut freed = 0;
+    for w in WRITERS.iter() {
+        let writers = w.read().await.values().cloned().collect::<Vec<_>>();
+        for r in writers {
+            if freed >= max {
+                return freed;
+            }
+            let memtable = r.memtable.read().await;
+            match memtable.spill(max - freed) {
+                Ok(v) => freed += v,
+                Err(e) => log::error!(
+                    "[INGESTER:SPILL] spill memtable of writer {} error: {e}",
+                    r.idx
+                ),
+            }
+        }
+    }
+    freed
+}
+
+/// Fsyncs the writers with batches of the interval sync policy pending since
+/// more than `ZO_WAL_SYNC_INTERVAL`
+pub(crate) async fn sync_interval() {
+    loop {
+        let interval = get_config().common.wal_sync_interval;
+        tokio::time::sleep(tokio::time::Duration::from_millis(interval)).await;
+        let expired = Utc::now().timestamp_micros() - interval as i64 * 1000;
+        for w in WRITERS.iter() {
+            let writers = w.read().await.values().cloned().collect::<Vec<_>>();
+            for r in writers {
+                if !r.unsynced.load(Ordering::Acquire)
+                    || r.synced_at.load(Ordering::Relaxed) > expired
+                {
+                    continue;
+                }
+                if let Err(e) = r.sync_wal(WalSyncPolicy::Batch).await {
+                    log::error!("[INGESTER:MEM:{}] writer sync wal error: {e}", r.idx);
+                }
+            }
+        }
+    }
+}
+
 pub async fn flush_all() -> Result<()> {
     log::info!("[INGESTER:MEM] start flush all writers");
     for w in WRITERS.iter() {
@@ -297,6 +403,20 @@ pub async fn flush_all() -> Result<()> {
     Ok(())
 }
 
+/// The wal files in use, the ones the writers write to and the ones of the
+/// immutables waiting to be persisted
+pub(crate) async fn get_active_wal_files() -> HashSet<PathBuf> {
+    let mut files = HashSet::new();
+    for w in WRITERS.iter() {
+        let writers = w.read().await.values().cloned().collect::<Vec<_>>();
+        for r in writers {
+            files.insert(r.wal.read().await.path().clone());
+        }
+    }
+    files.extend(IMMUTABLES.read().await.keys().cloned());
+    files
+}
+
 // get the max seq id of all writers
 pub async fn get_max_writer_seq_id() -> u64 {
     let mut max_seq_id = 0;
@@ -346,6 +466,10 @@ impl Writer {
             next_seq,
             created_at: AtomicI64::new(now),
             write_queue: Arc::new(tx),
+            queued: AtomicU64::new(0),
+            committed: AtomicU64::new(0),
+            unsynced: AtomicBool::new(false),
+            synced_at: AtomicI64::new(now),
         };
         let writer = Arc::new(writer);
         let writer_clone = writer.clone();
@@ -364,14 +488,14 @@ impl Writer {
 
     async fn consume_loop(
         writer: Arc<Writer>,
-        mut rx: mpsc::Receiver<(WriterSignal, crate::ProcessedBatch, bool)>,
+        mut rx: mpsc::Receiver<(WriterSignal, crate::ProcessedBatch, WalSyncPolicy)>,
         idx: usize,
     ) {
         let mut total: usize = 0;
         loop {
             match rx.recv().await {
                 None => break,
-                Some((sign, batch, fsync)) => match sign {
+                Some((sign, batch, sync_policy)) => match sign {
                     WriterSignal::Close => break,
                     WriterSignal::Rotate => {
                         if let Err(e) = writer.rotate(0, 0).await {
@@ -379,9 +503,11 @@ impl Writer {
                         }
                     }
                     WriterSignal::Produce => {
-                        if let Err(e) = writer.consume_processed(batch, fsync).await {
+                        if let Err(e) = writer.consume_processed(batch, sync_policy).await {
                             log::error!("[INGESTER:MEM:{idx}] writer consume batch error: {e}");
+                            WRITE_QUEUE_ERRORS.fetch_add(1, Ordering::SeqCst);
                         }
+                        writer.committed.fetch_add(1, Ordering::SeqCst);
                     }
                 },
             }
@@ -401,21 +527,30 @@ impl Writer {
         format!("{}/{}", self.key.org_id, self.key.stream_type)
     }
 
+    pub fn get_stream_type(&self) -> &str {
+        &self.key.stream_type
+    }
+
     pub fn is_channel_closed(&self) -> bool {
         self.write_queue.is_closed()
     }
 
     // check_ttl is used to check if the memtable has expired
-    pub async fn write(&self, schema: Arc<Schema>, mut entry: Entry, fsync: bool) -> Result<()> {
+    pub async fn write(
+        &self,
+        schema: Arc<Schema>,
+        mut entry: Entry,
+        sync_policy: WalSyncPolicy,
+    ) -> Result<()> {
         if entry.data.is_empty() {
             return Ok(());
         }
 
         entry.schema = Some(schema);
-        self.write_batch(vec![entry], fsync).await
+        self.write_batch(vec![entry], sync_policy).await
     }
 
-    pub async fn write_batch(&self, entries: Vec<Entry>, fsync: bool) -> Result<()> {
+    pub async fn write_batch(&self, entries: Vec<Entry>, sync_policy: WalSyncPolicy) -> Result<()> {
         if entries.is_empty() {
             return Ok(());
         }
@@ -427,14 +562,18 @@ impl Writer {
 
         let cfg = get_config();
         if !cfg.common.wal_write_queue_enabled {
-            return self.consume_processed(processed_batch, fsync).await;
+            return self.consume_processed(processed_batch, sync_policy).await;
         }
 
+        // counted before it is sent, so that the batches queued before a
+        // position are always in the queue ahead of it
+        self.queued.fetch_add(1, Ordering::SeqCst);
         if cfg.common.wal_write_queue_full_reject {
             if let Err(e) =
                 self.write_queue
-                    .try_send((WriterSignal::Produce, processed_batch, fsync))
+                    .try_send((WriterSignal::Produce, processed_batch, sync_policy))
             {
+                self.queued.fetch_sub(1, Ordering::SeqCst);
                 log::error!(
                     "[INGESTER:MEM:{}] write queue full, reject write: {}",
                     self.idx,
@@ -444,11 +583,13 @@ impl Writer {
                     source: wal::Error::WriteQueueFull { idx: self.idx },
                 });
             }
-        } else {
-            self.write_queue
-                .send((WriterSignal::Produce, processed_batch, fsync))
-                .await
-                .context(TokioMpscSendEntriesSnafu)?;
+        } else if let Err(e) = self
+            .write_queue
+            .send((WriterSignal::Produce, processed_batch, sync_policy))
+            .await
+        {
+            self.queued.fetch_sub(1, Ordering::SeqCst);
+            return Err(e).context(TokioMpscSendEntriesSnafu);
         }
 
         Ok(())
@@ -501,7 +642,11 @@ impl Writer {
         })
     }
 
-    async fn consume_processed(&self, batch: crate::ProcessedBatch, fsync: bool) -> Result<()> {
+    async fn consume_processed(
+        &self,
+        batch: crate::ProcessedBatch,
+        sync_policy: WalSyncPolicy,
+    ) -> Result<()> {
         if batch.entries.is_empty() {
             return Ok(());
         }
@@ -552,12 +697,7 @@ impl Writer {
             log::warn!("_start_mem_processed_duration: {_start_mem_processed_duration:?}");
         }
 
-        // Check fsync
-        if fsync {
-            let mut wal = self.wal.write().await;
-            wal.sync().context(WalSnafu)?;
-            drop(wal);
-        }
+        self.sync_wal(sync_policy).await?;
 
         let _start_consume_processed_duration = _start_consume_processed.elapsed();
         if _start_consume_processed_duration.as_millis() > 500 {
@@ -567,6 +707,39 @@ impl Writer {
         Ok(())
     }
 
+    async fn sync_wal(&self, sync_policy: WalSyncPolicy) -> Result<()> {
+        let now = Utc::now().timestamp_micros();
+        let fsync = match sync_policy {
+            WalSyncPolicy::Batch => true,
+            WalSyncPolicy::Interval => {
+                let interval = get_config().common.wal_sync_interval as i64 * 1000;
+                now - self.synced_at.load(Ordering::Relaxed) >= interval
+            }
+            WalSyncPolicy::Async => return Ok(()),
+        };
+        let mut wal = self.wal.write().await;
+        if fsync {
+            wal.sync().context(WalSnafu)?;
+            self.synced_at.store(now, Ordering::Relaxed);
+            self.unsynced.store(false, Ordering::Release);
+        } else {
+            // fsynced by sync_interval, or with the next batch after the interval
+            wal.flush().context(WalSnafu)?;
+            self.unsynced.store(true, Ordering::Release);
+        }
+        Ok(())
+    }
+
+    /// Syncs the wal before it is closed, interval batches still pending are
+    /// fsynced whatever the default policy
+    fn close_wal(&self, wal: &mut WalWriter) -> Result<()> {
+        if self.unsynced.swap(false, Ordering::AcqRel) {
+            wal.sync().context(WalSnafu)
+        } else {
+            wal.close().context(WalSnafu)
+        }
+    }
+
     // rotate is used to rotate the wal and memtable if the size exceeds the threshold
     async fn rotate(&self, entry_bytes_size: usize, entry_batch_size: usize) -> Result<()> {
         if !self.check_wal_threshold(self.wal.read().await.size(), entry_bytes_size)
@@ -609,7 +782,7 @@ impl Writer {
             None,
         )
         .context(WalSnafu)?;
-        wal.sync().context(WalSnafu)?; // sync wal before rotation
+        self.close_wal(&mut wal)?; // sync wal before rotation
         let old_wal = std::mem::replace(&mut *wal, new_wal);
         drop(wal);
 
@@ -642,7 +815,11 @@ impl Writer {
         // wait for all messages to be processed
         if let Err(e) = self
             .write_queue
-            .send((WriterSignal::Close, crate::ProcessedBatch::empty(), true))
+            .send((
+                WriterSignal::Close,
+                crate::ProcessedBatch::empty(),
+                WalSyncPolicy::Batch,
+            ))
             .await
         {
             log::error!("[INGESTER:MEM:{}] close writer error: {}", self.idx, e);
@@ -652,7 +829,7 @@ impl Writer {
 
         // rotation wal
         let mut wal = self.wal.write().await;
-        wal.sync().context(WalSnafu)?;
+        self.close_wal(&mut wal)?;
         let path = wal.path().clone();
         drop(wal);
 
@@ -97,6 +97,23 @@ pub async fn run() -> Result<(), anyhow::Error> {
         }
     );
 
+    for i in 0..cfg.limit.async_search_workers {
+        spawn_pausable_job!(
+            format!("async_search_worker_{}", i),
+            get_config().limit.async_search_interval,
+            {
+                if let Err(e) = service::async_search::run(i).await {
+                    log::error!("[ASYNC SEARCH {i}] run async search error: {e}");
+                }
+            }
+        );
+    }
+    spawn_pausable_job!("async_search_delete_expired", 3600, {
+        if let Err(e) = service::async_search::delete_expired().await {
+            log::error!("[ASYNC SEARCH] delete expired jobs error: {e}");
+        }
+    });
+
     // Alert deduplication state cleanup job
     spawn_pausable_job!(
         "alert_dedup_cleanup",
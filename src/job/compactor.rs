@@ -85,6 +85,34 @@ pub async fn run() -> Result<(), anyhow::Error> {
         }
     );
 
+    spawn_pausable_job!(
+        "compactor_metrics_rollup",
+        get_config().compact.metrics_rollup_interval,
+        {
+            if get_config().compact.metrics_rollup_rules.is_empty() {
+                continue;
+            }
+            log::debug!("[COMPACTOR::JOB] Running metrics rollup job");
+            if let Err(e) = compact::rollup::run().await {
+                log::error!("[COMPACTOR::JOB] run metrics rollup job error: {e}");
+            }
+        }
+    );
+
+    spawn_pausable_job!(
+        "compactor_cold_storage_tiering",
+        get_config().compact.cold_storage_interval,
+        {
+            if get_config().compact.cold_storage_account.is_empty() {
+                continue;
+            }
+            log::debug!("[COMPACTOR::JOB] Running cold storage tiering job");
+            if let Err(e) = compact::tiering::run().await {
+                log::error!("[COMPACTOR::JOB] run cold storage tiering job error: {e}");
+            }
+        }
+    );
+
     spawn_pausable_job!("run_merge", get_config().compact.interval + 2, {
         log::debug!("[COMPACTOR::JOB] Running data merge");
         if let Err(e) = compact::run_merge(scheduler.tx().clone()).await {
@@ -106,6 +134,13 @@ pub async fn run() -> Result<(), anyhow::Error> {
         }
     });
 
+    spawn_pausable_job!("run_record_deletion", get_config().compact.interval + 5, {
+        log::debug!("[COMPACTOR::JOB] Running record deletion");
+        if let Err(e) = compact::record_deletion::run().await {
+            log::error!("[COMPACTOR::JOB] run record deletion error: {e}");
+        }
+    });
+
     spawn_pausable_job!(
         "compactor_sync_to_db",
         get_config().compact.sync_to_db_interval,
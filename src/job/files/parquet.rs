@@ -38,7 +38,7 @@ use hashbrown::HashSet;
 use infra::{
     schema::{
         SchemaCache, get_stream_setting_bloom_filter_fields, get_stream_setting_fts_fields,
-        get_stream_setting_index_fields,
+        get_stream_setting_index_bloom_filter_fields, get_stream_setting_index_fields,
     },
     storage,
 };
@@ -55,7 +55,7 @@ use crate::{
         db,
         schema::generate_schema_for_defined_schema_fields,
         search::datafusion::exec::{self, MergeParquetResult, TableBuilder},
-        tantivy::create_tantivy_index,
+        tantivy::{create_tantivy_index, offload},
     },
 };
 
@@ -528,22 +528,23 @@ async fn move_files(
         // yield to other tasks
         tokio::task::yield_now().await;
         // merge file and get the big file key
-        let (account, new_file_name, new_file_meta, new_file_list) = match merge_files(
-            thread_id,
-            latest_schema.clone(),
-            &wal_dir,
-            &files_with_size,
-            num_uds_fields,
-        )
-        .await
-        {
-            Ok(v) => v,
-            Err(e) => {
-                log::error!("[INGESTER:JOB] merge files failed: {e}");
-                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-                continue;
-            }
-        };
+        let (account, new_file_name, new_file_meta, new_file_list, index_offloaded) =
+            match merge_files(
+                thread_id,
+                latest_schema.clone(),
+                &wal_dir,
+                &files_with_size,
+                num_uds_fields,
+            )
+            .await
+            {
+                Ok(v) => v,
+                Err(e) => {
+                    log::error!("[INGESTER:JOB] merge files failed: {e}");
+                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                    continue;
+                }
+            };
         if new_file_name.is_empty() {
             if new_file_list.is_empty() {
                 // no file need to merge
@@ -568,6 +569,9 @@ async fn move_files(
             }
             return Ok(());
         };
+        if index_offloaded {
+            offload::offload(&account, &new_file_name).await;
+        }
 
         // check if allowed to delete the file
         for file in new_file_list.iter() {
@@ -648,20 +652,21 @@ async fn move_files(
 }
 
 /// merge some small files into one big file, upload to storage, returns the big
-/// file key and merged files
+/// file key and merged files, and whether its index is left to an indexer node
 async fn merge_files(
     thread_id: usize,
     latest_schema: Arc<Schema>,
     wal_dir: &Path,
     files_with_size: &[FileKey],
     num_uds_fields: usize,
-) -> Result<(String, String, FileMeta, Vec<FileKey>), anyhow::Error> {
+) -> Result<(String, String, FileMeta, Vec<FileKey>, bool), anyhow::Error> {
     if files_with_size.is_empty() {
         return Ok((
             String::from(""),
             String::from(""),
             FileMeta::default(),
             Vec::new(),
+            false,
         ));
     }
 
@@ -699,6 +704,7 @@ async fn merge_files(
             String::from(""),
             FileMeta::default(),
             Vec::new(),
+            false,
         ));
     }
 
@@ -740,6 +746,7 @@ async fn merge_files(
     let bloom_filter_fields = get_stream_setting_bloom_filter_fields(&stream_settings);
     let full_text_search_fields = get_stream_setting_fts_fields(&stream_settings);
     let index_fields = get_stream_setting_index_fields(&stream_settings);
+    let index_bloom_filter_fields = get_stream_setting_index_bloom_filter_fields(&stream_settings);
     let (defined_schema_fields, need_original, index_original_data, index_all_values) =
         match stream_settings {
             Some(s) => (
@@ -802,6 +809,7 @@ async fn merge_files(
         &bloom_filter_fields,
         &new_file_meta,
         true,
+        None,
     )
     .await;
 
@@ -948,7 +956,13 @@ async fn merge_files(
 
     // skip index generation if not enabled or not supported by stream type
     if !cfg.common.inverted_index_enabled || !stream_type.support_index() {
-        return Ok((account, new_file_key, new_file_meta, retain_file_list));
+        return Ok((
+            account,
+            new_file_key,
+            new_file_meta,
+            retain_file_list,
+            false,
+        ));
     }
 
     // skip index generation if no fields to index
@@ -960,10 +974,22 @@ async fn merge_files(
     let need_index = full_text_search_fields
         .iter()
         .chain(index_fields.iter())
+        .chain(index_bloom_filter_fields.iter())
         .any(|f| latest_schema_fields.contains(f));
     if !need_index {
         log::debug!("skip index generation for stream: {org_id}/{stream_type}/{stream_name}");
-        return Ok((account, new_file_key, new_file_meta, retain_file_list));
+        return Ok((
+            account,
+            new_file_key,
+            new_file_meta,
+            retain_file_list,
+            false,
+        ));
+    }
+
+    // the index is built by an indexer node once the file is in the file list
+    if offload::should_offload().await {
+        return Ok((account, new_file_key, new_file_meta, retain_file_list, true));
     }
 
     // generate tantivy inverted index and write to storage
@@ -973,6 +999,7 @@ async fn merge_files(
         &new_file_key,
         &full_text_search_fields,
         &index_fields,
+        &index_bloom_filter_fields,
         latest_schema.clone(), // Use stream schema to include all configured fields
         reader,
     )
@@ -980,7 +1007,13 @@ async fn merge_files(
     .map_err(|e| anyhow::anyhow!("generate_tantivy_index_on_ingester error: {}", e))?;
     new_file_meta.index_size = index_size as i64;
 
-    Ok((account, new_file_key, new_file_meta, retain_file_list))
+    Ok((
+        account,
+        new_file_key,
+        new_file_meta,
+        retain_file_list,
+        false,
+    ))
 }
 
 fn split_perfix(prefix: &str) -> (String, StreamType, String, String) {
@@ -0,0 +1,67 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use config::{cluster::LOCAL_NODE, get_config, utils::json};
+use infra::queue::{self, DeliverPolicy};
+use tokio::sync::Semaphore;
+
+use crate::service::tantivy::offload::{self, IndexJob, QUEUE_TOPIC};
+
+pub async fn run() -> Result<(), anyhow::Error> {
+    if !LOCAL_NODE.is_indexer() {
+        return Ok(());
+    }
+
+    offload::create_queue().await?;
+    // the consumer is shared by the indexer nodes, each job goes to one of them
+    let rx = queue::get_queue()
+        .await
+        .consume(QUEUE_TOPIC, Some(DeliverPolicy::All))
+        .await?;
+    let Some(mut rx) = Arc::into_inner(rx) else {
+        return Err(anyhow::anyhow!(
+            "[INDEXER:JOB] index jobs receiver is shared"
+        ));
+    };
+
+    let semaphore = Arc::new(Semaphore::new(get_config().limit.indexer_thread_num));
+    while let Some(message) = rx.recv().await {
+        let permit = semaphore.clone().acquire_owned().await?;
+        tokio::spawn(async move {
+            let _permit = permit;
+            match json::from_slice::<IndexJob>(message.message()) {
+                Ok(job) => match offload::build(&job).await {
+                    Ok(size) => {
+                        log::info!("[INDEXER:JOB] built index of {}, size: {size}", job.key);
+                    }
+                    Err(e) => {
+                        log::error!("[INDEXER:JOB] build index of {} error: {e}", job.key);
+                    }
+                },
+                Err(e) => {
+                    log::error!("[INDEXER:JOB] invalid index job: {e}");
+                }
+            }
+            // a failed job is not retried, the file is searched without index
+            if let Err(e) = message.ack().await {
+                log::error!("[INDEXER:JOB] ack index job error: {e}");
+            }
+        });
+    }
+    log::debug!("[INDEXER:JOB] Receiving index jobs channel is closed");
+    Ok(())
+}
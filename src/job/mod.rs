@@ -43,6 +43,7 @@ pub(crate) mod files;
 mod flatten_compactor;
 #[cfg(feature = "enterprise")]
 mod incidents;
+mod indexer;
 pub mod metrics;
 mod mmdb_downloader;
 #[cfg(feature = "enterprise")]
@@ -283,6 +284,11 @@ pub async fn init() -> Result<(), anyhow::Error> {
     // initialize metadata watcher
     tokio::task::spawn(db::schema::watch());
     tokio::task::spawn(db::functions::watch());
+    tokio::task::spawn(db::stream_alias::watch());
+    tokio::task::spawn(db::dataset::watch());
+    tokio::task::spawn(db::org_lifecycle::watch());
+    tokio::task::spawn(db::level_mapping::watch());
+    tokio::task::spawn(db::log_metrics::watch());
     tokio::task::spawn(db::compact::retention::watch());
     tokio::task::spawn(db::metrics::watch_prom_cluster_leader());
     tokio::task::spawn(db::system_settings::watch());
@@ -321,6 +327,19 @@ pub async fn init() -> Result<(), anyhow::Error> {
     db::functions::cache()
         .await
         .expect("functions cache failed");
+    db::stream_alias::cache()
+        .await
+        .expect("stream alias cache failed");
+    db::dataset::cache().await.expect("dataset cache failed");
+    db::org_lifecycle::cache()
+        .await
+        .expect("org lifecycle cache failed");
+    db::level_mapping::cache()
+        .await
+        .expect("level mapping cache failed");
+    db::log_metrics::cache()
+        .await
+        .expect("log metrics cache failed");
     db::compact::retention::cache()
         .await
         .expect("compact delete cache failed");
@@ -392,11 +411,82 @@ pub async fn init() -> Result<(), anyhow::Error> {
     tokio::task::spawn(stats::run());
     tokio::task::spawn(compactor::run());
     tokio::task::spawn(flatten_compactor::run());
+    tokio::task::spawn(indexer::run());
     #[cfg(feature = "enterprise")]
     tokio::task::spawn(service_graph::run());
     #[cfg(feature = "enterprise")]
     tokio::task::spawn(incidents::run());
     tokio::task::spawn(metrics::run());
+    if LOCAL_NODE.is_ingester() && cfg.flow_collector.enabled {
+        tokio::task::spawn(async move {
+            if let Err(e) = crate::service::flow::run().await {
+                log::error!("[FLOW] collector failed: {e}");
+            }
+        });
+    }
+    if LOCAL_NODE.is_ingester() && cfg.docker_logs.gelf_enabled {
+        tokio::task::spawn(async move {
+            if let Err(e) = crate::service::logs::docker::run_gelf_server().await {
+                log::error!("[DOCKER] GELF server failed: {e}");
+            }
+        });
+    }
+    if LOCAL_NODE.is_ingester() && cfg.gelf.enabled {
+        tokio::task::spawn(async move {
+            if let Err(e) = crate::service::logs::gelf::run_server().await {
+                log::error!("[GELF] server failed: {e}");
+            }
+        });
+    }
+    if LOCAL_NODE.is_ingester() && cfg.k8s_events.enabled {
+        tokio::task::spawn(async move {
+            if let Err(e) = crate::service::k8s_events::run().await {
+                log::error!("[K8S_EVENTS] watcher failed: {e}");
+            }
+        });
+    }
+    if LOCAL_NODE.is_ingester() && cfg.access_log_import.enabled {
+        tokio::task::spawn(async move {
+            if let Err(e) = crate::service::access_logs::run().await {
+                log::error!("[ACCESS_LOGS] importer failed: {e}");
+            }
+        });
+    }
+    if LOCAL_NODE.is_ingester() && cfg.kafka_ingestion.enabled {
+        tokio::task::spawn(async move {
+            if let Err(e) = crate::service::ingestion::kafka::run().await {
+                log::error!("[KAFKA] consumer failed: {e}");
+            }
+        });
+    }
+    if LOCAL_NODE.is_ingester() && cfg.kinesis_ingestion.enabled {
+        tokio::task::spawn(async move {
+            if let Err(e) = crate::service::ingestion::kinesis::run().await {
+                log::error!("[KINESIS] consumer failed: {e}");
+            }
+        });
+    }
+    if LOCAL_NODE.is_ingester() && cfg.mqtt_ingestion.enabled {
+        tokio::task::spawn(async move {
+            if let Err(e) = crate::service::ingestion::mqtt::run().await {
+                log::error!("[MQTT] consumer failed: {e}");
+            }
+        });
+    }
+    if LOCAL_NODE.is_ingester() && cfg.redis_streams_ingestion.enabled {
+        tokio::task::spawn(async move {
+            if let Err(e) = crate::service::ingestion::redis_streams::run().await {
+                log::error!("[REDIS_STREAMS] consumers failed: {e}");
+            }
+        });
+    }
+    if LOCAL_NODE.is_ingester() {
+        tokio::task::spawn(async move {
+            if let Err(e) = crate::service::ingestion::rabbitmq::run().await {
+                log::error!("[RABBITMQ] consumers failed: {e}");
+            }
+        });
+    }
     let _ = promql::run();
     tokio::task::spawn(alert_manager::run());
     #[cfg(feature = "enterprise")]
@@ -418,10 +508,112 @@ pub async fn init() -> Result<(), anyhow::Error> {
     pipeline_error_cleanup::run();
     session_cleanup::run();
 
+    // persist the field usage recorded by searches
+    spawn_pausable_job!(
+        "field_usage_flush",
+        config::get_config().common.field_usage_flush_interval,
+        {
+            crate::service::field_usage::flush().await;
+        },
+        pause_if: !config::get_config().common.field_usage_enabled
+    );
+
+    // persist the clock skew of the sources seen by this node
+    spawn_pausable_job!(
+        "clock_skew_flush",
+        config::get_config().common.clock_skew_flush_interval,
+        {
+            crate::service::ingestion::clock_skew::flush().await;
+        },
+        pause_if: config::get_config().common.clock_skew_flush_interval == 0
+    );
+
+    // write the traces decided by tail sampling
+    spawn_pausable_job!("tail_sampling_flush", 1, {
+        crate::service::traces::tail_sampling::flush().await;
+    });
+
+    // persist the calls between services seen by this node
+    spawn_pausable_job!(
+        "service_map_flush",
+        config::get_config().common.service_map_flush_interval,
+        {
+            crate::service::traces::service_map::flush().await;
+        },
+        pause_if: config::get_config().common.service_map_flush_interval == 0
+    );
+
+    // write the metrics extracted from the ingested logs
+    spawn_pausable_job!(
+        "log_metrics_flush",
+        config::get_config().common.log_metrics_flush_interval,
+        {
+            crate::service::log_metrics::flush().await;
+        },
+        pause_if: config::get_config().common.log_metrics_flush_interval == 0
+    );
+
+    // share the ingestion rates used by the quotas
+    if LOCAL_NODE.is_ingester() {
+        spawn_pausable_job!(
+            "ingest_quota_sync",
+            config::get_config().limit.ingest_quota_sync_interval,
+            {
+                if let Err(e) = crate::service::ingestion::quota::sync().await {
+                    log::error!("[INGEST_QUOTA] sync error: {e}");
+                }
+            },
+            pause_if: !config::get_config().limit.ingest_quota_enabled
+        );
+    }
+
+    // share the search consumption used by the quotas
+    if LOCAL_NODE.is_querier() {
+        spawn_pausable_job!(
+            "search_quota_sync",
+            config::get_config().limit.search_quota_sync_interval,
+            {
+                if let Err(e) = crate::service::search::quota::sync().await {
+                    log::error!("[SEARCH_QUOTA] sync error: {e}");
+                }
+            },
+            pause_if: !config::get_config().limit.search_quota_enabled
+        );
+    }
+
     if LOCAL_NODE.is_compactor() {
         tokio::task::spawn(file_list_dump::run());
     }
 
+    // purge the archived orgs whose grace period is over
+    if LOCAL_NODE.is_compactor() {
+        spawn_pausable_job!("org_lifecycle_purge", 3600, {
+            if let Err(e) = crate::service::org_lifecycle::purge().await {
+                log::error!("[ORG_LIFECYCLE] purge error: {e}");
+            }
+        });
+    }
+
+    // suggest user defined schemas for wide streams
+    if LOCAL_NODE.is_compactor() {
+        spawn_pausable_job!(
+            "schema_suggestion",
+            config::get_config().common.schema_suggestion_interval,
+            {
+                crate::service::schema_suggestion::run().await;
+            },
+            pause_if: !config::get_config().common.schema_suggestion_enabled
+        );
+        spawn_pausable_job!(
+            "pattern_stats",
+            config::get_config().common.pattern_stats_interval,
+            {
+                crate::service::pattern_stats::run().await;
+            },
+            pause_if: config::get_config().common.pattern_stats_interval == 0
+        );
+    }
+
     // load metrics disk cache
     tokio::task::spawn(crate::service::promql::search::init());
 
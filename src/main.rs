@@ -38,7 +38,7 @@ use openobserve::{
     handler::{
         grpc::{
             auth::check_auth,
-            flight::FlightServiceImpl,
+            flight::{FlightServiceImpl, external::ExternalFlightServiceImpl},
             request::{
                 event::Eventer,
                 ingest::Ingester,
@@ -58,7 +58,7 @@ use openobserve::{
         metadata,
         node::NodeService,
         search::SEARCH_SERVER,
-        self_reporting,
+        self_reporting, tls, traces,
     },
 };
 use opentelemetry::{KeyValue, global, trace::TracerProvider};
@@ -83,7 +83,6 @@ use tokio::{net::TcpListener, sync::oneshot};
 use tonic::{
     codec::CompressionEncoding,
     metadata::{MetadataKey, MetadataMap, MetadataValue},
-    transport::{Identity, ServerTlsConfig},
 };
 use tower_http::{compression::CompressionLayer, trace::TraceLayer};
 use tracing_appender::non_blocking::WorkerGuard;
@@ -227,6 +226,10 @@ async fn main() -> Result<(), anyhow::Error> {
             .install_default()
             .expect("Failed to install rustls crypto provider");
     }
+    if cfg.grpc.tls_enabled {
+        tls::grpc::init().expect("Failed to load gRPC TLS certificates");
+        tokio::task::spawn(tls::grpc::run_reload());
+    }
 
     // init action server
     #[cfg(feature = "enterprise")]
@@ -386,6 +389,14 @@ async fn main() -> Result<(), anyhow::Error> {
         .await
         .expect("Deferred jobs failed to init");
 
+    if cfg.pgwire.enabled && config::cluster::LOCAL_NODE.is_querier() {
+        tokio::task::spawn(async move {
+            if let Err(e) = openobserve::handler::pgwire::run_server().await {
+                log::error!("[PGWIRE] server failed: {e}");
+            }
+        });
+    }
+
     if cfg.log.events_enabled {
         tokio::task::spawn(zo_logger::send_logs());
     }
@@ -476,6 +487,9 @@ async fn main() -> Result<(), anyhow::Error> {
     // flush usage report
     self_reporting::flush().await;
 
+    // write the traces held by tail sampling
+    traces::tail_sampling::flush_all().await;
+
     // flush service discovery
     #[cfg(feature = "enterprise")]
     {
@@ -552,18 +566,24 @@ async fn init_common_grpc_server(
         .max_encoding_message_size(cfg.grpc.max_message_size * 1024 * 1024);
     let metrics_ingest_svc = MetricsServiceServer::new(MetricsIngester)
         .send_compressed(CompressionEncoding::Gzip)
+        .send_compressed(CompressionEncoding::Zstd)
         .accept_compressed(CompressionEncoding::Gzip)
-        .max_decoding_message_size(cfg.grpc.max_message_size * 1024 * 1024)
+        .accept_compressed(CompressionEncoding::Zstd)
+        .max_decoding_message_size(cfg.grpc.otlp_max_message_size * 1024 * 1024)
         .max_encoding_message_size(cfg.grpc.max_message_size * 1024 * 1024);
     let logs_svc = LogsServiceServer::new(LogsServer)
         .send_compressed(CompressionEncoding::Gzip)
+        .send_compressed(CompressionEncoding::Zstd)
         .accept_compressed(CompressionEncoding::Gzip)
-        .max_decoding_message_size(cfg.grpc.max_message_size * 1024 * 1024)
+        .accept_compressed(CompressionEncoding::Zstd)
+        .max_decoding_message_size(cfg.grpc.otlp_max_message_size * 1024 * 1024)
         .max_encoding_message_size(cfg.grpc.max_message_size * 1024 * 1024);
     let trace_svc = TraceServiceServer::new(TraceServer)
         .send_compressed(CompressionEncoding::Gzip)
+        .send_compressed(CompressionEncoding::Zstd)
         .accept_compressed(CompressionEncoding::Gzip)
-        .max_decoding_message_size(cfg.grpc.max_message_size * 1024 * 1024)
+        .accept_compressed(CompressionEncoding::Zstd)
+        .max_decoding_message_size(cfg.grpc.otlp_max_message_size * 1024 * 1024)
         .max_encoding_message_size(cfg.grpc.max_message_size * 1024 * 1024);
     let query_cache_svc = QueryCacheServer::new(QueryCacheServerImpl)
         .send_compressed(CompressionEncoding::Gzip)
@@ -580,11 +600,14 @@ async fn init_common_grpc_server(
         .accept_compressed(CompressionEncoding::Gzip)
         .max_decoding_message_size(cfg.grpc.max_message_size * 1024 * 1024)
         .max_encoding_message_size(cfg.grpc.max_message_size * 1024 * 1024);
-    let flight_svc = FlightServiceServer::new(FlightServiceImpl)
-        .send_compressed(CompressionEncoding::Gzip)
-        .accept_compressed(CompressionEncoding::Gzip)
+    let mut flight_svc = FlightServiceServer::new(FlightServiceImpl)
         .max_decoding_message_size(cfg.grpc.max_message_size * 1024 * 1024)
         .max_encoding_message_size(cfg.grpc.max_message_size * 1024 * 1024);
+    if flight::encoder::grpc_compression_enabled() {
+        flight_svc = flight_svc
+            .send_compressed(CompressionEncoding::Gzip)
+            .accept_compressed(CompressionEncoding::Gzip);
+    }
     let node_svc = NodeServiceServer::new(NodeService)
         .send_compressed(CompressionEncoding::Gzip)
         .accept_compressed(CompressionEncoding::Gzip)
@@ -603,15 +626,16 @@ async fn init_common_grpc_server(
     );
     init_tx.send(()).ok();
 
-    let builder = if cfg.grpc.tls_enabled {
-        let cert = std::fs::read_to_string(&cfg.grpc.tls_cert_path)?;
-        let key = std::fs::read_to_string(&cfg.grpc.tls_key_path)?;
-        let identity = Identity::from_pem(cert, key);
-        tonic::transport::Server::builder().tls_config(ServerTlsConfig::new().identity(identity))?
-    } else {
-        tonic::transport::Server::builder()
-    };
-    let ret = builder
+    let (flight_shutdown_tx, flight_shutdown_rx) = oneshot::channel();
+    if cfg.grpc.flight_enabled && config::cluster::LOCAL_NODE.is_querier() {
+        tokio::task::spawn(async move {
+            if let Err(e) = init_external_flight_server(flight_shutdown_rx).await {
+                log::error!("Arrow Flight server init failed: {e}");
+            }
+        });
+    }
+
+    let router = tonic::transport::Server::builder()
         .layer(tonic::service::InterceptorLayer::new(check_auth))
         .add_service(event_svc)
         .add_service(search_svc)
@@ -624,12 +648,19 @@ async fn init_common_grpc_server(
         .add_service(streams_svc)
         .add_service(flight_svc)
         .add_service(node_svc)
-        .add_service(cluster_info_svc)
-        .serve_with_shutdown(gaddr, async {
-            shutdown_rx.await.ok();
-            log::info!("gRPC server starts shutting down");
-        })
-        .await;
+        .add_service(cluster_info_svc);
+    let shutdown = async {
+        shutdown_rx.await.ok();
+        flight_shutdown_tx.send(()).ok();
+        log::info!("gRPC server starts shutting down");
+    };
+    let ret = if cfg.grpc.tls_enabled {
+        router
+            .serve_with_incoming_shutdown(tls::grpc::incoming(gaddr).await?, shutdown)
+            .await
+    } else {
+        router.serve_with_shutdown(gaddr, shutdown).await
+    };
     if let Err(e) = ret {
         return Err(anyhow::anyhow!("{e}"));
     }
@@ -638,6 +669,45 @@ async fn init_common_grpc_server(
     Ok(())
 }
 
+/// Serves search results to external clients over Arrow Flight
+async fn init_external_flight_server(
+    shutdown_rx: oneshot::Receiver<()>,
+) -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    let ip = if !cfg.grpc.addr.is_empty() {
+        cfg.grpc.addr.clone()
+    } else {
+        "0.0.0.0".to_string()
+    };
+    let faddr: SocketAddr = format!("{}:{}", ip, cfg.grpc.flight_port).parse()?;
+    let flight_svc = FlightServiceServer::new(ExternalFlightServiceImpl)
+        .send_compressed(CompressionEncoding::Gzip)
+        .accept_compressed(CompressionEncoding::Gzip)
+        .max_decoding_message_size(cfg.grpc.max_message_size * 1024 * 1024)
+        .max_encoding_message_size(cfg.grpc.max_message_size * 1024 * 1024);
+
+    log::info!(
+        "starting Arrow Flight server {} at {}",
+        if cfg.grpc.tls_enabled { "with TLS" } else { "" },
+        faddr
+    );
+    let router = tonic::transport::Server::builder()
+        .layer(tonic::service::InterceptorLayer::new(check_auth))
+        .add_service(flight_svc);
+    let shutdown = async {
+        shutdown_rx.await.ok();
+        log::info!("Arrow Flight server starts shutting down");
+    };
+    if cfg.grpc.tls_enabled {
+        router
+            .serve_with_incoming_shutdown(tls::grpc::incoming(faddr).await?, shutdown)
+            .await
+    } else {
+        router.serve_with_shutdown(faddr, shutdown).await
+    }
+    .map_err(|e| anyhow::anyhow!("{e}"))
+}
+
 async fn init_router_grpc_server(
     init_tx: oneshot::Sender<()>,
     shutdown_rx: oneshot::Receiver<()>,
@@ -647,18 +717,24 @@ async fn init_router_grpc_server(
     let gaddr: SocketAddr = format!("0.0.0.0:{}", cfg.grpc.port).parse()?;
     let logs_svc = LogsServiceServer::new(router::grpc::ingest::logs::LogsServer)
         .send_compressed(CompressionEncoding::Gzip)
+        .send_compressed(CompressionEncoding::Zstd)
         .accept_compressed(CompressionEncoding::Gzip)
-        .max_decoding_message_size(cfg.grpc.max_message_size * 1024 * 1024)
+        .accept_compressed(CompressionEncoding::Zstd)
+        .max_decoding_message_size(cfg.grpc.otlp_max_message_size * 1024 * 1024)
         .max_encoding_message_size(cfg.grpc.max_message_size * 1024 * 1024);
     let metrics_svc = MetricsServiceServer::new(router::grpc::ingest::metrics::MetricsServer)
         .send_compressed(CompressionEncoding::Gzip)
+        .send_compressed(CompressionEncoding::Zstd)
         .accept_compressed(CompressionEncoding::Gzip)
-        .max_decoding_message_size(cfg.grpc.max_message_size * 1024 * 1024)
+        .accept_compressed(CompressionEncoding::Zstd)
+        .max_decoding_message_size(cfg.grpc.otlp_max_message_size * 1024 * 1024)
         .max_encoding_message_size(cfg.grpc.max_message_size * 1024 * 1024);
     let traces_svc = TraceServiceServer::new(router::grpc::ingest::traces::TraceServer)
         .send_compressed(CompressionEncoding::Gzip)
+        .send_compressed(CompressionEncoding::Zstd)
         .accept_compressed(CompressionEncoding::Gzip)
-        .max_decoding_message_size(cfg.grpc.max_message_size * 1024 * 1024)
+        .accept_compressed(CompressionEncoding::Zstd)
+        .max_decoding_message_size(cfg.grpc.otlp_max_message_size * 1024 * 1024)
         .max_encoding_message_size(cfg.grpc.max_message_size * 1024 * 1024);
 
     log::info!(
@@ -668,24 +744,22 @@ async fn init_router_grpc_server(
     );
     init_tx.send(()).ok();
 
-    let builder = if cfg.grpc.tls_enabled {
-        let cert = std::fs::read_to_string(&cfg.grpc.tls_cert_path)?;
-        let key = std::fs::read_to_string(&cfg.grpc.tls_key_path)?;
-        let identity = Identity::from_pem(cert, key);
-        tonic::transport::Server::builder().tls_config(ServerTlsConfig::new().identity(identity))?
-    } else {
-        tonic::transport::Server::builder()
-    };
-    let ret = builder
+    let router = tonic::transport::Server::builder()
         .layer(tonic::service::InterceptorLayer::new(check_auth))
         .add_service(logs_svc)
         .add_service(metrics_svc)
-        .add_service(traces_svc)
-        .serve_with_shutdown(gaddr, async {
-            shutdown_rx.await.ok();
-            log::info!("gRPC server starts shutting down");
-        })
-        .await;
+        .add_service(traces_svc);
+    let shutdown = async {
+        shutdown_rx.await.ok();
+        log::info!("gRPC server starts shutting down");
+    };
+    let ret = if cfg.grpc.tls_enabled {
+        router
+            .serve_with_incoming_shutdown(tls::grpc::incoming(gaddr).await?, shutdown)
+            .await
+    } else {
+        router.serve_with_shutdown(gaddr, shutdown).await
+    };
     if let Err(e) = ret {
         return Err(anyhow::anyhow!("{e}"));
     }
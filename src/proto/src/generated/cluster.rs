@@ -2428,6 +2428,10 @@ pub struct DeleteResultCacheRequest {
     pub path: ::prost::alloc::string::String,
     #[prost(int64, tag = "2")]
     pub ts: i64,
+    #[prost(int64, tag = "3")]
+    pub start_time: i64,
+    #[prost(int64, tag = "4")]
+    pub end_time: i64,
 }
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct DeleteResultCacheResponse {
@@ -3048,6 +3052,7 @@ pub enum Role {
     AlertManager = 5,
     FlattenCompactor = 6,
     ScriptServer = 7,
+    Indexer = 8,
 }
 impl Role {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -3064,6 +3069,7 @@ impl Role {
             Self::AlertManager => "ALERT_MANAGER",
             Self::FlattenCompactor => "FLATTEN_COMPACTOR",
             Self::ScriptServer => "SCRIPT_SERVER",
+            Self::Indexer => "INDEXER",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -3077,6 +3083,7 @@ impl Role {
             "ALERT_MANAGER" => Some(Self::AlertManager),
             "FLATTEN_COMPACTOR" => Some(Self::FlattenCompactor),
             "SCRIPT_SERVER" => Some(Self::ScriptServer),
+            "INDEXER" => Some(Self::Indexer),
             _ => None,
         }
     }
@@ -3088,6 +3095,7 @@ pub enum RoleGroup {
     None = 0,
     Interactive = 1,
     Background = 2,
+    Shadow = 3,
 }
 impl RoleGroup {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -3099,6 +3107,7 @@ impl RoleGroup {
             Self::None => "NONE",
             Self::Interactive => "INTERACTIVE",
             Self::Background => "BACKGROUND",
+            Self::Shadow => "SHADOW",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -3107,6 +3116,7 @@ impl RoleGroup {
             "NONE" => Some(Self::None),
             "INTERACTIVE" => Some(Self::Interactive),
             "BACKGROUND" => Some(Self::Background),
+            "SHADOW" => Some(Self::Shadow),
             _ => None,
         }
     }
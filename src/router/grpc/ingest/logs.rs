@@ -66,7 +66,7 @@ impl LogsService for LogsServer {
             .send_compressed(CompressionEncoding::Gzip)
             .accept_compressed(CompressionEncoding::Gzip)
             .max_decoding_message_size(cfg.grpc.max_message_size * 1024 * 1024)
-            .max_encoding_message_size(cfg.grpc.max_message_size * 1024 * 1024)
+            .max_encoding_message_size(cfg.grpc.otlp_max_message_size * 1024 * 1024)
             .export(request)
             .await
         {
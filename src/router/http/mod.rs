@@ -651,6 +651,7 @@ pub fn create_router_routes() -> axum::Router {
         .route("/aws/{*path}", any(dispatch))
         .route("/gcp/{*path}", any(dispatch))
         .route("/rum/{*path}", any(dispatch))
+        .route("/webhooks/{*path}", any(dispatch))
 }
 
 #[cfg(test)]
@@ -0,0 +1,446 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! CDN and load balancer access log importer
+//!
+//! Imports the access log objects CloudFront, ALB/ELB and Fastly write to S3.
+//! New objects are found either by listing the configured prefixes past the
+//! last imported key, or from S3 event notifications delivered through SQS.
+//! Records keep the time the request was served. Objects older than
+//! `ZO_INGEST_ALLOWED_UPTO` would be rejected by ingestion, so they are
+//! skipped instead. Only one ingester imports at a time.
+
+use std::{sync::Arc, time::Duration};
+
+use config::{
+    TIMESTAMP_COL_NAME,
+    cluster::LOCAL_NODE,
+    get_config,
+    utils::{json, time::now_micros},
+};
+use futures::{StreamExt, TryStreamExt};
+use hashbrown::HashMap;
+use object_store::{ObjectStore, aws::AmazonS3Builder, path::Path};
+
+use crate::{
+    common::meta::ingestion::{IngestUser, IngestionRequest, IngestionValueType, SystemJobType},
+    service::{
+        db,
+        ingestion::{claim_owner, still_owner},
+    },
+};
+
+pub mod parser;
+
+use parser::Format;
+
+const OWNER_PREFIX: &str = "/access_log_import";
+/// How often a standby node checks whether the importer is still running
+const STANDBY_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+/// Objects imported per source and listing, the rest are picked up next time
+const MAX_OBJECTS_PER_POLL: usize = 1000;
+/// SQS long polling wait, the maximum SQS allows
+const SQS_WAIT_SECS: i32 = 20;
+
+/// A configured `format:s3://bucket/prefix`
+#[derive(Debug, Clone, PartialEq)]
+struct Source {
+    format: Format,
+    bucket: String,
+    prefix: String,
+}
+
+impl Source {
+    /// Key the source's watermark is stored under
+    fn id(&self) -> String {
+        format!("{}/{}", self.bucket, self.prefix)
+    }
+
+    fn matches(&self, bucket: &str, key: &str) -> bool {
+        self.bucket == bucket
+            && (self.prefix.is_empty()
+                || key
+                    .strip_prefix(&self.prefix)
+                    .is_some_and(|rest| rest.starts_with('/')))
+    }
+}
+
+fn parse_sources(sources: &str) -> Result<Vec<Source>, anyhow::Error> {
+    sources
+        .split(',')
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            let (format, location) = v
+                .split_once(':')
+                .ok_or_else(|| anyhow::anyhow!("invalid access log source: {v}"))?;
+            let format = format.parse::<Format>().map_err(|e| anyhow::anyhow!(e))?;
+            let location = location
+                .strip_prefix("s3://")
+                .ok_or_else(|| anyhow::anyhow!("access log source must be an s3:// url: {v}"))?;
+            let (bucket, prefix) = location.split_once('/').unwrap_or((location, ""));
+            if bucket.is_empty() {
+                return Err(anyhow::anyhow!("access log source has no bucket: {v}"));
+            }
+            Ok(Source {
+                format,
+                bucket: bucket.to_string(),
+                prefix: prefix.trim_matches('/').to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Runs forever, importing while this node owns the importer
+pub async fn run() -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    let sources = parse_sources(&cfg.access_log_import.sources)?;
+    let mut stores: HashMap<String, Arc<dyn ObjectStore>> = HashMap::new();
+    for source in sources.iter() {
+        if stores.contains_key(&source.bucket) {
+            continue;
+        }
+        let mut builder = AmazonS3Builder::from_env().with_bucket_name(&source.bucket);
+        if !cfg.access_log_import.region.is_empty() {
+            builder = builder.with_region(&cfg.access_log_import.region);
+        }
+        stores.insert(source.bucket.clone(), Arc::new(builder.build()?));
+    }
+
+    loop {
+        match claim_owner(OWNER_PREFIX).await {
+            Ok(true) => {
+                log::info!(
+                    "[ACCESS_LOGS] importer acquired by node {}",
+                    LOCAL_NODE.name
+                );
+                let ret = if cfg.access_log_import.sqs_queue_url.is_empty() {
+                    poll_sources(&sources, &stores).await
+                } else {
+                    receive_notifications(&sources, &stores).await
+                };
+                if let Err(e) = ret {
+                    log::error!("[ACCESS_LOGS] importer stopped: {e}");
+                }
+            }
+            Ok(false) => {}
+            Err(e) => log::error!("[ACCESS_LOGS] failed to claim importer: {e}"),
+        }
+        tokio::time::sleep(STANDBY_CHECK_INTERVAL).await;
+    }
+}
+
+async fn poll_sources(
+    sources: &[Source],
+    stores: &HashMap<String, Arc<dyn ObjectStore>>,
+) -> Result<(), anyhow::Error> {
+    let interval = Duration::from_secs(get_config().access_log_import.interval_secs);
+    loop {
+        if !still_owner(OWNER_PREFIX).await {
+            return Ok(());
+        }
+        for source in sources {
+            if let Err(e) = poll_source(source, &stores[&source.bucket]).await {
+                log::error!(
+                    "[ACCESS_LOGS] failed to import from s3://{}: {e}",
+                    source.id()
+                );
+            }
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Imports the objects listed after the source's watermark. S3 lists keys in
+/// lexicographical order and CDN and load balancer keys embed the time they
+/// were written, so the last imported key is a stable position.
+async fn poll_source(source: &Source, store: &Arc<dyn ObjectStore>) -> Result<(), anyhow::Error> {
+    let source_id = source.id();
+    let watermark = db::access_log_import::get_watermark(&source_id).await;
+    let prefix = (!source.prefix.is_empty()).then(|| Path::from(source.prefix.as_str()));
+    let listing = if watermark.is_empty() {
+        store.list(prefix.as_ref())
+    } else {
+        store.list_with_offset(prefix.as_ref(), &Path::from(watermark.as_str()))
+    };
+    let objects: Vec<_> = listing.take(MAX_OBJECTS_PER_POLL).try_collect().await?;
+
+    let min_ts = now_micros() - get_config().limit.ingest_allowed_upto_micro;
+    for meta in objects {
+        if meta.last_modified.timestamp_micros() < min_ts {
+            log::debug!(
+                "[ACCESS_LOGS] skipping s3://{}/{}, older than ZO_INGEST_ALLOWED_UPTO",
+                source.bucket,
+                meta.location
+            );
+        } else {
+            import_object(source, store, &meta.location).await?;
+        }
+        db::access_log_import::set_watermark(&source_id, meta.location.as_ref()).await?;
+    }
+    Ok(())
+}
+
+async fn receive_notifications(
+    sources: &[Source],
+    stores: &HashMap<String, Arc<dyn ObjectStore>>,
+) -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    let queue_url = &cfg.access_log_import.sqs_queue_url;
+    let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest());
+    if !cfg.access_log_import.region.is_empty() {
+        loader = loader.region(aws_config::Region::new(
+            cfg.access_log_import.region.clone(),
+        ));
+    }
+    let client = aws_sdk_sqs::Client::new(&loader.load().await);
+
+    loop {
+        if !still_owner(OWNER_PREFIX).await {
+            return Ok(());
+        }
+        let resp = match client
+            .receive_message()
+            .queue_url(queue_url)
+            .max_number_of_messages(10)
+            .wait_time_seconds(SQS_WAIT_SECS)
+            .send()
+            .await
+        {
+            Ok(v) => v,
+            Err(e) => {
+                log::error!("[ACCESS_LOGS] failed to receive from {queue_url}: {e}");
+                tokio::time::sleep(RETRY_DELAY).await;
+                continue;
+            }
+        };
+        for message in resp.messages() {
+            let mut imported = true;
+            for (bucket, key) in s3_event_objects(message.body().unwrap_or_default()) {
+                let Some(source) = sources.iter().find(|s| s.matches(&bucket, &key)) else {
+                    continue;
+                };
+                if let Err(e) =
+                    import_object(source, &stores[&bucket], &Path::from(key.as_str())).await
+                {
+                    log::error!("[ACCESS_LOGS] failed to import s3://{bucket}/{key}: {e}");
+                    imported = false;
+                }
+            }
+            // failed messages become visible again and are retried
+            if imported
+                && let Some(handle) = message.receipt_handle()
+                && let Err(e) = client
+                    .delete_message()
+                    .queue_url(queue_url)
+                    .receipt_handle(handle)
+                    .send()
+                    .await
+            {
+                log::error!("[ACCESS_LOGS] failed to delete message from {queue_url}: {e}");
+            }
+        }
+    }
+}
+
+/// Returns the bucket and key of created objects in an S3 event notification,
+/// either delivered to SQS directly or through SNS
+fn s3_event_objects(body: &str) -> Vec<(String, String)> {
+    let Ok(mut event) = json::from_str::<json::Value>(body) else {
+        return vec![];
+    };
+    if let Some(message) = event["Message"].as_str() {
+        let Ok(inner) = json::from_str::<json::Value>(message) else {
+            return vec![];
+        };
+        event = inner;
+    }
+    event["Records"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|r| {
+            r["eventName"]
+                .as_str()
+                .is_some_and(|v| v.starts_with("ObjectCreated"))
+        })
+        .filter_map(|r| {
+            let bucket = r["s3"]["bucket"]["name"].as_str()?;
+            let key = r["s3"]["object"]["key"].as_str()?;
+            Some((bucket.to_string(), decode_key(key)))
+        })
+        .collect()
+}
+
+/// Object keys in event notifications are form url encoded
+fn decode_key(key: &str) -> String {
+    let bytes = key.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| key.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|v| u8::from_str_radix(v, 16).ok());
+        match (bytes[i], escaped) {
+            (_, Some(v)) => {
+                out.push(v);
+                i += 2;
+            }
+            (b'+', None) => out.push(b' '),
+            (b, None) => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+async fn import_object(
+    source: &Source,
+    store: &Arc<dyn ObjectStore>,
+    location: &Path,
+) -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    let object = store.get(location).await?;
+    // records without a usable time get the time the object was written
+    let object_ts = object.meta.last_modified.timestamp_micros();
+    let data = object.bytes().await?;
+    let content = parser::decompress(&data)?;
+    let parsed = parser::parse(source.format, &content);
+    let object_url = format!("s3://{}/{location}", source.bucket);
+    if parsed.invalid > 0 {
+        log::warn!(
+            "[ACCESS_LOGS] {} lines of {object_url} are not valid {} access logs",
+            parsed.invalid,
+            source.format.as_str()
+        );
+    }
+
+    let min_ts = now_micros() - cfg.limit.ingest_allowed_upto_micro;
+    let batch_size = cfg.access_log_import.batch_size;
+    let mut too_old = 0;
+    let mut batches: Vec<Vec<json::Value>> = vec![];
+    for mut record in parsed.records {
+        let ts = record
+            .get(TIMESTAMP_COL_NAME)
+            .and_then(|v| v.as_i64())
+            .unwrap_or(object_ts);
+        if ts < min_ts {
+            too_old += 1;
+            continue;
+        }
+        record.insert(TIMESTAMP_COL_NAME.to_string(), ts.into());
+        record.insert(
+            "access_log_format".to_string(),
+            source.format.as_str().into(),
+        );
+        record.insert("access_log_object".to_string(), object_url.as_str().into());
+        match batches.last_mut() {
+            Some(batch) if batch.len() < batch_size => batch.push(json::Value::Object(record)),
+            _ => batches.push(vec![json::Value::Object(record)]),
+        }
+    }
+    if too_old > 0 {
+        log::warn!(
+            "[ACCESS_LOGS] skipped {too_old} records of {object_url} older than ZO_INGEST_ALLOWED_UPTO"
+        );
+    }
+
+    let org_id = &cfg.access_log_import.org_id;
+    let stream_name = &cfg.access_log_import.stream_name;
+    for batch in batches {
+        let resp = crate::service::logs::ingest::ingest(
+            0,
+            org_id,
+            stream_name,
+            IngestionRequest::JsonValues(IngestionValueType::AccessLogs, batch),
+            IngestUser::SystemJob(SystemJobType::AccessLogImport),
+            None,
+            false,
+        )
+        .await?;
+        if resp.code != 200 {
+            return Err(anyhow::anyhow!(
+                "failed to ingest {object_url} into {org_id}/{stream_name}: {}",
+                resp.error.unwrap_or_default()
+            ));
+        }
+    }
+    log::debug!("[ACCESS_LOGS] imported {object_url} into {org_id}/{stream_name}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sources() {
+        let sources =
+            parse_sources("cloudfront:s3://logs/cdn/, alb:s3://lb-logs/AWSLogs/123/,json:s3://x")
+                .unwrap();
+        assert_eq!(sources.len(), 3);
+        assert_eq!(sources[0].format, Format::W3c);
+        assert_eq!(sources[0].bucket, "logs");
+        assert_eq!(sources[0].prefix, "cdn");
+        assert_eq!(sources[1].id(), "lb-logs/AWSLogs/123");
+        assert_eq!(sources[2].prefix, "");
+
+        assert!(parse_sources("alb:logs/prefix").is_err());
+        assert!(parse_sources("csv:s3://logs").is_err());
+        assert!(parse_sources("s3://logs").is_err());
+    }
+
+    #[test]
+    fn test_source_matches() {
+        let source = parse_sources("alb:s3://logs/lb").unwrap().remove(0);
+        assert!(source.matches("logs", "lb/2026/01/01/a.log.gz"));
+        assert!(!source.matches("logs", "lb2/a.log.gz"));
+        assert!(!source.matches("other", "lb/a.log.gz"));
+        let source = parse_sources("alb:s3://logs").unwrap().remove(0);
+        assert!(source.matches("logs", "anything.gz"));
+    }
+
+    #[test]
+    fn test_s3_event_objects() {
+        let event = r#"{"Records":[
+            {"eventName":"ObjectCreated:Put","s3":{"bucket":{"name":"logs"},"object":{"key":"cdn/E2K2LNL5N3WR51.2026-01-01-00.a1b2+c%3D.gz"}}},
+            {"eventName":"ObjectRemoved:Delete","s3":{"bucket":{"name":"logs"},"object":{"key":"cdn/old.gz"}}}
+        ]}"#;
+        assert_eq!(
+            s3_event_objects(event),
+            vec![(
+                "logs".to_string(),
+                "cdn/E2K2LNL5N3WR51.2026-01-01-00.a1b2 c=.gz".to_string()
+            )]
+        );
+
+        // wrapped in an SNS notification
+        let sns = json::json!({"Type": "Notification", "Message": event}).to_string();
+        assert_eq!(s3_event_objects(&sns).len(), 1);
+
+        assert!(s3_event_objects(r#"{"Event":"s3:TestEvent"}"#).is_empty());
+        assert!(s3_event_objects("not json").is_empty());
+    }
+
+    #[test]
+    fn test_decode_key() {
+        assert_eq!(decode_key("a%2Fb+c"), "a/b c");
+        assert_eq!(decode_key("100%"), "100%");
+        assert_eq!(decode_key("%zz"), "%zz");
+        assert_eq!(decode_key("caf%C3%A9"), "café");
+    }
+}
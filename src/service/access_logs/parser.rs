@@ -0,0 +1,481 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Parsers for CDN and load balancer access log formats
+
+use std::{io::Read, str::FromStr};
+
+use config::{
+    TIMESTAMP_COL_NAME,
+    utils::{json, time::parse_str_to_timestamp_micros_as_option},
+};
+use flate2::read::MultiGzDecoder;
+
+/// Field order of Application Load Balancer access logs, fields appended by
+/// AWS later on are kept as `field_{n}`
+const ALB_FIELDS: [&str; 30] = [
+    "type",
+    "time",
+    "elb",
+    "client",
+    "target",
+    "request_processing_time",
+    "target_processing_time",
+    "response_processing_time",
+    "elb_status_code",
+    "target_status_code",
+    "received_bytes",
+    "sent_bytes",
+    "request",
+    "user_agent",
+    "ssl_cipher",
+    "ssl_protocol",
+    "target_group_arn",
+    "trace_id",
+    "domain_name",
+    "chosen_cert_arn",
+    "matched_rule_priority",
+    "request_creation_time",
+    "actions_executed",
+    "redirect_url",
+    "error_reason",
+    "target_port_list",
+    "target_status_code_list",
+    "classification",
+    "classification_reason",
+    "conn_trace_id",
+];
+
+/// Field order of Classic Load Balancer access logs
+const ELB_FIELDS: [&str; 15] = [
+    "time",
+    "elb",
+    "client",
+    "backend",
+    "request_processing_time",
+    "backend_processing_time",
+    "response_processing_time",
+    "elb_status_code",
+    "backend_status_code",
+    "received_bytes",
+    "sent_bytes",
+    "request",
+    "user_agent",
+    "ssl_cipher",
+    "ssl_protocol",
+];
+
+/// Field order of the combined log format, the common log format is its
+/// first seven fields
+const CLF_FIELDS: [&str; 9] = [
+    "client_ip",
+    "ident",
+    "user",
+    "time",
+    "request",
+    "status",
+    "bytes",
+    "referer",
+    "user_agent",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// W3C extended log format, CloudFront standard logs use it
+    W3c,
+    /// Application Load Balancer
+    Alb,
+    /// Classic Load Balancer
+    Elb,
+    /// Common or combined log format, the Fastly and nginx default
+    Clf,
+    /// One JSON object per line
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "w3c" | "cloudfront" => Ok(Format::W3c),
+            "alb" => Ok(Format::Alb),
+            "elb" => Ok(Format::Elb),
+            "clf" | "combined" | "fastly" => Ok(Format::Clf),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown access log format: {s}")),
+        }
+    }
+}
+
+impl Format {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Format::W3c => "w3c",
+            Format::Alb => "alb",
+            Format::Elb => "elb",
+            Format::Clf => "clf",
+            Format::Json => "json",
+        }
+    }
+}
+
+/// Parsed records of one log object
+#[derive(Debug, Default)]
+pub struct Parsed {
+    pub records: Vec<json::Map<String, json::Value>>,
+    /// lines that couldn't be parsed
+    pub invalid: usize,
+}
+
+/// Decompresses gzip objects, detected by their magic bytes rather than the
+/// key as not every writer uses a `.gz` suffix
+pub fn decompress(data: &[u8]) -> std::io::Result<String> {
+    if data.starts_with(&[0x1f, 0x8b]) {
+        let mut out = String::new();
+        MultiGzDecoder::new(data).read_to_string(&mut out)?;
+        Ok(out)
+    } else {
+        Ok(String::from_utf8_lossy(data).into_owned())
+    }
+}
+
+pub fn parse(format: Format, content: &str) -> Parsed {
+    let mut parsed = Parsed::default();
+    // W3C logs declare their columns in a `#Fields:` directive
+    let mut w3c_fields: Vec<String> = Vec::new();
+    for line in content.lines() {
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = match format {
+            Format::W3c => {
+                if let Some(directive) = line.strip_prefix('#') {
+                    if let Some(fields) = directive.strip_prefix("Fields:") {
+                        w3c_fields = fields.split_whitespace().map(w3c_field_name).collect();
+                    }
+                    continue;
+                }
+                parse_w3c(&w3c_fields, line)
+            }
+            Format::Alb => parse_positional(&ALB_FIELDS, line),
+            Format::Elb => parse_positional(&ELB_FIELDS, line),
+            Format::Clf => parse_positional(&CLF_FIELDS, strip_syslog_prefix(line)),
+            Format::Json => json::from_str::<json::Map<String, json::Value>>(line).ok(),
+        };
+        match record {
+            Some(mut record) => {
+                set_timestamp(format, &mut record);
+                parsed.records.push(record);
+            }
+            None => parsed.invalid += 1,
+        }
+    }
+    parsed
+}
+
+/// `cs(User-Agent)` becomes `cs_user_agent`, `x-edge-location` becomes
+/// `x_edge_location`
+fn w3c_field_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+        } else if !out.ends_with('_') {
+            out.push('_');
+        }
+    }
+    out.trim_matches('_').to_string()
+}
+
+fn parse_w3c(fields: &[String], line: &str) -> Option<json::Map<String, json::Value>> {
+    if fields.is_empty() {
+        return None;
+    }
+    // CloudFront separates values with tabs, other writers with spaces
+    let values: Vec<&str> = if line.contains('\t') {
+        line.split('\t').collect()
+    } else {
+        line.split(' ').collect()
+    };
+    if values.len() != fields.len() {
+        return None;
+    }
+    let mut record = json::Map::new();
+    for (name, value) in fields.iter().zip(values) {
+        insert_value(&mut record, name, value);
+    }
+    Some(record)
+}
+
+fn parse_positional(fields: &[&str], line: &str) -> Option<json::Map<String, json::Value>> {
+    let values = tokenize(line)?;
+    // anything shorter than the common log format isn't an access log line
+    if values.len() < fields.len().min(7) {
+        return None;
+    }
+    let mut record = json::Map::new();
+    for (i, value) in values.iter().enumerate() {
+        match fields.get(i) {
+            Some(&"client") | Some(&"target") | Some(&"backend") => {
+                let name = fields[i];
+                match value.rsplit_once(':') {
+                    Some((ip, port)) => {
+                        insert_value(&mut record, &format!("{name}_ip"), ip);
+                        insert_value(&mut record, &format!("{name}_port"), port);
+                    }
+                    None => insert_value(&mut record, name, value),
+                }
+            }
+            Some(&"request") => {
+                insert_value(&mut record, "request", value);
+                let mut parts = value.splitn(3, ' ');
+                if let (Some(method), Some(url)) = (parts.next(), parts.next()) {
+                    insert_value(&mut record, "request_method", method);
+                    insert_value(&mut record, "request_url", url);
+                    if let Some(protocol) = parts.next() {
+                        insert_value(&mut record, "request_protocol", protocol);
+                    }
+                }
+            }
+            Some(name) => insert_value(&mut record, name, value),
+            None => insert_value(&mut record, &format!("field_{i}"), value),
+        }
+    }
+    Some(record)
+}
+
+/// Splits on spaces, keeping `"quoted values"` and `[bracketed values]`
+/// together. Returns None for unterminated quotes.
+fn tokenize(line: &str) -> Option<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if_eq(&' ').is_some() {}
+        let Some(c) = chars.next() else {
+            return Some(tokens);
+        };
+        let mut token = String::new();
+        match c {
+            '"' => loop {
+                match chars.next()? {
+                    '\\' => token.push(chars.next()?),
+                    '"' => break,
+                    c => token.push(c),
+                }
+            },
+            '[' => loop {
+                match chars.next()? {
+                    ']' => break,
+                    c => token.push(c),
+                }
+            },
+            c => {
+                token.push(c);
+                while let Some(c) = chars.next_if(|c| *c != ' ') {
+                    token.push(c);
+                }
+            }
+        }
+        tokens.push(token);
+    }
+}
+
+/// Fastly prefixes lines with a syslog header unless the endpoint's message
+/// type is set to blank, e.g. `<134>2026-01-01T00:00:00Z cache-fra1 s3[1]: `
+fn strip_syslog_prefix(line: &str) -> &str {
+    if line.starts_with('<')
+        && let Some((_, rest)) = line.split_once("]: ")
+    {
+        return rest;
+    }
+    line
+}
+
+/// Drops `-` placeholders and stores numbers as numbers
+fn insert_value(record: &mut json::Map<String, json::Value>, name: &str, value: &str) {
+    if value.is_empty() || value == "-" {
+        return;
+    }
+    let value = if let Ok(v) = value.parse::<i64>() {
+        v.into()
+    } else if let Some(v) = value
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite() && value.contains('.'))
+    {
+        v.into()
+    } else {
+        value.into()
+    };
+    record.insert(name.to_string(), value);
+}
+
+/// Sets `_timestamp` from the time the request was served. Records without a
+/// usable time are left alone and get the object's time from the caller.
+fn set_timestamp(format: Format, record: &mut json::Map<String, json::Value>) {
+    let ts = match format {
+        Format::W3c => match (record.get("date"), record.get("time")) {
+            (Some(json::Value::String(date)), Some(json::Value::String(time))) => {
+                parse_str_to_timestamp_micros_as_option(&format!("{date}T{time}Z"))
+            }
+            // CloudFront real-time logs carry epoch seconds with milliseconds
+            _ => record
+                .get("timestamp")
+                .and_then(|v| v.as_f64())
+                .map(|v| (v * 1_000_000.0) as i64),
+        },
+        Format::Alb | Format::Elb => record
+            .get("time")
+            .and_then(|v| v.as_str())
+            .and_then(parse_str_to_timestamp_micros_as_option),
+        Format::Clf => record.get("time").and_then(|v| v.as_str()).and_then(|v| {
+            chrono::DateTime::parse_from_str(v, "%d/%b/%Y:%H:%M:%S %z")
+                .ok()
+                .map(|t| t.timestamp_micros())
+        }),
+        Format::Json => {
+            if record.contains_key(TIMESTAMP_COL_NAME) {
+                return;
+            }
+            ["timestamp", "time", "@timestamp"]
+                .iter()
+                .find_map(|key| record.get(*key).and_then(|v| v.as_str()))
+                .and_then(parse_str_to_timestamp_micros_as_option)
+        }
+    };
+    if let Some(ts) = ts {
+        record.insert(TIMESTAMP_COL_NAME.to_string(), ts.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{Compression, write::GzEncoder};
+
+    use super::*;
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("cloudfront".parse::<Format>().unwrap(), Format::W3c);
+        assert_eq!("ALB".parse::<Format>().unwrap(), Format::Alb);
+        assert_eq!("fastly".parse::<Format>().unwrap(), Format::Clf);
+        assert!("csv".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_parse_cloudfront() {
+        let content = "#Version: 1.0\n#Fields: date time x-edge-location sc-bytes c-ip cs-method cs(Host) cs-uri-stem sc-status cs(Referer) cs(User-Agent) time-taken\n2019-12-04\t21:02:31\tLAX1\t392\t192.0.2.100\tGET\td111111abcdef8.cloudfront.net\t/index.html\t200\t-\tMozilla/5.0\t0.001\n";
+        let parsed = parse(Format::W3c, content);
+        assert_eq!(parsed.invalid, 0);
+        assert_eq!(parsed.records.len(), 1);
+        let r = &parsed.records[0];
+        assert_eq!(r["x_edge_location"], "LAX1");
+        assert_eq!(r["sc_bytes"], 392);
+        assert_eq!(r["cs_host"], "d111111abcdef8.cloudfront.net");
+        assert_eq!(r["cs_user_agent"], "Mozilla/5.0");
+        assert_eq!(r["time_taken"], 0.001);
+        assert!(r.get("cs_referer").is_none());
+        assert_eq!(r["_timestamp"], 1_575_493_351_000_000i64);
+    }
+
+    #[test]
+    fn test_parse_w3c_without_fields() {
+        let parsed = parse(Format::W3c, "2019-12-04 21:02:31 LAX1\n");
+        assert_eq!(parsed.invalid, 1);
+        assert!(parsed.records.is_empty());
+    }
+
+    #[test]
+    fn test_parse_alb() {
+        let line = r#"https 2018-07-02T22:23:00.186641Z app/my-loadbalancer/50dc6c495c0c9188 192.168.131.39:2817 10.0.0.1:80 0.086 0.048 0.037 200 200 0 57 "GET https://www.example.com:443/ HTTP/1.1" "curl/7.46.0" ECDHE-RSA-AES128-GCM-SHA256 TLSv1.2 arn:aws:elasticloadbalancing:us-east-2:123456789012:targetgroup/my-targets/73e2d6bc24d8a067 "Root=1-58337281-1d84f3d73c47ec4e58577259" "www.example.com" "arn:aws:acm:us-east-2:123456789012:certificate/12345678-1234-1234-1234-123456789012" 1 2018-07-02T22:22:48.364000Z "authenticate,forward" "-" "-" "10.0.0.1:80" "200" "-" "-" TID_1234 "extra""#;
+        let parsed = parse(Format::Alb, line);
+        assert_eq!(parsed.invalid, 0);
+        let r = &parsed.records[0];
+        assert_eq!(r["type"], "https");
+        assert_eq!(r["client_ip"], "192.168.131.39");
+        assert_eq!(r["client_port"], 2817);
+        assert_eq!(r["target_ip"], "10.0.0.1");
+        assert_eq!(r["elb_status_code"], 200);
+        assert_eq!(r["request_method"], "GET");
+        assert_eq!(r["request_url"], "https://www.example.com:443/");
+        assert_eq!(r["request_protocol"], "HTTP/1.1");
+        assert_eq!(r["user_agent"], "curl/7.46.0");
+        assert_eq!(r["actions_executed"], "authenticate,forward");
+        assert!(r.get("redirect_url").is_none());
+        assert_eq!(r["conn_trace_id"], "TID_1234");
+        assert_eq!(r["field_30"], "extra");
+        assert_eq!(r["_timestamp"], 1_530_570_180_186_641i64);
+    }
+
+    #[test]
+    fn test_parse_elb() {
+        let line = r#"2015-05-13T23:39:43.945958Z my-loadbalancer 192.168.131.39:2817 10.0.0.1:80 0.000073 0.001048 0.000057 200 200 0 29 "GET http://www.example.com:80/ HTTP/1.1" "curl/7.38.0" - -"#;
+        let r = parse(Format::Elb, line).records.remove(0);
+        assert_eq!(r["backend_ip"], "10.0.0.1");
+        assert_eq!(r["sent_bytes"], 29);
+        assert!(r.get("ssl_cipher").is_none());
+        assert_eq!(r["_timestamp"], 1_431_560_383_945_958i64);
+    }
+
+    #[test]
+    fn test_parse_clf() {
+        let content = "<134>2026-01-01T00:00:00Z cache-fra19120 s3logs[326204]: 203.0.113.5 - - [10/Oct/2000:13:55:36 -0700] \"GET /apache_pb.gif HTTP/1.0\" 200 2326 \"http://www.example.com/start.html\" \"Mozilla/4.08 [en] (Win98; I ;Nav)\"\n198.51.100.7 - frank [10/Oct/2000:13:55:37 -0700] \"POST /login HTTP/1.1\" 302 -\nnot a log line\n";
+        let parsed = parse(Format::Clf, content);
+        assert_eq!(parsed.invalid, 1);
+        assert_eq!(parsed.records.len(), 2);
+        let r = &parsed.records[0];
+        assert_eq!(r["client_ip"], "203.0.113.5");
+        assert_eq!(r["status"], 200);
+        assert_eq!(r["user_agent"], "Mozilla/4.08 [en] (Win98; I ;Nav)");
+        assert_eq!(r["_timestamp"], 971_211_336_000_000i64);
+        let r = &parsed.records[1];
+        assert_eq!(r["user"], "frank");
+        assert!(r.get("bytes").is_none());
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let content = "{\"timestamp\":\"2026-01-01T00:00:00Z\",\"status\":200}\n{broken\n";
+        let parsed = parse(Format::Json, content);
+        assert_eq!(parsed.invalid, 1);
+        assert_eq!(parsed.records[0]["_timestamp"], 1_767_225_600_000_000i64);
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize(r#"a  "b \"c\"" [d e] -"#).unwrap(),
+            vec!["a", "b \"c\"", "d e", "-"]
+        );
+        assert!(tokenize(r#"a "unterminated"#).is_none());
+    }
+
+    #[test]
+    fn test_decompress() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"line 1\n").unwrap();
+        let mut data = encoder.finish().unwrap();
+        // concatenated gzip members
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"line 2\n").unwrap();
+        data.extend(encoder.finish().unwrap());
+        assert_eq!(decompress(&data).unwrap(), "line 1\nline 2\n");
+        assert_eq!(decompress(b"plain").unwrap(), "plain");
+    }
+}
@@ -14,8 +14,9 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     str::FromStr,
+    time::Duration as StdDuration,
 };
 
 use async_trait::async_trait;
@@ -23,14 +24,15 @@ use async_trait::async_trait;
 use axum::http::HeaderMap;
 use chrono::{Duration, Local, TimeZone, Timelike, Utc};
 use config::{
-    SMTP_CLIENT, TIMESTAMP_COL_NAME, get_config,
+    TIMESTAMP_COL_NAME, get_config,
     meta::{
         alerts::{
             FrequencyType, Operator, QueryType, TriggerEvalResults,
             alert::{Alert, AlertListFilter, ListAlertsParams, RowTemplateType},
         },
         destinations::{
-            AwsSns, DestinationType, Email, Endpoint, HTTPType, Module, Template, TemplateType,
+            AwsSns, DestinationType, Endpoint, HTTPType, KAFKA_ALERT_MESSAGE_VERSION,
+            KafkaAlertMessage, KafkaTopic, Module, Template, TemplateType,
         },
         folder::{DEFAULT_FOLDER, Folder, FolderType},
         search::{SearchEventContext, SearchEventType},
@@ -39,7 +41,8 @@ use config::{
     },
     utils::{
         base64,
-        json::{Map, Value},
+        hash::{Sum64, gxhash},
+        json::{self, Map, Value},
     },
 };
 use cron::Schedule;
@@ -49,7 +52,6 @@ use infra::{
     table,
 };
 use itertools::Itertools;
-use lettre::{AsyncTransport, Message, message::MultiPart};
 #[cfg(feature = "enterprise")]
 use o2_enterprise::enterprise::actions::meta::{TriggerActionRequest, TriggerSource};
 #[cfg(feature = "enterprise")]
@@ -57,6 +59,13 @@ use o2_openfga::{
     authorizer::authz::{get_ofga_type, remove_parent_relation, set_parent_relation},
     config::get_config as get_openfga_config,
 };
+use rskafka::{
+    client::{
+        ClientBuilder, Credentials, SaslConfig,
+        partition::{Compression, UnknownTopicHandling},
+    },
+    record::Record,
+};
 use sea_orm::{ConnectionTrait, TransactionTrait};
 use svix_ksuid::Ksuid;
 #[cfg(feature = "enterprise")]
@@ -73,7 +82,10 @@ use crate::{
         utils::auth::{is_ofga_unsupported, remove_ownership, set_ownership},
     },
     service::{
-        alerts::{QueryConditionExt, build_sql, destinations},
+        alerts::{
+            QueryConditionExt, build_sql, destinations, egress, email, jira, servicenow,
+            vrl_template::{self, NotificationContext},
+        },
         db, folders,
         search::sql::RE_ONLY_SELECT,
         short_url,
@@ -137,6 +149,9 @@ pub enum AlertError {
     #[error("Realtime alert should use Custom query type")]
     RealtimeMissingCustomQuery,
 
+    #[error("Realtime alert can't query the dataset {dataset}, only a stream")]
+    RealtimeDataset { dataset: String },
+
     #[error("Alert with SQL mode should have a query")]
     SqlMissingQuery,
 
@@ -373,24 +388,57 @@ async fn prepare_alert(
 
     // before saving alert check column type to decide numeric condition
     let schema = infra::schema::get(org_id, stream_name, stream_type).await?;
-    if stream_name.is_empty() || schema.fields().is_empty() {
-        return Err(AlertError::StreamNotFound {
-            stream_name: stream_name.to_owned(),
-        });
-    }
-
-    // Alerts must follow the max_query_range of the stream as set in the schema
-    if let Some(settings) = unwrap_stream_settings(&schema) {
-        let max_query_range = settings.max_query_range;
-        if max_query_range > 0
-            && !alert.is_real_time
-            && alert.trigger_condition.period > max_query_range * 60
-        {
-            return Err(AlertError::PeriodExceedsMaxQueryRange {
-                max_query_range_hours: max_query_range,
+    let dataset = if schema.fields().is_empty() {
+        crate::service::dataset::get(org_id, stream_type, stream_name)
+    } else {
+        None
+    };
+    if let Some(dataset) = dataset {
+        // a dataset has no schema, the alert follows the max_query_range of
+        // each of its streams. The realtime alerts are evaluated on the
+        // records ingested into a stream, so they can't query a dataset
+        if alert.is_real_time {
+            return Err(AlertError::RealtimeDataset {
+                dataset: stream_name.to_owned(),
+            });
+        }
+        let streams = crate::service::dataset::member_streams(org_id, &dataset).await;
+        if streams.is_empty() {
+            return Err(AlertError::StreamNotFound {
                 stream_name: stream_name.to_owned(),
             });
         }
+        for stream in streams.iter() {
+            if let Some(settings) = infra::schema::get_settings(org_id, stream, stream_type).await {
+                let max_query_range = settings.max_query_range;
+                if max_query_range > 0 && alert.trigger_condition.period > max_query_range * 60 {
+                    return Err(AlertError::PeriodExceedsMaxQueryRange {
+                        max_query_range_hours: max_query_range,
+                        stream_name: stream.to_owned(),
+                    });
+                }
+            }
+        }
+    } else {
+        if stream_name.is_empty() || schema.fields().is_empty() {
+            return Err(AlertError::StreamNotFound {
+                stream_name: stream_name.to_owned(),
+            });
+        }
+
+        // Alerts must follow the max_query_range of the stream as set in the schema
+        if let Some(settings) = unwrap_stream_settings(&schema) {
+            let max_query_range = settings.max_query_range;
+            if max_query_range > 0
+                && !alert.is_real_time
+                && alert.trigger_condition.period > max_query_range * 60
+            {
+                return Err(AlertError::PeriodExceedsMaxQueryRange {
+                    max_query_range_hours: max_query_range,
+                    stream_name: stream_name.to_owned(),
+                });
+            }
+        }
     }
 
     if alert.is_real_time && alert.query_condition.query_type != QueryType::Custom {
@@ -1035,6 +1083,7 @@ impl AlertExt for Alert {
             None
         };
 
+        let empty_template = Template::default();
         for dest_name in self.destinations.iter() {
             let (dest, dest_template) =
                 destinations::get_with_template(&self.org_id, dest_name).await?;
@@ -1051,6 +1100,8 @@ impl AlertExt for Alert {
             let template = match (&alert_template, &dest_template) {
                 (Some(alert_tpl), _) => alert_tpl,
                 (None, Some(dest_tpl)) => dest_tpl,
+                // the VRL template renders the whole request
+                (None, None) if vrl_template::is_rendered(&destination_type) => &empty_template,
                 (None, None) => {
                     no_of_error += 1;
                     err_message = format!(
@@ -1069,7 +1120,7 @@ impl AlertExt for Alert {
                 }
             };
 
-            match send_notification(
+            let result = send_notification(
                 self,
                 &destination_type,
                 template,
@@ -1078,8 +1129,11 @@ impl AlertExt for Alert {
                 start_time,
                 evaluation_timestamp,
             )
-            .await
-            {
+            .await;
+            if let DestinationType::Email(email) = &destination_type {
+                email::record_delivery(&self.org_id, &dest.name, &self.name, email, &result).await;
+            }
+            match result {
                 Ok(resp) => {
                     success_message =
                         format!("{success_message} destination {} {resp};", dest.name);
@@ -1173,12 +1227,166 @@ async fn send_notification(
     };
 
     match dest_type {
-        DestinationType::Http(endpoint) => send_http_notification(endpoint, msg).await,
-        DestinationType::Email(email) => send_email_notification(&email_subject, email, msg).await,
+        DestinationType::Http(endpoint) => match &endpoint.vrl_template {
+            Some(program) => {
+                let context = NotificationContext {
+                    org_name: &org_name,
+                    alert,
+                    rows,
+                    start_time,
+                    end_time: rows_end_time,
+                    evaluation_timestamp,
+                    message: &msg,
+                    headers: endpoint.headers.as_ref(),
+                };
+                let (body, headers) = vrl_template::render(&alert.org_id, program, &context)?;
+                let endpoint = Endpoint {
+                    headers: Some(headers),
+                    ..endpoint.clone()
+                };
+                send_http_notification(&endpoint, body).await
+            }
+            None => send_http_notification(endpoint, msg).await,
+        },
+        DestinationType::Email(email) => {
+            email::send_notification(&alert.org_id, &alert.name, &email_subject, email, rows, msg)
+                .await
+        }
         DestinationType::Sns(aws_sns) => send_sns_notification(&alert.name, aws_sns, msg).await,
+        DestinationType::Kafka(kafka) => {
+            let message = KafkaAlertMessage {
+                version: KAFKA_ALERT_MESSAGE_VERSION,
+                org_id: alert.org_id.clone(),
+                alert_id: alert.id.map(|id| id.to_string()),
+                alert_name: alert.name.clone(),
+                stream_type: alert.stream_type.to_string(),
+                stream_name: alert.stream_name.clone(),
+                is_real_time: alert.is_real_time,
+                evaluation_timestamp,
+                start_time,
+                end_time: rows_end_time,
+                rows: rows.iter().cloned().map(Value::Object).collect(),
+                message: msg,
+            };
+            send_kafka_notification(kafka, message).await
+        }
+        DestinationType::Jira(jira) => jira::send_notification(alert, jira, rows, msg).await,
+        DestinationType::ServiceNow(servicenow) => {
+            servicenow::send_notification(alert, servicenow, rows, msg).await
+        }
+    }
+}
+
+/// Resolves the incidents opened by the ServiceNow destinations of the alert
+/// which close them on resolve, once the alert is not satisfied anymore, the
+/// errors are only logged
+pub async fn resolve_incidents(alert: &Alert) {
+    for dest_name in alert.destinations.iter() {
+        let dest = match destinations::get(&alert.org_id, dest_name).await {
+            Ok(dest) => dest,
+            Err(e) => {
+                log::error!(
+                    "Error getting destination {dest_name} to resolve the incident of alert {}/{}: {e}",
+                    alert.org_id,
+                    alert.name
+                );
+                continue;
+            }
+        };
+        let Module::Alert {
+            destination_type: DestinationType::ServiceNow(servicenow),
+            ..
+        } = dest.module
+        else {
+            continue;
+        };
+        if !servicenow.close_on_resolve {
+            continue;
+        }
+        match servicenow::resolve(alert, &servicenow).await {
+            Ok(resp) => log::info!(
+                "Alert {}/{} resolved, destination {dest_name} {resp}",
+                alert.org_id,
+                alert.name
+            ),
+            Err(e) => log::error!(
+                "Error resolving the incident of alert {}/{} for destination {dest_name}: {e}",
+                alert.org_id,
+                alert.name
+            ),
+        }
     }
 }
 
+/// Renders the request of an HTTP destination with a VRL template for a sample
+/// alert of the organization, the rows default to a sample row
+pub async fn preview_notification(
+    org_id: &str,
+    endpoint: &Endpoint,
+    template: Option<&Template>,
+    context_attributes: Option<hashbrown::HashMap<String, String>>,
+    mut rows: Vec<Map<String, Value>>,
+) -> Result<(String, hashbrown::HashMap<String, String>), anyhow::Error> {
+    let Some(program) = &endpoint.vrl_template else {
+        return Err(anyhow::anyhow!("The destination has no VRL template"));
+    };
+    let org_name = if let Some(org) = ORGANIZATIONS.read().await.get(org_id) {
+        org.name.clone()
+    } else {
+        org_id.to_string()
+    };
+    let alert = Alert {
+        org_id: org_id.to_string(),
+        name: "sample_alert".to_string(),
+        stream_type: StreamType::Logs,
+        stream_name: "default".to_string(),
+        context_attributes,
+        ..Default::default()
+    };
+    let now = Utc::now().timestamp_micros();
+    let start_time = now
+        - Duration::minutes(alert.trigger_condition.period)
+            .num_microseconds()
+            .unwrap();
+    if rows.is_empty() {
+        let mut row = Map::new();
+        row.insert(TIMESTAMP_COL_NAME.to_string(), Value::from(now));
+        row.insert("level".to_string(), Value::from("error"));
+        row.insert("message".to_string(), Value::from("sample log message"));
+        rows.push(row);
+    }
+    let msg = match template {
+        Some(template) => {
+            process_dest_template(
+                &org_name,
+                &template.body,
+                &alert,
+                &rows,
+                &[Value::String("".to_string())],
+                ProcessTemplateOptions {
+                    rows_end_time: now,
+                    start_time: Some(start_time),
+                    evaluation_timestamp: now,
+                    is_email: false,
+                },
+            )
+            .await
+        }
+        None => String::new(),
+    };
+    let context = NotificationContext {
+        org_name: &org_name,
+        alert: &alert,
+        rows: &rows,
+        start_time: Some(start_time),
+        end_time: now,
+        evaluation_timestamp: now,
+        message: &msg,
+        headers: endpoint.headers.as_ref(),
+    };
+    vrl_template::render(org_id, program, &context)
+}
+
 async fn send_http_notification(endpoint: &Endpoint, msg: String) -> Result<String, anyhow::Error> {
     #[cfg(feature = "enterprise")]
     let msg = if endpoint.action_id.is_some() {
@@ -1211,13 +1419,10 @@ async fn send_http_notification(endpoint: &Endpoint, msg: String) -> Result<Stri
         msg
     };
 
-    let client = if endpoint.skip_tls_verify {
-        reqwest::Client::builder()
-            .danger_accept_invalid_certs(true)
-            .build()?
-    } else {
-        reqwest::Client::new()
-    };
+    let client = egress::client_builder(&endpoint.url, endpoint.proxy.as_deref())
+        .await?
+        .danger_accept_invalid_certs(endpoint.skip_tls_verify)
+        .build()?;
     let url = url::Url::parse(&endpoint.url)?;
     let mut req = match endpoint.method {
         HTTPType::POST => client.post(url),
@@ -1267,40 +1472,6 @@ async fn send_http_notification(endpoint: &Endpoint, msg: String) -> Result<Stri
     Ok(format!("sent status: {resp_status}, body: {resp_body}"))
 }
 
-async fn send_email_notification(
-    email_subject: &str,
-    email: &Email,
-    msg: String,
-) -> Result<String, anyhow::Error> {
-    let cfg = get_config();
-    if !cfg.smtp.smtp_enabled {
-        return Err(anyhow::anyhow!("SMTP configuration not enabled"));
-    }
-
-    let recipients = email.recipients.clone();
-    let mut email = Message::builder()
-        .from(cfg.smtp.smtp_from_email.parse()?)
-        .subject(email_subject.to_string());
-
-    for recipient in recipients {
-        email = email.to(recipient.parse()?);
-    }
-
-    if !cfg.smtp.smtp_reply_to.is_empty() {
-        email = email.reply_to(cfg.smtp.smtp_reply_to.parse()?);
-    }
-
-    let email = email
-        .multipart(MultiPart::alternative_plain_html(msg.clone(), msg))
-        .unwrap();
-
-    // Send the email
-    match SMTP_CLIENT.as_ref().unwrap().send(email).await {
-        Ok(resp) => Ok(format!("sent email response code: {}", resp.code())),
-        Err(e) => Err(anyhow::anyhow!("Error sending email: {e}")),
-    }
-}
-
 async fn send_sns_notification(
     alert_name: &str,
     aws_sns: &AwsSns,
@@ -1333,6 +1504,95 @@ async fn send_sns_notification(
     }
 }
 
+const KAFKA_PUBLISH_TIMEOUT: StdDuration = StdDuration::from_secs(30);
+
+/// Publishes the notification to the topic, a client is connected for every
+/// notification as alerts fire too rarely to keep connections to every
+/// destination
+async fn send_kafka_notification(
+    kafka: &KafkaTopic,
+    message: KafkaAlertMessage,
+) -> Result<String, anyhow::Error> {
+    egress::check_brokers(&kafka.brokers).await?;
+    let brokers = kafka
+        .brokers
+        .split(',')
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect::<Vec<_>>();
+    let mut builder = ClientBuilder::new(brokers);
+    if !kafka.sasl_username.is_empty() {
+        builder = builder.sasl_config(SaslConfig::Plain(Credentials::new(
+            kafka.sasl_username.clone(),
+            kafka.sasl_password.clone(),
+        )));
+    }
+    if kafka.tls_enabled {
+        builder = builder.tls_config(crate::service::ingestion::kafka::tls_config()?);
+    }
+    let key = kafka_message_key(&message);
+    let value = json::to_vec(&message)?;
+
+    let publish = async move {
+        let client = builder.build().await?;
+        let topics = client.list_topics().await?;
+        let partitions = topics
+            .iter()
+            .find(|t| t.name == kafka.topic)
+            .map(|t| t.partitions.keys().copied().collect::<Vec<_>>())
+            .unwrap_or_default();
+        let Some(partition) = kafka_partition(&key, &partitions) else {
+            return Err(anyhow::anyhow!(
+                "kafka topic {} does not exist",
+                kafka.topic
+            ));
+        };
+        let partition_client = client
+            .partition_client(kafka.topic.as_str(), partition, UnknownTopicHandling::Error)
+            .await?;
+        let record = Record {
+            key: Some(key.into_bytes()),
+            value: Some(value),
+            headers: BTreeMap::new(),
+            timestamp: Utc::now(),
+        };
+        let offsets = partition_client
+            .produce(vec![record], Compression::NoCompression)
+            .await?;
+        Ok((partition, offsets))
+    };
+    match tokio::time::timeout(KAFKA_PUBLISH_TIMEOUT, publish).await {
+        Ok(Ok((partition, offsets))) => Ok(format!(
+            "sent to kafka topic {} partition {partition} offset {:?}",
+            kafka.topic,
+            offsets.first()
+        )),
+        Ok(Err(e)) => Err(anyhow::anyhow!("Error sending Kafka notification: {e}")),
+        Err(_) => Err(anyhow::anyhow!(
+            "Error sending Kafka notification: timed out after {}s",
+            KAFKA_PUBLISH_TIMEOUT.as_secs()
+        )),
+    }
+}
+
+/// The notifications of an alert go to the same partition, so they keep
+/// their order
+fn kafka_message_key(message: &KafkaAlertMessage) -> String {
+    format!(
+        "{}/{}",
+        message.org_id,
+        message.alert_id.as_deref().unwrap_or(&message.alert_name)
+    )
+}
+
+fn kafka_partition(key: &str, partitions: &[i32]) -> Option<i32> {
+    if partitions.is_empty() {
+        return None;
+    }
+    let i = gxhash::new().sum64(key) % partitions.len() as u64;
+    Some(partitions[i as usize])
+}
+
 fn process_row_template(
     org_name: &str,
     tpl: &String,
@@ -2045,6 +2305,23 @@ mod tests {
     use super::*;
     use crate::service::alerts::{Condition, build_expr};
 
+    #[test]
+    fn test_kafka_partition() {
+        let message = KafkaAlertMessage {
+            org_id: "default".to_string(),
+            alert_name: "high_latency".to_string(),
+            ..Default::default()
+        };
+        let key = kafka_message_key(&message);
+        assert_eq!(key, "default/high_latency");
+        assert_eq!(kafka_partition(&key, &[]), None);
+        assert_eq!(kafka_partition(&key, &[3]), Some(3));
+        let partitions = [0, 1, 2, 3];
+        let partition = kafka_partition(&key, &partitions).unwrap();
+        assert!(partitions.contains(&partition));
+        assert_eq!(kafka_partition(&key, &partitions), Some(partition));
+    }
+
     #[test]
     fn test_format_variable_value() {
         // Test common control characters
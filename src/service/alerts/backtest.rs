@@ -0,0 +1,320 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Alert backtests
+//!
+//! Replays a scheduled alert over a past time range: the alert is evaluated
+//! on every window it would have run on, one row per window is written to the
+//! `_alert_backtest` stream of the org, and the backtest keeps the windows
+//! which fired. A fired window silences the alert like it does when the
+//! alert runs, so the fired windows are the notifications it would have sent.
+//! The backtest is a trigger of the scheduler which evaluates a bounded
+//! number of windows per run, so long ranges don't hold an alert manager and
+//! aren't lost when a node restarts.
+
+use std::str::FromStr;
+
+use chrono::Duration;
+use config::{
+    TIMESTAMP_COL_NAME, get_config, ider,
+    meta::{
+        alerts::{
+            TriggerCondition,
+            alert::Alert,
+            backtest::{
+                ALERT_BACKTEST_STREAM, AlertBacktest, AlertBacktestRequest, BacktestStatus,
+                MAX_FIRED_WINDOWS,
+            },
+        },
+        stream::StreamType,
+    },
+    utils::{json, time::now_micros},
+};
+use proto::cluster_rpc;
+use svix_ksuid::Ksuid;
+
+use crate::service::{
+    alerts::alert::{AlertExt, get_by_id_db},
+    db,
+    ingestion::ingestion_service,
+};
+
+/// Windows evaluated by one run of the trigger
+const WINDOWS_PER_RUN: usize = 50;
+
+#[derive(Debug, thiserror::Error)]
+pub enum BacktestError {
+    #[error("{0}")]
+    InvalidBacktest(String),
+
+    #[error("Alert not found")]
+    AlertNotFound,
+
+    #[error("Alert backtest not found")]
+    BacktestNotFound,
+
+    #[error(transparent)]
+    InfraError(#[from] infra::errors::Error),
+}
+
+pub async fn create(
+    org_id: &str,
+    req: AlertBacktestRequest,
+    user_email: &str,
+) -> Result<AlertBacktest, BacktestError> {
+    let now = now_micros();
+    req.validate(now).map_err(BacktestError::InvalidBacktest)?;
+    let mut alert = match (req.alert_id, req.alert) {
+        (Some(alert_id), _) => {
+            let alert_id = Ksuid::from_str(&alert_id)
+                .map_err(|_| BacktestError::InvalidBacktest("invalid alert_id".to_string()))?;
+            get_by_id_db(org_id, alert_id)
+                .await
+                .map_err(|_| BacktestError::AlertNotFound)?
+        }
+        (None, Some(alert)) => alert,
+        (None, None) => unreachable!("validated"),
+    };
+    alert.org_id = org_id.to_string();
+    if alert.is_real_time {
+        return Err(BacktestError::InvalidBacktest(
+            "realtime alerts can't be backtested".to_string(),
+        ));
+    }
+    let windows = count_windows(
+        &alert,
+        req.start_time,
+        req.end_time,
+        get_config().limit.alert_backtest_max_windows,
+    )
+    .map_err(|e| BacktestError::InvalidBacktest(e.to_string()))?;
+    if windows == 0 {
+        return Err(BacktestError::InvalidBacktest(
+            "the alert doesn't run in the time range".to_string(),
+        ));
+    }
+
+    let backtest = AlertBacktest {
+        id: ider::uuid(),
+        alert,
+        start_time: req.start_time,
+        end_time: req.end_time,
+        status: BacktestStatus::Running,
+        position: req.start_time,
+        windows_evaluated: 0,
+        windows_fired: 0,
+        last_fired: false,
+        fired_windows: vec![],
+        error: None,
+        created_by: user_email.to_string(),
+        created_at: now,
+        updated_at: now,
+    };
+    db::alerts::backtest::set(org_id, &backtest).await?;
+    db::scheduler::push(db::scheduler::Trigger {
+        org: org_id.to_string(),
+        module: db::scheduler::TriggerModule::AlertBacktest,
+        module_key: backtest.id.clone(),
+        next_run_at: now,
+        ..Default::default()
+    })
+    .await?;
+    Ok(backtest)
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<AlertBacktest, BacktestError> {
+    db::alerts::backtest::get(org_id, id)
+        .await
+        .map_err(|_| BacktestError::BacktestNotFound)
+}
+
+/// End of the window the alert evaluates after the one ending at `after`,
+/// the alert is silenced after a window which fired
+fn next_window_end(alert: &Alert, after: i64, fired: bool) -> Result<i64, anyhow::Error> {
+    // the tolerance spreads the live runs, it has no use for a replay
+    let condition = TriggerCondition {
+        tolerance_in_secs: None,
+        ..alert.trigger_condition.clone()
+    };
+    let next = condition.get_next_trigger_time(true, alert.tz_offset, fired, Some(after))?;
+    if next <= after {
+        return Err(anyhow::anyhow!("the alert schedule doesn't move forward"));
+    }
+    Ok(next)
+}
+
+/// Number of windows ending in the time range, counting stops after `max`
+/// and errors
+fn count_windows(
+    alert: &Alert,
+    start_time: i64,
+    end_time: i64,
+    max: usize,
+) -> Result<usize, anyhow::Error> {
+    let mut count = 0;
+    let mut position = start_time;
+    loop {
+        position = next_window_end(alert, position, false)?;
+        if position > end_time {
+            return Ok(count);
+        }
+        count += 1;
+        if count > max {
+            return Err(anyhow::anyhow!(
+                "the time range has more than {max} evaluations of the alert"
+            ));
+        }
+    }
+}
+
+/// Evaluates the next windows of the backtest and writes them to the stream,
+/// returns whether the backtest reached its end time
+pub async fn run(trace_id: &str, backtest: &mut AlertBacktest) -> Result<bool, anyhow::Error> {
+    let alert = &backtest.alert;
+    let period = Duration::try_minutes(alert.trigger_condition.period)
+        .unwrap()
+        .num_microseconds()
+        .unwrap();
+    let alert_id = alert.id.map(|id| id.to_string()).unwrap_or_default();
+    let mut records = Vec::with_capacity(WINDOWS_PER_RUN);
+    let mut done = false;
+    while records.len() < WINDOWS_PER_RUN {
+        let window_end = next_window_end(alert, backtest.position, backtest.last_fired)?;
+        if window_end > backtest.end_time {
+            done = true;
+            break;
+        }
+        let window_start = window_end - period;
+        // a window which fails fails the run, the windows evaluated before
+        // are kept and the retry starts after them
+        let rows = match alert
+            .evaluate(
+                None,
+                (Some(window_start), window_end),
+                Some(trace_id.to_string()),
+            )
+            .await
+        {
+            Ok(ret) => ret.data.map(|v| v.len()).unwrap_or_default(),
+            Err(e) => {
+                if !records.is_empty() {
+                    ingest(&alert.org_id, records).await?;
+                }
+                return Err(e);
+            }
+        };
+        let fired = rows > 0;
+        backtest.position = window_end;
+        backtest.last_fired = fired;
+        backtest.windows_evaluated += 1;
+        if fired {
+            backtest.windows_fired += 1;
+            if backtest.fired_windows.len() < MAX_FIRED_WINDOWS {
+                backtest.fired_windows.push(window_end);
+            }
+        }
+        records.push(json::json!({
+            TIMESTAMP_COL_NAME: window_end,
+            "backtest_id": backtest.id,
+            "alert_id": alert_id,
+            "alert_name": alert.name,
+            "stream_type": alert.stream_type.to_string(),
+            "stream_name": alert.stream_name,
+            "window_start": window_start,
+            "window_end": window_end,
+            "fired": fired,
+            "rows": rows,
+        }));
+    }
+    if !records.is_empty() {
+        ingest(&alert.org_id, records).await?;
+    }
+    backtest.updated_at = now_micros();
+    if done {
+        backtest.status = BacktestStatus::Completed;
+    }
+    Ok(done)
+}
+
+async fn ingest(org_id: &str, records: Vec<json::Value>) -> Result<(), anyhow::Error> {
+    let req = cluster_rpc::IngestionRequest {
+        org_id: org_id.to_string(),
+        stream_name: ALERT_BACKTEST_STREAM.to_string(),
+        stream_type: StreamType::Logs.to_string(),
+        data: Some(cluster_rpc::IngestionData::from(records)),
+        ingestion_type: Some(cluster_rpc::IngestionType::Json.into()),
+        metadata: None,
+    };
+    match ingestion_service::ingest(req).await {
+        Ok(resp) if resp.status_code == 200 => Ok(()),
+        Ok(resp) => Err(anyhow::anyhow!(resp.message)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use config::meta::alerts::FrequencyType;
+
+    use super::*;
+
+    const MINUTE: i64 = 60_000_000;
+
+    fn alert(trigger_condition: TriggerCondition) -> Alert {
+        Alert {
+            trigger_condition,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_next_window_end() {
+        let alert = alert(TriggerCondition {
+            period: 10,
+            frequency: 300,
+            silence: 30,
+            ..Default::default()
+        });
+        assert_eq!(next_window_end(&alert, 0, false).unwrap(), 5 * MINUTE);
+        assert_eq!(next_window_end(&alert, 0, true).unwrap(), 30 * MINUTE);
+
+        let hourly = self::alert(TriggerCondition {
+            period: 60,
+            frequency_type: FrequencyType::Cron,
+            cron: "0 0 * * * *".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(
+            next_window_end(&hourly, 10 * MINUTE, false).unwrap(),
+            60 * MINUTE
+        );
+
+        let stuck = self::alert(TriggerCondition::default());
+        assert!(next_window_end(&stuck, 0, false).is_err());
+    }
+
+    #[test]
+    fn test_count_windows() {
+        let alert = alert(TriggerCondition {
+            period: 10,
+            frequency: 600,
+            ..Default::default()
+        });
+        let day = 24 * 60 * MINUTE;
+        assert_eq!(count_windows(&alert, 0, day, 1000).unwrap(), 144);
+        assert_eq!(count_windows(&alert, 0, 5 * MINUTE, 1000).unwrap(), 0);
+        assert!(count_windows(&alert, 0, day, 100).is_err());
+    }
+}
@@ -21,10 +21,13 @@ use crate::{
         meta::authz::Authz,
         utils::auth::{is_ofga_unsupported, remove_ownership, set_ownership},
     },
-    service::db::{
-        self,
-        alerts::{destinations::DestinationError, templates::TemplateError},
-        user,
+    service::{
+        alerts::{egress, email, vrl_template},
+        db::{
+            self,
+            alerts::{destinations::DestinationError, templates::TemplateError},
+            user,
+        },
     },
 };
 
@@ -139,7 +142,7 @@ pub async fn save(
                 if email.recipients.is_empty() {
                     return Err(DestinationError::EmptyEmail);
                 }
-                if !config::get_config().smtp.smtp_enabled {
+                if !email::is_enabled(&destination.org_id).await {
                     return Err(DestinationError::SMTPUnavailable);
                 }
                 let mut lowercase_emails = vec![];
@@ -158,12 +161,48 @@ pub async fn save(
                 if endpoint.url.is_empty() {
                     return Err(DestinationError::EmptyUrl);
                 }
+                if let Some(program) = &endpoint.vrl_template {
+                    vrl_template::validate(&destination.org_id, program)
+                        .map_err(DestinationError::InvalidVrlTemplate)?;
+                }
             }
             DestinationType::Sns(aws_sns) => {
                 if aws_sns.sns_topic_arn.is_empty() || aws_sns.aws_region.is_empty() {
                     return Err(DestinationError::InvalidSns);
                 }
             }
+            DestinationType::Kafka(kafka) => {
+                kafka.brokers = kafka.brokers.trim().to_string();
+                kafka.topic = kafka.topic.trim().to_string();
+                if kafka.brokers.is_empty() || kafka.topic.is_empty() {
+                    return Err(DestinationError::InvalidKafka);
+                }
+            }
+            // the API token is checked once the current one is kept
+            DestinationType::Jira(jira) => {
+                jira.url = jira.url.trim().trim_end_matches('/').to_string();
+                jira.project_key = jira.project_key.trim().to_string();
+                jira.issue_type = jira.issue_type.trim().to_string();
+                if url::Url::parse(&jira.url).is_err()
+                    || jira.username.is_empty()
+                    || jira.project_key.is_empty()
+                    || jira.issue_type.is_empty()
+                {
+                    return Err(DestinationError::InvalidJira);
+                }
+            }
+            // the password is checked once the current one is kept
+            DestinationType::ServiceNow(servicenow) => {
+                servicenow.url = servicenow.url.trim().trim_end_matches('/').to_string();
+                servicenow.table = servicenow.table.trim().to_string();
+                servicenow.severity_label = servicenow.severity_label.trim().to_string();
+                if url::Url::parse(&servicenow.url).is_err()
+                    || servicenow.username.is_empty()
+                    || (servicenow.import_set && servicenow.table.is_empty())
+                {
+                    return Err(DestinationError::InvalidServiceNow);
+                }
+            }
         },
         Module::Pipeline { endpoint, .. } => {
             if endpoint.url.is_empty() {
@@ -171,6 +210,14 @@ pub async fn save(
             }
         }
     }
+    if let Module::Alert {
+        destination_type, ..
+    } = &destination.module
+    {
+        egress::check(destination_type)
+            .await
+            .map_err(|e| DestinationError::EgressNotAllowed(e.to_string()))?;
+    }
 
     if !name.is_empty() {
         destination.name = name.to_string();
@@ -184,10 +231,11 @@ pub async fn save(
     }
 
     match db::alerts::destinations::get(&destination.org_id, &destination.name).await {
-        Ok(_) => {
+        Ok(old) => {
             if create {
                 return Err(DestinationError::AlreadyExists);
             }
+            keep_secrets(&mut destination, old)?;
         }
         Err(_) => {
             if !create {
@@ -195,6 +243,22 @@ pub async fn save(
             }
         }
     }
+    if let Module::Alert {
+        destination_type: DestinationType::Jira(jira),
+        ..
+    } = &destination.module
+        && jira.api_token.is_empty()
+    {
+        return Err(DestinationError::InvalidJira);
+    }
+    if let Module::Alert {
+        destination_type: DestinationType::ServiceNow(servicenow),
+        ..
+    } = &destination.module
+        && servicenow.password.is_empty()
+    {
+        return Err(DestinationError::InvalidServiceNow);
+    }
 
     // For prebuilt destinations, ensure template exists before saving destination
     // This implements template reuse: multiple destinations can share the same prebuilt template
@@ -219,6 +283,9 @@ pub async fn save(
                 }
             }
             DestinationType::Sns(_) => None, // SNS doesn't have prebuilt templates yet
+            DestinationType::Kafka(_)
+            | DestinationType::Jira(_)
+            | DestinationType::ServiceNow(_) => None,
         };
 
         // If it's a prebuilt type and doesn't have a custom template, ensure prebuilt template
@@ -243,8 +310,13 @@ pub async fn save(
     }
 
     // Validate that alert destinations have a template
-    // Templates are REQUIRED for alert destinations to format alert messages
-    if let Module::Alert { template, .. } = &destination.module
+    // Templates are REQUIRED for alert destinations to format alert messages, but the ones
+    // rendered by a VRL template
+    if let Module::Alert {
+        template,
+        destination_type,
+    } = &destination.module
+        && !vrl_template::is_rendered(destination_type)
         && (template.is_none() || template.as_ref().is_some_and(|t| t.is_empty()))
     {
         return Err(DestinationError::TemplateNotFound);
@@ -257,6 +329,54 @@ pub async fn save(
     Ok(saved)
 }
 
+/// The SASL password of a Kafka destination, the API token of a Jira one and
+/// the password of a ServiceNow one are never returned, an update without one
+/// keeps the current one as long as it is still sent to the same server and user
+fn keep_secrets(destination: &mut Destination, old: Destination) -> Result<(), DestinationError> {
+    let (
+        Module::Alert {
+            destination_type, ..
+        },
+        Module::Alert {
+            destination_type: old,
+            ..
+        },
+    ) = (&mut destination.module, old.module)
+    else {
+        return Ok(());
+    };
+    match (destination_type, old) {
+        (DestinationType::Kafka(kafka), DestinationType::Kafka(old))
+            if kafka.sasl_password.is_empty() && !old.sasl_password.is_empty() =>
+        {
+            if kafka.brokers != old.brokers || kafka.sasl_username != old.sasl_username {
+                return Err(DestinationError::SecretRequired(
+                    "SASL password".to_string(),
+                ));
+            }
+            kafka.sasl_password = old.sasl_password;
+        }
+        (DestinationType::Jira(jira), DestinationType::Jira(old))
+            if jira.api_token.is_empty() && !old.api_token.is_empty() =>
+        {
+            if jira.url != old.url || jira.username != old.username {
+                return Err(DestinationError::SecretRequired("API token".to_string()));
+            }
+            jira.api_token = old.api_token;
+        }
+        (DestinationType::ServiceNow(servicenow), DestinationType::ServiceNow(old))
+            if servicenow.password.is_empty() && !old.password.is_empty() =>
+        {
+            if servicenow.url != old.url || servicenow.username != old.username {
+                return Err(DestinationError::SecretRequired("password".to_string()));
+            }
+            servicenow.password = old.password;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
 pub async fn get(org_id: &str, name: &str) -> Result<Destination, DestinationError> {
     db::alerts::destinations::get(org_id, name).await
 }
@@ -326,6 +446,7 @@ pub async fn delete(org_id: &str, name: &str) -> Result<(), DestinationError> {
     }
 
     db::alerts::destinations::delete(org_id, name).await?;
+    email::delete_deliveries(org_id, name).await;
     remove_ownership(org_id, "destinations", Authz::new(name)).await;
     Ok(())
 }
@@ -689,4 +810,51 @@ mod tests {
             "Should return TemplateNotFound error for empty template"
         );
     }
+
+    fn jira_destination(url: &str, api_token: &str) -> Destination {
+        Destination {
+            id: None,
+            org_id: "default".to_string(),
+            name: "jira".to_string(),
+            module: Module::Alert {
+                template: None,
+                destination_type: DestinationType::Jira(config::meta::destinations::Jira {
+                    url: url.to_string(),
+                    username: "bot@example.com".to_string(),
+                    api_token: api_token.to_string(),
+                    ..Default::default()
+                }),
+            },
+        }
+    }
+
+    #[test]
+    fn test_keep_secrets() {
+        let api_token = |destination: &Destination| match &destination.module {
+            Module::Alert {
+                destination_type: DestinationType::Jira(jira),
+                ..
+            } => jira.api_token.clone(),
+            _ => unreachable!(),
+        };
+        let old = jira_destination("https://example.atlassian.net", "secret");
+
+        let mut destination = jira_destination("https://example.atlassian.net", "");
+        keep_secrets(&mut destination, old.clone()).unwrap();
+        assert_eq!(api_token(&destination), "secret");
+
+        let mut destination = jira_destination("https://example.atlassian.net", "new");
+        keep_secrets(&mut destination, old.clone()).unwrap();
+        assert_eq!(api_token(&destination), "new");
+
+        let mut destination = jira_destination("https://attacker.example.com", "");
+        assert!(matches!(
+            keep_secrets(&mut destination, old.clone()),
+            Err(DestinationError::SecretRequired(_))
+        ));
+
+        let mut destination = jira_destination("https://attacker.example.com", "new");
+        keep_secrets(&mut destination, old).unwrap();
+        assert_eq!(api_token(&destination), "new");
+    }
 }
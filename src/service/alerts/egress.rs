@@ -0,0 +1,296 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Egress controls of the alert destinations
+//!
+//! `ZO_ALERT_EGRESS_ALLOWLIST` lists the CIDRs and hostnames the alert
+//! destinations may reach, a hostname entry may start with `*.` to allow its
+//! subdomains. A destination is allowed when its host matches a hostname, or
+//! all the addresses it resolves to are in the CIDRs. The addresses are
+//! checked at save and at send time, and the requests connect to the
+//! addresses checked so a name can not resolve elsewhere in between.
+//!
+//! With a proxy the proxy host is checked the same way, the proxy resolving
+//! the destination, its host must match a hostname or be an address of the
+//! CIDRs. The other servers the orgs connect to, like their SMTP servers or
+//! the databases they poll, are checked like the destinations.
+
+use std::net::{IpAddr, SocketAddr};
+
+use config::{get_config, meta::destinations::DestinationType};
+
+#[derive(Debug, PartialEq)]
+enum Rule {
+    Cidr(IpAddr, u8),
+    Host(String),
+}
+
+impl Rule {
+    fn parse(entry: &str) -> Option<Self> {
+        if let Some((addr, prefix)) = entry.split_once('/') {
+            let addr: IpAddr = addr.parse().ok()?;
+            let prefix: u8 = prefix.parse().ok()?;
+            let max = if addr.is_ipv4() { 32 } else { 128 };
+            return (prefix <= max).then_some(Rule::Cidr(addr, prefix));
+        }
+        match entry.parse::<IpAddr>() {
+            Ok(addr) => Some(Rule::Cidr(addr, if addr.is_ipv4() { 32 } else { 128 })),
+            Err(_) => Some(Rule::Host(normalize_host(entry))),
+        }
+    }
+
+    fn allows_host(&self, host: &str) -> bool {
+        match self {
+            Rule::Host(pattern) => match pattern.strip_prefix("*.") {
+                Some(domain) => host
+                    .strip_suffix(domain)
+                    .is_some_and(|sub| sub.len() > 1 && sub.ends_with('.')),
+                None => pattern == host,
+            },
+            Rule::Cidr(..) => false,
+        }
+    }
+
+    fn allows_ip(&self, ip: IpAddr) -> bool {
+        let Rule::Cidr(net, prefix) = self else {
+            return false;
+        };
+        match (net.to_canonical(), ip.to_canonical()) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - *prefix as u32).unwrap_or(0);
+                u32::from(net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - *prefix as u32).unwrap_or(0);
+                u128::from(net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+fn normalize_host(host: &str) -> String {
+    host.trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim_end_matches('.')
+        .to_lowercase()
+}
+
+/// The rules of the allowlist, empty when all the destinations are allowed
+fn allowlist() -> Vec<Rule> {
+    get_config()
+        .common
+        .alert_egress_allowlist
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let rule = Rule::parse(entry);
+            if rule.is_none() {
+                log::warn!("[ALERT EGRESS] ignoring the invalid allowlist entry {entry}");
+            }
+            rule
+        })
+        .collect()
+}
+
+/// Checks the host against the rules, returning the addresses to connect to
+/// when it was resolved. Without `resolve` a hostname only matches the
+/// hostname rules.
+async fn check_host(
+    rules: &[Rule],
+    host: &str,
+    port: u16,
+    resolve: bool,
+) -> Result<Vec<SocketAddr>, anyhow::Error> {
+    let host = normalize_host(host);
+    if rules.iter().any(|rule| rule.allows_host(&host)) {
+        return Ok(vec![]);
+    }
+    let addrs = match host.parse::<IpAddr>() {
+        Ok(ip) => vec![SocketAddr::new(ip, port)],
+        Err(_) if resolve => tokio::net::lookup_host((host.as_str(), port))
+            .await
+            .map_err(|e| anyhow::anyhow!("failed to resolve {host}: {e}"))?
+            .collect(),
+        Err(_) => vec![],
+    };
+    if !addrs.is_empty()
+        && addrs
+            .iter()
+            .all(|addr| rules.iter().any(|rule| rule.allows_ip(addr.ip())))
+    {
+        Ok(addrs)
+    } else {
+        Err(anyhow::anyhow!(
+            "{host} is not allowed by the egress allowlist of the alert destinations"
+        ))
+    }
+}
+
+fn host_and_port(url: &str) -> Result<(String, u16), anyhow::Error> {
+    let url = url::Url::parse(url)?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| anyhow::anyhow!("{url} has no host"))?
+        .to_string();
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| anyhow::anyhow!("{url} has no port"))?;
+    Ok((host, port))
+}
+
+/// Returns the builder of the client sending the requests of a destination
+/// to `url`, through `proxy` if any, once the allowlist allows them
+pub async fn client_builder(
+    url: &str,
+    proxy: Option<&str>,
+) -> Result<reqwest::ClientBuilder, anyhow::Error> {
+    let rules = allowlist();
+    let mut builder = reqwest::Client::builder();
+    let (host, port) = host_and_port(url)?;
+    match proxy {
+        Some(proxy) => {
+            builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+            if !rules.is_empty() {
+                check_host(&rules, &host, port, false).await?;
+                let (proxy_host, proxy_port) = host_and_port(proxy)?;
+                let addrs = check_host(&rules, &proxy_host, proxy_port, true).await?;
+                if !addrs.is_empty() && proxy_host.parse::<IpAddr>().is_err() {
+                    builder = builder.resolve_to_addrs(&proxy_host, &addrs);
+                }
+            }
+        }
+        None if !rules.is_empty() => {
+            let addrs = check_host(&rules, &host, port, true).await?;
+            if !addrs.is_empty() && host.parse::<IpAddr>().is_err() {
+                builder = builder.resolve_to_addrs(&host, &addrs);
+            }
+        }
+        None => {}
+    }
+    Ok(builder)
+}
+
+/// Checks that the allowlist allows the destination, and its proxy is valid
+pub async fn check(destination: &DestinationType) -> Result<(), anyhow::Error> {
+    let (url, proxy) = match destination {
+        DestinationType::Http(endpoint) => (&endpoint.url, &endpoint.proxy),
+        DestinationType::Jira(jira) => (&jira.url, &jira.proxy),
+        DestinationType::ServiceNow(servicenow) => (&servicenow.url, &servicenow.proxy),
+        DestinationType::Kafka(kafka) => return check_brokers(&kafka.brokers).await,
+        DestinationType::Email(_) | DestinationType::Sns(_) => return Ok(()),
+    };
+    client_builder(url, proxy.as_deref()).await.map(|_| ())
+}
+
+/// Checks the comma separated `host:port` brokers of a Kafka destination
+pub async fn check_brokers(brokers: &str) -> Result<(), anyhow::Error> {
+    let rules = allowlist();
+    if rules.is_empty() {
+        return Ok(());
+    }
+    for broker in brokers.split(',').map(str::trim).filter(|b| !b.is_empty()) {
+        let (host, port) = broker
+            .rsplit_once(':')
+            .and_then(|(host, port)| Some((host, port.parse().ok()?)))
+            .ok_or_else(|| anyhow::anyhow!("invalid Kafka broker {broker}"))?;
+        check_host(&rules, host, port, true).await?;
+    }
+    Ok(())
+}
+
+/// Checks a server an org connects to outside of the destinations, like its
+/// SMTP server or the databases it polls, returning the address to connect to
+/// when the allowlist allowed it by its addresses
+pub async fn check_server(host: &str, port: u16) -> Result<Option<SocketAddr>, anyhow::Error> {
+    let rules = allowlist();
+    if rules.is_empty() {
+        return Ok(None);
+    }
+    Ok(check_host(&rules, host, port, true)
+        .await?
+        .into_iter()
+        .next())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(entries: &[&str]) -> Vec<Rule> {
+        entries.iter().filter_map(|e| Rule::parse(e)).collect()
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        assert_eq!(
+            Rule::parse("10.0.0.0/8"),
+            Some(Rule::Cidr("10.0.0.0".parse().unwrap(), 8))
+        );
+        assert_eq!(
+            Rule::parse("192.168.1.10"),
+            Some(Rule::Cidr("192.168.1.10".parse().unwrap(), 32))
+        );
+        assert_eq!(
+            Rule::parse("Hooks.Example.com."),
+            Some(Rule::Host("hooks.example.com".to_string()))
+        );
+        assert_eq!(Rule::parse("10.0.0.0/33"), None);
+        assert_eq!(Rule::parse("example.com/8"), None);
+    }
+
+    #[test]
+    fn test_allows_host() {
+        let rules = rules(&["hooks.example.com", "*.example.org"]);
+        let allowed = |host: &str| rules.iter().any(|r| r.allows_host(host));
+        assert!(allowed("hooks.example.com"));
+        assert!(!allowed("evil.hooks.example.com"));
+        assert!(allowed("a.example.org"));
+        assert!(allowed("a.b.example.org"));
+        assert!(!allowed("example.org"));
+        assert!(!allowed("badexample.org"));
+    }
+
+    #[test]
+    fn test_allows_ip() {
+        let rules = rules(&["10.0.0.0/8", "fd00::/8", "0.0.0.0/0"]);
+        assert!(rules[0].allows_ip("10.1.2.3".parse().unwrap()));
+        assert!(!rules[0].allows_ip("11.1.2.3".parse().unwrap()));
+        assert!(rules[0].allows_ip("::ffff:10.1.2.3".parse().unwrap()));
+        assert!(rules[1].allows_ip("fd12::1".parse().unwrap()));
+        assert!(!rules[1].allows_ip("10.1.2.3".parse().unwrap()));
+        assert!(rules[2].allows_ip("8.8.8.8".parse().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_check_host() {
+        let rules = rules(&["10.0.0.0/8", "hooks.example.com"]);
+        assert!(check_host(&rules, "10.0.0.1", 443, true).await.is_ok());
+        assert!(check_host(&rules, "192.168.0.1", 443, true).await.is_err());
+        assert!(
+            check_host(&rules, "hooks.example.com", 443, false)
+                .await
+                .is_ok()
+        );
+        // not resolved, only the hostnames rules match
+        assert!(
+            check_host(&rules, "other.example.com", 443, false)
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,345 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Email alert destinations
+//!
+//! The emails are sent through the SMTP server of the org when its settings
+//! have one, else through the one of the instance. The rendered template is
+//! the HTML part of the email, its plain text part is derived from it, and
+//! the rows which triggered the alert can be attached as CSV. The outcome of
+//! the last emails of every destination is kept to be listed.
+
+use config::{
+    SMTP_CLIENT, get_config,
+    meta::destinations::{Email, EmailDelivery},
+    utils::{
+        json::{Map, Value},
+        time::now_micros,
+    },
+};
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+    message::{Attachment, MultiPart, header::ContentType},
+    transport::smtp::{
+        authentication::Credentials,
+        client::{Tls, TlsParameters},
+    },
+};
+
+use super::egress;
+use crate::{common::meta::organization::OrgSmtp, service::db};
+
+/// Number of deliveries kept by destination
+const MAX_DELIVERIES: usize = 50;
+
+struct Sender {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from_email: String,
+    reply_to: String,
+}
+
+/// The SMTP server of the org, or the one of the instance
+async fn sender(org_id: &str) -> Result<Sender, anyhow::Error> {
+    let smtp = db::organization::get_org_setting(org_id)
+        .await
+        .map(|setting| setting.smtp)
+        .unwrap_or_default();
+    if !smtp.is_empty() {
+        return Ok(Sender {
+            transport: org_transport(&smtp).await?,
+            from_email: smtp.from_email,
+            reply_to: smtp.reply_to,
+        });
+    }
+    let cfg = get_config();
+    match SMTP_CLIENT.as_ref() {
+        Some(transport) if cfg.smtp.smtp_enabled => Ok(Sender {
+            transport: transport.clone(),
+            from_email: cfg.smtp.smtp_from_email.clone(),
+            reply_to: cfg.smtp.smtp_reply_to.clone(),
+        }),
+        _ => Err(anyhow::anyhow!("SMTP configuration not enabled")),
+    }
+}
+
+/// The transport to the SMTP server of the org, it connects to the address
+/// the egress allowlist checked while the TLS still verifies the host
+async fn org_transport(
+    smtp: &OrgSmtp,
+) -> Result<AsyncSmtpTransport<Tokio1Executor>, anyhow::Error> {
+    let server = match egress::check_server(&smtp.host, smtp.port()).await? {
+        Some(addr) => addr.ip().to_string(),
+        None => smtp.host.clone(),
+    };
+    let mut builder =
+        AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(server).port(smtp.port());
+    builder = match smtp.encryption.as_str() {
+        "starttls" => builder.tls(Tls::Required(TlsParameters::new(smtp.host.clone())?)),
+        "ssltls" => builder.tls(Tls::Wrapper(TlsParameters::new(smtp.host.clone())?)),
+        _ => builder,
+    };
+    if !smtp.username.is_empty() && !smtp.password.is_empty() {
+        builder = builder.credentials(Credentials::new(
+            smtp.username.clone(),
+            smtp.password.clone(),
+        ));
+    }
+    Ok(builder.build())
+}
+
+/// Whether the email destinations of the org can send, through its SMTP
+/// server or the one of the instance
+pub async fn is_enabled(org_id: &str) -> bool {
+    get_config().smtp.smtp_enabled
+        || db::organization::get_org_setting(org_id)
+            .await
+            .is_ok_and(|setting| !setting.smtp.is_empty())
+}
+
+/// Rejects the SMTP settings of an org which can't be used
+pub fn validate_smtp(smtp: &OrgSmtp) -> Result<(), String> {
+    if smtp.is_empty() {
+        return Ok(());
+    }
+    if !["", "starttls", "ssltls"].contains(&smtp.encryption.as_str()) {
+        return Err(format!(
+            "invalid SMTP encryption [{}], it is one of starttls, ssltls or empty",
+            smtp.encryption
+        ));
+    }
+    if smtp.from_email.parse::<lettre::message::Mailbox>().is_err() {
+        return Err(format!("invalid SMTP from email [{}]", smtp.from_email));
+    }
+    if !smtp.reply_to.is_empty() && smtp.reply_to.parse::<lettre::message::Mailbox>().is_err() {
+        return Err(format!("invalid SMTP reply to [{}]", smtp.reply_to));
+    }
+    Ok(())
+}
+
+pub async fn send_notification(
+    org_id: &str,
+    alert_name: &str,
+    subject: &str,
+    email: &Email,
+    rows: &[Map<String, Value>],
+    msg: String,
+) -> Result<String, anyhow::Error> {
+    let sender = sender(org_id).await?;
+    let mut message = Message::builder()
+        .from(sender.from_email.parse()?)
+        .subject(subject.to_string());
+    for recipient in email.recipients.iter() {
+        message = message.to(recipient.parse()?);
+    }
+    if !sender.reply_to.is_empty() {
+        message = message.reply_to(sender.reply_to.parse()?);
+    }
+
+    let body = MultiPart::alternative_plain_html(html_to_text(&msg), msg);
+    let message = if email.attach_csv && !rows.is_empty() {
+        message.multipart(
+            MultiPart::mixed().multipart(body).singlepart(
+                Attachment::new(format!("{}.csv", attachment_name(alert_name)))
+                    .body(encode_csv(rows)?, ContentType::parse("text/csv")?),
+            ),
+        )?
+    } else {
+        message.multipart(body)?
+    };
+
+    match sender.transport.send(message).await {
+        Ok(resp) => Ok(format!("sent email response code: {}", resp.code())),
+        Err(e) => Err(anyhow::anyhow!("Error sending email: {e}")),
+    }
+}
+
+/// Keeps the outcome of an email sent by the destination, the errors are
+/// only logged
+pub async fn record_delivery(
+    org_id: &str,
+    destination: &str,
+    alert_name: &str,
+    email: &Email,
+    result: &Result<String, anyhow::Error>,
+) {
+    let delivery = EmailDelivery {
+        timestamp: now_micros(),
+        alert_name: alert_name.to_string(),
+        recipients: email.recipients.clone(),
+        delivered: result.is_ok(),
+        response: match result {
+            Ok(resp) => resp.clone(),
+            Err(e) => e.to_string(),
+        },
+    };
+    let mut deliveries = list_deliveries(org_id, destination).await;
+    deliveries.insert(0, delivery);
+    deliveries.truncate(MAX_DELIVERIES);
+    if let Err(e) = db::alerts::email_deliveries::set(org_id, destination, &deliveries).await {
+        log::error!("[ALERT EMAIL] recording a delivery of {org_id}/{destination} failed: {e}");
+    }
+}
+
+/// The last emails sent by the destination, the newest first
+pub async fn list_deliveries(org_id: &str, destination: &str) -> Vec<EmailDelivery> {
+    db::alerts::email_deliveries::get(org_id, destination)
+        .await
+        .unwrap_or_default()
+}
+
+/// Removes the deliveries of a deleted destination
+pub async fn delete_deliveries(org_id: &str, destination: &str) {
+    if let Err(e) = db::alerts::email_deliveries::delete(org_id, destination).await {
+        log::warn!("[ALERT EMAIL] deleting the deliveries of {org_id}/{destination} failed: {e}");
+    }
+}
+
+fn attachment_name(alert_name: &str) -> String {
+    alert_name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Writes the rows with a header holding every column, in the order they are
+/// first seen
+fn encode_csv(rows: &[Map<String, Value>]) -> Result<Vec<u8>, anyhow::Error> {
+    let mut columns: Vec<&str> = Vec::new();
+    for row in rows {
+        for key in row.keys() {
+            if !columns.contains(&key.as_str()) {
+                columns.push(key.as_str());
+            }
+        }
+    }
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(&columns)?;
+    for row in rows {
+        writer.write_record(columns.iter().map(|column| match row.get(*column) {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(v)) => v.to_string(),
+            Some(v) => v.to_string(),
+        }))?;
+    }
+    Ok(writer.into_inner()?)
+}
+
+/// The text of an HTML message for the plain text part of the email, the
+/// tags are removed and the line breaks kept
+fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+        let tag = rest[start + 1..start + end]
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        if matches!(
+            tag.as_str(),
+            "br" | "p" | "div" | "tr" | "li" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6"
+        ) && !text.ends_with('\n')
+        {
+            text.push('\n');
+        }
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use config::utils::json;
+
+    use super::*;
+
+    #[test]
+    fn test_html_to_text() {
+        assert_eq!(
+            html_to_text("<h3>Alert <b>high_latency</b></h3><p>p99 &gt; 2s<br/>on api</p>"),
+            "Alert high_latency\np99 > 2s\non api"
+        );
+        assert_eq!(html_to_text("no html"), "no html");
+        assert_eq!(html_to_text("a < b"), "a < b");
+    }
+
+    #[test]
+    fn test_encode_csv() {
+        let rows = vec![
+            json::json!({"host": "a", "count": 3}),
+            json::json!({"host": "b,c", "level": "error"}),
+        ]
+        .into_iter()
+        .map(|v| v.as_object().unwrap().clone())
+        .collect::<Vec<_>>();
+        let csv = String::from_utf8(encode_csv(&rows).unwrap()).unwrap();
+        let mut lines = csv.lines();
+        let header = lines.next().unwrap();
+        assert!(header.contains("host") && header.contains("count") && header.contains("level"));
+        assert_eq!(lines.count(), 2);
+        assert!(csv.contains("\"b,c\""));
+    }
+
+    #[test]
+    fn test_validate_smtp() {
+        assert!(validate_smtp(&OrgSmtp::default()).is_ok());
+        let smtp = OrgSmtp {
+            host: "smtp.example.com".to_string(),
+            from_email: "alerts@example.com".to_string(),
+            encryption: "starttls".to_string(),
+            ..Default::default()
+        };
+        assert!(validate_smtp(&smtp).is_ok());
+        assert!(
+            validate_smtp(&OrgSmtp {
+                encryption: "tls".to_string(),
+                ..smtp.clone()
+            })
+            .is_err()
+        );
+        assert!(
+            validate_smtp(&OrgSmtp {
+                from_email: "not an email".to_string(),
+                ..smtp
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_attachment_name() {
+        assert_eq!(attachment_name("high latency/api"), "high_latency_api");
+    }
+}
@@ -0,0 +1,269 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Jira destinations
+//!
+//! A firing alert opens an issue in the project of the destination, or
+//! comments on the issue it opened before while that one is not done, so an
+//! alert firing again and again does not flood the project. The issues of an
+//! alert are found by a label derived from the alert.
+
+use config::{
+    meta::{alerts::alert::Alert, destinations::Jira},
+    utils::{
+        hash::{Sum64, gxhash},
+        json::{self, Map, Value},
+    },
+};
+
+use super::{egress, read_json_response};
+
+const JIRA_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+pub async fn send_notification(
+    alert: &Alert,
+    jira: &Jira,
+    rows: &[Map<String, Value>],
+    msg: String,
+) -> Result<String, anyhow::Error> {
+    let client = egress::client_builder(&jira.url, jira.proxy.as_deref())
+        .await?
+        .timeout(JIRA_TIMEOUT)
+        .build()?;
+    let base = jira.url.trim_end_matches('/');
+    let label = dedup_label(alert);
+
+    let jql = format!(
+        "project = \"{}\" AND labels = \"{label}\" AND statusCategory != Done ORDER BY created DESC",
+        jira.project_key
+    );
+    let resp = client
+        .get(format!("{base}/rest/api/2/search"))
+        .basic_auth(&jira.username, Some(&jira.api_token))
+        .query(&[
+            ("jql", jql.as_str()),
+            ("maxResults", "1"),
+            ("fields", "key"),
+        ])
+        .send()
+        .await?;
+    let open_issue = read_json_response("Jira", resp)
+        .await?
+        .get("issues")
+        .and_then(|issues| issues.get(0))
+        .and_then(|issue| issue.get("key"))
+        .and_then(|key| key.as_str())
+        .map(|key| key.to_string());
+
+    if let Some(key) = open_issue {
+        let resp = client
+            .post(format!("{base}/rest/api/2/issue/{key}/comment"))
+            .basic_auth(&jira.username, Some(&jira.api_token))
+            .json(&json::json!({ "body": msg }))
+            .send()
+            .await?;
+        read_json_response("Jira", resp).await?;
+        return Ok(format!("commented on Jira issue {key}"));
+    }
+
+    let lookup = |name: &str| lookup_label(alert, rows, name);
+    let fields = issue_fields(jira, &alert.name, &msg, &label, &lookup);
+    let resp = client
+        .post(format!("{base}/rest/api/2/issue"))
+        .basic_auth(&jira.username, Some(&jira.api_token))
+        .json(&json::json!({ "fields": fields }))
+        .send()
+        .await?;
+    let created = read_json_response("Jira", resp).await?;
+    Ok(format!(
+        "created Jira issue {}",
+        created
+            .get("key")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+    ))
+}
+
+/// The label of the issues opened for the alert, the name of the alert may be
+/// reused by another alert after it was deleted, the id is not
+pub(super) fn dedup_label(alert: &Alert) -> String {
+    let key = match alert.id {
+        Some(id) => format!("{}/{id}", alert.org_id),
+        None => format!("{}/{}", alert.org_id, alert.name),
+    };
+    format!("o2-alert-{:016x}", gxhash::new().sum64(&key))
+}
+
+/// The value of an alert label, a context attribute of the alert or a field
+/// of the first row which triggered it
+pub(super) fn lookup_label(
+    alert: &Alert,
+    rows: &[Map<String, Value>],
+    name: &str,
+) -> Option<String> {
+    if let Some(value) = alert
+        .context_attributes
+        .as_ref()
+        .and_then(|attrs| attrs.get(name))
+    {
+        return Some(value.clone());
+    }
+    match rows.first()?.get(name)? {
+        Value::String(v) => Some(v.clone()),
+        Value::Null => None,
+        v => Some(v.to_string()),
+    }
+}
+
+/// Replaces the `{label}` placeholders of a mapped value, a value with a
+/// missing label is not set
+pub(super) fn fill(template: &str, lookup: &dyn Fn(&str) -> Option<String>) -> Option<String> {
+    let mut ret = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        ret.push_str(&rest[..start]);
+        ret.push_str(&lookup(&rest[start + 1..start + len])?);
+        rest = &rest[start + len + 1..];
+    }
+    ret.push_str(rest);
+    Some(ret)
+}
+
+fn issue_fields(
+    jira: &Jira,
+    alert_name: &str,
+    description: &str,
+    label: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Map<String, Value> {
+    let mut labels = vec![Value::from(label)];
+    let mut fields = Map::new();
+    for (field, template) in jira.fields.iter() {
+        let Some(value) = fill(template, lookup) else {
+            continue;
+        };
+        // the fields Jira expects as objects or lists, the others are set as
+        // text
+        match field.as_str() {
+            "labels" => labels.extend(
+                value
+                    .split(',')
+                    .map(|v| v.trim().replace(' ', "_"))
+                    .filter(|v| !v.is_empty())
+                    .map(Value::from),
+            ),
+            "priority" => {
+                fields.insert(field.clone(), json::json!({ "name": value }));
+            }
+            "components" => {
+                let components = value
+                    .split(',')
+                    .map(|v| v.trim())
+                    .filter(|v| !v.is_empty())
+                    .map(|v| json::json!({ "name": v }))
+                    .collect::<Vec<_>>();
+                fields.insert(field.clone(), Value::from(components));
+            }
+            "assignee" | "reporter" => {
+                fields.insert(field.clone(), json::json!({ "accountId": value }));
+            }
+            _ => {
+                fields.insert(field.clone(), Value::from(value));
+            }
+        }
+    }
+    fields.insert(
+        "project".to_string(),
+        json::json!({ "key": jira.project_key }),
+    );
+    fields.insert(
+        "issuetype".to_string(),
+        json::json!({ "name": jira.issue_type }),
+    );
+    fields
+        .entry("summary".to_string())
+        .or_insert_with(|| Value::from(format!("Alert {alert_name} fired")));
+    fields.insert("description".to_string(), Value::from(description));
+    fields.insert("labels".to_string(), Value::from(labels));
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use hashbrown::HashMap;
+
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "severity" => Some("High".to_string()),
+            "team" => Some("payments".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_fill() {
+        assert_eq!(fill("{severity}", &lookup).unwrap(), "High");
+        assert_eq!(
+            fill("team {team}, {severity}!", &lookup).unwrap(),
+            "team payments, High!"
+        );
+        assert_eq!(fill("constant", &lookup).unwrap(), "constant");
+        assert_eq!(fill("{missing}", &lookup), None);
+        assert_eq!(fill("unclosed {brace", &lookup).unwrap(), "unclosed {brace");
+    }
+
+    #[test]
+    fn test_issue_fields() {
+        let jira = Jira {
+            project_key: "OPS".to_string(),
+            issue_type: "Incident".to_string(),
+            fields: HashMap::from([
+                ("priority".to_string(), "{severity}".to_string()),
+                ("labels".to_string(), "observability,{team}".to_string()),
+                ("customfield_10010".to_string(), "{missing}".to_string()),
+            ]),
+            ..Default::default()
+        };
+        let fields = issue_fields(&jira, "high_latency", "p99 > 2s", "o2-alert-1", &lookup);
+        assert_eq!(fields["project"], json::json!({"key": "OPS"}));
+        assert_eq!(fields["issuetype"], json::json!({"name": "Incident"}));
+        assert_eq!(fields["summary"], "Alert high_latency fired");
+        assert_eq!(fields["description"], "p99 > 2s");
+        assert_eq!(fields["priority"], json::json!({"name": "High"}));
+        assert_eq!(
+            fields["labels"],
+            json::json!(["o2-alert-1", "observability", "payments"])
+        );
+        assert!(!fields.contains_key("customfield_10010"));
+    }
+
+    #[test]
+    fn test_dedup_label() {
+        let alert = Alert {
+            org_id: "default".to_string(),
+            name: "high_latency".to_string(),
+            ..Default::default()
+        };
+        let label = dedup_label(&alert);
+        assert!(label.starts_with("o2-alert-"));
+        assert_eq!(label.len(), "o2-alert-".len() + 16);
+        assert_eq!(label, dedup_label(&alert));
+    }
+}
@@ -31,7 +31,7 @@ use config::{
     },
     utils::{
         base64,
-        json::{Map, Value},
+        json::{self, Map, Value},
     },
 };
 use tracing::Instrument;
@@ -45,18 +45,24 @@ use crate::service::{
 
 pub mod alert;
 pub mod backfill;
+pub mod backtest;
 #[cfg(feature = "enterprise")]
 pub mod deduplication;
 pub mod derived_streams;
 pub mod destinations;
+pub mod egress;
+pub mod email;
 #[cfg(feature = "enterprise")]
 pub mod grouping;
 #[cfg(feature = "enterprise")]
 pub mod incidents;
+pub mod jira;
 #[cfg(feature = "enterprise")]
 pub mod org_config;
 pub mod scheduler;
+pub mod servicenow;
 pub mod templates;
+pub mod vrl_template;
 
 #[async_trait]
 pub trait QueryConditionExt: Sync + Send + 'static {
@@ -1251,6 +1257,25 @@ fn build_expr(
     Ok(expr)
 }
 
+/// Reads the JSON body of the response of a ticketing destination, `service`
+/// names it in the error of a failed request
+async fn read_json_response(
+    service: &str,
+    resp: reqwest::Response,
+) -> Result<Value, anyhow::Error> {
+    let status = resp.status();
+    let body = resp.text().await?;
+    if !status.is_success() {
+        return Err(anyhow::anyhow!(
+            "{service} responded with status: {status}, body: {body}"
+        ));
+    }
+    if body.is_empty() {
+        return Ok(Value::Null);
+    }
+    Ok(json::from_str(&body)?)
+}
+
 #[cfg(test)]
 mod tests {
     use arrow_schema::{DataType, Field, Schema};
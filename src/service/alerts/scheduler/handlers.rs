@@ -20,9 +20,12 @@ use config::{
     cluster::LOCAL_NODE,
     get_config, ider,
     meta::{
-        alerts::TriggerCondition,
+        alerts::{TriggerCondition, backtest::BacktestStatus},
         dashboards::reports::ReportFrequencyType,
+        exports::ExportRun,
         pipeline::components::NodeData,
+        recording_rules::RecordingRuleRun,
+        replay::ReplayStatus,
         self_reporting::{
             error::{ErrorData, ErrorSource, PipelineError},
             usage::{TriggerData, TriggerDataStatus, TriggerDataType},
@@ -51,13 +54,18 @@ use crate::service::alerts::scheduler::query_optimization_recommendation::QueryO
 use crate::service::organization::is_org_in_free_trial_period;
 use crate::service::{
     alerts::{
-        alert::{AlertExt, get_alert_start_end_time, get_by_id_db, get_row_column_map},
+        alert::{
+            AlertExt, get_alert_start_end_time, get_by_id_db, get_row_column_map, resolve_incidents,
+        },
+        backtest,
         derived_streams::DerivedStreamExt,
     },
     dashboards::reports::SendReport,
     db::{self, alerts::alert::set_without_updating_trigger},
+    db_pollers,
     ingestion::ingestion_service,
     pipeline::batch_execution::ExecutablePipeline,
+    recording_rules, replay, scheduled_exports,
     self_reporting::publish_triggers_usage,
 };
 
@@ -75,6 +83,19 @@ pub async fn handle_triggers(
             handle_query_recommendations_triggers(trace_id, trigger).await
         }
         db::scheduler::TriggerModule::Backfill => handle_backfill_triggers(trace_id, trigger).await,
+        db::scheduler::TriggerModule::ScheduledExport => {
+            handle_scheduled_export_triggers(trace_id, trigger).await
+        }
+        db::scheduler::TriggerModule::AlertBacktest => {
+            handle_alert_backtest_triggers(trace_id, trigger).await
+        }
+        db::scheduler::TriggerModule::RecordingRule => {
+            handle_recording_rule_triggers(trace_id, trigger).await
+        }
+        db::scheduler::TriggerModule::Replay => handle_replay_triggers(trace_id, trigger).await,
+        db::scheduler::TriggerModule::DbPoller => {
+            handle_db_poller_triggers(trace_id, trigger).await
+        }
     }
 }
 
@@ -396,6 +417,7 @@ async fn handle_alert_triggers(
             period_end_time: None,
             tolerance: 0,
             last_satisfied_at: None,
+            firing: false,
             backfill_job: None,
         }
     };
@@ -643,6 +665,7 @@ async fn handle_alert_triggers(
 
     if trigger_results.data.is_some() {
         trigger_data.last_satisfied_at = Some(triggered_at);
+        trigger_data.firing = true;
     }
 
     // send notification
@@ -1015,6 +1038,9 @@ async fn handle_alert_triggers(
             &new_trigger.org,
             &new_trigger.module_key
         );
+        if std::mem::take(&mut trigger_data.firing) {
+            resolve_incidents(&alert).await;
+        }
         // Condition did not match, store the last used end_time in the triggers
         // In the next run, the alert will be checked from the last end_time
         trigger_data.period_end_time = if should_store_last_end_time {
@@ -1421,6 +1447,601 @@ async fn handle_report_triggers(
     Ok(())
 }
 
+async fn handle_scheduled_export_triggers(
+    trace_id: &str,
+    trigger: db::scheduler::Trigger,
+) -> Result<(), anyhow::Error> {
+    let (_, max_retries) = get_scheduler_max_retries();
+    let query_trace_id = ider::generate_trace_id();
+    let scheduler_trace_id = format!("{trace_id}/{query_trace_id}");
+    // For scheduled export, trigger.module_key is the export id
+    let export_id = &trigger.module_key;
+    let now = now_micros();
+    let triggered_at = trigger.start_time.unwrap_or_default();
+
+    let export = match db::scheduled_exports::get(&trigger.org, export_id).await {
+        Ok(export) => export,
+        Err(e) => {
+            log::error!(
+                "[SCHEDULER trace_id {scheduler_trace_id}] Scheduled export not found: org: {}, id: {export_id}, error: {e}",
+                &trigger.org
+            );
+            db::scheduler::delete(
+                &trigger.org,
+                db::scheduler::TriggerModule::ScheduledExport,
+                export_id,
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let mut new_trigger = db::scheduler::Trigger {
+        next_run_at: scheduled_exports::next_run_at(now, export.frequency),
+        is_realtime: false,
+        is_silenced: false,
+        status: db::scheduler::TriggerStatus::Waiting,
+        retries: 0,
+        ..trigger.clone()
+    };
+    if !export.enabled {
+        db::scheduler::update_trigger(new_trigger, true, &query_trace_id).await?;
+        return Ok(());
+    }
+
+    // The window ends when the run was due, and the next run exports the
+    // following window even if it is already late, so no window is missed
+    let end_time = trigger.next_run_at;
+    new_trigger.next_run_at = end_time
+        + Duration::try_minutes(export.frequency)
+            .unwrap()
+            .num_microseconds()
+            .unwrap();
+
+    let start = Instant::now();
+    let run = match scheduled_exports::run(&query_trace_id, &trigger.org, &export, end_time).await {
+        Ok(run) => {
+            log::info!(
+                "[SCHEDULER trace_id {scheduler_trace_id}] Scheduled export {}/{} wrote {} rows to {:?}",
+                &trigger.org,
+                export.name,
+                run.rows,
+                run.object
+            );
+            run
+        }
+        Err(e) => {
+            log::error!(
+                "[SCHEDULER trace_id {scheduler_trace_id}] Scheduled export {}/{} failed: {e}",
+                &trigger.org,
+                export.name
+            );
+            if trigger.retries + 1 < max_retries {
+                db::scheduler::update_status(
+                    &trigger.org,
+                    db::scheduler::TriggerModule::ScheduledExport,
+                    export_id,
+                    db::scheduler::TriggerStatus::Waiting,
+                    trigger.retries + 1,
+                    None,
+                    true,
+                    &query_trace_id,
+                )
+                .await?;
+                return Err(anyhow::anyhow!("Scheduled export {export_id} failed: {e}"));
+            }
+            ExportRun {
+                end_time,
+                error: Some(e.to_string()),
+                ..Default::default()
+            }
+        }
+    };
+
+    publish_triggers_usage(TriggerData {
+        _timestamp: now,
+        org: trigger.org.clone(),
+        module: TriggerDataType::ScheduledExport,
+        key: export_id.to_string(),
+        next_run_at: new_trigger.next_run_at,
+        is_realtime: false,
+        is_silenced: false,
+        status: if run.error.is_some() {
+            TriggerDataStatus::Failed
+        } else {
+            TriggerDataStatus::Completed
+        },
+        start_time: triggered_at,
+        end_time: now_micros(),
+        retries: trigger.retries,
+        error: run.error.clone(),
+        delay_in_secs: Some(Duration::microseconds(now - trigger.next_run_at).num_seconds()),
+        evaluation_took_in_secs: Some(start.elapsed().as_secs_f64()),
+        source_node: Some(LOCAL_NODE.name.clone()),
+        scheduler_trace_id: Some(scheduler_trace_id.clone()),
+        ..Default::default()
+    });
+
+    // save the outcome on the latest version, it may have been edited meanwhile
+    match db::scheduled_exports::get(&trigger.org, export_id).await {
+        Ok(mut latest) => {
+            latest.last_run = Some(run);
+            if let Err(e) = db::scheduled_exports::set(&trigger.org, &latest).await {
+                log::error!(
+                    "[SCHEDULER trace_id {scheduler_trace_id}] Failed to save the last run of scheduled export {export_id}: {e}"
+                );
+            }
+        }
+        Err(_) => return Ok(()),
+    }
+    db::scheduler::update_trigger(new_trigger, true, &query_trace_id).await?;
+    Ok(())
+}
+
+async fn handle_alert_backtest_triggers(
+    trace_id: &str,
+    trigger: db::scheduler::Trigger,
+) -> Result<(), anyhow::Error> {
+    let (_, max_retries) = get_scheduler_max_retries();
+    let query_trace_id = ider::generate_trace_id();
+    let scheduler_trace_id = format!("{trace_id}/{query_trace_id}");
+    // For alert backtest, trigger.module_key is the backtest id
+    let backtest_id = &trigger.module_key;
+    let now = now_micros();
+
+    let mut backtest = match db::alerts::backtest::get(&trigger.org, backtest_id).await {
+        Ok(backtest) if backtest.status == BacktestStatus::Running => backtest,
+        ret => {
+            if let Err(e) = ret {
+                log::error!(
+                    "[SCHEDULER trace_id {scheduler_trace_id}] Alert backtest not found: org: {}, id: {backtest_id}, error: {e}",
+                    &trigger.org
+                );
+            }
+            db::scheduler::delete(
+                &trigger.org,
+                db::scheduler::TriggerModule::AlertBacktest,
+                backtest_id,
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let start = Instant::now();
+    let ret = backtest::run(&query_trace_id, &mut backtest).await;
+    let error = match &ret {
+        Ok(_) => None,
+        Err(e) => {
+            log::error!(
+                "[SCHEDULER trace_id {scheduler_trace_id}] Alert backtest {}/{backtest_id} failed: {e}",
+                &trigger.org
+            );
+            if trigger.retries + 1 >= max_retries {
+                backtest.status = BacktestStatus::Failed;
+                backtest.error = Some(e.to_string());
+            }
+            Some(e.to_string())
+        }
+    };
+    // the windows evaluated before a failure are kept, a retry goes on after
+    // them
+    db::alerts::backtest::set(&trigger.org, &backtest).await?;
+
+    publish_triggers_usage(TriggerData {
+        _timestamp: now,
+        org: trigger.org.clone(),
+        module: TriggerDataType::AlertBacktest,
+        key: format!("{}/{backtest_id}", backtest.alert.name),
+        next_run_at: now,
+        is_realtime: false,
+        is_silenced: false,
+        status: if error.is_some() {
+            TriggerDataStatus::Failed
+        } else {
+            TriggerDataStatus::Completed
+        },
+        start_time: trigger.start_time.unwrap_or_default(),
+        end_time: now_micros(),
+        retries: trigger.retries,
+        error: error.clone(),
+        evaluation_took_in_secs: Some(start.elapsed().as_secs_f64()),
+        source_node: Some(LOCAL_NODE.name.clone()),
+        scheduler_trace_id: Some(scheduler_trace_id.clone()),
+        ..Default::default()
+    });
+
+    match ret {
+        Ok(false) => {
+            // more windows to evaluate, the next run picks them up right away
+            let new_trigger = db::scheduler::Trigger {
+                next_run_at: now_micros(),
+                is_realtime: false,
+                is_silenced: false,
+                status: db::scheduler::TriggerStatus::Waiting,
+                retries: 0,
+                ..trigger.clone()
+            };
+            db::scheduler::update_trigger(new_trigger, true, &query_trace_id).await?;
+        }
+        Err(e) if backtest.status == BacktestStatus::Running => {
+            db::scheduler::update_status(
+                &trigger.org,
+                db::scheduler::TriggerModule::AlertBacktest,
+                backtest_id,
+                db::scheduler::TriggerStatus::Waiting,
+                trigger.retries + 1,
+                None,
+                true,
+                &query_trace_id,
+            )
+            .await?;
+            return Err(anyhow::anyhow!("Alert backtest {backtest_id} failed: {e}"));
+        }
+        _ => {
+            log::info!(
+                "[SCHEDULER trace_id {scheduler_trace_id}] Alert backtest {}/{backtest_id} {:?}: {} windows evaluated, {} fired",
+                &trigger.org,
+                backtest.status,
+                backtest.windows_evaluated,
+                backtest.windows_fired
+            );
+            db::scheduler::delete(
+                &trigger.org,
+                db::scheduler::TriggerModule::AlertBacktest,
+                backtest_id,
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+async fn handle_recording_rule_triggers(
+    trace_id: &str,
+    trigger: db::scheduler::Trigger,
+) -> Result<(), anyhow::Error> {
+    let (_, max_retries) = get_scheduler_max_retries();
+    let query_trace_id = ider::generate_trace_id();
+    let scheduler_trace_id = format!("{trace_id}/{query_trace_id}");
+    // For recording rule, trigger.module_key is the rule id
+    let rule_id = &trigger.module_key;
+    let now = now_micros();
+    let triggered_at = trigger.start_time.unwrap_or_default();
+
+    let rule = match db::recording_rules::get(&trigger.org, rule_id).await {
+        Ok(rule) => rule,
+        Err(e) => {
+            log::error!(
+                "[SCHEDULER trace_id {scheduler_trace_id}] Recording rule not found: org: {}, id: {rule_id}, error: {e}",
+                &trigger.org
+            );
+            db::scheduler::delete(
+                &trigger.org,
+                db::scheduler::TriggerModule::RecordingRule,
+                rule_id,
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    // A late run evaluates the time it was due and skips the evaluations it
+    // missed, like Prometheus does
+    let new_trigger = db::scheduler::Trigger {
+        next_run_at: recording_rules::next_run_at(now, rule.interval),
+        is_realtime: false,
+        is_silenced: false,
+        status: db::scheduler::TriggerStatus::Waiting,
+        retries: 0,
+        ..trigger.clone()
+    };
+    if !rule.enabled {
+        db::scheduler::update_trigger(new_trigger, true, &query_trace_id).await?;
+        return Ok(());
+    }
+
+    let timestamp = trigger.next_run_at;
+    let start = Instant::now();
+    let run = match recording_rules::run(&query_trace_id, &trigger.org, &rule, timestamp).await {
+        Ok(run) => {
+            log::debug!(
+                "[SCHEDULER trace_id {scheduler_trace_id}] Recording rule {}/{} wrote {} series",
+                &trigger.org,
+                rule.name,
+                run.series
+            );
+            run
+        }
+        Err(e) => {
+            log::error!(
+                "[SCHEDULER trace_id {scheduler_trace_id}] Recording rule {}/{} failed: {e}",
+                &trigger.org,
+                rule.name
+            );
+            if trigger.retries + 1 < max_retries {
+                db::scheduler::update_status(
+                    &trigger.org,
+                    db::scheduler::TriggerModule::RecordingRule,
+                    rule_id,
+                    db::scheduler::TriggerStatus::Waiting,
+                    trigger.retries + 1,
+                    None,
+                    true,
+                    &query_trace_id,
+                )
+                .await?;
+                return Err(anyhow::anyhow!("Recording rule {rule_id} failed: {e}"));
+            }
+            RecordingRuleRun {
+                timestamp,
+                error: Some(e.to_string()),
+                ..Default::default()
+            }
+        }
+    };
+
+    publish_triggers_usage(TriggerData {
+        _timestamp: now,
+        org: trigger.org.clone(),
+        module: TriggerDataType::RecordingRule,
+        key: format!("{}/{rule_id}", rule.name),
+        next_run_at: new_trigger.next_run_at,
+        is_realtime: false,
+        is_silenced: false,
+        status: if run.error.is_some() {
+            TriggerDataStatus::Failed
+        } else {
+            TriggerDataStatus::Completed
+        },
+        start_time: triggered_at,
+        end_time: now_micros(),
+        retries: trigger.retries,
+        error: run.error.clone(),
+        delay_in_secs: Some(Duration::microseconds(now - trigger.next_run_at).num_seconds()),
+        evaluation_took_in_secs: Some(start.elapsed().as_secs_f64()),
+        source_node: Some(LOCAL_NODE.name.clone()),
+        scheduler_trace_id: Some(scheduler_trace_id.clone()),
+        ..Default::default()
+    });
+
+    // save the outcome on the latest version, it may have been edited meanwhile
+    match db::recording_rules::get(&trigger.org, rule_id).await {
+        Ok(mut latest) => {
+            latest.last_run = Some(run);
+            if let Err(e) = db::recording_rules::set(&trigger.org, &latest).await {
+                log::error!(
+                    "[SCHEDULER trace_id {scheduler_trace_id}] Failed to save the last run of recording rule {rule_id}: {e}"
+                );
+            }
+        }
+        Err(_) => return Ok(()),
+    }
+    db::scheduler::update_trigger(new_trigger, true, &query_trace_id).await?;
+    Ok(())
+}
+
+async fn handle_db_poller_triggers(
+    trace_id: &str,
+    trigger: db::scheduler::Trigger,
+) -> Result<(), anyhow::Error> {
+    let query_trace_id = ider::generate_trace_id();
+    let scheduler_trace_id = format!("{trace_id}/{query_trace_id}");
+    // For db poller, trigger.module_key is the poller id
+    let poller_id = &trigger.module_key;
+    let now = now_micros();
+    let triggered_at = trigger.start_time.unwrap_or_default();
+
+    let poller = match db::db_pollers::get(&trigger.org, poller_id).await {
+        Ok(poller) => poller,
+        Err(e) => {
+            log::error!(
+                "[SCHEDULER trace_id {scheduler_trace_id}] DB poller not found: org: {}, id: {poller_id}, error: {e}",
+                &trigger.org
+            );
+            db::scheduler::delete(
+                &trigger.org,
+                db::scheduler::TriggerModule::DbPoller,
+                poller_id,
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let new_trigger = db::scheduler::Trigger {
+        next_run_at: db_pollers::next_run_at(now, poller.interval),
+        is_realtime: false,
+        is_silenced: false,
+        status: db::scheduler::TriggerStatus::Waiting,
+        retries: 0,
+        ..trigger.clone()
+    };
+    if !poller.enabled {
+        db::scheduler::update_trigger(new_trigger, true, &query_trace_id).await?;
+        return Ok(());
+    }
+
+    // A failed poll isn't retried, the next one resumes from the watermark
+    let start = Instant::now();
+    let (watermark, run) = db_pollers::poll(&trigger.org, &poller).await;
+    match &run.error {
+        None => log::debug!(
+            "[SCHEDULER trace_id {scheduler_trace_id}] DB poller {}/{} ingested {} rows",
+            &trigger.org,
+            poller.name,
+            run.rows
+        ),
+        Some(e) => log::error!(
+            "[SCHEDULER trace_id {scheduler_trace_id}] DB poller {}/{} failed after {} rows: {e}",
+            &trigger.org,
+            poller.name,
+            run.rows
+        ),
+    }
+
+    publish_triggers_usage(TriggerData {
+        _timestamp: now,
+        org: trigger.org.clone(),
+        module: TriggerDataType::DbPoller,
+        key: format!("{}/{poller_id}", poller.name),
+        next_run_at: new_trigger.next_run_at,
+        is_realtime: false,
+        is_silenced: false,
+        status: if run.error.is_some() {
+            TriggerDataStatus::Failed
+        } else {
+            TriggerDataStatus::Completed
+        },
+        start_time: triggered_at,
+        end_time: now_micros(),
+        retries: trigger.retries,
+        error: run.error.clone(),
+        delay_in_secs: Some(Duration::microseconds(now - trigger.next_run_at).num_seconds()),
+        evaluation_took_in_secs: Some(start.elapsed().as_secs_f64()),
+        source_node: Some(LOCAL_NODE.name.clone()),
+        scheduler_trace_id: Some(scheduler_trace_id.clone()),
+        ..Default::default()
+    });
+
+    // save the outcome on the latest version, the watermark is only moved if
+    // it wasn't reset meanwhile
+    match db::db_pollers::get(&trigger.org, poller_id).await {
+        Ok(mut latest) => {
+            if latest.watermark == poller.watermark {
+                latest.watermark = watermark;
+            }
+            latest.last_run = Some(run);
+            if let Err(e) = db::db_pollers::set(&trigger.org, &latest).await {
+                log::error!(
+                    "[SCHEDULER trace_id {scheduler_trace_id}] Failed to save the watermark of db poller {poller_id}: {e}"
+                );
+            }
+        }
+        Err(_) => return Ok(()),
+    }
+    db::scheduler::update_trigger(new_trigger, true, &query_trace_id).await?;
+    Ok(())
+}
+
+async fn handle_replay_triggers(
+    trace_id: &str,
+    trigger: db::scheduler::Trigger,
+) -> Result<(), anyhow::Error> {
+    let (_, max_retries) = get_scheduler_max_retries();
+    let query_trace_id = ider::generate_trace_id();
+    let scheduler_trace_id = format!("{trace_id}/{query_trace_id}");
+    // For replay, trigger.module_key is the replay id
+    let replay_id = &trigger.module_key;
+    let now = now_micros();
+
+    let mut replay = match db::replay::get(&trigger.org, replay_id).await {
+        Ok(replay) if replay.status == ReplayStatus::Running => replay,
+        ret => {
+            if let Err(e) = ret {
+                log::error!(
+                    "[SCHEDULER trace_id {scheduler_trace_id}] Replay not found: org: {}, id: {replay_id}, error: {e}",
+                    &trigger.org
+                );
+            }
+            db::scheduler::delete(
+                &trigger.org,
+                db::scheduler::TriggerModule::Replay,
+                replay_id,
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let start = Instant::now();
+    let ret = replay::run(&query_trace_id, &trigger.org, &mut replay).await;
+    let error = match &ret {
+        Ok(_) => None,
+        Err(e) => {
+            log::error!(
+                "[SCHEDULER trace_id {scheduler_trace_id}] Replay {}/{replay_id} failed: {e}",
+                &trigger.org
+            );
+            if trigger.retries + 1 >= max_retries {
+                replay.status = ReplayStatus::Failed;
+                replay.error = Some(e.to_string());
+            }
+            Some(e.to_string())
+        }
+    };
+    // the pages replayed before a failure are kept, a retry goes on after them
+    db::replay::set(&trigger.org, &replay).await?;
+
+    publish_triggers_usage(TriggerData {
+        _timestamp: now,
+        org: trigger.org.clone(),
+        module: TriggerDataType::Replay,
+        key: format!("{}/{replay_id}", replay.request.stream_name),
+        next_run_at: now,
+        is_realtime: false,
+        is_silenced: false,
+        status: if error.is_some() {
+            TriggerDataStatus::Failed
+        } else {
+            TriggerDataStatus::Completed
+        },
+        start_time: trigger.start_time.unwrap_or_default(),
+        end_time: now_micros(),
+        retries: trigger.retries,
+        error: error.clone(),
+        evaluation_took_in_secs: Some(start.elapsed().as_secs_f64()),
+        source_node: Some(LOCAL_NODE.name.clone()),
+        scheduler_trace_id: Some(scheduler_trace_id.clone()),
+        ..Default::default()
+    });
+
+    match ret {
+        Ok(false) => {
+            // more records to replay, the next run picks them up right away
+            let new_trigger = db::scheduler::Trigger {
+                next_run_at: now_micros(),
+                is_realtime: false,
+                is_silenced: false,
+                status: db::scheduler::TriggerStatus::Waiting,
+                retries: 0,
+                ..trigger.clone()
+            };
+            db::scheduler::update_trigger(new_trigger, true, &query_trace_id).await?;
+        }
+        Err(e) if replay.status == ReplayStatus::Running => {
+            db::scheduler::update_status(
+                &trigger.org,
+                db::scheduler::TriggerModule::Replay,
+                replay_id,
+                db::scheduler::TriggerStatus::Waiting,
+                trigger.retries + 1,
+                None,
+                true,
+                &query_trace_id,
+            )
+            .await?;
+            return Err(anyhow::anyhow!("Replay {replay_id} failed: {e}"));
+        }
+        _ => {
+            log::info!(
+                "[SCHEDULER trace_id {scheduler_trace_id}] Replay {}/{replay_id} {:?}: {} records read, {} written",
+                &trigger.org,
+                replay.status,
+                replay.records_read,
+                replay.records_written
+            );
+            db::scheduler::delete(
+                &trigger.org,
+                db::scheduler::TriggerModule::Replay,
+                replay_id,
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
 async fn handle_derived_stream_triggers(
     trace_id: &str,
     trigger: db::scheduler::Trigger,
@@ -2074,6 +2695,7 @@ async fn handle_derived_stream_triggers(
             period_end_time: Some(start_time),
             tolerance: 0,
             last_satisfied_at: None,
+            firing: false,
             backfill_job: None,
         })
         .unwrap();
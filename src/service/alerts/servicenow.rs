@@ -0,0 +1,332 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! ServiceNow destinations
+//!
+//! A firing alert opens an incident, or adds a work note to the incident it
+//! opened before while that one is active, the incidents of an alert are
+//! found by their correlation id. The urgency and impact of the incident are
+//! mapped from the severity of the alert. With close on resolve, the incident
+//! is resolved once the scheduled alert is not satisfied anymore.
+//!
+//! An import set destination posts the same fields to its staging table, with
+//! an `action` of `trigger` or `resolve`, its transform map coalescing on the
+//! correlation id opens, updates and resolves the incidents.
+
+use config::{
+    meta::{alerts::alert::Alert, destinations::ServiceNow},
+    utils::json::{self, Map, Value},
+};
+
+use super::{
+    egress,
+    jira::{dedup_label, fill, lookup_label},
+    read_json_response,
+};
+
+const SERVICENOW_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+const DEFAULT_TABLE: &str = "incident";
+const DEFAULT_SEVERITY_LABEL: &str = "severity";
+/// The `Resolved` state of the incidents
+const STATE_RESOLVED: &str = "6";
+const DEFAULT_CLOSE_CODE: &str = "Solution provided";
+
+pub async fn send_notification(
+    alert: &Alert,
+    servicenow: &ServiceNow,
+    rows: &[Map<String, Value>],
+    msg: String,
+) -> Result<String, anyhow::Error> {
+    let client = egress::client_builder(&servicenow.url, servicenow.proxy.as_deref())
+        .await?
+        .timeout(SERVICENOW_TIMEOUT)
+        .build()?;
+    let correlation_id = dedup_label(alert);
+    let lookup = |name: &str| lookup_label(alert, rows, name);
+    let mut fields = incident_fields(servicenow, &alert.name, &msg, &correlation_id, &lookup);
+
+    if servicenow.import_set {
+        fields.insert("action".to_string(), Value::from("trigger"));
+        let resp = client
+            .post(import_url(servicenow))
+            .basic_auth(&servicenow.username, Some(&servicenow.password))
+            .json(&fields)
+            .send()
+            .await?;
+        read_json_response("ServiceNow", resp).await?;
+        return Ok(format!(
+            "posted to ServiceNow import set {}",
+            servicenow.table
+        ));
+    }
+
+    if let Some((sys_id, number)) = find_open_incident(&client, servicenow, &correlation_id).await?
+    {
+        let resp = client
+            .patch(format!("{}/{sys_id}", table_url(servicenow)))
+            .basic_auth(&servicenow.username, Some(&servicenow.password))
+            .json(&json::json!({ "work_notes": msg }))
+            .send()
+            .await?;
+        read_json_response("ServiceNow", resp).await?;
+        return Ok(format!("updated ServiceNow incident {number}"));
+    }
+
+    let resp = client
+        .post(table_url(servicenow))
+        .basic_auth(&servicenow.username, Some(&servicenow.password))
+        .json(&fields)
+        .send()
+        .await?;
+    let created = read_json_response("ServiceNow", resp).await?;
+    Ok(format!(
+        "created ServiceNow incident {}",
+        created
+            .get("result")
+            .and_then(|v| v.get("number"))
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+    ))
+}
+
+/// Resolves the incident opened for the alert, if it is still active
+pub async fn resolve(alert: &Alert, servicenow: &ServiceNow) -> Result<String, anyhow::Error> {
+    let client = egress::client_builder(&servicenow.url, servicenow.proxy.as_deref())
+        .await?
+        .timeout(SERVICENOW_TIMEOUT)
+        .build()?;
+    let correlation_id = dedup_label(alert);
+    let mut fields = resolve_fields(servicenow, &alert.name);
+
+    if servicenow.import_set {
+        fields.insert("correlation_id".to_string(), Value::from(correlation_id));
+        fields.insert("action".to_string(), Value::from("resolve"));
+        let resp = client
+            .post(import_url(servicenow))
+            .basic_auth(&servicenow.username, Some(&servicenow.password))
+            .json(&fields)
+            .send()
+            .await?;
+        read_json_response("ServiceNow", resp).await?;
+        return Ok(format!(
+            "posted resolve to ServiceNow import set {}",
+            servicenow.table
+        ));
+    }
+
+    let Some((sys_id, number)) = find_open_incident(&client, servicenow, &correlation_id).await?
+    else {
+        return Ok("no active ServiceNow incident".to_string());
+    };
+    let resp = client
+        .patch(format!("{}/{sys_id}", table_url(servicenow)))
+        .basic_auth(&servicenow.username, Some(&servicenow.password))
+        .json(&fields)
+        .send()
+        .await?;
+    read_json_response("ServiceNow", resp).await?;
+    Ok(format!("resolved ServiceNow incident {number}"))
+}
+
+/// The sys_id and number of the active incident of the alert
+async fn find_open_incident(
+    client: &reqwest::Client,
+    servicenow: &ServiceNow,
+    correlation_id: &str,
+) -> Result<Option<(String, String)>, anyhow::Error> {
+    let query = format!("correlation_id={correlation_id}^active=true^ORDERBYDESCsys_created_on");
+    let resp = client
+        .get(table_url(servicenow))
+        .basic_auth(&servicenow.username, Some(&servicenow.password))
+        .query(&[
+            ("sysparm_query", query.as_str()),
+            ("sysparm_limit", "1"),
+            ("sysparm_fields", "sys_id,number"),
+        ])
+        .send()
+        .await?;
+    let found = read_json_response("ServiceNow", resp).await?;
+    let Some(incident) = found.get("result").and_then(|v| v.get(0)) else {
+        return Ok(None);
+    };
+    let field = |name: &str| {
+        incident
+            .get(name)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    Ok(Some((field("sys_id"), field("number"))))
+}
+
+fn table_url(servicenow: &ServiceNow) -> String {
+    let table = if servicenow.table.is_empty() {
+        DEFAULT_TABLE
+    } else {
+        &servicenow.table
+    };
+    format!("{}/api/now/table/{table}", servicenow.url)
+}
+
+fn import_url(servicenow: &ServiceNow) -> String {
+    format!("{}/api/now/import/{}", servicenow.url, servicenow.table)
+}
+
+/// The fields of a new incident, the urgency and impact mapped from the
+/// severity are only set when not set by the fields of the destination
+fn incident_fields(
+    servicenow: &ServiceNow,
+    alert_name: &str,
+    description: &str,
+    correlation_id: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Map<String, Value> {
+    let mut fields = Map::new();
+    for (field, template) in servicenow.fields.iter() {
+        // the close code is only set on resolve
+        if field == "close_code" {
+            continue;
+        }
+        if let Some(value) = fill(template, lookup) {
+            fields.insert(field.clone(), Value::from(value));
+        }
+    }
+    let severity_label = if servicenow.severity_label.is_empty() {
+        DEFAULT_SEVERITY_LABEL
+    } else {
+        &servicenow.severity_label
+    };
+    if let Some(severity) = lookup(severity_label) {
+        for (field, mapping) in [
+            ("urgency", &servicenow.urgency_mapping),
+            ("impact", &servicenow.impact_mapping),
+        ] {
+            if let Some((_, value)) = mapping
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(&severity))
+            {
+                fields
+                    .entry(field.to_string())
+                    .or_insert_with(|| Value::from(value.as_str()));
+            }
+        }
+    }
+    fields
+        .entry("short_description".to_string())
+        .or_insert_with(|| Value::from(format!("Alert {alert_name} fired")));
+    fields.insert("description".to_string(), Value::from(description));
+    fields.insert("correlation_id".to_string(), Value::from(correlation_id));
+    fields.insert(
+        "correlation_display".to_string(),
+        Value::from("OpenObserve"),
+    );
+    fields
+}
+
+fn resolve_fields(servicenow: &ServiceNow, alert_name: &str) -> Map<String, Value> {
+    let close_code = servicenow
+        .fields
+        .get("close_code")
+        .map(|v| v.as_str())
+        .unwrap_or(DEFAULT_CLOSE_CODE);
+    let mut fields = Map::new();
+    fields.insert("state".to_string(), Value::from(STATE_RESOLVED));
+    fields.insert("close_code".to_string(), Value::from(close_code));
+    fields.insert(
+        "close_notes".to_string(),
+        Value::from(format!("Alert {alert_name} is not satisfied anymore")),
+    );
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use hashbrown::HashMap;
+
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "severity" => Some("Critical".to_string()),
+            "team" => Some("payments".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_incident_fields() {
+        let servicenow = ServiceNow {
+            urgency_mapping: HashMap::from([
+                ("critical".to_string(), "1".to_string()),
+                ("warning".to_string(), "2".to_string()),
+            ]),
+            impact_mapping: HashMap::from([("critical".to_string(), "1".to_string())]),
+            fields: HashMap::from([
+                ("assignment_group".to_string(), "{team}".to_string()),
+                ("impact".to_string(), "2".to_string()),
+                ("close_code".to_string(), "Workaround provided".to_string()),
+                ("u_service".to_string(), "{missing}".to_string()),
+            ]),
+            ..Default::default()
+        };
+        let fields = incident_fields(
+            &servicenow,
+            "high_latency",
+            "p99 > 2s",
+            "o2-alert-1",
+            &lookup,
+        );
+        assert_eq!(fields["short_description"], "Alert high_latency fired");
+        assert_eq!(fields["description"], "p99 > 2s");
+        assert_eq!(fields["correlation_id"], "o2-alert-1");
+        assert_eq!(fields["assignment_group"], "payments");
+        assert_eq!(fields["urgency"], "1");
+        // the fields of the destination win over the mapping
+        assert_eq!(fields["impact"], "2");
+        assert!(!fields.contains_key("close_code"));
+        assert!(!fields.contains_key("u_service"));
+    }
+
+    #[test]
+    fn test_resolve_fields() {
+        let fields = resolve_fields(&ServiceNow::default(), "high_latency");
+        assert_eq!(fields["state"], STATE_RESOLVED);
+        assert_eq!(fields["close_code"], DEFAULT_CLOSE_CODE);
+
+        let servicenow = ServiceNow {
+            fields: HashMap::from([("close_code".to_string(), "Workaround provided".to_string())]),
+            ..Default::default()
+        };
+        let fields = resolve_fields(&servicenow, "high_latency");
+        assert_eq!(fields["close_code"], "Workaround provided");
+    }
+
+    #[test]
+    fn test_urls() {
+        let mut servicenow = ServiceNow {
+            url: "https://example.service-now.com".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            table_url(&servicenow),
+            "https://example.service-now.com/api/now/table/incident"
+        );
+        servicenow.table = "u_o2_alerts".to_string();
+        assert_eq!(
+            import_url(&servicenow),
+            "https://example.service-now.com/api/now/import/u_o2_alerts"
+        );
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! VRL templates of HTTP destinations
+//!
+//! The VRL template of an HTTP alert destination renders the whole request of
+//! a notification, where the templates only substitute placeholders. The
+//! program runs on the context of the notification:
+//!
+//! - `.org_id` and `.org_name`
+//! - `.alert`: `id`, `name`, `type` (`realtime` or `scheduled`), `stream_type`, `stream_name`,
+//!   `description`, `period`, `operator`, `threshold` and the context attributes as `labels`
+//! - `.rows`: the rows which triggered the alert
+//! - `.start_time`, `.end_time` and `.evaluation_timestamp`, in microseconds
+//! - `.message`: the template of the destination rendered, empty without one
+//! - `.headers`: the headers of the destination
+//!
+//! Its value is the body, a string is sent as is, other values as JSON. The
+//! headers it leaves in `.headers` are the ones of the request, so it can set
+//! or remove some. Loops and conditionals are the ones of VRL, e.g.
+//! `map_values(array!(.rows)) -> |row| { row.message }` or
+//! `if length(array!(.rows)) > 10 { ... }`.
+
+use config::{
+    meta::{alerts::alert::Alert, destinations::DestinationType},
+    utils::json::{self, Map, Value},
+};
+use hashbrown::HashMap;
+use vrl::compiler::{TargetValue, TimeZone, runtime::Runtime};
+
+use crate::service::ingestion::compile_vrl_function;
+
+pub struct NotificationContext<'a> {
+    pub org_name: &'a str,
+    pub alert: &'a Alert,
+    pub rows: &'a [Map<String, Value>],
+    pub start_time: Option<i64>,
+    pub end_time: i64,
+    pub evaluation_timestamp: i64,
+    pub message: &'a str,
+    pub headers: Option<&'a HashMap<String, String>>,
+}
+
+impl NotificationContext<'_> {
+    fn to_value(&self) -> Value {
+        let alert = self.alert;
+        json::json!({
+            "org_id": alert.org_id,
+            "org_name": self.org_name,
+            "alert": {
+                "id": alert.id.map(|id| id.to_string()),
+                "name": alert.name,
+                "type": if alert.is_real_time { "realtime" } else { "scheduled" },
+                "stream_type": alert.stream_type.to_string(),
+                "stream_name": alert.stream_name,
+                "description": alert.description,
+                "period": alert.trigger_condition.period,
+                "operator": alert.trigger_condition.operator.to_string(),
+                "threshold": alert.trigger_condition.threshold,
+                "labels": alert.context_attributes.clone().unwrap_or_default(),
+            },
+            "rows": self.rows,
+            "start_time": self.start_time,
+            "end_time": self.end_time,
+            "evaluation_timestamp": self.evaluation_timestamp,
+            "message": self.message,
+            "headers": self.headers.cloned().unwrap_or_default(),
+        })
+    }
+}
+
+/// Whether the requests of the destination are rendered by a VRL template, it
+/// doesn't need a template then
+pub fn is_rendered(dest_type: &DestinationType) -> bool {
+    matches!(dest_type, DestinationType::Http(endpoint) if endpoint.vrl_template.is_some())
+}
+
+/// Checks the VRL template compiles
+pub fn validate(org_id: &str, program: &str) -> Result<(), String> {
+    compile_vrl_function(program, org_id)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Renders the body and the headers of a notification
+pub fn render(
+    org_id: &str,
+    program: &str,
+    context: &NotificationContext<'_>,
+) -> Result<(String, HashMap<String, String>), anyhow::Error> {
+    let vrl = compile_vrl_function(program, org_id)
+        .map_err(|e| anyhow::anyhow!("VRL template compilation failed: {e}"))?;
+    let mut target = TargetValue {
+        value: context.to_value().into(),
+        metadata: vrl::value::Value::Object(Default::default()),
+        secrets: vrl::value::Secrets::new(),
+    };
+    let mut runtime = Runtime::default();
+    let result = runtime
+        .resolve(&mut target, &vrl.program, &TimeZone::Local)
+        .map_err(|e| anyhow::anyhow!("VRL template failed: {e}"))?;
+    let body: Value = result
+        .try_into()
+        .map_err(|e| anyhow::anyhow!("VRL template returned an invalid value: {e:?}"))?;
+    let event: Value = target
+        .value
+        .try_into()
+        .map_err(|e| anyhow::anyhow!("VRL template left an invalid event: {e:?}"))?;
+    Ok((body_text(body), headers(&event)))
+}
+
+fn body_text(body: Value) -> String {
+    match body {
+        Value::String(v) => v,
+        Value::Null => String::new(),
+        v => v.to_string(),
+    }
+}
+
+/// The headers left in `.headers`, a header set to null is removed
+fn headers(event: &Value) -> HashMap<String, String> {
+    let Some(headers) = event.get("headers").and_then(|v| v.as_object()) else {
+        return HashMap::new();
+    };
+    headers
+        .iter()
+        .filter_map(|(k, v)| match v {
+            Value::Null => None,
+            Value::String(v) => Some((k.clone(), v.clone())),
+            v => Some((k.clone(), v.to_string())),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert() -> Alert {
+        Alert {
+            org_id: "default".to_string(),
+            name: "high_latency".to_string(),
+            stream_name: "app".to_string(),
+            context_attributes: Some(HashMap::from([(
+                "severity".to_string(),
+                "critical".to_string(),
+            )])),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let alert = alert();
+        let rows = vec![
+            json::json!({"service": "api", "latency": 2.5})
+                .as_object()
+                .unwrap()
+                .clone(),
+            json::json!({"service": "web", "latency": 3})
+                .as_object()
+                .unwrap()
+                .clone(),
+        ];
+        let headers = HashMap::from([
+            ("Authorization".to_string(), "Bearer token".to_string()),
+            ("X-Remove".to_string(), "1".to_string()),
+        ]);
+        let context = NotificationContext {
+            org_name: "Default",
+            alert: &alert,
+            rows: &rows,
+            start_time: Some(1),
+            end_time: 2,
+            evaluation_timestamp: 3,
+            message: "",
+            headers: Some(&headers),
+        };
+        let program = r#"
+.headers."X-Severity" = .alert.labels.severity
+.headers."X-Remove" = null
+services = map_values(array!(.rows)) -> |row| { row.service }
+level = if length(array!(.rows)) > 1 { "many" } else { "one" }
+{"title": .alert.name, "services": services, "level": level}
+"#;
+        let (body, headers) = render("default", program, &context).unwrap();
+        let body: Value = json::from_str(&body).unwrap();
+        assert_eq!(
+            body,
+            json::json!({"title": "high_latency", "services": ["api", "web"], "level": "many"})
+        );
+        assert_eq!(headers.get("X-Severity").unwrap(), "critical");
+        assert_eq!(headers.get("Authorization").unwrap(), "Bearer token");
+        assert!(!headers.contains_key("X-Remove"));
+
+        let (body, _) = render("default", r#""alert " + string!(.alert.name)"#, &context).unwrap();
+        assert_eq!(body, "alert high_latency");
+        assert!(render("default", "{", &context).is_err());
+    }
+}
@@ -0,0 +1,440 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Async search
+//!
+//! A long search is submitted as a job and run in the background by the
+//! alert managers, one partition at a time. The job and its progress are kept
+//! in the meta store and the result of every partition is written to the
+//! object store, so the partial results can be read while the job runs, and a
+//! job whose node stopped is resumed by another node from the last finished
+//! partition. The job and its results are deleted once their TTL has passed.
+
+use std::ops::Range;
+
+use config::{
+    cluster::LOCAL_NODE,
+    get_config, ider,
+    meta::{
+        async_search::{AsyncSearchJob, AsyncSearchResultResponse, AsyncSearchStatus},
+        search::{self, SearchPartitionRequest},
+        stream::StreamType,
+    },
+    utils::{json, sql::is_aggregate_query, time::now_micros},
+};
+use infra::{dist_lock, storage};
+use once_cell::sync::Lazy;
+use tokio::sync::Mutex;
+
+use crate::service::db;
+
+/// Errors that can occur when interacting with async searches.
+#[derive(Debug, thiserror::Error)]
+pub enum AsyncSearchError {
+    #[error("{0}")]
+    InvalidRequest(String),
+
+    #[error("Async search not found")]
+    JobNotFound,
+
+    #[error(transparent)]
+    InfraError(#[from] infra::errors::Error),
+}
+
+const CLAIM_LOCK_KEY: &str = "/async_search/claim";
+
+/// Serializes the claims of the workers of this node, the dist lock is a no-op
+/// in local mode
+static CLAIM_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+pub async fn submit(
+    org_id: &str,
+    user_id: &str,
+    stream_type: StreamType,
+    req: search::Request,
+) -> Result<AsyncSearchJob, AsyncSearchError> {
+    if req.query.sql.trim().is_empty() {
+        return Err(AsyncSearchError::InvalidRequest(
+            "sql is required".to_string(),
+        ));
+    }
+    if req.query.start_time >= req.query.end_time {
+        return Err(AsyncSearchError::InvalidRequest(
+            "start_time must be before end_time".to_string(),
+        ));
+    }
+    let now = now_micros();
+    let job = AsyncSearchJob {
+        id: ider::uuid(),
+        org_id: org_id.to_string(),
+        user_id: user_id.to_string(),
+        stream_type,
+        request: req,
+        status: AsyncSearchStatus::Pending,
+        created_at: now,
+        updated_at: now,
+        ..Default::default()
+    };
+    db::async_search::set(&job).await?;
+    Ok(job)
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<AsyncSearchJob, AsyncSearchError> {
+    db::async_search::get(org_id, id)
+        .await
+        .map_err(|_| AsyncSearchError::JobNotFound)
+}
+
+/// Returns the rows found so far, `from` and `size` default to the ones of the
+/// submitted request. Only the results of the partitions holding the page are
+/// read, so are the scan stats of the response.
+pub async fn get_result(
+    org_id: &str,
+    id: &str,
+    from: Option<i64>,
+    size: Option<i64>,
+) -> Result<AsyncSearchResultResponse, AsyncSearchError> {
+    let job = get(org_id, id).await?;
+    let from = from.unwrap_or(job.request.query.from).max(0);
+    let size = size
+        .or(Some(job.request.query.size))
+        .filter(|size| *size > 0)
+        .unwrap_or(get_config().limit.query_default_limit);
+
+    // the jobs stored before the rows of every partition were counted read
+    // all of them
+    let (partitions, skip) = if job.partition_hits.len() == job.partitions_done {
+        page_partitions(&job.partition_hits, from as usize, size as usize)
+    } else {
+        (0..job.partitions_done, from as usize)
+    };
+    let mut response = search::Response::default();
+    for partition in partitions {
+        let buf = storage::get_bytes("", &result_path(&job, partition)).await?;
+        let res: search::Response = json::from_slice(&buf).map_err(infra::errors::Error::from)?;
+        merge_response(&mut response, res);
+    }
+    response.total = response.hits.len();
+    response.pagination(skip as i64, size);
+    response.from = from;
+    response.total = job.hits;
+    response.is_partial = job.status != AsyncSearchStatus::Finished;
+    if let Some(error) = job.error.as_ref() {
+        response.function_error.push(error.clone());
+    }
+
+    Ok(AsyncSearchResultResponse {
+        status: job.status,
+        is_partial: response.is_partial,
+        partitions: job.partitions.len(),
+        partitions_done: job.partitions_done,
+        response,
+    })
+}
+
+/// Cancels the job, if it is running, and deletes it with its results
+pub async fn delete(org_id: &str, id: &str) -> Result<(), AsyncSearchError> {
+    let job = get(org_id, id).await?;
+    db::async_search::delete(org_id, id).await?;
+    delete_results(&job).await;
+    Ok(())
+}
+
+/// Deletes the jobs whose TTL has passed
+pub async fn delete_expired() -> Result<(), anyhow::Error> {
+    let now = now_micros();
+    for job in db::async_search::list().await? {
+        if job.expires_at > 0 && job.expires_at < now {
+            db::async_search::delete(&job.org_id, &job.id).await?;
+            delete_results(&job).await;
+            log::info!("[ASYNC SEARCH] job {}/{} expired", job.org_id, job.id);
+        }
+    }
+    Ok(())
+}
+
+/// Claims a job and runs its remaining partitions
+pub async fn run(worker: i64) -> Result<(), anyhow::Error> {
+    let Some(mut job) = claim().await? else {
+        return Ok(());
+    };
+    let start = std::time::Instant::now();
+    log::info!(
+        "[ASYNC SEARCH {worker}] job {}/{} start running from partition {}",
+        job.org_id,
+        job.id,
+        job.partitions_done
+    );
+    if let Err(e) = run_job(&mut job).await {
+        log::error!(
+            "[ASYNC SEARCH {worker}] job {}/{} failed: {e}",
+            job.org_id,
+            job.id
+        );
+        job.status = AsyncSearchStatus::Failed;
+        job.error = Some(e.to_string());
+        finish(&mut job).await?;
+        return Ok(());
+    }
+    log::info!(
+        "[ASYNC SEARCH {worker}] job {}/{} {:?}, hits: {}, took: {} ms",
+        job.org_id,
+        job.id,
+        job.status,
+        job.hits,
+        start.elapsed().as_millis()
+    );
+    Ok(())
+}
+
+/// Takes the oldest pending job, or a running job not updated since its node
+/// stopped
+async fn claim() -> Result<Option<AsyncSearchJob>, anyhow::Error> {
+    let now = now_micros();
+    let stale_before = now - stale_after();
+    let claimable = |job: &AsyncSearchJob| {
+        job.status == AsyncSearchStatus::Pending
+            || (job.status == AsyncSearchStatus::Running && job.updated_at < stale_before)
+    };
+    let Some(candidate) = db::async_search::list()
+        .await?
+        .into_iter()
+        .find(|job| claimable(job))
+    else {
+        return Ok(None);
+    };
+
+    let _guard = CLAIM_LOCK.lock().await;
+    let locker = dist_lock::lock(CLAIM_LOCK_KEY, 0).await?;
+    // the job may have been claimed or deleted before the lock was taken
+    let ret = match db::async_search::get(&candidate.org_id, &candidate.id).await {
+        Ok(mut job) if claimable(&job) => {
+            job.status = AsyncSearchStatus::Running;
+            job.node = LOCAL_NODE.name.clone();
+            job.updated_at = now;
+            db::async_search::set(&job).await.map(|_| Some(job))
+        }
+        _ => Ok(None),
+    };
+    dist_lock::unlock(&locker).await?;
+    Ok(ret?)
+}
+
+async fn run_job(job: &mut AsyncSearchJob) -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    let trace_id = ider::generate_trace_id();
+    if job.partitions.is_empty() {
+        job.partitions = plan_partitions(&trace_id, job).await?;
+        if !save_progress(job).await? {
+            return Ok(());
+        }
+    }
+
+    while job.partitions_done < job.partitions.len() {
+        let need = job.remaining(cfg.limit.query_default_limit);
+        if need <= 0 {
+            break;
+        }
+        let [start_time, end_time] = job.partitions[job.partitions_done];
+        let mut req = job.request.clone();
+        req.query.start_time = start_time;
+        req.query.end_time = end_time;
+        req.query.from = 0;
+        req.query.size = need;
+        let res = crate::service::search::search(
+            &trace_id,
+            &job.org_id,
+            job.stream_type,
+            Some(job.user_id.clone()),
+            &req,
+        )
+        .await?;
+
+        let path = result_path(job, job.partitions_done);
+        storage::put("", &path, json::to_vec(&res)?.into()).await?;
+        job.partitions_done += 1;
+        job.hits += res.hits.len();
+        job.partition_hits.push(res.hits.len());
+        if !save_progress(job).await? {
+            // the job was deleted while the partition ran
+            if let Err(e) = storage::del(vec![("", path.as_str())]).await {
+                log::error!("[ASYNC SEARCH] failed to delete result {path}: {e}");
+            }
+            return Ok(());
+        }
+    }
+
+    job.status = AsyncSearchStatus::Finished;
+    finish(job).await
+}
+
+/// Time ranges to search one after the other. Aggregations are searched at
+/// once, as their partial results can not be appended.
+async fn plan_partitions(
+    trace_id: &str,
+    job: &AsyncSearchJob,
+) -> Result<Vec<[i64; 2]>, anyhow::Error> {
+    let query = &job.request.query;
+    let whole = vec![[query.start_time, query.end_time]];
+    if is_aggregate_query(&query.sql).unwrap_or(true) {
+        return Ok(whole);
+    }
+    let res = crate::service::search::search_partition(
+        trace_id,
+        &job.org_id,
+        Some(&job.user_id),
+        job.stream_type,
+        &SearchPartitionRequest::from(&job.request),
+        false,
+        false,
+        false,
+        false,
+    )
+    .await?;
+    if res.partitions.is_empty() || res.streaming_aggs {
+        return Ok(whole);
+    }
+    Ok(res.partitions)
+}
+
+/// Saves the progress of a running job, returns false when the job was
+/// deleted, or taken over by another node, and must stop
+async fn save_progress(job: &mut AsyncSearchJob) -> Result<bool, anyhow::Error> {
+    match db::async_search::get(&job.org_id, &job.id).await {
+        Ok(current) if current.status == AsyncSearchStatus::Running && current.node == job.node => {
+        }
+        _ => return Ok(false),
+    }
+    job.updated_at = now_micros();
+    db::async_search::set(job).await?;
+    Ok(true)
+}
+
+async fn finish(job: &mut AsyncSearchJob) -> Result<(), anyhow::Error> {
+    let now = now_micros();
+    job.expires_at = now + get_config().limit.async_search_result_ttl * 3600 * 1_000_000;
+    match db::async_search::get(&job.org_id, &job.id).await {
+        Ok(current) if current.status == AsyncSearchStatus::Running && current.node == job.node => {
+        }
+        _ => return Ok(()),
+    }
+    job.updated_at = now;
+    db::async_search::set(job).await?;
+    Ok(())
+}
+
+async fn delete_results(job: &AsyncSearchJob) {
+    let paths = (0..job.partitions.len())
+        .map(|partition| result_path(job, partition))
+        .collect::<Vec<_>>();
+    if paths.is_empty() {
+        return;
+    }
+    if let Err(e) = storage::del(paths.iter().map(|p| ("", p.as_str())).collect()).await {
+        log::error!(
+            "[ASYNC SEARCH] failed to delete results of job {}/{}: {e}",
+            job.org_id,
+            job.id
+        );
+    }
+}
+
+/// A running job is taken over when it was not updated for this long, in
+/// microseconds. Every partition must finish within the query timeout.
+fn stale_after() -> i64 {
+    (get_config().limit.query_timeout as i64 * 2).max(60) * 1_000_000
+}
+
+fn result_path(job: &AsyncSearchJob, partition: usize) -> String {
+    format!("async_search/{}/{}/{partition}.json", job.org_id, job.id)
+}
+
+/// The partitions holding the rows `from..from + size`, and the rows to skip
+/// in the first of them
+fn page_partitions(partition_hits: &[usize], from: usize, size: usize) -> (Range<usize>, usize) {
+    let mut first = None;
+    let mut skip = 0;
+    let mut end = partition_hits.len();
+    let mut before = 0;
+    for (i, hits) in partition_hits.iter().enumerate() {
+        let rows = before + hits;
+        if first.is_none() && rows > from {
+            first = Some(i);
+            skip = from - before;
+        }
+        if rows >= from + size {
+            end = i + 1;
+            break;
+        }
+        before = rows;
+    }
+    let first = first.unwrap_or(partition_hits.len());
+    (first..end.max(first), skip)
+}
+
+fn merge_response(resp: &mut search::Response, res: search::Response) {
+    resp.took += res.took;
+    resp.took_detail.add(&res.took_detail);
+    resp.hits.extend(res.hits);
+    resp.scan_files += res.scan_files;
+    resp.scan_size += res.scan_size;
+    resp.idx_scan_size += res.idx_scan_size;
+    resp.scan_records += res.scan_records;
+    resp.function_error.extend(res.function_error);
+    if resp.columns.is_empty() {
+        resp.columns = res.columns;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_response() {
+        let mut resp = search::Response::default();
+        for i in 0..2 {
+            let mut res = search::Response::default();
+            res.add_hit(&json::json!({"n": i}));
+            res.scan_size = 10;
+            merge_response(&mut resp, res);
+        }
+        resp.total = resp.hits.len();
+        assert_eq!(resp.scan_size, 20);
+        resp.pagination(1, 10);
+        assert_eq!(resp.hits, vec![json::json!({"n": 1})]);
+    }
+
+    #[test]
+    fn test_page_partitions() {
+        let hits = [3, 0, 4, 2];
+        assert_eq!(page_partitions(&hits, 0, 2), (0..1, 0));
+        assert_eq!(page_partitions(&hits, 2, 3), (0..3, 2));
+        assert_eq!(page_partitions(&hits, 3, 4), (2..3, 0));
+        assert_eq!(page_partitions(&hits, 5, 100), (2..4, 2));
+        assert_eq!(page_partitions(&hits, 9, 10), (4..4, 0));
+        assert_eq!(page_partitions(&[], 0, 10), (0..0, 0));
+    }
+
+    #[test]
+    fn test_result_path() {
+        let job = AsyncSearchJob {
+            id: "job1".to_string(),
+            org_id: "default".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(result_path(&job, 3), "async_search/default/job1/3.json");
+    }
+}
@@ -416,6 +416,44 @@ pub async fn delete_by_time_range(
     Ok(())
 }
 
+/// The data files of the stream recorded in the dumped file list whose hour
+/// is in the time range, as (account, key)
+pub async fn list_files(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    range: (i64, i64),
+) -> Result<Vec<(String, String)>, errors::Error> {
+    let cfg = get_config();
+    if !cfg.compact.file_list_dump_enabled {
+        return Ok(vec![]);
+    }
+    let dump_stream_name = generate_dump_stream_name(stream_type, stream_name);
+    let list =
+        infra::file_list::query_for_dump(org_id, StreamType::Filelist, &dump_stream_name, range)
+            .await?;
+    if list.is_empty() {
+        return Ok(vec![]);
+    }
+    let dump_files = list.iter().map(|f| f.into()).collect::<Vec<_>>();
+    let query = "SELECT * FROM file_list";
+    let trace_id = config::ider::generate_trace_id();
+    let ret = exec(&trace_id, cfg.limit.cpu_num, dump_files, query).await?;
+    let start_date = get_ymdh_from_micros(range.0);
+    let end_date = get_ymdh_from_micros(range.1);
+    Ok(ret
+        .into_iter()
+        .flat_map(record_batch_to_file_record)
+        .filter(|f| !f.deleted && f.date >= start_date && f.date <= end_date)
+        .map(|f| {
+            (
+                f.account,
+                format!("files/{}/{}/{}", f.stream, f.date, f.file),
+            )
+        })
+        .collect())
+}
+
 async fn delete_daily_inner(
     org_id: &str,
     list: Vec<FileRecord>,
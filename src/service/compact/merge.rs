@@ -46,7 +46,8 @@ use infra::{
     dist_lock, file_list as infra_file_list,
     runtime::DATAFUSION_RUNTIME,
     schema::{
-        SchemaCache, get_stream_setting_bloom_filter_fields, get_stream_setting_fts_fields,
+        SchemaCache, get_stream_setting_bloom_filter_fields, get_stream_setting_compact_sort,
+        get_stream_setting_fts_fields, get_stream_setting_index_bloom_filter_fields,
         get_stream_setting_index_fields, unwrap_partition_time_level, unwrap_stream_created_at,
         unwrap_stream_settings,
     },
@@ -440,108 +441,121 @@ pub async fn merge_by_stream(
             offset_time.format("%Y/%m/%d/%H").to_string(),
         )
     };
-    let files =
-        file_list::query_for_merge(org_id, stream_type, stream_name, &date_start, &date_end)
-            .await
-            .map_err(|e| anyhow::anyhow!("query file list failed: {}", e))?;
+    // the rewrites of the files of the partition take the same lock
+    let merged = super::with_partition_lock(org_id, stream_type, stream_name, &date_start, async {
+        let files =
+            file_list::query_for_merge(org_id, stream_type, stream_name, &date_start, &date_end)
+                .await
+                .map_err(|e| anyhow::anyhow!("query file list failed: {}", e))?;
 
-    log::debug!(
-        "[COMPACTOR] merge_by_stream [{}/{}/{}] date range: [{},{}], files: {}",
-        org_id,
-        stream_type,
-        stream_name,
-        date_start,
-        date_end,
-        files.len(),
-    );
+        log::debug!(
+            "[COMPACTOR] merge_by_stream [{}/{}/{}] date range: [{},{}], files: {}",
+            org_id,
+            stream_type,
+            stream_name,
+            date_start,
+            date_end,
+            files.len(),
+        );
 
-    if files.is_empty() {
-        // update job status
-        if let Err(e) = infra_file_list::set_job_done(&[job_id]).await {
-            log::error!("[COMPACTOR] set_job_done failed: {e}");
+        if files.is_empty() {
+            return Ok(false);
         }
-        return Ok(());
-    }
 
-    // do partition by partition key
-    let mut partition_files_with_size: HashMap<String, Vec<FileKey>> = HashMap::default();
-    for file in files {
-        // skip the files which already reach the max_file_size * 95%
-        if file.meta.original_size > cfg.compact.max_file_size as i64 * 95 / 100 {
-            continue;
+        // do partition by partition key
+        let mut partition_files_with_size: HashMap<String, Vec<FileKey>> = HashMap::default();
+        for file in files {
+            // skip the files which already reach the max_file_size * 95%
+            if file.meta.original_size > cfg.compact.max_file_size as i64 * 95 / 100 {
+                continue;
+            }
+            let file_name = file.key.clone();
+            let prefix = file_name[..file_name.rfind('/').unwrap()].to_string();
+            let partition = partition_files_with_size.entry(prefix).or_default();
+            partition.push(file.to_owned());
         }
-        let file_name = file.key.clone();
-        let prefix = file_name[..file_name.rfind('/').unwrap()].to_string();
-        let partition = partition_files_with_size.entry(prefix).or_default();
-        partition.push(file.to_owned());
-    }
 
-    // use multiple threads to merge
-    let semaphore = std::sync::Arc::new(Semaphore::new(cfg.limit.file_merge_thread_num));
-    let mut tasks = Vec::with_capacity(partition_files_with_size.len());
-    for (prefix, mut files_with_size) in partition_files_with_size.into_iter() {
-        let org_id = org_id.to_string();
-        let stream_name = stream_name.to_string();
-        let permit = semaphore.clone().acquire_owned().await.unwrap();
-        let worker_tx = worker_tx.clone();
-        let task: JoinHandle<Result<(), anyhow::Error>> = tokio::task::spawn(async move {
-            let cfg = get_config();
-            // sort by file size
-            let job_strategy = MergeStrategy::from(&cfg.compact.strategy);
-            match job_strategy {
-                MergeStrategy::FileSize => {
-                    files_with_size.sort_by(|a, b| a.meta.original_size.cmp(&b.meta.original_size));
-                }
-                MergeStrategy::FileTime => {
-                    files_with_size.sort_by(|a, b| a.meta.min_ts.cmp(&b.meta.min_ts));
-                }
-                MergeStrategy::TimeRange => {
-                    files_with_size = sort_by_time_range(files_with_size);
+        // use multiple threads to merge
+        let semaphore = std::sync::Arc::new(Semaphore::new(cfg.limit.file_merge_thread_num));
+        let mut tasks = Vec::with_capacity(partition_files_with_size.len());
+        for (prefix, mut files_with_size) in partition_files_with_size.into_iter() {
+            let org_id = org_id.to_string();
+            let stream_name = stream_name.to_string();
+            let permit = semaphore.clone().acquire_owned().await.unwrap();
+            let worker_tx = worker_tx.clone();
+            let task: JoinHandle<Result<(), anyhow::Error>> = tokio::task::spawn(async move {
+                let cfg = get_config();
+                // sort by file size
+                let job_strategy = MergeStrategy::from(&cfg.compact.strategy);
+                match job_strategy {
+                    MergeStrategy::FileSize => {
+                        files_with_size.sort_by(|a, b| a.meta.original_size.cmp(&b.meta.original_size));
+                    }
+                    MergeStrategy::FileTime => {
+                        files_with_size.sort_by(|a, b| a.meta.min_ts.cmp(&b.meta.min_ts));
+                    }
+                    MergeStrategy::TimeRange => {
+                        files_with_size = sort_by_time_range(files_with_size);
+                    }
                 }
-            }
 
-            #[cfg(feature = "enterprise")]
-            let skip_group_files = stream_type == StreamType::Metrics
-                && get_largest_downsampling_rule(
-                    &stream_name,
-                    files_with_size.iter().map(|f| f.meta.max_ts).max().unwrap(),
-                )
-                .is_some();
+                #[cfg(feature = "enterprise")]
+                let skip_group_files = stream_type == StreamType::Metrics
+                    && get_largest_downsampling_rule(
+                        &stream_name,
+                        files_with_size.iter().map(|f| f.meta.max_ts).max().unwrap(),
+                    )
+                    .is_some();
 
-            #[cfg(not(feature = "enterprise"))]
-            let skip_group_files = false;
+                #[cfg(not(feature = "enterprise"))]
+                let skip_group_files = false;
 
-            if files_with_size.len() <= 1 && !skip_group_files {
-                return Ok(());
-            }
+                if files_with_size.len() <= 1 && !skip_group_files {
+                    return Ok(());
+                }
 
-            // group files need to merge
-            let mut batch_groups = Vec::new();
-            if skip_group_files {
-                batch_groups.push(MergeBatch {
-                    batch_id: 0,
-                    org_id: org_id.clone(),
-                    stream_type,
-                    stream_name: stream_name.clone(),
-                    prefix: prefix.clone(),
-                    files: files_with_size.clone(),
-                });
-            } else {
-                let mut new_file_list = Vec::new();
-                let mut new_file_size = 0;
-                for file in files_with_size.iter() {
-                    if new_file_size + file.meta.original_size > cfg.compact.max_file_size as i64
-                        || (cfg.compact.max_group_files > 0
-                            && new_file_list.len() >= cfg.compact.max_group_files)
-                    {
-                        if new_file_list.len() <= 1 {
-                            if job_strategy == MergeStrategy::FileSize {
-                                break;
+                // group files need to merge
+                let mut batch_groups = Vec::new();
+                if skip_group_files {
+                    batch_groups.push(MergeBatch {
+                        batch_id: 0,
+                        org_id: org_id.clone(),
+                        stream_type,
+                        stream_name: stream_name.clone(),
+                        prefix: prefix.clone(),
+                        files: files_with_size.clone(),
+                    });
+                } else {
+                    let mut new_file_list = Vec::new();
+                    let mut new_file_size = 0;
+                    for file in files_with_size.iter() {
+                        if new_file_size + file.meta.original_size > cfg.compact.max_file_size as i64
+                            || (cfg.compact.max_group_files > 0
+                                && new_file_list.len() >= cfg.compact.max_group_files)
+                        {
+                            if new_file_list.len() <= 1 {
+                                if job_strategy == MergeStrategy::FileSize {
+                                    break;
+                                }
+                                new_file_size = 0;
+                                new_file_list.clear();
+                                continue; // this batch don't need to merge, skip
                             }
+                            batch_groups.push(MergeBatch {
+                                batch_id: batch_groups.len(),
+                                org_id: org_id.clone(),
+                                stream_type,
+                                stream_name: stream_name.clone(),
+                                prefix: prefix.clone(),
+                                files: new_file_list.clone(),
+                            });
                             new_file_size = 0;
                             new_file_list.clear();
-                            continue; // this batch don't need to merge, skip
                         }
+                        new_file_size += file.meta.original_size;
+                        new_file_list.push(file.clone());
+                    }
+                    if new_file_list.len() > 1 {
                         batch_groups.push(MergeBatch {
                             batch_id: batch_groups.len(),
                             org_id: org_id.clone(),
@@ -550,105 +564,96 @@ pub async fn merge_by_stream(
                             prefix: prefix.clone(),
                             files: new_file_list.clone(),
                         });
-                        new_file_size = 0;
-                        new_file_list.clear();
                     }
-                    new_file_size += file.meta.original_size;
-                    new_file_list.push(file.clone());
-                }
-                if new_file_list.len() > 1 {
-                    batch_groups.push(MergeBatch {
-                        batch_id: batch_groups.len(),
-                        org_id: org_id.clone(),
-                        stream_type,
-                        stream_name: stream_name.clone(),
-                        prefix: prefix.clone(),
-                        files: new_file_list.clone(),
-                    });
-                }
 
-                if batch_groups.is_empty() {
-                    return Ok(()); // no files need to merge
+                    if batch_groups.is_empty() {
+                        return Ok(()); // no files need to merge
+                    }
                 }
-            }
 
-            // send to worker
-            let batch_group_len = batch_groups.len();
-            let (inner_tx, mut inner_rx) = mpsc::channel(batch_group_len);
-            for batch in batch_groups.iter() {
-                if let Err(e) = worker_tx.send((inner_tx.clone(), batch.clone())).await {
-                    log::error!("[COMPACTOR] send batch to worker failed: {e}");
-                    return Err(anyhow::Error::msg("send batch to worker failed"));
+                // send to worker
+                let batch_group_len = batch_groups.len();
+                let (inner_tx, mut inner_rx) = mpsc::channel(batch_group_len);
+                for batch in batch_groups.iter() {
+                    if let Err(e) = worker_tx.send((inner_tx.clone(), batch.clone())).await {
+                        log::error!("[COMPACTOR] send batch to worker failed: {e}");
+                        return Err(anyhow::Error::msg("send batch to worker failed"));
+                    }
                 }
-            }
-            let mut worker_results = Vec::with_capacity(batch_group_len);
-            for _ in 0..batch_group_len {
-                let result = inner_rx.recv().await.unwrap();
-                worker_results.push(result);
-            }
+                let mut worker_results = Vec::with_capacity(batch_group_len);
+                for _ in 0..batch_group_len {
+                    let result = inner_rx.recv().await.unwrap();
+                    worker_results.push(result);
+                }
+
+                let mut last_error = None;
+                let mut check_guard = HashSet::with_capacity(batch_groups.len());
+                for ret in worker_results {
+                    let (batch_id, new_files) = match ret {
+                        Ok(v) => v,
+                        Err(e) => {
+                            log::error!("[COMPACTOR] merge files failed: {e}");
+                            last_error = Some(e);
+                            continue;
+                        }
+                    };
 
-            let mut last_error = None;
-            let mut check_guard = HashSet::with_capacity(batch_groups.len());
-            for ret in worker_results {
-                let (batch_id, new_files) = match ret {
-                    Ok(v) => v,
-                    Err(e) => {
-                        log::error!("[COMPACTOR] merge files failed: {e}");
-                        last_error = Some(e);
+                    if check_guard.contains(&batch_id) {
+                        log::warn!(
+                            "[COMPACTOR] merge files for stream: [{org_id}/{stream_type}/{stream_name}] found error files, batch_id: {batch_id} duplicate"
+                        );
                         continue;
                     }
-                };
+                    check_guard.insert(batch_id);
+
+                    // delete small files keys & write big files keys, use transaction
+                    let delete_file_list = batch_groups.get(batch_id).unwrap().files.as_slice();
+                    let mut events = Vec::with_capacity(new_files.len() + delete_file_list.len());
+                    for new_file in new_files {
+                        if !new_file.key.is_empty() {
+                            events.push(new_file);
+                        }
+                    }
 
-                if check_guard.contains(&batch_id) {
-                    log::warn!(
-                        "[COMPACTOR] merge files for stream: [{org_id}/{stream_type}/{stream_name}] found error files, batch_id: {batch_id} duplicate"
-                    );
-                    continue;
-                }
-                check_guard.insert(batch_id);
-
-                // delete small files keys & write big files keys, use transaction
-                let delete_file_list = batch_groups.get(batch_id).unwrap().files.as_slice();
-                let mut events = Vec::with_capacity(new_files.len() + delete_file_list.len());
-                for new_file in new_files {
-                    if !new_file.key.is_empty() {
-                        events.push(new_file);
+                    for file in delete_file_list {
+                        events.push(FileKey {
+                            deleted: true,
+                            segment_ids: None,
+                            ..file.clone()
+                        });
                     }
-                }
+                    events.sort_by(|a, b| a.key.cmp(&b.key));
 
-                for file in delete_file_list {
-                    events.push(FileKey {
-                        deleted: true,
-                        segment_ids: None,
-                        ..file.clone()
-                    });
+                    // write file list to storage
+                    if let Err(e) = write_file_list(&org_id, stream_type, &events).await {
+                        log::error!("[COMPACTOR] write file list failed: {e}");
+                        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                        continue;
+                    }
                 }
-                events.sort_by(|a, b| a.key.cmp(&b.key));
-
-                // write file list to storage
-                if let Err(e) = write_file_list(&org_id, stream_type, &events).await {
-                    log::error!("[COMPACTOR] write file list failed: {e}");
-                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-                    continue;
+                drop(permit);
+                if let Some(e) = last_error {
+                    return Err(e);
                 }
-            }
-            drop(permit);
-            if let Some(e) = last_error {
-                return Err(e);
-            }
-            Ok(())
-        });
-        tasks.push(task);
-    }
+                Ok(())
+            });
+            tasks.push(task);
+        }
 
-    for task in tasks {
-        task.await??;
-    }
+        for task in tasks {
+            task.await??;
+        }
+        Ok(true)
+    })
+    .await?;
 
     // update job status
     if let Err(e) = infra_file_list::set_job_done(&[job_id]).await {
         log::error!("[COMPACTOR] set_job_done failed: {e}");
     }
+    if !merged {
+        return Ok(()); // no files
+    }
 
     // metrics
     let time = start.elapsed().as_secs_f64();
@@ -768,8 +773,15 @@ pub async fn merge_files(
     let latest_schema = infra::schema::get(org_id, stream_name, stream_type).await?;
     let stream_settings = infra::schema::unwrap_stream_settings(&latest_schema);
     let bloom_filter_fields = get_stream_setting_bloom_filter_fields(&stream_settings);
+    let compact_sort = get_stream_setting_compact_sort(&stream_settings);
+    // the files merged under a removed compact sort may still be sorted by its
+    // fields
+    let sorted_by_time = stream_settings
+        .as_ref()
+        .is_none_or(|s| s.files_sorted_by_time(min_ts));
     let full_text_search_fields = get_stream_setting_fts_fields(&stream_settings);
     let index_fields = get_stream_setting_index_fields(&stream_settings);
+    let index_bloom_filter_fields = get_stream_setting_index_bloom_filter_fields(&stream_settings);
     let (defined_schema_fields, need_original, index_original_data, index_all_values) =
         match stream_settings {
             Some(s) => (
@@ -837,8 +849,9 @@ pub async fn merge_files(
         target_partitions: 2,
     };
 
+    // the files of the streams sorted by other fields aren't sorted by time
     let table = match TableBuilder::new()
-        .sorted_by_time(true)
+        .sorted_by_time(sorted_by_time)
         .build(session, files.clone(), latest_schema.clone())
         .await
     {
@@ -865,6 +878,7 @@ pub async fn merge_files(
                     &bloom_filter_fields,
                     &new_file_meta,
                     false,
+                    compact_sort.as_ref(),
                 )
                 .await
             })
@@ -932,6 +946,7 @@ pub async fn merge_files(
     let need_index = full_text_search_fields
         .iter()
         .chain(index_fields.iter())
+        .chain(index_bloom_filter_fields.iter())
         .any(|f| latest_schema_fields.contains(f));
     if !need_index {
         log::debug!("skip index generation for stream: {org_id}/{stream_type}/{stream_name}");
@@ -977,6 +992,7 @@ pub async fn merge_files(
                     &new_file_key,
                     &full_text_search_fields,
                     &index_fields,
+                    &index_bloom_filter_fields,
                     &retain_file_list,
                     &mut new_file_meta,
                     stream_schema_for_index.clone(),
@@ -1018,6 +1034,7 @@ pub async fn merge_files(
                         &new_file_key,
                         &full_text_search_fields,
                         &index_fields,
+                        &index_bloom_filter_fields,
                         &retain_file_list,
                         &mut new_file_meta,
                         stream_schema_for_index.clone(),
@@ -1049,6 +1066,7 @@ async fn generate_inverted_index(
     new_file_key: &str,
     full_text_search_fields: &[String],
     index_fields: &[String],
+    bloom_filter_fields: &[String],
     retain_file_list: &[FileKey],
     new_file_meta: &mut FileMeta,
     latest_schema: Arc<Schema>,
@@ -1060,6 +1078,7 @@ async fn generate_inverted_index(
         new_file_key,
         full_text_search_fields,
         index_fields,
+        bloom_filter_fields,
         latest_schema, // Use stream schema to include all configured fields
         reader,
     )
@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use chrono::{Datelike, Duration, TimeZone, Timelike, Utc};
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
 use config::{
     COMPACT_OLD_DATA_STREAM_SET,
     cluster::LOCAL_NODE,
@@ -22,10 +22,11 @@ use config::{
         cluster::{CompactionJobType, Role},
         stream::{ALL_STREAM_TYPES, PartitionTimeLevel, StreamType},
     },
+    utils::time::{day_micros, hour_micros},
 };
 use infra::{
     cluster::get_node_from_consistent_hash,
-    file_list as infra_file_list,
+    dist_lock, file_list as infra_file_list,
     schema::{get_settings, unwrap_partition_time_level},
 };
 #[cfg(feature = "enterprise")]
@@ -38,8 +39,11 @@ pub mod deleted;
 pub mod dump;
 pub mod flatten;
 pub mod merge;
+pub mod record_deletion;
 pub mod retention;
+pub mod rollup;
 pub mod stats;
+pub mod tiering;
 pub mod worker;
 
 /// compactor retention run steps:
@@ -445,3 +449,73 @@ pub async fn run_delay_deletion() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+/// Runs `f` holding the lock of a partition of the stream. The merge of the
+/// partition and the rewrites of its files take it, so neither replaces the
+/// files the other one is working on
+pub(crate) async fn with_partition_lock<T, F>(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    partition: &str,
+    f: F,
+) -> Result<T, anyhow::Error>
+where
+    F: Future<Output = Result<T, anyhow::Error>>,
+{
+    let lock_key = format!("/compact/partition/{org_id}/{stream_type}/{stream_name}/{partition}");
+    let local_lock = infra::local_lock::lock(&lock_key).await?;
+    let _guard = local_lock.lock().await;
+    let locker = dist_lock::lock(&lock_key, 0).await?;
+    let ret = f.await;
+    dist_lock::unlock(&locker).await?;
+    ret
+}
+
+/// The partition of a file, the `YYYY/MM/DD/HH` part of its key
+pub(crate) fn partition_key(file_key: &str) -> String {
+    let columns: Vec<_> = file_key.split('/').collect();
+    format!(
+        "{}/{}/{}/{}",
+        columns[4], columns[5], columns[6], columns[7]
+    )
+}
+
+/// The time range of a partition, its hour or its day
+pub(crate) fn partition_time_range(
+    partition: &str,
+    partition_time_level: PartitionTimeLevel,
+) -> Result<(i64, i64), anyhow::Error> {
+    let start =
+        DateTime::parse_from_str(&format!("{partition}/00/00+0000"), "%Y/%m/%d/%H/%M/%S%z")?
+            .timestamp_micros();
+    let unit = if partition_time_level == PartitionTimeLevel::Daily {
+        day_micros(1)
+    } else {
+        hour_micros(1)
+    };
+    Ok((start, start + unit - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition_time_range() {
+        let partition = "2024/03/05/07";
+        let start = Utc
+            .with_ymd_and_hms(2024, 3, 5, 7, 0, 0)
+            .unwrap()
+            .timestamp_micros();
+        assert_eq!(
+            partition_time_range(partition, PartitionTimeLevel::Hourly).unwrap(),
+            (start, start + hour_micros(1) - 1)
+        );
+        let start = start - hour_micros(7);
+        assert_eq!(
+            partition_time_range("2024/03/05/00", PartitionTimeLevel::Daily).unwrap(),
+            (start, start + day_micros(1) - 1)
+        );
+    }
+}
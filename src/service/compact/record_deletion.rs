@@ -0,0 +1,304 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runs the record deletion jobs, see [`crate::service::record_deletion`].
+//!
+//! A job scans the files of its time range, each file holding matching records
+//! is replaced by a copy without them. The partition being rewritten is locked,
+//! the compactor doesn't merge its files meanwhile. The files only known from
+//! the dumped file list can't be rewritten, they are counted as skipped.
+
+use std::{collections::BTreeSet, sync::Arc};
+
+use arrow::{
+    array::{Array, BooleanArray, Int64Array},
+    compute::filter_record_batch,
+    record_batch::RecordBatch,
+};
+use config::{
+    TIMESTAMP_COL_NAME,
+    cluster::LOCAL_NODE,
+    get_config,
+    meta::{
+        cluster::Role,
+        record_deletion::{RecordDeletion, RecordDeletionStatus},
+        stream::{FileKey, PartitionTimeLevel},
+    },
+    utils::time::now_micros,
+};
+use datafusion::{common::DFSchema, prelude::SessionContext};
+use hashbrown::HashSet;
+use infra::cluster::get_node_from_consistent_hash;
+
+use super::{
+    partition_key,
+    retention::{Rewrite, delete_cached_results, rewrite_file, write_file_list},
+    with_partition_lock,
+};
+use crate::service::{db, file_list, record_deletion::parse_filter};
+
+/// Files rewritten between two saves of the progress of the job
+const SAVE_EVERY_FILES: usize = 20;
+
+/// Runs the pending jobs of this node, and the running ones whose node
+/// stopped updating them
+pub async fn run() -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    let stale_before = now_micros() - cfg.compact.job_run_timeout * 1_000_000;
+    for (org_id, mut job) in db::record_deletion::list_all().await? {
+        let runnable = match job.status {
+            RecordDeletionStatus::Pending => true,
+            RecordDeletionStatus::Running => job.updated_at < stale_before,
+            _ => false,
+        };
+        if !runnable {
+            continue;
+        }
+        let Some(node_name) = get_node_from_consistent_hash(&job.id, &Role::Compactor, None).await
+        else {
+            continue; // no compactor node
+        };
+        if LOCAL_NODE.name.ne(&node_name) {
+            continue; // not this node
+        }
+
+        log::info!(
+            "[COMPACTOR] record deletion {org_id}/{} started on {}/{}/{}",
+            job.id,
+            job.request.stream_type,
+            job.request.stream_name,
+            job.request.filter
+        );
+        job.status = RecordDeletionStatus::Running;
+        job.error = None;
+        job.updated_at = now_micros();
+        db::record_deletion::set(&org_id, &job).await?;
+        match process(&org_id, &mut job).await {
+            Ok(()) if job.files_skipped > 0 => {
+                job.status = RecordDeletionStatus::Partial;
+                job.error = Some(format!(
+                    "{} files only in the dumped file list may still hold matching records",
+                    job.files_skipped
+                ));
+            }
+            Ok(()) => job.status = RecordDeletionStatus::Completed,
+            Err(e) => {
+                log::error!(
+                    "[COMPACTOR] record deletion {org_id}/{} failed: {e}",
+                    job.id
+                );
+                job.status = RecordDeletionStatus::Failed;
+                job.error = Some(e.to_string());
+            }
+        }
+        job.updated_at = now_micros();
+        db::record_deletion::set(&org_id, &job).await?;
+        log::info!(
+            "[COMPACTOR] record deletion {org_id}/{} {:?}: {} files scanned, {} rewritten, {} records deleted",
+            job.id,
+            job.status,
+            job.files_scanned,
+            job.files_rewritten,
+            job.records_deleted
+        );
+    }
+    Ok(())
+}
+
+async fn process(org_id: &str, job: &mut RecordDeletion) -> Result<(), anyhow::Error> {
+    let req = job.request.clone();
+    let ctx = SessionContext::new();
+    let schema = infra::schema::get(org_id, &req.stream_name, req.stream_type).await?;
+    let (_, fields) =
+        parse_filter(&ctx, &Arc::new(schema), &req.filter).map_err(|e| anyhow::anyhow!(e))?;
+    let stream_settings = infra::schema::get_settings(org_id, &req.stream_name, req.stream_type)
+        .await
+        .unwrap_or_default();
+    let time_range = (req.start_time, req.end_time);
+    job.files_skipped =
+        super::dump::list_files(org_id, req.stream_type, &req.stream_name, time_range)
+            .await?
+            .len();
+
+    let partitions = file_list::query(
+        &format!("record_deletion-{}", job.id),
+        org_id,
+        req.stream_type,
+        &req.stream_name,
+        PartitionTimeLevel::Unset,
+        req.start_time,
+        req.end_time,
+    )
+    .await?
+    .iter()
+    .map(|f| partition_key(&f.key))
+    .collect::<BTreeSet<_>>();
+
+    // the counters of a job resumed after its node stopped go on from there
+    for partition in partitions {
+        // the merge of the partition takes the same lock, its files stay as
+        // listed until they are replaced
+        with_partition_lock(
+            org_id,
+            req.stream_type,
+            &req.stream_name,
+            &partition,
+            async {
+                let files = file_list::query_for_merge(
+                    org_id,
+                    req.stream_type,
+                    &req.stream_name,
+                    &partition,
+                    &partition,
+                )
+                .await?;
+                for mut file in files {
+                    if file.meta.max_ts < time_range.0 || file.meta.min_ts > time_range.1 {
+                        continue;
+                    }
+                    job.files_scanned += 1;
+                    let rewrite = rewrite_file(&file, req.stream_type, &stream_settings, |batch| {
+                        retain_unmatched(&ctx, &req.filter, &fields, batch, time_range)
+                    })
+                    .await?;
+                    let new_file = match rewrite {
+                        Rewrite::Unchanged => continue,
+                        Rewrite::Emptied => None,
+                        Rewrite::Replaced(new_file) => Some(new_file),
+                    };
+                    let removed = file.meta.records
+                        - new_file
+                            .as_ref()
+                            .map(|f| f.meta.records)
+                            .unwrap_or_default();
+                    let mut events: Vec<FileKey> = new_file.into_iter().collect();
+                    file.deleted = true;
+                    events.push(file);
+                    write_file_list(org_id, vec![(partition.clone(), events)]).await?;
+                    job.files_rewritten += 1;
+                    job.records_deleted += removed;
+                    if job.files_rewritten % SAVE_EVERY_FILES == 0 {
+                        job.updated_at = now_micros();
+                        db::record_deletion::set(org_id, job).await?;
+                    }
+                }
+                Ok(())
+            },
+        )
+        .await?;
+    }
+    if job.files_rewritten > 0 {
+        delete_cached_results(org_id, req.stream_type, &req.stream_name, time_range).await;
+    }
+
+    let stats_data_range = ("".to_string(), super::stats::get_yesterday_boundary());
+    super::stats::update_stats_from_file_list_for_stream(
+        org_id,
+        req.stream_type,
+        &req.stream_name,
+        stats_data_range,
+        false,
+    )
+    .await
+}
+
+/// Keeps the records of the batch not matching the filter or out of the time
+/// range, the batches of files without the fields of the filter match nothing
+fn retain_unmatched(
+    ctx: &SessionContext,
+    filter: &str,
+    fields: &HashSet<String>,
+    batch: &RecordBatch,
+    time_range: (i64, i64),
+) -> Result<RecordBatch, anyhow::Error> {
+    let schema = batch.schema();
+    if fields.iter().any(|f| schema.field_with_name(f).is_err()) {
+        return Ok(batch.clone());
+    }
+    let df_schema = DFSchema::try_from(schema.as_ref().clone())?;
+    let expr = ctx.parse_sql_expr(filter, &df_schema)?;
+    let matched = ctx
+        .create_physical_expr(expr, &df_schema)?
+        .evaluate(batch)?
+        .into_array(batch.num_rows())?;
+    let Some(matched) = matched.as_any().downcast_ref::<BooleanArray>() else {
+        return Err(anyhow::anyhow!("the filter is not a condition"));
+    };
+    let Some(timestamps) = batch
+        .column_by_name(TIMESTAMP_COL_NAME)
+        .and_then(|c| c.as_any().downcast_ref::<Int64Array>())
+    else {
+        return Err(anyhow::anyhow!("{TIMESTAMP_COL_NAME} column not found"));
+    };
+    let keep = matched
+        .iter()
+        .zip(timestamps.iter())
+        .map(|(matched, ts)| {
+            let in_range = ts.is_some_and(|ts| ts >= time_range.0 && ts < time_range.1);
+            Some(!(in_range && matched == Some(true)))
+        })
+        .collect::<BooleanArray>();
+    Ok(filter_record_batch(batch, &keep)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::StringArray;
+    use arrow_schema::{DataType, Field, Schema};
+
+    use super::*;
+
+    #[test]
+    fn test_retain_unmatched() {
+        let ctx = SessionContext::new();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(TIMESTAMP_COL_NAME, DataType::Int64, false),
+            Field::new("user_id", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from(vec![100, 200, 300, 400])),
+                Arc::new(StringArray::from(vec![
+                    Some("u-42"),
+                    Some("u-7"),
+                    None,
+                    Some("u-42"),
+                ])),
+            ],
+        )
+        .unwrap();
+        let fields = HashSet::from(["user_id".to_string()]);
+        let filter = "user_id = 'u-42'";
+
+        let kept = retain_unmatched(&ctx, filter, &fields, &batch, (0, 1_000)).unwrap();
+        assert_eq!(kept.num_rows(), 2);
+        // the matching records out of the time range are kept
+        let kept = retain_unmatched(&ctx, filter, &fields, &batch, (0, 400)).unwrap();
+        assert_eq!(kept.num_rows(), 3);
+        // files written before the field existed match nothing
+        let fields = HashSet::from(["email".to_string()]);
+        let kept = retain_unmatched(&ctx, "email = 'a@b.c'", &fields, &batch, (0, 1_000)).unwrap();
+        assert_eq!(kept.num_rows(), 4);
+    }
+
+    #[test]
+    fn test_partition_key() {
+        assert_eq!(
+            partition_key("files/default/logs/app/2025/01/02/03/7000.parquet"),
+            "2025/01/02/03"
+        );
+    }
+}
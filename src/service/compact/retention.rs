@@ -13,29 +13,53 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::PathBuf,
+};
 
+use arrow::{
+    array::{Array, BooleanArray, Int64Array},
+    compute::filter_record_batch,
+    record_batch::RecordBatch,
+};
+use bytes::Bytes;
 use chrono::{DateTime, Duration, TimeZone, Timelike, Utc};
 use config::{
+    FILE_EXT_PARQUET, TIMESTAMP_COL_NAME,
     cluster::LOCAL_NODE,
-    get_config, is_local_disk_storage,
+    get_config, ider, is_local_disk_storage,
     meta::{
         cluster::Role,
         stream::{
-            ALL_STREAM_TYPES, FileKey, FileListBookKeepMode, FileListDeleted, PartitionTimeLevel,
-            StreamType, TimeRange,
+            ALL_STREAM_TYPES, FileKey, FileListBookKeepMode, FileListDeleted, FileMeta,
+            PartitionTimeLevel, StreamSettings, StreamType, TimeRange,
+        },
+    },
+    utils::{
+        parquet::{
+            get_recordbatch_reader_from_bytes, read_recordbatch_from_bytes,
+            write_recordbatch_to_parquet,
         },
+        time::{BASE_TIME, day_micros, get_ymdh_from_micros, hour_micros},
     },
-    utils::time::{BASE_TIME, day_micros, get_ymdh_from_micros, hour_micros},
 };
 use infra::{
     cluster::{get_node_by_uuid, get_node_from_consistent_hash},
     file_list as infra_file_list,
+    schema::{
+        get_stream_setting_fts_fields, get_stream_setting_index_bloom_filter_fields,
+        get_stream_setting_index_fields, unwrap_partition_time_level,
+    },
+    storage,
     table::compactor_manual_jobs::Status as CompactorManualJobStatus,
 };
 use itertools::Itertools;
 
-use crate::service::{db, file_list, file_list_dump::generate_dump_stream_name};
+use crate::service::{
+    db, file_list, file_list_dump::generate_dump_stream_name, search::cluster::cacher,
+    tantivy::create_tantivy_index,
+};
 
 pub(crate) async fn generate_jobs() -> Result<(), anyhow::Error> {
     let cfg = get_config();
@@ -426,21 +450,45 @@ pub async fn delete_by_date(
         return handle_delete_by_date_done(org_id, stream_type, stream_name, date_range).await;
     }
 
-    let is_hourly = date_range.0.ends_with("00Z") || date_range.1.ends_with("00Z");
+    let is_hourly = date_range.0.contains('T') || date_range.1.contains('T');
     let mut date_start = if is_hourly {
         DateTime::parse_from_rfc3339(date_range.0)?.with_timezone(&Utc)
     } else {
         DateTime::parse_from_rfc3339(&format!("{}T00:00:00Z", date_range.0))?.with_timezone(&Utc)
     };
-    // Hack for 1970-01-01
-    if date_range.0.starts_with("1970-01-01") {
-        date_start += Duration::try_milliseconds(1).unwrap();
-    }
     let date_end = if is_hourly {
         DateTime::parse_from_rfc3339(date_range.1)?.with_timezone(&Utc)
     } else {
         DateTime::parse_from_rfc3339(&format!("{}T00:00:00Z", date_range.1))?.with_timezone(&Utc)
     };
+    // windows not aligned to the partitions of the stream only delete some
+    // records of the files of their bounds
+    let stream_settings = infra::schema::get_settings(org_id, stream_name, stream_type)
+        .await
+        .unwrap_or_default();
+    let partition_time_level =
+        unwrap_partition_time_level(stream_settings.partition_time_level, stream_type);
+    if !is_aligned(date_start.timestamp_micros(), partition_time_level)
+        || !is_aligned(date_end.timestamp_micros(), partition_time_level)
+    {
+        let time_range = (
+            date_start.timestamp_micros(),
+            date_end.timestamp_micros() - 1,
+        );
+        delete_window(
+            org_id,
+            stream_type,
+            stream_name,
+            time_range,
+            partition_time_level,
+        )
+        .await?;
+        return handle_delete_by_date_done(org_id, stream_type, stream_name, date_range).await;
+    }
+    // Hack for 1970-01-01
+    if date_range.0.starts_with("1970-01-01") {
+        date_start += Duration::try_milliseconds(1).unwrap();
+    }
     let time_range = {
         (
             date_start.timestamp_micros(),
@@ -448,6 +496,25 @@ pub async fn delete_by_date(
         )
     };
 
+    // the manual deletions, by time range, are never archived
+    if stream_settings.archive_expired_data
+        && db::compact::compactor_manual_jobs::list_jobs_by_key(
+            org_id,
+            stream_type,
+            stream_name,
+            Some(date_range),
+        )
+        .await
+        .is_empty()
+    {
+        archive_files(org_id, stream_type, stream_name, time_range)
+            .await
+            .map_err(|e| {
+                log::error!("[COMPACTOR] delete_by_date archive_files failed: {e}");
+                e
+            })?;
+    }
+
     if is_local_disk_storage() {
         let dirs_to_delete =
             generate_local_dirs(org_id, stream_type, stream_name, date_start, date_end);
@@ -520,6 +587,361 @@ pub async fn delete_by_date(
     handle_delete_by_date_done(org_id, stream_type, stream_name, date_range).await
 }
 
+/// Copies the files of the time range to the archive storage, they are
+/// deleted after by the retention
+async fn archive_files(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    time_range: (i64, i64),
+) -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    let trace_id = format!(
+        "archive_files-{org_id}-{stream_name}-{}-{}",
+        time_range.0, time_range.1
+    );
+    let mut files = file_list::query(
+        &trace_id,
+        org_id,
+        stream_type,
+        stream_name,
+        PartitionTimeLevel::Unset,
+        time_range.0,
+        time_range.1,
+    )
+    .await?
+    .into_iter()
+    .map(|f| (f.account, f.key))
+    .collect::<Vec<_>>();
+    files.extend(super::dump::list_files(org_id, stream_type, stream_name, time_range).await?);
+    for (account, key) in files.iter() {
+        let data = storage::get_bytes(account, key).await?;
+        let archive_account = if cfg.compact.retention_archive_account.is_empty() {
+            account
+        } else {
+            &cfg.compact.retention_archive_account
+        };
+        let archive_key = archive_key(&cfg.compact.retention_archive_prefix, key);
+        if archive_account == account && archive_key == *key {
+            return Err(anyhow::anyhow!(
+                "archiving {account}/{key} would replace the file, set an archive prefix"
+            ));
+        }
+        storage::put(archive_account, &archive_key, data).await?;
+    }
+    log::info!(
+        "[COMPACTOR] archived {} files of {org_id}/{stream_type}/{stream_name}, time_range: [{}, {}]",
+        files.len(),
+        get_ymdh_from_micros(time_range.0),
+        get_ymdh_from_micros(time_range.1),
+    );
+    Ok(())
+}
+
+fn archive_key(prefix: &str, key: &str) -> String {
+    let prefix = prefix.trim_matches('/');
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{prefix}/{key}")
+    }
+}
+
+fn is_aligned(micros: i64, partition_time_level: PartitionTimeLevel) -> bool {
+    if partition_time_level == PartitionTimeLevel::Daily {
+        micros % day_micros(1) == 0
+    } else {
+        micros % hour_micros(1) == 0
+    }
+}
+
+/// The partitions, hours or days, fully inside the time range
+fn inner_partitions(time_range: (i64, i64), is_hourly: bool) -> Option<(i64, i64)> {
+    let unit = if is_hourly {
+        hour_micros(1)
+    } else {
+        day_micros(1)
+    };
+    let start = time_range.0 + (unit - time_range.0 % unit) % unit;
+    let end = (time_range.1 + 1) - (time_range.1 + 1) % unit;
+    (start < end).then_some((start, end - 1))
+}
+
+#[derive(Debug, PartialEq)]
+enum WindowOverlap {
+    None,
+    Inside,
+    Partial,
+}
+
+fn window_overlap(meta: &FileMeta, time_range: (i64, i64)) -> WindowOverlap {
+    if meta.max_ts < time_range.0 || meta.min_ts > time_range.1 {
+        WindowOverlap::None
+    } else if meta.min_ts >= time_range.0 && meta.max_ts <= time_range.1 {
+        WindowOverlap::Inside
+    } else {
+        WindowOverlap::Partial
+    }
+}
+
+/// Deletes the records of a time range not aligned to the partitions of the
+/// stream: the files inside the range are deleted, the ones overlapping one
+/// of its bounds are replaced by a copy without the records of the range.
+///
+/// Only the files of the file list table are rewritten, the partitions of the
+/// dumped file list are deleted when fully inside the range. The rewritten
+/// files have no inverted index, the searches scan them.
+async fn delete_window(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    time_range: (i64, i64),
+    partition_time_level: PartitionTimeLevel,
+) -> Result<(), anyhow::Error> {
+    log::info!(
+        "[COMPACTOR] delete_window {org_id}/{stream_type}/{stream_name}, time_range: [{}, {}]",
+        time_range.0,
+        time_range.1,
+    );
+    let stream_settings = infra::schema::get_settings(org_id, stream_name, stream_type)
+        .await
+        .unwrap_or_default();
+    let trace_id = format!(
+        "delete_window-{org_id}-{stream_name}-{}-{}",
+        time_range.0, time_range.1
+    );
+    let partitions = file_list::query(
+        &trace_id,
+        org_id,
+        stream_type,
+        stream_name,
+        PartitionTimeLevel::Unset,
+        time_range.0,
+        time_range.1,
+    )
+    .await?
+    .iter()
+    .filter(|f| window_overlap(&f.meta, time_range) != WindowOverlap::None)
+    .map(|f| super::partition_key(&f.key))
+    .collect::<BTreeSet<_>>();
+
+    for partition in partitions {
+        let partition_range = super::partition_time_range(&partition, partition_time_level)?;
+        let range = (
+            partition_range.0.max(time_range.0),
+            partition_range.1.min(time_range.1),
+        );
+        // the merge of the partition takes the same lock, its files stay as
+        // listed until they are replaced
+        super::with_partition_lock(org_id, stream_type, stream_name, &partition, async {
+            let files = file_list::query(
+                &trace_id,
+                org_id,
+                stream_type,
+                stream_name,
+                PartitionTimeLevel::Unset,
+                range.0,
+                range.1,
+            )
+            .await?;
+            let mut events = Vec::new();
+            for mut file in files {
+                if super::partition_key(&file.key) != partition {
+                    continue;
+                }
+                let overlap = window_overlap(&file.meta, time_range);
+                if overlap == WindowOverlap::None {
+                    continue;
+                }
+                let new_file = if overlap == WindowOverlap::Partial {
+                    match rewrite_file(&file, stream_type, &stream_settings, |batch| {
+                        retain_outside_window(batch, time_range)
+                    })
+                    .await?
+                    {
+                        Rewrite::Unchanged => continue,
+                        Rewrite::Emptied => None,
+                        Rewrite::Replaced(new_file) => Some(new_file),
+                    }
+                } else {
+                    None
+                };
+                events.extend(new_file);
+                file.deleted = true;
+                events.push(file);
+            }
+            if !events.is_empty() {
+                write_file_list(org_id, vec![(partition.clone(), events)]).await?;
+            }
+            Ok(())
+        })
+        .await?;
+    }
+    delete_cached_results(org_id, stream_type, stream_name, time_range).await;
+
+    // the dumped partitions fully inside the range
+    let is_hourly = partition_time_level != PartitionTimeLevel::Daily;
+    if let Some(inner_range) = inner_partitions(time_range, is_hourly) {
+        super::dump::delete_by_time_range(org_id, stream_type, stream_name, inner_range, is_hourly)
+            .await?;
+    }
+
+    let stats_data_range = ("".to_string(), super::stats::get_yesterday_boundary());
+    super::stats::update_stats_from_file_list_for_stream(
+        org_id,
+        stream_type,
+        stream_name,
+        stats_data_range,
+        false,
+    )
+    .await
+}
+
+/// Deletes the cached search results of the stream overlapping the time range
+/// its records were deleted from
+pub(crate) async fn delete_cached_results(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    time_range: (i64, i64),
+) {
+    if !get_config().common.result_cache_enabled {
+        return;
+    }
+    let path = format!("{org_id}/{stream_type}/{stream_name}");
+    if !cacher::delete_cached_results(path.clone(), 0, Some(time_range)).await {
+        log::warn!(
+            "[COMPACTOR] delete cached results of {path} in [{}, {}] failed",
+            time_range.0,
+            time_range.1
+        );
+    }
+}
+
+/// The result of rewriting a file without some of its records
+pub(crate) enum Rewrite {
+    /// No record was removed, the file is kept
+    Unchanged,
+    /// Every record was removed, the file is only deleted
+    Emptied,
+    /// The file is replaced by the new one
+    Replaced(FileKey),
+}
+
+/// Writes a copy of the file, next to it, with the records of each batch
+/// `retain` returns. Its inverted index is generated like the one of a merged
+/// file, it has no flattened copy
+pub(crate) async fn rewrite_file<F>(
+    file: &FileKey,
+    stream_type: StreamType,
+    stream_settings: &StreamSettings,
+    retain: F,
+) -> Result<Rewrite, anyhow::Error>
+where
+    F: Fn(&RecordBatch) -> Result<RecordBatch, anyhow::Error>,
+{
+    let data = storage::get_bytes(&file.account, &file.key).await?;
+    let (schema, batches) = read_recordbatch_from_bytes(&data).await?;
+    let total = batches.iter().map(|b| b.num_rows()).sum::<usize>();
+    let mut kept = Vec::with_capacity(batches.len());
+    for batch in batches.iter() {
+        let batch = retain(batch)?;
+        if batch.num_rows() > 0 {
+            kept.push(batch);
+        }
+    }
+    let records = kept.iter().map(|b| b.num_rows() as i64).sum::<i64>();
+    if records as usize == total {
+        return Ok(Rewrite::Unchanged);
+    }
+    let Some((min_ts, max_ts)) = timestamp_bounds(&kept) else {
+        return Ok(Rewrite::Emptied);
+    };
+    let mut meta = FileMeta {
+        min_ts,
+        max_ts,
+        records,
+        original_size: file.meta.original_size * records / file.meta.records.max(1),
+        compressed_size: 0,
+        index_size: 0,
+        flattened: false,
+    };
+    let buf = write_recordbatch_to_parquet(
+        schema.clone(),
+        &kept,
+        &stream_settings.bloom_filter_fields,
+        &meta,
+    )
+    .await?;
+    let buf = Bytes::from(buf);
+    meta.compressed_size = buf.len() as i64;
+    let prefix = file
+        .key
+        .rsplit_once('/')
+        .map(|(p, _)| p)
+        .unwrap_or_default();
+    let new_key = format!("{prefix}/{}{FILE_EXT_PARQUET}", ider::generate_file_name());
+    let account = storage::get_account(&new_key).unwrap_or_default();
+    storage::put(&account, &new_key, buf.clone()).await?;
+
+    let settings = Some(stream_settings.clone());
+    let full_text_search_fields = get_stream_setting_fts_fields(&settings);
+    let index_fields = get_stream_setting_index_fields(&settings);
+    let index_bloom_filter_fields = get_stream_setting_index_bloom_filter_fields(&settings);
+    let need_index = !full_text_search_fields.is_empty()
+        || !index_fields.is_empty()
+        || !index_bloom_filter_fields.is_empty();
+    if get_config().common.inverted_index_enabled && stream_type.support_index() && need_index {
+        let (_, reader) = get_recordbatch_reader_from_bytes(&buf).await?;
+        meta.index_size = create_tantivy_index(
+            "COMPACTOR",
+            &new_key,
+            &full_text_search_fields,
+            &index_fields,
+            &index_bloom_filter_fields,
+            schema,
+            reader,
+        )
+        .await? as i64;
+    }
+    log::info!(
+        "[COMPACTOR] rewrote {} into {new_key}, records: {total} -> {records}",
+        file.key,
+    );
+    Ok(Rewrite::Replaced(FileKey::new(
+        0, account, new_key, meta, false,
+    )))
+}
+
+fn retain_outside_window(
+    batch: &RecordBatch,
+    time_range: (i64, i64),
+) -> Result<RecordBatch, anyhow::Error> {
+    let Some(timestamps) = batch
+        .column_by_name(TIMESTAMP_COL_NAME)
+        .and_then(|c| c.as_any().downcast_ref::<Int64Array>())
+    else {
+        return Err(anyhow::anyhow!("{TIMESTAMP_COL_NAME} column not found"));
+    };
+    let keep = timestamps
+        .iter()
+        .map(|ts| ts.map(|ts| ts < time_range.0 || ts > time_range.1))
+        .collect::<BooleanArray>();
+    Ok(filter_record_batch(batch, &keep)?)
+}
+
+fn timestamp_bounds(batches: &[RecordBatch]) -> Option<(i64, i64)> {
+    batches
+        .iter()
+        .filter_map(|b| b.column_by_name(TIMESTAMP_COL_NAME))
+        .filter_map(|c| c.as_any().downcast_ref::<Int64Array>())
+        .flat_map(|c| c.iter().flatten())
+        .fold(None, |bounds, ts| match bounds {
+            None => Some((ts, ts)),
+            Some((min, max)) => Some((min.min(ts), max.max(ts))),
+        })
+}
+
 pub async fn delete_from_file_list(
     org_id: &str,
     stream_type: StreamType,
@@ -568,13 +990,19 @@ pub async fn delete_from_file_list(
     Ok(())
 }
 
-// write file list to db, all the files should be deleted
-async fn write_file_list(
+// write file list to db, the files to delete are marked as deleted
+pub(crate) async fn write_file_list(
     org_id: &str,
     hours_files: Vec<(String, Vec<FileKey>)>,
 ) -> Result<(), anyhow::Error> {
     let cfg = get_config();
     for (_, events) in hours_files {
+        // the new files of the rewritten ones are added, not deleted
+        let deleted = events
+            .iter()
+            .filter(|v| v.deleted)
+            .cloned()
+            .collect::<Vec<_>>();
         // set to db, retry 5 times
         let mut success = false;
         let created_at = Utc::now().timestamp_micros();
@@ -585,7 +1013,7 @@ async fn write_file_list(
                 .file_list_deleted_mode
                 .eq(&FileListBookKeepMode::History.to_string())
             {
-                let events = events
+                let events = deleted
                     .iter()
                     .map(|v| FileKey {
                         deleted: false,
@@ -609,7 +1037,7 @@ async fn write_file_list(
                 .file_list_deleted_mode
                 .eq(&FileListBookKeepMode::Deleted.to_string())
             {
-                let del_items = events
+                let del_items = deleted
                     .iter()
                     .map(|v| FileListDeleted {
                         id: 0,
@@ -841,4 +1269,77 @@ mod tests {
         println!("res time ranges : {}", res_time_ranges.iter().join(", "));
         assert_eq!(res_time_ranges.len(), 2);
     }
+
+    #[test]
+    fn test_window_overlap() {
+        let meta = |min_ts, max_ts| FileMeta {
+            min_ts,
+            max_ts,
+            ..Default::default()
+        };
+        let range = (100, 199);
+        assert_eq!(window_overlap(&meta(0, 99), range), WindowOverlap::None);
+        assert_eq!(window_overlap(&meta(200, 300), range), WindowOverlap::None);
+        assert_eq!(
+            window_overlap(&meta(100, 199), range),
+            WindowOverlap::Inside
+        );
+        assert_eq!(
+            window_overlap(&meta(50, 150), range),
+            WindowOverlap::Partial
+        );
+        assert_eq!(
+            window_overlap(&meta(150, 250), range),
+            WindowOverlap::Partial
+        );
+        assert_eq!(window_overlap(&meta(0, 300), range), WindowOverlap::Partial);
+        assert!(is_aligned(hour_micros(5), PartitionTimeLevel::Hourly));
+        assert!(!is_aligned(hour_micros(5) + 1, PartitionTimeLevel::Hourly));
+        assert!(is_aligned(day_micros(2), PartitionTimeLevel::Daily));
+        assert!(!is_aligned(hour_micros(5), PartitionTimeLevel::Daily));
+
+        let range = (hour_micros(1) + 1, hour_micros(4) + 5);
+        assert_eq!(
+            inner_partitions(range, true),
+            Some((hour_micros(2), hour_micros(4) - 1))
+        );
+        assert_eq!(inner_partitions(range, false), None);
+        let range = (hour_micros(2), day_micros(2) - 1);
+        assert_eq!(
+            inner_partitions(range, false),
+            Some((day_micros(1), day_micros(2) - 1))
+        );
+    }
+
+    #[test]
+    fn test_retain_outside_window() {
+        let schema = std::sync::Arc::new(arrow_schema::Schema::new(vec![
+            arrow_schema::Field::new(TIMESTAMP_COL_NAME, arrow_schema::DataType::Int64, false),
+            arrow_schema::Field::new("msg", arrow_schema::DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                std::sync::Arc::new(Int64Array::from(vec![90, 100, 150, 199, 200])),
+                std::sync::Arc::new(arrow::array::StringArray::from(vec![
+                    "a", "b", "c", "d", "e",
+                ])),
+            ],
+        )
+        .unwrap();
+        let kept = retain_outside_window(&batch, (100, 199)).unwrap();
+        assert_eq!(kept.num_rows(), 2);
+        assert_eq!(timestamp_bounds(&[kept]), Some((90, 200)));
+        let kept = retain_outside_window(&batch, (0, 300)).unwrap();
+        assert_eq!(kept.num_rows(), 0);
+        assert_eq!(timestamp_bounds(&[kept]), None);
+    }
+
+    #[test]
+    fn test_archive_key() {
+        let key = "files/default/logs/app/2025/01/02/03/7000.parquet";
+        assert_eq!(archive_key("archive", key), format!("archive/{key}"));
+        assert_eq!(archive_key("/cold/", key), format!("cold/{key}"));
+        assert_eq!(archive_key("", key), key);
+    }
 }
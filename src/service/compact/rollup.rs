@@ -0,0 +1,370 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Rolls up the metrics streams into lower resolution streams, one per level
+//! of `ZO_COMPACT_METRICS_ROLLUP_RULES`, e.g. `cpu_usage__rollup_5m`.
+//!
+//! The steps older than `ZO_COMPACT_METRICS_ROLLUP_DELAY` are aggregated per
+//! series with a search and ingested into the rollup stream, which keeps the
+//! labels, so the PromQL engine reads the samples before the end of a rollup
+//! from it. The rollup streams get the retention of their level.
+
+use arrow_schema::Schema;
+use config::{
+    TIMESTAMP_COL_NAME,
+    cluster::LOCAL_NODE,
+    get_config,
+    meta::{
+        cluster::Role,
+        promql::{
+            EXEMPLARS_LABEL, Function, HASH_LABEL, METADATA_LABEL, Metadata, MetricType,
+            RollupRule, TYPE_LABEL, VALUE_LABEL, get_rollup_rules, is_rollup_stream,
+        },
+        search,
+        stream::StreamType,
+    },
+    utils::{
+        json::{self, Map, Value},
+        time::now_micros,
+    },
+};
+use infra::cluster::get_node_from_consistent_hash;
+use proto::cluster_rpc;
+
+use crate::service::{db, ingestion::ingestion_service};
+
+/// Max time rolled up by a run, per stream and level
+const MAX_WINDOW: i64 = 24 * 3600 * 1_000_000;
+const PAGE_SIZE: i64 = 10_000;
+const BUCKET_COL: &str = "__bucket__";
+
+pub async fn run() -> Result<(), anyhow::Error> {
+    let rules = get_rollup_rules();
+    if rules.is_empty() {
+        return Ok(());
+    }
+    let orgs = db::schema::list_organizations_from_cache().await;
+    for org_id in orgs {
+        let streams = db::schema::list_streams_from_cache(&org_id, StreamType::Metrics).await;
+        for stream_name in streams {
+            if is_rollup_stream(&stream_name) {
+                continue;
+            }
+            let Some(node_name) =
+                get_node_from_consistent_hash(&stream_name, &Role::Compactor, None).await
+            else {
+                continue; // no compactor node
+            };
+            if LOCAL_NODE.name.ne(&node_name) {
+                continue; // not this node
+            }
+            if db::compact::retention::is_deleting_stream(
+                &org_id,
+                StreamType::Metrics,
+                &stream_name,
+                None,
+            ) {
+                continue;
+            }
+            for rule in rules.iter() {
+                if let Err(e) = rollup_stream(&org_id, &stream_name, rule).await {
+                    log::error!(
+                        "[ROLLUP] rollup stream [{org_id}/metrics/{stream_name}] level {} error: {e}",
+                        rule.level
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn rollup_stream(
+    org_id: &str,
+    stream_name: &str,
+    rule: &RollupRule,
+) -> Result<(), anyhow::Error> {
+    let step = rule.step_micros();
+    let mut offset =
+        db::compact::rollup::get_offset(org_id, StreamType::Metrics, stream_name, &rule.level)
+            .await;
+    if offset == 0 {
+        let stats = infra::cache::stats::get_stream_stats(org_id, stream_name, StreamType::Metrics);
+        if stats.doc_time_min == 0 {
+            return Ok(());
+        }
+        offset = align(stats.doc_time_min, step);
+    }
+    let delay = get_config().compact.metrics_rollup_delay as i64 * 1_000_000;
+    let end = align(now_micros() - delay, step).min(align(offset + MAX_WINDOW.max(step), step));
+    if end <= offset {
+        return Ok(());
+    }
+
+    let schema = infra::schema::get(org_id, stream_name, StreamType::Metrics).await?;
+    if schema == Schema::empty() {
+        return Ok(());
+    }
+    let (metric_type, function) = rollup_function(&schema, rule);
+    let sql = generate_rollup_sql(stream_name, &schema, &function, step);
+
+    let start = std::time::Instant::now();
+    let rollup_name = rule.stream_name(stream_name);
+    let mut from = 0;
+    let mut total = 0;
+    loop {
+        let hits = search_page(org_id, &sql, offset, end, from).await?;
+        let len = hits.len() as i64;
+        let records = hits
+            .into_iter()
+            .filter_map(|hit| to_rollup_record(hit, &metric_type))
+            .collect::<Vec<_>>();
+        total += records.len();
+        ingest(org_id, &rollup_name, &records).await?;
+        if len < PAGE_SIZE {
+            break;
+        }
+        from += PAGE_SIZE;
+    }
+    if total > 0 {
+        set_retention(org_id, &rollup_name, rule.retention_days).await?;
+    }
+    db::compact::rollup::set_offset(org_id, StreamType::Metrics, stream_name, &rule.level, end)
+        .await?;
+    log::info!(
+        "[ROLLUP] rolled up [{org_id}/metrics/{stream_name}] level {} [{offset}, {end}) into {total} samples, took: {} ms",
+        rule.level,
+        start.elapsed().as_millis()
+    );
+    Ok(())
+}
+
+fn align(ts: i64, step: i64) -> i64 {
+    ts - ts.rem_euclid(step)
+}
+
+/// The metric type written with the rollup samples, and the function they are
+/// aggregated with
+fn rollup_function(schema: &Schema, rule: &RollupRule) -> (String, Function) {
+    let metric_type = schema
+        .metadata()
+        .get(METADATA_LABEL)
+        .and_then(|v| json::from_str::<Metadata>(v).ok())
+        .map(|m| m.metric_type)
+        .unwrap_or(MetricType::Gauge);
+    match metric_type {
+        MetricType::Counter | MetricType::Histogram | MetricType::Summary => {
+            (metric_type.to_string(), Function::Max)
+        }
+        _ => (MetricType::Gauge.to_string(), rule.function.clone()),
+    }
+}
+
+fn generate_rollup_sql(
+    stream_name: &str,
+    schema: &Schema,
+    function: &Function,
+    step: i64,
+) -> String {
+    let labels = schema
+        .fields()
+        .iter()
+        .map(|f| f.name())
+        .filter(|name| {
+            ![HASH_LABEL, VALUE_LABEL, EXEMPLARS_LABEL, TIMESTAMP_COL_NAME].contains(&name.as_str())
+        })
+        .map(|name| format!("max(\"{name}\") AS \"{name}\""))
+        .collect::<Vec<_>>();
+    let mut columns = labels;
+    columns.push(format!(
+        "{}(\"{VALUE_LABEL}\") AS \"{VALUE_LABEL}\"",
+        function.fun()
+    ));
+    columns.push(format!(
+        "{TIMESTAMP_COL_NAME} - ({TIMESTAMP_COL_NAME} % {step}) AS \"{BUCKET_COL}\""
+    ));
+    format!(
+        "SELECT {} FROM \"{stream_name}\" GROUP BY \"{HASH_LABEL}\", \"{BUCKET_COL}\" ORDER BY \"{BUCKET_COL}\", \"{HASH_LABEL}\"",
+        columns.join(", ")
+    )
+}
+
+async fn search_page(
+    org_id: &str,
+    sql: &str,
+    start_time: i64,
+    end_time: i64,
+    from: i64,
+) -> Result<Vec<Value>, anyhow::Error> {
+    let req = search::Request {
+        query: search::Query {
+            sql: sql.to_string(),
+            from,
+            size: PAGE_SIZE,
+            start_time,
+            end_time,
+            ..Default::default()
+        },
+        encoding: search::RequestEncoding::Empty,
+        regions: vec![],
+        clusters: vec![],
+        timeout: 300,
+        search_type: None,
+        search_event_context: None,
+        use_cache: false,
+        clear_cache: false,
+        local_mode: Some(false),
+    };
+    let trace_id = config::ider::generate();
+    let resp =
+        crate::service::search::search(&trace_id, org_id, StreamType::Metrics, None, &req).await?;
+    Ok(resp.hits)
+}
+
+/// Turns an aggregated row into a sample of the metrics json ingestion, the
+/// hash is computed again from the labels, it matches the one of the series
+fn to_rollup_record(hit: Value, metric_type: &str) -> Option<Map<String, Value>> {
+    let Value::Object(mut record) = hit else {
+        return None;
+    };
+    let timestamp = record.remove(BUCKET_COL)?;
+    record.retain(|_, v| !v.is_null());
+    record.get(VALUE_LABEL)?;
+    record.insert(TIMESTAMP_COL_NAME.to_string(), timestamp);
+    record.insert(TYPE_LABEL.to_string(), metric_type.into());
+    Some(record)
+}
+
+async fn ingest(
+    org_id: &str,
+    stream_name: &str,
+    records: &[Map<String, Value>],
+) -> Result<(), anyhow::Error> {
+    for chunk in records.chunks(PAGE_SIZE as usize) {
+        let req = cluster_rpc::IngestionRequest {
+            org_id: org_id.to_string(),
+            stream_type: StreamType::Metrics.to_string(),
+            stream_name: stream_name.to_string(),
+            data: Some(cluster_rpc::IngestionData {
+                data: json::to_vec(chunk)?,
+            }),
+            ingestion_type: Some(cluster_rpc::IngestionType::Json.into()),
+            metadata: None,
+        };
+        let resp = ingestion_service::ingest(req).await?;
+        if resp.status_code != 200 {
+            return Err(anyhow::anyhow!(
+                "ingest into {stream_name} error: {}",
+                resp.message
+            ));
+        }
+    }
+    Ok(())
+}
+
+async fn set_retention(
+    org_id: &str,
+    stream_name: &str,
+    retention_days: i64,
+) -> Result<(), anyhow::Error> {
+    let Some(mut settings) =
+        infra::schema::get_settings(org_id, stream_name, StreamType::Metrics).await
+    else {
+        return Ok(());
+    };
+    if settings.data_retention == retention_days {
+        return Ok(());
+    }
+    settings.data_retention = retention_days;
+    crate::service::stream::save_stream_settings(
+        org_id,
+        stream_name,
+        StreamType::Metrics,
+        settings,
+    )
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use arrow_schema::{DataType, Field};
+
+    use super::*;
+
+    fn schema(metric_type: &str) -> Schema {
+        let metadata = format!(
+            r#"{{"metric_type":"{metric_type}","metric_family_name":"up","help":"","unit":""}}"#
+        );
+        Schema::new(vec![
+            Arc::new(Field::new(TIMESTAMP_COL_NAME, DataType::Int64, false)),
+            Arc::new(Field::new(HASH_LABEL, DataType::UInt64, false)),
+            Arc::new(Field::new(VALUE_LABEL, DataType::Float64, false)),
+            Arc::new(Field::new("__name__", DataType::Utf8, true)),
+            Arc::new(Field::new("instance", DataType::Utf8, true)),
+        ])
+        .with_metadata(HashMap::from([(METADATA_LABEL.to_string(), metadata)]))
+    }
+
+    fn rule() -> RollupRule {
+        config::meta::promql::parse_rollup_rules("5m:avg:30d")
+            .unwrap()
+            .remove(0)
+    }
+
+    #[test]
+    fn test_rollup_function() {
+        let (metric_type, function) = rollup_function(&schema("gauge"), &rule());
+        assert_eq!(metric_type, "gauge");
+        assert_eq!(function, Function::Avg);
+        let (metric_type, function) = rollup_function(&schema("counter"), &rule());
+        assert_eq!(metric_type, "counter");
+        assert_eq!(function, Function::Max);
+    }
+
+    #[test]
+    fn test_generate_rollup_sql() {
+        let sql = generate_rollup_sql("up", &schema("gauge"), &Function::Avg, 300_000_000);
+        assert_eq!(
+            sql,
+            "SELECT max(\"__name__\") AS \"__name__\", max(\"instance\") AS \"instance\", avg(\"value\") AS \"value\", _timestamp - (_timestamp % 300000000) AS \"__bucket__\" FROM \"up\" GROUP BY \"__hash__\", \"__bucket__\" ORDER BY \"__bucket__\", \"__hash__\""
+        );
+    }
+
+    #[test]
+    fn test_to_rollup_record() {
+        let hit = json::json!({
+            "__name__": "up",
+            "instance": null,
+            "value": 1.5,
+            "__bucket__": 300_000_000,
+        });
+        let record = to_rollup_record(hit, "gauge").unwrap();
+        assert_eq!(record.get(TIMESTAMP_COL_NAME).unwrap(), 300_000_000);
+        assert_eq!(record.get(TYPE_LABEL).unwrap(), "gauge");
+        assert!(!record.contains_key("instance"));
+        assert!(!record.contains_key(BUCKET_COL));
+        assert!(to_rollup_record(json::json!({"__bucket__": 0}), "gauge").is_none());
+    }
+
+    #[test]
+    fn test_align() {
+        assert_eq!(align(301, 300), 300);
+        assert_eq!(align(300, 300), 300);
+        assert_eq!(align(299, 300), 0);
+    }
+}
@@ -0,0 +1,535 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Moves the files older than `ZO_COMPACT_COLD_STORAGE_AFTER_DAYS` to the
+//! storage account `ZO_COMPACT_COLD_STORAGE_ACCOUNT`, e.g. a bucket with an
+//! archive storage class, the file list records the account of each file.
+//!
+//! With `ZO_COMPACT_COLD_STORAGE_QUERY_MODE=fetch` the searches read the cold
+//! files like the others, with the latency of the storage class, so the bucket
+//! must be readable without restoring its objects, e.g. S3 Glacier Instant
+//! Retrieval. With `restore` the searches touching cold files fail until their
+//! time range is restored: the files are copied back to the storage of the
+//! stream for `ZO_COMPACT_COLD_STORAGE_RESTORE_DAYS`, then moved back.
+//!
+//! Only the files of the file list table are moved, the dumped file list is
+//! left in place.
+
+use std::collections::BTreeSet;
+
+use config::{
+    cluster::LOCAL_NODE,
+    get_config, ider,
+    meta::{
+        cluster::Role,
+        stream::{FileKey, FileListDeleted, PartitionTimeLevel, StreamType},
+    },
+    utils::{
+        inverted_index::convert_parquet_file_name_to_tantivy_file,
+        time::{day_micros, now_micros},
+    },
+};
+use infra::{
+    cluster::get_node_from_consistent_hash,
+    errors::{Error, ErrorCodes},
+    file_list as infra_file_list, storage,
+};
+
+use crate::{
+    common::meta::stream::{
+        ColdStorageRestore, ColdStorageRestoreRequest, ColdStorageRestoreStatus,
+    },
+    service::{db, file_list},
+};
+
+const TIERED_STREAM_TYPES: [StreamType; 3] =
+    [StreamType::Logs, StreamType::Metrics, StreamType::Traces];
+
+pub async fn run() -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    if cfg.compact.cold_storage_account.is_empty() || cfg.compact.cold_storage_after_days == 0 {
+        return Ok(());
+    }
+    let orgs = db::schema::list_organizations_from_cache().await;
+    for org_id in orgs {
+        for stream_type in TIERED_STREAM_TYPES {
+            let streams = db::schema::list_streams_from_cache(&org_id, stream_type).await;
+            for stream_name in streams {
+                let Some(node_name) =
+                    get_node_from_consistent_hash(&stream_name, &Role::Compactor, None).await
+                else {
+                    continue; // no compactor node
+                };
+                if LOCAL_NODE.name.ne(&node_name) {
+                    continue; // not this node
+                }
+                if db::compact::retention::is_deleting_stream(
+                    &org_id,
+                    stream_type,
+                    &stream_name,
+                    None,
+                ) {
+                    continue;
+                }
+                if let Err(e) = tier_stream(&org_id, stream_type, &stream_name).await {
+                    log::error!(
+                        "[TIERING] tier stream [{org_id}/{stream_type}/{stream_name}] error: {e}"
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn tier_stream(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    let cold_account = &cfg.compact.cold_storage_account;
+    let now = now_micros();
+    let cutoff = now - day_micros(cfg.compact.cold_storage_after_days);
+    let mut offset = db::compact::tiering::get_offset(org_id, stream_type, stream_name).await;
+    if offset == 0 {
+        let stats = infra::cache::stats::get_stream_stats(org_id, stream_name, stream_type);
+        if stats.doc_time_min == 0 {
+            return Ok(());
+        }
+        offset = stats.doc_time_min;
+    }
+
+    // the files of the expired restores go back to the cold storage
+    let (restores, expired): (Vec<_>, Vec<_>) =
+        db::compact::tiering::list_restores(org_id, stream_type, stream_name)
+            .await?
+            .into_iter()
+            .partition(|r| r.is_active(now));
+    for restore in expired {
+        let end = restore.end_time.min(offset);
+        if restore.start_time < end {
+            move_files(
+                org_id,
+                stream_type,
+                stream_name,
+                (restore.start_time, end),
+                end,
+                &restores,
+            )
+            .await?;
+        }
+        db::compact::tiering::delete_restore(org_id, stream_type, stream_name, &restore.id).await?;
+    }
+
+    let mut moved = 0;
+    while offset < cutoff {
+        let end = (offset + day_micros(1)).min(cutoff);
+        moved += move_files(
+            org_id,
+            stream_type,
+            stream_name,
+            (offset, end),
+            cutoff,
+            &restores,
+        )
+        .await?;
+        db::compact::tiering::set_offset(org_id, stream_type, stream_name, end).await?;
+        offset = end;
+    }
+    if moved > 0 {
+        log::info!(
+            "[TIERING] moved {moved} files of {org_id}/{stream_type}/{stream_name} to the cold storage account {cold_account}"
+        );
+    }
+    Ok(())
+}
+
+/// Moves the files of the time range ending before the cutoff to the cold
+/// storage, the hot copies are deleted with the delay of the merged files as
+/// the searches can still be reading them
+async fn move_files(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    time_range: (i64, i64),
+    cutoff: i64,
+    restores: &[ColdStorageRestore],
+) -> Result<usize, anyhow::Error> {
+    let cold_account = &get_config().compact.cold_storage_account;
+    let partitions = infra_file_list::query(
+        org_id,
+        stream_type,
+        stream_name,
+        PartitionTimeLevel::Unset,
+        time_range,
+        None,
+    )
+    .await?
+    .iter()
+    .filter(|f| should_move(f, cold_account, cutoff, restores))
+    .map(|f| super::partition_key(&f.key))
+    .collect::<BTreeSet<_>>();
+
+    let mut moved = 0;
+    for partition in partitions {
+        // the merge of the partition takes the same lock, its files stay as
+        // listed until they are moved
+        moved += super::with_partition_lock(org_id, stream_type, stream_name, &partition, async {
+            let files = file_list::query_for_merge(
+                org_id,
+                stream_type,
+                stream_name,
+                &partition,
+                &partition,
+            )
+            .await?;
+            move_partition_files(
+                org_id,
+                files.iter().filter(|f| {
+                    overlaps(f, time_range) && should_move(f, cold_account, cutoff, restores)
+                }),
+                cold_account,
+            )
+            .await
+        })
+        .await?;
+    }
+    Ok(moved)
+}
+
+async fn move_partition_files<'a>(
+    org_id: &str,
+    files: impl Iterator<Item = &'a FileKey>,
+    cold_account: &str,
+) -> Result<usize, anyhow::Error> {
+    let mut moved = Vec::new();
+    let mut ret = Ok(());
+    for file in files {
+        if let Err(e) = copy_file(file, cold_account, true).await {
+            ret = Err(e);
+            break;
+        }
+        if let Err(e) = file_list::update_account(&file.key, cold_account).await {
+            ret = Err(e.into());
+            break;
+        }
+        moved.push(FileListDeleted {
+            id: 0,
+            account: file.account.clone(),
+            file: file.key.clone(),
+            index_file: file.meta.index_size > 0,
+            flattened: file.meta.flattened,
+        });
+    }
+    if !moved.is_empty() {
+        infra_file_list::batch_add_deleted(org_id, now_micros(), &moved).await?;
+    }
+    ret.map(|_| moved.len())
+}
+
+fn overlaps(file: &FileKey, time_range: (i64, i64)) -> bool {
+    file.meta.max_ts >= time_range.0 && file.meta.min_ts <= time_range.1
+}
+
+fn should_move(
+    file: &FileKey,
+    cold_account: &str,
+    cutoff: i64,
+    restores: &[ColdStorageRestore],
+) -> bool {
+    file.account != cold_account
+        && file.meta.max_ts < cutoff
+        && !restores
+            .iter()
+            .any(|r| r.overlaps(file.meta.min_ts, file.meta.max_ts))
+}
+
+/// The objects of a file: the parquet, its inverted index and its flattened
+/// copy
+fn object_keys(file: &FileKey) -> Vec<String> {
+    let mut keys = vec![file.key.clone()];
+    if file.meta.index_size > 0
+        && let Some(ttv_file) = convert_parquet_file_name_to_tantivy_file(&file.key)
+    {
+        keys.push(ttv_file);
+    }
+    if file.meta.flattened
+        && let Some(name) = file.key.strip_prefix("files/")
+    {
+        keys.push(format!("files{}/{name}", get_config().common.column_all));
+    }
+    keys
+}
+
+/// Copies the objects of the file to the account, the ones already there are
+/// skipped when `skip_existing`, e.g. the cold copy of a restored file
+async fn copy_file(
+    file: &FileKey,
+    account: &str,
+    skip_existing: bool,
+) -> Result<(), anyhow::Error> {
+    for key in object_keys(file) {
+        if skip_existing && storage::head(account, &key).await.is_ok() {
+            continue;
+        }
+        let data = storage::get_bytes(&file.account, &key).await?;
+        storage::put(account, &key, data).await?;
+    }
+    Ok(())
+}
+
+/// Starts the restore of the time range, the files are copied back from the
+/// cold storage in the background
+pub async fn restore(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    req: ColdStorageRestoreRequest,
+) -> Result<ColdStorageRestore, anyhow::Error> {
+    let cfg = get_config();
+    if cfg.compact.cold_storage_account.is_empty() {
+        return Err(anyhow::anyhow!("the cold storage is not enabled"));
+    }
+    if req.start_time >= req.end_time {
+        return Err(anyhow::anyhow!("start_time must be before end_time"));
+    }
+    let now = now_micros();
+    let restore = ColdStorageRestore {
+        id: ider::generate(),
+        start_time: req.start_time,
+        end_time: req.end_time,
+        created_at: now,
+        expires_at: now + day_micros(cfg.compact.cold_storage_restore_days),
+        ..Default::default()
+    };
+    db::compact::tiering::set_restore(org_id, stream_type, stream_name, &restore).await?;
+
+    let org_id = org_id.to_string();
+    let stream_name = stream_name.to_string();
+    let mut task = restore.clone();
+    tokio::spawn(async move {
+        match restore_files(
+            &org_id,
+            stream_type,
+            &stream_name,
+            (task.start_time, task.end_time),
+        )
+        .await
+        {
+            Ok(files) => {
+                task.files = files;
+                task.status = ColdStorageRestoreStatus::Completed;
+            }
+            Err(e) => {
+                log::error!(
+                    "[TIERING] restore {} of {org_id}/{stream_type}/{stream_name} error: {e}",
+                    task.id
+                );
+                task.status = ColdStorageRestoreStatus::Failed;
+                task.error = Some(e.to_string());
+            }
+        }
+        if let Err(e) =
+            db::compact::tiering::set_restore(&org_id, stream_type, &stream_name, &task).await
+        {
+            log::error!(
+                "[TIERING] save restore {} of {org_id}/{stream_type}/{stream_name} error: {e}",
+                task.id
+            );
+        }
+    });
+    Ok(restore)
+}
+
+/// Copies the cold files of the time range back to the account of the stream,
+/// the cold copies are kept for when the restore expires
+async fn restore_files(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    time_range: (i64, i64),
+) -> Result<usize, anyhow::Error> {
+    let cold_account = &get_config().compact.cold_storage_account;
+    let partitions = infra_file_list::query(
+        org_id,
+        stream_type,
+        stream_name,
+        PartitionTimeLevel::Unset,
+        time_range,
+        None,
+    )
+    .await?
+    .iter()
+    .filter(|f| &f.account == cold_account)
+    .map(|f| super::partition_key(&f.key))
+    .collect::<BTreeSet<_>>();
+
+    let mut restored = 0;
+    for partition in partitions {
+        // the merge of the partition takes the same lock, its files stay as
+        // listed until they are restored
+        restored +=
+            super::with_partition_lock(org_id, stream_type, stream_name, &partition, async {
+                let files = file_list::query_for_merge(
+                    org_id,
+                    stream_type,
+                    stream_name,
+                    &partition,
+                    &partition,
+                )
+                .await?;
+                let mut restored = 0;
+                for file in files
+                    .iter()
+                    .filter(|f| overlaps(f, time_range) && &f.account == cold_account)
+                {
+                    let account = storage::get_account(&file.key).unwrap_or_default();
+                    copy_file(file, &account, false).await?;
+                    file_list::update_account(&file.key, &account).await?;
+                    restored += 1;
+                }
+                Ok(restored)
+            })
+            .await?;
+    }
+    Ok(restored)
+}
+
+pub async fn list_restores(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> Result<Vec<ColdStorageRestore>, anyhow::Error> {
+    db::compact::tiering::list_restores(org_id, stream_type, stream_name).await
+}
+
+/// Fails the search reading files in the cold storage when they must be
+/// restored first
+pub fn check_cold_files(stream_name: &str, files: &[FileKey]) -> Result<(), Error> {
+    let cfg = get_config();
+    if cfg.compact.cold_storage_account.is_empty()
+        || cfg.compact.cold_storage_query_mode != "restore"
+    {
+        return Ok(());
+    }
+    cold_files_error(stream_name, files, &cfg.compact.cold_storage_account)
+}
+
+fn cold_files_error(stream_name: &str, files: &[FileKey], cold_account: &str) -> Result<(), Error> {
+    let mut num = 0;
+    let (mut min_ts, mut max_ts) = (i64::MAX, i64::MIN);
+    for file in files.iter().filter(|f| f.account == cold_account) {
+        num += 1;
+        min_ts = min_ts.min(file.meta.min_ts);
+        max_ts = max_ts.max(file.meta.max_ts);
+    }
+    if num == 0 {
+        return Ok(());
+    }
+    Err(Error::ErrorCode(
+        ErrorCodes::SearchColdStorageRestoreRequired(format!(
+            "{num} files of stream {stream_name} are in the cold storage, restore the time range [{min_ts}, {max_ts}] to search it"
+        )),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use config::meta::stream::FileMeta;
+
+    use super::*;
+
+    fn file_key(account: &str, min_ts: i64, max_ts: i64) -> FileKey {
+        FileKey {
+            account: account.to_string(),
+            key: "files/default/logs/app/2025/01/01/00/a.parquet".to_string(),
+            meta: FileMeta {
+                min_ts,
+                max_ts,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_should_move() {
+        let restores = vec![ColdStorageRestore {
+            start_time: 500,
+            end_time: 600,
+            ..Default::default()
+        }];
+        assert!(should_move(
+            &file_key("", 100, 200),
+            "cold",
+            1000,
+            &restores
+        ));
+        assert!(!should_move(
+            &file_key("cold", 100, 200),
+            "cold",
+            1000,
+            &restores
+        ));
+        assert!(!should_move(
+            &file_key("", 900, 1000),
+            "cold",
+            1000,
+            &restores
+        ));
+        assert!(!should_move(
+            &file_key("", 550, 700),
+            "cold",
+            1000,
+            &restores
+        ));
+    }
+
+    #[test]
+    fn test_object_keys() {
+        let mut file = file_key("", 100, 200);
+        assert_eq!(object_keys(&file), vec![file.key.clone()]);
+
+        file.meta.index_size = 10;
+        file.meta.flattened = true;
+        let keys = object_keys(&file);
+        assert_eq!(keys.len(), 3);
+        assert_eq!(
+            keys[2],
+            format!(
+                "files{}/default/logs/app/2025/01/01/00/a.parquet",
+                get_config().common.column_all
+            )
+        );
+    }
+
+    #[test]
+    fn test_cold_files_error() {
+        let files = vec![
+            file_key("", 100, 200),
+            file_key("cold", 300, 400),
+            file_key("cold", 250, 350),
+        ];
+        assert!(cold_files_error("app", &files[..1], "cold").is_ok());
+        let Err(Error::ErrorCode(ErrorCodes::SearchColdStorageRestoreRequired(msg))) =
+            cold_files_error("app", &files, "cold")
+        else {
+            panic!("expected a restore required error");
+        };
+        assert!(msg.starts_with("2 files of stream app"));
+        assert!(msg.contains("[250, 400]"));
+    }
+}
@@ -39,6 +39,7 @@ use crate::common::{
 };
 pub mod reports;
 pub mod timed_annotations;
+pub mod variables;
 
 #[cfg(feature = "enterprise")]
 use o2_enterprise::enterprise::common::config::get_config as get_o2_config;
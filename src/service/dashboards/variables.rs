@@ -0,0 +1,231 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Values of the dashboard variables
+//!
+//! The dropdowns of the dashboard variables list the values of a field, they
+//! are read from the distinct values stream of the stream instead of a
+//! `SELECT DISTINCT` over its records. The fields of the variables of a
+//! dashboard are added to the distinct value fields of their stream when the
+//! dashboard is saved. The values are cached for
+//! `ZO_DASHBOARD_VARIABLES_CACHE_TTL` seconds, with the time range aligned on
+//! the TTL so the dashboards refreshing a relative time range share them.
+
+use config::{
+    get_config,
+    meta::{
+        dashboards::variables::{VariableValue, VariableValuesResponse},
+        stream::StreamType,
+    },
+    utils::{json, time::now_micros, util::get_distinct_stream_name},
+};
+use hashbrown::HashMap;
+use infra::errors::Error;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+
+use crate::service::{
+    metadata::distinct_values::{
+        self, DistinctValuesError, hit_count, search_distinct_stream, sql_literal,
+    },
+    search::field_access,
+};
+
+/// Maximum number of values of a variable
+pub const MAX_VALUES: usize = 1000;
+
+struct Cached {
+    values: Vec<VariableValue>,
+    expires_at: i64,
+}
+
+static CACHE: Lazy<RwLock<HashMap<String, Cached>>> = Lazy::new(Default::default);
+
+#[derive(Debug, thiserror::Error)]
+pub enum VariableError {
+    #[error("{0}")]
+    InvalidRequest(String),
+    #[error("{0}")]
+    AccessDenied(String),
+    #[error(transparent)]
+    SearchError(#[from] Error),
+}
+
+impl From<DistinctValuesError> for VariableError {
+    fn from(value: DistinctValuesError) -> Self {
+        match value {
+            DistinctValuesError::InvalidRequest(e) => VariableError::InvalidRequest(e),
+            DistinctValuesError::SearchError(e) => VariableError::SearchError(e),
+        }
+    }
+}
+
+pub struct ValuesQuery<'a> {
+    pub stream_type: StreamType,
+    pub stream_name: &'a str,
+    pub field: &'a str,
+    /// Only the values starting with it, ignoring the case
+    pub prefix: Option<&'a str>,
+    pub time_range: (i64, i64),
+    pub size: usize,
+}
+
+pub async fn get_values(
+    trace_id: &str,
+    org_id: &str,
+    user_id: &str,
+    query: &ValuesQuery<'_>,
+) -> Result<VariableValuesResponse, VariableError> {
+    let (start_time, end_time) = query.time_range;
+    if start_time >= end_time {
+        return Err(VariableError::InvalidRequest(
+            "start_time must be before end_time".to_string(),
+        ));
+    }
+    distinct_values::check_distinct_field(
+        org_id,
+        query.stream_type,
+        query.stream_name,
+        query.field,
+    )
+    .await?;
+    check_field_access(org_id, user_id, query).await?;
+
+    let ttl = get_config().limit.dashboard_variables_cache_ttl * 1_000_000;
+    let prefix = query
+        .prefix
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty());
+    let size = query.size.clamp(1, MAX_VALUES);
+    let time_range = align_time_range(query.time_range, ttl);
+    let key = format!(
+        "{org_id}/{}/{}/{}/{}/{}/{}/{size}",
+        query.stream_type,
+        query.stream_name,
+        query.field,
+        time_range.0,
+        time_range.1,
+        prefix.as_deref().unwrap_or_default(),
+    );
+    let now = now_micros();
+    if let Some(cached) = CACHE.read().get(&key).filter(|v| v.expires_at > now) {
+        return Ok(VariableValuesResponse {
+            field: query.field.to_string(),
+            values: cached.values.clone(),
+            cached: true,
+        });
+    }
+
+    let distinct_stream_name = get_distinct_stream_name(query.stream_type, query.stream_name);
+    let sql = values_sql(&distinct_stream_name, query.field, prefix.as_deref(), size);
+    let values = search_distinct_stream(trace_id, org_id, sql, time_range, size)
+        .await?
+        .into_iter()
+        .filter_map(|hit| {
+            let value = hit.get("zo_sql_key").filter(|v| !v.is_null())?.clone();
+            Some(VariableValue {
+                value,
+                count: hit_count(&hit),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if ttl > 0 {
+        let mut cache = CACHE.write();
+        cache.retain(|_, v| v.expires_at > now);
+        cache.insert(
+            key,
+            Cached {
+                values: values.clone(),
+                expires_at: now + ttl,
+            },
+        );
+    }
+    Ok(VariableValuesResponse {
+        field: query.field.to_string(),
+        values,
+        cached: false,
+    })
+}
+
+/// Rejects the fields denied to the user by the field access rules of the
+/// stream
+async fn check_field_access(
+    org_id: &str,
+    user_id: &str,
+    query: &ValuesQuery<'_>,
+) -> Result<(), VariableError> {
+    field_access::check_fields(
+        org_id,
+        user_id,
+        query.stream_type,
+        query.stream_name,
+        &[query.field.to_string()],
+    )
+    .await
+    .map_err(|e| VariableError::AccessDenied(e.to_string()))
+}
+
+/// Widens the time range to the multiples of `ttl` around it
+fn align_time_range((start_time, end_time): (i64, i64), ttl: i64) -> (i64, i64) {
+    if ttl <= 0 {
+        return (start_time, end_time);
+    }
+    let start = start_time - start_time.rem_euclid(ttl);
+    let end = match end_time.rem_euclid(ttl) {
+        0 => end_time,
+        rem => end_time - rem + ttl,
+    };
+    (start, end)
+}
+
+fn values_sql(
+    distinct_stream_name: &str,
+    field: &str,
+    prefix: Option<&str>,
+    size: usize,
+) -> String {
+    let prefix = prefix
+        .and_then(|v| sql_literal(&json::Value::from(v)))
+        .map(|v| format!(" AND starts_with(lower(CAST(\"{field}\" AS VARCHAR)), {v})"))
+        .unwrap_or_default();
+    format!(
+        "SELECT \"{field}\" AS zo_sql_key, SUM(count) AS zo_sql_num FROM \"{distinct_stream_name}\" WHERE \"{field}\" IS NOT NULL{prefix} GROUP BY zo_sql_key ORDER BY zo_sql_num DESC LIMIT {size}"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_align_time_range() {
+        assert_eq!(align_time_range((15, 95), 10), (10, 100));
+        assert_eq!(align_time_range((10, 100), 10), (10, 100));
+        assert_eq!(align_time_range((15, 95), 0), (15, 95));
+    }
+
+    #[test]
+    fn test_values_sql() {
+        assert_eq!(
+            values_sql("distinct_values_logs_app", "host", None, 10),
+            "SELECT \"host\" AS zo_sql_key, SUM(count) AS zo_sql_num FROM \"distinct_values_logs_app\" WHERE \"host\" IS NOT NULL GROUP BY zo_sql_key ORDER BY zo_sql_num DESC LIMIT 10"
+        );
+        assert!(
+            values_sql("distinct_values_logs_app", "host", Some("we'b"), 10)
+                .contains("AND starts_with(lower(CAST(\"host\" AS VARCHAR)), 'we''b')")
+        );
+    }
+}
@@ -0,0 +1,241 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Datasets, named groups of streams queried like a stream.
+//!
+//! The SQL of a search, so of an alert or a dashboard panel, querying a
+//! dataset is rewritten before it is planned to query the union of the
+//! member streams. The members are resolved from the patterns of the dataset
+//! at that time, so the streams created later join it. A role is granted a
+//! dataset like a stream of its name, the grant covers the member streams
+//! when they are read through the dataset, so saving a dataset needs the
+//! permission on each of its current members.
+
+use config::{
+    get_config,
+    meta::{
+        dataset::Dataset,
+        sql::{TableReferenceExt, resolve_stream_names_with_type},
+        stream::StreamType,
+    },
+    utils::{schema::format_stream_name, time::now_micros},
+};
+use hashbrown::HashMap;
+use sqlparser::{ast::VisitMut, dialect::PostgreSqlDialect, parser::Parser};
+
+use crate::{
+    common::infra::config::DATASETS,
+    service::{db, search::sql::rewriter::replace_dataset::ReplaceDatasetVisitor},
+};
+
+fn dataset_key(org_id: &str, stream_type: StreamType, name: &str) -> String {
+    format!("{org_id}/{stream_type}/{name}")
+}
+
+pub fn get(org_id: &str, stream_type: StreamType, name: &str) -> Option<Dataset> {
+    DATASETS
+        .get(&dataset_key(org_id, stream_type, name))
+        .map(|v| v.value().clone())
+}
+
+/// The datasets of the org, of every stream type when none is given
+pub fn list(org_id: &str, stream_type: Option<StreamType>) -> Vec<Dataset> {
+    let prefix = match stream_type {
+        Some(stream_type) => format!("{org_id}/{stream_type}/"),
+        None => format!("{org_id}/"),
+    };
+    let mut datasets = DATASETS
+        .iter()
+        .filter(|v| v.key().starts_with(&prefix))
+        .map(|v| v.value().clone())
+        .collect::<Vec<_>>();
+    datasets.sort_by(|a, b| a.name.cmp(&b.name));
+    datasets
+}
+
+/// The streams of the dataset, a stream of the name of the dataset is never
+/// one of them
+pub async fn member_streams(org_id: &str, dataset: &Dataset) -> Vec<String> {
+    let mut streams = db::schema::list_streams_from_cache(org_id, dataset.stream_type)
+        .await
+        .into_iter()
+        .filter(|name| *name != dataset.name && dataset.matches(name))
+        .collect::<Vec<_>>();
+    streams.sort();
+    streams
+}
+
+async fn stream_exists(org_id: &str, stream_type: StreamType, name: &str) -> bool {
+    infra::schema::get_cache(org_id, name, stream_type)
+        .await
+        .is_ok_and(|schema| !schema.fields_map().is_empty())
+}
+
+/// Rewrites the datasets the SQL queries to the union of their streams,
+/// returns None when it queries no dataset. A stream of the name of a dataset
+/// is queried instead of the dataset.
+pub async fn resolve_sql(org_id: &str, stream_type: StreamType, sql: &str) -> Option<String> {
+    if DATASETS.is_empty() {
+        return None;
+    }
+    let mut datasets = HashMap::new();
+    for stream in resolve_stream_names_with_type(sql).ok()? {
+        let name = stream.stream_name();
+        let stream_type = stream.get_stream_type(stream_type);
+        let Some(dataset) = get(org_id, stream_type, &name) else {
+            continue;
+        };
+        if stream_exists(org_id, stream_type, &name).await {
+            continue;
+        }
+        let streams = member_streams(org_id, &dataset).await;
+        if !streams.is_empty() {
+            datasets.insert(name, streams);
+        }
+    }
+    if datasets.is_empty() {
+        return None;
+    }
+    let mut statement = Parser::parse_sql(&PostgreSqlDialect {}, sql).ok()?.pop()?;
+    let _ = statement.visit(&mut ReplaceDatasetVisitor::new(datasets));
+    Some(statement.to_string())
+}
+
+/// Formats the name and the patterns of the dataset like the stream names
+pub fn normalize(dataset: &mut Dataset) -> Result<(), anyhow::Error> {
+    let skip_formatting = get_config().common.skip_formatting_stream_name;
+    dataset.name = dataset.name.trim().to_string();
+    if !skip_formatting {
+        dataset.name = format_stream_name(dataset.name);
+    }
+    if dataset.name.is_empty() {
+        return Err(anyhow::anyhow!("dataset name can't be empty"));
+    }
+    // the patterns are matched against formatted stream names, the `*` are
+    // kept
+    dataset.streams = dataset
+        .streams
+        .iter()
+        .map(|pattern| pattern.trim())
+        .filter(|pattern| !pattern.is_empty())
+        .map(|pattern| {
+            if skip_formatting {
+                pattern.to_string()
+            } else {
+                pattern
+                    .split('*')
+                    .map(|part| format_stream_name(part.to_string()))
+                    .collect::<Vec<_>>()
+                    .join("*")
+            }
+        })
+        .collect();
+    dataset.streams.sort();
+    dataset.streams.dedup();
+    if dataset.streams.is_empty() {
+        return Err(anyhow::anyhow!("dataset needs at least one stream"));
+    }
+    Ok(())
+}
+
+/// Creates the dataset, or updates it when `create` is false
+pub async fn save(
+    org_id: &str,
+    mut dataset: Dataset,
+    create: bool,
+) -> Result<Dataset, anyhow::Error> {
+    normalize(&mut dataset)?;
+
+    let stream_type = dataset.stream_type;
+    let existing = get(org_id, stream_type, &dataset.name);
+    match (&existing, create) {
+        (Some(_), true) => {
+            return Err(anyhow::anyhow!("dataset [{}] already exists", dataset.name));
+        }
+        (None, false) => {
+            return Err(anyhow::anyhow!("dataset [{}] not found", dataset.name));
+        }
+        _ => {}
+    }
+    if stream_exists(org_id, stream_type, &dataset.name).await {
+        return Err(anyhow::anyhow!("stream [{}] already exists", dataset.name));
+    }
+    if crate::service::stream_alias::resolve(org_id, stream_type, &dataset.name).is_some() {
+        return Err(anyhow::anyhow!(
+            "[{}] is already an alias of a stream",
+            dataset.name
+        ));
+    }
+
+    let now = now_micros();
+    dataset.created_at = existing.map(|v| v.created_at).unwrap_or(now);
+    dataset.updated_at = now;
+    db::dataset::set(org_id, &dataset).await?;
+    // searches right after on this node must see it before the watch does
+    DATASETS.insert(
+        dataset_key(org_id, stream_type, &dataset.name),
+        dataset.clone(),
+    );
+    Ok(dataset)
+}
+
+pub async fn delete(
+    org_id: &str,
+    stream_type: StreamType,
+    name: &str,
+) -> Result<bool, anyhow::Error> {
+    if get(org_id, stream_type, name).is_none() {
+        return Ok(false);
+    }
+    db::dataset::delete(org_id, stream_type, name).await?;
+    DATASETS.remove(&dataset_key(org_id, stream_type, name));
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list() {
+        let org_id = "test_dataset_list_org";
+        for (name, stream_type) in [
+            ("web", StreamType::Logs),
+            ("k8s", StreamType::Logs),
+            ("k8s", StreamType::Traces),
+        ] {
+            DATASETS.insert(
+                dataset_key(org_id, stream_type, name),
+                Dataset {
+                    name: name.to_string(),
+                    stream_type,
+                    streams: vec![format!("{name}_*")],
+                    ..Default::default()
+                },
+            );
+        }
+
+        let names = |list: Vec<Dataset>| list.into_iter().map(|v| v.name).collect::<Vec<_>>();
+        assert_eq!(
+            names(list(org_id, Some(StreamType::Logs))),
+            vec!["k8s", "web"]
+        );
+        assert_eq!(names(list(org_id, Some(StreamType::Traces))), vec!["k8s"]);
+        assert_eq!(list(org_id, None).len(), 3);
+        assert!(get(org_id, StreamType::Metrics, "k8s").is_none());
+
+        DATASETS.retain(|k, _| !k.starts_with(org_id));
+    }
+}
@@ -0,0 +1,33 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::service::db;
+
+const WATERMARK_KEY: &str = "/access_log_import/watermark";
+
+/// Returns the last imported object key of a source, `source` is
+/// `bucket/prefix`
+pub async fn get_watermark(source: &str) -> String {
+    let key = format!("{WATERMARK_KEY}/{source}");
+    match db::get(&key).await {
+        Ok(ret) => String::from_utf8_lossy(&ret).to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+pub async fn set_watermark(source: &str, object_key: &str) -> Result<(), anyhow::Error> {
+    let key = format!("{WATERMARK_KEY}/{source}");
+    Ok(db::put(&key, object_key.to_string().into(), db::NO_NEED_WATCH, None).await?)
+}
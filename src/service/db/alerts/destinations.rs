@@ -40,11 +40,23 @@ pub enum DestinationError {
     EmptyUrl,
     #[error("SNS destination must have Topic ARN and Region")]
     InvalidSns,
+    #[error("Kafka destination must have brokers and a topic")]
+    InvalidKafka,
+    #[error("Jira destination must have a url, a user, an API token, a project and an issue type")]
+    InvalidJira,
+    #[error("ServiceNow destination must have a url, a user and a password")]
+    InvalidServiceNow,
+    #[error("Invalid VRL template: {0}")]
+    InvalidVrlTemplate(String),
+    #[error("Destination is not allowed: {0}")]
+    EgressNotAllowed(String),
+    #[error("The {0} must be set again when the server or the user changes")]
+    SecretRequired(String),
     #[error("Email destination must have at least one email recipient")]
     EmptyEmail,
     #[error("Email destination recipients must be part of this org")]
     UserNotPermitted,
-    #[error("Email destination must have SMTP configured")]
+    #[error("Email destination must have SMTP configured, for the org or the instance")]
     SMTPUnavailable,
     #[error("Alert destination must have a template")]
     TemplateNotFound,
@@ -0,0 +1,44 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::{meta::destinations::EmailDelivery, utils::json};
+use infra::errors::Result;
+
+use crate::service::db;
+
+pub const EMAIL_DELIVERIES_KEY_PREFIX: &str = "/alert_email_deliveries";
+
+/// The deliveries of the destination, the newest first
+pub async fn get(org_id: &str, destination: &str) -> Result<Vec<EmailDelivery>> {
+    let key = format!("{EMAIL_DELIVERIES_KEY_PREFIX}/{org_id}/{destination}");
+    let ret = db::get(&key).await?;
+    Ok(json::from_slice(&ret)?)
+}
+
+pub async fn set(org_id: &str, destination: &str, deliveries: &[EmailDelivery]) -> Result<()> {
+    let key = format!("{EMAIL_DELIVERIES_KEY_PREFIX}/{org_id}/{destination}");
+    db::put(
+        &key,
+        json::to_vec(deliveries)?.into(),
+        db::NO_NEED_WATCH,
+        None,
+    )
+    .await
+}
+
+pub async fn delete(org_id: &str, destination: &str) -> Result<()> {
+    let key = format!("{EMAIL_DELIVERIES_KEY_PREFIX}/{org_id}/{destination}");
+    db::delete(&key, false, db::NO_NEED_WATCH, None).await
+}
@@ -14,6 +14,8 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 pub mod alert;
+pub mod backtest;
 pub mod destinations;
+pub mod email_deliveries;
 pub mod realtime_triggers;
 pub mod templates;
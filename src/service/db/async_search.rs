@@ -0,0 +1,49 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::{meta::async_search::AsyncSearchJob, utils::json};
+use infra::errors::Result;
+
+use crate::service::db;
+
+pub const ASYNC_SEARCH_KEY_PREFIX: &str = "/async_search";
+
+pub async fn get(org_id: &str, id: &str) -> Result<AsyncSearchJob> {
+    let key = format!("{ASYNC_SEARCH_KEY_PREFIX}/{org_id}/{id}");
+    let ret = db::get(&key).await?;
+    Ok(json::from_slice(&ret)?)
+}
+
+/// Lists the jobs of all orgs, oldest first
+pub async fn list() -> Result<Vec<AsyncSearchJob>> {
+    let key = format!("{ASYNC_SEARCH_KEY_PREFIX}/");
+    let mut jobs = db::list_values(&key)
+        .await?
+        .iter()
+        .filter_map(|v| json::from_slice::<AsyncSearchJob>(v).ok())
+        .collect::<Vec<_>>();
+    jobs.sort_by_key(|job| job.created_at);
+    Ok(jobs)
+}
+
+pub async fn set(job: &AsyncSearchJob) -> Result<()> {
+    let key = format!("{ASYNC_SEARCH_KEY_PREFIX}/{}/{}", job.org_id, job.id);
+    db::put(&key, json::to_vec(job)?.into(), db::NO_NEED_WATCH, None).await
+}
+
+pub async fn delete(org_id: &str, id: &str) -> Result<()> {
+    let key = format!("{ASYNC_SEARCH_KEY_PREFIX}/{org_id}/{id}");
+    db::delete(&key, false, db::NO_NEED_WATCH, None).await
+}
@@ -18,5 +18,7 @@ pub mod downsampling;
 pub mod files;
 pub mod organization;
 pub mod retention;
+pub mod rollup;
 pub mod stats;
 pub mod stream;
+pub mod tiering;
@@ -0,0 +1,146 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::{meta::stream::StreamType, utils::json};
+
+use crate::{common::meta::stream::ColdStorageRestore, service::db};
+
+fn mk_offset_key(org_id: &str, stream_type: StreamType, stream_name: &str) -> String {
+    format!("/compact/tiering/offset/{org_id}/{stream_type}/{stream_name}")
+}
+
+fn mk_restore_prefix(org_id: &str, stream_type: StreamType, stream_name: &str) -> String {
+    format!("/compact/tiering/restore/{org_id}/{stream_type}/{stream_name}/")
+}
+
+/// The time up to which the files of the stream are moved to the cold
+/// storage, 0 if they never were
+pub async fn get_offset(org_id: &str, stream_type: StreamType, stream_name: &str) -> i64 {
+    let key = mk_offset_key(org_id, stream_type, stream_name);
+    match db::get(&key).await {
+        Ok(ret) => String::from_utf8_lossy(&ret).parse().unwrap_or_default(),
+        Err(_) => 0,
+    }
+}
+
+pub async fn set_offset(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    offset: i64,
+) -> Result<(), anyhow::Error> {
+    let key = mk_offset_key(org_id, stream_type, stream_name);
+    db::put(&key, offset.to_string().into(), db::NO_NEED_WATCH, None).await?;
+    Ok(())
+}
+
+pub async fn get_restore(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    id: &str,
+) -> Result<ColdStorageRestore, anyhow::Error> {
+    let key = format!(
+        "{}{id}",
+        mk_restore_prefix(org_id, stream_type, stream_name)
+    );
+    let val = db::get(&key).await?;
+    Ok(json::from_slice(&val)?)
+}
+
+pub async fn set_restore(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    restore: &ColdStorageRestore,
+) -> Result<(), anyhow::Error> {
+    let key = format!(
+        "{}{}",
+        mk_restore_prefix(org_id, stream_type, stream_name),
+        restore.id
+    );
+    db::put(&key, json::to_vec(restore)?.into(), db::NO_NEED_WATCH, None).await?;
+    Ok(())
+}
+
+/// The restores of the stream, the latest first
+pub async fn list_restores(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> Result<Vec<ColdStorageRestore>, anyhow::Error> {
+    let prefix = mk_restore_prefix(org_id, stream_type, stream_name);
+    let mut list = db::list_values(&prefix)
+        .await?
+        .into_iter()
+        .map(|v| json::from_slice::<ColdStorageRestore>(&v))
+        .collect::<Result<Vec<_>, _>>()?;
+    list.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(list)
+}
+
+pub async fn delete_restore(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    id: &str,
+) -> Result<(), anyhow::Error> {
+    let key = format!(
+        "{}{id}",
+        mk_restore_prefix(org_id, stream_type, stream_name)
+    );
+    db::delete_if_exists(&key, false, db::NO_NEED_WATCH)
+        .await
+        .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_tiering_offset_and_restores() {
+        set_offset("default", StreamType::Logs, "tiering_offset", 300)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_offset("default", StreamType::Logs, "tiering_offset").await,
+            300
+        );
+
+        let restore = ColdStorageRestore {
+            id: "r1".to_string(),
+            start_time: 100,
+            end_time: 200,
+            created_at: 10,
+            ..Default::default()
+        };
+        set_restore("default", StreamType::Logs, "tiering_offset", &restore)
+            .await
+            .unwrap();
+        let ret = get_restore("default", StreamType::Logs, "tiering_offset", "r1")
+            .await
+            .unwrap();
+        assert_eq!(ret.end_time, 200);
+        delete_restore("default", StreamType::Logs, "tiering_offset", "r1")
+            .await
+            .unwrap();
+        assert!(
+            get_restore("default", StreamType::Logs, "tiering_offset", "r1")
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use config::{
+    meta::{dataset::Dataset, stream::StreamType},
+    utils::json,
+};
+
+use crate::{common::infra::config::DATASETS, service::db};
+
+const DATASET_KEY_PREFIX: &str = "/datasets/";
+
+pub async fn set(org_id: &str, dataset: &Dataset) -> Result<(), anyhow::Error> {
+    let key = format!(
+        "{DATASET_KEY_PREFIX}{org_id}/{}/{}",
+        dataset.stream_type, dataset.name
+    );
+    if let Err(e) = db::put(&key, json::to_vec(dataset)?.into(), db::NEED_WATCH, None).await {
+        log::error!("Error saving dataset: {e}");
+        return Err(anyhow::anyhow!("Error saving dataset: {}", e));
+    }
+    Ok(())
+}
+
+pub async fn delete(
+    org_id: &str,
+    stream_type: StreamType,
+    name: &str,
+) -> Result<(), anyhow::Error> {
+    let key = format!("{DATASET_KEY_PREFIX}{org_id}/{stream_type}/{name}");
+    if let Err(e) = db::delete(&key, false, db::NEED_WATCH, None).await {
+        log::error!("Error deleting dataset: {e}");
+        return Err(anyhow::anyhow!("Error deleting dataset: {}", e));
+    }
+    Ok(())
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<Dataset>, anyhow::Error> {
+    Ok(db::list(&format!("{DATASET_KEY_PREFIX}{org_id}/"))
+        .await?
+        .values()
+        .filter_map(|val| json::from_slice(val).ok())
+        .collect())
+}
+
+pub async fn watch() -> Result<(), anyhow::Error> {
+    let key = DATASET_KEY_PREFIX;
+    let cluster_coordinator = db::get_coordinator().await;
+    let mut events = cluster_coordinator.watch(key).await?;
+    let events = Arc::get_mut(&mut events).unwrap();
+    log::info!("Start watching datasets");
+    loop {
+        let ev = match events.recv().await {
+            Some(ev) => ev,
+            None => {
+                log::error!("watch_datasets: event channel closed");
+                break;
+            }
+        };
+        match ev {
+            db::Event::Put(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                let item_value: Dataset = match db::get(&ev.key).await {
+                    Ok(val) => match json::from_slice(&val) {
+                        Ok(val) => val,
+                        Err(e) => {
+                            log::error!("Error getting value: {e}");
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        log::error!("Error getting value: {e}");
+                        continue;
+                    }
+                };
+                DATASETS.insert(item_key.to_owned(), item_value);
+            }
+            db::Event::Delete(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                DATASETS.remove(item_key);
+            }
+            db::Event::Empty => {}
+        }
+    }
+    Ok(())
+}
+
+pub async fn cache() -> Result<(), anyhow::Error> {
+    let key = DATASET_KEY_PREFIX;
+    let ret = db::list(key).await?;
+    for (item_key, item_value) in ret {
+        let item_key = item_key.strip_prefix(key).unwrap();
+        let json_val: Dataset = json::from_slice(&item_value)?;
+        DATASETS.insert(item_key.to_string(), json_val);
+    }
+    log::info!("Datasets Cached");
+    Ok(())
+}
@@ -20,7 +20,9 @@ use arrow::{
     datatypes::DataType,
 };
 use config::{
-    QUERY_WITH_NO_LIMIT, ider,
+    QUERY_WITH_NO_LIMIT,
+    cluster::LOCAL_NODE,
+    ider,
     meta::stream::{EnrichmentTableMetaStreamStats, StreamType},
     utils::{json, time::BASE_TIME},
 };
@@ -40,7 +42,7 @@ use crate::{
     service::{
         db as db_service,
         enrichment::{StreamTable, storage::Values},
-        search::cluster::http as search_cluster,
+        search::{cluster::http as search_cluster, datafusion::distributed_plan::enrich_exec},
     },
 };
 
@@ -595,12 +597,19 @@ pub async fn watch() -> Result<(), anyhow::Error> {
                         data,
                     },
                 );
+                if LOCAL_NODE.is_querier() {
+                    enrich_exec::warm_cache(org_id, stream_name).await;
+                }
             }
             infra_db::Event::Delete(ev) => {
                 let item_key = ev.key.strip_prefix(key).unwrap();
                 if let Some((key, _)) = ENRICHMENT_TABLES.remove(item_key) {
                     log::info!("deleted enrichment table: {key}");
                 }
+                let keys = item_key.split('/').collect::<Vec<&str>>();
+                if keys.len() > 2 {
+                    enrich_exec::evict_cache(keys[0], keys[2]);
+                }
             }
             infra_db::Event::Empty => {}
         }
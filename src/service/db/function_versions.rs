@@ -0,0 +1,61 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::{meta::function::FunctionVersion, utils::json};
+use infra::errors::Result;
+
+use crate::service::db;
+
+pub const FUNCTION_VERSIONS_KEY_PREFIX: &str = "/function_versions";
+
+fn key(org_id: &str, name: &str, version: u32) -> String {
+    format!("{FUNCTION_VERSIONS_KEY_PREFIX}/{org_id}/{name}/{version:010}")
+}
+
+pub async fn get(org_id: &str, name: &str, version: u32) -> Result<FunctionVersion> {
+    let ret = db::get(&key(org_id, name, version)).await?;
+    Ok(json::from_slice(&ret)?)
+}
+
+pub async fn set(org_id: &str, name: &str, version: &FunctionVersion) -> Result<()> {
+    db::put(
+        &key(org_id, name, version.version),
+        json::to_vec(version)?.into(),
+        db::NO_NEED_WATCH,
+        None,
+    )
+    .await
+}
+
+/// The versions of the function, oldest first
+pub async fn list(org_id: &str, name: &str) -> Result<Vec<FunctionVersion>> {
+    let prefix = format!("{FUNCTION_VERSIONS_KEY_PREFIX}/{org_id}/{name}/");
+    let mut versions = db::list_values(&prefix)
+        .await?
+        .into_iter()
+        .filter_map(|val| json::from_slice::<FunctionVersion>(&val).ok())
+        .collect::<Vec<_>>();
+    versions.sort_by_key(|v| v.version);
+    Ok(versions)
+}
+
+pub async fn delete(org_id: &str, name: &str, version: u32) -> Result<()> {
+    db::delete(&key(org_id, name, version), false, db::NO_NEED_WATCH, None).await
+}
+
+pub async fn delete_all(org_id: &str, name: &str) -> Result<()> {
+    let prefix = format!("{FUNCTION_VERSIONS_KEY_PREFIX}/{org_id}/{name}/");
+    db::delete(&prefix, true, db::NO_NEED_WATCH, None).await
+}
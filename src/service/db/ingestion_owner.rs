@@ -0,0 +1,30 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::service::db;
+
+/// Returns the node running the singleton ingester stored under `prefix`, e.g.
+/// `/kafka_ingestion`
+pub async fn get(prefix: &str) -> String {
+    match db::get(&format!("{prefix}/owner")).await {
+        Ok(ret) => String::from_utf8_lossy(&ret).to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+pub async fn set(prefix: &str, node: &str) -> Result<(), anyhow::Error> {
+    let key = format!("{prefix}/owner");
+    Ok(db::put(&key, node.to_string().into(), db::NO_NEED_WATCH, None).await?)
+}
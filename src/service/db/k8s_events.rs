@@ -0,0 +1,39 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::service::db;
+
+const WATCH_STATE_KEY: &str = "/k8s_events/watch";
+
+/// Returns the timestamp of the last ingested event, the node holding the watch
+/// was stored after it by the earlier versions
+pub async fn get_watermark() -> i64 {
+    let value = match db::get(WATCH_STATE_KEY).await {
+        Ok(ret) => String::from_utf8_lossy(&ret).to_string(),
+        Err(_) => return 0,
+    };
+    let watermark = value.split_once(';').map(|(v, _)| v).unwrap_or(&value);
+    watermark.parse().unwrap_or_default()
+}
+
+pub async fn set_watermark(watermark: i64) -> Result<(), anyhow::Error> {
+    Ok(db::put(
+        WATCH_STATE_KEY,
+        watermark.to_string().into(),
+        db::NO_NEED_WATCH,
+        None,
+    )
+    .await?)
+}
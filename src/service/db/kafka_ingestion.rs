@@ -0,0 +1,37 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::service::db;
+
+const OFFSET_KEY: &str = "/kafka_ingestion/offset";
+
+/// Returns the offset of the next record to consume from a partition for the
+/// subscription, `None` when the partition was never consumed
+pub async fn get_offset(subscription: &str, partition: i32) -> Option<i64> {
+    let key = format!("{OFFSET_KEY}/{subscription}/{partition}");
+    match db::get(&key).await {
+        Ok(ret) => String::from_utf8_lossy(&ret).parse().ok(),
+        Err(_) => None,
+    }
+}
+
+pub async fn set_offset(
+    subscription: &str,
+    partition: i32,
+    offset: i64,
+) -> Result<(), anyhow::Error> {
+    let key = format!("{OFFSET_KEY}/{subscription}/{partition}");
+    Ok(db::put(&key, offset.to_string().into(), db::NO_NEED_WATCH, None).await?)
+}
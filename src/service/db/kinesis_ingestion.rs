@@ -0,0 +1,73 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::utils::json;
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::service::db;
+
+const LEASE_KEY: &str = "/kinesis_ingestion/lease";
+
+/// The lease of a shard, held by the node consuming it
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ShardLease {
+    /// The node consuming the shard
+    pub owner: String,
+    /// The lease can be taken over by another node after this time, in
+    /// microseconds
+    pub expires_at: i64,
+    /// Sequence number of the last record ingested
+    #[serde(default)]
+    pub checkpoint: Option<String>,
+    /// The shard was closed by resharding and consumed to its end
+    #[serde(default)]
+    pub finished: bool,
+}
+
+pub async fn get_lease(stream: &str, shard_id: &str) -> Option<ShardLease> {
+    let key = format!("{LEASE_KEY}/{stream}/{shard_id}");
+    match db::get(&key).await {
+        Ok(ret) => json::from_slice(&ret).ok(),
+        Err(_) => None,
+    }
+}
+
+/// Returns the leases of the shards of a stream by shard id
+pub async fn list_leases(stream: &str) -> Result<HashMap<String, ShardLease>, anyhow::Error> {
+    let key = format!("{LEASE_KEY}/{stream}/");
+    Ok(db::list(&key)
+        .await?
+        .into_iter()
+        .filter_map(|(k, v)| {
+            let shard_id = k.rsplit('/').next()?.to_string();
+            Some((shard_id, json::from_slice(&v).ok()?))
+        })
+        .collect())
+}
+
+pub async fn set_lease(
+    stream: &str,
+    shard_id: &str,
+    lease: &ShardLease,
+) -> Result<(), anyhow::Error> {
+    let key = format!("{LEASE_KEY}/{stream}/{shard_id}");
+    Ok(db::put(&key, json::to_vec(lease)?.into(), db::NO_NEED_WATCH, None).await?)
+}
+
+pub async fn delete_lease(stream: &str, shard_id: &str) -> Result<(), anyhow::Error> {
+    let key = format!("{LEASE_KEY}/{stream}/{shard_id}");
+    Ok(db::delete(&key, false, db::NO_NEED_WATCH, None).await?)
+}
@@ -0,0 +1,94 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use config::{meta::stream::LevelMapping, utils::json};
+
+use crate::{common::infra::config::LEVEL_MAPPINGS, service::db};
+
+const LEVEL_MAPPING_KEY_PREFIX: &str = "/level_mapping/";
+
+pub async fn set(org_id: &str, mapping: &LevelMapping) -> Result<(), anyhow::Error> {
+    let key = format!("{LEVEL_MAPPING_KEY_PREFIX}{org_id}");
+    if let Err(e) = db::put(&key, json::to_vec(mapping)?.into(), db::NEED_WATCH, None).await {
+        log::error!("Error saving level mapping: {e}");
+        return Err(anyhow::anyhow!("Error saving level mapping: {}", e));
+    }
+    Ok(())
+}
+
+pub async fn delete(org_id: &str) -> Result<(), anyhow::Error> {
+    let key = format!("{LEVEL_MAPPING_KEY_PREFIX}{org_id}");
+    if let Err(e) = db::delete(&key, false, db::NEED_WATCH, None).await {
+        log::error!("Error deleting level mapping: {e}");
+        return Err(anyhow::anyhow!("Error deleting level mapping: {}", e));
+    }
+    Ok(())
+}
+
+pub async fn watch() -> Result<(), anyhow::Error> {
+    let key = LEVEL_MAPPING_KEY_PREFIX;
+    let cluster_coordinator = db::get_coordinator().await;
+    let mut events = cluster_coordinator.watch(key).await?;
+    let events = Arc::get_mut(&mut events).unwrap();
+    log::info!("Start watching level mappings");
+    loop {
+        let ev = match events.recv().await {
+            Some(ev) => ev,
+            None => {
+                log::error!("watch_level_mappings: event channel closed");
+                break;
+            }
+        };
+        match ev {
+            db::Event::Put(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                let item_value: LevelMapping = match db::get(&ev.key).await {
+                    Ok(val) => match json::from_slice(&val) {
+                        Ok(val) => val,
+                        Err(e) => {
+                            log::error!("Error getting value: {e}");
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        log::error!("Error getting value: {e}");
+                        continue;
+                    }
+                };
+                LEVEL_MAPPINGS.insert(item_key.to_owned(), Arc::new(item_value));
+            }
+            db::Event::Delete(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                LEVEL_MAPPINGS.remove(item_key);
+            }
+            db::Event::Empty => {}
+        }
+    }
+    Ok(())
+}
+
+pub async fn cache() -> Result<(), anyhow::Error> {
+    let key = LEVEL_MAPPING_KEY_PREFIX;
+    let ret = db::list(key).await?;
+    for (item_key, item_value) in ret {
+        let item_key = item_key.strip_prefix(key).unwrap();
+        let json_val: LevelMapping = json::from_slice(&item_value)?;
+        LEVEL_MAPPINGS.insert(item_key.to_string(), Arc::new(json_val));
+    }
+    log::info!("Level mappings Cached");
+    Ok(())
+}
@@ -0,0 +1,131 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use config::{meta::log_metrics::LogMetricRule, utils::json};
+use infra::errors::Result;
+
+use crate::{common::infra::config::LOG_METRIC_RULES, service::db};
+
+pub const LOG_METRICS_KEY_PREFIX: &str = "/organization/log_metrics/";
+
+pub async fn get(org_id: &str, id: &str) -> Result<LogMetricRule> {
+    let key = format!("{LOG_METRICS_KEY_PREFIX}{org_id}/{id}");
+    let ret = db::get(&key).await?;
+    Ok(json::from_slice(&ret)?)
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<LogMetricRule>> {
+    let key = format!("{LOG_METRICS_KEY_PREFIX}{org_id}/");
+    let mut rules = db::list_values(&key)
+        .await?
+        .iter()
+        .filter_map(|v| json::from_slice::<LogMetricRule>(v).ok())
+        .collect::<Vec<_>>();
+    rules.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(rules)
+}
+
+pub async fn set(org_id: &str, rule: &LogMetricRule) -> Result<()> {
+    let key = format!("{LOG_METRICS_KEY_PREFIX}{org_id}/{}", rule.id);
+    db::put(&key, json::to_vec(rule)?.into(), db::NEED_WATCH, None).await
+}
+
+pub async fn delete(org_id: &str, id: &str) -> Result<()> {
+    let key = format!("{LOG_METRICS_KEY_PREFIX}{org_id}/{id}");
+    db::delete(&key, false, db::NEED_WATCH, None).await
+}
+
+/// Removes a rule from the cache, wherever its stream
+fn uncache(org_id: &str, id: &str) {
+    let prefix = format!("{org_id}/");
+    LOG_METRIC_RULES.retain(|key, rules| {
+        if key.starts_with(&prefix) {
+            rules.retain(|rule| rule.id != id);
+        }
+        !rules.is_empty()
+    });
+}
+
+fn cache_rule(org_id: &str, rule: LogMetricRule) {
+    uncache(org_id, &rule.id);
+    LOG_METRIC_RULES
+        .entry(format!("{org_id}/{}", rule.stream_name))
+        .or_default()
+        .push(Arc::new(rule));
+}
+
+pub async fn watch() -> Result<(), anyhow::Error> {
+    let key = LOG_METRICS_KEY_PREFIX;
+    let cluster_coordinator = db::get_coordinator().await;
+    let mut events = cluster_coordinator.watch(key).await?;
+    let events = Arc::get_mut(&mut events).unwrap();
+    log::info!("Start watching log metrics");
+    loop {
+        let ev = match events.recv().await {
+            Some(ev) => ev,
+            None => {
+                log::error!("watch_log_metrics: event channel closed");
+                break;
+            }
+        };
+        match ev {
+            db::Event::Put(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                let Some((org_id, _)) = item_key.split_once('/') else {
+                    continue;
+                };
+                let item_value: LogMetricRule = match db::get(&ev.key).await {
+                    Ok(val) => match json::from_slice(&val) {
+                        Ok(val) => val,
+                        Err(e) => {
+                            log::error!("Error getting value: {e}");
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        log::error!("Error getting value: {e}");
+                        continue;
+                    }
+                };
+                cache_rule(org_id, item_value);
+            }
+            db::Event::Delete(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                if let Some((org_id, id)) = item_key.split_once('/') {
+                    uncache(org_id, id);
+                }
+            }
+            db::Event::Empty => {}
+        }
+    }
+    Ok(())
+}
+
+pub async fn cache() -> Result<(), anyhow::Error> {
+    let key = LOG_METRICS_KEY_PREFIX;
+    let ret = db::list(key).await?;
+    for (item_key, item_value) in ret {
+        let item_key = item_key.strip_prefix(key).unwrap();
+        let Some((org_id, _)) = item_key.split_once('/') else {
+            continue;
+        };
+        let json_val: LogMetricRule = json::from_slice(&item_value)?;
+        cache_rule(org_id, json_val);
+    }
+    log::info!("Log metrics Cached");
+    Ok(())
+}
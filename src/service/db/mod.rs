@@ -21,38 +21,57 @@ use {
     infra::errors::Error, o2_enterprise::enterprise::common::config::get_config as get_o2_config,
 };
 
+pub mod access_log_import;
 pub mod ai_prompts;
 pub mod alerts;
+pub mod async_search;
 pub mod backfill;
 pub mod compact;
 pub mod dashboards;
+pub mod dataset;
+pub mod db_pollers;
 pub mod distinct_values;
 pub mod enrichment_table;
 pub mod file_list;
+pub mod function_versions;
 pub mod functions;
+pub mod ingestion_owner;
+pub mod k8s_events;
+pub mod kafka_ingestion;
 #[cfg(feature = "enterprise")]
 pub mod keys;
+pub mod kinesis_ingestion;
 pub mod kv;
+pub mod level_mapping;
 #[cfg(feature = "enterprise")]
 pub mod license;
+pub mod log_metrics;
 pub mod metas;
 pub mod metrics;
 #[cfg(feature = "enterprise")]
 pub mod ofga;
+pub mod org_lifecycle;
 pub mod org_users;
 pub mod organization;
 pub mod pipeline;
 pub mod pipeline_errors;
+pub mod rabbitmq;
 #[cfg(feature = "vectorscan")]
 pub mod re_pattern;
+pub mod record_deletion;
+pub mod recording_rules;
+pub mod replay;
 pub mod saved_view;
+pub mod scheduled_exports;
 pub mod scheduler;
 pub mod schema;
 pub mod search_job;
 pub mod session;
 pub mod short_url;
+pub mod stream_alias;
 pub mod system_settings;
 pub mod user;
+pub mod webhook;
 
 pub(crate) use infra_db::{Event, NEED_WATCH, NO_NEED_WATCH, get_coordinator};
 
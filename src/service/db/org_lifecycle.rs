@@ -0,0 +1,97 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use config::utils::json;
+
+use crate::{
+    common::{infra::config::ORG_LIFECYCLES, meta::organization::OrgLifecycle},
+    service::db,
+};
+
+const ORG_LIFECYCLE_KEY_PREFIX: &str = "/organization/lifecycle/";
+
+pub async fn set(org_id: &str, lifecycle: &OrgLifecycle) -> Result<(), anyhow::Error> {
+    let key = format!("{ORG_LIFECYCLE_KEY_PREFIX}{org_id}");
+    if let Err(e) = db::put(&key, json::to_vec(lifecycle)?.into(), db::NEED_WATCH, None).await {
+        log::error!("Error saving org lifecycle: {e}");
+        return Err(anyhow::anyhow!("Error saving org lifecycle: {}", e));
+    }
+    Ok(())
+}
+
+pub async fn delete(org_id: &str) -> Result<(), anyhow::Error> {
+    let key = format!("{ORG_LIFECYCLE_KEY_PREFIX}{org_id}");
+    if let Err(e) = db::delete(&key, false, db::NEED_WATCH, None).await {
+        log::error!("Error deleting org lifecycle: {e}");
+        return Err(anyhow::anyhow!("Error deleting org lifecycle: {}", e));
+    }
+    Ok(())
+}
+
+pub async fn watch() -> Result<(), anyhow::Error> {
+    let key = ORG_LIFECYCLE_KEY_PREFIX;
+    let cluster_coordinator = db::get_coordinator().await;
+    let mut events = cluster_coordinator.watch(key).await?;
+    let events = Arc::get_mut(&mut events).unwrap();
+    log::info!("Start watching org lifecycles");
+    loop {
+        let ev = match events.recv().await {
+            Some(ev) => ev,
+            None => {
+                log::error!("watch_org_lifecycles: event channel closed");
+                break;
+            }
+        };
+        match ev {
+            db::Event::Put(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                let item_value: OrgLifecycle = match db::get(&ev.key).await {
+                    Ok(val) => match json::from_slice(&val) {
+                        Ok(val) => val,
+                        Err(e) => {
+                            log::error!("Error getting value: {e}");
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        log::error!("Error getting value: {e}");
+                        continue;
+                    }
+                };
+                ORG_LIFECYCLES.insert(item_key.to_owned(), item_value);
+            }
+            db::Event::Delete(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                ORG_LIFECYCLES.remove(item_key);
+            }
+            db::Event::Empty => {}
+        }
+    }
+    Ok(())
+}
+
+pub async fn cache() -> Result<(), anyhow::Error> {
+    let key = ORG_LIFECYCLE_KEY_PREFIX;
+    let ret = db::list(key).await?;
+    for (item_key, item_value) in ret {
+        let item_key = item_key.strip_prefix(key).unwrap();
+        let json_val: OrgLifecycle = json::from_slice(&item_value)?;
+        ORG_LIFECYCLES.insert(item_key.to_string(), json_val);
+    }
+    log::info!("Org lifecycles Cached");
+    Ok(())
+}
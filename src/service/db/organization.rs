@@ -15,7 +15,13 @@
 
 use std::sync::Arc;
 
-use config::utils::json;
+use config::{
+    meta::{
+        search::{SearchLimits, SearchQuota},
+        stream::{IngestQuota, StreamSettingsTemplate, StreamType},
+    },
+    utils::json,
+};
 use infra::{
     db::put_into_db_coordinator,
     errors::{self, Error},
@@ -140,6 +146,69 @@ pub async fn get_org_setting_toggle_ingestion_logs(org_id: &str) -> Result<bool,
     Ok(toggle_ingestion_logs)
 }
 
+/// Get the settings template for new streams of a type, if the org has one
+pub async fn get_stream_settings_template(
+    org_id: &str,
+    stream_type: StreamType,
+) -> Option<StreamSettingsTemplate> {
+    let key = format!("{ORG_SETTINGS_KEY_PREFIX}/{org_id}");
+    if let Some(v) = ORGANIZATION_SETTING.read().await.get(&key) {
+        return v.stream_settings_templates.get(&stream_type).cloned();
+    }
+    match get_org_setting(org_id).await {
+        Ok(v) => v.stream_settings_templates.get(&stream_type).cloned(),
+        Err(e) => {
+            log::error!("[ORG] get settings for {org_id} failed: {e}");
+            None
+        }
+    }
+}
+
+/// Get the ingestion quota set for the org, empty when it uses the defaults
+pub async fn get_ingest_quota(org_id: &str) -> IngestQuota {
+    let key = format!("{ORG_SETTINGS_KEY_PREFIX}/{org_id}");
+    if let Some(v) = ORGANIZATION_SETTING.read().await.get(&key) {
+        return v.ingest_quota;
+    }
+    match get_org_setting(org_id).await {
+        Ok(v) => v.ingest_quota,
+        Err(e) => {
+            log::error!("[ORG] get settings for {org_id} failed: {e}");
+            IngestQuota::default()
+        }
+    }
+}
+
+/// Get the search quota set for the org, empty when it uses the defaults
+pub async fn get_search_quota(org_id: &str) -> SearchQuota {
+    let key = format!("{ORG_SETTINGS_KEY_PREFIX}/{org_id}");
+    if let Some(v) = ORGANIZATION_SETTING.read().await.get(&key) {
+        return v.search_quota;
+    }
+    match get_org_setting(org_id).await {
+        Ok(v) => v.search_quota,
+        Err(e) => {
+            log::error!("[ORG] get settings for {org_id} failed: {e}");
+            SearchQuota::default()
+        }
+    }
+}
+
+/// Get the limits of the searches of the users of the org
+pub async fn get_search_limits(org_id: &str) -> SearchLimits {
+    let key = format!("{ORG_SETTINGS_KEY_PREFIX}/{org_id}");
+    if let Some(v) = ORGANIZATION_SETTING.read().await.get(&key) {
+        return v.search_limits.clone();
+    }
+    match get_org_setting(org_id).await {
+        Ok(v) => v.search_limits,
+        Err(e) => {
+            log::error!("[ORG] get settings for {org_id} failed: {e}");
+            SearchLimits::default()
+        }
+    }
+}
+
 /// Cache the existing org settings in the beginning
 pub async fn org_settings_cache() -> Result<(), anyhow::Error> {
     let prefix = ORG_SETTINGS_KEY_PREFIX;
@@ -0,0 +1,67 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::utils::json;
+use infra::errors::Error;
+
+use crate::{common::meta::rabbitmq::RabbitmqSource, service::db};
+
+pub const RABBITMQ_KEY_PREFIX: &str = "/rabbitmq";
+
+pub async fn set(org_id: &str, source: &RabbitmqSource) -> Result<(), Error> {
+    let key = format!("{RABBITMQ_KEY_PREFIX}/{org_id}/{}", source.name);
+    db::put(
+        &key,
+        json::to_vec(source).unwrap().into(),
+        db::NO_NEED_WATCH,
+        None,
+    )
+    .await
+}
+
+pub async fn get(org_id: &str, name: &str) -> Result<RabbitmqSource, Error> {
+    let key = format!("{RABBITMQ_KEY_PREFIX}/{org_id}/{name}");
+    let ret = db::get(&key).await?;
+    Ok(json::from_slice(&ret)?)
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<RabbitmqSource>, Error> {
+    let key = format!("{RABBITMQ_KEY_PREFIX}/{org_id}/");
+    let mut sources: Vec<RabbitmqSource> = db::list_values(&key)
+        .await?
+        .iter()
+        .filter_map(|v| json::from_slice(v).ok())
+        .collect();
+    sources.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(sources)
+}
+
+/// Returns the sources of every organization with their org id
+pub async fn list_all() -> Result<Vec<(String, RabbitmqSource)>, Error> {
+    let key = format!("{RABBITMQ_KEY_PREFIX}/");
+    Ok(db::list(&key)
+        .await?
+        .into_iter()
+        .filter_map(|(k, v)| {
+            let org_id = k.strip_prefix(&key)?.split('/').next()?.to_string();
+            Some((org_id, json::from_slice(&v).ok()?))
+        })
+        .collect())
+}
+
+pub async fn delete(org_id: &str, name: &str) -> Result<(), Error> {
+    let key = format!("{RABBITMQ_KEY_PREFIX}/{org_id}/{name}");
+    db::delete(&key, false, db::NO_NEED_WATCH, None).await
+}
@@ -0,0 +1,64 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::{meta::record_deletion::RecordDeletion, utils::json};
+use infra::errors::Result;
+
+use crate::service::db;
+
+pub const RECORD_DELETIONS_KEY_PREFIX: &str = "/record_deletions";
+
+pub async fn get(org_id: &str, id: &str) -> Result<RecordDeletion> {
+    let key = format!("{RECORD_DELETIONS_KEY_PREFIX}/{org_id}/{id}");
+    let ret = db::get(&key).await?;
+    Ok(json::from_slice(&ret)?)
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<RecordDeletion>> {
+    let key = format!("{RECORD_DELETIONS_KEY_PREFIX}/{org_id}/");
+    let mut jobs = db::list_values(&key)
+        .await?
+        .iter()
+        .filter_map(|v| json::from_slice::<RecordDeletion>(v).ok())
+        .collect::<Vec<_>>();
+    jobs.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(jobs)
+}
+
+/// The jobs of all the organizations, by organization, the oldest first
+pub async fn list_all() -> Result<Vec<(String, RecordDeletion)>> {
+    let key = format!("{RECORD_DELETIONS_KEY_PREFIX}/");
+    let mut jobs = db::list(&key)
+        .await?
+        .into_iter()
+        .filter_map(|(k, v)| {
+            let org_id = k.strip_prefix(&key)?.split('/').next()?.to_string();
+            let job = json::from_slice::<RecordDeletion>(&v).ok()?;
+            Some((org_id, job))
+        })
+        .collect::<Vec<_>>();
+    jobs.sort_by(|(_, a), (_, b)| a.created_at.cmp(&b.created_at));
+    Ok(jobs)
+}
+
+pub async fn set(org_id: &str, job: &RecordDeletion) -> Result<()> {
+    let key = format!("{RECORD_DELETIONS_KEY_PREFIX}/{org_id}/{}", job.id);
+    db::put(&key, json::to_vec(job)?.into(), db::NO_NEED_WATCH, None).await
+}
+
+pub async fn delete(org_id: &str, id: &str) -> Result<()> {
+    let key = format!("{RECORD_DELETIONS_KEY_PREFIX}/{org_id}/{id}");
+    db::delete(&key, false, db::NO_NEED_WATCH, None).await
+}
@@ -0,0 +1,48 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::{meta::recording_rules::RecordingRule, utils::json};
+use infra::errors::Result;
+
+use crate::service::db;
+
+pub const RECORDING_RULES_KEY_PREFIX: &str = "/organization/recording_rules";
+
+pub async fn get(org_id: &str, id: &str) -> Result<RecordingRule> {
+    let key = format!("{RECORDING_RULES_KEY_PREFIX}/{org_id}/{id}");
+    let ret = db::get(&key).await?;
+    Ok(json::from_slice(&ret)?)
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<RecordingRule>> {
+    let key = format!("{RECORDING_RULES_KEY_PREFIX}/{org_id}/");
+    let mut rules = db::list_values(&key)
+        .await?
+        .iter()
+        .filter_map(|v| json::from_slice::<RecordingRule>(v).ok())
+        .collect::<Vec<_>>();
+    rules.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(rules)
+}
+
+pub async fn set(org_id: &str, rule: &RecordingRule) -> Result<()> {
+    let key = format!("{RECORDING_RULES_KEY_PREFIX}/{org_id}/{}", rule.id);
+    db::put(&key, json::to_vec(rule)?.into(), db::NO_NEED_WATCH, None).await
+}
+
+pub async fn delete(org_id: &str, id: &str) -> Result<()> {
+    let key = format!("{RECORDING_RULES_KEY_PREFIX}/{org_id}/{id}");
+    db::delete(&key, false, db::NO_NEED_WATCH, None).await
+}
@@ -0,0 +1,48 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::{meta::replay::Replay, utils::json};
+use infra::errors::Result;
+
+use crate::service::db;
+
+pub const REPLAYS_KEY_PREFIX: &str = "/replays";
+
+pub async fn get(org_id: &str, id: &str) -> Result<Replay> {
+    let key = format!("{REPLAYS_KEY_PREFIX}/{org_id}/{id}");
+    let ret = db::get(&key).await?;
+    Ok(json::from_slice(&ret)?)
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<Replay>> {
+    let key = format!("{REPLAYS_KEY_PREFIX}/{org_id}/");
+    let mut replays = db::list_values(&key)
+        .await?
+        .iter()
+        .filter_map(|v| json::from_slice::<Replay>(v).ok())
+        .collect::<Vec<_>>();
+    replays.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(replays)
+}
+
+pub async fn set(org_id: &str, replay: &Replay) -> Result<()> {
+    let key = format!("{REPLAYS_KEY_PREFIX}/{org_id}/{}", replay.id);
+    db::put(&key, json::to_vec(replay)?.into(), db::NO_NEED_WATCH, None).await
+}
+
+pub async fn delete(org_id: &str, id: &str) -> Result<()> {
+    let key = format!("{REPLAYS_KEY_PREFIX}/{org_id}/{id}");
+    db::delete(&key, false, db::NO_NEED_WATCH, None).await
+}
@@ -0,0 +1,112 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use config::{meta::stream::StreamType, utils::json};
+
+use crate::{
+    common::{infra::config::STREAM_ALIASES, meta::stream::StreamAlias},
+    service::db,
+};
+
+const ALIAS_KEY_PREFIX: &str = "/stream_alias/";
+
+pub async fn set(org_id: &str, alias: &StreamAlias) -> Result<(), anyhow::Error> {
+    let key = format!(
+        "{ALIAS_KEY_PREFIX}{org_id}/{}/{}",
+        alias.stream_type, alias.alias
+    );
+    if let Err(e) = db::put(&key, json::to_vec(alias)?.into(), db::NEED_WATCH, None).await {
+        log::error!("Error saving stream alias: {e}");
+        return Err(anyhow::anyhow!("Error saving stream alias: {}", e));
+    }
+    Ok(())
+}
+
+pub async fn delete(
+    org_id: &str,
+    stream_type: StreamType,
+    alias: &str,
+) -> Result<(), anyhow::Error> {
+    let key = format!("{ALIAS_KEY_PREFIX}{org_id}/{stream_type}/{alias}");
+    if let Err(e) = db::delete(&key, false, db::NEED_WATCH, None).await {
+        log::error!("Error deleting stream alias: {e}");
+        return Err(anyhow::anyhow!("Error deleting stream alias: {}", e));
+    }
+    Ok(())
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<StreamAlias>, anyhow::Error> {
+    Ok(db::list(&format!("{ALIAS_KEY_PREFIX}{org_id}/"))
+        .await?
+        .values()
+        .filter_map(|val| json::from_slice(val).ok())
+        .collect())
+}
+
+pub async fn watch() -> Result<(), anyhow::Error> {
+    let key = ALIAS_KEY_PREFIX;
+    let cluster_coordinator = db::get_coordinator().await;
+    let mut events = cluster_coordinator.watch(key).await?;
+    let events = Arc::get_mut(&mut events).unwrap();
+    log::info!("Start watching stream alias");
+    loop {
+        let ev = match events.recv().await {
+            Some(ev) => ev,
+            None => {
+                log::error!("watch_stream_alias: event channel closed");
+                break;
+            }
+        };
+        match ev {
+            db::Event::Put(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                let item_value: StreamAlias = match db::get(&ev.key).await {
+                    Ok(val) => match json::from_slice(&val) {
+                        Ok(val) => val,
+                        Err(e) => {
+                            log::error!("Error getting value: {e}");
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        log::error!("Error getting value: {e}");
+                        continue;
+                    }
+                };
+                STREAM_ALIASES.insert(item_key.to_owned(), item_value);
+            }
+            db::Event::Delete(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                STREAM_ALIASES.remove(item_key);
+            }
+            db::Event::Empty => {}
+        }
+    }
+    Ok(())
+}
+
+pub async fn cache() -> Result<(), anyhow::Error> {
+    let key = ALIAS_KEY_PREFIX;
+    let ret = db::list(key).await?;
+    for (item_key, item_value) in ret {
+        let item_key = item_key.strip_prefix(key).unwrap();
+        let json_val: StreamAlias = json::from_slice(&item_value)?;
+        STREAM_ALIASES.insert(item_key.to_string(), json_val);
+    }
+    log::info!("Stream aliases Cached");
+    Ok(())
+}
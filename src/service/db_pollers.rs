@@ -0,0 +1,625 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Database pollers
+//!
+//! Runs the query of a poller against its Postgres or MySQL database on a
+//! schedule and ingests the rows after the watermark, the last value of the
+//! watermark column ingested, like the JDBC inputs of the log shippers. Every
+//! poller has a trigger in the scheduler, so the polls are spread over the
+//! alert managers and resume where they stopped when a node restarts. The
+//! watermark is saved after the rows are ingested, so rows are ingested at
+//! least once.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use config::{
+    TIMESTAMP_COL_NAME, ider,
+    meta::db_pollers::{DbPoller, DbPollerDriver, DbPollerRun, MAX_BATCHES_PER_POLL, Watermark},
+    utils::{json, schema::format_stream_name, time::now_micros},
+};
+use proto::cluster_rpc;
+use sqlx::{
+    Column, Connection, Row, TypeInfo,
+    mysql::{MySqlConnectOptions, MySqlConnection, MySqlRow},
+    postgres::{PgConnectOptions, PgConnection, PgRow},
+};
+
+use crate::service::{alerts::egress, db, ingestion::ingestion_service};
+
+/// Errors that can occur when interacting with database pollers.
+#[derive(Debug, thiserror::Error)]
+pub enum DbPollerError {
+    #[error("{0}")]
+    InvalidPoller(String),
+
+    #[error("Database poller not found")]
+    PollerNotFound,
+
+    #[error(transparent)]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Failed to poll the database: {0}")]
+    PollError(String),
+}
+
+impl From<sqlx::Error> for DbPollerError {
+    fn from(e: sqlx::Error) -> Self {
+        DbPollerError::PollError(e.to_string())
+    }
+}
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+
+pub async fn create(org_id: &str, mut poller: DbPoller) -> Result<DbPoller, DbPollerError> {
+    poller.validate().map_err(DbPollerError::InvalidPoller)?;
+    check_egress(&poller).await?;
+    poller.id = ider::uuid();
+    poller.stream_name = format_stream_name(poller.stream_name.trim().to_string());
+    poller.last_run = None;
+    db::db_pollers::set(org_id, &poller).await?;
+    save_trigger(org_id, &poller).await?;
+    Ok(poller.redacted())
+}
+
+/// Updates a poller. An empty password keeps the current one while the poller
+/// connects to the same database as the same user, and a missing watermark
+/// keeps the current one.
+pub async fn update(
+    org_id: &str,
+    id: &str,
+    mut poller: DbPoller,
+) -> Result<DbPoller, DbPollerError> {
+    poller.validate().map_err(DbPollerError::InvalidPoller)?;
+    let old = db::db_pollers::get(org_id, id)
+        .await
+        .map_err(|_| DbPollerError::PollerNotFound)?;
+    poller.id = old.id;
+    poller.stream_name = format_stream_name(poller.stream_name.trim().to_string());
+    poller.last_run = old.last_run;
+    if poller.password.is_empty() && !old.password.is_empty() {
+        // the stored password is never sent to another server
+        if poller.url != old.url || poller.driver != old.driver || poller.username != old.username {
+            return Err(DbPollerError::InvalidPoller(
+                "password is required when the url, driver or username changes".to_string(),
+            ));
+        }
+        poller.password = old.password;
+    }
+    check_egress(&poller).await?;
+    if poller.watermark.is_none() {
+        poller.watermark = old.watermark;
+    }
+    db::db_pollers::set(org_id, &poller).await?;
+    if poller.interval != old.interval {
+        save_trigger(org_id, &poller).await?;
+    }
+    Ok(poller.redacted())
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<DbPoller, DbPollerError> {
+    db::db_pollers::get(org_id, id)
+        .await
+        .map(|poller| poller.redacted())
+        .map_err(|_| DbPollerError::PollerNotFound)
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<DbPoller>, DbPollerError> {
+    Ok(db::db_pollers::list(org_id)
+        .await?
+        .iter()
+        .map(|poller| poller.redacted())
+        .collect())
+}
+
+pub async fn delete(org_id: &str, id: &str) -> Result<(), DbPollerError> {
+    get(org_id, id).await?;
+    db::db_pollers::delete(org_id, id).await?;
+    if let Err(e) = db::scheduler::delete(org_id, db::scheduler::TriggerModule::DbPoller, id).await
+    {
+        log::error!("[DB_POLLER] failed to delete trigger of poller {org_id}/{id}: {e}");
+    }
+    Ok(())
+}
+
+/// Checks the database of the poller against the egress allowlist of the
+/// alert destinations
+async fn check_egress(poller: &DbPoller) -> Result<(), DbPollerError> {
+    let url =
+        url::Url::parse(&poller.url).map_err(|e| DbPollerError::InvalidPoller(e.to_string()))?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| DbPollerError::InvalidPoller("url must have a host".to_string()))?;
+    let port = url.port().unwrap_or(match poller.driver {
+        DbPollerDriver::Postgres => 5432,
+        DbPollerDriver::Mysql => 3306,
+    });
+    egress::check_server(host, port)
+        .await
+        .map_err(|e| DbPollerError::InvalidPoller(e.to_string()))?;
+    Ok(())
+}
+
+pub fn next_run_at(now: i64, interval: i64) -> i64 {
+    now + interval.max(1) * MICROS_PER_SECOND
+}
+
+async fn save_trigger(org_id: &str, poller: &DbPoller) -> Result<(), DbPollerError> {
+    let trigger = db::scheduler::Trigger {
+        org: org_id.to_string(),
+        module: db::scheduler::TriggerModule::DbPoller,
+        module_key: poller.id.clone(),
+        next_run_at: now_micros(),
+        ..Default::default()
+    };
+    if db::scheduler::exists(org_id, trigger.module.clone(), &poller.id).await {
+        db::scheduler::update_trigger(trigger, false, "").await?;
+    } else {
+        db::scheduler::push(trigger).await?;
+    }
+    Ok(())
+}
+
+/// A value of a row
+#[derive(Clone, Debug, PartialEq)]
+enum Cell {
+    Null,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Text(String),
+    Json(json::Value),
+    /// Microseconds since the epoch
+    Timestamp(i64),
+}
+
+type DbRow = Vec<(String, Cell)>;
+
+impl From<Cell> for json::Value {
+    fn from(cell: Cell) -> Self {
+        match cell {
+            Cell::Null => json::Value::Null,
+            Cell::Bool(v) => v.into(),
+            Cell::Int(v) => v.into(),
+            Cell::UInt(v) => v.into(),
+            Cell::Float(v) if v.is_finite() => v.into(),
+            Cell::Float(_) => json::Value::Null,
+            Cell::Text(v) => v.into(),
+            Cell::Json(v) => v,
+            Cell::Timestamp(v) => DateTime::<Utc>::from_timestamp_micros(v)
+                .map(|t| t.to_rfc3339().into())
+                .unwrap_or(json::Value::Null),
+        }
+    }
+}
+
+enum DbConnection {
+    Postgres(PgConnection),
+    Mysql(MySqlConnection),
+}
+
+impl DbConnection {
+    async fn connect(poller: &DbPoller) -> Result<Self, DbPollerError> {
+        // the allowlist may have changed since the poller was saved
+        check_egress(poller).await?;
+        Ok(match poller.driver {
+            DbPollerDriver::Postgres => {
+                let mut opts = PgConnectOptions::from_str(&poller.url)?;
+                if !poller.username.is_empty() {
+                    opts = opts.username(&poller.username);
+                }
+                if !poller.password.is_empty() {
+                    opts = opts.password(&poller.password);
+                }
+                Self::Postgres(PgConnection::connect_with(&opts).await?)
+            }
+            DbPollerDriver::Mysql => {
+                let mut opts = MySqlConnectOptions::from_str(&poller.url)?;
+                if !poller.username.is_empty() {
+                    opts = opts.username(&poller.username);
+                }
+                if !poller.password.is_empty() {
+                    opts = opts.password(&poller.password);
+                }
+                Self::Mysql(MySqlConnection::connect_with(&opts).await?)
+            }
+        })
+    }
+
+    async fn close(self) {
+        let ret = match self {
+            Self::Postgres(conn) => conn.close().await,
+            Self::Mysql(conn) => conn.close().await,
+        };
+        if let Err(e) = ret {
+            log::warn!("[DB_POLLER] failed to close the connection: {e}");
+        }
+    }
+
+    /// Reads the next batch of rows after the watermark
+    async fn fetch(
+        &mut self,
+        poller: &DbPoller,
+        watermark: Option<&Watermark>,
+    ) -> Result<Vec<DbRow>, DbPollerError> {
+        let sql = batch_query(poller, watermark.is_some());
+        match self {
+            Self::Postgres(conn) => {
+                let query = sqlx::query(&sql);
+                let query = match watermark {
+                    None => query,
+                    Some(Watermark::Integer(v)) => query.bind(*v),
+                    Some(Watermark::Float(v)) => query.bind(*v),
+                    Some(Watermark::Text(v)) => query.bind(v.as_str()),
+                    Some(Watermark::Timestamp(v)) => {
+                        query.bind(DateTime::<Utc>::from_timestamp_micros(*v))
+                    }
+                };
+                let rows = query.fetch_all(&mut *conn).await?;
+                rows.iter().map(pg_row).collect()
+            }
+            Self::Mysql(conn) => {
+                let query = sqlx::query(&sql);
+                let query = match watermark {
+                    None => query,
+                    Some(Watermark::Integer(v)) => query.bind(*v),
+                    Some(Watermark::Float(v)) => query.bind(*v),
+                    Some(Watermark::Text(v)) => query.bind(v.as_str()),
+                    Some(Watermark::Timestamp(v)) => {
+                        query.bind(DateTime::<Utc>::from_timestamp_micros(*v))
+                    }
+                };
+                let rows = query.fetch_all(&mut *conn).await?;
+                rows.iter().map(mysql_row).collect()
+            }
+        }
+    }
+}
+
+/// The query of the poller restricted to the rows after the watermark, in the
+/// order of the watermark column
+fn batch_query(poller: &DbPoller, after_watermark: bool) -> String {
+    let column = &poller.watermark_column;
+    let param = match poller.driver {
+        DbPollerDriver::Postgres => "$1",
+        DbPollerDriver::Mysql => "?",
+    };
+    let filter = if after_watermark {
+        format!(" WHERE {column} > {param}")
+    } else {
+        String::new()
+    };
+    format!(
+        "SELECT * FROM ({}) AS o2_poll{filter} ORDER BY {column} LIMIT {}",
+        poller.query.trim(),
+        poller.batch_size
+    )
+}
+
+fn unsupported_type(column: &str, type_name: &str) -> DbPollerError {
+    DbPollerError::PollError(format!(
+        "column {column} has the unsupported type {type_name}, cast it in the query, e.g. CAST({column} AS TEXT)"
+    ))
+}
+
+fn pg_row(row: &PgRow) -> Result<DbRow, DbPollerError> {
+    row.columns()
+        .iter()
+        .map(|column| {
+            let i = column.ordinal();
+            let cell = match column.type_info().name() {
+                "BOOL" => row.try_get::<Option<bool>, _>(i)?.map(Cell::Bool),
+                "INT2" => row
+                    .try_get::<Option<i16>, _>(i)?
+                    .map(|v| Cell::Int(v.into())),
+                "INT4" => row
+                    .try_get::<Option<i32>, _>(i)?
+                    .map(|v| Cell::Int(v.into())),
+                "INT8" => row.try_get::<Option<i64>, _>(i)?.map(Cell::Int),
+                "FLOAT4" => row
+                    .try_get::<Option<f32>, _>(i)?
+                    .map(|v| Cell::Float(v.into())),
+                "FLOAT8" => row.try_get::<Option<f64>, _>(i)?.map(Cell::Float),
+                "TEXT" | "VARCHAR" | "BPCHAR" | "NAME" => {
+                    row.try_get::<Option<String>, _>(i)?.map(Cell::Text)
+                }
+                "JSON" | "JSONB" => row.try_get::<Option<json::Value>, _>(i)?.map(Cell::Json),
+                "TIMESTAMPTZ" => row
+                    .try_get::<Option<DateTime<Utc>>, _>(i)?
+                    .map(|v| Cell::Timestamp(v.timestamp_micros())),
+                "TIMESTAMP" => row
+                    .try_get::<Option<NaiveDateTime>, _>(i)?
+                    .map(|v| Cell::Timestamp(v.and_utc().timestamp_micros())),
+                "DATE" => row
+                    .try_get::<Option<NaiveDate>, _>(i)?
+                    .map(|v| Cell::Text(v.to_string())),
+                other => return Err(unsupported_type(column.name(), other)),
+            };
+            Ok((column.name().to_string(), cell.unwrap_or(Cell::Null)))
+        })
+        .collect()
+}
+
+fn mysql_row(row: &MySqlRow) -> Result<DbRow, DbPollerError> {
+    row.columns()
+        .iter()
+        .map(|column| {
+            let i = column.ordinal();
+            let cell = match column.type_info().name() {
+                "BOOLEAN" => row.try_get::<Option<bool>, _>(i)?.map(Cell::Bool),
+                "TINYINT" | "SMALLINT" | "MEDIUMINT" | "INT" | "BIGINT" | "YEAR" => {
+                    row.try_get::<Option<i64>, _>(i)?.map(Cell::Int)
+                }
+                "TINYINT UNSIGNED" | "SMALLINT UNSIGNED" | "MEDIUMINT UNSIGNED"
+                | "INT UNSIGNED" | "BIGINT UNSIGNED" => {
+                    row.try_get::<Option<u64>, _>(i)?.map(Cell::UInt)
+                }
+                "FLOAT" => row
+                    .try_get::<Option<f32>, _>(i)?
+                    .map(|v| Cell::Float(v.into())),
+                "DOUBLE" => row.try_get::<Option<f64>, _>(i)?.map(Cell::Float),
+                // the decimals are sent as text
+                "DECIMAL" => {
+                    row.try_get_unchecked::<Option<String>, _>(i)?
+                        .map(|v| match v.parse::<f64>() {
+                            Ok(v) => Cell::Float(v),
+                            Err(_) => Cell::Text(v),
+                        })
+                }
+                "CHAR" | "VARCHAR" | "TINYTEXT" | "TEXT" | "MEDIUMTEXT" | "LONGTEXT" | "ENUM"
+                | "SET" => row.try_get::<Option<String>, _>(i)?.map(Cell::Text),
+                "JSON" => row.try_get::<Option<json::Value>, _>(i)?.map(Cell::Json),
+                "TIMESTAMP" => row
+                    .try_get::<Option<DateTime<Utc>>, _>(i)?
+                    .map(|v| Cell::Timestamp(v.timestamp_micros())),
+                "DATETIME" => row
+                    .try_get::<Option<NaiveDateTime>, _>(i)?
+                    .map(|v| Cell::Timestamp(v.and_utc().timestamp_micros())),
+                "DATE" => row
+                    .try_get::<Option<NaiveDate>, _>(i)?
+                    .map(|v| Cell::Text(v.to_string())),
+                other => return Err(unsupported_type(column.name(), other)),
+            };
+            Ok((column.name().to_string(), cell.unwrap_or(Cell::Null)))
+        })
+        .collect()
+}
+
+fn column_value<'a>(row: &'a DbRow, column: &str) -> Option<&'a Cell> {
+    row.iter()
+        .find(|(name, _)| name == column)
+        .map(|(_, cell)| cell)
+}
+
+/// A full batch may end in the middle of the rows of its last watermark, they
+/// are left to the next batch, unless the whole batch shares the watermark
+fn complete_rows(mut rows: Vec<DbRow>, column: &str, full: bool) -> Vec<DbRow> {
+    if !full {
+        return rows;
+    }
+    let Some(last) = rows
+        .last()
+        .and_then(|row| column_value(row, column))
+        .cloned()
+    else {
+        return rows;
+    };
+    if let Some(i) = rows
+        .iter()
+        .rposition(|row| column_value(row, column) != Some(&last))
+    {
+        rows.truncate(i + 1);
+    }
+    rows
+}
+
+fn to_watermark(row: &DbRow, column: &str) -> Result<Watermark, DbPollerError> {
+    let cell = column_value(row, column).ok_or_else(|| {
+        DbPollerError::PollError(format!(
+            "the watermark column {column} isn't returned by the query"
+        ))
+    })?;
+    match cell {
+        Cell::Int(v) => Ok(Watermark::Integer(*v)),
+        Cell::UInt(v) if *v <= i64::MAX as u64 => Ok(Watermark::Integer(*v as i64)),
+        Cell::Float(v) if v.is_finite() => Ok(Watermark::Float(*v)),
+        Cell::Text(v) => Ok(Watermark::Text(v.clone())),
+        Cell::Timestamp(v) => Ok(Watermark::Timestamp(*v)),
+        cell => Err(DbPollerError::PollError(format!(
+            "the watermark column {column} must be a number, a text or a timestamp, not {cell:?}"
+        ))),
+    }
+}
+
+/// The columns of the row as fields, the rows without a time get the time
+/// they were polled at
+fn to_record(row: DbRow, timestamp_column: Option<&str>, polled_at: i64) -> json::Value {
+    let mut timestamp = None;
+    let mut record = json::Map::new();
+    for (name, cell) in row {
+        if timestamp_column == Some(name.as_str()) {
+            timestamp = match &cell {
+                Cell::Timestamp(v) | Cell::Int(v) => Some(json::Value::from(*v)),
+                Cell::Text(v) => Some(v.clone().into()),
+                _ => None,
+            };
+        }
+        record.insert(name, cell.into());
+    }
+    record.insert(
+        TIMESTAMP_COL_NAME.to_string(),
+        timestamp.unwrap_or_else(|| polled_at.into()),
+    );
+    json::Value::Object(record)
+}
+
+/// Polls the database of the poller, returns the new watermark and the
+/// outcome of the poll. The watermark moves after every batch ingested, it is
+/// returned even when a later batch failed.
+pub async fn poll(org_id: &str, poller: &DbPoller) -> (Option<Watermark>, DbPollerRun) {
+    let mut run = DbPollerRun {
+        timestamp: now_micros(),
+        ..Default::default()
+    };
+    let mut watermark = poller.watermark.clone();
+    if let Err(e) = poll_batches(org_id, poller, run.timestamp, &mut watermark, &mut run.rows).await
+    {
+        run.error = Some(e.to_string());
+    }
+    (watermark, run)
+}
+
+async fn poll_batches(
+    org_id: &str,
+    poller: &DbPoller,
+    polled_at: i64,
+    watermark: &mut Option<Watermark>,
+    ingested: &mut usize,
+) -> Result<(), DbPollerError> {
+    let mut conn = DbConnection::connect(poller).await?;
+    let ret = async {
+        for _ in 0..MAX_BATCHES_PER_POLL {
+            let rows = conn.fetch(poller, watermark.as_ref()).await?;
+            let full = rows.len() as i64 >= poller.batch_size;
+            let rows = complete_rows(rows, &poller.watermark_column, full);
+            let Some(last) = rows.last() else {
+                return Ok(());
+            };
+            let next = to_watermark(last, &poller.watermark_column)?;
+            let records = rows
+                .into_iter()
+                .map(|row| to_record(row, poller.timestamp_column.as_deref(), polled_at))
+                .collect::<Vec<_>>();
+            let n = records.len();
+            write(org_id, &poller.stream_name, records).await?;
+            *ingested += n;
+            *watermark = Some(next);
+            if !full {
+                return Ok(());
+            }
+        }
+        Ok(())
+    }
+    .await;
+    conn.close().await;
+    ret
+}
+
+async fn write(
+    org_id: &str,
+    stream_name: &str,
+    records: Vec<json::Value>,
+) -> Result<(), DbPollerError> {
+    let req = cluster_rpc::IngestionRequest {
+        org_id: org_id.to_string(),
+        stream_name: stream_name.to_string(),
+        stream_type: config::meta::stream::StreamType::Logs.to_string(),
+        data: Some(cluster_rpc::IngestionData::from(records)),
+        ingestion_type: Some(cluster_rpc::IngestionType::Json.into()),
+        metadata: None,
+    };
+    match ingestion_service::ingest(req).await {
+        Ok(resp) if resp.status_code == 200 => Ok(()),
+        Ok(resp) => Err(DbPollerError::PollError(resp.message)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(id: i64, action: &str) -> DbRow {
+        vec![
+            ("id".to_string(), Cell::Int(id)),
+            ("action".to_string(), Cell::Text(action.to_string())),
+            (
+                "created_at".to_string(),
+                Cell::Timestamp(1_700_000_000_000_000 + id),
+            ),
+        ]
+    }
+
+    fn poller() -> DbPoller {
+        DbPoller {
+            name: "audit".to_string(),
+            url: "postgres://db:5432/app".to_string(),
+            query: "SELECT * FROM audit_log ".to_string(),
+            watermark_column: "id".to_string(),
+            stream_name: "audit".to_string(),
+            batch_size: 100,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_batch_query() {
+        let p = poller();
+        assert_eq!(
+            batch_query(&p, false),
+            "SELECT * FROM (SELECT * FROM audit_log) AS o2_poll ORDER BY id LIMIT 100"
+        );
+        assert_eq!(
+            batch_query(&p, true),
+            "SELECT * FROM (SELECT * FROM audit_log) AS o2_poll WHERE id > $1 ORDER BY id LIMIT 100"
+        );
+        let p = DbPoller {
+            driver: DbPollerDriver::Mysql,
+            ..p
+        };
+        assert!(batch_query(&p, true).contains("WHERE id > ? ORDER BY id"));
+    }
+
+    #[test]
+    fn test_complete_rows() {
+        let rows = vec![row(1, "a"), row(2, "b"), row(2, "c")];
+        // a full batch leaves the rows of its last watermark to the next one
+        let rows_kept = complete_rows(rows.clone(), "id", true);
+        assert_eq!(rows_kept.len(), 1);
+        assert_eq!(complete_rows(rows, "id", false).len(), 3);
+        // unless every row shares it
+        let rows = vec![row(2, "b"), row(2, "c")];
+        assert_eq!(complete_rows(rows, "id", true).len(), 2);
+    }
+
+    #[test]
+    fn test_to_watermark() {
+        assert_eq!(
+            to_watermark(&row(7, "a"), "id").unwrap(),
+            Watermark::Integer(7)
+        );
+        assert_eq!(
+            to_watermark(&row(7, "a"), "created_at").unwrap(),
+            Watermark::Timestamp(1_700_000_000_000_007)
+        );
+        assert!(to_watermark(&row(7, "a"), "missing").is_err());
+        let null = vec![("id".to_string(), Cell::Null)];
+        assert!(to_watermark(&null, "id").is_err());
+    }
+
+    #[test]
+    fn test_to_record() {
+        let record = to_record(row(1, "login"), Some("created_at"), 5);
+        assert_eq!(record["id"], 1);
+        assert_eq!(record["action"], "login");
+        assert_eq!(record["created_at"], "2023-11-14T22:13:20.000001+00:00");
+        assert_eq!(record[TIMESTAMP_COL_NAME], 1_700_000_000_000_001i64);
+
+        let record = to_record(row(1, "login"), None, 5);
+        assert_eq!(record[TIMESTAMP_COL_NAME], 5);
+    }
+}
@@ -156,7 +156,8 @@ impl Geoip {
         }
     }
 
-    fn lookup(&self, ip: IpAddr, select: Option<&[String]>) -> Option<ObjectMap> {
+    /// Looks the ip up, with only the `select` fields when given
+    pub(crate) fn lookup(&self, ip: IpAddr, select: Option<&[String]>) -> Option<ObjectMap> {
         let mut map = ObjectMap::new();
         let mut add_field = |key: &str, value: Option<Value>| {
             if select
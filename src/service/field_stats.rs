@@ -0,0 +1,360 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Statistics of the fields of a stream over a time range, merged from the
+//! column statistics in the footers of its parquet files, no data is scanned.
+//! The number of distinct values comes from the cardinality cache when a
+//! search calculated it recently, else from the distinct counts the files
+//! carry, which are a lower bound.
+
+use std::{cmp::Ordering, collections::HashMap, sync::Arc};
+
+use config::{
+    meta::stream::{FileKey, StreamType},
+    utils::json,
+};
+use futures::{StreamExt, stream};
+use hashlink::lru_cache::LruCache;
+use infra::schema::unwrap_partition_time_level;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use parquet::file::{metadata::ParquetMetaData, statistics::Statistics};
+
+use crate::{
+    common::meta::stream::{FieldStats, FieldStatsResponse},
+    service::{file_list, search::cardinality},
+};
+
+/// Number of the newest files in the time range the statistics are computed
+/// from
+const MAX_FILES: usize = 1000;
+
+/// Number of files whose statistics are kept in memory
+const CACHED_FILES: usize = 10_000;
+
+/// Number of footers read at once
+const CONCURRENCY: usize = 16;
+
+/// Column statistics of the files read before, keyed by file, the files never
+/// change once written
+static FILE_STATS: Lazy<Mutex<LruCache<String, Arc<FileStats>>>> =
+    Lazy::new(|| Mutex::new(LruCache::new(CACHED_FILES)));
+
+#[derive(Debug, Default)]
+struct FileStats {
+    records: u64,
+    columns: HashMap<String, ColumnStats>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct ColumnStats {
+    min: Option<StatValue>,
+    max: Option<StatValue>,
+    null_count: u64,
+    /// Largest distinct count of a row group
+    distinct_count: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+enum StatValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+impl From<StatValue> for json::Value {
+    fn from(value: StatValue) -> Self {
+        match value {
+            StatValue::Bool(v) => json::Value::Bool(v),
+            StatValue::Int(v) => json::Value::from(v),
+            StatValue::Float(v) => json::Number::from_f64(v)
+                .map(json::Value::Number)
+                .unwrap_or(json::Value::Null),
+            StatValue::Str(v) => json::Value::String(v),
+        }
+    }
+}
+
+impl ColumnStats {
+    fn merge(&mut self, other: &ColumnStats) {
+        self.min = pick(self.min.take(), other.min.as_ref(), Ordering::Less);
+        self.max = pick(self.max.take(), other.max.as_ref(), Ordering::Greater);
+        self.null_count += other.null_count;
+        self.distinct_count = self.distinct_count.max(other.distinct_count);
+    }
+}
+
+/// The other value when it is ordered `ord` to the current one
+fn pick(current: Option<StatValue>, other: Option<&StatValue>, ord: Ordering) -> Option<StatValue> {
+    match (current, other) {
+        (Some(current), Some(other)) if other.partial_cmp(&current) == Some(ord) => {
+            Some(other.clone())
+        }
+        (None, Some(other)) => Some(other.clone()),
+        (current, _) => current,
+    }
+}
+
+fn stat_values(stats: &Statistics) -> (Option<StatValue>, Option<StatValue>) {
+    match stats {
+        Statistics::Boolean(s) => (
+            s.min_opt().map(|v| StatValue::Bool(*v)),
+            s.max_opt().map(|v| StatValue::Bool(*v)),
+        ),
+        Statistics::Int32(s) => (
+            s.min_opt().map(|v| StatValue::Int(*v as i64)),
+            s.max_opt().map(|v| StatValue::Int(*v as i64)),
+        ),
+        Statistics::Int64(s) => (
+            s.min_opt().map(|v| StatValue::Int(*v)),
+            s.max_opt().map(|v| StatValue::Int(*v)),
+        ),
+        Statistics::Float(s) => (
+            s.min_opt().map(|v| StatValue::Float(*v as f64)),
+            s.max_opt().map(|v| StatValue::Float(*v as f64)),
+        ),
+        Statistics::Double(s) => (
+            s.min_opt().map(|v| StatValue::Float(*v)),
+            s.max_opt().map(|v| StatValue::Float(*v)),
+        ),
+        Statistics::ByteArray(s) => (
+            s.min_opt()
+                .and_then(|v| v.as_utf8().ok())
+                .map(|v| StatValue::Str(v.to_string())),
+            s.max_opt()
+                .and_then(|v| v.as_utf8().ok())
+                .map(|v| StatValue::Str(v.to_string())),
+        ),
+        _ => (None, None),
+    }
+}
+
+fn file_stats(meta: &ParquetMetaData) -> FileStats {
+    let mut stats = FileStats::default();
+    for row_group in meta.row_groups() {
+        stats.records += row_group.num_rows().max(0) as u64;
+        for column in row_group.columns() {
+            // the leaves of nested columns aren't fields of the stream
+            let [name] = column.column_path().parts() else {
+                continue;
+            };
+            let column_stats = match column.statistics() {
+                Some(s) => {
+                    let (min, max) = stat_values(s);
+                    ColumnStats {
+                        min,
+                        max,
+                        null_count: s.null_count_opt().unwrap_or_default(),
+                        distinct_count: s.distinct_count_opt(),
+                    }
+                }
+                None => ColumnStats::default(),
+            };
+            stats
+                .columns
+                .entry(name.to_string())
+                .or_default()
+                .merge(&column_stats);
+        }
+    }
+    stats
+}
+
+async fn get_file_stats(file: &FileKey) -> Option<Arc<FileStats>> {
+    if let Some(stats) = FILE_STATS.lock().get(&file.key) {
+        return Some(stats.clone());
+    }
+    match infra::storage::get_parquet_metadata(&file.account, &file.key).await {
+        Ok((_, meta)) => {
+            let stats = Arc::new(file_stats(&meta));
+            FILE_STATS.lock().insert(file.key.clone(), stats.clone());
+            Some(stats)
+        }
+        Err(e) => {
+            log::warn!("[FIELD_STATS] failed to read metadata of {}: {e}", file.key);
+            None
+        }
+    }
+}
+
+/// Merges the statistics of the field over the files, the records of the
+/// files without the field are null
+fn merge_field(files: &[Arc<FileStats>], field: &str) -> ColumnStats {
+    let mut merged = ColumnStats::default();
+    for file in files {
+        match file.columns.get(field) {
+            Some(column) => merged.merge(column),
+            None => merged.null_count += file.records,
+        }
+    }
+    merged
+}
+
+/// Statistics of the fields of a stream over the time range, all the fields
+/// when `fields` is empty, `None` when the stream does not exist
+pub async fn list(
+    trace_id: &str,
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    (start_time, end_time): (i64, i64),
+    fields: &[String],
+) -> Result<Option<FieldStatsResponse>, anyhow::Error> {
+    let schema = infra::schema::get(org_id, stream_name, stream_type).await?;
+    if schema.fields().is_empty() {
+        return Ok(None);
+    }
+    let settings = infra::schema::get_settings(org_id, stream_name, stream_type).await;
+    let time_level = unwrap_partition_time_level(
+        settings.as_ref().and_then(|s| s.partition_time_level),
+        stream_type,
+    );
+    let mut files = file_list::query(
+        trace_id,
+        org_id,
+        stream_type,
+        stream_name,
+        time_level,
+        start_time,
+        end_time,
+    )
+    .await?;
+    let total_files = files.len();
+    files.sort_by(|a, b| b.meta.max_ts.cmp(&a.meta.max_ts));
+    files.truncate(MAX_FILES);
+    let file_stats = stream::iter(files)
+        .map(|file| async move { get_file_stats(&file).await })
+        .buffer_unordered(CONCURRENCY)
+        .filter_map(|v| async move { v })
+        .collect::<Vec<_>>()
+        .await;
+    let records = file_stats.iter().map(|f| f.records).sum::<u64>();
+
+    let mut stats = Vec::new();
+    for field in schema.fields() {
+        let name = field.name();
+        if !fields.is_empty() && !fields.contains(name) {
+            continue;
+        }
+        let merged = merge_field(&file_stats, name);
+        let ndv = match cardinality::get_cached_cardinality(org_id, stream_type, stream_name, name)
+            .await
+        {
+            Some(v) => Some(v.round() as u64),
+            None if merged.min.is_some() && merged.min == merged.max => Some(1),
+            None => merged.distinct_count,
+        };
+        stats.push(FieldStats {
+            name: name.to_string(),
+            data_type: field.data_type().to_string(),
+            min: merged.min.map(Into::into),
+            max: merged.max.map(Into::into),
+            ndv,
+            null_count: merged.null_count,
+            null_fraction: if records > 0 {
+                merged.null_count.min(records) as f64 / records as f64
+            } else {
+                0.0
+            },
+        });
+    }
+
+    Ok(Some(FieldStatsResponse {
+        records,
+        files: total_files,
+        sampled_files: file_stats.len(),
+        fields: stats,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::{
+        array::{Int64Array, StringArray},
+        datatypes::{DataType, Field, Schema},
+        record_batch::RecordBatch,
+    };
+    use parquet::file::metadata::ParquetMetaDataReader;
+
+    use super::*;
+
+    async fn write_file(batch: RecordBatch) -> FileStats {
+        let mut buf = Vec::new();
+        let file_meta = config::meta::stream::FileMeta {
+            records: batch.num_rows() as i64,
+            ..Default::default()
+        };
+        let schema = batch.schema();
+        let mut writer = config::utils::parquet::new_parquet_writer(
+            &mut buf,
+            &schema,
+            &[],
+            &file_meta,
+            false,
+            None,
+        );
+        writer.write(&batch).await.unwrap();
+        writer.close().await.unwrap();
+        let meta = ParquetMetaDataReader::new()
+            .parse_and_finish(&bytes::Bytes::from(buf))
+            .unwrap();
+        file_stats(&meta)
+    }
+
+    #[tokio::test]
+    async fn test_field_stats() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("_timestamp", DataType::Int64, false),
+            Field::new("status", DataType::Utf8, true),
+        ]));
+        let first = write_file(
+            RecordBatch::try_new(
+                schema,
+                vec![
+                    Arc::new(Int64Array::from(vec![10, 20, 30])),
+                    Arc::new(StringArray::from(vec![Some("ok"), None, Some("error")])),
+                ],
+            )
+            .unwrap(),
+        )
+        .await;
+        assert_eq!(first.records, 3);
+        let status = first.columns.get("status").unwrap();
+        assert_eq!(status.min, Some(StatValue::Str("error".to_string())));
+        assert_eq!(status.max, Some(StatValue::Str("ok".to_string())));
+        assert_eq!(status.null_count, 1);
+
+        // a file written before the field was added
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "_timestamp",
+            DataType::Int64,
+            false,
+        )]));
+        let second = write_file(
+            RecordBatch::try_new(schema, vec![Arc::new(Int64Array::from(vec![5, 40]))]).unwrap(),
+        )
+        .await;
+
+        let files = vec![Arc::new(first), Arc::new(second)];
+        let timestamp = merge_field(&files, "_timestamp");
+        assert_eq!(timestamp.min, Some(StatValue::Int(5)));
+        assert_eq!(timestamp.max, Some(StatValue::Int(40)));
+        assert_eq!(timestamp.null_count, 0);
+        let status = merge_field(&files, "status");
+        assert_eq!(status.null_count, 3);
+        assert_eq!(json::Value::from(status.max.unwrap()), "ok");
+    }
+}
@@ -0,0 +1,332 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Field usage analytics. Searches record the fields they reference, the
+//! counters are kept in memory and merged periodically into the kv store, so
+//! the usage outlives restarts and covers the searches of every node.
+//! Dashboards, alerts and reports are counted when their queries run.
+
+use std::collections::HashMap;
+
+use config::{
+    get_config,
+    meta::{
+        search::SearchEventType,
+        stream::{FileKey, StreamType},
+    },
+    utils::{json, time::now_micros},
+};
+use datafusion::common::TableReference;
+use infra::schema::unwrap_partition_time_level;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+use crate::{
+    common::meta::stream::{FieldUsage, FieldUsageEntry, FieldUsageResponse, StreamFieldUsage},
+    service::{db::kv, file_list},
+};
+
+/// Number of the most recent files the storage size of fields is estimated
+/// from
+const SAMPLE_FILES: usize = 5;
+
+/// Time range of the files sampled for the storage size estimation, ending at
+/// the newest data of the stream
+const SAMPLE_TIME_RANGE_MICROS: i64 = 24 * 3600 * 1_000_000;
+
+/// Usage recorded since the last flush, by `org_id/stream_type/stream_name`
+static PENDING: Lazy<Mutex<HashMap<String, HashMap<String, FieldUsage>>>> =
+    Lazy::new(Default::default);
+
+fn usage_key(stream_type: StreamType, stream_name: &str) -> String {
+    format!("field_usage/{stream_type}/{stream_name}")
+}
+
+/// Records the fields referenced by a search. `SELECT *` references no field
+/// in particular and is not counted.
+pub fn record(
+    org_id: &str,
+    stream_type: StreamType,
+    search_type: Option<SearchEventType>,
+    columns: &hashbrown::HashMap<TableReference, hashbrown::HashSet<String>>,
+) {
+    if !get_config().common.field_usage_enabled || columns.is_empty() {
+        return;
+    }
+    let now = now_micros();
+    let mut pending = PENDING.lock();
+    for (table, fields) in columns.iter() {
+        let stream_type = match table.schema() {
+            Some(v) => StreamType::from(v),
+            None => stream_type,
+        };
+        let key = format!("{org_id}/{stream_type}/{}", table.table());
+        let entry = pending.entry(key).or_default();
+        for field in fields.iter() {
+            let usage = entry.entry(field.to_string()).or_default();
+            match search_type {
+                Some(SearchEventType::Dashboards) => usage.dashboards += 1,
+                Some(SearchEventType::Alerts | SearchEventType::DerivedStream) => usage.alerts += 1,
+                Some(SearchEventType::Reports) => usage.reports += 1,
+                _ => usage.queries += 1,
+            }
+            if usage.first_used == 0 {
+                usage.first_used = now;
+            }
+            usage.last_used = now;
+        }
+    }
+}
+
+/// Merges the usage recorded since the last flush into the kv store
+pub async fn flush() {
+    let pending = std::mem::take(&mut *PENDING.lock());
+    for (key, fields) in pending {
+        let mut parts = key.splitn(3, '/');
+        let (Some(org_id), Some(stream_type), Some(stream_name)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let stream_type = StreamType::from(stream_type);
+        // read-modify-write, a concurrent flush of another node can lose its
+        // counts but never the first and last usage times of a field
+        let mut usage = get(org_id, stream_type, stream_name).await;
+        if usage.tracking_since == 0 {
+            usage.tracking_since = fields
+                .values()
+                .map(|v| v.first_used)
+                .min()
+                .unwrap_or_else(now_micros);
+        }
+        for (field, field_usage) in fields.iter() {
+            usage
+                .fields
+                .entry(field.clone())
+                .or_default()
+                .merge(field_usage);
+        }
+        let val = match json::to_vec(&usage) {
+            Ok(v) => v,
+            Err(e) => {
+                log::error!("[FIELD_USAGE] failed to serialize usage of {key}: {e}");
+                continue;
+            }
+        };
+        if let Err(e) = kv::set(org_id, &usage_key(stream_type, stream_name), val.into()).await {
+            log::error!("[FIELD_USAGE] failed to save usage of {key}: {e}");
+        }
+    }
+}
+
+/// Persisted field usage of a stream, empty when nothing was recorded yet
+pub async fn get(org_id: &str, stream_type: StreamType, stream_name: &str) -> StreamFieldUsage {
+    match kv::get(org_id, &usage_key(stream_type, stream_name)).await {
+        Ok(val) => json::from_slice(&val).unwrap_or_default(),
+        Err(_) => StreamFieldUsage::default(),
+    }
+}
+
+pub async fn delete(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> Result<(), anyhow::Error> {
+    PENDING
+        .lock()
+        .remove(&format!("{org_id}/{stream_type}/{stream_name}"));
+    if kv::get(org_id, &usage_key(stream_type, stream_name))
+        .await
+        .is_err()
+    {
+        return Ok(());
+    }
+    kv::delete(org_id, &usage_key(stream_type, stream_name)).await
+}
+
+/// Usage and estimated storage size of every field of a stream, sorted by
+/// storage size, `None` when the stream does not exist. With `unused_only`
+/// only the fields never referenced are returned.
+pub async fn list(
+    trace_id: &str,
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    unused_only: bool,
+) -> Result<Option<FieldUsageResponse>, anyhow::Error> {
+    let schema = infra::schema::get(org_id, stream_name, stream_type).await?;
+    if schema.fields().is_empty() {
+        return Ok(None);
+    }
+    let settings = infra::schema::get_settings(org_id, stream_name, stream_type).await;
+    let fts_fields = infra::schema::get_stream_setting_fts_fields(&settings);
+    let index_fields = infra::schema::get_stream_setting_index_fields(&settings);
+    let uds_fields = settings
+        .as_ref()
+        .map(|s| s.defined_schema_fields.clone())
+        .unwrap_or_default();
+
+    let mut usage = get(org_id, stream_type, stream_name).await;
+    // include what this node recorded but did not flush yet
+    if let Some(pending) = PENDING
+        .lock()
+        .get(&format!("{org_id}/{stream_type}/{stream_name}"))
+    {
+        for (field, field_usage) in pending.iter() {
+            usage
+                .fields
+                .entry(field.clone())
+                .or_default()
+                .merge(field_usage);
+        }
+        if usage.tracking_since == 0 {
+            usage.tracking_since = pending.values().map(|v| v.first_used).min().unwrap_or(0);
+        }
+    }
+
+    let time_level = unwrap_partition_time_level(
+        settings.as_ref().and_then(|s| s.partition_time_level),
+        stream_type,
+    );
+    let stats = infra::cache::stats::get_stream_stats(org_id, stream_name, stream_type);
+    let files = if stats.doc_time_max > 0 {
+        file_list::query(
+            trace_id,
+            org_id,
+            stream_type,
+            stream_name,
+            time_level,
+            stats.doc_time_max - SAMPLE_TIME_RANGE_MICROS,
+            stats.doc_time_max + 1,
+        )
+        .await?
+    } else {
+        vec![]
+    };
+    let (sizes, sampled_files) = estimate_field_sizes(&files, stats.compressed_size as i64).await;
+
+    let mut fields = schema
+        .fields()
+        .iter()
+        .map(|f| {
+            let name = f.name();
+            FieldUsageEntry {
+                name: name.to_string(),
+                data_type: f.data_type().to_string(),
+                usage: usage.fields.get(name).cloned(),
+                storage_size: sizes.get(name).copied().unwrap_or_default(),
+                full_text_search: fts_fields.contains(name),
+                index: index_fields.contains(name),
+                user_defined_schema: uds_fields.contains(name),
+            }
+        })
+        .collect::<Vec<_>>();
+    let unused_storage_size = fields
+        .iter()
+        .filter(|f| f.usage.is_none())
+        .map(|f| f.storage_size)
+        .sum();
+    if unused_only {
+        fields.retain(|f| f.usage.is_none());
+    }
+    fields.sort_by(|a, b| {
+        b.storage_size
+            .cmp(&a.storage_size)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    Ok(Some(FieldUsageResponse {
+        tracking_since: usage.tracking_since,
+        sampled_files,
+        unused_storage_size,
+        fields,
+    }))
+}
+
+/// Estimates the compressed size of every field in the stream from the column
+/// sizes of the newest files, scaled to the compressed size of the stream
+async fn estimate_field_sizes(
+    files: &[FileKey],
+    stream_compressed_size: i64,
+) -> (HashMap<String, i64>, usize) {
+    let mut sampled = HashMap::new();
+    let mut sampled_files = 0;
+    for file in files.iter().rev().take(SAMPLE_FILES) {
+        match infra::storage::get_column_sizes(&file.account, &file.key).await {
+            Ok(sizes) => {
+                for (name, size) in sizes {
+                    *sampled.entry(name).or_insert(0) += size;
+                }
+                sampled_files += 1;
+            }
+            Err(e) => log::warn!("[FIELD_USAGE] failed to read metadata of {}: {e}", file.key),
+        }
+    }
+    (scale_sizes(sampled, stream_compressed_size), sampled_files)
+}
+
+fn scale_sizes(sampled: HashMap<String, i64>, total: i64) -> HashMap<String, i64> {
+    let sampled_total: i64 = sampled.values().sum();
+    if sampled_total == 0 || total <= 0 {
+        return sampled;
+    }
+    let ratio = total as f64 / sampled_total as f64;
+    sampled
+        .into_iter()
+        .map(|(name, size)| (name, (size as f64 * ratio) as i64))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let mut columns = hashbrown::HashMap::new();
+        columns.insert(
+            TableReference::from("usage_test_stream"),
+            hashbrown::HashSet::from(["status".to_string(), "message".to_string()]),
+        );
+        record(
+            "usage_test_org",
+            StreamType::Logs,
+            Some(SearchEventType::Dashboards),
+            &columns,
+        );
+        record("usage_test_org", StreamType::Logs, None, &columns);
+
+        let pending = PENDING.lock();
+        let fields = pending
+            .get("usage_test_org/logs/usage_test_stream")
+            .unwrap();
+        let status = fields.get("status").unwrap();
+        assert_eq!(status.dashboards, 1);
+        assert_eq!(status.queries, 1);
+        assert_eq!(status.total(), 2);
+        assert!(status.first_used > 0 && status.first_used <= status.last_used);
+        assert!(fields.contains_key("message"));
+    }
+
+    #[test]
+    fn test_scale_sizes() {
+        let sampled = HashMap::from([("a".to_string(), 30), ("b".to_string(), 10)]);
+        let scaled = scale_sizes(sampled.clone(), 400);
+        assert_eq!(scaled.get("a"), Some(&300));
+        assert_eq!(scaled.get("b"), Some(&100));
+        // unknown stream size keeps the sampled sizes
+        assert_eq!(scale_sizes(sampled.clone(), 0), sampled);
+    }
+}
@@ -318,6 +318,14 @@ pub async fn update_compressed_size(key: &str, size: i64) -> Result<()> {
     Ok(())
 }
 
+pub async fn update_account(key: &str, account: &str) -> Result<()> {
+    infra_file_list::update_account(key, account).await?;
+    infra_file_list::LOCAL_CACHE
+        .update_account(key, account)
+        .await?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use config::meta::{
@@ -0,0 +1,317 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! NetFlow/IPFIX and sFlow collector
+//!
+//! Listens on UDP for flow exports sent by routers and switches, decodes them
+//! into flat JSON records and ingests them into a logs stream, so network
+//! observability does not require a separate flow collector.
+
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
+
+use config::{get_config, utils::json};
+use tokio::{net::UdpSocket, sync::mpsc};
+
+use crate::common::meta::ingestion::{
+    IngestUser, IngestionRequest, IngestionValueType, SystemJobType,
+};
+
+pub mod netflow;
+pub mod sflow;
+
+/// Largest datagram we accept, UDP can't carry more than this anyway
+const MAX_DATAGRAM_SIZE: usize = 65535;
+/// Number of decoded packets that can be queued before listeners wait for the flusher
+const CHANNEL_SIZE: usize = 4096;
+/// How often expired NetFlow v9/IPFIX templates are purged
+const TEMPLATE_PURGE_INTERVAL: Duration = Duration::from_secs(60);
+
+pub type FlowRecord = json::Map<String, json::Value>;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum DecodeError {
+    #[error("truncated packet: need {need} bytes at offset {offset}")]
+    Truncated { offset: usize, need: usize },
+    #[error("unsupported version {0}")]
+    UnsupportedVersion(u32),
+    #[error("malformed packet: {0}")]
+    Malformed(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FlowProtocol {
+    NetFlow,
+    SFlow,
+}
+
+impl std::fmt::Display for FlowProtocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FlowProtocol::NetFlow => write!(f, "netflow"),
+            FlowProtocol::SFlow => write!(f, "sflow"),
+        }
+    }
+}
+
+/// Starts the configured UDP listeners and the flusher that ingests decoded
+/// records. Returns once all listeners have stopped.
+pub async fn run() -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    if !cfg.flow_collector.enabled {
+        return Ok(());
+    }
+
+    let (tx, rx) = mpsc::channel::<Vec<FlowRecord>>(CHANNEL_SIZE);
+    let listeners = [
+        (FlowProtocol::NetFlow, cfg.flow_collector.netflow_port),
+        (FlowProtocol::SFlow, cfg.flow_collector.sflow_port),
+    ];
+    for (protocol, port) in listeners {
+        if port == 0 {
+            continue;
+        }
+        let addr: SocketAddr = format!("{}:{}", cfg.flow_collector.addr, port).parse()?;
+        let socket = UdpSocket::bind(addr).await?;
+        log::info!("[FLOW] {protocol} collector listening on udp://{addr}");
+        tokio::task::spawn(listen(socket, protocol, tx.clone()));
+    }
+    drop(tx);
+
+    flush(rx).await;
+    Ok(())
+}
+
+async fn listen(socket: UdpSocket, protocol: FlowProtocol, tx: mpsc::Sender<Vec<FlowRecord>>) {
+    let cfg = get_config();
+    let mut templates =
+        netflow::TemplateCache::new(Duration::from_secs(cfg.flow_collector.template_ttl));
+    let mut last_purge = std::time::Instant::now();
+    let mut buf = vec![0u8; MAX_DATAGRAM_SIZE];
+    loop {
+        let (len, peer) = match socket.recv_from(&mut buf).await {
+            Ok(v) => v,
+            Err(e) => {
+                log::error!("[FLOW] {protocol} collector receive error: {e}");
+                continue;
+            }
+        };
+        let exporter = peer.ip();
+        let ret = match protocol {
+            FlowProtocol::NetFlow => netflow::decode(&buf[..len], exporter, &mut templates),
+            FlowProtocol::SFlow => sflow::decode(&buf[..len], exporter),
+        };
+        match ret {
+            Ok(records) if records.is_empty() => {}
+            Ok(records) => {
+                if tx.send(records).await.is_err() {
+                    log::error!("[FLOW] {protocol} collector flusher stopped, exiting");
+                    return;
+                }
+            }
+            Err(e) => {
+                log::debug!("[FLOW] failed to decode {protocol} packet from {exporter}: {e}");
+            }
+        }
+
+        if last_purge.elapsed() >= TEMPLATE_PURGE_INTERVAL {
+            templates.purge_expired();
+            last_purge = std::time::Instant::now();
+        }
+    }
+}
+
+async fn flush(mut rx: mpsc::Receiver<Vec<FlowRecord>>) {
+    let cfg = get_config();
+    let batch_size = cfg.flow_collector.batch_size;
+    let mut interval =
+        tokio::time::interval(Duration::from_millis(cfg.flow_collector.flush_interval_ms));
+    interval.tick().await; // the first tick completes immediately
+    let mut buffer: Vec<json::Value> = Vec::with_capacity(batch_size);
+    loop {
+        tokio::select! {
+            records = rx.recv() => {
+                let Some(records) = records else {
+                    ingest(std::mem::take(&mut buffer)).await;
+                    return;
+                };
+                buffer.extend(records.into_iter().map(json::Value::Object));
+                if buffer.len() >= batch_size {
+                    ingest(std::mem::replace(&mut buffer, Vec::with_capacity(batch_size))).await;
+                }
+            }
+            _ = interval.tick() => {
+                if !buffer.is_empty() {
+                    ingest(std::mem::replace(&mut buffer, Vec::with_capacity(batch_size))).await;
+                }
+            }
+        }
+    }
+}
+
+async fn ingest(records: Vec<json::Value>) {
+    if records.is_empty() {
+        return;
+    }
+    let cfg = get_config();
+    let org_id = &cfg.flow_collector.org_id;
+    let stream_name = &cfg.flow_collector.stream_name;
+    let count = records.len();
+    match crate::service::logs::ingest::ingest(
+        0,
+        org_id,
+        stream_name,
+        IngestionRequest::JsonValues(IngestionValueType::Flow, records),
+        IngestUser::SystemJob(SystemJobType::FlowCollector),
+        None,
+        false,
+    )
+    .await
+    {
+        Ok(resp) if resp.code == 200 => {
+            log::debug!("[FLOW] ingested {count} flow records into {org_id}/{stream_name}");
+        }
+        Ok(resp) => {
+            log::error!(
+                "[FLOW] failed to ingest {count} flow records into {org_id}/{stream_name}: {}",
+                resp.error.unwrap_or_default()
+            );
+        }
+        Err(e) => {
+            log::error!(
+                "[FLOW] failed to ingest {count} flow records into {org_id}/{stream_name}: {e}"
+            );
+        }
+    }
+}
+
+/// Bounds checked big-endian reader over a datagram
+pub(crate) struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    pub(crate) fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    pub(crate) fn bytes(&mut self, n: usize) -> Result<&'a [u8], DecodeError> {
+        if self.remaining() < n {
+            return Err(DecodeError::Truncated {
+                offset: self.pos,
+                need: n,
+            });
+        }
+        let v = &self.buf[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(v)
+    }
+
+    pub(crate) fn skip(&mut self, n: usize) -> Result<(), DecodeError> {
+        self.bytes(n).map(|_| ())
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    pub(crate) fn u16(&mut self) -> Result<u16, DecodeError> {
+        let b = self.bytes(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, DecodeError> {
+        let b = self.bytes(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64, DecodeError> {
+        let b = self.bytes(8)?;
+        Ok(u64::from_be_bytes(b.try_into().unwrap()))
+    }
+
+    /// Returns a reader over the next `n` bytes and advances past them
+    pub(crate) fn sub(&mut self, n: usize) -> Result<Reader<'a>, DecodeError> {
+        Ok(Reader::new(self.bytes(n)?))
+    }
+}
+
+pub(crate) fn ipv4_from_slice(b: &[u8]) -> Option<IpAddr> {
+    let octets: [u8; 4] = b.try_into().ok()?;
+    Some(IpAddr::from(octets))
+}
+
+pub(crate) fn ipv6_from_slice(b: &[u8]) -> Option<IpAddr> {
+    let octets: [u8; 16] = b.try_into().ok()?;
+    Some(IpAddr::from(octets))
+}
+
+pub(crate) fn mac_to_string(b: &[u8]) -> String {
+    b.iter()
+        .map(|v| format!("{v:02x}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reader_bounds() {
+        let data = [0x00, 0x09, 0x00, 0x00, 0x00, 0x01];
+        let mut r = Reader::new(&data);
+        assert_eq!(r.u16().unwrap(), 9);
+        assert_eq!(r.u32().unwrap(), 1);
+        assert_eq!(r.remaining(), 0);
+        assert_eq!(
+            r.u8().unwrap_err(),
+            DecodeError::Truncated { offset: 6, need: 1 }
+        );
+    }
+
+    #[test]
+    fn test_reader_sub() {
+        let data = [1, 2, 3, 4, 5];
+        let mut r = Reader::new(&data);
+        let mut sub = r.sub(3).unwrap();
+        assert_eq!(sub.u8().unwrap(), 1);
+        assert_eq!(sub.remaining(), 2);
+        assert_eq!(r.u16().unwrap(), 0x0405);
+    }
+
+    #[test]
+    fn test_address_helpers() {
+        assert_eq!(
+            ipv4_from_slice(&[10, 0, 0, 1]).unwrap().to_string(),
+            "10.0.0.1"
+        );
+        assert!(ipv4_from_slice(&[10, 0, 0]).is_none());
+        let mut v6 = [0u8; 16];
+        v6[15] = 1;
+        assert_eq!(ipv6_from_slice(&v6).unwrap().to_string(), "::1");
+        assert_eq!(
+            mac_to_string(&[0xde, 0xad, 0xbe, 0xef, 0x00, 0x01]),
+            "de:ad:be:ef:00:01"
+        );
+    }
+}
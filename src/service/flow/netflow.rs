@@ -0,0 +1,771 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! NetFlow v5, NetFlow v9 and IPFIX (NetFlow v10) decoder
+
+use std::{
+    net::IpAddr,
+    time::{Duration, Instant},
+};
+
+use config::{TIMESTAMP_COL_NAME, utils::json};
+use hashbrown::HashMap;
+
+use super::{DecodeError, FlowRecord, Reader, ipv4_from_slice, ipv6_from_slice, mac_to_string};
+
+const V5_HEADER_LEN: usize = 24;
+const IPFIX_HEADER_LEN: usize = 16;
+const V5_RECORD_LEN: usize = 48;
+const V9_TEMPLATE_SET_ID: u16 = 0;
+const V9_OPTIONS_TEMPLATE_SET_ID: u16 = 1;
+const IPFIX_TEMPLATE_SET_ID: u16 = 2;
+const IPFIX_OPTIONS_TEMPLATE_SET_ID: u16 = 3;
+const MIN_DATA_SET_ID: u16 = 256;
+const VARIABLE_LENGTH: u16 = 65535;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FieldSpec {
+    id: u16,
+    length: u16,
+    enterprise: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+struct Template {
+    fields: Vec<FieldSpec>,
+    is_options: bool,
+    updated_at: Instant,
+}
+
+/// Templates are scoped per exporter and observation domain (source id in v9)
+type TemplateKey = (IpAddr, u32, u16);
+
+/// Cache of NetFlow v9/IPFIX templates announced by exporters.
+///
+/// Data sets can only be decoded once the matching template has been seen, so
+/// templates are kept until the exporter stops refreshing them for `ttl`.
+pub struct TemplateCache {
+    ttl: Duration,
+    templates: HashMap<TemplateKey, Template>,
+}
+
+impl TemplateCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            templates: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+
+    fn insert(&mut self, key: TemplateKey, fields: Vec<FieldSpec>, is_options: bool) {
+        self.templates.insert(
+            key,
+            Template {
+                fields,
+                is_options,
+                updated_at: Instant::now(),
+            },
+        );
+    }
+
+    fn get(&self, key: &TemplateKey) -> Option<&Template> {
+        self.templates
+            .get(key)
+            .filter(|t| t.updated_at.elapsed() < self.ttl)
+    }
+
+    pub fn purge_expired(&mut self) {
+        let ttl = self.ttl;
+        self.templates.retain(|_, t| t.updated_at.elapsed() < ttl);
+    }
+}
+
+/// Header values needed to turn relative uptime stamps into wall clock time
+#[derive(Debug, Clone, Copy)]
+struct ExportContext {
+    version: u16,
+    /// export time in milliseconds since epoch
+    export_ms: i64,
+    /// exporter uptime in milliseconds at export time, only known for v5/v9
+    sys_uptime_ms: Option<i64>,
+}
+
+impl ExportContext {
+    fn uptime_to_epoch_ms(&self, uptime_ms: i64) -> i64 {
+        match self.sys_uptime_ms {
+            Some(sys_uptime) => self.export_ms - (sys_uptime - uptime_ms),
+            None => self.export_ms,
+        }
+    }
+
+    fn flow_type(&self) -> &'static str {
+        match self.version {
+            5 => "netflow_v5",
+            9 => "netflow_v9",
+            _ => "ipfix",
+        }
+    }
+}
+
+/// Decodes a NetFlow v5/v9 or IPFIX datagram.
+pub fn decode(
+    buf: &[u8],
+    exporter: IpAddr,
+    templates: &mut TemplateCache,
+) -> Result<Vec<FlowRecord>, DecodeError> {
+    let mut r = Reader::new(buf);
+    let version = r.u16()?;
+    match version {
+        5 => decode_v5(buf, exporter),
+        9 => decode_v9(buf, exporter, templates),
+        10 => decode_ipfix(buf, exporter, templates),
+        v => Err(DecodeError::UnsupportedVersion(v as u32)),
+    }
+}
+
+fn decode_v5(buf: &[u8], exporter: IpAddr) -> Result<Vec<FlowRecord>, DecodeError> {
+    let mut r = Reader::new(buf);
+    r.skip(2)?; // version
+    let count = r.u16()? as usize;
+    let sys_uptime = r.u32()? as i64;
+    let unix_secs = r.u32()? as i64;
+    let unix_nsecs = r.u32()? as i64;
+    let flow_sequence = r.u32()?;
+    let engine_type = r.u8()?;
+    let engine_id = r.u8()?;
+    let sampling = r.u16()?;
+    if buf.len() < V5_HEADER_LEN + count * V5_RECORD_LEN {
+        return Err(DecodeError::Truncated {
+            offset: buf.len(),
+            need: V5_HEADER_LEN + count * V5_RECORD_LEN - buf.len(),
+        });
+    }
+    let ctx = ExportContext {
+        version: 5,
+        export_ms: unix_secs * 1000 + unix_nsecs / 1_000_000,
+        sys_uptime_ms: Some(sys_uptime),
+    };
+
+    let mut records = Vec::with_capacity(count);
+    for _ in 0..count {
+        let mut rec = base_record(&ctx, exporter);
+        rec.insert("flow_sequence".to_string(), flow_sequence.into());
+        rec.insert("engine_type".to_string(), engine_type.into());
+        rec.insert("engine_id".to_string(), engine_id.into());
+        // top two bits are the sampling mode, the rest is the interval
+        rec.insert("sampling_interval".to_string(), (sampling & 0x3fff).into());
+
+        rec.insert("src_addr".to_string(), ip_value(r.bytes(4)?));
+        rec.insert("dst_addr".to_string(), ip_value(r.bytes(4)?));
+        rec.insert("next_hop".to_string(), ip_value(r.bytes(4)?));
+        rec.insert("input_snmp".to_string(), r.u16()?.into());
+        rec.insert("output_snmp".to_string(), r.u16()?.into());
+        rec.insert("packets".to_string(), r.u32()?.into());
+        rec.insert("bytes".to_string(), r.u32()?.into());
+        let first = ctx.uptime_to_epoch_ms(r.u32()? as i64);
+        let last = ctx.uptime_to_epoch_ms(r.u32()? as i64);
+        rec.insert("flow_start_ms".to_string(), first.into());
+        rec.insert("flow_end_ms".to_string(), last.into());
+        rec.insert("src_port".to_string(), r.u16()?.into());
+        rec.insert("dst_port".to_string(), r.u16()?.into());
+        r.skip(1)?; // pad1
+        rec.insert("tcp_flags".to_string(), r.u8()?.into());
+        rec.insert("protocol".to_string(), r.u8()?.into());
+        rec.insert("tos".to_string(), r.u8()?.into());
+        rec.insert("src_as".to_string(), r.u16()?.into());
+        rec.insert("dst_as".to_string(), r.u16()?.into());
+        rec.insert("src_mask".to_string(), r.u8()?.into());
+        rec.insert("dst_mask".to_string(), r.u8()?.into());
+        r.skip(2)?; // pad2
+        rec.insert(TIMESTAMP_COL_NAME.to_string(), (last * 1000).into());
+        records.push(rec);
+    }
+    Ok(records)
+}
+
+fn decode_v9(
+    buf: &[u8],
+    exporter: IpAddr,
+    templates: &mut TemplateCache,
+) -> Result<Vec<FlowRecord>, DecodeError> {
+    let mut r = Reader::new(buf);
+    r.skip(2)?; // version
+    let _count = r.u16()?;
+    let sys_uptime = r.u32()? as i64;
+    let unix_secs = r.u32()? as i64;
+    let _sequence = r.u32()?;
+    let source_id = r.u32()?;
+    let ctx = ExportContext {
+        version: 9,
+        export_ms: unix_secs * 1000,
+        sys_uptime_ms: Some(sys_uptime),
+    };
+
+    let mut records = Vec::new();
+    while r.remaining() >= 4 {
+        let set_id = r.u16()?;
+        let length = r.u16()? as usize;
+        if length < 4 {
+            return Err(DecodeError::Malformed(format!(
+                "flowset {set_id} has invalid length {length}"
+            )));
+        }
+        let mut set = r.sub(length - 4)?;
+        match set_id {
+            V9_TEMPLATE_SET_ID => parse_templates(&mut set, exporter, source_id, templates, false)?,
+            V9_OPTIONS_TEMPLATE_SET_ID => {
+                parse_v9_options_templates(&mut set, exporter, source_id, templates)?
+            }
+            id if id >= MIN_DATA_SET_ID => decode_data_set(
+                &mut set,
+                id,
+                exporter,
+                source_id,
+                &ctx,
+                templates,
+                &mut records,
+            )?,
+            _ => {} // reserved
+        }
+    }
+    Ok(records)
+}
+
+fn decode_ipfix(
+    buf: &[u8],
+    exporter: IpAddr,
+    templates: &mut TemplateCache,
+) -> Result<Vec<FlowRecord>, DecodeError> {
+    let mut r = Reader::new(buf);
+    r.skip(2)?; // version
+    let length = r.u16()? as usize;
+    if length < IPFIX_HEADER_LEN {
+        return Err(DecodeError::Malformed(format!(
+            "message has invalid length {length}"
+        )));
+    }
+    if length > buf.len() {
+        return Err(DecodeError::Truncated {
+            offset: buf.len(),
+            need: length - buf.len(),
+        });
+    }
+    let export_secs = r.u32()? as i64;
+    let _sequence = r.u32()?;
+    let domain_id = r.u32()?;
+    let ctx = ExportContext {
+        version: 10,
+        export_ms: export_secs * 1000,
+        sys_uptime_ms: None,
+    };
+
+    let mut r = Reader::new(&buf[IPFIX_HEADER_LEN..length]);
+    let mut records = Vec::new();
+    while r.remaining() >= 4 {
+        let set_id = r.u16()?;
+        let length = r.u16()? as usize;
+        if length < 4 {
+            return Err(DecodeError::Malformed(format!(
+                "set {set_id} has invalid length {length}"
+            )));
+        }
+        let mut set = r.sub(length - 4)?;
+        match set_id {
+            IPFIX_TEMPLATE_SET_ID => {
+                parse_templates(&mut set, exporter, domain_id, templates, false)?
+            }
+            IPFIX_OPTIONS_TEMPLATE_SET_ID => {
+                parse_templates(&mut set, exporter, domain_id, templates, true)?
+            }
+            id if id >= MIN_DATA_SET_ID => decode_data_set(
+                &mut set,
+                id,
+                exporter,
+                domain_id,
+                &ctx,
+                templates,
+                &mut records,
+            )?,
+            _ => {} // reserved
+        }
+    }
+    Ok(records)
+}
+
+/// Parses a v9 template flowset or an IPFIX (options) template set. IPFIX
+/// field specifiers may carry an enterprise number, signalled by the top bit of
+/// the element id, which never happens in v9.
+fn parse_templates(
+    set: &mut Reader<'_>,
+    exporter: IpAddr,
+    domain: u32,
+    templates: &mut TemplateCache,
+    is_options: bool,
+) -> Result<(), DecodeError> {
+    // anything shorter than a template header is padding
+    while set.remaining() >= 4 {
+        let template_id = set.u16()?;
+        let field_count = set.u16()? as usize;
+        if is_options {
+            let _scope_count = set.u16()?;
+        }
+        if field_count == 0 {
+            // template withdrawal
+            templates.templates.remove(&(exporter, domain, template_id));
+            continue;
+        }
+        let mut fields = Vec::with_capacity(field_count);
+        for _ in 0..field_count {
+            let raw_id = set.u16()?;
+            let length = set.u16()?;
+            let enterprise = if raw_id & 0x8000 != 0 {
+                Some(set.u32()?)
+            } else {
+                None
+            };
+            fields.push(FieldSpec {
+                id: raw_id & 0x7fff,
+                length,
+                enterprise,
+            });
+        }
+        templates.insert((exporter, domain, template_id), fields, is_options);
+    }
+    Ok(())
+}
+
+/// v9 options templates list scope and option fields with separate byte lengths
+fn parse_v9_options_templates(
+    set: &mut Reader<'_>,
+    exporter: IpAddr,
+    domain: u32,
+    templates: &mut TemplateCache,
+) -> Result<(), DecodeError> {
+    while set.remaining() >= 6 {
+        let template_id = set.u16()?;
+        let scope_len = set.u16()? as usize;
+        let option_len = set.u16()? as usize;
+        let mut fields = Vec::with_capacity((scope_len + option_len) / 4);
+        for _ in 0..(scope_len + option_len) / 4 {
+            let id = set.u16()?;
+            let length = set.u16()?;
+            fields.push(FieldSpec {
+                id,
+                length,
+                enterprise: None,
+            });
+        }
+        templates.insert((exporter, domain, template_id), fields, true);
+    }
+    Ok(())
+}
+
+fn decode_data_set(
+    set: &mut Reader<'_>,
+    template_id: u16,
+    exporter: IpAddr,
+    domain: u32,
+    ctx: &ExportContext,
+    templates: &TemplateCache,
+    records: &mut Vec<FlowRecord>,
+) -> Result<(), DecodeError> {
+    let Some(template) = templates.get(&(exporter, domain, template_id)) else {
+        log::debug!(
+            "[FLOW] no template {template_id} from {exporter} domain {domain}, skipping data set"
+        );
+        return Ok(());
+    };
+    let min_len: usize = template
+        .fields
+        .iter()
+        .map(|f| {
+            if f.length == VARIABLE_LENGTH {
+                1
+            } else {
+                f.length as usize
+            }
+        })
+        .sum();
+    if min_len == 0 {
+        return Ok(());
+    }
+
+    // trailing bytes shorter than a record are padding
+    while set.remaining() >= min_len {
+        let mut rec = base_record(ctx, exporter);
+        rec.insert("template_id".to_string(), template_id.into());
+        if template.is_options {
+            rec.insert("record_type".to_string(), "options".into());
+        }
+        for field in template.fields.iter() {
+            let length = match field.length {
+                VARIABLE_LENGTH => match set.u8()? {
+                    255 => set.u16()? as usize,
+                    n => n as usize,
+                },
+                n => n as usize,
+            };
+            let value = set.bytes(length)?;
+            let (name, value) = decode_field(field, value);
+            rec.insert(name, value);
+        }
+        rec.insert(
+            TIMESTAMP_COL_NAME.to_string(),
+            (flow_timestamp_ms(&rec, ctx) * 1000).into(),
+        );
+        records.push(rec);
+    }
+    Ok(())
+}
+
+fn base_record(ctx: &ExportContext, exporter: IpAddr) -> FlowRecord {
+    let mut rec = FlowRecord::new();
+    rec.insert("flow_type".to_string(), ctx.flow_type().into());
+    rec.insert("exporter".to_string(), exporter.to_string().into());
+    rec
+}
+
+/// Uses the flow end time when the exporter sent one, the export time otherwise
+fn flow_timestamp_ms(rec: &FlowRecord, ctx: &ExportContext) -> i64 {
+    if let Some(v) = rec.get("flow_end_ms").and_then(json::Value::as_i64) {
+        return v;
+    }
+    if let Some(v) = rec.get("last_switched").and_then(json::Value::as_i64) {
+        return ctx.uptime_to_epoch_ms(v);
+    }
+    if let Some(v) = rec.get("flow_end_seconds").and_then(json::Value::as_i64) {
+        return v * 1000;
+    }
+    ctx.export_ms
+}
+
+fn decode_field(field: &FieldSpec, value: &[u8]) -> (String, json::Value) {
+    if let Some(pen) = field.enterprise {
+        return (format!("ent_{pen}_{}", field.id), unsigned_or_hex(value));
+    }
+    let name = field_name(field.id);
+    let value = match field.id {
+        // IPv4 addresses
+        8 | 12 | 15 | 18 | 130 | 225 | 226 => ip_value(value),
+        // IPv6 addresses
+        27 | 28 | 62 | 63 | 131 | 281 | 282 => ip_value(value),
+        // MAC addresses
+        56 | 57 | 80 | 81 => mac_to_string(value).into(),
+        // strings
+        82 | 83 | 84 | 94 | 96 | 100 => String::from_utf8_lossy(value)
+            .trim_end_matches('\0')
+            .to_string()
+            .into(),
+        _ => unsigned_or_hex(value),
+    };
+    (name, value)
+}
+
+fn ip_value(b: &[u8]) -> json::Value {
+    match b.len() {
+        4 => ipv4_from_slice(b).map(|v| v.to_string()).into(),
+        16 => ipv6_from_slice(b).map(|v| v.to_string()).into(),
+        _ => hex::encode(b).into(),
+    }
+}
+
+fn unsigned_or_hex(b: &[u8]) -> json::Value {
+    if b.is_empty() || b.len() > 8 {
+        return hex::encode(b).into();
+    }
+    let v = b.iter().fold(0u64, |acc, x| (acc << 8) | *x as u64);
+    v.into()
+}
+
+/// Names for the most common information elements, shared by NetFlow v9 and
+/// IPFIX (IANA registry), everything else is exported as `field_<id>`
+fn field_name(id: u16) -> String {
+    let name = match id {
+        1 => "bytes",
+        2 => "packets",
+        3 => "flows",
+        4 => "protocol",
+        5 => "tos",
+        6 => "tcp_flags",
+        7 => "src_port",
+        8 => "src_addr",
+        9 => "src_mask",
+        10 => "input_snmp",
+        11 => "dst_port",
+        12 => "dst_addr",
+        13 => "dst_mask",
+        14 => "output_snmp",
+        15 => "next_hop",
+        16 => "src_as",
+        17 => "dst_as",
+        18 => "bgp_next_hop",
+        21 => "last_switched",
+        22 => "first_switched",
+        23 => "out_bytes",
+        24 => "out_packets",
+        27 => "src_addr",
+        28 => "dst_addr",
+        29 => "src_mask",
+        30 => "dst_mask",
+        31 => "ipv6_flow_label",
+        32 => "icmp_type",
+        34 => "sampling_interval",
+        35 => "sampling_algorithm",
+        56 => "src_mac",
+        57 => "dst_mac",
+        58 => "vlan_id",
+        59 => "post_vlan_id",
+        60 => "ip_version",
+        61 => "direction",
+        62 => "next_hop",
+        63 => "bgp_next_hop",
+        80 => "post_dst_mac",
+        81 => "post_src_mac",
+        82 => "interface_name",
+        83 => "interface_description",
+        84 => "sampler_name",
+        85 => "total_bytes",
+        86 => "total_packets",
+        89 => "forwarding_status",
+        94 => "application_description",
+        95 => "application_id",
+        96 => "application_name",
+        100 => "class_name",
+        130 => "exporter_addr",
+        131 => "exporter_addr",
+        136 => "flow_end_reason",
+        148 => "flow_id",
+        150 => "flow_start_seconds",
+        151 => "flow_end_seconds",
+        152 => "flow_start_ms",
+        153 => "flow_end_ms",
+        176 => "icmp_type",
+        177 => "icmp_code",
+        225 => "post_nat_src_addr",
+        226 => "post_nat_dst_addr",
+        227 => "post_napt_src_port",
+        228 => "post_napt_dst_port",
+        281 => "post_nat_src_addr",
+        282 => "post_nat_dst_addr",
+        _ => return format!("field_{id}"),
+    };
+    name.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exporter() -> IpAddr {
+        "192.0.2.1".parse().unwrap()
+    }
+
+    fn cache() -> TemplateCache {
+        TemplateCache::new(Duration::from_secs(60))
+    }
+
+    #[test]
+    fn test_decode_v5() {
+        let mut buf = vec![];
+        buf.extend_from_slice(&5u16.to_be_bytes());
+        buf.extend_from_slice(&1u16.to_be_bytes()); // count
+        buf.extend_from_slice(&10_000u32.to_be_bytes()); // sys uptime
+        buf.extend_from_slice(&1_700_000_000u32.to_be_bytes()); // unix secs
+        buf.extend_from_slice(&0u32.to_be_bytes()); // unix nsecs
+        buf.extend_from_slice(&42u32.to_be_bytes()); // sequence
+        buf.extend_from_slice(&[0, 0]); // engine type/id
+        buf.extend_from_slice(&100u16.to_be_bytes()); // sampling
+        // record
+        buf.extend_from_slice(&[10, 0, 0, 1]);
+        buf.extend_from_slice(&[10, 0, 0, 2]);
+        buf.extend_from_slice(&[0, 0, 0, 0]);
+        buf.extend_from_slice(&1u16.to_be_bytes());
+        buf.extend_from_slice(&2u16.to_be_bytes());
+        buf.extend_from_slice(&5u32.to_be_bytes()); // packets
+        buf.extend_from_slice(&1500u32.to_be_bytes()); // bytes
+        buf.extend_from_slice(&8_000u32.to_be_bytes()); // first
+        buf.extend_from_slice(&9_000u32.to_be_bytes()); // last
+        buf.extend_from_slice(&443u16.to_be_bytes());
+        buf.extend_from_slice(&51000u16.to_be_bytes());
+        buf.extend_from_slice(&[0, 0x18, 6, 0]);
+        buf.extend_from_slice(&[0, 0, 0, 0, 24, 24, 0, 0]);
+
+        let records = decode(&buf, exporter(), &mut cache()).unwrap();
+        assert_eq!(records.len(), 1);
+        let rec = &records[0];
+        assert_eq!(rec["flow_type"], "netflow_v5");
+        assert_eq!(rec["src_addr"], "10.0.0.1");
+        assert_eq!(rec["dst_addr"], "10.0.0.2");
+        assert_eq!(rec["bytes"], 1500);
+        assert_eq!(rec["protocol"], 6);
+        assert_eq!(rec["src_port"], 443);
+        assert_eq!(rec["sampling_interval"], 100);
+        // last switched was 1s before export
+        assert_eq!(rec["flow_end_ms"], 1_700_000_000_000i64 - 1000);
+        assert_eq!(rec["_timestamp"], (1_700_000_000_000i64 - 1000) * 1000);
+    }
+
+    #[test]
+    fn test_decode_v5_truncated() {
+        let mut buf = vec![0u8; V5_HEADER_LEN];
+        buf[1] = 5;
+        buf[3] = 2; // claims two records
+        assert!(matches!(
+            decode(&buf, exporter(), &mut cache()),
+            Err(DecodeError::Truncated { .. })
+        ));
+    }
+
+    fn v9_header(count: u16) -> Vec<u8> {
+        let mut buf = vec![];
+        buf.extend_from_slice(&9u16.to_be_bytes());
+        buf.extend_from_slice(&count.to_be_bytes());
+        buf.extend_from_slice(&60_000u32.to_be_bytes()); // sys uptime
+        buf.extend_from_slice(&1_700_000_000u32.to_be_bytes());
+        buf.extend_from_slice(&1u32.to_be_bytes()); // sequence
+        buf.extend_from_slice(&7u32.to_be_bytes()); // source id
+        buf
+    }
+
+    #[test]
+    fn test_decode_v9_template_then_data() {
+        let mut templates = cache();
+
+        // template flowset: id 256 with src addr, dst addr, bytes(4), protocol(1)
+        let mut pkt = v9_header(1);
+        let fields: [(u16, u16); 4] = [(8, 4), (12, 4), (1, 4), (4, 1)];
+        pkt.extend_from_slice(&0u16.to_be_bytes());
+        pkt.extend_from_slice(&((4 + 4 + fields.len() * 4) as u16).to_be_bytes());
+        pkt.extend_from_slice(&256u16.to_be_bytes());
+        pkt.extend_from_slice(&(fields.len() as u16).to_be_bytes());
+        for (id, len) in fields {
+            pkt.extend_from_slice(&id.to_be_bytes());
+            pkt.extend_from_slice(&len.to_be_bytes());
+        }
+        assert!(decode(&pkt, exporter(), &mut templates).unwrap().is_empty());
+        assert_eq!(templates.len(), 1);
+
+        // data flowset with two records and padding
+        let mut pkt = v9_header(2);
+        pkt.extend_from_slice(&256u16.to_be_bytes());
+        pkt.extend_from_slice(&(4u16 + 13 * 2 + 2).to_be_bytes());
+        for last in [1u8, 2u8] {
+            pkt.extend_from_slice(&[172, 16, 0, last]);
+            pkt.extend_from_slice(&[172, 16, 1, last]);
+            pkt.extend_from_slice(&1000u32.to_be_bytes());
+            pkt.push(17);
+        }
+        pkt.extend_from_slice(&[0, 0]);
+        let records = decode(&pkt, exporter(), &mut templates).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["flow_type"], "netflow_v9");
+        assert_eq!(records[0]["src_addr"], "172.16.0.1");
+        assert_eq!(records[1]["dst_addr"], "172.16.1.2");
+        assert_eq!(records[1]["protocol"], 17);
+        assert_eq!(records[1]["template_id"], 256);
+        assert_eq!(records[1]["_timestamp"], 1_700_000_000_000_000i64);
+
+        // another exporter doesn't share templates
+        let other: IpAddr = "192.0.2.2".parse().unwrap();
+        assert!(decode(&pkt, other, &mut templates).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_decode_ipfix_variable_length_and_enterprise() {
+        let mut templates = cache();
+        let mut set = vec![];
+        set.extend_from_slice(&300u16.to_be_bytes()); // template id
+        set.extend_from_slice(&3u16.to_be_bytes()); // field count
+        set.extend_from_slice(&153u16.to_be_bytes()); // flowEndMilliseconds
+        set.extend_from_slice(&8u16.to_be_bytes());
+        set.extend_from_slice(&96u16.to_be_bytes()); // applicationName
+        set.extend_from_slice(&VARIABLE_LENGTH.to_be_bytes());
+        set.extend_from_slice(&(0x8000u16 | 5).to_be_bytes()); // enterprise field
+        set.extend_from_slice(&2u16.to_be_bytes());
+        set.extend_from_slice(&9u32.to_be_bytes()); // PEN
+
+        let mut data = vec![];
+        data.extend_from_slice(&1_700_000_123_456u64.to_be_bytes());
+        data.push(5);
+        data.extend_from_slice(b"https");
+        data.extend_from_slice(&7u16.to_be_bytes());
+
+        let mut pkt = vec![];
+        pkt.extend_from_slice(&10u16.to_be_bytes());
+        let total = 16 + 4 + set.len() + 4 + data.len();
+        pkt.extend_from_slice(&(total as u16).to_be_bytes());
+        pkt.extend_from_slice(&1_700_000_200u32.to_be_bytes());
+        pkt.extend_from_slice(&1u32.to_be_bytes());
+        pkt.extend_from_slice(&1u32.to_be_bytes()); // domain
+        pkt.extend_from_slice(&IPFIX_TEMPLATE_SET_ID.to_be_bytes());
+        pkt.extend_from_slice(&((4 + set.len()) as u16).to_be_bytes());
+        pkt.extend_from_slice(&set);
+        pkt.extend_from_slice(&300u16.to_be_bytes());
+        pkt.extend_from_slice(&((4 + data.len()) as u16).to_be_bytes());
+        pkt.extend_from_slice(&data);
+
+        let records = decode(&pkt, exporter(), &mut templates).unwrap();
+        assert_eq!(records.len(), 1);
+        let rec = &records[0];
+        assert_eq!(rec["flow_type"], "ipfix");
+        assert_eq!(rec["application_name"], "https");
+        assert_eq!(rec["ent_9_5"], 7);
+        assert_eq!(rec["_timestamp"], 1_700_000_123_456_000i64);
+    }
+
+    #[test]
+    fn test_decode_ipfix_invalid_length() {
+        let mut buf = vec![0u8; IPFIX_HEADER_LEN];
+        buf[1] = 10;
+        buf[3] = 8; // shorter than the header
+        assert!(matches!(
+            decode(&buf, exporter(), &mut cache()),
+            Err(DecodeError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let buf = [0u8, 7, 0, 0];
+        assert_eq!(
+            decode(&buf, exporter(), &mut cache()).unwrap_err(),
+            DecodeError::UnsupportedVersion(7)
+        );
+    }
+
+    #[test]
+    fn test_template_cache_expiry() {
+        let mut templates = TemplateCache::new(Duration::from_millis(0));
+        templates.insert((exporter(), 1, 256), vec![], false);
+        assert!(templates.get(&(exporter(), 1, 256)).is_none());
+        templates.purge_expired();
+        assert!(templates.is_empty());
+    }
+
+    #[test]
+    fn test_field_name() {
+        assert_eq!(field_name(8), "src_addr");
+        assert_eq!(field_name(27), "src_addr");
+        assert_eq!(field_name(9999), "field_9999");
+    }
+}
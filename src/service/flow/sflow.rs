@@ -0,0 +1,432 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! sFlow v5 decoder
+
+use std::net::IpAddr;
+
+use config::TIMESTAMP_COL_NAME;
+
+use super::{DecodeError, FlowRecord, Reader, ipv4_from_slice, ipv6_from_slice, mac_to_string};
+
+const SAMPLE_FLOW: u32 = 1;
+const SAMPLE_COUNTER: u32 = 2;
+const SAMPLE_EXPANDED_FLOW: u32 = 3;
+const SAMPLE_EXPANDED_COUNTER: u32 = 4;
+
+const FLOW_RAW_PACKET_HEADER: u32 = 1;
+const FLOW_EXTENDED_SWITCH: u32 = 1001;
+const COUNTER_GENERIC_INTERFACE: u32 = 1;
+
+const HEADER_PROTOCOL_ETHERNET: u32 = 1;
+const ETHERTYPE_VLAN: u16 = 0x8100;
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86dd;
+const IP_PROTO_TCP: u8 = 6;
+const IP_PROTO_UDP: u8 = 17;
+
+/// Decodes an sFlow v5 datagram into one record per flow or counter sample.
+pub fn decode(buf: &[u8], exporter: IpAddr) -> Result<Vec<FlowRecord>, DecodeError> {
+    let mut r = Reader::new(buf);
+    let version = r.u32()?;
+    if version != 5 {
+        return Err(DecodeError::UnsupportedVersion(version));
+    }
+    let agent = match r.u32()? {
+        1 => ipv4_from_slice(r.bytes(4)?),
+        2 => ipv6_from_slice(r.bytes(16)?),
+        t => {
+            return Err(DecodeError::Malformed(format!(
+                "unknown agent address type {t}"
+            )));
+        }
+    };
+    let sub_agent_id = r.u32()?;
+    let sequence = r.u32()?;
+    let _uptime = r.u32()?;
+    let sample_count = r.u32()?;
+
+    // sFlow carries no wall clock, samples are stamped on arrival
+    let now = chrono::Utc::now().timestamp_micros();
+    // the count is untrusted, the records grow with the samples actually read
+    let mut records = Vec::new();
+    for _ in 0..sample_count {
+        let format = r.u32()?;
+        let length = r.u32()? as usize;
+        let mut sample = r.sub(length)?;
+        // enterprise 0 is standard sFlow, vendor formats are skipped
+        if format >> 12 != 0 {
+            continue;
+        }
+        let mut rec = FlowRecord::new();
+        rec.insert("flow_type".to_string(), "sflow".into());
+        rec.insert("exporter".to_string(), exporter.to_string().into());
+        if let Some(agent) = agent {
+            rec.insert("agent_addr".to_string(), agent.to_string().into());
+        }
+        rec.insert("sub_agent_id".to_string(), sub_agent_id.into());
+        rec.insert("datagram_sequence".to_string(), sequence.into());
+        match format & 0xfff {
+            SAMPLE_FLOW => decode_flow_sample(&mut sample, false, &mut rec)?,
+            SAMPLE_EXPANDED_FLOW => decode_flow_sample(&mut sample, true, &mut rec)?,
+            SAMPLE_COUNTER => decode_counter_sample(&mut sample, false, &mut rec)?,
+            SAMPLE_EXPANDED_COUNTER => decode_counter_sample(&mut sample, true, &mut rec)?,
+            _ => continue,
+        }
+        rec.insert(TIMESTAMP_COL_NAME.to_string(), now.into());
+        records.push(rec);
+    }
+    Ok(records)
+}
+
+fn decode_flow_sample(
+    r: &mut Reader<'_>,
+    expanded: bool,
+    rec: &mut FlowRecord,
+) -> Result<(), DecodeError> {
+    rec.insert("sample_type".to_string(), "flow".into());
+    rec.insert("sample_sequence".to_string(), r.u32()?.into());
+    let (source_type, source_index) = source_id(r, expanded)?;
+    rec.insert("source_id_type".to_string(), source_type.into());
+    rec.insert("source_id_index".to_string(), source_index.into());
+    rec.insert("sampling_rate".to_string(), r.u32()?.into());
+    rec.insert("sample_pool".to_string(), r.u32()?.into());
+    rec.insert("drops".to_string(), r.u32()?.into());
+    let (input, output) = if expanded {
+        r.skip(4)?; // input format
+        let input = r.u32()?;
+        r.skip(4)?; // output format
+        (input, r.u32()?)
+    } else {
+        // top two bits are the format, the rest is the ifIndex
+        (r.u32()? & 0x3fff_ffff, r.u32()? & 0x3fff_ffff)
+    };
+    rec.insert("input_snmp".to_string(), input.into());
+    rec.insert("output_snmp".to_string(), output.into());
+
+    let record_count = r.u32()?;
+    for _ in 0..record_count {
+        let format = r.u32()?;
+        let length = r.u32()? as usize;
+        let mut data = r.sub(length)?;
+        match format {
+            FLOW_RAW_PACKET_HEADER => decode_raw_header(&mut data, rec)?,
+            FLOW_EXTENDED_SWITCH => {
+                rec.insert("src_vlan".to_string(), data.u32()?.into());
+                rec.insert("src_priority".to_string(), data.u32()?.into());
+                rec.insert("dst_vlan".to_string(), data.u32()?.into());
+                rec.insert("dst_priority".to_string(), data.u32()?.into());
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn decode_counter_sample(
+    r: &mut Reader<'_>,
+    expanded: bool,
+    rec: &mut FlowRecord,
+) -> Result<(), DecodeError> {
+    rec.insert("sample_type".to_string(), "counter".into());
+    rec.insert("sample_sequence".to_string(), r.u32()?.into());
+    let (source_type, source_index) = source_id(r, expanded)?;
+    rec.insert("source_id_type".to_string(), source_type.into());
+    rec.insert("source_id_index".to_string(), source_index.into());
+
+    let record_count = r.u32()?;
+    for _ in 0..record_count {
+        let format = r.u32()?;
+        let length = r.u32()? as usize;
+        let mut data = r.sub(length)?;
+        if format != COUNTER_GENERIC_INTERFACE {
+            continue;
+        }
+        rec.insert("if_index".to_string(), data.u32()?.into());
+        rec.insert("if_type".to_string(), data.u32()?.into());
+        rec.insert("if_speed".to_string(), data.u64()?.into());
+        rec.insert("if_direction".to_string(), data.u32()?.into());
+        rec.insert("if_status".to_string(), data.u32()?.into());
+        rec.insert("if_in_octets".to_string(), data.u64()?.into());
+        rec.insert("if_in_ucast_pkts".to_string(), data.u32()?.into());
+        rec.insert("if_in_multicast_pkts".to_string(), data.u32()?.into());
+        rec.insert("if_in_broadcast_pkts".to_string(), data.u32()?.into());
+        rec.insert("if_in_discards".to_string(), data.u32()?.into());
+        rec.insert("if_in_errors".to_string(), data.u32()?.into());
+        rec.insert("if_in_unknown_protos".to_string(), data.u32()?.into());
+        rec.insert("if_out_octets".to_string(), data.u64()?.into());
+        rec.insert("if_out_ucast_pkts".to_string(), data.u32()?.into());
+        rec.insert("if_out_multicast_pkts".to_string(), data.u32()?.into());
+        rec.insert("if_out_broadcast_pkts".to_string(), data.u32()?.into());
+        rec.insert("if_out_discards".to_string(), data.u32()?.into());
+        rec.insert("if_out_errors".to_string(), data.u32()?.into());
+        rec.insert("if_promiscuous_mode".to_string(), data.u32()?.into());
+    }
+    Ok(())
+}
+
+/// Compact samples pack type and index into one word, expanded ones don't
+fn source_id(r: &mut Reader<'_>, expanded: bool) -> Result<(u32, u32), DecodeError> {
+    if expanded {
+        Ok((r.u32()?, r.u32()?))
+    } else {
+        let v = r.u32()?;
+        Ok((v >> 24, v & 0x00ff_ffff))
+    }
+}
+
+/// Extracts L2-L4 fields from the sampled packet header
+fn decode_raw_header(r: &mut Reader<'_>, rec: &mut FlowRecord) -> Result<(), DecodeError> {
+    let protocol = r.u32()?;
+    let frame_length = r.u32()?;
+    let _stripped = r.u32()?;
+    let header_length = r.u32()? as usize;
+    let header = r.bytes(header_length)?;
+    rec.insert("frame_length".to_string(), frame_length.into());
+    if protocol != HEADER_PROTOCOL_ETHERNET {
+        return Ok(());
+    }
+
+    // sampled headers are truncated by the agent, so parse as far as possible
+    let mut h = Reader::new(header);
+    let Ok(dst_mac) = h.bytes(6) else {
+        return Ok(());
+    };
+    let Ok(src_mac) = h.bytes(6) else {
+        return Ok(());
+    };
+    rec.insert("dst_mac".to_string(), mac_to_string(dst_mac).into());
+    rec.insert("src_mac".to_string(), mac_to_string(src_mac).into());
+    let Ok(mut ether_type) = h.u16() else {
+        return Ok(());
+    };
+    if ether_type == ETHERTYPE_VLAN {
+        let Ok(tci) = h.u16() else {
+            return Ok(());
+        };
+        rec.insert("vlan_id".to_string(), (tci & 0x0fff).into());
+        let Ok(inner) = h.u16() else {
+            return Ok(());
+        };
+        ether_type = inner;
+    }
+    rec.insert("ether_type".to_string(), ether_type.into());
+
+    let ip_proto = match ether_type {
+        ETHERTYPE_IPV4 => decode_ipv4(&mut h, rec),
+        ETHERTYPE_IPV6 => decode_ipv6(&mut h, rec),
+        _ => None,
+    };
+    if let Some(proto) = ip_proto
+        && (proto == IP_PROTO_TCP || proto == IP_PROTO_UDP)
+    {
+        let (Ok(src_port), Ok(dst_port)) = (h.u16(), h.u16()) else {
+            return Ok(());
+        };
+        rec.insert("src_port".to_string(), src_port.into());
+        rec.insert("dst_port".to_string(), dst_port.into());
+        if proto == IP_PROTO_TCP
+            && h.skip(9).is_ok()
+            && let Ok(flags) = h.u8()
+        {
+            rec.insert("tcp_flags".to_string(), flags.into());
+        }
+    }
+    Ok(())
+}
+
+fn decode_ipv4(h: &mut Reader<'_>, rec: &mut FlowRecord) -> Option<u8> {
+    let ver_ihl = h.u8().ok()?;
+    let ihl = ((ver_ihl & 0x0f) as usize) * 4;
+    let tos = h.u8().ok()?;
+    h.skip(6).ok()?; // total length, id, fragment
+    let ttl = h.u8().ok()?;
+    let proto = h.u8().ok()?;
+    h.skip(2).ok()?; // checksum
+    let src = ipv4_from_slice(h.bytes(4).ok()?)?;
+    let dst = ipv4_from_slice(h.bytes(4).ok()?)?;
+    rec.insert("ip_version".to_string(), 4u8.into());
+    rec.insert("tos".to_string(), tos.into());
+    rec.insert("ttl".to_string(), ttl.into());
+    rec.insert("protocol".to_string(), proto.into());
+    rec.insert("src_addr".to_string(), src.to_string().into());
+    rec.insert("dst_addr".to_string(), dst.to_string().into());
+    h.skip(ihl.saturating_sub(20)).ok()?; // options
+    Some(proto)
+}
+
+fn decode_ipv6(h: &mut Reader<'_>, rec: &mut FlowRecord) -> Option<u8> {
+    h.skip(6).ok()?; // version, traffic class, flow label, payload length
+    let next_header = h.u8().ok()?;
+    let hop_limit = h.u8().ok()?;
+    let src = ipv6_from_slice(h.bytes(16).ok()?)?;
+    let dst = ipv6_from_slice(h.bytes(16).ok()?)?;
+    rec.insert("ip_version".to_string(), 6u8.into());
+    rec.insert("ttl".to_string(), hop_limit.into());
+    rec.insert("protocol".to_string(), next_header.into());
+    rec.insert("src_addr".to_string(), src.to_string().into());
+    rec.insert("dst_addr".to_string(), dst.to_string().into());
+    Some(next_header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_u32(buf: &mut Vec<u8>, v: u32) {
+        buf.extend_from_slice(&v.to_be_bytes());
+    }
+
+    fn datagram(samples: &[(u32, Vec<u8>)]) -> Vec<u8> {
+        let mut buf = vec![];
+        push_u32(&mut buf, 5);
+        push_u32(&mut buf, 1);
+        buf.extend_from_slice(&[10, 1, 1, 1]);
+        push_u32(&mut buf, 0); // sub agent
+        push_u32(&mut buf, 99); // sequence
+        push_u32(&mut buf, 1000); // uptime
+        push_u32(&mut buf, samples.len() as u32);
+        for (format, data) in samples {
+            push_u32(&mut buf, *format);
+            push_u32(&mut buf, data.len() as u32);
+            buf.extend_from_slice(data);
+        }
+        buf
+    }
+
+    fn tcp_packet() -> Vec<u8> {
+        let mut p = vec![];
+        p.extend_from_slice(&[0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        p.extend_from_slice(&[0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb]);
+        p.extend_from_slice(&ETHERTYPE_VLAN.to_be_bytes());
+        p.extend_from_slice(&100u16.to_be_bytes());
+        p.extend_from_slice(&ETHERTYPE_IPV4.to_be_bytes());
+        p.extend_from_slice(&[0x45, 0, 0, 60, 0, 0, 0, 0, 64, IP_PROTO_TCP, 0, 0]);
+        p.extend_from_slice(&[192, 168, 0, 1, 192, 168, 0, 2]);
+        p.extend_from_slice(&443u16.to_be_bytes());
+        p.extend_from_slice(&40000u16.to_be_bytes());
+        p.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0, 0x50, 0x12]);
+        p
+    }
+
+    #[test]
+    fn test_decode_flow_sample() {
+        let pkt = tcp_packet();
+        let mut raw = vec![];
+        push_u32(&mut raw, HEADER_PROTOCOL_ETHERNET);
+        push_u32(&mut raw, 1514);
+        push_u32(&mut raw, 4);
+        push_u32(&mut raw, pkt.len() as u32);
+        raw.extend_from_slice(&pkt);
+
+        let mut sample = vec![];
+        push_u32(&mut sample, 7); // sequence
+        push_u32(&mut sample, 3); // source id (type 0, index 3)
+        push_u32(&mut sample, 1024); // sampling rate
+        push_u32(&mut sample, 2048); // pool
+        push_u32(&mut sample, 0); // drops
+        push_u32(&mut sample, 3); // input
+        push_u32(&mut sample, 4); // output
+        push_u32(&mut sample, 1); // records
+        push_u32(&mut sample, FLOW_RAW_PACKET_HEADER);
+        push_u32(&mut sample, raw.len() as u32);
+        sample.extend_from_slice(&raw);
+
+        let buf = datagram(&[(SAMPLE_FLOW, sample)]);
+        let records = decode(&buf, "10.1.1.1".parse().unwrap()).unwrap();
+        assert_eq!(records.len(), 1);
+        let rec = &records[0];
+        assert_eq!(rec["flow_type"], "sflow");
+        assert_eq!(rec["sample_type"], "flow");
+        assert_eq!(rec["agent_addr"], "10.1.1.1");
+        assert_eq!(rec["sampling_rate"], 1024);
+        assert_eq!(rec["vlan_id"], 100);
+        assert_eq!(rec["src_mac"], "66:77:88:99:aa:bb");
+        assert_eq!(rec["src_addr"], "192.168.0.1");
+        assert_eq!(rec["dst_addr"], "192.168.0.2");
+        assert_eq!(rec["protocol"], 6);
+        assert_eq!(rec["src_port"], 443);
+        assert_eq!(rec["dst_port"], 40000);
+        assert_eq!(rec["tcp_flags"], 0x12);
+        assert!(rec.contains_key("_timestamp"));
+    }
+
+    #[test]
+    fn test_decode_counter_sample() {
+        let mut counters = vec![];
+        push_u32(&mut counters, 3); // if index
+        push_u32(&mut counters, 6); // if type
+        counters.extend_from_slice(&10_000_000_000u64.to_be_bytes());
+        push_u32(&mut counters, 1);
+        push_u32(&mut counters, 3);
+        counters.extend_from_slice(&123_456u64.to_be_bytes());
+        for _ in 0..6 {
+            push_u32(&mut counters, 0);
+        }
+        counters.extend_from_slice(&654_321u64.to_be_bytes());
+        for _ in 0..6 {
+            push_u32(&mut counters, 0);
+        }
+
+        let mut sample = vec![];
+        push_u32(&mut sample, 1);
+        push_u32(&mut sample, 3);
+        push_u32(&mut sample, 1);
+        push_u32(&mut sample, COUNTER_GENERIC_INTERFACE);
+        push_u32(&mut sample, counters.len() as u32);
+        sample.extend_from_slice(&counters);
+
+        let buf = datagram(&[(SAMPLE_COUNTER, sample)]);
+        let records = decode(&buf, "10.1.1.1".parse().unwrap()).unwrap();
+        assert_eq!(records.len(), 1);
+        let rec = &records[0];
+        assert_eq!(rec["sample_type"], "counter");
+        assert_eq!(rec["if_speed"], 10_000_000_000u64);
+        assert_eq!(rec["if_in_octets"], 123_456);
+        assert_eq!(rec["if_out_octets"], 654_321);
+    }
+
+    #[test]
+    fn test_decode_skips_vendor_samples() {
+        let buf = datagram(&[((9 << 12) | 1, vec![0, 0, 0, 0])]);
+        assert!(
+            decode(&buf, "10.1.1.1".parse().unwrap())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_decode_unsupported_version() {
+        let mut buf = vec![];
+        push_u32(&mut buf, 4);
+        assert_eq!(
+            decode(&buf, "10.1.1.1".parse().unwrap()).unwrap_err(),
+            DecodeError::UnsupportedVersion(4)
+        );
+    }
+
+    #[test]
+    fn test_decode_truncated_sample() {
+        let mut buf = datagram(&[]);
+        // claim a sample that isn't there
+        let len = buf.len();
+        buf[len - 1] = 1;
+        assert!(matches!(
+            decode(&buf, "10.1.1.1".parse().unwrap()),
+            Err(DecodeError::Truncated { .. })
+        ));
+    }
+}
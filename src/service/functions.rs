@@ -20,13 +20,20 @@ use axum::{
     response::{IntoResponse, Response as HttpResponse},
 };
 use config::{
+    TIMESTAMP_COL_NAME,
     meta::{
         function::{
-            FunctionList, RESULT_ARRAY, TestVRLResponse, Transform, VRLResult, VRLResultResolver,
+            FUNCTION_MAX_VERSIONS, FunctionList, FunctionVersion, FunctionVersionList,
+            RESULT_ARRAY, TestVRLResponse, Transform, VRLResult, VRLResultResolver,
         },
         pipeline::{PipelineDependencyItem, PipelineDependencyResponse},
+        search,
+        stream::StreamType,
+    },
+    utils::{
+        json::Value,
+        time::{day_micros, hour_micros, now_micros},
     },
-    utils::json::Value,
 };
 
 use crate::{
@@ -43,6 +50,7 @@ use crate::{
 
 const FN_SUCCESS: &str = "Function saved successfully";
 const FN_NOT_FOUND: &str = "Function not found";
+const FN_VERSION_NOT_FOUND: &str = "Function version not found";
 const FN_ALREADY_EXIST: &str = "Function already exist";
 const FN_IN_USE: &str =
     "Function is associated with streams, please remove association from streams before deleting:";
@@ -95,6 +103,12 @@ pub async fn save_function(org_id: String, mut func: Transform) -> Result<HttpRe
             Ok(map_error_to_http_response(&error.into(), None))
         } else {
             set_ownership(&org_id, "functions", Authz::new(&func.name)).await;
+            if let Err(e) = record_version(&org_id, &func, None).await {
+                log::error!(
+                    "[FUNCTIONS] error recording version of {org_id}/{}: {e}",
+                    func.name
+                );
+            }
 
             Ok(MetaHttpResponse::ok(FN_SUCCESS))
         }
@@ -141,6 +155,41 @@ pub async fn test_run_function(
     }
 }
 
+/// The latest records of the stream, to test a function on. They are searched
+/// in the last day, or in the hour before the latest data of the stream when
+/// it is older.
+pub async fn sample_events(
+    trace_id: &str,
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    size: usize,
+) -> Result<Vec<Value>, anyhow::Error> {
+    let end_time = now_micros();
+    let mut start_time = end_time - day_micros(1);
+    let stats = infra::cache::stats::get_stream_stats(org_id, stream_name, stream_type);
+    if stats.doc_time_max > 0 && stats.doc_time_max < start_time {
+        start_time = stats.doc_time_max - hour_micros(1);
+    }
+    // quoted so the query reads the stream whose permissions were checked,
+    // and only it
+    let table = stream_name.replace('"', "\"\"");
+    let req = search::Request {
+        query: search::Query {
+            sql: format!("SELECT * FROM \"{table}\" ORDER BY {TIMESTAMP_COL_NAME} DESC"),
+            start_time,
+            end_time: end_time.max(stats.doc_time_max + 1),
+            size: size as i64,
+            ..Default::default()
+        },
+        search_type: Some(search::SearchEventType::Other),
+        use_cache: false,
+        ..Default::default()
+    };
+    let resp = crate::service::search::search(trace_id, org_id, stream_type, None, &req).await?;
+    Ok(resp.hits)
+}
+
 #[tracing::instrument(skip(org_id, function))]
 async fn test_run_vrl_function(
     org_id: &str,
@@ -172,6 +221,7 @@ async fn test_run_vrl_function(
     let fields = runtime_config.fields;
     let program = runtime_config.program;
 
+    let start = std::time::Instant::now();
     let mut transformed_events = vec![];
     if apply_over_hits {
         let (ret_val, err) = crate::service::ingestion::apply_vrl_fn(
@@ -234,9 +284,7 @@ async fn test_run_vrl_function(
         });
     }
 
-    let results = TestVRLResponse {
-        results: transformed_events,
-    };
+    let results = TestVRLResponse::new(transformed_events, start.elapsed());
 
     Ok(MetaHttpResponse::json(results))
 }
@@ -258,6 +306,7 @@ async fn test_run_js_function(
         }
     };
 
+    let start = std::time::Instant::now();
     let mut transformed_events = vec![];
 
     if apply_over_array {
@@ -314,9 +363,7 @@ async fn test_run_js_function(
         }
     }
 
-    let results = TestVRLResponse {
-        results: transformed_events,
-    };
+    let results = TestVRLResponse::new(transformed_events, start.elapsed());
 
     Ok(MetaHttpResponse::json(results))
 }
@@ -374,6 +421,12 @@ pub async fn update_function(
     if let Err(error) = db::functions::set(org_id, &func.name, &func).await {
         return Ok(map_error_to_http_response(&(error.into()), None));
     }
+    if let Err(e) = record_version(org_id, &func, Some(&existing_fn)).await {
+        log::error!(
+            "[FUNCTIONS] error recording version of {org_id}/{}: {e}",
+            func.name
+        );
+    }
 
     // update associated pipelines
     if let Ok(associated_pipelines) = db::pipeline::list_by_org(org_id).await {
@@ -470,12 +523,115 @@ pub async fn delete_function(org_id: &str, fn_name: &str) -> Result<(), Function
     match result {
         Ok(_) => {
             remove_ownership(org_id, "functions", Authz::new(fn_name)).await;
+            if let Err(e) = db::function_versions::delete_all(org_id, fn_name).await {
+                log::error!("[FUNCTIONS] error deleting versions of {org_id}/{fn_name}: {e}");
+            }
             Ok(())
         }
         Err(_) => Err(FunctionDeleteError::NotFound),
     }
 }
 
+/// Records the function as its next version and drops the oldest versions
+/// above the limit. A function saved before versions were kept has its
+/// previous content recorded first, so that it can be rolled back to.
+async fn record_version(
+    org_id: &str,
+    func: &Transform,
+    previous: Option<&Transform>,
+) -> Result<(), anyhow::Error> {
+    let mut versions = db::function_versions::list(org_id, &func.name).await?;
+    if versions.is_empty()
+        && let Some(previous) = previous
+    {
+        let first = FunctionVersion::new(1, previous);
+        db::function_versions::set(org_id, &func.name, &first).await?;
+        versions.push(first);
+    }
+    let next = versions.last().map_or(1, |v| v.version + 1);
+    let version = FunctionVersion::new(next, func);
+    db::function_versions::set(org_id, &func.name, &version).await?;
+    versions.push(version);
+    let expired = versions.len().saturating_sub(FUNCTION_MAX_VERSIONS);
+    for old in &versions[..expired] {
+        db::function_versions::delete(org_id, &func.name, old.version).await?;
+    }
+    Ok(())
+}
+
+/// The versions of the function, oldest first. A function without recorded
+/// versions has its current content as the first version.
+async fn get_versions(
+    org_id: &str,
+    func: &Transform,
+) -> Result<Vec<FunctionVersion>, anyhow::Error> {
+    let versions = db::function_versions::list(org_id, &func.name).await?;
+    if versions.is_empty() {
+        return Ok(vec![FunctionVersion {
+            created_at: 0,
+            ..FunctionVersion::new(1, func)
+        }]);
+    }
+    Ok(versions)
+}
+
+pub async fn list_function_versions(org_id: &str, fn_name: &str) -> Result<HttpResponse, Error> {
+    let Some(existing_fn) = check_existing_fn(org_id, fn_name).await else {
+        return Ok(MetaHttpResponse::not_found(FN_NOT_FOUND));
+    };
+    match get_versions(org_id, &existing_fn).await {
+        Ok(mut list) => {
+            list.reverse();
+            Ok(MetaHttpResponse::json(FunctionVersionList { list }))
+        }
+        Err(e) => Ok(MetaHttpResponse::internal_error(e)),
+    }
+}
+
+/// Restores the content of a version of the function. The restored content is
+/// saved as a new version, and the pipelines using the function are updated.
+pub async fn rollback_function(
+    org_id: &str,
+    fn_name: &str,
+    version: u32,
+) -> Result<HttpResponse, Error> {
+    let Some(existing_fn) = check_existing_fn(org_id, fn_name).await else {
+        return Ok(MetaHttpResponse::not_found(FN_NOT_FOUND));
+    };
+    let versions = match get_versions(org_id, &existing_fn).await {
+        Ok(versions) => versions,
+        Err(e) => return Ok(MetaHttpResponse::internal_error(e)),
+    };
+    let Some(version) = versions.into_iter().find(|v| v.version == version) else {
+        return Ok(MetaHttpResponse::not_found(FN_VERSION_NOT_FOUND));
+    };
+    update_function(org_id, fn_name, version.apply_to(&existing_fn)).await
+}
+
+/// Runs the saved function, or one of its versions, on the events without
+/// saving anything.
+pub async fn test_saved_function(
+    org_id: &str,
+    fn_name: &str,
+    version: Option<u32>,
+    events: Vec<Value>,
+) -> Result<HttpResponse, anyhow::Error> {
+    let Some(existing_fn) = check_existing_fn(org_id, fn_name).await else {
+        return Ok(MetaHttpResponse::not_found(FN_NOT_FOUND));
+    };
+    let func = match version {
+        None => existing_fn,
+        Some(version) => {
+            let versions = get_versions(org_id, &existing_fn).await?;
+            match versions.into_iter().find(|v| v.version == version) {
+                Some(version) => version.apply_to(&existing_fn),
+                None => return Ok(MetaHttpResponse::not_found(FN_VERSION_NOT_FOUND)),
+            }
+        }
+    };
+    test_run_function(org_id, func.function, events, func.trans_type).await
+}
+
 pub async fn get_pipeline_dependencies(
     org_id: &str,
     func_name: &str,
@@ -699,6 +855,72 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_function_versions_and_rollback() {
+        use http_body_util::BodyExt;
+        use serde_json::json;
+
+        let org_id = "test_fn_versions";
+        let name = "versioned_fn";
+        let func = Transform {
+            name: name.to_owned(),
+            function: ".version = 1".to_owned(),
+            params: "row".to_owned(),
+            trans_type: Some(0),
+            num_args: 0,
+            streams: None,
+        };
+        let resp = save_function(org_id.to_string(), func.clone())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let resp = update_function(
+            org_id,
+            name,
+            Transform {
+                function: ".version = 2".to_owned(),
+                ..func.clone()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        let resp = list_function_versions(org_id, name).await.unwrap();
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        let list: FunctionVersionList = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            list.list.iter().map(|v| v.version).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+
+        // the first version still runs while the function is at the second
+        let resp = test_saved_function(org_id, name, Some(1), vec![json!({"a": 1})])
+            .await
+            .unwrap();
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        let body: TestVRLResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.results[0].event, json!({"a": 1, "version": 1}));
+
+        let resp = rollback_function(org_id, name, 1).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let current = check_existing_fn(org_id, name).await.unwrap();
+        assert!(current.function.starts_with(".version = 1"));
+        let versions = db::function_versions::list(org_id, name).await.unwrap();
+        assert_eq!(versions.len(), 3);
+
+        let resp = rollback_function(org_id, name, 10).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+
+        assert!(delete_function(org_id, name).await.is_ok());
+        assert!(
+            db::function_versions::list(org_id, name)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
     #[tokio::test]
     async fn test_vrl_function_allowed_in_all_orgs() {
         use serde_json::json;
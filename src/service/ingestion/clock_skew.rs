@@ -0,0 +1,401 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Clock skew of the sources of the streams which track it. The skew of a
+//! source is the moving average of the offset of the timestamps of its
+//! records from their arrival time, so a few late records don't make it
+//! skewed. A source whose clock is behind makes its recent records invisible
+//! to searches of the last minutes, the streams which correct the skew shift
+//! the timestamps of the sources skewed beyond the threshold.
+//!
+//! The skew is kept per node and merged periodically into the kv store, the
+//! most recently seen state of a source wins, so the API lists the sources
+//! seen by every ingester.
+
+use std::collections::HashMap;
+
+use config::{
+    TIMESTAMP_COL_NAME,
+    meta::stream::{ClockSkew, DEFAULT_CLOCK_SKEW_SOURCE_FIELDS, StreamType},
+    metrics,
+    utils::{
+        json::{self, Map, Value},
+        time::now_micros,
+    },
+};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+use crate::{
+    common::meta::stream::{ClockSkewEntry, ClockSkewResponse, SourceClockSkew, StreamClockSkew},
+    service::db::kv,
+};
+
+/// Records of a source measured before its skew is trusted
+const MIN_RECORDS: u64 = 20;
+
+/// Weight of a record in the moving average of the skew of its source
+const SMOOTHING: f64 = 0.05;
+
+/// Sources tracked per stream, the records of new sources aren't measured
+/// beyond it
+const MAX_SOURCES: usize = 10_000;
+
+/// Sources not seen for a week are forgotten, in microseconds
+const SOURCE_TTL_MICROS: i64 = 7 * 24 * 3600 * 1_000_000;
+
+const SKEW_KEY_PREFIX: &str = "clock_skew/";
+
+#[derive(Default)]
+struct StreamSources {
+    sources: HashMap<String, SourceClockSkew>,
+    /// Measured since the last flush
+    dirty: bool,
+}
+
+/// Sources seen by this node, by `org_id/stream_type/stream_name`
+static SKEWS: Lazy<Mutex<HashMap<String, StreamSources>>> = Lazy::new(Default::default);
+
+fn skew_key(stream_type: StreamType, stream_name: &str) -> String {
+    format!("{SKEW_KEY_PREFIX}{stream_type}/{stream_name}")
+}
+
+fn observe(skew: &mut SourceClockSkew, offset: i64, now: i64) {
+    skew.skew = if skew.records == 0 {
+        offset
+    } else {
+        (skew.skew as f64 + SMOOTHING * (offset - skew.skew) as f64) as i64
+    };
+    skew.records += 1;
+    skew.last_seen = now;
+}
+
+pub struct ClockSkewTracker {
+    org_id: String,
+    stream_type: StreamType,
+    stream_name: String,
+    source_fields: Vec<String>,
+    correct: bool,
+    threshold: i64,
+}
+
+impl ClockSkewTracker {
+    /// Returns None when the stream doesn't track the skew of its sources.
+    pub fn new(
+        org_id: &str,
+        stream_type: StreamType,
+        stream_name: &str,
+        settings: &ClockSkew,
+    ) -> Option<Self> {
+        if !settings.enabled {
+            return None;
+        }
+        let source_fields = if settings.source_fields.is_empty() {
+            DEFAULT_CLOCK_SKEW_SOURCE_FIELDS
+                .iter()
+                .map(|f| f.to_string())
+                .collect()
+        } else {
+            settings.source_fields.clone()
+        };
+        Some(Self {
+            org_id: org_id.to_string(),
+            stream_type,
+            stream_name: stream_name.to_string(),
+            source_fields,
+            correct: settings.correct,
+            threshold: settings.threshold_micros(),
+        })
+    }
+
+    /// Measures the skew of the source of every record, and shifts the
+    /// timestamp of the records of the sources skewed beyond the threshold
+    /// when the stream corrects them. Returns the number of corrected records.
+    pub fn process(&self, records: &mut [(i64, Map<String, Value>)]) -> u64 {
+        let now = now_micros();
+        let mut corrected = 0;
+        let mut skews = SKEWS.lock();
+        let stream = skews
+            .entry(format!(
+                "{}/{}/{}",
+                self.org_id, self.stream_type, self.stream_name
+            ))
+            .or_default();
+        for (timestamp, record) in records.iter_mut() {
+            let Some(source) = self.source(record) else {
+                continue;
+            };
+            if stream.sources.len() >= MAX_SOURCES && !stream.sources.contains_key(&source) {
+                continue;
+            }
+            let skew = stream.sources.entry(source).or_default();
+            observe(skew, *timestamp - now, now);
+            stream.dirty = true;
+            if self.correct && skew.records >= MIN_RECORDS && skew.skew.abs() >= self.threshold {
+                *timestamp -= skew.skew;
+                record.insert(TIMESTAMP_COL_NAME.to_string(), (*timestamp).into());
+                skew.corrected += 1;
+                corrected += 1;
+            }
+        }
+        let max_skew = stream
+            .sources
+            .values()
+            .filter(|s| s.records >= MIN_RECORDS)
+            .map(|s| s.skew)
+            .max_by_key(|s| s.abs())
+            .unwrap_or_default();
+        drop(skews);
+
+        let labels = [
+            self.org_id.as_str(),
+            self.stream_type.as_str(),
+            self.stream_name.as_str(),
+        ];
+        metrics::INGEST_CLOCK_SKEW_SECONDS
+            .with_label_values(&labels)
+            .set(max_skew / 1_000_000);
+        if corrected > 0 {
+            metrics::INGEST_CLOCK_SKEW_CORRECTED_RECORDS
+                .with_label_values(&labels)
+                .inc_by(corrected);
+        }
+        corrected
+    }
+
+    fn source(&self, record: &Map<String, Value>) -> Option<String> {
+        self.source_fields
+            .iter()
+            .find_map(|f| match record.get(f)? {
+                Value::String(v) if !v.is_empty() => Some(v.clone()),
+                Value::Number(v) => Some(v.to_string()),
+                _ => None,
+            })
+    }
+}
+
+/// Merges the sources measured since the last flush into the kv store
+pub async fn flush() {
+    let now = now_micros();
+    let pending = {
+        let mut skews = SKEWS.lock();
+        let mut pending = Vec::new();
+        for (key, stream) in skews.iter_mut() {
+            stream
+                .sources
+                .retain(|_, s| now - s.last_seen < SOURCE_TTL_MICROS);
+            if stream.dirty {
+                stream.dirty = false;
+                pending.push((key.clone(), stream.sources.clone()));
+            }
+        }
+        skews.retain(|_, stream| !stream.sources.is_empty());
+        pending
+    };
+    for (key, sources) in pending {
+        let mut parts = key.splitn(3, '/');
+        let (Some(org_id), Some(stream_type), Some(stream_name)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let stream_type = StreamType::from(stream_type);
+        // read-modify-write, a concurrent flush of another node can lose the
+        // state of a source until the next flush
+        let mut skew = get(org_id, stream_type, stream_name).await;
+        merge(&mut skew, sources, now);
+        let val = match json::to_vec(&skew) {
+            Ok(v) => v,
+            Err(e) => {
+                log::error!("[CLOCK_SKEW] failed to serialize skew of {key}: {e}");
+                continue;
+            }
+        };
+        if let Err(e) = kv::set(org_id, &skew_key(stream_type, stream_name), val.into()).await {
+            log::error!("[CLOCK_SKEW] failed to save skew of {key}: {e}");
+        }
+    }
+}
+
+/// Merges the sources of a node, the state of a source seen the most recently
+/// wins and the sources not seen within the ttl are forgotten
+fn merge(skew: &mut StreamClockSkew, sources: HashMap<String, SourceClockSkew>, now: i64) {
+    for (source, state) in sources {
+        match skew.sources.get(&source) {
+            Some(v) if v.last_seen >= state.last_seen => {}
+            _ => {
+                skew.sources.insert(source, state);
+            }
+        }
+    }
+    skew.sources
+        .retain(|_, s| now - s.last_seen < SOURCE_TTL_MICROS);
+}
+
+/// Persisted clock skew of the sources of a stream, empty when none was
+/// measured yet
+pub async fn get(org_id: &str, stream_type: StreamType, stream_name: &str) -> StreamClockSkew {
+    match kv::get(org_id, &skew_key(stream_type, stream_name)).await {
+        Ok(val) => json::from_slice(&val).unwrap_or_default(),
+        Err(_) => StreamClockSkew::default(),
+    }
+}
+
+pub async fn delete(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> Result<(), anyhow::Error> {
+    SKEWS
+        .lock()
+        .remove(&format!("{org_id}/{stream_type}/{stream_name}"));
+    if kv::get(org_id, &skew_key(stream_type, stream_name))
+        .await
+        .is_err()
+    {
+        return Ok(());
+    }
+    kv::delete(org_id, &skew_key(stream_type, stream_name)).await
+}
+
+/// The sources of the streams of the organization skewed the most, the ones
+/// measured on too few records are left out
+pub async fn list(
+    org_id: &str,
+    stream_type: Option<StreamType>,
+    limit: usize,
+) -> Result<ClockSkewResponse, anyhow::Error> {
+    let prefix = match stream_type {
+        Some(stream_type) => format!("{SKEW_KEY_PREFIX}{stream_type}/"),
+        None => SKEW_KEY_PREFIX.to_string(),
+    };
+    let now = now_micros();
+    let mut list = Vec::new();
+    for key in kv::list_keys(org_id, &prefix).await? {
+        let Some((stream_type, stream_name)) = key
+            .strip_prefix(SKEW_KEY_PREFIX)
+            .and_then(|v| v.split_once('/'))
+        else {
+            continue;
+        };
+        let stream_type = StreamType::from(stream_type);
+        let mut skew = get(org_id, stream_type, stream_name).await;
+        // include what this node measured but did not flush yet
+        if let Some(stream) = SKEWS
+            .lock()
+            .get(&format!("{org_id}/{stream_type}/{stream_name}"))
+        {
+            merge(&mut skew, stream.sources.clone(), now);
+        }
+        list.extend(
+            skew.sources
+                .into_iter()
+                .filter(|(_, s)| s.records >= MIN_RECORDS)
+                .map(|(source, skew)| ClockSkewEntry {
+                    stream_type,
+                    stream_name: stream_name.to_string(),
+                    source,
+                    skew,
+                }),
+        );
+    }
+    list.sort_by(|a, b| {
+        b.skew
+            .skew
+            .abs()
+            .cmp(&a.skew.skew.abs())
+            .then_with(|| a.source.cmp(&b.source))
+    });
+    list.truncate(limit);
+    Ok(ClockSkewResponse { list })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(host: &str) -> Map<String, Value> {
+        json::json!({ "host": host, "message": "hello" })
+            .as_object()
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn test_clock_skew_tracker() {
+        let settings = ClockSkew {
+            enabled: true,
+            correct: true,
+            ..Default::default()
+        };
+        assert!(
+            ClockSkewTracker::new("org", StreamType::Logs, "s", &ClockSkew::default()).is_none()
+        );
+        let tracker =
+            ClockSkewTracker::new("test_clock_skew_org", StreamType::Logs, "s", &settings).unwrap();
+
+        // an hour behind, corrected once enough records were measured
+        let behind = now_micros() - 3600 * 1_000_000;
+        let mut records = (0..MIN_RECORDS + 5)
+            .map(|_| (behind, record("skewed")))
+            .chain(std::iter::once((now_micros(), record("in-sync"))))
+            .collect::<Vec<_>>();
+        let corrected = tracker.process(&mut records);
+        assert_eq!(corrected, 6);
+        let (ts, last) = &records[MIN_RECORDS as usize + 4];
+        assert!((now_micros() - ts).abs() < 60 * 1_000_000);
+        assert_eq!(last[TIMESTAMP_COL_NAME], *ts);
+        // the in-sync source is left alone
+        assert!(!records.last().unwrap().1.contains_key(TIMESTAMP_COL_NAME));
+
+        let skews = SKEWS.lock();
+        let stream = skews.get("test_clock_skew_org/logs/s").unwrap();
+        let skewed = stream.sources.get("skewed").unwrap();
+        assert!(skewed.skew <= -3599 * 1_000_000);
+        assert_eq!(skewed.corrected, 6);
+        assert_eq!(stream.sources.get("in-sync").unwrap().records, 1);
+    }
+
+    #[test]
+    fn test_merge() {
+        let now = now_micros();
+        let state = |skew, last_seen| SourceClockSkew {
+            skew,
+            records: MIN_RECORDS,
+            corrected: 0,
+            last_seen,
+        };
+        let mut skew = StreamClockSkew {
+            sources: HashMap::from([
+                ("a".to_string(), state(10, now - 10)),
+                ("b".to_string(), state(20, now)),
+                ("old".to_string(), state(30, now - SOURCE_TTL_MICROS)),
+            ]),
+        };
+        merge(
+            &mut skew,
+            HashMap::from([
+                ("a".to_string(), state(11, now)),
+                ("b".to_string(), state(21, now - 10)),
+                ("c".to_string(), state(40, now)),
+            ]),
+            now,
+        );
+        assert_eq!(skew.sources["a"].skew, 11);
+        assert_eq!(skew.sources["b"].skew, 20);
+        assert_eq!(skew.sources["c"].skew, 40);
+        assert!(!skew.sources.contains_key("old"));
+    }
+}
@@ -0,0 +1,252 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Drops the records of a stream already ingested within its dedup window,
+//! so the batches re-sent by at-least-once shippers are counted once.
+//!
+//! The records are compared by a hash of the configured fields, or of the
+//! whole record. The hashes are kept by each ingester in a bounded LRU, a
+//! duplicate routed to another ingester, or seen after its hash was evicted,
+//! is ingested again. The hashes are only kept once the records are written,
+//! so a batch failing to be written is not dropped when it is sent again.
+
+use std::{collections::BTreeMap, hash::Hasher};
+
+use config::{
+    ALL_VALUES_COL_NAME, ID_COL_NAME, ORIGINAL_DATA_COL_NAME, TIMESTAMP_COL_NAME, get_config,
+    meta::stream::{IngestDedup, StreamType},
+    utils::{
+        hash::cityhash,
+        json::{self, Map, Value},
+        time::now_micros,
+    },
+};
+use hashbrown::HashSet;
+use hashlink::lru_cache::LruCache;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+/// Hash of the stream and the record to the time it was first seen
+static SEEN: Lazy<Mutex<LruCache<u64, i64>>> = Lazy::new(|| {
+    Mutex::new(LruCache::new(
+        get_config().limit.ingest_dedup_max_entries.max(1),
+    ))
+});
+
+/// Columns set by the server, they differ between two sends of a record
+const SERVER_COLUMNS: [&str; 4] = [
+    TIMESTAMP_COL_NAME,
+    ID_COL_NAME,
+    ORIGINAL_DATA_COL_NAME,
+    ALL_VALUES_COL_NAME,
+];
+
+pub struct Deduplicator<'a> {
+    stream_key: String,
+    fields: &'a [String],
+    window: i64,
+}
+
+impl<'a> Deduplicator<'a> {
+    /// Returns None when the stream has no dedup window
+    pub fn new(
+        org_id: &str,
+        stream_type: StreamType,
+        stream_name: &str,
+        dedup: &'a IngestDedup,
+    ) -> Option<Self> {
+        (!dedup.is_empty()).then(|| Self {
+            stream_key: format!("{org_id}/{stream_type}/{stream_name}"),
+            fields: &dedup.fields,
+            window: (dedup.window_secs as i64).saturating_mul(1_000_000),
+        })
+    }
+
+    /// Removes the duplicated records, returns the hashes of the kept ones,
+    /// in their order, to pass to [`Self::commit`] once they are written
+    pub fn retain(&self, records: &mut Vec<(i64, Map<String, Value>)>) -> Vec<u64> {
+        self.retain_at(records, now_micros())
+    }
+
+    /// Marks the written records as seen
+    pub fn commit(&self, hashes: &[u64]) {
+        self.commit_at(hashes, now_micros())
+    }
+
+    fn retain_at(&self, records: &mut Vec<(i64, Map<String, Value>)>, now: i64) -> Vec<u64> {
+        let hashes = records
+            .iter()
+            .map(|(_, record)| self.hash(record))
+            .collect::<Vec<_>>();
+        let mut kept = Vec::with_capacity(hashes.len());
+        // the duplicates inside the batch are dropped too
+        let mut in_batch = HashSet::with_capacity(hashes.len());
+        let mut hashes = hashes.into_iter();
+        let mut seen = SEEN.lock();
+        records.retain(|_| {
+            let hash = hashes.next().unwrap();
+            let duplicated = seen
+                .get(&hash)
+                .is_some_and(|first_seen| now - *first_seen < self.window);
+            if duplicated || !in_batch.insert(hash) {
+                return false;
+            }
+            kept.push(hash);
+            true
+        });
+        kept
+    }
+
+    fn commit_at(&self, hashes: &[u64], now: i64) {
+        let mut seen = SEEN.lock();
+        for hash in hashes {
+            seen.insert(*hash, now);
+        }
+    }
+
+    fn hash(&self, record: &Map<String, Value>) -> u64 {
+        let mut hasher = cityhash::new_hasher();
+        hasher.write(self.stream_key.as_bytes());
+        hasher.write_u8(0);
+        if self.fields.is_empty() {
+            let record = record
+                .iter()
+                .filter(|(k, _)| !SERVER_COLUMNS.contains(&k.as_str()))
+                .collect::<BTreeMap<_, _>>();
+            hasher.write(&json::to_vec(&record).unwrap_or_default());
+            return hasher.finish();
+        }
+        for field in self.fields.iter() {
+            hasher.write(field.as_bytes());
+            hasher.write_u8(0);
+            // a missing field doesn't hash like a null one
+            match record.get(field) {
+                Some(value) => {
+                    hasher.write_u8(1);
+                    hasher.write(value.to_string().as_bytes());
+                }
+                None => hasher.write_u8(0),
+            }
+            hasher.write_u8(0);
+        }
+        hasher.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(values: &[Value]) -> Vec<(i64, Map<String, Value>)> {
+        values
+            .iter()
+            .map(|v| (0, v.as_object().unwrap().clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_dedup_whole_record() {
+        let dedup = IngestDedup {
+            window_secs: 10,
+            fields: vec![],
+        };
+        let d = Deduplicator::new("default", StreamType::Logs, "test_dedup_whole", &dedup).unwrap();
+        let batch = [
+            json::json!({"id": 1, "message": "a"}),
+            json::json!({"id": 2, "message": "b"}),
+            json::json!({"id": 1, "message": "a"}),
+        ];
+        let mut data = records(&batch);
+        let hashes = d.retain_at(&mut data, 1_000_000);
+        assert_eq!(data.len(), 2);
+        assert_eq!(hashes.len(), 2);
+
+        // the batch failed to be written, it is sent again
+        let mut data = records(&batch);
+        let hashes = d.retain_at(&mut data, 2_000_000);
+        assert_eq!(data.len(), 2);
+        d.commit_at(&hashes, 2_000_000);
+
+        // the batch was written, it is sent again
+        let mut data = records(&batch);
+        assert!(d.retain_at(&mut data, 5_000_000).is_empty());
+        assert!(data.is_empty());
+
+        // out of the window
+        let mut data = records(&batch[..1]);
+        d.retain_at(&mut data, 12_000_000);
+        assert_eq!(data.len(), 1);
+    }
+
+    #[test]
+    fn test_dedup_fields() {
+        let dedup = IngestDedup {
+            window_secs: 60,
+            fields: vec!["id".to_string()],
+        };
+        let d =
+            Deduplicator::new("default", StreamType::Logs, "test_dedup_fields", &dedup).unwrap();
+        let mut data = records(&[
+            json::json!({"id": 1, "message": "a"}),
+            json::json!({"id": 1, "message": "b"}),
+            json::json!({"id": null}),
+            json::json!({"message": "c"}),
+        ]);
+        let hashes = d.retain_at(&mut data, 1_000_000);
+        assert_eq!(data.len(), 3);
+        d.commit_at(&hashes, 1_000_000);
+
+        // the same record in another stream is not a duplicate
+        let other =
+            Deduplicator::new("default", StreamType::Logs, "test_dedup_other", &dedup).unwrap();
+        let mut data = records(&[json::json!({"id": 1})]);
+        other.retain_at(&mut data, 1_000_000);
+        assert_eq!(data.len(), 1);
+    }
+
+    #[test]
+    fn test_dedup_server_columns() {
+        let dedup = IngestDedup {
+            window_secs: 60,
+            fields: vec![],
+        };
+        let d =
+            Deduplicator::new("default", StreamType::Logs, "test_dedup_server", &dedup).unwrap();
+        let mut data = records(&[json::json!({
+            "_timestamp": 1_000_000,
+            "_id": "a",
+            "_original": "{\"message\":\"a\"}",
+            "message": "a"
+        })]);
+        let hashes = d.retain_at(&mut data, 1_000_000);
+        d.commit_at(&hashes, 1_000_000);
+
+        // sent again, the server set other values to its columns
+        let mut data = records(&[json::json!({
+            "_timestamp": 2_000_000,
+            "_id": "b",
+            "_original": "{\"message\": \"a\"}",
+            "message": "a"
+        })]);
+        d.retain_at(&mut data, 2_000_000);
+        assert!(data.is_empty());
+    }
+
+    #[test]
+    fn test_dedup_disabled() {
+        let dedup = IngestDedup::default();
+        assert!(Deduplicator::new("default", StreamType::Logs, "test", &dedup).is_none());
+    }
+}
@@ -0,0 +1,450 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Kafka consumer
+//!
+//! Consumes the configured topics and ingests their records into log streams
+//! through the same pipeline and UDS processing as HTTP ingestion. The offset
+//! of the next record of every partition is checkpointed in the meta store
+//! once the records before it were ingested, so records are ingested at least
+//! once. Records still failing after `ZO_KAFKA_MAX_RETRIES` attempts are moved
+//! to the dead letter stream, or skipped, so they don't block the partition.
+//! Only one ingester consumes at a time.
+
+use std::{sync::Arc, time::Duration};
+
+use config::{
+    TIMESTAMP_COL_NAME,
+    cluster::LOCAL_NODE,
+    get_config,
+    utils::{json, schema::format_stream_name},
+};
+use hashbrown::HashMap;
+use rskafka::{
+    client::{
+        Client, ClientBuilder, Credentials, SaslConfig,
+        error::{Error as KafkaError, ProtocolError},
+        partition::{OffsetAt, UnknownTopicHandling},
+    },
+    record::RecordAndOffset,
+};
+use tokio::task::JoinHandle;
+
+use crate::{
+    common::meta::ingestion::{IngestUser, IngestionRequest, IngestionValueType, SystemJobType},
+    service::{
+        db,
+        ingestion::{claim_owner, parse_source_streams, still_owner},
+    },
+};
+
+const OWNER_PREFIX: &str = "/kafka_ingestion";
+/// How often a standby node checks whether the consumer is still running
+const STANDBY_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// A configured `topic:org/stream`
+#[derive(Debug, Clone, PartialEq)]
+struct Subscription {
+    topic: String,
+    org_id: String,
+    stream_name: String,
+}
+
+impl Subscription {
+    /// Identifies the subscription, a topic can be consumed into several
+    /// streams with their own offsets
+    fn id(&self) -> String {
+        format!("{}/{}/{}", self.org_id, self.stream_name, self.topic)
+    }
+}
+
+fn parse_subscriptions(topics: &str) -> Result<Vec<Subscription>, anyhow::Error> {
+    Ok(
+        parse_source_streams(topics, "kafka topic", "topic:org/stream")?
+            .into_iter()
+            .map(|(topic, org_id, stream_name)| Subscription {
+                topic,
+                org_id,
+                stream_name,
+            })
+            .collect(),
+    )
+}
+
+/// Runs forever, consuming while this node owns the consumer
+pub async fn run() -> Result<(), anyhow::Error> {
+    let subscriptions = parse_subscriptions(&get_config().kafka_ingestion.topics)?;
+    loop {
+        match claim_owner(OWNER_PREFIX).await {
+            Ok(true) => {
+                log::info!("[KAFKA] consumer acquired by node {}", LOCAL_NODE.name);
+                if let Err(e) = consume(&subscriptions).await {
+                    log::error!("[KAFKA] consumer stopped: {e}");
+                }
+            }
+            Ok(false) => {}
+            Err(e) => log::error!("[KAFKA] failed to claim consumer: {e}"),
+        }
+        tokio::time::sleep(STANDBY_CHECK_INTERVAL).await;
+    }
+}
+
+async fn connect() -> Result<Client, anyhow::Error> {
+    let cfg = get_config();
+    let brokers = cfg
+        .kafka_ingestion
+        .brokers
+        .split(',')
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect::<Vec<_>>();
+    let mut builder = ClientBuilder::new(brokers);
+    if !cfg.kafka_ingestion.sasl_username.is_empty() {
+        builder = builder.sasl_config(SaslConfig::Plain(Credentials::new(
+            cfg.kafka_ingestion.sasl_username.clone(),
+            cfg.kafka_ingestion.sasl_password.clone(),
+        )));
+    }
+    if cfg.kafka_ingestion.tls_enabled {
+        builder = builder.tls_config(tls_config()?);
+    }
+    Ok(builder.build().await?)
+}
+
+/// Trusts the system root certificates, or the bundled ones when the system
+/// has none
+pub(crate) fn tls_config() -> Result<Arc<rustls::ClientConfig>, anyhow::Error> {
+    let mut cert_store = rustls::RootCertStore::empty();
+    let certs = rustls_native_certs::load_native_certs();
+    for cert in certs.certs {
+        cert_store.add(cert)?;
+    }
+    if cert_store.is_empty() {
+        cert_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    }
+    Ok(Arc::new(
+        rustls::ClientConfig::builder()
+            .with_root_certificates(cert_store)
+            .with_no_client_auth(),
+    ))
+}
+
+/// Consumes every partition of the subscribed topics until another node takes
+/// over the consumer. Partitions added to a topic are picked up with the next
+/// metadata refresh.
+async fn consume(subscriptions: &[Subscription]) -> Result<(), anyhow::Error> {
+    let client = Arc::new(connect().await?);
+    let refresh = Duration::from_secs(get_config().kafka_ingestion.metadata_refresh_interval);
+    let mut consumers: HashMap<(String, i32), JoinHandle<()>> = HashMap::new();
+    while still_owner(OWNER_PREFIX).await {
+        match client.list_topics().await {
+            Ok(topics) => {
+                for sub in subscriptions {
+                    let Some(topic) = topics.iter().find(|t| t.name == sub.topic) else {
+                        log::warn!("[KAFKA] topic {} does not exist", sub.topic);
+                        continue;
+                    };
+                    for partition in topic.partitions.keys() {
+                        let key = (sub.id(), *partition);
+                        if consumers.get(&key).is_some_and(|h| !h.is_finished()) {
+                            continue;
+                        }
+                        let handle = tokio::task::spawn(consume_partition(
+                            client.clone(),
+                            sub.clone(),
+                            *partition,
+                        ));
+                        consumers.insert(key, handle);
+                    }
+                }
+            }
+            Err(e) => log::error!("[KAFKA] failed to list topics: {e}"),
+        }
+        tokio::time::sleep(refresh).await;
+    }
+    for handle in consumers.values() {
+        handle.abort();
+    }
+    log::info!("[KAFKA] consumer taken over by another node");
+    Ok(())
+}
+
+async fn consume_partition(client: Arc<Client>, sub: Subscription, partition: i32) {
+    loop {
+        if let Err(e) = consume_partition_inner(&client, &sub, partition).await {
+            log::error!(
+                "[KAFKA] failed to consume partition {partition} of {}: {e}",
+                sub.topic
+            );
+        }
+        tokio::time::sleep(RETRY_DELAY).await;
+    }
+}
+
+async fn consume_partition_inner(
+    client: &Client,
+    sub: &Subscription,
+    partition: i32,
+) -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    let partition_client = client
+        .partition_client(sub.topic.as_str(), partition, UnknownTopicHandling::Retry)
+        .await?;
+    let mut offset = match db::kafka_ingestion::get_offset(&sub.id(), partition).await {
+        Some(v) => v,
+        None if cfg.kafka_ingestion.start_offset == "latest" => {
+            partition_client.get_offset(OffsetAt::Latest).await?
+        }
+        None => partition_client.get_offset(OffsetAt::Earliest).await?,
+    };
+
+    loop {
+        let records = match partition_client
+            .fetch_records(
+                offset,
+                1..cfg.kafka_ingestion.fetch_max_bytes,
+                cfg.kafka_ingestion.fetch_max_wait_ms,
+            )
+            .await
+        {
+            Ok((records, _high_watermark)) => records,
+            Err(KafkaError::ServerError {
+                protocol_error: ProtocolError::OffsetOutOfRange,
+                ..
+            }) => {
+                // the checkpoint fell behind the topic retention, or the topic
+                // was recreated with fewer records
+                let earliest = partition_client.get_offset(OffsetAt::Earliest).await?;
+                let next = if offset < earliest {
+                    earliest
+                } else {
+                    partition_client.get_offset(OffsetAt::Latest).await?
+                };
+                log::warn!(
+                    "[KAFKA] offset {offset} of partition {partition} of {} is out of range, continuing from {next}",
+                    sub.topic
+                );
+                offset = next;
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+        // compressed batches can start before the requested offset
+        let records = records
+            .into_iter()
+            .filter(|r| r.offset >= offset)
+            .collect::<Vec<_>>();
+        let Some(next_offset) = records.iter().map(|r| r.offset + 1).max() else {
+            continue;
+        };
+        let mut attempts = 0;
+        while let Err(e) = ingest_records(sub, &sub.stream_name, partition, &records, None).await {
+            attempts += 1;
+            if attempts > cfg.kafka_ingestion.max_retries {
+                dead_letter(sub, partition, &records, &e.to_string()).await;
+                break;
+            }
+            log::warn!("[KAFKA] attempt {attempts} failed: {e}");
+            tokio::time::sleep(RETRY_DELAY).await;
+        }
+        db::kafka_ingestion::set_offset(&sub.id(), partition, next_offset).await?;
+        offset = next_offset;
+    }
+}
+
+/// Moves the records which failed to ingest to the dead letter stream with
+/// the error, they are skipped when there is none or it fails too
+async fn dead_letter(sub: &Subscription, partition: i32, records: &[RecordAndOffset], error: &str) {
+    let (first, last) = (
+        records.first().map(|r| r.offset).unwrap_or_default(),
+        records.last().map(|r| r.offset).unwrap_or_default(),
+    );
+    let stream_name = get_config().kafka_ingestion.dead_letter_stream.clone();
+    if stream_name.is_empty() {
+        log::error!(
+            "[KAFKA] skipping offsets {first}..={last} of partition {partition} of {}: {error}",
+            sub.topic
+        );
+        return;
+    }
+    let stream_name = format_stream_name(stream_name);
+    match ingest_records(sub, &stream_name, partition, records, Some(error)).await {
+        Ok(()) => log::error!(
+            "[KAFKA] moved offsets {first}..={last} of partition {partition} of {} to {}/{stream_name}: {error}",
+            sub.topic,
+            sub.org_id
+        ),
+        Err(e) => log::error!(
+            "[KAFKA] skipping offsets {first}..={last} of partition {partition} of {}, failed to move them to the dead letter stream: {e}",
+            sub.topic
+        ),
+    }
+}
+
+/// Ingests the records into `stream_name` of the org of the subscription,
+/// with the `error` which made them fail for the dead letter stream
+async fn ingest_records(
+    sub: &Subscription,
+    stream_name: &str,
+    partition: i32,
+    records: &[RecordAndOffset],
+    error: Option<&str>,
+) -> Result<(), anyhow::Error> {
+    let batch_size = get_config().kafka_ingestion.batch_size;
+    let mut batches: Vec<Vec<json::Value>> = vec![];
+    for record in records {
+        let Some(mut record) = to_record(&sub.topic, partition, record) else {
+            continue;
+        };
+        if let Some(error) = error {
+            record["kafka_error"] = error.into();
+        }
+        match batches.last_mut() {
+            Some(batch) if batch.len() < batch_size => batch.push(record),
+            _ => batches.push(vec![record]),
+        }
+    }
+
+    for batch in batches {
+        let resp = crate::service::logs::ingest::ingest(
+            0,
+            &sub.org_id,
+            stream_name,
+            IngestionRequest::JsonValues(IngestionValueType::Kafka, batch),
+            IngestUser::SystemJob(SystemJobType::KafkaConsumer),
+            None,
+            false,
+        )
+        .await?;
+        if resp.code != 200 {
+            return Err(anyhow::anyhow!(
+                "failed to ingest partition {partition} of {} into {}/{stream_name}: {}",
+                sub.topic,
+                sub.org_id,
+                resp.error.unwrap_or_default()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// JSON object values are ingested as they are, other values become the
+/// message of the record. Records without a time get the time of the Kafka
+/// record, tombstones are skipped.
+fn to_record(topic: &str, partition: i32, record: &RecordAndOffset) -> Option<json::Value> {
+    let value = record.record.value.as_ref()?;
+    let mut fields = match json::from_slice::<json::Value>(value) {
+        Ok(json::Value::Object(fields)) => fields,
+        _ => {
+            let mut fields = json::Map::new();
+            fields.insert(
+                "message".to_string(),
+                String::from_utf8_lossy(value).into_owned().into(),
+            );
+            fields
+        }
+    };
+    if !fields.contains_key(TIMESTAMP_COL_NAME) {
+        fields.insert(
+            TIMESTAMP_COL_NAME.to_string(),
+            record.record.timestamp.timestamp_micros().into(),
+        );
+    }
+    fields.insert("kafka_topic".to_string(), topic.into());
+    fields.insert("kafka_partition".to_string(), partition.into());
+    fields.insert("kafka_offset".to_string(), record.offset.into());
+    if let Some(key) = record.record.key.as_ref() {
+        fields.insert(
+            "kafka_key".to_string(),
+            String::from_utf8_lossy(key).into_owned().into(),
+        );
+    }
+    Some(json::Value::Object(fields))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::{TimeZone, Utc};
+    use rskafka::record::Record;
+
+    use super::*;
+
+    fn kafka_record(key: Option<&str>, value: Option<&str>, offset: i64) -> RecordAndOffset {
+        RecordAndOffset {
+            record: Record {
+                key: key.map(|v| v.as_bytes().to_vec()),
+                value: value.map(|v| v.as_bytes().to_vec()),
+                headers: BTreeMap::new(),
+                timestamp: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+            },
+            offset,
+        }
+    }
+
+    #[test]
+    fn test_parse_subscriptions() {
+        let subs =
+            parse_subscriptions("app-logs:default/App Logs, audit.events:security/audit").unwrap();
+        assert_eq!(subs.len(), 2);
+        assert_eq!(subs[0].topic, "app-logs");
+        assert_eq!(subs[0].org_id, "default");
+        assert_eq!(subs[0].stream_name, "app_logs");
+        assert_eq!(subs[1].topic, "audit.events");
+        assert_eq!(subs[1].org_id, "security");
+
+        assert!(parse_subscriptions("app-logs").is_err());
+        assert!(parse_subscriptions("app-logs:default").is_err());
+        assert!(parse_subscriptions(":default/app").is_err());
+        assert!(parse_subscriptions("").unwrap().is_empty());
+
+        // a topic consumed into two streams has an offset for each
+        let subs = parse_subscriptions("app-logs:default/app, app-logs:default/archive").unwrap();
+        assert_ne!(subs[0].id(), subs[1].id());
+    }
+
+    #[test]
+    fn test_to_record() {
+        let record = to_record(
+            "app-logs",
+            3,
+            &kafka_record(Some("host-1"), Some(r#"{"level":"info","msg":"ok"}"#), 42),
+        )
+        .unwrap();
+        assert_eq!(record["level"], "info");
+        assert_eq!(record["kafka_topic"], "app-logs");
+        assert_eq!(record["kafka_partition"], 3);
+        assert_eq!(record["kafka_offset"], 42);
+        assert_eq!(record["kafka_key"], "host-1");
+        assert_eq!(record[TIMESTAMP_COL_NAME], 1_700_000_000_000_000i64);
+
+        // the time of the record wins over the time of the kafka record
+        let record = to_record(
+            "app-logs",
+            0,
+            &kafka_record(None, Some(r#"{"_timestamp":1}"#), 0),
+        )
+        .unwrap();
+        assert_eq!(record[TIMESTAMP_COL_NAME], 1);
+        assert!(record.get("kafka_key").is_none());
+
+        let record = to_record("app-logs", 0, &kafka_record(None, Some("plain text"), 0)).unwrap();
+        assert_eq!(record["message"], "plain text");
+
+        assert!(to_record("app-logs", 0, &kafka_record(Some("k"), None, 0)).is_none());
+    }
+}
@@ -0,0 +1,589 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Kinesis Data Streams consumer
+//!
+//! Consumes the shards of the configured Kinesis streams into log streams,
+//! the way the KCL does. Every ingester takes leases on shards in the meta
+//! store, up to its share of the shards, and renews them while it consumes
+//! the shards. The leases a node stops renewing expire and are taken over by
+//! the other nodes. The sequence number of the last record of a shard is
+//! checkpointed in its lease once the records are in the WAL, so records are
+//! ingested at least once.
+//!
+//! After resharding, the closed shards are consumed to their end before their
+//! children, so the records of a partition key are ingested in order.
+
+use std::{sync::Arc, time::Duration};
+
+use aws_sdk_kinesis::{
+    Client,
+    types::{Record, ShardIteratorType},
+};
+use config::{
+    TIMESTAMP_COL_NAME,
+    cluster::LOCAL_NODE,
+    get_config,
+    utils::{json, time::now_micros},
+};
+use hashbrown::{HashMap, HashSet};
+use infra::{cluster::get_cached_online_ingester_nodes, dist_lock};
+use ingester::WalCommit;
+use tokio::task::JoinHandle;
+
+use crate::{
+    common::meta::ingestion::{IngestUser, IngestionRequest, IngestionValueType, SystemJobType},
+    service::{
+        db::{self, kinesis_ingestion::ShardLease},
+        ingestion::parse_source_streams,
+    },
+};
+
+const LOCK_KEY: &str = "/kinesis_ingestion/lock";
+/// How long the records of a shard may take to reach the WAL before they are
+/// read again
+const WAL_COMMIT_TIMEOUT: Duration = Duration::from_secs(60);
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// A configured `kinesis_stream:org/stream`
+#[derive(Debug, Clone, PartialEq)]
+struct Subscription {
+    kinesis_stream: String,
+    org_id: String,
+    stream_name: String,
+}
+
+/// A shard of a Kinesis stream and the shards it was split or merged from
+#[derive(Debug, Clone)]
+struct ShardInfo {
+    shard_id: String,
+    parents: Vec<String>,
+}
+
+fn parse_subscriptions(streams: &str) -> Result<Vec<Subscription>, anyhow::Error> {
+    Ok(
+        parse_source_streams(streams, "kinesis stream", "kinesis_stream:org/stream")?
+            .into_iter()
+            .map(|(kinesis_stream, org_id, stream_name)| Subscription {
+                kinesis_stream,
+                org_id,
+                stream_name,
+            })
+            .collect(),
+    )
+}
+
+/// Runs forever, taking the leases of the shards this node can consume
+pub async fn run() -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    let subscriptions = parse_subscriptions(&cfg.kinesis_ingestion.streams)?;
+    let client = Arc::new(connect().await);
+    let sync_interval = Duration::from_secs(cfg.kinesis_ingestion.shard_sync_interval);
+    let mut consumers: HashMap<(String, String), JoinHandle<()>> = HashMap::new();
+    loop {
+        consumers.retain(|_, h| !h.is_finished());
+        for sub in &subscriptions {
+            let running = consumers
+                .keys()
+                .filter(|(stream, _)| *stream == sub.kinesis_stream)
+                .map(|(_, shard_id)| shard_id.clone())
+                .collect::<HashSet<_>>();
+            let shard_ids = match take_leases(&client, sub, &running).await {
+                Ok(v) => v,
+                Err(e) => {
+                    log::error!(
+                        "[KINESIS] failed to sync the shards of {}: {e}",
+                        sub.kinesis_stream
+                    );
+                    continue;
+                }
+            };
+            for shard_id in shard_ids {
+                log::info!(
+                    "[KINESIS] shard {shard_id} of {} acquired by node {}",
+                    sub.kinesis_stream,
+                    LOCAL_NODE.name
+                );
+                let handle = tokio::task::spawn(consume_shard(
+                    client.clone(),
+                    sub.clone(),
+                    shard_id.clone(),
+                ));
+                consumers.insert((sub.kinesis_stream.clone(), shard_id), handle);
+            }
+        }
+        tokio::time::sleep(sync_interval).await;
+    }
+}
+
+async fn connect() -> Client {
+    let cfg = get_config();
+    let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest());
+    if !cfg.kinesis_ingestion.region.is_empty() {
+        loader = loader.region(aws_config::Region::new(
+            cfg.kinesis_ingestion.region.clone(),
+        ));
+    }
+    if !cfg.kinesis_ingestion.endpoint.is_empty() {
+        loader = loader.endpoint_url(&cfg.kinesis_ingestion.endpoint);
+    }
+    Client::new(&loader.load().await)
+}
+
+async fn list_shards(
+    client: &Client,
+    kinesis_stream: &str,
+) -> Result<Vec<ShardInfo>, anyhow::Error> {
+    let mut shards = vec![];
+    let mut next_token: Option<String> = None;
+    loop {
+        // the stream name can't be given with a pagination token
+        let req = match &next_token {
+            Some(token) => client.list_shards().next_token(token),
+            None => client.list_shards().stream_name(kinesis_stream),
+        };
+        let resp = req.send().await?;
+        for shard in resp.shards() {
+            shards.push(ShardInfo {
+                shard_id: shard.shard_id().to_string(),
+                parents: shard
+                    .parent_shard_id()
+                    .into_iter()
+                    .chain(shard.adjacent_parent_shard_id())
+                    .map(|v| v.to_string())
+                    .collect(),
+            });
+        }
+        match resp.next_token() {
+            Some(token) => next_token = Some(token.to_string()),
+            None => return Ok(shards),
+        }
+    }
+}
+
+/// Takes the leases of the shards this node should start consuming, and
+/// returns their ids
+async fn take_leases(
+    client: &Client,
+    sub: &Subscription,
+    running: &HashSet<String>,
+) -> Result<Vec<String>, anyhow::Error> {
+    let cfg = get_config();
+    let shards = list_shards(client, &sub.kinesis_stream).await?;
+    let ingesters = get_cached_online_ingester_nodes()
+        .await
+        .map(|nodes| nodes.len())
+        .unwrap_or(1)
+        .max(1);
+
+    let locker = dist_lock::lock(LOCK_KEY, 0).await?;
+    let ret: Result<Vec<String>, anyhow::Error> = async {
+        let leases = db::kinesis_ingestion::list_leases(&sub.kinesis_stream).await?;
+        // the shards trimmed from the stream don't need their lease anymore
+        let shard_ids = shards
+            .iter()
+            .map(|s| s.shard_id.as_str())
+            .collect::<HashSet<_>>();
+        for (shard_id, lease) in leases.iter() {
+            if lease.finished && !shard_ids.contains(shard_id.as_str()) {
+                db::kinesis_ingestion::delete_lease(&sub.kinesis_stream, shard_id).await?;
+            }
+        }
+
+        let now = now_micros();
+        let claimable =
+            claimable_shards(&shards, &leases, running, &LOCAL_NODE.uuid, ingesters, now);
+        for shard_id in claimable.iter() {
+            let mut lease = leases.get(shard_id).cloned().unwrap_or_default();
+            lease.owner = LOCAL_NODE.uuid.clone();
+            lease.expires_at = now + cfg.kinesis_ingestion.lease_duration * 1_000_000;
+            db::kinesis_ingestion::set_lease(&sub.kinesis_stream, shard_id, &lease).await?;
+        }
+        Ok(claimable)
+    }
+    .await;
+    dist_lock::unlock(&locker).await?;
+    ret
+}
+
+/// The shards this node can take: the ones without a lease, with an expired
+/// lease or with a lease of this node it doesn't consume anymore, up to its
+/// share of the open shards. A shard is consumed once its parents were
+/// consumed to their end, the parents trimmed from the stream are done.
+fn claimable_shards(
+    shards: &[ShardInfo],
+    leases: &HashMap<String, ShardLease>,
+    running: &HashSet<String>,
+    node: &str,
+    nodes: usize,
+    now: i64,
+) -> Vec<String> {
+    let listed = shards
+        .iter()
+        .map(|s| s.shard_id.as_str())
+        .collect::<HashSet<_>>();
+    let is_finished = |shard_id: &str| leases.get(shard_id).is_some_and(|l| l.finished);
+    let open = shards.iter().filter(|s| !is_finished(&s.shard_id)).count();
+    let owned = leases
+        .iter()
+        .filter(|(_, l)| !l.finished && l.owner == node && l.expires_at > now)
+        .count()
+        .max(running.len());
+    let share = open.div_ceil(nodes.max(1));
+
+    let mut claimable = vec![];
+    for shard in shards {
+        if owned + claimable.len() >= share {
+            break;
+        }
+        if running.contains(&shard.shard_id) || is_finished(&shard.shard_id) {
+            continue;
+        }
+        let parents_done = shard
+            .parents
+            .iter()
+            .all(|p| !listed.contains(p.as_str()) || is_finished(p));
+        if !parents_done {
+            continue;
+        }
+        let available = match leases.get(&shard.shard_id) {
+            None => true,
+            Some(lease) => lease.owner == node || lease.expires_at <= now,
+        };
+        if available {
+            claimable.push(shard.shard_id.clone());
+        }
+    }
+    claimable
+}
+
+async fn consume_shard(client: Arc<Client>, sub: Subscription, shard_id: String) {
+    if let Err(e) = consume_shard_inner(&client, &sub, &shard_id).await {
+        log::error!(
+            "[KINESIS] failed to consume shard {shard_id} of {}: {e}",
+            sub.kinesis_stream
+        );
+        // the lease is kept, the shard is consumed again with the next sync
+        tokio::time::sleep(RETRY_DELAY).await;
+    }
+}
+
+async fn consume_shard_inner(
+    client: &Client,
+    sub: &Subscription,
+    shard_id: &str,
+) -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    let Some(mut lease) = db::kinesis_ingestion::get_lease(&sub.kinesis_stream, shard_id).await
+    else {
+        return Ok(());
+    };
+    let mut iterator = shard_iterator(client, sub, shard_id, lease.checkpoint.as_deref()).await?;
+    let poll_interval = Duration::from_millis(cfg.kinesis_ingestion.poll_interval_ms);
+
+    loop {
+        let Some(it) = iterator else {
+            // the shard was closed by resharding and is consumed to its end,
+            // its children can be consumed
+            lease.finished = true;
+            db::kinesis_ingestion::set_lease(&sub.kinesis_stream, shard_id, &lease).await?;
+            log::info!(
+                "[KINESIS] shard {shard_id} of {} consumed to its end",
+                sub.kinesis_stream
+            );
+            return Ok(());
+        };
+        let resp = match client
+            .get_records()
+            .shard_iterator(it)
+            .limit(cfg.kinesis_ingestion.max_records)
+            .send()
+            .await
+        {
+            Ok(resp) => resp,
+            Err(e) => {
+                let expired = e
+                    .as_service_error()
+                    .is_some_and(|e| e.is_expired_iterator_exception());
+                let throttled = e
+                    .as_service_error()
+                    .is_some_and(|e| e.is_provisioned_throughput_exceeded_exception());
+                if expired {
+                    iterator =
+                        shard_iterator(client, sub, shard_id, lease.checkpoint.as_deref()).await?;
+                    continue;
+                }
+                if throttled {
+                    tokio::time::sleep(poll_interval).await;
+                    continue;
+                }
+                return Err(e.into());
+            }
+        };
+        iterator = resp.next_shard_iterator().map(|v| v.to_string());
+        let records = resp.records();
+        if let Some(last) = records.last() {
+            ingest_records(sub, shard_id, records).await?;
+            lease.checkpoint = Some(last.sequence_number().to_string());
+        }
+        if !renew_lease(sub, shard_id, &mut lease).await? {
+            log::info!(
+                "[KINESIS] shard {shard_id} of {} taken over by another node",
+                sub.kinesis_stream
+            );
+            return Ok(());
+        }
+        if records.is_empty() || resp.millis_behind_latest() == Some(0) {
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+}
+
+/// Returns the iterator after the checkpoint, or at the configured start
+/// position for a shard never consumed
+async fn shard_iterator(
+    client: &Client,
+    sub: &Subscription,
+    shard_id: &str,
+    checkpoint: Option<&str>,
+) -> Result<Option<String>, anyhow::Error> {
+    let req = client
+        .get_shard_iterator()
+        .stream_name(&sub.kinesis_stream)
+        .shard_id(shard_id);
+    let req = match checkpoint {
+        Some(seq) => req
+            .shard_iterator_type(ShardIteratorType::AfterSequenceNumber)
+            .starting_sequence_number(seq),
+        None if get_config().kinesis_ingestion.start_position == "latest" => {
+            req.shard_iterator_type(ShardIteratorType::Latest)
+        }
+        None => req.shard_iterator_type(ShardIteratorType::TrimHorizon),
+    };
+    let resp = req.send().await?;
+    Ok(resp.shard_iterator().map(|v| v.to_string()))
+}
+
+/// Extends the lease, returns false when another node has taken it over
+async fn renew_lease(
+    sub: &Subscription,
+    shard_id: &str,
+    lease: &mut ShardLease,
+) -> Result<bool, anyhow::Error> {
+    match db::kinesis_ingestion::get_lease(&sub.kinesis_stream, shard_id).await {
+        Some(current) if current.owner == LOCAL_NODE.uuid => {}
+        _ => return Ok(false),
+    }
+    lease.owner = LOCAL_NODE.uuid.clone();
+    lease.expires_at = now_micros() + get_config().kinesis_ingestion.lease_duration * 1_000_000;
+    db::kinesis_ingestion::set_lease(&sub.kinesis_stream, shard_id, lease).await?;
+    Ok(true)
+}
+
+/// Ingests the records and waits for them to be in the WAL, so the checkpoint
+/// never gets ahead of the records
+async fn ingest_records(
+    sub: &Subscription,
+    shard_id: &str,
+    records: &[Record],
+) -> Result<(), anyhow::Error> {
+    let records = records
+        .iter()
+        .map(|r| {
+            to_record(
+                &sub.kinesis_stream,
+                shard_id,
+                r.data().as_ref(),
+                r.partition_key(),
+                r.sequence_number(),
+                r.approximate_arrival_timestamp()
+                    .and_then(|t| t.to_millis().ok())
+                    .map(|t| t * 1000),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut commit = WalCommit::begin();
+    let resp = crate::service::logs::ingest::ingest(
+        0,
+        &sub.org_id,
+        &sub.stream_name,
+        IngestionRequest::JsonValues(IngestionValueType::Kinesis, records),
+        IngestUser::SystemJob(SystemJobType::KinesisConsumer),
+        None,
+        false,
+    )
+    .await?;
+    if resp.code != 200 {
+        return Err(anyhow::anyhow!(
+            "failed to ingest shard {shard_id} of {} into {}/{}: {}",
+            sub.kinesis_stream,
+            sub.org_id,
+            sub.stream_name,
+            resp.error.unwrap_or_default()
+        ));
+    }
+    commit.end().await;
+
+    let started = std::time::Instant::now();
+    while !commit.is_committed() {
+        if started.elapsed() > WAL_COMMIT_TIMEOUT {
+            return Err(anyhow::anyhow!(
+                "records of shard {shard_id} of {} were not written to the WAL",
+                sub.kinesis_stream
+            ));
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    Ok(())
+}
+
+/// JSON object records are ingested as they are, other records become the
+/// message of the record. Records without a time get the time they arrived
+/// in Kinesis.
+fn to_record(
+    kinesis_stream: &str,
+    shard_id: &str,
+    data: &[u8],
+    partition_key: &str,
+    sequence_number: &str,
+    arrived_at: Option<i64>,
+) -> json::Value {
+    let mut fields = match json::from_slice::<json::Value>(data) {
+        Ok(json::Value::Object(fields)) => fields,
+        _ => {
+            let mut fields = json::Map::new();
+            fields.insert(
+                "message".to_string(),
+                String::from_utf8_lossy(data).into_owned().into(),
+            );
+            fields
+        }
+    };
+    if !fields.contains_key(TIMESTAMP_COL_NAME) {
+        fields.insert(
+            TIMESTAMP_COL_NAME.to_string(),
+            arrived_at.unwrap_or_else(now_micros).into(),
+        );
+    }
+    fields.insert("kinesis_stream".to_string(), kinesis_stream.into());
+    fields.insert("kinesis_shard_id".to_string(), shard_id.into());
+    fields.insert("kinesis_partition_key".to_string(), partition_key.into());
+    fields.insert(
+        "kinesis_sequence_number".to_string(),
+        sequence_number.into(),
+    );
+    json::Value::Object(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shard(shard_id: &str, parents: &[&str]) -> ShardInfo {
+        ShardInfo {
+            shard_id: shard_id.to_string(),
+            parents: parents.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+
+    fn lease(owner: &str, expires_at: i64, finished: bool) -> ShardLease {
+        ShardLease {
+            owner: owner.to_string(),
+            expires_at,
+            checkpoint: None,
+            finished,
+        }
+    }
+
+    #[test]
+    fn test_parse_subscriptions() {
+        let subs =
+            parse_subscriptions("app-events:default/App Events, audit:security/audit").unwrap();
+        assert_eq!(subs.len(), 2);
+        assert_eq!(subs[0].kinesis_stream, "app-events");
+        assert_eq!(subs[0].org_id, "default");
+        assert_eq!(subs[0].stream_name, "app_events");
+        assert_eq!(subs[1].kinesis_stream, "audit");
+
+        assert!(parse_subscriptions("app-events").is_err());
+        assert!(parse_subscriptions("app-events:default").is_err());
+        assert!(parse_subscriptions("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_claimable_shards() {
+        let now = 1_000;
+        let shards = vec![shard("s0", &[]), shard("s1", &[]), shard("s2", &[])];
+        let mut leases = HashMap::new();
+        leases.insert("s0".to_string(), lease("other", now + 10, false));
+        leases.insert("s1".to_string(), lease("other", now - 10, false));
+
+        // the live lease of the other node is kept, the expired one is taken
+        let claimable = claimable_shards(&shards, &leases, &HashSet::new(), "me", 1, now);
+        assert_eq!(claimable, vec!["s1", "s2"]);
+
+        // with two nodes, a node takes at most two of the three shards
+        let running = HashSet::from(["s2".to_string()]);
+        let claimable = claimable_shards(&shards, &leases, &running, "me", 2, now);
+        assert_eq!(claimable, vec!["s1"]);
+        let running = HashSet::from(["s1".to_string(), "s2".to_string()]);
+        assert!(claimable_shards(&shards, &leases, &running, "me", 2, now).is_empty());
+    }
+
+    #[test]
+    fn test_claimable_shards_after_resharding() {
+        let now = 1_000;
+        // s0 was split into s1 and s2, s3 is the child of a trimmed shard
+        let shards = vec![
+            shard("s0", &[]),
+            shard("s1", &["s0"]),
+            shard("s2", &["s0"]),
+            shard("s3", &["trimmed"]),
+        ];
+        let mut leases = HashMap::new();
+        leases.insert("s0".to_string(), lease("other", now + 10, false));
+        let claimable = claimable_shards(&shards, &leases, &HashSet::new(), "me", 1, now);
+        assert_eq!(claimable, vec!["s3"]);
+
+        leases.insert("s0".to_string(), lease("other", now + 10, true));
+        let claimable = claimable_shards(&shards, &leases, &HashSet::new(), "me", 1, now);
+        assert_eq!(claimable, vec!["s1", "s2", "s3"]);
+    }
+
+    #[test]
+    fn test_to_record() {
+        let record = to_record(
+            "app-events",
+            "shardId-000000000001",
+            br#"{"level":"info","msg":"ok"}"#,
+            "host-1",
+            "49590338271490256608559692538361571095921575989136588898",
+            Some(1_700_000_000_000_000),
+        );
+        assert_eq!(record["level"], "info");
+        assert_eq!(record["kinesis_stream"], "app-events");
+        assert_eq!(record["kinesis_shard_id"], "shardId-000000000001");
+        assert_eq!(record["kinesis_partition_key"], "host-1");
+        assert_eq!(record[TIMESTAMP_COL_NAME], 1_700_000_000_000_000i64);
+
+        // the time of the record wins over the arrival time
+        let record = to_record("s", "shard", br#"{"_timestamp":1}"#, "k", "1", Some(5));
+        assert_eq!(record[TIMESTAMP_COL_NAME], 1);
+
+        let record = to_record("s", "shard", b"plain text", "k", "1", Some(5));
+        assert_eq!(record["message"], "plain text");
+    }
+}
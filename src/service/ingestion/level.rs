@@ -0,0 +1,168 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Normalizes the level of the records of the streams which enable it, the
+//! values producers use for the same level (`WARN`, `warning`, `40`, `sev=4`)
+//! are written to `level` as one canonical value, through the level mapping of
+//! the organization. The organizations which never edited their mapping use
+//! the built-in one.
+
+use std::sync::Arc;
+
+use config::{
+    meta::stream::{DEFAULT_LEVEL_SOURCE_FIELDS, LEVEL_FIELD, LevelMapping, LevelNormalization},
+    utils::{
+        json::{Map, Value},
+        time::now_micros,
+    },
+};
+use once_cell::sync::Lazy;
+
+use crate::{common::infra::config::LEVEL_MAPPINGS, service::db};
+
+static DEFAULT_LEVEL_MAPPING: Lazy<Arc<LevelMapping>> =
+    Lazy::new(|| Arc::new(LevelMapping::default()));
+
+pub fn get_mapping(org_id: &str) -> Arc<LevelMapping> {
+    LEVEL_MAPPINGS
+        .get(org_id)
+        .map(|v| v.value().clone())
+        .unwrap_or_else(|| DEFAULT_LEVEL_MAPPING.clone())
+}
+
+/// Replaces the level mapping of the organization
+pub async fn set_mapping(
+    org_id: &str,
+    mut mapping: LevelMapping,
+    user_email: &str,
+) -> Result<LevelMapping, anyhow::Error> {
+    mapping.validate().map_err(|e| anyhow::anyhow!(e))?;
+    mapping.updated_by = Some(user_email.to_string());
+    mapping.updated_at = now_micros();
+    db::level_mapping::set(org_id, &mapping).await?;
+    // requests right after on this node must see it before the watch does
+    LEVEL_MAPPINGS.insert(org_id.to_string(), Arc::new(mapping.clone()));
+    Ok(mapping)
+}
+
+/// Goes back to the built-in level mapping
+pub async fn reset_mapping(org_id: &str) -> Result<(), anyhow::Error> {
+    db::level_mapping::delete(org_id).await?;
+    LEVEL_MAPPINGS.remove(org_id);
+    Ok(())
+}
+
+pub struct LevelNormalizer {
+    source_fields: Vec<String>,
+    mapping: Arc<LevelMapping>,
+}
+
+impl LevelNormalizer {
+    /// Returns None when the stream doesn't normalize its levels.
+    pub fn new(org_id: &str, normalization: &LevelNormalization) -> Option<Self> {
+        if !normalization.enabled {
+            return None;
+        }
+        let source_fields = if normalization.source_fields.is_empty() {
+            DEFAULT_LEVEL_SOURCE_FIELDS
+                .iter()
+                .map(|f| f.to_string())
+                .collect()
+        } else {
+            normalization.source_fields.clone()
+        };
+        Some(Self {
+            source_fields,
+            mapping: get_mapping(org_id),
+        })
+    }
+
+    /// Sets `level` to the canonical level of the first source field the
+    /// record has, the record is left as is when the value isn't mapped
+    pub fn normalize(&self, record: &mut Map<String, Value>) {
+        let Some(value) = self
+            .source_fields
+            .iter()
+            .find_map(|f| record.get(f).filter(|v| !v.is_null()))
+        else {
+            return;
+        };
+        let level = match value {
+            Value::String(v) => self.mapping.get(v),
+            Value::Number(v) => self.mapping.get(&v.to_string()),
+            _ => None,
+        };
+        if let Some(level) = level {
+            record.insert(LEVEL_FIELD.to_string(), Value::String(level.to_string()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use config::utils::json;
+
+    use super::*;
+
+    #[test]
+    fn test_level_normalizer() {
+        let org_id = "test_level_normalizer";
+        assert!(LevelNormalizer::new(org_id, &LevelNormalization::default()).is_none());
+
+        let normalizer = LevelNormalizer::new(
+            org_id,
+            &LevelNormalization {
+                enabled: true,
+                source_fields: vec![],
+            },
+        )
+        .unwrap();
+        for (record, level) in [
+            (json::json!({"level": "WARNING"}), Some("warn")),
+            (json::json!({"severity": 50}), Some("error")),
+            (json::json!({"level": null, "sev": "sev=4"}), Some("warn")),
+            (json::json!({"level": "chatty"}), Some("chatty")),
+            (json::json!({"message": "no level"}), None),
+        ] {
+            let mut record = record.as_object().unwrap().clone();
+            normalizer.normalize(&mut record);
+            assert_eq!(record.get(LEVEL_FIELD).and_then(|v| v.as_str()), level);
+        }
+
+        LEVEL_MAPPINGS.insert(
+            org_id.to_string(),
+            Arc::new(LevelMapping {
+                mappings: [("chatty".to_string(), "debug".to_string())].into(),
+                updated_by: None,
+                updated_at: 0,
+            }),
+        );
+        let normalizer = LevelNormalizer::new(
+            org_id,
+            &LevelNormalization {
+                enabled: true,
+                source_fields: vec!["lvl".to_string()],
+            },
+        )
+        .unwrap();
+        let mut record = json::json!({"lvl": "Chatty", "level": "x"})
+            .as_object()
+            .unwrap()
+            .clone();
+        normalizer.normalize(&mut record);
+        assert_eq!(record[LEVEL_FIELD], "debug");
+        LEVEL_MAPPINGS.remove(org_id);
+    }
+}
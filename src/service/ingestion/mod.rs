@@ -32,13 +32,20 @@ use config::{
         function::{VRLResultResolver, VRLRuntimeConfig},
         self_reporting::usage::{RequestStats, TriggerData, TriggerDataStatus, TriggerDataType},
         stream::{
-            PartitionTimeLevel, PartitioningDetails, StreamParams, StreamPartition, StreamType,
+            IngestPriority, PartitionTimeLevel, PartitioningDetails, StreamParams, StreamPartition,
+            StreamType, WalSyncPolicy,
         },
     },
     metrics,
-    utils::{flatten, json::*, schema::format_partition_key},
+    utils::{
+        flatten,
+        json::*,
+        schema::{format_partition_key, format_stream_name},
+    },
 };
 use infra::{
+    cluster::get_node_by_uuid,
+    dist_lock,
     errors::{Error, Result},
     schema::STREAM_RECORD_ID_GENERATOR,
 };
@@ -71,8 +78,18 @@ use crate::{
     },
 };
 
+pub mod clock_skew;
+pub mod dedup;
 pub mod grpc;
 pub mod ingestion_service;
+pub mod kafka;
+pub mod kinesis;
+pub mod level;
+pub mod mqtt;
+pub mod quota;
+pub mod rabbitmq;
+pub mod redaction;
+pub mod redis_streams;
 
 pub type TriggerAlertData = Vec<(Alert, Vec<Map<String, Value>>)>;
 
@@ -412,7 +429,7 @@ pub async fn write_file(
     org_id: &str,
     stream_name: &str,
     buf: HashMap<String, SchemaRecords>,
-    fsync: bool,
+    sync_policy: WalSyncPolicy,
 ) -> Result<RequestStats> {
     let mut req_stats = RequestStats::default();
     let entries = buf
@@ -421,6 +438,13 @@ pub async fn write_file(
             if entry.records.is_empty() {
                 None
             } else {
+                super::tail::publish(
+                    org_id,
+                    writer.get_stream_type(),
+                    stream_name,
+                    &entry.schema,
+                    &entry.records,
+                );
                 Some(ingester::Entry {
                     org_id: Arc::from(org_id),
                     stream: Arc::from(stream_name),
@@ -439,7 +463,7 @@ pub async fn write_file(
         .fold((0, 0), |(acc_records, acc_size), (records, size)| {
             (acc_records + records, acc_size + size)
         });
-    if let Err(e) = writer.write_batch(entries, fsync).await {
+    if let Err(e) = writer.write_batch(entries, sync_policy).await {
         log::error!(
             "ingestion write file for stream {}/{} error: {}",
             writer.get_key_str(),
@@ -472,6 +496,9 @@ pub async fn check_ingestion_allowed(
         )));
     }
 
+    // suspended and archived orgs take no new data
+    super::org_lifecycle::check_ingestion(org_id).map_err(Error::IngestionError)?;
+
     // check if we are allowed to ingest
     if let Some(stream_name) = stream_name
         && db::compact::retention::is_deleting_stream(org_id, stream_type, stream_name, None)
@@ -488,6 +515,35 @@ pub async fn check_ingestion_allowed(
         }
     }
 
+    // check the ingestion quotas of the org and the stream
+    quota::check(org_id, stream_type, stream_name).await?;
+
+    // the stream priority decides how it is treated under backpressure
+    let priority = get_ingest_priority(org_id, stream_type, stream_name).await;
+    check_ingestion_resources(priority)
+}
+
+/// Ingestion priority of a stream, requests without a stream are normal
+pub async fn get_ingest_priority(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: Option<&str>,
+) -> IngestPriority {
+    match stream_name {
+        // settings are only looked up when they can make a difference
+        Some(stream_name) if config::get_config().common.ingest_backpressure_enabled => {
+            infra::schema::get_settings(org_id, stream_name, stream_type)
+                .await
+                .map(|s| s.ingest_priority)
+                .unwrap_or_default()
+        }
+        _ => IngestPriority::Normal,
+    }
+}
+
+/// Checks the node can take more data: memory and disk circuit breakers,
+/// memtable size and the ingestion backpressure thresholds for the priority
+pub fn check_ingestion_resources(priority: IngestPriority) -> Result<()> {
     // check memory circuit breaker
     ingester::check_memory_circuit_breaker().map_err(|e| Error::ResourceError(e.to_string()))?;
 
@@ -497,6 +553,9 @@ pub async fn check_ingestion_allowed(
     // check memtable
     ingester::check_memtable_size().map_err(|e| Error::ResourceError(e.to_string()))?;
 
+    // check backpressure
+    ingester::check_backpressure(priority).map_err(|e| Error::ResourceError(e.to_string()))?;
+
     Ok(())
 }
 
@@ -679,12 +738,110 @@ pub fn refactor_map(
     new_map
 }
 
+/// Parses the comma separated `source:org/stream` entries of the setting of an
+/// ingester, e.g. the Kafka topics, into the sources with the org and the
+/// stream they are ingested into. The source ends at the last `:` as the names
+/// of Redis streams often contain some, `kind` and `expected` describe the
+/// entries in the errors
+pub(crate) fn parse_source_streams(
+    value: &str,
+    kind: &str,
+    expected: &str,
+) -> Result<Vec<(String, String, String)>, anyhow::Error> {
+    value
+        .split(',')
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            let invalid = || anyhow::anyhow!("invalid {kind}, expected {expected}: {v}");
+            let (source, target) = v.rsplit_once(':').ok_or_else(invalid)?;
+            let (org_id, stream_name) = target.split_once('/').ok_or_else(invalid)?;
+            let (source, org_id, stream_name) = (source.trim(), org_id.trim(), stream_name.trim());
+            if source.is_empty() || org_id.is_empty() || stream_name.is_empty() {
+                return Err(invalid());
+            }
+            Ok((
+                source.to_string(),
+                org_id.to_string(),
+                format_stream_name(stream_name.to_string()),
+            ))
+        })
+        .collect()
+}
+
+/// Binds the singleton ingester stored under `prefix`, e.g. the Kafka consumer
+/// under `/kafka_ingestion`, to this node unless another live node already
+/// holds it
+pub(crate) async fn claim_owner(prefix: &str) -> Result<bool, anyhow::Error> {
+    if owned_by_other(&db::ingestion_owner::get(prefix).await).await {
+        return Ok(false);
+    }
+
+    let locker = dist_lock::lock(&format!("{prefix}/lock"), 0).await?;
+    // check the working node again, maybe other node locked it first
+    if owned_by_other(&db::ingestion_owner::get(prefix).await).await {
+        dist_lock::unlock(&locker).await?;
+        return Ok(false);
+    }
+    let ret = db::ingestion_owner::set(prefix, &LOCAL_NODE.uuid).await;
+    dist_lock::unlock(&locker).await?;
+    ret.map(|_| true)
+}
+
+/// Returns false when another node has taken over the singleton ingester
+/// stored under `prefix`
+pub(crate) async fn still_owner(prefix: &str) -> bool {
+    let node = db::ingestion_owner::get(prefix).await;
+    node.is_empty() || LOCAL_NODE.uuid.eq(&node)
+}
+
+async fn owned_by_other(node: &str) -> bool {
+    !node.is_empty() && LOCAL_NODE.uuid.ne(node) && get_node_by_uuid(node).await.is_some()
+}
+
 #[cfg(test)]
 mod tests {
     use infra::schema::{STREAM_SETTINGS, unwrap_stream_settings};
 
     use super::*;
 
+    #[test]
+    fn test_parse_source_streams() {
+        let entries = parse_source_streams(
+            "logs:app:default/App Logs, audit:security/audit",
+            "redis stream",
+            "redis_stream:org/stream",
+        )
+        .unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                (
+                    "logs:app".to_string(),
+                    "default".to_string(),
+                    "app_logs".to_string()
+                ),
+                (
+                    "audit".to_string(),
+                    "security".to_string(),
+                    "audit".to_string()
+                ),
+            ]
+        );
+        let err = parse_source_streams("app-logs:default", "kafka topic", "topic:org/stream")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid kafka topic, expected topic:org/stream: app-logs:default"
+        );
+        assert!(parse_source_streams(":default/app", "kafka topic", "topic:org/stream").is_err());
+        assert!(
+            parse_source_streams("", "kafka topic", "topic:org/stream")
+                .unwrap()
+                .is_empty()
+        );
+    }
+
     #[test]
     fn test_format_partition_key() {
         assert_eq!(format_partition_key("default/olympics"), "defaultolympics");
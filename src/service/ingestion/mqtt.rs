@@ -0,0 +1,490 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! MQTT consumer
+//!
+//! Subscribes to the configured topic filters of an MQTT broker and ingests
+//! the JSON or CBOR payloads of the messages into log streams, through the
+//! same pipeline and UDS processing as HTTP ingestion. The `{name}` levels of
+//! a topic filter match any level, and the value of the level is ingested as
+//! the `name` field, e.g. the device id of `devices/{device_id}/telemetry`.
+//!
+//! The subscriptions use a persistent session and QoS 1 messages are only
+//! acknowledged once ingested, so the broker redelivers the messages which
+//! were not ingested when the consumer reconnects. Only one ingester
+//! subscribes at a time.
+
+use std::time::Duration;
+
+use base64::Engine;
+use bytes::Bytes;
+use config::{
+    TIMESTAMP_COL_NAME,
+    cluster::LOCAL_NODE,
+    get_config,
+    utils::{json, time::now_micros},
+};
+use hashbrown::HashMap;
+use rumqttc::{AsyncClient, Event, EventLoop, MqttOptions, Packet, Publish, QoS, Transport};
+
+use crate::{
+    common::meta::ingestion::{IngestUser, IngestionRequest, IngestionValueType, SystemJobType},
+    service::ingestion::{claim_owner, parse_source_streams, still_owner},
+};
+
+const OWNER_PREFIX: &str = "/mqtt_ingestion";
+/// How often a standby node checks whether the consumer is still running
+const STANDBY_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// A configured `topic_filter:org/stream`
+#[derive(Debug, Clone, PartialEq)]
+struct Subscription {
+    /// The filter subscribed to, with the `{name}` levels replaced by `+`
+    filter: String,
+    /// The levels of the filter as configured
+    levels: Vec<Level>,
+    org_id: String,
+    stream_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Level {
+    Exact(String),
+    /// `+`, or `{name}` when the level is ingested as the `name` field
+    Single(Option<String>),
+    /// `#`
+    Multi,
+}
+
+fn parse_subscriptions(topics: &str) -> Result<Vec<Subscription>, anyhow::Error> {
+    parse_source_streams(topics, "mqtt topic", "topic_filter:org/stream")?
+        .into_iter()
+        .map(|(filter, org_id, stream_name)| {
+            let levels = parse_filter(&filter)?;
+            Ok(Subscription {
+                filter: levels
+                    .iter()
+                    .map(|level| match level {
+                        Level::Exact(v) => v.as_str(),
+                        Level::Single(_) => "+",
+                        Level::Multi => "#",
+                    })
+                    .collect::<Vec<_>>()
+                    .join("/"),
+                levels,
+                org_id,
+                stream_name,
+            })
+        })
+        .collect()
+}
+
+fn parse_filter(filter: &str) -> Result<Vec<Level>, anyhow::Error> {
+    let parts = filter.split('/').collect::<Vec<_>>();
+    parts
+        .iter()
+        .enumerate()
+        .map(|(i, part)| match *part {
+            "#" if i + 1 == parts.len() => Ok(Level::Multi),
+            "+" => Ok(Level::Single(None)),
+            v if v.len() > 2 && v.starts_with('{') && v.ends_with('}') => {
+                Ok(Level::Single(Some(v[1..v.len() - 1].to_string())))
+            }
+            v if v.contains(['#', '+', '{', '}']) => Err(anyhow::anyhow!(
+                "invalid mqtt topic filter {filter}: wildcards must be a whole level, and # the last one"
+            )),
+            v => Ok(Level::Exact(v.to_string())),
+        })
+        .collect()
+}
+
+impl Subscription {
+    /// Returns the fields of the topic levels when the topic matches the
+    /// filter
+    fn match_topic(&self, topic: &str) -> Option<Vec<(String, String)>> {
+        // wildcards don't match the topics reserved by the broker
+        if topic.starts_with('$') && !matches!(self.levels.first(), Some(Level::Exact(_))) {
+            return None;
+        }
+        let mut fields = Vec::new();
+        let mut parts = topic.split('/');
+        for level in self.levels.iter() {
+            if *level == Level::Multi {
+                return Some(fields);
+            }
+            let part = parts.next()?;
+            match level {
+                Level::Exact(v) if v != part => return None,
+                Level::Single(Some(name)) => fields.push((name.clone(), part.to_string())),
+                _ => {}
+            }
+        }
+        parts.next().is_none().then_some(fields)
+    }
+}
+
+/// Runs forever, consuming while this node owns the consumer
+pub async fn run() -> Result<(), anyhow::Error> {
+    let subscriptions = parse_subscriptions(&get_config().mqtt_ingestion.topics)?;
+    loop {
+        match claim_owner(OWNER_PREFIX).await {
+            Ok(true) => {
+                log::info!("[MQTT] consumer acquired by node {}", LOCAL_NODE.name);
+                if let Err(e) = consume(&subscriptions).await {
+                    log::error!("[MQTT] consumer stopped: {e}");
+                }
+            }
+            Ok(false) => {}
+            Err(e) => log::error!("[MQTT] failed to claim consumer: {e}"),
+        }
+        tokio::time::sleep(STANDBY_CHECK_INTERVAL).await;
+    }
+}
+
+fn connect(subscriptions: usize) -> Result<(AsyncClient, EventLoop), anyhow::Error> {
+    let cfg = get_config();
+    let broker = cfg.mqtt_ingestion.broker.trim();
+    let default_port = if cfg.mqtt_ingestion.tls_enabled {
+        8883
+    } else {
+        1883
+    };
+    let (host, port) = match broker.rsplit_once(':') {
+        Some((host, port)) => (
+            host,
+            port.parse::<u16>()
+                .map_err(|_| anyhow::anyhow!("invalid mqtt broker port: {broker}"))?,
+        ),
+        None => (broker, default_port),
+    };
+    let mut options = MqttOptions::new(cfg.mqtt_ingestion.client_id.clone(), host, port);
+    options
+        .set_keep_alive(Duration::from_secs(cfg.mqtt_ingestion.keep_alive))
+        .set_clean_session(false)
+        .set_manual_acks(true);
+    if !cfg.mqtt_ingestion.username.is_empty() {
+        options.set_credentials(
+            cfg.mqtt_ingestion.username.clone(),
+            cfg.mqtt_ingestion.password.clone(),
+        );
+    }
+    if cfg.mqtt_ingestion.tls_enabled {
+        // trusts the system root certificates
+        options.set_transport(Transport::tls_with_default_config());
+    }
+    // room for the acknowledgements of a full batch of every subscription,
+    // which are queued while the event loop is waiting for the ingestion
+    let cap = cfg.mqtt_ingestion.batch_size * (subscriptions + 1);
+    Ok(AsyncClient::new(options, cap))
+}
+
+/// The messages received for a subscription and not ingested yet
+#[derive(Default)]
+struct Pending {
+    records: Vec<json::Value>,
+    messages: Vec<Publish>,
+}
+
+/// Ingests the messages of the subscribed topics until another node takes
+/// over the consumer. The messages are buffered by subscription and ingested
+/// when the batch is full or the flush interval elapsed.
+async fn consume(subscriptions: &[Subscription]) -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    let qos = if cfg.mqtt_ingestion.qos == 0 {
+        QoS::AtMostOnce
+    } else {
+        QoS::AtLeastOnce
+    };
+    let (client, mut eventloop) = connect(subscriptions.len())?;
+    let mut pending: HashMap<usize, Pending> = HashMap::new();
+    let mut flush =
+        tokio::time::interval(Duration::from_millis(cfg.mqtt_ingestion.flush_interval_ms));
+    let mut owner_check = tokio::time::interval(STANDBY_CHECK_INTERVAL);
+    loop {
+        tokio::select! {
+            event = eventloop.poll() => match event {
+                Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                    log::info!("[MQTT] connected to {}", cfg.mqtt_ingestion.broker);
+                    for sub in subscriptions {
+                        client.subscribe(sub.filter.as_str(), qos).await?;
+                    }
+                }
+                Ok(Event::Incoming(Packet::Publish(message))) => {
+                    let Some((idx, fields)) = subscriptions
+                        .iter()
+                        .enumerate()
+                        .find_map(|(i, sub)| Some((i, sub.match_topic(&message.topic)?)))
+                    else {
+                        // a subscription of the session which is no longer configured
+                        client.ack(&message).await?;
+                        continue;
+                    };
+                    let entry = pending.entry(idx).or_default();
+                    entry.records.push(to_record(
+                        &message.topic,
+                        fields,
+                        &message.payload,
+                        &cfg.mqtt_ingestion.payload_format,
+                    ));
+                    entry.messages.push(message);
+                    if entry.records.len() >= cfg.mqtt_ingestion.batch_size {
+                        let entry = pending.remove(&idx).unwrap_or_default();
+                        ingest_messages(&client, &subscriptions[idx], entry).await?;
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    // the broker redelivers the messages which were not
+                    // acknowledged on this connection
+                    pending.clear();
+                    log::error!("[MQTT] connection to {} failed: {e}", cfg.mqtt_ingestion.broker);
+                    tokio::time::sleep(RETRY_DELAY).await;
+                }
+            },
+            _ = flush.tick() => {
+                for (idx, entry) in pending.drain() {
+                    ingest_messages(&client, &subscriptions[idx], entry).await?;
+                }
+            }
+            _ = owner_check.tick() => {
+                if !still_owner(OWNER_PREFIX).await {
+                    break;
+                }
+            }
+        }
+    }
+    // the messages which were not ingested are redelivered to the new owner
+    let _ = client.disconnect().await;
+    log::info!("[MQTT] consumer taken over by another node");
+    Ok(())
+}
+
+/// Ingests the messages of a subscription and acknowledges them. An error
+/// stops the consumer, the messages are redelivered once it reconnects.
+async fn ingest_messages(
+    client: &AsyncClient,
+    sub: &Subscription,
+    pending: Pending,
+) -> Result<(), anyhow::Error> {
+    if pending.records.is_empty() {
+        return Ok(());
+    }
+    let resp = crate::service::logs::ingest::ingest(
+        0,
+        &sub.org_id,
+        &sub.stream_name,
+        IngestionRequest::JsonValues(IngestionValueType::Mqtt, pending.records),
+        IngestUser::SystemJob(SystemJobType::MqttConsumer),
+        None,
+        false,
+    )
+    .await?;
+    if resp.code != 200 {
+        return Err(anyhow::anyhow!(
+            "failed to ingest {} into {}/{}: {}",
+            sub.filter,
+            sub.org_id,
+            sub.stream_name,
+            resp.error.unwrap_or_default()
+        ));
+    }
+    for message in pending.messages.iter() {
+        client.ack(message).await?;
+    }
+    Ok(())
+}
+
+/// Decoded object payloads are ingested as they are, other payloads become
+/// the message of the record. The fields of the topic levels override the
+/// fields of the payload, records without a time get the time they were
+/// received.
+fn to_record(
+    topic: &str,
+    topic_fields: Vec<(String, String)>,
+    payload: &Bytes,
+    format: &str,
+) -> json::Value {
+    let mut fields = match decode_payload(payload, format) {
+        Some(json::Value::Object(fields)) => fields,
+        Some(value) => {
+            let mut fields = json::Map::new();
+            fields.insert("message".to_string(), value);
+            fields
+        }
+        None => {
+            let mut fields = json::Map::new();
+            fields.insert(
+                "message".to_string(),
+                String::from_utf8_lossy(payload).into_owned().into(),
+            );
+            fields
+        }
+    };
+    for (name, value) in topic_fields {
+        fields.insert(name, value.into());
+    }
+    if !fields.contains_key(TIMESTAMP_COL_NAME) {
+        fields.insert(TIMESTAMP_COL_NAME.to_string(), now_micros().into());
+    }
+    fields.insert("mqtt_topic".to_string(), topic.into());
+    json::Value::Object(fields)
+}
+
+/// In the auto format, payloads which aren't JSON are only taken as CBOR when
+/// they decode to a map, text payloads can happen to be valid CBOR
+fn decode_payload(payload: &[u8], format: &str) -> Option<json::Value> {
+    match format {
+        "json" => json::from_slice(payload).ok(),
+        "cbor" => ciborium::from_reader::<ciborium::Value, _>(payload)
+            .ok()
+            .map(cbor_to_json),
+        _ => json::from_slice(payload).ok().or_else(|| {
+            match ciborium::from_reader::<ciborium::Value, _>(payload) {
+                Ok(value @ ciborium::Value::Map(_)) => Some(cbor_to_json(value)),
+                _ => None,
+            }
+        }),
+    }
+}
+
+/// Byte strings are encoded in base64, tags are dropped for their content
+fn cbor_to_json(value: ciborium::Value) -> json::Value {
+    match value {
+        ciborium::Value::Null => json::Value::Null,
+        ciborium::Value::Bool(v) => v.into(),
+        ciborium::Value::Integer(v) => {
+            let v = i128::from(v);
+            if let Ok(v) = i64::try_from(v) {
+                v.into()
+            } else if let Ok(v) = u64::try_from(v) {
+                v.into()
+            } else {
+                v.to_string().into()
+            }
+        }
+        ciborium::Value::Float(v) => json::Number::from_f64(v)
+            .map(json::Value::Number)
+            .unwrap_or(json::Value::Null),
+        ciborium::Value::Text(v) => v.into(),
+        ciborium::Value::Bytes(v) => base64::engine::general_purpose::STANDARD.encode(v).into(),
+        ciborium::Value::Tag(_, v) => cbor_to_json(*v),
+        ciborium::Value::Array(v) => v.into_iter().map(cbor_to_json).collect(),
+        ciborium::Value::Map(v) => json::Value::Object(
+            v.into_iter()
+                .map(|(k, v)| {
+                    let key = match cbor_to_json(k) {
+                        json::Value::String(k) => k,
+                        k => k.to_string(),
+                    };
+                    (key, cbor_to_json(v))
+                })
+                .collect(),
+        ),
+        _ => json::Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cbor(value: &ciborium::Value) -> Bytes {
+        let mut buf = Vec::new();
+        ciborium::into_writer(value, &mut buf).unwrap();
+        buf.into()
+    }
+
+    #[test]
+    fn test_parse_subscriptions() {
+        let subs = parse_subscriptions(
+            "devices/{device_id}/telemetry:iot/Telemetry, sites/+/{sensor}/#:iot/sensors",
+        )
+        .unwrap();
+        assert_eq!(subs.len(), 2);
+        assert_eq!(subs[0].filter, "devices/+/telemetry");
+        assert_eq!(subs[0].org_id, "iot");
+        assert_eq!(subs[0].stream_name, "telemetry");
+        assert_eq!(subs[1].filter, "sites/+/+/#");
+
+        assert!(parse_subscriptions("devices/+/telemetry").is_err());
+        assert!(parse_subscriptions("devices/+/telemetry:iot").is_err());
+        assert!(parse_subscriptions(":iot/telemetry").is_err());
+        assert!(parse_subscriptions("devices/#/telemetry:iot/telemetry").is_err());
+        assert!(parse_subscriptions("devices/dev+1:iot/telemetry").is_err());
+        assert!(parse_subscriptions("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_match_topic() {
+        let subs = parse_subscriptions(
+            "devices/{device_id}/telemetry:iot/telemetry, sites/{site}/#:iot/sensors, #:iot/all",
+        )
+        .unwrap();
+        assert_eq!(
+            subs[0].match_topic("devices/dev-1/telemetry").unwrap(),
+            vec![("device_id".to_string(), "dev-1".to_string())]
+        );
+        assert!(subs[0].match_topic("devices/dev-1/status").is_none());
+        assert!(subs[0].match_topic("devices/dev-1/telemetry/cpu").is_none());
+        assert!(subs[0].match_topic("devices/dev-1").is_none());
+
+        assert_eq!(
+            subs[1].match_topic("sites/paris/floor-1/temp").unwrap(),
+            vec![("site".to_string(), "paris".to_string())]
+        );
+        assert!(subs[1].match_topic("sites/paris").is_some());
+
+        assert!(subs[2].match_topic("anything/at/all").is_some());
+        assert!(subs[2].match_topic("$SYS/broker/uptime").is_none());
+    }
+
+    #[test]
+    fn test_to_record() {
+        let fields = vec![("device_id".to_string(), "dev-1".to_string())];
+        let record = to_record(
+            "devices/dev-1/telemetry",
+            fields.clone(),
+            &Bytes::from_static(br#"{"temp":21.5,"device_id":"spoofed","_timestamp":1}"#),
+            "auto",
+        );
+        assert_eq!(record["temp"], 21.5);
+        assert_eq!(record["device_id"], "dev-1");
+        assert_eq!(record["mqtt_topic"], "devices/dev-1/telemetry");
+        assert_eq!(record[TIMESTAMP_COL_NAME], 1);
+
+        let payload = cbor(&ciborium::Value::Map(vec![
+            ("temp".into(), ciborium::Value::Float(21.5)),
+            ("seq".into(), ciborium::Value::Integer(7.into())),
+            ("raw".into(), ciborium::Value::Bytes(vec![1, 2, 3])),
+        ]));
+        for format in ["cbor", "auto"] {
+            let record = to_record("devices/dev-1/telemetry", fields.clone(), &payload, format);
+            assert_eq!(record["temp"], 21.5);
+            assert_eq!(record["seq"], 7);
+            assert_eq!(record["raw"], "AQID");
+            assert_eq!(record["device_id"], "dev-1");
+            assert!(record[TIMESTAMP_COL_NAME].as_i64().unwrap() > 0);
+        }
+        // a cbor payload isn't json
+        let record = to_record("devices/dev-1/telemetry", vec![], &payload, "json");
+        assert!(record["message"].is_string());
+
+        let record = to_record("alerts", vec![], &Bytes::from_static(b"door open"), "auto");
+        assert_eq!(record["message"], "door open");
+        let record = to_record("alerts", vec![], &Bytes::from_static(b"42"), "auto");
+        assert_eq!(record["message"], 42);
+    }
+}
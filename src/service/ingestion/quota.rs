@@ -0,0 +1,243 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Ingestion quotas, in records and bytes per second, per org and per stream.
+//!
+//! Each ingester counts what it accepts over a sliding window of one second
+//! and shares its rates with the other ingesters, see [`crate::service::quota`].
+
+use config::{
+    get_config,
+    meta::stream::{IngestQuota, StreamType},
+    utils::time::now_micros,
+};
+use hashbrown::HashMap;
+use infra::errors::{Error, Result};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+use crate::service::{
+    db,
+    quota::{self, SlidingCounter},
+};
+
+const QUOTA_KEY_PREFIX: &str = "/ingest_quota/";
+
+/// Rates of the keys on this node
+static LOCAL: Lazy<RwLock<HashMap<String, Window>>> = Lazy::new(Default::default);
+/// Rates of the keys on the other ingesters, summed
+static REMOTE: Lazy<RwLock<HashMap<String, Rate>>> = Lazy::new(Default::default);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Rate {
+    records: f64,
+    bytes: f64,
+}
+
+impl Rate {
+    fn is_zero(&self) -> bool {
+        self.records == 0.0 && self.bytes == 0.0
+    }
+}
+
+impl std::ops::AddAssign for Rate {
+    fn add_assign(&mut self, other: Self) {
+        self.records += other.records;
+        self.bytes += other.bytes;
+    }
+}
+
+/// Records and bytes over a sliding window of one second
+#[derive(Debug, Default)]
+struct Window {
+    records: SlidingCounter<1_000_000>,
+    bytes: SlidingCounter<1_000_000>,
+}
+
+impl Window {
+    fn add(&mut self, now: i64, records: u64, bytes: u64) {
+        self.records.add(now, records);
+        self.bytes.add(now, bytes);
+    }
+
+    fn rate(&mut self, now: i64) -> Rate {
+        Rate {
+            records: self.records.value(now),
+            bytes: self.bytes.value(now),
+        }
+    }
+}
+
+fn stream_key(org_id: &str, stream_type: StreamType, stream_name: &str) -> String {
+    format!("{org_id}/{stream_type}/{stream_name}")
+}
+
+/// Rejects the request when the org, or the stream when it is known, is over
+/// its quota
+pub async fn check(org_id: &str, stream_type: StreamType, stream_name: Option<&str>) -> Result<()> {
+    let cfg = get_config();
+    if !cfg.limit.ingest_quota_enabled {
+        return Ok(());
+    }
+
+    let now = now_micros();
+    let org_quota = db::organization::get_ingest_quota(org_id)
+        .await
+        .or(IngestQuota {
+            records_per_sec: cfg.limit.ingest_quota_org_records,
+            bytes_per_sec: cfg.limit.ingest_quota_org_bytes,
+        });
+    if let Some(e) = over_quota(org_id, org_quota, now) {
+        return Err(Error::QuotaExceeded(format!(
+            "organization [{org_id}] is over its quota of {e}"
+        )));
+    }
+
+    if let Some(stream_name) = stream_name {
+        let stream_quota = infra::schema::get_settings(org_id, stream_name, stream_type)
+            .await
+            .map(|s| s.ingest_quota)
+            .unwrap_or_default()
+            .or(IngestQuota {
+                records_per_sec: cfg.limit.ingest_quota_stream_records,
+                bytes_per_sec: cfg.limit.ingest_quota_stream_bytes,
+            });
+        let key = stream_key(org_id, stream_type, stream_name);
+        if let Some(e) = over_quota(&key, stream_quota, now) {
+            return Err(Error::QuotaExceeded(format!(
+                "stream [{stream_name}] is over its quota of {e}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Counts the records and bytes accepted for a stream
+pub fn consume(org_id: &str, stream_type: StreamType, stream_name: &str, records: u64, bytes: u64) {
+    if !get_config().limit.ingest_quota_enabled || (records == 0 && bytes == 0) {
+        return;
+    }
+    let now = now_micros();
+    let stream_key = stream_key(org_id, stream_type, stream_name);
+    let mut local = LOCAL.write();
+    for key in [org_id, stream_key.as_str()] {
+        local.entry_ref(key).or_default().add(now, records, bytes);
+    }
+}
+
+/// Returns the exceeded limit
+fn over_quota(key: &str, quota: IngestQuota, now: i64) -> Option<String> {
+    if quota.is_empty() {
+        return None;
+    }
+    let mut rate = LOCAL
+        .write()
+        .get_mut(key)
+        .map(|w| w.rate(now))
+        .unwrap_or_default();
+    if let Some(remote) = REMOTE.read().get(key) {
+        rate += *remote;
+    }
+    if quota.records_per_sec > 0 && rate.records >= quota.records_per_sec as f64 {
+        return Some(format!("{} records per second", quota.records_per_sec));
+    }
+    if quota.bytes_per_sec > 0 && rate.bytes >= quota.bytes_per_sec as f64 {
+        return Some(format!("{} bytes per second", quota.bytes_per_sec));
+    }
+    None
+}
+
+/// Publishes the rates of this node and collects the ones of the other
+/// ingesters
+pub async fn sync() -> Result<()> {
+    let now = now_micros();
+    let rates = {
+        let mut local = LOCAL.write();
+        let rates = local
+            .iter_mut()
+            .map(|(k, w)| (k.clone(), w.rate(now)))
+            .filter(|(_, rate)| !rate.is_zero())
+            .collect::<HashMap<_, _>>();
+        local.retain(|k, _| rates.contains_key(k));
+        rates
+    };
+    let remote = quota::exchange(
+        QUOTA_KEY_PREFIX,
+        get_config().limit.ingest_quota_sync_interval,
+        now,
+        rates,
+    )
+    .await?;
+    *REMOTE.write() = remote;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_rate() {
+        let mut w = Window::default();
+        w.add(10_000_000, 100, 1000);
+        w.add(10_500_000, 100, 1000);
+        assert_eq!(
+            w.rate(10_900_000),
+            Rate {
+                records: 200.0,
+                bytes: 2000.0
+            }
+        );
+        // a quarter of the previous second is still in the window
+        w.add(11_750_000, 10, 100);
+        assert_eq!(
+            w.rate(11_750_000),
+            Rate {
+                records: 60.0,
+                bytes: 600.0
+            }
+        );
+        // nothing left after two seconds
+        assert!(w.rate(13_000_000).is_zero());
+    }
+
+    #[test]
+    fn test_over_quota() {
+        let now = 20_000_000;
+        let key = "test_over_quota_org";
+        LOCAL.write().entry_ref(key).or_default().add(now, 60, 600);
+        let quota = IngestQuota {
+            records_per_sec: 100,
+            bytes_per_sec: 0,
+        };
+        assert_eq!(over_quota(key, quota, now), None);
+        assert_eq!(over_quota(key, IngestQuota::default(), now), None);
+
+        REMOTE.write().insert(
+            key.to_string(),
+            Rate {
+                records: 50.0,
+                bytes: 0.0,
+            },
+        );
+        assert_eq!(
+            over_quota(key, quota, now).as_deref(),
+            Some("100 records per second")
+        );
+        REMOTE.write().remove(key);
+        LOCAL.write().remove(key);
+    }
+}
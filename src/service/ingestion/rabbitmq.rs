@@ -0,0 +1,359 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RabbitMQ consumers
+//!
+//! Every ingester consumes the enabled RabbitMQ sources of every organization,
+//! the broker spreads the messages of a queue over its consumers. Messages are
+//! ingested in batches of up to `prefetch` messages and acknowledged once the
+//! batch was written, the ones of a failed batch are requeued. The sources are
+//! reloaded periodically, the consumers of changed sources are restarted.
+
+use std::time::Duration;
+
+use config::{
+    TIMESTAMP_COL_NAME,
+    cluster::LOCAL_NODE,
+    utils::{json, time::now_micros},
+};
+use futures::StreamExt;
+use hashbrown::HashMap;
+use lapin::{
+    Channel, Connection, ConnectionProperties,
+    options::{
+        BasicAckOptions, BasicConsumeOptions, BasicNackOptions, BasicQosOptions, QueueBindOptions,
+        QueueDeclareOptions,
+    },
+    types::FieldTable,
+    uri::{AMQPUri, AMQPUserInfo},
+};
+use tokio::{
+    sync::oneshot,
+    task::JoinHandle,
+    time::{Instant, sleep_until},
+};
+
+use crate::{
+    common::meta::{
+        ingestion::{IngestUser, IngestionRequest, IngestionValueType, SystemJobType},
+        rabbitmq::RabbitmqSource,
+    },
+    service::{db, rabbitmq::check_egress},
+};
+
+/// How often the sources are reloaded
+const SYNC_INTERVAL: Duration = Duration::from_secs(30);
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+struct RunningConsumer {
+    source: RabbitmqSource,
+    stop: oneshot::Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+/// Runs forever, keeping a consumer running for every enabled source
+pub async fn run() -> Result<(), anyhow::Error> {
+    let mut consumers: HashMap<(String, String), RunningConsumer> = HashMap::new();
+    loop {
+        match db::rabbitmq::list_all().await {
+            Ok(sources) => sync(&mut consumers, sources),
+            Err(e) => log::error!("[RABBITMQ] failed to list sources: {e}"),
+        }
+        tokio::time::sleep(SYNC_INTERVAL).await;
+    }
+}
+
+fn sync(
+    consumers: &mut HashMap<(String, String), RunningConsumer>,
+    sources: Vec<(String, RabbitmqSource)>,
+) {
+    let mut wanted = sources
+        .into_iter()
+        .filter(|(_, source)| source.enabled)
+        .map(|(org_id, source)| ((org_id, source.name.clone()), source))
+        .collect::<HashMap<_, _>>();
+    let stale = consumers
+        .iter()
+        .filter(|(key, running)| {
+            running.handle.is_finished() || wanted.get(*key) != Some(&running.source)
+        })
+        .map(|(key, _)| key.clone())
+        .collect::<Vec<_>>();
+    for key in stale {
+        if let Some(running) = consumers.remove(&key) {
+            // the consumer closes its connection, the broker requeues the
+            // messages which were not acknowledged
+            let _ = running.stop.send(());
+        }
+    }
+    for (key, source) in wanted.drain() {
+        if consumers.contains_key(&key) {
+            continue;
+        }
+        log::info!("[RABBITMQ] starting consumer of source {}/{}", key.0, key.1);
+        let (stop, stop_rx) = oneshot::channel();
+        let handle = tokio::task::spawn(consume(key.0.clone(), source.clone(), stop_rx));
+        consumers.insert(
+            key,
+            RunningConsumer {
+                source,
+                stop,
+                handle,
+            },
+        );
+    }
+}
+
+async fn consume(org_id: String, source: RabbitmqSource, mut stop: oneshot::Receiver<()>) {
+    loop {
+        match consume_inner(&org_id, &source, &mut stop).await {
+            Ok(()) => return,
+            Err(e) => log::error!(
+                "[RABBITMQ] consumer of source {org_id}/{} failed: {e}",
+                source.name
+            ),
+        }
+        tokio::select! {
+            _ = &mut stop => return,
+            _ = tokio::time::sleep(RETRY_DELAY) => {}
+        }
+    }
+}
+
+async fn connect(source: &RabbitmqSource) -> Result<Connection, anyhow::Error> {
+    // the allowlist may have changed since the source was saved
+    check_egress(&source.uri).await?;
+    let mut uri = source
+        .uri
+        .parse::<AMQPUri>()
+        .map_err(|e| anyhow::anyhow!("invalid uri: {e}"))?;
+    if !source.username.is_empty() {
+        uri.authority.userinfo = AMQPUserInfo {
+            username: source.username.clone(),
+            password: source.password.clone(),
+        };
+    }
+    Ok(Connection::connect_uri(uri, ConnectionProperties::default()).await?)
+}
+
+/// Consumes the queue of the source until the consumer is stopped, returns an
+/// error when the connection fails or a batch can't be ingested
+async fn consume_inner(
+    org_id: &str,
+    source: &RabbitmqSource,
+    stop: &mut oneshot::Receiver<()>,
+) -> Result<(), anyhow::Error> {
+    let conn = connect(source).await?;
+    let channel = conn.create_channel().await?;
+    channel
+        .basic_qos(source.prefetch, BasicQosOptions::default())
+        .await?;
+    if let Some(exchange) = source.exchange.as_deref() {
+        channel
+            .queue_declare(
+                &source.queue,
+                QueueDeclareOptions {
+                    durable: true,
+                    ..Default::default()
+                },
+                FieldTable::default(),
+            )
+            .await?;
+        channel
+            .queue_bind(
+                &source.queue,
+                exchange,
+                &source.routing_key,
+                QueueBindOptions::default(),
+                FieldTable::default(),
+            )
+            .await?;
+    }
+    let mut consumer = channel
+        .basic_consume(
+            &source.queue,
+            &format!("openobserve-{}-{}", LOCAL_NODE.name, source.name),
+            BasicConsumeOptions::default(),
+            FieldTable::default(),
+        )
+        .await?;
+
+    let flush_interval = Duration::from_millis(source.flush_interval_ms);
+    let mut records = Vec::new();
+    let mut last_tag = 0;
+    let mut deadline = None;
+    loop {
+        let flush_at = async move {
+            match deadline {
+                Some(at) => sleep_until(at).await,
+                None => std::future::pending().await,
+            }
+        };
+        tokio::select! {
+            _ = &mut *stop => {
+                let _ = conn.close(200, "consumer stopped").await;
+                return Ok(());
+            }
+            delivery = consumer.next() => {
+                let delivery = match delivery {
+                    Some(v) => v?,
+                    None => return Err(anyhow::anyhow!("consumer cancelled by the broker")),
+                };
+                records.push(to_record(
+                    &delivery.data,
+                    delivery.exchange.as_str(),
+                    delivery.routing_key.as_str(),
+                    *delivery.properties.timestamp(),
+                ));
+                last_tag = delivery.delivery_tag;
+                deadline.get_or_insert_with(|| Instant::now() + flush_interval);
+                if records.len() < source.prefetch as usize {
+                    continue;
+                }
+            }
+            _ = flush_at => {}
+        }
+        deadline = None;
+        ingest_batch(
+            org_id,
+            source,
+            &channel,
+            std::mem::take(&mut records),
+            last_tag,
+        )
+        .await?;
+    }
+}
+
+/// Ingests a batch and acknowledges its messages up to `last_tag`, the
+/// messages of a batch which can't be ingested are requeued
+async fn ingest_batch(
+    org_id: &str,
+    source: &RabbitmqSource,
+    channel: &Channel,
+    records: Vec<json::Value>,
+    last_tag: u64,
+) -> Result<(), anyhow::Error> {
+    if records.is_empty() {
+        return Ok(());
+    }
+    let ret = crate::service::logs::ingest::ingest(
+        0,
+        org_id,
+        &source.stream_name,
+        IngestionRequest::JsonValues(IngestionValueType::Rabbitmq, records),
+        IngestUser::SystemJob(SystemJobType::RabbitmqConsumer),
+        None,
+        false,
+    )
+    .await
+    .map_err(anyhow::Error::from)
+    .and_then(|resp| {
+        if resp.code == 200 {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "failed to ingest into {org_id}/{}: {}",
+                source.stream_name,
+                resp.error.unwrap_or_default()
+            ))
+        }
+    });
+    match ret {
+        Ok(()) => {
+            channel
+                .basic_ack(last_tag, BasicAckOptions { multiple: true })
+                .await?;
+            Ok(())
+        }
+        Err(e) => {
+            channel
+                .basic_nack(
+                    last_tag,
+                    BasicNackOptions {
+                        multiple: true,
+                        requeue: true,
+                    },
+                )
+                .await?;
+            Err(e)
+        }
+    }
+}
+
+/// JSON object payloads are ingested as they are, other payloads become the
+/// message of the record. Records without a time get the timestamp property
+/// of the message, or the time they were received.
+fn to_record(
+    data: &[u8],
+    exchange: &str,
+    routing_key: &str,
+    timestamp: Option<u64>,
+) -> json::Value {
+    let mut fields = match json::from_slice::<json::Value>(data) {
+        Ok(json::Value::Object(fields)) => fields,
+        _ => {
+            let mut fields = json::Map::new();
+            fields.insert(
+                "message".to_string(),
+                String::from_utf8_lossy(data).into_owned().into(),
+            );
+            fields
+        }
+    };
+    if !fields.contains_key(TIMESTAMP_COL_NAME) {
+        // the timestamp property is in seconds
+        let ts = timestamp
+            .and_then(|v| i64::try_from(v).ok())
+            .map(|v| v * 1_000_000)
+            .unwrap_or_else(now_micros);
+        fields.insert(TIMESTAMP_COL_NAME.to_string(), ts.into());
+    }
+    if !exchange.is_empty() {
+        fields.insert("rabbitmq_exchange".to_string(), exchange.into());
+    }
+    if !routing_key.is_empty() {
+        fields.insert("rabbitmq_routing_key".to_string(), routing_key.into());
+    }
+    json::Value::Object(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_record() {
+        let record = to_record(
+            br#"{"level":"error","order_id":42}"#,
+            "logs",
+            "orders.eu",
+            Some(1_700_000_000),
+        );
+        assert_eq!(record["level"], "error");
+        assert_eq!(record["rabbitmq_exchange"], "logs");
+        assert_eq!(record["rabbitmq_routing_key"], "orders.eu");
+        assert_eq!(record[TIMESTAMP_COL_NAME], 1_700_000_000_000_000i64);
+
+        // the time of the record wins over the timestamp property
+        let record = to_record(br#"{"_timestamp":1}"#, "", "orders", Some(1_700_000_000));
+        assert_eq!(record[TIMESTAMP_COL_NAME], 1);
+        assert!(record.get("rabbitmq_exchange").is_none());
+
+        let record = to_record(b"plain text", "", "orders", None);
+        assert_eq!(record["message"], "plain text");
+        assert!(record[TIMESTAMP_COL_NAME].as_i64().unwrap() > 0);
+    }
+}
@@ -0,0 +1,293 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Applies the redaction rules of a stream to the records before they are
+//! written, so the masked values never reach the WAL.
+
+use std::{borrow::Cow, sync::Arc};
+
+use config::{
+    ALL_VALUES_COL_NAME, ID_COL_NAME, ORIGINAL_DATA_COL_NAME, TIMESTAMP_COL_NAME, get_config,
+    meta::stream::RedactionRule,
+    utils::json::{self, Map, Value},
+};
+use hashbrown::HashMap;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use regex::Regex;
+
+/// Compiled patterns of the rules, shared by all the streams.
+static PATTERNS: Lazy<RwLock<HashMap<String, Option<Arc<Regex>>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+const MAX_CACHED_PATTERNS: usize = 1024;
+
+struct Rule {
+    fields: Vec<String>,
+    regex: Option<Arc<Regex>>,
+    replacement: String,
+}
+
+pub struct Redactor {
+    rules: Vec<Rule>,
+}
+
+impl Redactor {
+    /// Returns None when the stream has no rule to apply.
+    pub fn new(rules: &[RedactionRule]) -> Option<Self> {
+        let rules = rules
+            .iter()
+            .filter_map(|rule| {
+                let regex = match rule.pattern.as_deref() {
+                    Some(pattern) if !pattern.is_empty() => Some(compile(pattern)?),
+                    _ if rule.fields.is_empty() => return None,
+                    _ => None,
+                };
+                Some(Rule {
+                    fields: rule.fields.clone(),
+                    regex,
+                    replacement: rule.replacement.clone(),
+                })
+            })
+            .collect::<Vec<_>>();
+        (!rules.is_empty()).then_some(Self { rules })
+    }
+
+    pub fn redact(&self, record: &mut Map<String, Value>) {
+        let mut redacted = false;
+        for rule in self.rules.iter() {
+            match rule.regex.as_ref() {
+                None => {
+                    for field in rule.fields.iter() {
+                        if is_internal(field) {
+                            continue;
+                        }
+                        match record.get_mut(field) {
+                            None | Some(Value::Null) => continue,
+                            Some(value) => *value = Value::String(rule.replacement.clone()),
+                        }
+                        redacted = true;
+                    }
+                }
+                Some(regex) => {
+                    for (field, value) in record.iter_mut() {
+                        let is_copy =
+                            field == ORIGINAL_DATA_COL_NAME || field == ALL_VALUES_COL_NAME;
+                        // a rule for all the fields also applies to the copies, they may
+                        // hold values the pipeline dropped from the record
+                        let skip = if rule.fields.is_empty() {
+                            field == TIMESTAMP_COL_NAME || field == ID_COL_NAME
+                        } else {
+                            is_internal(field) || !rule.fields.contains(field)
+                        };
+                        if skip {
+                            continue;
+                        }
+                        if let Value::String(v) = value
+                            && let Cow::Owned(masked) =
+                                regex.replace_all(v, rule.replacement.as_str())
+                        {
+                            *v = masked;
+                            redacted |= !is_copy;
+                        }
+                    }
+                }
+            }
+        }
+        if redacted {
+            rebuild_copies(record);
+        }
+    }
+}
+
+/// Columns added by the ingestion, the rules naming fields don't apply to them.
+fn is_internal(field: &str) -> bool {
+    [
+        TIMESTAMP_COL_NAME,
+        ID_COL_NAME,
+        ORIGINAL_DATA_COL_NAME,
+        ALL_VALUES_COL_NAME,
+    ]
+    .contains(&field)
+}
+
+/// Serializes `_original` and `_all_values` again from the redacted record, the
+/// masked values can't be reliably found in the serialized copies.
+fn rebuild_copies(record: &mut Map<String, Value>) {
+    if record.contains_key(ORIGINAL_DATA_COL_NAME) {
+        let original = record
+            .iter()
+            .filter(|(k, _)| {
+                ![ID_COL_NAME, ORIGINAL_DATA_COL_NAME, ALL_VALUES_COL_NAME].contains(&k.as_str())
+            })
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect::<Map<_, _>>();
+        record.insert(
+            ORIGINAL_DATA_COL_NAME.to_string(),
+            Value::String(json::to_string(&original).unwrap_or_default()),
+        );
+    }
+    if record.contains_key(ALL_VALUES_COL_NAME) {
+        let max_value_length = get_config().limit.index_all_max_value_length;
+        let values = record
+            .iter()
+            .filter(|(k, v)| {
+                !is_internal(k)
+                    && (max_value_length == 0
+                        || v.as_str().is_none_or(|s| s.len() <= max_value_length))
+            })
+            .map(|(_, v)| v.to_string())
+            .collect::<Vec<_>>();
+        record.insert(
+            ALL_VALUES_COL_NAME.to_string(),
+            Value::String(values.join(" ")),
+        );
+    }
+}
+
+fn compile(pattern: &str) -> Option<Arc<Regex>> {
+    if let Some(regex) = PATTERNS.read().get(pattern) {
+        return regex.clone();
+    }
+    let regex = match Regex::new(pattern) {
+        Ok(v) => Some(Arc::new(v)),
+        Err(e) => {
+            log::error!("[REDACTION] invalid pattern {pattern}: {e}");
+            None
+        }
+    };
+    let mut patterns = PATTERNS.write();
+    if patterns.len() >= MAX_CACHED_PATTERNS {
+        patterns.clear();
+    }
+    patterns.insert(pattern.to_string(), regex.clone());
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(name: &str, fields: &[&str], pattern: Option<&str>) -> RedactionRule {
+        RedactionRule {
+            name: name.to_string(),
+            fields: fields.iter().map(|f| f.to_string()).collect(),
+            pattern: pattern.map(|p| p.to_string()),
+            replacement: "[REDACTED]".to_string(),
+        }
+    }
+
+    fn record(value: json::Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_no_rules() {
+        assert!(Redactor::new(&[]).is_none());
+        assert!(Redactor::new(&[rule("empty", &[], None)]).is_none());
+        assert!(Redactor::new(&[rule("invalid", &[], Some("(unclosed"))]).is_none());
+    }
+
+    #[test]
+    fn test_redact_fields() {
+        let redactor =
+            Redactor::new(&[rule("secrets", &["password", "card", "none"], None)]).unwrap();
+        let mut rec = record(json::json!({
+            "password": "hunter2",
+            "card": 4111111111111111u64,
+            "none": null,
+            "user": "alice",
+            "note": "hunter2 is not a secret here",
+            "_original": "{\"password\":\"hunter2\",\"card\":4111111111111111,\"user\":\"alice\"}",
+            "_all_values": "hunter2 4111111111111111 alice"
+        }));
+        redactor.redact(&mut rec);
+        assert_eq!(rec["password"], "[REDACTED]");
+        assert_eq!(rec["card"], "[REDACTED]");
+        assert_eq!(rec["none"], Value::Null);
+        assert_eq!(rec["user"], "alice");
+        // the copies are serialized again, the other values equal to the
+        // masked ones are kept
+        let original: Map<String, Value> =
+            json::from_str(rec["_original"].as_str().unwrap()).unwrap();
+        assert_eq!(original["password"], "[REDACTED]");
+        assert_eq!(original["card"], "[REDACTED]");
+        assert_eq!(original["user"], "alice");
+        assert_eq!(original["note"], "hunter2 is not a secret here");
+        assert!(!original.contains_key("_all_values"));
+        let all_values = rec["_all_values"].as_str().unwrap();
+        assert!(all_values.contains(r#""[REDACTED]""#));
+        assert!(!all_values.contains("4111111111111111"));
+    }
+
+    #[test]
+    fn test_redact_pattern() {
+        let mut email = rule(
+            "email",
+            &[],
+            Some(r"[A-Za-z0-9._%+-]+@([A-Za-z0-9.-]+\.[A-Za-z]{2,})"),
+        );
+        email.replacement = "***@$1".to_string();
+        let card = rule("card", &["message"], Some(r"\b(?:\d[ -]?){13,16}\b"));
+        let redactor = Redactor::new(&[email, card]).unwrap();
+        let mut rec = record(json::json!({
+            "message": "paid with 4111 1111 1111 1111 by bob@example.com",
+            "contact": "alice@example.org",
+            "order": "4111111111111111",
+            "count": 3
+        }));
+        redactor.redact(&mut rec);
+        assert_eq!(rec["message"], "paid with [REDACTED] by ***@example.com");
+        assert_eq!(rec["contact"], "***@example.org");
+        // the card rule only applies to message
+        assert_eq!(rec["order"], "4111111111111111");
+        assert_eq!(rec["count"], 3);
+    }
+
+    #[test]
+    fn test_redact_pattern_copies() {
+        let redactor = Redactor::new(&[rule("token", &["auth"], Some(r"tok_\w+"))]).unwrap();
+        let mut rec = record(json::json!({
+            "auth": "Bearer tok_abc123",
+            "message": "tok_abc123 in a message is kept",
+            "_original": "{\"auth\":\"Bearer tok_abc123\",\"message\":\"tok_abc123 in a message is kept\"}"
+        }));
+        redactor.redact(&mut rec);
+        assert_eq!(rec["auth"], "Bearer [REDACTED]");
+        let original: Map<String, Value> =
+            json::from_str(rec["_original"].as_str().unwrap()).unwrap();
+        assert_eq!(original["auth"], "Bearer [REDACTED]");
+        assert_eq!(original["message"], "tok_abc123 in a message is kept");
+
+        // nothing to mask, the copies are untouched
+        let mut rec = record(json::json!({
+            "auth": "Basic xyz",
+            "_original": "{ \"auth\": \"Basic xyz\" }"
+        }));
+        redactor.redact(&mut rec);
+        assert_eq!(rec["_original"], "{ \"auth\": \"Basic xyz\" }");
+
+        // a rule for all the fields masks the values only left in the copies
+        let redactor = Redactor::new(&[rule("token", &[], Some(r"tok_\w+"))]).unwrap();
+        let mut rec = record(json::json!({
+            "message": "login",
+            "_original": "{\"message\":\"login\",\"auth\":\"tok_abc123\"}"
+        }));
+        redactor.redact(&mut rec);
+        assert_eq!(
+            rec["_original"],
+            "{\"message\":\"login\",\"auth\":\"[REDACTED]\"}"
+        );
+    }
+}
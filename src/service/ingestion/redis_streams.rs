@@ -0,0 +1,400 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Redis Streams consumer
+//!
+//! Consumes the configured Redis streams into log streams with a consumer
+//! group. Every ingester is a consumer of the group named after the node, so
+//! the entries are shared by the ingesters. The entries are acknowledged once
+//! they are in the WAL, so they are ingested at least once: a node reads its
+//! own pending entries again when it starts, and the entries left pending by
+//! a node which stopped are claimed by the other nodes once they were idle
+//! for `ZO_REDIS_STREAMS_CLAIM_IDLE_MS`. The claims use XAUTOCLAIM, which
+//! needs Redis 6.2 or later.
+
+use std::time::{Duration, Instant};
+
+use config::{
+    TIMESTAMP_COL_NAME,
+    cluster::LOCAL_NODE,
+    get_config,
+    utils::{json, time::now_micros},
+};
+use ingester::WalCommit;
+use redis::{
+    AsyncCommands, IntoConnectionInfo,
+    aio::MultiplexedConnection,
+    streams::{
+        StreamAutoClaimOptions, StreamAutoClaimReply, StreamId, StreamReadOptions, StreamReadReply,
+    },
+};
+
+use crate::{
+    common::meta::ingestion::{IngestUser, IngestionRequest, IngestionValueType, SystemJobType},
+    service::ingestion::parse_source_streams,
+};
+
+/// How long the entries may take to reach the WAL before they are read again
+const WAL_COMMIT_TIMEOUT: Duration = Duration::from_secs(60);
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// A configured `redis_stream:org/stream`
+#[derive(Debug, Clone, PartialEq)]
+struct Subscription {
+    redis_stream: String,
+    org_id: String,
+    stream_name: String,
+}
+
+fn parse_subscriptions(streams: &str) -> Result<Vec<Subscription>, anyhow::Error> {
+    Ok(
+        parse_source_streams(streams, "redis stream", "redis_stream:org/stream")?
+            .into_iter()
+            .map(|(redis_stream, org_id, stream_name)| Subscription {
+                redis_stream,
+                org_id,
+                stream_name,
+            })
+            .collect(),
+    )
+}
+
+/// Runs forever, consuming every configured Redis stream
+pub async fn run() -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    let subscriptions = parse_subscriptions(&cfg.redis_streams_ingestion.streams)?;
+    let client = connect()?;
+    let consumers = subscriptions
+        .into_iter()
+        .map(|sub| tokio::task::spawn(consume(client.clone(), sub)))
+        .collect::<Vec<_>>();
+    for consumer in consumers {
+        consumer.await?;
+    }
+    Ok(())
+}
+
+fn connect() -> Result<redis::Client, anyhow::Error> {
+    let cfg = get_config();
+    let mut info = cfg
+        .redis_streams_ingestion
+        .url
+        .as_str()
+        .into_connection_info()?;
+    if !cfg.redis_streams_ingestion.username.is_empty() {
+        info.redis.username = Some(cfg.redis_streams_ingestion.username.clone());
+    }
+    if !cfg.redis_streams_ingestion.password.is_empty() {
+        info.redis.password = Some(cfg.redis_streams_ingestion.password.clone());
+    }
+    Ok(redis::Client::open(info)?)
+}
+
+async fn consume(client: redis::Client, sub: Subscription) {
+    loop {
+        if let Err(e) = consume_inner(&client, &sub).await {
+            log::error!(
+                "[REDIS_STREAMS] failed to consume {}: {e}",
+                sub.redis_stream
+            );
+        }
+        tokio::time::sleep(RETRY_DELAY).await;
+    }
+}
+
+async fn consume_inner(client: &redis::Client, sub: &Subscription) -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    let mut conn = client.get_multiplexed_async_connection().await?;
+    create_group(&mut conn, sub).await?;
+    log::info!(
+        "[REDIS_STREAMS] node {} consuming {} into {}/{}",
+        LOCAL_NODE.name,
+        sub.redis_stream,
+        sub.org_id,
+        sub.stream_name
+    );
+
+    // the entries this node read before it stopped and didn't acknowledge
+    loop {
+        let entries = read(&mut conn, sub, "0", None).await?;
+        if entries.is_empty() {
+            break;
+        }
+        ingest_entries(&mut conn, sub, &entries).await?;
+    }
+
+    let claim_interval = Duration::from_secs(cfg.redis_streams_ingestion.claim_interval);
+    let mut claimed_at: Option<Instant> = None;
+    loop {
+        if claimed_at.is_none_or(|t| t.elapsed() >= claim_interval) {
+            claim_pending(&mut conn, sub).await?;
+            claimed_at = Some(Instant::now());
+        }
+        let block_ms = cfg.redis_streams_ingestion.block_ms;
+        let entries = read(&mut conn, sub, ">", Some(block_ms)).await?;
+        if !entries.is_empty() {
+            ingest_entries(&mut conn, sub, &entries).await?;
+        }
+    }
+}
+
+/// Creates the consumer group, and the Redis stream when it doesn't exist yet
+async fn create_group(
+    conn: &mut MultiplexedConnection,
+    sub: &Subscription,
+) -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    let start = match cfg.redis_streams_ingestion.start_position.as_str() {
+        "latest" => "$",
+        _ => "0",
+    };
+    let ret: redis::RedisResult<()> = conn
+        .xgroup_create_mkstream(&sub.redis_stream, &cfg.redis_streams_ingestion.group, start)
+        .await;
+    match ret {
+        Ok(()) => {
+            log::info!(
+                "[REDIS_STREAMS] created consumer group {} of {}",
+                cfg.redis_streams_ingestion.group,
+                sub.redis_stream
+            );
+            Ok(())
+        }
+        // another node created it
+        Err(e) if e.code() == Some("BUSYGROUP") => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Reads the entries after `id` for this node, `>` for the entries never
+/// delivered to the group, `0` for the entries pending for this node
+async fn read(
+    conn: &mut MultiplexedConnection,
+    sub: &Subscription,
+    id: &str,
+    block_ms: Option<usize>,
+) -> Result<Vec<StreamId>, anyhow::Error> {
+    let cfg = get_config();
+    let mut opts = StreamReadOptions::default()
+        .group(&cfg.redis_streams_ingestion.group, &LOCAL_NODE.name)
+        .count(cfg.redis_streams_ingestion.batch_size);
+    if let Some(block_ms) = block_ms {
+        opts = opts.block(block_ms);
+    }
+    let reply: Option<StreamReadReply> = conn
+        .xread_options(&[&sub.redis_stream], &[id], &opts)
+        .await?;
+    Ok(reply
+        .map(|reply| reply.keys.into_iter().flat_map(|key| key.ids).collect())
+        .unwrap_or_default())
+}
+
+/// Takes over and ingests the entries pending for too long for the other
+/// consumers of the group
+async fn claim_pending(
+    conn: &mut MultiplexedConnection,
+    sub: &Subscription,
+) -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    let mut start = "0-0".to_string();
+    loop {
+        let opts = StreamAutoClaimOptions::default().count(cfg.redis_streams_ingestion.batch_size);
+        let reply: StreamAutoClaimReply = conn
+            .xautoclaim_options(
+                &sub.redis_stream,
+                &cfg.redis_streams_ingestion.group,
+                &LOCAL_NODE.name,
+                cfg.redis_streams_ingestion.claim_idle_ms,
+                &start,
+                opts,
+            )
+            .await?;
+        if !reply.claimed.is_empty() {
+            log::info!(
+                "[REDIS_STREAMS] node {} claimed {} pending entries of {}",
+                LOCAL_NODE.name,
+                reply.claimed.len(),
+                sub.redis_stream
+            );
+            ingest_entries(conn, sub, &reply.claimed).await?;
+        }
+        if reply.next_stream_id == "0-0" {
+            return Ok(());
+        }
+        start = reply.next_stream_id;
+    }
+}
+
+/// Ingests the entries, waits for them to be in the WAL and acknowledges
+/// them. The entries trimmed from the Redis stream while they were pending
+/// have no fields, they are only acknowledged.
+async fn ingest_entries(
+    conn: &mut MultiplexedConnection,
+    sub: &Subscription,
+    entries: &[StreamId],
+) -> Result<(), anyhow::Error> {
+    let records = entries
+        .iter()
+        .filter(|entry| !entry.map.is_empty())
+        .map(|entry| {
+            let fields = entry
+                .map
+                .iter()
+                .filter_map(|(k, v)| {
+                    redis::from_redis_value::<String>(v)
+                        .ok()
+                        .map(|v| (k.clone(), v))
+                })
+                .collect();
+            to_record(&sub.redis_stream, &entry.id, fields)
+        })
+        .collect::<Vec<_>>();
+
+    if !records.is_empty() {
+        let mut commit = WalCommit::begin();
+        let resp = crate::service::logs::ingest::ingest(
+            0,
+            &sub.org_id,
+            &sub.stream_name,
+            IngestionRequest::JsonValues(IngestionValueType::RedisStreams, records),
+            IngestUser::SystemJob(SystemJobType::RedisStreamsConsumer),
+            None,
+            false,
+        )
+        .await?;
+        if resp.code != 200 {
+            return Err(anyhow::anyhow!(
+                "failed to ingest {} into {}/{}: {}",
+                sub.redis_stream,
+                sub.org_id,
+                sub.stream_name,
+                resp.error.unwrap_or_default()
+            ));
+        }
+        commit.end().await;
+
+        let started = Instant::now();
+        while !commit.is_committed() {
+            if started.elapsed() > WAL_COMMIT_TIMEOUT {
+                return Err(anyhow::anyhow!(
+                    "entries of {} were not written to the WAL",
+                    sub.redis_stream
+                ));
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    let ids = entries
+        .iter()
+        .map(|entry| entry.id.as_str())
+        .collect::<Vec<_>>();
+    let _: i64 = conn
+        .xack(
+            &sub.redis_stream,
+            &get_config().redis_streams_ingestion.group,
+            &ids,
+        )
+        .await?;
+    Ok(())
+}
+
+/// An entry with a single field holding a JSON object is ingested as the
+/// object, the fields of other entries are ingested as they are. Entries
+/// without a time get the time of their id.
+fn to_record(redis_stream: &str, id: &str, fields: Vec<(String, String)>) -> json::Value {
+    let mut record = match fields.as_slice() {
+        [(_, value)] => match json::from_str::<json::Value>(value) {
+            Ok(json::Value::Object(record)) => Some(record),
+            _ => None,
+        },
+        _ => None,
+    }
+    .unwrap_or_else(|| {
+        fields
+            .into_iter()
+            .map(|(k, v)| (k, json::Value::String(v)))
+            .collect()
+    });
+    if !record.contains_key(TIMESTAMP_COL_NAME) {
+        // the ids are `<milliseconds>-<sequence>`
+        let added_at = id
+            .split_once('-')
+            .and_then(|(ms, _)| ms.parse::<i64>().ok())
+            .map(|ms| ms * 1000);
+        record.insert(
+            TIMESTAMP_COL_NAME.to_string(),
+            added_at.unwrap_or_else(now_micros).into(),
+        );
+    }
+    record.insert("redis_stream".to_string(), redis_stream.into());
+    record.insert("redis_entry_id".to_string(), id.into());
+    json::Value::Object(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(values: &[(&str, &str)]) -> Vec<(String, String)> {
+        values
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_subscriptions() {
+        let subs = parse_subscriptions("logs:app:default/App Logs, audit:security/audit").unwrap();
+        assert_eq!(subs.len(), 2);
+        assert_eq!(subs[0].redis_stream, "logs:app");
+        assert_eq!(subs[0].org_id, "default");
+        assert_eq!(subs[0].stream_name, "app_logs");
+        assert_eq!(subs[1].redis_stream, "audit");
+
+        assert!(parse_subscriptions("logs").is_err());
+        assert!(parse_subscriptions("logs:default").is_err());
+        assert!(parse_subscriptions(":default/app").is_err());
+        assert!(parse_subscriptions("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_to_record() {
+        let record = to_record(
+            "logs:app",
+            "1700000000000-0",
+            fields(&[("level", "info"), ("msg", "ok")]),
+        );
+        assert_eq!(record["level"], "info");
+        assert_eq!(record["msg"], "ok");
+        assert_eq!(record["redis_stream"], "logs:app");
+        assert_eq!(record["redis_entry_id"], "1700000000000-0");
+        assert_eq!(record[TIMESTAMP_COL_NAME], 1_700_000_000_000_000i64);
+
+        // a single field holding an object is the record
+        let record = to_record(
+            "logs:app",
+            "1700000000000-1",
+            fields(&[("data", r#"{"level":"warn","_timestamp":1}"#)]),
+        );
+        assert_eq!(record["level"], "warn");
+        assert_eq!(record[TIMESTAMP_COL_NAME], 1);
+        assert!(record.get("data").is_none());
+
+        let record = to_record("logs:app", "1-0", fields(&[("message", "plain text")]));
+        assert_eq!(record["message"], "plain text");
+        assert_eq!(record[TIMESTAMP_COL_NAME], 1000);
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Minimal Kubernetes API client using the pod's service account
+
+use std::time::Duration;
+
+use config::{get_config, utils::json};
+use futures::StreamExt;
+use reqwest::StatusCode;
+
+const SERVICE_ACCOUNT_DIR: &str = "/var/run/secrets/kubernetes.io/serviceaccount";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: String,
+}
+
+impl Client {
+    pub fn new() -> Result<Self, anyhow::Error> {
+        let mut builder = reqwest::Client::builder().connect_timeout(CONNECT_TIMEOUT);
+        let ca_path = format!("{SERVICE_ACCOUNT_DIR}/ca.crt");
+        if let Ok(pem) = std::fs::read(&ca_path) {
+            builder = builder.add_root_certificate(reqwest::Certificate::from_pem(&pem)?);
+        }
+        Ok(Self {
+            http: builder.build()?,
+            base_url: get_config().k8s_events.api_server.clone(),
+        })
+    }
+
+    /// Service account tokens are rotated by the kubelet, so read it for every
+    /// request instead of caching it
+    fn request(&self, path: &str) -> reqwest::RequestBuilder {
+        let req = self.http.get(format!("{}{path}", self.base_url));
+        match std::fs::read_to_string(format!("{SERVICE_ACCOUNT_DIR}/token")) {
+            Ok(token) => req.bearer_auth(token.trim()),
+            Err(_) => req,
+        }
+    }
+
+    /// Fetches a single object, returns `None` when it doesn't exist or the
+    /// service account isn't allowed to read it
+    pub async fn get(&self, path: &str) -> Result<Option<json::Value>, anyhow::Error> {
+        let resp = self.request(path).timeout(REQUEST_TIMEOUT).send().await?;
+        match resp.status() {
+            StatusCode::NOT_FOUND | StatusCode::FORBIDDEN => Ok(None),
+            status if status.is_success() => Ok(Some(resp.json().await?)),
+            status => Err(anyhow::anyhow!(
+                "GET {path} failed: {status} {}",
+                resp.text().await.unwrap_or_default()
+            )),
+        }
+    }
+
+    /// Opens a long running request (watch or log follow) and returns the
+    /// response for the caller to consume line by line
+    pub async fn stream(&self, path: &str) -> Result<LineStream, anyhow::Error> {
+        let resp = self.request(path).send().await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            return Err(anyhow::anyhow!(
+                "GET {path} failed: {status} {}",
+                resp.text().await.unwrap_or_default()
+            ));
+        }
+        Ok(LineStream {
+            inner: Box::pin(resp.bytes_stream()),
+            buf: LineBuffer::default(),
+        })
+    }
+}
+
+type ByteStream =
+    std::pin::Pin<Box<dyn futures::Stream<Item = reqwest::Result<bytes::Bytes>> + Send>>;
+
+pub struct LineStream {
+    inner: ByteStream,
+    buf: LineBuffer,
+}
+
+impl LineStream {
+    /// Returns the next complete line, `None` once the server closed the stream
+    pub async fn next_line(&mut self) -> Result<Option<String>, anyhow::Error> {
+        loop {
+            if let Some(line) = self.buf.next_line() {
+                return Ok(Some(line));
+            }
+            match self.inner.next().await {
+                Some(chunk) => self.buf.push(&chunk?),
+                None => return Ok(self.buf.take_remaining()),
+            }
+        }
+    }
+}
+
+/// Splits a chunked byte stream into newline terminated lines
+#[derive(Default)]
+pub(crate) struct LineBuffer {
+    data: Vec<u8>,
+}
+
+impl LineBuffer {
+    pub(crate) fn push(&mut self, chunk: &[u8]) {
+        self.data.extend_from_slice(chunk);
+    }
+
+    pub(crate) fn next_line(&mut self) -> Option<String> {
+        let pos = self.data.iter().position(|b| *b == b'\n')?;
+        let line: Vec<u8> = self.data.drain(..=pos).collect();
+        Some(
+            String::from_utf8_lossy(&line[..line.len() - 1])
+                .trim_end_matches('\r')
+                .to_string(),
+        )
+    }
+
+    pub(crate) fn take_remaining(&mut self) -> Option<String> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let line = std::mem::take(&mut self.data);
+        Some(String::from_utf8_lossy(&line).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_buffer() {
+        let mut buf = LineBuffer::default();
+        buf.push(b"{\"type\":\"ADDED\"}\n{\"type\":");
+        assert_eq!(buf.next_line().unwrap(), "{\"type\":\"ADDED\"}");
+        assert!(buf.next_line().is_none());
+        buf.push(b"\"MODIFIED\"}\r\n");
+        assert_eq!(buf.next_line().unwrap(), "{\"type\":\"MODIFIED\"}");
+        assert!(buf.take_remaining().is_none());
+        buf.push(b"partial");
+        assert_eq!(buf.take_remaining().unwrap(), "partial");
+    }
+}
@@ -0,0 +1,569 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Kubernetes events watcher
+//!
+//! Watches core/v1 Events through the API server and ingests them with
+//! namespace, involved object and owner labels. Optionally follows container
+//! logs of running pods as well. Only one ingester holds the watch at a time,
+//! the others stand by and take over when that node goes away.
+
+use std::time::Duration;
+
+use config::{
+    TIMESTAMP_COL_NAME,
+    cluster::LOCAL_NODE,
+    get_config,
+    utils::{json, time::parse_str_to_timestamp_micros_as_option},
+};
+use hashbrown::HashMap;
+use tokio::sync::mpsc;
+
+use crate::{
+    common::meta::ingestion::{IngestUser, IngestionRequest, IngestionValueType, SystemJobType},
+    service::{
+        db,
+        ingestion::{claim_owner, still_owner},
+    },
+};
+
+pub mod client;
+mod pod_logs;
+
+use client::Client;
+
+const OWNER_PREFIX: &str = "/k8s_events/watch";
+/// How often a standby node checks whether the watch is still held
+const STANDBY_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// Upper bound for the server side watch timeout, the watch is re-opened after it
+const WATCH_TIMEOUT_SECS: u64 = 300;
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+const CHANNEL_SIZE: usize = 10240;
+/// Owner lookups are cached per object, the cache is reset when it grows past this
+const OWNER_CACHE_SIZE: usize = 10000;
+/// Maximum owner references followed, e.g. Pod -> ReplicaSet -> Deployment
+const MAX_OWNER_DEPTH: usize = 3;
+
+/// A record and the stream it belongs to
+#[derive(Debug)]
+pub(crate) enum Entry {
+    Event(json::Value),
+    PodLog(json::Value),
+}
+
+/// Runs forever, holding the watch while this node owns it
+pub async fn run() -> Result<(), anyhow::Error> {
+    let client = Client::new()?;
+    loop {
+        match claim().await {
+            Ok(Some(watermark)) => {
+                log::info!("[K8S_EVENTS] watch acquired by node {}", LOCAL_NODE.name);
+                if let Err(e) = watch_all(client.clone(), watermark).await {
+                    log::error!("[K8S_EVENTS] watcher stopped: {e}");
+                }
+            }
+            Ok(None) => {}
+            Err(e) => log::error!("[K8S_EVENTS] failed to claim watch: {e}"),
+        }
+        tokio::time::sleep(STANDBY_CHECK_INTERVAL).await;
+    }
+}
+
+/// Binds the watch to this node unless another live node already holds it,
+/// returns the watermark to resume from
+async fn claim() -> Result<Option<i64>, anyhow::Error> {
+    if !claim_owner(OWNER_PREFIX).await? {
+        return Ok(None);
+    }
+    Ok(Some(db::k8s_events::get_watermark().await))
+}
+
+async fn watch_all(client: Client, watermark: i64) -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    let (tx, rx) = mpsc::channel::<Entry>(CHANNEL_SIZE);
+    let mut tasks = Vec::new();
+    for namespace in parse_namespaces(&cfg.k8s_events.namespaces) {
+        tasks.push(tokio::task::spawn(watch_events(
+            client.clone(),
+            namespace.clone(),
+            watermark,
+            tx.clone(),
+        )));
+        if cfg.k8s_events.pod_logs_enabled {
+            tasks.push(tokio::task::spawn(pod_logs::watch_pods(
+                client.clone(),
+                namespace,
+                tx.clone(),
+            )));
+        }
+    }
+    drop(tx);
+
+    let ret = flush(rx, watermark).await;
+    for task in tasks {
+        task.abort();
+    }
+    ret
+}
+
+/// `None` stands for all namespaces
+fn parse_namespaces(namespaces: &str) -> Vec<Option<String>> {
+    let namespaces: Vec<_> = namespaces
+        .split(',')
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(|v| Some(v.to_string()))
+        .collect();
+    if namespaces.is_empty() {
+        vec![None]
+    } else {
+        namespaces
+    }
+}
+
+pub(crate) fn resource_path(namespace: &Option<String>, resource: &str) -> String {
+    match namespace {
+        Some(ns) => format!("/api/v1/namespaces/{ns}/{resource}"),
+        None => format!("/api/v1/{resource}"),
+    }
+}
+
+/// Lists events to get a consistent starting point and then watches from there.
+/// Events at or after `watermark` are ingested, older ones were already seen
+/// before a restart or a relist.
+async fn watch_events(
+    client: Client,
+    namespace: Option<String>,
+    mut watermark: i64,
+    tx: mpsc::Sender<Entry>,
+) {
+    let path = resource_path(&namespace, "events");
+    let mut owners = OwnerCache::default();
+    loop {
+        // list
+        let list = match client.get(&path).await {
+            Ok(Some(v)) => v,
+            Ok(None) => {
+                log::error!("[K8S_EVENTS] {path} is not accessible, check RBAC permissions");
+                tokio::time::sleep(STANDBY_CHECK_INTERVAL).await;
+                continue;
+            }
+            Err(e) => {
+                log::error!("[K8S_EVENTS] failed to list events: {e}");
+                tokio::time::sleep(RETRY_DELAY).await;
+                continue;
+            }
+        };
+        let mut resource_version = list["metadata"]["resourceVersion"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        for event in list["items"].as_array().into_iter().flatten() {
+            if event_timestamp(event) >= watermark
+                && !send_event(&client, &mut owners, event, &tx).await
+            {
+                return;
+            }
+        }
+
+        // watch until the resource version expires
+        loop {
+            let watch_path = format!(
+                "{path}?watch=1&allowWatchBookmarks=true&timeoutSeconds={WATCH_TIMEOUT_SECS}&resourceVersion={resource_version}"
+            );
+            let mut stream = match client.stream(&watch_path).await {
+                Ok(v) => v,
+                Err(e) => {
+                    log::error!("[K8S_EVENTS] failed to watch events: {e}");
+                    tokio::time::sleep(RETRY_DELAY).await;
+                    break;
+                }
+            };
+            let mut expired = false;
+            loop {
+                let line = match stream.next_line().await {
+                    Ok(Some(line)) => line,
+                    Ok(None) => break,
+                    Err(e) => {
+                        log::warn!("[K8S_EVENTS] watch stream error: {e}");
+                        break;
+                    }
+                };
+                let Ok(item) = json::from_str::<json::Value>(&line) else {
+                    continue;
+                };
+                let object = &item["object"];
+                match item["type"].as_str().unwrap_or_default() {
+                    "ADDED" | "MODIFIED" => {
+                        watermark = watermark.max(event_timestamp(object));
+                        if !send_event(&client, &mut owners, object, &tx).await {
+                            return;
+                        }
+                    }
+                    "ERROR" => {
+                        // 410 Gone, the resource version is too old
+                        log::debug!("[K8S_EVENTS] watch error: {}", object["message"]);
+                        expired = true;
+                        break;
+                    }
+                    _ => {} // BOOKMARK and DELETED only move the resource version
+                }
+                if let Some(v) = object["metadata"]["resourceVersion"].as_str() {
+                    resource_version = v.to_string();
+                }
+            }
+            if expired {
+                break;
+            }
+        }
+    }
+}
+
+async fn send_event(
+    client: &Client,
+    owners: &mut OwnerCache,
+    event: &json::Value,
+    tx: &mpsc::Sender<Entry>,
+) -> bool {
+    let object = &event["involvedObject"];
+    let owner = owners.resolve(client, object).await;
+    let record = event_to_record(event, owner.as_ref());
+    tx.send(Entry::Event(record)).await.is_ok()
+}
+
+/// Time the event last happened, in microseconds
+fn event_timestamp(event: &json::Value) -> i64 {
+    ["lastTimestamp", "eventTime", "firstTimestamp"]
+        .iter()
+        .find_map(|k| event[*k].as_str())
+        .or_else(|| event["metadata"]["creationTimestamp"].as_str())
+        .and_then(parse_str_to_timestamp_micros_as_option)
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Owner {
+    kind: String,
+    name: String,
+    labels: json::Map<String, json::Value>,
+}
+
+fn event_to_record(event: &json::Value, owner: Option<&Owner>) -> json::Value {
+    let object = &event["involvedObject"];
+    let mut record = json::Map::new();
+    let mut set = |key: &str, v: &json::Value| {
+        if !v.is_null() {
+            record.insert(key.to_string(), v.clone());
+        }
+    };
+    set("k8s_namespace", &object["namespace"]);
+    set("k8s_object_kind", &object["kind"]);
+    set("k8s_object_name", &object["name"]);
+    set("k8s_object_uid", &object["uid"]);
+    set("k8s_object_field_path", &object["fieldPath"]);
+    set("k8s_event_name", &event["metadata"]["name"]);
+    set("k8s_event_uid", &event["metadata"]["uid"]);
+    set("type", &event["type"]);
+    set("reason", &event["reason"]);
+    set("message", &event["message"]);
+    set("count", &event["count"]);
+    set("first_timestamp", &event["firstTimestamp"]);
+    set("last_timestamp", &event["lastTimestamp"]);
+    set("source_component", &event["source"]["component"]);
+    set("source_host", &event["source"]["host"]);
+    set("reporting_controller", &event["reportingComponent"]);
+    set("reporting_instance", &event["reportingInstance"]);
+    if let Some(owner) = owner {
+        record.insert("k8s_owner_kind".to_string(), owner.kind.clone().into());
+        record.insert("k8s_owner_name".to_string(), owner.name.clone().into());
+        insert_labels(&mut record, &owner.labels);
+    }
+    let ts = event_timestamp(event);
+    if ts > 0 {
+        record.insert(TIMESTAMP_COL_NAME.to_string(), ts.into());
+    }
+    json::Value::Object(record)
+}
+
+pub(crate) fn insert_labels(
+    record: &mut json::Map<String, json::Value>,
+    labels: &json::Map<String, json::Value>,
+) {
+    for (k, v) in labels {
+        record.insert(format!("k8s_label_{}", sanitize_label(k)), v.clone());
+    }
+}
+
+/// `app.kubernetes.io/name` -> `app_kubernetes_io_name`
+fn sanitize_label(key: &str) -> String {
+    key.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// API path for kinds we know how to walk owner references for
+fn object_path(kind: &str, namespace: &str, name: &str) -> Option<String> {
+    let (group, resource) = match kind {
+        "Pod" => ("api/v1", "pods"),
+        "ReplicaSet" => ("apis/apps/v1", "replicasets"),
+        "Deployment" => ("apis/apps/v1", "deployments"),
+        "StatefulSet" => ("apis/apps/v1", "statefulsets"),
+        "DaemonSet" => ("apis/apps/v1", "daemonsets"),
+        "Job" => ("apis/batch/v1", "jobs"),
+        "CronJob" => ("apis/batch/v1", "cronjobs"),
+        _ => return None,
+    };
+    Some(format!("/{group}/namespaces/{namespace}/{resource}/{name}"))
+}
+
+/// Returns the controlling owner reference of an object
+pub(crate) fn controller_of(object: &json::Value) -> Option<(String, String)> {
+    let refs = object["metadata"]["ownerReferences"].as_array()?;
+    let owner = refs
+        .iter()
+        .find(|r| r["controller"].as_bool().unwrap_or_default())
+        .or_else(|| refs.first())?;
+    Some((
+        owner["kind"].as_str()?.to_string(),
+        owner["name"].as_str()?.to_string(),
+    ))
+}
+
+#[derive(Default)]
+struct OwnerCache {
+    owners: HashMap<String, Option<Owner>>,
+}
+
+impl OwnerCache {
+    /// Walks up the owner chain of the involved object to its top level
+    /// controller, e.g. the Deployment of a Pod. Labels come from the object
+    /// itself.
+    async fn resolve(&mut self, client: &Client, object: &json::Value) -> Option<Owner> {
+        let kind = object["kind"].as_str()?;
+        let namespace = object["namespace"].as_str()?;
+        let name = object["name"].as_str()?;
+        let key = format!("{kind}/{namespace}/{name}");
+        if let Some(owner) = self.owners.get(&key) {
+            return owner.clone();
+        }
+
+        let mut owner: Option<Owner> = None;
+        let mut current = (kind.to_string(), name.to_string());
+        for depth in 0..MAX_OWNER_DEPTH {
+            let Some(path) = object_path(&current.0, namespace, &current.1) else {
+                break;
+            };
+            let obj = match client.get(&path).await {
+                Ok(Some(v)) => v,
+                Ok(None) => break,
+                Err(e) => {
+                    log::debug!("[K8S_EVENTS] failed to get {path}: {e}");
+                    return None; // don't cache transient errors
+                }
+            };
+            if depth == 0 {
+                owner = Some(Owner {
+                    kind: current.0.clone(),
+                    name: current.1.clone(),
+                    labels: obj["metadata"]["labels"]
+                        .as_object()
+                        .cloned()
+                        .unwrap_or_default(),
+                });
+            }
+            let Some(parent) = controller_of(&obj) else {
+                break;
+            };
+            if let Some(owner) = owner.as_mut() {
+                owner.kind = parent.0.clone();
+                owner.name = parent.1.clone();
+            }
+            current = parent;
+        }
+
+        if self.owners.len() >= OWNER_CACHE_SIZE {
+            self.owners.clear();
+        }
+        self.owners.insert(key, owner.clone());
+        owner
+    }
+}
+
+/// Buffers records per stream and ingests them in batches. The watermark of
+/// the newest ingested event is saved so a restart or takeover resumes there.
+async fn flush(mut rx: mpsc::Receiver<Entry>, mut watermark: i64) -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    let events_stream = &cfg.k8s_events.stream_name;
+    let logs_stream = &cfg.k8s_events.pod_logs_stream_name;
+    let batch_size = cfg.k8s_events.batch_size;
+    let mut interval =
+        tokio::time::interval(Duration::from_millis(cfg.k8s_events.flush_interval_ms));
+    interval.tick().await; // the first tick completes immediately
+    let mut last_saved = std::time::Instant::now();
+    let mut events: Vec<json::Value> = Vec::with_capacity(batch_size);
+    let mut pod_logs: Vec<json::Value> = Vec::with_capacity(batch_size);
+    loop {
+        tokio::select! {
+            entry = rx.recv() => {
+                let Some(entry) = entry else {
+                    ingest(events_stream, std::mem::take(&mut events)).await;
+                    ingest(logs_stream, std::mem::take(&mut pod_logs)).await;
+                    return Ok(());
+                };
+                match entry {
+                    Entry::Event(v) => {
+                        let ts = v[TIMESTAMP_COL_NAME].as_i64().unwrap_or_default();
+                        watermark = watermark.max(ts);
+                        events.push(v);
+                    }
+                    Entry::PodLog(v) => pod_logs.push(v),
+                }
+                if events.len() >= batch_size {
+                    ingest(events_stream, std::mem::take(&mut events)).await;
+                }
+                if pod_logs.len() >= batch_size {
+                    ingest(logs_stream, std::mem::take(&mut pod_logs)).await;
+                }
+            }
+            _ = interval.tick() => {
+                ingest(events_stream, std::mem::take(&mut events)).await;
+                ingest(logs_stream, std::mem::take(&mut pod_logs)).await;
+                if last_saved.elapsed() >= STANDBY_CHECK_INTERVAL {
+                    if !still_owner(OWNER_PREFIX).await {
+                        return Err(anyhow::anyhow!("watch was taken over by another node"));
+                    }
+                    db::k8s_events::set_watermark(watermark).await?;
+                    last_saved = std::time::Instant::now();
+                }
+            }
+        }
+    }
+}
+
+async fn ingest(stream_name: &str, records: Vec<json::Value>) {
+    if records.is_empty() {
+        return;
+    }
+    let org_id = &get_config().k8s_events.org_id;
+    let count = records.len();
+    match crate::service::logs::ingest::ingest(
+        0,
+        org_id,
+        stream_name,
+        IngestionRequest::JsonValues(IngestionValueType::K8sEvents, records),
+        IngestUser::SystemJob(SystemJobType::K8sEventsWatcher),
+        None,
+        false,
+    )
+    .await
+    {
+        Ok(resp) if resp.code == 200 => {}
+        Ok(resp) => {
+            log::error!(
+                "[K8S_EVENTS] failed to ingest {count} records into {org_id}/{stream_name}: {}",
+                resp.error.unwrap_or_default()
+            );
+        }
+        Err(e) => {
+            log::error!(
+                "[K8S_EVENTS] failed to ingest {count} records into {org_id}/{stream_name}: {e}"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event() -> json::Value {
+        json::json!({
+            "metadata": {"name": "web-7d4b9.17a", "uid": "e1", "resourceVersion": "100"},
+            "involvedObject": {
+                "kind": "Pod", "namespace": "prod", "name": "web-7d4b9", "uid": "p1"
+            },
+            "reason": "BackOff",
+            "message": "Back-off restarting failed container",
+            "type": "Warning",
+            "count": 3,
+            "firstTimestamp": "2026-01-01T00:00:00Z",
+            "lastTimestamp": "2026-01-01T00:05:00Z",
+            "source": {"component": "kubelet", "host": "node-1"}
+        })
+    }
+
+    #[test]
+    fn test_parse_namespaces() {
+        assert_eq!(parse_namespaces(""), vec![None]);
+        assert_eq!(
+            parse_namespaces("prod, staging,"),
+            vec![Some("prod".to_string()), Some("staging".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_event_to_record() {
+        let mut labels = json::Map::new();
+        labels.insert("app.kubernetes.io/name".to_string(), "web".into());
+        let owner = Owner {
+            kind: "Deployment".to_string(),
+            name: "web".to_string(),
+            labels,
+        };
+        let record = event_to_record(&event(), Some(&owner));
+        assert_eq!(record["k8s_namespace"], "prod");
+        assert_eq!(record["k8s_object_kind"], "Pod");
+        assert_eq!(record["reason"], "BackOff");
+        assert_eq!(record["type"], "Warning");
+        assert_eq!(record["source_host"], "node-1");
+        assert_eq!(record["k8s_owner_kind"], "Deployment");
+        assert_eq!(record["k8s_label_app_kubernetes_io_name"], "web");
+        assert_eq!(record["_timestamp"], 1_767_225_900_000_000i64);
+        assert!(record.get("reporting_instance").is_none());
+    }
+
+    #[test]
+    fn test_event_timestamp_fallback() {
+        let mut e = event();
+        e["lastTimestamp"] = json::Value::Null;
+        e["eventTime"] = "2026-01-01T00:01:00.000000Z".into();
+        assert_eq!(event_timestamp(&e), 1_767_225_660_000_000);
+        e["eventTime"] = json::Value::Null;
+        assert_eq!(event_timestamp(&e), 1_767_225_600_000_000);
+    }
+
+    #[test]
+    fn test_controller_of() {
+        let pod = json::json!({"metadata": {"ownerReferences": [
+            {"kind": "Node", "name": "n1"},
+            {"kind": "ReplicaSet", "name": "web-7d4b9", "controller": true}
+        ]}});
+        assert_eq!(
+            controller_of(&pod),
+            Some(("ReplicaSet".to_string(), "web-7d4b9".to_string()))
+        );
+        assert_eq!(controller_of(&json::json!({"metadata": {}})), None);
+    }
+
+    #[test]
+    fn test_object_path() {
+        assert_eq!(
+            object_path("ReplicaSet", "prod", "web").unwrap(),
+            "/apis/apps/v1/namespaces/prod/replicasets/web"
+        );
+        assert_eq!(resource_path(&None, "events"), "/api/v1/events".to_string());
+        assert!(object_path("ConfigMap", "prod", "x").is_none());
+    }
+}
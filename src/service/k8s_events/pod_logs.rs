@@ -0,0 +1,307 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Follows container logs of running pods through the API server
+
+use config::{
+    TIMESTAMP_COL_NAME,
+    utils::{json, time::parse_str_to_timestamp_micros_as_option},
+};
+use hashbrown::HashMap;
+use tokio::{sync::mpsc, task::JoinHandle};
+
+use super::{
+    Entry, RETRY_DELAY, WATCH_TIMEOUT_SECS, client::Client, controller_of, insert_labels,
+    resource_path,
+};
+
+/// Container identity and the metadata attached to each of its log lines
+#[derive(Debug, Clone, PartialEq)]
+struct Container {
+    namespace: String,
+    pod: String,
+    name: String,
+    /// changes on every restart, so a restarted container gets a new follower
+    container_id: String,
+    started_at: String,
+    fields: json::Map<String, json::Value>,
+}
+
+/// Watches pods and keeps one log follower per running container
+pub(super) async fn watch_pods(client: Client, namespace: Option<String>, tx: mpsc::Sender<Entry>) {
+    let path = resource_path(&namespace, "pods");
+    // only lines written after the watcher started are collected
+    let since = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let mut followers: HashMap<String, JoinHandle<()>> = HashMap::new();
+    loop {
+        let list = match client.get(&path).await {
+            Ok(Some(v)) => v,
+            Ok(None) => {
+                log::error!("[K8S_EVENTS] {path} is not accessible, check RBAC permissions");
+                tokio::time::sleep(RETRY_DELAY * 12).await;
+                continue;
+            }
+            Err(e) => {
+                log::error!("[K8S_EVENTS] failed to list pods: {e}");
+                tokio::time::sleep(RETRY_DELAY).await;
+                continue;
+            }
+        };
+        let mut resource_version = list["metadata"]["resourceVersion"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let mut running: Vec<Container> = Vec::new();
+        for pod in list["items"].as_array().into_iter().flatten() {
+            running.extend(running_containers(pod));
+        }
+        // drop followers of containers that went away while we weren't watching
+        let keep: Vec<String> = running.iter().map(|c| c.container_id.clone()).collect();
+        followers.retain(|id, handle| {
+            let alive = keep.contains(id) && !handle.is_finished();
+            if !alive {
+                handle.abort();
+            }
+            alive
+        });
+        for c in running {
+            start_follower(&client, &mut followers, c, &since, &tx);
+        }
+
+        loop {
+            let watch_path = format!(
+                "{path}?watch=1&timeoutSeconds={WATCH_TIMEOUT_SECS}&resourceVersion={resource_version}"
+            );
+            let mut stream = match client.stream(&watch_path).await {
+                Ok(v) => v,
+                Err(e) => {
+                    log::error!("[K8S_EVENTS] failed to watch pods: {e}");
+                    tokio::time::sleep(RETRY_DELAY).await;
+                    break;
+                }
+            };
+            let mut expired = false;
+            while let Ok(Some(line)) = stream.next_line().await {
+                let Ok(item) = json::from_str::<json::Value>(&line) else {
+                    continue;
+                };
+                let pod = &item["object"];
+                match item["type"].as_str().unwrap_or_default() {
+                    "ADDED" | "MODIFIED" => {
+                        for c in running_containers(pod) {
+                            start_follower(&client, &mut followers, c, &since, &tx);
+                        }
+                    }
+                    "DELETED" => {
+                        for id in container_ids(pod) {
+                            if let Some(handle) = followers.remove(&id) {
+                                handle.abort();
+                            }
+                        }
+                    }
+                    "ERROR" => {
+                        expired = true;
+                        break;
+                    }
+                    _ => {}
+                }
+                if let Some(v) = pod["metadata"]["resourceVersion"].as_str() {
+                    resource_version = v.to_string();
+                }
+            }
+            // followers of stopped containers end on their own
+            followers.retain(|_, handle| !handle.is_finished());
+            if expired || tx.is_closed() {
+                break;
+            }
+        }
+        if tx.is_closed() {
+            for (_, handle) in followers.drain() {
+                handle.abort();
+            }
+            return;
+        }
+    }
+}
+
+fn start_follower(
+    client: &Client,
+    followers: &mut HashMap<String, JoinHandle<()>>,
+    container: Container,
+    since: &str,
+    tx: &mpsc::Sender<Entry>,
+) {
+    if followers.contains_key(&container.container_id) {
+        return;
+    }
+    // containers started after the watcher are read from their first line
+    let since = if container.started_at.as_str() > since {
+        container.started_at.clone()
+    } else {
+        since.to_string()
+    };
+    let id = container.container_id.clone();
+    let handle = tokio::task::spawn(follow(client.clone(), container, since, tx.clone()));
+    followers.insert(id, handle);
+}
+
+async fn follow(client: Client, container: Container, since: String, tx: mpsc::Sender<Entry>) {
+    let path = format!(
+        "/api/v1/namespaces/{}/pods/{}/log?container={}&follow=true&timestamps=true&sinceTime={since}",
+        container.namespace, container.pod, container.name
+    );
+    let mut stream = match client.stream(&path).await {
+        Ok(v) => v,
+        Err(e) => {
+            log::debug!(
+                "[K8S_EVENTS] failed to follow logs of {}/{}: {e}",
+                container.pod,
+                container.name
+            );
+            return;
+        }
+    };
+    while let Ok(Some(line)) = stream.next_line().await {
+        if line.is_empty() {
+            continue;
+        }
+        let record = log_line_to_record(&container, &line);
+        if tx.send(Entry::PodLog(record)).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Log lines requested with `timestamps=true` start with an RFC3339 timestamp
+fn log_line_to_record(container: &Container, line: &str) -> json::Value {
+    let mut record = container.fields.clone();
+    let (ts, message) = match line.split_once(' ') {
+        Some((ts, message)) => match parse_str_to_timestamp_micros_as_option(ts) {
+            Some(ts) => (Some(ts), message),
+            None => (None, line),
+        },
+        None => (None, line),
+    };
+    if let Some(ts) = ts {
+        record.insert(TIMESTAMP_COL_NAME.to_string(), ts.into());
+    }
+    record.insert("message".to_string(), message.into());
+    json::Value::Object(record)
+}
+
+fn container_ids(pod: &json::Value) -> Vec<String> {
+    pod["status"]["containerStatuses"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|s| s["containerID"].as_str().map(|v| v.to_string()))
+        .collect()
+}
+
+fn running_containers(pod: &json::Value) -> Vec<Container> {
+    let meta = &pod["metadata"];
+    let (Some(namespace), Some(pod_name)) = (meta["namespace"].as_str(), meta["name"].as_str())
+    else {
+        return vec![];
+    };
+    let mut fields = json::Map::new();
+    fields.insert("k8s_namespace".to_string(), namespace.into());
+    fields.insert("k8s_pod_name".to_string(), pod_name.into());
+    if let Some(uid) = meta["uid"].as_str() {
+        fields.insert("k8s_pod_uid".to_string(), uid.into());
+    }
+    if let Some(node) = pod["spec"]["nodeName"].as_str() {
+        fields.insert("k8s_node_name".to_string(), node.into());
+    }
+    if let Some((kind, name)) = controller_of(pod) {
+        fields.insert("k8s_owner_kind".to_string(), kind.into());
+        fields.insert("k8s_owner_name".to_string(), name.into());
+    }
+    if let Some(labels) = meta["labels"].as_object() {
+        insert_labels(&mut fields, labels);
+    }
+
+    pod["status"]["containerStatuses"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|status| {
+            let started_at = status["state"]["running"]["startedAt"].as_str()?;
+            let name = status["name"].as_str()?;
+            let container_id = status["containerID"].as_str()?;
+            let mut fields = fields.clone();
+            fields.insert("k8s_container_name".to_string(), name.into());
+            Some(Container {
+                namespace: namespace.to_string(),
+                pod: pod_name.to_string(),
+                name: name.to_string(),
+                container_id: container_id.to_string(),
+                started_at: started_at.to_string(),
+                fields,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pod() -> json::Value {
+        json::json!({
+            "metadata": {
+                "namespace": "prod",
+                "name": "web-7d4b9-x2",
+                "uid": "p1",
+                "labels": {"app": "web"},
+                "ownerReferences": [{"kind": "ReplicaSet", "name": "web-7d4b9", "controller": true}]
+            },
+            "spec": {"nodeName": "node-1"},
+            "status": {"containerStatuses": [
+                {"name": "app", "containerID": "containerd://a1",
+                 "state": {"running": {"startedAt": "2026-01-01T00:00:00Z"}}},
+                {"name": "sidecar", "containerID": "containerd://b1",
+                 "state": {"waiting": {"reason": "CrashLoopBackOff"}}}
+            ]}
+        })
+    }
+
+    #[test]
+    fn test_running_containers() {
+        let containers = running_containers(&pod());
+        assert_eq!(containers.len(), 1);
+        let c = &containers[0];
+        assert_eq!(c.name, "app");
+        assert_eq!(c.container_id, "containerd://a1");
+        assert_eq!(c.fields["k8s_owner_kind"], "ReplicaSet");
+        assert_eq!(c.fields["k8s_node_name"], "node-1");
+        assert_eq!(c.fields["k8s_label_app"], "web");
+        assert_eq!(c.fields["k8s_container_name"], "app");
+        assert_eq!(container_ids(&pod()).len(), 2);
+    }
+
+    #[test]
+    fn test_log_line_to_record() {
+        let c = running_containers(&pod()).remove(0);
+        let record = log_line_to_record(&c, "2026-01-01T00:00:01.5Z GET /health 200");
+        assert_eq!(record["message"], "GET /health 200");
+        assert_eq!(record["_timestamp"], 1_767_225_601_500_000i64);
+        assert_eq!(record["k8s_pod_name"], "web-7d4b9-x2");
+
+        let record = log_line_to_record(&c, "no timestamp here");
+        assert_eq!(record["message"], "no timestamp here");
+        assert!(record.get("_timestamp").is_none());
+    }
+}
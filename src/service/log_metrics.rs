@@ -0,0 +1,497 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Metrics extracted from the logs at ingestion
+//!
+//! The records of a logs stream are observed by the log metric rules of the
+//! stream before they are written, e.g. counting the requests by status code
+//! or observing their duration into a histogram. Every node keeps cumulative
+//! series labeled with its name and writes them periodically to the metrics
+//! streams of the rules, so `rate()` and `histogram_quantile()` work over the
+//! series of every ingester.
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+
+use config::{
+    TIMESTAMP_COL_NAME,
+    cluster::LOCAL_NODE,
+    ider,
+    meta::{
+        log_metrics::{INSTANCE_LABEL, LogMetricKind, LogMetricRule},
+        promql::{NAME_LABEL, TYPE_LABEL, VALUE_LABEL},
+    },
+    utils::{
+        json::{self, Map, Value, get_string_value},
+        schema::format_stream_name,
+        time::now_micros,
+    },
+};
+use hashbrown::HashMap;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+use crate::{
+    common::{
+        infra::config::LOG_METRIC_RULES,
+        meta::ingestion::{IngestUser, SystemJobType},
+    },
+    service::db,
+};
+
+/// Errors that can occur when interacting with log metric rules.
+#[derive(Debug, thiserror::Error)]
+pub enum LogMetricError {
+    #[error("{0}")]
+    InvalidRule(String),
+
+    #[error("Log metric rule not found")]
+    RuleNotFound,
+
+    #[error(transparent)]
+    InfraError(#[from] infra::errors::Error),
+}
+
+/// Series kept per node, the observations of new series are dropped above it
+const MAX_SERIES: usize = 100_000;
+
+/// `(org_id, rule id, label values)`
+type SeriesKey = (String, String, Vec<String>);
+
+static SERIES: Lazy<Mutex<HashMap<SeriesKey, Series>>> = Lazy::new(Default::default);
+
+/// Observations dropped since the last flush because of [`MAX_SERIES`]
+static DROPPED: AtomicU64 = AtomicU64::new(0);
+
+/// The cumulative values of a series since the node started, or since the
+/// rule changed
+#[derive(Debug)]
+struct Series {
+    name: String,
+    kind: LogMetricKind,
+    labels: Vec<(String, String)>,
+    bounds: Vec<f64>,
+    /// Observations per bucket, not cumulative, the last one holds the
+    /// observations above every bound
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Series {
+    fn new(rule: &LogMetricRule, values: &[String]) -> Self {
+        let bounds = match rule.kind {
+            LogMetricKind::Counter => vec![],
+            LogMetricKind::Histogram => rule.buckets.clone(),
+        };
+        Self {
+            name: rule.name.clone(),
+            kind: rule.kind,
+            labels: rule
+                .labels
+                .iter()
+                .cloned()
+                .zip(values.iter().cloned())
+                .collect(),
+            buckets: vec![0; bounds.len() + 1],
+            bounds,
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    /// The series was built by the current version of the rule
+    fn matches(&self, rule: &LogMetricRule) -> bool {
+        self.name == rule.name
+            && self.kind == rule.kind
+            && self.labels.len() == rule.labels.len()
+            && self
+                .labels
+                .iter()
+                .zip(rule.labels.iter())
+                .all(|((l, _), r)| l == r)
+            && (self.kind == LogMetricKind::Counter || self.bounds == rule.buckets)
+    }
+
+    fn observe(&mut self, value: f64) {
+        if self.kind == LogMetricKind::Histogram {
+            let idx = self.bounds.partition_point(|bound| *bound < value);
+            self.buckets[idx] += 1;
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    fn to_records(&self, instance: &str, timestamp: i64) -> Vec<Value> {
+        let record = |name: String, extra: Option<(&str, String)>, value: f64| {
+            let mut record = Map::new();
+            for (name, value) in self.labels.iter() {
+                record.insert(name.clone(), value.clone().into());
+            }
+            if let Some((name, value)) = extra {
+                record.insert(name.to_string(), value.into());
+            }
+            record.insert(INSTANCE_LABEL.to_string(), instance.into());
+            record.insert(NAME_LABEL.to_string(), name.into());
+            record.insert(TYPE_LABEL.to_string(), "counter".into());
+            record.insert(TIMESTAMP_COL_NAME.to_string(), timestamp.into());
+            record.insert(VALUE_LABEL.to_string(), value.into());
+            Value::Object(record)
+        };
+        match self.kind {
+            LogMetricKind::Counter => vec![record(self.name.clone(), None, self.sum)],
+            LogMetricKind::Histogram => {
+                let mut cumulative = 0;
+                let les = self
+                    .bounds
+                    .iter()
+                    .map(|bound| bound.to_string())
+                    .chain(["+Inf".to_string()]);
+                let mut records = les
+                    .zip(self.buckets.iter())
+                    .map(|(le, n)| {
+                        cumulative += n;
+                        record(
+                            format!("{}_bucket", self.name),
+                            Some(("le", le)),
+                            cumulative as f64,
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                records.push(record(format!("{}_sum", self.name), None, self.sum));
+                records.push(record(
+                    format!("{}_count", self.name),
+                    None,
+                    self.count as f64,
+                ));
+                records
+            }
+        }
+    }
+}
+
+fn validate(rule: &LogMetricRule) -> Result<(), LogMetricError> {
+    rule.validate().map_err(LogMetricError::InvalidRule)
+}
+
+pub async fn create(
+    org_id: &str,
+    mut rule: LogMetricRule,
+) -> Result<LogMetricRule, LogMetricError> {
+    validate(&rule)?;
+    rule.id = ider::uuid();
+    rule.stream_name = format_stream_name(rule.stream_name.trim().to_string());
+    db::log_metrics::set(org_id, &rule).await?;
+    Ok(rule)
+}
+
+pub async fn update(
+    org_id: &str,
+    id: &str,
+    mut rule: LogMetricRule,
+) -> Result<LogMetricRule, LogMetricError> {
+    validate(&rule)?;
+    let old = get(org_id, id).await?;
+    rule.id = old.id;
+    rule.stream_name = format_stream_name(rule.stream_name.trim().to_string());
+    db::log_metrics::set(org_id, &rule).await?;
+    Ok(rule)
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<LogMetricRule, LogMetricError> {
+    db::log_metrics::get(org_id, id)
+        .await
+        .map_err(|_| LogMetricError::RuleNotFound)
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<LogMetricRule>, LogMetricError> {
+    Ok(db::log_metrics::list(org_id).await?)
+}
+
+pub async fn delete(org_id: &str, id: &str) -> Result<(), LogMetricError> {
+    get(org_id, id).await?;
+    db::log_metrics::delete(org_id, id).await?;
+    Ok(())
+}
+
+/// Observes the records ingested into a logs stream with the rules of the
+/// stream
+pub fn observe<'a>(
+    org_id: &str,
+    rules: &[Arc<LogMetricRule>],
+    records: impl Iterator<Item = &'a Map<String, Value>>,
+) {
+    let dropped = observe_into(&mut SERIES.lock(), org_id, rules, records);
+    if dropped > 0 {
+        DROPPED.fetch_add(dropped, Ordering::Relaxed);
+    }
+}
+
+/// Returns the number of observations dropped because of [`MAX_SERIES`]
+fn observe_into<'a>(
+    series: &mut HashMap<SeriesKey, Series>,
+    org_id: &str,
+    rules: &[Arc<LogMetricRule>],
+    records: impl Iterator<Item = &'a Map<String, Value>>,
+) -> u64 {
+    let rules = rules.iter().filter(|r| r.enabled).collect::<Vec<_>>();
+    if rules.is_empty() {
+        return 0;
+    }
+    let mut dropped = 0;
+    for record in records {
+        for rule in rules.iter() {
+            let matched = rule.filters.iter().all(|(field, expected)| {
+                record
+                    .get(field)
+                    .is_some_and(|v| get_string_value(v) == *expected)
+            });
+            if !matched {
+                continue;
+            }
+            let value = match &rule.value_field {
+                None => 1.0,
+                Some(field) => match record.get(field).and_then(numeric_value) {
+                    Some(v) => v,
+                    None => continue,
+                },
+            };
+            // a counter never goes down
+            if rule.kind == LogMetricKind::Counter && value < 0.0 {
+                continue;
+            }
+            let values = rule
+                .labels
+                .iter()
+                .map(|l| record.get(l).map(get_string_value).unwrap_or_default())
+                .collect::<Vec<_>>();
+            let key = (org_id.to_string(), rule.id.clone(), values);
+            if let Some(entry) = series.get_mut(&key) {
+                if !entry.matches(rule) {
+                    *entry = Series::new(rule, &key.2);
+                }
+                entry.observe(value);
+                continue;
+            }
+            if series.len() >= MAX_SERIES {
+                dropped += 1;
+                continue;
+            }
+            let mut entry = Series::new(rule, &key.2);
+            entry.observe(value);
+            series.insert(key, entry);
+        }
+    }
+    dropped
+}
+
+/// A finite number, or a string holding one
+fn numeric_value(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(v) => v.as_f64(),
+        Value::String(v) => v.trim().parse::<f64>().ok(),
+        _ => None,
+    }
+    .filter(|v| v.is_finite())
+}
+
+/// Writes the series of this node to the metrics streams of the rules, the
+/// series of the rules deleted or changed since they were observed are
+/// dropped
+pub async fn flush() {
+    let rules = LOG_METRIC_RULES
+        .iter()
+        .flat_map(|entry| {
+            let org_id = entry
+                .key()
+                .split_once('/')
+                .map(|(org_id, _)| org_id.to_string())
+                .unwrap_or_default();
+            entry
+                .value()
+                .iter()
+                .map(|rule| ((org_id.clone(), rule.id.clone()), rule.clone()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<HashMap<_, _>>();
+
+    let timestamp = now_micros();
+    let mut records: HashMap<String, Vec<Value>> = HashMap::new();
+    {
+        let mut series = SERIES.lock();
+        series.retain(|(org_id, id, _), s| {
+            rules
+                .get(&(org_id.clone(), id.clone()))
+                .is_some_and(|rule| rule.enabled && s.matches(rule))
+        });
+        for ((org_id, ..), s) in series.iter() {
+            records
+                .entry(org_id.clone())
+                .or_default()
+                .extend(s.to_records(&LOCAL_NODE.name, timestamp));
+        }
+    }
+    let dropped = DROPPED.swap(0, Ordering::Relaxed);
+    if dropped > 0 {
+        log::warn!(
+            "[LOG_METRICS] dropped {dropped} observations above the limit of {MAX_SERIES} series"
+        );
+    }
+
+    for (org_id, records) in records {
+        let body = match json::to_vec(&records) {
+            Ok(body) => body,
+            Err(e) => {
+                log::error!("[LOG_METRICS] failed to serialize the series of {org_id}: {e}");
+                continue;
+            }
+        };
+        match crate::service::metrics::json::ingest(
+            &org_id,
+            None,
+            body.into(),
+            IngestUser::SystemJob(SystemJobType::LogMetrics),
+        )
+        .await
+        {
+            Ok(resp) if resp.code != 200 => log::error!(
+                "[LOG_METRICS] failed to write the series of {org_id}: {}",
+                resp.error
+                    .unwrap_or_else(|| format!("status {}", resp.code))
+            ),
+            Ok(_) => {}
+            Err(e) => log::error!("[LOG_METRICS] failed to write the series of {org_id}: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn records(values: &[Value]) -> Vec<Map<String, Value>> {
+        values
+            .iter()
+            .map(|v| v.as_object().unwrap().clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_observe_counter() {
+        let rule = Arc::new(LogMetricRule {
+            id: "r1".to_string(),
+            name: "http_requests_total".to_string(),
+            stream_name: "nginx".to_string(),
+            filters: BTreeMap::from([("method".to_string(), "GET".to_string())]),
+            labels: vec!["status".to_string()],
+            enabled: true,
+            ..Default::default()
+        });
+        let records = records(&[
+            json::json!({"method": "GET", "status": 200}),
+            json::json!({"method": "GET", "status": 200}),
+            json::json!({"method": "GET", "status": 500}),
+            json::json!({"method": "POST", "status": 200}),
+            json::json!({"method": "GET"}),
+        ]);
+        let mut series = HashMap::new();
+        let dropped = observe_into(&mut series, "org1", &[rule.clone()], records.iter());
+        assert_eq!(dropped, 0);
+        assert_eq!(series.len(), 3);
+        let key = |v: &str| ("org1".to_string(), "r1".to_string(), vec![v.to_string()]);
+        assert_eq!(series[&key("200")].sum, 2.0);
+        assert_eq!(series[&key("500")].sum, 1.0);
+        assert_eq!(series[&key("")].sum, 1.0);
+
+        let out = series[&key("200")].to_records("node1", 10);
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0][NAME_LABEL], "http_requests_total");
+        assert_eq!(out[0]["status"], "200");
+        assert_eq!(out[0][INSTANCE_LABEL], "node1");
+        assert_eq!(out[0][VALUE_LABEL], 2.0);
+
+        // a change of the labels starts the series over
+        let changed = Arc::new(LogMetricRule {
+            labels: vec!["method".to_string()],
+            ..(*rule).clone()
+        });
+        assert!(!series[&key("200")].matches(&changed));
+    }
+
+    #[test]
+    fn test_observe_histogram() {
+        let rule = Arc::new(LogMetricRule {
+            id: "r2".to_string(),
+            name: "http_request_duration_seconds".to_string(),
+            stream_name: "nginx".to_string(),
+            kind: LogMetricKind::Histogram,
+            value_field: Some("duration".to_string()),
+            buckets: vec![0.5, 1.0],
+            enabled: true,
+            ..Default::default()
+        });
+        let records = records(&[
+            json::json!({"duration": 0.25}),
+            json::json!({"duration": "0.5"}),
+            json::json!({"duration": 0.75}),
+            json::json!({"duration": 3}),
+            json::json!({"duration": "slow"}),
+            json::json!({}),
+        ]);
+        let mut series = HashMap::new();
+        observe_into(&mut series, "org1", &[rule], records.iter());
+        let s = &series[&("org1".to_string(), "r2".to_string(), vec![])];
+        assert_eq!(s.count, 4);
+        assert_eq!(s.buckets, vec![2, 1, 1]);
+
+        let out = s.to_records("node1", 10);
+        let values = out
+            .iter()
+            .map(|r| {
+                (
+                    r[NAME_LABEL].as_str().unwrap().to_string(),
+                    r.get("le")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default()
+                        .to_string(),
+                    r[VALUE_LABEL].as_f64().unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let bucket = "http_request_duration_seconds_bucket".to_string();
+        assert_eq!(
+            values,
+            vec![
+                (bucket.clone(), "0.5".to_string(), 2.0),
+                (bucket.clone(), "1".to_string(), 3.0),
+                (bucket, "+Inf".to_string(), 4.0),
+                (
+                    "http_request_duration_seconds_sum".to_string(),
+                    String::new(),
+                    4.5
+                ),
+                (
+                    "http_request_duration_seconds_count".to_string(),
+                    String::new(),
+                    4.0
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,300 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Docker log driver compatible ingestion
+//!
+//! Containers can ship logs straight to OpenObserve with either
+//! `--log-driver=splunk` (HTTP, see [`ingest_splunk`]) or `--log-driver=gelf`
+//! (UDP/TCP, see [`run_gelf_server`]). Both are mapped onto the same record
+//! layout with container metadata under `container_*` fields.
+
+use axum::body::Bytes;
+use config::{
+    TIMESTAMP_COL_NAME, get_config,
+    meta::stream::StreamType,
+    utils::{json, time::now_micros},
+};
+use hashbrown::HashMap;
+use infra::errors::{Error, Result};
+use ingester::WalCommit;
+use serde::Deserialize;
+
+use crate::{
+    common::meta::ingestion::{
+        IngestUser, IngestionRequest, IngestionValueType, SplunkEventResponse, SystemJobType,
+    },
+    service::{
+        ingestion::check_ingestion_allowed,
+        logs::{
+            gelf::{self, parse_epoch_seconds},
+            hec,
+        },
+    },
+};
+
+/// Message format of the docker splunk log driver, events are sent as
+/// concatenated JSON objects without separators
+#[derive(Deserialize)]
+struct SplunkDriverEntry {
+    event: json::Value,
+    time: Option<json::Value>,
+    host: Option<String>,
+    source: Option<String>,
+    sourcetype: Option<String>,
+    index: Option<String>,
+    fields: Option<json::Map<String, json::Value>>,
+}
+
+/// Ingests a batch from the docker splunk log driver. The target stream is the
+/// entry's `index` (`splunk-index` option), then `stream_name` from the url,
+/// then `ZO_DOCKER_LOGS_STREAM`. Requests sent on a `channel` get an ack id,
+/// acknowledged like the HEC ones once the records are in the WAL.
+pub async fn ingest_splunk(
+    thread_id: usize,
+    org_id: &str,
+    stream_name: Option<&str>,
+    body: Bytes,
+    user_email: &str,
+    channel: Option<&str>,
+) -> Result<SplunkEventResponse> {
+    check_ingestion_allowed(org_id, StreamType::Logs, None).await?;
+
+    let cfg = get_config();
+    let default_stream = stream_name.unwrap_or(&cfg.docker_logs.stream_name);
+    let mut streams: HashMap<String, Vec<json::Value>> = HashMap::new();
+    for entry in json::Deserializer::from_slice(&body).into_iter::<SplunkDriverEntry>() {
+        let entry = match entry {
+            Ok(v) => v,
+            Err(e) => {
+                log::info!("[DOCKER] invalid splunk driver message for {org_id}: {e}");
+                return Ok(SplunkEventResponse::invalid_format());
+            }
+        };
+        let stream = entry
+            .index
+            .clone()
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| default_stream.to_string());
+        streams
+            .entry(stream)
+            .or_default()
+            .push(splunk_entry_to_record(entry));
+    }
+
+    let commit = channel.map(|_| WalCommit::begin());
+    for (stream, records) in streams {
+        let ret = super::ingest::ingest(
+            thread_id,
+            org_id,
+            &stream,
+            IngestionRequest::JsonValues(IngestionValueType::Docker, records),
+            IngestUser::from_user_email(user_email),
+            None,
+            false,
+        )
+        .await?;
+        if ret.code != 200 {
+            return Err(Error::IngestionError(ret.error.unwrap_or_default()));
+        }
+    }
+    let ack_id = match (channel, commit) {
+        (Some(channel), Some(mut commit)) => {
+            commit.end().await;
+            Some(hec::register_ack(channel, commit, now_micros()))
+        }
+        _ => None,
+    };
+    Ok(SplunkEventResponse::success(ack_id))
+}
+
+fn splunk_entry_to_record(entry: SplunkDriverEntry) -> json::Value {
+    let mut record = json::Map::new();
+    match entry.event {
+        // `splunk-format=inline` (default) and `json`
+        json::Value::Object(event) => {
+            for (k, v) in event {
+                match k.as_str() {
+                    "line" => match v {
+                        json::Value::Object(line) => record.extend(line),
+                        v => {
+                            record.insert("log".to_string(), v);
+                        }
+                    },
+                    "source" => {
+                        record.insert("container_stream".to_string(), v);
+                    }
+                    "tag" => {
+                        if let Some(tag) = v.as_str() {
+                            insert_tag(&mut record, tag);
+                        }
+                        record.insert("container_tag".to_string(), v);
+                    }
+                    "attrs" => {
+                        if let json::Value::Object(attrs) = v {
+                            record.extend(attrs);
+                        }
+                    }
+                    _ => {
+                        record.insert(k, v);
+                    }
+                }
+            }
+        }
+        // `splunk-format=raw`, tag and attrs are already part of the line
+        event => {
+            record.insert("log".to_string(), event);
+        }
+    }
+    if let Some(fields) = entry.fields {
+        record.extend(fields);
+    }
+    if let Some(host) = entry.host {
+        record.insert("host".to_string(), host.into());
+    }
+    if let Some(source) = entry.source {
+        record.insert("source".to_string(), source.into());
+    }
+    if let Some(sourcetype) = entry.sourcetype {
+        record.insert("sourcetype".to_string(), sourcetype.into());
+    }
+    if let Some(ts) = entry.time.as_ref().and_then(parse_epoch_seconds) {
+        record.insert(TIMESTAMP_COL_NAME.to_string(), ts.into());
+    }
+    json::Value::Object(record)
+}
+
+/// The default docker tag is the short container id, anything else is assumed
+/// to be a name produced by a `tag` template
+fn insert_tag(record: &mut json::Map<String, json::Value>, tag: &str) {
+    let is_id = (tag.len() == 12 || tag.len() == 64) && tag.chars().all(|c| c.is_ascii_hexdigit());
+    let key = if is_id {
+        "container_id"
+    } else {
+        "container_name"
+    };
+    record.entry(key.to_string()).or_insert_with(|| tag.into());
+}
+
+/// Maps a GELF message onto the same layout as splunk driver records. The gelf
+/// driver puts container metadata in `_container_*`, `_image_*`, `_command`,
+/// `_created` and `_tag`, labels and env vars selected with `labels`/`env` are
+/// sent as additional `_` fields.
+fn gelf_to_record(message: json::Value) -> Option<json::Value> {
+    let json::Value::Object(mut message) = message else {
+        return None;
+    };
+    for (from, to) in [
+        ("_tag", "_container_tag"),
+        ("_command", "_container_command"),
+        ("_created", "_container_created"),
+        ("_image_id", "_container_image_id"),
+        ("_image_name", "_container_image_name"),
+    ] {
+        if let Some(v) = message.remove(from) {
+            message.insert(to.to_string(), v);
+        }
+    }
+    let mut record = gelf::normalize(message);
+    if let Some(v) = record.remove("message") {
+        record.insert("log".to_string(), v);
+    }
+    Some(json::Value::Object(record))
+}
+
+/// Starts the GELF UDP and TCP listeners and ingests what they receive into
+/// `ZO_DOCKER_GELF_ORG`/`ZO_DOCKER_LOGS_STREAM`
+pub async fn run_gelf_server() -> std::result::Result<(), anyhow::Error> {
+    let cfg = get_config();
+    if !cfg.docker_logs.gelf_enabled {
+        return Ok(());
+    }
+    gelf::serve(gelf::Listener {
+        name: "DOCKER",
+        addr: cfg.docker_logs.gelf_addr.clone(),
+        udp_port: cfg.docker_logs.gelf_udp_port,
+        tcp_port: cfg.docker_logs.gelf_tcp_port,
+        org_id: cfg.docker_logs.gelf_org_id.clone(),
+        stream_name: cfg.docker_logs.stream_name.clone(),
+        value_type: IngestionValueType::Docker,
+        job: SystemJobType::DockerGelf,
+        to_record: gelf_to_record,
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_splunk(body: &str) -> Vec<json::Value> {
+        json::Deserializer::from_str(body)
+            .into_iter::<SplunkDriverEntry>()
+            .map(|e| splunk_entry_to_record(e.unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_splunk_inline_format() {
+        // two concatenated messages, exactly as the driver sends them
+        let body = r#"{"event":{"line":"hello","source":"stdout","tag":"6d1c6d1c6d1c","attrs":{"env":"prod"}},"time":"1485283346.123456","host":"docker-1","source":"web","sourcetype":"httpevent"}{"event":{"line":{"level":"info"},"source":"stderr","tag":"web"},"time":"1485283347.000000","host":"docker-1"}"#;
+        let records = parse_splunk(body);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["log"], "hello");
+        assert_eq!(records[0]["container_stream"], "stdout");
+        assert_eq!(records[0]["container_id"], "6d1c6d1c6d1c");
+        assert_eq!(records[0]["env"], "prod");
+        assert_eq!(records[0]["host"], "docker-1");
+        assert_eq!(records[0]["_timestamp"], 1_485_283_346_123_456i64);
+        // json format lines are flattened, named tags become the container name
+        assert_eq!(records[1]["level"], "info");
+        assert_eq!(records[1]["container_name"], "web");
+        assert_eq!(records[1]["container_stream"], "stderr");
+    }
+
+    #[test]
+    fn test_splunk_raw_format() {
+        let records = parse_splunk(r#"{"event":"web env=prod hello","time":1485283346.5}"#);
+        assert_eq!(records[0]["log"], "web env=prod hello");
+        assert_eq!(records[0]["_timestamp"], 1_485_283_346_500_000i64);
+    }
+
+    #[test]
+    fn test_gelf_to_record() {
+        let message = json::json!({
+            "version": "1.1",
+            "host": "docker-1",
+            "short_message": "hello",
+            "timestamp": 1485283346.25,
+            "level": 6,
+            "_container_id": "6d1c",
+            "_container_name": "web",
+            "_image_name": "nginx:latest",
+            "_command": "nginx -g daemon off;",
+            "_tag": "6d1c",
+            "_env": "prod"
+        });
+        let record = gelf_to_record(message).unwrap();
+        assert_eq!(record["log"], "hello");
+        assert_eq!(record["container_id"], "6d1c");
+        assert_eq!(record["container_name"], "web");
+        assert_eq!(record["container_image_name"], "nginx:latest");
+        assert_eq!(record["container_command"], "nginx -g daemon off;");
+        assert_eq!(record["env"], "prod");
+        assert_eq!(record["level"], 6);
+        assert_eq!(record["_timestamp"], 1_485_283_346_250_000i64);
+        assert!(record.get("version").is_none());
+        assert!(gelf_to_record(json::json!("not an object")).is_none());
+    }
+}
@@ -0,0 +1,613 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! GELF (Graylog Extended Log Format) ingestion
+//!
+//! Graylog shippers send GELF messages over UDP, optionally chunked and
+//! compressed, over TCP as null byte delimited frames (see [`run_server`]) or
+//! posted over HTTP (see [`ingest`]). Messages are mapped onto log records
+//! with `short_message` as `message`, the syslog `level` named in `severity`
+//! and the additional `_` fields without their prefix.
+
+use std::{
+    io::Read,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use axum::body::Bytes;
+use config::{
+    TIMESTAMP_COL_NAME, get_config,
+    utils::{json, time::parse_str_to_timestamp_micros_as_option},
+};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use hashbrown::HashMap;
+use infra::errors::{Error, Result};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, BufReader},
+    net::{TcpListener, UdpSocket},
+    sync::mpsc,
+};
+
+use crate::common::meta::ingestion::{
+    IngestUser, IngestionRequest, IngestionResponse, IngestionValueType, SystemJobType,
+};
+
+/// Largest GELF datagram we accept
+const MAX_DATAGRAM_SIZE: usize = 65535;
+/// Largest GELF message received over UDP or TCP, once decompressed, like the
+/// default of Graylog
+const MAX_MESSAGE_SIZE: usize = 2 * 1024 * 1024;
+/// Chunked messages being reassembled at once, and the bytes they hold
+const MAX_PENDING_MESSAGES: usize = 1024;
+const MAX_PENDING_SIZE: usize = 64 * 1024 * 1024;
+/// GELF chunked message magic bytes
+const CHUNK_MAGIC: [u8; 2] = [0x1e, 0x0f];
+/// GELF limits a message to 128 chunks
+const MAX_CHUNKS: u8 = 128;
+/// Incomplete chunked messages are dropped after this, as required by the spec
+const CHUNK_TIMEOUT: Duration = Duration::from_secs(5);
+const BATCH_SIZE: usize = 1000;
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+const CHANNEL_SIZE: usize = 10240;
+
+/// Syslog severity names, indexed by the GELF `level`
+const SEVERITIES: [&str; 8] = [
+    "emergency",
+    "alert",
+    "critical",
+    "error",
+    "warning",
+    "notice",
+    "informational",
+    "debug",
+];
+
+/// Splunk and GELF send epoch seconds with a fractional part, either as a
+/// number or as a string
+pub(super) fn parse_epoch_seconds(v: &json::Value) -> Option<i64> {
+    match v {
+        json::Value::Number(n) => n.as_f64().map(|v| (v * 1_000_000.0) as i64),
+        json::Value::String(s) => match s.parse::<f64>() {
+            Ok(v) => Some((v * 1_000_000.0) as i64),
+            Err(_) => parse_str_to_timestamp_micros_as_option(s),
+        },
+        _ => None,
+    }
+}
+
+/// Normalizes the fields of a GELF message. The additional `_` fields lose
+/// their prefix but never replace a standard field, `_id` is reserved by the
+/// spec and dropped.
+pub(super) fn normalize(message: json::Map<String, json::Value>) -> json::Map<String, json::Value> {
+    let mut record = json::Map::new();
+    let mut additional = Vec::new();
+    for (k, v) in message {
+        match k.as_str() {
+            "version" | "_id" => {}
+            "short_message" => {
+                record.insert("message".to_string(), v);
+            }
+            "timestamp" => {
+                if let Some(ts) = parse_epoch_seconds(&v) {
+                    record.insert(TIMESTAMP_COL_NAME.to_string(), ts.into());
+                }
+            }
+            "level" => {
+                if let Some(severity) = v.as_u64().and_then(|l| SEVERITIES.get(l as usize)) {
+                    record.insert("severity".to_string(), (*severity).into());
+                }
+                record.insert(k, v);
+            }
+            _ => match k.strip_prefix('_') {
+                Some(key) if !key.is_empty() => additional.push((key.to_string(), v)),
+                _ => {
+                    record.insert(k, v);
+                }
+            },
+        }
+    }
+    for (k, v) in additional {
+        record.entry(k).or_insert(v);
+    }
+    record
+}
+
+/// Maps a GELF message onto a log record, None when it isn't a JSON object
+pub(super) fn to_record(message: json::Value) -> Option<json::Value> {
+    match message {
+        json::Value::Object(message) => Some(json::Value::Object(normalize(message))),
+        _ => None,
+    }
+}
+
+/// Decompresses a GELF payload, which may be gzip or zlib compressed, up to
+/// `limit` bytes
+fn decompress(payload: &[u8], limit: usize) -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    let max = limit as u64 + 1;
+    match payload {
+        [0x1f, 0x8b, ..] => {
+            GzDecoder::new(payload).take(max).read_to_end(&mut buf)?;
+        }
+        [0x78, ..] => {
+            ZlibDecoder::new(payload).take(max).read_to_end(&mut buf)?;
+        }
+        _ => buf.extend_from_slice(payload),
+    }
+    if buf.len() > limit {
+        return Err(Error::IngestionError(format!(
+            "GELF payload is larger than {limit} bytes"
+        )));
+    }
+    Ok(buf)
+}
+
+/// Decodes a complete GELF payload holding one message
+pub(super) fn decode_payload(payload: &[u8]) -> Result<json::Value> {
+    Ok(json::from_slice(&decompress(payload, MAX_MESSAGE_SIZE)?)?)
+}
+
+/// Decodes an HTTP body, which holds one message or several concatenated or
+/// newline delimited ones
+fn parse_messages(body: &[u8]) -> Result<Vec<json::Value>> {
+    let payload = decompress(body, get_config().limit.req_payload_limit)?;
+    let mut records = Vec::new();
+    for message in json::Deserializer::from_slice(&payload).into_iter::<json::Value>() {
+        let message =
+            message.map_err(|e| Error::IngestionError(format!("invalid GELF message: {e}")))?;
+        let Some(record) = to_record(message) else {
+            return Err(Error::IngestionError(
+                "GELF message must be a JSON object".to_string(),
+            ));
+        };
+        records.push(record);
+    }
+    Ok(records)
+}
+
+struct PendingMessage {
+    chunks: Vec<Option<Vec<u8>>>,
+    received: usize,
+    started_at: Instant,
+}
+
+/// Reassembles chunked GELF UDP messages, the chunks of new messages are
+/// dropped while too many are pending
+#[derive(Default)]
+struct Chunks {
+    pending: HashMap<(SocketAddr, [u8; 8]), PendingMessage>,
+    /// Bytes of the pending chunks
+    size: usize,
+}
+
+impl Chunks {
+    /// Returns the complete payload once all chunks of a message arrived
+    fn push(&mut self, peer: SocketAddr, datagram: &[u8]) -> Option<Vec<u8>> {
+        if !datagram.starts_with(&CHUNK_MAGIC) {
+            return Some(datagram.to_vec());
+        }
+        if datagram.len() < 12 {
+            return None;
+        }
+        let id: [u8; 8] = datagram[2..10].try_into().ok()?;
+        let (seq, count) = (datagram[10], datagram[11]);
+        if count == 0 || count > MAX_CHUNKS || seq >= count {
+            return None;
+        }
+        let data = &datagram[12..];
+        if self.size + data.len() > MAX_PENDING_SIZE
+            || (self.pending.len() >= MAX_PENDING_MESSAGES
+                && !self.pending.contains_key(&(peer, id)))
+        {
+            self.purge_expired();
+            if self.size + data.len() > MAX_PENDING_SIZE
+                || self.pending.len() >= MAX_PENDING_MESSAGES
+            {
+                return None;
+            }
+        }
+        let msg = self
+            .pending
+            .entry((peer, id))
+            .or_insert_with(|| PendingMessage {
+                chunks: vec![None; count as usize],
+                received: 0,
+                started_at: Instant::now(),
+            });
+        if msg.chunks.len() != count as usize {
+            return None;
+        }
+        let slot = &mut msg.chunks[seq as usize];
+        if slot.is_none() {
+            *slot = Some(data.to_vec());
+            msg.received += 1;
+            self.size += data.len();
+        }
+        if msg.received < count as usize {
+            return None;
+        }
+        let msg = self.pending.remove(&(peer, id))?;
+        let payload = msg
+            .chunks
+            .into_iter()
+            .flatten()
+            .flatten()
+            .collect::<Vec<_>>();
+        self.size -= payload.len();
+        Some(payload)
+    }
+
+    fn purge_expired(&mut self) {
+        let mut size = self.size;
+        self.pending.retain(|_, m| {
+            let keep = m.started_at.elapsed() < CHUNK_TIMEOUT;
+            if !keep {
+                size -= m.chunks.iter().flatten().map(Vec::len).sum::<usize>();
+            }
+            keep
+        });
+        self.size = size;
+    }
+}
+
+/// A GELF UDP and TCP listener and where it ingests what it receives
+pub(super) struct Listener {
+    /// Prefix of the log lines of the listener
+    pub name: &'static str,
+    pub addr: String,
+    /// 0 disables the UDP listener
+    pub udp_port: u16,
+    /// 0 disables the TCP listener
+    pub tcp_port: u16,
+    pub org_id: String,
+    pub stream_name: String,
+    pub value_type: IngestionValueType,
+    pub job: SystemJobType,
+    /// Maps a decoded message onto the ingested record
+    pub to_record: fn(json::Value) -> Option<json::Value>,
+}
+
+/// Starts the GELF UDP and TCP listeners configured by `ZO_GELF_*`, they
+/// ingest into `ZO_GELF_ORG`/`ZO_GELF_STREAM`
+pub async fn run_server() -> std::result::Result<(), anyhow::Error> {
+    let cfg = get_config();
+    if !cfg.gelf.enabled {
+        return Ok(());
+    }
+    serve(Listener {
+        name: "GELF",
+        addr: cfg.gelf.addr.clone(),
+        udp_port: cfg.gelf.udp_port,
+        tcp_port: cfg.gelf.tcp_port,
+        org_id: cfg.gelf.org_id.clone(),
+        stream_name: cfg.gelf.stream_name.clone(),
+        value_type: IngestionValueType::Gelf,
+        job: SystemJobType::Gelf,
+        to_record,
+    })
+    .await
+}
+
+/// Runs the listener until its sockets are closed
+pub(super) async fn serve(listener: Listener) -> std::result::Result<(), anyhow::Error> {
+    let listener = Arc::new(listener);
+    let (tx, rx) = mpsc::channel::<json::Value>(CHANNEL_SIZE);
+    if listener.udp_port > 0 {
+        let addr: SocketAddr = format!("{}:{}", listener.addr, listener.udp_port).parse()?;
+        let socket = UdpSocket::bind(addr).await?;
+        log::info!("[{}] GELF listening on udp://{addr}", listener.name);
+        tokio::task::spawn(serve_udp(listener.clone(), socket, tx.clone()));
+    }
+    if listener.tcp_port > 0 {
+        let addr: SocketAddr = format!("{}:{}", listener.addr, listener.tcp_port).parse()?;
+        let socket = TcpListener::bind(addr).await?;
+        log::info!("[{}] GELF listening on tcp://{addr}", listener.name);
+        tokio::task::spawn(serve_tcp(listener.clone(), socket, tx.clone()));
+    }
+    drop(tx);
+    flush(listener, rx).await;
+    Ok(())
+}
+
+async fn serve_udp(listener: Arc<Listener>, socket: UdpSocket, tx: mpsc::Sender<json::Value>) {
+    let mut chunks = Chunks::default();
+    let mut last_purge = Instant::now();
+    let mut buf = vec![0u8; MAX_DATAGRAM_SIZE];
+    loop {
+        let (len, peer) = match socket.recv_from(&mut buf).await {
+            Ok(v) => v,
+            Err(e) => {
+                log::error!("[{}] GELF udp receive error: {e}", listener.name);
+                continue;
+            }
+        };
+        if last_purge.elapsed() >= CHUNK_TIMEOUT {
+            chunks.purge_expired();
+            last_purge = Instant::now();
+        }
+        let Some(payload) = chunks.push(peer, &buf[..len]) else {
+            continue;
+        };
+        match decode_payload(&payload).map(listener.to_record) {
+            Ok(Some(record)) => {
+                if tx.send(record).await.is_err() {
+                    return;
+                }
+            }
+            Ok(None) => {}
+            Err(e) => log::debug!("[{}] invalid GELF message from {peer}: {e}", listener.name),
+        }
+    }
+}
+
+async fn serve_tcp(listener: Arc<Listener>, socket: TcpListener, tx: mpsc::Sender<json::Value>) {
+    loop {
+        let (stream, peer) = match socket.accept().await {
+            Ok(v) => v,
+            Err(e) => {
+                log::error!("[{}] GELF tcp accept error: {e}", listener.name);
+                continue;
+            }
+        };
+        let listener = listener.clone();
+        let tx = tx.clone();
+        tokio::task::spawn(async move {
+            // GELF over TCP is uncompressed and null byte delimited
+            let mut reader = BufReader::new(stream);
+            let mut frame = Vec::new();
+            loop {
+                frame.clear();
+                let mut limited = (&mut reader).take(MAX_MESSAGE_SIZE as u64 + 1);
+                match limited.read_until(0, &mut frame).await {
+                    Ok(0) => return,
+                    Ok(_) => {}
+                    Err(e) => {
+                        log::debug!("[{}] GELF tcp read error from {peer}: {e}", listener.name);
+                        return;
+                    }
+                }
+                // the end of an oversized frame can't be found without
+                // reading it, the connection is closed
+                if frame.len() > MAX_MESSAGE_SIZE && frame.last() != Some(&0) {
+                    log::warn!(
+                        "[{}] GELF tcp message from {peer} is larger than {MAX_MESSAGE_SIZE} bytes, closing",
+                        listener.name
+                    );
+                    return;
+                }
+                let payload = frame.strip_suffix(&[0]).unwrap_or(&frame);
+                if payload.is_empty() {
+                    continue;
+                }
+                match json::from_slice::<json::Value>(payload).map(listener.to_record) {
+                    Ok(Some(record)) => {
+                        if tx.send(record).await.is_err() {
+                            return;
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        log::debug!("[{}] invalid GELF message from {peer}: {e}", listener.name)
+                    }
+                }
+            }
+        });
+    }
+}
+
+async fn flush(listener: Arc<Listener>, mut rx: mpsc::Receiver<json::Value>) {
+    let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+    interval.tick().await; // the first tick completes immediately
+    let mut buffer = Vec::with_capacity(BATCH_SIZE);
+    loop {
+        tokio::select! {
+            record = rx.recv() => {
+                let Some(record) = record else {
+                    ingest_batch(&listener, std::mem::take(&mut buffer)).await;
+                    return;
+                };
+                buffer.push(record);
+                if buffer.len() >= BATCH_SIZE {
+                    ingest_batch(&listener, std::mem::take(&mut buffer)).await;
+                }
+            }
+            _ = interval.tick() => {
+                ingest_batch(&listener, std::mem::take(&mut buffer)).await;
+            }
+        }
+    }
+}
+
+async fn ingest_batch(listener: &Listener, records: Vec<json::Value>) {
+    if records.is_empty() {
+        return;
+    }
+    let (org_id, stream_name) = (&listener.org_id, &listener.stream_name);
+    let count = records.len();
+    match super::ingest::ingest(
+        0,
+        org_id,
+        stream_name,
+        IngestionRequest::JsonValues(listener.value_type, records),
+        IngestUser::SystemJob(listener.job),
+        None,
+        false,
+    )
+    .await
+    {
+        Ok(resp) if resp.code == 200 => {}
+        Ok(resp) => log::error!(
+            "[{}] failed to ingest {count} GELF messages into {org_id}/{stream_name}: {}",
+            listener.name,
+            resp.error.unwrap_or_default()
+        ),
+        Err(e) => log::error!(
+            "[{}] failed to ingest {count} GELF messages into {org_id}/{stream_name}: {e}",
+            listener.name
+        ),
+    }
+}
+
+/// Ingests GELF messages posted over HTTP, the body may be gzip or zlib
+/// compressed
+pub async fn ingest(
+    thread_id: usize,
+    org_id: &str,
+    stream_name: &str,
+    body: Bytes,
+    user_email: &str,
+) -> Result<IngestionResponse> {
+    let records = parse_messages(&body)?;
+    if records.is_empty() {
+        return Ok(IngestionResponse::new(200, vec![]));
+    }
+    super::ingest::ingest(
+        thread_id,
+        org_id,
+        stream_name,
+        IngestionRequest::JsonValues(IngestionValueType::Gelf, records),
+        IngestUser::from_user_email(user_email),
+        None,
+        false,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{
+        Compression,
+        write::{GzEncoder, ZlibEncoder},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_to_record() {
+        let message = json::json!({
+            "version": "1.1",
+            "host": "web-1",
+            "short_message": "GET /health 200",
+            "full_message": "GET /health 200\nuser-agent: curl",
+            "timestamp": 1485283346.25,
+            "level": 3,
+            "_id": "dropped",
+            "_user_id": 42,
+            "_host": "shadowed",
+            "_": "empty"
+        });
+        let record = to_record(message).unwrap();
+        assert_eq!(record["message"], "GET /health 200");
+        assert_eq!(record["full_message"], "GET /health 200\nuser-agent: curl");
+        assert_eq!(record["_timestamp"], 1_485_283_346_250_000i64);
+        assert_eq!(record["level"], 3);
+        assert_eq!(record["severity"], "error");
+        assert_eq!(record["user_id"], 42);
+        assert_eq!(record["host"], "web-1");
+        assert_eq!(record["_"], "empty");
+        assert!(record.get("version").is_none());
+        assert!(record.get("id").is_none());
+        assert!(record.get("short_message").is_none());
+
+        let record = to_record(json::json!({"short_message": "x", "level": 9})).unwrap();
+        assert_eq!(record["level"], 9);
+        assert!(record.get("severity").is_none());
+        assert!(to_record(json::json!("not an object")).is_none());
+    }
+
+    #[test]
+    fn test_decode_payload_gzip() {
+        let mut enc = GzEncoder::new(Vec::new(), Compression::default());
+        enc.write_all(br#"{"short_message":"zipped"}"#).unwrap();
+        let payload = enc.finish().unwrap();
+        let v = decode_payload(&payload).unwrap();
+        assert_eq!(v["short_message"], "zipped");
+        assert!(decode_payload(b"not json").is_err());
+    }
+
+    #[test]
+    fn test_parse_messages() {
+        let body = b"{\"short_message\":\"a\"}\n{\"short_message\":\"b\"}{\"short_message\":\"c\"}";
+        let records = parse_messages(body).unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[2]["message"], "c");
+
+        let mut enc = ZlibEncoder::new(Vec::new(), Compression::default());
+        enc.write_all(body).unwrap();
+        assert_eq!(parse_messages(&enc.finish().unwrap()).unwrap().len(), 3);
+
+        assert!(parse_messages(b"").unwrap().is_empty());
+        assert!(parse_messages(b"[1, 2]").is_err());
+        assert!(parse_messages(b"{\"short_message\":").is_err());
+    }
+
+    #[test]
+    fn test_chunks() {
+        let peer: SocketAddr = "127.0.0.1:5000".parse().unwrap();
+        let mut chunks = Chunks::default();
+        let body = br#"{"short_message":"chunked"}"#;
+        let (a, b) = body.split_at(10);
+        let chunk = |seq: u8, data: &[u8]| {
+            let mut v = CHUNK_MAGIC.to_vec();
+            v.extend_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8, seq, 2]);
+            v.extend_from_slice(data);
+            v
+        };
+        // out of order and duplicated chunks
+        assert!(chunks.push(peer, &chunk(1, b)).is_none());
+        assert!(chunks.push(peer, &chunk(1, b)).is_none());
+        assert_eq!(chunks.push(peer, &chunk(0, a)).unwrap(), body.to_vec());
+        assert!(chunks.pending.is_empty());
+        // unchunked messages pass through
+        assert_eq!(chunks.push(peer, body).unwrap(), body.to_vec());
+    }
+
+    #[test]
+    fn test_decompress_limit() {
+        let mut enc = GzEncoder::new(Vec::new(), Compression::default());
+        enc.write_all(&[b' '; 1024]).unwrap();
+        let payload = enc.finish().unwrap();
+        assert_eq!(decompress(&payload, 1024).unwrap().len(), 1024);
+        assert!(decompress(&payload, 1023).is_err());
+        assert!(decompress(b"{}", 1).is_err());
+    }
+
+    #[test]
+    fn test_chunks_limit() {
+        let mut chunks = Chunks::default();
+        let chunk = |seq: u8, data: &[u8]| {
+            let mut v = CHUNK_MAGIC.to_vec();
+            v.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0, seq, 2]);
+            v.extend_from_slice(data);
+            v
+        };
+        for i in 0..MAX_PENDING_MESSAGES {
+            let peer = SocketAddr::from(([127, 0, 0, 1], i as u16 + 1));
+            assert!(chunks.push(peer, &chunk(0, b"{")).is_none());
+        }
+        assert_eq!(chunks.size, MAX_PENDING_MESSAGES);
+        // a new message is dropped, a pending one completes
+        let peer = SocketAddr::from(([127, 0, 0, 1], 1));
+        let other = SocketAddr::from(([127, 0, 0, 2], 1));
+        assert!(chunks.push(other, &chunk(0, b"{")).is_none());
+        assert!(!chunks.pending.contains_key(&(other, [0; 8])));
+        assert_eq!(chunks.push(peer, &chunk(1, b"}")).unwrap(), b"{}".to_vec());
+        assert_eq!(chunks.size, MAX_PENDING_MESSAGES - 1);
+    }
+}
@@ -12,12 +12,22 @@
 //
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
-use std::io::{BufRead, BufReader};
+use std::{
+    collections::BTreeMap,
+    io::{BufRead, BufReader},
+};
 
 use axum::body::Bytes;
-use config::{get_config, meta::stream::StreamType, utils::json};
+use config::{
+    get_config,
+    meta::stream::StreamType,
+    utils::{json, time::now_micros},
+};
 use hashbrown::HashMap;
 use infra::errors::Result;
+use ingester::WalCommit;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
 use serde::Deserialize;
 
 use crate::{
@@ -27,6 +37,24 @@ use crate::{
     service::ingestion::check_ingestion_allowed,
 };
 
+/// Indexer acknowledgement: a request sent on a channel gets an ack id, which
+/// is reported as acknowledged once the records of the request are in the
+/// WAL, and forgotten after that as Splunk does. The ids are only known by
+/// the node that took the request, so the load balancer has to keep the
+/// requests of a channel on the same node.
+static CHANNELS: Lazy<RwLock<HashMap<String, Channel>>> = Lazy::new(Default::default);
+/// Ids not reported yet kept per channel, the oldest are dropped first
+const MAX_PENDING_ACKS: usize = 10_000;
+/// Channels without any request for 10 minutes are dropped
+const CHANNEL_IDLE_TIMEOUT: i64 = 600 * 1_000_000;
+
+#[derive(Default)]
+struct Channel {
+    next_id: u64,
+    pending: BTreeMap<u64, WalCommit>,
+    updated_at: i64,
+}
+
 #[derive(Deserialize, Clone)]
 struct HecEntry {
     index: Option<String>,
@@ -40,6 +68,7 @@ pub async fn ingest(
     org_id: &str,
     body: Bytes,
     user_email: &str,
+    channel: Option<&str>,
 ) -> Result<HecResponse> {
     // check system resource
     if check_ingestion_allowed(org_id, StreamType::Logs, None)
@@ -102,6 +131,7 @@ pub async fn ingest(
         streams.entry(index).or_default().push(data);
     }
 
+    let commit = channel.map(|_| WalCommit::begin());
     for (stream, entries) in streams {
         let in_req = IngestionRequest::JsonValues(IngestionValueType::Hec, entries);
         if let Err(e) = super::ingest::ingest(
@@ -119,7 +149,49 @@ pub async fn ingest(
         }
     }
 
-    Ok(HecStatus::Success.into())
+    let mut resp: HecResponse = HecStatus::Success.into();
+    if let (Some(channel), Some(mut commit)) = (channel, commit) {
+        commit.end().await;
+        resp.ack_id = Some(register_ack(channel, commit, now_micros()));
+    }
+    Ok(resp)
+}
+
+pub(crate) fn register_ack(channel: &str, commit: WalCommit, now: i64) -> u64 {
+    let mut channels = CHANNELS.write();
+    channels.retain(|_, c| now - c.updated_at < CHANNEL_IDLE_TIMEOUT);
+    let c = channels.entry_ref(channel).or_default();
+    let ack_id = c.next_id;
+    c.next_id += 1;
+    c.updated_at = now;
+    c.pending.insert(ack_id, commit);
+    if c.pending.len() > MAX_PENDING_ACKS {
+        c.pending.pop_first();
+    }
+    ack_id
+}
+
+/// Returns whether the records of each ack id of the channel are in the WAL,
+/// unknown ids are not acknowledged
+pub fn query_acks(channel: &str, ack_ids: &[u64]) -> std::collections::HashMap<String, bool> {
+    let mut channels = CHANNELS.write();
+    let mut c = channels.get_mut(channel);
+    if let Some(c) = c.as_mut() {
+        c.updated_at = now_micros();
+    }
+    ack_ids
+        .iter()
+        .map(|ack_id| {
+            let acked = c.as_mut().is_some_and(|c| {
+                let acked = c.pending.get(ack_id).is_some_and(|v| v.is_committed());
+                if acked {
+                    c.pending.remove(ack_id);
+                }
+                acked
+            });
+            (ack_id.to_string(), acked)
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -135,7 +207,7 @@ mod tests {
         let org_id = "test-org";
         let user_email = "test@example.com";
 
-        let result = ingest(thread_id, org_id, body, user_email).await;
+        let result = ingest(thread_id, org_id, body, user_email, None).await;
 
         match result {
             Ok(response) => {
@@ -148,4 +220,33 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_hec_acks() {
+        let channel = "test_hec_acks";
+        let now = now_micros();
+        let first = register_ack(channel, WalCommit::default(), now);
+        let second = register_ack(channel, WalCommit::default(), now);
+        assert_eq!(second, first + 1);
+
+        let acks = query_acks(channel, &[first, second, second + 1]);
+        assert!(acks[&first.to_string()]);
+        assert!(acks[&second.to_string()]);
+        assert!(!acks[&(second + 1).to_string()]);
+        // an acknowledged id is reported once
+        assert!(!query_acks(channel, &[first])[&first.to_string()]);
+        assert!(!query_acks("test_hec_acks_unknown", &[0])["0"]);
+    }
+
+    #[test]
+    fn test_hec_ack_channel_timeout() {
+        let channel = "test_hec_ack_channel_timeout";
+        register_ack(channel, WalCommit::default(), 0);
+        register_ack(
+            "test_hec_ack_channel_other",
+            WalCommit::default(),
+            CHANNEL_IDLE_TIMEOUT,
+        );
+        assert!(!CHANNELS.read().contains_key(channel));
+    }
 }
@@ -60,6 +60,7 @@ use crate::{
         ingestion::check_ingestion_allowed,
         logs::bulk::TRANSFORM_FAILED,
         schema::{get_future_discard_error, get_upto_discard_error},
+        zpages,
     },
 };
 
@@ -170,6 +171,66 @@ pub async fn ingest(
             UsageType::Loki,
             IngestionData::JSON(logs),
         ),
+        IngestionRequest::JsonValues(IngestionValueType::Flow, logs) => (
+            "/api/org/ingest/logs/_flow",
+            UsageType::Flow,
+            IngestionData::JSON(logs),
+        ),
+        IngestionRequest::JsonValues(IngestionValueType::K8sEvents, logs) => (
+            "/api/org/ingest/logs/_k8s_events",
+            UsageType::K8sEvents,
+            IngestionData::JSON(logs),
+        ),
+        IngestionRequest::JsonValues(IngestionValueType::Docker, logs) => (
+            "/api/org/ingest/logs/_docker",
+            UsageType::Docker,
+            IngestionData::JSON(logs),
+        ),
+        IngestionRequest::JsonValues(IngestionValueType::Logplex, logs) => (
+            "/api/org/ingest/logs/_logplex",
+            UsageType::Syslog,
+            IngestionData::JSON(logs),
+        ),
+        IngestionRequest::JsonValues(IngestionValueType::AccessLogs, logs) => (
+            "/api/org/ingest/logs/_access_logs",
+            UsageType::AccessLogs,
+            IngestionData::JSON(logs),
+        ),
+        IngestionRequest::JsonValues(IngestionValueType::Webhook, logs) => (
+            "/api/org/ingest/logs/_webhook",
+            UsageType::Webhook,
+            IngestionData::JSON(logs),
+        ),
+        IngestionRequest::JsonValues(IngestionValueType::Kafka, logs) => (
+            "/api/org/ingest/logs/_kafka",
+            UsageType::Kafka,
+            IngestionData::JSON(logs),
+        ),
+        IngestionRequest::JsonValues(IngestionValueType::Gelf, logs) => (
+            "/api/org/ingest/logs/_gelf",
+            UsageType::Gelf,
+            IngestionData::JSON(logs),
+        ),
+        IngestionRequest::JsonValues(IngestionValueType::Kinesis, logs) => (
+            "/api/org/ingest/logs/_kinesis",
+            UsageType::Kinesis,
+            IngestionData::JSON(logs),
+        ),
+        IngestionRequest::JsonValues(IngestionValueType::Mqtt, logs) => (
+            "/api/org/ingest/logs/_mqtt",
+            UsageType::Mqtt,
+            IngestionData::JSON(logs),
+        ),
+        IngestionRequest::JsonValues(IngestionValueType::Rabbitmq, logs) => (
+            "/api/org/ingest/logs/_rabbitmq",
+            UsageType::Rabbitmq,
+            IngestionData::JSON(logs),
+        ),
+        IngestionRequest::JsonValues(IngestionValueType::RedisStreams, logs) => (
+            "/api/org/ingest/logs/_redis_streams",
+            UsageType::RedisStreams,
+            IngestionData::JSON(logs),
+        ),
         IngestionRequest::GCP(req) => (
             "/api/org/ingest/logs/_gcs",
             UsageType::GCPSubscription,
@@ -497,6 +558,12 @@ pub async fn ingest(
 
     // if no data, fast return
     if json_data_by_stream.is_empty() {
+        zpages::record_receiver(
+            zpages::receiver_name(endpoint),
+            0,
+            stream_status.status.failed as usize,
+            0,
+        );
         return Ok(IngestionResponse::new(
             http::StatusCode::OK.into(),
             vec![stream_status],
@@ -529,6 +596,11 @@ pub async fn ingest(
         }
     }
 
+    let handed_records = json_data_by_stream
+        .values()
+        .map(|(records, _)| records.len())
+        .sum::<usize>();
+    let refused_before_write = stream_status.status.failed as usize;
     let (metric_rpt_status_code, response_body) = {
         let mut status = if usage_type == UsageType::Bulk {
             IngestionStatus::Bulk(BulkResponse {
@@ -568,6 +640,29 @@ pub async fn ingest(
         }
     };
 
+    // update receiver stats, the writer reports bulk failures per item
+    let refused_by_writer = if usage_type == UsageType::Bulk {
+        response_body
+            .items
+            .iter()
+            .map(|i| i.values().filter(|res| res.error.is_some()).count())
+            .sum::<usize>()
+    } else {
+        (response_body.status.failed as usize).saturating_sub(refused_before_write)
+    };
+    let written = handed_records.saturating_sub(refused_by_writer);
+    let (accepted, dropped) = if metric_rpt_status_code == "200" {
+        (written, 0)
+    } else {
+        (0, written)
+    };
+    zpages::record_receiver(
+        zpages::receiver_name(endpoint),
+        accepted,
+        refused_before_write + refused_by_writer,
+        dropped,
+    );
+
     // update ingestion metrics
     let took_time = start.elapsed().as_secs_f64();
     metrics::HTTP_RESPONSE_TIME
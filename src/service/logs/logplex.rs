@@ -0,0 +1,375 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Heroku / Cloud Foundry Logplex HTTPS drain
+//!
+//! Logplex posts `application/logplex-1` bodies made of octet counted RFC5424
+//! syslog frames (`<len> <syslog message>`), along with the number of
+//! messages in `Logplex-Msg-Count` and a frame id that is reused on retries.
+
+use std::collections::{HashSet, VecDeque};
+
+use axum::body::Bytes;
+use config::{
+    TIMESTAMP_COL_NAME,
+    utils::{json, time::parse_str_to_timestamp_micros_as_option},
+};
+use infra::errors::{Error, Result};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+use crate::common::meta::ingestion::{
+    IngestUser, IngestionRequest, IngestionResponse, IngestionValueType,
+};
+
+/// Number of recent frame ids remembered to drop retried frames
+const FRAME_ID_CACHE_SIZE: usize = 10000;
+
+static RECENT_FRAMES: Lazy<Mutex<RecentFrames>> = Lazy::new(Default::default);
+
+#[derive(Default)]
+struct RecentFrames {
+    ids: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+impl RecentFrames {
+    fn contains(&self, id: &str) -> bool {
+        self.ids.contains(id)
+    }
+
+    fn insert(&mut self, id: &str) {
+        if !self.ids.insert(id.to_string()) {
+            return;
+        }
+        self.order.push_back(id.to_string());
+        if self.order.len() > FRAME_ID_CACHE_SIZE
+            && let Some(old) = self.order.pop_front()
+        {
+            self.ids.remove(&old);
+        }
+    }
+}
+
+/// Logplex request headers
+#[derive(Debug, Default)]
+pub struct LogplexHeaders {
+    pub msg_count: Option<usize>,
+    pub frame_id: Option<String>,
+    pub drain_token: Option<String>,
+}
+
+pub async fn ingest(
+    thread_id: usize,
+    org_id: &str,
+    stream_name: &str,
+    body: Bytes,
+    headers: LogplexHeaders,
+    user_email: &str,
+) -> Result<IngestionResponse> {
+    if let Some(frame_id) = headers.frame_id.as_deref()
+        && RECENT_FRAMES.lock().contains(frame_id)
+    {
+        log::debug!("[LOGPLEX] dropping retried frame {frame_id} for {org_id}/{stream_name}");
+        return Ok(IngestionResponse::new(200, vec![]));
+    }
+
+    let body = std::str::from_utf8(&body)
+        .map_err(|e| Error::IngestionError(format!("logplex body is not utf-8: {e}")))?;
+    let messages = split_frames(body)?;
+    if let Some(count) = headers.msg_count
+        && count != messages.len()
+    {
+        return Err(Error::IngestionError(format!(
+            "Logplex-Msg-Count is {count} but the body has {} messages",
+            messages.len()
+        )));
+    }
+
+    let records = messages
+        .into_iter()
+        .map(|msg| {
+            let mut record = parse_syslog(msg);
+            if let Some(token) = headers.drain_token.as_deref() {
+                record.insert("logplex_drain_token".to_string(), token.into());
+            }
+            if let Some(frame_id) = headers.frame_id.as_deref() {
+                record.insert("logplex_frame_id".to_string(), frame_id.into());
+            }
+            json::Value::Object(record)
+        })
+        .collect::<Vec<_>>();
+
+    let resp = super::ingest::ingest(
+        thread_id,
+        org_id,
+        stream_name,
+        IngestionRequest::JsonValues(IngestionValueType::Logplex, records),
+        IngestUser::from_user_email(user_email),
+        None,
+        false,
+    )
+    .await?;
+    if resp.code == 200
+        && let Some(frame_id) = headers.frame_id.as_deref()
+    {
+        RECENT_FRAMES.lock().insert(frame_id);
+    }
+    Ok(resp)
+}
+
+/// Splits an octet counted body into syslog messages. Bodies that don't start
+/// with a length prefix are treated as one message per line, which is what
+/// Cloud Foundry HTTPS drains send.
+fn split_frames(body: &str) -> Result<Vec<&str>> {
+    let body = body.trim_start();
+    if !body.starts_with(|c: char| c.is_ascii_digit()) {
+        return Ok(body
+            .lines()
+            .map(|v| v.trim_end())
+            .filter(|v| !v.is_empty())
+            .collect());
+    }
+
+    let mut messages = Vec::new();
+    let mut rest = body;
+    while !rest.trim().is_empty() {
+        rest = rest.trim_start();
+        let Some((len, tail)) = rest.split_once(' ') else {
+            return Err(Error::IngestionError(
+                "invalid logplex frame: missing length".to_string(),
+            ));
+        };
+        let len: usize = len
+            .parse()
+            .map_err(|_| Error::IngestionError(format!("invalid logplex frame length: {len}")))?;
+        if tail.len() < len || !tail.is_char_boundary(len) {
+            return Err(Error::IngestionError(format!(
+                "invalid logplex frame: expected {len} bytes, got {}",
+                tail.len()
+            )));
+        }
+        let (msg, tail) = tail.split_at(len);
+        messages.push(msg.trim_end_matches('\n'));
+        rest = tail;
+    }
+    Ok(messages)
+}
+
+const SEVERITIES: [&str; 8] = [
+    "emergency",
+    "alert",
+    "critical",
+    "error",
+    "warning",
+    "notice",
+    "info",
+    "debug",
+];
+
+/// Parses an RFC5424 message. Heroku omits the structured data element, so it
+/// is only read when present. Anything that doesn't look like syslog is kept
+/// verbatim in `message`.
+fn parse_syslog(msg: &str) -> json::Map<String, json::Value> {
+    let mut record = json::Map::new();
+    let Some((pri, rest)) = msg
+        .strip_prefix('<')
+        .and_then(|v| v.split_once('>'))
+        .and_then(|(pri, rest)| pri.parse::<u8>().ok().map(|pri| (pri, rest)))
+    else {
+        record.insert("message".to_string(), msg.into());
+        return record;
+    };
+    record.insert("facility".to_string(), (pri >> 3).into());
+    record.insert(
+        "severity".to_string(),
+        SEVERITIES[(pri & 0x07) as usize].into(),
+    );
+
+    // VERSION TIMESTAMP HOSTNAME APP-NAME PROCID MSGID
+    let mut parts = rest.splitn(7, ' ');
+    let _version = parts.next();
+    let header = ["timestamp", "hostname", "appname", "procid", "msgid"];
+    for key in header {
+        let Some(v) = parts.next() else {
+            break;
+        };
+        if v == "-" {
+            continue;
+        }
+        if key == "timestamp" {
+            if let Some(ts) = parse_str_to_timestamp_micros_as_option(v) {
+                record.insert(TIMESTAMP_COL_NAME.to_string(), ts.into());
+            }
+            continue;
+        }
+        record.insert(key.to_string(), v.into());
+    }
+
+    let rest = parts.next().unwrap_or_default();
+    let message = if rest.starts_with('[') {
+        parse_structured_data(rest, &mut record)
+    } else {
+        rest
+    };
+    record.insert("message".to_string(), message.trim_end().into());
+    record
+}
+
+/// Flattens `[id key="value" ...]` elements into `{id}_{key}` fields and
+/// returns the message that follows them
+fn parse_structured_data<'a>(
+    mut rest: &'a str,
+    record: &mut json::Map<String, json::Value>,
+) -> &'a str {
+    while let Some(element) = rest.strip_prefix('[') {
+        let mut end = None;
+        let mut in_quotes = false;
+        let mut escaped = false;
+        for (i, c) in element.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_quotes = !in_quotes,
+                ']' if !in_quotes => {
+                    end = Some(i);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let Some(end) = end else {
+            return rest;
+        };
+        let (id, params) = element[..end]
+            .split_once(' ')
+            .unwrap_or((&element[..end], ""));
+        let id = sanitize_key(id.split('@').next().unwrap_or(id));
+        for (key, value) in parse_sd_params(params) {
+            record.insert(format!("{id}_{}", sanitize_key(key)), value.into());
+        }
+        rest = &element[end + 1..];
+    }
+    rest.strip_prefix(' ').unwrap_or(rest)
+}
+
+fn parse_sd_params(mut params: &str) -> Vec<(&str, String)> {
+    let mut out = Vec::new();
+    loop {
+        params = params.trim_start();
+        let Some((key, tail)) = params.split_once("=\"") else {
+            return out;
+        };
+        let mut value = String::new();
+        let mut escaped = false;
+        let mut end = tail.len();
+        for (i, c) in tail.char_indices() {
+            match c {
+                _ if escaped => {
+                    value.push(c);
+                    escaped = false;
+                }
+                '\\' => escaped = true,
+                '"' => {
+                    end = i;
+                    break;
+                }
+                _ => value.push(c),
+            }
+        }
+        out.push((key, value));
+        params = tail.get(end + 1..).unwrap_or_default();
+    }
+}
+
+fn sanitize_key(key: &str) -> String {
+    key.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_frames() {
+        let body = "83 <40>1 2012-11-30T06:45:29+00:00 host app web.3 - State changed from starting to up\n119 <40>1 2012-11-30T06:45:26+00:00 host app web.3 - Starting process with command `bundle exec rackup config.ru -p 24405`\n";
+        let messages = split_frames(body).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert!(messages[0].ends_with("State changed from starting to up"));
+        assert!(messages[1].ends_with("-p 24405`"));
+    }
+
+    #[test]
+    fn test_split_frames_invalid() {
+        assert!(split_frames("500 <40>1 too short").is_err());
+        assert!(split_frames("12x <40>1").is_err());
+    }
+
+    #[test]
+    fn test_split_frames_lines() {
+        let body = "<14>1 2024-01-01T00:00:00Z host app web - - one\n<14>1 2024-01-01T00:00:01Z host app web - - two\n";
+        assert_eq!(split_frames(body).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_parse_heroku_message() {
+        let record = parse_syslog(
+            "<40>1 2012-11-30T06:45:29+00:00 host app web.3 - State changed from starting to up",
+        );
+        assert_eq!(record["facility"], 5);
+        assert_eq!(record["severity"], "emergency");
+        assert_eq!(record["hostname"], "host");
+        assert_eq!(record["appname"], "app");
+        assert_eq!(record["procid"], "web.3");
+        assert!(record.get("msgid").is_none());
+        assert_eq!(record["message"], "State changed from starting to up");
+        assert_eq!(record["_timestamp"], 1_354_257_929_000_000i64);
+    }
+
+    #[test]
+    fn test_parse_structured_data() {
+        let record = parse_syslog(
+            r#"<14>1 2024-01-01T00:00:00Z cf-host 1a2b [APP/PROC/WEB/0] - [tags@47450 app_name="my \"app\"" space_name="dev"][gauge@47450 name="cpu" value="0.5"] request done"#,
+        );
+        assert_eq!(record["severity"], "info");
+        assert_eq!(record["procid"], "[APP/PROC/WEB/0]");
+        assert_eq!(record["tags_app_name"], "my \"app\"");
+        assert_eq!(record["tags_space_name"], "dev");
+        assert_eq!(record["gauge_value"], "0.5");
+        assert_eq!(record["message"], "request done");
+    }
+
+    #[test]
+    fn test_parse_non_syslog() {
+        let record = parse_syslog("plain text");
+        assert_eq!(record["message"], "plain text");
+        assert_eq!(record.len(), 1);
+    }
+
+    #[test]
+    fn test_recent_frames() {
+        let mut frames = RecentFrames::default();
+        frames.insert("a");
+        frames.insert("a");
+        assert!(frames.contains("a"));
+        for i in 0..FRAME_ID_CACHE_SIZE {
+            frames.insert(&i.to_string());
+        }
+        assert!(!frames.contains("a"));
+        assert_eq!(frames.order.len(), FRAME_ID_CACHE_SIZE);
+    }
+}
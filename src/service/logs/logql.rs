@@ -0,0 +1,645 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! LogQL log queries for the Loki compatible read API. The stream selector
+//! picks the log stream the same way the push API names it, the other label
+//! matchers and the line filters become the WHERE clause of a search over it.
+
+use std::collections::{BTreeMap, HashMap};
+
+use config::{
+    ALL_VALUES_COL_NAME, ID_COL_NAME, MESSAGE_COL_NAME, ORIGINAL_DATA_COL_NAME, STREAM_NAME_LABEL,
+    TIMESTAMP_COL_NAME,
+    meta::{
+        search::{self as meta_search, default_use_cache},
+        stream::StreamType,
+    },
+    utils::json,
+};
+use datafusion::arrow::datatypes::Schema;
+use infra::{cache::stats, errors::Result};
+use promql_parser::{
+    label::{MatchOp, Matcher},
+    parser::{self, Expr as PromExpr},
+};
+
+use super::loki::determine_service_stream_name;
+use crate::{
+    common::meta::{
+        loki::{LokiStreamResult, LokiStreamsData},
+        stream::StreamSchema,
+    },
+    service::{db, search as search_service},
+};
+
+/// Max number of distinct values returned for a label
+const LABEL_VALUES_LIMIT: i64 = 1000;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LineFilterOp {
+    /// `|=`
+    Contains,
+    /// `!=`
+    NotContains,
+    /// `|~`
+    Regex,
+    /// `!~`
+    NotRegex,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineFilter {
+    pub op: LineFilterOp,
+    pub value: String,
+}
+
+/// A parsed LogQL log query: `{label="value", ...} |= "text" !~ "regex"`
+#[derive(Clone, Debug, Default)]
+pub struct LogQuery {
+    pub matchers: Vec<Matcher>,
+    pub filters: Vec<LineFilter>,
+}
+
+impl LogQuery {
+    /// Log stream the selector points to, named like the push API does
+    pub fn stream_name(&self) -> String {
+        let labels = self
+            .matchers
+            .iter()
+            .filter(|m| m.op == MatchOp::Equal)
+            .map(|m| (m.name.clone(), m.value.clone()))
+            .collect::<HashMap<_, _>>();
+        determine_service_stream_name(&labels)
+    }
+
+    /// SQL conditions for the stream schema, `None` when the query can not
+    /// match anything, e.g. a label that is not in the stream must be set
+    fn conditions(&self, schema: &Schema) -> Option<Vec<String>> {
+        let mut conditions = Vec::new();
+        for m in self.matchers.iter() {
+            // the stream is already selected by its name
+            if m.name == STREAM_NAME_LABEL && m.op == MatchOp::Equal {
+                continue;
+            }
+            if schema.field_with_name(&m.name).is_err() {
+                // a missing label has the empty value
+                if matches_empty(m) {
+                    continue;
+                }
+                return None;
+            }
+            let column = quote_ident(&m.name);
+            let value = quote_str(&m.value);
+            conditions.push(match &m.op {
+                MatchOp::Equal => format!("{column} = {value}"),
+                MatchOp::NotEqual => format!("({column} IS NULL OR {column} != {value})"),
+                MatchOp::Re(_) => {
+                    format!("re_match({column}, {})", quote_str(&anchored(&m.value)))
+                }
+                MatchOp::NotRe(_) => {
+                    format!("re_not_match({column}, {})", quote_str(&anchored(&m.value)))
+                }
+            });
+        }
+
+        let has_message = schema.field_with_name(MESSAGE_COL_NAME).is_ok();
+        let column = quote_ident(MESSAGE_COL_NAME);
+        for f in self.filters.iter() {
+            let value = quote_str(&f.value);
+            // without a message column no line contains anything
+            if !has_message {
+                match f.op {
+                    LineFilterOp::Contains | LineFilterOp::Regex => return None,
+                    LineFilterOp::NotContains | LineFilterOp::NotRegex => continue,
+                }
+            }
+            conditions.push(match f.op {
+                LineFilterOp::Contains => format!("str_match({column}, {value})"),
+                LineFilterOp::NotContains => format!("NOT str_match({column}, {value})"),
+                LineFilterOp::Regex => format!("re_match({column}, {value})"),
+                LineFilterOp::NotRegex => format!("re_not_match({column}, {value})"),
+            });
+        }
+        Some(conditions)
+    }
+
+    /// SQL returning the matching log lines, `None` when nothing can match
+    pub fn to_sql(&self, stream_name: &str, schema: &Schema, forward: bool) -> Option<String> {
+        let conditions = self.conditions(schema)?;
+        let mut sql = format!("SELECT * FROM {}", quote_ident(stream_name));
+        if !conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&conditions.join(" AND "));
+        }
+        sql.push_str(&format!(
+            " ORDER BY {} {}",
+            quote_ident(TIMESTAMP_COL_NAME),
+            if forward { "ASC" } else { "DESC" }
+        ));
+        Some(sql)
+    }
+}
+
+/// Parses a LogQL log query, metric queries and parser stages are not
+/// supported
+pub fn parse(query: &str) -> Result<LogQuery, String> {
+    let query = query.trim();
+    if !query.starts_with('{') {
+        return Err("only log queries starting with a stream selector are supported".to_string());
+    }
+    let end = selector_end(query).ok_or_else(|| "unterminated stream selector".to_string())?;
+    let selector = &query[..=end];
+
+    let matchers = if selector[1..end].trim().is_empty() {
+        vec![]
+    } else {
+        match parser::parse(&format!("dummy{selector}")) {
+            Ok(PromExpr::VectorSelector(vs)) => vs.matchers.matchers,
+            Ok(_) => return Err(format!("invalid stream selector: {selector}")),
+            Err(e) => return Err(format!("invalid stream selector: {e}")),
+        }
+    };
+
+    let mut filters = Vec::new();
+    let mut rest = query[end + 1..].trim_start();
+    while !rest.is_empty() {
+        let op = match rest.get(..2) {
+            Some("|=") => LineFilterOp::Contains,
+            Some("!=") => LineFilterOp::NotContains,
+            Some("|~") => LineFilterOp::Regex,
+            Some("!~") => LineFilterOp::NotRegex,
+            _ => {
+                return Err(format!(
+                    "unsupported expression `{rest}`, only line filters are supported after the \
+                     stream selector"
+                ));
+            }
+        };
+        let (value, remaining) = parse_string(rest[2..].trim_start())?;
+        if matches!(op, LineFilterOp::Regex | LineFilterOp::NotRegex)
+            && let Err(e) = regex::Regex::new(&value)
+        {
+            return Err(format!("invalid line filter regex: {e}"));
+        }
+        filters.push(LineFilter { op, value });
+        rest = remaining.trim_start();
+    }
+
+    Ok(LogQuery { matchers, filters })
+}
+
+/// Runs a log query over `[start, end)` in microseconds, returning at most
+/// `limit` entries grouped by label set
+pub async fn query_range(
+    org_id: &str,
+    user_id: &str,
+    query: &LogQuery,
+    start: i64,
+    end: i64,
+    limit: i64,
+    forward: bool,
+) -> Result<LokiStreamsData> {
+    let mut data = LokiStreamsData {
+        result_type: "streams".to_string(),
+        result: vec![],
+        stats: json::json!({}),
+    };
+    let stream_name = query.stream_name();
+    let schema = infra::schema::get(org_id, &stream_name, StreamType::Logs).await?;
+    if schema.fields().is_empty() {
+        return Ok(data);
+    }
+    let Some(sql) = query.to_sql(&stream_name, &schema, forward) else {
+        return Ok(data);
+    };
+
+    let resp = search_service::search(
+        "",
+        org_id,
+        StreamType::Logs,
+        Some(user_id.to_string()),
+        &search_request(sql, start, end, limit),
+    )
+    .await?;
+    data.result = group_streams(resp.hits);
+    Ok(data)
+}
+
+/// Label names of the selected stream, or of every log stream of the user
+/// with data in the time range
+pub async fn get_labels(
+    org_id: &str,
+    user_id: &str,
+    query: Option<&LogQuery>,
+    start: i64,
+    end: i64,
+) -> Result<Vec<String>> {
+    let schemas = match query {
+        Some(query) => {
+            let stream_name = query.stream_name();
+            vec![infra::schema::get(org_id, &stream_name, StreamType::Logs).await?]
+        }
+        None => list_streams(org_id, user_id, start, end)
+            .await?
+            .into_iter()
+            .map(|s| s.schema)
+            .collect(),
+    };
+
+    let mut labels = schemas
+        .iter()
+        .flat_map(|s| s.fields().iter().map(|f| f.name()))
+        .filter(|name| is_label(name))
+        .cloned()
+        .collect::<Vec<_>>();
+    labels.push(STREAM_NAME_LABEL.to_string());
+    labels.sort();
+    labels.dedup();
+    Ok(labels)
+}
+
+/// Values of a label in the selected stream, the stream name label lists the
+/// log streams of the user with data in the time range
+pub async fn get_label_values(
+    org_id: &str,
+    user_id: &str,
+    label_name: &str,
+    query: Option<&LogQuery>,
+    start: i64,
+    end: i64,
+) -> Result<Vec<String>> {
+    if label_name == STREAM_NAME_LABEL {
+        let mut values = list_streams(org_id, user_id, start, end)
+            .await?
+            .into_iter()
+            .map(|s| s.stream_name)
+            .collect::<Vec<_>>();
+        values.sort();
+        return Ok(values);
+    }
+
+    let query = query.cloned().unwrap_or_default();
+    let stream_name = query.stream_name();
+    let schema = infra::schema::get(org_id, &stream_name, StreamType::Logs).await?;
+    if !is_label(label_name) || schema.field_with_name(label_name).is_err() {
+        return Ok(vec![]);
+    }
+    let Some(conditions) = query.conditions(&schema) else {
+        return Ok(vec![]);
+    };
+
+    let column = quote_ident(label_name);
+    let mut sql = format!(
+        "SELECT DISTINCT {column} FROM {} WHERE {column} IS NOT NULL",
+        quote_ident(&stream_name)
+    );
+    for condition in conditions {
+        sql.push_str(" AND ");
+        sql.push_str(&condition);
+    }
+
+    let resp = search_service::search(
+        "",
+        org_id,
+        StreamType::Logs,
+        Some(user_id.to_string()),
+        &search_request(sql, start, end, LABEL_VALUES_LIMIT),
+    )
+    .await?;
+    let mut values = resp
+        .hits
+        .iter()
+        .filter_map(|hit| hit.get(label_name).and_then(value_to_string))
+        .collect::<Vec<_>>();
+    values.sort();
+    values.dedup();
+    Ok(values)
+}
+
+/// The log streams with data in the time range the user can read
+#[allow(unused_variables)]
+async fn list_streams(
+    org_id: &str,
+    user_id: &str,
+    start: i64,
+    end: i64,
+) -> Result<Vec<StreamSchema>> {
+    #[allow(unused_mut)]
+    let mut streams = db::schema::list(org_id, Some(StreamType::Logs), true)
+        .await
+        .unwrap_or_default();
+    #[cfg(feature = "enterprise")]
+    {
+        use o2_openfga::meta::mapping::OFGA_MODELS;
+
+        let s_type = StreamType::Logs.as_str();
+        let permitted = crate::handler::http::auth::validator::list_objects_for_user(
+            org_id,
+            user_id,
+            "GET",
+            OFGA_MODELS.get(s_type).map_or(s_type, |model| model.key),
+        )
+        .await
+        .map_err(|e| infra::errors::Error::Message(e.to_string()))?;
+        if let Some(permitted) = permitted
+            && !permitted.contains(&format!("{s_type}:_all_{org_id}"))
+        {
+            streams.retain(|s| permitted.contains(&format!("{s_type}:{}", s.stream_name)));
+        }
+    }
+    streams.retain(|s| {
+        stats::get_stream_stats(org_id, &s.stream_name, StreamType::Logs)
+            .time_range_intersects(start, end)
+    });
+    Ok(streams)
+}
+
+fn search_request(sql: String, start: i64, end: i64, size: i64) -> meta_search::Request {
+    meta_search::Request {
+        query: meta_search::Query {
+            sql,
+            from: 0,
+            size,
+            start_time: start,
+            end_time: end,
+            ..Default::default()
+        },
+        encoding: meta_search::RequestEncoding::Empty,
+        regions: vec![],
+        clusters: vec![],
+        timeout: 0,
+        search_type: None,
+        search_event_context: None,
+        use_cache: default_use_cache(),
+        clear_cache: false,
+        local_mode: None,
+    }
+}
+
+/// Groups hits into Loki streams, keeping the order of the hits. Every
+/// scalar field except the timestamp and the message is a label, streams
+/// without a message column get the whole record as the line.
+fn group_streams(hits: Vec<json::Value>) -> Vec<LokiStreamResult> {
+    let mut streams: Vec<LokiStreamResult> = Vec::new();
+    let mut index: HashMap<Vec<(String, String)>, usize> = HashMap::new();
+    for hit in hits {
+        let json::Value::Object(mut record) = hit else {
+            continue;
+        };
+        let ts = record
+            .remove(TIMESTAMP_COL_NAME)
+            .and_then(|v| v.as_i64())
+            .unwrap_or_default();
+        for name in [ID_COL_NAME, ORIGINAL_DATA_COL_NAME, ALL_VALUES_COL_NAME] {
+            record.remove(name);
+        }
+        let line = match record.remove(MESSAGE_COL_NAME) {
+            Some(json::Value::String(v)) => v,
+            Some(v) => v.to_string(),
+            None => json::Value::Object(record.clone()).to_string(),
+        };
+        let labels = record
+            .iter()
+            .filter_map(|(k, v)| value_to_string(v).map(|v| (k.clone(), v)))
+            .collect::<BTreeMap<_, _>>();
+
+        let key = labels
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect::<Vec<_>>();
+        let idx = *index.entry(key).or_insert_with(|| {
+            streams.push(LokiStreamResult {
+                stream: labels,
+                values: vec![],
+            });
+            streams.len() - 1
+        });
+        streams[idx].values.push([(ts * 1000).to_string(), line]);
+    }
+    streams
+}
+
+fn is_label(name: &str) -> bool {
+    ![
+        TIMESTAMP_COL_NAME,
+        MESSAGE_COL_NAME,
+        ID_COL_NAME,
+        ORIGINAL_DATA_COL_NAME,
+        ALL_VALUES_COL_NAME,
+    ]
+    .contains(&name)
+}
+
+fn value_to_string(v: &json::Value) -> Option<String> {
+    match v {
+        json::Value::String(v) => Some(v.clone()),
+        json::Value::Number(v) => Some(v.to_string()),
+        json::Value::Bool(v) => Some(v.to_string()),
+        _ => None,
+    }
+}
+
+fn matches_empty(m: &Matcher) -> bool {
+    match &m.op {
+        MatchOp::Equal => m.value.is_empty(),
+        MatchOp::NotEqual => !m.value.is_empty(),
+        MatchOp::Re(re) => re.is_match(""),
+        MatchOp::NotRe(re) => !re.is_match(""),
+    }
+}
+
+// label matchers match the whole value, line filters any part of the line
+fn anchored(re: &str) -> String {
+    format!("^(?:{re})$")
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn quote_str(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Index of the `}` closing the stream selector, skipping quoted values
+fn selector_end(query: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in query.char_indices() {
+        match quote {
+            Some(q) => {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' && q != '`' {
+                    escaped = true;
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None => match c {
+                '"' | '\'' | '`' => quote = Some(c),
+                '}' => return Some(i),
+                _ => {}
+            },
+        }
+    }
+    None
+}
+
+/// Parses a double quoted or backtick string, returning it and the rest of
+/// the input
+fn parse_string(input: &str) -> Result<(String, &str), String> {
+    let mut chars = input.char_indices();
+    let quote = match chars.next() {
+        Some((_, c)) if c == '"' || c == '`' => c,
+        _ => return Err(format!("expected a quoted string at `{input}`")),
+    };
+    let mut value = String::new();
+    let mut escaped = false;
+    for (i, c) in chars {
+        if escaped {
+            value.push(match c {
+                'n' => '\n',
+                't' => '\t',
+                'r' => '\r',
+                c => c,
+            });
+            escaped = false;
+        } else if c == '\\' && quote == '"' {
+            escaped = true;
+        } else if c == quote {
+            return Ok((value, &input[i + 1..]));
+        } else {
+            value.push(c);
+        }
+    }
+    Err(format!("unterminated string at `{input}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::arrow::datatypes::{DataType, Field};
+
+    use super::*;
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new(TIMESTAMP_COL_NAME, DataType::Int64, false),
+            Field::new(MESSAGE_COL_NAME, DataType::Utf8, true),
+            Field::new("app", DataType::Utf8, true),
+            Field::new("level", DataType::Utf8, true),
+            Field::new(STREAM_NAME_LABEL, DataType::Utf8, true),
+        ])
+    }
+
+    #[test]
+    fn test_parse_selector_and_filters() {
+        let q = parse(
+            r#"{o2_stream_name="k8s", app=~"api|web"} |= "error" != `debug` |~ "tim(e|ed)out""#,
+        )
+        .unwrap();
+        assert_eq!(q.matchers.len(), 2);
+        assert_eq!(q.stream_name(), "k8s");
+        assert_eq!(
+            q.filters,
+            vec![
+                LineFilter {
+                    op: LineFilterOp::Contains,
+                    value: "error".to_string()
+                },
+                LineFilter {
+                    op: LineFilterOp::NotContains,
+                    value: "debug".to_string()
+                },
+                LineFilter {
+                    op: LineFilterOp::Regex,
+                    value: "tim(e|ed)out".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_quoted_braces_and_escapes() {
+        let q = parse(r#"{app="a}b"} |= "say \"hi\"""#).unwrap();
+        assert_eq!(q.matchers[0].value, "a}b");
+        assert_eq!(q.filters[0].value, r#"say "hi""#);
+        assert_eq!(q.stream_name(), "default");
+    }
+
+    #[test]
+    fn test_parse_unsupported() {
+        assert!(parse(r#"rate({app="api"}[5m])"#).is_err());
+        assert!(parse(r#"{app="api"} | json"#).is_err());
+        assert!(parse(r#"{app="api""#).is_err());
+        assert!(parse(r#"{app="api"} |= "unterminated"#).is_err());
+        assert!(parse(r#"{app="api"} |~ "(""#).is_err());
+    }
+
+    #[test]
+    fn test_to_sql() {
+        let q = parse(r#"{o2_stream_name="k8s", app="api", level!="debug"} |= "it's" !~ "x+""#)
+            .unwrap();
+        assert_eq!(
+            q.to_sql("k8s", &schema(), false).unwrap(),
+            "SELECT * FROM \"k8s\" WHERE \"app\" = 'api' AND (\"level\" IS NULL OR \"level\" != \
+             'debug') AND str_match(\"message\", 'it''s') AND re_not_match(\"message\", 'x+') \
+             ORDER BY \"_timestamp\" DESC"
+        );
+
+        let q = parse(r#"{app=~"api|web"}"#).unwrap();
+        assert_eq!(
+            q.to_sql("default", &schema(), true).unwrap(),
+            "SELECT * FROM \"default\" WHERE re_match(\"app\", '^(?:api|web)$') ORDER BY \
+             \"_timestamp\" ASC"
+        );
+    }
+
+    #[test]
+    fn test_to_sql_missing_labels() {
+        // a label that is not in the stream only matches the empty value
+        let q = parse(r#"{app="api", env="prod"}"#).unwrap();
+        assert!(q.to_sql("default", &schema(), false).is_none());
+
+        let q = parse(r#"{app="api", env!="prod", zone=~".*"}"#).unwrap();
+        assert_eq!(
+            q.to_sql("default", &schema(), false).unwrap(),
+            "SELECT * FROM \"default\" WHERE \"app\" = 'api' ORDER BY \"_timestamp\" DESC"
+        );
+    }
+
+    #[test]
+    fn test_group_streams() {
+        let hits = vec![
+            json::json!({"_timestamp": 2, "message": "b", "app": "api", "_o2_id": 1}),
+            json::json!({"_timestamp": 1, "message": "a", "app": "web"}),
+            json::json!({"_timestamp": 0, "message": "c", "app": "api"}),
+            json::json!({"_timestamp": 3, "app": "api", "code": 500}),
+        ];
+        let streams = group_streams(hits);
+        assert_eq!(streams.len(), 3);
+        assert_eq!(streams[0].stream.get("app").unwrap(), "api");
+        assert_eq!(
+            streams[0].values,
+            vec![
+                ["2000".to_string(), "b".to_string()],
+                ["0".to_string(), "c".to_string()]
+            ]
+        );
+        assert_eq!(streams[1].values[0][1], "a");
+        assert_eq!(streams[2].stream.get("code").unwrap(), "500");
+        assert_eq!(streams[2].values[0][1], r#"{"app":"api","code":500}"#);
+    }
+}
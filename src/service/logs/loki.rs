@@ -249,7 +249,7 @@ fn parse_prometheus_labels(labels_str: &str) -> Result<HashMap<String, String>,
     }
 }
 
-fn determine_service_stream_name(labels: &HashMap<String, String>) -> String {
+pub(super) fn determine_service_stream_name(labels: &HashMap<String, String>) -> String {
     labels
         .get(STREAM_NAME_LABEL)
         .map(|name| format_stream_name(name.to_string()))
@@ -44,11 +44,18 @@ use infra::{
 #[cfg(feature = "cloud")]
 use crate::service::stream::get_stream;
 use crate::{
-    common::meta::{ingestion::IngestionStatus, stream::SchemaRecords},
+    common::{
+        infra::config::LOG_METRIC_RULES,
+        meta::{ingestion::IngestionStatus, stream::SchemaRecords},
+    },
     service::{
         alerts::alert::AlertExt,
         db,
-        ingestion::{TriggerAlertData, evaluate_trigger, get_write_partition_key, write_file},
+        ingestion::{
+            TriggerAlertData, clock_skew::ClockSkewTracker, dedup::Deduplicator, evaluate_trigger,
+            get_write_partition_key, level::LevelNormalizer, redaction::Redactor, write_file,
+        },
+        log_metrics,
         metadata::{MetadataItem, MetadataType, distinct_values::DvItem, write},
         schema::{check_for_schema, stream_schema_exists},
         self_reporting::report_request_usage_stats,
@@ -56,8 +63,12 @@ use crate::{
 };
 
 pub mod bulk;
+pub mod docker;
+pub mod gelf;
 pub mod hec;
 pub mod ingest;
+pub mod logplex;
+pub mod logql;
 pub mod loki;
 pub mod otlp;
 pub mod patterns;
@@ -305,7 +316,7 @@ async fn write_logs(
     org_id: &str,
     stream_name: &str,
     status: &mut IngestionStatus,
-    json_data: Vec<(i64, Map<String, Value>)>,
+    mut json_data: Vec<(i64, Map<String, Value>)>,
     is_derived: bool,
 ) -> Result<RequestStats> {
     let cfg = get_config();
@@ -328,7 +339,67 @@ async fn write_logs(
             )));
         }
     };
-    let stream_settings = infra::schema::unwrap_stream_settings(&schema).unwrap_or_default();
+    let stream_settings = infra::schema::unwrap_stream_settings(&schema);
+    let wal_sync_policy = infra::schema::get_stream_setting_wal_sync_policy(&stream_settings);
+    let stream_settings = stream_settings.unwrap_or_default();
+
+    // mask the sensitive values before the records are checked and written
+    if let Some(redactor) = Redactor::new(&stream_settings.redaction_rules) {
+        for (_, record) in json_data.iter_mut() {
+            redactor.redact(record);
+        }
+    }
+
+    if let Some(normalizer) = LevelNormalizer::new(org_id, &stream_settings.level_normalization) {
+        for (_, record) in json_data.iter_mut() {
+            normalizer.normalize(record);
+        }
+    }
+
+    // drop the records re-sent within the dedup window of the stream, they
+    // are reported as successful so the shippers don't send them again
+    let dedup = Deduplicator::new(
+        org_id,
+        StreamType::Logs,
+        stream_name,
+        &stream_settings.ingest_dedup,
+    );
+    let mut dedup_hashes = Vec::new();
+    if let Some(dedup) = dedup.as_ref() {
+        let len = json_data.len();
+        dedup_hashes = dedup.retain(&mut json_data);
+        let removed = len - json_data.len();
+        if removed > 0 {
+            log::debug!("[LOGS] dropped {removed} duplicated records of stream {stream_name}");
+        }
+        if json_data.is_empty() {
+            return Ok(RequestStats::default());
+        }
+    }
+
+    if let Some(tracker) = ClockSkewTracker::new(
+        org_id,
+        StreamType::Logs,
+        stream_name,
+        &stream_settings.clock_skew,
+    ) {
+        let corrected = tracker.process(&mut json_data);
+        if corrected > 0 {
+            log::debug!(
+                "[LOGS] corrected the clock skew of {corrected} records of stream {stream_name}"
+            );
+        }
+    }
+
+    if cfg.common.log_metrics_flush_interval > 0
+        && let Some(rules) = LOG_METRIC_RULES.get(&format!("{org_id}/{stream_name}"))
+    {
+        log_metrics::observe(
+            org_id,
+            rules.value(),
+            json_data.iter().map(|(_, record)| record),
+        );
+    }
 
     let mut partition_keys: Vec<StreamPartition> = vec![];
     let mut partition_time_level = PartitionTimeLevel::from(cfg.limit.logs_file_retention.as_str());
@@ -389,8 +460,10 @@ async fn write_logs(
     let mut distinct_values = Vec::with_capacity(16);
 
     let mut write_buf: HashMap<String, SchemaRecords> = HashMap::new();
+    // the dedup hashes of the records written
+    let mut written_hashes = Vec::with_capacity(dedup_hashes.len());
 
-    for (timestamp, mut record_val) in json_data {
+    for (i, (timestamp, mut record_val)) in json_data.into_iter().enumerate() {
         let doc_id = record_val
             .get("_id")
             .map(|v| v.as_str().unwrap().to_string());
@@ -538,6 +611,9 @@ async fn write_logs(
         let record_size = estimate_json_bytes(&record_val);
         hour_buf.records.push(Arc::new(record_val));
         hour_buf.records_size += record_size;
+        if let Some(hash) = dedup_hashes.get(i) {
+            written_hashes.push(*hash);
+        }
 
         // update status(success)
         match status {
@@ -561,14 +637,10 @@ async fn write_logs(
     // write data to wal
     let writer =
         ingester::get_writer(thread_id, org_id, StreamType::Logs.as_str(), stream_name).await;
-    let req_stats = write_file(
-        &writer,
-        org_id,
-        stream_name,
-        write_buf,
-        !cfg.common.wal_fsync_disabled,
-    )
-    .await?;
+    let req_stats = write_file(&writer, org_id, stream_name, write_buf, wal_sync_policy).await?;
+    if let Some(dedup) = dedup {
+        dedup.commit(&written_hashes);
+    }
 
     // send distinct_values
     if !distinct_values.is_empty()
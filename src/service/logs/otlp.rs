@@ -54,9 +54,13 @@ use crate::{
         },
         logs::bulk::TRANSFORM_FAILED,
         schema::{get_future_discard_error, get_upto_discard_error},
+        zpages,
     },
 };
 
+/// Receiver name of OTLP logs in the zPages
+const OTLP_RECEIVER: &str = "otlp";
+
 pub async fn handle_request(
     thread_id: usize,
     org_id: &str,
@@ -463,6 +467,7 @@ pub async fn handle_request(
 
     // if no data, fast return
     if json_data_by_stream.is_empty() {
+        zpages::record_receiver(OTLP_RECEIVER, 0, stream_status.status.failed as usize, 0);
         let mut out = BytesMut::with_capacity(res.encoded_len());
         res.encode(&mut out).expect("Out of memory");
         return Ok((
@@ -473,6 +478,11 @@ pub async fn handle_request(
             .into_response()); // just return
     }
 
+    let handed_records = json_data_by_stream
+        .values()
+        .map(|(records, _)| records.len())
+        .sum::<usize>();
+    let refused_before_write = stream_status.status.failed as usize;
     let mut status = IngestionStatus::Record(stream_status.status);
     let (metric_rpt_status_code, response_body) = match super::write_logs_by_stream(
         thread_id,
@@ -494,8 +504,8 @@ pub async fn handle_request(
         }
         Err(e) => {
             log::error!("Error while writing logs: {e}");
-            stream_status.status = match status {
-                IngestionStatus::Record(status) => status,
+            stream_status.status = match &status {
+                IngestionStatus::Record(status) => status.clone(),
                 IngestionStatus::Bulk(_) => unreachable!(),
             };
             res.partial_success = Some(ExportLogsPartialSuccess {
@@ -508,6 +518,24 @@ pub async fn handle_request(
         }
     };
 
+    // receiver stats
+    let refused_by_writer = match &status {
+        IngestionStatus::Record(s) => (s.failed as usize).saturating_sub(refused_before_write),
+        IngestionStatus::Bulk(_) => 0,
+    };
+    let written = handed_records.saturating_sub(refused_by_writer);
+    let (accepted, dropped) = if metric_rpt_status_code == "200" {
+        (written, 0)
+    } else {
+        (0, written)
+    };
+    zpages::record_receiver(
+        OTLP_RECEIVER,
+        accepted,
+        refused_before_write + refused_by_writer,
+        dropped,
+    );
+
     // metric + data usage
     let took_time = start.elapsed().as_secs_f64();
     let label_values = [
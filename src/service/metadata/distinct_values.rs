@@ -23,8 +23,11 @@ use std::{
 
 use arrow_schema::{DataType, Field, Schema};
 use config::{
-    FxIndexMap, TIMESTAMP_COL_NAME, get_config,
-    meta::stream::StreamType,
+    DISTINCT_FIELDS, FxIndexMap, TIMESTAMP_COL_NAME, get_config,
+    meta::{
+        search,
+        stream::{IngestPriority, StreamType, WalSyncPolicy},
+    },
     spawn_pausable_job,
     utils::{
         json, schema::infer_json_schema_from_map, time::now_micros, util::get_distinct_stream_name,
@@ -40,17 +43,32 @@ use serde_json::{Map, Value};
 use tokio::sync::{RwLock, mpsc};
 
 use crate::{
-    common::meta::stream::SchemaRecords,
+    common::meta::stream::{DistinctValueBucketsResponse, DistinctValueCounts, SchemaRecords},
     service::{
         db,
         ingestion::{self, get_thread_id},
         metadata::{Metadata, MetadataItem},
         schema::get_schema_changes,
+        search::sql::visitor::histogram_interval::{
+            convert_histogram_interval_to_seconds, generate_histogram_interval,
+        },
     },
 };
 
 const CHANNEL_SIZE: usize = 10240;
 
+/// Maximum number of buckets of the value counts of a field
+const MAX_VALUE_BUCKETS: usize = 1000;
+
+#[derive(Debug, thiserror::Error)]
+pub enum DistinctValuesError {
+    #[error("{0}")]
+    InvalidRequest(String),
+
+    #[error(transparent)]
+    SearchError(#[from] Error),
+}
+
 pub(crate) static INSTANCE: Lazy<DistinctValues> = Lazy::new(DistinctValues::new);
 
 type MemTable = FxIndexMap<String, FxIndexMap<DvItem, u32>>;
@@ -185,7 +203,6 @@ impl Metadata for DistinctValues {
     }
 
     async fn flush(&self) -> Result<()> {
-        let cfg = get_config();
         let mut mem_table = self.mem_table.write().await;
         let mut new_table: MemTable = FxIndexMap::default();
         std::mem::swap(&mut new_table, &mut *mem_table);
@@ -334,7 +351,7 @@ impl Metadata for DistinctValues {
                 &org_id,
                 &distinct_stream_name,
                 buf,
-                !cfg.common.wal_fsync_disabled,
+                WalSyncPolicy::default_for(IngestPriority::Normal),
             )
             .await;
 
@@ -375,3 +392,207 @@ impl Metadata for DistinctValues {
         Ok(())
     }
 }
+
+/// Counts of the most frequent values of a distinct value field per time
+/// bucket, from the distinct values stream, so facets can draw sparklines
+/// without a GROUP BY search on the stream for each bucket
+#[allow(clippy::too_many_arguments)]
+pub async fn get_value_buckets(
+    trace_id: &str,
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    field: &str,
+    time_range: (i64, i64),
+    interval: Option<&str>,
+    size: usize,
+) -> std::result::Result<DistinctValueBucketsResponse, DistinctValuesError> {
+    let (start_time, end_time) = time_range;
+    if start_time >= end_time {
+        return Err(DistinctValuesError::InvalidRequest(
+            "start_time must be before end_time".to_string(),
+        ));
+    }
+    check_distinct_field(org_id, stream_type, stream_name, field).await?;
+    let interval = interval.unwrap_or_else(|| generate_histogram_interval(Some(time_range)));
+    let interval = convert_histogram_interval_to_seconds(interval)
+        .ok()
+        .filter(|v| *v > 0)
+        .ok_or_else(|| {
+            DistinctValuesError::InvalidRequest(format!("invalid interval [{interval}]"))
+        })?
+        * 1_000_000;
+    let buckets = bucket_starts(start_time, end_time, interval);
+    if buckets.len() > MAX_VALUE_BUCKETS {
+        return Err(DistinctValuesError::InvalidRequest(format!(
+            "the interval makes more than {MAX_VALUE_BUCKETS} buckets"
+        )));
+    }
+
+    let distinct_stream_name = get_distinct_stream_name(stream_type, stream_name);
+    let sql = format!(
+        "SELECT \"{field}\" AS zo_sql_key, SUM(count) AS zo_sql_num FROM \"{distinct_stream_name}\" WHERE \"{field}\" IS NOT NULL GROUP BY zo_sql_key ORDER BY zo_sql_num DESC LIMIT {size}"
+    );
+    let top = search_distinct_stream(trace_id, org_id, sql, time_range, size).await?;
+    let mut values = top
+        .into_iter()
+        .filter_map(|hit| {
+            let value = hit.get("zo_sql_key").filter(|v| !v.is_null())?.clone();
+            Some(DistinctValueCounts {
+                value,
+                count: hit_count(&hit),
+                counts: vec![0; buckets.len()],
+            })
+        })
+        .collect::<Vec<_>>();
+    let literals = values
+        .iter()
+        .filter_map(|v| sql_literal(&v.value))
+        .collect::<Vec<_>>();
+    if literals.is_empty() {
+        return Ok(DistinctValueBucketsResponse {
+            field: field.to_string(),
+            interval,
+            buckets,
+            values,
+        });
+    }
+
+    let sql = format!(
+        "SELECT ({TIMESTAMP_COL_NAME} / {interval}) * {interval} AS zo_sql_time, \"{field}\" AS zo_sql_key, SUM(count) AS zo_sql_num FROM \"{distinct_stream_name}\" WHERE \"{field}\" IN ({}) GROUP BY zo_sql_time, zo_sql_key",
+        literals.join(", ")
+    );
+    let hits = search_distinct_stream(
+        trace_id,
+        org_id,
+        sql,
+        time_range,
+        values.len() * buckets.len(),
+    )
+    .await?;
+    let first_bucket = buckets.first().copied().unwrap_or_default();
+    for hit in hits {
+        let (Some(value), Some(time)) = (
+            hit.get("zo_sql_key"),
+            hit.get("zo_sql_time").and_then(|v| v.as_i64()),
+        ) else {
+            continue;
+        };
+        let Some(entry) = values.iter_mut().find(|v| &v.value == value) else {
+            continue;
+        };
+        let idx = (time - first_bucket) / interval;
+        if idx >= 0
+            && let Some(count) = entry.counts.get_mut(idx as usize)
+        {
+            *count += hit_count(&hit);
+        }
+    }
+    Ok(DistinctValueBucketsResponse {
+        field: field.to_string(),
+        interval,
+        buckets,
+        values,
+    })
+}
+
+/// Rejects the fields whose values the stream doesn't keep in its distinct
+/// values stream
+pub async fn check_distinct_field(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    field: &str,
+) -> std::result::Result<(), DistinctValuesError> {
+    let settings = infra::schema::get_settings(org_id, stream_name, stream_type)
+        .await
+        .unwrap_or_default();
+    let is_distinct_field = DISTINCT_FIELDS.iter().any(|f| f == field)
+        || settings
+            .distinct_value_fields
+            .iter()
+            .any(|f| f.name == field);
+    if !settings.enable_distinct_fields || !is_distinct_field || field == "count" {
+        return Err(DistinctValuesError::InvalidRequest(format!(
+            "field [{field}] isn't a distinct value field of the stream"
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) async fn search_distinct_stream(
+    trace_id: &str,
+    org_id: &str,
+    sql: String,
+    (start_time, end_time): (i64, i64),
+    size: usize,
+) -> Result<Vec<json::Value>> {
+    let req = search::Request {
+        query: search::Query {
+            sql,
+            start_time,
+            end_time,
+            size: size as i64,
+            ..Default::default()
+        },
+        search_type: Some(search::SearchEventType::Values),
+        use_cache: false,
+        ..Default::default()
+    };
+    let resp =
+        crate::service::search::search(trace_id, org_id, StreamType::Metadata, None, &req).await?;
+    Ok(resp.hits)
+}
+
+/// Start of the buckets covering the time range, aligned on the interval
+fn bucket_starts(start_time: i64, end_time: i64, interval: i64) -> Vec<i64> {
+    let first = start_time - start_time.rem_euclid(interval);
+    (0..)
+        .map(|i| first + i * interval)
+        .take_while(|t| *t < end_time)
+        .take(MAX_VALUE_BUCKETS + 1)
+        .collect()
+}
+
+pub(crate) fn hit_count(hit: &json::Value) -> i64 {
+    hit.get("zo_sql_num")
+        .and_then(|v| v.as_i64().or_else(|| v.as_f64().map(|v| v as i64)))
+        .unwrap_or_default()
+}
+
+pub(crate) fn sql_literal(value: &json::Value) -> Option<String> {
+    match value {
+        json::Value::String(v) => Some(format!("'{}'", v.replace('\'', "''"))),
+        json::Value::Number(v) => Some(v.to_string()),
+        json::Value::Bool(v) => Some(v.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_starts() {
+        assert_eq!(bucket_starts(0, 30, 10), vec![0, 10, 20]);
+        assert_eq!(bucket_starts(5, 31, 10), vec![0, 10, 20, 30]);
+        assert_eq!(bucket_starts(10, 11, 10), vec![10]);
+        assert_eq!(
+            bucket_starts(0, i64::MAX / 2, 1).len(),
+            MAX_VALUE_BUCKETS + 1
+        );
+    }
+
+    #[test]
+    fn test_sql_literal() {
+        assert_eq!(
+            sql_literal(&json::json!("it's")).as_deref(),
+            Some("'it''s'")
+        );
+        assert_eq!(sql_literal(&json::json!(42)).as_deref(), Some("42"));
+        assert_eq!(sql_literal(&json::json!(true)).as_deref(), Some("true"));
+        assert_eq!(sql_literal(&json::Value::Null), None);
+        assert_eq!(hit_count(&json::json!({"zo_sql_num": 3.0})), 3);
+    }
+}
@@ -24,8 +24,8 @@ use std::{
 
 use arrow_schema::{DataType, Field, Schema};
 use config::{
-    TIMESTAMP_COL_NAME, get_config,
-    meta::stream::{StreamSettings, StreamType},
+    TIMESTAMP_COL_NAME,
+    meta::stream::{IngestPriority, StreamSettings, StreamType, WalSyncPolicy},
     utils::{json, schema_ext::SchemaExt, time::now_micros},
 };
 use infra::schema::unwrap_partition_time_level;
@@ -127,7 +127,7 @@ impl Metadata for TraceListIndex {
             org_id,
             STREAM_NAME,
             buf,
-            !get_config().common.wal_fsync_disabled,
+            WalSyncPolicy::default_for(IngestPriority::Normal),
         )
         .await;
 
@@ -222,6 +222,21 @@ impl TraceListIndex {
                 index_original_data: false,
                 enable_distinct_fields: true,
                 enable_log_patterns_extraction: false,
+                ingest_priority: Default::default(),
+                ingest_quota: Default::default(),
+                redaction_rules: vec![],
+                computed_fields: vec![],
+                low_cardinality_fields: vec![],
+                ingest_dedup: Default::default(),
+                wal_sync_policy: None,
+                pinned_field_types: vec![],
+                level_normalization: Default::default(),
+                clock_skew: Default::default(),
+                tail_sampling: Default::default(),
+                field_access_rules: vec![],
+                archive_expired_data: false,
+                compact_sort: Default::default(),
+                compact_sort_removed_at: 0,
             };
 
             stream::save_stream_settings(org_id, STREAM_NAME, StreamType::Metadata, settings)
@@ -310,7 +325,8 @@ mod tests {
                 val.schema_key, val.schema, val.records_size, val.records
             );
         }
-        let r = ingestion::write_file(&writer, "default", STREAM_NAME, buf, false).await;
+        let r =
+            ingestion::write_file(&writer, "default", STREAM_NAME, buf, WalSyncPolicy::Async).await;
         println!("r: {r:?}");
     }
 }
@@ -28,7 +28,7 @@ use config::{
         alerts::alert::Alert,
         promql::{HASH_LABEL, METADATA_LABEL, Metadata, NAME_LABEL, TYPE_LABEL, VALUE_LABEL},
         self_reporting::usage::UsageType,
-        stream::{PartitioningDetails, StreamParams, StreamType},
+        stream::{PartitioningDetails, StreamParams, StreamType, WalSyncPolicy},
     },
     metrics,
     utils::{
@@ -71,8 +71,11 @@ pub async fn ingest(
 ) -> Result<IngestionResponse> {
     // check system resource
     if let Err(e) = check_ingestion_allowed(org_id, StreamType::Metrics, stream_name).await {
-        // we do not want to log trial period expired errors
-        if matches!(e, infra::errors::Error::TrialPeriodExpired) {
+        // we do not want to log trial period expired and quota errors
+        if matches!(
+            e,
+            infra::errors::Error::TrialPeriodExpired | infra::errors::Error::QuotaExceeded(_)
+        ) {
             return Ok(IngestionResponse {
                 code: http::StatusCode::TOO_MANY_REQUESTS.into(),
                 status: vec![],
@@ -511,9 +514,14 @@ pub async fn ingest(
             &stream_name,
         )
         .await;
-        // for performance issue, we will flush all when the app shutdown
-        let fsync = false;
-        let mut req_stats = write_file(&writer, org_id, &stream_name, stream_data, fsync).await?;
+        // for performance issue, metrics are not fsynced unless the stream
+        // asks for it, we will flush all when the app shutdown
+        let sync_policy = infra::schema::get_settings(org_id, &stream_name, StreamType::Metrics)
+            .await
+            .and_then(|s| s.wal_sync_policy)
+            .unwrap_or(WalSyncPolicy::Async);
+        let mut req_stats =
+            write_file(&writer, org_id, &stream_name, stream_data, sync_policy).await?;
 
         let email_str = user.to_email();
         req_stats.user_email = if email_str.is_empty() {
@@ -20,19 +20,21 @@ use std::{
 };
 
 use axum::{
-    Json, http,
+    Json,
+    body::Body,
+    http,
     response::{IntoResponse, Response as HttpResponse},
 };
-use bytes::{Bytes, BytesMut};
+use bytes::BytesMut;
 use chrono::Utc;
 use config::{
-    TIMESTAMP_COL_NAME,
+    TIMESTAMP_COL_NAME, get_config,
     meta::{
         alerts::alert,
         otlp::OtlpRequestType,
         promql::*,
         self_reporting::usage::UsageType,
-        stream::{PartitioningDetails, StreamParams, StreamType},
+        stream::{PartitioningDetails, StreamParams, StreamType, WalSyncPolicy},
     },
     metrics,
     utils::{
@@ -53,7 +55,10 @@ use opentelemetry_proto::tonic::{
 use prost::Message;
 
 use crate::{
-    common::meta::{http::HttpResponse as MetaHttpResponse, stream::SchemaRecords},
+    common::{
+        meta::{http::HttpResponse as MetaHttpResponse, stream::SchemaRecords},
+        utils::otlp,
+    },
     service::{
         alerts::alert::AlertExt,
         db, format_stream_name,
@@ -71,14 +76,14 @@ use crate::{
 
 pub async fn otlp_proto(
     org_id: &str,
-    body: Bytes,
+    body: Body,
     user: crate::common::meta::ingestion::IngestUser,
 ) -> Result<HttpResponse, std::io::Error> {
-    let request = match ExportMetricsServiceRequest::decode(body) {
-        Ok(v) => v,
+    let request = match otlp::decode_resources(body, get_config().limit.otlp_payload_limit).await {
+        Ok(resource_metrics) => ExportMetricsServiceRequest { resource_metrics },
         Err(e) => {
             log::error!("[METRICS:OTLP] Invalid proto: org_id: {org_id}, error: {e}");
-            return Ok(MetaHttpResponse::bad_request(format!("Invalid proto: {e}")));
+            return Ok(e.into_response());
         }
     };
     match handle_otlp_request(org_id, request, OtlpRequestType::HttpProtobuf, user).await {
@@ -99,9 +104,13 @@ pub async fn otlp_proto(
 
 pub async fn otlp_json(
     org_id: &str,
-    body: Bytes,
+    body: Body,
     user: crate::common::meta::ingestion::IngestUser,
 ) -> Result<HttpResponse, std::io::Error> {
+    let body = match otlp::read_body(body, get_config().limit.otlp_payload_limit).await {
+        Ok(v) => v,
+        Err(e) => return Ok(e.into_response()),
+    };
     let request = match serde_json::from_slice::<ExportMetricsServiceRequest>(body.as_ref()) {
         Ok(req) => req,
         Err(e) => {
@@ -131,8 +140,11 @@ pub async fn handle_otlp_request(
 ) -> Result<HttpResponse, anyhow::Error> {
     // check system resource
     if let Err(e) = check_ingestion_allowed(org_id, StreamType::Metrics, None).await {
-        // we do not want to log trial period expired errors
-        if matches!(e, infra::errors::Error::TrialPeriodExpired) {
+        // we do not want to log trial period expired and quota errors
+        if matches!(
+            e,
+            infra::errors::Error::TrialPeriodExpired | infra::errors::Error::QuotaExceeded(_)
+        ) {
             return Ok(MetaHttpResponse::too_many_requests(e));
         } else {
             log::error!("[METRICS:OTLP] ingestion error: {e}");
@@ -594,9 +606,14 @@ pub async fn handle_otlp_request(
             &stream_name,
         )
         .await;
-        // for performance issue, we will flush all when the app shutdown
-        let fsync = false;
-        let mut req_stats = write_file(&writer, org_id, &stream_name, stream_data, fsync).await?;
+        // for performance issue, metrics are not fsynced unless the stream
+        // asks for it, we will flush all when the app shutdown
+        let sync_policy = infra::schema::get_settings(org_id, &stream_name, StreamType::Metrics)
+            .await
+            .and_then(|s| s.wal_sync_policy)
+            .unwrap_or(WalSyncPolicy::Async);
+        let mut req_stats =
+            write_file(&writer, org_id, &stream_name, stream_data, sync_policy).await?;
 
         let fns_length: usize =
             stream_executable_pipelines
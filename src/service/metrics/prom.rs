@@ -26,10 +26,10 @@ use config::{
     get_config,
     meta::{
         alerts::alert,
-        promql::*,
+        promql::{value::Value, *},
         search::default_use_cache,
         self_reporting::usage::UsageType,
-        stream::{PartitioningDetails, StreamParams, StreamType},
+        stream::{PartitioningDetails, StreamParams, StreamType, WalSyncPolicy},
     },
     metrics,
     utils::{
@@ -651,10 +651,15 @@ pub async fn remote_write(
         .await;
         get_writer_time += t.elapsed().as_micros();
 
-        // for performance issue, we will flush all when the app shutdown
-        let fsync = false;
+        // for performance issue, metrics are not fsynced unless the stream
+        // asks for it, we will flush all when the app shutdown
+        let sync_policy = infra::schema::get_settings(org_id, &stream_name, StreamType::Metrics)
+            .await
+            .and_then(|s| s.wal_sync_policy)
+            .unwrap_or(WalSyncPolicy::Async);
         let t = std::time::Instant::now();
-        let mut req_stats = write_file(&writer, org_id, &stream_name, stream_data, fsync).await?;
+        let mut req_stats =
+            write_file(&writer, org_id, &stream_name, stream_data, sync_policy).await?;
         write_file_time += t.elapsed().as_micros();
 
         let fns_length: usize =
@@ -1103,6 +1108,164 @@ pub(crate) async fn get_label_values(
     Ok(label_values)
 }
 
+pub(crate) fn decode_read_request(
+    body: &[u8],
+) -> std::result::Result<prometheus_rpc::ReadRequest, anyhow::Error> {
+    let decoded = snap::raw::Decoder::new()
+        .decompress_vec(body)
+        .map_err(|e| anyhow::anyhow!("Invalid snappy compressed data: {}", e.to_string()))?;
+    let request = prometheus_rpc::ReadRequest::decode(bytes::Bytes::from(decoded))
+        .map_err(|e| anyhow::anyhow!("Invalid protobuf: {}", e.to_string()))?;
+    // only the raw samples are supported, prometheus falls back to them when
+    // the streamed chunks are not accepted
+    if !request.accepted_response_types.is_empty()
+        && !request
+            .accepted_response_types()
+            .any(|t| t == prometheus_rpc::read_request::ResponseType::Samples)
+    {
+        return Err(anyhow::anyhow!(
+            "Unsupported response types, only SAMPLES is supported"
+        ));
+    }
+    Ok(request)
+}
+
+pub(crate) fn encode_read_response(
+    response: &prometheus_rpc::ReadResponse,
+) -> std::result::Result<Vec<u8>, anyhow::Error> {
+    snap::raw::Encoder::new()
+        .compress_vec(&response.encode_to_vec())
+        .map_err(|e| anyhow::anyhow!("Failed to compress response: {}", e.to_string()))
+}
+
+/// Names of the metrics a remote read query selects, empty when the query
+/// selects no metric by name, such queries are rejected when they are run
+pub(crate) fn read_query_metric_names(query: &prometheus_rpc::Query) -> Vec<String> {
+    query
+        .matchers
+        .iter()
+        .filter(|m| m.name == NAME_LABEL && m.r#type() == prometheus_rpc::label_matcher::Type::Eq)
+        .map(|m| format_stream_name(m.value.to_string()))
+        .collect()
+}
+
+/// Answers the queries of a remote read request with the raw samples of the
+/// series they select, in the order of the queries
+pub(crate) async fn remote_read(
+    trace_id: &str,
+    org_id: &str,
+    request: &prometheus_rpc::ReadRequest,
+    user_email: &str,
+    timeout: i64,
+    is_super_cluster: bool,
+) -> Result<prometheus_rpc::ReadResponse> {
+    let mut results = Vec::with_capacity(request.queries.len());
+    for (i, query) in request.queries.iter().enumerate() {
+        let promql = read_query_to_promql(query).map_err(|e| Error::Message(e.to_string()))?;
+        let end = query.end_timestamp_ms * 1000;
+        let req = crate::service::promql::MetricsQueryRequest {
+            query: promql,
+            start: end,
+            end,
+            step: 300_000_000, // 5m
+            query_exemplars: false,
+            use_cache: None,
+            search_type: None,
+            regions: vec![],
+            clusters: vec![],
+        };
+        let value = crate::service::promql::search::search(
+            &format!("{trace_id}-{i}"),
+            org_id,
+            &req,
+            user_email,
+            timeout,
+            is_super_cluster,
+        )
+        .await?;
+        results.push(prometheus_rpc::QueryResult {
+            timeseries: to_read_timeseries(value),
+        });
+    }
+    Ok(prometheus_rpc::ReadResponse { results })
+}
+
+/// Turns the matchers of a remote read query into a range vector selector, an
+/// instant query of it at the end of the query returns the raw samples of the
+/// query time range
+fn read_query_to_promql(query: &prometheus_rpc::Query) -> std::result::Result<String, String> {
+    if query.matchers.is_empty() {
+        return Err("query has no matchers".to_string());
+    }
+    // the metric is the stream which is permission checked, it has to be
+    // named exactly
+    let mut names = query.matchers.iter().filter(|m| m.name == NAME_LABEL);
+    match (names.next(), names.next()) {
+        (Some(m), None) if m.r#type() == prometheus_rpc::label_matcher::Type::Eq => {}
+        _ => {
+            return Err(format!(
+                "query must select the metric with a single {NAME_LABEL} equality matcher"
+            ));
+        }
+    }
+    let range_ms = query.end_timestamp_ms - query.start_timestamp_ms;
+    if range_ms <= 0 {
+        return Err(format!(
+            "query end {} is not after its start {}",
+            query.end_timestamp_ms, query.start_timestamp_ms
+        ));
+    }
+    let matchers = query
+        .matchers
+        .iter()
+        .map(|m| {
+            let op = match m.r#type() {
+                prometheus_rpc::label_matcher::Type::Eq => "=",
+                prometheus_rpc::label_matcher::Type::Neq => "!=",
+                prometheus_rpc::label_matcher::Type::Re => "=~",
+                prometheus_rpc::label_matcher::Type::Nre => "!~",
+            };
+            let value = m.value.replace('\\', "\\\\").replace('"', "\\\"");
+            format!("{}{op}\"{value}\"", m.name)
+        })
+        .collect::<Vec<_>>();
+    Ok(format!("{{{}}}[{range_ms}ms]", matchers.join(",")))
+}
+
+fn to_read_timeseries(value: Value) -> Vec<prometheus_rpc::TimeSeries> {
+    let series = match value {
+        Value::Matrix(series) => series,
+        Value::Range(series) => vec![series],
+        _ => return vec![],
+    };
+    series
+        .into_iter()
+        .map(|s| {
+            let mut labels = s
+                .labels
+                .iter()
+                .map(|l| prometheus_rpc::Label {
+                    name: l.name.clone(),
+                    value: l.value.clone(),
+                })
+                .collect::<Vec<_>>();
+            labels.sort_by(|a, b| a.name.cmp(&b.name));
+            prometheus_rpc::TimeSeries {
+                labels,
+                samples: s
+                    .samples
+                    .iter()
+                    .map(|v| prometheus_rpc::Sample {
+                        value: v.value,
+                        timestamp: v.timestamp / 1000,
+                    })
+                    .collect(),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
 pub(crate) fn try_into_metric_name(selector: &parser::VectorSelector) -> Option<String> {
     match &selector.name {
         Some(name) => {
@@ -1193,3 +1356,112 @@ async fn prom_ha_handler(
 
     _accept_record
 }
+
+#[cfg(test)]
+mod tests {
+    use config::meta::promql::value::{Label, RangeValue, Sample};
+
+    use super::*;
+
+    fn matcher(
+        r#type: prometheus_rpc::label_matcher::Type,
+        name: &str,
+        value: &str,
+    ) -> prometheus_rpc::LabelMatcher {
+        prometheus_rpc::LabelMatcher {
+            r#type: r#type as i32,
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_read_query_to_promql() {
+        use prometheus_rpc::label_matcher::Type;
+
+        let query = prometheus_rpc::Query {
+            start_timestamp_ms: 1_000,
+            end_timestamp_ms: 61_000,
+            matchers: vec![
+                matcher(Type::Eq, NAME_LABEL, "up"),
+                matcher(Type::Re, "job", "api\\.\"v1\""),
+                matcher(Type::Nre, "instance", "dev-.*"),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            read_query_to_promql(&query).unwrap(),
+            r#"{__name__="up",job=~"api\\.\"v1\"",instance!~"dev-.*"}[60000ms]"#
+        );
+        assert_eq!(read_query_metric_names(&query), vec!["up"]);
+
+        let query = prometheus_rpc::Query {
+            start_timestamp_ms: 1_000,
+            end_timestamp_ms: 1_000,
+            matchers: vec![matcher(Type::Eq, NAME_LABEL, "up")],
+            ..Default::default()
+        };
+        assert!(read_query_to_promql(&query).is_err());
+
+        // the metric must be selected by name
+        for matchers in [
+            vec![matcher(Type::Re, NAME_LABEL, "secret.*")],
+            vec![matcher(Type::Eq, "job", "api")],
+            vec![
+                matcher(Type::Eq, NAME_LABEL, "up"),
+                matcher(Type::Re, NAME_LABEL, "secret"),
+            ],
+        ] {
+            let query = prometheus_rpc::Query {
+                start_timestamp_ms: 1_000,
+                end_timestamp_ms: 61_000,
+                matchers,
+                ..Default::default()
+            };
+            assert!(read_query_to_promql(&query).is_err());
+        }
+    }
+
+    #[test]
+    fn test_to_read_timeseries() {
+        let value = Value::Matrix(vec![RangeValue {
+            labels: vec![
+                Arc::new(Label::new("job", "api")),
+                Arc::new(Label::new(NAME_LABEL, "up")),
+            ],
+            samples: vec![Sample::new(1_000_000, 1.0), Sample::new(2_000_000, 0.0)],
+            exemplars: None,
+            time_window: None,
+        }]);
+        let series = to_read_timeseries(value);
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].labels[0].name, NAME_LABEL);
+        assert_eq!(series[0].labels[1].value, "api");
+        assert_eq!(series[0].samples[1].timestamp, 2_000);
+        assert_eq!(series[0].samples[1].value, 0.0);
+        assert!(to_read_timeseries(Value::None).is_empty());
+    }
+
+    #[test]
+    fn test_read_request_round_trip() {
+        let request = prometheus_rpc::ReadRequest {
+            queries: vec![prometheus_rpc::Query::default()],
+            accepted_response_types: vec![
+                prometheus_rpc::read_request::ResponseType::StreamedXorChunks as i32,
+            ],
+        };
+        let body = snap::raw::Encoder::new()
+            .compress_vec(&request.encode_to_vec())
+            .unwrap();
+        assert!(decode_read_request(&body).is_err());
+
+        let request = prometheus_rpc::ReadRequest {
+            accepted_response_types: vec![],
+            ..request
+        };
+        let body = snap::raw::Encoder::new()
+            .compress_vec(&request.encode_to_vec())
+            .unwrap();
+        assert_eq!(decode_read_request(&body).unwrap().queries.len(), 1);
+    }
+}
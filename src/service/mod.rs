@@ -18,34 +18,52 @@ use infra::errors::Result;
 use opentelemetry::trace::TraceContextExt;
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
+pub mod access_logs;
 pub mod alerts;
+pub mod async_search;
 pub mod cluster_info;
 pub mod compact;
 pub mod dashboards;
+pub mod dataset;
+pub mod db_pollers;
 pub mod db;
 pub mod enrichment;
 pub mod enrichment_table;
+pub mod field_stats;
+pub mod field_usage;
 pub mod file_list;
 pub mod file_list_dump;
+pub mod flow;
 pub mod folders;
 pub mod functions;
 pub mod github;
 pub mod grpc;
 pub mod ingestion;
+pub mod k8s_events;
 pub mod kv;
+pub mod log_metrics;
 pub mod logs;
 pub mod metadata;
 pub mod metrics;
 pub mod node;
+pub mod org_lifecycle;
 #[cfg(feature = "cloud")]
 pub mod org_usage;
 pub mod organization;
+pub mod pattern_stats;
 pub mod pipeline;
 pub mod promql;
+pub mod quota;
+pub mod rabbitmq;
 #[cfg(feature = "enterprise")]
 pub mod ratelimit;
+pub mod record_deletion;
+pub mod recording_rules;
+pub mod replay;
 pub mod runtime_metrics;
+pub mod scheduled_exports;
 pub mod schema;
+pub mod schema_suggestion;
 pub mod search;
 pub mod tantivy;
 
@@ -55,13 +73,21 @@ pub mod self_reporting;
 pub mod session;
 pub mod short_url;
 pub mod stream;
+pub mod stream_alias;
+pub mod tail;
 pub mod tls;
 pub mod traces;
 pub mod users;
+pub mod webhook;
+pub mod zpages;
 
 // format stream name
 pub async fn get_formatted_stream_name(params: StreamParams) -> Result<String> {
     let stream_name = params.stream_name.to_string();
+    // the records sent to an alias go to its stream
+    if let Some(name) = stream_alias::resolve(&params.org_id, params.stream_type, &stream_name) {
+        return Ok(name);
+    }
     let schema = infra::schema::get_cache(&params.org_id, &stream_name, params.stream_type).await?;
     Ok(if schema.fields_map().is_empty() {
         format_stream_name(stream_name)
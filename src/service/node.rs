@@ -39,6 +39,7 @@ pub fn config_node_to_proto(node: ConfigNode) -> NodeDetails {
             Role::AlertManager => ProtoRole::AlertManager as i32,
             Role::FlattenCompactor => ProtoRole::FlattenCompactor as i32,
             Role::ActionServer => ProtoRole::ScriptServer as i32,
+            Role::Indexer => ProtoRole::Indexer as i32,
         })
         .collect();
 
@@ -46,6 +47,7 @@ pub fn config_node_to_proto(node: ConfigNode) -> NodeDetails {
         RoleGroup::None => ProtoRoleGroup::None as i32,
         RoleGroup::Interactive => ProtoRoleGroup::Interactive as i32,
         RoleGroup::Background => ProtoRoleGroup::Background as i32,
+        RoleGroup::Shadow => ProtoRoleGroup::Shadow as i32,
     };
 
     let status = match node.status {
@@ -98,6 +100,7 @@ pub fn proto_node_to_config(node: NodeDetails) -> ConfigNode {
             r if r == ProtoRole::AlertManager as i32 => Some(Role::AlertManager),
             r if r == ProtoRole::FlattenCompactor as i32 => Some(Role::FlattenCompactor),
             r if r == ProtoRole::ScriptServer as i32 => Some(Role::ActionServer),
+            r if r == ProtoRole::Indexer as i32 => Some(Role::Indexer),
             _ => None,
         })
         .collect();
@@ -106,6 +109,7 @@ pub fn proto_node_to_config(node: NodeDetails) -> ConfigNode {
         r if r == ProtoRoleGroup::None as i32 => RoleGroup::None,
         r if r == ProtoRoleGroup::Interactive as i32 => RoleGroup::Interactive,
         r if r == ProtoRoleGroup::Background as i32 => RoleGroup::Background,
+        r if r == ProtoRoleGroup::Shadow as i32 => RoleGroup::Shadow,
         _ => RoleGroup::None,
     };
 
@@ -0,0 +1,292 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Organization lifecycle. An organization is active until an admin suspends
+//! or archives it: a suspended organization rejects ingestion but can still be
+//! searched, an archived one is read-only and its data is purged once its
+//! grace period is over. The states are enforced where requests enter, at
+//! authentication, ingestion and search, and only the organizations which
+//! aren't active are stored.
+
+use axum::http::Method;
+use config::{
+    META_ORG_ID,
+    cluster::LOCAL_NODE,
+    get_config,
+    meta::{cluster::Role, stream::ALL_STREAM_TYPES},
+    utils::time::{day_micros, now_micros},
+};
+use infra::cluster::get_node_from_consistent_hash;
+
+use crate::{
+    common::{
+        infra::config::ORG_LIFECYCLES,
+        meta::organization::{DEFAULT_ORG, OrgLifecycle, OrgLifecycleRequest, OrgLifecycleState},
+    },
+    service::{db, organization, stream},
+};
+
+/// The POST endpoints which only read data, besides the `_search*` and the
+/// prometheus query ones
+const READ_ENDPOINTS: [&str; 4] = ["_msearch", "_values_stream", "_around", "result_schema"];
+
+pub fn get(org_id: &str) -> OrgLifecycle {
+    ORG_LIFECYCLES
+        .get(org_id)
+        .map(|v| v.value().clone())
+        .unwrap_or_default()
+}
+
+/// Moves the organization to the state of the request. Archiving an archived
+/// organization again keeps its purge time unless a grace period is given.
+pub async fn set(
+    org_id: &str,
+    req: OrgLifecycleRequest,
+    user_email: &str,
+) -> Result<OrgLifecycle, anyhow::Error> {
+    if req.state != OrgLifecycleState::Active && (org_id == DEFAULT_ORG || org_id == META_ORG_ID) {
+        return Err(anyhow::anyhow!(
+            "organization [{org_id}] can't be suspended or archived"
+        ));
+    }
+    if organization::get_org(org_id).await.is_none() {
+        return Err(anyhow::anyhow!("organization [{org_id}] not found"));
+    }
+
+    let now = now_micros();
+    let current = get(org_id);
+    let purge_at = match (req.state, req.grace_days) {
+        (OrgLifecycleState::Archived, Some(days)) if days < 1 => {
+            return Err(anyhow::anyhow!("grace_days must be at least 1"));
+        }
+        (OrgLifecycleState::Archived, Some(days)) => Some(now + days * day_micros(1)),
+        (OrgLifecycleState::Archived, None) => current.purge_at.or(Some(
+            now + get_config().compact.org_archive_grace_days * day_micros(1),
+        )),
+        _ => None,
+    };
+    let lifecycle = OrgLifecycle {
+        state: req.state,
+        reason: req.reason,
+        purge_at,
+        updated_by: user_email.to_string(),
+        updated_at: now,
+    };
+    if lifecycle.state == OrgLifecycleState::Active {
+        db::org_lifecycle::delete(org_id).await?;
+        ORG_LIFECYCLES.remove(org_id);
+    } else {
+        db::org_lifecycle::set(org_id, &lifecycle).await?;
+        // requests right after on this node must see it before the watch does
+        ORG_LIFECYCLES.insert(org_id.to_string(), lifecycle.clone());
+    }
+    log::info!(
+        "[ORG_LIFECYCLE] {user_email} moved organization {org_id} from {} to {}",
+        current.state,
+        lifecycle.state
+    );
+    Ok(lifecycle)
+}
+
+/// Whether the request only reads, the path is relative to `/api/`
+fn is_read_request(method: &Method, path: &str) -> bool {
+    if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        return true;
+    }
+    if *method != Method::POST {
+        return false;
+    }
+    let endpoint = path
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or_default();
+    endpoint.starts_with("_search")
+        || READ_ENDPOINTS.contains(&endpoint)
+        || (path.contains("/prometheus/api/v1/") && endpoint != "write")
+}
+
+/// Checks the HTTP request can be served once authenticated, whatever the
+/// credentials, an archived organization only takes requests which read. The
+/// gRPC services only ingest and search, see [`check_ingestion`] and
+/// [`check_search`].
+pub fn check_request(org_id: &str, method: &Method, path: &str) -> Result<(), String> {
+    if ORG_LIFECYCLES.is_empty() {
+        return Ok(());
+    }
+    match ORG_LIFECYCLES.get(org_id).map(|v| v.state) {
+        Some(OrgLifecycleState::Archived) if !is_read_request(method, path) => Err(format!(
+            "organization [{org_id}] is archived, it is read-only"
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Checks the organization takes new data
+pub fn check_ingestion(org_id: &str) -> Result<(), String> {
+    if ORG_LIFECYCLES.is_empty() {
+        return Ok(());
+    }
+    match ORG_LIFECYCLES.get(org_id).map(|v| v.state) {
+        Some(state @ (OrgLifecycleState::Suspended | OrgLifecycleState::Archived)) => Err(format!(
+            "organization [{org_id}] is {state}, ingestion is disabled"
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Checks the organization can be searched, the data of an archived
+/// organization can't once its grace period is over
+pub fn check_search(org_id: &str) -> Result<(), String> {
+    if ORG_LIFECYCLES.is_empty() {
+        return Ok(());
+    }
+    match ORG_LIFECYCLES.get(org_id) {
+        Some(v)
+            if v.state == OrgLifecycleState::Archived
+                && v.purge_at.is_some_and(|t| t <= now_micros()) =>
+        {
+            Err(format!(
+                "organization [{org_id}] is archived and its data is being purged"
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Purges the archived organizations whose grace period is over, each one by
+/// the compactor it hashes to
+pub async fn purge() -> Result<(), anyhow::Error> {
+    let now = now_micros();
+    let due = ORG_LIFECYCLES
+        .iter()
+        .filter(|v| v.state == OrgLifecycleState::Archived && v.purge_at.is_some_and(|t| t <= now))
+        .map(|v| v.key().clone())
+        .collect::<Vec<_>>();
+    for org_id in due {
+        let Some(node_name) = get_node_from_consistent_hash(&org_id, &Role::Compactor, None).await
+        else {
+            return Ok(()); // no compactor node
+        };
+        if LOCAL_NODE.name.ne(&node_name) {
+            continue;
+        }
+        if let Err(e) = purge_org(&org_id).await {
+            log::error!("[ORG_LIFECYCLE] purge organization {org_id} error: {e}");
+        }
+    }
+    Ok(())
+}
+
+/// Deletes the streams and then the organization, the deleted streams are
+/// removed from storage by the retention job
+async fn purge_org(org_id: &str) -> Result<(), anyhow::Error> {
+    log::info!("[ORG_LIFECYCLE] purging archived organization {org_id}");
+    for stream_type in ALL_STREAM_TYPES {
+        for stream_name in db::schema::list_streams_from_cache(org_id, stream_type).await {
+            let resp = stream::delete_stream(org_id, &stream_name, stream_type, true).await?;
+            if !resp.status().is_success() {
+                return Err(anyhow::anyhow!(
+                    "delete stream {stream_type}/{stream_name} failed with {}",
+                    resp.status()
+                ));
+            }
+        }
+    }
+    organization::remove_org(org_id).await?;
+    db::org_lifecycle::delete(org_id).await?;
+    ORG_LIFECYCLES.remove(org_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_read_request() {
+        assert!(is_read_request(&Method::GET, "org/streams"));
+        assert!(is_read_request(&Method::POST, "org/_search"));
+        assert!(is_read_request(&Method::POST, "org/_search_partition"));
+        assert!(is_read_request(&Method::POST, "org/logs/_msearch"));
+        assert!(is_read_request(
+            &Method::POST,
+            "org/prometheus/api/v1/query_range"
+        ));
+        assert!(!is_read_request(
+            &Method::POST,
+            "org/prometheus/api/v1/write"
+        ));
+        assert!(!is_read_request(&Method::POST, "org/query"));
+        assert!(!is_read_request(&Method::POST, "org/logs/_json"));
+        assert!(!is_read_request(&Method::PUT, "org/streams/logs/settings"));
+        assert!(!is_read_request(&Method::DELETE, "org/streams/logs"));
+    }
+
+    #[test]
+    fn test_checks() {
+        let (suspended, archived, purging) = (
+            "test_org_lifecycle_suspended",
+            "test_org_lifecycle_archived",
+            "test_org_lifecycle_purging",
+        );
+        ORG_LIFECYCLES.insert(
+            suspended.to_string(),
+            OrgLifecycle {
+                state: OrgLifecycleState::Suspended,
+                ..Default::default()
+            },
+        );
+        ORG_LIFECYCLES.insert(
+            archived.to_string(),
+            OrgLifecycle {
+                state: OrgLifecycleState::Archived,
+                purge_at: Some(now_micros() + day_micros(1)),
+                ..Default::default()
+            },
+        );
+        ORG_LIFECYCLES.insert(
+            purging.to_string(),
+            OrgLifecycle {
+                state: OrgLifecycleState::Archived,
+                purge_at: Some(now_micros() - 1),
+                ..Default::default()
+            },
+        );
+
+        assert!(check_ingestion("test_org_lifecycle_active").is_ok());
+        assert!(check_ingestion(suspended).is_err());
+        assert!(check_ingestion(archived).is_err());
+
+        assert!(check_search(suspended).is_ok());
+        assert!(check_search(archived).is_ok());
+        assert!(check_search(purging).is_err());
+
+        let path = format!("{suspended}/streams/logs/settings");
+        assert!(check_request(suspended, &Method::PUT, &path).is_ok());
+        let path = format!("{archived}/streams/logs/settings");
+        assert!(check_request(archived, &Method::PUT, &path).is_err());
+        let path = format!("{archived}/_search");
+        assert!(check_request(archived, &Method::POST, &path).is_ok());
+
+        assert_eq!(get(suspended).state, OrgLifecycleState::Suspended);
+        assert_eq!(
+            get("test_org_lifecycle_active").state,
+            OrgLifecycleState::Active
+        );
+
+        ORG_LIFECYCLES.retain(|k, _| !k.starts_with("test_org_lifecycle_"));
+    }
+}
@@ -18,14 +18,23 @@ use config::{
     meta::{
         alerts::alert::ListAlertsParams,
         dashboards::ListDashboardsParams,
-        pipeline::components::PipelineSource,
+        folder::DEFAULT_FOLDER,
+        function::Transform,
+        pipeline::{
+            Pipeline,
+            components::{NodeData, PipelineSource},
+        },
         self_reporting::usage,
-        stream::StreamType,
+        stream::{FileKey, PartitionTimeLevel, StreamField, StreamType},
         user::{UserOrg, UserRole},
     },
-    utils::{json, rand::generate_random_string, time},
+    utils::{json, rand::generate_random_string, schema::format_stream_name, time},
+};
+use futures::StreamExt;
+use infra::{
+    db::{ORM_CLIENT, connect_to_orm},
+    table::{self, org_users::UserOrgExpandedRecord},
 };
-use infra::table::{self, org_users::UserOrgExpandedRecord};
 #[cfg(feature = "enterprise")]
 use o2_openfga::config::get_config as get_openfga_config;
 #[cfg(feature = "cloud")]
@@ -46,13 +55,19 @@ use super::self_reporting::cloud_events::{CloudEvent, EventType, enqueue_cloud_e
 use crate::{
     common::{
         infra::config::ORG_USERS,
-        meta::organization::{
-            AlertSummary, CUSTOM, DEFAULT_ORG, IngestionPasscode, IngestionTokensContainer,
-            OrgSummary, Organization, PipelineSummary, RumIngestionToken, StreamSummary,
-            TriggerStatus, TriggerStatusSearchResult,
+        meta::{
+            organization::{
+                AlertSummary, CUSTOM, DEFAULT_ORG, IngestionPasscode, IngestionTokensContainer,
+                ORG_ARCHIVE_VERSION, OrgArchive, OrgArchiveFile, OrgProvisionResponse,
+                OrgProvisionSummary, OrgSummary, OrgTemplate, OrgTemplateRole, OrgTemplateStream,
+                Organization, OrganizationCreationResponse, PipelineSummary, RumIngestionToken,
+                StreamSummary, TriggerStatus, TriggerStatusSearchResult,
+            },
+            stream::StreamCreate,
         },
         utils::auth::{delete_org_tuples, is_root_user, save_org_tuples},
     },
+    handler::http::router::ERROR_HEADER,
     service::{
         db::{self, org_users},
         self_reporting,
@@ -528,6 +543,381 @@ pub async fn create_org(
     }
 }
 
+/// Creates the organization and provisions it with the resources of the
+/// template: the streams first, then the roles, the pipelines, the dashboards
+/// and the alerts which may use them. A resource which can't be created
+/// doesn't stop the others, its error is returned in the summary so it can be
+/// fixed in the new organization.
+pub async fn provision_org(
+    org: &mut Organization,
+    user_email: &str,
+    template: OrgTemplate,
+) -> Result<OrgProvisionResponse, anyhow::Error> {
+    let (organization, service_account) = create_org(org, user_email).await?;
+    let org_id = organization.identifier.as_str();
+    let mut summary = OrgProvisionSummary::default();
+
+    for stream in template.streams {
+        let stream_name = stream.name.clone();
+        match provision_stream(org_id, stream).await {
+            Ok(()) => summary.streams += 1,
+            Err(e) => summary.errors.push(format!("stream {stream_name}: {e}")),
+        }
+    }
+    for role in template.roles {
+        let role_name = role.name.clone();
+        match provision_role(org_id, role).await {
+            Ok(()) => summary.roles += 1,
+            Err(e) => summary.errors.push(format!("role {role_name}: {e}")),
+        }
+    }
+    // the pipelines reference the functions by name
+    for function in template.functions {
+        let function_name = function.name.clone();
+        match provision_function(org_id, function).await {
+            Ok(()) => summary.functions += 1,
+            Err(e) => summary
+                .errors
+                .push(format!("function {function_name}: {e}")),
+        }
+    }
+    for mut pipeline in template.pipelines {
+        bind_pipeline_to_org(&mut pipeline, org_id);
+        let pipeline_name = pipeline.name.clone();
+        match super::pipeline::save_pipeline(pipeline).await {
+            Ok(()) => summary.pipelines += 1,
+            Err(e) => summary
+                .errors
+                .push(format!("pipeline {pipeline_name}: {e}")),
+        }
+    }
+    for mut dashboard in template.dashboards {
+        if dashboard.owner().filter(|v| !v.is_empty()).is_none() {
+            dashboard.set_owner(user_email.to_string());
+        }
+        let title = dashboard.title().unwrap_or_default().to_string();
+        match super::dashboards::create_dashboard(org_id, DEFAULT_FOLDER, dashboard).await {
+            Ok(_) => summary.dashboards += 1,
+            Err(e) => summary.errors.push(format!("dashboard {title}: {e}")),
+        }
+    }
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    for mut alert in template.alerts {
+        alert.id = None;
+        alert.org_id = org_id.to_string();
+        if alert.owner.clone().filter(|o| !o.is_empty()).is_none() {
+            alert.owner = Some(user_email.to_string());
+        }
+        alert.last_edited_by = Some(user_email.to_string());
+        let alert_name = alert.name.clone();
+        match super::alerts::alert::create(client, org_id, DEFAULT_FOLDER, alert, false).await {
+            Ok(_) => summary.alerts += 1,
+            Err(e) => summary.errors.push(format!("alert {alert_name}: {e}")),
+        }
+    }
+
+    if !summary.errors.is_empty() {
+        log::warn!(
+            "[org {org_id}] provisioned from template with {} errors: {:?}",
+            summary.errors.len(),
+            summary.errors
+        );
+    }
+    Ok(OrgProvisionResponse {
+        organization: OrganizationCreationResponse {
+            organization,
+            service_account,
+        },
+        provisioned: summary,
+    })
+}
+
+async fn provision_stream(org_id: &str, stream: OrgTemplateStream) -> Result<(), anyhow::Error> {
+    let mut stream_name = stream.name.trim().to_string();
+    if !config::get_config().common.skip_formatting_stream_name {
+        stream_name = format_stream_name(stream_name);
+    }
+    if stream_name.is_empty() {
+        return Err(anyhow::anyhow!("stream name can't be empty"));
+    }
+    if stream.stream_type == StreamType::EnrichmentTables || stream.stream_type == StreamType::Index
+    {
+        return Err(anyhow::anyhow!(
+            "stream type '{}' not allowed",
+            stream.stream_type
+        ));
+    }
+    let create = StreamCreate {
+        fields: stream.fields,
+        settings: stream.settings,
+    };
+    let resp =
+        super::stream::create_stream(org_id, &stream_name, stream.stream_type, create).await?;
+    if !resp.status().is_success() {
+        let msg = resp
+            .headers()
+            .get(ERROR_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("stream creation failed");
+        return Err(anyhow::anyhow!("{msg}"));
+    }
+    Ok(())
+}
+
+async fn provision_function(org_id: &str, function: Transform) -> Result<(), anyhow::Error> {
+    let resp = super::functions::save_function(org_id.to_string(), function).await?;
+    if !resp.status().is_success() {
+        let msg = resp
+            .headers()
+            .get(ERROR_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("function creation failed");
+        return Err(anyhow::anyhow!("{msg}"));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "enterprise")]
+async fn provision_role(org_id: &str, role: OrgTemplateRole) -> Result<(), anyhow::Error> {
+    use crate::{
+        common::meta::user::is_standard_role, handler::http::auth::jwt::format_role_name_only,
+    };
+
+    let role_name = format_role_name_only(role.name.trim());
+    if role_name.is_empty() || is_standard_role(&role_name) {
+        return Err(anyhow::anyhow!(
+            "custom role name cannot be empty or standard role"
+        ));
+    }
+    let permissions = role
+        .permissions
+        .into_iter()
+        .map(json::from_value::<o2_dex::meta::auth::O2EntityAuthorization>)
+        .collect::<Result<Vec<_>, _>>()?;
+    o2_openfga::authorizer::roles::create_role(&role_name, org_id)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    if !permissions.is_empty() {
+        o2_openfga::authorizer::roles::update_role(
+            org_id,
+            &role_name,
+            permissions,
+            vec![],
+            None,
+            None,
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "enterprise"))]
+async fn provision_role(_org_id: &str, _role: OrgTemplateRole) -> Result<(), anyhow::Error> {
+    Err(anyhow::anyhow!("custom roles are not supported"))
+}
+
+/// Points the streams of the pipeline of another organization to the org, and
+/// gives it a new id like a created pipeline
+fn bind_pipeline_to_org(pipeline: &mut Pipeline, org_id: &str) {
+    pipeline.id = ider::generate();
+    pipeline.name = pipeline.name.trim().to_lowercase();
+    pipeline.org = org_id.to_string();
+    if let PipelineSource::Realtime(stream) = &mut pipeline.source {
+        stream.org_id = org_id.to_string().into();
+    }
+    for node in pipeline.nodes.iter_mut() {
+        match &mut node.data {
+            NodeData::Stream(stream) => stream.org_id = org_id.to_string().into(),
+            NodeData::Query(derived_stream) => derived_stream.org_id = org_id.to_string(),
+            _ => {}
+        }
+    }
+}
+
+/// Exports the org into an archive which [`import_org`] provisions another
+/// org with, e.g. on another cluster. The dashboards and alerts of all the
+/// folders are exported, they are imported in the default folder. With
+/// `include_data` the archive lists the data files of the streams, the users
+/// and roles are never exported.
+pub async fn export_org(org_id: &str, include_data: bool) -> Result<OrgArchive, anyhow::Error> {
+    let Some(org) = get_org(org_id).await else {
+        return Err(anyhow::anyhow!("organization {org_id} not found"));
+    };
+
+    let mut streams = Vec::new();
+    for stream in db::schema::list(org_id, None, true).await? {
+        if stream.stream_type == StreamType::EnrichmentTables
+            || stream.stream_type == StreamType::Index
+        {
+            continue;
+        }
+        let fields = stream
+            .schema
+            .fields()
+            .iter()
+            .map(|f| StreamField {
+                name: f.name().to_string(),
+                r#type: f.data_type().to_string(),
+            })
+            .collect();
+        let settings = infra::schema::unwrap_stream_settings(&stream.schema).unwrap_or_default();
+        streams.push(OrgTemplateStream {
+            name: stream.stream_name,
+            stream_type: stream.stream_type,
+            fields,
+            settings,
+        });
+    }
+    let functions = db::functions::list(org_id).await?;
+    let pipelines = db::pipeline::list_by_org(org_id).await?;
+    let dashboards = table::dashboards::list(ListDashboardsParams::new(org_id))
+        .await?
+        .into_iter()
+        .map(|(_, dashboard)| dashboard)
+        .collect();
+    let alerts = super::alerts::alert::list_with_folders_db(ListAlertsParams::new(org_id))
+        .await?
+        .into_iter()
+        .map(|(_, alert)| alert)
+        .collect();
+
+    let files = if include_data {
+        let trace_id = ider::generate_trace_id();
+        let now = time::now_micros();
+        let mut files = Vec::new();
+        for stream in streams.iter() {
+            let stats =
+                infra::cache::stats::get_stream_stats(org_id, &stream.name, stream.stream_type);
+            if stats.doc_time_min == 0 {
+                continue;
+            }
+            let keys = super::file_list::query(
+                &trace_id,
+                org_id,
+                stream.stream_type,
+                &stream.name,
+                PartitionTimeLevel::Unset,
+                stats.doc_time_min,
+                now,
+            )
+            .await?;
+            files.extend(keys.into_iter().map(|f| OrgArchiveFile {
+                key: f.key,
+                meta: f.meta,
+            }));
+        }
+        Some(files)
+    } else {
+        None
+    };
+
+    Ok(OrgArchive {
+        version: ORG_ARCHIVE_VERSION,
+        org_id: org.identifier,
+        org_name: org.name,
+        exported_at: time::now_micros(),
+        streams,
+        functions,
+        pipelines,
+        dashboards,
+        alerts,
+        files,
+    })
+}
+
+/// Creates a new org provisioned with the resources of an archive exported by
+/// [`export_org`]. The data files of the archive are expected to have been
+/// copied, with their keys, to the object storage of this cluster. They are
+/// copied under the new org and registered in its file list.
+pub async fn import_org(
+    org: &mut Organization,
+    user_email: &str,
+    archive: OrgArchive,
+) -> Result<OrgProvisionResponse, anyhow::Error> {
+    if archive.version > ORG_ARCHIVE_VERSION {
+        return Err(anyhow::anyhow!(
+            "archive version {} is newer than the supported version {ORG_ARCHIVE_VERSION}",
+            archive.version
+        ));
+    }
+    let template = OrgTemplate {
+        streams: archive.streams,
+        roles: vec![],
+        functions: archive.functions,
+        pipelines: archive.pipelines,
+        dashboards: archive.dashboards,
+        alerts: archive.alerts,
+    };
+    let mut resp = provision_org(org, user_email, template).await?;
+    if let Some(files) = archive.files {
+        let org_id = resp.organization.organization.identifier.clone();
+        import_files(&archive.org_id, &org_id, files, &mut resp.provisioned).await;
+    }
+    Ok(resp)
+}
+
+async fn import_files(
+    from_org_id: &str,
+    org_id: &str,
+    files: Vec<OrgArchiveFile>,
+    summary: &mut OrgProvisionSummary,
+) {
+    let cfg = config::get_config();
+    let mut tasks = futures::stream::iter(files)
+        .map(|file| async move {
+            let Some(key) = rebind_file_key(&file.key, from_org_id, org_id) else {
+                return Err(anyhow::anyhow!("file {} isn't a file of the org", file.key));
+            };
+            if from_org_id != org_id {
+                let from_account = infra::storage::get_account(&file.key).unwrap_or_default();
+                let data = infra::storage::get_bytes(&from_account, &file.key)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("file {}: {e}", file.key))?;
+                let account = infra::storage::get_account(&key).unwrap_or_default();
+                infra::storage::put(&account, &key, data)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("file {key}: {e}"))?;
+            }
+            let account = infra::storage::get_account(&key).unwrap_or_default();
+            Ok(FileKey::new(0, account, key, file.meta, false))
+        })
+        .buffer_unordered(cfg.limit.file_download_thread_num.max(1));
+
+    let mut copied = Vec::new();
+    while let Some(ret) = tasks.next().await {
+        match ret {
+            Ok(file) => copied.push(file),
+            Err(e) => summary.errors.push(e.to_string()),
+        }
+    }
+    for chunk in copied.chunks(1000) {
+        match infra::file_list::batch_add(chunk).await {
+            Ok(()) => summary.files += chunk.len(),
+            Err(e) => summary
+                .errors
+                .push(format!("failed to register {} files: {e}", chunk.len())),
+        }
+    }
+    if !summary.errors.is_empty() {
+        log::warn!(
+            "[org {org_id}] imported {} files from org {from_org_id} with {} errors",
+            summary.files,
+            summary.errors.len()
+        );
+    }
+}
+
+/// Returns the key of the data file of an org under another org, e.g.
+/// `files/a/logs/app/...` of org `a` is `files/b/logs/app/...` in org `b`
+fn rebind_file_key(key: &str, from_org_id: &str, org_id: &str) -> Option<String> {
+    let path = key.strip_prefix(&format!("files/{from_org_id}/"))?;
+    if path.is_empty() {
+        return None;
+    }
+    Some(format!("files/{org_id}/{path}"))
+}
+
 /// Checks if the org exists, otherwise creates the org. Does not associate any user
 /// with the org, only saves the org in the meta and creates org tuples.
 pub async fn check_and_create_org(org_id: &str) -> Result<Organization, anyhow::Error> {
@@ -977,6 +1367,89 @@ mod tests {
     use super::*;
     use crate::{common::meta::user::UserRequest, service::users};
 
+    #[test]
+    fn test_bind_pipeline_to_org() {
+        use config::meta::{pipeline::components::Node, stream::StreamParams};
+
+        let mut pipeline = Pipeline {
+            id: "template_pipeline".to_string(),
+            version: 1,
+            enabled: true,
+            org: "template_org".to_string(),
+            name: " Errors ".to_string(),
+            description: String::new(),
+            source: PipelineSource::Realtime(StreamParams::new(
+                "template_org",
+                "app",
+                StreamType::Logs,
+            )),
+            nodes: vec![
+                Node::new(
+                    "1".to_string(),
+                    NodeData::Stream(StreamParams::new("template_org", "app", StreamType::Logs)),
+                    0.0,
+                    0.0,
+                    "input".to_string(),
+                ),
+                Node::new(
+                    "2".to_string(),
+                    NodeData::Stream(StreamParams::new(
+                        "template_org",
+                        "errors",
+                        StreamType::Logs,
+                    )),
+                    0.0,
+                    0.0,
+                    "output".to_string(),
+                ),
+            ],
+            edges: vec![],
+        };
+        bind_pipeline_to_org(&mut pipeline, "new_org");
+
+        assert_ne!(pipeline.id, "template_pipeline");
+        assert_eq!(pipeline.name, "errors");
+        assert_eq!(pipeline.org, "new_org");
+        let PipelineSource::Realtime(source) = &pipeline.source else {
+            panic!("source changed");
+        };
+        assert_eq!(source.org_id.as_str(), "new_org");
+        for node in pipeline.nodes.iter() {
+            let NodeData::Stream(stream) = &node.data else {
+                panic!("node changed");
+            };
+            assert_eq!(stream.org_id.as_str(), "new_org");
+        }
+    }
+
+    #[test]
+    fn test_rebind_file_key() {
+        assert_eq!(
+            rebind_file_key("files/a/logs/app/2026/01/01/00/1.parquet", "a", "b").as_deref(),
+            Some("files/b/logs/app/2026/01/01/00/1.parquet")
+        );
+        // the prefix of another org isn't rebound
+        assert!(rebind_file_key("files/ab/logs/app/1.parquet", "a", "b").is_none());
+        assert!(rebind_file_key("files/a/", "a", "b").is_none());
+    }
+
+    #[test]
+    fn test_org_archive_files() {
+        let archive: OrgArchive = json::from_str(
+            r#"{"version": 1, "org_id": "a", "org_name": "A", "exported_at": 1,
+                "files": [{"key": "files/a/logs/app/1.parquet", "min_ts": 1, "max_ts": 2,
+                "records": 3, "original_size": 4, "compressed_size": 5, "index_size": 0,
+                "flattened": false}]}"#,
+        )
+        .unwrap();
+        assert!(archive.streams.is_empty());
+        let files = archive.files.unwrap();
+        assert_eq!(files[0].meta.records, 3);
+        let value = json::to_value(&files[0]).unwrap();
+        assert_eq!(value["key"], "files/a/logs/app/1.parquet");
+        assert_eq!(value["compressed_size"], 5);
+    }
+
     // TODO: move these tests to integration tests,
     // the below test case will fail as is_root_user()
     // will not work as watchers are not initialized
@@ -0,0 +1,401 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Log patterns followed over time. The logs streams with log patterns
+//! enabled are mined in the background every interval, and the sampled count
+//! of every pattern in every window is kept in the kv store for a week. The
+//! patterns first seen recently, or whose share of the logs grew several
+//! times, are found from the kept counts without mining the past again.
+
+use config::{
+    get_config,
+    meta::{
+        patterns::{
+            DEFAULT_SIMILARITY, PatternRequest, PatternResponse, PatternTrend,
+            PatternTrendResponse, PatternWindow, PatternWindowCount, StreamPatternStats,
+            TrackedPattern,
+        },
+        stream::StreamType,
+    },
+    utils::{
+        drain::{self, WILDCARD},
+        json,
+        time::now_micros,
+    },
+};
+use infra::schema::STREAM_SCHEMAS_LATEST;
+
+use crate::service::{db::kv, search::patterns};
+
+/// Windows and patterns older than a week are forgotten
+const RETENTION_MICROS: i64 = 7 * 24 * 3600 * 1_000_000;
+
+/// Longest time range mined per run, a stream not mined for longer resumes
+/// from there
+const MAX_WINDOW_MICROS: i64 = 24 * 3600 * 1_000_000;
+
+/// Patterns followed per stream, the least recently seen are forgotten first
+const MAX_PATTERNS: usize = 1000;
+
+/// Examples kept per pattern
+const MAX_EXAMPLES: usize = 3;
+
+/// Growth of the share of a pattern from which it is anomalous
+const SPIKE_RATIO: f64 = 3.0;
+
+/// Sampled logs a pattern needs in the recent windows to be anomalous
+const MIN_ANOMALY_COUNT: u64 = 10;
+
+/// Earlier windows needed to tell a pattern is anomalous
+const MIN_BASELINE_WINDOWS: usize = 3;
+
+fn stats_key(stream_name: &str) -> String {
+    format!("pattern_stats/{stream_name}")
+}
+
+/// Mines the logs streams with log patterns enabled not mined for an interval
+pub async fn run() {
+    let interval = get_config().common.pattern_stats_interval as i64 * 1_000_000;
+    let streams = STREAM_SCHEMAS_LATEST
+        .read()
+        .await
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    for key in streams {
+        let mut parts = key.splitn(3, '/');
+        let (Some(org_id), Some(stream_type), Some(stream_name)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        if StreamType::from(stream_type) != StreamType::Logs {
+            continue;
+        }
+        if !infra::schema::get_settings(org_id, stream_name, StreamType::Logs)
+            .await
+            .is_some_and(|s| s.enable_log_patterns_extraction)
+        {
+            continue;
+        }
+        let now = now_micros();
+        let mut stats = get(org_id, stream_name).await.unwrap_or_default();
+        let last_end = stats.windows.last().map(|w| w.end_time);
+        // every compactor runs the job, skip what another one mined lately
+        if last_end.is_some_and(|end| end > now - interval / 2) {
+            continue;
+        }
+        let start_time = last_end
+            .unwrap_or(now - interval)
+            .max(now - MAX_WINDOW_MICROS);
+        let trace_id = config::ider::generate_trace_id();
+        if let Err(e) = refresh(&trace_id, org_id, stream_name, &mut stats, start_time, now).await {
+            log::error!("[PATTERN_STATS] failed to mine {key}: {e}");
+        }
+    }
+}
+
+async fn refresh(
+    trace_id: &str,
+    org_id: &str,
+    stream_name: &str,
+    stats: &mut StreamPatternStats,
+    start_time: i64,
+    end_time: i64,
+) -> Result<(), anyhow::Error> {
+    let req = PatternRequest {
+        start_time,
+        end_time,
+        field: (!stats.field.is_empty()).then(|| stats.field.clone()),
+        top_k: Some(MAX_PATTERNS),
+        ..Default::default()
+    };
+    let resp = patterns::mine(trace_id, org_id, stream_name, None, &req).await?;
+    add_window(stats, resp, start_time, end_time);
+    kv::set(org_id, &stats_key(stream_name), json::to_vec(stats)?.into()).await?;
+    Ok(())
+}
+
+fn tokens(template: &str) -> Vec<String> {
+    template.split(' ').map(|t| t.to_string()).collect()
+}
+
+/// Adds the patterns mined in a window to the followed ones, a mined pattern
+/// similar to a followed one is counted for it and generalizes its template
+fn add_window(
+    stats: &mut StreamPatternStats,
+    resp: PatternResponse,
+    start_time: i64,
+    end_time: i64,
+) {
+    stats.field = resp.field;
+    stats.windows.push(PatternWindow {
+        start_time,
+        end_time,
+        total_logs: resp.total_logs,
+    });
+    for pattern in resp.patterns {
+        let mined = tokens(&pattern.template);
+        let found = stats
+            .patterns
+            .iter()
+            .position(|p| p.template == pattern.template)
+            .or_else(|| {
+                stats.patterns.iter().position(|p| {
+                    let followed = tokens(&p.template);
+                    followed.len() == mined.len()
+                        && drain::similarity(&followed, &mined) >= DEFAULT_SIMILARITY
+                })
+            });
+        let Some(i) = found else {
+            stats.patterns.push(TrackedPattern {
+                template: pattern.template,
+                examples: pattern.examples,
+                first_seen: pattern.first_seen,
+                last_seen: pattern.last_seen,
+                counts: vec![PatternWindowCount {
+                    start_time,
+                    count: pattern.count,
+                }],
+            });
+            continue;
+        };
+        let followed = &mut stats.patterns[i];
+        if followed.template != pattern.template {
+            followed.template = tokens(&followed.template)
+                .into_iter()
+                .zip(mined.iter())
+                .map(|(t, m)| if t == *m { t } else { WILDCARD.to_string() })
+                .collect::<Vec<_>>()
+                .join(" ");
+        }
+        followed.first_seen = followed.first_seen.min(pattern.first_seen);
+        followed.last_seen = followed.last_seen.max(pattern.last_seen);
+        let room = MAX_EXAMPLES.saturating_sub(followed.examples.len());
+        followed
+            .examples
+            .extend(pattern.examples.into_iter().take(room));
+        match followed.counts.last_mut() {
+            Some(last) if last.start_time == start_time => last.count += pattern.count,
+            _ => followed.counts.push(PatternWindowCount {
+                start_time,
+                count: pattern.count,
+            }),
+        }
+    }
+
+    let expired = end_time - RETENTION_MICROS;
+    stats.windows.retain(|w| w.end_time > expired);
+    let oldest = stats.windows.first().map_or(start_time, |w| w.start_time);
+    for pattern in stats.patterns.iter_mut() {
+        pattern.counts.retain(|c| c.start_time >= oldest);
+    }
+    stats.patterns.retain(|p| !p.counts.is_empty());
+    stats.patterns.sort_by(|a, b| b.last_seen.cmp(&a.last_seen));
+    stats.patterns.truncate(MAX_PATTERNS);
+}
+
+/// The patterns of the windows ending after `start_time`, compared to the
+/// earlier windows
+pub fn trends(stats: &StreamPatternStats, start_time: i64) -> PatternTrendResponse {
+    let recent_start = stats
+        .windows
+        .iter()
+        .find(|w| w.end_time > start_time)
+        .map_or(i64::MAX, |w| w.start_time);
+    let (recent, earlier): (Vec<_>, Vec<_>) = stats
+        .windows
+        .iter()
+        .partition(|w| w.start_time >= recent_start);
+    let total_logs = recent.iter().map(|w| w.total_logs).sum::<u64>();
+    let baseline_logs = earlier.iter().map(|w| w.total_logs).sum::<u64>();
+    let share = |count: u64, total: u64| {
+        if total == 0 {
+            0.0
+        } else {
+            count as f64 * 100.0 / total as f64
+        }
+    };
+
+    let mut patterns = Vec::new();
+    for pattern in stats.patterns.iter() {
+        let (recent_counts, earlier_counts): (Vec<_>, Vec<_>) = pattern
+            .counts
+            .iter()
+            .partition(|c| c.start_time >= recent_start);
+        let count = recent_counts.iter().map(|c| c.count).sum::<u64>();
+        if count == 0 {
+            continue;
+        }
+        let baseline = earlier_counts.iter().map(|c| c.count).sum::<u64>();
+        let percentage = share(count, total_logs);
+        let baseline_percentage = share(baseline, baseline_logs);
+        let is_new = !earlier.is_empty() && earlier_counts.is_empty();
+        let is_anomalous = !is_new
+            && earlier.len() >= MIN_BASELINE_WINDOWS
+            && count >= MIN_ANOMALY_COUNT
+            && percentage >= SPIKE_RATIO * baseline_percentage;
+        patterns.push(PatternTrend {
+            template: pattern.template.clone(),
+            count,
+            percentage,
+            baseline_percentage,
+            examples: pattern.examples.clone(),
+            first_seen: pattern.first_seen,
+            last_seen: pattern.last_seen,
+            is_new,
+            is_anomalous,
+        });
+    }
+    patterns.sort_by(|a, b| {
+        b.is_new
+            .cmp(&a.is_new)
+            .then(b.is_anomalous.cmp(&a.is_anomalous))
+            .then(b.count.cmp(&a.count))
+    });
+
+    PatternTrendResponse {
+        field: stats.field.clone(),
+        start_time: recent.first().map_or(start_time, |w| w.start_time),
+        end_time: stats.windows.last().map_or(0, |w| w.end_time),
+        total_logs,
+        new_patterns: patterns.iter().filter(|p| p.is_new).count(),
+        anomalous_patterns: patterns.iter().filter(|p| p.is_anomalous).count(),
+        patterns,
+    }
+}
+
+/// Patterns of the stream mined in the background, `None` when it wasn't
+/// mined yet
+pub async fn get(org_id: &str, stream_name: &str) -> Option<StreamPatternStats> {
+    let val = kv::get(org_id, &stats_key(stream_name)).await.ok()?;
+    json::from_slice(&val).ok()
+}
+
+pub async fn delete(org_id: &str, stream_name: &str) -> Result<(), anyhow::Error> {
+    if kv::get(org_id, &stats_key(stream_name)).await.is_err() {
+        return Ok(());
+    }
+    kv::delete(org_id, &stats_key(stream_name)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use config::meta::patterns::LogPattern;
+
+    use super::*;
+
+    const HOUR: i64 = 3600 * 1_000_000;
+
+    fn mined(total_logs: u64, patterns: &[(&str, u64)]) -> PatternResponse {
+        PatternResponse {
+            field: "message".to_string(),
+            total_logs,
+            patterns: patterns
+                .iter()
+                .map(|(template, count)| LogPattern {
+                    template: template.to_string(),
+                    count: *count,
+                    examples: vec![template.replace(WILDCARD, "x")],
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_add_window() {
+        let mut stats = StreamPatternStats::default();
+        add_window(
+            &mut stats,
+            mined(10, &[("user alice logged in", 10)]),
+            0,
+            HOUR,
+        );
+        add_window(
+            &mut stats,
+            mined(10, &[("user <*> logged in", 8), ("disk full", 2)]),
+            HOUR,
+            2 * HOUR,
+        );
+        assert_eq!(stats.field, "message");
+        assert_eq!(stats.windows.len(), 2);
+        assert_eq!(stats.patterns.len(), 2);
+        let login = stats
+            .patterns
+            .iter()
+            .find(|p| p.template == "user <*> logged in")
+            .unwrap();
+        assert_eq!(login.counts.len(), 2);
+        assert_eq!(login.counts[1].count, 8);
+        assert_eq!(login.examples.len(), 2);
+
+        // a week later the first windows are forgotten
+        add_window(
+            &mut stats,
+            mined(5, &[("disk full", 5)]),
+            8 * 24 * HOUR,
+            8 * 24 * HOUR + HOUR,
+        );
+        assert_eq!(stats.windows.len(), 1);
+        assert_eq!(stats.patterns.len(), 1);
+        assert_eq!(stats.patterns[0].counts.len(), 1);
+    }
+
+    #[test]
+    fn test_trends() {
+        let mut stats = StreamPatternStats::default();
+        for hour in 0..4 {
+            add_window(
+                &mut stats,
+                mined(100, &[("request served", 98), ("request failed", 2)]),
+                hour * HOUR,
+                (hour + 1) * HOUR,
+            );
+        }
+        add_window(
+            &mut stats,
+            mined(
+                100,
+                &[
+                    ("request served", 70),
+                    ("request failed", 20),
+                    ("payment gateway unreachable", 10),
+                ],
+            ),
+            4 * HOUR,
+            5 * HOUR,
+        );
+
+        let resp = trends(&stats, 4 * HOUR);
+        assert_eq!(resp.start_time, 4 * HOUR);
+        assert_eq!(resp.end_time, 5 * HOUR);
+        assert_eq!(resp.total_logs, 100);
+        assert_eq!(resp.new_patterns, 1);
+        assert_eq!(resp.anomalous_patterns, 1);
+        assert_eq!(resp.patterns[0].template, "payment gateway unreachable");
+        assert!(resp.patterns[0].is_new);
+        assert_eq!(resp.patterns[1].template, "request failed");
+        assert!(resp.patterns[1].is_anomalous);
+        assert_eq!(resp.patterns[1].baseline_percentage, 2.0);
+        assert!(!resp.patterns[2].is_new && !resp.patterns[2].is_anomalous);
+
+        // nothing is new without earlier windows
+        let resp = trends(&stats, 0);
+        assert_eq!(resp.new_patterns, 0);
+        assert_eq!(resp.total_logs, 500);
+    }
+}
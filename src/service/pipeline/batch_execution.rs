@@ -25,6 +25,7 @@ use config::{
         self_reporting::error::{ErrorData, ErrorSource, PipelineError},
         stream::{StreamParams, StreamType},
     },
+    metrics,
     stats::MemorySize,
     utils::{
         flatten,
@@ -48,6 +49,7 @@ use crate::{
     service::{
         alerts::{ConditionExt, ConditionGroupExt},
         ingestion::{apply_js_fn, apply_vrl_fn, compile_js_function, compile_vrl_function},
+        pipeline::error_stream::{self, FailedRecord},
         self_reporting::publish_error,
     },
 };
@@ -329,7 +331,7 @@ impl ExecutablePipeline {
 
         // error_channel
         let (error_sender, mut error_receiver) =
-            channel::<(String, String, String, Option<String>)>(batch_size);
+            channel::<(String, String, String, Option<String>, Option<Value>)>(batch_size);
 
         let mut node_senders = HashMap::new();
         let mut node_receivers = HashMap::new();
@@ -393,21 +395,42 @@ impl ExecutablePipeline {
             results
         });
 
-        // task to collect errors
+        // task to collect errors, counting them per node and keeping the failed records for the
+        // error stream when one is set
         let mut pipeline_error = PipelineError::new(&self.id, &self.name);
+        let error_stream = error_stream::stream_name(&source_stream_params);
+        let keep_failed = error_stream.is_some();
+        let error_org_id = org_id.to_string();
+        let error_pipeline_id = self.id.clone();
         let error_task = tokio::spawn(async move {
             log::debug!("[Pipeline]: starts error collecting job");
             let mut count = 0;
-            while let Some((node_id, node_type, error, fn_name)) = error_receiver.recv().await {
+            let mut failed_records = Vec::new();
+            while let Some((node_id, node_type, error, fn_name, record)) =
+                error_receiver.recv().await
+            {
+                metrics::PIPELINE_NODE_ERRORS
+                    .with_label_values(&[&error_org_id, &error_pipeline_id, &node_id, &node_type])
+                    .inc();
+                if keep_failed {
+                    failed_records.push(FailedRecord {
+                        node_id: node_id.clone(),
+                        node_type: node_type.clone(),
+                        function: fn_name.clone(),
+                        error: error.clone(),
+                        record,
+                    });
+                }
                 pipeline_error.add_node_error(node_id, node_type, error, fn_name);
                 count += 1;
             }
             log::debug!("[Pipeline]: collected {count} errors");
-            if count > 0 {
+            let pipeline_error = if count > 0 {
                 Some(pipeline_error)
             } else {
                 None
-            }
+            };
+            (pipeline_error, failed_records)
         });
 
         // Send records to the source node to begin processing
@@ -441,10 +464,22 @@ impl ExecutablePipeline {
         }
 
         // Publish errors if received any
-        if let Some(pipeline_errors) = error_task.await.map_err(|e| {
+        let (pipeline_errors, failed_records) = error_task.await.map_err(|e| {
             log::error!("[Pipeline] error collecting job failed: {e}");
             anyhow!("[Pipeline] error collecting job failed: {}", e)
-        })? {
+        })?;
+        if let Some(error_stream) = error_stream
+            && !failed_records.is_empty()
+        {
+            let entries = failed_records
+                .into_iter()
+                .map(|failed| {
+                    error_stream::to_entry(&self.id, &self.name, &source_stream_params, failed)
+                })
+                .collect();
+            error_stream::write(org_id, error_stream, entries);
+        }
+        if let Some(pipeline_errors) = pipeline_errors {
             let stream_params = self.get_source_stream_params();
             let error_data = ErrorData {
                 _timestamp: Utc::now().timestamp_micros(),
@@ -571,7 +606,7 @@ async fn process_node(
     mut child_senders: Vec<Sender<PipelineItem>>,
     function_runtime: Option<CompiledFunctionRuntime>,
     result_sender: Option<Sender<(usize, StreamParams, Value)>>,
-    error_sender: Sender<(String, String, String, Option<String>)>,
+    error_sender: Sender<(String, String, String, Option<String>, Option<Value>)>,
     pipeline_name: String,
     stream_name: Option<String>,
 ) -> Result<()> {
@@ -599,7 +634,13 @@ async fn process_node(
                             Err(e) => {
                                 let err_msg = format!("LeafNode error with flattening: {e}");
                                 if let Err(send_err) = error_sender
-                                    .send((node.id.to_string(), node.node_type(), err_msg, None))
+                                    .send((
+                                        node.id.to_string(),
+                                        node.node_type(),
+                                        err_msg,
+                                        None,
+                                        None,
+                                    ))
                                     .await
                                 {
                                     log::error!(
@@ -634,7 +675,13 @@ async fn process_node(
                                 };
                                 log::warn!("{err_msg}");
                                 if let Err(send_err) = error_sender
-                                    .send((node.id.to_string(), node.node_type(), err_msg, None))
+                                    .send((
+                                        node.id.to_string(),
+                                        node.node_type(),
+                                        err_msg,
+                                        None,
+                                        Some(record),
+                                    ))
                                     .await
                                 {
                                     log::error!(
@@ -688,7 +735,7 @@ async fn process_node(
                         Err(e) => {
                             let err_msg = format!("ConditionNode error with flattening: {e}");
                             if let Err(send_err) = error_sender
-                                .send((node.id.to_string(), node.node_type(), err_msg, None))
+                                .send((node.id.to_string(), node.node_type(), err_msg, None, None))
                                 .await
                             {
                                 log::error!(
@@ -763,6 +810,7 @@ async fn process_node(
                                         node.node_type(),
                                         err_msg.to_owned(),
                                         Some(func_params.name.to_owned()),
+                                        None,
                                     ))
                                     .await
                                 {
@@ -800,6 +848,7 @@ async fn process_node(
                                                 node.node_type(),
                                                 err_msg.to_owned(),
                                                 Some(func_params.name.to_owned()),
+                                                Some(res.clone()),
                                             ))
                                             .await
                                         {
@@ -848,6 +897,7 @@ async fn process_node(
                                                 node.node_type(),
                                                 err_msg.to_owned(),
                                                 Some(func_params.name.to_owned()),
+                                                Some(res.clone()),
                                             ))
                                             .await
                                         {
@@ -906,6 +956,7 @@ async fn process_node(
                                         node.node_type(),
                                         err_msg.to_owned(),
                                         Some(func_params.name.to_owned()),
+                                        None,
                                     ))
                                     .await
                                 {
@@ -952,6 +1003,7 @@ async fn process_node(
                                         node.node_type(),
                                         err_msg.to_owned(),
                                         Some(func_params.name.to_owned()),
+                                        None,
                                     ))
                                     .await
                                 {
@@ -1024,7 +1076,7 @@ async fn process_node(
                         Err(e) => {
                             let err_msg = format!("DestinationNode error with flattening: {e}");
                             if let Err(send_err) = error_sender
-                                .send((node.id.to_string(), node.node_type(), err_msg, None))
+                                .send((node.id.to_string(), node.node_type(), err_msg, None, None))
                                 .await
                             {
                                 log::error!(
@@ -1042,7 +1094,13 @@ async fn process_node(
                     {
                         let err_msg = format!("DestinationNode error handling timestamp: {e}");
                         if let Err(send_err) = error_sender
-                            .send((node.id.to_string(), node.node_type(), err_msg, None))
+                            .send((
+                                node.id.to_string(),
+                                node.node_type(),
+                                err_msg,
+                                None,
+                                Some(record),
+                            ))
                             .await
                         {
                             log::error!(
@@ -1139,7 +1197,13 @@ async fn process_node(
                                     "DestinationNode error persisting data for batch_key '{batch_key}' to be ingested externally: {e}"
                                 );
                                 if let Err(send_err) = error_sender
-                                    .send((node.id.to_string(), node.node_type(), err_msg, None))
+                                    .send((
+                                        node.id.to_string(),
+                                        node.node_type(),
+                                        err_msg,
+                                        None,
+                                        None,
+                                    ))
                                     .await
                                 {
                                     log::error!(
@@ -1186,7 +1250,7 @@ async fn process_node(
             let err_msg = "[Pipeline]: remote destination is not supported in open source version. Records dropped".to_string();
             log::error!("{err_msg}");
             if let Err(send_err) = error_sender
-                .send((node.id.to_string(), node.node_type(), err_msg, None))
+                .send((node.id.to_string(), node.node_type(), err_msg, None, None))
                 .await
             {
                 log::error!(
@@ -0,0 +1,157 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Records failing in a pipeline node, written with their error to the stream
+//! set by `ZO_PIPELINE_ERROR_STREAM` so that they are not only counted.
+
+use config::{
+    get_config,
+    meta::stream::{StreamParams, StreamType},
+    utils::{
+        json::{self, Value},
+        schema::format_stream_name,
+        time::now_micros,
+    },
+};
+
+/// A record that failed in a node, with the error
+#[derive(Debug, Clone)]
+pub struct FailedRecord {
+    pub node_id: String,
+    pub node_type: String,
+    pub function: Option<String>,
+    pub error: String,
+    pub record: Option<Value>,
+}
+
+/// The stream the failed records of a pipeline reading `source` are written
+/// to. None when no error stream is set, or when it is the source itself, as
+/// the failures would then feed back into the pipeline.
+pub fn stream_name(source: &StreamParams) -> Option<String> {
+    let cfg = get_config();
+    let name = cfg.pipeline.error_stream.trim();
+    if name.is_empty() {
+        return None;
+    }
+    let name = format_stream_name(name.to_string());
+    if source.stream_type == StreamType::Logs && source.stream_name.as_str() == name {
+        return None;
+    }
+    Some(name)
+}
+
+/// The entry written to the error stream for a failed record. The record is
+/// kept as a string so that its fields don't end up in the schema of the
+/// error stream.
+pub fn to_entry(
+    pipeline_id: &str,
+    pipeline_name: &str,
+    source: &StreamParams,
+    failed: FailedRecord,
+) -> Value {
+    let mut entry = json::Map::new();
+    entry.insert("_timestamp".to_string(), now_micros().into());
+    entry.insert("pipeline_id".to_string(), pipeline_id.into());
+    entry.insert("pipeline_name".to_string(), pipeline_name.into());
+    entry.insert(
+        "source_stream".to_string(),
+        source.stream_name.as_str().into(),
+    );
+    entry.insert(
+        "source_stream_type".to_string(),
+        source.stream_type.as_str().into(),
+    );
+    entry.insert("node_id".to_string(), failed.node_id.into());
+    entry.insert("node_type".to_string(), failed.node_type.into());
+    if let Some(function) = failed.function {
+        entry.insert("function".to_string(), function.into());
+    }
+    entry.insert("error".to_string(), failed.error.into());
+    if let Some(record) = failed.record {
+        entry.insert("record".to_string(), record.to_string().into());
+    }
+    Value::Object(entry)
+}
+
+/// Writes the entries to the error stream of the organization in the
+/// background, so that the ingestion of the pipeline is not held up.
+pub fn write(org_id: &str, stream_name: String, entries: Vec<Value>) {
+    if entries.is_empty() {
+        return;
+    }
+    let stream_params = StreamParams::new(org_id, &stream_name, StreamType::Logs);
+    tokio::spawn(async move {
+        let count = entries.len();
+        if let Err(e) =
+            crate::service::self_reporting::ingest_reporting_data(entries, stream_params.clone())
+                .await
+        {
+            log::error!(
+                "[Pipeline] failed writing {count} failed records to error stream {}/{}: {e}",
+                stream_params.org_id,
+                stream_params.stream_name
+            );
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_entry() {
+        let source = StreamParams::new("default", "app", StreamType::Logs);
+        let entry = to_entry(
+            "pl1",
+            "my_pipeline",
+            &source,
+            FailedRecord {
+                node_id: "n1".to_string(),
+                node_type: "function".to_string(),
+                function: Some("parse".to_string()),
+                error: "FunctionNode VRL error: boom".to_string(),
+                record: Some(json::json!({"message": "hello"})),
+            },
+        );
+        assert_eq!(entry["pipeline_id"], "pl1");
+        assert_eq!(entry["source_stream"], "app");
+        assert_eq!(entry["source_stream_type"], "logs");
+        assert_eq!(entry["function"], "parse");
+        assert_eq!(entry["record"], r#"{"message":"hello"}"#);
+        assert!(entry["_timestamp"].as_i64().unwrap() > 0);
+
+        let entry = to_entry(
+            "pl1",
+            "my_pipeline",
+            &source,
+            FailedRecord {
+                node_id: "n2".to_string(),
+                node_type: "stream".to_string(),
+                function: None,
+                error: "Dynamic Stream Name resolved to empty. Record dropped".to_string(),
+                record: None,
+            },
+        );
+        assert!(entry.get("function").is_none());
+        assert!(entry.get("record").is_none());
+    }
+
+    #[test]
+    fn test_stream_name_disabled_by_default() {
+        let source = StreamParams::new("default", "app", StreamType::Logs);
+        assert!(stream_name(&source).is_none());
+    }
+}
@@ -34,6 +34,7 @@ use crate::common::{
 };
 
 pub mod batch_execution;
+pub mod error_stream;
 
 /// Validates that no JavaScript functions are used in the pipeline.
 /// JavaScript functions are restricted from pipelines in ALL organizations (including _meta).
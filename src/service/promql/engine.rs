@@ -19,7 +19,13 @@ use arrow::array::Array;
 use async_recursion::async_recursion;
 use config::{
     TIMESTAMP_COL_NAME,
-    meta::promql::{EXEMPLARS_LABEL, HASH_LABEL, NAME_LABEL, VALUE_LABEL, value::*},
+    meta::{
+        promql::{
+            EXEMPLARS_LABEL, Function as RollupFunction, HASH_LABEL, NAME_LABEL, VALUE_LABEL,
+            get_rollup_rules, is_rollup_stream, value::*,
+        },
+        stream::StreamType,
+    },
     utils::{
         hash::{Sum64, gxhash},
         json,
@@ -78,6 +84,10 @@ pub struct Engine {
     label_selector: HashSet<String>,
     /// The result type of the query
     result_type: Option<String>,
+    /// The rollup function matching the range function whose matrix selector
+    /// is being evaluated, only set for `*_over_time` functions a rollup can
+    /// answer
+    rollup_function: Option<RollupFunction>,
 }
 
 impl Engine {
@@ -87,6 +97,7 @@ impl Engine {
             eval_ctx,
             label_selector: HashSet::new(),
             result_type: None,
+            rollup_function: None,
             trace_id: trace_id.to_string(),
         }
     }
@@ -628,18 +639,56 @@ impl Engine {
             drop(super_tx);
         }
 
-        let ctxs = self
-            .ctx
-            .table_provider
-            .create_context(
+        // read the samples before the end of the rollup from it, and the
+        // ones after from the stream
+        let lookback = range.map_or(self.ctx.lookback_delta, micros);
+        let sources = match self.rollup_function.as_ref().and_then(|function| {
+            select_rollup(
                 &self.ctx.query_ctx.org_id,
                 table_name,
-                (start, end),
-                selector.matchers.clone(),
-                self.label_selector.clone(),
-                &mut filters,
+                function,
+                start,
+                self.eval_ctx.step,
+                lookback,
             )
-            .await?;
+        }) {
+            Some((rollup_name, rollup_end)) if rollup_end >= end => {
+                vec![(rollup_name, (start, end), None)]
+            }
+            Some((rollup_name, rollup_end)) => vec![
+                (
+                    rollup_name,
+                    (start, rollup_end),
+                    Some((i64::MIN, rollup_end)),
+                ),
+                (
+                    table_name.to_string(),
+                    (rollup_end, end),
+                    Some((rollup_end, i64::MAX)),
+                ),
+            ],
+            None => vec![(table_name.to_string(), (start, end), None)],
+        };
+        let mut ctxs = Vec::new();
+        for (stream_name, time_range, bounds) in sources {
+            let stream_ctxs = self
+                .ctx
+                .table_provider
+                .create_context(
+                    &self.ctx.query_ctx.org_id,
+                    &stream_name,
+                    time_range,
+                    selector.matchers.clone(),
+                    self.label_selector.clone(),
+                    &mut filters,
+                )
+                .await?;
+            ctxs.extend(
+                stream_ctxs
+                    .into_iter()
+                    .map(|ctx| (stream_name.clone(), bounds, ctx)),
+            );
+        }
 
         // check if we need to load data from local cluster
         #[cfg(feature = "enterprise")]
@@ -662,16 +711,17 @@ impl Engine {
         let start = self.eval_ctx.start + offset_modifier;
         let end = self.eval_ctx.end + offset_modifier;
         let step = self.eval_ctx.step;
-        let lookback = range.map_or(self.ctx.lookback_delta, micros);
 
         let mut tasks = Vec::with_capacity(ctxs.len());
         let mut abort_handles = Vec::with_capacity(ctxs.len());
-        for (ctx, schema, scan_stats, keep_filters) in ctxs {
+        for (stream_name, bounds, (ctx, schema, scan_stats, keep_filters)) in ctxs {
             let query_ctx = self.ctx.query_ctx.clone();
             let mut selector = selector.clone();
             if !keep_filters {
                 selector.matchers = Matchers::empty();
             };
+            // the table of the context is named after the stream it reads
+            selector.name = Some(stream_name);
             let label_selector = label_selector.clone();
             let task = tokio::spawn(async move {
                 tokio::time::timeout(
@@ -686,6 +736,7 @@ impl Engine {
                         end,
                         step,
                         lookback,
+                        bounds,
                     ),
                 )
                 .await
@@ -1019,7 +1070,10 @@ impl Engine {
                 let last_arg = args
                     .last()
                     .expect("BUG: promql-parser should have validated function arguments");
-                self.exec_expr(&last_arg).await?
+                self.rollup_function = rollup_function(func_name, &last_arg);
+                let input = self.exec_expr(&last_arg).await;
+                self.rollup_function = None;
+                input?
             }
         };
 
@@ -1258,7 +1312,51 @@ impl Engine {
     }
 }
 
-#[allow(clippy::too_many_arguments)]
+/// The rollup function that answers the range function over a matrix
+/// selector, the aggregation of the rolled up steps equals the aggregation of
+/// the raw samples only for the same function
+fn rollup_function(func: functions::Func, arg: &PromExpr) -> Option<RollupFunction> {
+    if !matches!(arg, PromExpr::MatrixSelector(_)) {
+        return None;
+    }
+    match func {
+        functions::Func::AvgOverTime => Some(RollupFunction::Avg),
+        functions::Func::SumOverTime => Some(RollupFunction::Sum),
+        functions::Func::MinOverTime => Some(RollupFunction::Min),
+        functions::Func::MaxOverTime => Some(RollupFunction::Max),
+        _ => None,
+    }
+}
+
+/// Picks the coarsest rollup of the stream with the same function as the
+/// query whose step is within the query step and the range, returns its name
+/// and the time it is rolled up to, if it covers the start of the query
+fn select_rollup(
+    org_id: &str,
+    stream_name: &str,
+    function: &RollupFunction,
+    start: i64,
+    step: i64,
+    range: i64,
+) -> Option<(String, i64)> {
+    if is_rollup_stream(stream_name) {
+        return None;
+    }
+    get_rollup_rules().into_iter().rev().find_map(|rule| {
+        let rule_step = rule.step_micros();
+        if rule.function != *function || rule_step > step || rule_step > range {
+            return None;
+        }
+        let rollup_name = rule.stream_name(stream_name);
+        let stats =
+            infra::cache::stats::get_stream_stats(org_id, &rollup_name, StreamType::Metrics);
+        // a step is rolled up once complete, the last one ends a step after
+        // its timestamp
+        let rollup_end = stats.doc_time_max + rule_step;
+        (stats.doc_time_max > 0 && rollup_end > start).then_some((rollup_name, rollup_end))
+    })
+}
+
 async fn selector_load_data_from_datafusion(
     query_ctx: Arc<QueryContext>,
     ctx: SessionContext,
@@ -1269,6 +1367,7 @@ async fn selector_load_data_from_datafusion(
     end: i64,
     step: i64,
     lookback: i64,
+    bounds: Option<(i64, i64)>,
 ) -> Result<HashMap<u64, RangeValue>> {
     let start_time = std::time::Instant::now();
     let table_name = selector.name.as_ref().unwrap();
@@ -1316,6 +1415,14 @@ async fn selector_load_data_from_datafusion(
         }
     };
 
+    if let Some((min_ts, max_ts)) = bounds {
+        df_group = df_group.filter(
+            col(TIMESTAMP_COL_NAME)
+                .gt_eq(lit(min_ts))
+                .and(col(TIMESTAMP_COL_NAME).lt(lit(max_ts))),
+        )?;
+    }
+
     df_group = apply_matchers(df_group, &schema, &selector.matchers)?;
 
     match apply_label_selector(df_group, &schema, &label_selector) {
@@ -3356,4 +3463,23 @@ mod tests {
         let values = result.unwrap();
         assert_eq!(values.len(), 0); // Mock provider returns empty data
     }
+
+    #[test]
+    fn test_rollup_function() {
+        use crate::service::promql::functions::Func;
+
+        let matrix = promql_parser::parser::parse("test_metric[5m]").unwrap();
+        let vector = promql_parser::parser::parse("test_metric").unwrap();
+        assert_eq!(
+            rollup_function(Func::AvgOverTime, &matrix),
+            Some(RollupFunction::Avg)
+        );
+        assert_eq!(
+            rollup_function(Func::MaxOverTime, &matrix),
+            Some(RollupFunction::Max)
+        );
+        assert_eq!(rollup_function(Func::CountOverTime, &matrix), None);
+        assert_eq!(rollup_function(Func::Rate, &matrix), None);
+        assert_eq!(rollup_function(Func::SumOverTime, &vector), None);
+    }
 }
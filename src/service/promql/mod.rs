@@ -36,6 +36,7 @@ pub mod name_visitor;
 mod rewrite;
 pub mod search;
 pub mod selector_visitor;
+pub mod translate;
 mod utils;
 
 pub use engine::Engine;
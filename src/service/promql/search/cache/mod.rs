@@ -33,6 +33,7 @@ use hashbrown::HashMap;
 use infra::errors::{Error, Result};
 use once_cell::sync::Lazy;
 use prost::Message;
+use regex::Regex;
 use tokio::sync::RwLock;
 
 const METRICS_INDEX_CACHE_GC_TRIGGER_NUM: usize = 10;
@@ -40,6 +41,11 @@ const METRICS_INDEX_CACHE_GC_PERCENT: usize = 10; // 10% of the items will be re
 const METRICS_INDEX_CACHE_MAX_ITEMS: usize = 100;
 const METRICS_INDEX_CACHE_BUCKETS: usize = 100;
 
+/// `@ start()` and `@ end()` evaluate the selectors at the bounds of the
+/// range, the samples of a query using them change with its range
+static RANGE_BOUND_AT_MODIFIER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"@\s*(start|end)\s*\(\s*\)").unwrap());
+
 static CACHE_KEY_SUFFIX: Lazy<AtomicI64> = Lazy::new(|| AtomicI64::new(now_micros()));
 
 static GLOBAL_CACHE: Lazy<Vec<RwLock<MetricsIndex>>> = Lazy::new(|| {
@@ -97,18 +103,27 @@ pub async fn init() -> Result<()> {
     Ok(())
 }
 
+/// Whether the results of the query can be reused by the queries of other
+/// ranges
+pub fn is_cacheable(query: &str) -> bool {
+    !RANGE_BOUND_AT_MODIFIER.is_match(query)
+}
+
 /// Get the samples from the cache
 ///
 /// This function will return the samples from the cache if the samples are found.
-/// If the samples are not found, it will return None.
+/// If the samples are not found, it will return None. A partial hit returns the
+/// start of the range left to compute with the cached samples before it.
 pub async fn get(
+    org: &str,
     query: &str,
     start: i64,
     end: i64,
     step: i64,
 ) -> Result<Option<(i64, Vec<proto::cluster_rpc::Series>)>> {
     // get the bucket cache
-    let key = get_hash_key(query, step);
+    let query = normalize_query(query);
+    let key = get_hash_key(org, &query, start, step);
     let bucket_id = get_bucket_id(&key);
     let r = GLOBAL_CACHE[bucket_id].read().await;
     let Some(index) = r.data.get(&key) else {
@@ -247,7 +262,8 @@ pub async fn set(
     }
 
     // get the bucket cache
-    let key = get_hash_key(query, step);
+    let query = normalize_query(query);
+    let key = get_hash_key(org, &query, start, step);
     let bucket_id = get_bucket_id(&key);
     let r = GLOBAL_CACHE[bucket_id].read().await;
     if let Some(index) = r.data.get(&key) {
@@ -351,7 +367,7 @@ pub async fn set(
     let cache_item = MetricsIndexCacheItem::new(&cache_key, start, new_end);
     let mut w = GLOBAL_CACHE[bucket_id].write().await;
     w.cacher.push_back(key.to_string());
-    let index = w.data.entry(key).or_insert(MetricsIndexCache::new(query));
+    let index = w.data.entry(key).or_insert(MetricsIndexCache::new(&query));
     if index.entries.len() >= METRICS_INDEX_CACHE_MAX_ITEMS {
         // remove the first half items
         index.entries.drain(0..METRICS_INDEX_CACHE_MAX_ITEMS / 2);
@@ -397,8 +413,20 @@ async fn gc(bucket_id: usize) -> Result<()> {
     Ok(())
 }
 
-fn get_hash_key(query: &str, step: i64) -> String {
-    config::utils::md5::hash(&format!("{query}-{step}"))
+/// The results are cached per organization, query, step and alignment of the
+/// range on the step: the samples of a range query are at `start + n * step`,
+/// only the ranges with the same offset from the step boundaries share them
+fn get_hash_key(org: &str, query: &str, start: i64, step: i64) -> String {
+    let offset = if step > 0 { start.rem_euclid(step) } else { 0 };
+    config::utils::md5::hash(&format!("{org}-{query}-{step}-{offset}"))
+}
+
+/// The query as printed by the parser, so the queries differing only by their
+/// spacing share their results
+fn normalize_query(query: &str) -> String {
+    promql_parser::parser::parse(query)
+        .map(|expr| expr.to_string())
+        .unwrap_or_else(|_| query.to_string())
 }
 
 fn get_cache_item_key(prefix: &str, org: &str, start: i64, end: i64) -> String {
@@ -519,8 +547,30 @@ mod tests {
         let query = "test_query";
         let step = 60000000; // 60 seconds in microseconds
 
-        let key = get_hash_key(query, step);
-        assert_eq!(key, "b235015c612525ad7c11c109e3fdc261");
+        let key = get_hash_key("default", query, 0, step);
+        assert_eq!(key, "c037b9c83aa14f3cbb4e183c25e3a894");
+
+        // the organizations, and the alignments of the range, don't share results
+        assert_ne!(key, get_hash_key("other", query, 0, step));
+        assert_ne!(key, get_hash_key("default", query, 1_000_000, step));
+        assert_eq!(key, get_hash_key("default", query, 5 * step, step));
+    }
+
+    #[test]
+    fn test_promql_cache_normalize_query() {
+        assert_eq!(
+            normalize_query("sum by (job) (rate(http_requests_total[5m]))"),
+            normalize_query("sum  by(job)(rate( http_requests_total[5m] ))")
+        );
+        assert_eq!(normalize_query("invalid("), "invalid(");
+    }
+
+    #[test]
+    fn test_promql_cache_is_cacheable() {
+        assert!(is_cacheable("rate(http_requests_total[5m])"));
+        assert!(is_cacheable("http_requests_total @ 1700000000"));
+        assert!(!is_cacheable("http_requests_total @ start()"));
+        assert!(!is_cacheable("rate(http_requests_total[5m] @ end ( ))"));
     }
 
     #[test]
@@ -572,7 +622,7 @@ mod tests {
         assert!(set_result.is_ok());
 
         // Test getting cache
-        let get_result = get(query, start, end, step).await;
+        let get_result = get(org, query, start, end, step).await;
         assert!(get_result.is_ok());
 
         if let Ok(Some((new_start, cached_range_values))) = get_result {
@@ -625,7 +675,7 @@ mod tests {
         }
 
         // Verify that the cache size is maintained
-        let key = get_hash_key(query, step);
+        let key = get_hash_key(org, query, start, step);
         let bucket_id = get_bucket_id(&key);
         let metrics = GLOBAL_CACHE[bucket_id].read().await;
 
@@ -28,7 +28,7 @@ use config::{
         stream::StreamType,
     },
     utils::{
-        time::{now_micros, second_micros},
+        time::{hour_micros, now_micros, second_micros},
         took_watcher::TookWatcher,
     },
 };
@@ -230,7 +230,8 @@ async fn search_in_cluster(
     } = req.query.as_ref().unwrap();
 
     // cache enabled if result cache is enabled and use_cache is true and start != end
-    let use_cache = cfg.common.result_cache_enabled && req.use_cache && start != end;
+    let cacheable = cfg.common.result_cache_enabled && cache::is_cacheable(query);
+    let use_cache = cacheable && req.use_cache && start != end;
     // adjust start and end time
     let (start, end) = adjust_start_end(start, end, step);
 
@@ -258,12 +259,6 @@ async fn search_in_cluster(
             "no querier node found".to_string(),
         )));
     }
-    let nr_queriers = nodes.len() as i64;
-
-    // The number of resolution steps; see the diagram at
-    // https://promlabs.com/blog/2020/06/18/the-anatomy-of-a-promql-query/#range-queries
-    let partition_step = max(micros(DEFAULT_LOOKBACK) * 2, step);
-    let nr_steps = (end - start + partition_step - 1) / partition_step;
 
     // get cache data
     let original_start = start;
@@ -271,7 +266,7 @@ async fn search_in_cluster(
         (start, vec![])
     } else {
         let start_time = std::time::Instant::now();
-        match cache::get(query, start, end, step).await {
+        match cache::get(&req.org_id, query, start, end, step).await {
             Ok(Some((new_start, values))) => {
                 let took = start_time.elapsed().as_millis() as i32;
                 let cache_ratio = (new_start - start) as f64 / (end - start) as f64;
@@ -315,12 +310,15 @@ async fn search_in_cluster(
         )));
     }
 
-    // A span of time covered by an individual querier (worker).
-    let worker_dt = if nr_steps > nr_queriers {
-        partition_step * ((nr_steps + nr_queriers - 1) / nr_queriers)
-    } else {
-        partition_step
-    };
+    // The range is split into contiguous shards executed in parallel, the
+    // series of the shards are merged below
+    let shards = split_time_range(
+        start,
+        end,
+        step,
+        nodes.len() as i64,
+        hour_micros(cfg.limit.metrics_query_shard_hours),
+    );
 
     let job = cluster_rpc::Job {
         trace_id: trace_id.to_string(),
@@ -330,21 +328,18 @@ async fn search_in_cluster(
     };
 
     // make cluster request
-    let mut tasks = Vec::with_capacity(nodes.len());
-    let mut worker_start = start;
-    for node in nodes.iter() {
-        let node = node.clone();
-        if worker_start > end {
-            break;
-        }
+    let mut tasks = Vec::with_capacity(shards.len());
+    for (i, (shard_start, shard_end)) in shards.into_iter().enumerate() {
+        // the shards are spread round robin over the queriers
+        let node = nodes[i % nodes.len()].clone();
         let job = Some(cluster_rpc::Job {
-            partition: node.id as _,
+            partition: i as _,
             ..job.clone()
         });
         let mut req = cluster_rpc::MetricsQueryRequest { job, ..req.clone() };
         let req_query = req.query.as_mut().unwrap();
-        req_query.start = worker_start;
-        req_query.end = min(end, worker_start + worker_dt);
+        req_query.start = shard_start;
+        req_query.end = shard_end;
         // if the end time is within the last 3 retention time, we need to fetch wal data
         if req_query.end
             >= now_micros() - second_micros(cfg.limit.max_file_retention_time as i64 * 3)
@@ -352,7 +347,6 @@ async fn search_in_cluster(
             req.need_wal = true;
         }
         let req_need_wal = req.need_wal;
-        worker_start += worker_dt;
 
         log::info!(
             "[trace_id {trace_id}] promql->search->partition: node: {}, need_wal: {}, time_range: [{},{})",
@@ -488,7 +482,7 @@ async fn search_in_cluster(
     .await;
 
     // cache the result
-    if cfg.common.result_cache_enabled
+    if cacheable
         && let Some(matrix) = values.get_ref_matrix_values()
         && let Err(err) = cache::set(
             trace_id,
@@ -511,6 +505,47 @@ async fn search_in_cluster(
     Ok(values)
 }
 
+/// Splits the time range of a query into contiguous shards: one per querier,
+/// each shorter than `max_shard_dt` when it is greater than 0, so a long range
+/// is spread over more shards than queriers. A shard ends on the first point of
+/// the next one, the merge of the matrix dedups it.
+fn split_time_range(
+    start: i64,
+    end: i64,
+    step: i64,
+    nr_queriers: i64,
+    max_shard_dt: i64,
+) -> Vec<(i64, i64)> {
+    // The number of resolution steps; see the diagram at
+    // https://promlabs.com/blog/2020/06/18/the-anatomy-of-a-promql-query/#range-queries
+    let partition_step = max(micros(DEFAULT_LOOKBACK) * 2, step);
+    let nr_steps = (end - start + partition_step - 1) / partition_step;
+
+    // A span of time covered by an individual querier (worker).
+    let mut worker_dt = if nr_steps > nr_queriers {
+        partition_step * ((nr_steps + nr_queriers - 1) / nr_queriers)
+    } else {
+        partition_step
+    };
+    if max_shard_dt > 0 && worker_dt > max_shard_dt {
+        // a multiple of the step keeps the points of the shards on the grid of
+        // the query
+        let dt = max(partition_step, max_shard_dt);
+        worker_dt = (dt + step - 1) / step * step;
+    }
+
+    let mut shards = Vec::new();
+    let mut worker_start = start;
+    loop {
+        shards.push((worker_start, min(end, worker_start + worker_dt)));
+        worker_start += worker_dt;
+        if worker_start >= end {
+            break;
+        }
+    }
+    shards
+}
+
 async fn merge_matrix_query(series: &[cluster_rpc::Series], org_id: &str) -> Result<Value> {
     let mut merged_data = HashMap::new();
     let mut merged_metrics = HashMap::new();
@@ -706,6 +741,41 @@ fn should_truncate_series(series_count: usize, max_limit: usize) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_split_time_range() {
+        let step = second_micros(60);
+        let start = hour_micros(1000);
+
+        // one shard per querier
+        let shards = split_time_range(start, start + hour_micros(6), step, 3, hour_micros(24));
+        assert_eq!(
+            shards,
+            vec![
+                (start, start + hour_micros(2)),
+                (start + hour_micros(2), start + hour_micros(4)),
+                (start + hour_micros(4), start + hour_micros(6)),
+            ]
+        );
+
+        // the long range is split into more shards than queriers
+        let end = start + hour_micros(24 * 30);
+        let shards = split_time_range(start, end, step, 3, hour_micros(24));
+        assert_eq!(shards.len(), 30);
+        assert_eq!(shards[0], (start, start + hour_micros(24)));
+        assert_eq!(shards.last().unwrap().1, end);
+        assert!(shards.windows(2).all(|w| w[0].1 == w[1].0));
+        assert!(shards.iter().all(|(s, _)| (s - start) % step == 0));
+
+        // not split by duration when disabled
+        assert_eq!(split_time_range(start, end, step, 3, 0).len(), 3);
+
+        // instant query
+        assert_eq!(
+            split_time_range(start, start, step, 3, 0),
+            vec![(start, start)]
+        );
+    }
+
     #[test]
     fn test_should_truncate_series_within_limit() {
         // Test series count within limit - should not truncate
@@ -0,0 +1,535 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Translation between PromQL and SQL for the cases both can express: the
+//! samples of a metric, the `sum`, `avg`, `min`, `max` and `count` of them by
+//! labels, and the `*_over_time` functions. PromQL evaluates the last sample of
+//! every series at each step while SQL aggregates every sample of a time
+//! bucket, the translation reports such differences as warnings.
+
+use std::time::Duration;
+
+use config::{
+    TIMESTAMP_COL_NAME,
+    meta::promql::{HASH_LABEL, NAME_LABEL, VALUE_LABEL},
+    utils::schema::format_stream_name,
+};
+use promql_parser::{
+    label::{MatchOp, Matcher},
+    parser::{self, AggregateExpr, Call, Expr as PromExpr, LabelModifier, VectorSelector},
+};
+use sqlparser::{
+    ast::{
+        BinaryOperator, DuplicateTreatment, Expr, FunctionArg, FunctionArgExpr, FunctionArguments,
+        GroupByExpr, SelectItem, SetExpr, Statement, TableFactor, Value, ValueWithSpan,
+    },
+    dialect::GenericDialect,
+    parser::Parser,
+};
+
+const HISTOGRAM_ALIAS: &str = "x_axis_1";
+
+#[derive(Debug, PartialEq)]
+pub struct Translation {
+    pub query: String,
+    pub warnings: Vec<String>,
+}
+
+/// Translates a PromQL expression to SQL, aggregations use buckets of `step`
+pub fn promql_to_sql(query: &str, step: Duration) -> Result<Translation, String> {
+    let expr = parser::parse(query)?;
+    let mut warnings = vec![];
+    let sql = match unparen(&expr) {
+        PromExpr::VectorSelector(selector) => {
+            let (table, filter) = selector_to_sql(selector)?;
+            format!("SELECT * FROM \"{table}\"{filter} ORDER BY {TIMESTAMP_COL_NAME}")
+        }
+        PromExpr::Call(Call { func, args }) => {
+            let agg = match func.name {
+                "avg_over_time" => "avg",
+                "sum_over_time" => "sum",
+                "min_over_time" => "min",
+                "max_over_time" => "max",
+                "count_over_time" => "count",
+                name => return Err(format!("function {name} can not be translated to SQL")),
+            };
+            let Some(PromExpr::MatrixSelector(matrix)) = args.args.first().map(|a| unparen(a))
+            else {
+                return Err(format!("{} needs a range vector selector", func.name));
+            };
+            let (table, filter) = selector_to_sql(&matrix.vs)?;
+            warnings.push(format!(
+                "SQL buckets do not slide, every sample is counted in a single bucket of {}",
+                format_interval(matrix.range)
+            ));
+            format!(
+                "SELECT histogram({TIMESTAMP_COL_NAME}, '{}') AS {HISTOGRAM_ALIAS}, {HASH_LABEL}, {agg}({VALUE_LABEL}) AS {VALUE_LABEL} FROM \"{table}\"{filter} GROUP BY {HISTOGRAM_ALIAS}, {HASH_LABEL} ORDER BY {HISTOGRAM_ALIAS}",
+                format_interval(matrix.range)
+            )
+        }
+        PromExpr::Aggregate(AggregateExpr {
+            op,
+            expr,
+            param: _,
+            modifier,
+        }) => {
+            let op = op.to_string().to_lowercase();
+            let agg = match op.as_str() {
+                "sum" | "avg" | "min" | "max" => format!("{op}({VALUE_LABEL})"),
+                // PromQL counts series, not samples
+                "count" => format!("count(DISTINCT {HASH_LABEL})"),
+                _ => return Err(format!("aggregation {op} can not be translated to SQL")),
+            };
+            let labels = match modifier {
+                None => vec![],
+                Some(LabelModifier::Include(labels)) => labels.labels.clone(),
+                Some(LabelModifier::Exclude(_)) => {
+                    return Err(
+                        "`without` needs every label of the metric, use `by` instead".to_string(),
+                    );
+                }
+            };
+            let PromExpr::VectorSelector(selector) = unparen(expr) else {
+                return Err(format!(
+                    "only the {op} of a vector selector can be translated to SQL"
+                ));
+            };
+            let (table, filter) = selector_to_sql(selector)?;
+            if op != "count" {
+                warnings.push(format!(
+                    "PromQL takes the {op} of the last sample of every series at each step, SQL takes the {op} of every sample of the bucket"
+                ));
+            }
+            let group_by = std::iter::once(HISTOGRAM_ALIAS.to_string())
+                .chain(labels.iter().map(|l| format!("\"{l}\"")))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                "SELECT histogram({TIMESTAMP_COL_NAME}, '{}') AS {HISTOGRAM_ALIAS}, {}{agg} AS {VALUE_LABEL} FROM \"{table}\"{filter} GROUP BY {group_by} ORDER BY {HISTOGRAM_ALIAS}",
+                format_interval(step),
+                labels
+                    .iter()
+                    .map(|l| format!("\"{l}\", "))
+                    .collect::<String>(),
+            )
+        }
+        _ => {
+            return Err("only vector selectors, the sum, avg, min, max and count aggregations and the *_over_time functions can be translated to SQL".to_string());
+        }
+    };
+    Ok(Translation {
+        query: sql,
+        warnings,
+    })
+}
+
+/// Translates a SQL query to PromQL, the inverse of [promql_to_sql]
+pub fn sql_to_promql(sql: &str) -> Result<Translation, String> {
+    let statements = Parser::parse_sql(&GenericDialect {}, sql).map_err(|e| e.to_string())?;
+    let [Statement::Query(query)] = statements.as_slice() else {
+        return Err("only a single SELECT can be translated to PromQL".to_string());
+    };
+    let SetExpr::Select(select) = query.body.as_ref() else {
+        return Err("only a single SELECT can be translated to PromQL".to_string());
+    };
+    let [from] = select.from.as_slice() else {
+        return Err("the query must select from a single metric".to_string());
+    };
+    if !from.joins.is_empty() {
+        return Err("joins can not be translated to PromQL".to_string());
+    }
+    let TableFactor::Table { name, .. } = &from.relation else {
+        return Err("the query must select from a single metric".to_string());
+    };
+    let metric = name.to_string().trim_matches('"').to_string();
+    let mut warnings = vec![];
+
+    // projection: labels, at most one aggregation of the value and the histogram
+    let mut labels = vec![];
+    let mut aggregation = None;
+    let mut histogram_alias = None;
+    let mut wildcard = false;
+    for item in select.projection.iter() {
+        let (expr, alias) = match item {
+            SelectItem::UnnamedExpr(expr) => (expr, None),
+            SelectItem::ExprWithAlias { expr, alias } => (expr, Some(alias.value.clone())),
+            SelectItem::Wildcard(_) => {
+                wildcard = true;
+                continue;
+            }
+            _ => return Err(format!("unsupported select item: {item}")),
+        };
+        if let Some(column) = column_name(expr) {
+            if column != TIMESTAMP_COL_NAME && column != VALUE_LABEL && column != HASH_LABEL {
+                labels.push(column);
+            }
+            continue;
+        }
+        let Expr::Function(func) = expr else {
+            return Err(format!("unsupported select item: {item}"));
+        };
+        let func_name = func.name.to_string().to_lowercase();
+        let FunctionArguments::List(list) = &func.args else {
+            return Err(format!("unsupported select item: {item}"));
+        };
+        if func_name == "histogram" {
+            histogram_alias = Some(alias.unwrap_or_default());
+            warnings.push(
+                "the histogram interval is replaced by the step of the PromQL query".to_string(),
+            );
+            continue;
+        }
+        if aggregation.is_some() {
+            return Err("only one aggregation can be translated to PromQL".to_string());
+        }
+        let arg = list.args.first().and_then(|arg| match arg {
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(e)) => column_name(e),
+            FunctionArg::Unnamed(FunctionArgExpr::Wildcard) => Some("*".to_string()),
+            _ => None,
+        });
+        let distinct = matches!(list.duplicate_treatment, Some(DuplicateTreatment::Distinct));
+        aggregation = Some(match (func_name.as_str(), arg.as_deref()) {
+            ("sum" | "avg" | "min" | "max", Some(VALUE_LABEL)) => func_name.clone(),
+            ("count", Some(HASH_LABEL)) if distinct => "count".to_string(),
+            _ => {
+                return Err(format!(
+                    "aggregation {item} can not be translated to PromQL"
+                ));
+            }
+        });
+    }
+    if aggregation.is_some() && wildcard {
+        return Err("`*` can not be selected with an aggregation".to_string());
+    }
+
+    // the group by has to match the selected labels
+    if let GroupByExpr::Expressions(exprs, _) = &select.group_by {
+        for expr in exprs.iter() {
+            let matches_projection = match column_name(expr) {
+                Some(column) => {
+                    histogram_alias.as_deref() == Some(column.as_str()) || labels.contains(&column)
+                }
+                None => matches!(expr, Expr::Function(f)
+                    if f.name.to_string().eq_ignore_ascii_case("histogram")),
+            };
+            if !matches_projection {
+                return Err(format!(
+                    "group by {expr} does not match the selected labels"
+                ));
+            }
+        }
+    }
+
+    let mut matchers = vec![];
+    if let Some(selection) = &select.selection {
+        where_to_matchers(selection, &mut matchers, &mut warnings)?;
+    }
+    let selector = format_selector(&metric, &matchers);
+    let promql = match aggregation {
+        Some(agg) if labels.is_empty() => format!("{agg}({selector})"),
+        Some(agg) => format!("{agg} by ({}) ({selector})", labels.join(", ")),
+        None => selector,
+    };
+    Ok(Translation {
+        query: promql,
+        warnings,
+    })
+}
+
+fn unparen(expr: &PromExpr) -> &PromExpr {
+    match expr {
+        PromExpr::Paren(p) => unparen(&p.expr),
+        _ => expr,
+    }
+}
+
+/// Table name and WHERE clause of a vector selector
+fn selector_to_sql(selector: &VectorSelector) -> Result<(String, String), String> {
+    if selector.offset.is_some() || selector.at.is_some() {
+        return Err("offset and @ modifiers can not be translated to SQL".to_string());
+    }
+    if !selector.matchers.or_matchers.is_empty() {
+        return Err("`or` matchers can not be translated to SQL".to_string());
+    }
+    let name = selector.name.clone().or_else(|| {
+        selector
+            .matchers
+            .matchers
+            .iter()
+            .find(|m| m.name == NAME_LABEL && m.op == MatchOp::Equal)
+            .map(|m| m.value.clone())
+    });
+    let Some(name) = name else {
+        return Err("the metric name is required".to_string());
+    };
+    let conditions = selector
+        .matchers
+        .matchers
+        .iter()
+        .filter(|m| m.name != NAME_LABEL)
+        .map(matcher_to_sql)
+        .collect::<Vec<_>>();
+    let filter = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", conditions.join(" AND "))
+    };
+    Ok((format_stream_name(name), filter))
+}
+
+fn matcher_to_sql(matcher: &Matcher) -> String {
+    let name = &matcher.name;
+    let value = matcher.value.replace('\'', "''");
+    match &matcher.op {
+        // an empty value matches the series without the label
+        MatchOp::Equal if value.is_empty() => format!("\"{name}\" IS NULL"),
+        MatchOp::NotEqual if value.is_empty() => format!("\"{name}\" IS NOT NULL"),
+        MatchOp::Equal => format!("\"{name}\" = '{value}'"),
+        MatchOp::NotEqual => format!("\"{name}\" != '{value}'"),
+        // PromQL regular expressions are anchored
+        MatchOp::Re(_) => format!("re_match(\"{name}\", '^(?:{value})$')"),
+        MatchOp::NotRe(_) => format!("re_not_match(\"{name}\", '^(?:{value})$')"),
+    }
+}
+
+fn column_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Identifier(ident) => Some(ident.value.clone()),
+        Expr::CompoundIdentifier(idents) => idents.last().map(|i| i.value.clone()),
+        _ => None,
+    }
+}
+
+fn string_value(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Value(ValueWithSpan {
+            value: Value::SingleQuotedString(s),
+            ..
+        }) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+/// Appends the label matchers of a conjunction of simple label conditions
+fn where_to_matchers(
+    expr: &Expr,
+    matchers: &mut Vec<String>,
+    warnings: &mut Vec<String>,
+) -> Result<(), String> {
+    match expr {
+        Expr::Nested(expr) => where_to_matchers(expr, matchers, warnings),
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            where_to_matchers(left, matchers, warnings)?;
+            where_to_matchers(right, matchers, warnings)
+        }
+        Expr::BinaryOp { left, .. } if column_name(left).as_deref() == Some(TIMESTAMP_COL_NAME) => {
+            warnings.push(
+                "conditions on _timestamp are replaced by the time range of the PromQL query"
+                    .to_string(),
+            );
+            Ok(())
+        }
+        Expr::BinaryOp { left, op, right } => {
+            let (Some(name), Some(value)) = (column_name(left), string_value(right)) else {
+                return Err(format!("condition {expr} can not be translated to PromQL"));
+            };
+            let op = match op {
+                BinaryOperator::Eq => "=",
+                BinaryOperator::NotEq => "!=",
+                _ => return Err(format!("condition {expr} can not be translated to PromQL")),
+            };
+            matchers.push(format!("{name}{op}\"{}\"", escape_promql(&value)));
+            Ok(())
+        }
+        Expr::IsNull(e) | Expr::IsNotNull(e) => {
+            let Some(name) = column_name(e) else {
+                return Err(format!("condition {expr} can not be translated to PromQL"));
+            };
+            let op = if matches!(expr, Expr::IsNull(_)) {
+                "="
+            } else {
+                "!="
+            };
+            matchers.push(format!("{name}{op}\"\""));
+            Ok(())
+        }
+        Expr::Function(func) => {
+            let func_name = func.name.to_string().to_lowercase();
+            let op = match func_name.as_str() {
+                "re_match" => "=~",
+                "re_not_match" => "!~",
+                _ => return Err(format!("condition {expr} can not be translated to PromQL")),
+            };
+            let FunctionArguments::List(list) = &func.args else {
+                return Err(format!("condition {expr} can not be translated to PromQL"));
+            };
+            let args = list
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    FunctionArg::Unnamed(FunctionArgExpr::Expr(e)) => Some(e),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            let (Some(name), Some(pattern)) = (
+                args.first().and_then(|e| column_name(e)),
+                args.get(1).and_then(|e| string_value(e)),
+            ) else {
+                return Err(format!("condition {expr} can not be translated to PromQL"));
+            };
+            matchers.push(format!(
+                "{name}{op}\"{}\"",
+                escape_promql(&anchored_regex(&pattern))
+            ));
+            Ok(())
+        }
+        _ => Err(format!("condition {expr} can not be translated to PromQL")),
+    }
+}
+
+/// SQL regular expressions match anywhere in the value, PromQL ones are
+/// anchored
+fn anchored_regex(pattern: &str) -> String {
+    if let Some(inner) = pattern
+        .strip_prefix("^(?:")
+        .and_then(|p| p.strip_suffix(")$"))
+    {
+        inner.to_string()
+    } else if let Some(inner) = pattern.strip_prefix('^').and_then(|p| p.strip_suffix('$')) {
+        inner.to_string()
+    } else {
+        format!(".*(?:{pattern}).*")
+    }
+}
+
+fn escape_promql(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn format_selector(metric: &str, matchers: &[String]) -> String {
+    let is_identifier = metric
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == ':')
+        && metric
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':');
+    if is_identifier {
+        if matchers.is_empty() {
+            metric.to_string()
+        } else {
+            format!("{metric}{{{}}}", matchers.join(", "))
+        }
+    } else {
+        let name = format!("{NAME_LABEL}=\"{}\"", escape_promql(metric));
+        let matchers = std::iter::once(name)
+            .chain(matchers.iter().cloned())
+            .collect::<Vec<_>>();
+        format!("{{{}}}", matchers.join(", "))
+    }
+}
+
+fn format_interval(d: Duration) -> String {
+    format!("{} second", d.as_secs().max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEP: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn test_promql_to_sql_selector() {
+        let t = promql_to_sql(
+            r#"up{job="api", instance=~"web-.*", env!="", zone=""}"#,
+            STEP,
+        )
+        .unwrap();
+        assert_eq!(
+            t.query,
+            r#"SELECT * FROM "up" WHERE "job" = 'api' AND re_match("instance", '^(?:web-.*)$') AND "env" IS NOT NULL AND "zone" IS NULL ORDER BY _timestamp"#
+        );
+        assert!(t.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_promql_to_sql_aggregation() {
+        let t = promql_to_sql(r#"sum by (job) (http_requests{code="500"})"#, STEP).unwrap();
+        assert_eq!(
+            t.query,
+            r#"SELECT histogram(_timestamp, '60 second') AS x_axis_1, "job", sum(value) AS value FROM "http_requests" WHERE "code" = '500' GROUP BY x_axis_1, "job" ORDER BY x_axis_1"#
+        );
+        assert_eq!(t.warnings.len(), 1);
+
+        let t = promql_to_sql("count(up)", STEP).unwrap();
+        assert_eq!(
+            t.query,
+            r#"SELECT histogram(_timestamp, '60 second') AS x_axis_1, count(DISTINCT __hash__) AS value FROM "up" GROUP BY x_axis_1 ORDER BY x_axis_1"#
+        );
+        assert!(t.warnings.is_empty());
+
+        assert!(promql_to_sql("sum without (job) (up)", STEP).is_err());
+        assert!(promql_to_sql("sum(rate(up[5m]))", STEP).is_err());
+        assert!(promql_to_sql("up offset 5m", STEP).is_err());
+    }
+
+    #[test]
+    fn test_promql_to_sql_over_time() {
+        let t = promql_to_sql(r#"max_over_time(cpu{host="a"}[5m])"#, STEP).unwrap();
+        assert_eq!(
+            t.query,
+            r#"SELECT histogram(_timestamp, '300 second') AS x_axis_1, __hash__, max(value) AS value FROM "cpu" WHERE "host" = 'a' GROUP BY x_axis_1, __hash__ ORDER BY x_axis_1"#
+        );
+        assert!(promql_to_sql("rate(cpu[5m])", STEP).is_err());
+    }
+
+    #[test]
+    fn test_sql_to_promql() {
+        let t = sql_to_promql(
+            r#"SELECT * FROM "up" WHERE job = 'api' AND re_match(instance, '^(?:web-.*)$') AND zone IS NULL"#,
+        )
+        .unwrap();
+        assert_eq!(t.query, r#"up{job="api", instance=~"web-.*", zone=""}"#);
+
+        let t = sql_to_promql(
+            "SELECT histogram(_timestamp, '1 minute') AS x_axis_1, job, sum(value) AS value FROM http_requests WHERE code != '500' AND _timestamp > 0 GROUP BY x_axis_1, job",
+        )
+        .unwrap();
+        assert_eq!(t.query, r#"sum by (job) (http_requests{code!="500"})"#);
+        assert_eq!(t.warnings.len(), 2);
+
+        let t = sql_to_promql("SELECT count(DISTINCT __hash__) FROM \"k8s.cpu\"").unwrap();
+        assert_eq!(t.query, r#"count({__name__="k8s.cpu"})"#);
+
+        let t = sql_to_promql("SELECT * FROM up WHERE re_match(job, 'api')").unwrap();
+        assert_eq!(t.query, r#"up{job=~".*(?:api).*"}"#);
+
+        assert!(sql_to_promql("SELECT job, sum(value) FROM up GROUP BY env").is_err());
+        assert!(sql_to_promql("SELECT * FROM up WHERE value > 1").is_err());
+        assert!(sql_to_promql("SELECT * FROM up JOIN down ON up.a = down.a").is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let promql = r#"avg by (job) (up{env="prod"})"#;
+        let sql = promql_to_sql(promql, STEP).unwrap().query;
+        assert_eq!(sql_to_promql(&sql).unwrap().query, promql);
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The bookkeeping shared by the ingestion and the search quotas.
+//!
+//! Each node counts its own consumption over a sliding window and publishes
+//! it to the cluster coordinator every sync interval. The consumption of the
+//! other nodes is added to the local one, so a quota holds for the whole
+//! cluster, give or take one sync interval.
+
+use std::ops::AddAssign;
+
+use config::{cluster::LOCAL_NODE, get_config, utils::json};
+use hashbrown::HashMap;
+use infra::errors::Result;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+/// Counter over a sliding window of `WIDTH` microseconds, the count of the
+/// previous window is weighted by the part of it still in the window
+#[derive(Debug, Default)]
+pub struct SlidingCounter<const WIDTH: i64> {
+    slot: i64,
+    count: u64,
+    prev: u64,
+}
+
+impl<const WIDTH: i64> SlidingCounter<WIDTH> {
+    fn roll(&mut self, slot: i64) {
+        if slot <= self.slot {
+            return;
+        }
+        self.prev = if slot == self.slot + 1 { self.count } else { 0 };
+        self.count = 0;
+        self.slot = slot;
+    }
+
+    pub fn add(&mut self, now: i64, n: u64) {
+        self.roll(now / WIDTH);
+        self.count += n;
+    }
+
+    pub fn value(&mut self, now: i64) -> f64 {
+        self.roll(now / WIDTH);
+        let weight = 1.0 - (now % WIDTH) as f64 / WIDTH as f64;
+        self.prev as f64 * weight + self.count as f64
+    }
+}
+
+/// Consumption published by a node
+#[derive(Debug, Serialize, Deserialize)]
+struct NodeUsage<T> {
+    updated_at: i64,
+    usage: HashMap<String, T>,
+}
+
+/// Publishes the consumption of this node under `prefix` and returns the one
+/// of the other nodes, summed by key. A node is skipped when it did not
+/// publish for 3 sync intervals, and its entry is deleted after 10.
+pub async fn exchange<T>(
+    prefix: &str,
+    sync_interval: u64,
+    now: i64,
+    usage: HashMap<String, T>,
+) -> Result<HashMap<String, T>>
+where
+    T: Serialize + DeserializeOwned + AddAssign + Default,
+{
+    // a single node has nothing to share
+    if get_config().common.local_mode {
+        return Ok(HashMap::new());
+    }
+
+    let coordinator = infra::coordinator::get_coordinator().await;
+    let key = format!("{prefix}{}", LOCAL_NODE.uuid);
+    let data = NodeUsage {
+        updated_at: now,
+        usage,
+    };
+    coordinator
+        .put(&key, json::to_vec(&data)?.into(), false, None)
+        .await?;
+
+    let interval = sync_interval as i64 * 1_000_000;
+    let mut remote = HashMap::new();
+    for (k, v) in coordinator.list(prefix).await? {
+        if k == key {
+            continue;
+        }
+        let node: NodeUsage<T> = match json::from_slice(&v) {
+            Ok(v) => v,
+            Err(e) => {
+                log::warn!("[QUOTA] invalid usage at {k}: {e}");
+                continue;
+            }
+        };
+        let age = now - node.updated_at;
+        // the node is gone, its usage is dropped
+        if age > 10 * interval {
+            if let Err(e) = coordinator.delete(&k, false, false, None).await {
+                log::error!("[QUOTA] delete stale usage at {k} error: {e}");
+            }
+            continue;
+        }
+        if age > 3 * interval {
+            continue;
+        }
+        merge(&mut remote, node.usage);
+    }
+    Ok(remote)
+}
+
+fn merge<T: AddAssign + Default>(dst: &mut HashMap<String, T>, src: HashMap<String, T>) {
+    for (key, usage) in src {
+        *dst.entry(key).or_default() += usage;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sliding_counter() {
+        let mut c = SlidingCounter::<1_000_000>::default();
+        c.add(10_000_000, 100);
+        c.add(10_500_000, 100);
+        assert_eq!(c.value(10_900_000), 200.0);
+        // a quarter of the previous window is still in the window
+        c.add(11_750_000, 10);
+        assert_eq!(c.value(11_750_000), 60.0);
+        // nothing left after two windows
+        assert_eq!(c.value(13_000_000), 0.0);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut dst = HashMap::new();
+        merge(&mut dst, HashMap::from([("a".to_string(), 1.0)]));
+        merge(
+            &mut dst,
+            HashMap::from([("a".to_string(), 1.0), ("b".to_string(), 2.0)]),
+        );
+        assert_eq!(dst["a"], 2.0);
+        assert_eq!(dst["b"], 2.0);
+    }
+}
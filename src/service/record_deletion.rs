@@ -0,0 +1,167 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Deletion of the records matching a filter
+//!
+//! A right to be forgotten request deletes the records of a user, not a time
+//! range or a whole stream. The deletion is a job run by a compactor, which
+//! rewrites the files of the stream holding matching records without them,
+//! see [`crate::service::compact::record_deletion`]. The job only deletes the
+//! records stored when it was created.
+
+use std::sync::Arc;
+
+use arrow_schema::{DataType, Schema};
+use config::{
+    ider,
+    meta::record_deletion::{RecordDeletion, RecordDeletionRequest, RecordDeletionStatus},
+    utils::time::now_micros,
+};
+use datafusion::{
+    common::DFSchema,
+    logical_expr::{Expr, ExprSchemable},
+    prelude::SessionContext,
+};
+use hashbrown::HashSet;
+
+use crate::service::db;
+
+#[derive(Debug, thiserror::Error)]
+pub enum RecordDeletionError {
+    #[error("{0}")]
+    InvalidRequest(String),
+
+    #[error("Stream not found")]
+    StreamNotFound,
+
+    #[error("Record deletion not found")]
+    NotFound,
+
+    #[error("Record deletion is running")]
+    Running,
+
+    #[error(transparent)]
+    InfraError(#[from] infra::errors::Error),
+}
+
+pub async fn create(
+    org_id: &str,
+    mut req: RecordDeletionRequest,
+    user_email: &str,
+) -> Result<RecordDeletion, RecordDeletionError> {
+    let now = now_micros();
+    req.validate(now)
+        .map_err(RecordDeletionError::InvalidRequest)?;
+    let schema = infra::schema::get(org_id, &req.stream_name, req.stream_type)
+        .await
+        .map_err(|_| RecordDeletionError::StreamNotFound)?;
+    if schema.fields().is_empty() {
+        return Err(RecordDeletionError::StreamNotFound);
+    }
+    parse_filter(&SessionContext::new(), &Arc::new(schema), &req.filter)
+        .map_err(RecordDeletionError::InvalidRequest)?;
+
+    let job = RecordDeletion {
+        id: ider::uuid(),
+        request: req,
+        status: RecordDeletionStatus::Pending,
+        files_scanned: 0,
+        files_rewritten: 0,
+        records_deleted: 0,
+        files_skipped: 0,
+        error: None,
+        created_by: user_email.to_string(),
+        created_at: now,
+        updated_at: now,
+    };
+    db::record_deletion::set(org_id, &job).await?;
+    Ok(job)
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<RecordDeletion, RecordDeletionError> {
+    db::record_deletion::get(org_id, id)
+        .await
+        .map_err(|_| RecordDeletionError::NotFound)
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<RecordDeletion>, RecordDeletionError> {
+    Ok(db::record_deletion::list(org_id).await?)
+}
+
+/// Deletes the job, a pending one is cancelled, a running one can't be
+/// deleted
+pub async fn delete(org_id: &str, id: &str) -> Result<(), RecordDeletionError> {
+    let job = get(org_id, id).await?;
+    if job.status == RecordDeletionStatus::Running {
+        return Err(RecordDeletionError::Running);
+    }
+    db::record_deletion::delete(org_id, id).await?;
+    Ok(())
+}
+
+/// Parses the filter against the schema, returns the condition and the
+/// fields it uses
+pub fn parse_filter(
+    ctx: &SessionContext,
+    schema: &Arc<Schema>,
+    filter: &str,
+) -> Result<(Expr, HashSet<String>), String> {
+    let df_schema = DFSchema::try_from(schema.as_ref().clone()).map_err(|e| e.to_string())?;
+    let expr = ctx
+        .parse_sql_expr(filter, &df_schema)
+        .map_err(|e| format!("invalid filter: {e}"))?;
+    match expr.get_type(&df_schema) {
+        Ok(DataType::Boolean) => {}
+        Ok(data_type) => {
+            return Err(format!(
+                "invalid filter: it is a {data_type}, not a condition"
+            ));
+        }
+        Err(e) => return Err(format!("invalid filter: {e}")),
+    }
+    ctx.create_physical_expr(expr.clone(), &df_schema)
+        .map_err(|e| format!("invalid filter: {e}"))?;
+    let fields = expr
+        .column_refs()
+        .into_iter()
+        .map(|c| c.name.clone())
+        .collect();
+    Ok((expr, fields))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_schema::Field;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_filter() {
+        let ctx = SessionContext::new();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("_timestamp", DataType::Int64, false),
+            Field::new("user_id", DataType::Utf8, true),
+            Field::new("took", DataType::Int64, true),
+        ]));
+        let (_, fields) = parse_filter(&ctx, &schema, "user_id = 'u-42' AND took > 10").unwrap();
+        assert_eq!(
+            fields,
+            HashSet::from(["user_id".to_string(), "took".to_string()])
+        );
+        assert!(parse_filter(&ctx, &schema, "email = 'a@b.c'").is_err());
+        assert!(parse_filter(&ctx, &schema, "took + 1").is_err());
+        assert!(parse_filter(&ctx, &schema, "user_id = ").is_err());
+    }
+}
@@ -0,0 +1,302 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! PromQL recording rules
+//!
+//! Evaluates PromQL expressions on a schedule and writes their results as new
+//! metrics, so dashboards read precomputed series instead of running the
+//! expensive aggregations on every refresh. Every rule has a trigger in the
+//! scheduler, so the evaluations are spread over the alert managers like
+//! alerts and reports, and are not lost when a node restarts.
+
+use config::{
+    TIMESTAMP_COL_NAME, ider,
+    meta::{
+        promql::{
+            NAME_LABEL, TYPE_LABEL, VALUE_LABEL,
+            value::{Labels, Value},
+        },
+        recording_rules::{RecordingRule, RecordingRuleRun},
+    },
+    utils::{json, time::now_micros},
+};
+
+use crate::{
+    common::meta::ingestion::{IngestUser, SystemJobType},
+    service::{db, promql},
+};
+
+/// Errors that can occur when interacting with recording rules.
+#[derive(Debug, thiserror::Error)]
+pub enum RecordingRuleError {
+    #[error("{0}")]
+    InvalidRule(String),
+
+    #[error("Recording rule not found")]
+    RuleNotFound,
+
+    #[error(transparent)]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Failed to write the recorded series: {0}")]
+    WriteError(String),
+}
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+
+fn validate(rule: &RecordingRule) -> Result<(), RecordingRuleError> {
+    rule.validate().map_err(RecordingRuleError::InvalidRule)?;
+    promql_parser::parser::parse(&rule.expr)
+        .map_err(|e| RecordingRuleError::InvalidRule(format!("invalid expr: {e}")))?;
+    Ok(())
+}
+
+pub async fn create(
+    org_id: &str,
+    mut rule: RecordingRule,
+) -> Result<RecordingRule, RecordingRuleError> {
+    validate(&rule)?;
+    rule.id = ider::uuid();
+    rule.last_run = None;
+    db::recording_rules::set(org_id, &rule).await?;
+    save_trigger(org_id, &rule).await?;
+    Ok(rule)
+}
+
+pub async fn update(
+    org_id: &str,
+    id: &str,
+    mut rule: RecordingRule,
+) -> Result<RecordingRule, RecordingRuleError> {
+    validate(&rule)?;
+    let old = get(org_id, id).await?;
+    rule.id = old.id;
+    rule.last_run = old.last_run;
+    db::recording_rules::set(org_id, &rule).await?;
+    if rule.interval != old.interval {
+        save_trigger(org_id, &rule).await?;
+    }
+    Ok(rule)
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<RecordingRule, RecordingRuleError> {
+    db::recording_rules::get(org_id, id)
+        .await
+        .map_err(|_| RecordingRuleError::RuleNotFound)
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<RecordingRule>, RecordingRuleError> {
+    Ok(db::recording_rules::list(org_id).await?)
+}
+
+pub async fn delete(org_id: &str, id: &str) -> Result<(), RecordingRuleError> {
+    get(org_id, id).await?;
+    db::recording_rules::delete(org_id, id).await?;
+    if let Err(e) =
+        db::scheduler::delete(org_id, db::scheduler::TriggerModule::RecordingRule, id).await
+    {
+        log::error!("[RECORDING_RULE] failed to delete trigger of rule {org_id}/{id}: {e}");
+    }
+    Ok(())
+}
+
+/// Evaluates `rule` at `timestamp` and writes the result to the metrics
+/// stream of the rule name. Series without a finite value are skipped.
+pub async fn run(
+    trace_id: &str,
+    org_id: &str,
+    rule: &RecordingRule,
+    timestamp: i64,
+) -> Result<RecordingRuleRun, RecordingRuleError> {
+    let req = promql::MetricsQueryRequest {
+        query: rule.expr.clone(),
+        start: timestamp,
+        end: timestamp,
+        step: 300_000_000, // 5m
+        query_exemplars: false,
+        use_cache: None,
+        search_type: None,
+        regions: vec![],
+        clusters: vec![],
+    };
+    // check super cluster
+    #[cfg(not(feature = "enterprise"))]
+    let is_super_cluster = false;
+    #[cfg(feature = "enterprise")]
+    let is_super_cluster = o2_enterprise::enterprise::common::config::get_config()
+        .super_cluster
+        .enabled;
+    let value = promql::search::search(trace_id, org_id, &req, "", 0, is_super_cluster).await?;
+    let records = to_records(rule, value, timestamp)?;
+
+    let run = RecordingRuleRun {
+        timestamp,
+        series: records.len(),
+        ..Default::default()
+    };
+    if records.is_empty() {
+        return Ok(run);
+    }
+    let body = json::to_vec(&records).map_err(|e| RecordingRuleError::WriteError(e.to_string()))?;
+    let resp = crate::service::metrics::json::ingest(
+        org_id,
+        None,
+        body.into(),
+        IngestUser::SystemJob(SystemJobType::RecordingRules),
+    )
+    .await
+    .map_err(|e| RecordingRuleError::WriteError(e.to_string()))?;
+    if resp.code != 200 {
+        return Err(RecordingRuleError::WriteError(
+            resp.error
+                .unwrap_or_else(|| format!("status {}", resp.code)),
+        ));
+    }
+    Ok(run)
+}
+
+/// The series of the result as metrics records named after the rule, the
+/// labels of the rule replacing the ones of the result
+fn to_records(
+    rule: &RecordingRule,
+    value: Value,
+    timestamp: i64,
+) -> Result<Vec<json::Value>, RecordingRuleError> {
+    let series: Vec<(Labels, f64)> = match value {
+        Value::Vector(values) => values
+            .into_iter()
+            .map(|v| (v.labels, v.sample.value))
+            .collect(),
+        Value::Instant(v) => vec![(v.labels, v.sample.value)],
+        Value::Sample(v) => vec![(vec![], v.value)],
+        Value::Float(v) => vec![(vec![], v)],
+        Value::None => vec![],
+        v => {
+            return Err(RecordingRuleError::InvalidRule(format!(
+                "expr must return an instant vector or a scalar, not a {}",
+                v.get_type()
+            )));
+        }
+    };
+    Ok(series
+        .into_iter()
+        .filter(|(_, value)| value.is_finite())
+        .map(|(labels, value)| {
+            let mut record = json::Map::new();
+            // the labels starting with `__` are internal to the result
+            for label in labels.iter().filter(|l| !l.name.starts_with("__")) {
+                record.insert(label.name.clone(), label.value.clone().into());
+            }
+            for (name, value) in rule.labels.iter() {
+                record.insert(name.clone(), value.clone().into());
+            }
+            record.insert(NAME_LABEL.to_string(), rule.name.clone().into());
+            record.insert(TYPE_LABEL.to_string(), "gauge".into());
+            record.insert(TIMESTAMP_COL_NAME.to_string(), timestamp.into());
+            record.insert(VALUE_LABEL.to_string(), value.into());
+            json::Value::Object(record)
+        })
+        .collect())
+}
+
+/// Returns the first evaluation time after `now` aligned on the interval
+pub fn next_run_at(now: i64, interval: i64) -> i64 {
+    let interval = interval.max(1) * MICROS_PER_SECOND;
+    (now / interval + 1) * interval
+}
+
+async fn save_trigger(org_id: &str, rule: &RecordingRule) -> Result<(), RecordingRuleError> {
+    let trigger = db::scheduler::Trigger {
+        org: org_id.to_string(),
+        module: db::scheduler::TriggerModule::RecordingRule,
+        module_key: rule.id.clone(),
+        next_run_at: next_run_at(now_micros(), rule.interval),
+        ..Default::default()
+    };
+    if db::scheduler::exists(org_id, trigger.module.clone(), &rule.id).await {
+        db::scheduler::update_trigger(trigger, false, "").await?;
+    } else {
+        db::scheduler::push(trigger).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, sync::Arc};
+
+    use config::meta::promql::value::{InstantValue, Label, Sample};
+
+    use super::*;
+
+    fn instant(labels: &[(&str, &str)], value: f64) -> InstantValue {
+        InstantValue {
+            labels: labels
+                .iter()
+                .map(|(name, value)| {
+                    Arc::new(Label {
+                        name: name.to_string(),
+                        value: value.to_string(),
+                    })
+                })
+                .collect(),
+            sample: Sample {
+                timestamp: 0,
+                value,
+            },
+        }
+    }
+
+    #[test]
+    fn test_next_run_at() {
+        let minute = 60 * MICROS_PER_SECOND;
+        assert_eq!(next_run_at(minute + 1, 60), 2 * minute);
+        assert_eq!(next_run_at(minute, 60), 2 * minute);
+        assert_eq!(next_run_at(minute + 1, 0), minute + MICROS_PER_SECOND);
+    }
+
+    #[test]
+    fn test_to_records() {
+        let rule = RecordingRule {
+            name: "job:http_requests:rate5m".to_string(),
+            expr: "sum by (job) (rate(http_requests_total[5m]))".to_string(),
+            labels: BTreeMap::from([("env".to_string(), "prod".to_string())]),
+            ..Default::default()
+        };
+        let value = Value::Vector(vec![
+            instant(&[("job", "api"), ("env", "dev"), ("__hash__", "1")], 1.5),
+            instant(&[("job", "web")], f64::NAN),
+        ]);
+        let records = to_records(&rule, value, 1000).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(
+            records[0],
+            json::json!({
+                "job": "api",
+                "env": "prod",
+                "__name__": "job:http_requests:rate5m",
+                "__type__": "gauge",
+                "_timestamp": 1000,
+                "value": 1.5
+            })
+        );
+
+        let records = to_records(&rule, Value::Float(2.0), 1000).unwrap();
+        assert_eq!(records[0]["value"], 2.0);
+        assert_eq!(records[0]["env"], "prod");
+
+        assert!(to_records(&rule, Value::Matrix(vec![]), 1000).is_err());
+    }
+}
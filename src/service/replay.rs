@@ -0,0 +1,292 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Ingestion replays
+//!
+//! Reads back the records a stream stored over a past time range and writes
+//! them to another stream, through a pipeline when one is given, so the data
+//! can be reprocessed, e.g. with a fixed parser, without touching the stream.
+//! The records keep their `_timestamp`. They are read one window at a time,
+//! in pages ordered by time, and the replay is a trigger of the scheduler
+//! which reads a bounded number of records per run, so long ranges don't hold
+//! an alert manager and aren't lost when a node restarts.
+
+use config::{
+    ALL_VALUES_COL_NAME, ID_COL_NAME, ORIGINAL_DATA_COL_NAME, TIMESTAMP_COL_NAME, ider,
+    meta::{
+        replay::{Replay, ReplayRequest, ReplayStatus},
+        search,
+    },
+    utils::{
+        json,
+        time::{hour_micros, now_micros},
+    },
+};
+use proto::cluster_rpc;
+
+use crate::service::{
+    db, ingestion::ingestion_service, pipeline::batch_execution::ExecutablePipeline,
+};
+
+/// Records read by one search
+const PAGE_SIZE: usize = 10_000;
+
+/// Records read by one run of the trigger
+const RECORDS_PER_RUN: usize = 100_000;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayError {
+    #[error("{0}")]
+    InvalidReplay(String),
+
+    #[error("Stream not found")]
+    StreamNotFound,
+
+    #[error("Pipeline not found")]
+    PipelineNotFound,
+
+    #[error("Replay not found")]
+    ReplayNotFound,
+
+    #[error(transparent)]
+    InfraError(#[from] infra::errors::Error),
+}
+
+pub async fn create(
+    org_id: &str,
+    req: ReplayRequest,
+    user_email: &str,
+) -> Result<Replay, ReplayError> {
+    let now = now_micros();
+    req.validate(now).map_err(ReplayError::InvalidReplay)?;
+    let schema = infra::schema::get(org_id, &req.stream_name, req.stream_type)
+        .await
+        .map_err(|_| ReplayError::StreamNotFound)?;
+    if schema.fields().is_empty() {
+        return Err(ReplayError::StreamNotFound);
+    }
+    if req.use_original && schema.field_with_name(ORIGINAL_DATA_COL_NAME).is_err() {
+        return Err(ReplayError::InvalidReplay(format!(
+            "stream {} doesn't store the original records",
+            req.stream_name
+        )));
+    }
+    if let Some(pipeline_id) = &req.pipeline_id {
+        match db::pipeline::get_by_id(pipeline_id).await {
+            Ok(pipeline) if pipeline.org == org_id => {}
+            _ => return Err(ReplayError::PipelineNotFound),
+        }
+    }
+
+    let replay = Replay {
+        id: ider::uuid(),
+        position: req.start_time,
+        request: req,
+        status: ReplayStatus::Running,
+        offset: 0,
+        records_read: 0,
+        records_written: 0,
+        error: None,
+        created_by: user_email.to_string(),
+        created_at: now,
+        updated_at: now,
+    };
+    db::replay::set(org_id, &replay).await?;
+    db::scheduler::push(db::scheduler::Trigger {
+        org: org_id.to_string(),
+        module: db::scheduler::TriggerModule::Replay,
+        module_key: replay.id.clone(),
+        next_run_at: now,
+        ..Default::default()
+    })
+    .await?;
+    Ok(replay)
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<Replay, ReplayError> {
+    db::replay::get(org_id, id)
+        .await
+        .map_err(|_| ReplayError::ReplayNotFound)
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<Replay>, ReplayError> {
+    Ok(db::replay::list(org_id).await?)
+}
+
+/// Deletes the replay and stops it if it is running, the records already
+/// written are kept
+pub async fn delete(org_id: &str, id: &str) -> Result<(), ReplayError> {
+    get(org_id, id).await?;
+    db::replay::delete(org_id, id).await?;
+    if let Err(e) = db::scheduler::delete(org_id, db::scheduler::TriggerModule::Replay, id).await {
+        log::error!("[REPLAY] failed to delete trigger of replay {org_id}/{id}: {e}");
+    }
+    Ok(())
+}
+
+/// Replays the next pages of records, returns whether the replay reached its
+/// end time
+pub async fn run(trace_id: &str, org_id: &str, replay: &mut Replay) -> Result<bool, anyhow::Error> {
+    let pipeline = match &replay.request.pipeline_id {
+        Some(pipeline_id) => {
+            let pipeline = db::pipeline::get_by_id(pipeline_id).await?;
+            Some(ExecutablePipeline::new(&pipeline).await?)
+        }
+        None => None,
+    };
+
+    let mut records_read = 0;
+    while records_read < RECORDS_PER_RUN {
+        if replay.position >= replay.request.end_time {
+            replay.status = ReplayStatus::Completed;
+            break;
+        }
+        let window_end = (replay.position + hour_micros(1)).min(replay.request.end_time);
+        // a page which fails fails the run, the pages replayed before are
+        // kept and the retry starts after them
+        let hits = read_page(trace_id, org_id, replay, window_end).await?;
+        let read = hits.len();
+        let records = hits
+            .into_iter()
+            .map(|hit| to_record(hit, replay.request.use_original))
+            .collect::<Vec<_>>();
+        let written = write(org_id, replay, pipeline.as_ref(), records).await?;
+
+        records_read += read;
+        replay.records_read += read;
+        replay.records_written += written;
+        if read < PAGE_SIZE {
+            replay.position = window_end;
+            replay.offset = 0;
+        } else {
+            replay.offset += read;
+        }
+    }
+    replay.updated_at = now_micros();
+    Ok(replay.status == ReplayStatus::Completed)
+}
+
+async fn read_page(
+    trace_id: &str,
+    org_id: &str,
+    replay: &Replay,
+    window_end: i64,
+) -> Result<Vec<json::Value>, anyhow::Error> {
+    let req = search::Request {
+        query: search::Query {
+            sql: format!(
+                "SELECT * FROM \"{}\" ORDER BY {TIMESTAMP_COL_NAME} ASC",
+                replay.request.stream_name
+            ),
+            start_time: replay.position,
+            end_time: window_end,
+            from: replay.offset as i64,
+            size: PAGE_SIZE as i64,
+            ..Default::default()
+        },
+        // like the scheduled pipelines, the search keeps the `_original` column
+        search_type: Some(search::SearchEventType::DerivedStream),
+        use_cache: false,
+        ..Default::default()
+    };
+    let resp =
+        crate::service::search::search(trace_id, org_id, replay.request.stream_type, None, &req)
+            .await?;
+    Ok(resp.hits)
+}
+
+/// The record to replay from a stored one, the original record when asked for
+/// and stored, with the stored `_timestamp`
+fn to_record(hit: json::Value, use_original: bool) -> json::Value {
+    let json::Value::Object(mut record) = hit else {
+        return hit;
+    };
+    let original = record.remove(ORIGINAL_DATA_COL_NAME);
+    record.remove(ID_COL_NAME);
+    record.remove(ALL_VALUES_COL_NAME);
+    if use_original
+        && let Some(json::Value::String(original)) = original
+        && let Ok(json::Value::Object(mut original)) = json::from_str::<json::Value>(&original)
+    {
+        if let Some(timestamp) = record.remove(TIMESTAMP_COL_NAME) {
+            original.insert(TIMESTAMP_COL_NAME.to_string(), timestamp);
+        }
+        return json::Value::Object(original);
+    }
+    json::Value::Object(record)
+}
+
+/// Writes the records to the destination stream, after the pipeline of the
+/// replay when there is one, returns the number of records written
+async fn write(
+    org_id: &str,
+    replay: &Replay,
+    pipeline: Option<&ExecutablePipeline>,
+    records: Vec<json::Value>,
+) -> Result<usize, anyhow::Error> {
+    let records = match pipeline {
+        Some(pipeline) if !records.is_empty() => pipeline
+            .process_batch(org_id, records, Some(replay.request.stream_name.clone()))
+            .await?
+            .into_values()
+            .flat_map(|results| results.into_iter().map(|(_, record)| record))
+            .collect(),
+        _ => records,
+    };
+    if records.is_empty() {
+        return Ok(0);
+    }
+    let written = records.len();
+    let req = cluster_rpc::IngestionRequest {
+        org_id: org_id.to_string(),
+        stream_name: replay.request.destination_stream.clone(),
+        stream_type: replay.request.stream_type.to_string(),
+        data: Some(cluster_rpc::IngestionData::from(records)),
+        ingestion_type: Some(cluster_rpc::IngestionType::Json.into()),
+        metadata: None,
+    };
+    match ingestion_service::ingest(req).await {
+        Ok(resp) if resp.status_code == 200 => Ok(written),
+        Ok(resp) => Err(anyhow::anyhow!(resp.message)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_record() {
+        let hit = json::json!({
+            "_timestamp": 1000,
+            "_o2_id": 1,
+            "level": "info",
+            "_original": "{\"log\":\"GET / 200\",\"_timestamp\":5}",
+        });
+        assert_eq!(
+            to_record(hit.clone(), false),
+            json::json!({"_timestamp": 1000, "level": "info"})
+        );
+        assert_eq!(
+            to_record(hit, true),
+            json::json!({"_timestamp": 1000, "log": "GET / 200"})
+        );
+
+        // records without the original are replayed as stored
+        let hit = json::json!({"_timestamp": 1000, "level": "info"});
+        assert_eq!(to_record(hit.clone(), true), hit);
+    }
+}
@@ -0,0 +1,440 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Scheduled exports
+//!
+//! Runs a search on a schedule and writes its results as CSV or Parquet to
+//! the object store configured for the org. Every export has a trigger in the
+//! scheduler, so the runs are spread over the alert managers like alerts and
+//! reports, and are not lost when a node restarts.
+
+use std::sync::Arc;
+
+use chrono::{TimeZone, Utc};
+use config::{
+    get_config, ider,
+    meta::{
+        exports::{ExportDestination, ExportFormat, ExportProvider, ExportRun, ScheduledExport},
+        search,
+        sql::resolve_stream_names,
+        stream::StreamType,
+    },
+    utils::{json, schema::infer_json_schema_from_values, time::now_micros},
+};
+use object_store::{ObjectStore, aws::AmazonS3Builder, gcp::GoogleCloudStorageBuilder, path::Path};
+
+use crate::service::db;
+
+/// Errors that can occur when interacting with scheduled exports.
+#[derive(Debug, thiserror::Error)]
+pub enum ExportError {
+    #[error("{0}")]
+    InvalidExport(String),
+
+    #[error("Scheduled export not found")]
+    ExportNotFound,
+
+    #[error("Export destination is not configured for the organization")]
+    DestinationNotFound,
+
+    #[error("Unauthorized access to stream {0}")]
+    PermissionDenied(String),
+
+    #[error(transparent)]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error(transparent)]
+    ObjectStoreError(#[from] object_store::Error),
+
+    #[error("Failed to encode the results: {0}")]
+    EncodeError(String),
+}
+
+const MICROS_PER_MINUTE: i64 = 60_000_000;
+/// Shown instead of the stored credentials
+const MASKED_SECRET: &str = "********";
+
+pub async fn create(
+    org_id: &str,
+    user_id: &str,
+    mut export: ScheduledExport,
+) -> Result<ScheduledExport, ExportError> {
+    export.validate().map_err(ExportError::InvalidExport)?;
+    check_streams(org_id, user_id, &export).await?;
+    if db::scheduled_exports::get_destination(org_id)
+        .await
+        .is_err()
+    {
+        return Err(ExportError::DestinationNotFound);
+    }
+    export.id = ider::uuid();
+    export.owner = user_id.to_string();
+    export.last_run = None;
+    db::scheduled_exports::set(org_id, &export).await?;
+    save_trigger(org_id, &export).await?;
+    Ok(export)
+}
+
+pub async fn update(
+    org_id: &str,
+    user_id: &str,
+    id: &str,
+    mut export: ScheduledExport,
+) -> Result<ScheduledExport, ExportError> {
+    export.validate().map_err(ExportError::InvalidExport)?;
+    check_streams(org_id, user_id, &export).await?;
+    let old = get(org_id, id).await?;
+    export.id = old.id;
+    export.owner = user_id.to_string();
+    export.last_run = old.last_run;
+    db::scheduled_exports::set(org_id, &export).await?;
+    if export.frequency != old.frequency {
+        save_trigger(org_id, &export).await?;
+    }
+    Ok(export)
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<ScheduledExport, ExportError> {
+    db::scheduled_exports::get(org_id, id)
+        .await
+        .map_err(|_| ExportError::ExportNotFound)
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<ScheduledExport>, ExportError> {
+    Ok(db::scheduled_exports::list(org_id).await?)
+}
+
+pub async fn delete(org_id: &str, id: &str) -> Result<(), ExportError> {
+    get(org_id, id).await?;
+    db::scheduled_exports::delete(org_id, id).await?;
+    if let Err(e) =
+        db::scheduler::delete(org_id, db::scheduler::TriggerModule::ScheduledExport, id).await
+    {
+        log::error!("[SCHEDULED_EXPORT] failed to delete trigger of export {org_id}/{id}: {e}");
+    }
+    Ok(())
+}
+
+pub async fn get_destination(org_id: &str) -> Result<ExportDestination, ExportError> {
+    db::scheduled_exports::get_destination(org_id)
+        .await
+        .map(|destination| destination.masked())
+        .map_err(|_| ExportError::DestinationNotFound)
+}
+
+pub async fn set_destination(
+    org_id: &str,
+    mut destination: ExportDestination,
+) -> Result<(), ExportError> {
+    // the masked credentials returned by get are sent back unchanged
+    if let Ok(old) = db::scheduled_exports::get_destination(org_id).await {
+        for (secret, old) in [
+            (&mut destination.access_key, old.access_key),
+            (&mut destination.secret_key, old.secret_key),
+            (
+                &mut destination.service_account_key,
+                old.service_account_key,
+            ),
+        ] {
+            if secret == MASKED_SECRET {
+                *secret = old;
+            }
+        }
+    }
+    destination.validate().map_err(ExportError::InvalidExport)?;
+    build_store(&destination)?;
+    Ok(db::scheduled_exports::set_destination(org_id, &destination).await?)
+}
+
+pub async fn delete_destination(org_id: &str) -> Result<(), ExportError> {
+    Ok(db::scheduled_exports::delete_destination(org_id).await?)
+}
+
+/// Exports the window of `export` ending at `end_time`. Nothing is written
+/// when the search returns no rows.
+pub async fn run(
+    trace_id: &str,
+    org_id: &str,
+    export: &ScheduledExport,
+    end_time: i64,
+) -> Result<ExportRun, ExportError> {
+    // the streams are checked again, the permissions of the owner may have
+    // changed since the export was saved
+    if export.owner.is_empty() {
+        return Err(ExportError::InvalidExport(
+            "the export has no owner, save it again".to_string(),
+        ));
+    }
+    check_streams(org_id, &export.owner, export).await?;
+    let destination = db::scheduled_exports::get_destination(org_id)
+        .await
+        .map_err(|_| ExportError::DestinationNotFound)?;
+    let req = search::Request {
+        query: search::Query {
+            sql: export.sql.clone(),
+            start_time: end_time - export.period() * MICROS_PER_MINUTE,
+            end_time,
+            size: get_config().limit.scheduled_export_max_rows as i64,
+            ..Default::default()
+        },
+        search_type: Some(search::SearchEventType::Reports),
+        use_cache: false,
+        ..Default::default()
+    };
+    let resp = crate::service::search::search(
+        trace_id,
+        org_id,
+        export.stream_type,
+        Some(export.owner.clone()),
+        &req,
+    )
+    .await?;
+
+    let mut run = ExportRun {
+        end_time,
+        rows: resp.hits.len(),
+        ..Default::default()
+    };
+    if resp.hits.is_empty() {
+        return Ok(run);
+    }
+    let data = match export.format {
+        ExportFormat::Csv => encode_csv(&resp.hits)?,
+        ExportFormat::Parquet => encode_parquet(&resp.hits)?,
+    };
+    let store = build_store(&destination)?;
+    let object = object_key(&destination, export, end_time);
+    store.put(&Path::from(object.as_str()), data.into()).await?;
+    run.object = object;
+    Ok(run)
+}
+
+/// Returns the first run time after `now` aligned on the frequency, so the
+/// exported windows start on round times.
+pub fn next_run_at(now: i64, frequency: i64) -> i64 {
+    let frequency = frequency.max(1) * MICROS_PER_MINUTE;
+    (now / frequency + 1) * frequency
+}
+
+/// Checks that the user can read every stream the SQL of the export reads.
+#[cfg_attr(not(feature = "enterprise"), allow(unused_variables))]
+async fn check_streams(
+    org_id: &str,
+    user_id: &str,
+    export: &ScheduledExport,
+) -> Result<(), ExportError> {
+    let stream_names = resolve_stream_names(&export.sql)
+        .map_err(|e| ExportError::InvalidExport(format!("Invalid SQL: {e}")))?;
+    #[cfg(feature = "enterprise")]
+    for stream_name in stream_names {
+        if crate::handler::http::request::search::utils::check_stream_permissions(
+            &stream_name,
+            org_id,
+            user_id,
+            &export.stream_type,
+        )
+        .await
+        .is_some()
+        {
+            return Err(ExportError::PermissionDenied(stream_name));
+        }
+    }
+    Ok(())
+}
+
+async fn save_trigger(org_id: &str, export: &ScheduledExport) -> Result<(), ExportError> {
+    let trigger = db::scheduler::Trigger {
+        org: org_id.to_string(),
+        module: db::scheduler::TriggerModule::ScheduledExport,
+        module_key: export.id.clone(),
+        next_run_at: next_run_at(now_micros(), export.frequency),
+        ..Default::default()
+    };
+    if db::scheduler::exists(org_id, trigger.module.clone(), &export.id).await {
+        db::scheduler::update_trigger(trigger, false, "").await?;
+    } else {
+        db::scheduler::push(trigger).await?;
+    }
+    Ok(())
+}
+
+pub(crate) fn build_store(
+    destination: &ExportDestination,
+) -> Result<Arc<dyn ObjectStore>, ExportError> {
+    let store: Arc<dyn ObjectStore> = match destination.provider {
+        ExportProvider::S3 => {
+            // not from the environment, the exports never use the credentials of the node
+            let mut builder = AmazonS3Builder::new()
+                .with_bucket_name(&destination.bucket)
+                .with_access_key_id(&destination.access_key)
+                .with_secret_access_key(&destination.secret_key);
+            if !destination.region.is_empty() {
+                builder = builder.with_region(&destination.region);
+            }
+            if !destination.endpoint.is_empty() {
+                builder = builder
+                    .with_endpoint(&destination.endpoint)
+                    .with_allow_http(destination.endpoint.starts_with("http://"));
+            }
+            Arc::new(builder.build()?)
+        }
+        ExportProvider::Gcs => {
+            let builder = GoogleCloudStorageBuilder::new()
+                .with_bucket_name(&destination.bucket)
+                .with_service_account_key(&destination.service_account_key);
+            Arc::new(builder.build()?)
+        }
+    };
+    Ok(store)
+}
+
+/// `prefix/path/YYYY/MM/DD/YYYYMMDDTHHMMSSZ.ext`, the time being the end of
+/// the exported window.
+fn object_key(destination: &ExportDestination, export: &ScheduledExport, end_time: i64) -> String {
+    let time = Utc.timestamp_nanos(end_time * 1000);
+    let dir = if export.path.trim_matches('/').is_empty() {
+        export.id.as_str()
+    } else {
+        export.path.trim_matches('/')
+    };
+    let key = [
+        destination.prefix.trim_matches('/'),
+        dir,
+        &time.format("%Y/%m/%d/%Y%m%dT%H%M%SZ").to_string(),
+    ]
+    .into_iter()
+    .filter(|v| !v.is_empty())
+    .collect::<Vec<_>>()
+    .join("/");
+    format!("{key}.{}", export.format.extension())
+}
+
+/// Writes the rows with a header holding every column, in the order they are
+/// first seen.
+fn encode_csv(hits: &[json::Value]) -> Result<Vec<u8>, ExportError> {
+    let mut columns: Vec<&str> = Vec::new();
+    for hit in hits {
+        if let Some(row) = hit.as_object() {
+            for key in row.keys() {
+                if !columns.contains(&key.as_str()) {
+                    columns.push(key.as_str());
+                }
+            }
+        }
+    }
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let encode_err = |e: csv::Error| ExportError::EncodeError(e.to_string());
+    writer.write_record(&columns).map_err(encode_err)?;
+    for hit in hits {
+        let record = columns.iter().map(|column| match hit.get(*column) {
+            None | Some(json::Value::Null) => String::new(),
+            Some(json::Value::String(v)) => v.to_string(),
+            Some(v) => v.to_string(),
+        });
+        writer.write_record(record).map_err(encode_err)?;
+    }
+    writer
+        .into_inner()
+        .map_err(|e| ExportError::EncodeError(e.to_string()))
+}
+
+fn encode_parquet(hits: &[json::Value]) -> Result<Vec<u8>, ExportError> {
+    let encode_err = |e: arrow_schema::ArrowError| ExportError::EncodeError(e.to_string());
+    let schema =
+        Arc::new(infer_json_schema_from_values(hits.iter(), StreamType::Logs).map_err(encode_err)?);
+    let mut decoder = arrow_json::ReaderBuilder::new(schema.clone())
+        .build_decoder()
+        .map_err(encode_err)?;
+    decoder.serialize(hits).map_err(encode_err)?;
+    let mut buf = Vec::new();
+    let mut writer = parquet::arrow::ArrowWriter::try_new(&mut buf, schema, None)
+        .map_err(|e| ExportError::EncodeError(e.to_string()))?;
+    if let Some(batch) = decoder.flush().map_err(encode_err)? {
+        writer
+            .write(&batch)
+            .map_err(|e| ExportError::EncodeError(e.to_string()))?;
+    }
+    writer
+        .close()
+        .map_err(|e| ExportError::EncodeError(e.to_string()))?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_run_at() {
+        let hour = 60 * MICROS_PER_MINUTE;
+        assert_eq!(next_run_at(hour + 1, 60), 2 * hour);
+        assert_eq!(next_run_at(hour, 60), 2 * hour);
+        assert_eq!(next_run_at(hour + 1, 0), hour + MICROS_PER_MINUTE);
+    }
+
+    #[test]
+    fn test_object_key() {
+        let destination = ExportDestination {
+            bucket: "bi".to_string(),
+            prefix: "/openobserve/".to_string(),
+            ..Default::default()
+        };
+        let mut export = ScheduledExport {
+            id: "abc".to_string(),
+            format: ExportFormat::Parquet,
+            ..Default::default()
+        };
+        // 2026-01-02T03:04:05Z
+        let end_time = 1_767_323_045_000_000;
+        assert_eq!(
+            object_key(&destination, &export, end_time),
+            "openobserve/abc/2026/01/02/20260102T030405Z.parquet"
+        );
+        export.path = "daily/errors".to_string();
+        export.format = ExportFormat::Csv;
+        assert_eq!(
+            object_key(&ExportDestination::default(), &export, end_time),
+            "daily/errors/2026/01/02/20260102T030405Z.csv"
+        );
+    }
+
+    #[test]
+    fn test_encode_csv() {
+        let hits = vec![
+            json::json!({"host": "a", "count": 1}),
+            json::json!({"host": "b, c", "count": 2, "level": null}),
+            json::json!({"level": "error"}),
+        ];
+        let data = String::from_utf8(encode_csv(&hits).unwrap()).unwrap();
+        assert_eq!(data, "host,count,level\na,1,\n\"b, c\",2,\n,,error\n");
+    }
+
+    #[test]
+    fn test_encode_parquet() {
+        let hits = vec![
+            json::json!({"host": "a", "count": 1}),
+            json::json!({"host": "b", "count": 2}),
+        ];
+        let data = encode_parquet(&hits).unwrap();
+        let reader = parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder::try_new(
+            bytes::Bytes::from(data),
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+        let rows = reader.map(|batch| batch.unwrap().num_rows()).sum::<usize>();
+        assert_eq!(rows, 2);
+    }
+}
@@ -30,16 +30,16 @@ use config::{
             BUCKET_LABEL, EXEMPLARS_LABEL, HASH_LABEL, METADATA_LABEL, NAME_LABEL, QUANTILE_LABEL,
             VALUE_LABEL,
         },
-        stream::StreamType,
+        stream::{StreamSettings, StreamSettingsTemplate, StreamType},
     },
     metrics,
     utils::{json, schema::infer_json_schema_from_map, schema_ext::SchemaExt, time::now_micros},
 };
-use datafusion::arrow::datatypes::{Field, Schema};
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
 use hashbrown::HashSet;
 use infra::schema::{
     STREAM_RECORD_ID_GENERATOR, STREAM_SCHEMAS_LATEST, STREAM_SETTINGS, SchemaCache,
-    unwrap_stream_settings,
+    get_stream_setting_pinned_fields, unwrap_stream_settings,
 };
 use serde_json::{Map, Value};
 
@@ -347,8 +347,24 @@ pub(crate) async fn handle_diff_schema(
         .await;
     }
 
+    // apply the org template to streams created by ingestion
+    let mut settings_changed = false;
+    let mut stream_setting = match unwrap_stream_settings(&final_schema) {
+        Some(setting) => setting,
+        None => {
+            let mut setting = StreamSettings::default();
+            if is_new
+                && let Some(template) =
+                    db::organization::get_stream_settings_template(org_id, stream_type).await
+            {
+                apply_settings_template(&template, &final_schema, stream_type, &mut setting);
+                settings_changed = true;
+            }
+            setting
+        }
+    };
+
     // check defined_schema_fields
-    let mut stream_setting = unwrap_stream_settings(&final_schema).unwrap_or_default();
     let mut defined_schema_fields = stream_setting.defined_schema_fields.clone();
 
     // Automatically enable User-defined schema when
@@ -424,6 +440,10 @@ pub(crate) async fn handle_diff_schema(
 
         defined_schema_fields = uds_fields.into_iter().collect::<Vec<_>>();
         stream_setting.defined_schema_fields = defined_schema_fields.clone();
+        settings_changed = true;
+    }
+
+    if settings_changed {
         final_schema.metadata.insert(
             "settings".to_string(),
             json::to_string(&stream_setting).unwrap(),
@@ -488,6 +508,26 @@ pub(crate) async fn handle_diff_schema(
     }))
 }
 
+/// Fills the settings of a new stream from the org template. Full text
+/// search keys must be text fields of the schema, user defined schema fields
+/// are dropped when they are no longer allowed.
+fn apply_settings_template(
+    template: &StreamSettingsTemplate,
+    schema: &Schema,
+    stream_type: StreamType,
+    setting: &mut StreamSettings,
+) {
+    template.apply(setting);
+    setting.full_text_search_keys.retain(|key| {
+        schema
+            .field_with_name(key)
+            .is_ok_and(|f| matches!(f.data_type(), DataType::Utf8 | DataType::LargeUtf8))
+    });
+    if !get_config().common.allow_user_defined_schemas || !stream_type.support_uds() {
+        setting.defined_schema_fields.clear();
+    }
+}
+
 // Generate filtered schema for UDS (User Defined Schema)
 // if defined_schema_fields is not empty, and schema fields greater than defined_schema_fields + 10,
 // then we will use defined_schema_fields
@@ -573,6 +613,7 @@ pub fn get_schema_changes(schema: &SchemaCache, inferred_schema: &Schema) -> (bo
     let mut field_datatype_delta: Vec<Field> = vec![];
 
     let stream_setting = unwrap_stream_settings(schema.schema());
+    let pinned_fields = get_stream_setting_pinned_fields(&stream_setting);
     let defined_schema_fields = stream_setting
         .map(|s| s.defined_schema_fields)
         .unwrap_or_default();
@@ -590,6 +631,18 @@ pub fn get_schema_changes(schema: &SchemaCache, inferred_schema: &Schema) -> (bo
                     continue;
                 }
                 let existing_field: Arc<Field> = schema.schema().fields()[*idx].clone();
+                if let Some(pinned) = pinned_fields.iter().find(|f| f.name() == item_name) {
+                    if existing_field.data_type() != pinned.data_type() {
+                        // the slow path migrates the field to its pinned type
+                        is_schema_changed = true;
+                    } else if item_data_type != pinned.data_type() {
+                        let mut meta = existing_field.metadata().clone();
+                        meta.insert("zo_cast".to_owned(), true.to_string());
+                        field_datatype_delta
+                            .push(existing_field.as_ref().clone().with_metadata(meta));
+                    }
+                    continue;
+                }
                 if existing_field.data_type() != item_data_type {
                     if infra::schema::is_widening_conversion(
                         existing_field.data_type(),
@@ -654,8 +707,6 @@ pub async fn stream_schema_exists(
 mod tests {
     use std::str::FromStr;
 
-    use datafusion::arrow::datatypes::DataType;
-
     use super::*;
 
     #[tokio::test]
@@ -707,4 +758,63 @@ mod tests {
         let value_iter = record_val.into_iter();
         infer_json_schema_from_map(value_iter, stream_type).unwrap();
     }
+
+    #[test]
+    fn test_apply_settings_template() {
+        let schema = Schema::new(vec![
+            Field::new("log", DataType::Utf8, true),
+            Field::new("code", DataType::Int64, true),
+            Field::new("_timestamp", DataType::Int64, false),
+        ]);
+        let template = StreamSettingsTemplate {
+            full_text_search_keys: vec!["log".to_string(), "code".to_string(), "body".to_string()],
+            index_fields: vec!["service".to_string()],
+            data_retention: 14,
+            ..Default::default()
+        };
+        let mut setting = StreamSettings::default();
+        apply_settings_template(&template, &schema, StreamType::Logs, &mut setting);
+        assert_eq!(setting.full_text_search_keys, vec!["log"]);
+        assert_eq!(setting.index_fields, vec!["service"]);
+        assert_eq!(setting.data_retention, 14);
+
+        // user defined schema needs a stream type supporting it
+        let template = StreamSettingsTemplate {
+            defined_schema_fields: vec!["log".to_string()],
+            ..Default::default()
+        };
+        let mut setting = StreamSettings::default();
+        apply_settings_template(&template, &schema, StreamType::Metadata, &mut setting);
+        assert!(setting.defined_schema_fields.is_empty());
+    }
+
+    #[test]
+    fn test_get_schema_changes_pinned_field() {
+        let settings = StreamSettings {
+            pinned_field_types: vec![config::meta::stream::DataField::new(
+                "code",
+                config::meta::stream::DataType::Int64,
+            )],
+            ..Default::default()
+        };
+        let metadata =
+            HashMap::from([("settings".to_string(), json::to_string(&settings).unwrap())]);
+        let inferred_schema = Schema::new(vec![Field::new("code", DataType::Utf8, true)]);
+
+        let schema = SchemaCache::new(
+            Schema::new(vec![Field::new("code", DataType::Int64, true)])
+                .with_metadata(metadata.clone()),
+        );
+        let (is_changed, delta) = get_schema_changes(&schema, &inferred_schema);
+        assert!(!is_changed);
+        assert_eq!(delta.len(), 1);
+        assert_eq!(delta[0].data_type(), &DataType::Int64);
+        assert!(delta[0].metadata().contains_key("zo_cast"));
+
+        let schema = SchemaCache::new(
+            Schema::new(vec![Field::new("code", DataType::Float64, true)]).with_metadata(metadata),
+        );
+        let (is_changed, _) = get_schema_changes(&schema, &inferred_schema);
+        assert!(is_changed);
+    }
 }
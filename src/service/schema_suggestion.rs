@@ -0,0 +1,477 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! User defined schema suggestions. The fields searches reference and the
+//! fields most records carry make the suggested user defined schema, fields
+//! often searched with many distinct values are suggested for the secondary
+//! index. Wide streams are analyzed in the background, the suggestion is kept
+//! in the kv store until it is applied or replaced.
+
+use std::collections::{HashMap, HashSet};
+
+use arrow::util::display::array_value_to_string;
+use arrow_schema::DataType;
+use config::{
+    ALL_VALUES_COL_NAME, ID_COL_NAME, ORIGINAL_DATA_COL_NAME, TIMESTAMP_COL_NAME, get_config,
+    meta::stream::{StreamSettings, StreamType, UpdateSettingsWrapper, UpdateStreamSettings},
+    utils::{json, parquet::read_recordbatch_from_bytes, time::now_micros},
+};
+use infra::schema::{STREAM_SCHEMAS_LATEST, unwrap_partition_time_level};
+
+use crate::{
+    common::meta::stream::{FieldSuggestion, SchemaSuggestion, StreamFieldUsage},
+    service::{db::kv, field_usage, file_list},
+};
+
+/// Number of the most recent records the presence and cardinality of fields
+/// are measured on
+const SAMPLE_RECORDS: usize = 1000;
+
+/// Time range of the files sampled, ending at the newest data of the stream
+const SAMPLE_TIME_RANGE_MICROS: i64 = 24 * 3600 * 1_000_000;
+
+/// Share of the sampled records a field needs to be in the schema without
+/// being searched
+const DENSE_FIELD_PRESENCE: f64 = 0.9;
+
+/// Searches a field needs to be suggested for the secondary index
+const MIN_INDEX_USAGE: u64 = 10;
+
+/// Distinct values counted per field, enough to tell apart low and high
+/// cardinality fields
+const MAX_DISTINCT_VALUES: usize = SAMPLE_RECORDS;
+
+/// Presence and cardinality of a field in the sampled records
+#[derive(Debug, Default)]
+struct SampleStats {
+    present: usize,
+    distinct: HashSet<String>,
+}
+
+fn suggestion_key(stream_type: StreamType, stream_name: &str) -> String {
+    format!("schema_suggestion/{stream_type}/{stream_name}")
+}
+
+/// Analyzes the wide streams whose suggestion is older than the run interval
+pub async fn run() {
+    let cfg = get_config();
+    let streams = STREAM_SCHEMAS_LATEST
+        .read()
+        .await
+        .iter()
+        .filter(|(_, schema)| {
+            schema.schema().fields().len() >= cfg.common.schema_suggestion_min_fields
+        })
+        .map(|(key, _)| key.clone())
+        .collect::<Vec<_>>();
+    // every compactor runs the job, skip what another one analyzed lately
+    let fresh_since = now_micros() - (cfg.common.schema_suggestion_interval as i64 * 1_000_000 / 2);
+    for key in streams {
+        let mut parts = key.splitn(3, '/');
+        let (Some(org_id), Some(stream_type), Some(stream_name)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let stream_type = StreamType::from(stream_type);
+        if !matches!(
+            stream_type,
+            StreamType::Logs | StreamType::Metrics | StreamType::Traces
+        ) {
+            continue;
+        }
+        if get(org_id, stream_type, stream_name)
+            .await
+            .is_some_and(|s| s.generated_at > fresh_since)
+        {
+            continue;
+        }
+        let trace_id = config::ider::generate_trace_id();
+        if let Err(e) = refresh(&trace_id, org_id, stream_type, stream_name).await {
+            log::error!("[SCHEMA_SUGGESTION] failed to analyze {key}: {e}");
+        }
+    }
+}
+
+/// Analyzes a stream and saves the suggestion, `None` when the stream does not
+/// exist
+pub async fn refresh(
+    trace_id: &str,
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> Result<Option<SchemaSuggestion>, anyhow::Error> {
+    let Some(suggestion) = analyze(trace_id, org_id, stream_type, stream_name).await? else {
+        return Ok(None);
+    };
+    kv::set(
+        org_id,
+        &suggestion_key(stream_type, stream_name),
+        json::to_vec(&suggestion)?.into(),
+    )
+    .await?;
+    Ok(Some(suggestion))
+}
+
+/// Saved suggestion of a stream
+pub async fn get(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> Option<SchemaSuggestion> {
+    let val = kv::get(org_id, &suggestion_key(stream_type, stream_name))
+        .await
+        .ok()?;
+    json::from_slice(&val).ok()
+}
+
+pub async fn delete(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> Result<(), anyhow::Error> {
+    if kv::get(org_id, &suggestion_key(stream_type, stream_name))
+        .await
+        .is_err()
+    {
+        return Ok(());
+    }
+    kv::delete(org_id, &suggestion_key(stream_type, stream_name)).await
+}
+
+async fn analyze(
+    trace_id: &str,
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> Result<Option<SchemaSuggestion>, anyhow::Error> {
+    let schema = infra::schema::get(org_id, stream_name, stream_type).await?;
+    if schema.fields().is_empty() {
+        return Ok(None);
+    }
+    let settings = infra::schema::get_settings(org_id, stream_name, stream_type)
+        .await
+        .unwrap_or_default();
+    let usage = field_usage::get(org_id, stream_type, stream_name).await;
+    let (samples, sampled_records) =
+        sample(trace_id, org_id, stream_type, stream_name, &settings).await?;
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|f| (f.name().to_string(), f.data_type().clone()))
+        .collect::<Vec<_>>();
+    Ok(Some(suggest(
+        &fields,
+        &settings,
+        &usage,
+        &samples,
+        sampled_records,
+        get_config().limit.user_defined_schema_max_fields,
+    )))
+}
+
+/// Measures the presence and cardinality of fields on the newest records
+async fn sample(
+    trace_id: &str,
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    settings: &StreamSettings,
+) -> Result<(HashMap<String, SampleStats>, usize), anyhow::Error> {
+    let mut samples: HashMap<String, SampleStats> = HashMap::new();
+    let stats = infra::cache::stats::get_stream_stats(org_id, stream_name, stream_type);
+    if stats.doc_time_max == 0 {
+        return Ok((samples, 0));
+    }
+    let time_level = unwrap_partition_time_level(settings.partition_time_level, stream_type);
+    let files = file_list::query(
+        trace_id,
+        org_id,
+        stream_type,
+        stream_name,
+        time_level,
+        stats.doc_time_max - SAMPLE_TIME_RANGE_MICROS,
+        stats.doc_time_max + 1,
+    )
+    .await?;
+
+    let mut sampled_records = 0;
+    for file in files.iter().rev() {
+        if sampled_records >= SAMPLE_RECORDS {
+            break;
+        }
+        let data = infra::storage::get_bytes(&file.account, &file.key).await?;
+        let (_, batches) = read_recordbatch_from_bytes(&data).await?;
+        for batch in batches {
+            let rows = batch.num_rows().min(SAMPLE_RECORDS - sampled_records);
+            if rows == 0 {
+                break;
+            }
+            let batch = batch.slice(0, rows);
+            for (field, column) in batch.schema().fields().iter().zip(batch.columns()) {
+                let stats = samples.entry(field.name().to_string()).or_default();
+                for i in 0..rows {
+                    if column.is_null(i) {
+                        continue;
+                    }
+                    stats.present += 1;
+                    if stats.distinct.len() < MAX_DISTINCT_VALUES {
+                        stats.distinct.insert(array_value_to_string(column, i)?);
+                    }
+                }
+            }
+            sampled_records += rows;
+        }
+    }
+    Ok((samples, sampled_records))
+}
+
+fn is_system_field(name: &str) -> bool {
+    name == TIMESTAMP_COL_NAME
+        || name == ID_COL_NAME
+        || name == ORIGINAL_DATA_COL_NAME
+        || name == ALL_VALUES_COL_NAME
+        || name == get_config().common.column_all
+}
+
+fn suggest(
+    fields: &[(String, DataType)],
+    settings: &StreamSettings,
+    usage: &StreamFieldUsage,
+    samples: &HashMap<String, SampleStats>,
+    sampled_records: usize,
+    max_fields: usize,
+) -> SchemaSuggestion {
+    let total_fields = fields.len();
+    let fts_fields = infra::schema::get_stream_setting_fts_fields(&Some(settings.clone()));
+    let mut candidates = Vec::new();
+    for (name, data_type) in fields.iter() {
+        // the system fields are always kept
+        if is_system_field(name) {
+            continue;
+        }
+        let field_usage = usage.fields.get(name).map(|u| u.total()).unwrap_or(0);
+        let (present, distinct_values) = samples
+            .get(name)
+            .map(|s| (s.present, s.distinct.len()))
+            .unwrap_or_default();
+        let presence = if sampled_records > 0 {
+            present as f64 / sampled_records as f64
+        } else {
+            0.0
+        };
+        let is_fts = fts_fields.contains(name);
+        let is_index = settings.index_fields.contains(name);
+
+        let mut reasons = Vec::new();
+        if field_usage > 0 {
+            reasons.push(format!("referenced by {field_usage} searches"));
+        }
+        if is_fts {
+            reasons.push("full text search field".to_string());
+        }
+        if is_index {
+            reasons.push("secondary index field".to_string());
+        }
+        if presence >= DENSE_FIELD_PRESENCE {
+            reasons.push(format!(
+                "present in {:.0}% of the sampled records",
+                presence * 100.0
+            ));
+        }
+        if reasons.is_empty() {
+            continue;
+        }
+
+        // equality filters on many distinct values are what the secondary
+        // index speeds up, few distinct values are better served by partitions
+        let index = field_usage >= MIN_INDEX_USAGE
+            && matches!(
+                data_type,
+                DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+            )
+            && !is_fts
+            && !is_index
+            && present > 0
+            && distinct_values * 10 >= present;
+        if index {
+            reasons.push(format!(
+                "{distinct_values} distinct values in {present} sampled records"
+            ));
+        }
+        candidates.push((
+            is_fts || is_index,
+            FieldSuggestion {
+                name: name.to_string(),
+                usage: field_usage,
+                presence,
+                distinct_values,
+                index,
+                reason: reasons.join(", "),
+            },
+        ));
+    }
+
+    // the fields the settings depend on first, then the most searched ones
+    candidates.sort_by(|(a_required, a), (b_required, b)| {
+        b_required
+            .cmp(a_required)
+            .then_with(|| b.usage.cmp(&a.usage))
+            .then_with(|| b.presence.total_cmp(&a.presence))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    candidates.truncate(max_fields);
+    let fields = candidates.into_iter().map(|(_, f)| f).collect::<Vec<_>>();
+
+    SchemaSuggestion {
+        generated_at: now_micros(),
+        total_fields,
+        sampled_records,
+        defined_schema_fields: fields.iter().map(|f| f.name.clone()).collect(),
+        index_fields: fields
+            .iter()
+            .filter(|f| f.index)
+            .map(|f| f.name.clone())
+            .collect(),
+        fields,
+    }
+}
+
+/// The settings update turning the current settings into the suggested ones
+pub fn to_settings_update(
+    suggestion: &SchemaSuggestion,
+    settings: &StreamSettings,
+) -> UpdateStreamSettings {
+    let suggested = suggestion
+        .defined_schema_fields
+        .iter()
+        .collect::<HashSet<_>>();
+    let current = settings
+        .defined_schema_fields
+        .iter()
+        .collect::<HashSet<_>>();
+    UpdateStreamSettings {
+        defined_schema_fields: UpdateSettingsWrapper {
+            add: suggestion
+                .defined_schema_fields
+                .iter()
+                .filter(|f| !current.contains(f))
+                .cloned()
+                .collect(),
+            remove: settings
+                .defined_schema_fields
+                .iter()
+                .filter(|f| !suggested.contains(f))
+                .cloned()
+                .collect(),
+        },
+        index_fields: UpdateSettingsWrapper {
+            add: suggestion
+                .index_fields
+                .iter()
+                .filter(|f| !settings.index_fields.contains(f))
+                .cloned()
+                .collect(),
+            remove: vec![],
+        },
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::meta::stream::FieldUsage;
+
+    fn sample_stats(present: usize, distinct: usize) -> SampleStats {
+        SampleStats {
+            present,
+            distinct: (0..distinct).map(|i| i.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_suggest() {
+        let fields = vec![
+            (TIMESTAMP_COL_NAME.to_string(), DataType::Int64),
+            ("trace_id".to_string(), DataType::Utf8),
+            ("level".to_string(), DataType::Utf8),
+            ("message".to_string(), DataType::Utf8),
+            ("host".to_string(), DataType::Utf8),
+            ("rarely_set".to_string(), DataType::Utf8),
+        ];
+        let settings = StreamSettings {
+            full_text_search_keys: vec!["message".to_string()],
+            ..Default::default()
+        };
+        let mut usage = StreamFieldUsage::default();
+        usage.fields.insert(
+            "trace_id".to_string(),
+            FieldUsage {
+                queries: 50,
+                ..Default::default()
+            },
+        );
+        usage.fields.insert(
+            "level".to_string(),
+            FieldUsage {
+                dashboards: 20,
+                ..Default::default()
+            },
+        );
+        let samples = HashMap::from([
+            ("trace_id".to_string(), sample_stats(100, 100)),
+            ("level".to_string(), sample_stats(100, 3)),
+            ("message".to_string(), sample_stats(100, 90)),
+            ("host".to_string(), sample_stats(95, 5)),
+            ("rarely_set".to_string(), sample_stats(2, 2)),
+        ]);
+
+        let suggestion = suggest(&fields, &settings, &usage, &samples, 100, 1000);
+        assert_eq!(
+            suggestion.defined_schema_fields,
+            vec!["message", "trace_id", "level", "host"]
+        );
+        // high cardinality and searched
+        assert_eq!(suggestion.index_fields, vec!["trace_id"]);
+        assert_eq!(suggestion.sampled_records, 100);
+
+        // the fields the settings depend on survive the limit
+        let suggestion = suggest(&fields, &settings, &usage, &samples, 100, 2);
+        assert_eq!(
+            suggestion.defined_schema_fields,
+            vec!["message", "trace_id"]
+        );
+    }
+
+    #[test]
+    fn test_to_settings_update() {
+        let suggestion = SchemaSuggestion {
+            defined_schema_fields: vec!["a".to_string(), "b".to_string()],
+            index_fields: vec!["a".to_string(), "c".to_string()],
+            ..Default::default()
+        };
+        let settings = StreamSettings {
+            defined_schema_fields: vec!["b".to_string(), "old".to_string()],
+            index_fields: vec!["c".to_string()],
+            ..Default::default()
+        };
+        let update = to_settings_update(&suggestion, &settings);
+        assert_eq!(update.defined_schema_fields.add, vec!["a"]);
+        assert_eq!(update.defined_schema_fields.remove, vec!["old"]);
+        assert_eq!(update.index_fields.add, vec!["a"]);
+        assert!(update.index_fields.remove.is_empty());
+    }
+}
@@ -0,0 +1,341 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Detects spikes and changepoints in the series returned by a query, so only
+//! the anomalies are shipped to the caller instead of the raw series.
+
+use std::collections::BTreeMap;
+
+use config::{
+    get_config,
+    meta::{
+        anomalies::{
+            Anomaly, AnomalyRequest, AnomalyResponse, DetectionMethod, Direction, SeriesAnomalies,
+        },
+        search,
+        stream::StreamType,
+    },
+    utils::json,
+};
+use infra::errors::{Error, ErrorCodes, Result};
+
+const DEFAULT_X_AXIS: &str = "x_axis_1";
+const DEFAULT_MIN_POINTS: usize = 8;
+/// Rows fetched when the query does not set a size.
+const DEFAULT_SIZE: i64 = 10_000;
+/// Scales the median absolute deviation to the standard deviation of a
+/// normal distribution.
+const MAD_SCALE: f64 = 1.4826;
+/// Same for the mean absolute deviation.
+const MEAN_AD_SCALE: f64 = 1.2533;
+/// Slack of the cumulative sums, in standard deviations.
+const CUSUM_DRIFT: f64 = 0.5;
+
+pub async fn detect(
+    trace_id: &str,
+    org_id: &str,
+    stream_type: StreamType,
+    user_id: Option<String>,
+    req: &AnomalyRequest,
+) -> Result<AnomalyResponse> {
+    let start = std::time::Instant::now();
+    let threshold = req
+        .threshold
+        .unwrap_or_else(|| req.method.default_threshold());
+    if threshold <= 0.0 {
+        return Err(Error::ErrorCode(ErrorCodes::InvalidParams(
+            "threshold must be greater than 0".to_string(),
+        )));
+    }
+    let min_points = req.min_points.unwrap_or(DEFAULT_MIN_POINTS).max(3);
+
+    let mut query = req.query.clone();
+    if query.size <= 0 {
+        query.size = DEFAULT_SIZE;
+    }
+    let search_req = search::Request {
+        query,
+        search_type: Some(search::SearchEventType::Other),
+        ..Default::default()
+    };
+    let resp = super::search(trace_id, org_id, stream_type, user_id, &search_req).await?;
+
+    let x_axis = match req.x_axis.as_deref() {
+        Some(x_axis) if !x_axis.is_empty() => x_axis.to_string(),
+        _ if resp
+            .hits
+            .first()
+            .is_some_and(|hit| hit.get(DEFAULT_X_AXIS).is_some()) =>
+        {
+            DEFAULT_X_AXIS.to_string()
+        }
+        _ => get_config().common.column_timestamp.clone(),
+    };
+
+    let mut series_analyzed = 0;
+    let mut total_anomalies = 0;
+    let mut series = Vec::new();
+    for Series {
+        labels,
+        field,
+        mut points,
+    } in group_series(&resp.hits, &x_axis, &req.y_axis)
+    {
+        if points.len() < min_points {
+            continue;
+        }
+        series_analyzed += 1;
+        points.sort_by(|a, b| compare_x(&a.0, &b.0));
+        let values = points.iter().map(|(_, v)| *v).collect::<Vec<_>>();
+        let found = match req.method {
+            DetectionMethod::Mad => mad_spikes(&values, threshold),
+            DetectionMethod::Cusum => cusum_changepoints(&values, threshold),
+        };
+        if found.is_empty() {
+            continue;
+        }
+        total_anomalies += found.len();
+        series.push(SeriesAnomalies {
+            labels,
+            field,
+            points: points.len(),
+            anomalies: found
+                .into_iter()
+                .map(|(i, score, direction)| Anomaly {
+                    x: points[i].0.clone(),
+                    value: points[i].1,
+                    score,
+                    direction,
+                })
+                .collect(),
+        });
+    }
+
+    Ok(AnomalyResponse {
+        took: start.elapsed().as_millis() as usize,
+        method: req.method,
+        threshold,
+        series_analyzed,
+        total_anomalies,
+        series,
+        function_error: resp.function_error,
+        is_partial: resp.is_partial,
+    })
+}
+
+struct Series {
+    labels: json::Map<String, json::Value>,
+    field: String,
+    points: Vec<(json::Value, f64)>,
+}
+
+/// Groups the rows by the values of their non numeric columns, one series per
+/// group and numeric column.
+fn group_series(hits: &[json::Value], x_axis: &str, y_axis: &[String]) -> Vec<Series> {
+    let mut series: BTreeMap<(String, String), Series> = BTreeMap::new();
+    for hit in hits {
+        let Some(row) = hit.as_object() else {
+            continue;
+        };
+        let Some(x) = row.get(x_axis) else {
+            continue;
+        };
+        let labels = row
+            .iter()
+            .filter(|(k, v)| {
+                k.as_str() != x_axis && !v.is_number() && !y_axis.iter().any(|y| y == *k)
+            })
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect::<BTreeMap<_, _>>();
+        let labels_key = json::to_string(&labels).unwrap_or_default();
+        for (k, v) in row {
+            if k == x_axis || (!y_axis.is_empty() && !y_axis.contains(k)) {
+                continue;
+            }
+            let Some(v) = v.as_f64() else {
+                continue;
+            };
+            series
+                .entry((labels_key.clone(), k.to_string()))
+                .or_insert_with(|| Series {
+                    labels: labels.clone().into_iter().collect(),
+                    field: k.to_string(),
+                    points: Vec::new(),
+                })
+                .points
+                .push((x.clone(), v));
+        }
+    }
+    series.into_values().collect()
+}
+
+fn compare_x(a: &json::Value, b: &json::Value) -> std::cmp::Ordering {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        _ => a
+            .as_str()
+            .unwrap_or_default()
+            .cmp(b.as_str().unwrap_or_default()),
+    }
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Returns the median and the scaled median absolute deviation of the values,
+/// None when all the values are equal.
+fn robust_center_scale(values: &[f64]) -> Option<(f64, f64)> {
+    let med = median(&mut values.to_vec());
+    let mut deviations = values.iter().map(|v| (v - med).abs()).collect::<Vec<_>>();
+    let mut scale = median(&mut deviations) * MAD_SCALE;
+    if scale == 0.0 {
+        // more than half of the points are equal, fall back to the mean
+        // absolute deviation so the outliers still stand out
+        scale = deviations.iter().sum::<f64>() / deviations.len() as f64 * MEAN_AD_SCALE;
+    }
+    (scale > 0.0).then_some((med, scale))
+}
+
+/// Returns the index, robust z-score and direction of the points whose robust
+/// z-score exceeds the threshold.
+fn mad_spikes(values: &[f64], threshold: f64) -> Vec<(usize, f64, Direction)> {
+    let Some((med, scale)) = robust_center_scale(values) else {
+        return vec![];
+    };
+    values
+        .iter()
+        .enumerate()
+        .filter_map(|(i, v)| {
+            let score = (v - med) / scale;
+            (score.abs() > threshold).then(|| (i, score, direction(score)))
+        })
+        .collect()
+}
+
+/// Returns the index, cumulative sum and direction of the changepoints found by
+/// a two-sided CUSUM over the values standardized with their median and
+/// median absolute deviation. The sums restart after every changepoint.
+fn cusum_changepoints(values: &[f64], threshold: f64) -> Vec<(usize, f64, Direction)> {
+    let Some((med, scale)) = robust_center_scale(values) else {
+        return vec![];
+    };
+    let (mut high, mut low) = (0.0_f64, 0.0_f64);
+    let mut changepoints = Vec::new();
+    for (i, v) in values.iter().enumerate() {
+        let z = (v - med) / scale;
+        high = (high + z - CUSUM_DRIFT).max(0.0);
+        low = (low - z - CUSUM_DRIFT).max(0.0);
+        if high > threshold {
+            changepoints.push((i, high, Direction::Up));
+            (high, low) = (0.0, 0.0);
+        } else if low > threshold {
+            changepoints.push((i, -low, Direction::Down));
+            (high, low) = (0.0, 0.0);
+        }
+    }
+    changepoints
+}
+
+fn direction(score: f64) -> Direction {
+    if score < 0.0 {
+        Direction::Down
+    } else {
+        Direction::Up
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(&mut [3.0, 1.0, 2.0]), 2.0);
+        assert_eq!(median(&mut [4.0, 1.0, 2.0, 3.0]), 2.5);
+    }
+
+    #[test]
+    fn test_mad_spikes() {
+        let values = [10.0, 11.0, 9.0, 10.0, 100.0, 10.0, 11.0, 9.0, 0.0, 10.0];
+        let spikes = mad_spikes(&values, 3.5);
+        let found = spikes.iter().map(|(i, _, d)| (*i, *d)).collect::<Vec<_>>();
+        assert_eq!(found, vec![(4, Direction::Up), (8, Direction::Down)]);
+    }
+
+    #[test]
+    fn test_mad_spikes_flat_series() {
+        assert!(mad_spikes(&[5.0; 10], 3.5).is_empty());
+        let mut values = [5.0; 10];
+        values[3] = 50.0;
+        let spikes = mad_spikes(&values, 3.5);
+        assert_eq!(spikes.len(), 1);
+        assert_eq!(spikes[0].0, 3);
+    }
+
+    #[test]
+    fn test_cusum_changepoints() {
+        let mut values = vec![10.0, 11.0, 9.0, 10.0, 11.0, 9.0, 10.0, 11.0, 9.0, 10.0];
+        values.extend([20.0, 21.0, 19.0, 20.0, 21.0]);
+        let changepoints = cusum_changepoints(&values, 5.0);
+        assert!(!changepoints.is_empty());
+        assert_eq!(changepoints[0].2, Direction::Up);
+        assert!(changepoints[0].0 >= 10);
+
+        let stable = [10.0, 11.0, 9.0, 10.0, 11.0, 9.0, 10.0, 11.0, 9.0, 10.0];
+        assert!(cusum_changepoints(&stable, 5.0).is_empty());
+    }
+
+    #[test]
+    fn test_group_series() {
+        let hits = vec![
+            json::json!({"x_axis_1": "2026-01-01T00:01:00", "host": "a", "cnt": 2, "avg": 1.5}),
+            json::json!({"x_axis_1": "2026-01-01T00:00:00", "host": "a", "cnt": 1, "avg": 0.5}),
+            json::json!({"x_axis_1": "2026-01-01T00:00:00", "host": "b", "cnt": 3, "avg": 2.5}),
+            json::json!({"host": "c", "cnt": 3}),
+        ];
+        let series = group_series(&hits, "x_axis_1", &[]);
+        assert_eq!(series.len(), 4);
+        let host_a = series
+            .iter()
+            .find(|s| s.labels["host"] == "a" && s.field == "cnt")
+            .unwrap();
+        assert_eq!(host_a.labels.len(), 1);
+        assert_eq!(host_a.points.len(), 2);
+
+        let series = group_series(&hits, "x_axis_1", &["cnt".to_string()]);
+        assert_eq!(series.len(), 2);
+        assert!(series.iter().all(|s| s.field == "cnt"));
+    }
+
+    #[test]
+    fn test_compare_x() {
+        use std::cmp::Ordering;
+        assert_eq!(compare_x(&json::json!(2), &json::json!(10)), Ordering::Less);
+        assert_eq!(
+            compare_x(
+                &json::json!("2026-01-01T00:01:00"),
+                &json::json!("2026-01-01T00:00:00")
+            ),
+            Ordering::Greater
+        );
+    }
+}
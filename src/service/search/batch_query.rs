@@ -0,0 +1,304 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Query of the scripts and the command line tools: the rows are streamed as
+//! newline delimited JSON, flushed after each record batch, and the last line,
+//! `{"_trailer": {..}}`, holds the scan stats, or the error that ended the
+//! query early.
+//!
+//! A query on one stream ordered by `_timestamp` only, without aggregation nor
+//! `LIMIT`, is searched partition by partition in its order, so the first rows
+//! come out before the whole time range is searched. The other queries are
+//! searched in one go. A query with `LIMIT 0` is only validated, the response
+//! is the trailer alone.
+
+use config::{
+    QUERY_WITH_NO_LIMIT, TIMESTAMP_COL_NAME,
+    meta::{
+        search::{self, ScanStats, SearchPartitionRequest},
+        sql::OrderBy,
+        stream::StreamType,
+    },
+    utils::{json, sql::is_aggregate_query},
+};
+use futures::{StreamExt, stream::BoxStream};
+use infra::errors::{Error, ErrorCodes, Result};
+use proto::cluster_rpc::SearchQuery;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{
+    external_flight,
+    ordered_export::{encode_ndjson, order_partitions},
+    sql::Sql,
+};
+
+#[derive(Clone, Debug, Deserialize, ToSchema)]
+pub struct BatchQueryRequest {
+    pub sql: String,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+/// Last line of the response, under the `_trailer` key
+#[derive(Clone, Debug, Default, Serialize, ToSchema)]
+pub struct BatchQueryTrailer {
+    pub took: usize,
+    pub rows: usize,
+    pub partitions: usize,
+    pub scan_files: i64,
+    pub scan_records: i64,
+    pub scan_size: i64,
+    pub compressed_size: i64,
+    pub idx_scan_size: i64,
+    /// The query has `LIMIT 0`, it was validated but not run
+    pub validate_only: bool,
+    /// The error that ended the query, the rows before it were sent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BatchQueryTrailer {
+    fn add_scan_stats(&mut self, stats: &ScanStats) {
+        self.scan_files += stats.files;
+        self.scan_records += stats.records;
+        self.scan_size += stats.original_size;
+        self.compressed_size += stats.compressed_size;
+        self.idx_scan_size += stats.idx_scan_size;
+    }
+
+    fn to_line(&self) -> Vec<u8> {
+        let mut line = json::to_vec(&json::json!({ "_trailer": self })).unwrap_or_default();
+        line.push(b'\n');
+        line
+    }
+}
+
+/// A validated query, with the time ranges to search in order
+#[derive(Debug)]
+pub struct BatchQuery {
+    trace_id: String,
+    org_id: String,
+    stream_type: StreamType,
+    user_id: Option<String>,
+    sql: String,
+    partitions: Vec<[i64; 2]>,
+    validate_only: bool,
+}
+
+impl BatchQuery {
+    pub async fn new(
+        trace_id: &str,
+        org_id: &str,
+        stream_type: StreamType,
+        user_id: Option<String>,
+        req: &BatchQueryRequest,
+    ) -> Result<Self> {
+        if req.start_time >= req.end_time {
+            return Err(Error::ErrorCode(ErrorCodes::InvalidParams(
+                "start_time must be less than end_time".to_string(),
+            )));
+        }
+        // without size the limit of the query is the one of the SQL, -1 if
+        // it has none
+        let query = SearchQuery {
+            sql: req.sql.clone(),
+            start_time: req.start_time,
+            end_time: req.end_time,
+            size: -1,
+            ..Default::default()
+        };
+        let sql = Sql::new(&query, org_id, stream_type, None).await?;
+        let mut batch_query = Self {
+            trace_id: trace_id.to_string(),
+            org_id: org_id.to_string(),
+            stream_type,
+            user_id,
+            sql: req.sql.clone(),
+            partitions: vec![],
+            validate_only: sql.limit == 0,
+        };
+        if batch_query.validate_only {
+            return Ok(batch_query);
+        }
+
+        let is_aggregate = is_aggregate_query(&req.sql).unwrap_or(true);
+        batch_query.partitions = match partition_order(
+            is_aggregate,
+            sql.limit,
+            sql.stream_names.len(),
+            &sql.order_by,
+        ) {
+            Some(order) => {
+                let res = super::search_partition(
+                    trace_id,
+                    org_id,
+                    batch_query.user_id.as_deref(),
+                    stream_type,
+                    &SearchPartitionRequest {
+                        sql: req.sql.clone(),
+                        start_time: req.start_time,
+                        end_time: req.end_time,
+                        ..Default::default()
+                    },
+                    false,
+                    false,
+                    false,
+                    false,
+                )
+                .await?;
+                if res.partitions.is_empty() {
+                    vec![[req.start_time, req.end_time]]
+                } else {
+                    order_partitions(res.partitions, order)
+                }
+            }
+            None => vec![[req.start_time, req.end_time]],
+        };
+        Ok(batch_query)
+    }
+
+    pub fn partitions(&self) -> usize {
+        self.partitions.len()
+    }
+
+    /// Searches the partitions one after the other, yielding their rows as
+    /// newline delimited JSON, one chunk per record batch, then the trailer
+    pub fn into_stream(self) -> BoxStream<'static, Vec<u8>> {
+        let Self {
+            trace_id,
+            org_id,
+            stream_type,
+            user_id,
+            sql,
+            partitions,
+            validate_only,
+        } = self;
+        let start = std::time::Instant::now();
+        let partition_num = partitions.len();
+        async_stream::stream! {
+            let mut trailer = BatchQueryTrailer {
+                partitions: partition_num,
+                validate_only,
+                ..Default::default()
+            };
+            for (i, [start_time, end_time]) in partitions.into_iter().enumerate() {
+                let trace_id = if partition_num == 1 {
+                    trace_id.clone()
+                } else {
+                    format!("{trace_id}-{i}")
+                };
+                let req = search::Request {
+                    query: search::Query {
+                        sql: sql.clone(),
+                        start_time,
+                        end_time,
+                        size: QUERY_WITH_NO_LIMIT,
+                        ..Default::default()
+                    },
+                    search_type: Some(search::SearchEventType::Other),
+                    use_cache: false,
+                    ..Default::default()
+                };
+                let batches =
+                    match external_flight::search(&trace_id, &org_id, stream_type, user_id.clone(), &req)
+                        .await
+                    {
+                        Ok((batches, stats)) => {
+                            trailer.add_scan_stats(&stats);
+                            batches
+                        }
+                        Err(e) => {
+                            log::error!("[trace_id {trace_id}] batch query error: {e}");
+                            trailer.error = Some(e.to_string());
+                            break;
+                        }
+                    };
+                for batch in batches {
+                    match encode_ndjson(&batch) {
+                        Ok(data) => {
+                            trailer.rows += batch.num_rows();
+                            yield data;
+                        }
+                        Err(e) => {
+                            trailer.error = Some(e.to_string());
+                            break;
+                        }
+                    }
+                }
+                if trailer.error.is_some() {
+                    break;
+                }
+            }
+            trailer.took = start.elapsed().as_millis() as usize;
+            yield trailer.to_line();
+        }
+        .boxed()
+    }
+}
+
+/// The order to search the partitions in, `None` when the query must be
+/// searched in one go
+fn partition_order(
+    is_aggregate: bool,
+    limit: i64,
+    stream_num: usize,
+    order_by: &[(String, OrderBy)],
+) -> Option<OrderBy> {
+    if is_aggregate || limit > 0 || stream_num != 1 {
+        return None;
+    }
+    match order_by {
+        [(field, order)] if field == TIMESTAMP_COL_NAME => Some(*order),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition_order() {
+        let by_time = vec![(TIMESTAMP_COL_NAME.to_string(), OrderBy::Asc)];
+        assert_eq!(partition_order(false, -1, 1, &by_time), Some(OrderBy::Asc));
+        assert_eq!(partition_order(true, -1, 1, &by_time), None);
+        assert_eq!(partition_order(false, 100, 1, &by_time), None);
+        assert_eq!(partition_order(false, -1, 2, &by_time), None);
+        let by_field = vec![("level".to_string(), OrderBy::Desc)];
+        assert_eq!(partition_order(false, -1, 1, &by_field), None);
+    }
+
+    #[test]
+    fn test_trailer_line() {
+        let mut trailer = BatchQueryTrailer {
+            rows: 2,
+            ..Default::default()
+        };
+        trailer.add_scan_stats(&ScanStats {
+            files: 3,
+            records: 40,
+            original_size: 1024,
+            ..Default::default()
+        });
+        let line = String::from_utf8(trailer.to_line()).unwrap();
+        assert!(line.ends_with('\n'));
+        let value: json::Value = json::from_str(line.trim_end()).unwrap();
+        assert_eq!(value["_trailer"]["rows"], 2);
+        assert_eq!(value["_trailer"]["scan_files"], 3);
+        assert_eq!(value["_trailer"]["scan_size"], 1024);
+        assert!(value["_trailer"].get("error").is_none());
+    }
+}
@@ -566,6 +566,61 @@ pub async fn cache_results_to_disk(
     Ok(true)
 }
 
+/// Removes the cached results extended by a new cache entry.
+///
+/// A refreshed query, like an auto-refreshed dashboard panel, is answered from
+/// its previous cache entry plus the newly arrived tail, and the merged result
+/// is cached again. Without this every refresh would leave a copy of nearly the
+/// same results on disk.
+pub async fn remove_superseded_results(
+    trace_id: &str,
+    file_path: &str,
+    new_meta: &ResultCacheMeta,
+) {
+    let query_key = file_path.replace('/', "_");
+    let superseded = {
+        let mut r = QUERY_RESULT_CACHE.write().await;
+        let Some(metas) = r.get_mut(&query_key) else {
+            return;
+        };
+        let (superseded, kept): (Vec<_>, Vec<_>) = std::mem::take(metas)
+            .into_iter()
+            .partition(|m| is_superseded_by(m, new_meta));
+        *metas = kept;
+        superseded
+    };
+    for meta in superseded {
+        let file = format!(
+            "results/{file_path}/{}_{}_{}_{}.json",
+            meta.start_time,
+            meta.end_time,
+            if meta.is_aggregate { 1 } else { 0 },
+            if meta.is_descending { 1 } else { 0 }
+        );
+        match disk::remove(&file).await {
+            Ok(_) => log::info!("[trace_id {trace_id}] Removed superseded cached results: {file}"),
+            Err(e) => {
+                log::error!(
+                    "[trace_id {trace_id}] Remove superseded cached results {file} error: {e}"
+                )
+            }
+        }
+    }
+}
+
+/// An entry is superseded when the new entry covers its end and is at least as
+/// long, that is the same window moved forward or grown. The part of the old
+/// entry before the start of the new one is dropped with it, which only costs a
+/// search if a query asks for it again.
+fn is_superseded_by(meta: &ResultCacheMeta, new_meta: &ResultCacheMeta) -> bool {
+    meta != new_meta
+        && meta.is_aggregate == new_meta.is_aggregate
+        && meta.is_descending == new_meta.is_descending
+        && meta.end_time > new_meta.start_time
+        && meta.end_time <= new_meta.end_time
+        && meta.end_time - meta.start_time <= new_meta.end_time - new_meta.start_time
+}
+
 pub async fn get_results(file_path: &str, file_name: &str) -> std::io::Result<String> {
     let file = format!("results/{file_path}/{file_name}");
     match disk::get(&file, None).await {
@@ -1102,6 +1157,7 @@ mod tests {
             histogram_interval: None,
             sorted_by_time: true,
             sampling_config: None,
+            masked_fields: vec![],
         };
 
         let result = get_ts_col_order_by(&sql, "_timestamp", false);
@@ -1286,4 +1342,28 @@ mod tests {
         assert_eq!(result.limit, 100);
         assert_eq!(result.file_path, "test_org/logs/test_stream");
     }
+
+    #[test]
+    fn test_is_superseded_by() {
+        let meta = |start_time, end_time| ResultCacheMeta {
+            start_time,
+            end_time,
+            is_aggregate: true,
+            is_descending: false,
+        };
+        let new_meta = meta(100, 200);
+        // the same window moved forward
+        assert!(is_superseded_by(&meta(90, 190), &new_meta));
+        // a shorter window inside
+        assert!(is_superseded_by(&meta(100, 150), &new_meta));
+        assert!(!is_superseded_by(&new_meta, &new_meta));
+        // a longer window is kept for the queries it can still answer
+        assert!(!is_superseded_by(&meta(0, 190), &new_meta));
+        // no overlap, or ends later
+        assert!(!is_superseded_by(&meta(0, 100), &new_meta));
+        assert!(!is_superseded_by(&meta(150, 250), &new_meta));
+        let mut other = meta(90, 190);
+        other.is_descending = true;
+        assert!(!is_superseded_by(&other, &new_meta));
+    }
 }
@@ -97,6 +97,10 @@ pub async fn search(
 
     let mut req = in_req.clone();
 
+    // the searches of the deltas run under the quota of this one
+    let is_background = in_req.search_type.is_some_and(|t| t.is_background());
+    let _quota_guard = SearchService::quota::acquire(org_id, is_background).await?;
+
     // check the original query function first
     let mut query_fn = req
         .query
@@ -116,8 +120,15 @@ pub async fn search(
     }
 
     // Result caching check start
-    let (mut c_resp, should_exec_query) =
-        prepare_cache_response(trace_id, org_id, stream_type, &mut req, use_cache).await?;
+    let (mut c_resp, should_exec_query) = prepare_cache_response(
+        trace_id,
+        org_id,
+        stream_type,
+        user_id.as_deref(),
+        &mut req,
+        use_cache,
+    )
+    .await?;
     let file_path = c_resp.file_path.clone();
 
     // get the modified original sql from req
@@ -248,7 +259,14 @@ pub async fn search(
                         );
                     }
 
-                    SearchService::search(&trace_id, &org_id, stream_type, user_id, &req).await
+                    SearchService::quota::sub_search(SearchService::search(
+                        &trace_id,
+                        &org_id,
+                        stream_type,
+                        user_id,
+                        &req,
+                    ))
+                    .await
                 })
                 .instrument(enter_span),
             );
@@ -365,6 +383,7 @@ pub async fn search(
         result_cache_ratio: Some(res.result_cache_ratio),
         dashboard_info,
         peak_memory_usage: res.peak_memory_usage,
+        took_detail: Some(res.took_detail.clone()),
         ..Default::default()
     };
     report_request_usage_stats(
@@ -485,6 +504,7 @@ pub async fn prepare_cache_response(
     trace_id: &str,
     org_id: &str,
     stream_type: StreamType,
+    user_id: Option<&str>,
     req: &mut search::Request,
     use_cache: bool,
 ) -> Result<(MultiCachedQueryResponse, bool), Error> {
@@ -571,6 +591,11 @@ pub async fn prepare_cache_response(
     if !req.clusters.is_empty() {
         hash_body.extend(req.clusters.clone());
     }
+    let denied =
+        crate::service::search::field_access::denied_rules(org_id, user_id, &sql.schemas).await;
+    if let Some(key) = crate::service::search::field_access::cache_key(&denied) {
+        hash_body.push(key);
+    }
     let mut h = config::utils::hash::gxhash::new();
     let hashed_query = h.sum64(&hash_body.join(","));
 
@@ -1000,17 +1025,25 @@ pub async fn write_results(
                 if success {
                     // success: true, cache to disk success
                     // success: false, cache to disk already exists, skipping caching
+                    let meta = ResultCacheMeta {
+                        start_time: accept_start_time,
+                        end_time: accept_end_time,
+                        is_aggregate,
+                        is_descending,
+                    };
+                    // the new results include the ones they were appended to
+                    if get_config().common.result_cache_append_enabled {
+                        SearchService::cache::cacher::remove_superseded_results(
+                            &trace_id, &file_path, &meta,
+                        )
+                        .await;
+                    }
                     QUERY_RESULT_CACHE
                         .write()
                         .await
                         .entry(query_key)
                         .or_insert_with(Vec::new)
-                        .push(ResultCacheMeta {
-                            start_time: accept_start_time,
-                            end_time: accept_end_time,
-                            is_aggregate,
-                            is_descending,
-                        });
+                        .push(meta);
                 }
             }
             Err(e) => {
@@ -202,6 +202,19 @@ pub async fn check_cardinality(
     Ok(results)
 }
 
+/// Cardinality of the field calculated in the last hour, `None` when there is
+/// none in the cache
+pub async fn get_cached_cardinality(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    field_name: &str,
+) -> Option<f64> {
+    get_cardinality_from_cache(org_id, stream_type, stream_name, field_name)
+        .await
+        .ok()
+}
+
 async fn get_cardinality_from_cache(
     org_id: &str,
     stream_type: StreamType,
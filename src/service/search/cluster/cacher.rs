@@ -25,7 +25,14 @@ use tracing::{Instrument, info_span};
 
 use crate::service::search::server_internal_error;
 
-pub async fn delete_cached_results(path: String, delete_ts: i64) -> bool {
+/// Deletes the cached results under `path` on all the queriers, the ones
+/// overlapping `time_range` when given, else the ones starting before
+/// `delete_ts`, or all of them when it is 0
+pub async fn delete_cached_results(
+    path: String,
+    delete_ts: i64,
+    time_range: Option<(i64, i64)>,
+) -> bool {
     let trace_id = path.clone();
     let mut delete_response = true;
     // get nodes from cluster
@@ -66,9 +73,12 @@ pub async fn delete_cached_results(path: String, delete_ts: i64) -> bool {
         let local_path = path.clone();
         let task = tokio::task::spawn(
             async move {
+                let (start_time, end_time) = time_range.unwrap_or_default();
                 let req = cluster_rpc::DeleteResultCacheRequest {
                    path: local_path.clone(),
                    ts: delete_ts,
+                   start_time,
+                   end_time,
                 };
 
                 let request = tonic::Request::new(req);
@@ -122,7 +132,14 @@ pub async fn delete_cached_results(path: String, delete_ts: i64) -> bool {
         );
         tasks.push(task);
     }
-    match crate::service::search::cache::cacher::delete_cache(&path, delete_ts, None, None).await {
+    match crate::service::search::cache::cacher::delete_cache(
+        &path,
+        delete_ts,
+        time_range.map(|(start, _)| start),
+        time_range.map(|(_, end)| end),
+    )
+    .await
+    {
         Ok(_) => {
             log::info!(
                 "[trace_id {trace_id}] delete_cached_results->grpc: local node delete success"
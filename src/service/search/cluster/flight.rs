@@ -31,7 +31,8 @@ use config::{
     utils::{json, time::now_micros, took_watcher::TookWatcher},
 };
 use datafusion::{
-    common::TableReference, physical_plan::visit_execution_plan, prelude::SessionContext,
+    catalog::TableProvider, common::TableReference, physical_plan::visit_execution_plan,
+    prelude::SessionContext,
 };
 use hashbrown::{HashMap, HashSet};
 use infra::{
@@ -39,6 +40,7 @@ use infra::{
     errors::{Error, ErrorCodes, Result},
     file_list::FileId,
     runtime::DATAFUSION_RUNTIME,
+    schema::unwrap_stream_settings,
 };
 use itertools::Itertools;
 use parking_lot::Mutex;
@@ -62,7 +64,10 @@ use crate::{
                     create_physical_plan, generate_analyzer_rules, generate_optimizer_rules,
                     generate_physical_optimizer_rules,
                 },
-                table_provider::{catalog::StreamTypeProvider, empty_table::NewEmptyTable},
+                table_provider::{
+                    catalog::StreamTypeProvider, computed_fields::with_computed_fields,
+                    empty_table::NewEmptyTable,
+                },
             },
             inspector::{SearchInspectorFieldsBuilder, search_inspector_fields},
             sql::Sql,
@@ -129,6 +134,11 @@ pub async fn search(trace_id: &str, sql: Arc<Sql>, mut req: Request) -> Result<S
         )
     );
 
+    crate::service::search::limits::check_scan(
+        req.max_scan_mb,
+        file_id_list_vec.iter().map(|v| v.original_size).sum(),
+    )?;
+
     #[cfg(feature = "enterprise")]
     let scan_stats = ScanStats {
         files: file_id_list_num as i64,
@@ -141,6 +151,8 @@ pub async fn search(trace_id: &str, sql: Arc<Sql>, mut req: Request) -> Result<S
     let is_local_mode = req.local_mode.unwrap_or_default();
     let role_group = if is_local_mode {
         None
+    } else if req.shadow {
+        Some(RoleGroup::Shadow)
     } else {
         req.search_event_type
             .as_ref()
@@ -488,6 +500,10 @@ pub async fn partition_file_list(
     if cfg.cache_latest_files.enabled {
         partition_strategy = QueryPartitionStrategy::FileHash;
     }
+    // the shadow queriers are not in the consistent hash rings
+    if group == Some(RoleGroup::Shadow) && partition_strategy == QueryPartitionStrategy::FileHash {
+        partition_strategy = QueryPartitionStrategy::FileSize;
+    }
     let partitions = match partition_strategy {
         QueryPartitionStrategy::FileNum => partition_file_by_nums(file_id_list, querier_num),
         QueryPartitionStrategy::FileSize => partition_file_by_bytes(file_id_list, querier_num),
@@ -663,17 +679,33 @@ pub async fn register_table(ctx: &SessionContext, sql: &Sql) -> Result<()> {
 
     // register table
     for (stream, schema) in &sql.schemas {
+        let computed_fields = unwrap_stream_settings(schema.schema())
+            .map(|settings| settings.computed_fields)
+            .unwrap_or_default();
         let schema = schema
             .schema()
             .as_ref()
             .clone()
             .with_metadata(Default::default());
         let stream_name = stream.to_quoted_string();
-        let table = Arc::new(
+        // the size of the enrichment tables lets the planner broadcast the small ones
+        let byte_size = if stream.get_stream_type(StreamType::Logs) == StreamType::EnrichmentTables
+        {
+            Some(
+                enrichment_table::get_table_size(&sql.org_id, &stream.stream_name()).await as usize,
+            )
+        } else {
+            None
+        };
+        let mut table: Arc<dyn TableProvider> = Arc::new(
             NewEmptyTable::new(&stream_name, Arc::new(schema))
                 .with_partitions(ctx.state().config().target_partitions())
-                .with_sorted_by_time(sql.sorted_by_time),
+                .with_sorted_by_time(sql.sorted_by_time)
+                .with_byte_size(byte_size),
         );
+        if !computed_fields.is_empty() {
+            table = with_computed_fields(ctx, &stream_name, table, &computed_fields)?;
+        }
         ctx.register_table(&stream_name, table)?;
     }
 
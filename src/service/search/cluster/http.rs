@@ -37,7 +37,7 @@ use proto::cluster_rpc::SearchQuery;
 use vector_enrichment::TableRegistry;
 
 use crate::service::search::{
-    SearchResult, cluster::flight, sql::Sql, utils::is_default_query_limit_exceeded,
+    SearchResult, cluster::flight, field_access, sql::Sql, utils::is_default_query_limit_exceeded,
 };
 
 #[tracing::instrument(name = "service:search:cluster", skip_all)]
@@ -357,5 +357,7 @@ pub async fn search_inner(
     #[cfg(not(feature = "enterprise"))]
     let ret = flight::search(&trace_id, sql.clone(), req).await;
 
-    ret
+    let (batches, scan_stats, took_wait, is_partial, partial_err) = ret?;
+    let batches = field_access::mask_batches(batches, &sql.masked_fields)?;
+    Ok((batches, scan_stats, took_wait, is_partial, partial_err))
 }
@@ -0,0 +1,200 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Search of several orgs at once, for the users of the meta org.
+//!
+//! The query runs in each org as a regular search, so it is distributed to
+//! the regions of a super cluster like any other, and the hits are merged
+//! with the org they come from in `_org_id`.
+
+use config::{
+    TIMESTAMP_COL_NAME, get_config,
+    meta::{search, stream::StreamType},
+    utils::json,
+};
+use futures::StreamExt;
+use infra::errors::Error;
+
+/// Field of the merged hits holding the org they come from
+pub const ORG_ID_FIELD: &str = "_org_id";
+
+pub async fn search(
+    trace_id: &str,
+    org_ids: &[String],
+    stream_type: StreamType,
+    user_id: Option<String>,
+    in_req: &search::Request,
+) -> Result<search::Response, Error> {
+    let from = in_req.query.from;
+    let size = in_req.query.size;
+    // every org returns the hits up to the requested page, which is cut from
+    // the merged hits
+    let mut req = in_req.clone();
+    req.query.from = 0;
+    if size > 0 {
+        req.query.size = from + size;
+    }
+
+    let concurrency = org_ids.len().min(get_config().limit.cpu_num).max(1);
+    let results = futures::stream::iter(org_ids.iter().enumerate())
+        .map(|(i, org_id)| {
+            let trace_id = format!("{trace_id}-{i}");
+            let user_id = user_id.clone();
+            let req = &req;
+            async move {
+                let res = super::search(&trace_id, org_id, stream_type, user_id, req).await;
+                if let Err(e) = &res {
+                    log::error!(
+                        "[trace_id {trace_id}] cross org search in org {org_id} error: {e}"
+                    );
+                }
+                (org_id.clone(), res)
+            }
+        })
+        .buffered(concurrency)
+        .collect::<Vec<_>>()
+        .await;
+
+    merge(trace_id, from, size, results)
+}
+
+/// Merges the responses of the orgs, the search fails only when it failed
+/// in every org, otherwise the response is partial
+fn merge(
+    trace_id: &str,
+    from: i64,
+    size: i64,
+    results: Vec<(String, Result<search::Response, Error>)>,
+) -> Result<search::Response, Error> {
+    let mut resp = search::Response::new(from, size);
+    resp.set_trace_id(trace_id.to_string());
+    let mut first_err = None;
+    let mut succeeded = false;
+    let mut hits = Vec::new();
+    for (org_id, res) in results {
+        let res = match res {
+            Ok(res) => res,
+            Err(e) => {
+                resp.set_partial(true, format!("search in org {org_id} failed: {e}"));
+                first_err.get_or_insert(e);
+                continue;
+            }
+        };
+        succeeded = true;
+        resp.took = resp.took.max(res.took);
+        resp.total += res.total;
+        resp.scan_size += res.scan_size;
+        resp.idx_scan_size += res.idx_scan_size;
+        resp.scan_records += res.scan_records;
+        resp.scan_files += res.scan_files;
+        if res.is_partial {
+            resp.is_partial = true;
+        }
+        resp.function_error.extend(
+            res.function_error
+                .into_iter()
+                .map(|e| format!("org {org_id}: {e}")),
+        );
+        if resp.histogram_interval.is_none() {
+            resp.histogram_interval = res.histogram_interval;
+        }
+        for mut hit in res.hits {
+            if let json::Value::Object(obj) = &mut hit {
+                obj.insert(
+                    ORG_ID_FIELD.to_string(),
+                    json::Value::String(org_id.clone()),
+                );
+            }
+            hits.push(hit);
+        }
+    }
+    if !succeeded && let Some(e) = first_err {
+        return Err(e);
+    }
+
+    // the latest first, when the hits are records
+    if hits.iter().all(|hit| {
+        hit.get(TIMESTAMP_COL_NAME)
+            .and_then(|v| v.as_i64())
+            .is_some()
+    }) {
+        hits.sort_by_key(|hit| {
+            std::cmp::Reverse(hit.get(TIMESTAMP_COL_NAME).and_then(|v| v.as_i64()))
+        });
+    }
+    let hits = hits.into_iter().skip(from.max(0) as usize);
+    resp.hits = if size > 0 {
+        hits.take(size as usize).collect()
+    } else {
+        hits.collect()
+    };
+    Ok(resp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(hits: Vec<json::Value>) -> search::Response {
+        let mut resp = search::Response::new(0, 10);
+        resp.total = hits.len();
+        resp.hits = hits;
+        resp
+    }
+
+    #[test]
+    fn test_merge() {
+        let results = vec![
+            (
+                "org1".to_string(),
+                Ok(response(vec![
+                    json::json!({"_timestamp": 5, "msg": "a"}),
+                    json::json!({"_timestamp": 1, "msg": "b"}),
+                ])),
+            ),
+            (
+                "org2".to_string(),
+                Ok(response(vec![json::json!({"_timestamp": 3, "msg": "c"})])),
+            ),
+        ];
+        let resp = merge("trace", 1, 1, results).unwrap();
+        assert_eq!(resp.total, 3);
+        assert!(!resp.is_partial);
+        assert_eq!(
+            resp.hits,
+            vec![json::json!({"_timestamp": 3, "msg": "c", "_org_id": "org2"})]
+        );
+    }
+
+    #[test]
+    fn test_merge_with_error() {
+        let results = vec![
+            ("org1".to_string(), Err(Error::Message("boom".to_string()))),
+            (
+                "org2".to_string(),
+                Ok(response(vec![json::json!({"count": 3})])),
+            ),
+        ];
+        let resp = merge("trace", 0, 10, results).unwrap();
+        assert!(resp.is_partial);
+        assert_eq!(
+            resp.hits,
+            vec![json::json!({"count": 3, "_org_id": "org2"})]
+        );
+
+        let results = vec![("org1".to_string(), Err(Error::Message("boom".to_string())))];
+        assert!(merge("trace", 0, 10, results).is_err());
+    }
+}
@@ -19,7 +19,7 @@ use arrow_schema::SortOptions;
 use config::TIMESTAMP_COL_NAME;
 use datafusion::{
     arrow::{array::RecordBatch, datatypes::SchemaRef},
-    common::{Result, Statistics, internal_err},
+    common::{Result, Statistics, internal_err, stats::Precision},
     execution::{SendableRecordBatchStream, TaskContext},
     physical_expr::{EquivalenceProperties, LexOrdering, Partitioning, PhysicalSortExpr},
     physical_plan::{
@@ -42,7 +42,8 @@ pub struct NewEmptyExec {
     filters: Vec<Expr>,
     limit: Option<usize>,
     sorted_by_time: bool,
-    full_schema: SchemaRef, // The schema use for remove filter feature
+    full_schema: SchemaRef,   // The schema use for remove filter feature
+    byte_size: Option<usize>, // The size of the data the table stands for, when known
 }
 
 impl NewEmptyExec {
@@ -67,9 +68,18 @@ impl NewEmptyExec {
             limit,
             sorted_by_time,
             full_schema,
+            byte_size: None,
         }
     }
 
+    /// Create a new NewEmptyExec reporting the size of the data it stands for,
+    /// the planner uses it to build small tables once for all the partitions
+    /// of a join
+    pub fn with_byte_size(mut self, byte_size: Option<usize>) -> Self {
+        self.byte_size = byte_size;
+        self
+    }
+
     /// Create a new NewEmptyExec with specified partition number
     pub fn with_partitions(mut self, partitions: usize) -> Self {
         self.partitions = partitions;
@@ -239,6 +249,14 @@ impl ExecutionPlan for NewEmptyExec {
             None,
         ))
     }
+
+    fn partition_statistics(&self, partition: Option<usize>) -> Result<Statistics> {
+        match (partition, self.byte_size) {
+            (None, Some(byte_size)) => Ok(Statistics::new_unknown(&self.schema)
+                .with_total_byte_size(Precision::Inexact(byte_size))),
+            _ => Ok(Statistics::new_unknown(&self.schema)),
+        }
+    }
 }
 
 // add some unit tests here
@@ -257,4 +275,16 @@ mod tests {
         assert_eq!(exec.filters().len(), 0);
         assert_eq!(exec.limit(), None);
     }
+
+    #[test]
+    fn test_new_empty_exec_byte_size() {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let exec = NewEmptyExec::new("test", schema.clone(), None, &[], None, false, schema);
+        let stats = exec.partition_statistics(None).unwrap();
+        assert_eq!(stats.total_byte_size, Precision::Absent);
+
+        let exec = exec.with_byte_size(Some(1024));
+        let stats = exec.partition_statistics(None).unwrap();
+        assert_eq!(stats.total_byte_size, Precision::Inexact(1024));
+    }
 }
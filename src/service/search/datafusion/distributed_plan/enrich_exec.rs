@@ -15,7 +15,10 @@
 
 use std::{any::Any, sync::Arc};
 
-use config::utils::record_batch_ext::convert_json_to_record_batch;
+use config::{
+    RwHashMap, get_config,
+    utils::{json, record_batch_ext::convert_json_to_record_batch},
+};
 use datafusion::{
     arrow::datatypes::SchemaRef,
     common::{Result, Statistics},
@@ -31,6 +34,17 @@ use datafusion::{
     },
 };
 use futures::TryStreamExt;
+use once_cell::sync::Lazy;
+
+/// The enrichment tables read by the queries of this node, by `{org_id}/{name}`
+static TABLE_CACHE: Lazy<RwHashMap<String, CachedTable>> = Lazy::new(Default::default);
+
+struct CachedTable {
+    /// The end time of the table when it was read, the table is read again
+    /// once it is updated
+    version: i64,
+    data: Arc<Vec<Arc<json::Value>>>,
+}
 
 // EnrichExec is used to read enrichment data from database
 #[derive(Debug)]
@@ -150,30 +164,11 @@ async fn get_data(
     let timer = metrics.elapsed_compute().timer();
     let clean_name = name.trim_matches('"');
 
-    // Get enrichment table metadata to determine the end_time for data filtering
-    // Search end_time is exclusive, so we add 1 to include records up to and including
-    // db_stats.end_time
-    let end_time_exclusive =
-        crate::service::db::enrichment_table::get_meta_table_stats(&org_id, clean_name)
-            .await
-            .map(|stats| stats.end_time + 1); // +1 because search end_time is exclusive
-
-    let data = match crate::service::db::enrichment_table::get_enrichment_data_from_db(
-        &org_id,
-        clean_name,
-        end_time_exclusive,
-    )
-    .await
-    {
-        Ok((data, _min_ts, _max_ts)) => data.into_iter().map(Arc::new).collect::<Vec<_>>(),
+    let data = match load(&org_id, clean_name).await {
+        Ok(data) => data,
         Err(e) => return internal_err!("get enrichment data from db: {e}"),
     };
 
-    log::info!(
-        "[EnrichExec] get_data: {org_id}/{clean_name} db data: {}",
-        data.len(),
-    );
-
     let batch = match convert_json_to_record_batch(&schema, &data) {
         Ok(batch) => batch,
         Err(e) => return internal_err!("convert enrichment data from json to record batch: {e}"),
@@ -191,6 +186,77 @@ async fn get_data(
     )?))
 }
 
+/// Reads the enrichment table from the cache, or from the database when it
+/// isn't cached or was updated since it was cached
+async fn load(
+    org_id: &str,
+    name: &str,
+) -> std::result::Result<Arc<Vec<Arc<json::Value>>>, infra::errors::Error> {
+    let cfg = get_config();
+    let key = format!("{org_id}/{name}");
+
+    // Get enrichment table metadata to determine the end_time for data filtering
+    let stats = crate::service::db::enrichment_table::get_meta_table_stats(org_id, name).await;
+    let cacheable = cfg.enrichment_table.query_cache_enabled
+        && stats.as_ref().is_some_and(|stats| {
+            stats.size as usize <= cfg.enrichment_table.query_cache_max_size_mb * 1024 * 1024
+        });
+    if cacheable
+        && let Some(stats) = stats.as_ref()
+        && let Some(data) = get_cached(&key, stats.end_time)
+    {
+        log::debug!("[EnrichExec] get_data: {key} cache data: {}", data.len());
+        return Ok(data);
+    }
+
+    // Search end_time is exclusive, so we add 1 to include records up to and including
+    // db_stats.end_time
+    let end_time_exclusive = stats.as_ref().map(|stats| stats.end_time + 1);
+    let (data, ..) = crate::service::db::enrichment_table::get_enrichment_data_from_db(
+        org_id,
+        name,
+        end_time_exclusive,
+    )
+    .await?;
+    let data = Arc::new(data.into_iter().map(Arc::new).collect::<Vec<_>>());
+    log::info!("[EnrichExec] get_data: {key} db data: {}", data.len());
+
+    match stats {
+        Some(stats) if cacheable => set_cached(key, stats.end_time, data.clone()),
+        _ => {
+            TABLE_CACHE.remove(&key);
+        }
+    }
+    Ok(data)
+}
+
+fn get_cached(key: &str, version: i64) -> Option<Arc<Vec<Arc<json::Value>>>> {
+    TABLE_CACHE
+        .get(key)
+        .filter(|table| table.version == version)
+        .map(|table| table.data.clone())
+}
+
+fn set_cached(key: String, version: i64, data: Arc<Vec<Arc<json::Value>>>) {
+    TABLE_CACHE.insert(key, CachedTable { version, data });
+}
+
+/// Reads an updated enrichment table into the cache of the querier, so the
+/// first query joining it after the update doesn't wait for the database
+pub async fn warm_cache(org_id: &str, name: &str) {
+    if !get_config().enrichment_table.query_cache_enabled {
+        return;
+    }
+    if let Err(e) = load(org_id, name).await {
+        log::error!("[EnrichExec] warm cache of enrichment table {org_id}/{name} error: {e}");
+    }
+}
+
+/// Drops a deleted enrichment table from the cache
+pub fn evict_cache(org_id: &str, name: &str) {
+    TABLE_CACHE.remove(&format!("{org_id}/{name}"));
+}
+
 // add some unit tests here
 #[cfg(test)]
 mod tests {
@@ -205,4 +271,17 @@ mod tests {
         assert_eq!(exec.org_id, "default");
         assert_eq!(exec.name, "test");
     }
+
+    #[test]
+    fn test_table_cache_version() {
+        let key = "default/test_table_cache_version".to_string();
+        let data = Arc::new(vec![Arc::new(json::json!({"ip": "10.0.0.1"}))]);
+        set_cached(key.clone(), 100, data);
+        assert_eq!(get_cached(&key, 100).map(|data| data.len()), Some(1));
+        // the table was updated since it was cached
+        assert!(get_cached(&key, 200).is_none());
+
+        evict_cache("default", "test_table_cache_version");
+        assert!(get_cached(&key, 100).is_none());
+    }
 }
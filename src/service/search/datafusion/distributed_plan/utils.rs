@@ -90,11 +90,14 @@ pub async fn make_flight_client(
             req.set_timeout(std::time::Duration::from_secs(timeout));
             Ok(req)
         });
-    let client = client
-        .send_compressed(CompressionEncoding::Gzip)
-        .accept_compressed(CompressionEncoding::Gzip)
+    let mut client = client
         .max_decoding_message_size(cfg.grpc.max_message_size * 1024 * 1024)
         .max_encoding_message_size(cfg.grpc.max_message_size * 1024 * 1024);
+    if flight::encoder::grpc_compression_enabled() {
+        client = client
+            .send_compressed(CompressionEncoding::Gzip)
+            .accept_compressed(CompressionEncoding::Gzip);
+    }
 
     Ok((client, request))
 }
@@ -21,14 +21,14 @@ use config::{
     PARQUET_BATCH_SIZE, TIMESTAMP_COL_NAME, get_config,
     meta::{
         search::{Session as SearchSession, StorageType},
-        stream::{FileKey, FileMeta, StreamType},
+        stream::{CompactSort, CompactSortMode, FileKey, FileMeta, StreamType},
     },
     utils::{parquet::new_parquet_writer, schema_ext::SchemaExt, util::DISTINCT_STREAM_PREFIX},
 };
 use datafusion::{
     arrow::datatypes::{DataType, Schema},
     catalog::TableProvider,
-    config::Dialect,
+    config::{Dialect, TableParquetOptions},
     datasource::{
         file_format::parquet::ParquetFormat,
         listing::{ListingOptions, ListingTableConfig, ListingTableUrl},
@@ -92,6 +92,7 @@ pub async fn merge_parquet_files(
     bloom_filter_fields: &[String],
     metadata: &FileMeta,
     is_ingester: bool,
+    compact_sort: Option<&CompactSort>,
 ) -> Result<(Arc<Schema>, MergeParquetResult)> {
     let start = std::time::Instant::now();
     let cfg = get_config();
@@ -134,18 +135,22 @@ pub async fn merge_parquet_files(
         // for file list we do not have timestamp, so we instead sort by min ts of entries
         "SELECT * FROM tbl ORDER BY min_ts DESC".to_string()
     } else {
-        format!("SELECT * FROM tbl ORDER BY {TIMESTAMP_COL_NAME} DESC")
+        let order_by = compact_sort
+            .map(|sort| compact_sort_order_by(&schema, sort))
+            .unwrap_or_default();
+        format!("SELECT * FROM tbl ORDER BY {order_by}{TIMESTAMP_COL_NAME} DESC")
     };
     log::debug!("merge_parquet_files sql: {sql}");
 
     // create datafusion context
-    let sort_by_timestamp_desc = true;
+    let sort_by_timestamp_desc = compact_sort.is_none();
     // force use DATAFUSION_MIN_PARTITION for each merge task
     let target_partitions = DATAFUSION_MIN_PARTITION;
     let ctx = DataFusionContextBuilder::new()
         .sorted_by_time(sort_by_timestamp_desc)
         .build(target_partitions)
         .await?;
+    ctx.register_udf(super::udf::zorder_udf::ZORDER_KEY_UDF.clone());
     // register union table
     let union_table = Arc::new(NewUnionTable::new(schema.clone(), tables));
     ctx.register_table("tbl", union_table)?;
@@ -230,6 +235,28 @@ pub async fn merge_parquet_files(
     Ok((schema, MergeParquetResult::Single(buf)))
 }
 
+/// The sort keys put before the timestamp for the sort of the stream, the
+/// fields missing from the merged files are skipped
+fn compact_sort_order_by(schema: &Schema, sort: &CompactSort) -> String {
+    let fields = sort
+        .fields
+        .iter()
+        .filter(|f| schema.field_with_name(f).is_ok())
+        .map(|f| format!("\"{}\"", f.replace('"', "\"\"")))
+        .collect::<Vec<_>>();
+    if fields.is_empty() {
+        return String::new();
+    }
+    match sort.mode {
+        CompactSortMode::Hierarchical => format!("{}, ", fields.join(", ")),
+        CompactSortMode::ZOrder => format!(
+            "{}({}), ",
+            super::udf::zorder_udf::ZORDER_KEY_UDF_NAME,
+            fields.join(", ")
+        ),
+    }
+}
+
 #[cfg(feature = "enterprise")]
 pub async fn merge_parquet_files_with_downsampling(
     schema: Arc<Schema>,
@@ -408,6 +435,10 @@ pub fn create_session_config(
         cfg.common.feature_pushdown_filter_enabled,
     );
     // config = config.set_bool("datafusion.execution.parquet.reorder_filters", true);
+    config = config.set_bool(
+        "datafusion.execution.parquet.enable_page_index",
+        cfg.common.parquet_page_index_enabled,
+    );
 
     if cfg.common.bloom_filter_enabled {
         config = config.set_bool("datafusion.execution.parquet.bloom_filter_on_read", true);
@@ -419,6 +450,14 @@ pub fn create_session_config(
         config = config.set_bool("datafusion.execution.split_file_groups_by_statistics", true);
     }
 
+    // the leader only knows the size of the enrichment tables, the ones up to this size are
+    // read once and shared by all the partitions of a join
+    config
+        .options_mut()
+        .optimizer
+        .hash_join_single_partition_threshold =
+        cfg.enrichment_table.broadcast_max_size_mb * 1024 * 1024;
+
     // due to: https://github.com/apache/datafusion/issues/19219
     config = config.set_bool("datafusion.optimizer.enable_topk_aggregation", false);
 
@@ -604,6 +643,7 @@ pub fn register_udf(ctx: &SessionContext, org_id: &str) -> Result<()> {
         super::udaf::summary_percentile::SummaryPercentile::new(),
     ));
     ctx.register_udf(super::udf::cast_to_timestamp_udf::CAST_TO_TIMESTAMP_UDF.clone());
+    ctx.register_udf(super::udf::geoip_udf::GEOIP_UDF.clone());
     let udf_list = get_all_transform(org_id)?;
     for udf in udf_list {
         ctx.register_udf(udf.clone());
@@ -721,8 +761,11 @@ impl TableBuilder {
         )
         .await?;
 
-        // Configure listing options
-        let file_format = ParquetFormat::default();
+        // Configure listing options, the page index is loaded to prune the
+        // pages inside the row groups kept by the row group statistics
+        let mut parquet_options = TableParquetOptions::default();
+        parquet_options.global.enable_page_index = cfg.common.parquet_page_index_enabled;
+        let file_format = ParquetFormat::default().with_options(parquet_options);
         let mut listing_options = ListingOptions::new(Arc::new(file_format))
             .with_target_partitions(target_partitions)
             .with_collect_stat(true); // current is default to true
@@ -1209,6 +1252,7 @@ mod tests {
             &bloom_fields,
             &metadata,
             false,
+            None,
         )
         .await;
 
@@ -1217,6 +1261,34 @@ mod tests {
         assert!(result.is_ok() || result.is_err());
     }
 
+    #[test]
+    fn test_compact_sort_order_by() {
+        let schema = Schema::new(vec![
+            Field::new(TIMESTAMP_COL_NAME, DataType::Int64, false),
+            Field::new("k8s_namespace", DataType::Utf8, true),
+            Field::new("service", DataType::Utf8, true),
+        ]);
+        let mut sort = CompactSort {
+            fields: vec![
+                "k8s_namespace".to_string(),
+                "missing".to_string(),
+                "service".to_string(),
+            ],
+            mode: CompactSortMode::Hierarchical,
+        };
+        assert_eq!(
+            compact_sort_order_by(&schema, &sort),
+            "\"k8s_namespace\", \"service\", "
+        );
+        sort.mode = CompactSortMode::ZOrder;
+        assert_eq!(
+            compact_sort_order_by(&schema, &sort),
+            "zorder_key(\"k8s_namespace\", \"service\"), "
+        );
+        sort.fields = vec!["missing".to_string()];
+        assert_eq!(compact_sort_order_by(&schema, &sort), "");
+    }
+
     #[tokio::test]
     async fn test_append_metadata_values() -> Result<()> {
         let mut buf = Vec::new();
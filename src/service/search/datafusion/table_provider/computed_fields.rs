@@ -0,0 +1,128 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use config::meta::stream::ComputedField;
+use datafusion::{
+    catalog::TableProvider,
+    common::{DFSchema, Result},
+    datasource::{ViewTable, provider_as_source},
+    logical_expr::LogicalPlanBuilder,
+    prelude::{SessionContext, ident},
+};
+
+/// Wraps the table of the stream in a view adding its computed fields to the
+/// stored ones. The view is inlined in the logical plan, so the filters on a
+/// computed field are still pushed down to the scan as filters on the fields
+/// it is computed from.
+///
+/// A computed field is skipped when the table doesn't have the fields it is
+/// computed from, the query then fails like on any unknown field if it uses
+/// it.
+pub fn with_computed_fields(
+    ctx: &SessionContext,
+    table_name: &str,
+    table: Arc<dyn TableProvider>,
+    fields: &[ComputedField],
+) -> Result<Arc<dyn TableProvider>> {
+    let schema = table.schema();
+    let df_schema = DFSchema::try_from(schema.as_ref().clone())?;
+    let mut exprs = schema
+        .fields()
+        .iter()
+        .map(|f| ident(f.name()))
+        .collect::<Vec<_>>();
+    for field in fields {
+        if schema.field_with_name(&field.name).is_ok() {
+            continue;
+        }
+        match ctx.parse_sql_expr(&field.expr, &df_schema) {
+            Ok(expr) => exprs.push(expr.alias(&field.name)),
+            Err(e) => log::debug!(
+                "skip computed field {} of table {table_name}: {e}",
+                field.name
+            ),
+        }
+    }
+    if exprs.len() == schema.fields().len() {
+        return Ok(table);
+    }
+    let plan = LogicalPlanBuilder::scan(table_name, provider_as_source(table), None)?
+        .project(exprs)?
+        .build()?;
+    Ok(Arc::new(ViewTable::new(plan, None)))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{Int64Array, RecordBatch, StringArray};
+    use arrow_schema::{DataType, Field, Schema};
+    use datafusion::datasource::MemTable;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_with_computed_fields() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("k8s_pod_labels_app", DataType::Utf8, true),
+            Field::new("took", DataType::Int64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec!["web", "db"])),
+                Arc::new(Int64Array::from(vec![1500, 250])),
+            ],
+        )
+        .unwrap();
+        let table = Arc::new(MemTable::try_new(schema, vec![vec![batch]]).unwrap());
+        let fields = vec![
+            ComputedField {
+                name: "app".to_string(),
+                expr: "k8s_pod_labels_app".to_string(),
+            },
+            ComputedField {
+                name: "took_secs".to_string(),
+                expr: "took / 1000".to_string(),
+            },
+            ComputedField {
+                name: "missing".to_string(),
+                expr: "no_such_field".to_string(),
+            },
+        ];
+
+        let ctx = SessionContext::new();
+        let table = with_computed_fields(&ctx, "t", table, &fields).unwrap();
+        assert!(table.schema().field_with_name("app").is_ok());
+        assert!(table.schema().field_with_name("missing").is_err());
+        ctx.register_table("t", table).unwrap();
+
+        let batches = ctx
+            .sql("SELECT took_secs FROM t WHERE app = 'web'")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 1);
+        let took_secs = batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(took_secs.value(0), 1);
+    }
+}
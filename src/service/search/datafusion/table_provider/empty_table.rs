@@ -36,6 +36,7 @@ pub struct NewEmptyTable {
     schema: SchemaRef,
     partitions: usize,
     pub sorted_by_time: bool,
+    byte_size: Option<usize>,
 }
 
 impl NewEmptyTable {
@@ -46,6 +47,7 @@ impl NewEmptyTable {
             schema,
             partitions: 1,
             sorted_by_time: false,
+            byte_size: None,
         }
     }
 
@@ -60,6 +62,12 @@ impl NewEmptyTable {
         self.sorted_by_time = sorted_by_time;
         self
     }
+
+    /// Creates a new EmptyTable with the size of the data it stands for.
+    pub fn with_byte_size(mut self, byte_size: Option<usize>) -> Self {
+        self.byte_size = byte_size;
+        self
+    }
 }
 
 #[async_trait]
@@ -94,7 +102,8 @@ impl TableProvider for NewEmptyTable {
                 self.sorted_by_time,
                 self.schema.clone(),
             )
-            .with_partitions(self.partitions),
+            .with_partitions(self.partitions)
+            .with_byte_size(self.byte_size),
         ))
     }
 
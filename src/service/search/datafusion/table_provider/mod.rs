@@ -31,9 +31,11 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 pub mod catalog;
+pub mod computed_fields;
 pub mod empty_table;
 pub mod enrich_table;
 mod helpers;
 pub mod listing_adapter;
 pub mod memtable;
+pub mod provenance;
 pub mod uniontable;
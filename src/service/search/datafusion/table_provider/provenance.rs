@@ -0,0 +1,186 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Row provenance, a debug mode to troubleshoot duplicated or missing data.
+//!
+//! When `ZO_FEATURE_QUERY_PROVENANCE_ENABLED` is set and a query references
+//! one of the provenance columns, the columns are added to the schema of the
+//! searched streams. The nodes searching the data see them in the schema of
+//! the plan and build one table per file, each one filling the columns with
+//! the file, the node and the tier the rows are read from.
+
+use std::sync::Arc;
+
+use arrow_schema::{DataType, Field, Schema};
+use datafusion::{
+    catalog::TableProvider,
+    common::{Result, ScalarValue},
+    datasource::{ViewTable, provider_as_source},
+    logical_expr::LogicalPlanBuilder,
+    prelude::{cast, ident, lit},
+};
+
+/// The file the row is read from, null for the rows of the memtable
+pub const SOURCE_FILE_COL: &str = "_o2_source_file";
+/// The node the row is read on
+pub const SOURCE_NODE_COL: &str = "_o2_source_node";
+/// The storage tier the row is read from, see [`SourceTier`]
+pub const SOURCE_TIER_COL: &str = "_o2_source_tier";
+
+const PROVENANCE_COLS: [&str; 3] = [SOURCE_FILE_COL, SOURCE_NODE_COL, SOURCE_TIER_COL];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SourceTier {
+    /// Rows not yet persisted, in the memtable of an ingester
+    Memtable,
+    /// Parquet files in the WAL of an ingester
+    Wal,
+    /// Parquet files of the object storage cached in memory
+    MemoryCache,
+    /// Parquet files of the object storage cached on disk
+    DiskCache,
+    /// Parquet files read from the object storage
+    ObjectStorage,
+}
+
+impl SourceTier {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SourceTier::Memtable => "memtable",
+            SourceTier::Wal => "wal",
+            SourceTier::MemoryCache => "memory_cache",
+            SourceTier::DiskCache => "disk_cache",
+            SourceTier::ObjectStorage => "object_storage",
+        }
+    }
+}
+
+/// Returns true if `sql` asks for the provenance of the rows
+pub fn is_referenced(sql: &str) -> bool {
+    PROVENANCE_COLS.iter().any(|col| sql.contains(col))
+}
+
+/// Returns true if the rows of a table with `schema` should be annotated
+pub fn is_requested(schema: &Schema) -> bool {
+    PROVENANCE_COLS
+        .iter()
+        .any(|col| schema.field_with_name(col).is_ok())
+}
+
+/// Returns `schema` with the provenance columns added
+pub fn add_fields(schema: &Schema) -> Schema {
+    let mut fields = schema.fields().to_vec();
+    for col in PROVENANCE_COLS {
+        if schema.field_with_name(col).is_err() {
+            fields.push(Arc::new(Field::new(col, DataType::Utf8, true)));
+        }
+    }
+    Schema::new(fields).with_metadata(schema.metadata().clone())
+}
+
+/// Wraps `table` in a view filling its provenance columns with constants. The
+/// table is expected to have the provenance columns in its schema, like any
+/// field missing from the data they are read as nulls and replaced by the view.
+pub fn with_provenance(
+    table: Arc<dyn TableProvider>,
+    node: &str,
+    file: Option<&str>,
+    tier: SourceTier,
+) -> Result<Arc<dyn TableProvider>> {
+    let schema = table.schema();
+    let exprs = schema
+        .fields()
+        .iter()
+        .map(|f| {
+            let value = match f.name().as_str() {
+                SOURCE_FILE_COL => file,
+                SOURCE_NODE_COL => Some(node),
+                SOURCE_TIER_COL => Some(tier.as_str()),
+                _ => return ident(f.name()),
+            };
+            cast(
+                lit(ScalarValue::Utf8(value.map(|v| v.to_string()))),
+                f.data_type().clone(),
+            )
+            .alias(f.name())
+        })
+        .collect::<Vec<_>>();
+    let plan = LogicalPlanBuilder::scan("provenance", provider_as_source(table), None)?
+        .project(exprs)?
+        .build()?;
+    Ok(Arc::new(ViewTable::new(plan, None)))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{Int64Array, RecordBatch, StringArray, new_null_array};
+    use datafusion::{datasource::MemTable, prelude::SessionContext};
+
+    use super::*;
+
+    #[test]
+    fn test_is_referenced() {
+        assert!(is_referenced(
+            "SELECT _o2_source_file, count(*) FROM t GROUP BY _o2_source_file"
+        ));
+        assert!(!is_referenced("SELECT * FROM t"));
+    }
+
+    #[tokio::test]
+    async fn test_with_provenance() {
+        let schema = Arc::new(add_fields(&Schema::new(vec![Field::new(
+            "took",
+            DataType::Int64,
+            true,
+        )])));
+        assert!(is_requested(&schema));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2])),
+                new_null_array(&DataType::Utf8, 2),
+                new_null_array(&DataType::Utf8, 2),
+                new_null_array(&DataType::Utf8, 2),
+            ],
+        )
+        .unwrap();
+        let table = Arc::new(MemTable::try_new(schema, vec![vec![batch]]).unwrap());
+        let table =
+            with_provenance(table, "node-1", Some("files/a.parquet"), SourceTier::Wal).unwrap();
+
+        let ctx = SessionContext::new();
+        ctx.register_table("t", table).unwrap();
+        let batches = ctx
+            .sql("SELECT _o2_source_file, _o2_source_node, _o2_source_tier FROM t WHERE took = 2")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 1);
+        let values = (0..3)
+            .map(|i| {
+                batches[0]
+                    .column(i)
+                    .as_any()
+                    .downcast_ref::<StringArray>()
+                    .unwrap()
+                    .value(0)
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec!["files/a.parquet", "node-1", "wal"]);
+    }
+}
@@ -0,0 +1,208 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{net::IpAddr, sync::Arc};
+
+use arrow::array::{Int64Builder, StringBuilder, StructArray};
+use datafusion::{
+    arrow::{
+        array::ArrayRef,
+        datatypes::{DataType, Field, Fields},
+    },
+    common::cast::as_string_array,
+    error::DataFusionError,
+    logical_expr::{ColumnarValue, ScalarUDF, Volatility},
+    prelude::create_udf,
+    sql::sqlparser::parser::ParserError,
+};
+use once_cell::sync::Lazy;
+use vrl::value::{ObjectMap, Value};
+
+use crate::common::infra::config::{GEOIP_ASN_TABLE, GEOIP_CITY_TABLE};
+
+/// The name of the geoip UDF given to DataFusion.
+pub const GEOIP_UDF_NAME: &str = "geoip";
+
+const CITY_FIELDS: [&str; 3] = ["country_name", "country_code", "city_name"];
+const ASN_FIELDS: [&str; 2] = ["autonomous_system_number", "autonomous_system_organization"];
+
+fn geoip_fields() -> Fields {
+    Fields::from(vec![
+        Field::new("country", DataType::Utf8, true),
+        Field::new("country_code", DataType::Utf8, true),
+        Field::new("city", DataType::Utf8, true),
+        Field::new("asn", DataType::Int64, true),
+        Field::new("asn_org", DataType::Utf8, true),
+    ])
+}
+
+/// Implementation of geoip, it looks the ip up in the MaxMind city and ASN
+/// databases downloaded for the RUM enrichment, e.g.
+/// `SELECT geoip(client_ip)['country'] AS country, count(*) FROM t GROUP BY country`
+pub(crate) static GEOIP_UDF: Lazy<ScalarUDF> = Lazy::new(|| {
+    create_udf(
+        GEOIP_UDF_NAME,
+        // expects the ip as a string
+        vec![DataType::Utf8],
+        // returns country, city and ASN as a struct
+        DataType::Struct(geoip_fields()),
+        // the databases are reloaded when they are updated
+        Volatility::Stable,
+        Arc::new(geoip_impl),
+    )
+});
+
+/// geoip function for datafusion
+pub fn geoip_impl(args: &[ColumnarValue]) -> datafusion::error::Result<ColumnarValue> {
+    if args.len() != 1 {
+        return Err(DataFusionError::SQL(
+            Box::new(ParserError::ParserError(
+                "UDF params should be: geoip(field)".to_string(),
+            )),
+            None,
+        ));
+    }
+    let args = ColumnarValue::values_to_arrays(args)?;
+    let ips = as_string_array(&args[0])?;
+
+    let city_select = CITY_FIELDS.map(String::from);
+    let asn_select = ASN_FIELDS.map(String::from);
+    let city_table = GEOIP_CITY_TABLE.read();
+    let asn_table = GEOIP_ASN_TABLE.read();
+
+    let mut country = StringBuilder::with_capacity(ips.len(), 0);
+    let mut country_code = StringBuilder::with_capacity(ips.len(), 0);
+    let mut city = StringBuilder::with_capacity(ips.len(), 0);
+    let mut asn = Int64Builder::with_capacity(ips.len());
+    let mut asn_org = StringBuilder::with_capacity(ips.len(), 0);
+    for ip in ips.iter() {
+        // null for the invalid ips, or the ones missing from the databases
+        let ip = ip.and_then(|ip| ip.trim().parse::<IpAddr>().ok());
+        let city_row = ip.and_then(|ip| {
+            city_table
+                .as_ref()
+                .and_then(|t| t.lookup(ip, Some(city_select.as_slice())))
+        });
+        let asn_row = ip.and_then(|ip| {
+            asn_table
+                .as_ref()
+                .and_then(|t| t.lookup(ip, Some(asn_select.as_slice())))
+        });
+        country.append_option(get_string(city_row.as_ref(), "country_name"));
+        country_code.append_option(get_string(city_row.as_ref(), "country_code"));
+        city.append_option(get_string(city_row.as_ref(), "city_name"));
+        asn.append_option(get_integer(asn_row.as_ref(), "autonomous_system_number"));
+        asn_org.append_option(get_string(
+            asn_row.as_ref(),
+            "autonomous_system_organization",
+        ));
+    }
+    drop(city_table);
+    drop(asn_table);
+
+    let array = StructArray::new(
+        geoip_fields(),
+        vec![
+            Arc::new(country.finish()) as ArrayRef,
+            Arc::new(country_code.finish()),
+            Arc::new(city.finish()),
+            Arc::new(asn.finish()),
+            Arc::new(asn_org.finish()),
+        ],
+        None,
+    );
+    Ok(ColumnarValue::from(Arc::new(array) as ArrayRef))
+}
+
+fn get_string(row: Option<&ObjectMap>, key: &str) -> Option<String> {
+    match row?.get(key)? {
+        Value::Bytes(v) => Some(String::from_utf8_lossy(v).into_owned()),
+        Value::Null => None,
+        v => Some(v.to_string_lossy().into_owned()),
+    }
+}
+
+fn get_integer(row: Option<&ObjectMap>, key: &str) -> Option<i64> {
+    match row?.get(key)? {
+        Value::Integer(v) => Some(*v),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::{
+        arrow::{datatypes::Schema, record_batch::RecordBatch},
+        assert_batches_eq,
+        datasource::MemTable,
+        prelude::SessionContext,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_geoip_udf_without_database() {
+        let schema = Arc::new(Schema::new(vec![Field::new("ip", DataType::Utf8, true)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(datafusion::arrow::array::StringArray::from(vec![
+                Some("8.8.8.8"),
+                Some("not an ip"),
+                None,
+            ]))],
+        )
+        .unwrap();
+        let ctx = SessionContext::new();
+        ctx.register_udf(GEOIP_UDF.clone());
+        let provider = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+        ctx.register_table("t", Arc::new(provider)).unwrap();
+
+        let df = ctx
+            .sql("select geoip(ip)['country'] as country, geoip(ip)['asn'] as asn from t")
+            .await
+            .unwrap();
+        let data = df.collect().await.unwrap();
+        assert_batches_eq!(
+            [
+                "+---------+-----+",
+                "| country | asn |",
+                "+---------+-----+",
+                "|         |     |",
+                "|         |     |",
+                "|         |     |",
+                "+---------+-----+",
+            ],
+            &data
+        );
+    }
+
+    #[test]
+    fn test_get_string() {
+        let mut row = ObjectMap::new();
+        row.insert("country_name".into(), Value::from("Japan"));
+        row.insert("city_name".into(), Value::Null);
+        row.insert("autonomous_system_number".into(), Value::Integer(15169));
+        assert_eq!(
+            get_string(Some(&row), "country_name").as_deref(),
+            Some("Japan")
+        );
+        assert_eq!(get_string(Some(&row), "city_name"), None);
+        assert_eq!(get_string(None, "country_name"), None);
+        assert_eq!(
+            get_integer(Some(&row), "autonomous_system_number"),
+            Some(15169)
+        );
+    }
+}
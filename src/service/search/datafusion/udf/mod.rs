@@ -27,6 +27,7 @@ pub(crate) mod cast_to_timestamp_udf;
 pub(crate) mod cipher_udf;
 pub(crate) mod date_format_udf;
 pub(crate) mod fuzzy_match_udf;
+pub(crate) mod geoip_udf;
 pub(crate) mod histogram_udf;
 pub(crate) mod match_all_hash_udf;
 pub(crate) mod match_all_udf;
@@ -38,6 +39,7 @@ pub(crate) mod string_to_array_v2_udf;
 pub(crate) mod time_range_udf;
 pub(crate) mod to_arr_string_udf;
 pub(crate) mod transform_udf;
+pub(crate) mod zorder_udf;
 
 /// The name of the str_match UDF given to DataFusion.
 pub(crate) const STR_MATCH_UDF_NAME: &str = "str_match";
@@ -56,7 +58,7 @@ pub(crate) const REGEX_NOT_MATCH_UDF_NAME: &str = "re_not_match";
 /// The name of the regex_matches UDF given to DataFusion.
 pub(crate) const REGEX_MATCHES_UDF_NAME: &str = "re_matches";
 
-pub(crate) const DEFAULT_FUNCTIONS: [ZoFunction; 11] = [
+pub(crate) const DEFAULT_FUNCTIONS: [ZoFunction; 12] = [
     ZoFunction {
         name: "match_all",
         text: "match_all('v')",
@@ -101,6 +103,10 @@ pub(crate) const DEFAULT_FUNCTIONS: [ZoFunction; 11] = [
         name: cast_to_timestamp_udf::CAST_TO_TIMESTAMP_UDF_NAME,
         text: "cast_to_timestamp('pattern')",
     },
+    ZoFunction {
+        name: geoip_udf::GEOIP_UDF_NAME,
+        text: "geoip(field)",
+    },
 ];
 
 pub fn stringify_json_value(field: &json::Value) -> String {
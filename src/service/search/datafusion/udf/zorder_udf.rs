@@ -0,0 +1,254 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use std::{any::Any, sync::Arc};
+
+use datafusion::{
+    arrow::{
+        array::{Array, ArrayRef, AsArray, BinaryBuilder},
+        compute::cast,
+        datatypes::{DataType, Float64Type, Int64Type, UInt64Type},
+    },
+    common::exec_err,
+    error::Result,
+    logical_expr::{
+        ColumnarValue, ScalarFunctionArgs, ScalarUDF, ScalarUDFImpl, Signature, Volatility,
+    },
+};
+use once_cell::sync::Lazy;
+
+/// The name of the zorder_key UDF given to DataFusion.
+pub const ZORDER_KEY_UDF_NAME: &str = "zorder_key";
+
+/// Implementation of zorder_key, used by the compactor to sort the merged
+/// files by several fields at once
+pub(crate) static ZORDER_KEY_UDF: Lazy<ScalarUDF> =
+    Lazy::new(|| ScalarUDF::from(ZOrderKeyUdf::new()));
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+struct ZOrderKeyUdf {
+    signature: Signature,
+}
+
+impl ZOrderKeyUdf {
+    fn new() -> Self {
+        Self {
+            signature: Signature::variadic_any(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for ZOrderKeyUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        ZORDER_KEY_UDF_NAME
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Binary)
+    }
+
+    /// The key of a row interleaves the bits of the order preserving 64 bits
+    /// key of each of its values, so the keys compare like the z-order curve
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        if args.args.is_empty() {
+            return exec_err!("{} expects at least one argument", self.name());
+        }
+        let num_rows = args.number_rows;
+        let columns = args
+            .args
+            .iter()
+            .map(|arg| order_keys(&arg.to_array(num_rows)?))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut builder = BinaryBuilder::with_capacity(num_rows, num_rows * columns.len() * 8);
+        let mut values = vec![0; columns.len()];
+        for row in 0..num_rows {
+            for (value, column) in values.iter_mut().zip(columns.iter()) {
+                *value = column[row];
+            }
+            builder.append_value(interleave_bits(&values));
+        }
+        Ok(ColumnarValue::Array(Arc::new(builder.finish()) as ArrayRef))
+    }
+}
+
+/// Maps the values to keys sorting like them, the strings by their first 8
+/// bytes. The nulls get the lowest key.
+fn order_keys(array: &ArrayRef) -> Result<Vec<u64>> {
+    let keys = match array.data_type() {
+        DataType::Boolean => {
+            let array = array.as_boolean();
+            (0..array.len())
+                .map(|i| {
+                    if array.is_valid(i) && array.value(i) {
+                        1 << 63
+                    } else {
+                        0
+                    }
+                })
+                .collect()
+        }
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => {
+            let array = cast(array, &DataType::Int64)?;
+            array
+                .as_primitive::<Int64Type>()
+                .iter()
+                .map(|v| v.map(|v| (v as u64) ^ (1 << 63)).unwrap_or_default())
+                .collect()
+        }
+        DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => {
+            let array = cast(array, &DataType::UInt64)?;
+            array
+                .as_primitive::<UInt64Type>()
+                .iter()
+                .map(|v| v.unwrap_or_default())
+                .collect()
+        }
+        DataType::Float16 | DataType::Float32 | DataType::Float64 => {
+            let array = cast(array, &DataType::Float64)?;
+            array
+                .as_primitive::<Float64Type>()
+                .iter()
+                .map(|v| v.map(float_key).unwrap_or_default())
+                .collect()
+        }
+        _ => {
+            let array = cast(array, &DataType::Utf8)?;
+            array
+                .as_string::<i32>()
+                .iter()
+                .map(|v| v.map(string_key).unwrap_or_default())
+                .collect()
+        }
+    };
+    Ok(keys)
+}
+
+fn float_key(v: f64) -> u64 {
+    let bits = v.to_bits();
+    if bits >> 63 == 1 {
+        !bits
+    } else {
+        bits ^ (1 << 63)
+    }
+}
+
+fn string_key(v: &str) -> u64 {
+    let mut buf = [0; 8];
+    let len = v.len().min(8);
+    buf[..len].copy_from_slice(&v.as_bytes()[..len]);
+    u64::from_be_bytes(buf)
+}
+
+/// Takes the bits of the values from the most significant one, one value
+/// after the other
+fn interleave_bits(values: &[u64]) -> Vec<u8> {
+    let mut buf = vec![0; values.len() * 8];
+    for bit in 0..64 {
+        for (i, value) in values.iter().enumerate() {
+            if (value >> (63 - bit)) & 1 == 1 {
+                let pos = bit * values.len() + i;
+                buf[pos / 8] |= 0x80 >> (pos % 8);
+            }
+        }
+    }
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::{
+        arrow::{
+            array::{Int64Array, StringArray},
+            datatypes::{Field, Schema},
+            record_batch::RecordBatch,
+        },
+        assert_batches_eq,
+        datasource::MemTable,
+        prelude::SessionContext,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_interleave_bits() {
+        assert_eq!(interleave_bits(&[u64::MAX]), vec![0xff; 8]);
+        let key = interleave_bits(&[1 << 63, 0]);
+        assert_eq!(key[0], 0x80);
+        let key = interleave_bits(&[0, 1 << 63]);
+        assert_eq!(key[0], 0x40);
+        let key = interleave_bits(&[1, 1]);
+        assert_eq!(key[15], 0x03);
+    }
+
+    #[test]
+    fn test_order_keys_preserve_order() {
+        assert!(float_key(-2.5) < float_key(-1.0));
+        assert!(float_key(-1.0) < float_key(0.0));
+        assert!(float_key(0.0) < float_key(3.5));
+        assert!(string_key("a") < string_key("ab"));
+        assert!(string_key("ab") < string_key("b"));
+        let array = Arc::new(Int64Array::from(vec![Some(-5), None, Some(0), Some(7)])) as ArrayRef;
+        let keys = order_keys(&array).unwrap();
+        assert_eq!(keys[1], 0);
+        assert!(keys[0] < keys[2] && keys[2] < keys[3]);
+    }
+
+    #[tokio::test]
+    async fn test_zorder_key_udf() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("service", DataType::Utf8, false),
+            Field::new("code", DataType::Int64, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec!["b", "a", "b", "a"])),
+                Arc::new(Int64Array::from(vec![1, 1, 0, 0])),
+            ],
+        )
+        .unwrap();
+
+        let ctx = SessionContext::new();
+        ctx.register_udf(ZORDER_KEY_UDF.clone());
+        let provider = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+        ctx.register_table("t", Arc::new(provider)).unwrap();
+        let df = ctx
+            .sql("SELECT service, code FROM t ORDER BY zorder_key(service, code)")
+            .await
+            .unwrap();
+        let data = df.collect().await.unwrap();
+        assert_batches_eq!(
+            vec![
+                "+---------+------+",
+                "| service | code |",
+                "+---------+------+",
+                "| a       | 0    |",
+                "| a       | 1    |",
+                "| b       | 0    |",
+                "| b       | 1    |",
+                "+---------+------+",
+            ],
+            &data
+        );
+    }
+}
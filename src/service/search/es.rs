@@ -0,0 +1,772 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Elasticsearch `_msearch` compatibility. The basics of the query DSL
+//! (match_all, term(s), match, query_string, range, exists, prefix,
+//! wildcard and bool) are translated to SQL over the stream named by the
+//! index, a range on `@timestamp` becomes the time range of the search.
+
+use config::{
+    ID_COL_NAME, TIMESTAMP_COL_NAME,
+    meta::{search, stream::StreamType},
+    utils::{
+        flatten::format_key,
+        json::{self, Value},
+        schema::format_stream_name,
+        time::{parse_i64_to_timestamp_micros, parse_str_to_timestamp_micros},
+    },
+};
+
+use crate::service::db;
+
+/// Default number of hits of an ES search
+const DEFAULT_SIZE: i64 = 10;
+
+/// One search of a multi search body
+#[derive(Debug)]
+pub struct MultiSearchItem {
+    pub index: Option<String>,
+    pub body: Value,
+}
+
+/// Splits an NDJSON multi search body into header and body pairs
+pub fn parse_msearch(body: &[u8]) -> Result<Vec<MultiSearchItem>, String> {
+    let body = std::str::from_utf8(body).map_err(|e| format!("invalid body: {e}"))?;
+    let mut lines = body.lines().filter(|line| !line.trim().is_empty());
+    let mut items = Vec::new();
+    while let Some(header) = lines.next() {
+        let header: Value =
+            json::from_str(header).map_err(|e| format!("invalid search header: {e}"))?;
+        let Some(body) = lines.next() else {
+            return Err("search header without a search body".to_string());
+        };
+        let body: Value = json::from_str(body).map_err(|e| format!("invalid search body: {e}"))?;
+        let index = match header.get("index") {
+            Some(Value::String(v)) => Some(v.clone()),
+            Some(Value::Array(v)) => Some(
+                v.iter()
+                    .filter_map(|v| v.as_str())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+            _ => None,
+        };
+        items.push(MultiSearchItem { index, body });
+    }
+    Ok(items)
+}
+
+/// Resolves an index expression to a single log stream, wildcards and comma
+/// lists must match exactly one stream
+pub async fn resolve_index(org_id: &str, index: &str) -> Result<String, String> {
+    let patterns = index
+        .split(',')
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .collect::<Vec<_>>();
+    if patterns.len() == 1 && !patterns[0].contains('*') {
+        return Ok(format_stream_name(patterns[0].to_string()));
+    }
+    let streams = db::schema::list(org_id, Some(StreamType::Logs), false)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut matched = streams
+        .into_iter()
+        .map(|s| s.stream_name)
+        .filter(|name| patterns.iter().any(|p| wildcard_match(p, name)))
+        .collect::<Vec<_>>();
+    matched.dedup();
+    match matched.len() {
+        1 => Ok(matched.remove(0)),
+        0 => Err(format!("no such index [{index}]")),
+        _ => Err(format!(
+            "index [{index}] matches {} streams, searching several indices at once is not supported",
+            matched.len()
+        )),
+    }
+}
+
+/// Builds the search request of an ES search body against a stream, `now` is
+/// in microseconds and used for date math
+pub fn build_request(stream_name: &str, body: &Value, now: i64) -> Result<search::Request, String> {
+    if body.get("aggs").is_some() || body.get("aggregations").is_some() {
+        return Err("aggregations are not supported".to_string());
+    }
+    let mut translator = Translator {
+        start: None,
+        end: None,
+        now,
+    };
+    let condition = match body.get("query") {
+        Some(query) => translator.condition(query, true)?,
+        None => None,
+    };
+
+    let mut sql = format!("SELECT {} FROM {}", select(body)?, quote_ident(stream_name));
+    if let Some(condition) = condition {
+        sql.push_str(" WHERE ");
+        sql.push_str(&condition);
+    }
+    let order_by = sort(body.get("sort"))?;
+    if !order_by.is_empty() {
+        sql.push_str(" ORDER BY ");
+        sql.push_str(&order_by.join(", "));
+    }
+
+    let size = match body.get("size") {
+        Some(v) => v.as_i64().ok_or("size must be a number")?,
+        None => DEFAULT_SIZE,
+    };
+    let from = match body.get("from") {
+        Some(v) => v.as_i64().ok_or("from must be a number")?,
+        None => 0,
+    };
+    Ok(search::Request {
+        query: search::Query {
+            sql,
+            from,
+            size,
+            start_time: translator.start.unwrap_or(0),
+            end_time: translator.end.unwrap_or(now),
+            track_total_hits: body
+                .get("track_total_hits")
+                .is_some_and(|v| v.as_bool() == Some(true)),
+            ..Default::default()
+        },
+        encoding: search::RequestEncoding::Empty,
+        regions: vec![],
+        clusters: vec![],
+        timeout: 0,
+        search_type: None,
+        search_event_context: None,
+        use_cache: search::default_use_cache(),
+        clear_cache: false,
+        local_mode: None,
+    })
+}
+
+/// ES shaped response of a search
+pub fn to_es_response(stream_name: &str, resp: search::Response, total_tracked: bool) -> Value {
+    let size = resp.size;
+    let hits = resp
+        .hits
+        .into_iter()
+        .enumerate()
+        .map(|(i, mut source)| {
+            let id = match source.get(ID_COL_NAME) {
+                Some(v) => v.to_string(),
+                None => format!("{stream_name}-{}", resp.from + i as i64),
+            };
+            if let Some(map) = source.as_object_mut()
+                && !map.contains_key("@timestamp")
+                && let Some(ts) = map.get(TIMESTAMP_COL_NAME).and_then(|v| v.as_i64())
+            {
+                let ts = chrono::DateTime::from_timestamp_micros(ts).unwrap_or_default();
+                map.insert(
+                    "@timestamp".to_string(),
+                    Value::String(ts.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)),
+                );
+            }
+            json::json!({
+                "_index": stream_name,
+                "_id": id,
+                "_score": null,
+                "_source": source,
+            })
+        })
+        .collect::<Vec<_>>();
+    let relation = if !total_tracked && resp.total as i64 >= size {
+        "gte"
+    } else {
+        "eq"
+    };
+    json::json!({
+        "took": resp.took,
+        "timed_out": false,
+        "_shards": {"total": 1, "successful": 1, "skipped": 0, "failed": 0},
+        "hits": {
+            "total": {"value": resp.total, "relation": relation},
+            "max_score": null,
+            "hits": hits,
+        },
+        "status": 200,
+    })
+}
+
+/// ES shaped error of a search
+pub fn to_es_error(status: u16, error_type: &str, reason: impl ToString) -> Value {
+    json::json!({
+        "error": {
+            "root_cause": [{"type": error_type, "reason": reason.to_string()}],
+            "type": error_type,
+            "reason": reason.to_string(),
+        },
+        "status": status,
+    })
+}
+
+struct Translator {
+    start: Option<i64>,
+    end: Option<i64>,
+    now: i64,
+}
+
+impl Translator {
+    /// SQL condition of a query clause, `None` matches everything. Timestamp
+    /// ranges in an AND context narrow the time range of the search instead.
+    fn condition(&mut self, query: &Value, and_ctx: bool) -> Result<Option<String>, String> {
+        let Some(map) = query.as_object() else {
+            return Err(format!("invalid query clause: {query}"));
+        };
+        let Some((kind, params)) = map.iter().next().filter(|_| map.len() == 1) else {
+            return Err(format!("query clause must have exactly one type: {query}"));
+        };
+        match kind.as_str() {
+            "match_all" => Ok(None),
+            "match_none" => Ok(Some("1 = 0".to_string())),
+            "term" => {
+                let (field, value) = field_param(params, "value")?;
+                Ok(Some(format!(
+                    "{} = {}",
+                    quote_ident(&field),
+                    literal(value)?
+                )))
+            }
+            "terms" => {
+                let (field, values) = field_param(params, "value")?;
+                let values = values.as_array().ok_or("terms values must be an array")?;
+                if values.is_empty() {
+                    return Ok(Some("1 = 0".to_string()));
+                }
+                let values = values.iter().map(literal).collect::<Result<Vec<_>, _>>()?;
+                Ok(Some(format!(
+                    "{} IN ({})",
+                    quote_ident(&field),
+                    values.join(", ")
+                )))
+            }
+            "match" | "match_phrase" => {
+                let (field, value) = field_param(params, "query")?;
+                let text = text(value)?;
+                if field == "_all" || field == "*" {
+                    return Ok(Some(format!("match_all({})", quote_str(&text))));
+                }
+                Ok(Some(format!(
+                    "str_match_ignore_case({}, {})",
+                    quote_ident(&field),
+                    quote_str(&text)
+                )))
+            }
+            "multi_match" => {
+                let text = text(params.get("query").ok_or("multi_match needs a query")?)?;
+                let fields = params
+                    .get("fields")
+                    .and_then(|v| v.as_array())
+                    .map(|v| v.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>())
+                    .unwrap_or_default();
+                if fields.is_empty() || fields.contains(&"*") {
+                    return Ok(Some(format!("match_all({})", quote_str(&text))));
+                }
+                let conditions = fields
+                    .iter()
+                    .map(|f| {
+                        format!(
+                            "str_match_ignore_case({}, {})",
+                            quote_ident(&field_name(f)),
+                            quote_str(&text)
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                Ok(Some(format!("({})", conditions.join(" OR "))))
+            }
+            "query_string" | "simple_query_string" => {
+                let text = text(params.get("query").ok_or("query_string needs a query")?)?;
+                if text.trim().is_empty() || text.trim() == "*" {
+                    return Ok(None);
+                }
+                match params.get("default_field").and_then(|v| v.as_str()) {
+                    Some(field) if field != "*" => Ok(Some(format!(
+                        "str_match_ignore_case({}, {})",
+                        quote_ident(&field_name(field)),
+                        quote_str(&text)
+                    ))),
+                    _ => Ok(Some(format!("match_all({})", quote_str(&text)))),
+                }
+            }
+            "exists" => {
+                let field = params
+                    .get("field")
+                    .and_then(|v| v.as_str())
+                    .ok_or("exists needs a field")?;
+                Ok(Some(format!(
+                    "{} IS NOT NULL",
+                    quote_ident(&field_name(field))
+                )))
+            }
+            "prefix" => {
+                let (field, value) = field_param(params, "value")?;
+                let pattern = format!("{}%", escape_like(&text(value)?));
+                Ok(Some(format!(
+                    "{} LIKE {}",
+                    quote_ident(&field),
+                    quote_str(&pattern)
+                )))
+            }
+            "wildcard" => {
+                let (field, value) = field_param(params, "value")?;
+                let pattern = escape_like(&text(value)?)
+                    .replace('*', "%")
+                    .replace('?', "_");
+                Ok(Some(format!(
+                    "{} LIKE {}",
+                    quote_ident(&field),
+                    quote_str(&pattern)
+                )))
+            }
+            "range" => self.range(params, and_ctx),
+            "bool" => self.bool(params, and_ctx),
+            _ => Err(format!("query type [{kind}] is not supported")),
+        }
+    }
+
+    fn range(&mut self, params: &Value, and_ctx: bool) -> Result<Option<String>, String> {
+        let (field, bounds) = field_param(params, "")?;
+        let bounds = bounds.as_object().ok_or("range bounds must be an object")?;
+        let is_time = field == TIMESTAMP_COL_NAME;
+        let mut conditions = Vec::new();
+        for (op, value) in bounds.iter() {
+            let op = match op.as_str() {
+                "gte" | "from" => ">=",
+                "gt" => ">",
+                "lte" | "to" => "<=",
+                "lt" => "<",
+                // format, time_zone, boost, include_lower...
+                _ => continue,
+            };
+            if value.is_null() {
+                continue;
+            }
+            if !is_time {
+                conditions.push(format!("{} {op} {}", quote_ident(&field), literal(value)?));
+                continue;
+            }
+            let ts = self.timestamp(value)?;
+            if !and_ctx {
+                conditions.push(format!("{} {op} {ts}", quote_ident(&field)));
+                continue;
+            }
+            // the time range of a search is [start, end)
+            match op {
+                ">=" => self.start = Some(self.start.map_or(ts, |v| v.max(ts))),
+                ">" => self.start = Some(self.start.map_or(ts + 1, |v| v.max(ts + 1))),
+                "<=" => self.end = Some(self.end.map_or(ts + 1, |v| v.min(ts + 1))),
+                _ => self.end = Some(self.end.map_or(ts, |v| v.min(ts))),
+            }
+        }
+        Ok(and(conditions))
+    }
+
+    fn bool(&mut self, params: &Value, and_ctx: bool) -> Result<Option<String>, String> {
+        let clauses = |name: &str| -> Vec<Value> {
+            match params.get(name) {
+                Some(Value::Array(v)) => v.clone(),
+                Some(v @ Value::Object(_)) => vec![v.clone()],
+                _ => vec![],
+            }
+        };
+        let mut conditions = Vec::new();
+        for clause in clauses("must").iter().chain(clauses("filter").iter()) {
+            if let Some(c) = self.condition(clause, and_ctx)? {
+                conditions.push(c);
+            }
+        }
+        for clause in clauses("must_not").iter() {
+            match self.condition(clause, false)? {
+                Some(c) => conditions.push(format!("NOT ({c})")),
+                None => conditions.push("1 = 0".to_string()),
+            }
+        }
+
+        // should clauses are optional next to must or filter clauses unless
+        // asked otherwise
+        let should = clauses("should");
+        let required = params
+            .get("minimum_should_match")
+            .map(|v| v.as_i64().unwrap_or(1) > 0 || v.as_str().is_some())
+            .unwrap_or(clauses("must").is_empty() && clauses("filter").is_empty());
+        if !should.is_empty() && required {
+            let mut any = Vec::with_capacity(should.len());
+            for clause in should.iter() {
+                match self.condition(clause, false)? {
+                    Some(c) => any.push(format!("({c})")),
+                    // one clause matches everything
+                    None => {
+                        any.clear();
+                        break;
+                    }
+                }
+            }
+            if !any.is_empty() {
+                conditions.push(format!("({})", any.join(" OR ")));
+            }
+        }
+        Ok(and(conditions))
+    }
+
+    /// Timestamp in microseconds of an epoch number, a date string or date
+    /// math like `now-15m`, rounding (`/d`) is ignored
+    fn timestamp(&self, value: &Value) -> Result<i64, String> {
+        match value {
+            Value::Number(v) => v
+                .as_i64()
+                .map(parse_i64_to_timestamp_micros)
+                .ok_or_else(|| format!("invalid timestamp: {v}")),
+            Value::String(v) if v.starts_with("now") => {
+                let expr = v.split('/').next().unwrap_or_default();
+                let offset = &expr[3..];
+                if offset.is_empty() {
+                    return Ok(self.now);
+                }
+                let (sign, amount) = match offset.split_at(1) {
+                    ("-", v) => (-1, v),
+                    ("+", v) => (1, v),
+                    _ => return Err(format!("invalid date math: {v}")),
+                };
+                let unit_at = amount
+                    .find(|c: char| !c.is_ascii_digit())
+                    .ok_or_else(|| format!("invalid date math: {v}"))?;
+                let (n, unit) = amount.split_at(unit_at);
+                let n: i64 = n.parse().map_err(|_| format!("invalid date math: {v}"))?;
+                let secs = match unit {
+                    "s" => 1,
+                    "m" => 60,
+                    "h" | "H" => 3600,
+                    "d" => 86400,
+                    "w" => 7 * 86400,
+                    "M" => 30 * 86400,
+                    "y" => 365 * 86400,
+                    _ => return Err(format!("invalid date math unit: {v}")),
+                };
+                Ok(self.now + sign * n * secs * 1_000_000)
+            }
+            Value::String(v) => parse_str_to_timestamp_micros(v).map_err(|e| e.to_string()),
+            _ => Err(format!("invalid timestamp: {value}")),
+        }
+    }
+}
+
+fn select(body: &Value) -> Result<String, String> {
+    let fields = match body.get("_source") {
+        Some(Value::Array(v)) => v.clone(),
+        Some(Value::Object(v)) => match v.get("includes") {
+            Some(Value::Array(v)) => v.clone(),
+            _ => vec![],
+        },
+        _ => vec![],
+    };
+    if fields.is_empty() || fields.iter().any(|f| f.as_str() == Some("*")) {
+        return Ok("*".to_string());
+    }
+    let mut columns = vec![quote_ident(TIMESTAMP_COL_NAME)];
+    for f in fields.iter() {
+        let f = f.as_str().ok_or("_source fields must be strings")?;
+        let column = quote_ident(&field_name(f));
+        if !columns.contains(&column) {
+            columns.push(column);
+        }
+    }
+    Ok(columns.join(", "))
+}
+
+fn sort(sort: Option<&Value>) -> Result<Vec<String>, String> {
+    let items = match sort {
+        None => return Ok(vec![]),
+        Some(Value::Array(v)) => v.clone(),
+        Some(v) => vec![v.clone()],
+    };
+    let mut order_by = Vec::new();
+    for item in items {
+        let (field, order) = match &item {
+            Value::String(f) => (f.clone(), None),
+            Value::Object(map) => {
+                let Some((f, v)) = map.iter().next() else {
+                    continue;
+                };
+                let order = match v {
+                    Value::String(o) => Some(o.clone()),
+                    Value::Object(o) => o.get("order").and_then(|v| v.as_str()).map(String::from),
+                    _ => None,
+                };
+                (f.clone(), order)
+            }
+            _ => return Err(format!("invalid sort: {item}")),
+        };
+        if field == "_score" || field == "_doc" {
+            continue;
+        }
+        let order = match order.as_deref() {
+            None | Some("asc") => "ASC",
+            Some("desc") => "DESC",
+            Some(v) => return Err(format!("invalid sort order: {v}")),
+        };
+        order_by.push(format!("{} {order}", quote_ident(&field_name(&field))));
+    }
+    Ok(order_by)
+}
+
+/// Field and value of `{field: value}` or `{field: {key: value}}`
+fn field_param<'a>(params: &'a Value, key: &str) -> Result<(String, &'a Value), String> {
+    let map = params
+        .as_object()
+        .ok_or_else(|| format!("invalid query parameters: {params}"))?;
+    let (field, value) = map
+        .iter()
+        .find(|(k, _)| !matches!(k.as_str(), "boost" | "_name"))
+        .ok_or_else(|| format!("missing field in: {params}"))?;
+    let value = match value {
+        Value::Object(v) if !key.is_empty() => v
+            .get(key)
+            .ok_or_else(|| format!("missing [{key}] for field [{field}]"))?,
+        v => v,
+    };
+    Ok((field_name(field), value))
+}
+
+/// Column of an ES field, `.keyword` sub fields are the field itself and
+/// nested names are flattened like ingestion does, `@timestamp` becomes
+/// `_timestamp`
+fn field_name(field: &str) -> String {
+    let mut name = field.strip_suffix(".keyword").unwrap_or(field).to_string();
+    format_key(&mut name);
+    name
+}
+
+fn literal(value: &Value) -> Result<String, String> {
+    match value {
+        Value::String(v) => Ok(quote_str(v)),
+        Value::Number(v) => Ok(v.to_string()),
+        Value::Bool(v) => Ok(v.to_string()),
+        _ => Err(format!("unsupported value: {value}")),
+    }
+}
+
+fn text(value: &Value) -> Result<String, String> {
+    match value {
+        Value::String(v) => Ok(v.clone()),
+        Value::Number(v) => Ok(v.to_string()),
+        Value::Bool(v) => Ok(v.to_string()),
+        _ => Err(format!("unsupported value: {value}")),
+    }
+}
+
+fn and(conditions: Vec<String>) -> Option<String> {
+    match conditions.len() {
+        0 => None,
+        1 => conditions.into_iter().next(),
+        _ => Some(
+            conditions
+                .iter()
+                .map(|c| format!("({c})"))
+                .collect::<Vec<_>>()
+                .join(" AND "),
+        ),
+    }
+}
+
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    if !pattern.contains('*') {
+        return format_stream_name(pattern.to_string()) == name;
+    }
+    let re = pattern
+        .split('*')
+        .map(|part| regex::escape(&format_stream_name(part.to_string())))
+        .collect::<Vec<_>>()
+        .join(".*");
+    regex::Regex::new(&format!("^{re}$")).is_ok_and(|re| re.is_match(name))
+}
+
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn quote_str(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000_000_000;
+
+    fn sql(body: Value) -> search::Request {
+        build_request("app_logs", &body, NOW).unwrap()
+    }
+
+    #[test]
+    fn test_parse_msearch() {
+        let body =
+            b"{\"index\":\"app-logs\"}\n{\"query\":{\"match_all\":{}}}\n\n{}\n{\"size\":0}\n";
+        let items = parse_msearch(body).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].index.as_deref(), Some("app-logs"));
+        assert!(items[1].index.is_none());
+        assert_eq!(items[1].body["size"], 0);
+
+        assert!(parse_msearch(b"{\"index\":\"a\"}\n").is_err());
+        assert!(parse_msearch(b"{\"index\":\"a\"}\nnot json\n").is_err());
+    }
+
+    #[test]
+    fn test_build_request_basics() {
+        let req = sql(json::json!({}));
+        assert_eq!(req.query.sql, "SELECT * FROM \"app_logs\"");
+        assert_eq!(req.query.size, 10);
+        assert_eq!(req.query.start_time, 0);
+        assert_eq!(req.query.end_time, NOW);
+
+        let req = sql(json::json!({
+            "query": {"term": {"kubernetes.namespace.keyword": "prod"}},
+            "size": 50,
+            "from": 10,
+            "sort": [{"@timestamp": {"order": "desc"}}, "_score"],
+            "_source": ["message", "level"]
+        }));
+        assert_eq!(
+            req.query.sql,
+            "SELECT \"_timestamp\", \"message\", \"level\" FROM \"app_logs\" WHERE \
+             \"kubernetes_namespace\" = 'prod' ORDER BY \"_timestamp\" DESC"
+        );
+        assert_eq!(req.query.size, 50);
+        assert_eq!(req.query.from, 10);
+    }
+
+    #[test]
+    fn test_build_request_bool() {
+        let req = sql(json::json!({
+            "query": {"bool": {
+                "must": [{"match": {"message": "timed out"}}],
+                "filter": [
+                    {"range": {"@timestamp": {"gte": "now-15m", "lte": "now", "format": "strict_date_optional_time"}}},
+                    {"terms": {"status": [500, 503]}}
+                ],
+                "must_not": {"exists": {"field": "trace_id"}},
+                "should": [{"term": {"level": "error"}}]
+            }}
+        }));
+        assert_eq!(
+            req.query.sql,
+            "SELECT * FROM \"app_logs\" WHERE (str_match_ignore_case(\"message\", 'timed out')) \
+             AND (\"status\" IN (500, 503)) AND (NOT (\"trace_id\" IS NOT NULL))"
+        );
+        assert_eq!(req.query.start_time, NOW - 15 * 60 * 1_000_000);
+        assert_eq!(req.query.end_time, NOW + 1);
+
+        // should clauses are required without must or filter
+        let req = sql(json::json!({
+            "query": {"bool": {"should": [
+                {"term": {"level": "error"}},
+                {"prefix": {"service": {"value": "api_"}}}
+            ]}}
+        }));
+        assert_eq!(
+            req.query.sql,
+            "SELECT * FROM \"app_logs\" WHERE ((\"level\" = 'error') OR (\"service\" LIKE \
+             'api\\_%'))"
+        );
+    }
+
+    #[test]
+    fn test_build_request_ranges() {
+        let req = sql(json::json!({
+            "query": {"bool": {"filter": [
+                {"range": {"@timestamp": {"gte": 1_699_999_000_000i64, "lt": 1_699_999_900_000i64, "format": "epoch_millis"}}},
+                {"range": {"latency": {"gt": 100}}}
+            ]}}
+        }));
+        assert_eq!(req.query.start_time, 1_699_999_000_000_000);
+        assert_eq!(req.query.end_time, 1_699_999_900_000_000);
+        assert_eq!(
+            req.query.sql,
+            "SELECT * FROM \"app_logs\" WHERE \"latency\" > 100"
+        );
+
+        // not narrowing the time range outside of an AND context
+        let req = sql(json::json!({
+            "query": {"bool": {"must_not": {"range": {"@timestamp": {"gte": "now-1h"}}}}}
+        }));
+        assert_eq!(req.query.start_time, 0);
+        assert!(req.query.sql.contains("NOT (\"_timestamp\" >= "));
+    }
+
+    #[test]
+    fn test_build_request_unsupported() {
+        let body = json::json!({"query": {"geo_distance": {}}});
+        assert!(build_request("app_logs", &body, NOW).is_err());
+        let body = json::json!({"aggs": {"by_level": {"terms": {"field": "level"}}}});
+        assert!(build_request("app_logs", &body, NOW).is_err());
+        let body = json::json!({"query": {"range": {"@timestamp": {"gte": "now-5x"}}}});
+        assert!(build_request("app_logs", &body, NOW).is_err());
+    }
+
+    #[test]
+    fn test_query_string() {
+        let req = sql(json::json!({"query": {"query_string": {"query": "*"}}}));
+        assert_eq!(req.query.sql, "SELECT * FROM \"app_logs\"");
+        let req = sql(json::json!({"query": {"query_string": {"query": "it's down"}}}));
+        assert_eq!(
+            req.query.sql,
+            "SELECT * FROM \"app_logs\" WHERE match_all('it''s down')"
+        );
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("app-*", "app_logs"));
+        assert!(wildcard_match("*logs", "app_logs"));
+        assert!(!wildcard_match("web-*", "app_logs"));
+        assert!(wildcard_match("app_logs", "app_logs"));
+    }
+
+    #[test]
+    fn test_to_es_response() {
+        let resp = search::Response {
+            took: 3,
+            hits: vec![json::json!({"_timestamp": 1_700_000_000_000_000i64, "message": "hi"})],
+            total: 1,
+            size: 10,
+            ..Default::default()
+        };
+        let v = to_es_response("app_logs", resp, false);
+        assert_eq!(v["status"], 200);
+        assert_eq!(v["hits"]["total"]["value"], 1);
+        assert_eq!(v["hits"]["total"]["relation"], "eq");
+        let hit = &v["hits"]["hits"][0];
+        assert_eq!(hit["_index"], "app_logs");
+        assert_eq!(hit["_id"], "app_logs-0");
+        assert_eq!(hit["_source"]["@timestamp"], "2023-11-14T22:13:20.000Z");
+
+        let v = to_es_error(400, "parsing_exception", "bad");
+        assert_eq!(v["status"], 400);
+        assert_eq!(v["error"]["reason"], "bad");
+    }
+}
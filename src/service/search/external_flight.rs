@@ -0,0 +1,145 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Search returning the record batches of the result, for the external Arrow
+//! Flight endpoint, so large results skip the conversion to JSON.
+
+use std::sync::Arc;
+
+use arrow::array::RecordBatch;
+use config::meta::{search, stream::StreamType};
+use infra::errors::{Error, ErrorCodes, Result};
+use proto::cluster_rpc::SearchQuery;
+#[cfg(feature = "enterprise")]
+use {super::SEARCH_SERVER, o2_enterprise::enterprise::search::TaskStatus};
+
+use super::{cluster, information_schema, sql::Sql, utils::is_default_query_limit_exceeded};
+
+pub async fn search(
+    trace_id: &str,
+    org_id: &str,
+    stream_type: StreamType,
+    user_id: Option<String>,
+    in_req: &search::Request,
+) -> Result<(Vec<RecordBatch>, search::ScanStats)> {
+    if in_req
+        .query
+        .query_fn
+        .as_ref()
+        .is_some_and(|f| !f.trim().is_empty())
+    {
+        return Err(Error::ErrorCode(ErrorCodes::InvalidParams(
+            "functions are not supported over Arrow Flight".to_string(),
+        )));
+    }
+
+    // the schema views are answered from the stream schemas, without a search
+    if information_schema::is_query(&in_req.query.sql) {
+        let batches =
+            information_schema::search(org_id, user_id.as_deref(), &in_req.query.sql).await?;
+        return Ok((batches, search::ScanStats::default()));
+    }
+
+    let query: SearchQuery = in_req.query.clone().into();
+    let mut request = config::datafusion::request::Request::new(
+        trace_id.to_string(),
+        org_id.to_string(),
+        stream_type,
+        in_req.timeout,
+        user_id.clone(),
+        Some((query.start_time, query.end_time)),
+        in_req.search_type.map(|v| v.to_string()),
+        in_req.query.histogram_interval,
+        in_req.clear_cache,
+    );
+    request.set_use_cache(in_req.use_cache);
+    let sql = Arc::new(Sql::new_from_req(&request, &query).await?);
+    crate::service::field_usage::record(org_id, stream_type, in_req.search_type, &sql.columns);
+
+    // the task is registered so the search can be listed and cancelled
+    #[cfg(feature = "enterprise")]
+    SEARCH_SERVER
+        .insert(
+            trace_id.to_string(),
+            TaskStatus::new_leader(
+                vec![],
+                true,
+                user_id,
+                Some(org_id.to_string()),
+                Some(stream_type.to_string()),
+                Some(in_req.query.sql.clone()),
+                Some(in_req.query.start_time),
+                Some(in_req.query.end_time),
+                in_req.search_type.map(|v| v.to_string()),
+                in_req.search_event_context.clone(),
+            ),
+        )
+        .await;
+    #[cfg(not(feature = "enterprise"))]
+    drop(user_id);
+
+    let ret =
+        cluster::http::search_inner(request, query, vec![], vec![], true, Some(sql.clone())).await;
+
+    #[cfg(feature = "enterprise")]
+    SEARCH_SERVER.remove(trace_id, false).await;
+
+    let (batches, scan_stats, ..) = ret?;
+    let num_rows = batches.iter().map(|b| b.num_rows()).sum::<usize>();
+    let batches = if is_default_query_limit_exceeded(num_rows, &sql) {
+        truncate_batches(
+            batches,
+            config::get_config().limit.query_default_limit as usize,
+        )
+    } else {
+        batches
+    };
+    Ok((batches, scan_stats))
+}
+
+/// Keeps the first `limit` rows
+fn truncate_batches(batches: Vec<RecordBatch>, mut limit: usize) -> Vec<RecordBatch> {
+    let mut ret = Vec::with_capacity(batches.len());
+    for batch in batches {
+        if limit == 0 {
+            break;
+        }
+        let rows = batch.num_rows().min(limit);
+        limit -= rows;
+        ret.push(batch.slice(0, rows));
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::Int64Array;
+    use arrow_schema::{DataType, Field, Schema};
+
+    use super::*;
+
+    #[test]
+    fn test_truncate_batches() {
+        let schema = Arc::new(Schema::new(vec![Field::new("n", DataType::Int64, false)]));
+        let batch = |values: Vec<i64>| {
+            RecordBatch::try_new(schema.clone(), vec![Arc::new(Int64Array::from(values))]).unwrap()
+        };
+        let batches = vec![batch(vec![1, 2, 3]), batch(vec![4, 5]), batch(vec![6])];
+        let ret = truncate_batches(batches, 4);
+        assert_eq!(ret.len(), 2);
+        assert_eq!(ret[0].num_rows(), 3);
+        assert_eq!(ret[1].num_rows(), 1);
+    }
+}
@@ -0,0 +1,343 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Field level access control of the searches.
+//!
+//! The field access rules of a stream deny roles, standard or custom ones,
+//! access to some of its fields. The searches of a user with a denied role
+//! which reference one of those fields are rejected, and `SELECT *` hides
+//! them: they are left out of the schema of the stream, or masked in the
+//! results. The `_original` column holds the records as they were received,
+//! it is denied to the users denied any field of the stream. The root user
+//! and the searches without a user, like the ones of alerts, see every field.
+//! The APIs reading a stream without searching it, like its field statistics
+//! or its distinct values, check the same rules.
+
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, StringArray};
+use arrow_schema::{DataType, Field, Schema};
+use config::{
+    ORIGINAL_DATA_COL_NAME,
+    meta::stream::{DEFAULT_REDACTION_REPLACEMENT, FieldAccessMode, FieldAccessRule, StreamType},
+};
+use datafusion::{arrow::record_batch::RecordBatch, common::TableReference};
+use hashbrown::{HashMap, HashSet};
+use infra::{
+    errors::{Error, ErrorCodes, Result},
+    schema::{SchemaCache, unwrap_stream_settings},
+};
+
+use crate::{common::utils::auth::is_root_user, service::search::limits};
+
+/// The rules of the streams of the search which deny access to the user, by
+/// stream
+pub async fn denied_rules(
+    org_id: &str,
+    user_id: Option<&str>,
+    schemas: &HashMap<TableReference, Arc<SchemaCache>>,
+) -> HashMap<TableReference, Vec<FieldAccessRule>> {
+    let Some(user_id) = user_id.filter(|user_id| !is_root_user(user_id)) else {
+        return HashMap::new();
+    };
+    let rules = schemas
+        .iter()
+        .filter_map(|(stream, schema)| {
+            let settings = unwrap_stream_settings(schema.schema())?;
+            (!settings.field_access_rules.is_empty())
+                .then(|| (stream.clone(), settings.field_access_rules))
+        })
+        .collect::<Vec<_>>();
+    if rules.is_empty() {
+        return HashMap::new();
+    }
+    // the roles are only looked up for the streams with rules
+    let roles = limits::user_roles(org_id, user_id).await;
+    rules
+        .into_iter()
+        .filter_map(|(stream, rules)| {
+            let denied = filter_denied(rules, &roles);
+            (!denied.is_empty()).then_some((stream, denied))
+        })
+        .collect()
+}
+
+/// The rules of the stream which deny access to the user, for the APIs which
+/// read a stream without searching it
+async fn stream_denied_rules(
+    org_id: &str,
+    user_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> (
+    TableReference,
+    HashMap<TableReference, Vec<FieldAccessRule>>,
+) {
+    let table = TableReference::from(stream_name);
+    let Ok(schema) = infra::schema::get_cache(org_id, stream_name, stream_type).await else {
+        return (table, HashMap::new());
+    };
+    let schemas = HashMap::from([(table.clone(), Arc::new(schema))]);
+    let denied = denied_rules(org_id, Some(user_id), &schemas).await;
+    (table, denied)
+}
+
+/// Rejects the fields of the stream denied to the user
+pub async fn check_fields(
+    org_id: &str,
+    user_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    fields: &[String],
+) -> Result<()> {
+    let (table, denied) = stream_denied_rules(org_id, user_id, stream_type, stream_name).await;
+    check_columns(
+        &denied,
+        &HashMap::from([(table, fields.iter().cloned().collect())]),
+    )
+}
+
+/// The fields of the stream denied to the user, with `_original` when any
+/// field is
+pub async fn denied_fields(
+    org_id: &str,
+    user_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> HashSet<String> {
+    let (_, denied) = stream_denied_rules(org_id, user_id, stream_type, stream_name).await;
+    let mut fields = denied
+        .into_values()
+        .flatten()
+        .map(|rule| rule.field)
+        .collect::<HashSet<_>>();
+    if !fields.is_empty() {
+        fields.insert(ORIGINAL_DATA_COL_NAME.to_string());
+    }
+    fields
+}
+
+fn filter_denied(rules: Vec<FieldAccessRule>, roles: &[String]) -> Vec<FieldAccessRule> {
+    rules
+        .into_iter()
+        .filter(|rule| rule.denies(roles.iter().map(|r| r.as_str())))
+        .collect()
+}
+
+/// Rejects the search if it references a field denied to the user, `columns`
+/// are the fields referenced by the query, by stream
+pub fn check_columns(
+    denied: &HashMap<TableReference, Vec<FieldAccessRule>>,
+    columns: &HashMap<TableReference, HashSet<String>>,
+) -> Result<()> {
+    for (stream, rules) in denied.iter() {
+        let Some(columns) = columns.get(stream) else {
+            continue;
+        };
+        let field = rules
+            .iter()
+            .map(|rule| rule.field.as_str())
+            .chain([ORIGINAL_DATA_COL_NAME])
+            .find(|field| columns.contains(*field));
+        if let Some(field) = field {
+            return Err(Error::ErrorCode(ErrorCodes::SearchFieldAccessDenied(
+                format!(
+                    "access to field {field} of stream {} is denied to your role",
+                    stream.table()
+                ),
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the schema of the stream without the fields stripped from the
+/// user, and the fields to mask
+pub fn hide_fields(schema: &SchemaCache, rules: &[FieldAccessRule]) -> (SchemaCache, Vec<String>) {
+    let mut stripped = HashSet::from([ORIGINAL_DATA_COL_NAME]);
+    let mut masked = Vec::new();
+    for rule in rules {
+        match rule.mode {
+            FieldAccessMode::Strip => {
+                stripped.insert(rule.field.as_str());
+            }
+            FieldAccessMode::Mask => masked.push(rule.field.clone()),
+        }
+    }
+    let fields = schema
+        .schema()
+        .fields()
+        .iter()
+        .filter(|f| !stripped.contains(f.name().as_str()))
+        .cloned()
+        .collect::<Vec<_>>();
+    let schema = Schema::new(fields).with_metadata(schema.schema().metadata().clone());
+    (SchemaCache::new(schema), masked)
+}
+
+/// A key of the fields denied to the user, the results of the searches of the
+/// users denied different fields are cached apart
+pub fn cache_key(denied: &HashMap<TableReference, Vec<FieldAccessRule>>) -> Option<String> {
+    if denied.is_empty() {
+        return None;
+    }
+    let mut keys = denied
+        .iter()
+        .flat_map(|(stream, rules)| {
+            rules
+                .iter()
+                .map(move |rule| format!("{stream}/{}/{:?}", rule.field, rule.mode))
+        })
+        .collect::<Vec<_>>();
+    keys.sort();
+    Some(keys.join(","))
+}
+
+/// Replaces the values of the masked fields in the results, the nulls are
+/// kept
+pub fn mask_batches(batches: Vec<RecordBatch>, fields: &[String]) -> Result<Vec<RecordBatch>> {
+    if fields.is_empty() {
+        return Ok(batches);
+    }
+    batches
+        .into_iter()
+        .map(|batch| mask_batch(batch, fields))
+        .collect()
+}
+
+fn mask_batch(batch: RecordBatch, fields: &[String]) -> Result<RecordBatch> {
+    let schema = batch.schema();
+    if !schema.fields().iter().any(|f| fields.contains(f.name())) {
+        return Ok(batch);
+    }
+    let mut new_fields = Vec::with_capacity(schema.fields().len());
+    let mut columns = Vec::with_capacity(schema.fields().len());
+    for (field, column) in schema.fields().iter().zip(batch.columns()) {
+        if fields.contains(field.name()) {
+            let masked = (0..column.len())
+                .map(|i| column.is_valid(i).then_some(DEFAULT_REDACTION_REPLACEMENT))
+                .collect::<StringArray>();
+            new_fields.push(Arc::new(Field::new(field.name(), DataType::Utf8, true)));
+            columns.push(Arc::new(masked) as ArrayRef);
+        } else {
+            new_fields.push(field.clone());
+            columns.push(column.clone());
+        }
+    }
+    let schema = Schema::new(new_fields).with_metadata(schema.metadata().clone());
+    RecordBatch::try_new(Arc::new(schema), columns).map_err(|e| Error::Message(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::Int64Array;
+
+    use super::*;
+
+    fn rule(field: &str, roles: &[&str], mode: FieldAccessMode) -> FieldAccessRule {
+        FieldAccessRule {
+            field: field.to_string(),
+            denied_roles: roles.iter().map(|r| r.to_string()).collect(),
+            mode,
+        }
+    }
+
+    #[test]
+    fn test_check_columns() {
+        let stream = TableReference::from("app");
+        let rules = filter_denied(
+            vec![
+                rule("ssn", &["analyst"], FieldAccessMode::Strip),
+                rule("email", &["viewer"], FieldAccessMode::Mask),
+            ],
+            &["editor".to_string(), "analyst".to_string()],
+        );
+        assert_eq!(rules.len(), 1);
+        let denied = HashMap::from([(stream.clone(), rules)]);
+
+        let columns = HashMap::from([(stream.clone(), HashSet::from(["email".to_string()]))]);
+        assert!(check_columns(&denied, &columns).is_ok());
+        for field in ["ssn", ORIGINAL_DATA_COL_NAME] {
+            let columns = HashMap::from([(stream.clone(), HashSet::from([field.to_string()]))]);
+            assert!(matches!(
+                check_columns(&denied, &columns),
+                Err(Error::ErrorCode(ErrorCodes::SearchFieldAccessDenied(_)))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_hide_fields() {
+        let schema = SchemaCache::new(Schema::new(vec![
+            Field::new("_timestamp", DataType::Int64, false),
+            Field::new("ssn", DataType::Utf8, true),
+            Field::new("email", DataType::Utf8, true),
+            Field::new(ORIGINAL_DATA_COL_NAME, DataType::Utf8, true),
+        ]));
+        let (schema, masked) = hide_fields(
+            &schema,
+            &[
+                rule("ssn", &["analyst"], FieldAccessMode::Strip),
+                rule("email", &["analyst"], FieldAccessMode::Mask),
+            ],
+        );
+        let names = schema
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["_timestamp", "email"]);
+        assert_eq!(masked, vec!["email".to_string()]);
+    }
+
+    #[test]
+    fn test_cache_key() {
+        assert!(cache_key(&HashMap::new()).is_none());
+        let denied = HashMap::from([(
+            TableReference::from("app"),
+            vec![
+                rule("ssn", &["analyst"], FieldAccessMode::Strip),
+                rule("email", &["analyst"], FieldAccessMode::Mask),
+            ],
+        )]);
+        assert_eq!(cache_key(&denied).unwrap(), "app/email/Mask,app/ssn/Strip");
+    }
+
+    #[test]
+    fn test_mask_batches() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("_timestamp", DataType::Int64, false),
+            Field::new("email", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec![Some("a@b.c"), None])),
+            ],
+        )
+        .unwrap();
+        let batches = mask_batches(vec![batch], &["email".to_string()]).unwrap();
+        let email = batches[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(email.value(0), DEFAULT_REDACTION_REPLACEMENT);
+        assert!(email.is_null(1));
+        assert_eq!(batches[0].num_columns(), 2);
+    }
+}
@@ -598,6 +598,8 @@ async fn get_file_list_by_ids(
     let stream_settings = infra::schema::get_settings(org_id, stream_name, stream_type)
         .await
         .unwrap_or_default();
+    let bloom_filter_fields =
+        infra::schema::get_stream_setting_index_bloom_filter_fields(&Some(stream_settings.clone()));
     let partition_keys = stream_settings.partition_keys;
     let file_list = crate::service::file_list::query_by_ids(
         trace_id,
@@ -625,6 +627,13 @@ async fn get_file_list_by_ids(
             files.push(file);
         }
     }
+    let mut files = super::storage::filter_file_list_by_bloom_filter(
+        trace_id,
+        files,
+        &bloom_filter_fields,
+        equal_items,
+    )
+    .await;
     files.par_sort_unstable_by(|a, b| a.key.cmp(&b.key));
     files.dedup_by(|a, b| a.key == b.key);
     Ok((files, start.elapsed().as_millis() as usize))
@@ -16,17 +16,26 @@
 use std::{collections::HashSet, sync::Arc};
 
 use arrow_schema::Schema;
-use config::meta::{
-    search::ScanStats,
-    stream::{FileKey, StreamType},
+use config::{
+    cluster::LOCAL_NODE,
+    meta::{
+        search::{ScanStats, StorageType},
+        stream::{FileKey, StreamType},
+    },
 };
 use datafusion::{
     datasource::TableProvider, execution::cache::cache_manager::FileStatisticsCache,
     sql::TableReference,
 };
-use infra::errors::Result;
-
-use super::{datafusion::exec::TableBuilder, index::IndexCondition};
+use infra::{cache::file_data, errors::Result};
+
+use super::{
+    datafusion::{
+        exec::TableBuilder,
+        table_provider::provenance::{self, SourceTier},
+    },
+    index::IndexCondition,
+};
 
 pub mod flight;
 pub mod storage;
@@ -51,6 +60,9 @@ pub struct QueryParams {
 /// Create tables from files, automatically splitting them based on time range overlap:
 /// - Files completely within the query time range: no timestamp filter applied
 /// - Files partially overlapping with the query time range: timestamp filter applied
+///
+/// When the query asks for the provenance of the rows, a table is created for every
+/// file instead, see [`provenance`].
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn create_tables_from_files<F>(
     files: Vec<FileKey>,
@@ -103,6 +115,45 @@ where
         }
     };
 
+    if provenance::is_requested(&schema_ref) {
+        let (start_time, end_time) = query.time_range;
+        for (i, file) in files.into_iter().enumerate() {
+            let tier = if session.storage_type == StorageType::Wal {
+                SourceTier::Wal
+            } else if file_data::memory::exist(&file.key).await {
+                SourceTier::MemoryCache
+            } else if file_data::disk::exist(&file.key).await {
+                SourceTier::DiskCache
+            } else {
+                SourceTier::ObjectStorage
+            };
+            let timestamp_filter = (file.meta.min_ts < start_time || file.meta.max_ts >= end_time)
+                .then_some(query.time_range);
+            let key = file.key.clone();
+            let mut session = session.clone();
+            session.id = format!("{}-{i}", session.id);
+            let table = TableBuilder::new()
+                .sorted_by_time(sorted_by_time)
+                .file_stat_cache(file_stat_cache.clone())
+                .index_condition(index_condition.clone())
+                .fst_fields(fst_fields.clone());
+            let table = match timestamp_filter {
+                Some(time_range) => table.timestamp_filter(time_range),
+                None => table,
+            }
+            .build(session, vec![file], schema_ref.clone())
+            .await
+            .inspect_err(|_| on_error())?;
+            tables.push(provenance::with_provenance(
+                table,
+                &LOCAL_NODE.name,
+                Some(&key),
+                tier,
+            )?);
+        }
+        return Ok(tables);
+    }
+
     // If the stream is enrichment tables, create a table for all files
     // this is used for add enrich before the stream name(usually for join with the stream)
     if let Some(schema) = query.stream.schema()
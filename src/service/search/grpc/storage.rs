@@ -50,8 +50,9 @@ use tokio_stream::StreamExt as _;
 use tracing::Instrument;
 
 use crate::service::{
-    file_list,
+    compact, file_list,
     search::{
+        generate_filter_from_equal_items,
         grpc::{
             tantivy_result::{TantivyMultiResult, TantivyMultiResultBuilder, TantivyResult},
             tantivy_result_cache::{self, CacheEntry},
@@ -94,6 +95,7 @@ pub async fn search(
     if files.is_empty() {
         return Ok((vec![], ScanStats::default(), HashSet::new()));
     }
+    compact::tiering::check_cold_files(stream_name, &files)?;
     let original_files_len = files.len();
     log::info!(
         "[trace_id {trace_id}] search->storage: stream {org_id}/{stream_type}/{stream_name}, load file_list num {}",
@@ -717,6 +719,79 @@ pub async fn get_tantivy_directory(
     Ok(PuffinDirReader::from_path(file_account, source).await?)
 }
 
+/// Drops the files whose index has a bloom filter of a field of `equal_items`
+/// without any of the values asked for the field, before they are downloaded
+pub async fn filter_file_list_by_bloom_filter(
+    trace_id: &str,
+    files: Vec<FileKey>,
+    bloom_filter_fields: &[String],
+    equal_items: &[(String, String)],
+) -> Vec<FileKey> {
+    let filters = generate_filter_from_equal_items(equal_items)
+        .into_iter()
+        .filter(|(field, values)| {
+            // the escaped quotes are left in the values, they can't be matched
+            bloom_filter_fields.contains(field) && !values.iter().any(|v| v.contains('\''))
+        })
+        .collect_vec();
+    if filters.is_empty() || !files.iter().any(|f| f.meta.index_size > 0) {
+        return files;
+    }
+
+    let start = std::time::Instant::now();
+    let original_files_len = files.len();
+    let filters = &filters;
+    let tasks = files.into_iter().map(|file| async move {
+        if file.meta.index_size == 0 {
+            return Some(file);
+        }
+        match match_bloom_filters(trace_id, &file, filters).await {
+            Ok(true) => Some(file),
+            Ok(false) => None,
+            Err(e) => {
+                log::warn!(
+                    "[trace_id {trace_id}] search->bloom_filter: read bloom filter of {} error: {e}",
+                    file.key
+                );
+                Some(file)
+            }
+        }
+    });
+    let files: Vec<Option<FileKey>> = StreamExt::collect(
+        stream::iter(tasks).buffer_unordered(get_config().limit.query_index_thread_num),
+    )
+    .await;
+    let files = files.into_iter().flatten().collect_vec();
+    log::info!(
+        "[trace_id {trace_id}] search->bloom_filter: reduced file_list num from {original_files_len} to {} in {} ms",
+        files.len(),
+        start.elapsed().as_millis()
+    );
+    files
+}
+
+/// False when a bloom filter of the index of the file has none of the values
+/// of its field
+async fn match_bloom_filters(
+    trace_id: &str,
+    file: &FileKey,
+    filters: &[(String, Vec<String>)],
+) -> anyhow::Result<bool> {
+    let Some(ttv_file) = convert_parquet_file_name_to_tantivy_file(&file.key) else {
+        return Ok(true);
+    };
+    let dir =
+        get_tantivy_directory(trace_id, &file.account, &ttv_file, file.meta.index_size).await?;
+    for (field, values) in filters {
+        if let Some(filter) = dir.bloom_filter(field).await?
+            && !values.iter().any(|v| filter.contains(v))
+        {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
 async fn search_tantivy_index(
     trace_id: &str,
     time_range: (i64, i64),
@@ -54,7 +54,10 @@ use crate::{
     service::{
         file_list,
         search::{
-            datafusion::table_provider::memtable::NewMemTable,
+            datafusion::table_provider::{
+                memtable::NewMemTable,
+                provenance::{self, SourceTier},
+            },
             generate_filter_from_equal_items, generate_search_schema_diff,
             index::IndexCondition,
             inspector::{SearchInspectorFieldsBuilder, search_inspector_fields},
@@ -438,7 +441,7 @@ pub async fn search_memtable(
 
         tokio::task::coop::consume_budget().await;
 
-        let table = match NewMemTable::try_new(
+        let table: Arc<dyn datafusion::datasource::TableProvider> = match NewMemTable::try_new(
             record_batches[0].schema().clone(),
             vec![record_batches],
             diff_fields,
@@ -456,7 +459,12 @@ pub async fn search_memtable(
                 return Err(e.into());
             }
         };
-        tables.push(table as _);
+        let table = if provenance::is_requested(&latest_schema) {
+            provenance::with_provenance(table, &LOCAL_NODE.name, None, SourceTier::Memtable)?
+        } else {
+            table
+        };
+        tables.push(table);
     }
 
     log::info!(
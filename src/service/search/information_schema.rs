@@ -0,0 +1,306 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! The `information_schema.tables` and `information_schema.columns` views over
+//! the streams of the org, so generic SQL clients can introspect the schemas.
+
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, Int64Array, RecordBatch, StringArray};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use config::meta::{sql::resolve_stream_names_with_type, stream::StreamType};
+use datafusion::{
+    catalog::{CatalogProvider, MemorySchemaProvider, SchemaProvider},
+    common::TableReference,
+    datasource::MemTable,
+    prelude::SessionContext,
+};
+use hashbrown::HashMap;
+use infra::{
+    errors::{Error, ErrorCodes, Result},
+    schema::SchemaCache,
+};
+
+use crate::{common::meta::stream::StreamSchema, service::search::field_access};
+
+pub const INFORMATION_SCHEMA: &str = "information_schema";
+
+/// The stream types listed in the views
+const STREAM_TYPES: [StreamType; 3] = [StreamType::Logs, StreamType::Metrics, StreamType::Traces];
+
+/// Whether the query only references the information_schema views
+pub fn is_query(sql: &str) -> bool {
+    resolve_stream_names_with_type(sql).is_ok_and(|tables| {
+        !tables.is_empty()
+            && tables
+                .iter()
+                .all(|table| table.schema() == Some(INFORMATION_SCHEMA))
+    })
+}
+
+/// Runs the query over the information_schema views of the streams of the org
+/// the user can read
+pub async fn search(org_id: &str, user_id: Option<&str>, sql: &str) -> Result<Vec<RecordBatch>> {
+    let streams = list_streams(org_id, user_id).await?;
+    let ctx = SessionContext::new();
+    let views = Arc::new(MemorySchemaProvider::new());
+    let tables = [
+        ("tables", tables_batch(org_id, &streams)?),
+        ("columns", columns_batch(org_id, &streams)?),
+    ];
+    for (name, batch) in tables {
+        let table = MemTable::try_new(batch.schema(), vec![vec![batch]]).map_err(sql_error)?;
+        views
+            .register_table(name.to_string(), Arc::new(table))
+            .map_err(sql_error)?;
+    }
+    let catalog_name = ctx
+        .state()
+        .config()
+        .options()
+        .catalog
+        .default_catalog
+        .clone();
+    let catalog = ctx.catalog(&catalog_name).ok_or_else(|| {
+        Error::ErrorCode(ErrorCodes::ServerInternalError(format!(
+            "catalog {catalog_name} not found"
+        )))
+    })?;
+    catalog
+        .register_schema(INFORMATION_SCHEMA, views)
+        .map_err(sql_error)?;
+    async { ctx.sql(sql).await?.collect().await }
+        .await
+        .map_err(sql_error)
+}
+
+fn sql_error(e: impl ToString) -> Error {
+    Error::ErrorCode(ErrorCodes::SearchSQLNotValid(e.to_string()))
+}
+
+/// The streams of the org permitted to the user, without the fields denied to
+/// the user
+async fn list_streams(org_id: &str, user_id: Option<&str>) -> Result<Vec<StreamSchema>> {
+    let mut streams = Vec::new();
+    for stream_type in STREAM_TYPES {
+        #[allow(unused_mut)]
+        let mut list = crate::service::db::schema::list(org_id, Some(stream_type), true)
+            .await
+            .map_err(|e| Error::Message(e.to_string()))?;
+        #[cfg(feature = "enterprise")]
+        if let Some(user_id) = user_id {
+            use o2_openfga::meta::mapping::OFGA_MODELS;
+
+            let s_type = stream_type.as_str();
+            let permitted = crate::handler::http::auth::validator::list_objects_for_user(
+                org_id,
+                user_id,
+                "GET",
+                OFGA_MODELS.get(s_type).map_or(s_type, |model| model.key),
+            )
+            .await
+            .map_err(|e| Error::Message(e.to_string()))?;
+            if let Some(permitted) = permitted
+                && !permitted.contains(&format!("{s_type}:_all_{org_id}"))
+            {
+                list.retain(|s| permitted.contains(&format!("{s_type}:{}", s.stream_name)));
+            }
+        }
+        streams.extend(list);
+    }
+
+    let schemas = streams
+        .iter()
+        .map(|s| {
+            (
+                TableReference::partial(s.stream_type.as_str(), s.stream_name.as_str()),
+                Arc::new(SchemaCache::new(s.schema.clone())),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+    let denied = field_access::denied_rules(org_id, user_id, &schemas).await;
+    if !denied.is_empty() {
+        for stream in streams.iter_mut() {
+            let table =
+                TableReference::partial(stream.stream_type.as_str(), stream.stream_name.as_str());
+            if let (Some(rules), Some(schema)) = (denied.get(&table), schemas.get(&table)) {
+                let (schema, _) = field_access::hide_fields(schema, rules);
+                stream.schema = schema.schema().as_ref().clone();
+            }
+        }
+    }
+    Ok(streams)
+}
+
+fn tables_batch(org_id: &str, streams: &[StreamSchema]) -> Result<RecordBatch> {
+    let schema: SchemaRef = Arc::new(Schema::new(vec![
+        Field::new("table_catalog", DataType::Utf8, false),
+        Field::new("table_schema", DataType::Utf8, false),
+        Field::new("table_name", DataType::Utf8, false),
+        Field::new("table_type", DataType::Utf8, false),
+    ]));
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from(vec![org_id; streams.len()])),
+        Arc::new(StringArray::from_iter_values(
+            streams.iter().map(|s| s.stream_type.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            streams.iter().map(|s| s.stream_name.as_str()),
+        )),
+        Arc::new(StringArray::from(vec!["BASE TABLE"; streams.len()])),
+    ];
+    Ok(RecordBatch::try_new(schema, columns)?)
+}
+
+fn columns_batch(org_id: &str, streams: &[StreamSchema]) -> Result<RecordBatch> {
+    let schema: SchemaRef = Arc::new(Schema::new(vec![
+        Field::new("table_catalog", DataType::Utf8, false),
+        Field::new("table_schema", DataType::Utf8, false),
+        Field::new("table_name", DataType::Utf8, false),
+        Field::new("column_name", DataType::Utf8, false),
+        Field::new("ordinal_position", DataType::Int64, false),
+        Field::new("column_default", DataType::Utf8, true),
+        Field::new("is_nullable", DataType::Utf8, false),
+        Field::new("data_type", DataType::Utf8, false),
+    ]));
+    let fields = streams
+        .iter()
+        .flat_map(|s| {
+            s.schema
+                .fields()
+                .iter()
+                .enumerate()
+                .map(move |(i, f)| (s, i as i64 + 1, f))
+        })
+        .collect::<Vec<_>>();
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from(vec![org_id; fields.len()])),
+        Arc::new(StringArray::from_iter_values(
+            fields.iter().map(|(s, ..)| s.stream_type.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            fields.iter().map(|(s, ..)| s.stream_name.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            fields.iter().map(|(.., f)| f.name().as_str()),
+        )),
+        Arc::new(Int64Array::from_iter_values(
+            fields.iter().map(|(_, i, _)| *i),
+        )),
+        Arc::new(StringArray::new_null(fields.len())),
+        Arc::new(StringArray::from_iter_values(
+            fields
+                .iter()
+                .map(|(.., f)| if f.is_nullable() { "YES" } else { "NO" }),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            fields.iter().map(|(.., f)| sql_type_name(f.data_type())),
+        )),
+    ];
+    Ok(RecordBatch::try_new(schema, columns)?)
+}
+
+/// The SQL name of the type, as reported by PostgreSQL
+fn sql_type_name(data_type: &DataType) -> &'static str {
+    match data_type {
+        DataType::Boolean => "boolean",
+        DataType::Int8 | DataType::Int16 | DataType::UInt8 => "smallint",
+        DataType::Int32 | DataType::UInt16 => "integer",
+        DataType::Int64 | DataType::UInt32 | DataType::UInt64 => "bigint",
+        DataType::Float16 | DataType::Float32 => "real",
+        DataType::Float64 => "double precision",
+        DataType::Timestamp(_, None) => "timestamp without time zone",
+        DataType::Timestamp(_, Some(_)) => "timestamp with time zone",
+        DataType::Date32 | DataType::Date64 => "date",
+        DataType::Binary | DataType::LargeBinary | DataType::BinaryView => "bytea",
+        _ => "text",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_schema::TimeUnit;
+
+    use super::*;
+
+    fn streams() -> Vec<StreamSchema> {
+        vec![
+            StreamSchema {
+                stream_name: "default".to_string(),
+                stream_type: StreamType::Logs,
+                schema: Schema::new(vec![
+                    Field::new("_timestamp", DataType::Int64, false),
+                    Field::new("log", DataType::Utf8, true),
+                ]),
+            },
+            StreamSchema {
+                stream_name: "cpu".to_string(),
+                stream_type: StreamType::Metrics,
+                schema: Schema::new(vec![Field::new("value", DataType::Float64, true)]),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_is_query() {
+        assert!(is_query("SELECT * FROM information_schema.tables"));
+        assert!(is_query(
+            "SELECT t.table_name FROM information_schema.tables t JOIN information_schema.columns c ON t.table_name = c.table_name"
+        ));
+        assert!(!is_query("SELECT * FROM tables"));
+        assert!(!is_query("SELECT * FROM logs.information_schema"));
+        assert!(!is_query(
+            "SELECT * FROM information_schema.tables t JOIN default d ON t.table_name = d.log"
+        ));
+        assert!(!is_query("SELECT 1"));
+    }
+
+    #[test]
+    fn test_sql_type_name() {
+        assert_eq!(sql_type_name(&DataType::Int64), "bigint");
+        assert_eq!(sql_type_name(&DataType::Float64), "double precision");
+        assert_eq!(
+            sql_type_name(&DataType::Timestamp(TimeUnit::Microsecond, None)),
+            "timestamp without time zone"
+        );
+        assert_eq!(sql_type_name(&DataType::Utf8View), "text");
+    }
+
+    #[test]
+    fn test_views_batches() {
+        let streams = streams();
+        let tables = tables_batch("org", &streams).unwrap();
+        assert_eq!(tables.num_rows(), 2);
+        let columns = columns_batch("org", &streams).unwrap();
+        assert_eq!(columns.num_rows(), 3);
+        let names = columns
+            .column(3)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(names.value(2), "value");
+        let positions = columns
+            .column(4)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(positions.values(), &[1, 2, 1]);
+        let nullable = columns
+            .column(6)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(nullable.value(0), "NO");
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Limits of the searches of the users, set by role in the org settings.
+//!
+//! A search is checked before it is distributed to the queriers: the rows it
+//! asks for against the rows the role can read, the searches the user already
+//! runs on this querier against the searches it can run at once, and the data
+//! of the files it would scan against the MB it can scan. Searches without a
+//! limit on their rows are cut at the limit of the role. Background searches,
+//! like alerts and reports, and the root user have no limits.
+
+use config::meta::search::QueryLimits;
+use hashbrown::HashMap;
+use infra::errors::{Error, ErrorCodes, Result};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+
+use crate::{
+    common::utils::auth::is_root_user,
+    service::{db, users},
+};
+
+/// Searches running on this node, by org and user
+static RUNNING: Lazy<RwLock<HashMap<(String, String), u64>>> = Lazy::new(Default::default);
+
+/// The limits of the searches of the user in the org
+pub async fn get_limits(org_id: &str, user_id: Option<&str>, is_background: bool) -> QueryLimits {
+    let Some(user_id) = user_id.filter(|_| !is_background) else {
+        return QueryLimits::default();
+    };
+    if is_root_user(user_id) {
+        return QueryLimits::default();
+    }
+    let limits = db::organization::get_search_limits(org_id).await;
+    if limits.is_empty() {
+        return QueryLimits::default();
+    }
+    let roles = user_roles(org_id, user_id).await;
+    limits.for_roles(roles.iter().map(|r| r.as_str()))
+}
+
+/// The standard role of the user in the org and its custom roles
+pub(crate) async fn user_roles(org_id: &str, user_id: &str) -> Vec<String> {
+    #[allow(unused_mut)]
+    let mut roles = users::get_user(Some(org_id), user_id)
+        .await
+        .map(|u| vec![u.role.to_string()])
+        .unwrap_or_default();
+    #[cfg(feature = "enterprise")]
+    roles.extend(users::get_user_roles(user_id, Some(org_id)).await);
+    roles
+}
+
+/// Rejects a search asking for more rows than the limit, `limit` is the
+/// limit of the query, 0 or less when it has none
+pub fn check_rows(limits: &QueryLimits, limit: i64) -> Result<()> {
+    if limits.max_result_rows > 0 && limit > 0 && limit as u64 > limits.max_result_rows {
+        return Err(Error::ErrorCode(ErrorCodes::SearchLimitExceeded(format!(
+            "the search asks for {limit} rows, your role can read up to {} rows per search",
+            limits.max_result_rows
+        ))));
+    }
+    Ok(())
+}
+
+/// Rejects a search which would scan more than the limit, `original_size`
+/// is the size of the files to scan before compression, in bytes
+pub fn check_scan(max_scan_mb: u64, original_size: i64) -> Result<()> {
+    let scan_mb = original_size.max(0) as u64 / 1024 / 1024;
+    if max_scan_mb > 0 && scan_mb > max_scan_mb {
+        return Err(Error::ErrorCode(ErrorCodes::SearchLimitExceeded(format!(
+            "the search would scan {scan_mb} MB, your role can scan up to {max_scan_mb} MB per \
+             search, narrow the time range or the streams"
+        ))));
+    }
+    Ok(())
+}
+
+/// Counts a search of the user as running until it is dropped
+pub struct LimitGuard {
+    key: Option<(String, String)>,
+}
+
+impl Drop for LimitGuard {
+    fn drop(&mut self) {
+        let Some(key) = self.key.take() else {
+            return;
+        };
+        let mut running = RUNNING.write();
+        if let Some(count) = running.get_mut(&key) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                running.remove(&key);
+            }
+        }
+    }
+}
+
+/// Admits a search of the user, rejected when the user already runs as many
+/// searches as the limit
+pub fn acquire(org_id: &str, user_id: Option<&str>, limits: &QueryLimits) -> Result<LimitGuard> {
+    let Some(user_id) = user_id.filter(|_| limits.max_concurrent > 0) else {
+        return Ok(LimitGuard { key: None });
+    };
+    let key = (org_id.to_string(), user_id.to_string());
+    let mut running = RUNNING.write();
+    let count = running.entry(key.clone()).or_default();
+    if *count >= limits.max_concurrent {
+        return Err(Error::ErrorCode(ErrorCodes::SearchQuotaExceeded(format!(
+            "you already run {count} searches, your role can run up to {} searches at once",
+            limits.max_concurrent
+        ))));
+    }
+    *count += 1;
+    Ok(LimitGuard { key: Some(key) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_rows_and_scan() {
+        let limits = QueryLimits {
+            max_scan_mb: 100,
+            max_result_rows: 1000,
+            max_concurrent: 0,
+        };
+        assert!(check_rows(&limits, 1000).is_ok());
+        assert!(check_rows(&limits, -1).is_ok());
+        assert!(check_rows(&limits, 1001).is_err());
+        assert!(check_rows(&QueryLimits::default(), 1_000_000).is_ok());
+
+        assert!(check_scan(100, 100 * 1024 * 1024).is_ok());
+        assert!(check_scan(100, 101 * 1024 * 1024).is_err());
+        assert!(check_scan(0, i64::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_acquire() {
+        let limits = QueryLimits {
+            max_concurrent: 2,
+            ..Default::default()
+        };
+        let org_id = "test_search_limits_acquire_org";
+        let user = Some("user@example.com");
+        let first = acquire(org_id, user, &limits).unwrap();
+        let _second = acquire(org_id, user, &limits).unwrap();
+        assert!(matches!(
+            acquire(org_id, user, &limits),
+            Err(Error::ErrorCode(ErrorCodes::SearchQuotaExceeded(_)))
+        ));
+        // another user has its own count
+        assert!(acquire(org_id, Some("other@example.com"), &limits).is_ok());
+        drop(first);
+        assert!(acquire(org_id, user, &limits).is_ok());
+        assert!(acquire(org_id, None, &limits).unwrap().key.is_none());
+    }
+}
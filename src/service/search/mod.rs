@@ -100,16 +100,30 @@ use crate::{
     },
 };
 
+pub(crate) mod anomalies;
+pub(crate) mod batch_query;
 pub(crate) mod cache;
 #[cfg(feature = "enterprise")]
 pub(crate) mod cardinality;
 pub(crate) mod cluster;
+pub(crate) mod cross_org;
 pub(crate) mod datafusion;
+pub(crate) mod es;
+pub(crate) mod external_flight;
+pub(crate) mod field_access;
 pub(crate) mod grpc;
 pub(crate) mod grpc_search;
 pub(crate) mod index;
+pub(crate) mod information_schema;
 pub(crate) mod inspector;
+pub(crate) mod limits;
+pub(crate) mod ordered_export;
 pub(crate) mod partition;
+pub(crate) mod patterns;
+pub(crate) mod pivot;
+pub(crate) mod query_diff;
+pub(crate) mod quota;
+pub(crate) mod shadow;
 pub(crate) mod sql;
 pub(crate) mod streaming;
 #[cfg(feature = "enterprise")]
@@ -149,6 +163,9 @@ pub async fn search(
         trace_id.to_string()
     };
 
+    crate::service::org_lifecycle::check_search(org_id)
+        .map_err(|e| Error::ErrorCode(ErrorCodes::InvalidParams(e)))?;
+
     #[cfg(not(feature = "enterprise"))]
     let req_regions = vec![];
     #[cfg(not(feature = "enterprise"))]
@@ -179,6 +196,17 @@ pub async fn search(
     }
     request.set_use_cache(in_req.use_cache);
     let meta = Sql::new_from_req(&request, &query).await?;
+    crate::service::field_usage::record(org_id, stream_type, in_req.search_type, &meta.columns);
+
+    // counts the search against the quota of the org until it returns
+    let is_background = in_req.search_type.is_some_and(|t| t.is_background());
+    let _quota_guard = quota::acquire(org_id, is_background).await?;
+
+    // the limits of the role of the user, the scan is checked on the file list
+    let limits = limits::get_limits(org_id, user_id.as_deref(), is_background).await;
+    limits::check_rows(&limits, meta.limit)?;
+    let _limit_guard = limits::acquire(org_id, user_id.as_deref(), &limits)?;
+    request.set_max_scan_mb(limits.max_scan_mb);
 
     #[cfg(feature = "enterprise")]
     {
@@ -258,10 +286,32 @@ pub async fn search(
 
     match res {
         Ok(mut res) => {
+            quota::consume(org_id, res.scan_size as u64);
+            if limits.max_result_rows > 0 && res.hits.len() as u64 > limits.max_result_rows {
+                res.hits.truncate(limits.max_result_rows as usize);
+                res.size = res.hits.len() as i64;
+                res.set_partial(
+                    true,
+                    format!(
+                        "the result is cut at {} rows, the limit of your role",
+                        limits.max_result_rows
+                    ),
+                );
+            }
             if in_req.query.streaming_output && meta.order_by.is_empty() {
                 res = crate::service::search::streaming::order_search_results(res, None);
             }
             res.set_work_group(_work_group.clone());
+            if shadow::sampled(in_req, &res).await {
+                shadow::spawn(
+                    &trace_id,
+                    org_id,
+                    stream_type,
+                    user_id.clone(),
+                    in_req,
+                    &res,
+                );
+            }
             let time = start.elapsed().as_secs_f64();
             let (report_usage, search_type, search_event_context) = match in_req.search_type {
                 Some(search_type) => {
@@ -320,6 +370,7 @@ pub async fn search(
                     work_group: _work_group,
                     result_cache_ratio: Some(res.result_cache_ratio),
                     peak_memory_usage: res.peak_memory_usage,
+                    took_detail: Some(res.took_detail.clone()),
                     ..Default::default()
                 };
                 let num_fn = if req_query.query_fn.is_empty() { 0 } else { 1 };
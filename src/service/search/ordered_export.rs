@@ -0,0 +1,369 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Export of the full result of a query in strict `_timestamp` order, to
+//! replay it into other systems.
+//!
+//! The time range is split in the partitions of the search, which are
+//! searched one after the other in the order of the export. Each partition is
+//! searched without limit over the whole cluster: the followers sort their
+//! files and the leader merges their sorted streams, so the rows of a
+//! partition come out in order, and as the partitions don't overlap, so does
+//! the whole export. Only one partition is held in memory at a time.
+
+use arrow::array::RecordBatch;
+use chrono::{TimeZone, Utc};
+use config::{
+    QUERY_WITH_NO_LIMIT, TIMESTAMP_COL_NAME,
+    meta::{
+        exports::ExportDestination,
+        search::{self, SearchPartitionRequest},
+        sql::OrderBy,
+        stream::StreamType,
+    },
+    utils::sql::is_aggregate_query,
+};
+use futures::{StreamExt, stream::BoxStream};
+use infra::errors::{Error, ErrorCodes, Result};
+use object_store::{WriteMultipart, path::Path};
+use proto::cluster_rpc::SearchQuery;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{external_flight, sql::Sql};
+use crate::service::scheduled_exports;
+
+#[derive(Clone, Debug, Deserialize, ToSchema)]
+pub struct OrderedExportRequest {
+    pub sql: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    #[serde(default)]
+    pub target: ExportTarget,
+    /// Path of the object under the prefix of the destination, `exports` by
+    /// default
+    #[serde(default)]
+    pub path: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportTarget {
+    /// The rows are streamed in the response, as newline delimited JSON
+    #[default]
+    Client,
+    /// The rows are written as newline delimited JSON to the export
+    /// destination of the organization
+    Destination,
+}
+
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct OrderedExportResponse {
+    pub took: usize,
+    pub rows: usize,
+    pub partitions: usize,
+    pub object: String,
+}
+
+/// A validated export, with the time ranges to search in order
+#[derive(Debug)]
+pub struct OrderedExport {
+    trace_id: String,
+    org_id: String,
+    stream_type: StreamType,
+    user_id: Option<String>,
+    sql: String,
+    partitions: Vec<[i64; 2]>,
+}
+
+impl OrderedExport {
+    pub async fn new(
+        trace_id: &str,
+        org_id: &str,
+        stream_type: StreamType,
+        user_id: Option<String>,
+        req: &OrderedExportRequest,
+    ) -> Result<Self> {
+        if req.start_time >= req.end_time {
+            return Err(invalid_params("start_time must be less than end_time"));
+        }
+        if is_aggregate_query(&req.sql).unwrap_or(true) {
+            return Err(invalid_params("aggregations can not be exported in order"));
+        }
+        let query = SearchQuery {
+            sql: req.sql.clone(),
+            start_time: req.start_time,
+            end_time: req.end_time,
+            ..Default::default()
+        };
+        let sql = Sql::new(&query, org_id, stream_type, None).await?;
+        let order = check_sql(&sql)?;
+
+        let res = crate::service::search::search_partition(
+            trace_id,
+            org_id,
+            user_id.as_deref(),
+            stream_type,
+            &SearchPartitionRequest {
+                sql: req.sql.clone(),
+                start_time: req.start_time,
+                end_time: req.end_time,
+                ..Default::default()
+            },
+            false,
+            false,
+            false,
+            false,
+        )
+        .await?;
+        let partitions = if res.partitions.is_empty() {
+            vec![[req.start_time, req.end_time]]
+        } else {
+            order_partitions(res.partitions, order)
+        };
+
+        Ok(Self {
+            trace_id: trace_id.to_string(),
+            org_id: org_id.to_string(),
+            stream_type,
+            user_id,
+            sql: req.sql.clone(),
+            partitions,
+        })
+    }
+
+    pub fn partitions(&self) -> usize {
+        self.partitions.len()
+    }
+
+    /// Searches the partitions one after the other, yielding their rows in
+    /// order. The stream ends after the first error.
+    pub fn into_stream(self) -> BoxStream<'static, Result<RecordBatch>> {
+        let Self {
+            trace_id,
+            org_id,
+            stream_type,
+            user_id,
+            sql,
+            partitions,
+        } = self;
+        let partition_num = partitions.len();
+        async_stream::stream! {
+            for (i, [start_time, end_time]) in partitions.into_iter().enumerate() {
+                let trace_id = format!("{trace_id}-{i}");
+                log::info!(
+                    "[trace_id {trace_id}] ordered export: partition {}/{partition_num}: {start_time} - {end_time}",
+                    i + 1
+                );
+                let req = search::Request {
+                    query: search::Query {
+                        sql: sql.clone(),
+                        start_time,
+                        end_time,
+                        size: QUERY_WITH_NO_LIMIT,
+                        ..Default::default()
+                    },
+                    search_type: Some(search::SearchEventType::Other),
+                    use_cache: false,
+                    ..Default::default()
+                };
+                match external_flight::search(&trace_id, &org_id, stream_type, user_id.clone(), &req)
+                    .await
+                {
+                    Ok((batches, _)) => {
+                        for batch in batches {
+                            yield Ok(batch);
+                        }
+                    }
+                    Err(e) => {
+                        yield Err(e);
+                        return;
+                    }
+                }
+            }
+        }
+        .boxed()
+    }
+}
+
+/// Writes the export to the export destination of the organization, with a
+/// multipart upload so the object never has to fit in memory
+pub async fn write_to_destination(
+    export: OrderedExport,
+    destination: &ExportDestination,
+    path: &str,
+) -> Result<OrderedExportResponse> {
+    let start = std::time::Instant::now();
+    let store =
+        scheduled_exports::build_store(destination).map_err(|e| Error::Message(e.to_string()))?;
+    let object = object_key(destination, path, &export.trace_id, Utc::now().timestamp());
+    let partitions = export.partitions();
+    let upload = store
+        .put_multipart(&Path::from(object.as_str()))
+        .await
+        .map_err(|e| Error::Message(e.to_string()))?;
+    let mut writer = WriteMultipart::new(upload);
+
+    let mut rows = 0;
+    let mut batches = export.into_stream();
+    while let Some(batch) = batches.next().await {
+        let data = match batch.and_then(|batch| {
+            rows += batch.num_rows();
+            encode_ndjson(&batch)
+        }) {
+            Ok(v) => v,
+            Err(e) => {
+                if let Err(e) = writer.abort().await {
+                    log::error!("ordered export: abort upload of {object} error: {e}");
+                }
+                return Err(e);
+            }
+        };
+        writer.write(&data);
+    }
+    writer
+        .finish()
+        .await
+        .map_err(|e| Error::Message(e.to_string()))?;
+
+    Ok(OrderedExportResponse {
+        took: start.elapsed().as_millis() as usize,
+        rows,
+        partitions,
+        object,
+    })
+}
+
+/// One JSON object per line
+pub fn encode_ndjson(batch: &RecordBatch) -> Result<Vec<u8>> {
+    let mut writer = arrow_json::LineDelimitedWriter::new(Vec::new());
+    writer
+        .write(batch)
+        .and_then(|_| writer.finish())
+        .map_err(|e| Error::Message(e.to_string()))?;
+    Ok(writer.into_inner())
+}
+
+/// Returns the order of the export, the one of the query
+fn check_sql(sql: &Sql) -> Result<OrderBy> {
+    if sql.stream_names.len() != 1 {
+        return Err(invalid_params("only one stream can be exported at a time"));
+    }
+    if sql.limit > 0 || sql.offset > 0 {
+        return Err(invalid_params(
+            "LIMIT and OFFSET are not supported, the whole result is exported",
+        ));
+    }
+    match sql.order_by.as_slice() {
+        [(field, order)] if field == TIMESTAMP_COL_NAME => Ok(*order),
+        _ => Err(invalid_params(&format!(
+            "the export must be ordered by {TIMESTAMP_COL_NAME} only"
+        ))),
+    }
+}
+
+pub(crate) fn order_partitions(mut partitions: Vec<[i64; 2]>, order: OrderBy) -> Vec<[i64; 2]> {
+    partitions.sort_by_key(|p| p[0]);
+    if order == OrderBy::Desc {
+        partitions.reverse();
+    }
+    partitions
+}
+
+/// `prefix/path/YYYY/MM/DD/<trace_id>.json`
+fn object_key(destination: &ExportDestination, path: &str, trace_id: &str, now: i64) -> String {
+    let date = Utc
+        .timestamp_opt(now, 0)
+        .single()
+        .unwrap_or_default()
+        .format("%Y/%m/%d")
+        .to_string();
+    let path = match path.trim_matches('/') {
+        "" => "exports",
+        v => v,
+    };
+    let key = [destination.prefix.trim_matches('/'), path, &date]
+        .into_iter()
+        .filter(|v| !v.is_empty())
+        .collect::<Vec<_>>()
+        .join("/");
+    format!("{key}/{trace_id}.json")
+}
+
+fn invalid_params(msg: &str) -> Error {
+    Error::ErrorCode(ErrorCodes::InvalidParams(msg.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{Int64Array, StringArray};
+    use arrow_schema::{DataType, Field, Schema};
+
+    use super::*;
+
+    #[test]
+    fn test_order_partitions() {
+        let partitions = vec![[20, 30], [0, 10], [10, 20]];
+        assert_eq!(
+            order_partitions(partitions.clone(), OrderBy::Asc),
+            vec![[0, 10], [10, 20], [20, 30]]
+        );
+        assert_eq!(
+            order_partitions(partitions, OrderBy::Desc),
+            vec![[20, 30], [10, 20], [0, 10]]
+        );
+    }
+
+    #[test]
+    fn test_object_key() {
+        let destination = ExportDestination {
+            prefix: "/openobserve/".to_string(),
+            ..Default::default()
+        };
+        // 2026-01-02T03:04:05Z
+        let now = 1_767_323_045;
+        assert_eq!(
+            object_key(&destination, "", "abc", now),
+            "openobserve/exports/2026/01/02/abc.json"
+        );
+        assert_eq!(
+            object_key(&ExportDestination::default(), "/replay/", "abc", now),
+            "replay/2026/01/02/abc.json"
+        );
+    }
+
+    #[test]
+    fn test_encode_ndjson() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(TIMESTAMP_COL_NAME, DataType::Int64, false),
+            Field::new("message", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec![Some("a"), None])),
+            ],
+        )
+        .unwrap();
+        let data = String::from_utf8(encode_ndjson(&batch).unwrap()).unwrap();
+        assert_eq!(
+            data,
+            "{\"_timestamp\":1,\"message\":\"a\"}\n{\"_timestamp\":2}\n"
+        );
+    }
+}
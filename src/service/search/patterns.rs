@@ -0,0 +1,348 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Mines the message templates of a logs stream.
+//!
+//! The time range is cut in partitions which are searched concurrently, every
+//! search being spread over the queriers like any other one. Each partition
+//! feeds its own miner and the miners are merged at the end.
+
+use std::ops::ControlFlow;
+
+use arrow_schema::DataType;
+use config::{
+    get_config,
+    meta::{
+        patterns::{
+            DEFAULT_SIMILARITY, DEFAULT_TOP_K, LogPattern, PatternRequest, PatternResponse,
+        },
+        search,
+        stream::StreamType,
+    },
+    utils::{drain::Drain, json},
+};
+use infra::errors::{Error, ErrorCodes, Result};
+use sqlparser::{
+    ast::{Expr, FunctionArguments, visit_expressions},
+    dialect::PostgreSqlDialect,
+    parser::Parser,
+    tokenizer::Token,
+};
+
+pub async fn mine(
+    trace_id: &str,
+    org_id: &str,
+    stream_name: &str,
+    user_id: Option<String>,
+    req: &PatternRequest,
+) -> Result<PatternResponse> {
+    let start = std::time::Instant::now();
+    let cfg = get_config();
+    if req.start_time >= req.end_time {
+        return Err(invalid("start_time must be less than end_time"));
+    }
+    let similarity = req.similarity.unwrap_or(DEFAULT_SIMILARITY);
+    if !(similarity > 0.0 && similarity <= 1.0) {
+        return Err(invalid("similarity must be greater than 0 and at most 1"));
+    }
+    let baseline = match (req.baseline_start_time, req.baseline_end_time) {
+        (None, None) => None,
+        (Some(start_time), Some(end_time)) if start_time < end_time => Some((start_time, end_time)),
+        (Some(_), Some(_)) => {
+            return Err(invalid(
+                "baseline_start_time must be less than baseline_end_time",
+            ));
+        }
+        _ => {
+            return Err(invalid(
+                "baseline_start_time and baseline_end_time must be set together",
+            ));
+        }
+    };
+    let field = match req.field.as_deref() {
+        Some(field) if !field.is_empty() => field.to_string(),
+        _ => message_field(org_id, stream_name).await?,
+    };
+    if field.contains('"') {
+        return Err(invalid("field must not contain double quotes"));
+    }
+    let filter = match req.filter.as_deref().filter(|f| !f.trim().is_empty()) {
+        Some(filter) => Some(parse_filter(filter)?),
+        None => None,
+    };
+    let size = req
+        .size
+        .unwrap_or(cfg.limit.pattern_mining_max_logs)
+        .clamp(1, cfg.limit.pattern_mining_max_logs);
+
+    let query = MineQuery {
+        trace_id,
+        org_id,
+        stream_name,
+        user_id,
+        field: &field,
+        filter: filter.as_deref(),
+        size,
+        similarity,
+    };
+    let miner = query.run(req.start_time, req.end_time).await?;
+    let baseline = match baseline {
+        Some((start_time, end_time)) => Some(query.run(start_time, end_time).await?),
+        None => None,
+    };
+
+    let total_logs = miner.total();
+    let mut patterns = Vec::new();
+    let mut new_patterns = 0;
+    for cluster in miner.into_clusters() {
+        let is_new = baseline
+            .as_ref()
+            .is_some_and(|baseline| !baseline.has_match(&cluster.tokens));
+        if is_new {
+            new_patterns += 1;
+        }
+        patterns.push(LogPattern {
+            template: cluster.template(),
+            count: cluster.count,
+            percentage: cluster.count as f64 * 100.0 / total_logs.max(1) as f64,
+            examples: cluster.examples,
+            first_seen: cluster.first_seen,
+            last_seen: cluster.last_seen,
+            is_new,
+        });
+    }
+    let total_patterns = patterns.len();
+    // keep the new patterns even if they are rare, they are what alerts look for
+    patterns.sort_by_key(|p| !p.is_new);
+    patterns.truncate(req.top_k.unwrap_or(DEFAULT_TOP_K).max(1).max(new_patterns));
+    patterns.sort_by(|a, b| b.count.cmp(&a.count));
+
+    Ok(PatternResponse {
+        field,
+        total_logs,
+        total_patterns,
+        new_patterns,
+        patterns,
+        took: start.elapsed().as_millis() as usize,
+    })
+}
+
+struct MineQuery<'a> {
+    trace_id: &'a str,
+    org_id: &'a str,
+    stream_name: &'a str,
+    user_id: Option<String>,
+    field: &'a str,
+    filter: Option<&'a str>,
+    size: usize,
+    similarity: f64,
+}
+
+impl MineQuery<'_> {
+    async fn run(&self, start_time: i64, end_time: i64) -> Result<Drain> {
+        let ranges = split_time_range(
+            start_time,
+            end_time,
+            get_config().limit.pattern_mining_partitions,
+        );
+        let size = self.size.div_ceil(ranges.len());
+        let tasks = ranges
+            .into_iter()
+            .enumerate()
+            .map(|(i, (start_time, end_time))| self.mine_partition(i, start_time, end_time, size));
+        let mut miner = Drain::new(self.similarity);
+        for partition in futures::future::try_join_all(tasks).await? {
+            miner.merge(partition);
+        }
+        Ok(miner)
+    }
+
+    async fn mine_partition(
+        &self,
+        partition: usize,
+        start_time: i64,
+        end_time: i64,
+        size: usize,
+    ) -> Result<Drain> {
+        let ts_column = get_config().common.column_timestamp.clone();
+        let req = search::Request {
+            query: search::Query {
+                sql: build_sql(self.stream_name, self.field, self.filter, &ts_column),
+                start_time,
+                end_time,
+                size: size as i64,
+                ..Default::default()
+            },
+            search_type: Some(search::SearchEventType::Other),
+            use_cache: false,
+            ..Default::default()
+        };
+        let trace_id = format!("{}-{partition}-{start_time}", self.trace_id);
+        let resp = super::search(
+            &trace_id,
+            self.org_id,
+            StreamType::Logs,
+            self.user_id.clone(),
+            &req,
+        )
+        .await?;
+
+        let mut miner = Drain::new(self.similarity);
+        for hit in resp.hits {
+            let message = match hit.get(self.field) {
+                Some(json::Value::String(v)) => v.to_string(),
+                Some(json::Value::Null) | None => continue,
+                Some(v) => v.to_string(),
+            };
+            let ts = hit
+                .get(&ts_column)
+                .and_then(|v| v.as_i64())
+                .unwrap_or(end_time);
+            miner.add(&message, ts);
+        }
+        Ok(miner)
+    }
+}
+
+/// Returns the first full text search field of the stream holding strings.
+async fn message_field(org_id: &str, stream_name: &str) -> Result<String> {
+    let schema = infra::schema::get(org_id, stream_name, StreamType::Logs).await?;
+    if schema.fields().is_empty() {
+        return Err(Error::ErrorCode(ErrorCodes::SearchStreamNotFound(
+            stream_name.to_string(),
+        )));
+    }
+    let settings = infra::schema::unwrap_stream_settings(&schema);
+    infra::schema::get_stream_setting_fts_fields(&settings)
+        .into_iter()
+        .find(|name| {
+            schema
+                .field_with_name(name)
+                .is_ok_and(|f| matches!(f.data_type(), DataType::Utf8 | DataType::LargeUtf8))
+        })
+        .ok_or_else(|| invalid("no message field found in the stream, set field explicitly"))
+}
+
+/// Parses the filter as a single condition without subqueries, so the search
+/// only reads the stream which was permission checked, and returns it as SQL
+fn parse_filter(filter: &str) -> Result<String> {
+    let dialect = PostgreSqlDialect {};
+    let mut parser = Parser::new(&dialect)
+        .try_with_sql(filter)
+        .map_err(|e| invalid(&format!("invalid filter: {e}")))?;
+    let expr = parser
+        .parse_expr()
+        .map_err(|e| invalid(&format!("invalid filter: {e}")))?;
+    if parser.peek_token().token != Token::EOF {
+        return Err(invalid(&format!(
+            "invalid filter: unexpected {}",
+            parser.peek_token().token
+        )));
+    }
+    let has_subquery = visit_expressions(&expr, |e| match e {
+        Expr::Subquery(_) | Expr::Exists { .. } | Expr::InSubquery { .. } => ControlFlow::Break(()),
+        Expr::Function(f) if matches!(f.args, FunctionArguments::Subquery(_)) => {
+            ControlFlow::Break(())
+        }
+        _ => ControlFlow::Continue(()),
+    })
+    .is_break();
+    if has_subquery {
+        return Err(invalid("filter must not contain subqueries"));
+    }
+    Ok(expr.to_string())
+}
+
+fn build_sql(stream_name: &str, field: &str, filter: Option<&str>, ts_column: &str) -> String {
+    let mut sql = format!(r#"SELECT "{ts_column}", "{field}" FROM "{stream_name}""#);
+    if let Some(filter) = filter {
+        sql.push_str(&format!(" WHERE {filter}"));
+    }
+    sql.push_str(&format!(r#" ORDER BY "{ts_column}" DESC"#));
+    sql
+}
+
+/// Cuts [start_time, end_time) in at most `partitions` ranges of the same
+/// length, none shorter than one second.
+fn split_time_range(start_time: i64, end_time: i64, partitions: usize) -> Vec<(i64, i64)> {
+    let duration = end_time - start_time;
+    let partitions = (partitions.max(1) as i64).min((duration / 1_000_000).max(1));
+    let step = duration / partitions;
+    (0..partitions)
+        .map(|i| {
+            let start = start_time + i * step;
+            let end = if i == partitions - 1 {
+                end_time
+            } else {
+                start + step
+            };
+            (start, end)
+        })
+        .collect()
+}
+
+fn invalid(msg: &str) -> Error {
+    Error::ErrorCode(ErrorCodes::InvalidParams(msg.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_sql() {
+        assert_eq!(
+            build_sql("app", "message", None, "_timestamp"),
+            r#"SELECT "_timestamp", "message" FROM "app" ORDER BY "_timestamp" DESC"#
+        );
+        assert_eq!(
+            build_sql("app", "log", Some("level = 'error'"), "_timestamp"),
+            r#"SELECT "_timestamp", "log" FROM "app" WHERE level = 'error' ORDER BY "_timestamp" DESC"#
+        );
+    }
+
+    #[test]
+    fn test_parse_filter() {
+        assert_eq!(
+            parse_filter("level = 'error' AND code >= 500").unwrap(),
+            "level = 'error' AND code >= 500"
+        );
+        assert!(parse_filter("str_match(log, 'timeout')").is_ok());
+        assert!(parse_filter(r#"1=1 UNION SELECT "log" FROM "other""#).is_err());
+        assert!(parse_filter("level = 'error'; DROP TABLE app").is_err());
+        assert!(parse_filter(r#"log IN (SELECT "log" FROM "other")"#).is_err());
+        assert!(parse_filter(r#"EXISTS (SELECT 1 FROM "other")"#).is_err());
+        assert!(parse_filter(r#"code = (SELECT max(code) FROM "other")"#).is_err());
+    }
+
+    #[test]
+    fn test_split_time_range() {
+        assert_eq!(
+            split_time_range(0, 4_000_000, 4),
+            vec![
+                (0, 1_000_000),
+                (1_000_000, 2_000_000),
+                (2_000_000, 3_000_000),
+                (3_000_000, 4_000_000)
+            ]
+        );
+        assert_eq!(split_time_range(0, 1_500_000, 4), vec![(0, 1_500_000)]);
+        assert_eq!(split_time_range(0, 10, 0), vec![(0, 10)]);
+        assert_eq!(
+            split_time_range(0, 10_000_001, 2),
+            vec![(0, 5_000_000), (5_000_000, 10_000_001)]
+        );
+    }
+}
@@ -0,0 +1,378 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Events around a record in the streams related to it, the ones sharing a key
+//! such as the host, the container or the trace of the record, so that an
+//! event can be investigated from a single request.
+
+use config::{
+    TIMESTAMP_COL_NAME,
+    meta::{
+        correlation::SemanticFieldGroup,
+        pivot::{DEFAULT_PIVOT_KEYS, PivotRequest, PivotResponse, PivotStream, PivotStreamHits},
+        search,
+        stream::StreamType,
+    },
+    utils::json,
+};
+use futures::future::join_all;
+use infra::errors::{Error, ErrorCodes, Result};
+
+use crate::service::{db, metadata::distinct_values::sql_literal};
+
+const DEFAULT_WINDOW_SECS: i64 = 300;
+const MAX_WINDOW_SECS: i64 = 86_400;
+const DEFAULT_SIZE: i64 = 20;
+const MAX_SIZE: i64 = 1_000;
+/// Streams searched at most when they are found from the keys
+const MAX_RELATED_STREAMS: usize = 20;
+
+/// Value of a key in the record, with the fields matching the key
+#[derive(Debug, Clone)]
+struct KeyValue {
+    key: String,
+    fields: Vec<String>,
+    value: json::Value,
+}
+
+/// A stream to search, with the fields and values its events are matched on
+#[derive(Debug, Clone)]
+pub struct PivotTarget {
+    pub stream: PivotStream,
+    filters: Vec<(String, json::Value)>,
+}
+
+/// The values of the keys in the record and the streams to search. They are
+/// resolved before the search so that the access to the streams can be
+/// checked.
+#[derive(Debug, Clone)]
+pub struct PivotPlan {
+    keys: Vec<KeyValue>,
+    pub streams: Vec<PivotTarget>,
+}
+
+pub async fn plan(
+    trace_id: &str,
+    org_id: &str,
+    user_id: Option<String>,
+    req: &PivotRequest,
+) -> Result<PivotPlan> {
+    if req.stream_name.is_empty() || req.timestamp <= 0 {
+        return Err(Error::ErrorCode(ErrorCodes::InvalidParams(
+            "stream_name and timestamp are required".to_string(),
+        )));
+    }
+    let record = match &req.record {
+        Some(record) => record.clone(),
+        None => fetch_record(trace_id, org_id, user_id, req).await?,
+    };
+
+    let groups = db::system_settings::get_semantic_field_groups(org_id).await;
+    let keys = if req.keys.is_empty() {
+        DEFAULT_PIVOT_KEYS.iter().map(|k| k.to_string()).collect()
+    } else {
+        req.keys.clone()
+    };
+    let keys = key_values(&record, keys, &groups);
+    if keys.is_empty() {
+        return Err(Error::ErrorCode(ErrorCodes::InvalidParams(
+            "the record has none of the keys".to_string(),
+        )));
+    }
+
+    let related = req.streams.is_empty();
+    let streams = if related {
+        related_streams(org_id, req).await
+    } else {
+        req.streams.clone()
+    };
+    let mut targets = Vec::new();
+    for stream in streams {
+        let schema = infra::schema::get(org_id, &stream.stream_name, stream.stream_type)
+            .await
+            .unwrap_or_else(|_| arrow_schema::Schema::empty());
+        let filters = keys
+            .iter()
+            .flat_map(|kv| {
+                kv.fields
+                    .iter()
+                    .filter(|f| schema.field_with_name(f).is_ok())
+                    .map(|f| (f.to_string(), kv.value.clone()))
+            })
+            .collect::<Vec<_>>();
+        // the streams given are all returned, with an error when they have
+        // none of the keys
+        if filters.is_empty() && related {
+            continue;
+        }
+        targets.push(PivotTarget { stream, filters });
+        if related && targets.len() >= MAX_RELATED_STREAMS {
+            break;
+        }
+    }
+    Ok(PivotPlan {
+        keys,
+        streams: targets,
+    })
+}
+
+pub async fn search(
+    trace_id: &str,
+    org_id: &str,
+    user_id: Option<String>,
+    req: &PivotRequest,
+    plan: PivotPlan,
+) -> Result<PivotResponse> {
+    let start = std::time::Instant::now();
+    let window = req
+        .window
+        .unwrap_or(DEFAULT_WINDOW_SECS)
+        .clamp(1, MAX_WINDOW_SECS)
+        * 1_000_000;
+    let size = req.size.unwrap_or(DEFAULT_SIZE).clamp(2, MAX_SIZE);
+
+    let streams = join_all(plan.streams.into_iter().map(|target| {
+        let user_id = user_id.clone();
+        async move {
+            let mut hits = PivotStreamHits {
+                stream_name: target.stream.stream_name.clone(),
+                stream_type: target.stream.stream_type,
+                fields: target.filters.iter().map(|(f, _)| f.to_string()).collect(),
+                ..Default::default()
+            };
+            if target.filters.is_empty() {
+                hits.error = Some("the stream has none of the keys".to_string());
+                return hits;
+            }
+            match search_around(
+                trace_id,
+                org_id,
+                user_id,
+                &target,
+                req.timestamp,
+                window,
+                size,
+            )
+            .await
+            {
+                Ok(v) => hits.hits = v,
+                Err(e) => {
+                    log::warn!(
+                        "[trace_id {trace_id}] search pivot {org_id}/{}/{} error: {e}",
+                        target.stream.stream_type,
+                        target.stream.stream_name
+                    );
+                    hits.error = Some(e.to_string());
+                }
+            }
+            hits
+        }
+    }))
+    .await;
+
+    Ok(PivotResponse {
+        took: start.elapsed().as_millis() as usize,
+        timestamp: req.timestamp,
+        keys: plan.keys.into_iter().map(|kv| (kv.key, kv.value)).collect(),
+        streams,
+    })
+}
+
+/// The record of the stream at the timestamp
+async fn fetch_record(
+    trace_id: &str,
+    org_id: &str,
+    user_id: Option<String>,
+    req: &PivotRequest,
+) -> Result<json::Map<String, json::Value>> {
+    let search_req = search::Request {
+        query: search::Query {
+            sql: format!(
+                "SELECT * FROM \"{}\" WHERE {TIMESTAMP_COL_NAME} = {}",
+                req.stream_name, req.timestamp
+            ),
+            start_time: req.timestamp,
+            end_time: req.timestamp + 1,
+            size: 1,
+            ..Default::default()
+        },
+        search_type: Some(search::SearchEventType::Other),
+        ..Default::default()
+    };
+    let resp = super::search(trace_id, org_id, req.stream_type, user_id, &search_req).await?;
+    match resp.hits.into_iter().next() {
+        Some(json::Value::Object(record)) => Ok(record),
+        _ => Err(Error::ErrorCode(ErrorCodes::InvalidParams(format!(
+            "no record at {} in stream [{}]",
+            req.timestamp, req.stream_name
+        )))),
+    }
+}
+
+/// The values in the record of the keys, a key matching the fields of its
+/// semantic field group as well
+fn key_values(
+    record: &json::Map<String, json::Value>,
+    keys: Vec<String>,
+    groups: &[SemanticFieldGroup],
+) -> Vec<KeyValue> {
+    keys.into_iter()
+        .filter_map(|key| {
+            let fields = match groups
+                .iter()
+                .find(|g| g.id == key || g.fields.contains(&key))
+            {
+                Some(group) if group.fields.contains(&key) => group.fields.clone(),
+                Some(group) => std::iter::once(key.clone())
+                    .chain(group.fields.iter().cloned())
+                    .collect(),
+                None => vec![key.clone()],
+            };
+            let value = fields.iter().find_map(|f| {
+                record
+                    .get(f)
+                    .filter(|v| sql_literal(v).is_some() && v.as_str() != Some(""))
+            })?;
+            Some(KeyValue {
+                value: value.clone(),
+                key,
+                fields,
+            })
+        })
+        .collect()
+}
+
+/// The stream of the record then the other logs and traces streams
+async fn related_streams(org_id: &str, req: &PivotRequest) -> Vec<PivotStream> {
+    let mut streams = vec![PivotStream {
+        stream_name: req.stream_name.clone(),
+        stream_type: req.stream_type,
+    }];
+    for stream_type in [StreamType::Logs, StreamType::Traces] {
+        let mut names = db::schema::list_streams_from_cache(org_id, stream_type).await;
+        names.sort();
+        for stream_name in names {
+            let stream = PivotStream {
+                stream_name,
+                stream_type,
+            };
+            if !streams.contains(&stream) {
+                streams.push(stream);
+            }
+        }
+    }
+    streams
+}
+
+/// The events of the stream matching one of the filters, half before the
+/// timestamp and half after, newest first
+async fn search_around(
+    trace_id: &str,
+    org_id: &str,
+    user_id: Option<String>,
+    target: &PivotTarget,
+    timestamp: i64,
+    window: i64,
+    size: i64,
+) -> Result<Vec<json::Value>> {
+    let sql = pivot_sql(&target.stream.stream_name, &target.filters);
+    let before = search::Request {
+        query: search::Query {
+            sql: format!("{sql} ORDER BY {TIMESTAMP_COL_NAME} DESC"),
+            start_time: timestamp - window,
+            end_time: timestamp,
+            size: size / 2,
+            ..Default::default()
+        },
+        search_type: Some(search::SearchEventType::Other),
+        ..Default::default()
+    };
+    let after = search::Request {
+        query: search::Query {
+            sql: format!("{sql} ORDER BY {TIMESTAMP_COL_NAME} ASC"),
+            start_time: timestamp,
+            end_time: timestamp + window,
+            size: size - size / 2,
+            ..Default::default()
+        },
+        ..before.clone()
+    };
+    let stream_type = target.stream.stream_type;
+    let (before, after) = futures::try_join!(
+        super::search(trace_id, org_id, stream_type, user_id.clone(), &before),
+        super::search(trace_id, org_id, stream_type, user_id, &after),
+    )?;
+    Ok(after.hits.into_iter().rev().chain(before.hits).collect())
+}
+
+fn pivot_sql(stream_name: &str, filters: &[(String, json::Value)]) -> String {
+    let conditions = filters
+        .iter()
+        .filter_map(|(field, value)| Some(format!("\"{field}\" = {}", sql_literal(value)?)))
+        .collect::<Vec<_>>();
+    format!(
+        "SELECT * FROM \"{stream_name}\" WHERE {}",
+        conditions.join(" OR ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_values() {
+        let record = json::json!({
+            "hostname": "node-1",
+            "trace_id": "abc",
+            "container_id": "",
+            "level": "error"
+        });
+        let record = record.as_object().unwrap();
+        let groups = vec![SemanticFieldGroup::new(
+            "host",
+            "Host",
+            &["host", "hostname", "node"],
+            false,
+        )];
+        let keys = key_values(
+            record,
+            DEFAULT_PIVOT_KEYS.iter().map(|k| k.to_string()).collect(),
+            &groups,
+        );
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0].key, "host");
+        assert_eq!(keys[0].fields, vec!["host", "hostname", "node"]);
+        assert_eq!(keys[0].value, "node-1");
+        assert_eq!(keys[1].key, "trace_id");
+        assert_eq!(keys[1].fields, vec!["trace_id"]);
+
+        // without the groups only the key itself is matched
+        let keys = key_values(record, vec!["host".to_string()], &[]);
+        assert!(keys.is_empty());
+    }
+
+    #[test]
+    fn test_pivot_sql() {
+        let filters = vec![
+            ("host".to_string(), json::json!("it's")),
+            ("pid".to_string(), json::json!(42)),
+        ];
+        assert_eq!(
+            pivot_sql("default", &filters),
+            "SELECT * FROM \"default\" WHERE \"host\" = 'it''s' OR \"pid\" = 42"
+        );
+    }
+}
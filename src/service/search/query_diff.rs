@@ -0,0 +1,307 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runs a query over a baseline and a comparison, two time ranges or two
+//! streams, and diffs the rows group by group, for release comparisons.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use config::{
+    get_config,
+    meta::{
+        query_diff::{
+            DEFAULT_DIFF_THRESHOLD, DiffGroup, QueryDiffRequest, QueryDiffResponse, ValueDelta,
+        },
+        search,
+        sql::resolve_stream_names,
+        stream::StreamType,
+    },
+    utils::json,
+};
+use infra::errors::{Error, ErrorCodes, Result};
+use sqlparser::{ast::VisitMut, dialect::PostgreSqlDialect, parser::Parser};
+
+use super::sql::rewriter::replace_stream::ReplaceStreamVisitor;
+
+/// Rows fetched when the query does not set a size.
+const DEFAULT_SIZE: i64 = 10_000;
+
+pub async fn diff(
+    trace_id: &str,
+    org_id: &str,
+    stream_type: StreamType,
+    user_id: Option<String>,
+    req: &QueryDiffRequest,
+) -> Result<QueryDiffResponse> {
+    let start = std::time::Instant::now();
+    let threshold = req.threshold.unwrap_or(DEFAULT_DIFF_THRESHOLD);
+    if threshold < 0.0 {
+        return Err(Error::ErrorCode(ErrorCodes::InvalidParams(
+            "threshold must not be negative".to_string(),
+        )));
+    }
+    if req.compare.is_empty() {
+        return Err(Error::ErrorCode(ErrorCodes::InvalidParams(
+            "compare must set a time range or a stream".to_string(),
+        )));
+    }
+
+    let mut baseline = req.query.clone();
+    if baseline.size <= 0 {
+        baseline.size = DEFAULT_SIZE;
+    }
+    let mut compare = baseline.clone();
+    if let Some(start_time) = req.compare.start_time {
+        compare.start_time = start_time;
+    }
+    if let Some(end_time) = req.compare.end_time {
+        compare.end_time = end_time;
+    }
+    if let Some(stream) = req.compare.stream.as_deref().filter(|s| !s.is_empty()) {
+        compare.sql = replace_stream(&baseline.sql, stream)?;
+    }
+
+    let baseline_req = search::Request {
+        query: baseline,
+        search_type: Some(search::SearchEventType::Other),
+        ..Default::default()
+    };
+    let compare_req = search::Request {
+        query: compare,
+        search_type: Some(search::SearchEventType::Other),
+        ..Default::default()
+    };
+    let (baseline, compare) = tokio::try_join!(
+        super::search(
+            trace_id,
+            org_id,
+            stream_type,
+            user_id.clone(),
+            &baseline_req
+        ),
+        super::search(trace_id, org_id, stream_type, user_id, &compare_req),
+    )?;
+
+    let group_by = if req.group_by.is_empty() {
+        None
+    } else {
+        Some(req.group_by.as_slice())
+    };
+    let baseline_groups = group_rows(&baseline.hits, group_by, &req.fields);
+    let compare_groups = group_rows(&compare.hits, group_by, &req.fields);
+    let mut resp = diff_groups(baseline_groups, compare_groups, threshold);
+    resp.took = start.elapsed().as_millis() as usize;
+    resp.baseline_rows = baseline.hits.len();
+    resp.compare_rows = compare.hits.len();
+    resp.function_error = baseline.function_error;
+    resp.function_error.extend(compare.function_error);
+    resp.is_partial = baseline.is_partial || compare.is_partial;
+    Ok(resp)
+}
+
+/// Rewrites the query of a single stream to query `stream` instead
+fn replace_stream(sql: &str, stream: &str) -> Result<String> {
+    let streams = resolve_stream_names(sql)
+        .map_err(|e| Error::ErrorCode(ErrorCodes::SearchSQLNotValid(e.to_string())))?;
+    let [from] = streams.as_slice() else {
+        return Err(Error::ErrorCode(ErrorCodes::InvalidParams(
+            "comparing streams needs a query over a single stream".to_string(),
+        )));
+    };
+    let mut statement = Parser::parse_sql(&PostgreSqlDialect {}, sql)
+        .map_err(|e| Error::ErrorCode(ErrorCodes::SearchSQLNotValid(e.to_string())))?
+        .pop()
+        .ok_or_else(|| Error::ErrorCode(ErrorCodes::SearchSQLNotValid(sql.to_string())))?;
+    let _ = statement.visit(&mut ReplaceStreamVisitor::new(from, stream));
+    Ok(statement.to_string())
+}
+
+#[derive(Debug, Default)]
+struct Group {
+    labels: json::Map<String, json::Value>,
+    values: BTreeMap<String, f64>,
+}
+
+/// Groups the rows by their labels, the values of rows having the same labels
+/// are summed
+fn group_rows(
+    hits: &[json::Value],
+    group_by: Option<&[String]>,
+    fields: &[String],
+) -> BTreeMap<String, Group> {
+    let ts_col = &get_config().common.column_timestamp;
+    let mut groups: BTreeMap<String, Group> = BTreeMap::new();
+    for hit in hits {
+        let Some(row) = hit.as_object() else {
+            continue;
+        };
+        let is_label = |k: &String, v: &json::Value| match group_by {
+            Some(group_by) => group_by.contains(k),
+            None => k != ts_col && !v.is_number() && !fields.contains(k),
+        };
+        let labels = row
+            .iter()
+            .filter(|&(k, v)| is_label(k, v))
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect::<BTreeMap<_, _>>();
+        let key = json::to_string(&labels).unwrap_or_default();
+        let group = groups.entry(key).or_insert_with(|| Group {
+            labels: labels.into_iter().collect(),
+            values: BTreeMap::new(),
+        });
+        for (k, v) in row {
+            if is_label(k, v) || (!fields.is_empty() && !fields.contains(k)) {
+                continue;
+            }
+            if let Some(v) = v.as_f64() {
+                *group.values.entry(k.to_string()).or_default() += v;
+            }
+        }
+    }
+    groups
+}
+
+fn diff_groups(
+    mut baseline: BTreeMap<String, Group>,
+    compare: BTreeMap<String, Group>,
+    threshold: f64,
+) -> QueryDiffResponse {
+    let mut resp = QueryDiffResponse {
+        threshold,
+        ..Default::default()
+    };
+    for (key, group) in compare {
+        let Some(base) = baseline.remove(&key) else {
+            resp.new_groups.push(group.into());
+            continue;
+        };
+        let fields = base
+            .values
+            .keys()
+            .chain(group.values.keys())
+            .collect::<BTreeSet<_>>();
+        let mut changed = false;
+        for field in fields {
+            let before = base.values.get(field).copied().unwrap_or_default();
+            let after = group.values.get(field).copied().unwrap_or_default();
+            let delta = after - before;
+            let delta_percent = (before != 0.0).then(|| delta / before.abs() * 100.0);
+            let beyond = match delta_percent {
+                Some(percent) => percent.abs() > threshold,
+                None => delta != 0.0,
+            };
+            if !beyond {
+                continue;
+            }
+            changed = true;
+            resp.changed.push(ValueDelta {
+                labels: group.labels.clone(),
+                field: field.to_string(),
+                baseline: before,
+                compare: after,
+                delta,
+                delta_percent,
+            });
+        }
+        if !changed {
+            resp.unchanged_groups += 1;
+        }
+    }
+    resp.missing_groups = baseline.into_values().map(DiffGroup::from).collect();
+    // changes from 0 are infinite, so they come first
+    resp.changed.sort_by(|a, b| {
+        let a = a.delta_percent.map_or(f64::INFINITY, f64::abs);
+        let b = b.delta_percent.map_or(f64::INFINITY, f64::abs);
+        b.total_cmp(&a)
+    });
+    resp
+}
+
+impl From<Group> for DiffGroup {
+    fn from(group: Group) -> Self {
+        Self {
+            labels: group.labels,
+            values: group
+                .values
+                .into_iter()
+                .map(|(k, v)| (k, json::json!(v)))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_rows() {
+        let hits = vec![
+            json::json!({"host": "a", "cnt": 2, "avg": 1.5}),
+            json::json!({"host": "a", "cnt": 3, "avg": 0.5}),
+            json::json!({"host": "b", "cnt": 1, "avg": 2.5}),
+        ];
+        let groups = group_rows(&hits, None, &[]);
+        assert_eq!(groups.len(), 2);
+        let host_a = groups.values().find(|g| g.labels["host"] == "a").unwrap();
+        assert_eq!(host_a.values["cnt"], 5.0);
+        assert_eq!(host_a.values["avg"], 2.0);
+
+        let groups = group_rows(&hits, Some(&[][..]), &["cnt".to_string()]);
+        assert_eq!(groups.len(), 1);
+        let all = groups.values().next().unwrap();
+        assert_eq!(all.values.len(), 1);
+        assert_eq!(all.values["cnt"], 6.0);
+    }
+
+    #[test]
+    fn test_diff_groups() {
+        let baseline = vec![
+            json::json!({"host": "a", "errors": 100}),
+            json::json!({"host": "b", "errors": 10}),
+            json::json!({"host": "c", "errors": 0}),
+            json::json!({"host": "gone", "errors": 1}),
+        ];
+        let compare = vec![
+            json::json!({"host": "a", "errors": 105}),
+            json::json!({"host": "b", "errors": 20}),
+            json::json!({"host": "c", "errors": 4}),
+            json::json!({"host": "new", "errors": 7}),
+        ];
+        let resp = diff_groups(
+            group_rows(&baseline, None, &[]),
+            group_rows(&compare, None, &[]),
+            10.0,
+        );
+        assert_eq!(resp.new_groups.len(), 1);
+        assert_eq!(resp.new_groups[0].labels["host"], "new");
+        assert_eq!(resp.missing_groups.len(), 1);
+        assert_eq!(resp.missing_groups[0].labels["host"], "gone");
+        assert_eq!(resp.unchanged_groups, 1);
+        let changed = resp
+            .changed
+            .iter()
+            .map(|c| (c.labels["host"].as_str().unwrap(), c.delta_percent))
+            .collect::<Vec<_>>();
+        assert_eq!(changed, vec![("c", None), ("b", Some(100.0))]);
+    }
+
+    #[test]
+    fn test_replace_stream() {
+        let sql = replace_stream("SELECT count(*) AS cnt FROM \"web\"", "web_v2").unwrap();
+        assert_eq!(sql, "SELECT count(*) AS cnt FROM \"web_v2\"");
+        assert!(replace_stream("SELECT * FROM a JOIN b ON a.id = b.id", "c").is_err());
+    }
+}
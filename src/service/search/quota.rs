@@ -0,0 +1,288 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Search quotas per org: searches running at once and MB scanned per minute.
+//!
+//! Each querier counts the searches it leads and what they scanned over a
+//! sliding window of one minute, and shares them with the other queriers like
+//! the ingestion quotas, see [`crate::service::quota`]. A search is rejected
+//! before it runs when its org is at a limit, so one tenant cannot take all
+//! the queriers of a shared cluster. Background searches, like alerts and
+//! reports, are counted but never rejected.
+//!
+//! A search is admitted once at its entry point, the searches it runs for its
+//! partitions or its result cache deltas are covered by its guard.
+
+use std::future::Future;
+
+use config::{
+    get_config,
+    meta::search::{SearchQuota, SearchQuotaUsage},
+    utils::time::now_micros,
+};
+use hashbrown::HashMap;
+use infra::errors::{Error, ErrorCodes, Result};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+use crate::service::{
+    db,
+    quota::{self, SlidingCounter},
+};
+
+const QUOTA_KEY_PREFIX: &str = "/search_quota/";
+const WINDOW_MICROS: i64 = 60 * 1_000_000;
+
+/// Consumption of the orgs on this node
+static LOCAL: Lazy<RwLock<HashMap<String, Local>>> = Lazy::new(Default::default);
+/// Consumption of the orgs on the other queriers, summed
+static REMOTE: Lazy<RwLock<HashMap<String, Usage>>> = Lazy::new(Default::default);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Usage {
+    running: u64,
+    scan_mb: f64,
+}
+
+impl Usage {
+    fn is_zero(&self) -> bool {
+        self.running == 0 && self.scan_mb == 0.0
+    }
+}
+
+impl std::ops::AddAssign for Usage {
+    fn add_assign(&mut self, other: Self) {
+        self.running += other.running;
+        self.scan_mb += other.scan_mb;
+    }
+}
+
+tokio::task_local! {
+    /// Set while an admitted search runs its sub-searches
+    static ADMITTED: ();
+}
+
+/// Running searches and the scanned MB over a sliding window of one minute
+#[derive(Debug, Default)]
+struct Local {
+    running: u64,
+    scan_mb: SlidingCounter<WINDOW_MICROS>,
+}
+
+impl Local {
+    fn add_scan(&mut self, now: i64, scan_mb: u64) {
+        self.scan_mb.add(now, scan_mb);
+    }
+
+    fn usage(&mut self, now: i64) -> Usage {
+        Usage {
+            running: self.running,
+            scan_mb: self.scan_mb.value(now),
+        }
+    }
+}
+
+/// Counts a search as running until it is dropped
+pub struct QuotaGuard {
+    org_id: Option<String>,
+}
+
+impl Drop for QuotaGuard {
+    fn drop(&mut self) {
+        let Some(org_id) = self.org_id.take() else {
+            return;
+        };
+        if let Some(local) = LOCAL.write().get_mut(&org_id) {
+            local.running = local.running.saturating_sub(1);
+        }
+    }
+}
+
+/// The quota of the org, the unset limits use the configured defaults
+async fn get_quota(org_id: &str) -> SearchQuota {
+    let cfg = get_config();
+    db::organization::get_search_quota(org_id)
+        .await
+        .or(SearchQuota {
+            max_concurrent: cfg.limit.search_quota_org_concurrent,
+            scan_mb_per_min: cfg.limit.search_quota_org_scan_mb,
+        })
+}
+
+/// Admits a search of the org, rejected when the org is at one of its
+/// limits unless it is a background search. A sub-search of an admitted
+/// search is not counted again.
+pub async fn acquire(org_id: &str, is_background: bool) -> Result<QuotaGuard> {
+    if !get_config().limit.search_quota_enabled || ADMITTED.try_with(|_| ()).is_ok() {
+        return Ok(QuotaGuard { org_id: None });
+    }
+
+    if !is_background {
+        let quota = get_quota(org_id).await;
+        if let Some(e) = over_quota(org_id, quota, now_micros()) {
+            return Err(Error::ErrorCode(ErrorCodes::SearchQuotaExceeded(format!(
+                "organization [{org_id}] is over its search quota of {e}"
+            ))));
+        }
+    }
+
+    LOCAL.write().entry_ref(org_id).or_default().running += 1;
+    Ok(QuotaGuard {
+        org_id: Some(org_id.to_string()),
+    })
+}
+
+/// Runs the sub-searches of an admitted search, they are covered by its guard
+pub async fn sub_search<F: Future>(fut: F) -> F::Output {
+    ADMITTED.scope((), fut).await
+}
+
+/// Counts the MB scanned by a search of the org
+pub fn consume(org_id: &str, scan_mb: u64) {
+    if !get_config().limit.search_quota_enabled || scan_mb == 0 {
+        return;
+    }
+    LOCAL
+        .write()
+        .entry_ref(org_id)
+        .or_default()
+        .add_scan(now_micros(), scan_mb);
+}
+
+/// The quota of the org and its consumption across the cluster
+pub async fn usage(org_id: &str) -> SearchQuotaUsage {
+    let quota = get_quota(org_id).await;
+    let usage = current_usage(org_id, now_micros());
+    SearchQuotaUsage {
+        quota,
+        running: usage.running,
+        scan_mb_last_min: usage.scan_mb.round() as u64,
+    }
+}
+
+fn current_usage(org_id: &str, now: i64) -> Usage {
+    let mut usage = LOCAL
+        .write()
+        .get_mut(org_id)
+        .map(|l| l.usage(now))
+        .unwrap_or_default();
+    if let Some(remote) = REMOTE.read().get(org_id) {
+        usage += *remote;
+    }
+    usage
+}
+
+/// Returns the exceeded limit
+fn over_quota(org_id: &str, quota: SearchQuota, now: i64) -> Option<String> {
+    if quota.is_empty() {
+        return None;
+    }
+    let usage = current_usage(org_id, now);
+    if quota.max_concurrent > 0 && usage.running >= quota.max_concurrent {
+        return Some(format!("{} concurrent searches", quota.max_concurrent));
+    }
+    if quota.scan_mb_per_min > 0 && usage.scan_mb >= quota.scan_mb_per_min as f64 {
+        return Some(format!("{} MB scanned per minute", quota.scan_mb_per_min));
+    }
+    None
+}
+
+/// Publishes the consumption of this node and collects the one of the other
+/// queriers
+pub async fn sync() -> Result<()> {
+    let now = now_micros();
+    let usage = {
+        let mut local = LOCAL.write();
+        let usage = local
+            .iter_mut()
+            .map(|(k, l)| (k.clone(), l.usage(now)))
+            .filter(|(_, usage)| !usage.is_zero())
+            .collect::<HashMap<_, _>>();
+        local.retain(|k, _| usage.contains_key(k));
+        usage
+    };
+    let remote = quota::exchange(
+        QUOTA_KEY_PREFIX,
+        get_config().limit.search_quota_sync_interval,
+        now,
+        usage,
+    )
+    .await?;
+    *REMOTE.write() = remote;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_usage() {
+        let mut l = Local::default();
+        l.add_scan(60_000_000, 100);
+        l.add_scan(90_000_000, 100);
+        assert_eq!(l.usage(100_000_000).scan_mb, 200.0);
+        // a quarter of the previous minute is still in the window
+        l.add_scan(165_000_000, 10);
+        assert_eq!(l.usage(165_000_000).scan_mb, 60.0);
+        // nothing left after two minutes
+        assert!(l.usage(240_000_000).is_zero());
+    }
+
+    #[test]
+    fn test_over_quota() {
+        let now = 60_000_000;
+        let org_id = "test_search_over_quota_org";
+        LOCAL.write().entry_ref(org_id).or_default().running = 1;
+        let quota = SearchQuota {
+            max_concurrent: 2,
+            scan_mb_per_min: 100,
+        };
+        assert_eq!(over_quota(org_id, quota, now), None);
+        assert_eq!(over_quota(org_id, SearchQuota::default(), now), None);
+
+        REMOTE.write().insert(
+            org_id.to_string(),
+            Usage {
+                running: 1,
+                scan_mb: 0.0,
+            },
+        );
+        assert_eq!(
+            over_quota(org_id, quota, now).as_deref(),
+            Some("2 concurrent searches")
+        );
+        REMOTE.write().remove(org_id);
+
+        LOCAL.write().get_mut(org_id).unwrap().add_scan(now, 100);
+        assert_eq!(
+            over_quota(org_id, quota, now).as_deref(),
+            Some("100 MB scanned per minute")
+        );
+        LOCAL.write().remove(org_id);
+    }
+
+    #[test]
+    fn test_quota_guard_drop() {
+        let org_id = "test_search_quota_guard_org";
+        LOCAL.write().entry_ref(org_id).or_default().running = 1;
+        drop(QuotaGuard {
+            org_id: Some(org_id.to_string()),
+        });
+        assert_eq!(LOCAL.read().get(org_id).unwrap().running, 0);
+        LOCAL.write().remove(org_id);
+    }
+}
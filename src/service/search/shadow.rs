@@ -0,0 +1,260 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Query shadowing, to try a new version of the query engine on the
+//! production searches before rolling it out.
+//!
+//! A sample of the interactive searches is run a second time on the queriers
+//! of the `shadow` role group, which never serve the production searches, and
+//! the result and the latency are compared with the ones of the response. The
+//! differences are written to the _query_shadow stream of the meta org. The
+//! shadow search runs in the background, the response never waits for it.
+
+use std::time::Instant;
+
+use config::{
+    cluster::LOCAL_NODE,
+    datafusion::request::Request,
+    get_config,
+    meta::{
+        cluster::RoleGroup, search, self_reporting::usage::QueryShadowData, stream::StreamType,
+    },
+    utils::{
+        hash::{Sum64, gxhash},
+        json,
+        time::now_micros,
+    },
+};
+use hashbrown::HashMap;
+use infra::cluster::get_cached_online_querier_nodes;
+use proto::cluster_rpc::SearchQuery;
+
+use super::cluster;
+
+/// What the shadow search is compared with
+struct Primary {
+    total: usize,
+    hits: Vec<json::Value>,
+    took: usize,
+    from_cache: bool,
+}
+
+/// Whether the search is sampled to run on the shadow queriers too
+pub async fn sampled(in_req: &search::Request, res: &search::Response) -> bool {
+    let cfg = get_config();
+    if !cfg.common.search_shadow_enabled || cfg.common.search_shadow_ratio == 0 {
+        return false;
+    }
+    // the partial and the streamed responses can't be compared
+    if res.is_partial
+        || in_req.query.streaming_output
+        || in_req.search_type.is_some_and(|t| t.is_background())
+    {
+        return false;
+    }
+    if rand::random::<f64>() * 100.0 >= cfg.common.search_shadow_ratio as f64 {
+        return false;
+    }
+    get_cached_online_querier_nodes(Some(RoleGroup::Shadow))
+        .await
+        .is_some_and(|nodes| !nodes.is_empty())
+}
+
+/// Runs the search on the shadow queriers in the background and records how
+/// it differs from the response
+pub fn spawn(
+    trace_id: &str,
+    org_id: &str,
+    stream_type: StreamType,
+    user_id: Option<String>,
+    in_req: &search::Request,
+    res: &search::Response,
+) {
+    let trace_id = trace_id.to_string();
+    let org_id = org_id.to_string();
+    let in_req = in_req.clone();
+    let primary = Primary {
+        total: res.total,
+        hits: res.hits.clone(),
+        took: res.took,
+        from_cache: res.result_cache_ratio > 0,
+    };
+    tokio::task::spawn(async move {
+        if let Some(data) = run(&trace_id, &org_id, stream_type, user_id, &in_req, primary).await {
+            log::warn!(
+                "[trace_id {trace_id}] shadow search mismatch: {}, {}",
+                data.mismatch,
+                data.detail
+            );
+            crate::service::self_reporting::publish_query_shadow(data).await;
+        }
+    });
+}
+
+async fn run(
+    trace_id: &str,
+    org_id: &str,
+    stream_type: StreamType,
+    user_id: Option<String>,
+    in_req: &search::Request,
+    primary: Primary,
+) -> Option<QueryShadowData> {
+    let start = Instant::now();
+    let query: SearchQuery = in_req.query.clone().into();
+    let mut request = Request::new(
+        format!("{trace_id}-shadow"),
+        org_id.to_string(),
+        stream_type,
+        in_req.timeout,
+        user_id,
+        Some((query.start_time, query.end_time)),
+        in_req.search_type.map(|v| v.to_string()),
+        in_req.query.histogram_interval,
+        false,
+    );
+    // the engine under test must compute the whole result
+    request.set_use_cache(false);
+    request.set_shadow(true);
+    let ret = cluster::http::search(request, query, vec![], vec![], false).await;
+    let shadow_took = start.elapsed().as_millis() as usize;
+
+    let mut data = QueryShadowData {
+        _timestamp: now_micros(),
+        org_id: org_id.to_string(),
+        stream_type,
+        trace_id: trace_id.to_string(),
+        sql: in_req.query.sql.clone(),
+        start_time: in_req.query.start_time,
+        end_time: in_req.query.end_time,
+        total: primary.total,
+        hits: primary.hits.len(),
+        took: primary.took,
+        shadow_took,
+        node_name: Some(LOCAL_NODE.name.clone()),
+        ..Default::default()
+    };
+    let shadow = match ret {
+        Ok(v) => v,
+        Err(e) => {
+            data.mismatch = "error".to_string();
+            data.detail = e.to_string();
+            return Some(data);
+        }
+    };
+    data.shadow_total = shadow.total;
+    data.shadow_hits = shadow.hits.len();
+
+    let latency_ratio = get_config().common.search_shadow_latency_ratio;
+    if let Some(detail) = compare_hits(&primary, &shadow) {
+        data.mismatch = "result".to_string();
+        data.detail = detail;
+    } else if !primary.from_cache
+        && primary.took > 0
+        && shadow_took * 100 > primary.took * latency_ratio
+    {
+        data.mismatch = "latency".to_string();
+        data.detail = format!(
+            "took {shadow_took}ms on the shadow queriers, {}ms in production",
+            primary.took
+        );
+    } else {
+        return None;
+    }
+    Some(data)
+}
+
+/// Returns what differs between the results, the hits are compared in any
+/// order since the engines may return the rows of a group or of a same
+/// timestamp differently
+fn compare_hits(primary: &Primary, shadow: &search::Response) -> Option<String> {
+    if primary.total != shadow.total {
+        return Some(format!(
+            "total is {} on the shadow queriers, {} in production",
+            shadow.total, primary.total
+        ));
+    }
+    if primary.hits.len() != shadow.hits.len() {
+        return Some(format!(
+            "{} hits on the shadow queriers, {} in production",
+            shadow.hits.len(),
+            primary.hits.len()
+        ));
+    }
+    let mut h = gxhash::new();
+    let mut counts: HashMap<u64, i64> = HashMap::with_capacity(primary.hits.len());
+    for hit in primary.hits.iter() {
+        *counts.entry(h.sum64(&hit.to_string())).or_default() += 1;
+    }
+    for hit in shadow.hits.iter() {
+        *counts.entry(h.sum64(&hit.to_string())).or_default() -= 1;
+    }
+    // the hits of production missing from the shadow result
+    let diff = counts.values().filter(|v| **v > 0).sum::<i64>();
+    if diff > 0 {
+        return Some(format!("{diff} of {} hits differ", primary.hits.len()));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn primary(hits: Vec<json::Value>) -> Primary {
+        Primary {
+            total: hits.len(),
+            hits,
+            took: 10,
+            from_cache: false,
+        }
+    }
+
+    fn response(hits: Vec<json::Value>) -> search::Response {
+        let mut res = search::Response::new(0, 10);
+        res.total = hits.len();
+        res.hits = hits;
+        res
+    }
+
+    #[test]
+    fn test_compare_hits() {
+        let a = json::json!({"k8s_namespace_name": "default", "count": 3});
+        let b = json::json!({"k8s_namespace_name": "kube-system", "count": 5});
+        let c = json::json!({"k8s_namespace_name": "kube-system", "count": 4});
+
+        // the order of the hits doesn't matter
+        let p = primary(vec![a.clone(), b.clone()]);
+        assert_eq!(
+            compare_hits(&p, &response(vec![b.clone(), a.clone()])),
+            None
+        );
+
+        assert_eq!(
+            compare_hits(&p, &response(vec![a.clone(), c.clone()])).as_deref(),
+            Some("1 of 2 hits differ")
+        );
+        assert_eq!(
+            compare_hits(&p, &response(vec![a.clone()])).as_deref(),
+            Some("total is 1 on the shadow queriers, 2 in production")
+        );
+
+        let mut res = response(vec![a]);
+        res.total = 2;
+        assert_eq!(
+            compare_hits(&p, &res).as_deref(),
+            Some("1 hits on the shadow queriers, 2 in production")
+        );
+    }
+}
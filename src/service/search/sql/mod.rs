@@ -38,6 +38,7 @@ use proto::cluster_rpc::SearchQuery;
 use regex::Regex;
 use sqlparser::{ast::VisitMut, dialect::PostgreSqlDialect, parser::Parser};
 
+use super::{datafusion::table_provider::provenance, field_access};
 use crate::service::search::sql::{
     rewriter::{
         add_o2_id::AddO2IdVisitor, add_timestamp::AddTimestampVisitor,
@@ -45,7 +46,10 @@ use crate::service::search::sql::{
         remove_dashboard_placeholder::RemoveDashboardAllVisitor,
         track_total_hits::TrackTotalHitsVisitor,
     },
-    schema::{generate_schema_fields, generate_select_star_schema, has_original_column},
+    schema::{
+        computed_field_columns, generate_schema_fields, generate_select_star_schema,
+        has_original_column,
+    },
     visitor::{
         column::ColumnVisitor,
         histogram_interval::{HistogramIntervalVisitor, validate_and_adjust_histogram_interval},
@@ -86,6 +90,8 @@ pub struct Sql {
     pub histogram_interval: Option<i64>,
     pub sorted_by_time: bool, // if only order by _timestamp
     pub sampling_config: Option<proto::cluster_rpc::SamplingConfig>,
+    /// Fields denied to the user whose values are masked in the results
+    pub masked_fields: Vec<String>,
 }
 
 impl Sql {
@@ -94,7 +100,15 @@ impl Sql {
             .search_event_type
             .as_ref()
             .and_then(|s| SearchEventType::try_from(s.as_str()).ok());
-        Self::new(query, &req.org_id, req.stream_type, search_event_type).await
+        Self::new_inner(
+            query,
+            &req.org_id,
+            req.stream_type,
+            search_event_type,
+            false,
+            req.user_id.as_deref(),
+        )
+        .await
     }
 
     pub async fn new(
@@ -112,6 +126,27 @@ impl Sql {
         stream_type: StreamType,
         search_event_type: Option<SearchEventType>,
         extract_patterns: bool,
+    ) -> Result<Sql, Error> {
+        Self::new_inner(
+            query,
+            org_id,
+            stream_type,
+            search_event_type,
+            extract_patterns,
+            None,
+        )
+        .await
+    }
+
+    /// Builds the query of `user_id`, with the fields denied to the user
+    /// hidden, see [`field_access`]
+    async fn new_inner(
+        query: &SearchQuery,
+        org_id: &str,
+        stream_type: StreamType,
+        search_event_type: Option<SearchEventType>,
+        extract_patterns: bool,
+        user_id: Option<&str>,
     ) -> Result<Sql, Error> {
         let cfg = get_config();
         let sql = query.sql.clone();
@@ -119,10 +154,23 @@ impl Sql {
         let mut limit = query.size as i64;
         let sql =
             config::utils::query_select_utils::replace_o2_custom_patterns(&sql).unwrap_or(sql);
+        // the streams searched by an alias are searched by their name
+        let sql = match crate::service::stream_alias::resolve_sql(org_id, stream_type, &sql) {
+            Some(v) => v,
+            None => sql,
+        };
+        // the datasets are searched as the union of their streams
+        let sql = match crate::service::dataset::resolve_sql(org_id, stream_type, &sql).await {
+            Some(v) => v,
+            None => sql,
+        };
 
         // 1. get table name
         let stream_names = resolve_stream_names_with_type(&sql)
             .map_err(|e| Error::ErrorCode(ErrorCodes::SearchSQLNotValid(e.to_string())))?;
+        // the provenance columns are only added when the query selects them
+        let with_provenance =
+            cfg.common.feature_query_provenance_enabled && provenance::is_referenced(&sql);
         let mut total_schemas = HashMap::with_capacity(stream_names.len());
         for stream in stream_names.iter() {
             let stream_name = stream.stream_name();
@@ -135,6 +183,11 @@ impl Sql {
                     stream_name,
                 )));
             }
+            let schema = if with_provenance {
+                provenance::add_fields(&schema)
+            } else {
+                schema
+            };
             total_schemas.insert(stream.clone(), Arc::new(SchemaCache::new(schema)));
         }
 
@@ -160,11 +213,35 @@ impl Sql {
 
         //********************Change the sql end*********************************//
 
+        // the fields denied to the user can't be referenced and are hidden
+        // from `SELECT *`
+        let denied = field_access::denied_rules(org_id, user_id, &total_schemas).await;
+        let mut masked_fields = Vec::new();
+        if !denied.is_empty() {
+            let mut column_visitor = ColumnVisitor::new(&total_schemas);
+            let _ = statement.visit(&mut column_visitor);
+            field_access::check_columns(&denied, &column_visitor.columns)?;
+            for (stream, rules) in denied.iter() {
+                if let Some(schema) = total_schemas.get_mut(stream) {
+                    let (hidden, masked) = field_access::hide_fields(schema, rules);
+                    *schema = Arc::new(hidden);
+                    masked_fields.extend(masked);
+                }
+            }
+        }
+
         // 5. get column name, alias, group by, order by
         let mut column_visitor = ColumnVisitor::new(&total_schemas);
         let _ = statement.visit(&mut column_visitor);
 
-        let columns = column_visitor.columns.clone();
+        let mut columns = column_visitor.columns.clone();
+        // the computed fields are computed from stored fields
+        for (stream, schema) in total_schemas.iter() {
+            let fields = computed_field_columns(&statement, schema, column_visitor.is_wildcard);
+            if !fields.is_empty() {
+                columns.entry(stream.clone()).or_default().extend(fields);
+            }
+        }
         let aliases = column_visitor
             .columns_alias
             .iter()
@@ -185,7 +262,8 @@ impl Sql {
         }
         let need_sort_by_time = order_by.len() == 1
             && order_by[0].0 == TIMESTAMP_COL_NAME
-            && order_by[0].1 == OrderBy::Desc;
+            && order_by[0].1 == OrderBy::Desc
+            && files_sorted_by_time(&total_schemas, query.start_time);
 
         // check if need exact limit and offset
         if (limit == -1 || limit == 0)
@@ -329,6 +407,7 @@ impl Sql {
                 (query.start_time, query.end_time),
                 extract_patterns,
             ),
+            masked_fields,
         })
     }
 
@@ -387,6 +466,19 @@ impl std::fmt::Display for Sql {
     }
 }
 
+/// The compactor sorts the files of the streams with a compact sort by other
+/// fields first. Once the sort is removed, only the days after are merged
+/// without it, the compactor merging the hours which passed
+fn files_sorted_by_time(
+    schemas: &HashMap<TableReference, Arc<SchemaCache>>,
+    start_time: i64,
+) -> bool {
+    schemas.values().all(|schema| {
+        unwrap_stream_settings(schema.schema())
+            .is_none_or(|setting| setting.files_sorted_by_time(start_time))
+    })
+}
+
 fn o2_id_is_needed(
     schemas: &HashMap<TableReference, Arc<SchemaCache>>,
     search_event_type: &Option<SearchEventType>,
@@ -21,4 +21,6 @@ pub mod approx_percentile;
 pub mod index;
 pub mod match_all_raw;
 pub mod remove_dashboard_placeholder;
+pub mod replace_dataset;
+pub mod replace_stream;
 pub mod track_total_hits;
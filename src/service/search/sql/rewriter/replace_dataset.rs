@@ -0,0 +1,127 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::ops::ControlFlow;
+
+use hashbrown::HashMap;
+use sqlparser::{
+    ast::{Ident, ObjectNamePart, SetExpr, Statement, TableFactor, VisitorMut},
+    dialect::PostgreSqlDialect,
+    parser::Parser,
+};
+
+// replace the dataset queried by the union of its streams,
+// `SELECT * FROM ds` -> `SELECT * FROM (SELECT * FROM "a" UNION ALL BY NAME SELECT * FROM "b") AS
+// "ds"`, the alias keeps the columns qualified by the dataset valid
+pub struct ReplaceDatasetVisitor {
+    datasets: HashMap<String, Vec<String>>,
+}
+
+impl ReplaceDatasetVisitor {
+    /// `datasets` maps the name of a dataset to its member streams
+    pub fn new(datasets: HashMap<String, Vec<String>>) -> Self {
+        Self { datasets }
+    }
+}
+
+impl VisitorMut for ReplaceDatasetVisitor {
+    type Break = ();
+
+    fn pre_visit_table_factor(&mut self, table: &mut TableFactor) -> ControlFlow<Self::Break> {
+        let TableFactor::Table { name, alias, .. } = table else {
+            return ControlFlow::Continue(());
+        };
+        // the dataset is the last part, the first one may be the stream type
+        let Some(ObjectNamePart::Identifier(ident)) = name.0.last() else {
+            return ControlFlow::Continue(());
+        };
+        let Some(streams) = self.datasets.get(&ident.value).filter(|s| !s.is_empty()) else {
+            return ControlFlow::Continue(());
+        };
+        let alias = match alias {
+            Some(alias) => alias.to_string(),
+            None => Ident::with_quote('"', ident.value.clone()).to_string(),
+        };
+        let relations = streams
+            .iter()
+            .map(|stream| {
+                let mut name = name.clone();
+                if let Some(last) = name.0.last_mut() {
+                    *last = ObjectNamePart::Identifier(Ident::with_quote('"', stream.clone()));
+                }
+                name.to_string()
+            })
+            .collect::<Vec<_>>();
+        let sql = if relations.len() == 1 {
+            format!("SELECT * FROM {} AS {alias}", relations[0])
+        } else {
+            format!(
+                "SELECT * FROM ({}) AS {alias}",
+                relations
+                    .iter()
+                    .map(|relation| format!("SELECT * FROM {relation}"))
+                    .collect::<Vec<_>>()
+                    .join(" UNION ALL BY NAME ")
+            )
+        };
+        let Ok(Some(Statement::Query(query))) =
+            Parser::parse_sql(&PostgreSqlDialect {}, &sql).map(|mut v| v.pop())
+        else {
+            return ControlFlow::Continue(());
+        };
+        if let SetExpr::Select(select) = *query.body
+            && let Some(from) = select.from.into_iter().next()
+        {
+            *table = from.relation;
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::ast::VisitMut;
+
+    use super::*;
+
+    fn rewrite(sql: &str) -> String {
+        let datasets = HashMap::from([
+            (
+                "k8s".to_string(),
+                vec!["k8s_default".to_string(), "k8s_system".to_string()],
+            ),
+            ("web".to_string(), vec!["nginx".to_string()]),
+        ]);
+        let mut statement = Parser::parse_sql(&PostgreSqlDialect {}, sql)
+            .unwrap()
+            .pop()
+            .unwrap();
+        let _ = statement.visit(&mut ReplaceDatasetVisitor::new(datasets));
+        statement.to_string()
+    }
+
+    #[test]
+    fn test_replace_dataset_visitor() {
+        assert_eq!(
+            rewrite("SELECT k8s.level, count(*) FROM k8s GROUP BY k8s.level"),
+            "SELECT k8s.level, count(*) FROM (SELECT * FROM \"k8s_default\" UNION ALL BY NAME SELECT * FROM \"k8s_system\") AS \"k8s\" GROUP BY k8s.level"
+        );
+        assert_eq!(
+            rewrite("SELECT * FROM logs.web AS w WHERE code >= 500"),
+            "SELECT * FROM logs.\"nginx\" AS w WHERE code >= 500"
+        );
+        assert_eq!(rewrite("SELECT * FROM other"), "SELECT * FROM other");
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::ops::ControlFlow;
+
+use sqlparser::ast::{Ident, ObjectName, ObjectNamePart, VisitorMut};
+
+// replace the stream queried, `SELECT * FROM a` -> `SELECT * FROM "b"`
+pub struct ReplaceStreamVisitor {
+    from: String,
+    to: String,
+}
+
+impl ReplaceStreamVisitor {
+    pub fn new(from: &str, to: &str) -> Self {
+        Self {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+}
+
+impl VisitorMut for ReplaceStreamVisitor {
+    type Break = ();
+
+    fn pre_visit_relation(&mut self, relation: &mut ObjectName) -> ControlFlow<Self::Break> {
+        // the stream is the last part, the first one may be the stream type
+        if let Some(ObjectNamePart::Identifier(ident)) = relation.0.last_mut()
+            && ident.value == self.from
+        {
+            *ident = Ident::with_quote('"', self.to.clone());
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::{ast::VisitMut, dialect::PostgreSqlDialect};
+
+    use super::*;
+
+    #[test]
+    fn test_replace_stream_visitor() {
+        let sql = "SELECT host, count(*) FROM \"web\" WHERE code >= 500 GROUP BY host";
+        let mut statement = sqlparser::parser::Parser::parse_sql(&PostgreSqlDialect {}, sql)
+            .unwrap()
+            .pop()
+            .unwrap();
+        let _ = statement.visit(&mut ReplaceStreamVisitor::new("web", "web-canary"));
+        assert_eq!(
+            statement.to_string(),
+            "SELECT host, count(*) FROM \"web-canary\" WHERE code >= 500 GROUP BY host"
+        );
+
+        let sql = "SELECT * FROM logs.web";
+        let mut statement = sqlparser::parser::Parser::parse_sql(&PostgreSqlDialect {}, sql)
+            .unwrap()
+            .pop()
+            .unwrap();
+        let _ = statement.visit(&mut ReplaceStreamVisitor::new("web", "api"));
+        assert_eq!(statement.to_string(), "SELECT * FROM logs.\"api\"");
+    }
+}
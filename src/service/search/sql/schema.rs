@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::sync::Arc;
+use std::{ops::ControlFlow, sync::Arc};
 
 use arrow_schema::FieldRef;
 use config::{
@@ -26,6 +26,7 @@ use infra::schema::{
     SchemaCache, get_stream_setting_defined_schema_fields, get_stream_setting_fts_fields,
     unwrap_stream_settings,
 };
+use sqlparser::ast::{Expr, Statement, visit_expressions};
 
 pub fn generate_select_star_schema(
     schemas: HashMap<TableReference, Arc<SchemaCache>>,
@@ -269,6 +270,45 @@ pub fn generate_schema_fields(
     fields
 }
 
+/// The fields of the stream the computed fields used by the query are
+/// computed from, a `SELECT *` uses every computed field
+pub fn computed_field_columns(
+    statement: &Statement,
+    schema: &SchemaCache,
+    is_wildcard: bool,
+) -> HashSet<String> {
+    let Some(settings) = unwrap_stream_settings(schema.schema()) else {
+        return HashSet::new();
+    };
+    if settings.computed_fields.is_empty() {
+        return HashSet::new();
+    }
+    let mut idents = HashSet::new();
+    if !is_wildcard {
+        let _ = visit_expressions(statement, |expr| {
+            match expr {
+                Expr::Identifier(ident) => {
+                    idents.insert(ident.value.clone());
+                }
+                Expr::CompoundIdentifier(parts) => {
+                    if let Some(ident) = parts.last() {
+                        idents.insert(ident.value.clone());
+                    }
+                }
+                _ => {}
+            }
+            ControlFlow::<()>::Continue(())
+        });
+    }
+    settings
+        .computed_fields
+        .iter()
+        .filter(|field| is_wildcard || idents.contains(&field.name))
+        .flat_map(|field| field.source_fields())
+        .filter(|name| schema.contains_field(name))
+        .collect()
+}
+
 // check if has original column in sql
 pub fn has_original_column(
     columns: &HashMap<TableReference, HashSet<String>>,
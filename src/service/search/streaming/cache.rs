@@ -460,6 +460,7 @@ async fn send_cached_responses(
         work_group: None, // TODO: add work group
         result_cache_ratio: Some(cached.cached_response.result_cache_ratio),
         peak_memory_usage: cached.cached_response.peak_memory_usage,
+        took_detail: Some(cached.cached_response.took_detail.clone()),
         ..Default::default()
     };
     report_request_usage_stats(
@@ -41,9 +41,9 @@ use tracing::Instrument;
 use crate::{
     common::{
         meta::search::{AuditContext, SearchResultType},
-        utils::stream::get_max_query_range,
+        utils::stream::{audit_max_query_range_bypass, get_max_query_range},
     },
-    service::search::cache as search_cache,
+    service::search::{cache as search_cache, quota},
 };
 #[cfg(feature = "enterprise")]
 use crate::{
@@ -63,9 +63,60 @@ pub use cache::{handle_cache_responses_and_deltas, write_results_to_cache};
 pub use execution::do_partitioned_search;
 pub use sorting::order_search_results;
 
-/// Main function to process search stream requests
+/// Main function to process search stream requests, the searches of the
+/// partitions run under the quota of the request
 #[allow(clippy::too_many_arguments)]
 pub async fn process_search_stream_request(
+    org_id: String,
+    user_id: String,
+    trace_id: String,
+    req: config::meta::search::Request,
+    stream_type: StreamType,
+    stream_names: Vec<String>,
+    req_order_by: OrderBy,
+    search_span: tracing::Span,
+    sender: mpsc::Sender<Result<config::meta::search::StreamResponses, infra::errors::Error>>,
+    values_ctx: Option<ValuesEventContext>,
+    fallback_order_by_col: Option<String>,
+    audit_ctx: Option<AuditContext>,
+    is_multi_stream_search: bool,
+    extract_patterns: bool,
+    bypass_max_query_range: bool,
+) {
+    let is_background = req.search_type.is_some_and(|t| t.is_background());
+    let _quota_guard = match quota::acquire(&org_id, is_background).await {
+        Ok(v) => v,
+        Err(e) => {
+            if sender.send(Err(e)).await.is_err() {
+                log::warn!(
+                    "[HTTP2_STREAM trace_id {trace_id}] Sender is closed, stop sending message to client",
+                );
+            }
+            return;
+        }
+    };
+    quota::sub_search(process_search_stream_request_inner(
+        org_id,
+        user_id,
+        trace_id,
+        req,
+        stream_type,
+        stream_names,
+        req_order_by,
+        search_span,
+        sender,
+        values_ctx,
+        fallback_order_by_col,
+        audit_ctx,
+        is_multi_stream_search,
+        extract_patterns,
+        bypass_max_query_range,
+    ))
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn process_search_stream_request_inner(
     org_id: String,
     user_id: String,
     trace_id: String,
@@ -80,6 +131,7 @@ pub async fn process_search_stream_request(
     _audit_ctx: Option<AuditContext>,
     is_multi_stream_search: bool,
     extract_patterns: bool,
+    bypass_max_query_range: bool,
 ) {
     log::info!(
         "[HTTP2_STREAM trace_id {trace_id}] Received HTTP/2 stream request for org_id: {org_id}",
@@ -208,7 +260,24 @@ pub async fn process_search_stream_request(
 
     req.query.query_fn = query_fn.clone();
 
-    let max_query_range = get_max_query_range(&stream_names, &org_id, &user_id, stream_type).await; // hours
+    let mut max_query_range =
+        get_max_query_range(&stream_names, &org_id, &user_id, stream_type).await; // hours
+    if bypass_max_query_range
+        && max_query_range > 0
+        && (req.query.end_time - req.query.start_time) > max_query_range * 3600 * 1_000_000
+    {
+        audit_max_query_range_bypass(
+            &trace_id,
+            &org_id,
+            &user_id,
+            &stream_names.join(","),
+            max_query_range,
+            req.query.start_time,
+            req.query.end_time,
+        )
+        .await;
+        max_query_range = 0;
+    }
 
     // HACK: always search from the first partition, this is because to support pagination in http2
     // streaming we need context of no of hits per partition, which currently is not available.
@@ -224,6 +293,7 @@ pub async fn process_search_stream_request(
             &trace_id,
             &org_id,
             stream_type,
+            Some(&user_id),
             &mut req,
             use_cache,
         )
@@ -810,6 +880,7 @@ pub async fn process_search_stream_request_multi(
                 None,  // no audit context for individualueries
                 false, // not multi stream search at individual level
                 false, // no pattern extraction for multi-stream
+                false, // the max query range applies to multi-stream
             );
 
             tokio::spawn(search_task);
@@ -213,7 +213,7 @@ pub(super) async fn ingest_usages(mut curr_usages: Vec<UsageData>) {
     }
 }
 
-pub(super) async fn ingest_reporting_data(
+pub(crate) async fn ingest_reporting_data(
     reporting_data_json: Vec<json::Value>,
     stream_params: StreamParams,
 ) -> Result<()> {
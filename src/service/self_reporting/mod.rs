@@ -28,11 +28,15 @@ use config::{
         self_reporting::{
             EnqueueError, ReportingData,
             error::ErrorData,
-            usage::{RequestStats, TriggerData, UsageData, UsageEvent, UsageType},
+            usage::{
+                QUERY_SHADOW_STREAM, QueryShadowData, RequestStats, SearchAuditData, TriggerData,
+                UsageData, UsageEvent, UsageType,
+            },
         },
-        stream::StreamType,
+        stream::{StreamParams, StreamType},
     },
     metrics,
+    utils::json,
 };
 #[cfg(feature = "enterprise")]
 use o2_enterprise::enterprise::common::auditor;
@@ -49,6 +53,7 @@ mod triggers_schema;
 
 #[cfg(feature = "cloud")]
 pub use ingestion::ingest_data_retention_usages;
+pub(crate) use ingestion::ingest_reporting_data;
 
 pub async fn run() {
     #[cfg(not(feature = "enterprise"))]
@@ -103,6 +108,23 @@ pub async fn report_request_usage_stats(
         metrics::INGEST_BYTES
             .with_label_values(&[org_id, stream_type.as_str()])
             .inc_by((stats.size * SIZE_IN_MB) as u64);
+        crate::service::ingestion::quota::consume(
+            org_id,
+            stream_type,
+            stream_name,
+            stats.records as u64,
+            (stats.size * SIZE_IN_MB) as u64,
+        );
+    }
+    if matches!(event, UsageEvent::Search) && get_config().common.search_audit_enabled {
+        publish_search_audit(SearchAuditData::new(
+            &stats,
+            org_id,
+            stream_name,
+            stream_type,
+            timestamp,
+        ))
+        .await;
     }
 
     #[cfg(not(feature = "enterprise"))]
@@ -227,6 +249,34 @@ async fn publish_usage(usages: Vec<UsageData>) {
     }
 }
 
+/// The search audit is written whether usage reporting is enabled or not
+async fn publish_search_audit(audit: SearchAuditData) {
+    if let Err(e) = queues::USAGE_QUEUE
+        .enqueue(ReportingData::SearchAudit(Box::new(audit)))
+        .await
+    {
+        log::error!(
+            "[SELF-REPORTING] Failed to send search audit data to background ingesting job: {e}"
+        );
+    }
+}
+
+/// The shadow mismatches are few, they are ingested right away instead of
+/// waiting in the usage queue
+pub async fn publish_query_shadow(data: QueryShadowData) {
+    let stream = StreamParams::new(config::META_ORG_ID, QUERY_SHADOW_STREAM, StreamType::Logs);
+    let data = match json::to_value(data) {
+        Ok(v) => v,
+        Err(e) => {
+            log::error!("[SELF-REPORTING] Error in serializing QueryShadowData: {e}");
+            return;
+        }
+    };
+    if let Err(e) = ingestion::ingest_reporting_data(vec![data], stream).await {
+        log::error!("[SELF-REPORTING] Error in ingesting QueryShadowData: {e}");
+    }
+}
+
 pub fn publish_triggers_usage(trigger: TriggerData) {
     #[cfg(not(feature = "enterprise"))]
     {
@@ -368,7 +418,7 @@ pub async fn flush() {
     #[cfg(feature = "enterprise")]
     let usage_enabled = true;
     #[cfg(not(feature = "enterprise"))]
-    let usage_enabled = cfg.common.usage_enabled;
+    let usage_enabled = cfg.common.usage_enabled || cfg.common.search_audit_enabled;
 
     // only ingester and querier nodes report usage
     if !usage_enabled || (!LOCAL_NODE.is_ingester() && !LOCAL_NODE.is_querier()) {
@@ -20,7 +20,7 @@ use config::{
     meta::{
         self_reporting::{
             ReportingData, ReportingMessage, ReportingQueue, ReportingRunner,
-            usage::{ERROR_STREAM, TRIGGERS_STREAM, TriggerData},
+            usage::{ERROR_STREAM, SEARCH_AUDIT_STREAM, TRIGGERS_STREAM, TriggerData},
         },
         stream::{StreamParams, StreamType},
     },
@@ -172,7 +172,9 @@ fn update_queue_depth_metric_for_runner(runner: &ReportingRunner) {
 
     for data in &runner.pending {
         match data {
-            ReportingData::Usage(_) | ReportingData::Trigger(_) => usage_count += 1,
+            ReportingData::Usage(_) | ReportingData::Trigger(_) | ReportingData::SearchAudit(_) => {
+                usage_count += 1
+            }
             ReportingData::Error(_) => error_count += 1,
         }
     }
@@ -192,7 +194,9 @@ fn update_queue_depth_metrics(batch: &[ReportingData]) {
 
     for data in batch {
         match data {
-            ReportingData::Usage(_) | ReportingData::Trigger(_) => usage_count += 1,
+            ReportingData::Usage(_) | ReportingData::Trigger(_) | ReportingData::SearchAudit(_) => {
+                usage_count += 1
+            }
             ReportingData::Error(_) => error_count += 1,
         }
     }
@@ -212,9 +216,9 @@ async fn ingest_buffered_data(thread_id: usize, buffered: Vec<ReportingData>) {
         buffered.len()
     );
 
-    let (usages, triggers, errors, raw_errors) = buffered.into_iter().fold(
-        (Vec::new(), Vec::new(), Vec::new(), Vec::new()),
-        |(mut usages, mut triggers, mut errors, mut raw_errors), item| {
+    let (usages, triggers, errors, raw_errors, audits) = buffered.into_iter().fold(
+        (Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new()),
+        |(mut usages, mut triggers, mut errors, mut raw_errors, mut audits), item| {
             match item {
                 ReportingData::Usage(usage) => usages.push(*usage),
                 ReportingData::Trigger(trigger) => triggers.push(json::to_value(*trigger).unwrap()),
@@ -224,8 +228,9 @@ async fn ingest_buffered_data(thread_id: usize, buffered: Vec<ReportingData>) {
                     errors.push(json::to_value(&error_data).unwrap());
                     raw_errors.push(error_data);
                 }
+                ReportingData::SearchAudit(audit) => audits.push(json::to_value(*audit).unwrap()),
             }
-            (usages, triggers, errors, raw_errors)
+            (usages, triggers, errors, raw_errors, audits)
         },
     );
 
@@ -341,6 +346,13 @@ async fn ingest_buffered_data(thread_id: usize, buffered: Vec<ReportingData>) {
         }
     }
 
+    if !audits.is_empty() {
+        let audit_stream = StreamParams::new(META_ORG_ID, SEARCH_AUDIT_STREAM, StreamType::Logs);
+        if let Err(e) = super::ingestion::ingest_reporting_data(audits, audit_stream).await {
+            log::error!("[SELF-REPORTING] Error in ingesting SearchAuditData: {e}");
+        }
+    }
+
     if cfg.common.usage_reporting_errors_enabled && !errors.is_empty() {
         let error_stream = StreamParams::new(META_ORG_ID, ERROR_STREAM, StreamType::Logs);
         if let Err(e) = super::ingestion::ingest_reporting_data(errors, error_stream).await {
@@ -594,6 +606,7 @@ mod tests {
                         triggers.push(json::to_value(*trigger).unwrap())
                     }
                     ReportingData::Error(error) => errors.push(json::to_value(*error).unwrap()),
+                    ReportingData::SearchAudit(_) => {}
                 }
                 (usages, triggers, errors)
             },
@@ -620,6 +633,7 @@ mod tests {
                         triggers.push(json::to_value(*trigger).unwrap())
                     }
                     ReportingData::Error(error) => errors.push(json::to_value(*error).unwrap()),
+                    ReportingData::SearchAudit(_) => {}
                 }
                 (usages, triggers, errors)
             },
@@ -656,6 +670,7 @@ mod tests {
                         triggers.push(json::to_value(*trigger).unwrap())
                     }
                     ReportingData::Error(error) => errors.push(json::to_value(*error).unwrap()),
+                    ReportingData::SearchAudit(_) => {}
                 }
                 (usages, triggers, errors)
             },
@@ -54,7 +54,7 @@ use crate::{
     common::meta::{
         authz::Authz,
         http::HttpResponse as MetaHttpResponse,
-        stream::{FieldUpdate, Stream, StreamCreate},
+        stream::{FieldUpdate, Stream, StreamCreate, StreamRetention, StreamRetentionResponse},
     },
     handler::http::router::ERROR_HEADER,
     service::{
@@ -629,6 +629,10 @@ pub async fn update_stream_settings(
         }
     }
 
+    if let Some(archive_expired_data) = new_settings.archive_expired_data {
+        settings.archive_expired_data = archive_expired_data;
+    }
+
     if let Some(index_original_data) = new_settings.index_original_data {
         settings.index_original_data = index_original_data;
     }
@@ -702,6 +706,30 @@ pub async fn update_stream_settings(
             .retain(|field| !new_settings.bloom_filter_fields.remove.contains(field));
     }
 
+    // check for low cardinality fields, only text fields can be hinted
+    if !new_settings.low_cardinality_fields.add.is_empty() {
+        for field in new_settings.low_cardinality_fields.add.iter() {
+            if let Ok(f) = schema.field_with_name(field)
+                && f.data_type() != &DataType::Utf8
+                && f.data_type() != &DataType::LargeUtf8
+            {
+                return Ok(MetaHttpResponse::bad_request(format!(
+                    "low cardinality field [{field}] must be text field"
+                )));
+            }
+        }
+        settings
+            .low_cardinality_fields
+            .extend(new_settings.low_cardinality_fields.add);
+        settings.low_cardinality_fields.sort();
+        settings.low_cardinality_fields.dedup();
+    }
+    if !new_settings.low_cardinality_fields.remove.is_empty() {
+        settings
+            .low_cardinality_fields
+            .retain(|field| !new_settings.low_cardinality_fields.remove.contains(field));
+    }
+
     // check for index fields
     if !new_settings.index_fields.add.is_empty() {
         settings.index_fields.extend(new_settings.index_fields.add);
@@ -832,6 +860,147 @@ pub async fn update_stream_settings(
         settings.enable_log_patterns_extraction = enable_log_patterns_extraction;
     }
 
+    if let Some(ingest_priority) = new_settings.ingest_priority {
+        settings.ingest_priority = ingest_priority;
+    }
+
+    if let Some(ingest_quota) = new_settings.ingest_quota {
+        settings.ingest_quota = ingest_quota;
+    }
+
+    if let Some(ingest_dedup) = new_settings.ingest_dedup {
+        settings.ingest_dedup = ingest_dedup;
+    }
+
+    if let Some(wal_sync_policy) = new_settings.wal_sync_policy {
+        settings.wal_sync_policy = Some(wal_sync_policy);
+    }
+
+    if let Some(level_normalization) = new_settings.level_normalization {
+        settings.level_normalization = level_normalization;
+    }
+
+    if let Some(clock_skew) = new_settings.clock_skew {
+        settings.clock_skew = clock_skew;
+    }
+
+    if let Some(compact_sort) = new_settings.compact_sort {
+        if let Err(e) = compact_sort.validate() {
+            return Ok(MetaHttpResponse::bad_request(e));
+        }
+        // the files merged until now keep the sort, the searches can't read
+        // them as sorted by time
+        if compact_sort.is_empty() && !settings.compact_sort.is_empty() {
+            settings.compact_sort_removed_at = now_micros();
+        }
+        settings.compact_sort = compact_sort;
+    }
+
+    if let Some(tail_sampling) = new_settings.tail_sampling {
+        if tail_sampling.enabled && stream_type != StreamType::Traces {
+            return Ok(MetaHttpResponse::bad_request(
+                "tail sampling is only supported for traces streams",
+            ));
+        }
+        if let Err(e) = tail_sampling.validate() {
+            return Ok(MetaHttpResponse::bad_request(e));
+        }
+        settings.tail_sampling = tail_sampling;
+    }
+
+    if !new_settings.redaction_rules.remove.is_empty() {
+        settings.redaction_rules.retain(|rule| {
+            !new_settings
+                .redaction_rules
+                .remove
+                .iter()
+                .any(|r| r.name == rule.name)
+        });
+    }
+
+    if !new_settings.redaction_rules.add.is_empty() {
+        for rule in new_settings.redaction_rules.add {
+            if let Err(e) = rule.validate() {
+                return Ok(MetaHttpResponse::bad_request(e));
+            }
+            // a rule with the same name is replaced
+            settings.redaction_rules.retain(|r| r.name != rule.name);
+            settings.redaction_rules.push(rule);
+        }
+    }
+
+    if !new_settings.field_access_rules.remove.is_empty() {
+        settings.field_access_rules.retain(|rule| {
+            !new_settings
+                .field_access_rules
+                .remove
+                .iter()
+                .any(|r| r.field == rule.field)
+        });
+    }
+
+    if !new_settings.field_access_rules.add.is_empty() {
+        for rule in new_settings.field_access_rules.add {
+            if let Err(e) = rule.validate() {
+                return Ok(MetaHttpResponse::bad_request(e));
+            }
+            // one rule by field, a rule of the same field is replaced
+            settings
+                .field_access_rules
+                .retain(|r| r.field != rule.field);
+            settings.field_access_rules.push(rule);
+        }
+    }
+
+    if !new_settings.pinned_field_types.remove.is_empty() {
+        settings.pinned_field_types.retain(|field| {
+            !new_settings
+                .pinned_field_types
+                .remove
+                .iter()
+                .any(|f| f.name == field.name)
+        });
+    }
+
+    let pinned_fields = std::mem::take(&mut new_settings.pinned_field_types.add);
+    for field in pinned_fields.iter() {
+        if field.name.is_empty() || field.name == TIMESTAMP_COL_NAME {
+            return Ok(MetaHttpResponse::bad_request(format!(
+                "field [{}] can't be pinned to a type",
+                field.name
+            )));
+        }
+        settings.pinned_field_types.retain(|f| f.name != field.name);
+        settings.pinned_field_types.push(field.clone());
+    }
+
+    if !new_settings.computed_fields.remove.is_empty() {
+        settings.computed_fields.retain(|field| {
+            !new_settings
+                .computed_fields
+                .remove
+                .iter()
+                .any(|f| f.name == field.name)
+        });
+    }
+
+    if !new_settings.computed_fields.add.is_empty() {
+        for field in new_settings.computed_fields.add {
+            if let Err(e) = field.validate() {
+                return Ok(MetaHttpResponse::bad_request(e));
+            }
+            // a stored field always wins over a computed one of the same name
+            if schema.field_with_name(&field.name).is_ok() {
+                return Ok(MetaHttpResponse::bad_request(format!(
+                    "computed field {} is already a field of the stream",
+                    field.name
+                )));
+            }
+            settings.computed_fields.retain(|f| f.name != field.name);
+            settings.computed_fields.push(field);
+        }
+    }
+
     if !new_settings.full_text_search_keys.add.is_empty() {
         settings
             .full_text_search_keys
@@ -877,7 +1046,32 @@ pub async fn update_stream_settings(
         }
     }
 
-    save_stream_settings(org_id, stream_name, stream_type, settings).await
+    let resp = save_stream_settings(org_id, stream_name, stream_type, settings).await?;
+    if resp.status() != http::StatusCode::OK || pinned_fields.is_empty() {
+        return Ok(resp);
+    }
+    // migrate the latest schema to the pinned types, the merge starts a new
+    // version for the fields which had another type
+    let pinned_schema = Schema::new(
+        pinned_fields
+            .into_iter()
+            .map(|f| Field::new(f.name, f.r#type.into(), true))
+            .collect::<Vec<_>>(),
+    );
+    if let Err(e) = db::schema::merge(
+        org_id,
+        stream_name,
+        stream_type,
+        &pinned_schema,
+        Some(now_micros()),
+    )
+    .await
+    {
+        return Ok(MetaHttpResponse::internal_error(format!(
+            "error in migrating the schema to the pinned field types: {e}"
+        )));
+    }
+    Ok(resp)
 }
 
 #[tracing::instrument]
@@ -975,6 +1169,15 @@ pub async fn delete_stream(
         }
     }
 
+    // the aliases of the stream point nowhere now
+    if let Err(e) =
+        crate::service::stream_alias::delete_by_stream(org_id, stream_type, stream_name).await
+    {
+        log::error!(
+            "Failed to delete the aliases of stream: {org_id}/{stream_type}/{stream_name}, error: {e}"
+        );
+    }
+
     // create delete for compactor
     if let Err(e) =
         db::compact::retention::delete_stream(org_id, stream_type, stream_name, None).await
@@ -1060,6 +1263,46 @@ pub async fn stream_delete_inner(
         STREAM_RECORD_ID_GENERATOR.remove(&key);
     }
 
+    // delete the clock skew of the stream sources
+    if let Err(e) = super::ingestion::clock_skew::delete(org_id, stream_type, stream_name).await {
+        log::error!(
+            "Failed to delete clock skew for stream: {org_id}/{stream_type}/{stream_name}, error: {e}"
+        );
+    }
+
+    // delete the service map and the held spans of the stream
+    if stream_type == StreamType::Traces {
+        super::traces::tail_sampling::delete(org_id, stream_name);
+    }
+    if stream_type == StreamType::Traces
+        && let Err(e) = super::traces::service_map::delete(org_id, stream_name).await
+    {
+        log::error!("Failed to delete service map for stream: {org_id}/{stream_name}, error: {e}");
+    }
+
+    // delete stream field usage
+    if let Err(e) = super::field_usage::delete(org_id, stream_type, stream_name).await {
+        log::error!(
+            "Failed to delete field usage for stream: {org_id}/{stream_type}/{stream_name}, error: {e}"
+        );
+    }
+
+    // delete stream schema suggestion
+    if let Err(e) = super::schema_suggestion::delete(org_id, stream_type, stream_name).await {
+        log::error!(
+            "Failed to delete schema suggestion for stream: {org_id}/{stream_type}/{stream_name}, error: {e}"
+        );
+    }
+
+    // delete the patterns followed for the stream
+    if stream_type == StreamType::Logs
+        && let Err(e) = super::pattern_stats::delete(org_id, stream_name).await
+    {
+        log::error!(
+            "Failed to delete pattern stats for stream: {org_id}/{stream_name}, error: {e}"
+        );
+    }
+
     // delete stream compaction offset
     if let Err(e) = db::compact::files::del_offset(org_id, stream_type, stream_name).await {
         log::error!(
@@ -1083,46 +1326,15 @@ pub async fn delete_stream_data_by_time_range(
         ));
     }
 
-    // Convert the time range to RFC3339 format
-    // we need check the date is hour or day, user can't delete data with minute and second
+    // Convert the time range to RFC3339 format, the ranges not aligned to the
+    // partitions of the stream only delete some records of the files of their
+    // bounds, the compactor rewrites those files
     let stream_settings = get_settings(org_id, stream_name, stream_type)
         .await
         .unwrap_or_default();
     let partition_time_level =
         unwrap_partition_time_level(stream_settings.partition_time_level, stream_type);
-    let start_time = Utc.timestamp_nanos(time_range.start * 1000);
-    let end_time = Utc.timestamp_nanos(time_range.end * 1000);
-    let (start_time, end_time) = if partition_time_level == PartitionTimeLevel::Daily {
-        if start_time.hour() != 0 || start_time.minute() != 0 || start_time.second() != 0 {
-            return Err(infra::errors::Error::Message(
-                "Start time must be with zero hour, minute and second".to_string(),
-            ));
-        }
-        if end_time.hour() != 0 || end_time.minute() != 0 || end_time.second() != 0 {
-            return Err(infra::errors::Error::Message(
-                "End time must be with zero hour, minute and second".to_string(),
-            ));
-        }
-        (
-            start_time.format("%Y-%m-%d").to_string(),
-            end_time.format("%Y-%m-%d").to_string(),
-        )
-    } else {
-        if start_time.minute() != 0 || start_time.second() != 0 {
-            return Err(infra::errors::Error::Message(
-                "Start time must be with zero minute and second".to_string(),
-            ));
-        }
-        if end_time.minute() != 0 || end_time.second() != 0 {
-            return Err(infra::errors::Error::Message(
-                "End time must be with zero minute and second".to_string(),
-            ));
-        }
-        (
-            start_time.format("%Y-%m-%dT%H:00:00Z").to_string(),
-            end_time.format("%Y-%m-%dT%H:00:00Z").to_string(),
-        )
-    };
+    let (start_time, end_time) = deletion_range_keys(partition_time_level, &time_range);
 
     // Create a job to delete the data by the time range
     let (key, _created) = match crate::service::db::compact::retention::delete_stream(
@@ -1150,6 +1362,72 @@ pub async fn delete_stream_data_by_time_range(
     crate::service::db::compact::compactor_manual_jobs::add_job(job).await
 }
 
+pub async fn get_retention(
+    org_id: &str,
+    stream_name: &str,
+    stream_type: StreamType,
+) -> Option<StreamRetentionResponse> {
+    let settings = get_settings(org_id, stream_name, stream_type).await?;
+    let effective_days = if settings.data_retention > 0 {
+        settings.data_retention
+    } else {
+        get_config().compact.data_retention_days
+    };
+    Some(StreamRetentionResponse {
+        retention: StreamRetention {
+            days: settings.data_retention,
+            archive: settings.archive_expired_data,
+        },
+        effective_days,
+    })
+}
+
+pub async fn set_retention(
+    org_id: &str,
+    stream_name: &str,
+    stream_type: StreamType,
+    retention: StreamRetention,
+) -> Result<HttpResponse, Error> {
+    if retention.days < 0 {
+        return Ok(MetaHttpResponse::bad_request(
+            "retention days must be positive, or 0 for the retention of the cluster",
+        ));
+    }
+    let update = UpdateStreamSettings {
+        data_retention: Some(retention.days),
+        archive_expired_data: Some(retention.archive),
+        ..Default::default()
+    };
+    update_stream_settings(org_id, stream_name, stream_type, update).await
+}
+
+/// The dates of the job deleting the time range: days or hours when it is
+/// aligned to the partitions, RFC3339 timestamps with microseconds otherwise
+fn deletion_range_keys(
+    partition_time_level: PartitionTimeLevel,
+    time_range: &TimeRange,
+) -> (String, String) {
+    let start_time = Utc.timestamp_nanos(time_range.start * 1000);
+    let end_time = Utc.timestamp_nanos(time_range.end * 1000);
+    let is_aligned = |t: &chrono::DateTime<Utc>| {
+        t.minute() == 0
+            && t.second() == 0
+            && t.nanosecond() == 0
+            && (partition_time_level != PartitionTimeLevel::Daily || t.hour() == 0)
+    };
+    let format = if !is_aligned(&start_time) || !is_aligned(&end_time) {
+        "%Y-%m-%dT%H:%M:%S%.6fZ"
+    } else if partition_time_level == PartitionTimeLevel::Daily {
+        "%Y-%m-%d"
+    } else {
+        "%Y-%m-%dT%H:00:00Z"
+    };
+    (
+        start_time.format(format).to_string(),
+        end_time.format(format).to_string(),
+    )
+}
+
 async fn transform_stats(
     stats: &mut StreamStats,
     org_id: &str,
@@ -1335,6 +1613,35 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_deletion_range_keys() {
+        let day = TimeRange::new(1735689600000000, 1735776000000000); // 2025-01-01, 2025-01-02
+        assert_eq!(
+            deletion_range_keys(PartitionTimeLevel::Daily, &day),
+            ("2025-01-01".to_string(), "2025-01-02".to_string())
+        );
+        assert_eq!(
+            deletion_range_keys(PartitionTimeLevel::Hourly, &day),
+            (
+                "2025-01-01T00:00:00Z".to_string(),
+                "2025-01-02T00:00:00Z".to_string()
+            )
+        );
+        let hours = TimeRange::new(1735689600000000, 1735693200000000); // 00:00, 01:00
+        assert_eq!(
+            deletion_range_keys(PartitionTimeLevel::Daily, &hours),
+            (
+                "2025-01-01T00:00:00.000000Z".to_string(),
+                "2025-01-01T01:00:00.000000Z".to_string()
+            )
+        );
+        let window = TimeRange::new(1735689600000000, 1735689690000001);
+        assert_eq!(
+            deletion_range_keys(PartitionTimeLevel::Hourly, &window).1,
+            "2025-01-01T00:01:30.000001Z"
+        );
+    }
+
     #[test]
     fn test_stream_res() {
         let stats = StreamStats::default();
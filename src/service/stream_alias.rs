@@ -0,0 +1,225 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Stream aliases and renaming. An alias is another name of a stream: the
+//! SQL of searches, and so of alerts and dashboards, is rewritten to the
+//! stream before it is planned, and records ingested into the alias go to the
+//! stream. Renaming a stream adds the new name as an alias, the data stays
+//! under the old name so the saved queries using it keep working.
+
+use config::{
+    get_config,
+    meta::{
+        sql::{TableReferenceExt, resolve_stream_names_with_type},
+        stream::StreamType,
+    },
+    utils::{schema::format_stream_name, time::now_micros},
+};
+use sqlparser::{ast::VisitMut, dialect::PostgreSqlDialect, parser::Parser};
+
+use crate::{
+    common::{infra::config::STREAM_ALIASES, meta::stream::StreamAlias},
+    service::{db, search::sql::rewriter::replace_stream::ReplaceStreamVisitor},
+};
+
+fn alias_key(org_id: &str, stream_type: StreamType, alias: &str) -> String {
+    format!("{org_id}/{stream_type}/{alias}")
+}
+
+/// The stream the name is an alias of
+pub fn resolve(org_id: &str, stream_type: StreamType, name: &str) -> Option<String> {
+    STREAM_ALIASES
+        .get(&alias_key(org_id, stream_type, name))
+        .map(|v| v.stream_name.clone())
+}
+
+/// Rewrites the streams the SQL queries by an alias to their stream, returns
+/// None when it queries no alias
+pub fn resolve_sql(org_id: &str, stream_type: StreamType, sql: &str) -> Option<String> {
+    if STREAM_ALIASES.is_empty() {
+        return None;
+    }
+    let replaces = resolve_stream_names_with_type(sql)
+        .ok()?
+        .into_iter()
+        .filter_map(|stream| {
+            let name = stream.stream_name();
+            resolve(org_id, stream.get_stream_type(stream_type), &name).map(|to| (name, to))
+        })
+        .collect::<Vec<_>>();
+    if replaces.is_empty() {
+        return None;
+    }
+    let mut statement = Parser::parse_sql(&PostgreSqlDialect {}, sql).ok()?.pop()?;
+    for (from, to) in replaces.iter() {
+        let _ = statement.visit(&mut ReplaceStreamVisitor::new(from, to));
+    }
+    Some(statement.to_string())
+}
+
+async fn stream_exists(org_id: &str, stream_type: StreamType, stream_name: &str) -> bool {
+    infra::schema::get_cache(org_id, stream_name, stream_type)
+        .await
+        .is_ok_and(|schema| !schema.fields_map().is_empty())
+}
+
+/// Adds an alias to the stream, the alias can't be the name of a stream or of
+/// another alias
+pub async fn create(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    alias: &str,
+    renamed: bool,
+) -> Result<StreamAlias, anyhow::Error> {
+    let mut alias = alias.trim().to_string();
+    if !get_config().common.skip_formatting_stream_name {
+        alias = format_stream_name(alias);
+    }
+    if alias.is_empty() {
+        return Err(anyhow::anyhow!("alias can't be empty"));
+    }
+    // an alias of an alias is an alias of its stream
+    let stream_name =
+        resolve(org_id, stream_type, stream_name).unwrap_or_else(|| stream_name.to_string());
+    if alias == stream_name {
+        return Err(anyhow::anyhow!("alias can't be the name of the stream"));
+    }
+    if !stream_exists(org_id, stream_type, &stream_name).await {
+        return Err(anyhow::anyhow!("stream [{stream_name}] not found"));
+    }
+    if let Some(other) = resolve(org_id, stream_type, &alias) {
+        return Err(anyhow::anyhow!(
+            "[{alias}] is already an alias of stream [{other}]"
+        ));
+    }
+    if stream_exists(org_id, stream_type, &alias).await {
+        return Err(anyhow::anyhow!("stream [{alias}] already exists"));
+    }
+
+    let alias = StreamAlias {
+        alias,
+        stream_name,
+        stream_type,
+        renamed,
+        created_at: now_micros(),
+    };
+    db::stream_alias::set(org_id, &alias).await?;
+    // searches right after on this node must see it before the watch does
+    STREAM_ALIASES.insert(alias_key(org_id, stream_type, &alias.alias), alias.clone());
+    Ok(alias)
+}
+
+/// Renames the stream, by the new name it is searched and ingested into
+pub async fn rename(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    new_name: &str,
+) -> Result<StreamAlias, anyhow::Error> {
+    create(org_id, stream_type, stream_name, new_name, true).await
+}
+
+pub async fn delete(
+    org_id: &str,
+    stream_type: StreamType,
+    alias: &str,
+) -> Result<bool, anyhow::Error> {
+    if resolve(org_id, stream_type, alias).is_none() {
+        return Ok(false);
+    }
+    db::stream_alias::delete(org_id, stream_type, alias).await?;
+    STREAM_ALIASES.remove(&alias_key(org_id, stream_type, alias));
+    Ok(true)
+}
+
+/// The aliases of the stream, or of every stream of the org
+pub fn list(org_id: &str, stream_type: StreamType, stream_name: Option<&str>) -> Vec<StreamAlias> {
+    let prefix = format!("{org_id}/{stream_type}/");
+    let mut aliases = STREAM_ALIASES
+        .iter()
+        .filter(|v| v.key().starts_with(&prefix))
+        .filter(|v| stream_name.is_none_or(|name| v.value().stream_name == name))
+        .map(|v| v.value().clone())
+        .collect::<Vec<_>>();
+    aliases.sort_by(|a, b| a.alias.cmp(&b.alias));
+    aliases
+}
+
+/// Drops the aliases of a deleted stream
+pub async fn delete_by_stream(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> Result<(), anyhow::Error> {
+    for alias in list(org_id, stream_type, Some(stream_name)) {
+        delete(org_id, stream_type, &alias.alias).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add_alias(org_id: &str, alias: &str, stream_name: &str) {
+        STREAM_ALIASES.insert(
+            alias_key(org_id, StreamType::Logs, alias),
+            StreamAlias {
+                alias: alias.to_string(),
+                stream_name: stream_name.to_string(),
+                stream_type: StreamType::Logs,
+                renamed: false,
+                created_at: 0,
+            },
+        );
+    }
+
+    #[test]
+    fn test_resolve_sql() {
+        let org_id = "test_stream_alias_org";
+        add_alias(org_id, "web", "nginx_access");
+
+        assert_eq!(
+            resolve_sql(
+                org_id,
+                StreamType::Logs,
+                "SELECT code, count(*) FROM \"web\" GROUP BY code"
+            )
+            .as_deref(),
+            Some("SELECT code, count(*) FROM \"nginx_access\" GROUP BY code")
+        );
+        // the stream type of the query must match the one of the alias
+        assert_eq!(
+            resolve_sql(org_id, StreamType::Traces, "SELECT * FROM web"),
+            None
+        );
+        assert_eq!(
+            resolve_sql(org_id, StreamType::Logs, "SELECT * FROM nginx_access"),
+            None
+        );
+        assert_eq!(
+            resolve(org_id, StreamType::Logs, "web").as_deref(),
+            Some("nginx_access")
+        );
+        assert_eq!(
+            list(org_id, StreamType::Logs, Some("nginx_access")).len(),
+            1
+        );
+        assert!(list(org_id, StreamType::Logs, Some("other")).is_empty());
+
+        STREAM_ALIASES.remove(&alias_key(org_id, StreamType::Logs, "web"));
+    }
+}
@@ -0,0 +1,510 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Live tail of the streams
+//!
+//! The ingesters publish the records they write to the tails of their stream,
+//! nothing is published for the streams without a tail. A tail filters the
+//! records with its condition, a SQL expression like the WHERE clause of a
+//! search, and sends the matching ones to its client as server sent events.
+//! The tail requests are routed to an ingester, which also follows the tails
+//! of the stream on the other ingesters.
+//!
+//! The field access rules of the stream apply to the tails like to the
+//! searches. A tail too slow to follow its stream skips write batches, its
+//! client is told how many.
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use arrow::array::{Array, BooleanArray};
+use arrow_schema::Schema;
+use config::{
+    ORIGINAL_DATA_COL_NAME,
+    cluster::LOCAL_NODE,
+    get_config,
+    meta::stream::{DEFAULT_REDACTION_REPLACEMENT, FieldAccessMode, FieldAccessRule, StreamType},
+    utils::{
+        json::{self, Value},
+        record_batch_ext::convert_json_to_record_batch,
+    },
+};
+use datafusion::{
+    common::{DFSchema, TableReference},
+    physical_expr::PhysicalExpr,
+    prelude::SessionContext,
+};
+use futures::StreamExt;
+use hashbrown::{HashMap, HashSet};
+use infra::schema::SchemaCache;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use tokio::sync::{broadcast, mpsc};
+
+use crate::service::{record_deletion::parse_filter, search::field_access};
+
+/// Interval of the comments sent to keep the connection of an idle tail open
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+const HEARTBEAT: &str = ": ping\n\n";
+
+struct TailBatch {
+    schema: Arc<Schema>,
+    records: Vec<Arc<Value>>,
+}
+
+/// The tails of the streams on this node, by stream
+static TAILS: Lazy<RwLock<HashMap<String, broadcast::Sender<Arc<TailBatch>>>>> =
+    Lazy::new(Default::default);
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, thiserror::Error)]
+pub enum TailError {
+    #[error("Stream not found")]
+    StreamNotFound,
+    #[error("Invalid filter: {0}")]
+    InvalidFilter(String),
+    #[error("{0}")]
+    AccessDenied(String),
+    #[error("Too many live tails on this node, try again later")]
+    TooManyTails,
+}
+
+pub struct TailRequest {
+    pub org_id: String,
+    pub stream_type: StreamType,
+    pub stream_name: String,
+    pub filter: Option<String>,
+    pub user_id: String,
+}
+
+fn tail_key(org_id: &str, stream_type: &str, stream_name: &str) -> String {
+    format!("{org_id}/{stream_type}/{stream_name}")
+}
+
+/// Publishes the records written to the stream to its tails
+pub fn publish(
+    org_id: &str,
+    stream_type: &str,
+    stream_name: &str,
+    schema: &Arc<Schema>,
+    records: &[Arc<Value>],
+) {
+    let tails = TAILS.read();
+    if tails.is_empty() {
+        return;
+    }
+    let Some(tx) = tails.get(&tail_key(org_id, stream_type, stream_name)) else {
+        return;
+    };
+    // fails only without receivers, the last tail removes the sender
+    let _ = tx.send(Arc::new(TailBatch {
+        schema: schema.clone(),
+        records: records.to_vec(),
+    }));
+}
+
+/// A tail of a stream on this node, the stream is not published to anymore
+/// once its last tail is dropped
+pub struct Tail {
+    key: String,
+    rx: broadcast::Receiver<Arc<TailBatch>>,
+    filter: Option<Filter>,
+    rules: Vec<FieldAccessRule>,
+}
+
+pub enum TailEvent {
+    Records(Vec<Value>),
+    /// Write batches skipped as the tail could not follow
+    Lagged(u64),
+}
+
+impl TailEvent {
+    pub fn to_sse(&self) -> String {
+        match self {
+            TailEvent::Records(records) => {
+                format!(
+                    "event: records\ndata: {}\n\n",
+                    json::to_string(records).unwrap()
+                )
+            }
+            TailEvent::Lagged(skipped) => {
+                format!("event: lagged\ndata: {{\"skipped_batches\":{skipped}}}\n\n")
+            }
+        }
+    }
+}
+
+pub async fn open(req: &TailRequest) -> Result<Tail, TailError> {
+    let schema = infra::schema::get(&req.org_id, &req.stream_name, req.stream_type)
+        .await
+        .unwrap_or_else(|_| Schema::empty());
+    if schema.fields().is_empty() {
+        return Err(TailError::StreamNotFound);
+    }
+    let schema = Arc::new(schema);
+    let table = TableReference::from(req.stream_name.as_str());
+    let denied = field_access::denied_rules(
+        &req.org_id,
+        Some(&req.user_id),
+        &HashMap::from([(
+            table.clone(),
+            Arc::new(SchemaCache::new(schema.as_ref().clone())),
+        )]),
+    )
+    .await;
+
+    let filter = match req.filter.as_deref().map(str::trim) {
+        Some(filter) if !filter.is_empty() => {
+            let ctx = SessionContext::new();
+            let (_, fields) =
+                parse_filter(&ctx, &schema, filter).map_err(TailError::InvalidFilter)?;
+            field_access::check_columns(&denied, &HashMap::from([(table.clone(), fields.clone())]))
+                .map_err(|e| TailError::AccessDenied(e.to_string()))?;
+            Some(Filter {
+                ctx,
+                filter: filter.to_string(),
+                fields,
+                expr: None,
+            })
+        }
+        _ => None,
+    };
+
+    let cfg = get_config();
+    if RUNNING.fetch_add(1, Ordering::SeqCst) >= cfg.http_streaming.tail_max_per_node {
+        RUNNING.fetch_sub(1, Ordering::SeqCst);
+        return Err(TailError::TooManyTails);
+    }
+    let key = tail_key(&req.org_id, req.stream_type.as_str(), &req.stream_name);
+    let rx = TAILS
+        .write()
+        .entry(key.clone())
+        .or_insert_with(|| broadcast::channel(cfg.http_streaming.tail_buffer_size.max(1)).0)
+        .subscribe();
+    Ok(Tail {
+        key,
+        rx,
+        filter,
+        rules: denied.into_values().next().unwrap_or_default(),
+    })
+}
+
+impl Tail {
+    /// Waits for the next records of the stream matching the filter
+    pub async fn next(&mut self) -> Option<TailEvent> {
+        loop {
+            let batch = match self.rx.recv().await {
+                Ok(batch) => batch,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    return Some(TailEvent::Lagged(skipped));
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            };
+            let records = match self.filter.as_mut() {
+                Some(filter) => match filter.matching(&batch) {
+                    Ok(records) => records,
+                    Err(e) => {
+                        log::warn!("[TAIL] {} filter failed: {e}", self.key);
+                        continue;
+                    }
+                },
+                None => batch.records.iter().collect(),
+            };
+            if records.is_empty() {
+                continue;
+            }
+            let records = records
+                .into_iter()
+                .map(|record| hide_fields(record, &self.rules))
+                .collect();
+            return Some(TailEvent::Records(records));
+        }
+    }
+}
+
+impl Drop for Tail {
+    fn drop(&mut self) {
+        RUNNING.fetch_sub(1, Ordering::SeqCst);
+        let mut tails = TAILS.write();
+        // the receiver of this tail is only dropped after
+        if tails
+            .get(&self.key)
+            .is_some_and(|tx| tx.receiver_count() <= 1)
+        {
+            tails.remove(&self.key);
+        }
+    }
+}
+
+/// Sends the events of the tail to `tx` until it is closed, with a comment
+/// now and then so the connection of an idle tail is kept open
+pub async fn run(mut tail: Tail, tx: mpsc::Sender<String>) {
+    let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+    loop {
+        let event = tokio::select! {
+            event = tail.next() => match event {
+                Some(event) => event.to_sse(),
+                None => return,
+            },
+            _ = heartbeat.tick() => HEARTBEAT.to_string(),
+        };
+        if tx.send(event).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Follows the tails of the stream on the other ingesters, their events are
+/// sent to `tx` until it is closed. `path` is the path and query of the tail
+/// on the other nodes, `headers` authenticate the user there.
+pub async fn follow_remote(
+    path: String,
+    headers: reqwest::header::HeaderMap,
+    tx: mpsc::Sender<String>,
+) {
+    if LOCAL_NODE.is_single_node() {
+        return;
+    }
+    let nodes = infra::cluster::get_cached_online_ingester_nodes()
+        .await
+        .unwrap_or_default();
+    for node in nodes.into_iter().filter(|n| n.uuid != LOCAL_NODE.uuid) {
+        let url = format!("{}{path}", node.http_addr);
+        let headers = headers.clone();
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            if let Err(e) = follow_node(&url, headers, tx).await {
+                log::warn!(
+                    "[TAIL] following the tail of node {} failed: {e}",
+                    node.name
+                );
+            }
+        });
+    }
+}
+
+async fn follow_node(
+    url: &str,
+    headers: reqwest::header::HeaderMap,
+    tx: mpsc::Sender<String>,
+) -> Result<(), anyhow::Error> {
+    let resp = reqwest::Client::new()
+        .get(url)
+        .headers(headers)
+        .send()
+        .await?;
+    if !resp.status().is_success() {
+        return Err(anyhow::anyhow!("responded with status {}", resp.status()));
+    }
+    let mut body = resp.bytes_stream();
+    let mut buf = String::new();
+    while let Some(chunk) = body.next().await {
+        buf.push_str(&String::from_utf8_lossy(&chunk?));
+        for event in split_events(&mut buf) {
+            if tx.send(event).await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Takes the complete events out of the buffer, the comments are dropped as
+/// the tail sends its own
+fn split_events(buf: &mut String) -> Vec<String> {
+    let mut events = Vec::new();
+    while let Some(end) = buf.find("\n\n") {
+        let event: String = buf.drain(..end + 2).collect();
+        if !event.starts_with(':') {
+            events.push(event);
+        }
+    }
+    events
+}
+
+struct Filter {
+    ctx: SessionContext,
+    filter: String,
+    fields: HashSet<String>,
+    /// The condition planned for the schema of the last batch
+    expr: Option<(Arc<Schema>, Arc<dyn PhysicalExpr>)>,
+}
+
+impl Filter {
+    /// The records of the batch matching the condition, a batch without a
+    /// field of the condition has none
+    fn matching<'a>(&mut self, batch: &'a TailBatch) -> Result<Vec<&'a Arc<Value>>, anyhow::Error> {
+        if self
+            .fields
+            .iter()
+            .any(|f| batch.schema.field_with_name(f).is_err())
+        {
+            return Ok(Vec::new());
+        }
+        let expr = match &self.expr {
+            Some((schema, expr)) if schema == &batch.schema => expr.clone(),
+            _ => {
+                let df_schema = DFSchema::try_from(batch.schema.as_ref().clone())?;
+                let expr = self.ctx.parse_sql_expr(&self.filter, &df_schema)?;
+                let expr = self.ctx.create_physical_expr(expr, &df_schema)?;
+                self.expr = Some((batch.schema.clone(), expr.clone()));
+                expr
+            }
+        };
+        let rb = convert_json_to_record_batch(&batch.schema, &batch.records)?;
+        let matched = expr.evaluate(&rb)?.into_array(rb.num_rows())?;
+        let Some(matched) = matched.as_any().downcast_ref::<BooleanArray>() else {
+            return Err(anyhow::anyhow!("the filter is not a condition"));
+        };
+        Ok(batch
+            .records
+            .iter()
+            .zip(matched.iter())
+            .filter_map(|(record, matched)| (matched == Some(true)).then_some(record))
+            .collect())
+    }
+}
+
+/// Applies the field access rules denying the user fields of the stream, the
+/// records as received are denied with any field
+fn hide_fields(record: &Value, rules: &[FieldAccessRule]) -> Value {
+    let mut record = record.clone();
+    if rules.is_empty() {
+        return record;
+    }
+    let Some(fields) = record.as_object_mut() else {
+        return record;
+    };
+    fields.remove(ORIGINAL_DATA_COL_NAME);
+    for rule in rules {
+        match rule.mode {
+            FieldAccessMode::Strip => {
+                fields.remove(&rule.field);
+            }
+            FieldAccessMode::Mask => {
+                if let Some(value) = fields.get_mut(&rule.field)
+                    && !value.is_null()
+                {
+                    *value = Value::from(DEFAULT_REDACTION_REPLACEMENT);
+                }
+            }
+        }
+    }
+    record
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_schema::{DataType, Field};
+
+    use super::*;
+
+    fn batch() -> TailBatch {
+        TailBatch {
+            schema: Arc::new(Schema::new(vec![
+                Field::new("_timestamp", DataType::Int64, false),
+                Field::new("level", DataType::Utf8, true),
+                Field::new("took", DataType::Int64, true),
+            ])),
+            records: vec![
+                Arc::new(json::json!({"_timestamp": 1, "level": "error", "took": 20})),
+                Arc::new(json::json!({"_timestamp": 2, "level": "info", "took": 30})),
+                Arc::new(json::json!({"_timestamp": 3, "level": "error", "took": 5})),
+            ],
+        }
+    }
+
+    fn filter(filter: &str) -> Filter {
+        let ctx = SessionContext::new();
+        let (_, fields) = parse_filter(&ctx, &batch().schema, filter).unwrap();
+        Filter {
+            ctx,
+            filter: filter.to_string(),
+            fields,
+            expr: None,
+        }
+    }
+
+    #[test]
+    fn test_filter_matching() {
+        let batch = batch();
+        let mut f = filter("level = 'error' AND took > 10");
+        let records = f.matching(&batch).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0]["_timestamp"], 1);
+        // planned once per schema
+        assert!(f.expr.is_some());
+        assert_eq!(f.matching(&batch).unwrap().len(), 1);
+
+        let other = TailBatch {
+            schema: Arc::new(Schema::new(vec![Field::new(
+                "_timestamp",
+                DataType::Int64,
+                false,
+            )])),
+            records: vec![Arc::new(json::json!({"_timestamp": 4}))],
+        };
+        assert!(f.matching(&other).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_hide_fields() {
+        let record =
+            json::json!({"email": "a@b.c", "ssn": "123", "level": "info", "_original": "{}"});
+        let rules = vec![
+            FieldAccessRule {
+                field: "ssn".to_string(),
+                denied_roles: vec!["analyst".to_string()],
+                mode: FieldAccessMode::Strip,
+            },
+            FieldAccessRule {
+                field: "email".to_string(),
+                denied_roles: vec!["analyst".to_string()],
+                mode: FieldAccessMode::Mask,
+            },
+        ];
+        assert_eq!(hide_fields(&record, &[]), record);
+        assert_eq!(
+            hide_fields(&record, &rules),
+            json::json!({"email": DEFAULT_REDACTION_REPLACEMENT, "level": "info"})
+        );
+    }
+
+    #[test]
+    fn test_split_events() {
+        let mut buf = "event: records\ndata: []\n\n: ping\n\nevent: lagged\ndata: {".to_string();
+        let events = split_events(&mut buf);
+        assert_eq!(events, vec!["event: records\ndata: []\n\n".to_string()]);
+        assert_eq!(buf, "event: lagged\ndata: {");
+    }
+
+    #[test]
+    fn test_event_to_sse() {
+        let event = TailEvent::Records(vec![json::json!({"level": "error"})]);
+        assert_eq!(
+            event.to_sse(),
+            "event: records\ndata: [{\"level\":\"error\"}]\n\n"
+        );
+        assert_eq!(
+            TailEvent::Lagged(3).to_sse(),
+            "event: lagged\ndata: {\"skipped_batches\":3}\n\n"
+        );
+    }
+}
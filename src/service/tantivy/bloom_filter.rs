@@ -0,0 +1,156 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Bloom filters of the values of a column of a parquet file, stored as blobs
+//! of its index file. A search for `field = 'value'` skips the files whose
+//! filter doesn't contain the value without downloading them, which the
+//! bloom filters inside the parquet files can't do.
+//!
+//! Blob layout: num_hashes [u32 LE] num_bits [u64 LE] bits [u64 LE]*
+
+use anyhow::{Result, ensure};
+use config::utils::hash::{Sum64, fnv, murmur3};
+use hashbrown::HashSet;
+
+/// False positive rate the filters are sized for
+const DEFAULT_FPP: f64 = 0.01;
+const HEADER_SIZE: usize = 12;
+/// Filters larger than this aren't worth reading before the file
+const MAX_NUM_BITS: u64 = 64 * 1024 * 1024;
+
+/// Blob tag of the filter of the field in the index file
+pub fn blob_tag(field: &str) -> String {
+    format!("bloom/{field}")
+}
+
+/// Collects the distinct values of a column
+#[derive(Debug, Default)]
+pub struct BloomFilterBuilder {
+    hashes: HashSet<(u64, u64)>,
+}
+
+impl BloomFilterBuilder {
+    pub fn insert(&mut self, value: &str) {
+        self.hashes.insert(hash(value));
+    }
+
+    pub fn build(self) -> BloomFilter {
+        let n = self.hashes.len().max(1) as f64;
+        let ln2 = std::f64::consts::LN_2;
+        let num_bits = (-n * DEFAULT_FPP.ln() / (ln2 * ln2)).ceil() as u64;
+        let num_bits = num_bits.clamp(64, MAX_NUM_BITS).next_multiple_of(64);
+        let num_hashes = ((num_bits as f64 / n) * ln2).round().clamp(1.0, 16.0) as u32;
+        let mut filter = BloomFilter {
+            num_hashes,
+            num_bits,
+            bits: vec![0; (num_bits / 64) as usize],
+        };
+        for (h1, h2) in self.hashes {
+            for i in 0..num_hashes {
+                let bit = filter.bit(h1, h2, i);
+                filter.bits[(bit / 64) as usize] |= 1 << (bit % 64);
+            }
+        }
+        filter
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BloomFilter {
+    num_hashes: u32,
+    num_bits: u64,
+    bits: Vec<u64>,
+}
+
+impl BloomFilter {
+    /// False when the value is surely not in the column
+    pub fn contains(&self, value: &str) -> bool {
+        let (h1, h2) = hash(value);
+        (0..self.num_hashes).all(|i| {
+            let bit = self.bit(h1, h2, i);
+            self.bits[(bit / 64) as usize] & (1 << (bit % 64)) != 0
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(HEADER_SIZE + self.bits.len() * 8);
+        buf.extend_from_slice(&self.num_hashes.to_le_bytes());
+        buf.extend_from_slice(&self.num_bits.to_le_bytes());
+        for word in &self.bits {
+            buf.extend_from_slice(&word.to_le_bytes());
+        }
+        buf
+    }
+
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        ensure!(buf.len() >= HEADER_SIZE, "bloom filter too short");
+        let num_hashes = u32::from_le_bytes(buf[0..4].try_into()?);
+        let num_bits = u64::from_le_bytes(buf[4..12].try_into()?);
+        let words = &buf[HEADER_SIZE..];
+        ensure!(
+            num_hashes > 0
+                && num_bits > 0
+                && num_bits % 64 == 0
+                && words.len() as u64 == num_bits / 8,
+            "invalid bloom filter"
+        );
+        let bits = words
+            .chunks_exact(8)
+            .map(|w| u64::from_le_bytes(w.try_into().unwrap()))
+            .collect();
+        Ok(Self {
+            num_hashes,
+            num_bits,
+            bits,
+        })
+    }
+
+    /// The i-th bit of the value, by double hashing
+    fn bit(&self, h1: u64, h2: u64, i: u32) -> u64 {
+        h1.wrapping_add((i as u64).wrapping_mul(h2)) % self.num_bits
+    }
+}
+
+/// Two hashes of the value which don't change across versions, the filters
+/// are read long after they are written
+fn hash(value: &str) -> (u64, u64) {
+    (murmur3::new().sum64(value), fnv::new().sum64(value) | 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bloom_filter() {
+        let mut builder = BloomFilterBuilder::default();
+        for i in 0..10_000 {
+            builder.insert(&format!("trace-{i}"));
+        }
+        let filter = builder.build();
+        assert!((0..10_000).all(|i| filter.contains(&format!("trace-{i}"))));
+        let false_positives = (10_000..20_000)
+            .filter(|i| filter.contains(&format!("trace-{i}")))
+            .count();
+        assert!(false_positives < 300, "false positives: {false_positives}");
+
+        let decoded = BloomFilter::from_bytes(&filter.to_bytes()).unwrap();
+        assert_eq!(decoded, filter);
+        assert!(BloomFilter::from_bytes(&[0; 4]).is_err());
+
+        let empty = BloomFilterBuilder::default().build();
+        assert!(!empty.contains("trace-1"));
+    }
+}
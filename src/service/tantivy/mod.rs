@@ -13,14 +13,20 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+pub mod bloom_filter;
+pub mod offload;
 pub mod puffin;
 pub mod puffin_directory;
 
 use std::{collections::HashMap, sync::Arc};
 
 use anyhow::Context;
-use arrow::array::{Array, BooleanArray, Float64Array, Int64Array, StringArray, UInt64Array};
+use arrow::{
+    array::{Array, BooleanArray, Float64Array, Int64Array, StringArray, UInt64Array},
+    record_batch::RecordBatch,
+};
 use arrow_schema::{DataType, Schema};
+use bloom_filter::{BloomFilter, BloomFilterBuilder};
 use bytes::Bytes;
 use config::{
     INDEX_FIELD_NAME_FOR_ALL, TIMESTAMP_COL_NAME, get_config,
@@ -41,6 +47,7 @@ pub(crate) async fn create_tantivy_index(
     parquet_file_name: &str,
     full_text_search_fields: &[String],
     index_fields: &[String],
+    bloom_filter_fields: &[String],
     schema: Arc<Schema>,
     reader: ParquetRecordBatchStream<std::io::Cursor<Bytes>>,
 ) -> Result<usize, anyhow::Error> {
@@ -48,17 +55,21 @@ pub(crate) async fn create_tantivy_index(
     let caller = format!("[{caller}:JOB]");
 
     let dir = PuffinDirWriter::new();
-    let index = generate_tantivy_index(
+    let (index, bloom_filters) = generate_index(
         dir.clone(),
         reader,
         full_text_search_fields,
         index_fields,
+        bloom_filter_fields,
         schema,
     )
     .await?;
-    if index.is_none() {
+    if index.is_none() && bloom_filters.is_empty() {
         return Ok(0);
     }
+    for (field, filter) in bloom_filters {
+        dir.add_bloom_filter(&field, filter);
+    }
     let puffin_bytes = dir.to_puffin_bytes()?;
     let index_size = puffin_bytes.len();
 
@@ -100,11 +111,34 @@ pub(crate) async fn create_tantivy_index(
 /// Create a tantivy index in the given directory for the record batch
 pub(crate) async fn generate_tantivy_index<D: tantivy::Directory>(
     tantivy_dir: D,
-    mut reader: ParquetRecordBatchStream<std::io::Cursor<Bytes>>,
+    reader: ParquetRecordBatchStream<std::io::Cursor<Bytes>>,
     full_text_search_fields: &[String],
     index_fields: &[String],
     schema: Arc<Schema>,
 ) -> Result<Option<tantivy::Index>, anyhow::Error> {
+    let (index, _) = generate_index(
+        tantivy_dir,
+        reader,
+        full_text_search_fields,
+        index_fields,
+        &[],
+        schema,
+    )
+    .await?;
+    Ok(index)
+}
+
+/// Create a tantivy index in the given directory and the bloom filters of the
+/// string columns of `bloom_filter_fields`, the bloom filters are built even
+/// when there is nothing to index
+async fn generate_index<D: tantivy::Directory>(
+    tantivy_dir: D,
+    mut reader: ParquetRecordBatchStream<std::io::Cursor<Bytes>>,
+    full_text_search_fields: &[String],
+    index_fields: &[String],
+    bloom_filter_fields: &[String],
+    schema: Arc<Schema>,
+) -> Result<(Option<tantivy::Index>, Vec<(String, BloomFilter)>), anyhow::Error> {
     let mut tantivy_schema_builder = tantivy::schema::SchemaBuilder::new();
     let schema_fields = schema
         .fields()
@@ -132,9 +166,24 @@ pub(crate) async fn generate_tantivy_index<D: tantivy::Directory>(
         .union(&index_fields)
         .cloned()
         .collect::<HashSet<_>>();
+    let mut bloom_filters = bloom_filter_fields
+        .iter()
+        .filter(|f| {
+            schema_fields
+                .get(f)
+                .is_some_and(|v| v.data_type() == &DataType::Utf8)
+        })
+        .map(|f| (f.to_string(), BloomFilterBuilder::default()))
+        .collect::<Vec<_>>();
 
     if tantivy_fields.is_empty() {
-        return Ok(None);
+        if bloom_filters.is_empty() {
+            return Ok((None, vec![]));
+        }
+        while let Some(batch) = reader.try_next().await? {
+            add_to_bloom_filters(&batch, &mut bloom_filters);
+        }
+        return Ok((None, build_bloom_filters(bloom_filters)));
     }
 
     // add fields to tantivy schema
@@ -178,7 +227,8 @@ pub(crate) async fn generate_tantivy_index<D: tantivy::Directory>(
     // docs per row to be added in the tantivy index
     log::debug!("start write documents to tantivy index");
     let (tx, mut rx) = tokio::sync::mpsc::channel::<Vec<tantivy::TantivyDocument>>(2);
-    let task: JoinHandle<Result<usize, anyhow::Error>> = tokio::task::spawn(async move {
+    type IndexTask = JoinHandle<Result<(usize, Vec<(String, BloomFilterBuilder)>), anyhow::Error>>;
+    let task: IndexTask = tokio::task::spawn(async move {
         let mut total_num_rows = 0;
         loop {
             let batch = reader.try_next().await?;
@@ -189,6 +239,7 @@ pub(crate) async fn generate_tantivy_index<D: tantivy::Directory>(
             if num_rows == 0 {
                 continue;
             }
+            add_to_bloom_filters(&inverted_idx_batch, &mut bloom_filters);
 
             // update total_num_rows
             total_num_rows += num_rows;
@@ -287,7 +338,7 @@ pub(crate) async fn generate_tantivy_index<D: tantivy::Directory>(
 
             tx.send(docs).await?;
         }
-        Ok(total_num_rows)
+        Ok((total_num_rows, bloom_filters))
     });
 
     while let Some(docs) = rx.recv().await {
@@ -299,7 +350,7 @@ pub(crate) async fn generate_tantivy_index<D: tantivy::Directory>(
             tokio::task::coop::consume_budget().await;
         }
     }
-    let total_num_rows = task.await??;
+    let (total_num_rows, bloom_filters) = task.await??;
     // Create index even with 0 rows since we have valid configured fields in stream schema
     // (empty index acts as a marker to prevent expensive DataFusion scans)
     log::debug!(
@@ -316,7 +367,30 @@ pub(crate) async fn generate_tantivy_index<D: tantivy::Directory>(
     })
     .await??;
 
-    Ok(Some(index))
+    Ok((Some(index), build_bloom_filters(bloom_filters)))
+}
+
+fn add_to_bloom_filters(batch: &RecordBatch, bloom_filters: &mut [(String, BloomFilterBuilder)]) {
+    for (field, builder) in bloom_filters.iter_mut() {
+        let Some(array) = batch
+            .column_by_name(field)
+            .and_then(|c| c.as_any().downcast_ref::<StringArray>())
+        else {
+            continue;
+        };
+        for value in array.iter().flatten() {
+            builder.insert(value);
+        }
+    }
+}
+
+fn build_bloom_filters(
+    bloom_filters: Vec<(String, BloomFilterBuilder)>,
+) -> Vec<(String, BloomFilter)> {
+    bloom_filters
+        .into_iter()
+        .map(|(field, builder)| (field, builder.build()))
+        .collect()
 }
 
 #[cfg(test)]
@@ -422,6 +496,33 @@ mod tests {
             .unwrap()
     }
 
+    #[tokio::test]
+    async fn test_generate_index_bloom_filters() {
+        let dir = RamDirectory::create();
+        let batch = create_test_batch(10, true, false, true);
+        let stream = create_test_stream(vec![batch.clone()]).await;
+
+        let (index, bloom_filters) = generate_index(
+            dir,
+            stream,
+            &[],
+            &[],
+            &["status".to_string()],
+            batch.schema(),
+        )
+        .await
+        .unwrap();
+
+        // bloom filters don't need a tantivy index
+        assert!(index.is_none());
+        assert_eq!(bloom_filters.len(), 1);
+        let (field, filter) = &bloom_filters[0];
+        assert_eq!(field, "status");
+        assert!(filter.contains("success"));
+        assert!(filter.contains("error"));
+        assert!(!filter.contains("pending"));
+    }
+
     #[tokio::test]
     async fn test_generate_tantivy_index_empty_data() {
         let dir = RamDirectory::create();
@@ -729,6 +830,7 @@ mod tests {
             "test_file.parquet",
             &["content".to_string()],
             &["status".to_string()],
+            &[],
             empty_batch.schema(),
             stream,
         )
@@ -749,6 +851,7 @@ mod tests {
             "test_file.parquet",
             &[], // No FTS fields
             &[], // No index fields
+            &[], // No bloom filter fields
             batch.schema(),
             stream,
         )
@@ -769,6 +872,7 @@ mod tests {
             "invalid_filename", // This won't convert to a valid tantivy filename
             &["content".to_string()],
             &["status".to_string()],
+            &[],
             batch.schema(),
             stream,
         )
@@ -0,0 +1,196 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Building the inverted index of new files on the indexer nodes.
+//!
+//! With `ZO_INVERTED_INDEX_OFFLOAD_ENABLED`, an ingester uploads a new file
+//! without its index and queues an index job once the file is in the file
+//! list. An indexer node takes the job, builds the index from the uploaded
+//! file and sets the index size of the file, from then on the searches use
+//! the index. Until then the file is searched without it.
+
+use std::sync::Arc;
+
+use config::{
+    cluster::LOCAL_NODE,
+    get_config,
+    meta::stream::StreamType,
+    utils::{json, parquet::get_recordbatch_reader_from_bytes},
+};
+use infra::{
+    file_list as infra_file_list,
+    queue::{QueueConfigBuilder, RetentionPolicy},
+    schema::{
+        SchemaCache, get_stream_setting_fts_fields, get_stream_setting_index_bloom_filter_fields,
+        get_stream_setting_index_fields,
+    },
+    storage,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::OnceCell;
+
+use super::create_tantivy_index;
+use crate::service::schema::generate_schema_for_defined_schema_fields;
+
+pub const QUEUE_TOPIC: &str = "inverted_index_jobs";
+
+static QUEUE_CREATED: OnceCell<()> = OnceCell::const_new();
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IndexJob {
+    pub account: String,
+    pub key: String,
+}
+
+/// Whether the ingesters should leave the index to an indexer node
+pub async fn should_offload() -> bool {
+    get_config().common.inverted_index_offload_enabled
+        && !LOCAL_NODE.is_indexer()
+        && infra::cluster::get_cached_online_indexer_nodes()
+            .await
+            .is_some_and(|nodes| !nodes.is_empty())
+}
+
+pub async fn create_queue() -> infra::errors::Result<()> {
+    QUEUE_CREATED
+        .get_or_try_init(|| async {
+            infra::queue::get_queue()
+                .await
+                .create_with_config(
+                    QUEUE_TOPIC,
+                    QueueConfigBuilder::new()
+                        .retention_policy(RetentionPolicy::Limits)
+                        .build(),
+                )
+                .await
+        })
+        .await
+        .map(|_| ())
+}
+
+/// Queues the index job of a file, the index is built here when the job
+/// can't be queued
+pub async fn offload(account: &str, key: &str) {
+    let job = IndexJob {
+        account: account.to_string(),
+        key: key.to_string(),
+    };
+    let ret = match create_queue().await {
+        Ok(_) => match json::to_vec(&job) {
+            Ok(data) => {
+                infra::queue::get_queue()
+                    .await
+                    .publish(QUEUE_TOPIC, data.into())
+                    .await
+            }
+            Err(e) => Err(e.into()),
+        },
+        Err(e) => Err(e),
+    };
+    let Err(e) = ret else {
+        return;
+    };
+    log::warn!("[INDEXER] queue index job of {key} error: {e}, building it on this node");
+    if let Err(e) = build(&job).await {
+        log::error!("[INDEXER] build index of {key} error: {e}");
+    }
+}
+
+/// Builds the index of the file of a job, returns its size
+pub async fn build(job: &IndexJob) -> Result<usize, anyhow::Error> {
+    let Some((org_id, stream_type, stream_name)) = parse_file_key(&job.key) else {
+        return Err(anyhow::anyhow!("invalid file key: {}", job.key));
+    };
+    // the file was merged or deleted in the meantime
+    if !infra_file_list::contains(&job.key).await? {
+        log::info!("[INDEXER] skip index of {}, the file is gone", job.key);
+        return Ok(0);
+    }
+
+    let schema = infra::schema::get(org_id, stream_name, stream_type).await?;
+    let stream_settings = infra::schema::unwrap_stream_settings(&schema);
+    let full_text_search_fields = get_stream_setting_fts_fields(&stream_settings);
+    let index_fields = get_stream_setting_index_fields(&stream_settings);
+    let bloom_filter_fields = get_stream_setting_index_bloom_filter_fields(&stream_settings);
+    let schema = Arc::new(schema);
+    let schema = match stream_settings {
+        Some(s) if !s.defined_schema_fields.is_empty() => {
+            generate_schema_for_defined_schema_fields(
+                stream_type,
+                &SchemaCache::new(schema.as_ref().clone()),
+                &s.defined_schema_fields,
+                s.store_original_data,
+                s.index_original_data,
+                s.index_all_values,
+            )
+            .schema()
+            .clone()
+        }
+        _ => schema,
+    };
+
+    let data = storage::get_bytes(&job.account, &job.key).await?;
+    let (_, reader) = get_recordbatch_reader_from_bytes(&data).await?;
+    let index_size = create_tantivy_index(
+        "INDEXER",
+        &job.key,
+        &full_text_search_fields,
+        &index_fields,
+        &bloom_filter_fields,
+        schema,
+        reader,
+    )
+    .await?;
+    if index_size > 0 {
+        infra_file_list::update_index_size(&job.key, index_size as i64).await?;
+    }
+    Ok(index_size)
+}
+
+/// eg: files/default/logs/olympics/2023/08/21/08/7099303408192061440f3XQ2p.parquet
+fn parse_file_key(key: &str) -> Option<(&str, StreamType, &str)> {
+    let columns = key.splitn(5, '/').collect::<Vec<_>>();
+    if columns.len() < 5 || columns[0] != "files" {
+        return None;
+    }
+    Some((columns[1], StreamType::from(columns[2]), columns[3]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_file_key() {
+        assert_eq!(
+            parse_file_key(
+                "files/default/logs/olympics/2023/08/21/08/7099303408192061440f3XQ2p.parquet"
+            ),
+            Some(("default", StreamType::Logs, "olympics"))
+        );
+        assert_eq!(parse_file_key("files/default/logs"), None);
+        assert_eq!(parse_file_key("wal/default/logs/olympics/x.parquet"), None);
+    }
+
+    #[test]
+    fn test_index_job_roundtrip() {
+        let job = IndexJob {
+            account: "default".to_string(),
+            key: "files/default/logs/olympics/2023/08/21/08/a.parquet".to_string(),
+        };
+        let data = json::to_vec(&job).unwrap();
+        assert_eq!(json::from_slice::<IndexJob>(&data).unwrap(), job);
+    }
+}
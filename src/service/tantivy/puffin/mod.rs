@@ -114,6 +114,8 @@ pub enum BlobTypes {
     O2TtvV1,
     #[serde(rename = "o2-ttv-footer-v1")]
     O2TtvFooterV1,
+    #[serde(rename = "o2-bloom-v1")]
+    O2BloomV1,
 }
 
 #[derive(Default)]
@@ -222,6 +224,7 @@ mod tests {
             (BlobTypes::O2FstV1, "\"o2-fst-v1\""),
             (BlobTypes::O2TtvV1, "\"o2-ttv-v1\""),
             (BlobTypes::O2TtvFooterV1, "\"o2-ttv-footer-v1\""),
+            (BlobTypes::O2BloomV1, "\"o2-bloom-v1\""),
         ];
 
         for (blob_type, expected_json) in types {
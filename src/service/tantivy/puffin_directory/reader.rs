@@ -29,7 +29,8 @@ use tantivy::{
 };
 
 use crate::service::tantivy::{
-    puffin::{BlobMetadata, reader::PuffinBytesReader},
+    bloom_filter::{self, BloomFilter},
+    puffin::{BlobMetadata, BlobTypes, reader::PuffinBytesReader},
     puffin_directory::{
         EMPTY_PUFFIN_DIRECTORY, EMPTY_PUFFIN_SEG_ID, get_file_from_empty_puffin_dir_with_ext,
     },
@@ -67,6 +68,29 @@ impl PuffinDirReader {
     }
 }
 
+impl PuffinDirReader {
+    /// The bloom filter of the field, None when the index has none
+    pub async fn bloom_filter(&self, field: &str) -> io::Result<Option<BloomFilter>> {
+        let Some(meta) = self
+            .blobs_metadata
+            .get(Path::new(&bloom_filter::blob_tag(field)))
+        else {
+            return Ok(None);
+        };
+        if meta.blob_type != BlobTypes::O2BloomV1 {
+            return Ok(None);
+        }
+        let data = self
+            .source
+            .read_blob_bytes(meta, None)
+            .await
+            .map_err(io::Error::other)?;
+        BloomFilter::from_bytes(&data)
+            .map(Some)
+            .map_err(io::Error::other)
+    }
+}
+
 impl Clone for PuffinDirReader {
     fn clone(&self) -> Self {
         PuffinDirReader {
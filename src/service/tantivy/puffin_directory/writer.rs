@@ -28,6 +28,7 @@ use tantivy::{
 
 use super::{FOOTER_CACHE, footer_cache::build_footer_cache};
 use crate::service::tantivy::{
+    bloom_filter::{self, BloomFilter},
     puffin::{BlobTypes, writer::PuffinBytesWriter},
     puffin_directory::{ALLOWED_FILE_EXT, META_JSON},
 };
@@ -38,6 +39,8 @@ pub struct PuffinDirWriter {
     ram_directory: Arc<RamDirectory>,
     /// record all the files paths in the puffin file
     file_paths: Arc<RwLock<HashSet<PathBuf>>>,
+    /// bloom filters of the columns, by field
+    bloom_filters: Arc<RwLock<Vec<(String, BloomFilter)>>>,
 }
 
 impl Default for PuffinDirWriter {
@@ -51,6 +54,7 @@ impl Clone for PuffinDirWriter {
         PuffinDirWriter {
             ram_directory: self.ram_directory.clone(),
             file_paths: self.file_paths.clone(),
+            bloom_filters: self.bloom_filters.clone(),
         }
     }
 }
@@ -60,9 +64,19 @@ impl PuffinDirWriter {
         PuffinDirWriter {
             ram_directory: Arc::new(RamDirectory::create()),
             file_paths: Arc::new(RwLock::new(HashSet::default())),
+            bloom_filters: Arc::new(RwLock::new(Vec::new())),
         }
     }
 
+    /// Adds the bloom filter of a column, written as a blob next to the
+    /// tantivy files
+    pub fn add_bloom_filter(&self, field: &str, filter: BloomFilter) {
+        self.bloom_filters
+            .write()
+            .expect("poisoned lock")
+            .push((field.to_string(), filter));
+    }
+
     pub fn list_files(&self) -> Vec<PathBuf> {
         self.file_paths
             .read()
@@ -113,6 +127,16 @@ impl PuffinDirWriter {
                 .context("Failed to add blob")?;
         }
 
+        for (field, filter) in self.bloom_filters.read().expect("poisoned lock").iter() {
+            puffin_writer
+                .add_blob(
+                    &filter.to_bytes(),
+                    BlobTypes::O2BloomV1,
+                    bloom_filter::blob_tag(field),
+                )
+                .context("Failed to add bloom filter blob")?;
+        }
+
         // write footer cache
         let meta_bytes = build_footer_cache(self.ram_directory.clone())?;
         puffin_writer
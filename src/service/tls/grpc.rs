@@ -0,0 +1,213 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! TLS of the gRPC servers
+//!
+//! The servers accept their connections through [`incoming`], which
+//! handshakes with the current server config. The config is swapped when the
+//! certificate files change, so renewed certificates are used by the new
+//! connections without a restart, and the cached client channels are dropped
+//! so the nodes present the new ones too. With client auth enabled the
+//! clients, other nodes and OTLP senders alike, must present a certificate
+//! signed by the configured CA.
+
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    io::BufReader,
+    net::SocketAddr,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use arc_swap::ArcSwapOption;
+use config::get_config;
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use rustls::{RootCertStore, ServerConfig, server::WebPkiClientVerifier};
+use rustls_pemfile::{certs, private_key};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::mpsc,
+};
+use tokio_rustls::{TlsAcceptor, server::TlsStream};
+use tokio_stream::wrappers::ReceiverStream;
+
+static SERVER_CONFIG: Lazy<ArcSwapOption<ServerConfig>> = Lazy::new(ArcSwapOption::empty);
+/// Hash of the certificate files the server config was loaded from
+static FILES_HASH: AtomicU64 = AtomicU64::new(0);
+
+/// Loads the server config, it must be called before the servers start
+pub fn init() -> Result<(), anyhow::Error> {
+    let hash = files_hash()?;
+    SERVER_CONFIG.store(Some(Arc::new(server_config()?)));
+    FILES_HASH.store(hash, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Checks the certificate files periodically and reloads them when they
+/// changed. A config which fails to load is logged and the current one kept.
+pub async fn run_reload() {
+    let interval = get_config().grpc.tls_reload_interval;
+    if interval == 0 {
+        return;
+    }
+    let mut interval = tokio::time::interval(Duration::from_secs(interval));
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        let hash = match files_hash() {
+            Ok(v) => v,
+            Err(e) => {
+                log::error!("[gRPC TLS] failed to read certificates: {e}");
+                continue;
+            }
+        };
+        if hash == FILES_HASH.load(Ordering::Relaxed) {
+            continue;
+        }
+        match server_config() {
+            Ok(config) => {
+                SERVER_CONFIG.store(Some(Arc::new(config)));
+                FILES_HASH.store(hash, Ordering::Relaxed);
+                infra::client::grpc::clear_cached_channels().await;
+                log::info!("[gRPC TLS] certificates reloaded");
+            }
+            Err(e) => log::error!("[gRPC TLS] failed to reload certificates: {e}"),
+        }
+    }
+}
+
+/// Accepts the connections of a gRPC server, the ones failing the handshake
+/// are dropped
+pub async fn incoming(
+    addr: SocketAddr,
+) -> Result<ReceiverStream<Result<TlsStream<TcpStream>, std::io::Error>>, anyhow::Error> {
+    let listener = TcpListener::bind(addr).await?;
+    let (tx, rx) = mpsc::channel(128);
+    tokio::task::spawn(async move {
+        while !tx.is_closed() {
+            let (stream, peer) = match listener.accept().await {
+                Ok(v) => v,
+                Err(e) => {
+                    log::error!("[gRPC TLS] failed to accept connection: {e}");
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    continue;
+                }
+            };
+            let Some(config) = SERVER_CONFIG.load_full() else {
+                log::error!("[gRPC TLS] server config not loaded");
+                continue;
+            };
+            let _ = stream.set_nodelay(true);
+            let tx = tx.clone();
+            // handshakes run apart so a slow client doesn't hold the others
+            tokio::task::spawn(async move {
+                match TlsAcceptor::from(config).accept(stream).await {
+                    Ok(stream) => {
+                        let _ = tx.send(Ok(stream)).await;
+                    }
+                    Err(e) => log::warn!("[gRPC TLS] handshake with {peer} failed: {e}"),
+                }
+            });
+        }
+    });
+    Ok(ReceiverStream::new(rx))
+}
+
+fn server_config() -> Result<ServerConfig, anyhow::Error> {
+    let cfg = get_config();
+    let cert_chain = read_certs(&cfg.grpc.tls_cert_path)?;
+    let key_file =
+        &mut BufReader::new(std::fs::File::open(&cfg.grpc.tls_key_path).map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to open TLS key file {}: {}",
+                &cfg.grpc.tls_key_path,
+                e
+            )
+        })?);
+    let key = private_key(key_file)?.ok_or_else(|| {
+        anyhow::anyhow!("No private key in TLS key file {}", &cfg.grpc.tls_key_path)
+    })?;
+
+    let builder = ServerConfig::builder();
+    let builder = if cfg.grpc.tls_client_auth {
+        let mut roots = RootCertStore::empty();
+        roots.add_parsable_certificates(read_certs(&cfg.grpc.tls_ca_cert_path)?);
+        builder.with_client_cert_verifier(WebPkiClientVerifier::builder(Arc::new(roots)).build()?)
+    } else {
+        builder.with_no_client_auth()
+    };
+    let mut config = builder.with_single_cert(cert_chain, key)?;
+    config.alpn_protocols = vec![b"h2".to_vec()];
+    Ok(config)
+}
+
+fn read_certs(
+    path: &str,
+) -> Result<Vec<rustls::pki_types::CertificateDer<'static>>, anyhow::Error> {
+    let file = &mut BufReader::new(
+        std::fs::File::open(path)
+            .map_err(|e| anyhow::anyhow!("Failed to open TLS certificate file {}: {}", path, e))?,
+    );
+    let certs = certs(file).try_collect::<_, Vec<_>, _>()?;
+    if certs.is_empty() {
+        return Err(anyhow::anyhow!(
+            "No certificate in TLS certificate file {path}"
+        ));
+    }
+    Ok(certs)
+}
+
+fn files_hash() -> Result<u64, anyhow::Error> {
+    let cfg = get_config();
+    let mut hasher = DefaultHasher::new();
+    for path in [
+        &cfg.grpc.tls_cert_path,
+        &cfg.grpc.tls_key_path,
+        &cfg.grpc.tls_ca_cert_path,
+    ] {
+        if !path.is_empty() {
+            std::fs::read(path)?.hash(&mut hasher);
+        }
+    }
+    Ok(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use rcgen::{CertifiedKey, generate_simple_self_signed};
+
+    use super::*;
+
+    #[test]
+    fn test_read_certs() {
+        let CertifiedKey { cert, .. } =
+            generate_simple_self_signed(vec!["openobserve.local".to_string()]).unwrap();
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(cert.pem().as_bytes()).unwrap();
+        let certs = read_certs(file.path().to_str().unwrap()).unwrap();
+        assert_eq!(certs.len(), 1);
+        assert_eq!(certs[0].as_ref(), cert.der().as_ref());
+
+        let empty = tempfile::NamedTempFile::new().unwrap();
+        assert!(read_certs(empty.path().to_str().unwrap()).is_err());
+        assert!(read_certs("/nonexistent/node.crt").is_err());
+    }
+}
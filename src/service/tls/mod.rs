@@ -23,6 +23,8 @@ use itertools::Itertools;
 use rustls_pemfile::{certs, private_key};
 use x509_parser::prelude::*;
 
+pub mod grpc;
+
 pub fn http_tls_config() -> Result<rustls::ServerConfig, anyhow::Error> {
     let cfg = config::get_config();
     let cert_file =
@@ -0,0 +1,230 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Finds the traces having a span attribute equal to a value.
+//!
+//! The trace ids are looked up with a top-n query over the secondary index,
+//! which is answered from the index files alone, then the spans of those
+//! traces are fetched. Both the attribute and `trace_id` must be secondary
+//! index fields of the stream.
+
+use config::{
+    TIMESTAMP_COL_NAME, get_config,
+    meta::{search, stream::StreamType},
+    utils::json,
+};
+use hashbrown::HashMap;
+use infra::{
+    errors::{Error, ErrorCodes, Result},
+    schema::get_stream_setting_index_fields,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+const TRACE_ID_FIELD: &str = "trace_id";
+
+/// Spans fetched for the matching traces at most.
+const MAX_SPANS: i64 = 10_000;
+
+#[derive(Debug, Clone)]
+pub struct AttributeQuery {
+    pub attribute: String,
+    pub value: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub size: i64,
+}
+
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct TracesByAttributeResponse {
+    pub took: usize,
+    pub total: usize,
+    #[schema(value_type = Vec<Object>)]
+    pub traces: Vec<TraceSpans>,
+    /// set when the spans of the traces did not fit in the response
+    pub is_partial: bool,
+}
+
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct TraceSpans {
+    pub trace_id: String,
+    #[schema(value_type = Vec<Object>)]
+    pub spans: Vec<json::Value>,
+}
+
+pub async fn search(
+    trace_id: &str,
+    org_id: &str,
+    stream_name: &str,
+    user_id: Option<String>,
+    query: &AttributeQuery,
+) -> Result<TracesByAttributeResponse> {
+    let start = std::time::Instant::now();
+    if !is_valid_field_name(&query.attribute) {
+        return Err(Error::ErrorCode(ErrorCodes::InvalidParams(format!(
+            "invalid attribute name: {}",
+            query.attribute
+        ))));
+    }
+    if query.size <= 0 {
+        return Err(Error::ErrorCode(ErrorCodes::InvalidParams(
+            "size must be greater than 0".to_string(),
+        )));
+    }
+    if !get_config().common.inverted_index_enabled {
+        return Err(Error::ErrorCode(ErrorCodes::InvalidParams(
+            "searching traces by attribute needs the inverted index".to_string(),
+        )));
+    }
+    let settings = infra::schema::get_settings(org_id, stream_name, StreamType::Traces).await;
+    let index_fields = get_stream_setting_index_fields(&settings);
+    for field in [TRACE_ID_FIELD, query.attribute.as_str()] {
+        if !index_fields.iter().any(|f| f == field) {
+            return Err(Error::ErrorCode(ErrorCodes::InvalidParams(format!(
+                "{field} is not a secondary index field of stream {stream_name}"
+            ))));
+        }
+    }
+
+    let mut req = search::Request {
+        query: search::Query {
+            sql: trace_ids_sql(stream_name, &query.attribute, &query.value),
+            size: query.size,
+            start_time: query.start_time,
+            end_time: query.end_time,
+            ..Default::default()
+        },
+        search_type: Some(search::SearchEventType::Other),
+        ..Default::default()
+    };
+    let resp =
+        crate::service::search::search(trace_id, org_id, StreamType::Traces, user_id.clone(), &req)
+            .await?;
+    let trace_ids = resp
+        .hits
+        .iter()
+        .filter_map(|hit| hit.get(TRACE_ID_FIELD).and_then(|v| v.as_str()))
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>();
+    if trace_ids.is_empty() {
+        return Ok(TracesByAttributeResponse {
+            took: start.elapsed().as_millis() as usize,
+            is_partial: resp.is_partial,
+            ..Default::default()
+        });
+    }
+
+    req.query.sql = spans_sql(stream_name, &trace_ids);
+    req.query.size = MAX_SPANS;
+    let spans =
+        crate::service::search::search(trace_id, org_id, StreamType::Traces, user_id, &req).await?;
+    let is_partial = resp.is_partial || spans.is_partial || spans.hits.len() as i64 >= MAX_SPANS;
+    let traces = group_spans(&trace_ids, spans.hits);
+    Ok(TracesByAttributeResponse {
+        took: start.elapsed().as_millis() as usize,
+        total: traces.len(),
+        traces,
+        is_partial,
+    })
+}
+
+/// Top-n query of the trace ids, planned as an index-only search since the
+/// filter and the group by are on secondary index fields
+fn trace_ids_sql(stream_name: &str, attribute: &str, value: &str) -> String {
+    format!(
+        "SELECT {TRACE_ID_FIELD}, count(*) AS zo_sql_num FROM \"{stream_name}\" WHERE {attribute} = '{}' GROUP BY {TRACE_ID_FIELD} ORDER BY zo_sql_num DESC",
+        escape_string(value)
+    )
+}
+
+fn spans_sql(stream_name: &str, trace_ids: &[String]) -> String {
+    let trace_ids = trace_ids
+        .iter()
+        .map(|v| format!("'{}'", escape_string(v)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "SELECT * FROM \"{stream_name}\" WHERE {TRACE_ID_FIELD} IN ({trace_ids}) ORDER BY {TIMESTAMP_COL_NAME} ASC"
+    )
+}
+
+/// Groups the spans by trace, the traces keep the order of `trace_ids`
+fn group_spans(trace_ids: &[String], spans: Vec<json::Value>) -> Vec<TraceSpans> {
+    let mut grouped: HashMap<String, Vec<json::Value>> = HashMap::with_capacity(trace_ids.len());
+    for span in spans {
+        let Some(id) = span.get(TRACE_ID_FIELD).and_then(|v| v.as_str()) else {
+            continue;
+        };
+        grouped.entry(id.to_string()).or_default().push(span);
+    }
+    trace_ids
+        .iter()
+        .filter_map(|id| {
+            grouped.remove(id).map(|spans| TraceSpans {
+                trace_id: id.to_string(),
+                spans,
+            })
+        })
+        .collect()
+}
+
+fn is_valid_field_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn escape_string(value: &str) -> String {
+    value.replace('\'', "''")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_ids_sql() {
+        assert_eq!(
+            trace_ids_sql("default", "user_id", "o'brien"),
+            "SELECT trace_id, count(*) AS zo_sql_num FROM \"default\" WHERE user_id = 'o''brien' GROUP BY trace_id ORDER BY zo_sql_num DESC"
+        );
+        assert_eq!(
+            spans_sql("default", &["a".to_string(), "b".to_string()]),
+            "SELECT * FROM \"default\" WHERE trace_id IN ('a', 'b') ORDER BY _timestamp ASC"
+        );
+    }
+
+    #[test]
+    fn test_is_valid_field_name() {
+        assert!(is_valid_field_name("http_status_code"));
+        assert!(!is_valid_field_name("service.name"));
+        assert!(!is_valid_field_name(""));
+        assert!(!is_valid_field_name("a = 'b' OR 1"));
+    }
+
+    #[test]
+    fn test_group_spans() {
+        let trace_ids = vec!["t2".to_string(), "t1".to_string(), "t3".to_string()];
+        let spans = vec![
+            json::json!({"trace_id": "t1", "span_id": "a"}),
+            json::json!({"trace_id": "t2", "span_id": "b"}),
+            json::json!({"trace_id": "t1", "span_id": "c"}),
+            json::json!({"span_id": "d"}),
+        ];
+        let traces = group_spans(&trace_ids, spans);
+        assert_eq!(traces.len(), 2);
+        assert_eq!(traces[0].trace_id, "t2");
+        assert_eq!(traces[1].trace_id, "t1");
+        assert_eq!(traces[1].spans.len(), 2);
+    }
+}
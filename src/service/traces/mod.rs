@@ -22,7 +22,7 @@ use std::{
 
 use axum::{
     Json,
-    body::Bytes,
+    body::{Body, Bytes},
     http,
     response::{IntoResponse, Response as HttpResponse},
 };
@@ -50,16 +50,22 @@ use opentelemetry_proto::tonic::{
 use prost::Message;
 use serde_json::Map;
 
+pub mod by_attribute;
 pub mod service_graph;
+pub mod service_map;
+pub mod tail_sampling;
 
 #[cfg(feature = "cloud")]
 use crate::service::stream::get_stream;
 use crate::{
-    common::meta::{
-        http::HttpResponse as MetaHttpResponse,
-        ingestion::IngestUser,
-        stream::SchemaRecords,
-        traces::{Event, Span, SpanLink, SpanLinkContext, SpanRefType},
+    common::{
+        meta::{
+            http::HttpResponse as MetaHttpResponse,
+            ingestion::IngestUser,
+            stream::SchemaRecords,
+            traces::{Event, Span, SpanLink, SpanLinkContext, SpanRefType},
+        },
+        utils::otlp,
     },
     handler::http::router::ERROR_HEADER,
     service::{
@@ -93,15 +99,15 @@ const ATTR_STATUS_MESSAGE: &str = "status_message";
 
 pub async fn otlp_proto(
     org_id: &str,
-    body: Bytes,
+    body: Body,
     in_stream_name: Option<&str>,
     user: IngestUser,
 ) -> Result<HttpResponse, Error> {
-    let request = match ExportTraceServiceRequest::decode(body) {
-        Ok(v) => v,
+    let request = match otlp::decode_resources(body, get_config().limit.otlp_payload_limit).await {
+        Ok(resource_spans) => ExportTraceServiceRequest { resource_spans },
         Err(e) => {
             log::error!("[TRACES:OTLP] Invalid proto: org_id: {org_id}, error: {e}");
-            return Ok(MetaHttpResponse::bad_request(format!("Invalid proto: {e}")));
+            return Ok(e.into_response());
         }
     };
     match handle_otlp_request(
@@ -125,10 +131,14 @@ pub async fn otlp_proto(
 
 pub async fn otlp_json(
     org_id: &str,
-    body: Bytes,
+    body: Body,
     in_stream_name: Option<&str>,
     user: IngestUser,
 ) -> Result<HttpResponse, Error> {
+    let body = match otlp::read_body(body, get_config().limit.otlp_payload_limit).await {
+        Ok(v) => v,
+        Err(e) => return Ok(e.into_response()),
+    };
     let request = match serde_json::from_slice::<ExportTraceServiceRequest>(body.as_ref()) {
         Ok(req) => req,
         Err(e) => {
@@ -162,8 +172,11 @@ pub async fn handle_otlp_request(
 ) -> Result<HttpResponse, Error> {
     // check system resource
     if let Err(e) = check_ingestion_allowed(org_id, StreamType::Traces, None).await {
-        // we do not want to log trial period expired errors
-        if matches!(e, infra::errors::Error::TrialPeriodExpired) {
+        // we do not want to log trial period expired and quota errors
+        if matches!(
+            e,
+            infra::errors::Error::TrialPeriodExpired | infra::errors::Error::QuotaExceeded(_)
+        ) {
             return Ok(MetaHttpResponse::too_many_requests(e));
         } else {
             log::error!("[TRACES:OTLP] ingestion error: {e}");
@@ -226,6 +239,8 @@ pub async fn handle_otlp_request(
     // End get user defined schema
 
     let mut service_name: String = traces_stream_name.to_string();
+    let service_map_enabled = cfg.common.service_map_flush_interval > 0;
+    let mut map_spans = Vec::new();
     let res_spans = request.resource_spans;
     let mut json_data_by_stream = HashMap::new();
     let mut partial_success = ExportTracePartialSuccess::default();
@@ -360,6 +375,20 @@ pub async fn handle_otlp_request(
                     partial_success.rejected_spans += 1;
                     continue;
                 }
+                if service_map_enabled {
+                    map_spans.push(service_map::MapSpan {
+                        trace_id: trace_id.clone(),
+                        span_id: span_id.clone(),
+                        parent_span_id: span_ref.get(PARENT_SPAN_ID).cloned(),
+                        service_name: service_name.clone(),
+                        timestamp,
+                        duration_ns: end_time.saturating_sub(start_time),
+                        is_error: span
+                            .status
+                            .as_ref()
+                            .is_some_and(|s| s.code() == StatusCode::Error),
+                    });
+                }
                 let local_val = Span {
                     trace_id: trace_id.clone(),
                     span_id: span_id.clone(),
@@ -378,8 +407,8 @@ pub async fn handle_otlp_request(
                     links: json::to_string(&links).unwrap(),
                 };
 
-                // Service graph processing is handled by periodic daemon
-                // No inline processing during trace ingestion
+                // Service graph processing is handled by periodic daemon, the
+                // service map only keeps the calls between services above
 
                 let mut value: json::Value = json::to_value(local_val).unwrap();
                 // add timestamp
@@ -427,6 +456,10 @@ pub async fn handle_otlp_request(
         }
     }
 
+    if !map_spans.is_empty() {
+        service_map::observe(org_id, &traces_stream_name, &map_spans);
+    }
+
     // batch process records through pipeline
     if let Some(exec_pl) = &executable_pipeline {
         let records = stream_pipeline_inputs;
@@ -509,6 +542,10 @@ pub async fn handle_otlp_request(
         }
     }
 
+    // hold the spans of the streams sampling their traces until the traces
+    // are decided
+    tail_sampling::sample(org_id, &mut json_data_by_stream, &user.to_email()).await;
+
     // if no data, fast return
     if json_data_by_stream.is_empty() {
         return format_response(partial_success, req_type);
@@ -569,8 +606,11 @@ pub async fn ingest_json(
 ) -> Result<HttpResponse, Error> {
     // check system resource
     if let Err(e) = check_ingestion_allowed(org_id, StreamType::Traces, None).await {
-        // we do not want to log trial period expired errors
-        if matches!(e, infra::errors::Error::TrialPeriodExpired) {
+        // we do not want to log trial period expired and quota errors
+        if matches!(
+            e,
+            infra::errors::Error::TrialPeriodExpired | infra::errors::Error::QuotaExceeded(_)
+        ) {
             return Ok(MetaHttpResponse::too_many_requests(e));
         } else {
             log::error!("[TRACES:JSON] ingestion error: {e}");
@@ -829,9 +869,10 @@ async fn write_traces(
     )
     .await;
 
-    let stream_settings = infra::schema::get_settings(org_id, stream_name, StreamType::Traces)
-        .await
-        .unwrap_or_default();
+    let stream_settings =
+        infra::schema::get_settings(org_id, stream_name, StreamType::Traces).await;
+    let wal_sync_policy = infra::schema::get_stream_setting_wal_sync_policy(&stream_settings);
+    let stream_settings = stream_settings.unwrap_or_default();
 
     let mut partition_keys: Vec<StreamPartition> = vec![];
     let mut partition_time_level =
@@ -996,18 +1037,12 @@ async fn write_traces(
         stream_name,
     )
     .await;
-    let req_stats = write_file(
-        &writer,
-        org_id,
-        stream_name,
-        data_buf,
-        !cfg.common.wal_fsync_disabled,
-    )
-    .await
-    .map_err(|e| {
-        log::error!("Error while writing traces: {e}");
-        std::io::Error::other(e.to_string())
-    })?;
+    let req_stats = write_file(&writer, org_id, stream_name, data_buf, wal_sync_policy)
+        .await
+        .map_err(|e| {
+            log::error!("Error while writing traces: {e}");
+            std::io::Error::other(e.to_string())
+        })?;
 
     // send distinct_values
     if !distinct_values.is_empty()
@@ -0,0 +1,551 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Service map of the trace streams, built from the spans at ingestion. A
+//! span whose parent belongs to another service is a call from the service of
+//! the parent to the service of the span, the duration and the status of the
+//! span are the latency and the outcome of the call. The parents are looked
+//! up in the spans this node ingested recently, a span ingested before its
+//! parent waits for it in a bounded buffer.
+//!
+//! The calls are counted per hour, with a histogram of their latency, kept
+//! per node and added periodically to the kv store, so the map covers the
+//! spans of every ingester.
+
+use std::collections::HashMap;
+
+use config::{
+    meta::service_graph::{ServiceEdge, ServiceGraphData, ServiceNode},
+    utils::{json, time::now_micros},
+};
+use hashlink::lru_cache::LruCache;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::service::db::kv;
+
+/// Spans remembered per node to find the parents of the spans ingested after
+/// them
+const MAX_SPANS: usize = 200_000;
+
+/// Parents waited for per node, the oldest are given up first
+const MAX_ORPHAN_PARENTS: usize = 50_000;
+
+/// Spans waiting for the same parent
+const MAX_ORPHANS_PER_PARENT: usize = 100;
+
+const HOUR_MICROS: i64 = 3600 * 1_000_000;
+
+/// Hours of calls kept in the kv store
+const RETENTION_HOURS: i64 = 7 * 24;
+
+/// Buckets of the latency histograms per doubling of the latency, the
+/// percentiles are off by up to 19%
+const BUCKETS_PER_OCTAVE: f64 = 4.0;
+
+/// The last bucket holds the calls slower than 2^40ns, about 18 minutes
+const MAX_BUCKETS: usize = 160;
+
+const MAP_KEY_PREFIX: &str = "service_map/";
+
+/// A span as seen by the service map
+#[derive(Clone, Debug)]
+pub struct MapSpan {
+    pub trace_id: String,
+    pub span_id: String,
+    pub parent_span_id: Option<String>,
+    pub service_name: String,
+    /// Start of the span, in microseconds
+    pub timestamp: i64,
+    pub duration_ns: u64,
+    pub is_error: bool,
+}
+
+/// `(org_id, stream_name, trace_id, span_id)`
+type SpanKey = (String, String, String, String);
+
+/// `(client, server)`
+type EdgeKey = (String, String);
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Calls {
+    requests: u64,
+    errors: u64,
+    /// Calls by latency bucket, without the trailing empty buckets
+    #[serde(default)]
+    latency: Vec<u64>,
+}
+
+impl Calls {
+    fn observe(&mut self, duration_ns: u64, is_error: bool) {
+        self.requests += 1;
+        if is_error {
+            self.errors += 1;
+        }
+        let bucket = bucket(duration_ns);
+        if self.latency.len() <= bucket {
+            self.latency.resize(bucket + 1, 0);
+        }
+        self.latency[bucket] += 1;
+    }
+
+    fn add(&mut self, other: &Calls) {
+        self.requests += other.requests;
+        self.errors += other.errors;
+        if self.latency.len() < other.latency.len() {
+            self.latency.resize(other.latency.len(), 0);
+        }
+        for (count, other) in self.latency.iter_mut().zip(other.latency.iter()) {
+            *count += other;
+        }
+    }
+
+    /// The upper bound of the bucket of the q-quantile of the latency, in
+    /// nanoseconds
+    fn percentile(&self, q: f64) -> u64 {
+        let total = self.latency.iter().sum::<u64>();
+        if total == 0 {
+            return 0;
+        }
+        let rank = ((q * total as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bucket, count) in self.latency.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return upper_bound(bucket);
+            }
+        }
+        upper_bound(self.latency.len() - 1)
+    }
+}
+
+fn bucket(duration_ns: u64) -> usize {
+    if duration_ns <= 1 {
+        return 0;
+    }
+    (((duration_ns as f64).log2() * BUCKETS_PER_OCTAVE).floor() as usize).min(MAX_BUCKETS - 1)
+}
+
+fn upper_bound(bucket: usize) -> u64 {
+    2f64.powf((bucket + 1) as f64 / BUCKETS_PER_OCTAVE).round() as u64
+}
+
+/// An edge of an hour as stored in the kv store
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredEdge {
+    client: String,
+    server: String,
+    #[serde(flatten)]
+    calls: Calls,
+}
+
+/// A span waiting for its parent
+struct Orphan {
+    service_name: String,
+    hour: i64,
+    duration_ns: u64,
+    is_error: bool,
+}
+
+struct Tracker {
+    /// Service of the recent spans
+    spans: LruCache<SpanKey, String>,
+    /// Spans waiting for their parent, by parent
+    orphans: LruCache<SpanKey, Vec<Orphan>>,
+    /// Calls since the last flush, by `(org_id, stream_name, hour)`
+    calls: HashMap<(String, String, i64), HashMap<EdgeKey, Calls>>,
+}
+
+impl Tracker {
+    fn new(max_spans: usize, max_orphan_parents: usize) -> Self {
+        Self {
+            spans: LruCache::new(max_spans),
+            orphans: LruCache::new(max_orphan_parents),
+            calls: HashMap::new(),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn record(
+        &mut self,
+        org_id: &str,
+        stream_name: &str,
+        hour: i64,
+        client: &str,
+        server: &str,
+        duration_ns: u64,
+        is_error: bool,
+    ) {
+        if client == server {
+            return;
+        }
+        self.calls
+            .entry((org_id.to_string(), stream_name.to_string(), hour))
+            .or_default()
+            .entry((client.to_string(), server.to_string()))
+            .or_default()
+            .observe(duration_ns, is_error);
+    }
+
+    fn observe(&mut self, org_id: &str, stream_name: &str, spans: &[MapSpan]) {
+        let key = |trace_id: &str, span_id: &str| {
+            (
+                org_id.to_string(),
+                stream_name.to_string(),
+                trace_id.to_string(),
+                span_id.to_string(),
+            )
+        };
+        // remember the spans first, the children of a span are often in the
+        // same request
+        for span in spans {
+            let span_key = key(&span.trace_id, &span.span_id);
+            if let Some(orphans) = self.orphans.remove(&span_key) {
+                for orphan in orphans {
+                    self.record(
+                        org_id,
+                        stream_name,
+                        orphan.hour,
+                        &span.service_name,
+                        &orphan.service_name,
+                        orphan.duration_ns,
+                        orphan.is_error,
+                    );
+                }
+            }
+            self.spans.insert(span_key, span.service_name.clone());
+        }
+        for span in spans {
+            let Some(parent_span_id) = span.parent_span_id.as_deref() else {
+                continue;
+            };
+            let parent_key = key(&span.trace_id, parent_span_id);
+            let hour = span.timestamp - span.timestamp.rem_euclid(HOUR_MICROS);
+            if let Some(parent_service) = self.spans.get(&parent_key).cloned() {
+                self.record(
+                    org_id,
+                    stream_name,
+                    hour,
+                    &parent_service,
+                    &span.service_name,
+                    span.duration_ns,
+                    span.is_error,
+                );
+                continue;
+            }
+            let orphan = Orphan {
+                service_name: span.service_name.clone(),
+                hour,
+                duration_ns: span.duration_ns,
+                is_error: span.is_error,
+            };
+            match self.orphans.get_mut(&parent_key) {
+                Some(orphans) if orphans.len() < MAX_ORPHANS_PER_PARENT => orphans.push(orphan),
+                Some(_) => {}
+                None => {
+                    self.orphans.insert(parent_key, vec![orphan]);
+                }
+            }
+        }
+    }
+}
+
+static TRACKER: Lazy<Mutex<Tracker>> =
+    Lazy::new(|| Mutex::new(Tracker::new(MAX_SPANS, MAX_ORPHAN_PARENTS)));
+
+fn map_key(stream_name: &str, hour: i64) -> String {
+    format!("{MAP_KEY_PREFIX}{stream_name}/{hour}")
+}
+
+/// `(stream_name, hour)` of a key of the kv store
+fn parse_key(key: &str) -> Option<(&str, i64)> {
+    let (stream_name, hour) = key.strip_prefix(MAP_KEY_PREFIX)?.rsplit_once('/')?;
+    Some((stream_name, hour.parse().ok()?))
+}
+
+/// Records the calls between services of the spans ingested into the stream
+pub fn observe(org_id: &str, stream_name: &str, spans: &[MapSpan]) {
+    TRACKER.lock().observe(org_id, stream_name, spans);
+}
+
+async fn get_hour(org_id: &str, key: &str) -> HashMap<EdgeKey, Calls> {
+    let edges: Vec<StoredEdge> = match kv::get(org_id, key).await {
+        Ok(val) => json::from_slice(&val).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    };
+    edges
+        .into_iter()
+        .map(|e| ((e.client, e.server), e.calls))
+        .collect()
+}
+
+fn add_edges(total: &mut HashMap<EdgeKey, Calls>, edges: &HashMap<EdgeKey, Calls>) {
+    for (edge, calls) in edges {
+        total.entry(edge.clone()).or_default().add(calls);
+    }
+}
+
+/// Adds the calls recorded since the last flush to the kv store, and drops
+/// the hours beyond the retention
+pub async fn flush() {
+    let pending = std::mem::take(&mut TRACKER.lock().calls);
+    let mut orgs = Vec::new();
+    for ((org_id, stream_name, hour), edges) in pending {
+        let key = map_key(&stream_name, hour);
+        // read-modify-write, a concurrent flush of another node can lose the
+        // calls of one of them for the hour
+        let mut total = get_hour(&org_id, &key).await;
+        add_edges(&mut total, &edges);
+        let stored = total
+            .into_iter()
+            .map(|((client, server), calls)| StoredEdge {
+                client,
+                server,
+                calls,
+            })
+            .collect::<Vec<_>>();
+        let val = match json::to_vec(&stored) {
+            Ok(v) => v,
+            Err(e) => {
+                log::error!("[SERVICE_MAP] failed to serialize {org_id}/{key}: {e}");
+                continue;
+            }
+        };
+        if let Err(e) = kv::set(&org_id, &key, val.into()).await {
+            log::error!("[SERVICE_MAP] failed to save {org_id}/{key}: {e}");
+        }
+        if !orgs.contains(&org_id) {
+            orgs.push(org_id);
+        }
+    }
+
+    let expired = now_micros() - RETENTION_HOURS * HOUR_MICROS;
+    for org_id in orgs {
+        let keys = match kv::list_keys(&org_id, MAP_KEY_PREFIX).await {
+            Ok(keys) => keys,
+            Err(e) => {
+                log::error!("[SERVICE_MAP] failed to list the hours of {org_id}: {e}");
+                continue;
+            }
+        };
+        for key in keys {
+            if parse_key(&key).is_some_and(|(_, hour)| hour < expired)
+                && let Err(e) = kv::delete(&org_id, &key).await
+            {
+                log::error!("[SERVICE_MAP] failed to delete {org_id}/{key}: {e}");
+            }
+        }
+    }
+}
+
+/// The service map of the trace streams of the organization over the time
+/// range, rounded to whole hours, of every stream when `stream_name` is None
+pub async fn get(
+    org_id: &str,
+    stream_name: Option<&str>,
+    start_time: i64,
+    end_time: i64,
+) -> Result<ServiceGraphData, anyhow::Error> {
+    let in_range = |stream: &str, hour: i64| {
+        stream_name.is_none_or(|s| s == stream)
+            && hour + HOUR_MICROS > start_time
+            && hour < end_time
+    };
+    let prefix = match stream_name {
+        Some(stream_name) => format!("{MAP_KEY_PREFIX}{stream_name}/"),
+        None => MAP_KEY_PREFIX.to_string(),
+    };
+    let mut total = HashMap::new();
+    for key in kv::list_keys(org_id, &prefix).await? {
+        if parse_key(&key).is_some_and(|(stream, hour)| in_range(stream, hour)) {
+            add_edges(&mut total, &get_hour(org_id, &key).await);
+        }
+    }
+    // include the calls this node recorded but did not flush yet
+    for ((org, stream, hour), edges) in TRACKER.lock().calls.iter() {
+        if org == org_id && in_range(stream, *hour) {
+            add_edges(&mut total, edges);
+        }
+    }
+    Ok(build_graph(total))
+}
+
+fn error_rate(requests: u64, errors: u64) -> f64 {
+    if requests == 0 {
+        0.0
+    } else {
+        errors as f64 * 100.0 / requests as f64
+    }
+}
+
+/// The services are the nodes, with the calls they receive, and the calls
+/// between them the edges, the busiest first
+fn build_graph(edges: HashMap<EdgeKey, Calls>) -> ServiceGraphData {
+    let mut services: HashMap<String, (u64, u64)> = HashMap::new();
+    for ((client, server), calls) in edges.iter() {
+        services.entry(client.clone()).or_default();
+        let (requests, errors) = services.entry(server.clone()).or_default();
+        *requests += calls.requests;
+        *errors += calls.errors;
+    }
+    let mut nodes = services
+        .into_iter()
+        .map(|(service, (requests, errors))| ServiceNode {
+            id: service.clone(),
+            label: service,
+            requests,
+            errors,
+            error_rate: error_rate(requests, errors),
+        })
+        .collect::<Vec<_>>();
+    nodes.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut edges = edges
+        .into_iter()
+        .map(|((client, server), calls)| ServiceEdge {
+            from: client,
+            to: server,
+            total_requests: calls.requests,
+            failed_requests: calls.errors,
+            error_rate: error_rate(calls.requests, calls.errors),
+            p50_latency_ns: calls.percentile(0.5),
+            p95_latency_ns: calls.percentile(0.95),
+            p99_latency_ns: calls.percentile(0.99),
+            connection_type: "standard".to_string(),
+        })
+        .collect::<Vec<_>>();
+    edges.sort_by(|a, b| {
+        b.total_requests
+            .cmp(&a.total_requests)
+            .then_with(|| a.from.cmp(&b.from))
+            .then_with(|| a.to.cmp(&b.to))
+    });
+    ServiceGraphData { nodes, edges }
+}
+
+pub async fn delete(org_id: &str, stream_name: &str) -> Result<(), anyhow::Error> {
+    TRACKER
+        .lock()
+        .calls
+        .retain(|(org, stream, _), _| org != org_id || stream != stream_name);
+    for key in kv::list_keys(org_id, &format!("{MAP_KEY_PREFIX}{stream_name}/")).await? {
+        kv::delete(org_id, &key).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(
+        span_id: &str,
+        parent: Option<&str>,
+        service: &str,
+        ms: u64,
+        is_error: bool,
+    ) -> MapSpan {
+        MapSpan {
+            trace_id: "t1".to_string(),
+            span_id: span_id.to_string(),
+            parent_span_id: parent.map(|p| p.to_string()),
+            service_name: service.to_string(),
+            timestamp: 10 * HOUR_MICROS + 5,
+            duration_ns: ms * 1_000_000,
+            is_error,
+        }
+    }
+
+    #[test]
+    fn test_latency_percentiles() {
+        let mut calls = Calls::default();
+        assert_eq!(calls.percentile(0.5), 0);
+        for ms in 1..=100 {
+            calls.observe(ms * 1_000_000, ms > 98);
+        }
+        assert_eq!(calls.requests, 100);
+        assert_eq!(calls.errors, 2);
+        let within = |v: u64, expected: u64| {
+            let (v, expected) = (v as f64, expected as f64);
+            v >= expected && v <= expected * 1.2
+        };
+        assert!(within(calls.percentile(0.5), 50_000_000));
+        assert!(within(calls.percentile(0.99), 99_000_000));
+        assert!(calls.percentile(0.5) <= calls.percentile(0.95));
+
+        let mut merged = Calls::default();
+        merged.add(&calls);
+        merged.add(&calls);
+        assert_eq!(merged.requests, 200);
+        assert_eq!(merged.percentile(0.5), calls.percentile(0.5));
+        assert_eq!(bucket(u64::MAX), MAX_BUCKETS - 1);
+        assert_eq!(bucket(0), 0);
+    }
+
+    #[test]
+    fn test_service_map() {
+        let mut tracker = Tracker::new(100, 100);
+        let hour = 10 * HOUR_MICROS;
+        tracker.observe(
+            "org",
+            "default",
+            &[
+                span("a", None, "frontend", 30, false),
+                span("b", Some("a"), "frontend", 25, false),
+                span("c", Some("b"), "checkout", 20, true),
+                // the parent comes in a later request
+                span("e", Some("d"), "db", 5, false),
+            ],
+        );
+        tracker.observe(
+            "org",
+            "default",
+            &[span("d", Some("c"), "checkout", 6, false)],
+        );
+
+        let calls = tracker
+            .calls
+            .get(&("org".to_string(), "default".to_string(), hour))
+            .unwrap();
+        assert_eq!(calls.len(), 2);
+        let edge = |c: &str, s: &str| calls.get(&(c.to_string(), s.to_string())).unwrap();
+        assert_eq!(edge("frontend", "checkout").requests, 1);
+        assert_eq!(edge("frontend", "checkout").errors, 1);
+        assert_eq!(edge("checkout", "db").requests, 1);
+        assert!(tracker.orphans.is_empty());
+
+        let graph = build_graph(calls.clone());
+        let ids = graph
+            .nodes
+            .iter()
+            .map(|n| n.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["checkout", "db", "frontend"]);
+        assert_eq!(graph.nodes[0].error_rate, 100.0);
+        assert_eq!(graph.nodes[2].requests, 0);
+        let edge = graph.edges.iter().find(|e| e.to == "db").unwrap();
+        assert_eq!(edge.from, "checkout");
+        assert!(edge.p50_latency_ns >= 5_000_000 && edge.p50_latency_ns <= 6_000_000);
+    }
+
+    #[test]
+    fn test_parse_key() {
+        let key = map_key("default", 3 * HOUR_MICROS);
+        assert_eq!(parse_key(&key), Some(("default", 3 * HOUR_MICROS)));
+        assert_eq!(parse_key("service_map/default"), None);
+        assert_eq!(parse_key("clock_skew/logs/default"), None);
+    }
+}
@@ -0,0 +1,525 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tail sampling of the traces streams which enable it. The spans of a trace
+//! are held in memory for the decision wait of the stream after the first
+//! one arrived, then the trace is kept when a span failed, when it lasted at
+//! least the latency threshold, or at the sample rate otherwise, and only the
+//! kept traces are written. The spans arriving after the decision follow it.
+//!
+//! The spans are held per node, so the spans of a trace sent to several
+//! ingesters are decided apart, only the probabilistic decision is the same
+//! on every node. Beyond the memory limit the new traces are decided on the
+//! spans of their first request, and the oldest held traces early. The held
+//! spans aren't in the WAL, a crash loses them.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Instant,
+};
+
+use config::{
+    get_config,
+    meta::stream::{StreamType, TailSampling},
+    metrics,
+    utils::{
+        hash::{Sum64, murmur3},
+        json::{Map, Value, estimate_json_bytes},
+        time::now_micros,
+    },
+};
+use hashlink::lru_cache::LruCache;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+use crate::service::logs::O2IngestJsonData;
+
+/// Decisions remembered per node for the spans arriving after their trace
+/// was decided
+const MAX_DECISIONS: usize = 500_000;
+
+const TRACE_ID: &str = "trace_id";
+const SPAN_STATUS: &str = "span_status";
+const START_TIME: &str = "start_time";
+const END_TIME: &str = "end_time";
+
+type Record = (i64, Map<String, Value>);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Decision {
+    Error,
+    Latency,
+    Probabilistic,
+    Dropped,
+}
+
+impl Decision {
+    fn is_kept(self) -> bool {
+        self != Decision::Dropped
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Decision::Error => "error",
+            Decision::Latency => "latency",
+            Decision::Probabilistic => "probabilistic",
+            Decision::Dropped => "dropped",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct PendingTrace {
+    user_email: String,
+    spans: Vec<Record>,
+    has_error: bool,
+    /// Earliest start and latest end of the spans, in nanoseconds
+    start: u64,
+    end: u64,
+    bytes: usize,
+}
+
+impl PendingTrace {
+    fn push(&mut self, record: Record) {
+        let span = &record.1;
+        self.has_error |= span.get(SPAN_STATUS).and_then(|v| v.as_str()) == Some("ERROR");
+        if let Some(start) = span.get(START_TIME).and_then(|v| v.as_u64()) {
+            self.start = if self.start == 0 {
+                start
+            } else {
+                self.start.min(start)
+            };
+        }
+        if let Some(end) = span.get(END_TIME).and_then(|v| v.as_u64()) {
+            self.end = self.end.max(end);
+        }
+        self.bytes += span
+            .iter()
+            .map(|(k, v)| k.len() + estimate_json_bytes(v) + 4)
+            .sum::<usize>();
+        self.spans.push(record);
+    }
+}
+
+#[derive(Default)]
+struct StreamBuffer {
+    settings: TailSampling,
+    traces: HashMap<String, PendingTrace>,
+    /// Held traces by the time their first span arrived
+    order: VecDeque<(i64, String)>,
+}
+
+/// Spans to write by `(org_id, stream_name, user_email)`
+type Released = HashMap<(String, String, String), Vec<Record>>;
+
+struct Sampler {
+    /// Held traces by `(org_id, stream_name)`
+    streams: HashMap<(String, String), StreamBuffer>,
+    /// Decided traces by `(org_id, stream_name, trace_id)`
+    decisions: LruCache<(String, String, String), Decision>,
+    bytes: usize,
+}
+
+impl Sampler {
+    fn new(max_decisions: usize) -> Self {
+        Self {
+            streams: HashMap::new(),
+            decisions: LruCache::new(max_decisions),
+            bytes: 0,
+        }
+    }
+
+    /// Holds the spans of the stream until their trace is decided, returns
+    /// the spans to write now
+    #[allow(clippy::too_many_arguments)]
+    fn add(
+        &mut self,
+        org_id: &str,
+        stream_name: &str,
+        settings: &TailSampling,
+        user_email: &str,
+        records: Vec<Record>,
+        now: i64,
+        max_bytes: usize,
+    ) -> Vec<Record> {
+        let mut write = Vec::new();
+        let mut early = HashMap::new();
+        let stream_key = (org_id.to_string(), stream_name.to_string());
+        let stream = self.streams.entry(stream_key).or_default();
+        stream.settings = settings.clone();
+        for record in records {
+            let Some(trace_id) = record
+                .1
+                .get(TRACE_ID)
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+            else {
+                write.push(record);
+                continue;
+            };
+            let decision_key = (org_id.to_string(), stream_name.to_string(), trace_id);
+            if let Some(decision) = self.decisions.get(&decision_key) {
+                if decision.is_kept() {
+                    write.push(record);
+                }
+                continue;
+            }
+            let (_, _, trace_id) = decision_key;
+            if let Some(trace) = stream.traces.get_mut(&trace_id) {
+                let bytes = trace.bytes;
+                trace.push(record);
+                self.bytes += trace.bytes - bytes;
+                continue;
+            }
+            // beyond the limit the new traces are decided on the spans of
+            // this request
+            if self.bytes >= max_bytes {
+                early
+                    .entry(trace_id)
+                    .or_insert_with(PendingTrace::default)
+                    .push(record);
+                continue;
+            }
+            let mut trace = PendingTrace {
+                user_email: user_email.to_string(),
+                ..Default::default()
+            };
+            trace.push(record);
+            self.bytes += trace.bytes;
+            stream.order.push_back((now, trace_id.clone()));
+            stream.traces.insert(trace_id, trace);
+        }
+        for (trace_id, trace) in early {
+            let decision = decide(settings, &trace_id, &trace);
+            self.record(org_id, stream_name, trace_id, decision);
+            if decision.is_kept() {
+                write.extend(trace.spans);
+            }
+        }
+        metrics::INGEST_TAIL_SAMPLING_BUFFERED_BYTES.set(self.bytes as i64);
+        write
+    }
+
+    fn record(&mut self, org_id: &str, stream_name: &str, trace_id: String, decision: Decision) {
+        metrics::INGEST_TAIL_SAMPLING_TRACES
+            .with_label_values(&[org_id, stream_name, decision.as_str()])
+            .inc();
+        self.decisions.insert(
+            (org_id.to_string(), stream_name.to_string(), trace_id),
+            decision,
+        );
+    }
+
+    /// Decides the traces whose decision wait elapsed, and the oldest ones
+    /// while the held spans take more than the limit
+    fn release(&mut self, now: i64, max_bytes: usize) -> Released {
+        let mut released = Released::new();
+        let keys = self.streams.keys().cloned().collect::<Vec<_>>();
+        for key in keys {
+            while let Some(stream) = self.streams.get(&key) {
+                let deadline = now.saturating_sub(stream.settings.decision_wait_micros());
+                if !stream
+                    .order
+                    .front()
+                    .is_some_and(|(first_seen, _)| *first_seen <= deadline)
+                {
+                    break;
+                }
+                self.decide_oldest(&key, &mut released);
+            }
+        }
+        while self.bytes > max_bytes {
+            let Some(key) = self
+                .streams
+                .iter()
+                .filter_map(|(key, stream)| {
+                    stream
+                        .order
+                        .front()
+                        .map(|(first_seen, _)| (*first_seen, key))
+                })
+                .min_by_key(|(first_seen, _)| *first_seen)
+                .map(|(_, key)| key.clone())
+            else {
+                break;
+            };
+            self.decide_oldest(&key, &mut released);
+        }
+        self.streams.retain(|_, stream| !stream.order.is_empty());
+        metrics::INGEST_TAIL_SAMPLING_BUFFERED_BYTES.set(self.bytes as i64);
+        released
+    }
+
+    /// Decides the trace of the stream held the longest
+    fn decide_oldest(&mut self, key: &(String, String), released: &mut Released) {
+        let Some(stream) = self.streams.get_mut(key) else {
+            return;
+        };
+        let Some((_, trace_id)) = stream.order.pop_front() else {
+            return;
+        };
+        let Some(trace) = stream.traces.remove(&trace_id) else {
+            return;
+        };
+        let decision = decide(&stream.settings, &trace_id, &trace);
+        self.bytes = self.bytes.saturating_sub(trace.bytes);
+        let (org_id, stream_name) = key;
+        self.record(org_id, stream_name, trace_id, decision);
+        if decision.is_kept() {
+            released
+                .entry((org_id.clone(), stream_name.clone(), trace.user_email))
+                .or_default()
+                .extend(trace.spans);
+        }
+    }
+}
+
+/// Keeps the failed traces, the slow ones and the rest at the sample rate
+fn decide(settings: &TailSampling, trace_id: &str, trace: &PendingTrace) -> Decision {
+    if trace.has_error {
+        return Decision::Error;
+    }
+    let threshold_ns = settings.latency_threshold_ms.saturating_mul(1_000_000);
+    if threshold_ns > 0 && trace.end.saturating_sub(trace.start) >= threshold_ns {
+        return Decision::Latency;
+    }
+    if is_sampled(trace_id, settings.sample_rate) {
+        Decision::Probabilistic
+    } else {
+        Decision::Dropped
+    }
+}
+
+/// Same result for the trace on every node
+fn is_sampled(trace_id: &str, sample_rate: f64) -> bool {
+    if sample_rate >= 1.0 {
+        return true;
+    }
+    if sample_rate <= 0.0 {
+        return false;
+    }
+    (murmur3::new().sum64(trace_id) as f64 / u64::MAX as f64) < sample_rate
+}
+
+static SAMPLER: Lazy<Mutex<Sampler>> = Lazy::new(|| Mutex::new(Sampler::new(MAX_DECISIONS)));
+
+fn max_bytes() -> usize {
+    get_config().common.tail_sampling_max_buffer_mb * 1024 * 1024
+}
+
+/// Holds the spans of the streams sampling their traces, leaving in the data
+/// only the spans to write now
+pub async fn sample(
+    org_id: &str,
+    json_data_by_stream: &mut HashMap<String, O2IngestJsonData>,
+    user_email: &str,
+) {
+    let now = now_micros();
+    for (stream_name, (records, _)) in json_data_by_stream.iter_mut() {
+        let Some(settings) = infra::schema::get_settings(org_id, stream_name, StreamType::Traces)
+            .await
+            .map(|s| s.tail_sampling)
+            .filter(|s| s.enabled)
+        else {
+            continue;
+        };
+        let held = std::mem::take(records);
+        *records = SAMPLER.lock().add(
+            org_id,
+            stream_name,
+            &settings,
+            user_email,
+            held,
+            now,
+            max_bytes(),
+        );
+    }
+    json_data_by_stream.retain(|_, (records, _)| !records.is_empty());
+}
+
+/// Writes the traces decided since the last run
+pub async fn flush() {
+    let released = SAMPLER.lock().release(now_micros(), max_bytes());
+    write_released(released).await;
+}
+
+/// Decides and writes every held trace, when the node stops
+pub async fn flush_all() {
+    let released = SAMPLER.lock().release(i64::MAX, max_bytes());
+    write_released(released).await;
+}
+
+async fn write_released(released: Released) {
+    let mut by_org: HashMap<(String, String), HashMap<String, O2IngestJsonData>> = HashMap::new();
+    for ((org_id, stream_name, user_email), spans) in released {
+        by_org
+            .entry((org_id, user_email))
+            .or_default()
+            .insert(stream_name, (spans, None));
+    }
+    for ((org_id, user_email), json_data_by_stream) in by_org {
+        let start = Instant::now();
+        if let Err(e) = super::write_traces_by_stream(
+            &org_id,
+            (now_micros(), &start),
+            json_data_by_stream,
+            &user_email,
+        )
+        .await
+        {
+            log::error!(
+                "[TRACES:TAIL_SAMPLING] failed to write the sampled traces of {org_id}: {e}"
+            );
+        }
+    }
+}
+
+/// Drops the held spans of a deleted stream
+pub fn delete(org_id: &str, stream_name: &str) {
+    let mut sampler = SAMPLER.lock();
+    if let Some(stream) = sampler
+        .streams
+        .remove(&(org_id.to_string(), stream_name.to_string()))
+    {
+        let bytes = stream.traces.values().map(|t| t.bytes).sum::<usize>();
+        sampler.bytes = sampler.bytes.saturating_sub(bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use config::utils::json;
+
+    use super::*;
+
+    fn span(trace_id: &str, status: &str, start: u64, end: u64) -> Record {
+        let span = json::json!({
+            "trace_id": trace_id,
+            "span_status": status,
+            "start_time": start,
+            "end_time": end,
+        });
+        (1, span.as_object().unwrap().clone())
+    }
+
+    #[test]
+    fn test_decide() {
+        let settings = TailSampling {
+            enabled: true,
+            latency_threshold_ms: 100,
+            ..Default::default()
+        };
+        let trace = |spans: Vec<Record>| {
+            let mut trace = PendingTrace::default();
+            spans.into_iter().for_each(|s| trace.push(s));
+            trace
+        };
+        let ms = 1_000_000;
+        let failed = trace(vec![span("t", "OK", 0, ms), span("t", "ERROR", ms, 2 * ms)]);
+        assert!(failed.has_error && failed.bytes > 0);
+        assert_eq!(decide(&settings, "t", &failed), Decision::Error);
+        let slow = trace(vec![
+            span("t", "OK", 10 * ms, 20 * ms),
+            span("t", "OK", 5 * ms, 150 * ms),
+        ]);
+        assert_eq!((slow.start, slow.end), (5 * ms, 150 * ms));
+        assert_eq!(decide(&settings, "t", &slow), Decision::Latency);
+        let fast = trace(vec![span("t", "OK", 0, ms)]);
+        assert_eq!(decide(&settings, "t", &fast), Decision::Dropped);
+
+        assert!(is_sampled("t", 1.0));
+        assert!(!is_sampled("t", 0.0));
+        let sampled = (0..10_000)
+            .filter(|i| is_sampled(&format!("trace-{i}"), 0.1))
+            .count();
+        assert!((800..1200).contains(&sampled), "sampled: {sampled}");
+    }
+
+    #[test]
+    fn test_sampler() {
+        let settings = TailSampling {
+            enabled: true,
+            decision_wait_secs: 10,
+            ..Default::default()
+        };
+        let wait = settings.decision_wait_micros();
+        let mut sampler = Sampler::new(100);
+        let org_id = "test_tail_sampling_org";
+        let add = |sampler: &mut Sampler, records: Vec<Record>, now: i64| {
+            sampler.add(org_id, "s", &settings, "u", records, now, usize::MAX)
+        };
+
+        let written = add(
+            &mut sampler,
+            vec![span("ok", "OK", 0, 1), span("failed", "OK", 0, 1)],
+            0,
+        );
+        assert!(written.is_empty());
+        // a later span of a held trace joins it
+        assert!(add(&mut sampler, vec![span("failed", "ERROR", 1, 2)], 1).is_empty());
+        assert!(sampler.release(wait - 1, usize::MAX).is_empty());
+
+        let released = sampler.release(wait, usize::MAX);
+        let spans = &released[&(org_id.to_string(), "s".to_string(), "u".to_string())];
+        assert_eq!(spans.len(), 2);
+        assert!(spans.iter().all(|(_, s)| s[TRACE_ID] == "failed"));
+        assert_eq!(sampler.bytes, 0);
+        assert!(sampler.streams.is_empty());
+
+        // the spans arriving after the decision follow it
+        let written = add(
+            &mut sampler,
+            vec![span("failed", "OK", 3, 4), span("ok", "ERROR", 3, 4)],
+            wait + 1,
+        );
+        assert_eq!(written.len(), 1);
+        assert_eq!(written[0].1[TRACE_ID], "failed");
+    }
+
+    #[test]
+    fn test_sampler_memory_limit() {
+        let settings = TailSampling {
+            enabled: true,
+            ..Default::default()
+        };
+        let mut sampler = Sampler::new(100);
+        let org_id = "test_tail_sampling_limit_org";
+        sampler.add(
+            org_id,
+            "s",
+            &settings,
+            "u",
+            vec![span("old", "ERROR", 0, 1)],
+            0,
+            1,
+        );
+        assert!(sampler.bytes > 0);
+        // over the limit, the new trace is decided right away
+        let written = sampler.add(
+            org_id,
+            "s",
+            &settings,
+            "u",
+            vec![span("new", "ERROR", 0, 1)],
+            1,
+            1,
+        );
+        assert_eq!(written.len(), 1);
+        // and the oldest held trace is decided early
+        let released = sampler.release(2, 1);
+        assert_eq!(released.values().map(|s| s.len()).sum::<usize>(), 1);
+        assert_eq!(sampler.bytes, 0);
+    }
+}
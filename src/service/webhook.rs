@@ -0,0 +1,556 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Catch-all webhook sources
+//!
+//! Every source gets its own unauthenticated URL, requests are accepted only
+//! when they carry a valid signature made with the source's secret. The events
+//! are mapped with the JSONPath mapping of the source, then transformed with
+//! its VRL program, so common payloads need no transformer in front.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use axum::http::HeaderMap;
+use config::{
+    meta::function::VRLResultResolver,
+    utils::{json, json_path::JsonPath},
+};
+use hmac::{Hmac, Mac};
+use infra::errors::{Error, Result};
+use sha2::Sha256;
+
+use crate::{
+    common::{
+        infra::config::WEBHOOK_TRANSFORMS,
+        meta::{
+            ingestion::{
+                IngestUser, IngestionRequest, IngestionResponse, IngestionValueType, SystemJobType,
+            },
+            webhook::{WebhookKind, WebhookSource},
+        },
+    },
+    service::{
+        db,
+        ingestion::{apply_vrl_fn, compile_vrl_function, init_functions_runtime},
+    },
+};
+
+/// Stripe rejects events signed more than five minutes ago to prevent replays
+const STRIPE_TOLERANCE_SECS: i64 = 300;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub async fn get(org_id: &str, name: &str) -> Result<WebhookSource> {
+    db::webhook::get(org_id, name).await
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<WebhookSource>> {
+    db::webhook::list(org_id).await
+}
+
+pub async fn delete(org_id: &str, name: &str) -> Result<()> {
+    if db::webhook::get(org_id, name).await.is_err() {
+        return Err(Error::Message(format!("webhook source {name} not found")));
+    }
+    WEBHOOK_TRANSFORMS.remove(&format!("{org_id}/{name}"));
+    db::webhook::delete(org_id, name).await
+}
+
+/// Creates or replaces a source. An empty secret keeps the current one.
+pub async fn save(org_id: &str, mut source: WebhookSource, create: bool) -> Result<()> {
+    source.name = source.name.trim().to_string();
+    if source.name.is_empty()
+        || !source
+            .name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(Error::Message(
+            "webhook source name may only contain letters, digits, '_' and '-'".to_string(),
+        ));
+    }
+    source.stream_name = source.stream_name.trim().to_string();
+    if source.stream_name.is_empty() {
+        return Err(Error::Message("stream_name is required".to_string()));
+    }
+
+    match db::webhook::get(org_id, &source.name).await {
+        Ok(_) if create => {
+            return Err(Error::Message(format!(
+                "webhook source {} already exists",
+                source.name
+            )));
+        }
+        Ok(existing) if source.secret.is_empty() => source.secret = existing.secret,
+        Err(_) if !create => {
+            return Err(Error::Message(format!(
+                "webhook source {} not found",
+                source.name
+            )));
+        }
+        _ => {}
+    }
+    if source.secret.is_empty() {
+        return Err(Error::Message("secret is required".to_string()));
+    }
+    if source.kind == WebhookKind::Custom
+        && source
+            .signature_header
+            .as_ref()
+            .is_some_and(|v| v.trim().is_empty())
+    {
+        source.signature_header = None;
+    }
+    source.mapping = source
+        .mapping
+        .into_iter()
+        .map(|(field, path)| (field.trim().to_string(), path.trim().to_string()))
+        .collect();
+    parse_mapping(&source.mapping).map_err(|e| Error::Message(format!("invalid mapping: {e}")))?;
+    if let Some(transform) = source.transform.as_deref().filter(|v| !v.trim().is_empty()) {
+        compile_vrl_function(&terminate(transform), org_id)
+            .map_err(|e| Error::Message(format!("invalid transform: {e}")))?;
+    } else {
+        source.transform = None;
+    }
+    db::webhook::set(org_id, &source).await
+}
+
+/// Checks the request was signed with the source's secret. `token` is the
+/// `token` query parameter.
+pub fn verify(
+    source: &WebhookSource,
+    headers: &HeaderMap,
+    token: Option<&str>,
+    body: &[u8],
+    now_secs: i64,
+) -> std::result::Result<(), String> {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let secret = source.secret.as_bytes();
+    let valid = match source.kind {
+        WebhookKind::Github => {
+            let signature = header("X-Hub-Signature-256")
+                .ok_or("missing X-Hub-Signature-256 header")?
+                .trim_start_matches("sha256=");
+            verify_hmac(secret, &[body], signature)
+        }
+        WebhookKind::Stripe => {
+            let signature = header("Stripe-Signature").ok_or("missing Stripe-Signature header")?;
+            let mut timestamp = None;
+            let mut signatures = vec![];
+            for (k, v) in signature
+                .split(',')
+                .filter_map(|v| v.trim().split_once('='))
+            {
+                match k {
+                    "t" => timestamp = v.parse::<i64>().ok(),
+                    "v1" => signatures.push(v),
+                    _ => {}
+                }
+            }
+            let timestamp = timestamp.ok_or("missing timestamp in Stripe-Signature header")?;
+            if (now_secs - timestamp).abs() > STRIPE_TOLERANCE_SECS {
+                return Err("Stripe-Signature timestamp is outside the tolerance".to_string());
+            }
+            let timestamp = timestamp.to_string();
+            signatures
+                .iter()
+                .any(|s| verify_hmac(secret, &[timestamp.as_bytes(), b".", body], s))
+        }
+        WebhookKind::Pagerduty => header("X-PagerDuty-Signature")
+            .ok_or("missing X-PagerDuty-Signature header")?
+            .split(',')
+            .filter_map(|v| v.trim().strip_prefix("v1="))
+            .any(|s| verify_hmac(secret, &[body], s)),
+        WebhookKind::Custom => match source.signature_header.as_deref() {
+            Some(name) => {
+                let signature = header(name).ok_or_else(|| format!("missing {name} header"))?;
+                verify_hmac(secret, &[body], signature.trim_start_matches("sha256="))
+            }
+            None => token.is_some_and(|t| constant_time_eq(t.as_bytes(), secret)),
+        },
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err("invalid signature".to_string())
+    }
+}
+
+fn verify_hmac(secret: &[u8], parts: &[&[u8]], signature: &str) -> bool {
+    let Ok(signature) = hex::decode(signature.trim()) else {
+        return false;
+    };
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.verify_slice(&signature).is_ok()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Turns a request body into events. Arrays are split into one event per
+/// item, GitHub form deliveries carry the JSON in the `payload` field and
+/// anything that isn't JSON is kept as `message`.
+fn parse_events(body: &[u8], form_encoded: bool) -> Vec<json::Map<String, json::Value>> {
+    let payload = if form_encoded {
+        url::form_urlencoded::parse(body)
+            .find(|(k, _)| k == "payload")
+            .map(|(_, v)| v.into_owned().into_bytes())
+    } else {
+        None
+    };
+    let body = payload.as_deref().unwrap_or(body);
+    let value = json::from_slice::<json::Value>(body)
+        .unwrap_or_else(|_| json::json!({ "message": String::from_utf8_lossy(body) }));
+    let values = match value {
+        json::Value::Array(v) => v,
+        v => vec![v],
+    };
+    values
+        .into_iter()
+        .map(|v| match v {
+            json::Value::Object(v) => v,
+            v => {
+                let mut map = json::Map::new();
+                map.insert("message".to_string(), v);
+                map
+            }
+        })
+        .collect()
+}
+
+/// Delivery details the senders put in headers rather than the body
+fn header_fields(kind: WebhookKind, headers: &HeaderMap) -> Vec<(&'static str, String)> {
+    let names: &[(&'static str, &str)] = match kind {
+        WebhookKind::Github => &[
+            ("github_event", "X-GitHub-Event"),
+            ("github_delivery", "X-GitHub-Delivery"),
+            ("github_hook_id", "X-GitHub-Hook-ID"),
+        ],
+        WebhookKind::Pagerduty => &[("pagerduty_webhook_id", "X-Webhook-Id")],
+        WebhookKind::Stripe | WebhookKind::Custom => &[],
+    };
+    names
+        .iter()
+        .filter_map(|(field, header)| {
+            headers
+                .get(*header)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| (*field, v.to_string()))
+        })
+        .collect()
+}
+
+fn parse_mapping(
+    mapping: &BTreeMap<String, String>,
+) -> std::result::Result<Vec<(String, JsonPath)>, String> {
+    mapping
+        .iter()
+        .map(|(field, path)| {
+            if field.is_empty() {
+                return Err("mapped field names can't be empty".to_string());
+            }
+            Ok((field.clone(), path.parse::<JsonPath>()?))
+        })
+        .collect()
+}
+
+/// Picks the mapped fields out of the event, a path matching nothing leaves
+/// its field out. The mapped fields override the ones of the payload when it
+/// is kept.
+fn apply_mapping(
+    event: json::Map<String, json::Value>,
+    mapping: &[(String, JsonPath)],
+    keep_payload: bool,
+) -> json::Map<String, json::Value> {
+    let event = json::Value::Object(event);
+    let mapped = mapping
+        .iter()
+        .filter_map(|(field, path)| path.query(&event).map(|v| (field.clone(), v)))
+        .collect::<Vec<_>>();
+    let mut event = match event {
+        json::Value::Object(v) if keep_payload => v,
+        _ => json::Map::new(),
+    };
+    event.extend(mapped);
+    event
+}
+
+/// VRL programs must end with the event itself
+fn terminate(transform: &str) -> String {
+    if transform.trim_end().ends_with('.') {
+        transform.to_string()
+    } else {
+        format!("{transform} \n .")
+    }
+}
+
+/// The compiled transform of the source, compiled again only when the program
+/// changed since it was cached
+fn compiled_transform(org_id: &str, name: &str, transform: &str) -> Result<Arc<VRLResultResolver>> {
+    let key = format!("{org_id}/{name}");
+    if let Some(cached) = WEBHOOK_TRANSFORMS.get(&key)
+        && cached.0 == transform
+    {
+        return Ok(cached.1.clone());
+    }
+    let runtime_config = compile_vrl_function(&terminate(transform), org_id)
+        .map_err(|e| Error::IngestionError(format!("invalid transform: {e}")))?;
+    if let Some(registry) = runtime_config
+        .config
+        .get_custom::<vector_enrichment::TableRegistry>()
+    {
+        registry.finish_load();
+    }
+    let resolver = Arc::new(VRLResultResolver {
+        program: runtime_config.program,
+        fields: runtime_config.fields,
+    });
+    WEBHOOK_TRANSFORMS.insert(key, (transform.to_string(), resolver.clone()));
+    Ok(resolver)
+}
+
+pub async fn ingest(
+    thread_id: usize,
+    org_id: &str,
+    source: &WebhookSource,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<IngestionResponse> {
+    let form_encoded = headers
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/x-www-form-urlencoded"));
+    let extra = header_fields(source.kind, headers);
+    let mapping = parse_mapping(&source.mapping)
+        .map_err(|e| Error::IngestionError(format!("invalid mapping: {e}")))?;
+    let mut events: Vec<json::Value> = parse_events(body, form_encoded)
+        .into_iter()
+        .map(|event| {
+            let mut event = if mapping.is_empty() {
+                event
+            } else {
+                apply_mapping(event, &mapping, source.keep_payload)
+            };
+            event.insert("webhook_source".to_string(), source.name.as_str().into());
+            event.insert("webhook_kind".to_string(), source.kind.as_str().into());
+            for (field, value) in extra.iter() {
+                event.insert(field.to_string(), value.as_str().into());
+            }
+            json::Value::Object(event)
+        })
+        .collect();
+
+    if let Some(transform) = source.transform.as_deref() {
+        let resolver = compiled_transform(org_id, &source.name, transform)?;
+        let mut runtime = init_functions_runtime();
+        let stream_name = [source.stream_name.clone()];
+        events = events
+            .into_iter()
+            .filter_map(|event| {
+                let (event, err) =
+                    apply_vrl_fn(&mut runtime, &resolver, event, org_id, &stream_name);
+                if let Some(err) = err {
+                    log::warn!("[WEBHOOK] {org_id}/{} transform failed: {err}", source.name);
+                }
+                // the transform drops events by returning null
+                (!event.is_null()).then_some(event)
+            })
+            .collect();
+    }
+    if events.is_empty() {
+        return Ok(IngestionResponse::new(200, vec![]));
+    }
+
+    crate::service::logs::ingest::ingest(
+        thread_id,
+        org_id,
+        &source.stream_name,
+        IngestionRequest::JsonValues(IngestionValueType::Webhook, events),
+        IngestUser::SystemJob(SystemJobType::Webhook),
+        None,
+        false,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    fn sign(secret: &str, parts: &[&[u8]]) -> String {
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        for part in parts {
+            mac.update(part);
+        }
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    fn source(kind: WebhookKind) -> WebhookSource {
+        WebhookSource {
+            name: "src".to_string(),
+            kind,
+            stream_name: "audit".to_string(),
+            secret: "s3cret".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn headers(name: &'static str, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_verify_github() {
+        let body = br#"{"action":"opened"}"#;
+        let sig = format!("sha256={}", sign("s3cret", &[body]));
+        let src = source(WebhookKind::Github);
+        assert!(verify(&src, &headers("x-hub-signature-256", &sig), None, body, 0).is_ok());
+        assert!(verify(&src, &headers("x-hub-signature-256", &sig), None, b"{}", 0).is_err());
+        assert!(verify(&src, &HeaderMap::new(), None, body, 0).is_err());
+    }
+
+    #[test]
+    fn test_verify_stripe() {
+        let body = br#"{"type":"charge.succeeded"}"#;
+        let sig = sign("s3cret", &[b"1700000000", b".", body]);
+        let value = format!("t=1700000000,v1=deadbeef,v1={sig}");
+        let src = source(WebhookKind::Stripe);
+        let h = headers("stripe-signature", &value);
+        assert!(verify(&src, &h, None, body, 1_700_000_100).is_ok());
+        // replayed outside the tolerance
+        assert!(verify(&src, &h, None, body, 1_700_001_000).is_err());
+        let h = headers("stripe-signature", &format!("v1={sig}"));
+        assert!(verify(&src, &h, None, body, 1_700_000_000).is_err());
+    }
+
+    #[test]
+    fn test_verify_pagerduty() {
+        let body = br#"{"event":{"event_type":"incident.triggered"}}"#;
+        let sig = sign("s3cret", &[body]);
+        let src = source(WebhookKind::Pagerduty);
+        let h = headers("x-pagerduty-signature", &format!("v1=00ff, v1={sig}"));
+        assert!(verify(&src, &h, None, body, 0).is_ok());
+        let h = headers("x-pagerduty-signature", "v1=00ff");
+        assert!(verify(&src, &h, None, body, 0).is_err());
+    }
+
+    #[test]
+    fn test_verify_custom() {
+        let body = b"hello";
+        let mut src = source(WebhookKind::Custom);
+        assert!(verify(&src, &HeaderMap::new(), Some("s3cret"), body, 0).is_ok());
+        assert!(verify(&src, &HeaderMap::new(), Some("wrong"), body, 0).is_err());
+        assert!(verify(&src, &HeaderMap::new(), None, body, 0).is_err());
+
+        src.signature_header = Some("X-Signature".to_string());
+        let h = headers("x-signature", &sign("s3cret", &[body]));
+        assert!(verify(&src, &h, None, body, 0).is_ok());
+        // the token is not accepted once a signature header is configured
+        assert!(verify(&src, &HeaderMap::new(), Some("s3cret"), body, 0).is_err());
+    }
+
+    #[test]
+    fn test_parse_events() {
+        let events = parse_events(br#"[{"a":1},{"b":2},3]"#, false);
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[1]["b"], 2);
+        assert_eq!(events[2]["message"], 3);
+
+        let events = parse_events(b"payload=%7B%22zen%22%3A%22ok%22%7D", true);
+        assert_eq!(events[0]["zen"], "ok");
+
+        let events = parse_events(b"plain text", false);
+        assert_eq!(events[0]["message"], "plain text");
+    }
+
+    #[test]
+    fn test_header_fields() {
+        let h = headers("x-github-event", "push");
+        assert_eq!(
+            header_fields(WebhookKind::Github, &h),
+            vec![("github_event", "push".to_string())]
+        );
+        assert!(header_fields(WebhookKind::Stripe, &h).is_empty());
+    }
+
+    #[test]
+    fn test_apply_mapping() {
+        let mapping = BTreeMap::from([
+            (
+                "repository".to_string(),
+                "$.repository.full_name".to_string(),
+            ),
+            ("commits".to_string(), "$.commits[*].id".to_string()),
+            ("issue".to_string(), "$.issue.number".to_string()),
+        ]);
+        let mapping = parse_mapping(&mapping).unwrap();
+        let event = parse_events(
+            br#"{"repository":{"full_name":"o2/o2"},"commits":[{"id":"a"},{"id":"b"}]}"#,
+            false,
+        )
+        .remove(0);
+
+        let mapped = apply_mapping(event.clone(), &mapping, false);
+        assert_eq!(mapped.len(), 2);
+        assert_eq!(mapped["repository"], "o2/o2");
+        assert_eq!(mapped["commits"], json::json!(["a", "b"]));
+
+        // the mapped fields override the payload ones
+        let mapped = apply_mapping(event, &mapping, true);
+        assert_eq!(mapped["repository"], "o2/o2");
+        assert_eq!(mapped["commits"], json::json!(["a", "b"]));
+
+        let invalid = BTreeMap::from([("repo".to_string(), "repository".to_string())]);
+        assert!(parse_mapping(&invalid).is_err());
+        let invalid = BTreeMap::from([(String::new(), "$.a".to_string())]);
+        assert!(parse_mapping(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_mapping_templates() {
+        for kind in [
+            WebhookKind::Github,
+            WebhookKind::Stripe,
+            WebhookKind::Pagerduty,
+        ] {
+            let template = crate::common::meta::webhook::WebhookTemplate::from(kind);
+            assert!(!template.mapping.is_empty());
+            assert!(parse_mapping(&template.mapping).is_ok());
+        }
+        assert!(WebhookKind::Custom.mapping_template().is_empty());
+    }
+
+    #[test]
+    fn test_terminate() {
+        assert_eq!(terminate(".a = 1\n."), ".a = 1\n.");
+        assert_eq!(terminate(".a = 1"), ".a = 1 \n .");
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"abc", b"abc"));
+        assert!(!constant_time_eq(b"abc", b"abd"));
+        assert!(!constant_time_eq(b"abc", b"ab"));
+    }
+}
@@ -0,0 +1,393 @@
+// Copyright 2026 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! OpenTelemetry Collector style zPages for the ingestion subsystem
+//!
+//! The collector exposes live diagnostics as `servicez` (build and process
+//! info), `pipelinez` (receivers and the queues behind them) and the
+//! `health_check` extension. The same views are built here from the node's
+//! own prometheus metrics, so nothing extra is tracked besides the per
+//! receiver record outcomes.
+
+use std::{collections::BTreeMap, fmt::Write};
+
+use chrono::{DateTime, Utc};
+use config::{
+    cluster::LOCAL_NODE, get_config, metrics, utils::sysinfo::os::get_process_start_time,
+};
+use once_cell::sync::Lazy;
+use prometheus::{core::Collector, proto::MetricType};
+use serde::Serialize;
+
+const OUTCOME_ACCEPTED: &str = "accepted";
+const OUTCOME_REFUSED: &str = "refused";
+const OUTCOME_DROPPED: &str = "dropped";
+
+static STARTED_AT: Lazy<DateTime<Utc>> = Lazy::new(|| {
+    DateTime::from_timestamp(get_process_start_time() as i64, 0).unwrap_or_else(Utc::now)
+});
+
+/// Records the outcome of a batch for a receiver:
+/// - accepted: records handed to the writer
+/// - refused: records rejected by validation, timestamps, pipelines or schema
+/// - dropped: accepted records that could not be written
+pub fn record_receiver(receiver: &str, accepted: usize, refused: usize, dropped: usize) {
+    for (outcome, count) in [
+        (OUTCOME_ACCEPTED, accepted),
+        (OUTCOME_REFUSED, refused),
+        (OUTCOME_DROPPED, dropped),
+    ] {
+        if count > 0 {
+            metrics::INGEST_RECEIVER_RECORDS
+                .with_label_values(&[receiver, outcome])
+                .inc_by(count as u64);
+        }
+    }
+}
+
+/// Receiver name of an ingestion endpoint, `/api/org/ingest/logs/_json`
+/// becomes `json`
+pub fn receiver_name(endpoint: &str) -> &str {
+    let name = endpoint.rsplit('/').next().unwrap_or(endpoint);
+    name.strip_prefix('_').unwrap_or(name)
+}
+
+/// Response of the collector's `health_check` extension
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Health {
+    pub status: String,
+    pub up_since: String,
+    pub uptime: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Servicez {
+    pub version: String,
+    pub commit_hash: String,
+    pub build_date: String,
+    pub node_name: String,
+    pub node_uuid: String,
+    pub roles: Vec<String>,
+    pub start_time: String,
+    pub uptime: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Pipelinez {
+    pub receivers: Vec<ReceiverStats>,
+    pub queues: Vec<QueueStats>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct ReceiverStats {
+    pub name: String,
+    pub accepted: u64,
+    pub refused: u64,
+    pub dropped: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueueStats {
+    pub name: String,
+    pub unit: &'static str,
+    pub size: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capacity: Option<i64>,
+}
+
+pub fn health() -> Health {
+    Health {
+        status: "Server available".to_string(),
+        up_since: STARTED_AT.to_rfc3339(),
+        uptime: format_uptime(uptime_secs()),
+    }
+}
+
+pub fn servicez() -> Servicez {
+    Servicez {
+        version: config::VERSION.to_string(),
+        commit_hash: config::COMMIT_HASH.to_string(),
+        build_date: config::BUILD_DATE.to_string(),
+        node_name: LOCAL_NODE.name.clone(),
+        node_uuid: LOCAL_NODE.uuid.clone(),
+        roles: LOCAL_NODE.role.iter().map(|r| r.to_string()).collect(),
+        start_time: STARTED_AT.to_rfc3339(),
+        uptime: format_uptime(uptime_secs()),
+    }
+}
+
+pub fn pipelinez() -> Pipelinez {
+    let cfg = get_config();
+    let mut queues = vec![
+        QueueStats {
+            name: "memtable".to_string(),
+            unit: "bytes",
+            size: sum_values(&*metrics::INGEST_MEMTABLE_BYTES) as i64,
+            capacity: Some(cfg.limit.mem_table_max_size as i64),
+        },
+        QueueStats {
+            name: "memtable_files".to_string(),
+            unit: "files",
+            size: sum_values(&*metrics::INGEST_MEMTABLE_FILES) as i64,
+            capacity: None,
+        },
+        QueueStats {
+            name: "wal".to_string(),
+            unit: "bytes",
+            size: sum_values(&*metrics::INGEST_WAL_USED_BYTES) as i64,
+            capacity: None,
+        },
+        QueueStats {
+            name: "pending_upload".to_string(),
+            unit: "files",
+            size: sum_values(&*metrics::INGEST_PARQUET_FILES) as i64,
+            capacity: None,
+        },
+        QueueStats {
+            name: "service_streams".to_string(),
+            unit: "items",
+            size: metrics::SERVICE_STREAMS_QUEUE_TOTAL.get(),
+            capacity: None,
+        },
+    ];
+    for (labels, size) in label_values(&*metrics::SELF_REPORTING_QUEUE_DEPTH) {
+        let queue_type = labels.get("queue_type").cloned().unwrap_or_default();
+        queues.push(QueueStats {
+            name: format!("self_reporting_{queue_type}"),
+            unit: "items",
+            size: size as i64,
+            capacity: None,
+        });
+    }
+
+    Pipelinez {
+        receivers: receiver_stats(),
+        queues,
+    }
+}
+
+fn receiver_stats() -> Vec<ReceiverStats> {
+    let mut receivers: BTreeMap<String, ReceiverStats> = BTreeMap::new();
+    for (labels, value) in label_values(&*metrics::INGEST_RECEIVER_RECORDS) {
+        let (Some(name), Some(outcome)) = (labels.get("receiver"), labels.get("outcome")) else {
+            continue;
+        };
+        let entry = receivers
+            .entry(name.to_string())
+            .or_insert_with(|| ReceiverStats {
+                name: name.to_string(),
+                ..Default::default()
+            });
+        match outcome.as_str() {
+            OUTCOME_ACCEPTED => entry.accepted += value as u64,
+            OUTCOME_REFUSED => entry.refused += value as u64,
+            OUTCOME_DROPPED => entry.dropped += value as u64,
+            _ => {}
+        }
+    }
+    receivers.into_values().collect()
+}
+
+/// Value of every series of a counter or gauge, with its labels
+fn label_values(c: &dyn Collector) -> Vec<(BTreeMap<String, String>, f64)> {
+    let mut out = Vec::new();
+    for mf in c.collect() {
+        let is_counter = mf.get_field_type() == MetricType::COUNTER;
+        for m in mf.get_metric() {
+            let labels = m
+                .get_label()
+                .iter()
+                .map(|lp| (lp.name().to_string(), lp.value().to_string()))
+                .collect();
+            let value = if is_counter {
+                m.get_counter().value()
+            } else {
+                m.get_gauge().value()
+            };
+            out.push((labels, value));
+        }
+    }
+    out
+}
+
+fn sum_values(c: &dyn Collector) -> f64 {
+    label_values(c).into_iter().map(|(_, v)| v).sum()
+}
+
+fn uptime_secs() -> i64 {
+    (Utc::now() - *STARTED_AT).num_seconds().max(0)
+}
+
+/// Formats seconds the way Go prints a duration, e.g. `26h3m4s`
+fn format_uptime(secs: i64) -> String {
+    let (h, m, s) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if h > 0 {
+        format!("{h}h{m}m{s}s")
+    } else if m > 0 {
+        format!("{m}m{s}s")
+    } else {
+        format!("{s}s")
+    }
+}
+
+impl Servicez {
+    pub fn to_html(&self) -> String {
+        let roles = self.roles.join(", ");
+        let rows = [
+            ("Version", self.version.as_str()),
+            ("Commit", self.commit_hash.as_str()),
+            ("Build date", self.build_date.as_str()),
+            ("Node", self.node_name.as_str()),
+            ("Node UUID", self.node_uuid.as_str()),
+            ("Roles", roles.as_str()),
+            ("Start time", self.start_time.as_str()),
+            ("Uptime", self.uptime.as_str()),
+        ];
+        let mut body = String::from("<table>");
+        for (key, value) in rows {
+            let _ = write!(
+                body,
+                "<tr><th>{key}</th><td>{}</td></tr>",
+                html_escape(value)
+            );
+        }
+        body.push_str("</table>");
+        html_page("servicez", &body)
+    }
+}
+
+impl Pipelinez {
+    pub fn to_html(&self) -> String {
+        let mut body = String::from(
+            "<h2>Receivers</h2><table><tr><th>Receiver</th><th>Accepted</th><th>Refused</th><th>Dropped</th></tr>",
+        );
+        for r in self.receivers.iter() {
+            let _ = write!(
+                body,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                html_escape(&r.name),
+                r.accepted,
+                r.refused,
+                r.dropped
+            );
+        }
+        body.push_str(
+            "</table><h2>Queues</h2><table><tr><th>Queue</th><th>Size</th><th>Capacity</th><th>Unit</th></tr>",
+        );
+        for q in self.queues.iter() {
+            let capacity = q.capacity.map(|v| v.to_string()).unwrap_or_default();
+            let _ = write!(
+                body,
+                "<tr><td>{}</td><td>{}</td><td>{capacity}</td><td>{}</td></tr>",
+                html_escape(&q.name),
+                q.size,
+                q.unit
+            );
+        }
+        body.push_str("</table>");
+        html_page("pipelinez", &body)
+    }
+}
+
+fn html_page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{title}</title>\
+         <style>body{{font-family:sans-serif}}table{{border-collapse:collapse}}\
+         th,td{{border:1px solid #ccc;padding:4px 8px;text-align:left}}</style></head>\
+         <body><h1>{title}</h1>{body}</body></html>"
+    )
+}
+
+fn html_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_receiver_name() {
+        assert_eq!(receiver_name("/api/org/ingest/logs/_json"), "json");
+        assert_eq!(
+            receiver_name("/api/org/ingest/logs/_k8s_events"),
+            "k8s_events"
+        );
+        assert_eq!(receiver_name("otlp"), "otlp");
+    }
+
+    #[test]
+    fn test_format_uptime() {
+        assert_eq!(format_uptime(0), "0s");
+        assert_eq!(format_uptime(59), "59s");
+        assert_eq!(format_uptime(61), "1m1s");
+        assert_eq!(format_uptime(93784), "26h3m4s");
+    }
+
+    #[test]
+    fn test_record_receiver() {
+        record_receiver("zpages_test", 10, 2, 0);
+        record_receiver("zpages_test", 5, 0, 1);
+        let stats = receiver_stats()
+            .into_iter()
+            .find(|r| r.name == "zpages_test")
+            .unwrap();
+        assert_eq!(stats.accepted, 15);
+        assert_eq!(stats.refused, 2);
+        assert_eq!(stats.dropped, 1);
+    }
+
+    #[test]
+    fn test_pipelinez_html() {
+        let page = Pipelinez {
+            receivers: vec![ReceiverStats {
+                name: "<json>".to_string(),
+                accepted: 3,
+                refused: 1,
+                dropped: 0,
+            }],
+            queues: vec![QueueStats {
+                name: "memtable".to_string(),
+                unit: "bytes",
+                size: 1024,
+                capacity: Some(4096),
+            }],
+        }
+        .to_html();
+        assert!(page.contains("<td>&lt;json&gt;</td><td>3</td><td>1</td><td>0</td>"));
+        assert!(page.contains("<td>memtable</td><td>1024</td><td>4096</td><td>bytes</td>"));
+    }
+
+    #[test]
+    fn test_health() {
+        let health = health();
+        assert_eq!(health.status, "Server available");
+        assert!(DateTime::parse_from_rfc3339(&health.up_since).is_ok());
+        let value = config::utils::json::to_value(&health).unwrap();
+        assert!(value.get("upSince").is_some());
+    }
+}
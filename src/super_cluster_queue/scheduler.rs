@@ -205,6 +205,111 @@ async fn update(msg: Message) -> Result<()> {
                 );
             }
         }
+        TriggerModule::ScheduledExport => {
+            if db::scheduled_exports::get(&trigger.org, &trigger.module_key)
+                .await
+                .is_ok()
+            {
+                // We need to add this trigger to the db in this region
+                scheduler::push(trigger.clone()).await.map_err(|e| {
+                    let error_msg = format!(
+                        "[SUPER_CLUSTER:sync] Failed to push scheduler: {}/{:?}/{}, error: {}",
+                        trigger.org, trigger.module, trigger.module_key, e
+                    );
+                    log::error!("{error_msg}");
+                    anyhow::anyhow!(error_msg)
+                })?;
+            } else {
+                log::warn!(
+                    "[SUPER_CLUSTER:sync] Scheduled export not found for module_key: {}. No need to sync this trigger",
+                    trigger.module_key
+                );
+            }
+        }
+        TriggerModule::AlertBacktest => {
+            if db::alerts::backtest::get(&trigger.org, &trigger.module_key)
+                .await
+                .is_ok()
+            {
+                // We need to add this trigger to the db in this region
+                scheduler::push(trigger.clone()).await.map_err(|e| {
+                    let error_msg = format!(
+                        "[SUPER_CLUSTER:sync] Failed to push scheduler: {}/{:?}/{}, error: {}",
+                        trigger.org, trigger.module, trigger.module_key, e
+                    );
+                    log::error!("{error_msg}");
+                    anyhow::anyhow!(error_msg)
+                })?;
+            } else {
+                log::warn!(
+                    "[SUPER_CLUSTER:sync] Alert backtest not found for module_key: {}. No need to sync this trigger",
+                    trigger.module_key
+                );
+            }
+        }
+        TriggerModule::RecordingRule => {
+            if db::recording_rules::get(&trigger.org, &trigger.module_key)
+                .await
+                .is_ok()
+            {
+                // We need to add this trigger to the db in this region
+                scheduler::push(trigger.clone()).await.map_err(|e| {
+                    let error_msg = format!(
+                        "[SUPER_CLUSTER:sync] Failed to push scheduler: {}/{:?}/{}, error: {}",
+                        trigger.org, trigger.module, trigger.module_key, e
+                    );
+                    log::error!("{error_msg}");
+                    anyhow::anyhow!(error_msg)
+                })?;
+            } else {
+                log::warn!(
+                    "[SUPER_CLUSTER:sync] Recording rule not found for module_key: {}. No need to sync this trigger",
+                    trigger.module_key
+                );
+            }
+        }
+        TriggerModule::Replay => {
+            if db::replay::get(&trigger.org, &trigger.module_key)
+                .await
+                .is_ok()
+            {
+                // We need to add this trigger to the db in this region
+                scheduler::push(trigger.clone()).await.map_err(|e| {
+                    let error_msg = format!(
+                        "[SUPER_CLUSTER:sync] Failed to push scheduler: {}/{:?}/{}, error: {}",
+                        trigger.org, trigger.module, trigger.module_key, e
+                    );
+                    log::error!("{error_msg}");
+                    anyhow::anyhow!(error_msg)
+                })?;
+            } else {
+                log::warn!(
+                    "[SUPER_CLUSTER:sync] Replay not found for module_key: {}. No need to sync this trigger",
+                    trigger.module_key
+                );
+            }
+        }
+        TriggerModule::DbPoller => {
+            if db::db_pollers::get(&trigger.org, &trigger.module_key)
+                .await
+                .is_ok()
+            {
+                // We need to add this trigger to the db in this region
+                scheduler::push(trigger.clone()).await.map_err(|e| {
+                    let error_msg = format!(
+                        "[SUPER_CLUSTER:sync] Failed to push scheduler: {}/{:?}/{}, error: {}",
+                        trigger.org, trigger.module, trigger.module_key, e
+                    );
+                    log::error!("{error_msg}");
+                    anyhow::anyhow!(error_msg)
+                })?;
+            } else {
+                log::warn!(
+                    "[SUPER_CLUSTER:sync] DB poller not found for module_key: {}. No need to sync this trigger",
+                    trigger.module_key
+                );
+            }
+        }
         TriggerModule::QueryRecommendations => {
             todo!("We will get here eventually")
         }
@@ -21,6 +21,7 @@ use std::{
 };
 
 use byteorder::{BigEndian, WriteBytesExt};
+use config::meta::stream::{IngestPriority, WalSyncPolicy};
 use crc32fast::Hasher;
 use snafu::ResultExt;
 
@@ -170,24 +171,35 @@ impl Writer {
         Ok(())
     }
 
+    /// Flushes the buffered writes to the OS, they survive a crash of the
+    /// process but not of the host
+    pub fn flush(&mut self) -> Result<()> {
+        self.f.flush().context(FileSyncSnafu {
+            path: self.path.clone(),
+        })
+    }
+
+    /// Flushes and fsyncs the writes to disk
     pub fn sync(&mut self) -> Result<()> {
         if self.synced {
             return Ok(());
         }
-        self.f.flush().context(FileSyncSnafu {
+        self.flush()?;
+        self.f.get_ref().sync_data().context(FileSyncSnafu {
             path: self.path.clone(),
         })?;
-        if !config::get_config().common.wal_fsync_disabled {
-            self.f.get_ref().sync_data().context(FileSyncSnafu {
-                path: self.path.clone(),
-            })?;
-        }
         self.synced = true;
         Ok(())
     }
 
+    /// Flushes the writes, they are fsynced unless the default WAL sync policy
+    /// is async
     pub fn close(&mut self) -> Result<()> {
-        self.sync()
+        if WalSyncPolicy::default_for(IngestPriority::Normal) == WalSyncPolicy::Async {
+            self.flush()
+        } else {
+            self.sync()
+        }
     }
 
     pub fn metadata(&self) -> io::Result<Metadata> {